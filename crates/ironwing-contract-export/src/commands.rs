@@ -86,7 +86,7 @@ const WASM_FACADE_COMMANDS: &[WasmFacadeSpec] = &[
     wasm_facade_command("motor_test", &["motorInstance", "throttlePct", "durationS"]),
     wasm_facade_command("param_cancel", &[]),
     wasm_facade_command("param_download_all", &[]),
-    wasm_facade_command("param_format_file", &["store"]),
+    wasm_facade_command("param_format_file", &["store", "format"]),
     wasm_facade_command("param_parse_file", &["contents"]),
     wasm_facade_command("param_write", &["name", "value"]),
     wasm_facade_command("param_write_batch", &["params"]),
@@ -104,8 +104,14 @@ const WASM_FACADE_COMMANDS: &[WasmFacadeSpec] = &[
 
 pub const COMMAND_NAMES: &[&str] = &[
     "ack_session_snapshot",
+    "alerts_get_rules",
+    "alerts_reset_rules",
+    "alerts_set_rules",
     "arm_vehicle",
     "available_transports",
+    "background_keepalive_start",
+    "background_keepalive_stop",
+    "battery_stats",
     "bt_get_bonded_devices",
     "bt_request_permissions",
     "bt_scan_ble",
@@ -115,9 +121,22 @@ pub const COMMAND_NAMES: &[&str] = &[
     "calibrate_compass_cancel",
     "calibrate_compass_start",
     "calibrate_gyro",
+    "camera_trigger",
+    "checklist_check",
+    "checklist_define",
+    "checklist_reset",
+    "checklist_state",
+    "components_list",
+    "connect_keep_waiting",
     "connect_link",
+    "connect_mock",
+    "connection_info",
+    "diagnostics",
+    "diagnostics_export",
     "disconnect_link",
     "disarm_vehicle",
+    "disconnect_link_ex",
+    "event_bridge_stats",
     "fence_clear",
     "fence_download",
     "fence_upload",
@@ -131,13 +150,42 @@ pub const COMMAND_NAMES: &[&str] = &[
     "firmware_session_cancel",
     "firmware_session_clear_completed",
     "firmware_session_status",
+    "firmware_stage",
+    "flight_mode_current",
+    "flight_summaries",
+    "flightmode_assign",
+    "flightmode_channel_config",
+    "frame_apply",
+    "frame_options",
+    "gcs_failsafe_test",
+    "gcs_position_set_manual",
+    "gcs_position_stream_start",
+    "gcs_position_stream_stop",
     "get_available_message_rates",
     "get_available_modes",
+    "get_current_link_state",
+    "get_current_telemetry",
+    "get_current_vehicle_state",
+    "gimbal_set_angles",
+    "gps_status",
+    "gripper_control",
+    "guided_queue_set",
+    "guided_queue_start",
+    "guided_queue_status",
+    "guided_queue_stop",
+    "journal_query",
+    "link_sources",
     "list_serial_port_inventory",
+    "list_usb_devices",
+    "local_api_start",
+    "local_api_stop",
+    "log_analyze",
     "log_chart_series_query",
     "log_close",
     "log_export",
     "log_export_csv",
+    "log_extract_params",
+    "log_follow",
     "log_format_adapters",
     "log_get_flight_path",
     "log_get_flight_summary",
@@ -150,32 +198,70 @@ pub const COMMAND_NAMES: &[&str] = &[
     "log_library_reindex",
     "log_library_relink",
     "log_library_remove",
+    "log_list_types",
     "log_open",
     "log_query",
+    "log_query_compare",
     "log_raw_messages_query",
+    "log_repair_timestamps",
+    "log_statustext",
+    "log_vtol_events",
+    "map_prefetch",
+    "map_prefetch_cancel",
     "mission_cancel",
     "mission_clear",
+    "mission_convert_frame",
     "mission_download",
+    "mission_insert_waypoint",
+    "mission_prepare_resume",
+    "mission_reverse",
+    "mission_scale_altitudes",
     "mission_set_current",
+    "mission_translate",
     "mission_upload",
     "mission_validate",
     "motor_test",
+    "named_values",
     "open_session_snapshot",
     "param_cancel",
+    "param_commit_to_storage",
     "param_download_all",
+    "param_export",
+    "param_external_changes",
     "param_format_file",
+    "param_get_all",
+    "param_get_bits",
     "param_parse_file",
+    "param_parse_file_ex",
+    "param_search",
+    "param_set_bits",
+    "param_set_metadata",
+    "param_tune_end",
+    "param_tune_revert",
+    "param_tune_session",
+    "param_tune_set",
     "param_write",
     "param_write_batch",
+    "pending_connect_take",
+    "plane_launch_info",
+    "plane_loiter_here",
+    "plane_set_cruise_throttle",
+    "plane_takeoff",
     "playback_pause",
     "playback_play",
     "playback_seek",
     "playback_set_speed",
     "playback_stop",
+    "precision_landing_status",
+    "preflight_position_check",
     "rally_clear",
     "rally_download",
     "rally_upload",
+    "rc_link_quality",
     "rc_override",
+    "rc_override_set",
+    "rc_override_start",
+    "rc_override_stop",
     "reboot_vehicle",
     "recording_settings_read",
     "recording_settings_write",
@@ -184,14 +270,56 @@ pub const COMMAND_NAMES: &[&str] = &[
     "recording_stop",
     "request_prearm_checks",
     "request_web_serial_port",
+    "rover_hold",
+    "rover_set_reverse",
+    "rtl_preview",
+    "script_delete",
+    "script_upload",
+    "scripts_list",
+    "send_command_long",
+    "send_raw_message",
+    "session_export",
+    "session_recover_info",
+    "set_auto_connect_usb",
+    "set_event_rates",
+    "set_expected_failsafe_check",
     "set_flight_mode",
+    "set_flight_mode_by_name",
+    "set_gcs_identity",
+    "set_link_profile",
+    "set_link_source_filter",
     "set_message_rate",
+    "set_safety_gates",
     "set_servo",
+    "set_telemetry_encoding",
     "set_telemetry_rate",
+    "set_unit_system",
+    "settings_get",
+    "settings_update",
     "start_guided_session",
+    "statustext_history",
     "stop_guided_session",
+    "takeoff_guided",
+    "time_sync_status",
+    "track_clear",
+    "track_get",
+    "tune_capture_start",
+    "tune_capture_stop",
+    "tune_report_export",
+    "tunnel_send",
     "update_guided_session",
+    "vehicle_guided_goto_ex",
+    "vehicle_guided_goto_terrain",
+    "vehicle_health",
+    "vehicle_list",
+    "vehicle_orbit",
+    "vehicle_orbit_stop",
+    "vehicle_select",
     "vehicle_takeoff",
+    "video_streams",
+    "vtol_status",
+    "vtol_transition",
+    "winch_control",
 ];
 
 pub const COMMAND_SPECS: &[CommandSpec] = &[
@@ -201,13 +329,54 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
         "AckSessionSnapshotResult",
         ALL_PLATFORMS,
     ),
-    command("arm_vehicle", "{ force: boolean }", "void", ALL_PLATFORMS),
+    command(
+        "alerts_get_rules",
+        "NoArgs",
+        "AlertRule[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "alerts_reset_rules",
+        "NoArgs",
+        "AlertRule[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "alerts_set_rules",
+        "{ rules: AlertRule[] }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "arm_vehicle",
+        "{ force: boolean; overrideGates?: boolean }",
+        "void",
+        ALL_PLATFORMS,
+    ),
     command(
         "available_transports",
         "NoArgs",
         "TransportDescriptor[]",
         ALL_PLATFORMS,
     ),
+    command(
+        "background_keepalive_start",
+        "NoArgs",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "background_keepalive_stop",
+        "NoArgs",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "battery_stats",
+        "NoArgs",
+        "BatteryStats",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "bt_get_bonded_devices",
         "NoArgs",
@@ -222,7 +391,12 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
         ALL_PLATFORMS,
     ),
     command("bt_stop_scan_ble", "NoArgs", "void", ALL_PLATFORMS),
-    command("calibrate_accel", "NoArgs", "void", ALL_PLATFORMS),
+    command(
+        "calibrate_accel",
+        "NoArgs",
+        "CalibrationResult",
+        ALL_PLATFORMS,
+    ),
     command(
         "calibrate_compass_accept",
         "{ compassMask: number }",
@@ -241,13 +415,74 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
         "void",
         ALL_PLATFORMS,
     ),
-    command("calibrate_gyro", "NoArgs", "void", ALL_PLATFORMS),
+    command(
+        "calibrate_gyro",
+        "NoArgs",
+        "CalibrationResult",
+        ALL_PLATFORMS,
+    ),
+    command(
+        "camera_trigger",
+        "{ targetComponent?: number }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "checklist_check",
+        "{ itemId: string; checked: boolean }",
+        "ChecklistState",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "checklist_define",
+        "{ items: ChecklistItem[]; enforce?: boolean }",
+        "ChecklistState",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "checklist_reset",
+        "NoArgs",
+        "ChecklistState",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "checklist_state",
+        "NoArgs",
+        "ChecklistState",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "components_list",
+        "NoArgs",
+        "ComponentListEntry[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command("connect_keep_waiting", "NoArgs", "void", NATIVE_REMOTE_MOCK),
     command(
         "connect_link",
         "{ request: ConnectLinkRequest }",
         "void",
         ALL_PLATFORMS,
     ),
+    command(
+        "connect_mock",
+        "{ vehiclePreset?: DemoVehiclePreset }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "connection_info",
+        "NoArgs",
+        "ConnectionInfo",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command("diagnostics", "NoArgs", "Diagnostics", NATIVE_REMOTE_MOCK),
+    command(
+        "diagnostics_export",
+        "{ path: string; redact?: boolean }",
+        "DiagnosticsExport",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "disconnect_link",
         "{ request?: DisconnectRequest }",
@@ -260,6 +495,18 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
         "void",
         ALL_PLATFORMS,
     ),
+    command(
+        "disconnect_link_ex",
+        "{ action: DisconnectAction; confirmArmed: boolean }",
+        "DisconnectActionResult",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "event_bridge_stats",
+        "NoArgs",
+        "EventBridgeStats[]",
+        NATIVE_REMOTE_MOCK,
+    ),
     command("fence_clear", "NoArgs", "void", ALL_PLATFORMS),
     command("fence_download", "NoArgs", "FencePlan", ALL_PLATFORMS),
     command("fence_upload", "{ plan: FencePlan }", "void", ALL_PLATFORMS),
@@ -318,6 +565,72 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
         "FirmwareSessionStatus",
         ALL_PLATFORMS,
     ),
+    command(
+        "firmware_stage",
+        "{ localPath: string }",
+        "FileUploadReport",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "flight_mode_current",
+        "NoArgs",
+        "FlightModeCurrent",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "flight_summaries",
+        "NoArgs",
+        "FlightSessionSummary[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "flightmode_assign",
+        "{ slot: number; customMode: number }",
+        "ParamWriteOutcome",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "flightmode_channel_config",
+        "NoArgs",
+        "FlightModeChannelConfig",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "frame_apply",
+        "{ optionId: string; reboot?: boolean }",
+        "FrameApplyResult",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "frame_options",
+        "NoArgs",
+        "FrameOption[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "gcs_failsafe_test",
+        "{ seconds: number }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "gcs_position_set_manual",
+        "{ lat: number; lon: number; alt: number }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "gcs_position_stream_start",
+        "{ rateHz: number }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "gcs_position_stream_stop",
+        "NoArgs",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "get_available_message_rates",
         "NoArgs",
@@ -330,19 +643,100 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
         "FlightModeEntry[]",
         ALL_PLATFORMS,
     ),
+    command(
+        "get_current_link_state",
+        "NoArgs",
+        "SessionConnection",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "get_current_telemetry",
+        "{ raw?: boolean }",
+        "TelemetryDomain",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "get_current_vehicle_state",
+        "NoArgs",
+        "VehicleState | null",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "gimbal_set_angles",
+        "{ pitchDeg: number; rollDeg: number; yawDeg: number; targetComponent?: number }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command("gps_status", "NoArgs", "GpsStatus", NATIVE_REMOTE_MOCK),
+    command(
+        "gripper_control",
+        "{ instance: number; action: GripperCommand }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "guided_queue_set",
+        "{ points: GuidedQueuePoint[] }",
+        "GuidedQueueStatus",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "guided_queue_start",
+        "NoArgs",
+        "GuidedQueueStatus",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "guided_queue_status",
+        "NoArgs",
+        "GuidedQueueStatus",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "guided_queue_stop",
+        "NoArgs",
+        "GuidedQueueStatus",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "journal_query",
+        "{ startMs: number; endMs: number }",
+        "JournalEntry[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "link_sources",
+        "NoArgs",
+        "LinkSourceEntry[]",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "list_serial_port_inventory",
         "NoArgs",
         "SerialPortInventoryResult",
         ALL_PLATFORMS,
     ),
+    command(
+        "list_usb_devices",
+        "NoArgs",
+        "UsbSerialDevice[]",
+        ALL_PLATFORMS,
+    ),
+    command(
+        "local_api_start",
+        "{ port: number; token: string; bindAll: boolean | null; stopOnDisconnect: boolean | null }",
+        "LocalApiStatus",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command("local_api_stop", "NoArgs", "void", NATIVE_REMOTE_MOCK),
+    command("log_analyze", "NoArgs", "LogFinding[]", NATIVE_REMOTE_MOCK),
     command(
         "log_chart_series_query",
         "{ request: ChartSeriesRequest }",
         "ChartSeriesPage",
         ALL_PLATFORMS,
     ),
-    command("log_close", "NoArgs", "void", ALL_PLATFORMS),
+    command("log_close", "{ logId?: string }", "void", ALL_PLATFORMS),
     command(
         "log_export",
         "{ request: LogExportRequest }",
@@ -355,6 +749,18 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
         "number",
         ALL_PLATFORMS,
     ),
+    command(
+        "log_extract_params",
+        "NoArgs",
+        "LogParam[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "log_follow",
+        "{ enabled: boolean; logId?: string }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "log_format_adapters",
         "NoArgs",
@@ -422,33 +828,106 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
         "LogLibraryCatalog",
         ALL_PLATFORMS,
     ),
+    command(
+        "log_list_types",
+        "{ logId?: string }",
+        "string[]",
+        NATIVE_REMOTE_MOCK,
+    ),
     command("log_open", "{ path: string }", "LogSummary", ALL_PLATFORMS),
     command(
         "log_query",
-        "{ msgType: string; startUsec: number | null; endUsec: number | null; maxPoints: number | null }",
+        "{ msgType: string; startUsec: number | null; endUsec: number | null; maxPoints: number | null; raw?: boolean; logId?: string; segment?: number; virtualFields?: VirtualField[] }",
         "LogDataPoint[]",
         ALL_PLATFORMS,
     ),
+    command(
+        "log_query_compare",
+        "{ logIds: string[]; msgType: string; field: string; normalizeTime: boolean; maxPoints?: number }",
+        "LogCompareSeries[]",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "log_raw_messages_query",
         "{ request: RawMessageQuery }",
         "RawMessagePage",
         ALL_PLATFORMS,
     ),
+    command(
+        "log_repair_timestamps",
+        "{ logId?: string }",
+        "LogSummary",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "log_statustext",
+        "NoArgs",
+        "LogStatusText[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "log_vtol_events",
+        "NoArgs",
+        "LogVtolEvent[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "map_prefetch",
+        "{ request: MapPrefetchRequest }",
+        "MapPrefetchProgress",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command("map_prefetch_cancel", "NoArgs", "void", NATIVE_REMOTE_MOCK),
     command("mission_cancel", "NoArgs", "void", ALL_PLATFORMS),
     command("mission_clear", "NoArgs", "void", ALL_PLATFORMS),
+    command(
+        "mission_convert_frame",
+        "{ plan: WireMissionPlan; targetFrame: AltitudeFrame; homeAltM: number | null }",
+        "MissionEditResult",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "mission_download",
         "NoArgs",
         "MissionDownload",
         ALL_PLATFORMS,
     ),
+    command(
+        "mission_insert_waypoint",
+        "{ plan: WireMissionPlan; afterSeq: number; item: WireMissionItem }",
+        "MissionEditResult",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "mission_prepare_resume",
+        "{ lastCompletedSeq: number }",
+        "WireMissionPlan",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "mission_reverse",
+        "{ plan: WireMissionPlan }",
+        "MissionEditResult",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "mission_scale_altitudes",
+        "{ plan: WireMissionPlan; change: AltitudeChange }",
+        "MissionEditResult",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "mission_set_current",
         "{ seq: number }",
         "void",
         ALL_PLATFORMS,
     ),
+    command(
+        "mission_translate",
+        "{ plan: WireMissionPlan; dlatDeg: number; dlonDeg: number }",
+        "MissionEditResult",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "mission_upload",
         "{ plan: WireMissionPlan }",
@@ -467,6 +946,7 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
         "void",
         ALL_PLATFORMS,
     ),
+    command("named_values", "NoArgs", "NamedValue[]", NATIVE_REMOTE_MOCK),
     command(
         "open_session_snapshot",
         "{ sourceKind: SourceKind }",
@@ -474,13 +954,38 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
         ALL_PLATFORMS,
     ),
     command("param_cancel", "NoArgs", "void", ALL_PLATFORMS),
+    command(
+        "param_commit_to_storage",
+        "NoArgs",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
     command("param_download_all", "NoArgs", "void", ALL_PLATFORMS),
+    command(
+        "param_export",
+        "{ options: ParamExportOptions }",
+        "string",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "param_external_changes",
+        "NoArgs",
+        "ParamExternalChange[]",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "param_format_file",
-        "{ store: ParamStore }",
+        "{ store: ParamStore; format?: ParamExportFormat }",
         "string",
         ALL_PLATFORMS,
     ),
+    command("param_get_all", "NoArgs", "ParamStore", NATIVE_REMOTE_MOCK),
+    command(
+        "param_get_bits",
+        "{ name: string }",
+        "ParamBitState[]",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "param_parse_file",
         "{ contents: string }",
@@ -488,17 +993,90 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
         ALL_PLATFORMS,
     ),
     command(
-        "param_write",
+        "param_parse_file_ex",
+        "{ contents: string }",
+        "ParsedParamFile",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "param_search",
+        "{ query: string; options: ParamSearchOptions }",
+        "Param[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "param_set_bits",
+        "{ name: string; bitsToSet: number[]; bitsToClear: number[]; persist?: boolean }",
+        "Param",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "param_set_metadata",
+        "{ metadata: Record<string, ParamMetadata> }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "param_tune_end",
+        "{ commit: boolean }",
+        "ParamTuneSummary",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command("param_tune_revert", "NoArgs", "void", NATIVE_REMOTE_MOCK),
+    command(
+        "param_tune_session",
+        "{ name: string }",
+        "ParamTuneSession",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "param_tune_set",
         "{ name: string; value: number }",
-        "ParamWriteResult",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "param_write",
+        "{ name: string; value: number; persist?: boolean }",
+        "ParamWriteOutcome",
         ALL_PLATFORMS,
     ),
     command(
         "param_write_batch",
-        "{ params: [string, number][] }",
-        "ParamWriteResult[]",
+        "{ params: [string, number][]; persist?: boolean }",
+        "ParamWriteOutcome[]",
         ALL_PLATFORMS,
     ),
+    command(
+        "pending_connect_take",
+        "NoArgs",
+        "PendingConnect | null",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "plane_launch_info",
+        "NoArgs",
+        "PlaneLaunch",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "plane_loiter_here",
+        "{ radiusM: number; turns?: number | null }",
+        "PlaneLoiterResult",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "plane_set_cruise_throttle",
+        "{ throttlePct: number }",
+        "PlaneCruiseThrottleResult",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "plane_takeoff",
+        "{ altitudeM: number }",
+        "PlaneTakeoffResult",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "playback_pause",
         "NoArgs",
@@ -507,7 +1085,7 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
     ),
     command(
         "playback_play",
-        "NoArgs",
+        "{ speed?: number; startUsec?: number }",
         "PlaybackStateSnapshot",
         ALL_PLATFORMS,
     ),
@@ -529,16 +1107,47 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
         "PlaybackStateSnapshot",
         ALL_PLATFORMS,
     ),
+    command(
+        "precision_landing_status",
+        "{ windowS?: number }",
+        "PrecisionLandingStatus",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "preflight_position_check",
+        "{ terrainElevationM?: number }",
+        "PositionIssue[]",
+        NATIVE_REMOTE_MOCK,
+    ),
     command("rally_clear", "NoArgs", "void", ALL_PLATFORMS),
     command("rally_download", "NoArgs", "RallyPlan", ALL_PLATFORMS),
     command("rally_upload", "{ plan: RallyPlan }", "void", ALL_PLATFORMS),
+    command(
+        "rc_link_quality",
+        "NoArgs",
+        "RcLinkQuality | null",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "rc_override",
         "{ channels: RcOverrideChannel[] }",
         "void",
         ALL_PLATFORMS,
     ),
-    command("reboot_vehicle", "NoArgs", "void", ALL_PLATFORMS),
+    command(
+        "rc_override_set",
+        "{ channels: (number | null)[] }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command("rc_override_start", "NoArgs", "void", NATIVE_REMOTE_MOCK),
+    command("rc_override_stop", "NoArgs", "void", NATIVE_REMOTE_MOCK),
+    command(
+        "reboot_vehicle",
+        "{ targetComponent?: number }",
+        "void",
+        ALL_PLATFORMS,
+    ),
     command(
         "recording_settings_read",
         "NoArgs",
@@ -571,54 +1180,281 @@ pub const COMMAND_SPECS: &[CommandSpec] = &[
         "SerialPortInfo | null",
         WEB_MOCK,
     ),
+    command("rover_hold", "NoArgs", "void", NATIVE_REMOTE_MOCK),
+    command(
+        "rover_set_reverse",
+        "{ enabled: boolean }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command("rtl_preview", "NoArgs", "RtlPreview", NATIVE_REMOTE_MOCK),
+    command(
+        "script_delete",
+        "{ name: string }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "script_upload",
+        "{ localPath: string }",
+        "FileUploadReport",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command("scripts_list", "NoArgs", "ScriptFile[]", NATIVE_REMOTE_MOCK),
+    command(
+        "send_command_long",
+        "{ commandId: number; params: number[]; targetComponent: number }",
+        "AckResult",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "send_raw_message",
+        "{ messageName: string; fieldsJson: unknown }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "session_export",
+        "{ path: string; include?: SessionExportOptions }",
+        "ExportReport",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "session_recover_info",
+        "NoArgs",
+        "SessionRecoverInfo | null",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "set_auto_connect_usb",
+        "{ enabled: boolean; baud: number }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "set_event_rates",
+        "{ rates: Record<string, number> }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "set_expected_failsafe_check",
+        "{ enabled: boolean }",
+        "string | null",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "set_flight_mode",
         "{ customMode: number }",
         "void",
         ALL_PLATFORMS,
     ),
+    command(
+        "set_flight_mode_by_name",
+        "{ name: string }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "set_gcs_identity",
+        "{ systemId: number; componentId: number }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "set_link_profile",
+        "{ profile: LinkProfile }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "set_link_source_filter",
+        "{ sources: LinkSource[] }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "set_message_rate",
-        "{ messageId: number; rateHz: number }",
+        "{ messageId: number; rateHz: number; targetComponent?: number }",
         "void",
         ALL_PLATFORMS,
     ),
+    command(
+        "set_safety_gates",
+        "{ config: SafetyGateConfig }",
+        "SafetyGateFailure[]",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "set_servo",
         "{ instance: number; pwmUs: number }",
         "void",
         ALL_PLATFORMS,
     ),
+    command(
+        "set_telemetry_encoding",
+        "{ encoding: TelemetryEncoding }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "set_telemetry_rate",
         "{ rateHz: number }",
         "void",
         ALL_PLATFORMS,
     ),
+    command(
+        "set_unit_system",
+        "{ distance: DistanceUnit; speed: SpeedUnit; temperature: TemperatureUnit }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command("settings_get", "NoArgs", "Settings", NATIVE_REMOTE_MOCK),
+    command(
+        "settings_update",
+        "{ patch: SettingsPatch }",
+        "Settings",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "start_guided_session",
         "{ request: StartGuidedSessionRequest }",
         "GuidedCommandResult",
         ALL_PLATFORMS,
     ),
+    command(
+        "statustext_history",
+        "NoArgs",
+        "StatusMessage[]",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "stop_guided_session",
         "NoArgs",
         "GuidedCommandResult",
         ALL_PLATFORMS,
     ),
+    command(
+        "takeoff_guided",
+        "{ request: TakeoffGuidedRequest }",
+        "TakeoffReport",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "time_sync_status",
+        "NoArgs",
+        "TimeSyncStatus",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command("track_clear", "NoArgs", "void", NATIVE_REMOTE_MOCK),
+    command(
+        "track_get",
+        "{ sinceUsec?: number; maxPoints?: number }",
+        "TrackPoint[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "tune_capture_start",
+        "{ axis: TuneAxis }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "tune_capture_stop",
+        "NoArgs",
+        "TuneReport",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "tune_report_export",
+        "{ path: string; report: TuneReport }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "tunnel_send",
+        "{ payloadType: number; data: number[] }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "update_guided_session",
         "{ request: UpdateGuidedSessionRequest }",
         "GuidedCommandResult",
         ALL_PLATFORMS,
     ),
+    command(
+        "vehicle_guided_goto_ex",
+        "{ request: GotoRequest }",
+        "GotoResult",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "vehicle_guided_goto_terrain",
+        "{ latitudeDeg: number; longitudeDeg: number; aglM: number; groundElevationM: number }",
+        "TerrainGotoResult",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "vehicle_health",
+        "NoArgs",
+        "HealthReport",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "vehicle_list",
+        "NoArgs",
+        "VehicleListEntry[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "vehicle_orbit",
+        "{ request: OrbitRequest }",
+        "OrbitResult",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "vehicle_orbit_stop",
+        "NoArgs",
+        "ParamWriteResult[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "vehicle_select",
+        "{ systemId: number }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
     command(
         "vehicle_takeoff",
-        "{ altitudeM: number }",
+        "{ altitudeM: number; overrideGates?: boolean }",
         "void",
         ALL_PLATFORMS,
     ),
+    command(
+        "video_streams",
+        "NoArgs",
+        "VideoStream[]",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "vtol_status",
+        "NoArgs",
+        "VtolStatus | null",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "vtol_transition",
+        "{ to: VtolTransitionTarget }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
+    command(
+        "winch_control",
+        "{ instance: number; action: WinchCommand }",
+        "void",
+        NATIVE_REMOTE_MOCK,
+    ),
 ];
 
 pub fn command_names_ts() -> Result<String, Box<dyn Error>> {
@@ -726,7 +1562,7 @@ fn command_map_ts() -> Result<String, Box<dyn Error>> {
 }
 
 fn imports_ts() -> &'static str {
-    r#"import type { SourceKind } from "./ironwing";
+    r#"import type { AckResult, AlertRule, AltitudeChange, AltitudeFrame, BatteryStats, CalibrationResult, ChecklistItem, ChecklistState, ComponentListEntry, ConnectionInfo, Diagnostics, DiagnosticsExport, DisconnectAction, DisconnectActionResult, DistanceUnit, EventBridgeStats, ExportReport, FileUploadReport, FlightModeChannelConfig, FlightModeCurrent, FlightSessionSummary, FrameApplyResult, FrameOption, GotoRequest, GotoResult, GpsStatus, GripperCommand, GuidedQueuePoint, GuidedQueueStatus, HealthReport, JournalEntry, LinkProfile, LinkSource, LinkSourceEntry, LocalApiStatus, LogCompareSeries, LogFinding, LogParam, LogStatusText, LogVtolEvent, MapPrefetchProgress, MapPrefetchRequest, MissionEditResult, NamedValue, OrbitRequest, OrbitResult, ParamBitState, ParamExportFormat, ParamExportOptions, ParamExternalChange, ParamMetadata, ParamSearchOptions, ParamTuneSession, ParamTuneSummary, ParamWriteOutcome, ParsedParamFile, PendingConnect, PlaneCruiseThrottleResult, PlaneLaunch, PlaneLoiterResult, PlaneTakeoffResult, PositionIssue, PrecisionLandingStatus, RcLinkQuality, RtlPreview, SafetyGateConfig, SafetyGateFailure, ScriptFile, SessionExportOptions, SessionRecoverInfo, Settings, SettingsPatch, SourceKind, SpeedUnit, TakeoffGuidedRequest, TakeoffReport, TelemetryEncoding, TemperatureUnit, TerrainGotoResult, TimeSyncStatus, TrackPoint, TuneAxis, TuneReport, UsbSerialDevice, VehicleListEntry, VideoStream, VirtualField, VtolStatus, VtolTransitionTarget, WinchCommand } from "./ironwing";
 import type { RcOverrideChannel } from "../../calibration";
 import type {
   BootloaderInstallationResult,
@@ -759,14 +1595,15 @@ import type {
   RawMessageQuery,
 } from "../../logs";
 import type { MissionDownload, MissionIssue } from "../../mission";
-import type { FencePlan, RallyPlan, WireMissionPlan } from "../mavkit-types";
+import type { FencePlan, RallyPlan, WireMissionItem, WireMissionPlan } from "../mavkit-types";
 import type { Param, ParamStore, ParamWriteResult } from "../../params";
 import type { FlightPathPoint, PlaybackSeekResult, PlaybackStateSnapshot, TelemetrySnapshot } from "../../playback";
 import type { RecordingSettings, RecordingSettingsResult, RecordingStartRequest, RecordingStatus } from "../../recording";
 import type { SerialPortInfo, SerialPortInventoryResult } from "../../serial-ports";
-import type { AckSessionSnapshotResult, OpenSessionSnapshot } from "../../session";
-import type { BluetoothDevice, FlightModeEntry, MessageRateInfo } from "../../telemetry";
-import type { BluetoothProfile, ConnectRequest, DisconnectRequest, TransportDescriptor } from "../../transport";
+import type { AckSessionSnapshotResult, OpenSessionSnapshot, SessionConnection } from "../../session";
+import type { StatusMessage } from "../../statustext";
+import type { BluetoothDevice, FlightModeEntry, MessageRateInfo, TelemetryDomain, VehicleState } from "../../telemetry";
+import type { BluetoothProfile, ConnectRequest, DemoVehiclePreset, DisconnectRequest, TransportDescriptor } from "../../transport";
 "#
 }
 
@@ -1,6 +1,7 @@
 use std::error::Error;
 
 use ironwing_core::event_names;
+use ironwing_core::telemetry_delta::{VALUE_PRESENT_KEY, telemetry_delta_fields};
 
 pub struct EventSpec {
     pub constant_name: &'static str,
@@ -31,6 +32,11 @@ pub const EVENT_SPECS: &[EventSpec] = &[
         event_names::TELEMETRY_STATE,
         "SessionEvent<TelemetryDomain>",
     ),
+    event(
+        "TELEMETRY_DELTA",
+        event_names::TELEMETRY_DELTA,
+        "SessionEvent<TelemetryDelta>",
+    ),
     event(
         "MISSION_STATE",
         event_names::MISSION_STATE,
@@ -39,7 +45,7 @@ pub const EVENT_SPECS: &[EventSpec] = &[
     event(
         "MISSION_PROGRESS",
         event_names::MISSION_PROGRESS,
-        "SessionEvent<TransferProgress>",
+        "SessionEvent<MissionTransferProgress>",
     ),
     event(
         "PARAM_STORE",
@@ -51,6 +57,11 @@ pub const EVENT_SPECS: &[EventSpec] = &[
         event_names::PARAM_PROGRESS,
         "SessionEvent<ParamProgress>",
     ),
+    event(
+        "PARAM_DELTA",
+        event_names::PARAM_DELTA,
+        "SessionEvent<ParamStoreDelta>",
+    ),
     event(
         "SENSOR_HEALTH_STATE",
         event_names::SENSOR_HEALTH_STATE,
@@ -61,6 +72,11 @@ pub const EVENT_SPECS: &[EventSpec] = &[
         event_names::CALIBRATION_STATE,
         "SessionEvent<CalibrationDomain>",
     ),
+    event(
+        "CALIBRATION_PROGRESS",
+        event_names::CALIBRATION_PROGRESS,
+        "CalibrationProgress",
+    ),
     event(
         "COMPASS_CAL_PROGRESS",
         event_names::COMPASS_CAL_PROGRESS,
@@ -76,22 +92,208 @@ pub const EVENT_SPECS: &[EventSpec] = &[
         event_names::STATUS_TEXT_STATE,
         "SessionEvent<StatusTextDomain>",
     ),
+    event(
+        "ALERT_TRIGGERED",
+        event_names::ALERT_TRIGGERED,
+        "SessionEvent<AlertTriggered>",
+    ),
+    event(
+        "ANNOUNCE_SAY",
+        event_names::ANNOUNCE_SAY,
+        "SessionEvent<Announcement>",
+    ),
+    event("JOURNAL_WARNING", event_names::JOURNAL_WARNING, "string"),
+    event(
+        "LINK_LOST_IN_FLIGHT",
+        event_names::LINK_LOST_IN_FLIGHT,
+        "LinkLostInFlight",
+    ),
+    event(
+        "LINK_CONNECTING",
+        event_names::LINK_CONNECTING,
+        "LinkConnecting",
+    ),
+    event(
+        "LINK_TRANSPORT_LOST",
+        event_names::LINK_TRANSPORT_LOST,
+        "TransportLost",
+    ),
+    event("TASK_STALLED", event_names::TASK_STALLED, "TaskStalled"),
+    event(
+        "LINK_FAILSAFE_WARNING",
+        event_names::LINK_FAILSAFE_WARNING,
+        "string",
+    ),
+    event(
+        "FLIGHT_SUMMARY",
+        event_names::FLIGHT_SUMMARY,
+        "SessionEvent<FlightSessionSummary>",
+    ),
+    event(
+        "TRACK_APPEND",
+        event_names::TRACK_APPEND,
+        "SessionEvent<TrackPoint[]>",
+    ),
+    event(
+        "NAMED_VALUE",
+        event_names::NAMED_VALUE,
+        "SessionEvent<NamedValue>",
+    ),
+    event(
+        "VIDEO_STREAMS",
+        event_names::VIDEO_STREAMS,
+        "SessionEvent<VideoStream[]>",
+    ),
+    event(
+        "GPS_FIX_CHANGED",
+        event_names::GPS_FIX_CHANGED,
+        "SessionEvent<GpsFixChanged>",
+    ),
+    event(
+        "BATTERY_STATS",
+        event_names::BATTERY_STATS,
+        "SessionEvent<BatteryStats>",
+    ),
+    event(
+        "CHECKLIST_UPDATE",
+        event_names::CHECKLIST_UPDATE,
+        "SessionEvent<ChecklistState>",
+    ),
+    event(
+        "GCS_COMMAND_RECEIVED",
+        event_names::GCS_COMMAND_RECEIVED,
+        "SessionEvent<GcsCommandReceived>",
+    ),
+    event(
+        "FTP_PROGRESS",
+        event_names::FTP_PROGRESS,
+        "SessionEvent<FileTransferProgress>",
+    ),
+    event(
+        "COMMAND_PROGRESS",
+        event_names::COMMAND_PROGRESS,
+        "CommandProgress",
+    ),
+    event(
+        "PARAM_EXTERNAL_CHANGE",
+        event_names::PARAM_EXTERNAL_CHANGE,
+        "SessionEvent<ParamExternalChange>",
+    ),
+    event(
+        "RANGEFINDER_READING",
+        event_names::RANGEFINDER_READING,
+        "SessionEvent<RangefinderReading>",
+    ),
+    event(
+        "LANDING_ASSIST_WARNING",
+        event_names::LANDING_ASSIST_WARNING,
+        "SessionEvent<LandingAssistWarning>",
+    ),
+    event(
+        "PRECISION_LANDING_TARGET",
+        event_names::PRECISION_LANDING_TARGET,
+        "SessionEvent<PrecisionLandingTarget>",
+    ),
+    event(
+        "OPTICAL_FLOW_QUALITY",
+        event_names::OPTICAL_FLOW_QUALITY,
+        "SessionEvent<OpticalFlowQuality>",
+    ),
+    event(
+        "WINCH_STATUS",
+        event_names::WINCH_STATUS,
+        "SessionEvent<WinchStatus>",
+    ),
+    event(
+        "VEHICLE_HEALTH",
+        event_names::VEHICLE_HEALTH,
+        "SessionEvent<HealthReport>",
+    ),
+    event(
+        "VEHICLE_VTOL",
+        event_names::VEHICLE_VTOL,
+        "SessionEvent<VtolStatus>",
+    ),
+    event(
+        "MAVLINK_UNKNOWN",
+        event_names::MAVLINK_UNKNOWN,
+        "SessionEvent<UnknownMessage>",
+    ),
+    event(
+        "FENCE_PROXIMITY",
+        event_names::FENCE_PROXIMITY,
+        "SessionEvent<FenceProximity>",
+    ),
+    event(
+        "LINK_DISCONNECT_ACTION",
+        event_names::LINK_DISCONNECT_ACTION,
+        "SessionEvent<DisconnectActionResult>",
+    ),
+    event(
+        "VEHICLE_FAILSAFE",
+        event_names::VEHICLE_FAILSAFE,
+        "SessionEvent<FailsafeEvent>",
+    ),
+    event(
+        "VEHICLE_SELECTED",
+        event_names::VEHICLE_SELECTED,
+        "VehicleSelected",
+    ),
+    event(
+        "HOME_MOVED",
+        event_names::HOME_MOVED,
+        "SessionEvent<HomeMoved>",
+    ),
+    event("SERIAL_ATTACHED", event_names::SERIAL_ATTACHED, "PortInfo"),
+    event("SERIAL_DETACHED", event_names::SERIAL_DETACHED, "PortInfo"),
     event(
         "SUPPORT_STATE",
         event_names::SUPPORT_STATE,
         "SessionEvent<SupportDomain>",
     ),
+    event(
+        "SYSTEM_RESUMED",
+        event_names::SYSTEM_RESUMED,
+        "SystemResumed",
+    ),
+    event(
+        "PENDING_CONNECT",
+        event_names::PENDING_CONNECT,
+        "PendingConnect",
+    ),
     event(
         "GUIDED_STATE",
         event_names::GUIDED_STATE,
         "SessionEvent<GuidedDomain>",
     ),
+    event("GUIDED_TARGET", event_names::GUIDED_TARGET, "GuidedTarget"),
+    event(
+        "GUIDED_QUEUE_PROGRESS",
+        event_names::GUIDED_QUEUE_PROGRESS,
+        "GuidedQueueStatus",
+    ),
     event(
         "PLAYBACK_STATE",
         event_names::PLAYBACK_STATE,
         "SessionEvent<PlaybackStateSnapshot>",
     ),
+    event(
+        "LOG_PLAYBACK_POSITION",
+        event_names::LOG_PLAYBACK_POSITION,
+        "SessionEvent<PlaybackPosition>",
+    ),
     event("LOG_PROGRESS", event_names::LOG_PROGRESS, "LogProgress"),
+    event("LOG_APPENDED", event_names::LOG_APPENDED, "LogAppended"),
+    event(
+        "SESSION_EXPORT_PROGRESS",
+        event_names::SESSION_EXPORT_PROGRESS,
+        "SessionExportProgress",
+    ),
+    event(
+        "MAP_PREFETCH_PROGRESS",
+        event_names::MAP_PREFETCH_PROGRESS,
+        "MapPrefetchProgress",
+    ),
     event(
         "FIRMWARE_PROGRESS",
         event_names::FIRMWARE_PROGRESS,
@@ -113,17 +315,39 @@ pub fn events_ts() -> Result<String, Box<dyn Error>> {
     body.push_str("} as const;\n\n");
     body.push_str("export type EventName = (typeof EVENT_NAMES)[keyof typeof EVENT_NAMES];\n");
     body.push('\n');
+    body.push_str(&telemetry_delta_fields_ts()?);
+    body.push('\n');
     body.push_str(&event_payload_map_ts());
 
     Ok(body)
 }
 
+fn telemetry_delta_fields_ts() -> Result<String, Box<dyn Error>> {
+    let mut body = String::from(
+        "/**\n * `telemetry://delta` wire keys and the JSON pointer each one fills in a\n * `TelemetryDomain`. The value-present key says whether the snapshot has a\n * `value` at all.\n */\n",
+    );
+    body.push_str("export const TELEMETRY_DELTA_VALUE_PRESENT_KEY = ");
+    body.push_str(&serde_json::to_string(VALUE_PRESENT_KEY)?);
+    body.push_str(";\n\n");
+    body.push_str("export const TELEMETRY_DELTA_FIELDS = {\n");
+    for (key, pointer) in telemetry_delta_fields() {
+        body.push_str("  ");
+        body.push_str(&serde_json::to_string(key)?);
+        body.push_str(": ");
+        body.push_str(&serde_json::to_string(pointer)?);
+        body.push_str(",\n");
+    }
+    body.push_str("} as const;\n");
+    Ok(body)
+}
+
 fn imports_ts() -> &'static str {
-    r#"import type { CalibrationDomain } from "../../calibration";
+    r#"import type { AlertTriggered, Announcement, BatteryStats, CalibrationProgress, ChecklistState, DisconnectActionResult, FailsafeEvent, FenceProximity, FileTransferProgress, FlightSessionSummary, GcsCommandReceived, GpsFixChanged, GuidedQueueStatus, GuidedTarget, HealthReport, HomeMoved, LandingAssistWarning, LinkConnecting, LinkLostInFlight, LogAppended, MapPrefetchProgress, MissionTransferProgress, NamedValue, OpticalFlowQuality, ParamExternalChange, ParamStoreDelta, PendingConnect, PlaybackPosition, PortInfo, PrecisionLandingTarget, RangefinderReading, SessionExportProgress, SystemResumed, TaskStalled, TelemetryDelta, TrackPoint, TransportLost, UnknownMessage, VehicleSelected, VideoStream, VtolStatus, WinchStatus } from "./ironwing";
+import type { CalibrationDomain } from "../../calibration";
 import type { FirmwareProgress } from "../../firmware";
 import type { GuidedDomain } from "../../guided";
 import type { LogProgress } from "../../logs";
-import type { MissionState, TransferProgress } from "../../mission";
+import type { MissionState } from "../../mission";
 import type { ParamProgress, ParamStore } from "../../params";
 import type { PlaybackStateSnapshot } from "../../playback";
 import type { MagCalProgress, MagCalReport, SensorHealthDomain } from "../../sensor-health";
@@ -189,6 +189,7 @@ fn generated_ironwing() -> Result<String, Box<dyn Error>> {
         .register_mut::<transport::UdpValidation>()
         .register_mut::<transport::TcpValidation>()
         .register_mut::<transport::SerialValidation>()
+        .register_mut::<transport::UsbSerialValidation>()
         .register_mut::<transport::AddressValidation>()
         .register_mut::<transport::UrlValidation>()
         .register_mut::<transport::WebSerialValidation>()
@@ -239,6 +240,8 @@ fn generated_ironwing() -> Result<String, Box<dyn Error>> {
         .register_mut::<logs::ChartPoint>()
         .register_mut::<logs::ChartSeries>()
         .register_mut::<logs::ChartSeriesPage>()
+        .register_mut::<logs::LogCompareSeries>()
+        .register_mut::<logs::VirtualField>()
         .register_mut::<logs::LogExportFormat>()
         .register_mut::<logs::LogExportRequest>()
         .register_mut::<logs::LogExportResult>()
@@ -249,6 +252,11 @@ fn generated_ironwing() -> Result<String, Box<dyn Error>> {
         .register_mut::<ipc::RecordingSettingsResult>()
         .register_mut::<logs::RecordingFailure>()
         .register_mut::<ipc::MissionDownload>()
+        .register_mut::<ipc::ParamExportFormat>()
+        .register_mut::<ipc::ParamMetadata>()
+        .register_mut::<ipc::ParamExportOptions>()
+        .register_mut::<ipc::ParamSearchOptions>()
+        .register_mut::<ipc::ParamStoreDelta>()
         .register_mut::<ipc::RcOverrideChannelValueWire>()
         .register_mut::<ipc::RcOverrideChannelWire>()
         .register_mut::<calibration::CalibrationLifecycle>()
@@ -256,6 +264,7 @@ fn generated_ironwing() -> Result<String, Box<dyn Error>> {
         .register_mut::<calibration::CalibrationState>()
         .register_mut::<ipc::PlaybackSnapshot>()
         .register_mut::<ipc::playback::PlaybackSeekResult>()
+        .register_mut::<ipc::playback::PlaybackPosition>()
         .register_mut::<ipc::playback::PlaybackState>()
         .register_mut::<ipc::telemetry::TelemetryFlight>()
         .register_mut::<ipc::telemetry::TelemetryNavigation>()
@@ -321,7 +330,198 @@ fn generated_ironwing() -> Result<String, Box<dyn Error>> {
         .register_mut::<firmware::FirmwareRebootToBootloaderResult>()
         .register_mut::<firmware::DfuRecoverySource>()
         .register_mut::<firmware::SerialFlowResult>()
-        .register_mut::<firmware::FirmwareError>();
+        .register_mut::<firmware::FirmwareError>()
+        .register_mut::<ipc::VehicleListEntry>()
+        .register_mut::<ipc::VehicleSelected>()
+        .register_mut::<ipc::ComponentListEntry>()
+        .register_mut::<ipc::GcsIdentity>()
+        .register_mut::<ipc::ConnectionInfo>()
+        .register_mut::<ipc::LinkProfile>()
+        .register_mut::<ipc::EventBridgeStats>()
+        .register_mut::<ipc::AlertCondition>()
+        .register_mut::<ipc::AlertRule>()
+        .register_mut::<ipc::AlertValue>()
+        .register_mut::<ipc::AlertTriggered>()
+        .register_mut::<ipc::FlightSessionSummary>()
+        .register_mut::<ipc::DistanceUnit>()
+        .register_mut::<ipc::SpeedUnit>()
+        .register_mut::<ipc::TemperatureUnit>()
+        .register_mut::<ipc::UnitSystem>()
+        .register_mut::<ipc::DisplayValue>()
+        .register_mut::<ipc::NamedValueKind>()
+        .register_mut::<ipc::NamedValue>()
+        .register_mut::<ipc::SerialFlowControl>()
+        .register_mut::<ipc::SerialParity>()
+        .register_mut::<ipc::UsbSerialDevice>()
+        .register_mut::<ipc::LinkFrameStats>()
+        .register_mut::<ipc::LinkSource>()
+        .register_mut::<ipc::LinkSourceEntry>()
+        .register_mut::<ipc::TransferOutcome>()
+        .register_mut::<ipc::MissionTransferProgress>()
+        .register_mut::<ipc::OrbitDirection>()
+        .register_mut::<ipc::OrbitRequest>()
+        .register_mut::<ipc::OrbitStrategy>()
+        .register_mut::<ipc::OrbitResult>()
+        .register_mut::<ipc::RtlPoint>()
+        .register_mut::<ipc::RtlSegmentKind>()
+        .register_mut::<ipc::RtlSegment>()
+        .register_mut::<ipc::RtlPreview>()
+        .register_mut::<ipc::Settings>()
+        .register_mut::<ipc::SettingsPatch>()
+        .register_mut::<ipc::JournalEntry>()
+        .register_mut::<ipc::JournalOutcome>()
+        .register_mut::<ipc::AppError>()
+        .register_mut::<ipc::MavResultKind>()
+        .register_mut::<ipc::TakeoffGuidedRequest>()
+        .register_mut::<ipc::TakeoffReport>()
+        .register_mut::<ipc::TakeoffStep>()
+        .register_mut::<ipc::TakeoffStepReport>()
+        .register_mut::<ipc::TakeoffStepStatus>()
+        .register_mut::<ipc::GotoFrame>()
+        .register_mut::<ipc::GotoMethod>()
+        .register_mut::<ipc::GotoOptions>()
+        .register_mut::<ipc::GotoRequest>()
+        .register_mut::<ipc::GotoResult>()
+        .register_mut::<ipc::GuidedTarget>()
+        .register_mut::<ipc::GcsFailsafeBehavior>()
+        .register_mut::<ipc::LinkLostInFlight>()
+        .register_mut::<ipc::VideoStream>()
+        .register_mut::<ipc::GpsFix>()
+        .register_mut::<ipc::GpsFixChanged>()
+        .register_mut::<ipc::GpsInstanceStatus>()
+        .register_mut::<ipc::GpsStatus>()
+        .register_mut::<ipc::PositionIssue>()
+        .register_mut::<ipc::PositionIssueKind>()
+        .register_mut::<ipc::LogFinding>()
+        .register_mut::<ipc::LogFindingCategory>()
+        .register_mut::<ipc::LogFindingMetric>()
+        .register_mut::<ipc::LogFindingSeverity>()
+        .register_mut::<ipc::TrackPoint>()
+        .register_mut::<ipc::ParamWriteOutcome>()
+        .register_mut::<ipc::FrameApplyResult>()
+        .register_mut::<ipc::FrameOption>()
+        .register_mut::<ipc::FlightModeChannelConfig>()
+        .register_mut::<ipc::FlightModeRange>()
+        .register_mut::<ipc::SafetyGateConfig>()
+        .register_mut::<ipc::SafetyGateFailure>()
+        .register_mut::<ipc::BatteryStats>()
+        .register_mut::<ipc::CellCountSource>()
+        .register_mut::<ipc::ChecklistCheck>()
+        .register_mut::<ipc::ChecklistConfig>()
+        .register_mut::<ipc::ChecklistItem>()
+        .register_mut::<ipc::ChecklistItemState>()
+        .register_mut::<ipc::ChecklistItemStatus>()
+        .register_mut::<ipc::ChecklistState>()
+        .register_mut::<ipc::SessionExportOptions>()
+        .register_mut::<ipc::SessionArtifact>()
+        .register_mut::<ipc::SessionArtifactStatus>()
+        .register_mut::<ipc::SessionArtifactEntry>()
+        .register_mut::<ipc::SessionExportManifest>()
+        .register_mut::<ipc::ExportReport>()
+        .register_mut::<ipc::SessionExportProgress>()
+        .register_mut::<ipc::AckResult>()
+        .register_mut::<ipc::CommandProgress>()
+        .register_mut::<ipc::GcsCommandReceived>()
+        .register_mut::<ipc::ScriptFile>()
+        .register_mut::<ipc::FileUploadReport>()
+        .register_mut::<ipc::FileTransferProgress>()
+        .register_mut::<ipc::FileTransferPhase>()
+        .register_mut::<ipc::ParamExternalChange>()
+        .register_mut::<ipc::TimeSyncStatus>()
+        .register_mut::<ipc::VehicleTime>()
+        .register_mut::<ipc::RangefinderOrientation>()
+        .register_mut::<ipc::RangefinderReading>()
+        .register_mut::<ipc::LandingAssistBand>()
+        .register_mut::<ipc::LandingAssistProfile>()
+        .register_mut::<ipc::LandingAssistWarning>()
+        .register_mut::<ipc::PrecisionLandingTarget>()
+        .register_mut::<ipc::OpticalFlowQuality>()
+        .register_mut::<ipc::PrecisionLandingStatus>()
+        .register_mut::<ipc::GripperCommand>()
+        .register_mut::<ipc::WinchCommand>()
+        .register_mut::<ipc::WinchStatus>()
+        .register_mut::<ipc::HealthComponent>()
+        .register_mut::<ipc::HealthEntry>()
+        .register_mut::<ipc::HealthReport>()
+        .register_mut::<ipc::HealthStatus>()
+        .register_mut::<ipc::HealthThresholds>()
+        .register_mut::<ipc::AltitudeFrame>()
+        .register_mut::<ipc::AltitudeChange>()
+        .register_mut::<ipc::MissionEditWarning>()
+        .register_mut::<ipc::MissionEditResult>()
+        .register_mut::<ipc::FlightModeCurrent>()
+        .register_mut::<ipc::FenceBoundaryKind>()
+        .register_mut::<ipc::FenceProximity>()
+        .register_mut::<ipc::LocalApiStatus>()
+        .register_mut::<ipc::DisconnectAction>()
+        .register_mut::<ipc::DisconnectActionOutcome>()
+        .register_mut::<ipc::DisconnectActionResult>()
+        .register_mut::<ipc::FailsafeKind>()
+        .register_mut::<ipc::FailsafeEvent>()
+        .register_mut::<ipc::TelemetryEncoding>()
+        .register_mut::<ipc::TelemetryDelta>()
+        .register_mut::<ipc::HomeMoved>()
+        .register_mut::<ipc::LogAppended>()
+        .register_mut::<ipc::LogParam>()
+        .register_mut::<ipc::LogStatusText>()
+        .register_mut::<ipc::SessionRecoverInfo>()
+        .register_mut::<ipc::LinkConnecting>()
+        .register_mut::<ipc::TransportStats>()
+        .register_mut::<ipc::TransportLost>()
+        .register_mut::<ipc::telemetry::RcLinkQuality>()
+        .register_mut::<ipc::CalibrationResult>()
+        .register_mut::<ipc::CalibrationProgress>()
+        .register_mut::<ipc::CalibrationKind>()
+        .register_mut::<ipc::SystemResumed>()
+        .register_mut::<ipc::MapBounds>()
+        .register_mut::<ipc::MapPrefetchArea>()
+        .register_mut::<ipc::MapPrefetchRequest>()
+        .register_mut::<ipc::MapPrefetchProgress>()
+        .register_mut::<ipc::ParamBitLabel>()
+        .register_mut::<ipc::ParamBitState>()
+        .register_mut::<ipc::TerrainGotoStrategy>()
+        .register_mut::<ipc::TerrainGotoResult>()
+        .register_mut::<ipc::GuidedQueuePoint>()
+        .register_mut::<ipc::GuidedQueueState>()
+        .register_mut::<ipc::GuidedQueueStatus>()
+        .register_mut::<ipc::TlogRotation>()
+        .register_mut::<ipc::TlogFsyncPolicy>()
+        .register_mut::<ipc::LinkTimeoutProfile>()
+        .register_mut::<ipc::LinkTimeouts>()
+        .register_mut::<ipc::LinkTimeoutOverrides>()
+        .register_mut::<ipc::PlaneLaunchKind>()
+        .register_mut::<ipc::PlaneLaunch>()
+        .register_mut::<ipc::PlaneTakeoffResult>()
+        .register_mut::<ipc::PlaneLoiterResult>()
+        .register_mut::<ipc::PlaneCruiseThrottleResult>()
+        .register_mut::<ipc::VtolState>()
+        .register_mut::<ipc::LandedState>()
+        .register_mut::<ipc::VtolStatus>()
+        .register_mut::<ipc::VtolTransitionTarget>()
+        .register_mut::<ipc::LogVtolEvent>()
+        .register_mut::<ipc::UnknownMessage>()
+        .register_mut::<ipc::TaskState>()
+        .register_mut::<ipc::TaskDiagnostic>()
+        .register_mut::<ipc::TaskStalled>()
+        .register_mut::<ipc::Diagnostics>()
+        .register_mut::<ipc::LinkQualitySample>()
+        .register_mut::<ipc::DiagnosticsBundleEntry>()
+        .register_mut::<ipc::DiagnosticsExport>()
+        .register_mut::<ipc::PendingConnectSource>()
+        .register_mut::<ipc::PendingConnect>()
+        .register_mut::<ipc::ParamFileEntry>()
+        .register_mut::<ipc::ParamFileWarning>()
+        .register_mut::<ipc::ParsedParamFile>()
+        .register_mut::<ipc::ParamTuneSession>()
+        .register_mut::<ipc::ParamTuneSummary>()
+        .register_mut::<ipc::TuneAxis>()
+        .register_mut::<ipc::TuneTargetSource>()
+        .register_mut::<ipc::TuneStep>()
+        .register_mut::<ipc::TuneReport>()
+        .register_mut::<ipc::AnnouncementPriority>()
+        .register_mut::<ipc::AnnouncementCategory>()
+        .register_mut::<ipc::Announcement>()
+        .register_mut::<ipc::AnnouncementSettings>();
 
     let semantic = semantic_config();
     let semantic_types = semantic.apply_types(&types);
@@ -0,0 +1,332 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use web_time::Instant;
+
+use crate::ipc::alerts::{AlertCondition, AlertRule, AlertTriggered, AlertValue};
+use crate::rc_link_quality::RC_LINK_LOW_PCT;
+
+/// File name the desktop shell persisted the rule set under before rules moved
+/// into the settings file; read once to migrate them.
+pub const ALERT_RULES_FILE_NAME: &str = "alerts.json";
+
+const EQ_EPSILON: f64 = 1e-9;
+
+#[derive(Debug, Default)]
+struct RuleState {
+    active: bool,
+    last_value: Option<AlertValue>,
+    last_triggered: Option<Instant>,
+}
+
+/// Built-in rules installed when no rule set has been saved yet. Each one can
+/// be switched off through its `enabled` flag rather than deleted.
+pub fn default_alert_rules() -> Vec<AlertRule> {
+    vec![
+        AlertRule {
+            id: "builtin.battery_low".to_string(),
+            source: "telemetry.power.battery_pct".to_string(),
+            condition: AlertCondition::Lt,
+            threshold: Some(20.0),
+            hysteresis: 2.0,
+            min_interval_s: 60.0,
+            enabled: true,
+        },
+        AlertRule {
+            id: "builtin.battery_critical".to_string(),
+            source: "telemetry.power.battery_pct".to_string(),
+            condition: AlertCondition::Lt,
+            threshold: Some(10.0),
+            hysteresis: 2.0,
+            min_interval_s: 30.0,
+            enabled: true,
+        },
+        AlertRule {
+            id: "builtin.battery_cell_low".to_string(),
+            source: "battery.cell_voltage_v".to_string(),
+            condition: AlertCondition::Lt,
+            threshold: Some(3.5),
+            hysteresis: 0.1,
+            min_interval_s: 60.0,
+            enabled: true,
+        },
+        AlertRule {
+            id: "builtin.battery_cell_critical".to_string(),
+            source: "battery.cell_voltage_v".to_string(),
+            condition: AlertCondition::Lt,
+            threshold: Some(3.3),
+            hysteresis: 0.1,
+            min_interval_s: 30.0,
+            enabled: true,
+        },
+        AlertRule {
+            id: "builtin.rc_link_low".to_string(),
+            source: "rc_link.armed_quality_pct".to_string(),
+            condition: AlertCondition::Lt,
+            threshold: Some(RC_LINK_LOW_PCT),
+            hysteresis: 5.0,
+            min_interval_s: 30.0,
+            enabled: true,
+        },
+        AlertRule {
+            id: "builtin.gps_fix_changed".to_string(),
+            source: "telemetry.gps.fix_type".to_string(),
+            condition: AlertCondition::Changed,
+            threshold: None,
+            hysteresis: 0.0,
+            min_interval_s: 5.0,
+            enabled: true,
+        },
+    ]
+}
+
+pub fn validate_alert_rules(rules: &[AlertRule]) -> Result<(), String> {
+    let mut ids = HashSet::new();
+    for rule in rules {
+        if rule.id.trim().is_empty() {
+            return Err("alert rule id must not be empty".to_string());
+        }
+        if !ids.insert(rule.id.as_str()) {
+            return Err(format!("duplicate alert rule id '{}'", rule.id));
+        }
+        if rule.source.split('.').any(str::is_empty) {
+            return Err(format!(
+                "alert rule '{}' has an invalid source '{}'",
+                rule.id, rule.source
+            ));
+        }
+        if rule.condition != AlertCondition::Changed && !rule.threshold.is_some_and(f64::is_finite)
+        {
+            return Err(format!("alert rule '{}' needs a finite threshold", rule.id));
+        }
+        if !(rule.hysteresis.is_finite() && rule.hysteresis >= 0.0)
+            || !(rule.min_interval_s.is_finite() && rule.min_interval_s >= 0.0)
+        {
+            return Err(format!(
+                "alert rule '{}' hysteresis and min_interval_s must be non-negative",
+                rule.id
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Evaluates alert rules against telemetry snapshots.
+///
+/// Threshold rules fire on the transition into the alerting region and re-arm
+/// only once the value has moved `hysteresis` back past the threshold.
+/// `changed` rules fire whenever the value differs from the previous sample.
+/// Either way a rule never fires twice within its `min_interval_s`.
+#[derive(Debug)]
+pub struct AlertEngine {
+    rules: Vec<AlertRule>,
+    states: HashMap<String, RuleState>,
+}
+
+impl Default for AlertEngine {
+    fn default() -> Self {
+        Self {
+            rules: default_alert_rules(),
+            states: HashMap::new(),
+        }
+    }
+}
+
+impl AlertEngine {
+    pub fn rules(&self) -> &[AlertRule] {
+        &self.rules
+    }
+
+    pub fn set_rules(&mut self, rules: Vec<AlertRule>) -> Result<(), String> {
+        validate_alert_rules(&rules)?;
+        self.rules = rules;
+        self.states.clear();
+        Ok(())
+    }
+
+    /// Forget edge and rate-limit state, e.g. when a new session starts.
+    pub fn reset(&mut self) {
+        self.states.clear();
+    }
+
+    /// Evaluate every enabled rule against `sources`, a JSON object whose
+    /// top-level keys are the source roots (`telemetry`, `vehicle`).
+    pub fn evaluate(&mut self, sources: &serde_json::Value, now: Instant) -> Vec<AlertTriggered> {
+        let mut triggered = Vec::new();
+        for rule in self.rules.iter().filter(|rule| rule.enabled) {
+            let Some(value) = lookup(sources, &rule.source) else {
+                continue;
+            };
+            let state = self.states.entry(rule.id.clone()).or_default();
+            let fired = match rule.condition {
+                AlertCondition::Changed => {
+                    let changed = state
+                        .last_value
+                        .as_ref()
+                        .is_some_and(|previous| *previous != value);
+                    state.last_value = Some(value.clone());
+                    changed
+                }
+                condition => {
+                    let Some(number) = numeric(&value) else {
+                        continue;
+                    };
+                    let threshold = rule.threshold.unwrap_or_default();
+                    let active = if state.active {
+                        !cleared(condition, number, threshold, rule.hysteresis)
+                    } else {
+                        entered(condition, number, threshold)
+                    };
+                    let rising = active && !state.active;
+                    state.active = active;
+                    rising
+                }
+            };
+            if !fired {
+                continue;
+            }
+            let min_interval = Duration::from_secs_f64(rule.min_interval_s);
+            if state
+                .last_triggered
+                .is_some_and(|last| now.saturating_duration_since(last) < min_interval)
+            {
+                continue;
+            }
+            state.last_triggered = Some(now);
+            triggered.push(AlertTriggered {
+                rule_id: rule.id.clone(),
+                source: rule.source.clone(),
+                value,
+            });
+        }
+        triggered
+    }
+}
+
+fn lookup(sources: &serde_json::Value, path: &str) -> Option<AlertValue> {
+    let value = path
+        .split('.')
+        .try_fold(sources, |value, segment| value.get(segment))?;
+    match value {
+        serde_json::Value::Number(number) => number.as_f64().map(AlertValue::Number),
+        serde_json::Value::String(text) => Some(AlertValue::Text(text.clone())),
+        serde_json::Value::Bool(flag) => Some(AlertValue::Bool(*flag)),
+        _ => None,
+    }
+}
+
+fn numeric(value: &AlertValue) -> Option<f64> {
+    match value {
+        AlertValue::Number(number) => Some(*number),
+        AlertValue::Bool(flag) => Some(if *flag { 1.0 } else { 0.0 }),
+        AlertValue::Text(_) => None,
+    }
+}
+
+fn entered(condition: AlertCondition, value: f64, threshold: f64) -> bool {
+    match condition {
+        AlertCondition::Lt => value < threshold,
+        AlertCondition::Gt => value > threshold,
+        AlertCondition::Eq => (value - threshold).abs() < EQ_EPSILON,
+        AlertCondition::Changed => false,
+    }
+}
+
+fn cleared(condition: AlertCondition, value: f64, threshold: f64, hysteresis: f64) -> bool {
+    match condition {
+        AlertCondition::Lt => value >= threshold + hysteresis,
+        AlertCondition::Gt => value <= threshold - hysteresis,
+        AlertCondition::Eq => (value - threshold).abs() > hysteresis,
+        AlertCondition::Changed => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn battery(pct: f64) -> serde_json::Value {
+        serde_json::json!({ "telemetry": { "power": { "battery_pct": pct } } })
+    }
+
+    fn fired(engine: &mut AlertEngine, sources: serde_json::Value, now: Instant) -> Vec<String> {
+        engine
+            .evaluate(&sources, now)
+            .into_iter()
+            .map(|alert| alert.rule_id)
+            .collect()
+    }
+
+    #[test]
+    fn threshold_rule_rearms_only_past_hysteresis() {
+        let start = Instant::now();
+        let mut engine = AlertEngine::default();
+        engine
+            .set_rules(vec![AlertRule {
+                id: "low".to_string(),
+                source: "telemetry.power.battery_pct".to_string(),
+                condition: AlertCondition::Lt,
+                threshold: Some(20.0),
+                hysteresis: 2.0,
+                min_interval_s: 0.0,
+                enabled: true,
+            }])
+            .expect("valid rules");
+
+        assert!(fired(&mut engine, battery(25.0), start).is_empty());
+        assert_eq!(fired(&mut engine, battery(19.5), start), ["low"]);
+        assert!(fired(&mut engine, battery(21.0), start).is_empty());
+        assert!(fired(&mut engine, battery(19.0), start).is_empty());
+        assert!(fired(&mut engine, battery(22.0), start).is_empty());
+        assert_eq!(fired(&mut engine, battery(19.0), start), ["low"]);
+    }
+
+    #[test]
+    fn min_interval_suppresses_alert_storms() {
+        let start = Instant::now();
+        let mut engine = AlertEngine::default();
+        engine
+            .set_rules(vec![AlertRule {
+                id: "mode".to_string(),
+                source: "vehicle.mode_name".to_string(),
+                condition: AlertCondition::Changed,
+                threshold: None,
+                hysteresis: 0.0,
+                min_interval_s: 5.0,
+                enabled: true,
+            }])
+            .expect("valid rules");
+        let mode = |name: &str| serde_json::json!({ "vehicle": { "mode_name": name } });
+
+        assert!(fired(&mut engine, mode("LOITER"), start).is_empty());
+        let alerts = engine.evaluate(&mode("RTL"), start + Duration::from_secs(1));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].value, AlertValue::Text("RTL".to_string()));
+        assert!(fired(&mut engine, mode("LAND"), start + Duration::from_secs(2)).is_empty());
+        assert_eq!(
+            fired(&mut engine, mode("RTL"), start + Duration::from_secs(7)),
+            ["mode"]
+        );
+    }
+
+    #[test]
+    fn disabled_and_invalid_rules() {
+        let mut rules = default_alert_rules();
+        for rule in &mut rules {
+            rule.enabled = false;
+        }
+        let mut engine = AlertEngine::default();
+        engine.set_rules(rules.clone()).expect("valid rules");
+        assert!(fired(&mut engine, battery(5.0), Instant::now()).is_empty());
+
+        rules.push(rules[0].clone());
+        assert!(engine.set_rules(rules).is_err());
+        assert!(
+            validate_alert_rules(&[AlertRule {
+                threshold: None,
+                ..default_alert_rules().remove(0)
+            }])
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,546 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use web_time::Instant;
+
+use crate::ipc::announcements::{
+    Announcement, AnnouncementCategory, AnnouncementPriority, AnnouncementSettings,
+};
+use crate::ipc::failsafe::{FailsafeEvent, FailsafeKind};
+
+/// A failsafe of a kind already announced within this window is not read out
+/// again; autopilots repeat the STATUSTEXT while the condition lasts.
+const FAILSAFE_REPEAT_WINDOW: Duration = Duration::from_secs(10);
+
+/// Battery milestones at or below this are read out at high priority.
+const BATTERY_LOW_PCT: f64 = 20.0;
+
+impl Default for AnnouncementSettings {
+    fn default() -> Self {
+        Self {
+            waypoint: true,
+            altitude: true,
+            altitude_step_m: 10.0,
+            battery: true,
+            battery_step_pct: 10.0,
+            mode: true,
+            mode_settle_s: 1.5,
+            failsafe: true,
+        }
+    }
+}
+
+pub fn validate_announcement_settings(settings: &AnnouncementSettings) -> Result<(), String> {
+    if !settings.altitude_step_m.is_finite() || settings.altitude_step_m <= 0.0 {
+        return Err("announcement altitude step must be greater than zero".to_string());
+    }
+    if !settings.battery_step_pct.is_finite()
+        || settings.battery_step_pct <= 0.0
+        || settings.battery_step_pct > 100.0
+    {
+        return Err("announcement battery step must be above 0 and at most 100 %".to_string());
+    }
+    if !settings.mode_settle_s.is_finite() || settings.mode_settle_s < 0.0 {
+        return Err("announcement mode settle time must not be negative".to_string());
+    }
+    Ok(())
+}
+
+/// Turns telemetry, mission progress, mode changes and failsafes into
+/// spoken progress announcements.
+///
+/// Every input is tracked whether or not its category is enabled, so
+/// switching a category on mid-flight does not read out a backlog.
+#[derive(Debug, Default)]
+pub struct Announcer {
+    settings: AnnouncementSettings,
+    /// Last altitude milestone passed, or the one nearest the arming
+    /// altitude; `None` while disarmed.
+    altitude_mark_m: Option<f64>,
+    battery_mark_pct: Option<f64>,
+    mission_index: Option<usize>,
+    spoken_mode: Option<String>,
+    /// A mode that differs from `spoken_mode` and when it was first seen.
+    pending_mode: Option<(String, Instant)>,
+    failsafes_spoken: HashMap<FailsafeKind, Instant>,
+}
+
+impl Announcer {
+    pub fn settings(&self) -> &AnnouncementSettings {
+        &self.settings
+    }
+
+    /// Replace the settings. Milestones already passed are kept.
+    pub fn set_settings(&mut self, settings: AnnouncementSettings) {
+        self.settings = settings;
+    }
+
+    /// Forget everything seen on the previous connection; settings stay.
+    pub fn reset(&mut self) {
+        *self = Self {
+            settings: std::mem::take(&mut self.settings),
+            ..Self::default()
+        };
+    }
+
+    /// Announce each `altitude_step_m` milestone passed while armed. A
+    /// milestone is not repeated until the vehicle has gone a full step
+    /// past it in the other direction, so hovering on one stays quiet.
+    pub fn observe_altitude(
+        &mut self,
+        altitude_m: Option<f64>,
+        armed: bool,
+    ) -> Option<Announcement> {
+        let step = self.settings.altitude_step_m;
+        let Some(altitude_m) = altitude_m.filter(|altitude_m| armed && altitude_m.is_finite())
+        else {
+            if !armed {
+                self.altitude_mark_m = None;
+            }
+            return None;
+        };
+        let Some(mark) = self.altitude_mark_m else {
+            self.altitude_mark_m = Some((altitude_m / step).round() * step);
+            return None;
+        };
+        let passed = if altitude_m >= mark + step {
+            (altitude_m / step).floor() * step
+        } else if altitude_m <= mark - step {
+            (altitude_m / step).ceil() * step
+        } else {
+            return None;
+        };
+        self.altitude_mark_m = Some(passed);
+        // Touching down is not a milestone worth reading out.
+        (self.settings.altitude && passed > 0.0).then(|| Announcement {
+            category: AnnouncementCategory::Altitude,
+            priority: AnnouncementPriority::Low,
+            text: format!("Altitude {} meters", spoken_number(passed)),
+        })
+    }
+
+    /// Announce the battery each time it drops to the next multiple of
+    /// `battery_step_pct`. A reading that climbs back, as a sagging pack
+    /// does once the load comes off, only re-arms the step once it has
+    /// risen a full step, e.g. after a pack swap.
+    pub fn observe_battery(&mut self, battery_pct: Option<f64>) -> Option<Announcement> {
+        let step = self.settings.battery_step_pct;
+        let battery_pct = battery_pct.filter(|battery_pct| battery_pct.is_finite())?;
+        let level = (battery_pct / step).ceil() * step;
+        let Some(mark) = self.battery_mark_pct else {
+            self.battery_mark_pct = Some(level);
+            return None;
+        };
+        if level > mark + step {
+            self.battery_mark_pct = Some(level);
+            return None;
+        }
+        if level >= mark {
+            return None;
+        }
+        self.battery_mark_pct = Some(level);
+        self.settings.battery.then(|| Announcement {
+            category: AnnouncementCategory::Battery,
+            priority: if level <= BATTERY_LOW_PCT {
+                AnnouncementPriority::High
+            } else {
+                AnnouncementPriority::Normal
+            },
+            text: format!("Battery {} percent", spoken_number(level)),
+        })
+    }
+
+    /// Announce the item the vehicle just finished when the current mission
+    /// item moves forward while armed. Moving back, or a new plan, only
+    /// re-baselines.
+    pub fn observe_mission(
+        &mut self,
+        current_index: Option<usize>,
+        item_count: usize,
+        armed: bool,
+    ) -> Option<Announcement> {
+        let previous = std::mem::replace(&mut self.mission_index, current_index);
+        let (reached, current) = previous.zip(current_index)?;
+        (self.settings.waypoint && armed && current > reached && reached < item_count).then(|| {
+            Announcement {
+                category: AnnouncementCategory::Waypoint,
+                priority: AnnouncementPriority::Normal,
+                text: format!("Waypoint {} of {item_count}", reached + 1),
+            }
+        })
+    }
+
+    /// Note the vehicle's mode. The first mode seen on a connection is taken
+    /// as given; later changes are announced by `poll_mode` once they hold.
+    pub fn observe_mode(&mut self, mode_name: &str, now: Instant) {
+        if self.spoken_mode.is_none() {
+            self.spoken_mode = Some(mode_name.to_string());
+            return;
+        }
+        if self.spoken_mode.as_deref() == Some(mode_name) {
+            self.pending_mode = None;
+        } else if self
+            .pending_mode
+            .as_ref()
+            .is_none_or(|(pending, _)| pending != mode_name)
+        {
+            self.pending_mode = Some((mode_name.to_string(), now));
+        }
+    }
+
+    /// The pending mode change once it has held for `mode_settle_s`.
+    pub fn poll_mode(&mut self, now: Instant) -> Option<Announcement> {
+        let settle = Duration::from_secs_f64(self.settings.mode_settle_s);
+        let (_, since) = self.pending_mode.as_ref()?;
+        if now.saturating_duration_since(*since) < settle {
+            return None;
+        }
+        let (mode_name, _) = self.pending_mode.take()?;
+        self.spoken_mode = Some(mode_name.clone());
+        self.settings.mode.then(|| Announcement {
+            category: AnnouncementCategory::Mode,
+            priority: AnnouncementPriority::Normal,
+            text: format!("Mode {mode_name}"),
+        })
+    }
+
+    /// Announce a decoded failsafe, once per kind within
+    /// `FAILSAFE_REPEAT_WINDOW`.
+    pub fn observe_failsafe(
+        &mut self,
+        event: &FailsafeEvent,
+        now: Instant,
+    ) -> Option<Announcement> {
+        if self
+            .failsafes_spoken
+            .get(&event.kind)
+            .is_some_and(|spoken| now.saturating_duration_since(*spoken) < FAILSAFE_REPEAT_WINDOW)
+        {
+            return None;
+        }
+        self.failsafes_spoken.insert(event.kind, now);
+        let mut text = match failsafe_name(event.kind) {
+            Some(name) => format!("{name} failsafe"),
+            None => "Failsafe".to_string(),
+        };
+        if let Some(action) = &event.action_taken {
+            text.push_str(". ");
+            text.push_str(action);
+        }
+        self.settings.failsafe.then(|| Announcement {
+            category: AnnouncementCategory::Failsafe,
+            priority: AnnouncementPriority::High,
+            text,
+        })
+    }
+}
+
+fn failsafe_name(kind: FailsafeKind) -> Option<&'static str> {
+    match kind {
+        FailsafeKind::Battery => Some("Battery"),
+        FailsafeKind::Radio => Some("Radio"),
+        FailsafeKind::Gcs => Some("Ground station"),
+        FailsafeKind::Ekf => Some("EKF"),
+        FailsafeKind::Geofence => Some("Geofence"),
+        FailsafeKind::Terrain => Some("Terrain"),
+        FailsafeKind::Crash => Some("Crash"),
+        FailsafeKind::Other => None,
+    }
+}
+
+/// Whole numbers without a trailing ".0"; anything else to one decimal.
+fn spoken_number(value: f64) -> String {
+    if (value - value.round()).abs() < 1e-6 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.1}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(announcements: impl IntoIterator<Item = Option<Announcement>>) -> Vec<String> {
+        announcements
+            .into_iter()
+            .flatten()
+            .map(|announcement| announcement.text)
+            .collect()
+    }
+
+    fn failsafe(kind: FailsafeKind, action_taken: Option<&str>) -> FailsafeEvent {
+        FailsafeEvent {
+            kind,
+            action_taken: action_taken.map(str::to_string),
+            timestamp_usec: None,
+            text: String::new(),
+        }
+    }
+
+    #[test]
+    fn climb_and_descent_announce_each_milestone_once() {
+        let mut announcer = Announcer::default();
+        let climb = [0.0, 4.0, 9.8, 10.2, 9.7, 10.4, 14.0, 21.0, 33.0, 35.0];
+        let descent = [31.0, 29.5, 24.0, 19.9, 20.3, 12.0, 9.9, 3.0, 0.1];
+        let spoken = texts(
+            climb
+                .into_iter()
+                .chain(descent)
+                .map(|altitude_m| announcer.observe_altitude(Some(altitude_m), true)),
+        );
+
+        assert_eq!(
+            spoken,
+            [
+                "Altitude 10 meters",
+                "Altitude 20 meters",
+                "Altitude 30 meters",
+                "Altitude 20 meters",
+                "Altitude 10 meters",
+            ]
+        );
+    }
+
+    #[test]
+    fn altitude_is_quiet_while_disarmed_and_rebaselines_on_arming() {
+        let mut announcer = Announcer::default();
+        let before_arming = texts(
+            [0.0, 15.0, 32.0].map(|altitude_m| announcer.observe_altitude(Some(altitude_m), false)),
+        );
+        assert!(before_arming.is_empty());
+
+        // Armed on a rooftop at 32 m: the first milestone is 40, not 10..30.
+        let spoken = texts(
+            [32.0, 38.0, 41.0].map(|altitude_m| announcer.observe_altitude(Some(altitude_m), true)),
+        );
+        assert_eq!(spoken, ["Altitude 40 meters"]);
+    }
+
+    #[test]
+    fn altitude_step_follows_the_settings() {
+        let mut announcer = Announcer::default();
+        announcer.set_settings(AnnouncementSettings {
+            altitude_step_m: 2.5,
+            ..AnnouncementSettings::default()
+        });
+        let spoken = texts(
+            [0.0, 2.6, 4.0, 5.1]
+                .map(|altitude_m| announcer.observe_altitude(Some(altitude_m), true)),
+        );
+        assert_eq!(spoken, ["Altitude 2.5 meters", "Altitude 5 meters"]);
+    }
+
+    #[test]
+    fn battery_announces_every_ten_percent_on_the_way_down() {
+        let mut announcer = Announcer::default();
+        let discharge = [
+            97.0, 91.0, 90.0, 89.0, 84.0, 80.5, 79.9, 81.0, 76.0, 62.0, 55.0, 31.0, 20.0, 19.0, 9.5,
+        ];
+        let announcements: Vec<_> = discharge
+            .into_iter()
+            .filter_map(|battery_pct| announcer.observe_battery(Some(battery_pct)))
+            .collect();
+
+        let spoken: Vec<_> = announcements
+            .iter()
+            .map(|announcement| announcement.text.as_str())
+            .collect();
+        assert_eq!(
+            spoken,
+            [
+                "Battery 90 percent",
+                "Battery 80 percent",
+                "Battery 70 percent",
+                "Battery 60 percent",
+                "Battery 40 percent",
+                "Battery 20 percent",
+                "Battery 10 percent",
+            ]
+        );
+        assert_eq!(announcements[0].priority, AnnouncementPriority::Normal);
+        assert_eq!(announcements[5].priority, AnnouncementPriority::High);
+    }
+
+    #[test]
+    fn battery_recovering_under_no_load_does_not_repeat_a_step() {
+        let mut announcer = Announcer::default();
+        let spoken = texts(
+            [72.0, 69.0, 71.5, 70.0, 68.0, 72.0, 69.0]
+                .map(|battery_pct| announcer.observe_battery(Some(battery_pct))),
+        );
+        assert_eq!(spoken, ["Battery 70 percent"]);
+
+        // A fresh pack re-arms the steps.
+        let spoken = texts(
+            [100.0, 95.0, 90.0].map(|battery_pct| announcer.observe_battery(Some(battery_pct))),
+        );
+        assert_eq!(spoken, ["Battery 90 percent"]);
+    }
+
+    #[test]
+    fn waypoints_are_announced_as_the_mission_advances() {
+        let mut announcer = Announcer::default();
+        let progress = [
+            None,
+            Some(0),
+            Some(0),
+            Some(1),
+            Some(2),
+            Some(4),
+            Some(1),
+            Some(2),
+        ];
+        let spoken =
+            texts(progress.map(|current_index| announcer.observe_mission(current_index, 12, true)));
+
+        assert_eq!(
+            spoken,
+            [
+                "Waypoint 1 of 12",
+                "Waypoint 2 of 12",
+                "Waypoint 3 of 12",
+                // Jumped back to item 1, then on again.
+                "Waypoint 2 of 12",
+            ]
+        );
+    }
+
+    #[test]
+    fn setting_the_current_item_on_the_ground_is_not_progress() {
+        let mut announcer = Announcer::default();
+        let spoken = texts(
+            [Some(0), Some(3), Some(5)]
+                .map(|current_index| announcer.observe_mission(current_index, 12, false)),
+        );
+        assert!(spoken.is_empty());
+    }
+
+    #[test]
+    fn mode_flapping_collapses_to_one_announcement_of_the_final_mode() {
+        let mut announcer = Announcer::default();
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        announcer.observe_mode("STABILIZE", at(0));
+        assert_eq!(announcer.poll_mode(at(100)), None);
+
+        let mut spoken = Vec::new();
+        let flaps = [
+            (1_000, "LOITER"),
+            (1_300, "ALT_HOLD"),
+            (1_500, "LOITER"),
+            (1_900, "ALT_HOLD"),
+            (2_200, "LOITER"),
+        ];
+        for (ms, mode_name) in flaps {
+            announcer.observe_mode(mode_name, at(ms));
+            spoken.extend(announcer.poll_mode(at(ms + 100)));
+        }
+        for ms in (2_400..=5_000).step_by(200) {
+            spoken.extend(announcer.poll_mode(at(ms)));
+        }
+
+        let spoken: Vec<_> = spoken
+            .into_iter()
+            .map(|announcement| announcement.text)
+            .collect();
+        assert_eq!(spoken, ["Mode LOITER"]);
+    }
+
+    #[test]
+    fn a_mode_switched_away_and_back_is_not_announced() {
+        let mut announcer = Announcer::default();
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        announcer.observe_mode("AUTO", at(0));
+        announcer.observe_mode("RTL", at(1_000));
+        announcer.observe_mode("AUTO", at(1_400));
+        assert_eq!(announcer.poll_mode(at(4_000)), None);
+
+        announcer.observe_mode("RTL", at(5_000));
+        assert_eq!(announcer.poll_mode(at(6_000)), None);
+        assert_eq!(
+            announcer
+                .poll_mode(at(6_500))
+                .map(|announcement| announcement.text),
+            Some("Mode RTL".to_string())
+        );
+    }
+
+    #[test]
+    fn repeated_failsafes_are_announced_once_per_window() {
+        let mut announcer = Announcer::default();
+        let start = Instant::now();
+        let radio = failsafe(FailsafeKind::Radio, Some("Switching to RTL"));
+
+        let first = announcer
+            .observe_failsafe(&radio, start)
+            .expect("announced");
+        assert_eq!(first.text, "Radio failsafe. Switching to RTL");
+        assert_eq!(first.priority, AnnouncementPriority::High);
+        assert_eq!(
+            announcer.observe_failsafe(&radio, start + Duration::from_secs(3)),
+            None
+        );
+        assert_eq!(
+            announcer
+                .observe_failsafe(
+                    &failsafe(FailsafeKind::Battery, None),
+                    start + Duration::from_secs(3)
+                )
+                .map(|announcement| announcement.text),
+            Some("Battery failsafe".to_string())
+        );
+        assert!(
+            announcer
+                .observe_failsafe(&radio, start + Duration::from_secs(11))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn disabled_categories_stay_quiet_but_keep_tracking() {
+        let mut announcer = Announcer::default();
+        announcer.set_settings(AnnouncementSettings {
+            battery: false,
+            ..AnnouncementSettings::default()
+        });
+        let spoken = texts(
+            [95.0, 88.0, 79.0].map(|battery_pct| announcer.observe_battery(Some(battery_pct))),
+        );
+        assert!(spoken.is_empty());
+
+        announcer.set_settings(AnnouncementSettings::default());
+        assert_eq!(announcer.observe_battery(Some(77.0)), None);
+        assert_eq!(
+            announcer
+                .observe_battery(Some(70.0))
+                .map(|announcement| announcement.text),
+            Some("Battery 70 percent".to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_steps_are_rejected() {
+        let valid = AnnouncementSettings::default();
+        assert!(validate_announcement_settings(&valid).is_ok());
+        for settings in [
+            AnnouncementSettings {
+                altitude_step_m: 0.0,
+                ..valid.clone()
+            },
+            AnnouncementSettings {
+                battery_step_pct: 150.0,
+                ..valid.clone()
+            },
+            AnnouncementSettings {
+                mode_settle_s: f64::NAN,
+                ..valid.clone()
+            },
+        ] {
+            assert!(validate_announcement_settings(&settings).is_err());
+        }
+    }
+}
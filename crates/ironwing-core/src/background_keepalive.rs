@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use crate::ipc::telemetry::TelemetryState;
+
+/// How often the Android keep-alive notification is refreshed, and how
+/// quickly it follows arming and disarming.
+pub const KEEPALIVE_UPDATE_INTERVAL: Duration = Duration::from_secs(5);
+
+pub const KEEPALIVE_NOTIFICATION_TITLE: &str = "IronWing: vehicle connected";
+
+/// One line of the keep-alive notification, e.g. `LOITER · 76% 15.2 V · 42 m`.
+/// Values the vehicle has not reported are left out.
+pub fn keepalive_notification_text(
+    mode: Option<&str>,
+    telemetry: Option<&TelemetryState>,
+) -> String {
+    let mut parts = vec![mode.unwrap_or("Unknown mode").to_string()];
+    let Some(telemetry) = telemetry else {
+        return parts.join(" · ");
+    };
+    let battery: Vec<String> = [
+        telemetry.power.battery_pct.map(|pct| format!("{pct:.0}%")),
+        telemetry
+            .power
+            .battery_voltage_v
+            .map(|volts| format!("{volts:.1} V")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if !battery.is_empty() {
+        parts.push(battery.join(" "));
+    }
+    if let Some(altitude_m) = telemetry.flight.altitude_m {
+        parts.push(format!("{altitude_m:.0} m"));
+    }
+    parts.join(" · ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_text_lists_mode_battery_and_altitude() {
+        let mut telemetry = TelemetryState::default();
+        telemetry.power.battery_pct = Some(76.4);
+        telemetry.power.battery_voltage_v = Some(15.18);
+        telemetry.flight.altitude_m = Some(42.3);
+        assert_eq!(
+            keepalive_notification_text(Some("LOITER"), Some(&telemetry)),
+            "LOITER · 76% 15.2 V · 42 m"
+        );
+    }
+
+    #[test]
+    fn missing_values_are_left_out() {
+        let mut telemetry = TelemetryState::default();
+        telemetry.power.battery_voltage_v = Some(11.1);
+        assert_eq!(
+            keepalive_notification_text(None, Some(&telemetry)),
+            "Unknown mode · 11.1 V"
+        );
+        assert_eq!(keepalive_notification_text(Some("RTL"), None), "RTL");
+    }
+}
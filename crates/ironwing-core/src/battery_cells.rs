@@ -0,0 +1,221 @@
+use crate::ipc::{BatteryStats, CellCountSource};
+
+/// Parameters that hold the pack's cell count, most specific first: PX4's
+/// per-battery and legacy names, then ArduPilot's OSD cell count (0 there
+/// means "auto").
+const CELL_COUNT_PARAMS: [&str; 3] = ["BAT1_N_CELLS", "BAT_N_CELLS", "OSD_CELL_COUNT"];
+
+/// A full LiPo/LiHV cell; dividing by this gives the fewest cells a pack
+/// voltage can come from.
+const MAX_CELL_V: f64 = 4.35;
+/// Per-cell voltage below which the fewest-cells guess is rejected; a pack
+/// reading that low is dead or sagging and inference waits for a better
+/// reading.
+const MIN_INFERENCE_CELL_V: f64 = 3.2;
+const MAX_CELLS: u8 = 14;
+/// Consecutive readings within `STABLE_SPREAD_V` before the voltage is used
+/// for inference, so the sag of a pack being plugged in is skipped.
+const STABLE_SAMPLES: usize = 5;
+const STABLE_SPREAD_V: f64 = 0.2;
+/// A jump this large between two readings while disarmed is taken as a
+/// battery swap.
+const SWAP_JUMP_V: f64 = 1.0;
+
+/// Cell count from the vehicle's parameters, when one is set.
+pub fn cell_count_from_params(param: impl Fn(&str) -> Option<f32>) -> Option<u8> {
+    CELL_COUNT_PARAMS.iter().find_map(|name| {
+        param(name)
+            .map(f32::round)
+            .filter(|cells| (1.0..=f32::from(MAX_CELLS)).contains(cells))
+            .map(|cells| cells as u8)
+    })
+}
+
+/// Fewest cells that can produce `voltage_v`, or `None` when the pack is too
+/// discharged (or absent) for the guess to be unambiguous.
+pub fn infer_cell_count(voltage_v: f64) -> Option<u8> {
+    if !voltage_v.is_finite() || voltage_v <= 0.0 {
+        return None;
+    }
+    let cells = (voltage_v / MAX_CELL_V).ceil().max(1.0);
+    if cells > f64::from(MAX_CELLS) || voltage_v / cells < MIN_INFERENCE_CELL_V {
+        return None;
+    }
+    Some(cells as u8)
+}
+
+/// Tracks the pack's cell count and per-cell voltage across telemetry
+/// updates. Reported cell voltages win over a cell-count parameter, which
+/// wins over inference from the pack voltage.
+#[derive(Debug, Clone, Default)]
+pub struct BatteryCellTracker {
+    stats: BatteryStats,
+    inferred_cells: Option<u8>,
+    recent_v: Vec<f64>,
+    last_voltage_v: Option<f64>,
+}
+
+impl BatteryCellTracker {
+    pub fn stats(&self) -> BatteryStats {
+        self.stats.clone()
+    }
+
+    /// Feed one telemetry update. Returns `true` when the stats changed.
+    pub fn observe(
+        &mut self,
+        voltage_v: Option<f64>,
+        reported_cells_v: Option<&[f64]>,
+        param_cells: Option<u8>,
+        armed: bool,
+    ) -> bool {
+        let voltage_v = voltage_v.filter(|voltage| voltage.is_finite() && *voltage > 0.0);
+        if let Some(voltage) = voltage_v {
+            self.track_inference(voltage, armed);
+        }
+
+        let reported: Vec<f64> = reported_cells_v
+            .unwrap_or_default()
+            .iter()
+            .copied()
+            .filter(|cell| cell.is_finite() && *cell > 0.0)
+            .collect();
+
+        let next = if reported.len() >= 2 {
+            BatteryStats {
+                cell_count: u8::try_from(reported.len()).ok(),
+                cell_count_source: Some(CellCountSource::Reported),
+                voltage_v,
+                cell_voltage_v: reported.iter().copied().reduce(f64::min),
+                cell_voltages_v: Some(reported),
+            }
+        } else {
+            let (cell_count, source) = match (param_cells, self.inferred_cells) {
+                (Some(cells), _) => (Some(cells), Some(CellCountSource::Param)),
+                (None, Some(cells)) => (Some(cells), Some(CellCountSource::Inferred)),
+                (None, None) => (None, None),
+            };
+            BatteryStats {
+                cell_count,
+                cell_count_source: source,
+                voltage_v,
+                cell_voltage_v: voltage_v
+                    .zip(cell_count)
+                    .map(|(voltage, cells)| voltage / f64::from(cells)),
+                cell_voltages_v: None,
+            }
+        };
+
+        let changed = next != self.stats;
+        self.stats = next;
+        changed
+    }
+
+    fn track_inference(&mut self, voltage_v: f64, armed: bool) {
+        let jumped = self
+            .last_voltage_v
+            .is_some_and(|last| (voltage_v - last).abs() > SWAP_JUMP_V);
+        self.last_voltage_v = Some(voltage_v);
+        if jumped && !armed {
+            self.inferred_cells = None;
+            self.recent_v.clear();
+        }
+        if self.inferred_cells.is_some() {
+            return;
+        }
+
+        if self
+            .recent_v
+            .iter()
+            .any(|recent| (recent - voltage_v).abs() > STABLE_SPREAD_V)
+        {
+            self.recent_v.clear();
+        }
+        self.recent_v.push(voltage_v);
+        if self.recent_v.len() < STABLE_SAMPLES {
+            return;
+        }
+        let mean = self.recent_v.iter().sum::<f64>() / self.recent_v.len() as f64;
+        self.recent_v.clear();
+        self.inferred_cells = infer_cell_count(mean);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settle(tracker: &mut BatteryCellTracker, voltage_v: f64, armed: bool) {
+        for _ in 0..STABLE_SAMPLES {
+            tracker.observe(Some(voltage_v), None, None, armed);
+        }
+    }
+
+    #[test]
+    fn infers_common_pack_sizes_from_voltage() {
+        assert_eq!(infer_cell_count(12.6), Some(3));
+        assert_eq!(infer_cell_count(11.1), Some(3));
+        assert_eq!(infer_cell_count(16.8), Some(4));
+        assert_eq!(infer_cell_count(22.2), Some(6));
+        assert_eq!(infer_cell_count(25.2), Some(6));
+        assert_eq!(infer_cell_count(9.0), None);
+        assert_eq!(infer_cell_count(0.0), None);
+    }
+
+    #[test]
+    fn waits_for_a_stable_voltage_before_inferring() {
+        let mut tracker = BatteryCellTracker::default();
+        tracker.observe(Some(10.0), None, None, false);
+        tracker.observe(Some(16.6), None, None, false);
+        assert_eq!(tracker.stats().cell_count, None);
+
+        settle(&mut tracker, 16.6, false);
+        let stats = tracker.stats();
+        assert_eq!(stats.cell_count, Some(4));
+        assert_eq!(stats.cell_count_source, Some(CellCountSource::Inferred));
+        assert!((stats.cell_voltage_v.unwrap() - 4.15).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reported_cells_override_params_and_inference() {
+        let mut tracker = BatteryCellTracker::default();
+        settle(&mut tracker, 16.6, false);
+        tracker.observe(Some(16.6), None, Some(6), false);
+        assert_eq!(
+            tracker.stats().cell_count_source,
+            Some(CellCountSource::Param)
+        );
+        assert_eq!(tracker.stats().cell_count, Some(6));
+
+        let cells = [4.15, 4.12, 4.16, 4.17];
+        assert!(tracker.observe(Some(16.6), Some(&cells), Some(6), false));
+        let stats = tracker.stats();
+        assert_eq!(stats.cell_count, Some(4));
+        assert_eq!(stats.cell_count_source, Some(CellCountSource::Reported));
+        assert_eq!(stats.cell_voltage_v, Some(4.12));
+    }
+
+    #[test]
+    fn battery_swap_while_disarmed_reruns_detection() {
+        let mut tracker = BatteryCellTracker::default();
+        settle(&mut tracker, 12.4, false);
+        assert_eq!(tracker.stats().cell_count, Some(3));
+
+        // A sag while armed keeps the count.
+        settle(&mut tracker, 11.0, true);
+        assert_eq!(tracker.stats().cell_count, Some(3));
+
+        settle(&mut tracker, 24.8, false);
+        assert_eq!(tracker.stats().cell_count, Some(6));
+    }
+
+    #[test]
+    fn reads_cell_count_params_in_order() {
+        let params = |name: &str| match name {
+            "BAT1_N_CELLS" => Some(0.0),
+            "OSD_CELL_COUNT" => Some(4.0),
+            _ => None,
+        };
+        assert_eq!(cell_count_from_params(params), Some(4));
+        assert_eq!(cell_count_from_params(|_| None), None);
+    }
+}
@@ -0,0 +1,357 @@
+use std::time::Duration;
+
+use crate::ipc::{CalibrationKind, CalibrationProgress, CalibrationResult, StatusTextEntry};
+
+/// How long after the ACK a simple calibration's result is waited for.
+pub const CALIBRATION_RESULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often the STATUSTEXT history is checked while waiting.
+pub const CALIBRATION_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long compass calibration STATUSTEXT is relayed after the start.
+pub const COMPASS_CALIBRATION_WATCH: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationFirmware {
+    ArduPilot,
+    Px4,
+}
+
+impl From<mavkit::AutopilotType> for CalibrationFirmware {
+    fn from(autopilot: mavkit::AutopilotType) -> Self {
+        match autopilot {
+            mavkit::AutopilotType::Px4 => Self::Px4,
+            _ => Self::ArduPilot,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternOutcome {
+    Progress,
+    Succeeded,
+    Failed,
+}
+
+/// A STATUSTEXT fragment a firmware sends during calibration. `needle` is
+/// matched case-insensitively anywhere in the text.
+#[derive(Debug, Clone, Copy)]
+pub struct CalibrationPattern {
+    pub firmware: CalibrationFirmware,
+    pub kinds: &'static [CalibrationKind],
+    pub needle: &'static str,
+    pub outcome: PatternOutcome,
+}
+
+const IMU: &[CalibrationKind] = &[CalibrationKind::Gyro, CalibrationKind::Accel];
+const ACCEL: &[CalibrationKind] = &[CalibrationKind::Accel];
+const GYRO: &[CalibrationKind] = &[CalibrationKind::Gyro];
+const COMPASS: &[CalibrationKind] = &[CalibrationKind::Compass];
+const ANY: &[CalibrationKind] = &[
+    CalibrationKind::Gyro,
+    CalibrationKind::Accel,
+    CalibrationKind::Compass,
+];
+
+const fn pattern(
+    firmware: CalibrationFirmware,
+    kinds: &'static [CalibrationKind],
+    needle: &'static str,
+    outcome: PatternOutcome,
+) -> CalibrationPattern {
+    CalibrationPattern {
+        firmware,
+        kinds,
+        needle,
+        outcome,
+    }
+}
+
+/// Calibration STATUSTEXT by firmware, first match wins. PX4 follows its
+/// `[cal]` calibration protocol; ArduPilot reports through AP_AccelCal,
+/// gyro init and the compass calibrator.
+pub const CALIBRATION_PATTERNS: &[CalibrationPattern] = {
+    use CalibrationFirmware::{ArduPilot, Px4};
+    use PatternOutcome::{Failed, Progress, Succeeded};
+    &[
+        pattern(Px4, ANY, "[cal] calibration failed", Failed),
+        pattern(Px4, ANY, "[cal] calibration cancelled", Failed),
+        pattern(Px4, ANY, "[cal] calibration done", Succeeded),
+        pattern(Px4, ANY, "[cal] ", Progress),
+        pattern(ArduPilot, IMU, "calibration failed", Failed),
+        pattern(ArduPilot, IMU, "calibration cancelled", Failed),
+        pattern(ArduPilot, IMU, "calibration successful", Succeeded),
+        pattern(ArduPilot, GYRO, "did not converge", Failed),
+        pattern(ArduPilot, GYRO, "init gyro", Progress),
+        pattern(ArduPilot, ACCEL, "place vehicle", Progress),
+        pattern(ArduPilot, ACCEL, "calibrating accel", Progress),
+        pattern(ArduPilot, COMPASS, "bad orientation", Failed),
+        pattern(ArduPilot, COMPASS, "bad radius", Failed),
+        pattern(ArduPilot, COMPASS, "bad fitness", Failed),
+        pattern(ArduPilot, COMPASS, "mag(", Progress),
+    ]
+};
+
+/// What a STATUSTEXT means for a running calibration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalibrationSignal {
+    Progress,
+    Finished(CalibrationResult),
+}
+
+pub fn classify_calibration_text(
+    firmware: CalibrationFirmware,
+    kind: CalibrationKind,
+    text: &str,
+) -> Option<CalibrationSignal> {
+    let lowered = text.to_ascii_lowercase();
+    let pattern = CALIBRATION_PATTERNS.iter().find(|pattern| {
+        pattern.firmware == firmware
+            && pattern.kinds.contains(&kind)
+            && lowered.contains(pattern.needle)
+    })?;
+    Some(match pattern.outcome {
+        PatternOutcome::Progress => CalibrationSignal::Progress,
+        PatternOutcome::Succeeded => CalibrationSignal::Finished(CalibrationResult::Succeeded),
+        PatternOutcome::Failed => CalibrationSignal::Finished(CalibrationResult::Failed {
+            reason: text.trim().to_string(),
+        }),
+    })
+}
+
+/// Whether the ACK itself is the result. ArduPilot runs the gyro and the
+/// simple accel calibration before acknowledging and reports a failure as a
+/// failed ACK.
+pub fn completes_on_ack(firmware: CalibrationFirmware, kind: CalibrationKind) -> bool {
+    firmware == CalibrationFirmware::ArduPilot
+        && matches!(kind, CalibrationKind::Gyro | CalibrationKind::Accel)
+}
+
+/// Follows the STATUSTEXT history for the outcome of one calibration. The
+/// caller polls [`CalibrationWatch::observe`] every
+/// `CALIBRATION_POLL_INTERVAL`; the timeout is counted in polls so the
+/// watch needs no clock of its own.
+#[derive(Debug, Clone)]
+pub struct CalibrationWatch {
+    firmware: CalibrationFirmware,
+    kind: CalibrationKind,
+    /// Sequence of the newest entry already looked at.
+    mark: u64,
+    polls_left: u128,
+}
+
+impl CalibrationWatch {
+    /// Watch entries newer than `mark`, taken before the calibration command
+    /// was sent.
+    pub fn new(
+        firmware: CalibrationFirmware,
+        kind: CalibrationKind,
+        mark: u64,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            firmware,
+            kind,
+            mark,
+            polls_left: timeout.as_millis() / CALIBRATION_POLL_INTERVAL.as_millis(),
+        }
+    }
+
+    /// Look at the entries added to `history` since the last poll, passing
+    /// each calibration message to `on_progress`. Returns the result once the
+    /// calibration finished or the time is up.
+    pub fn observe(
+        &mut self,
+        history: &[StatusTextEntry],
+        mut on_progress: impl FnMut(CalibrationProgress),
+    ) -> Option<CalibrationResult> {
+        for entry in history.iter().filter(|entry| entry.sequence > self.mark) {
+            self.mark = entry.sequence;
+            let Some(signal) = classify_calibration_text(self.firmware, self.kind, &entry.text)
+            else {
+                continue;
+            };
+            on_progress(CalibrationProgress {
+                kind: self.kind,
+                text: entry.text.clone(),
+            });
+            if let CalibrationSignal::Finished(result) = signal {
+                return Some(result);
+            }
+        }
+        if self.polls_left == 0 {
+            return Some(CalibrationResult::Timeout);
+        }
+        self.polls_left -= 1;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(firmware: CalibrationFirmware, kind: CalibrationKind, text: &str) -> String {
+        match classify_calibration_text(firmware, kind, text) {
+            None => "ignored".into(),
+            Some(CalibrationSignal::Progress) => "progress".into(),
+            Some(CalibrationSignal::Finished(CalibrationResult::Succeeded)) => "succeeded".into(),
+            Some(CalibrationSignal::Finished(CalibrationResult::Failed { reason })) => {
+                format!("failed: {reason}")
+            }
+            Some(CalibrationSignal::Finished(CalibrationResult::Timeout)) => "timeout".into(),
+        }
+    }
+
+    /// Strings captured from PX4 v1.14 commander during gyro and accel
+    /// calibration.
+    #[test]
+    fn px4_calibration_protocol() {
+        use CalibrationFirmware::Px4;
+        use CalibrationKind::{Accel, Gyro};
+        assert_eq!(
+            classify(Px4, Gyro, "[cal] calibration started: 2 gyro"),
+            "progress"
+        );
+        assert_eq!(classify(Px4, Gyro, "[cal] progress <40>"), "progress");
+        assert_eq!(
+            classify(Px4, Gyro, "[cal] calibration done: gyro"),
+            "succeeded"
+        );
+        assert_eq!(
+            classify(Px4, Accel, "[cal] calibration failed: accel"),
+            "failed: [cal] calibration failed: accel"
+        );
+        assert_eq!(
+            classify(Px4, Accel, "[cal] calibration cancelled"),
+            "failed: [cal] calibration cancelled"
+        );
+        assert_eq!(classify(Px4, Accel, "Takeoff detected"), "ignored");
+    }
+
+    /// Strings captured from ArduCopter 4.5 simple accel, gyro and compass
+    /// calibrations.
+    #[test]
+    fn ardupilot_calibration_messages() {
+        use CalibrationFirmware::ArduPilot;
+        use CalibrationKind::{Accel, Compass, Gyro};
+        assert_eq!(
+            classify(ArduPilot, Accel, "Place vehicle level and press any key."),
+            "progress"
+        );
+        assert_eq!(
+            classify(ArduPilot, Accel, "Calibration successful"),
+            "succeeded"
+        );
+        assert_eq!(
+            classify(ArduPilot, Accel, "Calibration FAILED"),
+            "failed: Calibration FAILED"
+        );
+        assert_eq!(
+            classify(
+                ArduPilot,
+                Gyro,
+                "gyro[0] did not converge: diff=0.42 dps (expected < 0.20)"
+            ),
+            "failed: gyro[0] did not converge: diff=0.42 dps (expected < 0.20)"
+        );
+        assert_eq!(
+            classify(ArduPilot, Compass, "Mag(0) bad orientation: 4/0 6.3"),
+            "failed: Mag(0) bad orientation: 4/0 6.3"
+        );
+        // Accel messages do not end a compass calibration.
+        assert_eq!(
+            classify(ArduPilot, Compass, "Calibration successful"),
+            "ignored"
+        );
+        assert!(completes_on_ack(ArduPilot, Gyro));
+        assert!(completes_on_ack(ArduPilot, Accel));
+        assert!(!completes_on_ack(ArduPilot, Compass));
+        assert!(!completes_on_ack(CalibrationFirmware::Px4, Accel));
+        assert!(!completes_on_ack(CalibrationFirmware::Px4, Gyro));
+    }
+
+    fn entry(sequence: u64, text: &str) -> StatusTextEntry {
+        StatusTextEntry {
+            sequence,
+            text: text.to_string(),
+            severity: "info".to_string(),
+            timestamp_usec: None,
+            repeat_count: 1,
+            vehicle_time: None,
+            failsafe: false,
+        }
+    }
+
+    #[test]
+    fn watch_reports_progress_then_the_result_or_times_out() {
+        let mut history = vec![entry(3, "Calibration FAILED")];
+        let mut watch = CalibrationWatch::new(
+            CalibrationFirmware::ArduPilot,
+            CalibrationKind::Accel,
+            3,
+            Duration::from_secs(1),
+        );
+        let mut progress = Vec::new();
+        // The failure before the mark belongs to an earlier attempt.
+        assert_eq!(
+            watch.observe(&history, |update| progress.push(update.text)),
+            None
+        );
+
+        history.push(entry(4, "Place vehicle level and press any key."));
+        history.push(entry(5, "EKF3 IMU0 is using GPS"));
+        history.push(entry(6, "Calibration successful"));
+        assert_eq!(
+            watch.observe(&history, |update| progress.push(update.text)),
+            Some(CalibrationResult::Succeeded)
+        );
+        assert_eq!(
+            progress,
+            [
+                "Place vehicle level and press any key.",
+                "Calibration successful"
+            ]
+        );
+
+        let mut silent = CalibrationWatch::new(
+            CalibrationFirmware::Px4,
+            CalibrationKind::Gyro,
+            0,
+            CALIBRATION_RESULT_TIMEOUT,
+        );
+        let polls = std::iter::repeat_with(|| silent.observe(&[], |_| {}))
+            .position(|result| result.is_some())
+            .unwrap();
+        assert_eq!(
+            polls as u128,
+            CALIBRATION_RESULT_TIMEOUT.as_millis() / CALIBRATION_POLL_INTERVAL.as_millis()
+        );
+        assert_eq!(
+            silent.observe(&[], |_| {}),
+            Some(CalibrationResult::Timeout)
+        );
+    }
+
+    #[test]
+    fn watch_sees_a_completion_text_that_repeats_the_last_entry() {
+        use crate::ipc::{push_status_text_entry, status_text_mark};
+
+        let mut history = Vec::new();
+        push_status_text_entry(&mut history, entry(1, "[cal] calibration done: gyro"));
+        let mut watch = CalibrationWatch::new(
+            CalibrationFirmware::Px4,
+            CalibrationKind::Gyro,
+            status_text_mark(&history),
+            Duration::from_secs(1),
+        );
+
+        push_status_text_entry(&mut history, entry(2, "[cal] calibration done: gyro"));
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(
+            watch.observe(&history, |_| {}),
+            Some(CalibrationResult::Succeeded)
+        );
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::{BTreeSet, HashSet};
+
+use mavkit::{SensorHealthState, SensorHealthSummary};
+
+use crate::ipc::{
+    AppError, ChecklistCheck, ChecklistConfig, ChecklistItem, ChecklistItemState,
+    ChecklistItemStatus, ChecklistState, GpsFix, StatusTextEntry,
+};
+
+/// A PreArm status text younger than this fails the `prearm_pass` check.
+pub const PREARM_TEXT_WINDOW_USEC: u64 = 30_000_000;
+
+/// Live state the automatic items are evaluated against. `None` means the
+/// backend has not seen that state yet.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ChecklistInputs {
+    pub gps_fix: Option<GpsFix>,
+    pub battery_pct: Option<f64>,
+    pub home_set: Option<bool>,
+    pub mission_items: Option<usize>,
+    pub prearm_pass: Option<bool>,
+}
+
+fn is_prearm_failure(text: &str) -> bool {
+    let text = text.to_ascii_lowercase();
+    // ArduPilot prefixes its failures "PreArm:", PX4 "Preflight Fail:".
+    text.starts_with("prearm") || text.starts_with("preflight fail")
+}
+
+/// `prearm_pass`: no sensor the vehicle reports is unhealthy and no pre-arm
+/// failure text arrived within `PREARM_TEXT_WINDOW_USEC` of `now_usec`.
+pub fn prearm_pass(
+    sensors: &SensorHealthSummary,
+    status_texts: &[StatusTextEntry],
+    now_usec: u64,
+) -> bool {
+    let sensor_states = [
+        sensors.gyro,
+        sensors.accel,
+        sensors.mag,
+        sensors.baro,
+        sensors.gps,
+        sensors.airspeed,
+        sensors.rc_receiver,
+        sensors.battery,
+        sensors.terrain,
+        sensors.geofence,
+    ];
+    let sensors_healthy = !sensor_states.contains(&SensorHealthState::Unhealthy);
+    let recent_failure = status_texts.iter().any(|entry| {
+        entry
+            .timestamp_usec
+            .is_some_and(|timestamp| now_usec.saturating_sub(timestamp) < PREARM_TEXT_WINDOW_USEC)
+            && is_prearm_failure(&entry.text)
+    });
+    sensors_healthy && !recent_failure
+}
+
+pub fn validate_checklist(config: &ChecklistConfig) -> Result<(), String> {
+    let mut ids = HashSet::new();
+    for item in &config.items {
+        if item.id.trim().is_empty() {
+            return Err("checklist item id must not be empty".to_string());
+        }
+        if !ids.insert(item.id.as_str()) {
+            return Err(format!("duplicate checklist item id '{}'", item.id));
+        }
+        if item.check == Some(ChecklistCheck::BatteryAbovePct)
+            && !item
+                .threshold
+                .is_some_and(|threshold| (0.0..=100.0).contains(&threshold))
+        {
+            return Err(format!(
+                "checklist item '{}' needs a battery threshold between 0 and 100",
+                item.id
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn automatic_status(
+    check: ChecklistCheck,
+    threshold: Option<f64>,
+    inputs: &ChecklistInputs,
+) -> (ChecklistItemStatus, Option<String>) {
+    let pass = |ok: bool| {
+        if ok {
+            ChecklistItemStatus::Pass
+        } else {
+            ChecklistItemStatus::Fail
+        }
+    };
+    match check {
+        ChecklistCheck::Gps3dFix => match inputs.gps_fix {
+            Some(fix) => (pass(fix >= GpsFix::Fix3d), Some(format!("{fix:?}"))),
+            None => (ChecklistItemStatus::Unknown, None),
+        },
+        ChecklistCheck::BatteryAbovePct => match (inputs.battery_pct, threshold) {
+            (Some(pct), Some(threshold)) => (pass(pct > threshold), Some(format!("{pct:.0}%"))),
+            _ => (ChecklistItemStatus::Unknown, None),
+        },
+        ChecklistCheck::HomeSet => match inputs.home_set {
+            Some(home_set) => (pass(home_set), None),
+            None => (ChecklistItemStatus::Unknown, None),
+        },
+        ChecklistCheck::MissionUploaded => match inputs.mission_items {
+            Some(items) => (pass(items > 0), Some(format!("{items} items"))),
+            None => (ChecklistItemStatus::Unknown, None),
+        },
+        ChecklistCheck::PrearmPass => match inputs.prearm_pass {
+            Some(ok) => (pass(ok), None),
+            None => (ChecklistItemStatus::Unknown, None),
+        },
+    }
+}
+
+fn item_state(
+    item: &ChecklistItem,
+    ticked: &BTreeSet<String>,
+    inputs: &ChecklistInputs,
+) -> ChecklistItemState {
+    let (status, detail) = match item.check {
+        Some(check) => automatic_status(check, item.threshold, inputs),
+        None if ticked.contains(&item.id) => (ChecklistItemStatus::Checked, None),
+        None => (ChecklistItemStatus::Unchecked, None),
+    };
+    ChecklistItemState {
+        id: item.id.clone(),
+        label: item.label.clone(),
+        check: item.check,
+        required: item.required,
+        status,
+        detail,
+    }
+}
+
+/// Labels of the required items that are neither ticked nor passing.
+pub fn checklist_blockers(state: &ChecklistState) -> Vec<String> {
+    state
+        .items
+        .iter()
+        .filter(|item| {
+            item.required
+                && !matches!(
+                    item.status,
+                    ChecklistItemStatus::Checked | ChecklistItemStatus::Pass
+                )
+        })
+        .map(|item| item.label.clone())
+        .collect()
+}
+
+/// `Ok` unless enforcement is on and a required item is outstanding.
+pub fn check_checklist(state: &ChecklistState) -> Result<(), AppError> {
+    if !state.enforce_on_arm {
+        return Ok(());
+    }
+    let blockers = checklist_blockers(state);
+    if blockers.is_empty() {
+        return Ok(());
+    }
+    Err(AppError::Validation {
+        message: format!("checklist incomplete: {}", blockers.join(", ")),
+        issues: blockers,
+    })
+}
+
+/// The defined checklist plus the manual items the crew has ticked.
+#[derive(Debug, Clone, Default)]
+pub struct ChecklistTracker {
+    config: ChecklistConfig,
+    ticked: BTreeSet<String>,
+    last_emitted: Option<ChecklistState>,
+}
+
+impl ChecklistTracker {
+    pub fn config(&self) -> &ChecklistConfig {
+        &self.config
+    }
+
+    /// Replace the definition. Ticks on items that survive keep their state.
+    pub fn set_config(&mut self, config: ChecklistConfig) -> Result<(), String> {
+        validate_checklist(&config)?;
+        let manual: HashSet<&str> = config
+            .items
+            .iter()
+            .filter(|item| item.check.is_none())
+            .map(|item| item.id.as_str())
+            .collect();
+        self.ticked.retain(|id| manual.contains(id.as_str()));
+        self.config = config;
+        Ok(())
+    }
+
+    pub fn tick(&mut self, item_id: &str, checked: bool) -> Result<(), String> {
+        let item = self
+            .config
+            .items
+            .iter()
+            .find(|item| item.id == item_id)
+            .ok_or_else(|| format!("unknown checklist item '{item_id}'"))?;
+        if item.check.is_some() {
+            return Err(format!(
+                "checklist item '{item_id}' is verified automatically"
+            ));
+        }
+        if checked {
+            self.ticked.insert(item_id.to_string());
+        } else {
+            self.ticked.remove(item_id);
+        }
+        Ok(())
+    }
+
+    /// Untick every manual item.
+    pub fn reset(&mut self) {
+        self.ticked.clear();
+    }
+
+    pub fn state(&self, inputs: &ChecklistInputs) -> ChecklistState {
+        let items: Vec<ChecklistItemState> = self
+            .config
+            .items
+            .iter()
+            .map(|item| item_state(item, &self.ticked, inputs))
+            .collect();
+        let mut state = ChecklistState {
+            items,
+            enforce_on_arm: self.config.enforce_on_arm,
+            complete: false,
+        };
+        state.complete = checklist_blockers(&state).is_empty();
+        state
+    }
+
+    /// The current state when it differs from the last one returned here.
+    pub fn observe(&mut self, inputs: &ChecklistInputs) -> Option<ChecklistState> {
+        let state = self.state(inputs);
+        if self.last_emitted.as_ref() == Some(&state) {
+            return None;
+        }
+        self.last_emitted = Some(state.clone());
+        Some(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, check: Option<ChecklistCheck>, threshold: Option<f64>) -> ChecklistItem {
+        ChecklistItem {
+            id: id.to_string(),
+            label: id.to_string(),
+            check,
+            threshold,
+            required: true,
+        }
+    }
+
+    fn tracker() -> ChecklistTracker {
+        let mut tracker = ChecklistTracker::default();
+        tracker
+            .set_config(ChecklistConfig {
+                items: vec![
+                    item("props", None, None),
+                    item("gps", Some(ChecklistCheck::Gps3dFix), None),
+                    item("battery", Some(ChecklistCheck::BatteryAbovePct), Some(80.0)),
+                    item("mission", Some(ChecklistCheck::MissionUploaded), None),
+                ],
+                enforce_on_arm: true,
+            })
+            .expect("valid checklist");
+        tracker
+    }
+
+    fn ready() -> ChecklistInputs {
+        ChecklistInputs {
+            gps_fix: Some(GpsFix::RtkFloat),
+            battery_pct: Some(95.0),
+            home_set: Some(true),
+            mission_items: Some(12),
+            prearm_pass: Some(true),
+        }
+    }
+
+    #[test]
+    fn enforcement_blocks_until_every_required_item_passes() {
+        let mut tracker = tracker();
+        let state = tracker.state(&ChecklistInputs {
+            battery_pct: Some(60.0),
+            ..ready()
+        });
+        assert!(!state.complete);
+        let Err(AppError::Validation { issues, .. }) = check_checklist(&state) else {
+            panic!("expected checklist refusal");
+        };
+        assert_eq!(issues, ["props", "battery"]);
+
+        tracker.tick("props", true).expect("manual item");
+        let state = tracker.state(&ready());
+        assert!(state.complete);
+        assert_eq!(check_checklist(&state), Ok(()));
+
+        tracker.reset();
+        assert!(!tracker.state(&ready()).complete);
+    }
+
+    #[test]
+    fn unknown_inputs_are_not_treated_as_passing() {
+        let state = tracker().state(&ChecklistInputs::default());
+        let statuses: Vec<ChecklistItemStatus> =
+            state.items.iter().map(|item| item.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                ChecklistItemStatus::Unchecked,
+                ChecklistItemStatus::Unknown,
+                ChecklistItemStatus::Unknown,
+                ChecklistItemStatus::Unknown,
+            ]
+        );
+    }
+
+    #[test]
+    fn observe_reports_only_changes() {
+        let mut tracker = tracker();
+        assert!(tracker.observe(&ready()).is_some());
+        assert!(tracker.observe(&ready()).is_none());
+        let degraded = ChecklistInputs {
+            gps_fix: Some(GpsFix::Fix2d),
+            ..ready()
+        };
+        let state = tracker.observe(&degraded).expect("gps change");
+        assert_eq!(state.items[1].status, ChecklistItemStatus::Fail);
+    }
+
+    #[test]
+    fn invalid_definitions_and_ticks_are_rejected() {
+        let mut tracker = tracker();
+        assert!(tracker.tick("gps", true).is_err());
+        assert!(tracker.tick("missing", true).is_err());
+        assert!(
+            tracker
+                .set_config(ChecklistConfig {
+                    items: vec![item("battery", Some(ChecklistCheck::BatteryAbovePct), None)],
+                    enforce_on_arm: false,
+                })
+                .is_err()
+        );
+        assert!(
+            tracker
+                .set_config(ChecklistConfig {
+                    items: vec![item("a", None, None), item("a", None, None)],
+                    enforce_on_arm: false,
+                })
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,500 @@
+use crate::dialect::{COMMAND_LONG_DATA, MavCmd, MavMessage};
+use std::time::Duration;
+
+use web_time::Instant;
+
+const MAV_RESULT_ACCEPTED: u8 = 0;
+const MAV_RESULT_TEMPORARILY_REJECTED: u8 = 1;
+const MAV_RESULT_DENIED: u8 = 2;
+const MAV_RESULT_UNSUPPORTED: u8 = 3;
+const MAV_RESULT_FAILED: u8 = 4;
+const MAV_RESULT_IN_PROGRESS: u8 = 5;
+const MAV_RESULT_CANCELLED: u8 = 6;
+
+/// MAV_CMD_COMPONENT_ARM_DISARM param2 that makes ArduPilot and PX4 skip
+/// their arming checks.
+const FORCE_ARM_MAGIC: f32 = 21196.0;
+
+/// How long after giving up on a command its ACK still counts as late rather
+/// than as an answer to something sent since.
+pub const LATE_ACK_WINDOW: Duration = Duration::from_secs(30);
+
+/// A COMMAND_LONG addressed to one component of a system.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComponentCommand {
+    pub system_id: u8,
+    pub component_id: u8,
+    pub command: MavCmd,
+    pub params: [f32; 7],
+}
+
+/// The fields of a received COMMAND_ACK needed to correlate it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObservedAck {
+    pub source_system: u8,
+    pub source_component: u8,
+    pub command: u16,
+    pub result: u8,
+    /// Percentage reported alongside MAV_RESULT_IN_PROGRESS, 255 when unknown.
+    pub progress: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AckOutcome {
+    Accepted,
+    InProgress,
+    Rejected(String),
+}
+
+/// How a COMMAND_LONG is resent when its ACK does not arrive: up to
+/// `attempts` sends `spacing` apart, each with the next `confirmation`
+/// value. Once the target answers MAV_RESULT_IN_PROGRESS nothing is resent
+/// and the final ACK gets `in_progress_timeout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandRetryPolicy {
+    pub attempts: u8,
+    pub spacing: Duration,
+    pub in_progress_timeout: Duration,
+}
+
+impl Default for CommandRetryPolicy {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            spacing: Duration::from_secs(1),
+            in_progress_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl ComponentCommand {
+    /// MAV_CMD_COMPONENT_ARM_DISARM arming the target; `force` skips its
+    /// arming checks.
+    pub fn arm(system_id: u8, component_id: u8, force: bool) -> Self {
+        let checks = if force { FORCE_ARM_MAGIC } else { 0.0 };
+        Self {
+            system_id,
+            component_id,
+            command: MavCmd::MAV_CMD_COMPONENT_ARM_DISARM,
+            params: [1.0, checks, 0.0, 0.0, 0.0, 0.0, 0.0],
+        }
+    }
+
+    /// MAV_CMD_NAV_TAKEOFF to `altitude_m` above home from where the vehicle
+    /// stands.
+    pub fn takeoff(system_id: u8, component_id: u8, altitude_m: f32) -> Self {
+        Self {
+            system_id,
+            component_id,
+            command: MavCmd::MAV_CMD_NAV_TAKEOFF,
+            params: [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, altitude_m],
+        }
+    }
+
+    pub fn message(&self) -> MavMessage {
+        self.message_with_confirmation(0)
+    }
+
+    /// The COMMAND_LONG for the `confirmation`-th resend (0 for the first
+    /// transmission), so the target can tell a retry from a new command.
+    pub fn message_with_confirmation(&self, confirmation: u8) -> MavMessage {
+        let [param1, param2, param3, param4, param5, param6, param7] = self.params;
+        MavMessage::COMMAND_LONG(COMMAND_LONG_DATA {
+            param1,
+            param2,
+            param3,
+            param4,
+            param5,
+            param6,
+            param7,
+            command: self.command,
+            target_system: self.system_id,
+            target_component: self.component_id,
+            confirmation,
+        })
+    }
+
+    /// Two components can ACK the same command ID (e.g. the autopilot forwarding
+    /// MAV_CMD_DO_DIGICAM_CONTROL while the camera answers it directly), so an
+    /// ACK only counts when it comes from the component the command targeted.
+    pub fn matches(&self, ack: &ObservedAck) -> bool {
+        ack.source_system == self.system_id
+            && ack.source_component == self.component_id
+            && ack.command == self.command as u16
+    }
+}
+
+pub fn ack_outcome(command: MavCmd, result: u8) -> AckOutcome {
+    let reason = match result {
+        MAV_RESULT_ACCEPTED => return AckOutcome::Accepted,
+        MAV_RESULT_IN_PROGRESS => return AckOutcome::InProgress,
+        MAV_RESULT_TEMPORARILY_REJECTED => "temporarily rejected",
+        MAV_RESULT_DENIED => "denied",
+        MAV_RESULT_UNSUPPORTED => "unsupported",
+        MAV_RESULT_CANCELLED => "cancelled",
+        _ => "failed",
+    };
+    AckOutcome::Rejected(format!("{command:?} {reason}"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExchangeEvent {
+    /// Not an ACK for this command, or a duplicate of one already handled.
+    Ignored,
+    InProgress {
+        progress: Option<u8>,
+    },
+    Finished(Result<(), String>),
+}
+
+/// One COMMAND_LONG and its ACKs under a [`CommandRetryPolicy`]. The caller
+/// sends whatever [`CommandExchange::next_send`] returns, then feeds ACKs in
+/// until one finishes the exchange or [`CommandExchange::wait_timeout`]
+/// passes.
+#[derive(Debug, Clone)]
+pub struct CommandExchange {
+    command: ComponentCommand,
+    policy: CommandRetryPolicy,
+    sent: u8,
+    in_progress: Option<u8>,
+    finished: bool,
+}
+
+impl CommandExchange {
+    pub fn new(command: ComponentCommand, policy: CommandRetryPolicy) -> Self {
+        Self {
+            command,
+            policy,
+            sent: 0,
+            in_progress: None,
+            finished: false,
+        }
+    }
+
+    /// The next transmission, or `None` once the attempts are used up, the
+    /// target is already working on the command, or it has answered.
+    pub fn next_send(&mut self) -> Option<MavMessage> {
+        if self.finished || self.in_progress.is_some() || self.sent >= self.policy.attempts.max(1) {
+            return None;
+        }
+        let message = self.command.message_with_confirmation(self.sent);
+        self.sent += 1;
+        Some(message)
+    }
+
+    pub fn sent(&self) -> u8 {
+        self.sent
+    }
+
+    pub fn in_progress(&self) -> bool {
+        self.in_progress.is_some()
+    }
+
+    /// How long to wait for the next ACK before resending (or giving up).
+    pub fn wait_timeout(&self) -> Duration {
+        if self.in_progress() {
+            self.policy.in_progress_timeout
+        } else {
+            self.policy.spacing
+        }
+    }
+
+    /// Apply an ACK. Retries can make the target answer the same command
+    /// more than once, so everything after the final ACK is ignored, as are
+    /// repeated IN_PROGRESS ACKs that report no new progress.
+    pub fn on_ack(&mut self, ack: &ObservedAck) -> ExchangeEvent {
+        if self.finished || !self.command.matches(ack) {
+            return ExchangeEvent::Ignored;
+        }
+        match ack_outcome(self.command.command, ack.result) {
+            AckOutcome::InProgress => {
+                if self.in_progress == Some(ack.progress) {
+                    return ExchangeEvent::Ignored;
+                }
+                self.in_progress = Some(ack.progress);
+                ExchangeEvent::InProgress {
+                    progress: (ack.progress <= 100).then_some(ack.progress),
+                }
+            }
+            AckOutcome::Accepted => {
+                self.finished = true;
+                ExchangeEvent::Finished(Ok(()))
+            }
+            AckOutcome::Rejected(reason) => {
+                self.finished = true;
+                ExchangeEvent::Finished(Err(reason))
+            }
+        }
+    }
+}
+
+/// Commands whose exchange gave up without an ACK. A slow link can still
+/// deliver that ACK, and the next exchange for the same command would take
+/// it as its own answer; claiming it here counts it as late instead.
+#[derive(Debug, Default)]
+pub struct LateAckLog {
+    timed_out: Vec<(ComponentCommand, Instant)>,
+    late: u64,
+}
+
+impl LateAckLog {
+    pub const fn new() -> Self {
+        Self {
+            timed_out: Vec::new(),
+            late: 0,
+        }
+    }
+
+    pub fn timed_out(&mut self, command: ComponentCommand, at: Instant) {
+        self.timed_out.push((command, at));
+    }
+
+    /// Stop claiming ACKs for `command` once a new exchange sends it again.
+    /// From then on a late answer to the old send cannot be told apart from
+    /// the new one's answer, and claiming it would leave the new exchange
+    /// waiting out its timeout for an ACK that already came.
+    pub fn exchange_started(&mut self, command: &ComponentCommand) {
+        self.timed_out.retain(|(timed_out, _)| {
+            (timed_out.system_id, timed_out.component_id, timed_out.command)
+                != (command.system_id, command.component_id, command.command)
+        });
+    }
+
+    /// Whether `ack` answers a command that already timed out; each timed-out
+    /// command claims at most one ACK.
+    pub fn claim(&mut self, ack: &ObservedAck, now: Instant) -> bool {
+        self.timed_out
+            .retain(|(_, at)| now.saturating_duration_since(*at) <= LATE_ACK_WINDOW);
+        let Some(index) = self
+            .timed_out
+            .iter()
+            .position(|(command, _)| command.matches(ack))
+        else {
+            return false;
+        };
+        self.timed_out.remove(index);
+        self.late += 1;
+        true
+    }
+
+    /// ACKs claimed as late so far.
+    pub fn late_acks(&self) -> u64 {
+        self.late
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interleaved_acks_from_two_components_resolve_to_the_targeted_one() {
+        let camera_trigger = ComponentCommand {
+            system_id: 1,
+            component_id: 100,
+            command: MavCmd::MAV_CMD_DO_DIGICAM_CONTROL,
+            params: [0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+        };
+        let command_id = MavCmd::MAV_CMD_DO_DIGICAM_CONTROL as u16;
+        let acks = [
+            ObservedAck {
+                source_system: 1,
+                source_component: 1,
+                command: command_id,
+                result: MAV_RESULT_DENIED,
+                progress: 0,
+            },
+            ObservedAck {
+                source_system: 1,
+                source_component: 100,
+                command: MavCmd::MAV_CMD_DO_MOUNT_CONTROL as u16,
+                result: MAV_RESULT_ACCEPTED,
+                progress: 0,
+            },
+            ObservedAck {
+                source_system: 1,
+                source_component: 100,
+                command: command_id,
+                result: MAV_RESULT_IN_PROGRESS,
+                progress: 0,
+            },
+            ObservedAck {
+                source_system: 1,
+                source_component: 1,
+                command: command_id,
+                result: MAV_RESULT_ACCEPTED,
+                progress: 0,
+            },
+            ObservedAck {
+                source_system: 1,
+                source_component: 100,
+                command: command_id,
+                result: MAV_RESULT_ACCEPTED,
+                progress: 0,
+            },
+        ];
+
+        let outcomes: Vec<AckOutcome> = acks
+            .iter()
+            .filter(|ack| camera_trigger.matches(ack))
+            .map(|ack| ack_outcome(camera_trigger.command, ack.result))
+            .collect();
+
+        assert_eq!(outcomes, [AckOutcome::InProgress, AckOutcome::Accepted]);
+    }
+
+    #[test]
+    fn rejected_ack_names_the_command() {
+        assert_eq!(
+            ack_outcome(MavCmd::MAV_CMD_DO_MOUNT_CONTROL, MAV_RESULT_UNSUPPORTED),
+            AckOutcome::Rejected("MAV_CMD_DO_MOUNT_CONTROL unsupported".into())
+        );
+    }
+
+    fn takeoff_command() -> ComponentCommand {
+        ComponentCommand::takeoff(1, 1, 10.0)
+    }
+
+    fn ack(result: u8, progress: u8) -> ObservedAck {
+        ObservedAck {
+            source_system: 1,
+            source_component: 1,
+            command: MavCmd::MAV_CMD_NAV_TAKEOFF as u16,
+            result,
+            progress,
+        }
+    }
+
+    fn confirmation(message: Option<MavMessage>) -> Option<u8> {
+        match message? {
+            MavMessage::COMMAND_LONG(data) => Some(data.confirmation),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn lost_first_ack_resends_with_next_confirmation() {
+        let mut exchange = CommandExchange::new(takeoff_command(), CommandRetryPolicy::default());
+
+        assert_eq!(confirmation(exchange.next_send()), Some(0));
+        // No ACK within `spacing`: resend.
+        assert_eq!(confirmation(exchange.next_send()), Some(1));
+        assert_eq!(
+            exchange.on_ack(&ack(MAV_RESULT_ACCEPTED, 0)),
+            ExchangeEvent::Finished(Ok(()))
+        );
+        // The ACK of the first send arriving late is a duplicate.
+        assert_eq!(
+            exchange.on_ack(&ack(MAV_RESULT_ACCEPTED, 0)),
+            ExchangeEvent::Ignored
+        );
+        assert!(exchange.next_send().is_none());
+        assert_eq!(exchange.sent(), 2);
+    }
+
+    #[test]
+    fn attempts_run_out_without_an_ack() {
+        let policy = CommandRetryPolicy {
+            attempts: 2,
+            ..CommandRetryPolicy::default()
+        };
+        let mut exchange = CommandExchange::new(takeoff_command(), policy);
+        assert!(exchange.next_send().is_some());
+        assert!(exchange.next_send().is_some());
+        assert!(exchange.next_send().is_none());
+    }
+
+    #[test]
+    fn in_progress_then_accepted_waits_without_resending() {
+        let policy = CommandRetryPolicy::default();
+        let mut exchange = CommandExchange::new(takeoff_command(), policy);
+        exchange.next_send();
+
+        assert_eq!(
+            exchange.on_ack(&ack(MAV_RESULT_IN_PROGRESS, 40)),
+            ExchangeEvent::InProgress { progress: Some(40) }
+        );
+        assert_eq!(
+            exchange.on_ack(&ack(MAV_RESULT_IN_PROGRESS, 40)),
+            ExchangeEvent::Ignored
+        );
+        assert_eq!(exchange.wait_timeout(), policy.in_progress_timeout);
+        assert!(exchange.next_send().is_none());
+        assert_eq!(
+            exchange.on_ack(&ack(MAV_RESULT_IN_PROGRESS, u8::MAX)),
+            ExchangeEvent::InProgress { progress: None }
+        );
+        assert_eq!(
+            exchange.on_ack(&ack(MAV_RESULT_ACCEPTED, 100)),
+            ExchangeEvent::Finished(Ok(()))
+        );
+    }
+
+    #[test]
+    fn in_progress_then_failed_reports_the_failure() {
+        let mut exchange = CommandExchange::new(takeoff_command(), CommandRetryPolicy::default());
+        exchange.next_send();
+
+        assert!(matches!(
+            exchange.on_ack(&ack(MAV_RESULT_IN_PROGRESS, 10)),
+            ExchangeEvent::InProgress { .. }
+        ));
+        assert_eq!(
+            exchange.on_ack(&ack(MAV_RESULT_FAILED, 0)),
+            ExchangeEvent::Finished(Err("MAV_CMD_NAV_TAKEOFF failed".into()))
+        );
+        assert_eq!(
+            exchange.on_ack(&ack(MAV_RESULT_ACCEPTED, 0)),
+            ExchangeEvent::Ignored
+        );
+    }
+
+    #[test]
+    fn force_arm_carries_the_magic_value() {
+        assert_eq!(ComponentCommand::arm(1, 1, false).params[..2], [1.0, 0.0]);
+        assert_eq!(
+            ComponentCommand::arm(1, 1, true).params[..2],
+            [1.0, FORCE_ARM_MAGIC]
+        );
+    }
+
+    #[test]
+    fn a_late_ack_is_claimed_once_and_counted() {
+        let mut log = LateAckLog::new();
+        let start = Instant::now();
+        log.timed_out(takeoff_command(), start);
+
+        let late = ack(MAV_RESULT_ACCEPTED, 0);
+        let mut other = late;
+        other.command = MavCmd::MAV_CMD_COMPONENT_ARM_DISARM as u16;
+        assert!(!log.claim(&other, start));
+        assert!(log.claim(&late, start + Duration::from_millis(1500)));
+        // The retry's own ACK is left for the new exchange.
+        assert!(!log.claim(&late, start + Duration::from_millis(1600)));
+        assert_eq!(log.late_acks(), 1);
+
+        log.timed_out(takeoff_command(), start);
+        assert!(!log.claim(&late, start + LATE_ACK_WINDOW + Duration::from_secs(1)));
+        assert_eq!(log.late_acks(), 1);
+    }
+
+    #[test]
+    fn a_new_exchange_for_the_same_command_takes_its_acks() {
+        let mut log = LateAckLog::new();
+        let start = Instant::now();
+        let mut arm = takeoff_command();
+        arm.command = MavCmd::MAV_CMD_COMPONENT_ARM_DISARM;
+        log.timed_out(takeoff_command(), start);
+        log.timed_out(arm, start);
+
+        let mut resend = takeoff_command();
+        resend.params[6] += 5.0;
+        log.exchange_started(&resend);
+
+        assert!(!log.claim(&ack(MAV_RESULT_ACCEPTED, 0), start));
+        let mut arm_ack = ack(MAV_RESULT_ACCEPTED, 0);
+        arm_ack.command = MavCmd::MAV_CMD_COMPONENT_ARM_DISARM as u16;
+        assert!(log.claim(&arm_ack, start));
+        assert_eq!(log.late_acks(), 1);
+    }
+}
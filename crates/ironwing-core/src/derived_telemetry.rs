@@ -0,0 +1,348 @@
+use std::time::Duration;
+
+use mavkit::{HomePosition, VehicleType};
+use web_time::Instant;
+
+use crate::flight_modes::is_ground_vehicle;
+use crate::ipc::telemetry::{RcLinkQuality, TelemetryDerived, TelemetryState, WindSource};
+use crate::log_engine::haversine_m;
+use crate::rc_link_quality::RcLinkTracker;
+use crate::rover::WheelOdometer;
+
+/// MAVLink message id of WIND_COV.
+pub const WIND_COV_MESSAGE_ID: u32 = 231;
+
+/// A WIND_COV report older than this falls back to the wind triangle.
+const REPORTED_WIND_MAX_AGE: Duration = Duration::from_secs(5);
+/// Below this groundspeed the track from successive fixes is mostly noise.
+const MIN_GROUNDSPEED_FOR_TRACK_MPS: f64 = 1.0;
+/// Shortest position change used to update the ground track.
+const MIN_TRACK_SEGMENT_M: f64 = 0.5;
+const MIN_DESCENT_RATE_MPS: f64 = 0.1;
+/// A downward rangefinder reading older than this no longer gives a height.
+const RANGEFINDER_MAX_AGE: Duration = Duration::from_secs(1);
+
+fn normalize_deg(deg: f64) -> f64 {
+    deg.rem_euclid(360.0)
+}
+
+/// Wind speed and the direction it blows from, given the wind velocity's
+/// north and east components (m/s).
+pub fn wind_from_components(north_mps: f64, east_mps: f64) -> (f64, f64) {
+    let speed = north_mps.hypot(east_mps);
+    let toward = east_mps.atan2(north_mps).to_degrees();
+    (speed, normalize_deg(toward + 180.0))
+}
+
+/// Solve the wind triangle: wind is ground velocity (groundspeed along the
+/// track) minus air velocity (airspeed along the heading).
+pub fn wind_from_triangle(
+    groundspeed_mps: f64,
+    track_deg: f64,
+    airspeed_mps: f64,
+    heading_deg: f64,
+) -> (f64, f64) {
+    let (track_sin, track_cos) = track_deg.to_radians().sin_cos();
+    let (heading_sin, heading_cos) = heading_deg.to_radians().sin_cos();
+    wind_from_components(
+        groundspeed_mps * track_cos - airspeed_mps * heading_cos,
+        groundspeed_mps * track_sin - airspeed_mps * heading_sin,
+    )
+}
+
+/// Initial great-circle bearing from the first point to the second.
+pub fn bearing_deg(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let dlon = (lon2 - lon1).to_radians();
+    let y = dlon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+    normalize_deg(y.atan2(x).to_degrees())
+}
+
+pub fn glide_ratio(groundspeed_mps: f64, climb_rate_mps: f64) -> Option<f64> {
+    (climb_rate_mps < -MIN_DESCENT_RATE_MPS).then(|| groundspeed_mps / -climb_rate_mps)
+}
+
+pub fn energy_wh_per_km(voltage_v: f64, current_a: f64, groundspeed_mps: f64) -> Option<f64> {
+    if groundspeed_mps < MIN_GROUNDSPEED_FOR_TRACK_MPS {
+        return None;
+    }
+    let power_w = voltage_v * current_a;
+    Some(power_w / (groundspeed_mps * 3.6))
+}
+
+fn position(state: &TelemetryState) -> Option<(f64, f64)> {
+    let lat = state.navigation.latitude_deg?;
+    let lon = state.navigation.longitude_deg?;
+    (lat.abs() > 1e-6 || lon.abs() > 1e-6).then_some((lat, lon))
+}
+
+/// Carries what the derived values need across telemetry ticks: the last
+/// position for the ground track, the latest WIND_COV report, the latest
+/// downward rangefinder height, the RC link reports and the wheel odometer.
+#[derive(Debug, Clone, Default)]
+pub struct DerivedTelemetryEstimator {
+    last_position: Option<(f64, f64)>,
+    track_deg: Option<f64>,
+    reported_wind: Option<(f64, f64, Instant)>,
+    rangefinder_height: Option<(f64, Instant)>,
+    rc_link: RcLinkTracker,
+    odometer: WheelOdometer,
+}
+
+impl DerivedTelemetryEstimator {
+    pub fn record_reported_wind(&mut self, north_mps: f64, east_mps: f64, now: Instant) {
+        self.reported_wind = Some((north_mps, east_mps, now));
+    }
+
+    /// A valid reading from a downward-facing rangefinder.
+    pub fn record_rangefinder_height(&mut self, height_m: f64, now: Instant) {
+        self.rangefinder_height = Some((height_m, now));
+    }
+
+    pub fn record_rc_link(&mut self, quality: RcLinkQuality, now: Instant) {
+        self.rc_link.record(quality, now);
+    }
+
+    pub fn rc_link(&self, now: Instant) -> Option<RcLinkQuality> {
+        self.rc_link.current(now)
+    }
+
+    pub fn record_wheel_distance(&mut self, time_usec: u64, distances: &[f64]) {
+        self.odometer.record(time_usec, distances);
+    }
+
+    fn track_to(&self, state: &TelemetryState) -> Option<f64> {
+        let (lat, lon) = position(state)?;
+        match self.last_position {
+            Some((prev_lat, prev_lon))
+                if haversine_m(prev_lat, prev_lon, lat, lon) >= MIN_TRACK_SEGMENT_M =>
+            {
+                Some(bearing_deg(prev_lat, prev_lon, lat, lon))
+            }
+            _ => self.track_deg,
+        }
+    }
+
+    fn wind(&self, state: &TelemetryState, now: Instant) -> Option<(f64, f64, WindSource)> {
+        if let Some((north, east, received_at)) = self.reported_wind
+            && now.saturating_duration_since(received_at) <= REPORTED_WIND_MAX_AGE
+        {
+            let (speed, direction) = wind_from_components(north, east);
+            return Some((speed, direction, WindSource::Reported));
+        }
+        let airspeed = state
+            .flight
+            .airspeed_mps
+            .filter(|airspeed| *airspeed > 0.0)?;
+        let groundspeed = state
+            .flight
+            .speed_mps
+            .filter(|speed| *speed >= MIN_GROUNDSPEED_FOR_TRACK_MPS)?;
+        let heading = state.navigation.heading_deg?;
+        let track = self.track_to(state)?;
+        let (speed, direction) = wind_from_triangle(groundspeed, track, airspeed, heading);
+        Some((speed, direction, WindSource::Estimated))
+    }
+
+    /// Rovers and boats get no glide ratio or rangefinder height: their
+    /// height above ground says nothing about the vehicle.
+    pub fn derive(
+        &self,
+        state: &TelemetryState,
+        home: Option<&HomePosition>,
+        vehicle_type: VehicleType,
+        now: Instant,
+    ) -> TelemetryDerived {
+        let airborne = !is_ground_vehicle(vehicle_type);
+        let wind = self.wind(state, now);
+        let home_leg = home.zip(position(state)).map(|(home, (lat, lon))| {
+            (
+                haversine_m(home.latitude_deg, home.longitude_deg, lat, lon),
+                bearing_deg(lat, lon, home.latitude_deg, home.longitude_deg),
+            )
+        });
+        let groundspeed = state.flight.speed_mps;
+        TelemetryDerived {
+            wind_speed_mps: wind.map(|(speed, _, _)| speed),
+            wind_direction_deg: wind.map(|(_, direction, _)| direction),
+            wind_source: wind.map(|(_, _, source)| source),
+            home_distance_m: home_leg.map(|(distance, _)| distance),
+            home_bearing_deg: home_leg.map(|(_, bearing)| bearing),
+            glide_ratio: groundspeed
+                .zip(state.flight.climb_rate_mps)
+                .filter(|_| airborne)
+                .and_then(|(speed, climb)| glide_ratio(speed, climb)),
+            energy_wh_per_km: state
+                .power
+                .battery_voltage_v
+                .zip(state.power.battery_current_a)
+                .zip(groundspeed)
+                .and_then(|((voltage, current), speed)| energy_wh_per_km(voltage, current, speed)),
+            rangefinder_height_m: self
+                .rangefinder_height
+                .filter(|(_, received_at)| {
+                    airborne && now.saturating_duration_since(*received_at) <= RANGEFINDER_MAX_AGE
+                })
+                .map(|(height_m, _)| height_m),
+            rc_link: self.rc_link(now),
+            odometer_m: self.odometer.distance_m(),
+        }
+    }
+
+    /// Advance the ground track with a telemetry sample that has been emitted.
+    pub fn observe(&mut self, state: &TelemetryState) {
+        let Some(current) = position(state) else {
+            return;
+        };
+        self.track_deg = self.track_to(state);
+        let moved = self.last_position.is_none_or(|(lat, lon)| {
+            haversine_m(lat, lon, current.0, current.1) >= MIN_TRACK_SEGMENT_M
+        });
+        if moved {
+            self.last_position = Some(current);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::telemetry::{TelemetryFlight, TelemetryNavigation};
+
+    fn assert_wind(actual: (f64, f64), speed: f64, from_deg: f64) {
+        assert!((actual.0 - speed).abs() < 1e-9, "speed {actual:?}");
+        let direction_error = (actual.1 - from_deg + 540.0).rem_euclid(360.0) - 180.0;
+        assert!(direction_error.abs() < 1e-9, "direction {actual:?}");
+    }
+
+    #[test]
+    fn wind_triangle_matches_known_vectors() {
+        // Headwind: flying north at 20 m/s airspeed, making 15 m/s over ground.
+        assert_wind(wind_from_triangle(15.0, 0.0, 20.0, 0.0), 5.0, 0.0);
+        // Tailwind from the west while flying east.
+        assert_wind(wind_from_triangle(25.0, 90.0, 20.0, 90.0), 5.0, 270.0);
+        // Pure crosswind: heading north at 10 m/s, drifting 5 m/s east.
+        let groundspeed = 10.0_f64.hypot(5.0);
+        let track = 5.0_f64.atan2(10.0).to_degrees();
+        assert_wind(
+            wind_from_triangle(groundspeed, track, 10.0, 0.0),
+            5.0,
+            270.0,
+        );
+        // No wind: air and ground vectors coincide.
+        assert!(wind_from_triangle(18.0, 123.0, 18.0, 123.0).0 < 1e-9);
+        // WIND_COV components: air moving south-west means wind from north-east.
+        assert_wind(wind_from_components(-3.0, -3.0), 18.0_f64.sqrt(), 45.0);
+    }
+
+    #[test]
+    fn derived_values_are_none_without_inputs() {
+        let estimator = DerivedTelemetryEstimator::default();
+        let state = TelemetryState {
+            flight: TelemetryFlight {
+                speed_mps: Some(12.0),
+                climb_rate_mps: Some(1.0),
+                ..TelemetryFlight::default()
+            },
+            navigation: TelemetryNavigation {
+                latitude_deg: Some(47.0),
+                longitude_deg: Some(8.0),
+                heading_deg: Some(90.0),
+                ..TelemetryNavigation::default()
+            },
+            ..TelemetryState::default()
+        };
+
+        let derived = estimator.derive(&state, None, VehicleType::Quadrotor, Instant::now());
+
+        assert_eq!(derived, TelemetryDerived::default());
+    }
+
+    #[test]
+    fn rangefinder_height_expires() {
+        let mut estimator = DerivedTelemetryEstimator::default();
+        let now = Instant::now();
+        estimator.record_rangefinder_height(3.2, now);
+        let state = TelemetryState::default();
+
+        let fresh = estimator.derive(
+            &state,
+            None,
+            VehicleType::Quadrotor,
+            now + Duration::from_millis(500),
+        );
+        assert_eq!(fresh.rangefinder_height_m, Some(3.2));
+        let stale = estimator.derive(
+            &state,
+            None,
+            VehicleType::Quadrotor,
+            now + Duration::from_secs(2),
+        );
+        assert_eq!(stale.rangefinder_height_m, None);
+    }
+
+    #[test]
+    fn rovers_get_an_odometer_instead_of_height() {
+        let mut estimator = DerivedTelemetryEstimator::default();
+        let now = Instant::now();
+        estimator.record_rangefinder_height(0.4, now);
+        estimator.record_wheel_distance(1_000_000, &[3.0, 3.0]);
+        estimator.record_wheel_distance(2_000_000, &[5.5, 5.5]);
+        let state = TelemetryState {
+            flight: TelemetryFlight {
+                speed_mps: Some(2.5),
+                climb_rate_mps: Some(-0.5),
+                ..TelemetryFlight::default()
+            },
+            ..TelemetryState::default()
+        };
+
+        let rover = estimator.derive(&state, None, VehicleType::GroundRover, now);
+        assert_eq!(rover.odometer_m, Some(2.5));
+        assert_eq!(rover.rangefinder_height_m, None);
+        assert_eq!(rover.glide_ratio, None);
+        let copter = estimator.derive(&state, None, VehicleType::Quadrotor, now);
+        assert_eq!(copter.rangefinder_height_m, Some(0.4));
+        assert_eq!(copter.glide_ratio, Some(5.0));
+    }
+
+    #[test]
+    fn estimated_wind_uses_track_from_successive_fixes() {
+        let mut estimator = DerivedTelemetryEstimator::default();
+        let mut state = TelemetryState {
+            flight: TelemetryFlight {
+                speed_mps: Some(15.0),
+                airspeed_mps: Some(20.0),
+                climb_rate_mps: Some(-2.0),
+                ..TelemetryFlight::default()
+            },
+            navigation: TelemetryNavigation {
+                latitude_deg: Some(47.0),
+                longitude_deg: Some(8.0),
+                heading_deg: Some(0.0),
+                ..TelemetryNavigation::default()
+            },
+            ..TelemetryState::default()
+        };
+        estimator.observe(&state);
+        state.navigation.latitude_deg = Some(47.0001);
+        let home = HomePosition {
+            latitude_deg: 47.0,
+            longitude_deg: 8.0,
+            altitude_m: 0.0,
+        };
+
+        let derived = estimator.derive(&state, Some(&home), VehicleType::FixedWing, Instant::now());
+
+        assert_eq!(derived.wind_source, Some(WindSource::Estimated));
+        assert!((derived.wind_speed_mps.unwrap() - 5.0).abs() < 1e-6);
+        assert_eq!(derived.glide_ratio, Some(7.5));
+        assert!((derived.home_bearing_deg.unwrap() - 180.0).abs() < 1e-6);
+        assert!((derived.home_distance_m.unwrap() - 11.12).abs() < 0.01);
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::VecDeque;
+
+use crate::ipc::LinkQualitySample;
+use crate::ipc::status_text::StatusTextEntry;
+
+/// Ten minutes of once-a-second link samples.
+pub const LINK_QUALITY_HISTORY_LEN: usize = 600;
+pub const DIAGNOSTICS_STATUS_TEXT_LIMIT: usize = 200;
+const REDACTED_MAC: &str = "xx:xx:xx:xx:xx:xx";
+
+/// The most recent link samples, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct LinkQualityHistory {
+    samples: VecDeque<LinkQualitySample>,
+}
+
+impl LinkQualityHistory {
+    pub fn record(&mut self, sample: LinkQualitySample) {
+        if self.samples.len() == LINK_QUALITY_HISTORY_LEN {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn samples(&self) -> Vec<LinkQualitySample> {
+        self.samples.iter().copied().collect()
+    }
+}
+
+pub fn recent_status_texts(entries: &[StatusTextEntry]) -> &[StatusTextEntry] {
+    &entries[entries.len().saturating_sub(DIAGNOSTICS_STATUS_TEXT_LIMIT)..]
+}
+
+/// Whether `byte` could continue an address, so one next to it is part of
+/// something longer.
+fn joins_address(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b':' | b'-')
+}
+
+fn is_mac_at(bytes: &[u8], start: usize) -> bool {
+    let Some(candidate) = bytes.get(start..start + REDACTED_MAC.len()) else {
+        return false;
+    };
+    let separator = candidate[2];
+    matches!(separator, b':' | b'-')
+        && candidate.iter().enumerate().all(|(index, byte)| {
+            if index % 3 == 2 {
+                *byte == separator
+            } else {
+                byte.is_ascii_hexdigit()
+            }
+        })
+}
+
+/// Replace every `AA:BB:CC:DD:EE:FF` (or dash separated) address, as BLE
+/// devices are named on Linux, Windows and Android.
+pub fn redact_mac_addresses(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = String::with_capacity(text.len());
+    let mut copied = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        let boundary_before = index == 0 || !joins_address(bytes[index - 1]);
+        let end = index + REDACTED_MAC.len();
+        let boundary_after = bytes.get(end).is_none_or(|byte| !joins_address(*byte));
+        if boundary_before && boundary_after && is_mac_at(bytes, index) {
+            out.push_str(&text[copied..index]);
+            out.push_str(REDACTED_MAC);
+            copied = end;
+            index = end;
+        } else {
+            index += 1;
+        }
+    }
+    out.push_str(&text[copied..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mac_addresses_are_redacted_and_nothing_else() {
+        let log = "connecting to BLE device C8:2B:96:0A:1F:e3 (rssi -60)\n\
+                   android 00-1A-7D-DA-71-13 done; uuid 6e400001-b5a3-f393-e0a9-e50e24dcca9e";
+        assert_eq!(
+            redact_mac_addresses(log),
+            "connecting to BLE device xx:xx:xx:xx:xx:xx (rssi -60)\n\
+             android xx:xx:xx:xx:xx:xx done; uuid 6e400001-b5a3-f393-e0a9-e50e24dcca9e"
+        );
+        // Times and mixed separators are left alone.
+        let kept = "12:30:45 AA:BB-CC:DD:EE:FF 0A:1F:e3:00:11:22:33";
+        assert_eq!(redact_mac_addresses(kept), kept);
+    }
+
+    #[test]
+    fn history_keeps_the_newest_samples() {
+        let mut history = LinkQualityHistory::default();
+        for unix_msec in 0..(LINK_QUALITY_HISTORY_LEN as u64 + 5) {
+            history.record(LinkQualitySample {
+                unix_msec,
+                connected: true,
+                drop_pct: None,
+                rc_link: None,
+                frame_stats: None,
+            });
+        }
+        let samples = history.samples();
+        assert_eq!(samples.len(), LINK_QUALITY_HISTORY_LEN);
+        assert_eq!(samples[0].unix_msec, 5);
+    }
+}
@@ -0,0 +1,14 @@
+//! The MAVLink dialect IronWing encodes and decodes with. Everything in the
+//! workspace names message types through this module rather than
+//! `mavkit::dialect`, so switching dialects stays a one-line change here and
+//! in mavkit, whose `Vehicle` and `StreamConnection` carry its `MavMessage`.
+//!
+//! Swapping in a fork's generated dialect at build time is not supported
+//! yet. mavkit fixes `Vehicle` and `StreamConnection` to its own
+//! `MavMessage`, so it has to take the dialect as a parameter first, and the
+//! pinned rust-mavlink fork has to generate the fork's XML (which must
+//! `<include>ardupilotmega.xml</include>`). Until then, messages outside the
+//! dialect still arrive: the live link reports them on `mavlink://unknown`
+//! and tlogs keep them as opaque entries.
+
+pub use mavkit::dialect::*;
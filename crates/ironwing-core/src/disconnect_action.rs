@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use crate::dialect::MavCmd;
+use crate::ipc::{DisconnectAction, DisconnectActionOutcome};
+
+/// How long a disconnect waits for the final COMMAND_ACK of its action
+/// before closing the link regardless.
+pub const DISCONNECT_ACTION_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// What a disconnect does before closing the transport.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DisconnectStep {
+    Send(MavCmd),
+    Skip(DisconnectActionOutcome),
+}
+
+/// Decides the step for `action`. Leaving an armed vehicle, with or without
+/// a final command, needs `confirm_armed`.
+pub fn disconnect_step(
+    action: DisconnectAction,
+    armed: bool,
+    confirm_armed: bool,
+) -> Result<DisconnectStep, String> {
+    if armed && !confirm_armed {
+        return Err("vehicle is armed; confirm to disconnect from an armed vehicle".to_string());
+    }
+    let skip = |reason: &str| {
+        Ok(DisconnectStep::Skip(DisconnectActionOutcome::Skipped {
+            reason: reason.to_string(),
+        }))
+    };
+    match (action, armed) {
+        (DisconnectAction::None, _) => skip("no disconnect action selected"),
+        (_, false) => skip("vehicle is disarmed"),
+        (DisconnectAction::Rtl, true) => {
+            Ok(DisconnectStep::Send(MavCmd::MAV_CMD_NAV_RETURN_TO_LAUNCH))
+        }
+        (DisconnectAction::Land, true) => Ok(DisconnectStep::Send(MavCmd::MAV_CMD_NAV_LAND)),
+    }
+}
+
+/// The outcome of a sent action: `ack` is the final ACK's result (`Err` with
+/// the refusal when not accepted), `None` when the bound elapsed first.
+pub fn sent_outcome(ack: Option<Result<Result<(), String>, String>>) -> DisconnectActionOutcome {
+    match ack {
+        None => DisconnectActionOutcome::TimedOut,
+        Some(Ok(Ok(()))) => DisconnectActionOutcome::Accepted,
+        Some(Ok(Err(message))) => DisconnectActionOutcome::Rejected { message },
+        Some(Err(message)) => DisconnectActionOutcome::Failed { message },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn armed_vehicles_need_confirmation_whatever_the_action() {
+        for action in [
+            DisconnectAction::None,
+            DisconnectAction::Rtl,
+            DisconnectAction::Land,
+        ] {
+            assert!(disconnect_step(action, true, false).is_err());
+            assert!(disconnect_step(action, false, false).is_ok());
+        }
+    }
+
+    #[test]
+    fn only_armed_vehicles_with_an_action_get_a_command() {
+        assert_eq!(
+            disconnect_step(DisconnectAction::Rtl, true, true),
+            Ok(DisconnectStep::Send(MavCmd::MAV_CMD_NAV_RETURN_TO_LAUNCH))
+        );
+        assert_eq!(
+            disconnect_step(DisconnectAction::Land, true, true),
+            Ok(DisconnectStep::Send(MavCmd::MAV_CMD_NAV_LAND))
+        );
+        assert!(matches!(
+            disconnect_step(DisconnectAction::Rtl, false, true),
+            Ok(DisconnectStep::Skip(DisconnectActionOutcome::Skipped { reason })) if reason.contains("disarmed")
+        ));
+        assert!(matches!(
+            disconnect_step(DisconnectAction::None, true, true),
+            Ok(DisconnectStep::Skip(_))
+        ));
+    }
+
+    #[test]
+    fn ack_results_map_to_outcomes() {
+        assert_eq!(sent_outcome(None), DisconnectActionOutcome::TimedOut);
+        assert_eq!(
+            sent_outcome(Some(Ok(Ok(())))),
+            DisconnectActionOutcome::Accepted
+        );
+        assert_eq!(
+            sent_outcome(Some(Ok(Err("denied".into())))),
+            DisconnectActionOutcome::Rejected {
+                message: "denied".into()
+            }
+        );
+        assert_eq!(
+            sent_outcome(Some(Err("link closed".into()))),
+            DisconnectActionOutcome::Failed {
+                message: "link closed".into()
+            }
+        );
+    }
+}
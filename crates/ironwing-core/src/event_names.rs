@@ -1,16 +1,61 @@
 pub const SESSION_STATE: &str = "session://state";
 pub const TELEMETRY_STATE: &str = "telemetry://state";
+pub const TELEMETRY_DELTA: &str = "telemetry://delta";
 pub const MISSION_STATE: &str = "mission://state";
 pub const MISSION_PROGRESS: &str = "mission://progress";
 pub const PARAM_STORE: &str = "param://store";
 pub const PARAM_PROGRESS: &str = "param://progress";
+pub const PARAM_DELTA: &str = "param://delta";
 pub const SENSOR_HEALTH_STATE: &str = "sensor_health://state";
 pub const CALIBRATION_STATE: &str = "calibration://state";
+pub const CALIBRATION_PROGRESS: &str = "calibration://progress";
 pub const COMPASS_CAL_PROGRESS: &str = "compass://cal_progress";
 pub const COMPASS_CAL_REPORT: &str = "compass://cal_report";
 pub const STATUS_TEXT_STATE: &str = "status_text://state";
+pub const ALERT_TRIGGERED: &str = "alert://triggered";
+pub const ANNOUNCE_SAY: &str = "announce://say";
+pub const JOURNAL_WARNING: &str = "journal://warning";
+pub const LINK_LOST_IN_FLIGHT: &str = "link://lost_in_flight";
+pub const LINK_CONNECTING: &str = "link://connecting";
+pub const LINK_TRANSPORT_LOST: &str = "link://transport_lost";
+pub const LINK_FAILSAFE_WARNING: &str = "link://failsafe_warning";
+pub const FLIGHT_SUMMARY: &str = "flight://summary";
+pub const TRACK_APPEND: &str = "track://append";
+pub const NAMED_VALUE: &str = "custom://named_value";
+pub const VIDEO_STREAMS: &str = "video://streams";
+pub const GPS_FIX_CHANGED: &str = "gps://fix_changed";
+pub const BATTERY_STATS: &str = "battery://stats";
+pub const CHECKLIST_UPDATE: &str = "checklist://update";
+pub const GCS_COMMAND_RECEIVED: &str = "gcs://command_received";
+pub const FTP_PROGRESS: &str = "ftp://progress";
+pub const COMMAND_PROGRESS: &str = "command://progress";
+pub const PARAM_EXTERNAL_CHANGE: &str = "param://external_change";
+pub const RANGEFINDER_READING: &str = "rangefinder://reading";
+pub const LANDING_ASSIST_WARNING: &str = "landing://assist_warning";
+pub const PRECISION_LANDING_TARGET: &str = "precision_landing://target";
+pub const OPTICAL_FLOW_QUALITY: &str = "optical_flow://quality";
+pub const WINCH_STATUS: &str = "winch://status";
+pub const VEHICLE_HEALTH: &str = "vehicle://health";
+pub const VEHICLE_VTOL: &str = "vehicle://vtol";
+pub const MAVLINK_UNKNOWN: &str = "mavlink://unknown";
+pub const FENCE_PROXIMITY: &str = "fence://proximity";
+pub const LINK_DISCONNECT_ACTION: &str = "link://disconnect_action";
+pub const VEHICLE_FAILSAFE: &str = "vehicle://failsafe";
+pub const VEHICLE_SELECTED: &str = "vehicle://selected";
+pub const HOME_MOVED: &str = "home://moved";
+pub const SESSION_EXPORT_PROGRESS: &str = "session_export://progress";
+pub const MAP_PREFETCH_PROGRESS: &str = "map://prefetch_progress";
+pub const SERIAL_ATTACHED: &str = "serial://attached";
+pub const SERIAL_DETACHED: &str = "serial://detached";
 pub const SUPPORT_STATE: &str = "support://state";
+pub const SYSTEM_RESUMED: &str = "system://resumed";
+pub const PENDING_CONNECT: &str = "app://pending_connect";
 pub const GUIDED_STATE: &str = "guided://state";
+pub const GUIDED_TARGET: &str = "guided://target";
+pub const GUIDED_QUEUE_PROGRESS: &str = "guided_queue://progress";
 pub const PLAYBACK_STATE: &str = "playback://state";
+pub const LOG_PLAYBACK_POSITION: &str = "log://playback_position";
 pub const LOG_PROGRESS: &str = "log://progress";
+pub const LOG_APPENDED: &str = "log://appended";
 pub const FIRMWARE_PROGRESS: &str = "firmware://progress";
+pub const TASK_STALLED: &str = "internal://task_stalled";
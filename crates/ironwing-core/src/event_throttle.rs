@@ -0,0 +1,183 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use web_time::Instant;
+
+use crate::ipc::event_rates::EventBridgeStats;
+
+pub const MIN_EVENT_RATE_HZ: u32 = 1;
+pub const MAX_EVENT_RATE_HZ: u32 = 50;
+pub const EVENT_RATE_RANGE_ERROR: &str = "event rates must be between 1 and 50 Hz";
+
+#[derive(Debug, Default)]
+struct StreamState {
+    last_emit: Option<Instant>,
+    pending: Option<serde_json::Value>,
+    changes: u64,
+    emitted: u64,
+}
+
+/// Per-event maximum emit rates with latest-value coalescing.
+///
+/// An update arriving inside an event's interval replaces any pending value
+/// instead of queueing behind it; [`EventThrottle::due`] releases the pending
+/// value once the interval has elapsed. Events without a configured rate pass
+/// straight through and are only counted.
+#[derive(Debug, Default)]
+pub struct EventThrottle {
+    rates: HashMap<String, u32>,
+    streams: BTreeMap<&'static str, StreamState>,
+}
+
+impl EventThrottle {
+    /// Replace the configured rates. Events missing from `rates` go back to
+    /// emitting on every change.
+    pub fn set_rates(&mut self, rates: HashMap<String, u32>) -> Result<(), &'static str> {
+        if rates
+            .values()
+            .any(|rate| !(MIN_EVENT_RATE_HZ..=MAX_EVENT_RATE_HZ).contains(rate))
+        {
+            return Err(EVENT_RATE_RANGE_ERROR);
+        }
+        self.rates = rates;
+        for (event, stream) in &mut self.streams {
+            if !self.rates.contains_key(*event) {
+                stream.pending = None;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_throttled(&self, event: &str) -> bool {
+        self.rates.contains_key(event)
+    }
+
+    fn interval(&self, event: &str) -> Option<Duration> {
+        self.rates
+            .get(event)
+            .map(|rate| Duration::from_secs(1) / *rate)
+    }
+
+    /// Count an update for an event that is not throttled.
+    pub fn record_passthrough(&mut self, event: &'static str, now: Instant) {
+        let stream = self.streams.entry(event).or_default();
+        stream.changes += 1;
+        stream.emitted += 1;
+        stream.last_emit = Some(now);
+    }
+
+    /// Offer an update for a throttled event. Returns the payload when it may
+    /// be emitted now; otherwise it becomes the pending value.
+    pub fn offer(
+        &mut self,
+        event: &'static str,
+        payload: serde_json::Value,
+        now: Instant,
+    ) -> Option<serde_json::Value> {
+        let interval = self.interval(event).unwrap_or_default();
+        let stream = self.streams.entry(event).or_default();
+        stream.changes += 1;
+        let ready = stream
+            .last_emit
+            .is_none_or(|last| now.saturating_duration_since(last) >= interval);
+        if ready {
+            stream.pending = None;
+            stream.last_emit = Some(now);
+            stream.emitted += 1;
+            Some(payload)
+        } else {
+            stream.pending = Some(payload);
+            None
+        }
+    }
+
+    /// Pending payloads whose interval has elapsed, ready to emit.
+    pub fn due(&mut self, now: Instant) -> Vec<(&'static str, serde_json::Value)> {
+        let mut due = Vec::new();
+        for (event, stream) in &mut self.streams {
+            let Some(interval) = self
+                .rates
+                .get(*event)
+                .map(|rate| Duration::from_secs(1) / *rate)
+            else {
+                continue;
+            };
+            let ready = stream
+                .last_emit
+                .is_none_or(|last| now.saturating_duration_since(last) >= interval);
+            if ready && let Some(payload) = stream.pending.take() {
+                stream.last_emit = Some(now);
+                stream.emitted += 1;
+                due.push((*event, payload));
+            }
+        }
+        due
+    }
+
+    /// Drop pending values, e.g. when the session they were scoped to ends.
+    pub fn clear_pending(&mut self) {
+        for stream in self.streams.values_mut() {
+            stream.pending = None;
+        }
+    }
+
+    pub fn stats(&self) -> Vec<EventBridgeStats> {
+        self.streams
+            .iter()
+            .map(|(event, stream)| EventBridgeStats {
+                event: (*event).to_string(),
+                max_rate_hz: self.rates.get(*event).copied(),
+                changes: stream.changes,
+                emitted: stream.emitted,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throttled_event_coalesces_to_latest_value() {
+        let start = Instant::now();
+        let mut throttle = EventThrottle::default();
+        throttle
+            .set_rates(HashMap::from([("vehicle://state".to_string(), 2)]))
+            .expect("valid rate");
+
+        assert_eq!(
+            throttle.offer("vehicle://state", 1.into(), start),
+            Some(1.into())
+        );
+        for (offset_ms, value) in [(50, 2), (100, 3), (150, 4)] {
+            let now = start + Duration::from_millis(offset_ms);
+            assert_eq!(throttle.offer("vehicle://state", value.into(), now), None);
+        }
+        assert!(throttle.due(start + Duration::from_millis(400)).is_empty());
+        assert_eq!(
+            throttle.due(start + Duration::from_millis(500)),
+            vec![("vehicle://state", 4.into())]
+        );
+        assert!(throttle.due(start + Duration::from_secs(2)).is_empty());
+
+        throttle.record_passthrough("mission://state", start);
+        let stats = throttle.stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].event, "mission://state");
+        assert_eq!((stats[0].changes, stats[0].emitted), (1, 1));
+        assert_eq!(stats[1].max_rate_hz, Some(2));
+        assert_eq!((stats[1].changes, stats[1].emitted), (4, 2));
+    }
+
+    #[test]
+    fn rates_outside_range_are_rejected() {
+        let mut throttle = EventThrottle::default();
+
+        assert_eq!(
+            throttle.set_rates(HashMap::from([("param://store".to_string(), 0)])),
+            Err(EVENT_RATE_RANGE_ERROR)
+        );
+        assert!(!throttle.is_throttled("param://store"));
+    }
+}
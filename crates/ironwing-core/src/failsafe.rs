@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use crate::ipc::{FailsafeEvent, FailsafeKind};
+
+/// Lowercase fragments of STATUSTEXT lines that announce a failsafe, by
+/// kind. ArduPilot Copter/Rover announce "<Type> Failsafe[ - <action>]";
+/// PX4 (without bundled events metadata) falls back to plain-text warnings.
+const FAILSAFE_PATTERNS: &[(&str, FailsafeKind)] = &[
+    ("radio failsafe", FailsafeKind::Radio),
+    ("throttle failsafe", FailsafeKind::Radio),
+    ("failsafe enabled: no rc", FailsafeKind::Radio),
+    ("manual control lost", FailsafeKind::Radio),
+    ("rc signal lost", FailsafeKind::Radio),
+    ("gcs failsafe", FailsafeKind::Gcs),
+    ("failsafe enabled: no datalink", FailsafeKind::Gcs),
+    ("data link lost", FailsafeKind::Gcs),
+    ("connection to ground station lost", FailsafeKind::Gcs),
+    ("battery failsafe", FailsafeKind::Battery),
+    ("low battery level", FailsafeKind::Battery),
+    ("critical battery level", FailsafeKind::Battery),
+    ("emergency battery level", FailsafeKind::Battery),
+    ("ekf failsafe", FailsafeKind::Ekf),
+    ("ekf variance", FailsafeKind::Ekf),
+    ("fence breach", FailsafeKind::Geofence),
+    ("geofence violated", FailsafeKind::Geofence),
+    ("geofence breach", FailsafeKind::Geofence),
+    ("terrain failsafe", FailsafeKind::Terrain),
+    ("crash: disarming", FailsafeKind::Crash),
+];
+
+/// Fragments of the lines that report a failsafe ending, not starting.
+const CLEARED_PATTERNS: &[&str] = &["cleared", "resolved", "failsafe off", "fs off"];
+
+/// ArduPilot's ModeReason numbers that Plane reports in its
+/// "Failsafe. Short event on: type=1/reason=3" lines.
+fn plane_mode_reason_kind(reason: u32) -> FailsafeKind {
+    match reason {
+        3 => FailsafeKind::Radio,
+        4 => FailsafeKind::Battery,
+        5 => FailsafeKind::Gcs,
+        6 => FailsafeKind::Ekf,
+        10 => FailsafeKind::Geofence,
+        11 => FailsafeKind::Terrain,
+        _ => FailsafeKind::Other,
+    }
+}
+
+fn plane_event_kind(text: &str) -> Option<FailsafeKind> {
+    let rest = text
+        .strip_prefix("failsafe. short event on:")
+        .or_else(|| text.strip_prefix("failsafe. long event on:"))?;
+    let reason = rest
+        .split(|c: char| c == '/' || c.is_whitespace())
+        .find_map(|field| field.strip_prefix("reason="))
+        .and_then(|reason| reason.parse().ok());
+    Some(reason.map_or(FailsafeKind::Other, plane_mode_reason_kind))
+}
+
+/// An ArduPilot battery monitor line: "Battery 1 is low 10.50V used 2200 mAh"
+/// or "... is critical ...".
+fn is_battery_monitor_warning(text: &str) -> bool {
+    text.starts_with("battery") && (text.contains(" is low") || text.contains(" is critical"))
+}
+
+/// "Radio Failsafe - Switching to RTL" → "Switching to RTL".
+fn action_taken(text: &str) -> Option<String> {
+    let (_, action) = text.split_once(" - ")?;
+    let action = action.trim();
+    (!action.is_empty()).then(|| action.to_string())
+}
+
+/// The failsafe `text` announces, if any. Lines reporting a failsafe has
+/// cleared decode to `None`.
+pub fn decode_failsafe_status_text(
+    text: &str,
+    timestamp_usec: Option<u64>,
+) -> Option<FailsafeEvent> {
+    let lower = text.trim().to_ascii_lowercase();
+    if CLEARED_PATTERNS
+        .iter()
+        .any(|pattern| lower.contains(pattern))
+    {
+        return None;
+    }
+    let kind = plane_event_kind(&lower)
+        .or_else(|| {
+            FAILSAFE_PATTERNS
+                .iter()
+                .find(|(pattern, _)| lower.contains(pattern))
+                .map(|(_, kind)| *kind)
+        })
+        .or_else(|| is_battery_monitor_warning(&lower).then_some(FailsafeKind::Battery))
+        .or_else(|| lower.contains("failsafe").then_some(FailsafeKind::Other))?;
+    Some(FailsafeEvent {
+        kind,
+        action_taken: action_taken(text),
+        timestamp_usec,
+        text: text.trim().to_string(),
+    })
+}
+
+/// Failsafes seen on the current link, as alert rule sources: `failsafe.count`
+/// and `failsafe.<kind>` count events (use them with `changed`), and
+/// `failsafe.last.kind` / `failsafe.last.action_taken` describe the latest.
+#[derive(Debug, Clone, Default)]
+pub struct FailsafeLog {
+    counts: HashMap<FailsafeKind, u32>,
+    last: Option<FailsafeEvent>,
+}
+
+impl FailsafeLog {
+    pub fn record(&mut self, event: FailsafeEvent) {
+        *self.counts.entry(event.kind).or_default() += 1;
+        self.last = Some(event);
+    }
+
+    pub fn alert_source(&self) -> serde_json::Value {
+        let mut source = serde_json::Map::new();
+        for (kind, count) in &self.counts {
+            if let Ok(serde_json::Value::String(name)) = serde_json::to_value(kind) {
+                source.insert(name, (*count).into());
+            }
+        }
+        source.insert("count".into(), self.counts.values().sum::<u32>().into());
+        source.insert("last".into(), serde_json::json!(self.last));
+        serde_json::Value::Object(source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(text: &str) -> Option<FailsafeKind> {
+        decode_failsafe_status_text(text, None).map(|event| event.kind)
+    }
+
+    #[test]
+    fn ardupilot_copter_announcements_decode_with_their_action() {
+        let event = decode_failsafe_status_text("Radio Failsafe - Switching to RTL", Some(5))
+            .expect("radio failsafe");
+        assert_eq!(event.kind, FailsafeKind::Radio);
+        assert_eq!(event.action_taken.as_deref(), Some("Switching to RTL"));
+        assert_eq!(event.timestamp_usec, Some(5));
+
+        let event = decode_failsafe_status_text("Battery Failsafe - Switching to LAND", None)
+            .expect("battery failsafe");
+        assert_eq!(event.kind, FailsafeKind::Battery);
+        assert_eq!(event.action_taken.as_deref(), Some("Switching to LAND"));
+
+        assert_eq!(kind("GCS Failsafe"), Some(FailsafeKind::Gcs));
+        assert_eq!(
+            kind("EKF Failsafe - Switching to LAND"),
+            Some(FailsafeKind::Ekf)
+        );
+        assert_eq!(kind("EKF variance"), Some(FailsafeKind::Ekf));
+        assert_eq!(
+            kind("Terrain Failsafe - Switching to RTL"),
+            Some(FailsafeKind::Terrain)
+        );
+        assert_eq!(
+            kind("Crash: Disarming: AngErr=45>30, Accel<0.3"),
+            Some(FailsafeKind::Crash)
+        );
+        assert_eq!(kind("Deadreckon Failsafe"), Some(FailsafeKind::Other));
+        assert_eq!(
+            kind("Battery 1 is low 10.47V used 2214 mAh"),
+            Some(FailsafeKind::Battery)
+        );
+        assert_eq!(
+            kind("Battery 1 is critical 9.90V used 2870 mAh"),
+            Some(FailsafeKind::Battery)
+        );
+    }
+
+    #[test]
+    fn ardupilot_plane_events_decode_by_mode_reason() {
+        assert_eq!(
+            kind("Failsafe. Short event on: type=1/reason=3"),
+            Some(FailsafeKind::Radio)
+        );
+        assert_eq!(
+            kind("Failsafe. Long event on: type=2/reason=5"),
+            Some(FailsafeKind::Gcs)
+        );
+        assert_eq!(
+            kind("Failsafe. Long event on: type=2/reason=99"),
+            Some(FailsafeKind::Other)
+        );
+        assert_eq!(kind("Throttle failsafe on"), Some(FailsafeKind::Radio));
+        assert_eq!(kind("Short Failsafe Cleared"), None);
+        assert_eq!(kind("MSG FS OFF"), None);
+    }
+
+    #[test]
+    fn px4_warnings_decode() {
+        assert_eq!(kind("Failsafe enabled: no RC"), Some(FailsafeKind::Radio));
+        assert_eq!(kind("Manual control lost"), Some(FailsafeKind::Radio));
+        assert_eq!(kind("Data link lost"), Some(FailsafeKind::Gcs));
+        assert_eq!(
+            kind("Connection to ground station lost"),
+            Some(FailsafeKind::Gcs)
+        );
+        assert_eq!(
+            kind("Low battery level! Return advised"),
+            Some(FailsafeKind::Battery)
+        );
+        assert_eq!(
+            kind("Critical battery level! Landing advised"),
+            Some(FailsafeKind::Battery)
+        );
+        assert_eq!(kind("Geofence violated"), Some(FailsafeKind::Geofence));
+    }
+
+    #[test]
+    fn cleared_and_unrelated_lines_are_not_failsafes() {
+        assert_eq!(kind("Radio Failsafe Cleared"), None);
+        assert_eq!(kind("GCS Failsafe Cleared"), None);
+        assert_eq!(kind("EKF failsafe cleared"), None);
+        assert_eq!(kind("Battery failsafe resolved"), None);
+        assert_eq!(kind("EKF3 IMU0 is using GPS"), None);
+        assert_eq!(kind("PreArm: Battery below minimum arming voltage"), None);
+        assert_eq!(kind("Arming motors"), None);
+    }
+
+    #[test]
+    fn the_log_counts_events_by_kind_for_alert_rules() {
+        let mut log = FailsafeLog::default();
+        assert_eq!(log.alert_source()["count"], 0);
+        for text in [
+            "Radio Failsafe",
+            "Battery Failsafe - Switching to RTL",
+            "Radio Failsafe",
+        ] {
+            log.record(decode_failsafe_status_text(text, None).expect("failsafe"));
+        }
+        let source = log.alert_source();
+        assert_eq!(source["count"], 3);
+        assert_eq!(source["radio"], 2);
+        assert_eq!(source["battery"], 1);
+        assert_eq!(source["last"]["kind"], "radio");
+        assert!(source.get("gcs").is_none());
+    }
+}
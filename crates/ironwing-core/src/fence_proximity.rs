@@ -0,0 +1,433 @@
+use mavkit::{FencePlan, FenceRegion, GeoPoint2d};
+
+use crate::geo::{closest_point_on_segment, local_offset_m, point_in_polygon};
+use crate::ipc::{FenceBoundaryKind, FenceProximity};
+
+pub const DEFAULT_FENCE_WARNING_MARGIN_M: f32 = 30.0;
+
+pub const FENCE_ENABLE_PARAM: &str = "FENCE_ENABLE";
+pub const FENCE_TYPE_PARAM: &str = "FENCE_TYPE";
+pub const FENCE_RADIUS_PARAM: &str = "FENCE_RADIUS";
+
+/// FENCE_TYPE bits: the circle around home and the uploaded regions.
+const FENCE_TYPE_CIRCLE: u32 = 2;
+const FENCE_TYPE_POLYGON: u32 = 4;
+
+/// Below this the vehicle is treated as holding position, not closing.
+const MIN_CLOSING_SPEED_MPS: f64 = 0.1;
+
+pub fn validate_fence_warning_margin(margin_m: f32) -> Result<(), String> {
+    if !margin_m.is_finite() || margin_m <= 0.0 {
+        return Err("fence_warning_margin_m must be greater than zero".to_string());
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FenceShape {
+    /// Vertices as `(latitude_deg, longitude_deg)`.
+    Polygon(Vec<(f64, f64)>),
+    Circle {
+        center: (f64, f64),
+        radius_m: f64,
+    },
+}
+
+/// One boundary the vehicle has to stay inside (inclusion) or outside
+/// (exclusion) of.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FenceBoundary {
+    pub kind: FenceBoundaryKind,
+    pub region_index: Option<u32>,
+    /// Inclusion regions sharing a group form one allowed area: being inside
+    /// any of them is enough.
+    pub inclusion_group: Option<u32>,
+    pub shape: FenceShape,
+}
+
+impl FenceBoundary {
+    fn inclusion(&self) -> bool {
+        matches!(
+            self.kind,
+            FenceBoundaryKind::InclusionPolygon
+                | FenceBoundaryKind::InclusionCircle
+                | FenceBoundaryKind::HomeCircle
+        )
+    }
+}
+
+fn point(point: &GeoPoint2d) -> (f64, f64) {
+    (point.latitude_deg, point.longitude_deg)
+}
+
+fn region_boundary(index: usize, region: &FenceRegion) -> FenceBoundary {
+    let region_index = Some(index as u32);
+    match region {
+        FenceRegion::InclusionPolygon(polygon) => FenceBoundary {
+            kind: FenceBoundaryKind::InclusionPolygon,
+            region_index,
+            inclusion_group: Some(u32::from(polygon.inclusion_group)),
+            shape: FenceShape::Polygon(polygon.vertices.iter().map(point).collect()),
+        },
+        FenceRegion::ExclusionPolygon(polygon) => FenceBoundary {
+            kind: FenceBoundaryKind::ExclusionPolygon,
+            region_index,
+            inclusion_group: None,
+            shape: FenceShape::Polygon(polygon.vertices.iter().map(point).collect()),
+        },
+        FenceRegion::InclusionCircle(circle) => FenceBoundary {
+            kind: FenceBoundaryKind::InclusionCircle,
+            region_index,
+            inclusion_group: Some(u32::from(circle.inclusion_group)),
+            shape: FenceShape::Circle {
+                center: point(&circle.center),
+                radius_m: f64::from(circle.radius_m),
+            },
+        },
+        FenceRegion::ExclusionCircle(circle) => FenceBoundary {
+            kind: FenceBoundaryKind::ExclusionCircle,
+            region_index,
+            inclusion_group: None,
+            shape: FenceShape::Circle {
+                center: point(&circle.center),
+                radius_m: f64::from(circle.radius_m),
+            },
+        },
+    }
+}
+
+/// The boundaries the autopilot enforces: the regions of `plan` and the
+/// FENCE_RADIUS circle around `home`, as far as FENCE_ENABLE and FENCE_TYPE
+/// say they are on. Missing parameters are taken as enabled so an unread
+/// parameter set still warns.
+pub fn fence_boundaries(
+    plan: Option<&FencePlan>,
+    param: impl Fn(&str) -> Option<f32>,
+    home: Option<(f64, f64)>,
+) -> Vec<FenceBoundary> {
+    if param(FENCE_ENABLE_PARAM).is_some_and(|enable| enable.round() as i32 == 0) {
+        return Vec::new();
+    }
+    let fence_type = param(FENCE_TYPE_PARAM).map(|value| value.round() as u32);
+    let enabled = |bit: u32| fence_type.is_none_or(|fence_type| fence_type & bit != 0);
+
+    let mut boundaries = Vec::new();
+    if enabled(FENCE_TYPE_CIRCLE) {
+        let radius_m = param(FENCE_RADIUS_PARAM).map(f64::from);
+        if let (Some(center), Some(radius_m)) = (home, radius_m.filter(|radius| *radius > 0.0)) {
+            boundaries.push(FenceBoundary {
+                kind: FenceBoundaryKind::HomeCircle,
+                region_index: None,
+                inclusion_group: None,
+                shape: FenceShape::Circle { center, radius_m },
+            });
+        }
+    }
+    if let Some(plan) = plan.filter(|_| enabled(FENCE_TYPE_POLYGON)) {
+        boundaries.extend(
+            plan.regions
+                .iter()
+                .enumerate()
+                .map(|(index, region)| region_boundary(index, region)),
+        );
+    }
+    boundaries
+}
+
+/// Signed distance to `boundary` (negative once breached) and how fast it is
+/// shrinking, for a vehicle at `position` moving at `velocity` (east, north).
+fn boundary_margin(
+    boundary: &FenceBoundary,
+    position: (f64, f64),
+    velocity: (f64, f64),
+) -> Option<(f64, f64)> {
+    let local = |(latitude_deg, longitude_deg): (f64, f64)| {
+        local_offset_m(position.0, position.1, latitude_deg, longitude_deg)
+    };
+    // Everything is relative to the vehicle, which sits at the origin.
+    let (inside, nearest) = match &boundary.shape {
+        FenceShape::Polygon(vertices) => {
+            if vertices.len() < 3 {
+                return None;
+            }
+            let vertices: Vec<(f64, f64)> = vertices.iter().copied().map(local).collect();
+            let nearest = (0..vertices.len())
+                .map(|index| {
+                    closest_point_on_segment(
+                        (0.0, 0.0),
+                        vertices[index],
+                        vertices[(index + 1) % vertices.len()],
+                    )
+                })
+                .min_by(|a, b| a.0.hypot(a.1).total_cmp(&b.0.hypot(b.1)))?;
+            (point_in_polygon((0.0, 0.0), &vertices), nearest)
+        }
+        FenceShape::Circle { center, radius_m } => {
+            if !radius_m.is_finite() || *radius_m <= 0.0 {
+                return None;
+            }
+            let (east, north) = local(*center);
+            let distance = east.hypot(north);
+            let nearest = if distance > 0.0 {
+                let scale = 1.0 - radius_m / distance;
+                (east * scale, north * scale)
+            } else {
+                // At the centre every direction is equally close.
+                (*radius_m, 0.0)
+            };
+            (distance < *radius_m, nearest)
+        }
+    };
+    let distance = nearest.0.hypot(nearest.1);
+    let toward_boundary = if distance > 0.0 {
+        (velocity.0 * nearest.0 + velocity.1 * nearest.1) / distance
+    } else {
+        velocity.0.hypot(velocity.1)
+    };
+    if inside == boundary.inclusion() {
+        Some((distance, toward_boundary))
+    } else {
+        Some((-distance, -toward_boundary))
+    }
+}
+
+fn proximity(
+    boundary: &FenceBoundary,
+    (distance_m, closing_speed_mps): (f64, f64),
+) -> FenceProximity {
+    FenceProximity {
+        kind: boundary.kind,
+        region_index: boundary.region_index,
+        distance_m,
+        closing_speed_mps,
+        time_to_breach_s: (distance_m >= 0.0 && closing_speed_mps >= MIN_CLOSING_SPEED_MPS)
+            .then(|| distance_m / closing_speed_mps),
+    }
+}
+
+/// The boundary closest to being breached from `position` (latitude,
+/// longitude) at ground velocity `velocity` (east, north m/s). An inclusion
+/// group counts through whichever of its regions leaves the most room.
+pub fn nearest_fence(
+    boundaries: &[FenceBoundary],
+    position: (f64, f64),
+    velocity: (f64, f64),
+) -> Option<FenceProximity> {
+    let mut best_in_group: Vec<(u32, FenceProximity)> = Vec::new();
+    let mut candidates = Vec::new();
+    for boundary in boundaries {
+        let Some(margin) = boundary_margin(boundary, position, velocity) else {
+            continue;
+        };
+        let report = proximity(boundary, margin);
+        match boundary.inclusion_group.filter(|_| boundary.inclusion()) {
+            Some(group) => match best_in_group.iter_mut().find(|(id, _)| *id == group) {
+                Some((_, best)) if best.distance_m >= report.distance_m => {}
+                Some((_, best)) => *best = report,
+                None => best_in_group.push((group, report)),
+            },
+            None => candidates.push(report),
+        }
+    }
+    candidates
+        .into_iter()
+        .chain(best_in_group.into_iter().map(|(_, report)| report))
+        .min_by(|a, b| a.distance_m.total_cmp(&b.distance_m))
+}
+
+/// Worth a `fence://proximity` event: not yet breached, within `margin_m` and
+/// closing.
+pub fn is_fence_warning(proximity: &FenceProximity, margin_m: f32) -> bool {
+    proximity.time_to_breach_s.is_some() && proximity.distance_m <= f64::from(margin_m)
+}
+
+/// Ground velocity (east, north) in m/s from GLOBAL_POSITION_INT's vx
+/// (north) and vy (east) in cm/s. This is the track over the ground, so a
+/// crosswind or a multirotor flying sideways closes on the fence the way it
+/// really does rather than the way the nose points.
+pub fn ground_velocity(vx_cm_s: i16, vy_cm_s: i16) -> (f64, f64) {
+    (f64::from(vy_cm_s) / 100.0, f64::from(vx_cm_s) / 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    const HOME: (f64, f64) = (47.3977, 8.5456);
+    /// Roughly one metre of latitude, in degrees.
+    const M: f64 = 1.0 / 111_195.0;
+
+    fn params(values: &[(&str, f32)]) -> impl Fn(&str) -> Option<f32> {
+        let values: HashMap<String, f32> = values
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect();
+        move |name| values.get(name).copied()
+    }
+
+    fn north_of_home(metres: f64) -> (f64, f64) {
+        (HOME.0 + metres * M, HOME.1)
+    }
+
+    /// A square `half_m` either side of `center`, in metres of latitude (the
+    /// east-west sides are wider in metres, which the tests don't rely on).
+    fn square(center: (f64, f64), half_m: f64) -> Vec<(f64, f64)> {
+        let d = half_m * M;
+        vec![
+            (center.0 - d, center.1 - d),
+            (center.0 - d, center.1 + d),
+            (center.0 + d, center.1 + d),
+            (center.0 + d, center.1 - d),
+        ]
+    }
+
+    fn boundary(kind: FenceBoundaryKind, group: Option<u32>, shape: FenceShape) -> FenceBoundary {
+        FenceBoundary {
+            kind,
+            region_index: Some(0),
+            inclusion_group: group,
+            shape,
+        }
+    }
+
+    #[test]
+    fn home_circle_follows_fence_type_and_radius() {
+        let circle = fence_boundaries(
+            None,
+            params(&[
+                ("FENCE_ENABLE", 1.0),
+                ("FENCE_TYPE", 7.0),
+                ("FENCE_RADIUS", 150.0),
+            ]),
+            Some(HOME),
+        );
+        assert_eq!(circle.len(), 1);
+        assert_eq!(circle[0].kind, FenceBoundaryKind::HomeCircle);
+
+        let disabled = fence_boundaries(
+            None,
+            params(&[("FENCE_ENABLE", 0.0), ("FENCE_RADIUS", 150.0)]),
+            Some(HOME),
+        );
+        assert!(disabled.is_empty());
+        let altitude_only = fence_boundaries(
+            None,
+            params(&[("FENCE_TYPE", 1.0), ("FENCE_RADIUS", 150.0)]),
+            Some(HOME),
+        );
+        assert!(altitude_only.is_empty());
+        assert!(fence_boundaries(None, params(&[("FENCE_RADIUS", 150.0)]), None).is_empty());
+    }
+
+    #[test]
+    fn approaching_a_circle_edge_predicts_the_breach() {
+        let fence = [boundary(
+            FenceBoundaryKind::HomeCircle,
+            None,
+            FenceShape::Circle {
+                center: HOME,
+                radius_m: 100.0,
+            },
+        )];
+        let heading_out = nearest_fence(&fence, north_of_home(80.0), (0.0, 5.0)).expect("near");
+        assert!((heading_out.distance_m - 20.0).abs() < 0.1);
+        assert!((heading_out.closing_speed_mps - 5.0).abs() < 1e-6);
+        assert!((heading_out.time_to_breach_s.expect("closing") - 4.0).abs() < 0.05);
+        assert!(is_fence_warning(&heading_out, 30.0));
+        assert!(!is_fence_warning(&heading_out, 10.0));
+
+        let heading_home = nearest_fence(&fence, north_of_home(80.0), (0.0, -5.0)).expect("near");
+        assert_eq!(heading_home.time_to_breach_s, None);
+        assert!(!is_fence_warning(&heading_home, 30.0));
+
+        // Tracking back towards home is not a breach, wherever the nose points.
+        let drifting_home =
+            nearest_fence(&fence, north_of_home(80.0), ground_velocity(-500, 100)).expect("near");
+        assert_eq!(drifting_home.time_to_breach_s, None);
+
+        let outside = nearest_fence(&fence, north_of_home(120.0), (0.0, 5.0)).expect("near");
+        assert!((outside.distance_m + 20.0).abs() < 0.1);
+        assert!(!is_fence_warning(&outside, 30.0));
+    }
+
+    #[test]
+    fn exclusion_zones_are_kept_out_of() {
+        let no_fly = square(north_of_home(100.0), 20.0);
+        let fence = [boundary(
+            FenceBoundaryKind::ExclusionPolygon,
+            None,
+            FenceShape::Polygon(no_fly),
+        )];
+        // The south edge is 80 m north of home.
+        let approaching = nearest_fence(&fence, north_of_home(60.0), (0.0, 2.0)).expect("near");
+        assert!((approaching.distance_m - 20.0).abs() < 0.1);
+        assert!((approaching.time_to_breach_s.expect("closing") - 10.0).abs() < 0.1);
+        let inside = nearest_fence(&fence, north_of_home(100.0), (0.0, 0.0)).expect("near");
+        assert!(inside.distance_m < 0.0);
+
+        let circle = [boundary(
+            FenceBoundaryKind::ExclusionCircle,
+            None,
+            FenceShape::Circle {
+                center: north_of_home(100.0),
+                radius_m: 10.0,
+            },
+        )];
+        let approaching = nearest_fence(&circle, HOME, ground_velocity(3.0, 0.0)).expect("near");
+        assert!((approaching.distance_m - 90.0).abs() < 0.1);
+        assert!((approaching.closing_speed_mps - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn overlapping_inclusion_regions_in_a_group_form_one_area() {
+        // Two squares overlapping around 40 m north; the vehicle at 45 m is
+        // near the top of the first but well inside the second.
+        let fence = [
+            boundary(
+                FenceBoundaryKind::InclusionPolygon,
+                Some(1),
+                FenceShape::Polygon(square(north_of_home(0.0), 50.0)),
+            ),
+            boundary(
+                FenceBoundaryKind::InclusionPolygon,
+                Some(1),
+                FenceShape::Polygon(square(north_of_home(80.0), 50.0)),
+            ),
+        ];
+        let grouped = nearest_fence(&fence, north_of_home(45.0), (0.0, 1.0)).expect("near");
+        assert!(
+            (grouped.distance_m - 15.0).abs() < 0.1,
+            "{}",
+            grouped.distance_m
+        );
+
+        let separate = [
+            fence[0].clone(),
+            FenceBoundary {
+                inclusion_group: Some(2),
+                ..fence[1].clone()
+            },
+        ];
+        let strictest = nearest_fence(&separate, north_of_home(45.0), (0.0, 1.0)).expect("near");
+        assert!(
+            (strictest.distance_m - 5.0).abs() < 0.1,
+            "{}",
+            strictest.distance_m
+        );
+    }
+
+    #[test]
+    fn degenerate_regions_are_skipped_and_margins_validated() {
+        let fence = [boundary(
+            FenceBoundaryKind::InclusionPolygon,
+            Some(0),
+            FenceShape::Polygon(vec![HOME, north_of_home(10.0)]),
+        )];
+        assert_eq!(nearest_fence(&fence, HOME, (0.0, 0.0)), None);
+        assert!(validate_fence_warning_margin(0.0).is_err());
+        assert!(validate_fence_warning_margin(f32::NAN).is_err());
+        assert!(validate_fence_warning_margin(DEFAULT_FENCE_WARNING_MARGIN_M).is_ok());
+    }
+}
@@ -0,0 +1,339 @@
+use mavkit::AutopilotType;
+
+use crate::ipc::{FlightModeChannelConfig, FlightModeRange};
+
+pub const FLIGHT_MODE_SLOT_COUNT: u8 = 6;
+
+/// ArduPilot's fixed switch bands (RC_Channel::read_6pos_switch):
+/// the upper bound of slots 1..5, slot 6 takes everything above.
+const ARDUPILOT_SLOT_UPPER_PWM: [u16; 5] = [1230, 1360, 1490, 1620, 1749];
+/// PX4 splits the nominal 1000..2000 µs stick range into six equal bands.
+const PX4_PWM_MIN: f32 = 1000.0;
+const PX4_PWM_MAX: f32 = 2000.0;
+
+const PX4_MAIN_MODE_SHIFT: u32 = 16;
+const PX4_SUB_MODE_SHIFT: u32 = 24;
+const PX4_MAIN_MODE_AUTO: u32 = 4;
+
+/// COM_FLTMODEn values and the PX4 custom mode (main mode << 16 | sub mode
+/// << 24) each one selects. -1 leaves the slot unassigned and 9
+/// (Rattitude) was removed from PX4.
+const PX4_FLTMODE_TABLE: &[(i32, u32, u32)] = &[
+    (0, 1, 0),
+    (1, 2, 0),
+    (2, 3, 0),
+    (3, PX4_MAIN_MODE_AUTO, 4),
+    (4, PX4_MAIN_MODE_AUTO, 3),
+    (5, PX4_MAIN_MODE_AUTO, 5),
+    (6, 5, 0),
+    (7, 6, 0),
+    (8, 7, 0),
+    (10, PX4_MAIN_MODE_AUTO, 2),
+    (11, PX4_MAIN_MODE_AUTO, 6),
+    (12, PX4_MAIN_MODE_AUTO, 8),
+    (13, PX4_MAIN_MODE_AUTO, 9),
+];
+
+/// Which parameters hold the mode-switch channel and slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SwitchParams {
+    /// Copter and Plane: FLTMODE_CH, FLTMODE1..6.
+    ArduPilot,
+    /// Rover: MODE_CH, MODE1..6.
+    Rover,
+    /// RC_MAP_FLTMODE, COM_FLTMODE1..6.
+    Px4,
+}
+
+impl SwitchParams {
+    fn detect(
+        autopilot: AutopilotType,
+        param: &impl Fn(&str) -> Option<f32>,
+    ) -> Result<Self, String> {
+        let candidates: &[Self] = match autopilot {
+            AutopilotType::Px4 => &[Self::Px4],
+            AutopilotType::ArduPilotMega => &[Self::ArduPilot, Self::Rover],
+            _ => return Err("mode switch setup is only available for ArduPilot and PX4".into()),
+        };
+        candidates
+            .iter()
+            .copied()
+            .find(|params| param(params.channel_param()).is_some())
+            .ok_or_else(|| {
+                format!(
+                    "{} is not in the downloaded parameters",
+                    candidates[0].channel_param()
+                )
+            })
+    }
+
+    fn channel_param(self) -> &'static str {
+        match self {
+            Self::ArduPilot => "FLTMODE_CH",
+            Self::Rover => "MODE_CH",
+            Self::Px4 => "RC_MAP_FLTMODE",
+        }
+    }
+
+    fn slot_param(self, slot: u8) -> String {
+        match self {
+            Self::ArduPilot => format!("FLTMODE{slot}"),
+            Self::Rover => format!("MODE{slot}"),
+            Self::Px4 => format!("COM_FLTMODE{slot}"),
+        }
+    }
+
+    fn pwm_range(self, slot: u8) -> (u16, u16) {
+        let index = usize::from(slot - 1);
+        match self {
+            Self::ArduPilot | Self::Rover => {
+                let low = index
+                    .checked_sub(1)
+                    .map_or(0, |previous| ARDUPILOT_SLOT_UPPER_PWM[previous] + 1);
+                let high = ARDUPILOT_SLOT_UPPER_PWM
+                    .get(index)
+                    .copied()
+                    .unwrap_or(u16::MAX);
+                (low, high)
+            }
+            Self::Px4 => {
+                let band = (PX4_PWM_MAX - PX4_PWM_MIN) / f32::from(FLIGHT_MODE_SLOT_COUNT);
+                let low = if index == 0 {
+                    0
+                } else {
+                    (PX4_PWM_MIN + band * index as f32).round() as u16
+                };
+                let high = if slot == FLIGHT_MODE_SLOT_COUNT {
+                    u16::MAX
+                } else {
+                    (PX4_PWM_MIN + band * (index + 1) as f32).round() as u16 - 1
+                };
+                (low, high)
+            }
+        }
+    }
+
+    /// The custom mode a slot parameter value selects.
+    fn custom_mode(self, value: f32) -> Option<u32> {
+        let value = value.round() as i32;
+        match self {
+            Self::ArduPilot | Self::Rover => u32::try_from(value).ok(),
+            Self::Px4 => PX4_FLTMODE_TABLE
+                .iter()
+                .find(|(fltmode, _, _)| *fltmode == value)
+                .map(|&(_, main, sub)| px4_custom_mode(main, sub)),
+        }
+    }
+
+    /// The slot parameter value that selects `custom_mode`.
+    fn slot_value(self, custom_mode: u32) -> Option<f32> {
+        match self {
+            Self::ArduPilot | Self::Rover => Some(custom_mode as f32),
+            Self::Px4 => PX4_FLTMODE_TABLE
+                .iter()
+                .find(|&&(_, main, sub)| px4_custom_mode(main, sub) == custom_mode)
+                .map(|(fltmode, _, _)| *fltmode as f32),
+        }
+    }
+}
+
+fn px4_custom_mode(main_mode: u32, sub_mode: u32) -> u32 {
+    (main_mode << PX4_MAIN_MODE_SHIFT) | (sub_mode << PX4_SUB_MODE_SHIFT)
+}
+
+/// The mode-switch channel, the PWM band and mode of every slot, and the
+/// slot the live switch position selects. `param` looks a parameter up in
+/// the downloaded store, `mode_name` names a custom mode from the vehicle's
+/// mode table and `channel_pwm` reads the latest RC_CHANNELS value of a
+/// 1-based channel.
+pub fn flight_mode_channel_config(
+    autopilot: AutopilotType,
+    param: impl Fn(&str) -> Option<f32>,
+    mode_name: impl Fn(u32) -> Option<String>,
+    channel_pwm: impl Fn(u8) -> Option<u16>,
+) -> Result<FlightModeChannelConfig, String> {
+    let params = SwitchParams::detect(autopilot, &param)?;
+    let channel = param(params.channel_param())
+        .map(|value| value.round())
+        .filter(|value| (1.0..=f32::from(u8::MAX)).contains(value))
+        .map(|value| value as u8);
+
+    let ranges: Vec<FlightModeRange> = (1..=FLIGHT_MODE_SLOT_COUNT)
+        .map(|slot| {
+            let slot_param = params.slot_param(slot);
+            let custom_mode = param(&slot_param).and_then(|value| params.custom_mode(value));
+            let (pwm_low, pwm_high) = params.pwm_range(slot);
+            FlightModeRange {
+                slot,
+                param: slot_param,
+                pwm_low,
+                pwm_high,
+                custom_mode,
+                mode: custom_mode.and_then(&mode_name),
+            }
+        })
+        .collect();
+
+    let channel_pwm = channel.and_then(channel_pwm);
+    let active_slot = channel_pwm.and_then(|pwm| {
+        ranges
+            .iter()
+            .find(|range| (range.pwm_low..=range.pwm_high).contains(&pwm))
+            .map(|range| range.slot)
+    });
+
+    Ok(FlightModeChannelConfig {
+        channel_param: params.channel_param().to_string(),
+        channel,
+        ranges,
+        channel_pwm,
+        active_slot,
+    })
+}
+
+/// The parameter write that puts `custom_mode` in switch slot `slot`.
+pub fn flight_mode_slot_write(
+    autopilot: AutopilotType,
+    param: impl Fn(&str) -> Option<f32>,
+    slot: u8,
+    custom_mode: u32,
+) -> Result<(String, f32), String> {
+    if !(1..=FLIGHT_MODE_SLOT_COUNT).contains(&slot) {
+        return Err(format!(
+            "flight mode slot must be 1..={FLIGHT_MODE_SLOT_COUNT}, got {slot}"
+        ));
+    }
+    let params = SwitchParams::detect(autopilot, &param)?;
+    let value = params
+        .slot_value(custom_mode)
+        .ok_or_else(|| format!("mode {custom_mode} cannot be put on the mode switch"))?;
+    Ok((params.slot_param(slot), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn params(values: &[(&str, f32)]) -> impl Fn(&str) -> Option<f32> {
+        let values: HashMap<String, f32> = values
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect();
+        move |name| values.get(name).copied()
+    }
+
+    fn copter_mode_name(custom_mode: u32) -> Option<String> {
+        match custom_mode {
+            0 => Some("STABILIZE".into()),
+            2 => Some("ALT_HOLD".into()),
+            5 => Some("LOITER".into()),
+            6 => Some("RTL".into()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn copter_slots_follow_fltmode_params_and_live_switch() {
+        let config = flight_mode_channel_config(
+            AutopilotType::ArduPilotMega,
+            params(&[
+                ("FLTMODE_CH", 5.0),
+                ("FLTMODE1", 0.0),
+                ("FLTMODE2", 2.0),
+                ("FLTMODE3", 5.0),
+                ("FLTMODE4", 6.0),
+                ("FLTMODE5", 5.0),
+                ("FLTMODE6", 6.0),
+            ]),
+            copter_mode_name,
+            |channel| (channel == 5).then_some(1300),
+        )
+        .expect("config");
+
+        assert_eq!(config.channel, Some(5));
+        assert_eq!(config.channel_pwm, Some(1300));
+        assert_eq!(config.active_slot, Some(2));
+        assert_eq!(
+            config.ranges[1],
+            FlightModeRange {
+                slot: 2,
+                param: "FLTMODE2".into(),
+                pwm_low: 1231,
+                pwm_high: 1360,
+                custom_mode: Some(2),
+                mode: Some("ALT_HOLD".into()),
+            }
+        );
+        assert_eq!(
+            (config.ranges[0].pwm_low, config.ranges[5].pwm_high),
+            (0, u16::MAX)
+        );
+        assert_eq!(config.ranges[5].pwm_low, 1750);
+    }
+
+    #[test]
+    fn rover_uses_mode_params() {
+        let param = params(&[("MODE_CH", 8.0), ("MODE1", 0.0)]);
+        let config =
+            flight_mode_channel_config(AutopilotType::ArduPilotMega, &param, |_| None, |_| None)
+                .expect("config");
+        assert_eq!(config.channel_param, "MODE_CH");
+        assert_eq!(config.ranges[0].param, "MODE1");
+        assert_eq!(config.active_slot, None);
+        assert_eq!(
+            flight_mode_slot_write(AutopilotType::ArduPilotMega, &param, 3, 11),
+            Ok(("MODE3".to_string(), 11.0))
+        );
+    }
+
+    #[test]
+    fn px4_slots_map_com_fltmode_to_custom_modes() {
+        let param = params(&[
+            ("RC_MAP_FLTMODE", 6.0),
+            ("COM_FLTMODE1", 2.0),
+            ("COM_FLTMODE2", -1.0),
+            ("COM_FLTMODE6", 5.0),
+        ]);
+        let config = flight_mode_channel_config(
+            AutopilotType::Px4,
+            &param,
+            |_| None,
+            |channel| (channel == 6).then_some(1950),
+        )
+        .expect("config");
+
+        let position = px4_custom_mode(3, 0);
+        let rtl = px4_custom_mode(PX4_MAIN_MODE_AUTO, 5);
+        assert_eq!(config.ranges[0].custom_mode, Some(position));
+        assert_eq!(config.ranges[1].custom_mode, None);
+        assert_eq!(config.ranges[5].custom_mode, Some(rtl));
+        assert_eq!(config.active_slot, Some(6));
+        assert_eq!(
+            (config.ranges[1].pwm_low, config.ranges[1].pwm_high),
+            (1167, 1332)
+        );
+
+        assert_eq!(
+            flight_mode_slot_write(AutopilotType::Px4, &param, 2, rtl),
+            Ok(("COM_FLTMODE2".to_string(), 5.0))
+        );
+        assert!(flight_mode_slot_write(AutopilotType::Px4, &param, 2, 12345).is_err());
+        assert!(flight_mode_slot_write(AutopilotType::Px4, &param, 7, rtl).is_err());
+    }
+
+    #[test]
+    fn unmapped_channel_has_no_active_slot() {
+        let config = flight_mode_channel_config(
+            AutopilotType::Px4,
+            params(&[("RC_MAP_FLTMODE", 0.0)]),
+            |_| None,
+            |_| Some(1500),
+        )
+        .expect("config");
+        assert_eq!(config.channel, None);
+        assert_eq!(config.channel_pwm, None);
+        assert_eq!(config.active_slot, None);
+    }
+}
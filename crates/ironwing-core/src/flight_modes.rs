@@ -0,0 +1,300 @@
+use mavkit::{AutopilotType, FlightMode, VehicleType};
+
+use crate::ipc::FlightModeCurrent;
+
+/// Modes with no self-levelling or position hold, by firmware. Copter's
+/// Stabilize and Rover's Manual are the everyday manual modes and stay off
+/// these lists; Plane's Manual passes the sticks straight to the surfaces.
+const COPTER_DANGEROUS_MODES: &[&str] = &["ACRO", "FLIP", "SPORT", "DRIFT"];
+const PLANE_DANGEROUS_MODES: &[&str] = &["MANUAL", "ACRO", "TRAINING", "QACRO"];
+const ROVER_DANGEROUS_MODES: &[&str] = &["ACRO"];
+const SUB_DANGEROUS_MODES: &[&str] = &["ACRO"];
+const PX4_MULTICOPTER_DANGEROUS_MODES: &[&str] = &["ACRO", "RATTITUDE"];
+const PX4_FIXED_WING_DANGEROUS_MODES: &[&str] = &["MANUAL", "ACRO", "RATTITUDE"];
+
+/// The danger lists are matched on letters and digits only, so they cover
+/// every spelling a firmware or mavkit version uses ("ALT_HOLD", "AltHold").
+pub(crate) fn normalized(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+pub fn is_fixed_wing(vehicle_type: VehicleType) -> bool {
+    matches!(vehicle_type, VehicleType::FixedWing | VehicleType::Vtol)
+}
+
+pub fn is_ground_vehicle(vehicle_type: VehicleType) -> bool {
+    matches!(vehicle_type, VehicleType::GroundRover)
+}
+
+fn dangerous_modes(autopilot: AutopilotType, vehicle_type: VehicleType) -> &'static [&'static str] {
+    match (autopilot, vehicle_type) {
+        (AutopilotType::Px4, vehicle_type) if is_fixed_wing(vehicle_type) => {
+            PX4_FIXED_WING_DANGEROUS_MODES
+        }
+        (AutopilotType::Px4, _) => PX4_MULTICOPTER_DANGEROUS_MODES,
+        (_, vehicle_type) if is_fixed_wing(vehicle_type) => PLANE_DANGEROUS_MODES,
+        (_, vehicle_type) if is_ground_vehicle(vehicle_type) => ROVER_DANGEROUS_MODES,
+        (_, VehicleType::Submarine) => SUB_DANGEROUS_MODES,
+        _ => COPTER_DANGEROUS_MODES,
+    }
+}
+
+pub fn is_dangerous_mode(autopilot: AutopilotType, vehicle_type: VehicleType, name: &str) -> bool {
+    let name = normalized(name);
+    dangerous_modes(autopilot, vehicle_type)
+        .iter()
+        .any(|dangerous| normalized(dangerous) == name)
+}
+
+fn mode_list<'a>(modes: impl IntoIterator<Item = &'a FlightMode>) -> String {
+    modes
+        .into_iter()
+        .map(|mode| mode.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The mode in `modes` (the vehicle's `available_modes()`) called `name`,
+/// ignoring ASCII case only. Near misses are refused rather than guessed, so
+/// a typo can never switch to some other mode.
+pub fn resolve_mode_by_name<'a>(
+    modes: &'a [FlightMode],
+    name: &str,
+) -> Result<&'a FlightMode, String> {
+    if name.trim().is_empty() {
+        return Err("flight mode name is empty".to_string());
+    }
+    let mut matches: Vec<&FlightMode> = modes
+        .iter()
+        .filter(|mode| mode.name.eq_ignore_ascii_case(name))
+        .collect();
+    // A table listing one custom_mode under two names is still one mode.
+    matches.sort_by_key(|mode| mode.custom_mode);
+    matches.dedup_by_key(|mode| mode.custom_mode);
+    match matches.as_slice() {
+        [mode] => Ok(mode),
+        [] => Err(format!(
+            "unknown flight mode \"{name}\"; this vehicle has: {}",
+            mode_list(modes)
+        )),
+        _ => Err(format!(
+            "flight mode \"{name}\" is ambiguous: it matches {}",
+            mode_list(matches)
+        )),
+    }
+}
+
+pub fn current_flight_mode(
+    modes: &[FlightMode],
+    custom_mode: u32,
+    autopilot: AutopilotType,
+    vehicle_type: VehicleType,
+) -> FlightModeCurrent {
+    let name = modes
+        .iter()
+        .find(|mode| mode.custom_mode == custom_mode)
+        .map(|mode| mode.name.clone());
+    let dangerous = name
+        .as_deref()
+        .is_some_and(|name| is_dangerous_mode(autopilot, vehicle_type, name));
+    FlightModeCurrent {
+        custom_mode,
+        name,
+        dangerous,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(entries: &[(u32, &str)]) -> Vec<FlightMode> {
+        entries
+            .iter()
+            .map(|(custom_mode, name)| FlightMode {
+                custom_mode: *custom_mode,
+                name: name.to_string(),
+            })
+            .collect()
+    }
+
+    fn copter() -> Vec<FlightMode> {
+        table(&[
+            (0, "STABILIZE"),
+            (1, "ACRO"),
+            (2, "ALT_HOLD"),
+            (3, "AUTO"),
+            (4, "GUIDED"),
+            (5, "LOITER"),
+            (6, "RTL"),
+            (7, "CIRCLE"),
+            (9, "LAND"),
+            (11, "DRIFT"),
+            (13, "SPORT"),
+            (14, "FLIP"),
+            (16, "POSHOLD"),
+            (21, "SMART_RTL"),
+        ])
+    }
+
+    fn plane() -> Vec<FlightMode> {
+        table(&[
+            (0, "MANUAL"),
+            (1, "CIRCLE"),
+            (2, "STABILIZE"),
+            (3, "TRAINING"),
+            (4, "ACRO"),
+            (5, "FBWA"),
+            (6, "FBWB"),
+            (10, "AUTO"),
+            (11, "RTL"),
+            (12, "LOITER"),
+            (15, "GUIDED"),
+            (17, "QSTABILIZE"),
+            (19, "QLOITER"),
+            (21, "QRTL"),
+            (23, "QACRO"),
+        ])
+    }
+
+    fn rover() -> Vec<FlightMode> {
+        table(&[
+            (0, "MANUAL"),
+            (1, "ACRO"),
+            (3, "STEERING"),
+            (4, "HOLD"),
+            (5, "LOITER"),
+            (10, "AUTO"),
+            (11, "RTL"),
+            (12, "SMART_RTL"),
+            (15, "GUIDED"),
+        ])
+    }
+
+    #[test]
+    fn names_resolve_exactly_ignoring_case() {
+        let modes = copter();
+        let resolve = |name| resolve_mode_by_name(&modes, name).map(|mode| mode.custom_mode);
+        assert_eq!(resolve("LOITER"), Ok(5));
+        assert_eq!(resolve("alt_hold"), Ok(2));
+        assert_eq!(resolve("rtl"), Ok(6));
+        assert_eq!(resolve("Smart_RTL"), Ok(21));
+        // No prefixes or respellings: each of these is refused.
+        assert!(resolve("POS").is_err());
+        assert!(resolve("AltHold").is_err());
+        assert!(resolve("alt hold").is_err());
+    }
+
+    #[test]
+    fn plane_and_rover_use_their_own_numbers() {
+        let plane = plane();
+        let rover = rover();
+        assert_eq!(
+            resolve_mode_by_name(&plane, "RTL").map(|mode| mode.custom_mode),
+            Ok(11)
+        );
+        assert_eq!(
+            resolve_mode_by_name(&rover, "RTL").map(|mode| mode.custom_mode),
+            Ok(11)
+        );
+        assert_eq!(
+            resolve_mode_by_name(&plane, "qloiter").map(|mode| mode.custom_mode),
+            Ok(19)
+        );
+        assert_eq!(
+            resolve_mode_by_name(&rover, "Hold").map(|mode| mode.custom_mode),
+            Ok(4)
+        );
+        assert!(resolve_mode_by_name(&plane, "HOLD").is_err());
+        assert!(resolve_mode_by_name(&rover, "FBWA").is_err());
+    }
+
+    #[test]
+    fn unknown_and_ambiguous_names_are_refused() {
+        let modes = copter();
+        let unknown = resolve_mode_by_name(&modes, "FBWA").expect_err("unknown");
+        assert!(unknown.contains("STABILIZE, ACRO, ALT_HOLD"));
+        assert!(resolve_mode_by_name(&plane(), "FBW").is_err());
+        assert!(resolve_mode_by_name(&modes, "  ").is_err());
+
+        let aliased = table(&[(5, "LOITER"), (5, "Loiter")]);
+        assert_eq!(
+            resolve_mode_by_name(&aliased, "loiter").map(|mode| mode.custom_mode),
+            Ok(5)
+        );
+        let clashing = table(&[(5, "LOITER"), (6, "Loiter")]);
+        let ambiguous = resolve_mode_by_name(&clashing, "loiter").expect_err("ambiguous");
+        assert!(ambiguous.contains("LOITER, Loiter"));
+    }
+
+    #[test]
+    fn danger_depends_on_the_firmware() {
+        let ardupilot = AutopilotType::ArduPilotMega;
+        assert!(is_dangerous_mode(ardupilot, VehicleType::Quadrotor, "Acro"));
+        assert!(is_dangerous_mode(ardupilot, VehicleType::Hexarotor, "FLIP"));
+        assert!(!is_dangerous_mode(
+            ardupilot,
+            VehicleType::Quadrotor,
+            "STABILIZE"
+        ));
+        assert!(is_dangerous_mode(
+            ardupilot,
+            VehicleType::FixedWing,
+            "MANUAL"
+        ));
+        assert!(!is_dangerous_mode(
+            ardupilot,
+            VehicleType::FixedWing,
+            "FBWA"
+        ));
+        assert!(!is_dangerous_mode(
+            ardupilot,
+            VehicleType::GroundRover,
+            "MANUAL"
+        ));
+        assert!(is_dangerous_mode(
+            ardupilot,
+            VehicleType::GroundRover,
+            "ACRO"
+        ));
+        assert!(!is_dangerous_mode(
+            AutopilotType::Px4,
+            VehicleType::Quadrotor,
+            "MANUAL"
+        ));
+        assert!(is_dangerous_mode(
+            AutopilotType::Px4,
+            VehicleType::FixedWing,
+            "MANUAL"
+        ));
+    }
+
+    #[test]
+    fn current_mode_is_decoded_from_the_table() {
+        let current = current_flight_mode(
+            &copter(),
+            1,
+            AutopilotType::ArduPilotMega,
+            VehicleType::Quadrotor,
+        );
+        assert_eq!(
+            current,
+            FlightModeCurrent {
+                custom_mode: 1,
+                name: Some("ACRO".into()),
+                dangerous: true,
+            }
+        );
+        let unknown = current_flight_mode(
+            &plane(),
+            99,
+            AutopilotType::ArduPilotMega,
+            VehicleType::FixedWing,
+        );
+        assert_eq!(unknown.name, None);
+        assert!(!unknown.dangerous);
+    }
+}
@@ -0,0 +1,165 @@
+use mavkit::HomePosition;
+
+use crate::ipc::flight_summary::FlightSessionSummary;
+use crate::ipc::telemetry::TelemetryState;
+use crate::log_engine::haversine_m;
+
+/// File name, under the app data directory, of the JSON-lines summary history.
+pub const FLIGHT_SUMMARIES_FILE_NAME: &str = "flight_summaries.jsonl";
+
+/// Accumulates flight aggregates from telemetry samples between arm and
+/// disarm.
+#[derive(Debug, Clone)]
+pub struct FlightSummaryTracker {
+    started_at_usec: u64,
+    max_altitude_m: Option<f64>,
+    max_distance_from_home_m: Option<f64>,
+    max_speed_mps: Option<f64>,
+    first_battery_pct: Option<f64>,
+    last_battery_pct: Option<f64>,
+    first_energy_wh: Option<f64>,
+    last_energy_wh: Option<f64>,
+    distance_flown_m: f64,
+    last_position: Option<(f64, f64)>,
+}
+
+impl FlightSummaryTracker {
+    pub fn start(started_at_usec: u64) -> Self {
+        Self {
+            started_at_usec,
+            max_altitude_m: None,
+            max_distance_from_home_m: None,
+            max_speed_mps: None,
+            first_battery_pct: None,
+            last_battery_pct: None,
+            first_energy_wh: None,
+            last_energy_wh: None,
+            distance_flown_m: 0.0,
+            last_position: None,
+        }
+    }
+
+    /// Fold one telemetry sample in. Distance from home is only tracked once
+    /// a home position is known.
+    pub fn observe(&mut self, telemetry: &TelemetryState, home: Option<&HomePosition>) {
+        self.max_altitude_m = max_of(self.max_altitude_m, telemetry.flight.altitude_m);
+        self.max_speed_mps = max_of(self.max_speed_mps, telemetry.flight.speed_mps);
+
+        if let Some(pct) = telemetry.power.battery_pct {
+            self.first_battery_pct.get_or_insert(pct);
+            self.last_battery_pct = Some(pct);
+        }
+        if let Some(energy) = telemetry.power.energy_consumed_wh {
+            self.first_energy_wh.get_or_insert(energy);
+            self.last_energy_wh = Some(energy);
+        }
+
+        let (Some(lat), Some(lon)) = (
+            telemetry.navigation.latitude_deg,
+            telemetry.navigation.longitude_deg,
+        ) else {
+            return;
+        };
+        if lat.abs() < 1e-6 && lon.abs() < 1e-6 {
+            return;
+        }
+        if let Some((prev_lat, prev_lon)) = self.last_position {
+            self.distance_flown_m += haversine_m(prev_lat, prev_lon, lat, lon);
+        }
+        self.last_position = Some((lat, lon));
+        if let Some(home) = home {
+            let distance = haversine_m(home.latitude_deg, home.longitude_deg, lat, lon);
+            self.max_distance_from_home_m = max_of(self.max_distance_from_home_m, Some(distance));
+        }
+    }
+
+    pub fn finish(self, ended_at_usec: u64) -> FlightSessionSummary {
+        let ended_at_usec = ended_at_usec.max(self.started_at_usec);
+        FlightSessionSummary {
+            started_at_usec: self.started_at_usec,
+            ended_at_usec,
+            duration_s: (ended_at_usec - self.started_at_usec) as f64 / 1e6,
+            max_altitude_m: self.max_altitude_m,
+            max_distance_from_home_m: self.max_distance_from_home_m,
+            max_speed_mps: self.max_speed_mps,
+            battery_used_pct: self
+                .first_battery_pct
+                .zip(self.last_battery_pct)
+                .map(|(first, last)| (first - last).max(0.0)),
+            energy_used_wh: self
+                .first_energy_wh
+                .zip(self.last_energy_wh)
+                .map(|(first, last)| (last - first).max(0.0)),
+            distance_flown_m: self.distance_flown_m,
+        }
+    }
+}
+
+fn max_of(current: Option<f64>, value: Option<f64>) -> Option<f64> {
+    match (current, value) {
+        (Some(current), Some(value)) => Some(current.max(value)),
+        (current, value) => current.or(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::telemetry::{TelemetryFlight, TelemetryNavigation, TelemetryPower};
+
+    fn sample(lat: f64, lon: f64, altitude_m: f64, battery_pct: f64) -> TelemetryState {
+        TelemetryState {
+            flight: TelemetryFlight {
+                altitude_m: Some(altitude_m),
+                speed_mps: Some(altitude_m / 10.0),
+                ..TelemetryFlight::default()
+            },
+            navigation: TelemetryNavigation {
+                latitude_deg: Some(lat),
+                longitude_deg: Some(lon),
+                ..TelemetryNavigation::default()
+            },
+            power: TelemetryPower {
+                battery_pct: Some(battery_pct),
+                ..TelemetryPower::default()
+            },
+            ..TelemetryState::default()
+        }
+    }
+
+    #[test]
+    fn summary_aggregates_armed_period() {
+        let home = HomePosition {
+            latitude_deg: 47.0,
+            longitude_deg: 8.0,
+            altitude_m: 500.0,
+        };
+        let mut tracker = FlightSummaryTracker::start(1_000_000);
+        tracker.observe(&sample(47.0, 8.0, 0.0, 95.0), Some(&home));
+        tracker.observe(&sample(47.001, 8.0, 30.0, 90.0), Some(&home));
+        tracker.observe(&sample(47.0, 8.0, 5.0, 82.0), Some(&home));
+
+        let summary = tracker.finish(61_000_000);
+
+        assert_eq!(summary.duration_s, 60.0);
+        assert_eq!(summary.max_altitude_m, Some(30.0));
+        assert_eq!(summary.max_speed_mps, Some(3.0));
+        assert_eq!(summary.battery_used_pct, Some(13.0));
+        assert_eq!(summary.energy_used_wh, None);
+        let out = summary.max_distance_from_home_m.expect("home known");
+        assert!((out - 111.19).abs() < 0.1, "{out}");
+        assert!((summary.distance_flown_m - 2.0 * out).abs() < 1e-6);
+    }
+
+    #[test]
+    fn missing_home_leaves_distance_from_home_empty() {
+        let mut tracker = FlightSummaryTracker::start(0);
+        tracker.observe(&sample(47.0, 8.0, 10.0, 50.0), None);
+        tracker.observe(&sample(47.001, 8.0, 10.0, 50.0), None);
+
+        let summary = tracker.finish(0);
+
+        assert_eq!(summary.max_distance_from_home_m, None);
+        assert!(summary.distance_flown_m > 100.0);
+    }
+}
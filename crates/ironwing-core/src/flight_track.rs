@@ -0,0 +1,238 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::dialect::GLOBAL_POSITION_INT_DATA;
+use crate::ipc::TrackPoint;
+use crate::log_engine::{bounded_max_points, downsample_if_needed};
+
+pub const GLOBAL_POSITION_INT_MESSAGE_ID: u32 = 33;
+
+/// File name, under the app data directory, of the JSON-lines track of the
+/// most recent live session.
+pub const LAST_FLIGHT_TRACK_FILE_NAME: &str = "last_flight_track.jsonl";
+
+pub const DEFAULT_TRACK_MAX_POINTS: u32 = 50_000;
+
+/// How often points recorded since the last batch go out as
+/// `track://append`.
+pub const TRACK_APPEND_INTERVAL: Duration = Duration::from_secs(2);
+
+const DEFAULT_TRACK_QUERY_POINTS: usize = 5_000;
+
+/// `None` for the all-zero position the autopilot reports before it has a
+/// fix.
+pub fn track_point_from_global_position(
+    data: &GLOBAL_POSITION_INT_DATA,
+    timestamp_usec: u64,
+) -> Option<TrackPoint> {
+    if data.lat == 0 && data.lon == 0 {
+        return None;
+    }
+    Some(TrackPoint {
+        timestamp_usec,
+        latitude_deg: f64::from(data.lat) / 1e7,
+        longitude_deg: f64::from(data.lon) / 1e7,
+        altitude_msl_m: f64::from(data.alt) / 1000.0,
+        relative_alt_m: f64::from(data.relative_alt) / 1000.0,
+        heading_deg: (data.hdg != u16::MAX).then(|| f64::from(data.hdg) / 100.0),
+    })
+}
+
+/// Points recorded since the previous batch. `restart` is set when the track
+/// was cleared or replaced in between, so a persisted copy has to be
+/// rewritten rather than appended to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackBatch {
+    pub points: Vec<TrackPoint>,
+    pub restart: bool,
+}
+
+/// Ring buffer of the live vehicle's positions, dropping the oldest point
+/// once `max_points` is reached.
+#[derive(Debug, Clone)]
+pub struct FlightTrack {
+    points: VecDeque<TrackPoint>,
+    max_points: usize,
+    pending: Vec<TrackPoint>,
+    restart: bool,
+    restored: bool,
+}
+
+impl FlightTrack {
+    pub fn new(max_points: usize) -> Self {
+        Self {
+            points: VecDeque::new(),
+            max_points: max_points.max(1),
+            pending: Vec::new(),
+            restart: false,
+            restored: false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn set_max_points(&mut self, max_points: usize) {
+        self.max_points = max_points.max(1);
+        self.trim();
+    }
+
+    /// Append a live sample. A track restored from disk belongs to the
+    /// previous session, so the first live sample starts over.
+    pub fn push(&mut self, point: TrackPoint) {
+        if self.restored {
+            self.points.clear();
+            self.restored = false;
+            self.restart = true;
+        }
+        self.points.push_back(point.clone());
+        self.pending.push(point);
+        self.trim();
+    }
+
+    /// Points newer than `since_usec`, downsampled to at most `max_points`
+    /// the same way log chart queries are.
+    pub fn since(&self, since_usec: Option<u64>, max_points: Option<usize>) -> Vec<TrackPoint> {
+        let max_points =
+            bounded_max_points(max_points, DEFAULT_TRACK_QUERY_POINTS, self.max_points);
+        let points = self
+            .points
+            .iter()
+            .filter(|point| since_usec.is_none_or(|since| point.timestamp_usec > since))
+            .cloned()
+            .collect();
+        downsample_if_needed(points, max_points)
+    }
+
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.pending.clear();
+        self.restored = false;
+        self.restart = true;
+    }
+
+    /// Seed the buffer with the track an earlier run persisted, for review
+    /// until the next live sample arrives.
+    pub fn restore(&mut self, points: Vec<TrackPoint>) {
+        self.points = points.into();
+        self.pending.clear();
+        self.restored = true;
+        self.trim();
+    }
+
+    /// Drain the points recorded since the previous call, or `None` when
+    /// nothing changed.
+    pub fn take_batch(&mut self) -> Option<TrackBatch> {
+        if self.pending.is_empty() && !self.restart {
+            return None;
+        }
+        let batch = TrackBatch {
+            points: std::mem::take(&mut self.pending),
+            restart: self.restart,
+        };
+        self.restart = false;
+        Some(batch)
+    }
+
+    fn trim(&mut self) {
+        while self.points.len() > self.max_points {
+            self.points.pop_front();
+        }
+    }
+}
+
+impl Default for FlightTrack {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRACK_MAX_POINTS as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(timestamp_usec: u64) -> TrackPoint {
+        TrackPoint {
+            timestamp_usec,
+            latitude_deg: 47.0,
+            longitude_deg: 8.0 + timestamp_usec as f64 * 1e-6,
+            altitude_msl_m: 500.0,
+            relative_alt_m: 20.0,
+            heading_deg: None,
+        }
+    }
+
+    #[test]
+    fn ring_drops_oldest_points_and_since_downsamples() {
+        let mut track = FlightTrack::new(100);
+        for ts in 0..150 {
+            track.push(point(ts));
+        }
+
+        assert_eq!(track.len(), 100);
+        let all = track.since(None, None);
+        assert_eq!(all.first().map(|p| p.timestamp_usec), Some(50));
+        assert_eq!(all.last().map(|p| p.timestamp_usec), Some(149));
+
+        let newer = track.since(Some(139), None);
+        assert_eq!(newer.len(), 10);
+
+        let sampled = track.since(None, Some(10));
+        assert_eq!(sampled.len(), 10);
+        assert_eq!(sampled.first().map(|p| p.timestamp_usec), Some(50));
+        assert_eq!(sampled.last().map(|p| p.timestamp_usec), Some(149));
+    }
+
+    #[test]
+    fn batches_drain_pending_points_and_flag_restarts() {
+        let mut track = FlightTrack::new(100);
+        track.restore(vec![point(1), point(2)]);
+        assert_eq!(track.take_batch(), None);
+        assert_eq!(track.len(), 2);
+
+        track.push(point(10));
+        track.push(point(11));
+        let batch = track.take_batch().expect("batch");
+        assert!(batch.restart);
+        assert_eq!(batch.points, vec![point(10), point(11)]);
+        assert_eq!(track.since(None, None), vec![point(10), point(11)]);
+        assert_eq!(track.take_batch(), None);
+
+        track.clear();
+        assert_eq!(
+            track.take_batch(),
+            Some(TrackBatch {
+                points: Vec::new(),
+                restart: true,
+            })
+        );
+    }
+
+    #[test]
+    fn global_position_without_fix_is_skipped() {
+        let mut data = GLOBAL_POSITION_INT_DATA {
+            time_boot_ms: 0,
+            lat: 0,
+            lon: 0,
+            alt: 100_000,
+            relative_alt: 0,
+            vx: 0,
+            vy: 0,
+            vz: 0,
+            hdg: u16::MAX,
+        };
+        assert_eq!(track_point_from_global_position(&data, 1), None);
+
+        data.lat = 471_234_567;
+        data.lon = 81_234_567;
+        let point = track_point_from_global_position(&data, 1).expect("point");
+        assert!((point.latitude_deg - 47.1234567).abs() < 1e-9);
+        assert_eq!(point.altitude_msl_m, 100.0);
+        assert_eq!(point.heading_deg, None);
+    }
+}
@@ -0,0 +1,265 @@
+use mavkit::{AutopilotType, VehicleType};
+
+use crate::ipc::{FrameOption, FrameParam};
+
+const ARDUPILOT_COPTER_FRAMES: &str = include_str!("../data/frames/ardupilot_copter.json");
+const PX4_AIRFRAMES: &str = include_str!("../data/frames/px4_airframes.json");
+
+const FRAME_CLASS: &str = "FRAME_CLASS";
+const FRAME_TYPE: &str = "FRAME_TYPE";
+/// QuadPlanes keep the VTOL motor frame under a `Q_` prefix.
+const QUADPLANE_PREFIX: &str = "Q_";
+const SYS_AUTOSTART: &str = "SYS_AUTOSTART";
+/// Makes PX4 reset the airframe's defaults on the next boot; without it a
+/// new SYS_AUTOSTART keeps the old frame's tuning.
+const SYS_AUTOCONFIG: &str = "SYS_AUTOCONFIG";
+
+#[derive(serde::Deserialize)]
+struct CopterFrameEntry {
+    frame_class: u8,
+    frame_type: u8,
+    group: String,
+    name: String,
+    motor_layout: String,
+    motors: u8,
+}
+
+#[derive(serde::Deserialize)]
+struct Px4AirframeEntry {
+    autostart: u32,
+    group: String,
+    name: String,
+    motor_layout: Option<String>,
+    motors: u8,
+}
+
+fn param(name: String, value: f32) -> FrameParam {
+    FrameParam { name, value }
+}
+
+fn ardupilot_frames(prefix: &str) -> Vec<FrameOption> {
+    let entries: Vec<CopterFrameEntry> =
+        serde_json::from_str(ARDUPILOT_COPTER_FRAMES).expect("bundled ArduPilot frame list");
+    entries
+        .into_iter()
+        .map(|entry| FrameOption {
+            id: format!("ardupilot:{}:{}", entry.frame_class, entry.frame_type),
+            name: entry.name,
+            group: entry.group,
+            motor_layout: Some(entry.motor_layout),
+            motor_count: entry.motors,
+            params: vec![
+                param(
+                    format!("{prefix}{FRAME_CLASS}"),
+                    f32::from(entry.frame_class),
+                ),
+                param(format!("{prefix}{FRAME_TYPE}"), f32::from(entry.frame_type)),
+            ],
+            current: false,
+        })
+        .collect()
+}
+
+fn px4_airframes() -> Vec<FrameOption> {
+    let entries: Vec<Px4AirframeEntry> =
+        serde_json::from_str(PX4_AIRFRAMES).expect("bundled PX4 airframe list");
+    entries
+        .into_iter()
+        .map(|entry| FrameOption {
+            id: format!("px4:{}", entry.autostart),
+            name: entry.name,
+            group: entry.group,
+            motor_layout: entry.motor_layout,
+            motor_count: entry.motors,
+            params: vec![
+                param(SYS_AUTOSTART.to_string(), entry.autostart as f32),
+                param(SYS_AUTOCONFIG.to_string(), 1.0),
+            ],
+            current: false,
+        })
+        .collect()
+}
+
+/// Whether the vehicle's parameters already select `option`. SYS_AUTOCONFIG
+/// is a one-shot request the autopilot clears after boot, so it is not
+/// compared.
+fn is_current(option: &FrameOption, param: &impl Fn(&str) -> Option<f32>) -> bool {
+    option
+        .params
+        .iter()
+        .filter(|frame_param| frame_param.name != SYS_AUTOCONFIG)
+        .all(|frame_param| {
+            param(&frame_param.name).is_some_and(|value| value.round() == frame_param.value.round())
+        })
+}
+
+/// The frames the connected vehicle can be switched to: ArduCopter (or
+/// QuadPlane VTOL) FRAME_CLASS/FRAME_TYPE pairs, or PX4 SYS_AUTOSTART
+/// airframes. `param` looks a parameter up in the downloaded store.
+pub fn frame_options(
+    autopilot: AutopilotType,
+    vehicle_type: VehicleType,
+    param: impl Fn(&str) -> Option<f32>,
+) -> Result<Vec<FrameOption>, String> {
+    let mut options = match autopilot {
+        AutopilotType::Px4 => {
+            if param(SYS_AUTOSTART).is_none() {
+                return Err(format!(
+                    "{SYS_AUTOSTART} is not in the downloaded parameters"
+                ));
+            }
+            px4_airframes()
+        }
+        AutopilotType::ArduPilotMega => {
+            if matches!(
+                vehicle_type,
+                VehicleType::GroundRover | VehicleType::Submarine
+            ) {
+                return Err("frame setup is only available for multirotors and QuadPlanes".into());
+            }
+            if param(FRAME_CLASS).is_some() {
+                ardupilot_frames("")
+            } else if param(&format!("{QUADPLANE_PREFIX}{FRAME_CLASS}")).is_some() {
+                ardupilot_frames(QUADPLANE_PREFIX)
+            } else {
+                return Err(format!(
+                    "neither {FRAME_CLASS} nor {QUADPLANE_PREFIX}{FRAME_CLASS} is in the downloaded parameters; enable Q_ENABLE on planes first"
+                ));
+            }
+        }
+        _ => return Err("frame setup is only available for ArduPilot and PX4".into()),
+    };
+    for option in &mut options {
+        option.current = is_current(option, &param);
+    }
+    Ok(options)
+}
+
+/// The parameter writes that select `option`, in the order they should be
+/// sent.
+pub fn frame_param_writes(option: &FrameOption) -> Vec<(String, f32)> {
+    option
+        .params
+        .iter()
+        .map(|frame_param| (frame_param.name.clone(), frame_param.value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    /// Parameter table standing in for a vehicle: writes land in the table
+    /// the way an acknowledged PARAM_SET updates the downloaded store.
+    struct MockVehicle {
+        params: HashMap<String, f32>,
+    }
+
+    impl MockVehicle {
+        fn new(values: &[(&str, f32)]) -> Self {
+            Self {
+                params: values
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), *value))
+                    .collect(),
+            }
+        }
+
+        fn write_batch(&mut self, writes: Vec<(String, f32)>) {
+            for (name, value) in writes {
+                self.params.insert(name, value);
+            }
+        }
+
+        fn param(&self) -> impl Fn(&str) -> Option<f32> + '_ {
+            |name| self.params.get(name).copied()
+        }
+    }
+
+    fn assert_round_trip(
+        autopilot: AutopilotType,
+        vehicle_type: VehicleType,
+        mut vehicle: MockVehicle,
+    ) {
+        let options = frame_options(autopilot, vehicle_type, vehicle.param()).expect("options");
+        assert!(!options.is_empty());
+        for option in options {
+            vehicle.write_batch(frame_param_writes(&option));
+            let current: Vec<String> = frame_options(autopilot, vehicle_type, vehicle.param())
+                .expect("options")
+                .into_iter()
+                .filter(|option| option.current)
+                .map(|option| option.id)
+                .collect();
+            assert_eq!(current, vec![option.id.clone()], "{}", option.name);
+        }
+    }
+
+    #[test]
+    fn every_copter_frame_round_trips_through_apply() {
+        assert_round_trip(
+            AutopilotType::ArduPilotMega,
+            VehicleType::Quadrotor,
+            MockVehicle::new(&[(FRAME_CLASS, 0.0), (FRAME_TYPE, 0.0)]),
+        );
+    }
+
+    #[test]
+    fn quadplane_frames_use_q_prefixed_params() {
+        let vehicle = MockVehicle::new(&[("Q_FRAME_CLASS", 1.0), ("Q_FRAME_TYPE", 1.0)]);
+        let options = frame_options(
+            AutopilotType::ArduPilotMega,
+            VehicleType::Vtol,
+            vehicle.param(),
+        )
+        .expect("options");
+        let quad_x = options
+            .iter()
+            .find(|option| option.id == "ardupilot:1:1")
+            .expect("quad x");
+        assert!(quad_x.current);
+        assert_eq!(quad_x.motor_layout.as_deref(), Some("quad_x"));
+        assert_eq!(
+            frame_param_writes(quad_x),
+            vec![
+                ("Q_FRAME_CLASS".to_string(), 1.0),
+                ("Q_FRAME_TYPE".to_string(), 1.0),
+            ]
+        );
+
+        assert_round_trip(AutopilotType::ArduPilotMega, VehicleType::Vtol, vehicle);
+    }
+
+    #[test]
+    fn every_px4_airframe_round_trips_through_apply() {
+        assert_round_trip(
+            AutopilotType::Px4,
+            VehicleType::Quadrotor,
+            MockVehicle::new(&[(SYS_AUTOSTART, 0.0), (SYS_AUTOCONFIG, 0.0)]),
+        );
+    }
+
+    #[test]
+    fn vehicles_without_frame_params_are_refused() {
+        let plane = MockVehicle::new(&[("Q_ENABLE", 0.0)]);
+        assert!(
+            frame_options(
+                AutopilotType::ArduPilotMega,
+                VehicleType::FixedWing,
+                plane.param()
+            )
+            .is_err()
+        );
+        let rover = MockVehicle::new(&[(FRAME_CLASS, 1.0)]);
+        assert!(
+            frame_options(
+                AutopilotType::ArduPilotMega,
+                VehicleType::GroundRover,
+                rover.param()
+            )
+            .is_err()
+        );
+    }
+}
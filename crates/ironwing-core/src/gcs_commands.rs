@@ -0,0 +1,275 @@
+use crate::dialect::{
+    COMMAND_ACK_DATA, COMMAND_LONG_DATA, GLOBAL_POSITION_INT_DATA, HEARTBEAT_DATA, MavAutopilot,
+    MavCmd, MavMessage, MavResult, MavState, MavType,
+};
+use crate::flight_track::GLOBAL_POSITION_INT_MESSAGE_ID;
+use crate::ipc::{GcsCommandReceived, GcsIdentity};
+use crate::preflight_position::PositionFix;
+
+pub const COMMAND_LONG_MESSAGE_ID: u32 = 76;
+const HEARTBEAT_MESSAGE_ID: u32 = 0;
+
+/// What the GCS does with a received COMMAND_LONG.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GcsCommandAction {
+    /// Addressed to another system or component, or broadcast.
+    Ignore,
+    /// MAV_CMD_REQUEST_MESSAGE for HEARTBEAT.
+    SendHeartbeat,
+    /// MAV_CMD_REQUEST_MESSAGE for GLOBAL_POSITION_INT of the GCS itself.
+    SendPosition,
+    /// ACK MAV_RESULT_UNSUPPORTED and report it.
+    Unsupported,
+}
+
+/// Commands to our system and either our component or all of them. Commands
+/// to every system are left to the vehicles they are meant for.
+fn addressed_to(command: &COMMAND_LONG_DATA, gcs: GcsIdentity) -> bool {
+    command.target_system == gcs.system_id
+        && (command.target_component == gcs.component_id || command.target_component == 0)
+}
+
+pub fn classify_gcs_command(command: &COMMAND_LONG_DATA, gcs: GcsIdentity) -> GcsCommandAction {
+    if !addressed_to(command, gcs) {
+        return GcsCommandAction::Ignore;
+    }
+    if command.command != MavCmd::MAV_CMD_REQUEST_MESSAGE {
+        return GcsCommandAction::Unsupported;
+    }
+    match command.param1 {
+        id if id == HEARTBEAT_MESSAGE_ID as f32 => GcsCommandAction::SendHeartbeat,
+        id if id == GLOBAL_POSITION_INT_MESSAGE_ID as f32 => GcsCommandAction::SendPosition,
+        _ => GcsCommandAction::Unsupported,
+    }
+}
+
+/// COMMAND_ACK back to whoever sent `command`.
+pub fn gcs_command_ack(
+    command: &COMMAND_LONG_DATA,
+    source_system: u8,
+    source_component: u8,
+    result: MavResult,
+) -> MavMessage {
+    MavMessage::COMMAND_ACK(COMMAND_ACK_DATA {
+        command: command.command,
+        result,
+        target_system: source_system,
+        target_component: source_component,
+        ..COMMAND_ACK_DATA::default()
+    })
+}
+
+/// Everything sent back for a classified command: its COMMAND_ACK and, when
+/// accepted, the requested message. `position` is the GCS fix for
+/// GLOBAL_POSITION_INT requests; without one the request fails on devices
+/// that have a location of their own (`device_location`) and is unsupported
+/// elsewhere.
+pub fn gcs_command_replies(
+    command: &COMMAND_LONG_DATA,
+    source: (u8, u8),
+    action: GcsCommandAction,
+    position: Option<&PositionFix>,
+    device_location: bool,
+    time_boot_ms: u32,
+) -> Vec<MavMessage> {
+    let (result, reply) = match action {
+        GcsCommandAction::Ignore => return Vec::new(),
+        GcsCommandAction::SendHeartbeat => (MavResult::MAV_RESULT_ACCEPTED, Some(gcs_heartbeat())),
+        GcsCommandAction::SendPosition => match position {
+            Some(position) => (
+                MavResult::MAV_RESULT_ACCEPTED,
+                Some(gcs_global_position_int(position, time_boot_ms)),
+            ),
+            None if device_location => (MavResult::MAV_RESULT_FAILED, None),
+            None => (MavResult::MAV_RESULT_UNSUPPORTED, None),
+        },
+        GcsCommandAction::Unsupported => (MavResult::MAV_RESULT_UNSUPPORTED, None),
+    };
+    std::iter::once(gcs_command_ack(command, source.0, source.1, result))
+        .chain(reply)
+        .collect()
+}
+
+pub fn gcs_command_received(
+    command: &COMMAND_LONG_DATA,
+    source_system: u8,
+    source_component: u8,
+) -> GcsCommandReceived {
+    GcsCommandReceived {
+        source_system,
+        source_component,
+        target_component: command.target_component,
+        command_id: command.command as u16,
+        command: format!("{:?}", command.command),
+        params: vec![
+            command.param1,
+            command.param2,
+            command.param3,
+            command.param4,
+            command.param5,
+            command.param6,
+            command.param7,
+        ],
+        confirmation: command.confirmation,
+    }
+}
+
+pub fn gcs_heartbeat() -> MavMessage {
+    MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+        mavtype: MavType::MAV_TYPE_GCS,
+        autopilot: MavAutopilot::MAV_AUTOPILOT_INVALID,
+        system_status: MavState::MAV_STATE_ACTIVE,
+        mavlink_version: 3,
+        ..HEARTBEAT_DATA::default()
+    })
+}
+
+/// The GCS's own position. Velocity and heading are unknown; altitude is 0
+/// when the device has no altitude.
+pub fn gcs_global_position_int(position: &PositionFix, time_boot_ms: u32) -> MavMessage {
+    MavMessage::GLOBAL_POSITION_INT(GLOBAL_POSITION_INT_DATA {
+        time_boot_ms,
+        lat: (position.latitude_deg * 1e7).round() as i32,
+        lon: (position.longitude_deg * 1e7).round() as i32,
+        alt: position
+            .altitude_msl_m
+            .map_or(0, |altitude_m| (altitude_m * 1000.0).round() as i32),
+        relative_alt: 0,
+        vx: 0,
+        vy: 0,
+        vz: 0,
+        hdg: u16::MAX,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GCS: GcsIdentity = GcsIdentity {
+        system_id: 255,
+        component_id: 190,
+    };
+    const COMPANION: (u8, u8) = (1, 191);
+
+    fn command_long(
+        target_system: u8,
+        target_component: u8,
+        command: MavCmd,
+        param1: f32,
+    ) -> COMMAND_LONG_DATA {
+        COMMAND_LONG_DATA {
+            target_system,
+            target_component,
+            command,
+            param1,
+            ..COMMAND_LONG_DATA::default()
+        }
+    }
+
+    const POSITION: PositionFix = PositionFix {
+        latitude_deg: 47.397_742,
+        longitude_deg: 8.545_594,
+        altitude_msl_m: Some(488.2),
+    };
+
+    /// Play a companion computer's requests against the handler and collect
+    /// the result each one is ACKed with.
+    fn ack_results(
+        script: &[COMMAND_LONG_DATA],
+        position: Option<&PositionFix>,
+        device_location: bool,
+    ) -> Vec<Option<MavResult>> {
+        script
+            .iter()
+            .map(|command| {
+                let action = classify_gcs_command(command, GCS);
+                let replies =
+                    gcs_command_replies(command, COMPANION, action, position, device_location, 0);
+                let MavMessage::COMMAND_ACK(ack) = replies.first()? else {
+                    panic!("expected COMMAND_ACK first");
+                };
+                assert_eq!(ack.command, command.command);
+                assert_eq!((ack.target_system, ack.target_component), COMPANION);
+                let answered = replies.len() == 2;
+                assert_eq!(answered, ack.result == MavResult::MAV_RESULT_ACCEPTED);
+                Some(ack.result)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn scripted_peer_gets_an_ack_for_every_command_addressed_to_us() {
+        let script = [
+            command_long(255, 190, MavCmd::MAV_CMD_REQUEST_MESSAGE, 0.0),
+            command_long(255, 0, MavCmd::MAV_CMD_REQUEST_MESSAGE, 33.0),
+            command_long(255, 190, MavCmd::MAV_CMD_REQUEST_MESSAGE, 24.0),
+            command_long(255, 190, MavCmd::MAV_CMD_DO_SET_MODE, 1.0),
+            command_long(1, 1, MavCmd::MAV_CMD_DO_SET_MODE, 1.0),
+            command_long(0, 0, MavCmd::MAV_CMD_REQUEST_MESSAGE, 0.0),
+            command_long(255, 191, MavCmd::MAV_CMD_REQUEST_MESSAGE, 0.0),
+        ];
+        assert_eq!(
+            ack_results(&script, Some(&POSITION), false),
+            [
+                Some(MavResult::MAV_RESULT_ACCEPTED),
+                Some(MavResult::MAV_RESULT_ACCEPTED),
+                Some(MavResult::MAV_RESULT_UNSUPPORTED),
+                Some(MavResult::MAV_RESULT_UNSUPPORTED),
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn position_requests_without_a_fix_fail_only_where_a_fix_can_exist() {
+        let request = [command_long(
+            255,
+            190,
+            MavCmd::MAV_CMD_REQUEST_MESSAGE,
+            33.0,
+        )];
+        assert_eq!(
+            ack_results(&request, None, true),
+            [Some(MavResult::MAV_RESULT_FAILED)]
+        );
+        assert_eq!(
+            ack_results(&request, None, false),
+            [Some(MavResult::MAV_RESULT_UNSUPPORTED)]
+        );
+    }
+
+    #[test]
+    fn requested_messages_describe_the_gcs() {
+        let MavMessage::HEARTBEAT(heartbeat) = gcs_heartbeat() else {
+            panic!("expected HEARTBEAT");
+        };
+        assert_eq!(heartbeat.mavtype, MavType::MAV_TYPE_GCS);
+
+        let MavMessage::GLOBAL_POSITION_INT(data) = gcs_global_position_int(&POSITION, 1_500)
+        else {
+            panic!("expected GLOBAL_POSITION_INT");
+        };
+        assert_eq!(
+            (data.lat, data.lon, data.alt),
+            (473_977_420, 85_455_940, 488_200)
+        );
+        assert_eq!(data.hdg, u16::MAX);
+    }
+
+    #[test]
+    fn unsupported_commands_are_reported_with_their_params() {
+        let command = COMMAND_LONG_DATA {
+            confirmation: 2,
+            param7: 7.0,
+            ..command_long(255, 190, MavCmd::MAV_CMD_DO_SET_MODE, 1.0)
+        };
+        let received = gcs_command_received(&command, COMPANION.0, COMPANION.1);
+        assert_eq!(received.command, "MAV_CMD_DO_SET_MODE");
+        assert_eq!(received.command_id, 176);
+        assert_eq!(received.params, [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 7.0]);
+        assert_eq!(received.confirmation, 2);
+    }
+}
@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use crate::preflight_position::PositionFix;
+
+pub const MIN_STREAM_RATE_HZ: f64 = 0.2;
+pub const MAX_STREAM_RATE_HZ: f64 = 10.0;
+/// Device fixes with a worse horizontal accuracy are not sent; a follow or
+/// return-to-GCS target jumping by tens of metres is worse than a stale one.
+pub const MAX_FIX_ACCURACY_M: f64 = 25.0;
+
+/// Period between sends for `rate_hz`.
+pub fn stream_period(rate_hz: f64) -> Result<Duration, String> {
+    if !(MIN_STREAM_RATE_HZ..=MAX_STREAM_RATE_HZ).contains(&rate_hz) {
+        return Err(format!(
+            "GCS position rate must be between {MIN_STREAM_RATE_HZ} and {MAX_STREAM_RATE_HZ} Hz"
+        ));
+    }
+    Ok(Duration::from_secs_f64(1.0 / rate_hz))
+}
+
+pub fn fix_accurate_enough(accuracy_m: f64) -> bool {
+    (0.0..=MAX_FIX_ACCURACY_M).contains(&accuracy_m)
+}
+
+pub fn manual_position(
+    latitude_deg: f64,
+    longitude_deg: f64,
+    altitude_msl_m: f64,
+) -> Result<PositionFix, String> {
+    if !(-90.0..=90.0).contains(&latitude_deg) {
+        return Err("latitude must be between -90 and 90 degrees".to_string());
+    }
+    if !(-180.0..=180.0).contains(&longitude_deg) {
+        return Err("longitude must be between -180 and 180 degrees".to_string());
+    }
+    if !altitude_msl_m.is_finite() {
+        return Err("altitude must be a finite number".to_string());
+    }
+    Ok(PositionFix {
+        latitude_deg,
+        longitude_deg,
+        altitude_msl_m: Some(altitude_msl_m),
+    })
+}
+
+/// `time_boot_ms` for messages the GCS originates: milliseconds since the GCS
+/// started, wrapping like the autopilot's own counter rather than saturating.
+pub fn gcs_time_boot_ms(since_start: Duration) -> u32 {
+    (since_start.as_millis() % (u128::from(u32::MAX) + 1)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_and_accuracy_limits() {
+        assert_eq!(stream_period(2.0), Ok(Duration::from_millis(500)));
+        assert!(stream_period(0.0).is_err());
+        assert!(stream_period(f64::NAN).is_err());
+        assert!(stream_period(50.0).is_err());
+
+        assert!(fix_accurate_enough(4.5));
+        assert!(!fix_accurate_enough(60.0));
+        assert!(!fix_accurate_enough(f64::NAN));
+    }
+
+    #[test]
+    fn manual_positions_are_range_checked() {
+        let fix = manual_position(47.39, 8.54, 488.0).expect("valid position");
+        assert_eq!(fix.altitude_msl_m, Some(488.0));
+        assert!(manual_position(91.0, 8.54, 0.0).is_err());
+        assert!(manual_position(47.39, -181.0, 0.0).is_err());
+        assert!(manual_position(47.39, 8.54, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn time_boot_ms_wraps() {
+        assert_eq!(gcs_time_boot_ms(Duration::from_millis(1_234)), 1_234);
+        let wrap = Duration::from_millis(u64::from(u32::MAX) + 11);
+        assert_eq!(gcs_time_boot_ms(wrap), 10);
+    }
+}
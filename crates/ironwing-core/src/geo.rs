@@ -0,0 +1,108 @@
+/// Mean Earth radius used for the local tangent-plane projection.
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+/// East and north offset, in metres, of `(latitude_deg, longitude_deg)` from
+/// the origin, on the plane tangent to the Earth there. Over the few
+/// kilometres a geofence spans this is within centimetres of the great-circle
+/// distance, and lines stay straight so polygon tests work unchanged.
+pub fn local_offset_m(
+    origin_latitude_deg: f64,
+    origin_longitude_deg: f64,
+    latitude_deg: f64,
+    longitude_deg: f64,
+) -> (f64, f64) {
+    let dlon_deg = (longitude_deg - origin_longitude_deg + 180.0).rem_euclid(360.0) - 180.0;
+    let north = (latitude_deg - origin_latitude_deg).to_radians() * EARTH_RADIUS_M;
+    let east = dlon_deg.to_radians() * EARTH_RADIUS_M * origin_latitude_deg.to_radians().cos();
+    (east, north)
+}
+
+/// Whether `point` is inside the polygon `vertices` (closed implicitly),
+/// by the even-odd rule. Points are `(east, north)` in one local frame.
+pub fn point_in_polygon(point: (f64, f64), vertices: &[(f64, f64)]) -> bool {
+    let (x, y) = point;
+    let mut inside = false;
+    for (index, &(x1, y1)) in vertices.iter().enumerate() {
+        let (x2, y2) = vertices[(index + 1) % vertices.len()];
+        if (y1 > y) != (y2 > y) && x < x1 + (y - y1) * (x2 - x1) / (y2 - y1) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// The point on the segment `a`..`b` nearest `point`.
+pub fn closest_point_on_segment(point: (f64, f64), a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_sq = dx * dx + dy * dy;
+    if length_sq == 0.0 {
+        return a;
+    }
+    let t = (((point.0 - a.0) * dx + (point.1 - a.1) * dy) / length_sq).clamp(0.0, 1.0);
+    (a.0 + t * dx, a.1 + t * dy)
+}
+
+pub fn distance_to_segment(point: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let nearest = closest_point_on_segment(point, a, b);
+    (nearest.0 - point.0).hypot(nearest.1 - point.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_engine::haversine_m;
+
+    #[test]
+    fn local_offsets_match_great_circle_distances_at_fence_scale() {
+        let (lat, lon) = (47.3977, 8.5456);
+        for (dlat, dlon) in [(0.01, 0.0), (0.0, 0.01), (-0.007, 0.012)] {
+            let (east, north) = local_offset_m(lat, lon, lat + dlat, lon + dlon);
+            let planar = east.hypot(north);
+            let great_circle = haversine_m(lat, lon, lat + dlat, lon + dlon);
+            assert!(
+                (planar - great_circle).abs() < 0.5,
+                "{planar} vs {great_circle}"
+            );
+        }
+        let (east, north) = local_offset_m(lat, lon, lat, lon + 0.01);
+        assert!(east > 0.0 && north.abs() < 1e-9);
+        // Across the antimeridian the short way round is taken.
+        let (east, _) = local_offset_m(0.0, 179.999, 0.0, -179.999);
+        assert!((east - 222.4).abs() < 0.5, "{east}");
+    }
+
+    #[test]
+    fn points_are_located_in_convex_and_concave_polygons() {
+        let square = [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
+        assert!(point_in_polygon((5.0, 5.0), &square));
+        assert!(!point_in_polygon((15.0, 5.0), &square));
+        assert!(!point_in_polygon((5.0, -0.1), &square));
+
+        // A "U": the notch between the arms is outside.
+        let u = [
+            (0.0, 0.0),
+            (30.0, 0.0),
+            (30.0, 30.0),
+            (20.0, 30.0),
+            (20.0, 10.0),
+            (10.0, 10.0),
+            (10.0, 30.0),
+            (0.0, 30.0),
+        ];
+        assert!(point_in_polygon((5.0, 25.0), &u));
+        assert!(point_in_polygon((25.0, 25.0), &u));
+        assert!(!point_in_polygon((15.0, 25.0), &u));
+        assert!(point_in_polygon((15.0, 5.0), &u));
+        assert!(!point_in_polygon((1.0, 1.0), &[]));
+    }
+
+    #[test]
+    fn segment_distance_uses_the_perpendicular_or_the_nearer_end() {
+        let (a, b) = ((0.0, 0.0), (10.0, 0.0));
+        assert_eq!(distance_to_segment((5.0, 3.0), a, b), 3.0);
+        assert_eq!(distance_to_segment((13.0, 4.0), a, b), 5.0);
+        assert_eq!(distance_to_segment((-3.0, -4.0), a, b), 5.0);
+        assert_eq!(distance_to_segment((2.0, 2.0), a, a), 8.0_f64.sqrt());
+        assert_eq!(closest_point_on_segment((5.0, 3.0), a, b), (5.0, 0.0));
+    }
+}
@@ -0,0 +1,316 @@
+use mavkit::{AutopilotType, VehicleType};
+
+use crate::dialect::{
+    COMMAND_INT_DATA, MavCmd, MavFrame, MavMessage, PositionTargetTypemask,
+    SET_POSITION_TARGET_GLOBAL_INT_DATA,
+};
+use crate::flight_modes::is_fixed_wing;
+use crate::ipc::{AppError, GotoFrame, GotoMethod, GotoOptions, GotoRequest, TerrainGotoStrategy};
+
+/// MAV_DO_REPOSITION_FLAGS_CHANGE_MODE: switch to Guided as part of the
+/// reposition.
+const REPOSITION_CHANGE_MODE: f32 = 1.0;
+/// DO_CHANGE_SPEED speed type for ground speed.
+const SPEED_TYPE_GROUND: f32 = 1.0;
+
+pub fn validate_goto(request: &GotoRequest) -> Result<(), AppError> {
+    let mut issues = Vec::new();
+    if !(-90.0..=90.0).contains(&request.latitude_deg) {
+        issues.push("latitude must be within ±90°".to_string());
+    }
+    if !(-180.0..=180.0).contains(&request.longitude_deg) {
+        issues.push("longitude must be within ±180°".to_string());
+    }
+    if !request.altitude_m.is_finite() {
+        issues.push("altitude must be a number".to_string());
+    }
+    let options = &request.options;
+    if options
+        .ground_speed_ms
+        .is_some_and(|speed| !speed.is_finite() || speed <= 0.0)
+    {
+        issues.push("ground speed must be greater than zero".to_string());
+    }
+    if options.yaw_deg.is_some_and(|yaw| !yaw.is_finite()) {
+        issues.push("yaw must be a number".to_string());
+    }
+    if options
+        .acceptance_radius_m
+        .is_some_and(|radius| !radius.is_finite() || radius <= 0.0)
+    {
+        issues.push("acceptance radius must be greater than zero".to_string());
+    }
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation {
+            message: issues.join("; "),
+            issues,
+        })
+    }
+}
+
+/// Planes take a target through DO_REPOSITION; ArduCopter and Rover follow
+/// position targets directly.
+pub fn goto_method(vehicle_type: VehicleType) -> GotoMethod {
+    if is_fixed_wing(vehicle_type) {
+        GotoMethod::Reposition
+    } else {
+        GotoMethod::PositionTarget
+    }
+}
+
+/// Options `method` has no field for.
+pub fn ignored_options(method: GotoMethod, options: &GotoOptions) -> Vec<String> {
+    let mut ignored = Vec::new();
+    match method {
+        GotoMethod::PositionTarget => {
+            if options.acceptance_radius_m.is_some() {
+                ignored.push("acceptance_radius_m".to_string());
+            }
+        }
+        GotoMethod::Reposition => {
+            if options.yaw_deg.is_some() {
+                ignored.push("yaw_deg".to_string());
+            }
+        }
+    }
+    ignored
+}
+
+pub fn mav_frame(frame: GotoFrame) -> MavFrame {
+    match frame {
+        GotoFrame::RelativeAlt => MavFrame::MAV_FRAME_GLOBAL_RELATIVE_ALT_INT,
+        GotoFrame::Amsl => MavFrame::MAV_FRAME_GLOBAL_INT,
+        GotoFrame::Terrain => MavFrame::MAV_FRAME_GLOBAL_TERRAIN_ALT_INT,
+    }
+}
+
+/// Position-only type mask; yaw is followed only when one was requested.
+pub fn position_target_type_mask(options: &GotoOptions) -> PositionTargetTypemask {
+    let mut mask = PositionTargetTypemask::POSITION_TARGET_TYPEMASK_VX_IGNORE
+        | PositionTargetTypemask::POSITION_TARGET_TYPEMASK_VY_IGNORE
+        | PositionTargetTypemask::POSITION_TARGET_TYPEMASK_VZ_IGNORE
+        | PositionTargetTypemask::POSITION_TARGET_TYPEMASK_AX_IGNORE
+        | PositionTargetTypemask::POSITION_TARGET_TYPEMASK_AY_IGNORE
+        | PositionTargetTypemask::POSITION_TARGET_TYPEMASK_AZ_IGNORE
+        | PositionTargetTypemask::POSITION_TARGET_TYPEMASK_YAW_RATE_IGNORE;
+    if options.yaw_deg.is_none() {
+        mask |= PositionTargetTypemask::POSITION_TARGET_TYPEMASK_YAW_IGNORE;
+    }
+    mask
+}
+
+fn degrees_e7(degrees: f64) -> i32 {
+    (degrees * 1e7).round() as i32
+}
+
+pub fn position_target_message(
+    target_system: u8,
+    target_component: u8,
+    request: &GotoRequest,
+) -> MavMessage {
+    MavMessage::SET_POSITION_TARGET_GLOBAL_INT(SET_POSITION_TARGET_GLOBAL_INT_DATA {
+        lat_int: degrees_e7(request.latitude_deg),
+        lon_int: degrees_e7(request.longitude_deg),
+        alt: request.altitude_m,
+        yaw: request.options.yaw_deg.unwrap_or(0.0).to_radians(),
+        type_mask: position_target_type_mask(&request.options),
+        target_system,
+        target_component,
+        coordinate_frame: mav_frame(request.options.frame),
+        ..Default::default()
+    })
+}
+
+/// MAV_CMD_DO_REPOSITION as COMMAND_INT, keeping full coordinate precision.
+/// A missing acceptance radius sends 0, which keeps the loiter radius.
+pub fn reposition_message(
+    target_system: u8,
+    target_component: u8,
+    request: &GotoRequest,
+) -> MavMessage {
+    MavMessage::COMMAND_INT(COMMAND_INT_DATA {
+        param1: -1.0,
+        param2: REPOSITION_CHANGE_MODE,
+        param3: request.options.acceptance_radius_m.unwrap_or(0.0),
+        param4: f32::NAN,
+        x: degrees_e7(request.latitude_deg),
+        y: degrees_e7(request.longitude_deg),
+        z: request.altitude_m,
+        command: MavCmd::MAV_CMD_DO_REPOSITION,
+        target_system,
+        target_component,
+        frame: mav_frame(request.options.frame),
+        current: 0,
+        autocontinue: 0,
+    })
+}
+
+/// MAV_CMD_DO_CHANGE_SPEED params for a ground speed, leaving throttle alone.
+pub fn change_speed_params(ground_speed_ms: f32) -> [f32; 7] {
+    [SPEED_TYPE_GROUND, ground_speed_ms, -1.0, 0.0, 0.0, 0.0, 0.0]
+}
+
+/// Whether the autopilot can hold a target in the terrain frame. ArduPilot
+/// only does so with its terrain database enabled; PX4 does not accept
+/// terrain-frame guided targets.
+pub fn terrain_frame_supported(autopilot: AutopilotType, terrain_enable: Option<f64>) -> bool {
+    autopilot == AutopilotType::ArduPilotMega && terrain_enable == Some(1.0)
+}
+
+/// The goto that holds `agl_m` above the ground at the target. Without
+/// terrain-frame support the height is only right over the target itself.
+pub fn terrain_goto_request(
+    latitude_deg: f64,
+    longitude_deg: f64,
+    agl_m: f32,
+    ground_elevation_m: f64,
+    terrain_frame: bool,
+) -> (GotoRequest, TerrainGotoStrategy, Vec<String>) {
+    let (strategy, frame, altitude_m, warnings) = if terrain_frame {
+        (
+            TerrainGotoStrategy::TerrainFrame,
+            GotoFrame::Terrain,
+            agl_m,
+            Vec::new(),
+        )
+    } else {
+        (
+            TerrainGotoStrategy::AmslEquivalent,
+            GotoFrame::Amsl,
+            (ground_elevation_m + f64::from(agl_m)) as f32,
+            vec![format!(
+                "the autopilot cannot follow terrain; {agl_m} m above ground holds only at the \
+                 target, not en route"
+            )],
+        )
+    };
+    let request = GotoRequest {
+        latitude_deg,
+        longitude_deg,
+        altitude_m,
+        options: GotoOptions {
+            frame,
+            ..GotoOptions::default()
+        },
+    };
+    (request, strategy, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(options: GotoOptions) -> GotoRequest {
+        GotoRequest {
+            latitude_deg: 47.397_742_1,
+            longitude_deg: 8.545_594_3,
+            altitude_m: 30.0,
+            options,
+        }
+    }
+
+    #[test]
+    fn yaw_is_only_followed_when_requested() {
+        let without = position_target_type_mask(&GotoOptions::default());
+        assert!(without.contains(PositionTargetTypemask::POSITION_TARGET_TYPEMASK_YAW_IGNORE));
+        assert_eq!(without.bits(), 0b1101_1111_1000);
+
+        let with = position_target_type_mask(&GotoOptions {
+            yaw_deg: Some(90.0),
+            ..GotoOptions::default()
+        });
+        assert!(!with.contains(PositionTargetTypemask::POSITION_TARGET_TYPEMASK_YAW_IGNORE));
+        assert!(with.contains(PositionTargetTypemask::POSITION_TARGET_TYPEMASK_YAW_RATE_IGNORE));
+    }
+
+    #[test]
+    fn position_target_carries_frame_and_yaw() {
+        let goto = request(GotoOptions {
+            yaw_deg: Some(180.0),
+            frame: GotoFrame::Terrain,
+            ..GotoOptions::default()
+        });
+
+        let MavMessage::SET_POSITION_TARGET_GLOBAL_INT(data) = position_target_message(1, 1, &goto)
+        else {
+            panic!("expected SET_POSITION_TARGET_GLOBAL_INT");
+        };
+
+        assert_eq!(data.lat_int, 473_977_421);
+        assert_eq!(data.lon_int, 85_455_943);
+        assert_eq!(data.alt, 30.0);
+        assert!((data.yaw - std::f32::consts::PI).abs() < 1e-6);
+        assert_eq!(
+            data.coordinate_frame,
+            MavFrame::MAV_FRAME_GLOBAL_TERRAIN_ALT_INT
+        );
+    }
+
+    #[test]
+    fn reposition_carries_acceptance_radius_and_reports_yaw_ignored() {
+        let options = GotoOptions {
+            yaw_deg: Some(45.0),
+            acceptance_radius_m: Some(80.0),
+            ..GotoOptions::default()
+        };
+
+        let MavMessage::COMMAND_INT(data) = reposition_message(1, 1, &request(options)) else {
+            panic!("expected COMMAND_INT");
+        };
+
+        assert_eq!(data.command, MavCmd::MAV_CMD_DO_REPOSITION);
+        assert_eq!(data.param3, 80.0);
+        assert_eq!(
+            ignored_options(GotoMethod::Reposition, &options),
+            vec!["yaw_deg".to_string()]
+        );
+        assert_eq!(
+            ignored_options(GotoMethod::PositionTarget, &options),
+            vec!["acceptance_radius_m".to_string()]
+        );
+    }
+
+    #[test]
+    fn invalid_options_are_reported_together() {
+        let goto = GotoRequest {
+            latitude_deg: 91.0,
+            ..request(GotoOptions {
+                ground_speed_ms: Some(0.0),
+                ..GotoOptions::default()
+            })
+        };
+
+        let Err(AppError::Validation { issues, .. }) = validate_goto(&goto) else {
+            panic!("expected a validation error");
+        };
+        assert_eq!(issues.len(), 2);
+        assert!(validate_goto(&request(GotoOptions::default())).is_ok());
+    }
+
+    #[test]
+    fn terrain_goto_falls_back_to_amsl_with_a_warning() {
+        assert!(terrain_frame_supported(
+            AutopilotType::ArduPilotMega,
+            Some(1.0)
+        ));
+        assert!(!terrain_frame_supported(
+            AutopilotType::ArduPilotMega,
+            Some(0.0)
+        ));
+        assert!(!terrain_frame_supported(AutopilotType::Px4, Some(1.0)));
+
+        let (goto, strategy, warnings) = terrain_goto_request(47.4, 8.5, 40.0, 512.5, true);
+        assert_eq!(strategy, TerrainGotoStrategy::TerrainFrame);
+        assert_eq!(goto.options.frame, GotoFrame::Terrain);
+        assert_eq!(goto.altitude_m, 40.0);
+        assert!(warnings.is_empty());
+
+        let (goto, strategy, warnings) = terrain_goto_request(47.4, 8.5, 40.0, 512.5, false);
+        assert_eq!(strategy, TerrainGotoStrategy::AmslEquivalent);
+        assert_eq!(goto.options.frame, GotoFrame::Amsl);
+        assert_eq!(goto.altitude_m, 552.5);
+        assert_eq!(warnings.len(), 1);
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+
+use web_time::Instant;
+
+use crate::ipc::{GpsFix, GpsFixChanged, GpsInstanceStatus, GpsStatus};
+
+pub const GPS_RAW_INT_MESSAGE_ID: u32 = 24;
+pub const GPS2_RAW_MESSAGE_ID: u32 = 124;
+pub const GPS_RTK_MESSAGE_ID: u32 = 127;
+pub const GPS2_RTK_MESSAGE_ID: u32 = 128;
+
+/// satellites_visible and eph use their type's maximum for "unknown".
+const SATELLITES_UNKNOWN: u8 = u8::MAX;
+const EPH_UNKNOWN: u16 = u16::MAX;
+
+pub fn gps_fix_from_raw(fix_type: u8) -> GpsFix {
+    match fix_type {
+        0 => GpsFix::NoGps,
+        2 => GpsFix::Fix2d,
+        3 => GpsFix::Fix3d,
+        4 => GpsFix::Dgps,
+        5 => GpsFix::RtkFloat,
+        6 => GpsFix::RtkFixed,
+        7 => GpsFix::Static,
+        8 => GpsFix::Ppp,
+        _ => GpsFix::NoFix,
+    }
+}
+
+/// One GPS_RAW_INT or GPS2_RAW report.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpsRawReport {
+    pub instance: u8,
+    pub fix_type: u8,
+    pub satellites_visible: u8,
+    pub eph: u16,
+}
+
+/// Tracks fix transitions per GPS instance and the time to a first 3D fix,
+/// measured from when the tracker was started at connect.
+#[derive(Debug, Clone)]
+pub struct GpsFixTracker {
+    started_at: Instant,
+    instances: BTreeMap<u8, GpsInstanceStatus>,
+    first_3d_fix_ms: Option<u64>,
+    /// Latest vehicle boot time seen, for turning GPS_RTK's baseline
+    /// timestamp into an age.
+    vehicle_time_boot_ms: Option<u32>,
+}
+
+impl Default for GpsFixTracker {
+    fn default() -> Self {
+        Self::start(Instant::now())
+    }
+}
+
+impl GpsFixTracker {
+    pub fn start(now: Instant) -> Self {
+        Self {
+            started_at: now,
+            instances: BTreeMap::new(),
+            first_3d_fix_ms: None,
+            vehicle_time_boot_ms: None,
+        }
+    }
+
+    pub fn observe_vehicle_time(&mut self, time_boot_ms: u32) {
+        self.vehicle_time_boot_ms = Some(time_boot_ms);
+    }
+
+    /// Record a GPS_RTK / GPS2_RTK baseline time for `instance`.
+    pub fn observe_rtk(&mut self, instance: u8, time_last_baseline_ms: u32) {
+        let Some(now_ms) = self.vehicle_time_boot_ms else {
+            return;
+        };
+        if let Some(status) = self.instances.get_mut(&instance) {
+            status.rtk_baseline_age_ms = Some(now_ms.saturating_sub(time_last_baseline_ms));
+        }
+    }
+
+    /// Record a raw GPS report. Returns the transition when the fix type
+    /// changed, including the first report from an instance.
+    pub fn observe(&mut self, report: GpsRawReport, now: Instant) -> Option<GpsFixChanged> {
+        let fix = gps_fix_from_raw(report.fix_type);
+        let elapsed_ms = now.duration_since(self.started_at).as_millis() as u64;
+        let previous = self
+            .instances
+            .get(&report.instance)
+            .map(|status| status.fix);
+        let status = self
+            .instances
+            .entry(report.instance)
+            .or_insert_with(|| GpsInstanceStatus {
+                instance: report.instance,
+                fix,
+                satellites: None,
+                hdop: None,
+                rtk_baseline_age_ms: None,
+                time_to_3d_fix_ms: None,
+            });
+        status.fix = fix;
+        status.satellites =
+            (report.satellites_visible != SATELLITES_UNKNOWN).then_some(report.satellites_visible);
+        status.hdop = (report.eph != EPH_UNKNOWN).then(|| f32::from(report.eph) / 100.0);
+        if !matches!(fix, GpsFix::RtkFloat | GpsFix::RtkFixed) {
+            status.rtk_baseline_age_ms = None;
+        }
+        if fix >= GpsFix::Fix3d && status.time_to_3d_fix_ms.is_none() {
+            status.time_to_3d_fix_ms = Some(elapsed_ms);
+            self.first_3d_fix_ms.get_or_insert(elapsed_ms);
+        }
+
+        if previous == Some(fix) {
+            return None;
+        }
+        Some(GpsFixChanged {
+            instance: report.instance,
+            previous,
+            fix,
+            satellites: status.satellites,
+            hdop: status.hdop,
+            rtk_baseline_age_ms: status.rtk_baseline_age_ms,
+        })
+    }
+
+    pub fn status(&self) -> GpsStatus {
+        GpsStatus {
+            instances: self.instances.values().cloned().collect(),
+            time_to_first_3d_fix_ms: self.first_3d_fix_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn report(instance: u8, fix_type: u8) -> GpsRawReport {
+        GpsRawReport {
+            instance,
+            fix_type,
+            satellites_visible: 14,
+            eph: 87,
+        }
+    }
+
+    #[test]
+    fn transitions_are_reported_once_per_change() {
+        let start = Instant::now();
+        let mut tracker = GpsFixTracker::start(start);
+
+        let first = tracker.observe(report(0, 1), start).expect("first report");
+        assert_eq!(first.previous, None);
+        assert_eq!(first.fix, GpsFix::NoFix);
+        assert_eq!(first.hdop, Some(0.87));
+        assert!(tracker.observe(report(0, 1), start).is_none());
+
+        let fixed = tracker
+            .observe(report(0, 3), start + Duration::from_millis(700))
+            .expect("3D fix");
+        assert_eq!(fixed.previous, Some(GpsFix::NoFix));
+        assert_eq!(fixed.fix, GpsFix::Fix3d);
+        assert_eq!(tracker.status().time_to_first_3d_fix_ms, Some(700));
+    }
+
+    #[test]
+    fn instances_are_tracked_separately() {
+        let start = Instant::now();
+        let mut tracker = GpsFixTracker::start(start);
+        tracker.observe(report(0, 3), start + Duration::from_secs(2));
+        tracker.observe(report(1, 3), start + Duration::from_secs(5));
+
+        let status = tracker.status();
+        assert_eq!(status.instances.len(), 2);
+        assert_eq!(status.instances[1].time_to_3d_fix_ms, Some(5000));
+        assert_eq!(status.time_to_first_3d_fix_ms, Some(2000));
+    }
+
+    #[test]
+    fn rtk_transitions_carry_the_baseline_age() {
+        let start = Instant::now();
+        let mut tracker = GpsFixTracker::start(start);
+        tracker.observe(report(0, 5), start);
+        tracker.observe_vehicle_time(10_400);
+        tracker.observe_rtk(0, 10_000);
+
+        let fixed = tracker.observe(report(0, 6), start).expect("RTK fixed");
+        assert_eq!(fixed.previous, Some(GpsFix::RtkFloat));
+        assert_eq!(fixed.rtk_baseline_age_ms, Some(400));
+
+        let unknown = GpsRawReport {
+            satellites_visible: u8::MAX,
+            eph: u16::MAX,
+            ..report(0, 3)
+        };
+        let dropped = tracker.observe(unknown, start).expect("dropped to 3D");
+        assert_eq!(dropped.rtk_baseline_age_ms, None);
+        assert_eq!((dropped.satellites, dropped.hdop), (None, None));
+    }
+}
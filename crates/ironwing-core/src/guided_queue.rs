@@ -0,0 +1,445 @@
+use mavkit::{AutopilotType, VehicleType};
+
+use crate::flight_modes::{is_fixed_wing, normalized};
+use crate::ipc::{GuidedQueuePoint, GuidedQueueState, GuidedQueueStatus};
+use crate::log_engine::haversine_m;
+
+pub const GUIDED_QUEUE_MAX_POINTS: usize = 100;
+/// Horizontal distance at which a copter or rover, which stop on the point,
+/// counts as having reached it.
+pub const GUIDED_QUEUE_ACCEPTANCE_M: f64 = 2.0;
+/// Firmware defaults for the plane radii, used when the parameters are not
+/// downloaded.
+const ARDUPLANE_DEFAULT_WP_RADIUS_M: f64 = 90.0;
+const ARDUPLANE_DEFAULT_LOITER_RADIUS_M: f64 = 60.0;
+const PX4_DEFAULT_ACCEPTANCE_RADIUS_M: f64 = 10.0;
+const PX4_DEFAULT_LOITER_RADIUS_M: f64 = 80.0;
+/// Head room over the loiter radius, so a plane circling the point with some
+/// wind drift still counts as there.
+const LOITER_RADIUS_MARGIN: f64 = 1.25;
+/// Modes a queue point is flown in. PX4 holds a reposition target in Hold
+/// (AUTO_LOITER) and follows setpoints in Offboard.
+const ARDUPILOT_QUEUE_MODES: &[&str] = &["GUIDED"];
+const PX4_QUEUE_MODES: &[&str] = &["HOLD", "AUTO_LOITER", "LOITER", "OFFBOARD"];
+/// Consecutive position updates that must agree before the queue advances,
+/// so one jittery fix near the edge of the radius does not count.
+pub const GUIDED_QUEUE_CONFIRM_SAMPLES: u32 = 3;
+/// A vehicle that came within this many acceptance radii and is now moving
+/// away has overshot the point rather than missed it.
+const OVERSHOOT_CAPTURE_RADII: f64 = 3.0;
+
+pub fn validate_queue(points: &[GuidedQueuePoint]) -> Result<(), String> {
+    if points.is_empty() {
+        return Err("a guided queue needs at least one point".to_string());
+    }
+    if points.len() > GUIDED_QUEUE_MAX_POINTS {
+        return Err(format!(
+            "a guided queue holds at most {GUIDED_QUEUE_MAX_POINTS} points, got {}",
+            points.len()
+        ));
+    }
+    for (index, point) in points.iter().enumerate() {
+        if !(-90.0..=90.0).contains(&point.latitude_deg)
+            || !(-180.0..=180.0).contains(&point.longitude_deg)
+        {
+            return Err(format!("point {} is not a valid position", index + 1));
+        }
+        if !point.altitude_m.is_finite() {
+            return Err(format!("point {} has no altitude", index + 1));
+        }
+    }
+    Ok(())
+}
+
+/// Whether `mode` is one the queue can fly in on `autopilot`.
+pub fn is_queue_mode(autopilot: AutopilotType, mode: &str) -> bool {
+    let modes = match autopilot {
+        AutopilotType::Px4 => PX4_QUEUE_MODES,
+        _ => ARDUPILOT_QUEUE_MODES,
+    };
+    let mode = normalized(mode);
+    modes.iter().any(|name| normalized(name) == mode)
+}
+
+/// How close the vehicle must come to a point for it to count as reached.
+/// A plane cannot stop on a point: ArduPlane turns for the next leg
+/// WP_RADIUS or one L1 distance (NAVL1_PERIOD and NAVL1_DAMPING at cruise
+/// airspeed) out, PX4 at NAV_ACC_RAD, and left on the point both circle it
+/// at their loiter radius.
+pub fn queue_acceptance_radius_m(
+    autopilot: AutopilotType,
+    vehicle_type: VehicleType,
+    param: impl Fn(&str) -> Option<f32>,
+) -> f64 {
+    if !is_fixed_wing(vehicle_type) {
+        return GUIDED_QUEUE_ACCEPTANCE_M;
+    }
+    let param_or = |name: &str, default: f64| param(name).map_or(default, f64::from).abs();
+    let (turn_m, loiter_m) = if autopilot == AutopilotType::Px4 {
+        (
+            param_or("NAV_ACC_RAD", PX4_DEFAULT_ACCEPTANCE_RADIUS_M),
+            param_or("NAV_LOITER_RAD", PX4_DEFAULT_LOITER_RADIUS_M),
+        )
+    } else {
+        let cruise_mps = param("AIRSPEED_CRUISE")
+            .map(f64::from)
+            .or_else(|| param("TRIM_ARSPD_CM").map(|cm| f64::from(cm) / 100.0));
+        let l1_m = match (param("NAVL1_PERIOD"), param("NAVL1_DAMPING"), cruise_mps) {
+            (Some(period), Some(damping), Some(cruise_mps)) => {
+                f64::from(period) * f64::from(damping) * cruise_mps / std::f64::consts::PI
+            }
+            _ => 0.0,
+        };
+        (
+            param_or("WP_RADIUS", ARDUPLANE_DEFAULT_WP_RADIUS_M).max(l1_m),
+            param_or("WP_LOITER_RAD", ARDUPLANE_DEFAULT_LOITER_RADIUS_M),
+        )
+    };
+    turn_m.max(loiter_m * LOITER_RADIUS_MARGIN)
+}
+
+/// Why a running queue must stop, given the vehicle's state at the latest
+/// update.
+pub fn queue_stop_reason(
+    link_connected: bool,
+    armed: bool,
+    in_guided: bool,
+) -> Option<&'static str> {
+    if !link_connected {
+        Some("link lost")
+    } else if !armed {
+        Some("vehicle disarmed")
+    } else if !in_guided {
+        Some("vehicle left its guided mode")
+    } else {
+        None
+    }
+}
+
+/// Decides when the vehicle has reached one point from a stream of distances
+/// to it.
+#[derive(Debug, Clone, PartialEq)]
+struct ArrivalTracker {
+    acceptance_m: f64,
+    closest_m: f64,
+    inside: u32,
+    receding: u32,
+}
+
+impl ArrivalTracker {
+    fn new(acceptance_m: f64) -> Self {
+        Self {
+            acceptance_m,
+            closest_m: f64::INFINITY,
+            inside: 0,
+            receding: 0,
+        }
+    }
+
+    /// Reached once several updates in a row are inside the acceptance
+    /// radius, or once the vehicle passed close by and keeps moving away.
+    fn observe(&mut self, distance_m: f64) -> bool {
+        self.closest_m = self.closest_m.min(distance_m);
+        if distance_m <= self.acceptance_m {
+            self.inside += 1;
+        } else {
+            self.inside = 0;
+        }
+        let passed = self.closest_m <= self.acceptance_m * OVERSHOOT_CAPTURE_RADII
+            && distance_m >= self.closest_m + self.acceptance_m;
+        if passed {
+            self.receding += 1;
+        } else {
+            self.receding = 0;
+        }
+        self.inside >= GUIDED_QUEUE_CONFIRM_SAMPLES || self.receding >= GUIDED_QUEUE_CONFIRM_SAMPLES
+    }
+}
+
+/// What the runner should do after a position update.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QueueStep {
+    Hold,
+    /// Send the vehicle on to this point.
+    Advance(GuidedQueuePoint),
+    /// The last point was reached.
+    Finished,
+}
+
+/// Points flown one after another in Guided, advancing as each is reached.
+#[derive(Debug, Clone)]
+pub struct GuidedQueue {
+    points: Vec<GuidedQueuePoint>,
+    state: GuidedQueueState,
+    current: usize,
+    distance_m: Option<f64>,
+    stop_reason: Option<String>,
+    tracker: ArrivalTracker,
+}
+
+impl Default for GuidedQueue {
+    fn default() -> Self {
+        Self {
+            points: Vec::new(),
+            state: GuidedQueueState::Empty,
+            current: 0,
+            distance_m: None,
+            stop_reason: None,
+            tracker: ArrivalTracker::new(GUIDED_QUEUE_ACCEPTANCE_M),
+        }
+    }
+}
+
+impl GuidedQueue {
+    pub fn new(points: Vec<GuidedQueuePoint>) -> Result<Self, String> {
+        validate_queue(&points)?;
+        Ok(Self {
+            points,
+            state: GuidedQueueState::Ready,
+            ..Self::default()
+        })
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.state == GuidedQueueState::Running
+    }
+
+    /// Fly the queue from its first point, which is returned for the first
+    /// goto, counting points reached within `acceptance_m`. A finished or
+    /// stopped queue starts over.
+    pub fn start(&mut self, acceptance_m: f64) -> Result<GuidedQueuePoint, String> {
+        let first = *self
+            .points
+            .first()
+            .ok_or("no guided queue points are set")?;
+        if self.is_running() {
+            return Err("the guided queue is already running".to_string());
+        }
+        *self = Self {
+            points: std::mem::take(&mut self.points),
+            state: GuidedQueueState::Running,
+            tracker: ArrivalTracker::new(acceptance_m),
+            ..Self::default()
+        };
+        Ok(first)
+    }
+
+    /// Feed a position update. Does nothing unless running.
+    pub fn observe(&mut self, latitude_deg: f64, longitude_deg: f64) -> QueueStep {
+        if !self.is_running() {
+            return QueueStep::Hold;
+        }
+        let target = self.points[self.current];
+        let distance_m = haversine_m(
+            latitude_deg,
+            longitude_deg,
+            target.latitude_deg,
+            target.longitude_deg,
+        );
+        self.distance_m = Some(distance_m);
+        if !self.tracker.observe(distance_m) {
+            return QueueStep::Hold;
+        }
+        if self.current + 1 == self.points.len() {
+            self.state = GuidedQueueState::Completed;
+            return QueueStep::Finished;
+        }
+        self.current += 1;
+        self.tracker = ArrivalTracker::new(self.tracker.acceptance_m);
+        self.distance_m = None;
+        QueueStep::Advance(self.points[self.current])
+    }
+
+    /// Stop a running queue; a queue that is not running keeps its state.
+    pub fn stop(&mut self, reason: impl Into<String>) {
+        if self.is_running() {
+            self.state = GuidedQueueState::Stopped;
+            self.stop_reason = Some(reason.into());
+        }
+    }
+
+    pub fn status(&self) -> GuidedQueueStatus {
+        let started = !matches!(
+            self.state,
+            GuidedQueueState::Empty | GuidedQueueState::Ready
+        );
+        GuidedQueueStatus {
+            state: self.state,
+            points: self.points.clone(),
+            current_index: started.then_some(self.current as u32),
+            distance_m: self.distance_m,
+            stop_reason: self.stop_reason.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Metres per degree of latitude, near enough for test geometry.
+    const M_PER_DEG: f64 = 111_195.0;
+
+    fn point(north_m: f64) -> GuidedQueuePoint {
+        GuidedQueuePoint {
+            latitude_deg: 47.0 + north_m / M_PER_DEG,
+            longitude_deg: 8.0,
+            altitude_m: 20.0,
+        }
+    }
+
+    /// Feed positions `north_m` metres north of the reference, returning the
+    /// step taken after each.
+    fn fly(queue: &mut GuidedQueue, north_m: &[f64]) -> Vec<QueueStep> {
+        north_m
+            .iter()
+            .map(|north| queue.observe(47.0 + north / M_PER_DEG, 8.0))
+            .collect()
+    }
+
+    fn running(points: &[f64]) -> GuidedQueue {
+        let mut queue = GuidedQueue::new(points.iter().copied().map(point).collect()).unwrap();
+        assert_eq!(queue.start(GUIDED_QUEUE_ACCEPTANCE_M), Ok(point(points[0])));
+        queue
+    }
+
+    #[test]
+    fn advances_after_consecutive_updates_inside_the_radius() {
+        let mut queue = running(&[100.0, 200.0]);
+        let steps = fly(&mut queue, &[0.0, 50.0, 99.0, 100.5, 101.0]);
+        assert_eq!(&steps[..4], &[QueueStep::Hold; 4]);
+        assert_eq!(steps[4], QueueStep::Advance(point(200.0)));
+        assert_eq!(queue.status().current_index, Some(1));
+
+        let steps = fly(&mut queue, &[199.0, 199.5, 200.0]);
+        assert_eq!(steps[2], QueueStep::Finished);
+        assert_eq!(queue.status().state, GuidedQueueState::Completed);
+        assert_eq!(fly(&mut queue, &[200.0]), vec![QueueStep::Hold]);
+    }
+
+    #[test]
+    fn jitter_across_the_radius_does_not_advance() {
+        let mut queue = running(&[100.0, 200.0]);
+        // Hovering at the edge, fixes alternate either side of 2 m.
+        let steps = fly(&mut queue, &[98.5, 97.5, 98.5, 97.5, 98.5, 97.5, 98.2]);
+        assert!(steps.iter().all(|step| *step == QueueStep::Hold));
+        let steps = fly(&mut queue, &[98.5, 99.0]);
+        assert_eq!(steps[1], QueueStep::Advance(point(200.0)));
+    }
+
+    #[test]
+    fn a_close_overshoot_counts_as_reached() {
+        let mut queue = running(&[100.0, 0.0]);
+        // Flies through at speed: one fix 4 m short, the next 3 m past.
+        let steps = fly(&mut queue, &[80.0, 96.0, 103.0, 106.0, 109.0, 112.0]);
+        assert_eq!(&steps[..5], &[QueueStep::Hold; 5]);
+        assert_eq!(steps[5], QueueStep::Advance(point(0.0)));
+    }
+
+    #[test]
+    fn a_wide_miss_is_not_an_arrival() {
+        let mut queue = running(&[100.0]);
+        // Passes 20 m east of the point.
+        let east_deg = 20.0 / (M_PER_DEG * 47f64.to_radians().cos());
+        for north_m in [0.0, 50.0, 100.0, 150.0, 200.0, 250.0] {
+            let step = queue.observe(47.0 + north_m / M_PER_DEG, 8.0 + east_deg);
+            assert_eq!(step, QueueStep::Hold);
+        }
+        assert!(queue.is_running());
+    }
+
+    #[test]
+    fn stopping_and_restarting() {
+        let mut queue = running(&[100.0, 200.0]);
+        fly(&mut queue, &[100.0, 100.0, 100.0]);
+        queue.stop("vehicle disarmed");
+        let status = queue.status();
+        assert_eq!(status.state, GuidedQueueState::Stopped);
+        assert_eq!(status.current_index, Some(1));
+        assert_eq!(status.stop_reason.as_deref(), Some("vehicle disarmed"));
+        assert_eq!(fly(&mut queue, &[200.0; 3]), vec![QueueStep::Hold; 3]);
+
+        assert_eq!(queue.start(2.0), Ok(point(100.0)));
+        assert!(queue.start(2.0).is_err());
+        assert_eq!(queue.status().stop_reason, None);
+
+        assert!(GuidedQueue::default().start(2.0).is_err());
+        assert!(GuidedQueue::new(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn stop_reasons_in_order_of_severity() {
+        assert_eq!(queue_stop_reason(true, true, true), None);
+        assert_eq!(
+            queue_stop_reason(true, true, false),
+            Some("vehicle left its guided mode")
+        );
+        assert_eq!(
+            queue_stop_reason(true, false, false),
+            Some("vehicle disarmed")
+        );
+        assert_eq!(queue_stop_reason(false, false, false), Some("link lost"));
+    }
+
+    #[test]
+    fn planes_are_given_their_turn_or_loiter_radius() {
+        let params = |pairs: &'static [(&'static str, f32)]| {
+            move |name: &str| {
+                pairs
+                    .iter()
+                    .find(|(param, _)| *param == name)
+                    .map(|(_, value)| *value)
+            }
+        };
+        let copter = queue_acceptance_radius_m(
+            AutopilotType::ArduPilotMega,
+            VehicleType::Quadrotor,
+            params(&[("WP_RADIUS", 90.0)]),
+        );
+        assert_eq!(copter, GUIDED_QUEUE_ACCEPTANCE_M);
+
+        // ArduPlane 4.5 defaults: the 90 m WP_RADIUS beats the 60 m loiter
+        // circle with its margin and the ~89 m L1 distance at 22 m/s.
+        let plane = queue_acceptance_radius_m(
+            AutopilotType::ArduPilotMega,
+            VehicleType::FixedWing,
+            params(&[
+                ("WP_RADIUS", 90.0),
+                ("WP_LOITER_RAD", 60.0),
+                ("NAVL1_PERIOD", 17.0),
+                ("NAVL1_DAMPING", 0.75),
+                ("AIRSPEED_CRUISE", 22.0),
+            ]),
+        );
+        assert_eq!(plane, 90.0);
+
+        // A tight WP_RADIUS on a fast plane turns one L1 distance out.
+        let fast = queue_acceptance_radius_m(
+            AutopilotType::ArduPilotMega,
+            VehicleType::Vtol,
+            params(&[
+                ("WP_RADIUS", 20.0),
+                ("WP_LOITER_RAD", -40.0),
+                ("NAVL1_PERIOD", 20.0),
+                ("NAVL1_DAMPING", 0.75),
+                ("TRIM_ARSPD_CM", 3000.0),
+            ]),
+        );
+        assert!((fast - 143.24).abs() < 0.01, "{fast}");
+
+        let px4 =
+            queue_acceptance_radius_m(AutopilotType::Px4, VehicleType::FixedWing, params(&[]));
+        assert_eq!(px4, PX4_DEFAULT_LOITER_RADIUS_M * LOITER_RADIUS_MARGIN);
+    }
+
+    #[test]
+    fn px4_flies_the_queue_in_hold_or_offboard() {
+        assert!(is_queue_mode(AutopilotType::ArduPilotMega, "GUIDED"));
+        assert!(!is_queue_mode(AutopilotType::ArduPilotMega, "LOITER"));
+        assert!(is_queue_mode(AutopilotType::Px4, "Hold"));
+        assert!(is_queue_mode(AutopilotType::Px4, "AUTO_LOITER"));
+        assert!(is_queue_mode(AutopilotType::Px4, "Offboard"));
+        assert!(!is_queue_mode(AutopilotType::Px4, "Position"));
+        assert!(!is_queue_mode(AutopilotType::Px4, "GUIDED"));
+    }
+}
@@ -0,0 +1,503 @@
+use mavkit::{SensorHealthState, SensorHealthSummary};
+
+use crate::dialect::EKF_STATUS_REPORT_DATA;
+use crate::ipc::{
+    GcsFailsafeBehavior, GpsFix, HealthComponent, HealthEntry, HealthReport, HealthStatus,
+    HealthThresholds, VtolState, VtolStatus,
+};
+use crate::link_loss::failsafe_check_warning;
+use crate::log_analysis::{EKF_VARIANCE_CRITICAL, EKF_VARIANCE_WARNING};
+
+pub const SYS_STATUS_MESSAGE_ID: u32 = 1;
+pub const EKF_STATUS_REPORT_MESSAGE_ID: u32 = 193;
+
+/// EKF_STATUS_FLAGS bits the EKF entry looks at.
+const EKF_ATTITUDE: u16 = 1;
+const EKF_POS_HORIZ_ABS: u16 = 16;
+const EKF_UNINITIALIZED: u16 = 1024;
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            battery_warn_pct: 30.0,
+            battery_error_pct: 15.0,
+            link_drop_warn_pct: 10.0,
+            link_drop_error_pct: 30.0,
+            heartbeat_warn_ms: 2_000,
+            heartbeat_error_ms: 5_000,
+        }
+    }
+}
+
+pub fn validate_health_thresholds(thresholds: &HealthThresholds) -> Result<(), String> {
+    let percentages = [
+        thresholds.battery_warn_pct,
+        thresholds.battery_error_pct,
+        thresholds.link_drop_warn_pct,
+        thresholds.link_drop_error_pct,
+    ];
+    if percentages
+        .iter()
+        .any(|pct| !pct.is_finite() || !(0.0..=100.0).contains(pct))
+    {
+        return Err("health thresholds must be percentages between 0 and 100".to_string());
+    }
+    if thresholds.battery_error_pct > thresholds.battery_warn_pct {
+        return Err("battery_error_pct must not be above battery_warn_pct".to_string());
+    }
+    if thresholds.link_drop_error_pct < thresholds.link_drop_warn_pct {
+        return Err("link_drop_error_pct must not be below link_drop_warn_pct".to_string());
+    }
+    if thresholds.heartbeat_error_ms < thresholds.heartbeat_warn_ms {
+        return Err("heartbeat_error_ms must not be below heartbeat_warn_ms".to_string());
+    }
+    Ok(())
+}
+
+/// EKF_STATUS_REPORT reduced to the flags and the worst variance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EkfStatus {
+    pub flags: u16,
+    pub max_variance: f32,
+}
+
+pub fn ekf_status(data: &EKF_STATUS_REPORT_DATA) -> EkfStatus {
+    let max_variance = [
+        data.velocity_variance,
+        data.pos_horiz_variance,
+        data.pos_vert_variance,
+        data.compass_variance,
+        data.terrain_alt_variance,
+    ]
+    .into_iter()
+    .filter(|variance| variance.is_finite())
+    .fold(0.0, f32::max);
+    EkfStatus {
+        flags: data.flags.bits(),
+        max_variance,
+    }
+}
+
+/// Everything the report is built from. `None` means the vehicle has not
+/// reported it, and the component is left out of the report; a missing
+/// heartbeat is the exception and is an error.
+#[derive(Debug, Clone, Default)]
+pub struct HealthInputs {
+    pub sensors: Option<SensorHealthSummary>,
+    pub ekf: Option<EkfStatus>,
+    pub gps_fix: Option<GpsFix>,
+    pub battery_pct: Option<f64>,
+    /// SYS_STATUS drop_rate_comm, as a percentage.
+    pub link_drop_pct: Option<f64>,
+    pub failsafe: Option<GcsFailsafeBehavior>,
+    pub heartbeat_age_ms: Option<u64>,
+    pub vtol: Option<VtolStatus>,
+}
+
+fn entry(
+    component: HealthComponent,
+    status: HealthStatus,
+    detail: impl Into<String>,
+) -> HealthEntry {
+    HealthEntry {
+        component,
+        status,
+        detail: detail.into(),
+    }
+}
+
+pub fn sensors_entry(sensors: &SensorHealthSummary) -> HealthEntry {
+    let sensors = [
+        ("gyro", sensors.gyro),
+        ("accelerometer", sensors.accel),
+        ("compass", sensors.mag),
+        ("barometer", sensors.baro),
+        ("GPS", sensors.gps),
+        ("airspeed", sensors.airspeed),
+        ("RC receiver", sensors.rc_receiver),
+        ("battery", sensors.battery),
+        ("terrain", sensors.terrain),
+        ("geofence", sensors.geofence),
+    ];
+    let unhealthy: Vec<&str> = sensors
+        .iter()
+        .filter(|(_, state)| *state == SensorHealthState::Unhealthy)
+        .map(|(name, _)| *name)
+        .collect();
+    if !unhealthy.is_empty() {
+        return entry(
+            HealthComponent::Sensors,
+            HealthStatus::Error,
+            format!("unhealthy: {}", unhealthy.join(", ")),
+        );
+    }
+    let healthy = sensors
+        .iter()
+        .filter(|(_, state)| *state == SensorHealthState::Healthy)
+        .count();
+    entry(
+        HealthComponent::Sensors,
+        HealthStatus::Ok,
+        format!("{healthy} enabled sensors healthy"),
+    )
+}
+
+pub fn ekf_entry(ekf: &EkfStatus) -> HealthEntry {
+    let (status, detail) = if ekf.flags & EKF_UNINITIALIZED != 0 {
+        (HealthStatus::Error, "not initialised".to_string())
+    } else if ekf.flags & EKF_ATTITUDE == 0 {
+        (HealthStatus::Error, "no attitude estimate".to_string())
+    } else if f64::from(ekf.max_variance) >= EKF_VARIANCE_CRITICAL {
+        (
+            HealthStatus::Error,
+            format!("variance {:.2}", ekf.max_variance),
+        )
+    } else if f64::from(ekf.max_variance) >= EKF_VARIANCE_WARNING {
+        (
+            HealthStatus::Warn,
+            format!("variance {:.2}", ekf.max_variance),
+        )
+    } else if ekf.flags & EKF_POS_HORIZ_ABS == 0 {
+        (HealthStatus::Warn, "no horizontal position".to_string())
+    } else {
+        (
+            HealthStatus::Ok,
+            format!("variance {:.2}", ekf.max_variance),
+        )
+    };
+    entry(HealthComponent::Ekf, status, detail)
+}
+
+pub fn gps_entry(fix: GpsFix) -> HealthEntry {
+    let (status, detail) = match fix {
+        GpsFix::NoGps => (HealthStatus::Warn, "no GPS detected"),
+        GpsFix::NoFix => (HealthStatus::Error, "no fix"),
+        GpsFix::Fix2d => (HealthStatus::Warn, "2D fix"),
+        GpsFix::Fix3d => (HealthStatus::Ok, "3D fix"),
+        GpsFix::Dgps => (HealthStatus::Ok, "DGPS fix"),
+        GpsFix::RtkFloat => (HealthStatus::Ok, "RTK float"),
+        GpsFix::RtkFixed => (HealthStatus::Ok, "RTK fixed"),
+        GpsFix::Static => (HealthStatus::Ok, "static fix"),
+        GpsFix::Ppp => (HealthStatus::Ok, "PPP fix"),
+    };
+    entry(HealthComponent::Gps, status, detail)
+}
+
+pub fn battery_entry(battery_pct: f64, thresholds: &HealthThresholds) -> HealthEntry {
+    let status = if battery_pct <= thresholds.battery_error_pct {
+        HealthStatus::Error
+    } else if battery_pct <= thresholds.battery_warn_pct {
+        HealthStatus::Warn
+    } else {
+        HealthStatus::Ok
+    };
+    entry(
+        HealthComponent::Battery,
+        status,
+        format!("{battery_pct:.0}% remaining"),
+    )
+}
+
+pub fn link_entry(drop_pct: f64, thresholds: &HealthThresholds) -> HealthEntry {
+    let status = if drop_pct >= thresholds.link_drop_error_pct {
+        HealthStatus::Error
+    } else if drop_pct >= thresholds.link_drop_warn_pct {
+        HealthStatus::Warn
+    } else {
+        HealthStatus::Ok
+    };
+    entry(
+        HealthComponent::Link,
+        status,
+        format!("{drop_pct:.1}% of packets dropped"),
+    )
+}
+
+/// `None` until the failsafe parameters have been downloaded.
+pub fn failsafe_entry(failsafe: &GcsFailsafeBehavior) -> Option<HealthEntry> {
+    failsafe.parameter.as_ref()?;
+    Some(match failsafe_check_warning(failsafe) {
+        Some(warning) => entry(HealthComponent::Failsafe, HealthStatus::Warn, warning),
+        None => entry(
+            HealthComponent::Failsafe,
+            HealthStatus::Ok,
+            format!("GCS failsafe: {}", failsafe.action),
+        ),
+    })
+}
+
+pub fn heartbeat_entry(age_ms: Option<u64>, thresholds: &HealthThresholds) -> HealthEntry {
+    let Some(age_ms) = age_ms else {
+        return entry(
+            HealthComponent::Heartbeat,
+            HealthStatus::Error,
+            "no heartbeat",
+        );
+    };
+    let status = if age_ms >= thresholds.heartbeat_error_ms {
+        HealthStatus::Error
+    } else if age_ms >= thresholds.heartbeat_warn_ms {
+        HealthStatus::Warn
+    } else {
+        HealthStatus::Ok
+    };
+    entry(
+        HealthComponent::Heartbeat,
+        status,
+        format!("last heartbeat {:.1} s ago", age_ms as f64 / 1000.0),
+    )
+}
+
+/// `None` for frames that are not VTOLs. A transition in progress is a
+/// warning: the vehicle is neither hovering nor flying on its wings yet.
+pub fn vtol_entry(vtol: &VtolStatus) -> Option<HealthEntry> {
+    let (status, detail) = match vtol.vtol_state {
+        VtolState::Undefined => return None,
+        VtolState::MultiCopter => (HealthStatus::Ok, "multicopter flight"),
+        VtolState::FixedWing => (HealthStatus::Ok, "fixed-wing flight"),
+        VtolState::TransitionToFixedWing => {
+            (HealthStatus::Warn, "transitioning to fixed-wing flight")
+        }
+        VtolState::TransitionToMultiCopter => {
+            (HealthStatus::Warn, "transitioning to multicopter flight")
+        }
+    };
+    Some(entry(HealthComponent::Vtol, status, detail))
+}
+
+pub fn health_report(inputs: &HealthInputs, thresholds: &HealthThresholds) -> HealthReport {
+    let entries: Vec<HealthEntry> = [
+        inputs.sensors.as_ref().map(sensors_entry),
+        inputs.ekf.as_ref().map(ekf_entry),
+        inputs.gps_fix.map(gps_entry),
+        inputs.battery_pct.map(|pct| battery_entry(pct, thresholds)),
+        inputs.link_drop_pct.map(|pct| link_entry(pct, thresholds)),
+        inputs.failsafe.as_ref().and_then(failsafe_entry),
+        Some(heartbeat_entry(inputs.heartbeat_age_ms, thresholds)),
+        inputs.vtol.as_ref().and_then(vtol_entry),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    HealthReport {
+        overall: entries
+            .iter()
+            .map(|entry| entry.status)
+            .max()
+            .unwrap_or(HealthStatus::Ok),
+        entries,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sensors(gyro: SensorHealthState, mag: SensorHealthState) -> SensorHealthSummary {
+        SensorHealthSummary {
+            gyro,
+            accel: SensorHealthState::Healthy,
+            mag,
+            baro: SensorHealthState::Healthy,
+            gps: SensorHealthState::Healthy,
+            airspeed: SensorHealthState::NotPresent,
+            rc_receiver: SensorHealthState::Healthy,
+            battery: SensorHealthState::Healthy,
+            terrain: SensorHealthState::NotPresent,
+            geofence: SensorHealthState::Disabled,
+        }
+    }
+
+    fn failsafe(parameter: Option<&str>, does_nothing: bool) -> GcsFailsafeBehavior {
+        GcsFailsafeBehavior {
+            parameter: parameter.map(str::to_string),
+            value: parameter.map(|_| 1.0),
+            action: "RTL".to_string(),
+            does_nothing,
+        }
+    }
+
+    #[test]
+    fn sensor_rules() {
+        use SensorHealthState::{Disabled, Healthy, Unhealthy};
+        let cases = [
+            (sensors(Healthy, Healthy), HealthStatus::Ok, "7 enabled"),
+            (sensors(Healthy, Disabled), HealthStatus::Ok, "6 enabled"),
+            (sensors(Unhealthy, Healthy), HealthStatus::Error, "gyro"),
+            (
+                sensors(Unhealthy, Unhealthy),
+                HealthStatus::Error,
+                "gyro, compass",
+            ),
+        ];
+        for (summary, status, detail) in cases {
+            let entry = sensors_entry(&summary);
+            assert_eq!(entry.status, status, "{summary:?}");
+            assert!(entry.detail.contains(detail), "{}", entry.detail);
+        }
+    }
+
+    #[test]
+    fn ekf_rules() {
+        let ok_flags = EKF_ATTITUDE | EKF_POS_HORIZ_ABS;
+        let cases = [
+            (ok_flags, 0.1, HealthStatus::Ok),
+            (ok_flags, 0.5, HealthStatus::Warn),
+            (ok_flags, 0.9, HealthStatus::Error),
+            (EKF_ATTITUDE, 0.1, HealthStatus::Warn),
+            (EKF_POS_HORIZ_ABS, 0.1, HealthStatus::Error),
+            (ok_flags | EKF_UNINITIALIZED, 0.1, HealthStatus::Error),
+        ];
+        for (flags, max_variance, status) in cases {
+            let ekf = EkfStatus {
+                flags,
+                max_variance,
+            };
+            assert_eq!(ekf_entry(&ekf).status, status, "{ekf:?}");
+        }
+    }
+
+    #[test]
+    fn ekf_status_takes_the_worst_variance() {
+        let ekf = ekf_status(&EKF_STATUS_REPORT_DATA {
+            velocity_variance: 0.2,
+            compass_variance: 0.6,
+            terrain_alt_variance: f32::NAN,
+            ..Default::default()
+        });
+        assert_eq!(ekf.max_variance, 0.6);
+    }
+
+    #[test]
+    fn gps_rules() {
+        let cases = [
+            (GpsFix::NoGps, HealthStatus::Warn),
+            (GpsFix::NoFix, HealthStatus::Error),
+            (GpsFix::Fix2d, HealthStatus::Warn),
+            (GpsFix::Fix3d, HealthStatus::Ok),
+            (GpsFix::RtkFixed, HealthStatus::Ok),
+        ];
+        for (fix, status) in cases {
+            assert_eq!(gps_entry(fix).status, status, "{fix:?}");
+        }
+    }
+
+    #[test]
+    fn threshold_rules() {
+        let thresholds = HealthThresholds::default();
+        let battery = [
+            (80.0, HealthStatus::Ok),
+            (30.0, HealthStatus::Warn),
+            (15.0, HealthStatus::Error),
+            (0.0, HealthStatus::Error),
+        ];
+        for (pct, status) in battery {
+            assert_eq!(battery_entry(pct, &thresholds).status, status, "{pct}%");
+        }
+        let link = [
+            (0.0, HealthStatus::Ok),
+            (10.0, HealthStatus::Warn),
+            (45.0, HealthStatus::Error),
+        ];
+        for (pct, status) in link {
+            assert_eq!(link_entry(pct, &thresholds).status, status, "{pct}%");
+        }
+        let heartbeat = [
+            (Some(500), HealthStatus::Ok),
+            (Some(2_000), HealthStatus::Warn),
+            (Some(6_000), HealthStatus::Error),
+            (None, HealthStatus::Error),
+        ];
+        for (age_ms, status) in heartbeat {
+            assert_eq!(
+                heartbeat_entry(age_ms, &thresholds).status,
+                status,
+                "{age_ms:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn failsafe_rules() {
+        let cases = [
+            (
+                failsafe(Some("FS_GCS_ENABLE"), false),
+                Some(HealthStatus::Ok),
+            ),
+            (
+                failsafe(Some("FS_GCS_ENABLE"), true),
+                Some(HealthStatus::Warn),
+            ),
+            (failsafe(None, false), None),
+        ];
+        for (behavior, status) in cases {
+            assert_eq!(
+                failsafe_entry(&behavior).map(|entry| entry.status),
+                status,
+                "{behavior:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn vtol_rules() {
+        let vtol = |vtol_state| VtolStatus {
+            vtol_state,
+            landed_state: crate::ipc::LandedState::InAir,
+        };
+        let cases = [
+            (VtolState::Undefined, None),
+            (VtolState::FixedWing, Some(HealthStatus::Ok)),
+            (VtolState::TransitionToMultiCopter, Some(HealthStatus::Warn)),
+        ];
+        for (state, status) in cases {
+            assert_eq!(
+                vtol_entry(&vtol(state)).map(|entry| entry.status),
+                status,
+                "{state:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn report_rolls_up_to_the_worst_entry_and_skips_unknowns() {
+        let thresholds = HealthThresholds::default();
+        let report = health_report(
+            &HealthInputs {
+                gps_fix: Some(GpsFix::Fix3d),
+                battery_pct: Some(25.0),
+                heartbeat_age_ms: Some(100),
+                ..Default::default()
+            },
+            &thresholds,
+        );
+        assert_eq!(report.overall, HealthStatus::Warn);
+        let components: Vec<_> = report.entries.iter().map(|entry| entry.component).collect();
+        assert_eq!(
+            components,
+            [
+                HealthComponent::Gps,
+                HealthComponent::Battery,
+                HealthComponent::Heartbeat
+            ]
+        );
+
+        let disconnected = health_report(&HealthInputs::default(), &thresholds);
+        assert_eq!(disconnected.overall, HealthStatus::Error);
+    }
+
+    #[test]
+    fn thresholds_must_be_ordered() {
+        assert!(validate_health_thresholds(&HealthThresholds::default()).is_ok());
+        let inverted = HealthThresholds {
+            battery_error_pct: 40.0,
+            ..HealthThresholds::default()
+        };
+        assert!(validate_health_thresholds(&inverted).is_err());
+        let out_of_range = HealthThresholds {
+            link_drop_error_pct: 150.0,
+            ..HealthThresholds::default()
+        };
+        assert!(validate_health_thresholds(&out_of_range).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+
+use web_time::Instant;
+
+use crate::ipc::vehicles::{ComponentListEntry, VehicleListEntry};
+
+const MAV_TYPE_GCS: u8 = 6;
+const MAV_AUTOPILOT_INVALID: u8 = 8;
+const MAV_MODE_FLAG_SAFETY_ARMED: u8 = 0x80;
+
+/// HEARTBEAT fields reduced to plain integers so the registry does not depend
+/// on the generated dialect enums.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeartbeatObservation {
+    pub system_id: u8,
+    pub component_id: u8,
+    pub mav_type: u8,
+    pub autopilot: u8,
+    pub base_mode: u8,
+    pub custom_mode: u32,
+    pub system_status: u8,
+}
+
+impl HeartbeatObservation {
+    fn is_vehicle(&self) -> bool {
+        self.autopilot != MAV_AUTOPILOT_INVALID && self.mav_type != MAV_TYPE_GCS
+    }
+}
+
+/// Every (system_id, component_id) pair that has sent a HEARTBEAT on the link.
+#[derive(Debug, Clone, Default)]
+pub struct HeartbeatRegistry {
+    sources: BTreeMap<(u8, u8), (HeartbeatObservation, Instant)>,
+}
+
+impl HeartbeatRegistry {
+    pub fn observe(&mut self, heartbeat: HeartbeatObservation, now: Instant) {
+        self.sources.insert(
+            (heartbeat.system_id, heartbeat.component_id),
+            (heartbeat, now),
+        );
+    }
+
+    pub fn clear(&mut self) {
+        self.sources.clear();
+    }
+
+    pub fn contains_vehicle(&self, system_id: u8) -> bool {
+        self.sources
+            .values()
+            .any(|(heartbeat, _)| heartbeat.system_id == system_id && heartbeat.is_vehicle())
+    }
+
+    /// One entry per system that has an autopilot component, represented by its
+    /// lowest autopilot component ID. GCS and peripheral-only systems are skipped.
+    pub fn vehicles(&self, now: Instant, selected: Option<u8>) -> Vec<VehicleListEntry> {
+        let mut vehicles: Vec<VehicleListEntry> = Vec::new();
+        for (heartbeat, seen_at) in self.sources.values() {
+            if !heartbeat.is_vehicle()
+                || vehicles
+                    .last()
+                    .is_some_and(|entry| entry.system_id == heartbeat.system_id)
+            {
+                continue;
+            }
+            vehicles.push(VehicleListEntry {
+                system_id: heartbeat.system_id,
+                component_id: heartbeat.component_id,
+                mav_type: heartbeat.mav_type,
+                autopilot: heartbeat.autopilot,
+                system_status: heartbeat.system_status,
+                armed: heartbeat.base_mode & MAV_MODE_FLAG_SAFETY_ARMED != 0,
+                custom_mode: heartbeat.custom_mode,
+                last_seen_ms_ago: now.saturating_duration_since(*seen_at).as_millis() as u64,
+                selected: selected == Some(heartbeat.system_id),
+            });
+        }
+        vehicles
+    }
+
+    pub fn components(&self, system_id: u8, now: Instant) -> Vec<ComponentListEntry> {
+        self.sources
+            .range((system_id, 0)..=(system_id, u8::MAX))
+            .map(|(_, (heartbeat, seen_at))| ComponentListEntry {
+                component_id: heartbeat.component_id,
+                mav_type: heartbeat.mav_type,
+                autopilot: heartbeat.autopilot,
+                last_seen_ms_ago: now.saturating_duration_since(*seen_at).as_millis() as u64,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn heartbeat(
+        system_id: u8,
+        component_id: u8,
+        mav_type: u8,
+        autopilot: u8,
+    ) -> HeartbeatObservation {
+        HeartbeatObservation {
+            system_id,
+            component_id,
+            mav_type,
+            autopilot,
+            base_mode: 0,
+            custom_mode: 0,
+            system_status: 4,
+        }
+    }
+
+    #[test]
+    fn vehicles_lists_one_entry_per_autopilot_system() {
+        let start = Instant::now();
+        let mut registry = HeartbeatRegistry::default();
+        registry.observe(
+            heartbeat(255, 190, MAV_TYPE_GCS, MAV_AUTOPILOT_INVALID),
+            start,
+        );
+        registry.observe(heartbeat(2, 1, 2, 3), start);
+        registry.observe(heartbeat(1, 154, 26, MAV_AUTOPILOT_INVALID), start);
+        registry.observe(
+            HeartbeatObservation {
+                base_mode: MAV_MODE_FLAG_SAFETY_ARMED,
+                custom_mode: 5,
+                ..heartbeat(1, 1, 2, 3)
+            },
+            start + Duration::from_millis(500),
+        );
+        registry.observe(heartbeat(3, 1, 1, 3), start);
+
+        let vehicles = registry.vehicles(start + Duration::from_secs(1), Some(1));
+
+        let ids: Vec<u8> = vehicles.iter().map(|entry| entry.system_id).collect();
+        assert_eq!(ids, [1, 2, 3]);
+        assert!(vehicles[0].armed);
+        assert!(vehicles[0].selected);
+        assert_eq!(vehicles[0].component_id, 1);
+        assert_eq!(vehicles[0].custom_mode, 5);
+        assert_eq!(vehicles[0].last_seen_ms_ago, 500);
+        assert!(!vehicles[1].selected);
+        assert!(registry.contains_vehicle(3));
+        let components: Vec<u8> = registry
+            .components(1, start)
+            .iter()
+            .map(|entry| entry.component_id)
+            .collect();
+        assert_eq!(components, [1, 154]);
+        assert!(!registry.contains_vehicle(255));
+    }
+}
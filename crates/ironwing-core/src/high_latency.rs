@@ -0,0 +1,103 @@
+use crate::dialect::HIGH_LATENCY2_DATA;
+use crate::ipc::telemetry::{TelemetryFlight, TelemetryNavigation, TelemetryPower, TelemetryState};
+
+/// MAVLink message id of HIGH_LATENCY2.
+pub const HIGH_LATENCY2_MESSAGE_ID: u32 = 235;
+
+/// The parts of a HIGH_LATENCY2 report the UI consumes: the telemetry fields
+/// it can fill and the (16-bit truncated) autopilot custom mode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HighLatencyReport {
+    pub telemetry: TelemetryState,
+    pub custom_mode: u32,
+}
+
+/// Convert HIGH_LATENCY2's coarse wire units into the regular telemetry
+/// fields so panels keep working without the full-rate streams.
+///
+/// Units per the MAVLink spec: latitude/longitude in degE7, altitude in m,
+/// headings in deg/2, target distance in dam, speeds in m/s*5, climb rate in
+/// dm/s and battery in % (-1 when unknown).
+pub fn high_latency2_report(data: &HIGH_LATENCY2_DATA) -> HighLatencyReport {
+    let telemetry = TelemetryState {
+        flight: TelemetryFlight {
+            altitude_m: Some(f64::from(data.altitude)),
+            speed_mps: Some(f64::from(data.groundspeed) / 5.0),
+            climb_rate_mps: Some(f64::from(data.climb_rate) / 10.0),
+            throttle_pct: Some(f64::from(data.throttle)),
+            airspeed_mps: Some(f64::from(data.airspeed) / 5.0),
+        },
+        navigation: TelemetryNavigation {
+            latitude_deg: Some(f64::from(data.latitude) / 1e7),
+            longitude_deg: Some(f64::from(data.longitude) / 1e7),
+            heading_deg: Some(f64::from(data.heading) * 2.0),
+            wp_dist_m: Some(f64::from(data.target_distance) * 10.0),
+            target_bearing_deg: Some(f64::from(data.target_heading) * 2.0),
+            ..TelemetryNavigation::default()
+        },
+        power: TelemetryPower {
+            battery_pct: (data.battery >= 0).then_some(f64::from(data.battery)),
+            ..TelemetryPower::default()
+        },
+        ..TelemetryState::default()
+    };
+
+    HighLatencyReport {
+        telemetry,
+        custom_mode: u32::from(data.custom_mode),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn high_latency2_units_map_to_telemetry_fields() {
+        let data = HIGH_LATENCY2_DATA {
+            latitude: 473_977_420,
+            longitude: 85_455_940,
+            custom_mode: 10,
+            altitude: 488,
+            target_distance: 25,
+            heading: 135,
+            target_heading: 45,
+            throttle: 62,
+            airspeed: 90,
+            groundspeed: 87,
+            climb_rate: -15,
+            battery: 73,
+            ..HIGH_LATENCY2_DATA::default()
+        };
+
+        let report = high_latency2_report(&data);
+        let telemetry = report.telemetry;
+
+        assert_eq!(report.custom_mode, 10);
+        assert_eq!(telemetry.navigation.latitude_deg, Some(47.397742));
+        assert_eq!(telemetry.navigation.longitude_deg, Some(8.545594));
+        assert_eq!(telemetry.navigation.heading_deg, Some(270.0));
+        assert_eq!(telemetry.navigation.target_bearing_deg, Some(90.0));
+        assert_eq!(telemetry.navigation.wp_dist_m, Some(250.0));
+        assert_eq!(telemetry.flight.altitude_m, Some(488.0));
+        assert_eq!(telemetry.flight.airspeed_mps, Some(18.0));
+        assert_eq!(telemetry.flight.speed_mps, Some(17.4));
+        assert_eq!(telemetry.flight.climb_rate_mps, Some(-1.5));
+        assert_eq!(telemetry.flight.throttle_pct, Some(62.0));
+        assert_eq!(telemetry.power.battery_pct, Some(73.0));
+        assert_eq!(telemetry.attitude.roll_deg, None);
+    }
+
+    #[test]
+    fn unknown_battery_is_left_empty() {
+        let data = HIGH_LATENCY2_DATA {
+            battery: -1,
+            ..HIGH_LATENCY2_DATA::default()
+        };
+
+        assert_eq!(
+            high_latency2_report(&data).telemetry.power.battery_pct,
+            None
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use crate::ipc::HomeMoved;
+use crate::log_engine::haversine_m;
+
+/// Smallest home shift reported; below this it is GPS noise from a
+/// re-sent HOME_POSITION, not a new home.
+pub const HOME_MOVED_THRESHOLD_M: f64 = 5.0;
+
+/// The report for home moving from `previous` to `new` (latitude, longitude
+/// in degrees), or `None` when the shift is under the threshold.
+/// `vehicle_position` is the vehicle's current fix, if it has one.
+pub fn home_moved(
+    previous: (f64, f64),
+    new: (f64, f64),
+    vehicle_position: Option<(f64, f64)>,
+) -> Option<HomeMoved> {
+    let distance_m = haversine_m(previous.0, previous.1, new.0, new.1);
+    if distance_m < HOME_MOVED_THRESHOLD_M {
+        return None;
+    }
+    Some(HomeMoved {
+        old_latitude_deg: previous.0,
+        old_longitude_deg: previous.1,
+        new_latitude_deg: new.0,
+        new_longitude_deg: new.1,
+        distance_m,
+        vehicle_to_new_home_m: vehicle_position
+            .map(|(latitude, longitude)| haversine_m(latitude, longitude, new.0, new.1)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOME: (f64, f64) = (47.3977, 8.5456);
+
+    #[test]
+    fn small_shifts_are_noise() {
+        assert_eq!(home_moved(HOME, HOME, None), None);
+        // ~1.1 m north.
+        assert_eq!(home_moved(HOME, (HOME.0 + 0.00001, HOME.1), None), None);
+    }
+
+    #[test]
+    fn a_moved_home_reports_both_distances() {
+        // ~111 m north.
+        let new = (HOME.0 + 0.001, HOME.1);
+        let moved = home_moved(HOME, new, Some(HOME)).expect("home moved");
+        assert_eq!(moved.old_latitude_deg, HOME.0);
+        assert_eq!(moved.new_latitude_deg, new.0);
+        assert!(
+            (moved.distance_m - 111.2).abs() < 0.5,
+            "{}",
+            moved.distance_m
+        );
+        let to_home = moved.vehicle_to_new_home_m.expect("vehicle has a fix");
+        assert!((to_home - moved.distance_m).abs() < 1e-6);
+
+        let moved = home_moved(HOME, new, None).expect("home moved");
+        assert_eq!(moved.vehicle_to_new_home_m, None);
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertCondition {
+    Lt,
+    Gt,
+    Eq,
+    Changed,
+}
+
+/// One alert rule evaluated by the backend against live telemetry.
+///
+/// `source` is a dotted path into `telemetry` (a `TelemetryState`),
+/// `vehicle` (a `VehicleState`), `battery` (a `BatteryStats`) or `failsafe`
+/// (decoded failsafe counts), e.g. `telemetry.power.battery_pct`,
+/// `vehicle.mode_name`, `battery.cell_voltage_v` or `failsafe.radio` with
+/// `changed`. `threshold` is required for `lt`, `gt` and `eq`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AlertRule {
+    pub id: String,
+    pub source: String,
+    pub condition: AlertCondition,
+    #[serde(default)]
+    pub threshold: Option<f64>,
+    /// Distance past the threshold the value must move back before the rule
+    /// can fire again.
+    #[serde(default)]
+    pub hysteresis: f64,
+    /// Minimum time between two triggers of this rule.
+    #[serde(default)]
+    pub min_interval_s: f64,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum AlertValue {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+}
+
+/// Payload of `alert://triggered`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AlertTriggered {
+    pub rule_id: String,
+    pub source: String,
+    pub value: AlertValue,
+}
@@ -0,0 +1,51 @@
+/// How urgently a speech consumer should read an announcement out.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnouncementPriority {
+    /// Progress that can wait behind anything else queued.
+    Low,
+    Normal,
+    /// Safety-relevant; may interrupt an announcement being spoken.
+    High,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnouncementCategory {
+    Waypoint,
+    Altitude,
+    Battery,
+    Mode,
+    Failsafe,
+}
+
+/// Payload of `announce://say`: one sentence worded to be spoken.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Announcement {
+    pub category: AnnouncementCategory,
+    pub priority: AnnouncementPriority,
+    /// e.g. "Waypoint 5 of 12".
+    pub text: String,
+}
+
+/// Which progress announcements are made and how often.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AnnouncementSettings {
+    pub waypoint: bool,
+    pub altitude: bool,
+    /// Spacing of the altitude milestones announced while armed, in metres.
+    pub altitude_step_m: f64,
+    pub battery: bool,
+    /// Battery percentage between two announcements.
+    pub battery_step_pct: f64,
+    pub mode: bool,
+    /// How long a new mode must hold before it is announced, so a switch
+    /// flicking between modes is announced once, as where it ended up.
+    pub mode_settle_s: f64,
+    pub failsafe: bool,
+}
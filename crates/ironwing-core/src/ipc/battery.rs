@@ -0,0 +1,25 @@
+/// Where `BatteryStats::cell_count` came from, in order of preference.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CellCountSource {
+    /// BATTERY_STATUS carries one voltage per cell.
+    Reported,
+    /// BATT_CELL_COUNT-style parameter set on the vehicle.
+    Param,
+    /// Guessed from the first stable pack voltage.
+    Inferred,
+}
+
+/// Payload of `battery_stats` and `battery://stats`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct BatteryStats {
+    pub cell_count: Option<u8>,
+    pub cell_count_source: Option<CellCountSource>,
+    pub voltage_v: Option<f64>,
+    /// Lowest reported cell, or the pack voltage divided by `cell_count`.
+    pub cell_voltage_v: Option<f64>,
+    /// Individual cells, when BATTERY_STATUS reports them.
+    pub cell_voltages_v: Option<Vec<f64>>,
+}
@@ -30,6 +30,39 @@ pub struct CalibrationState {
 
 pub type CalibrationSnapshot = DomainValue<CalibrationState>;
 
+/// A calibration whose outcome the vehicle reports over STATUSTEXT.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CalibrationKind {
+    Gyro,
+    Accel,
+    Compass,
+}
+
+/// How a simple calibration ended, as reported after its ACK.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum CalibrationResult {
+    Succeeded,
+    Failed {
+        reason: String,
+    },
+    /// No completion message arrived in time; the calibration may still be
+    /// running on the vehicle.
+    Timeout,
+}
+
+/// A calibration STATUSTEXT seen while waiting for the result, sent on
+/// `calibration://progress`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CalibrationProgress {
+    pub kind: CalibrationKind,
+    pub text: String,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct CalibrationSources {
     mag_progress: Option<MagCalProgress>,
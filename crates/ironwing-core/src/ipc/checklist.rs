@@ -0,0 +1,78 @@
+/// Backend state an automatic checklist item verifies.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecklistCheck {
+    Gps3dFix,
+    /// Needs `threshold` as a percentage.
+    BatteryAbovePct,
+    HomeSet,
+    /// A non-empty mission is on the vehicle.
+    MissionUploaded,
+    /// No sensor reports unhealthy and no PreArm message arrived recently.
+    PrearmPass,
+}
+
+/// One checklist entry. Items without a `check` are manual and pass once the
+/// user ticks them.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ChecklistItem {
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub check: Option<ChecklistCheck>,
+    #[serde(default)]
+    pub threshold: Option<f64>,
+    /// Whether the item blocks arming when enforcement is on.
+    #[serde(default = "required_by_default")]
+    pub required: bool,
+}
+
+fn required_by_default() -> bool {
+    true
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ChecklistConfig {
+    pub items: Vec<ChecklistItem>,
+    /// Refuse `arm_vehicle` while a required item is unticked or failing.
+    pub enforce_on_arm: bool,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecklistItemStatus {
+    /// Manual item not ticked yet.
+    Unchecked,
+    /// Manual item ticked.
+    Checked,
+    Pass,
+    Fail,
+    /// The backend has nothing to verify the item against yet.
+    Unknown,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ChecklistItemState {
+    pub id: String,
+    pub label: String,
+    pub check: Option<ChecklistCheck>,
+    pub required: bool,
+    pub status: ChecklistItemStatus,
+    pub detail: Option<String>,
+}
+
+/// Payload of `checklist_state` and `checklist://update`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ChecklistState {
+    pub items: Vec<ChecklistItemState>,
+    pub enforce_on_arm: bool,
+    /// Every required item is checked or passing.
+    pub complete: bool,
+}
@@ -1,3 +1,4 @@
+use crate::ipc::telemetry::RcLinkQuality;
 use crate::transport::BluetoothProfile;
 
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
@@ -6,6 +7,47 @@ pub struct ConnectRequest {
     pub transport: ConnectTransport,
     #[serde(default)]
     pub auto_record_on_connect: bool,
+    /// How long to wait for the first heartbeat once the transport is open.
+    /// Defaults to 30 s.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat_timeout_secs: Option<u32>,
+    /// Keep the transport open past the heartbeat timeout until a heartbeat
+    /// arrives or the attempt is cancelled.
+    #[serde(default)]
+    pub keep_waiting: bool,
+}
+
+/// Where a connection handed to the app at launch came from.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PendingConnectSource {
+    /// `--connect <address>`.
+    CommandLine,
+    /// An `ironwing://connect?address=...` link.
+    DeepLink,
+}
+
+/// A connection asked for from outside the app, waiting for the frontend
+/// to run the connect flow. Returned by `pending_connect_take`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum PendingConnect {
+    Ready {
+        source: PendingConnectSource,
+        input: String,
+        request: ConnectRequest,
+        /// Connect without asking, per the `auto_connect_launch_link`
+        /// setting.
+        auto_connect: bool,
+    },
+    /// The address could not be used; shown to the user instead.
+    Invalid {
+        source: PendingConnectSource,
+        input: String,
+        message: String,
+    },
 }
 
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
@@ -15,6 +57,45 @@ pub struct DisconnectRequest {
     pub session_id: Option<String>,
 }
 
+/// What to command an armed vehicle to do before the link is closed.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisconnectAction {
+    None,
+    Rtl,
+    Land,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DisconnectActionOutcome {
+    /// Nothing was sent: no action was selected or the vehicle was disarmed.
+    Skipped {
+        reason: String,
+    },
+    Accepted,
+    /// The vehicle answered with anything but MAV_RESULT_ACCEPTED.
+    Rejected {
+        message: String,
+    },
+    /// No final COMMAND_ACK within the bound; the link was closed anyway.
+    TimedOut,
+    Failed {
+        message: String,
+    },
+}
+
+/// Emitted on `link://disconnect_action` before the transport closes.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DisconnectActionResult {
+    pub action: DisconnectAction,
+    pub armed: bool,
+    pub outcome: DisconnectActionOutcome,
+}
+
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
@@ -28,6 +109,13 @@ pub enum ConnectTransport {
     Serial {
         port: String,
         baud: u32,
+        #[serde(default)]
+        flow_control: SerialFlowControl,
+        #[serde(default)]
+        parity: SerialParity,
+        /// Probe the common rates for a HEARTBEAT instead of trusting `baud`.
+        #[serde(default)]
+        auto_baud: bool,
     },
     BluetoothBle {
         address: String,
@@ -37,6 +125,10 @@ pub enum ConnectTransport {
     BluetoothSpp {
         address: String,
     },
+    UsbSerial {
+        device_id: String,
+        baud: u32,
+    },
     #[serde(rename = "websocket")]
     WebSocket {
         url: String,
@@ -55,6 +147,245 @@ pub enum ConnectTransport {
     },
 }
 
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SerialFlowControl {
+    #[default]
+    None,
+    /// RTS/CTS; some FTDI-based telemetry radios drop bytes at high rates
+    /// without it.
+    Hardware,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SerialParity {
+    #[default]
+    None,
+    Odd,
+    Even,
+}
+
+/// A USB serial adapter attached through OTG, as reported by Android.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct UsbSerialDevice {
+    /// Android's device name; pass it back as `device_id` to connect.
+    pub device_id: String,
+    pub vendor_id: u16,
+    pub product_id: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manufacturer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub product: Option<String>,
+    /// Whether the user has already granted access; connecting asks otherwise.
+    pub has_permission: bool,
+}
+
+/// MAVLink system/component IDs IronWing uses for its own GCS HEARTBEAT and
+/// outgoing commands. Defaults match MAVKit's (255/190).
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GcsIdentity {
+    pub system_id: u8,
+    pub component_id: u8,
+}
+
+impl Default for GcsIdentity {
+    fn default() -> Self {
+        Self {
+            system_id: 255,
+            component_id: 190,
+        }
+    }
+}
+
+impl GcsIdentity {
+    /// ID 0 is the MAVLink broadcast address and cannot identify a sender.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.system_id == 0 {
+            return Err("GCS system ID must be between 1 and 255".into());
+        }
+        if self.component_id == 0 {
+            return Err("GCS component ID must be between 1 and 255".into());
+        }
+        Ok(())
+    }
+}
+
+/// How telemetry is sourced from the vehicle. `high_latency` works from
+/// HIGH_LATENCY2 reports instead of full-rate streams (Iridium, LTE relays).
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkProfile {
+    #[default]
+    Normal,
+    HighLatency,
+}
+
+/// Timeout profile a link gets from its transport: `ble` and `spp` links are
+/// slow enough that UDP-tuned timeouts give up on ACKs that are still coming,
+/// and `tcp` links do not lose frames, so resending sooner gains nothing.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkTimeoutProfile {
+    Udp,
+    Tcp,
+    Serial,
+    Ble,
+    Spp,
+}
+
+/// Command, mission and parameter timeouts for one link.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LinkTimeouts {
+    /// Wait for a COMMAND_ACK before resending the command.
+    pub command_ack_ms: u32,
+    /// Sends of one command, the first included.
+    pub command_attempts: u8,
+    /// Wait for the final ACK once the vehicle reports IN_PROGRESS.
+    pub command_completion_ms: u32,
+    /// Wait for each reply during a mission or parameter transfer.
+    pub transfer_ms: u32,
+}
+
+/// Replacements for the built-in timeouts of each profile; a profile left
+/// unset keeps its defaults.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LinkTimeoutOverrides {
+    pub udp: Option<LinkTimeouts>,
+    pub tcp: Option<LinkTimeouts>,
+    pub serial: Option<LinkTimeouts>,
+    pub ble: Option<LinkTimeouts>,
+    pub spp: Option<LinkTimeouts>,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ConnectionInfo {
+    pub connected: bool,
+    pub gcs: GcsIdentity,
+    pub link_profile: LinkProfile,
+    pub vehicle_system_id: Option<u8>,
+    pub vehicle_component_id: Option<u8>,
+    /// Baud rate of the active serial link, including one found by auto-baud.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serial_baud: Option<u32>,
+    /// Frame counters for links bridged through a byte stream (BLE, SPP,
+    /// USB OTG); other transports are parsed inside MAVKit and report none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_stats: Option<LinkFrameStats>,
+    /// Outgoing write counters for the same bridged links.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport_stats: Option<TransportStats>,
+    /// GCS positions sent by the current or last `gcs_position_stream_start`.
+    #[serde(default)]
+    pub gcs_position_fixes_sent: u64,
+    /// COMMAND_ACKs that arrived after their command had already timed out
+    /// on this link.
+    #[serde(default)]
+    pub late_command_acks: u64,
+}
+
+/// A (system_id, component_id) pair on the link.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LinkSource {
+    pub system_id: u8,
+    pub component_id: u8,
+}
+
+/// A source observed on the link and how many messages it has sent.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LinkSourceEntry {
+    pub system_id: u8,
+    pub component_id: u8,
+    pub messages: u64,
+}
+
+/// What the frame scanner made of one link's byte stream.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LinkFrameStats {
+    pub frames: u64,
+    pub bad_crc_frames: u64,
+    /// Bytes dropped while resyncing, including those of bad frames.
+    pub skipped_bytes: u64,
+    /// Valid frames dropped by the link source filter.
+    pub filtered_frames: u64,
+}
+
+/// Link health once a second, kept across reconnects for bug reports.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LinkQualitySample {
+    pub unix_msec: u64,
+    /// Whether MAVKit read the link as connected.
+    pub connected: bool,
+    /// SYS_STATUS drop_rate_comm, as a percentage.
+    pub drop_pct: Option<f64>,
+    pub rc_link: Option<RcLinkQuality>,
+    /// Cumulative for the link; only bridged byte-stream links have them.
+    pub frame_stats: Option<LinkFrameStats>,
+}
+
+/// How the outgoing drain of a bridged link has fared.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TransportStats {
+    pub chunks_sent: u64,
+    pub write_failures: u64,
+    /// Failed writes that were retried after a backoff.
+    pub write_retries: u64,
+    /// Messages dropped because the stall buffer was full.
+    pub dropped_messages: u64,
+}
+
+/// A bridged transport that kept failing writes and was torn down.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TransportLost {
+    pub transport: String,
+    pub error: String,
+    pub stats: TransportStats,
+}
+
+/// A message outside the dialect, emitted on `mavlink://unknown` at most
+/// once a second for each source and message id.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct UnknownMessage {
+    pub system_id: u8,
+    pub component_id: u8,
+    pub message_id: u32,
+    /// Payload of the latest one, MAVLink 2 trailing zeros still trimmed.
+    pub payload: Vec<u8>,
+    /// Received since the link came up.
+    pub count: u64,
+    /// `None` until a second one has arrived.
+    pub rate_hz: Option<f64>,
+}
+
+impl LinkFrameStats {
+    /// Share of frames that failed their checksum.
+    pub fn frame_error_rate(&self) -> f64 {
+        let total = self.frames + self.bad_crc_frames;
+        if total == 0 {
+            0.0
+        } else {
+            self.bad_crc_frames as f64 / total as f64
+        }
+    }
+}
+
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -63,3 +394,43 @@ pub enum DemoVehiclePreset {
     Airplane,
     Quadplane,
 }
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkConnectPhase {
+    OpeningTransport,
+    /// The transport is open but no vehicle heartbeat has arrived yet.
+    WaitingForHeartbeat,
+}
+
+/// Progress of a `connect_link` call, emitted as `link://connecting` until
+/// the first heartbeat arrives or the attempt fails.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LinkConnecting {
+    pub phase: LinkConnectPhase,
+    pub elapsed_ms: u64,
+    /// `None` when the user chose to keep waiting.
+    pub timeout_ms: Option<u64>,
+}
+
+/// The connection a previous run had open when it was killed, for offering a
+/// one-tap reconnect. `request` can be passed back to `connect_link` as is.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SessionRecoverInfo {
+    pub request: ConnectRequest,
+    pub connected_at_unix_msec: u64,
+    /// When the app last refreshed the file; a recent value means it was
+    /// still connected shortly before it died.
+    pub updated_at_unix_msec: u64,
+    pub armed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_latitude_deg: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_longitude_deg: Option<f64>,
+    /// Tlogs recorded during the session, oldest first.
+    #[serde(default)]
+    pub recording_paths: Vec<String>,
+}
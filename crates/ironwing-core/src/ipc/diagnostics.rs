@@ -0,0 +1,67 @@
+use crate::ipc::EventBridgeStats;
+
+/// Where a watched internal task stands.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    Running,
+    /// Alive, but its heartbeat is overdue.
+    Stalled,
+    Panicked,
+    /// Returned while the session it serves was still up.
+    Exited,
+}
+
+/// One row of the task table in `diagnostics`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TaskDiagnostic {
+    pub name: String,
+    pub state: TaskState,
+    /// Restarts after a panic this session.
+    pub restarts: u32,
+    /// Since the last heartbeat, or since the task started for tasks that
+    /// do not send one.
+    pub last_beat_age_ms: u64,
+    /// The panic message, for a task that panicked.
+    pub detail: Option<String>,
+}
+
+/// Payload of `internal://task_stalled`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TaskStalled {
+    pub component: String,
+    pub state: TaskState,
+    pub detail: Option<String>,
+    /// Whether the task is being spawned again.
+    pub restarting: bool,
+}
+
+/// Internal state for bug reports, as returned by `diagnostics`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Diagnostics {
+    pub tasks: Vec<TaskDiagnostic>,
+    pub event_bridges: Vec<EventBridgeStats>,
+}
+
+/// One file of a `diagnostics_export` bundle.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DiagnosticsBundleEntry {
+    pub file_name: String,
+    /// Uncompressed size.
+    pub bytes: u64,
+}
+
+/// Result of `diagnostics_export`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DiagnosticsExport {
+    pub path: String,
+    /// Size of the archive on disk.
+    pub bytes_written: u64,
+    pub entries: Vec<DiagnosticsBundleEntry>,
+}
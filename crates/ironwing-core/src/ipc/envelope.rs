@@ -13,6 +13,10 @@ pub struct SessionEnvelope {
     pub source_kind: SourceKind,
     pub seek_epoch: u64,
     pub reset_revision: u64,
+    /// MAVLink system ID of the vehicle a live event came from. Absent for
+    /// playback and before a vehicle is bound.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_id: Option<u8>,
 }
 
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
@@ -77,6 +81,42 @@ pub enum OperationId {
     RecordingSettingsWrite,
     FirmwareInstallUpdate,
     FirmwareBootloaderInstallation,
+    CameraTrigger,
+    GimbalSetAngles,
+    SetLinkProfile,
+    TunnelSend,
+    VehicleOrbit,
+    VehicleOrbitStop,
+    TakeoffGuided,
+    VehicleGuidedGotoEx,
+    ParamCommitToStorage,
+    FrameApply,
+    FlightmodeAssign,
+    SendCommandLong,
+    SendRawMessage,
+    GcsPositionStreamStart,
+    ScriptUpload,
+    ScriptDelete,
+    FirmwareStage,
+    ParamExternalChange,
+    GripperControl,
+    WinchControl,
+    RcOverrideStart,
+    RcOverrideSet,
+    RcOverrideStop,
+    SetFlightModeByName,
+    DisconnectLinkEx,
+    HomeMoved,
+    ConnectLink,
+    ParamSetBits,
+    VehicleGuidedGotoTerrain,
+    GuidedQueueStart,
+    PlaneTakeoff,
+    PlaneLoiterHere,
+    PlaneSetCruiseThrottle,
+    VtolTransition,
+    RoverHold,
+    RoverSetReverse,
 }
 
 impl OperationId {
@@ -139,6 +179,42 @@ impl OperationId {
         Self::RecordingSettingsWrite,
         Self::FirmwareInstallUpdate,
         Self::FirmwareBootloaderInstallation,
+        Self::CameraTrigger,
+        Self::GimbalSetAngles,
+        Self::SetLinkProfile,
+        Self::TunnelSend,
+        Self::VehicleOrbit,
+        Self::VehicleOrbitStop,
+        Self::TakeoffGuided,
+        Self::VehicleGuidedGotoEx,
+        Self::ParamCommitToStorage,
+        Self::FrameApply,
+        Self::FlightmodeAssign,
+        Self::SendCommandLong,
+        Self::SendRawMessage,
+        Self::GcsPositionStreamStart,
+        Self::ScriptUpload,
+        Self::ScriptDelete,
+        Self::FirmwareStage,
+        Self::ParamExternalChange,
+        Self::GripperControl,
+        Self::WinchControl,
+        Self::RcOverrideStart,
+        Self::RcOverrideSet,
+        Self::RcOverrideStop,
+        Self::SetFlightModeByName,
+        Self::DisconnectLinkEx,
+        Self::HomeMoved,
+        Self::ConnectLink,
+        Self::ParamSetBits,
+        Self::VehicleGuidedGotoTerrain,
+        Self::GuidedQueueStart,
+        Self::PlaneTakeoff,
+        Self::PlaneLoiterHere,
+        Self::PlaneSetCruiseThrottle,
+        Self::VtolTransition,
+        Self::RoverHold,
+        Self::RoverSetReverse,
     ];
 
     pub const fn as_str(self) -> &'static str {
@@ -201,6 +277,42 @@ impl OperationId {
             Self::RecordingSettingsWrite => "recording_settings_write",
             Self::FirmwareInstallUpdate => "firmware_install_update",
             Self::FirmwareBootloaderInstallation => "firmware_bootloader_installation",
+            Self::CameraTrigger => "camera_trigger",
+            Self::GimbalSetAngles => "gimbal_set_angles",
+            Self::SetLinkProfile => "set_link_profile",
+            Self::TunnelSend => "tunnel_send",
+            Self::VehicleOrbit => "vehicle_orbit",
+            Self::VehicleOrbitStop => "vehicle_orbit_stop",
+            Self::TakeoffGuided => "takeoff_guided",
+            Self::VehicleGuidedGotoEx => "vehicle_guided_goto_ex",
+            Self::ParamCommitToStorage => "param_commit_to_storage",
+            Self::FrameApply => "frame_apply",
+            Self::FlightmodeAssign => "flightmode_assign",
+            Self::SendCommandLong => "send_command_long",
+            Self::SendRawMessage => "send_raw_message",
+            Self::GcsPositionStreamStart => "gcs_position_stream_start",
+            Self::ScriptUpload => "script_upload",
+            Self::ScriptDelete => "script_delete",
+            Self::FirmwareStage => "firmware_stage",
+            Self::ParamExternalChange => "param_external_change",
+            Self::GripperControl => "gripper_control",
+            Self::WinchControl => "winch_control",
+            Self::RcOverrideStart => "rc_override_start",
+            Self::RcOverrideSet => "rc_override_set",
+            Self::RcOverrideStop => "rc_override_stop",
+            Self::SetFlightModeByName => "set_flight_mode_by_name",
+            Self::DisconnectLinkEx => "disconnect_link_ex",
+            Self::HomeMoved => "home_moved",
+            Self::ConnectLink => "connect_link",
+            Self::ParamSetBits => "param_set_bits",
+            Self::VehicleGuidedGotoTerrain => "vehicle_guided_goto_terrain",
+            Self::GuidedQueueStart => "guided_queue_start",
+            Self::PlaneTakeoff => "plane_takeoff",
+            Self::PlaneLoiterHere => "plane_loiter_here",
+            Self::PlaneSetCruiseThrottle => "plane_set_cruise_throttle",
+            Self::VtolTransition => "vtol_transition",
+            Self::RoverHold => "rover_hold",
+            Self::RoverSetReverse => "rover_set_reverse",
         }
     }
 }
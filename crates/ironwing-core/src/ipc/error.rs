@@ -0,0 +1,284 @@
+use std::fmt;
+
+use crate::ipc::SafetyGateFailure;
+use crate::live_runtime::LiveCommandError;
+
+/// The MAV_RESULT a vehicle refused a command with. MAVKit's rejections and
+/// COMMAND_ACKs IronWing reads itself both map onto this, so the frontend
+/// sees one spelling whichever path sent the command.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MavResultKind {
+    TemporarilyRejected,
+    Denied,
+    Unsupported,
+    Failed,
+    Cancelled,
+    /// A MAV_RESULT this build has no name for.
+    Unknown,
+}
+
+impl MavResultKind {
+    /// The refusal a COMMAND_ACK `result` field carries.
+    pub fn from_mav_result(result: u8) -> Self {
+        match result {
+            1 => Self::TemporarilyRejected,
+            2 => Self::Denied,
+            3 => Self::Unsupported,
+            4 => Self::Failed,
+            6 => Self::Cancelled,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Match a result by name, either MAV_RESULT's own (`MAV_RESULT_DENIED`)
+    /// or the variant name MAVKit's result enum prints (`Denied`).
+    fn from_name(name: &str) -> Self {
+        let name = name.strip_prefix("MAV_RESULT_").unwrap_or(name);
+        let normalized: String = name
+            .chars()
+            .filter(|c| *c != '_')
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        match normalized.as_str() {
+            "temporarilyrejected" => Self::TemporarilyRejected,
+            "denied" => Self::Denied,
+            "unsupported" => Self::Unsupported,
+            "failed" => Self::Failed,
+            "cancelled" => Self::Cancelled,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Error returned by backend commands.
+///
+/// Serialized with a stable `code` the frontend can branch on, next to the
+/// human-readable `message`, which may be localized or reworded by firmware
+/// and must not be matched on.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "code", rename_all = "snake_case")]
+pub enum AppError {
+    NotConnected {
+        message: String,
+    },
+    Timeout {
+        message: String,
+    },
+    /// The vehicle answered and refused; `result` is its MAV_RESULT.
+    CommandDenied {
+        result: MavResultKind,
+        message: String,
+    },
+    Validation {
+        issues: Vec<String>,
+        message: String,
+    },
+    Transport {
+        kind: String,
+        message: String,
+    },
+    Internal {
+        message: String,
+    },
+    /// GCS-side safety gates refused the command before it was sent.
+    SafetyGate {
+        failures: Vec<SafetyGateFailure>,
+        message: String,
+    },
+    /// The command has no implementation for the connected autopilot, so
+    /// nothing was sent.
+    UnsupportedAutopilot {
+        autopilot: mavkit::AutopilotType,
+        message: String,
+    },
+}
+
+impl AppError {
+    pub fn not_connected() -> Self {
+        Self::NotConnected {
+            message: "not connected".to_string(),
+        }
+    }
+
+    pub fn validation(issue: impl Into<String>) -> Self {
+        let issue = issue.into();
+        Self::Validation {
+            issues: vec![issue.clone()],
+            message: issue,
+        }
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::Internal {
+            message: message.into(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotConnected { message }
+            | Self::Timeout { message }
+            | Self::CommandDenied { message, .. }
+            | Self::Validation { message, .. }
+            | Self::Transport { message, .. }
+            | Self::Internal { message }
+            | Self::SafetyGate { message, .. }
+            | Self::UnsupportedAutopilot { message, .. } => message,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// Errors from helpers that still report plain strings carry no code to
+/// preserve, so they surface as internal errors.
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::internal(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::internal(message)
+    }
+}
+
+/// Callers that still return `Result<_, String>` keep the message only.
+impl From<AppError> for String {
+    fn from(error: AppError) -> Self {
+        match error {
+            AppError::NotConnected { message }
+            | AppError::Timeout { message }
+            | AppError::CommandDenied { message, .. }
+            | AppError::Validation { message, .. }
+            | AppError::Transport { message, .. }
+            | AppError::Internal { message }
+            | AppError::SafetyGate { message, .. }
+            | AppError::UnsupportedAutopilot { message, .. } => message,
+        }
+    }
+}
+
+impl From<mavkit::VehicleError> for AppError {
+    fn from(error: mavkit::VehicleError) -> Self {
+        use mavkit::VehicleError;
+
+        let message = error.to_string();
+        match error {
+            VehicleError::Disconnected => Self::NotConnected { message },
+            VehicleError::Timeout(_) => Self::Timeout { message },
+            VehicleError::CommandRejected { result, .. } => Self::CommandDenied {
+                result: MavResultKind::from_name(&format!("{result:?}")),
+                message,
+            },
+            VehicleError::InvalidParameter(_) => Self::Validation {
+                issues: vec![message.clone()],
+                message,
+            },
+            VehicleError::ConnectionFailed(_) => Self::Transport {
+                kind: "connection_failed".to_string(),
+                message,
+            },
+            VehicleError::Io(_) => Self::Transport {
+                kind: "io".to_string(),
+                message,
+            },
+            _ => Self::Internal { message },
+        }
+    }
+}
+
+impl From<LiveCommandError> for AppError {
+    fn from(error: LiveCommandError) -> Self {
+        match error {
+            LiveCommandError::InvalidInput(message) => Self::validation(message),
+            LiveCommandError::Unavailable(message) => Self::Internal { message },
+            LiveCommandError::Vehicle(error) => error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serialized_shape_is_stable() {
+        assert_eq!(
+            serde_json::to_value(AppError::not_connected()).expect("serialize"),
+            json!({ "code": "not_connected", "message": "not connected" })
+        );
+        assert_eq!(
+            serde_json::to_value(AppError::CommandDenied {
+                result: MavResultKind::Denied,
+                message: "arming denied".to_string(),
+            })
+            .expect("serialize"),
+            json!({
+                "code": "command_denied",
+                "result": "denied",
+                "message": "arming denied",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(AppError::validation("altitude must be positive"))
+                .expect("serialize"),
+            json!({
+                "code": "validation",
+                "issues": ["altitude must be positive"],
+                "message": "altitude must be positive",
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(AppError::Transport {
+                kind: "io".to_string(),
+                message: "broken pipe".to_string(),
+            })
+            .expect("serialize"),
+            json!({ "code": "transport", "kind": "io", "message": "broken pipe" })
+        );
+        assert_eq!(
+            serde_json::to_value(AppError::Timeout {
+                message: "timed out".to_string(),
+            })
+            .expect("serialize"),
+            json!({ "code": "timeout", "message": "timed out" })
+        );
+        assert_eq!(
+            serde_json::to_value(AppError::internal("boom")).expect("serialize"),
+            json!({ "code": "internal", "message": "boom" })
+        );
+    }
+
+    #[test]
+    fn conversions_keep_the_message() {
+        let timeout = AppError::from(mavkit::VehicleError::Timeout("waiting for ACK".into()));
+        assert!(matches!(timeout, AppError::Timeout { .. }));
+        assert!(timeout.message().contains("waiting for ACK"));
+
+        let invalid = AppError::from(LiveCommandError::invalid_input("bad rate"));
+        assert_eq!(invalid, AppError::validation("bad rate"));
+        assert_eq!(String::from(invalid), "bad rate");
+    }
+
+    #[test]
+    fn both_result_spellings_map_to_one_kind() {
+        for name in ["TemporarilyRejected", "MAV_RESULT_TEMPORARILY_REJECTED"] {
+            assert_eq!(MavResultKind::from_name(name), MavResultKind::TemporarilyRejected);
+        }
+        assert_eq!(MavResultKind::from_name("Denied"), MavResultKind::Denied);
+        assert_eq!(MavResultKind::from_mav_result(2), MavResultKind::Denied);
+        assert_eq!(MavResultKind::from_mav_result(42), MavResultKind::Unknown);
+    }
+}
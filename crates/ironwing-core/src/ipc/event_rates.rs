@@ -0,0 +1,11 @@
+/// Raw update count versus emitted count for one event stream, as reported
+/// by `event_bridge_stats`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct EventBridgeStats {
+    pub event: String,
+    /// Configured maximum rate; `None` when the event emits on every change.
+    pub max_rate_hz: Option<u32>,
+    pub changes: u64,
+    pub emitted: u64,
+}
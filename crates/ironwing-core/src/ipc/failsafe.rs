@@ -0,0 +1,30 @@
+/// What tripped an onboard failsafe.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailsafeKind {
+    Battery,
+    /// RC signal or throttle failsafe.
+    Radio,
+    /// Loss of the GCS / telemetry link.
+    Gcs,
+    Ekf,
+    Geofence,
+    Terrain,
+    Crash,
+    /// Reported as a failsafe, but of no kind listed above.
+    Other,
+}
+
+/// A failsafe decoded from the vehicle's STATUSTEXT, emitted on
+/// `vehicle://failsafe`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FailsafeEvent {
+    pub kind: FailsafeKind,
+    /// What the vehicle said it is doing about it, e.g. "Switching to RTL".
+    pub action_taken: Option<String>,
+    pub timestamp_usec: Option<u64>,
+    /// The STATUSTEXT it was decoded from.
+    pub text: String,
+}
@@ -0,0 +1,29 @@
+/// Which kind of fence boundary a proximity report is about.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FenceBoundaryKind {
+    InclusionPolygon,
+    ExclusionPolygon,
+    InclusionCircle,
+    ExclusionCircle,
+    /// The FENCE_RADIUS circle around home.
+    HomeCircle,
+}
+
+/// The fence boundary nearest to being breached, emitted on
+/// `fence://proximity` while the vehicle is inside the warning margin and
+/// closing on it.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FenceProximity {
+    pub kind: FenceBoundaryKind,
+    /// Index into the fence plan's regions; `None` for the home circle.
+    pub region_index: Option<u32>,
+    /// Horizontal distance to the boundary, negative once it is breached.
+    pub distance_m: f64,
+    /// How fast `distance_m` is shrinking at the current ground velocity.
+    pub closing_speed_mps: f64,
+    /// `None` when the vehicle is not closing on the boundary.
+    pub time_to_breach_s: Option<f64>,
+}
@@ -0,0 +1,30 @@
+/// One position of the flight-mode switch: the PWM band that selects it and
+/// the mode its slot parameter holds.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FlightModeRange {
+    /// 1-based slot, as passed to `flightmode_assign`.
+    pub slot: u8,
+    /// FLTMODEn, MODEn (Rover) or COM_FLTMODEn (PX4).
+    pub param: String,
+    pub pwm_low: u16,
+    pub pwm_high: u16,
+    /// `None` when the slot is unassigned or holds a value with no known
+    /// mode.
+    pub custom_mode: Option<u32>,
+    pub mode: Option<String>,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FlightModeChannelConfig {
+    /// FLTMODE_CH, MODE_CH (Rover) or RC_MAP_FLTMODE (PX4).
+    pub channel_param: String,
+    /// 1-based RC channel, `None` when no channel is mapped.
+    pub channel: Option<u8>,
+    pub ranges: Vec<FlightModeRange>,
+    /// Latest RC_CHANNELS value of `channel`.
+    pub channel_pwm: Option<u16>,
+    /// Slot the physical switch selects right now.
+    pub active_slot: Option<u8>,
+}
@@ -0,0 +1,11 @@
+/// The vehicle's current mode, decoded from its heartbeat custom_mode.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FlightModeCurrent {
+    pub custom_mode: u32,
+    /// `None` when the firmware reports a mode its mode table does not know.
+    pub name: Option<String>,
+    /// Modes with no self-levelling or position hold, where the pilot flies
+    /// the vehicle directly (Acro, Flip, Plane's Manual, ...).
+    pub dangerous: bool,
+}
@@ -0,0 +1,17 @@
+/// Aggregates for one armed period of the live vehicle, emitted as
+/// `flight://summary` when it disarms.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FlightSessionSummary {
+    pub started_at_usec: u64,
+    pub ended_at_usec: u64,
+    pub duration_s: f64,
+    pub max_altitude_m: Option<f64>,
+    /// `None` when no home position was received during the flight.
+    pub max_distance_from_home_m: Option<f64>,
+    pub max_speed_mps: Option<f64>,
+    /// Drop in reported battery percentage between arm and disarm.
+    pub battery_used_pct: Option<f64>,
+    pub energy_used_wh: Option<f64>,
+    pub distance_flown_m: f64,
+}
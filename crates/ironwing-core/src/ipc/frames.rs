@@ -0,0 +1,38 @@
+use mavkit::ParamWriteResult;
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FrameParam {
+    pub name: String,
+    pub value: f32,
+}
+
+/// One airframe the setup wizard can offer: an ArduPilot FRAME_CLASS /
+/// FRAME_TYPE pair (Q_FRAME_* on QuadPlanes) or a PX4 SYS_AUTOSTART id.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FrameOption {
+    /// Stable id passed back to `frame_apply`, e.g. `ardupilot:1:1` or
+    /// `px4:4001`.
+    pub id: String,
+    pub name: String,
+    pub group: String,
+    /// Key of the motor diagram to show, e.g. `quad_x`; `None` for frames
+    /// without a multirotor layout.
+    pub motor_layout: Option<String>,
+    pub motor_count: u8,
+    /// Parameter writes that select this frame.
+    pub params: Vec<FrameParam>,
+    /// The vehicle's parameters already select this frame.
+    pub current: bool,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FrameApplyResult {
+    pub option_id: String,
+    pub param_results: Vec<ParamWriteResult>,
+    /// The new frame only takes effect after a reboot.
+    pub reboot_required: bool,
+    pub rebooted: bool,
+}
@@ -0,0 +1,16 @@
+/// A COMMAND_LONG addressed to this GCS that it has no handler for. It was
+/// ACKed MAV_RESULT_UNSUPPORTED; the event lets the UI (or a future plugin)
+/// see who is asking for what.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GcsCommandReceived {
+    pub source_system: u8,
+    pub source_component: u8,
+    pub target_component: u8,
+    pub command_id: u16,
+    /// MAV_CMD name.
+    pub command: String,
+    pub params: Vec<f32>,
+    /// The sender's retry counter; 0 on the first send.
+    pub confirmation: u8,
+}
@@ -0,0 +1,93 @@
+/// Altitude reference of a goto target.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GotoFrame {
+    /// Above the home position.
+    #[default]
+    RelativeAlt,
+    Amsl,
+    /// Above the terrain under the target.
+    Terrain,
+}
+
+/// Optional parts of a goto. Unset fields leave the autopilot's own defaults
+/// in place.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct GotoOptions {
+    #[serde(default)]
+    pub ground_speed_ms: Option<f32>,
+    /// Heading to hold on arrival, degrees clockwise from north.
+    #[serde(default)]
+    pub yaw_deg: Option<f32>,
+    #[serde(default)]
+    pub acceptance_radius_m: Option<f32>,
+    #[serde(default)]
+    pub frame: GotoFrame,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GotoRequest {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    /// Altitude in the reference chosen by `options.frame`.
+    pub altitude_m: f32,
+    #[serde(default)]
+    pub options: GotoOptions,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GotoMethod {
+    /// SET_POSITION_TARGET_GLOBAL_INT, used for copters and rovers.
+    PositionTarget,
+    /// MAV_CMD_DO_REPOSITION, used for planes.
+    Reposition,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GotoResult {
+    pub method: GotoMethod,
+    /// Options the chosen method cannot carry, so the vehicle never saw them.
+    pub ignored_options: Vec<String>,
+}
+
+/// Payload of `guided://target`: the target most recently commanded to the
+/// vehicle.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GuidedTarget {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    pub altitude_m: f32,
+    pub options: GotoOptions,
+}
+
+/// How `vehicle_guided_goto_terrain` held the requested height above ground.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TerrainGotoStrategy {
+    /// Sent in the terrain frame; the autopilot follows the ground en route.
+    TerrainFrame,
+    /// Sent as the AMSL altitude that puts the vehicle at the requested
+    /// height over the target only.
+    AmslEquivalent,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TerrainGotoResult {
+    pub strategy: TerrainGotoStrategy,
+    /// Ground elevation at the target the command was given, metres AMSL.
+    pub ground_elevation_m: f64,
+    /// Altitude sent, in the frame `strategy` implies.
+    pub altitude_m: f32,
+    pub goto: GotoResult,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
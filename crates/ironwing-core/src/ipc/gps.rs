@@ -0,0 +1,52 @@
+/// GPS_FIX_TYPE, in order of quality.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum GpsFix {
+    NoGps,
+    NoFix,
+    Fix2d,
+    Fix3d,
+    Dgps,
+    RtkFloat,
+    RtkFixed,
+    Static,
+    Ppp,
+}
+
+/// Payload of `gps://fix_changed`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GpsFixChanged {
+    /// 0 for GPS_RAW_INT, 1 for GPS2_RAW.
+    pub instance: u8,
+    /// `None` for the first report from this instance.
+    pub previous: Option<GpsFix>,
+    pub fix: GpsFix,
+    pub satellites: Option<u8>,
+    pub hdop: Option<f32>,
+    /// Age of the last RTK correction, when the receiver reports GPS_RTK.
+    pub rtk_baseline_age_ms: Option<u32>,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GpsInstanceStatus {
+    pub instance: u8,
+    pub fix: GpsFix,
+    pub satellites: Option<u8>,
+    pub hdop: Option<f32>,
+    pub rtk_baseline_age_ms: Option<u32>,
+    /// From connecting until this instance first reported a 3D fix or better.
+    pub time_to_3d_fix_ms: Option<u64>,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GpsStatus {
+    pub instances: Vec<GpsInstanceStatus>,
+    /// From connecting until any instance first reported a 3D fix or better.
+    pub time_to_first_3d_fix_ms: Option<u64>,
+}
@@ -0,0 +1,37 @@
+/// One stop of a guided queue, altitude relative to home.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GuidedQueuePoint {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    pub altitude_m: f32,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuidedQueueState {
+    /// No points set.
+    #[default]
+    Empty,
+    /// Points set and not yet flown.
+    Ready,
+    Running,
+    /// The last point was reached.
+    Completed,
+    /// Stopped before the last point; see `stop_reason`.
+    Stopped,
+}
+
+/// Sent on `guided_queue://progress` whenever the queue advances or stops.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct GuidedQueueStatus {
+    pub state: GuidedQueueState,
+    pub points: Vec<GuidedQueuePoint>,
+    /// Index of the point being flown to, or the last one reached.
+    pub current_index: Option<u32>,
+    /// Horizontal distance to the current point at the last position update.
+    pub distance_m: Option<f64>,
+    pub stop_reason: Option<String>,
+}
@@ -0,0 +1,62 @@
+/// Severity of one health entry, in increasing order of concern.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok,
+    Warn,
+    Error,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthComponent {
+    /// SYS_STATUS sensor present/enabled/health bits.
+    Sensors,
+    /// EKF_STATUS_REPORT flags and variances.
+    Ekf,
+    Gps,
+    Battery,
+    /// SYS_STATUS communication drop rate as seen by the vehicle.
+    Link,
+    /// GCS failsafe parameters.
+    Failsafe,
+    Heartbeat,
+    /// EXTENDED_SYS_STATE VTOL state, for VTOL frames only.
+    Vtol,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HealthEntry {
+    pub component: HealthComponent,
+    pub status: HealthStatus,
+    pub detail: String,
+}
+
+/// Payload of `vehicle_health` and `vehicle://health`. `overall` is the worst
+/// entry. Components the vehicle has not reported on are left out.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HealthReport {
+    pub overall: HealthStatus,
+    pub entries: Vec<HealthEntry>,
+}
+
+/// Limits the battery, link and heartbeat entries are graded against.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HealthThresholds {
+    /// Remaining battery at or below which the battery entry warns.
+    pub battery_warn_pct: f64,
+    pub battery_error_pct: f64,
+    /// Communication drop rate at or above which the link entry warns.
+    pub link_drop_warn_pct: f64,
+    pub link_drop_error_pct: f64,
+    /// Heartbeat silence at or above which the heartbeat entry warns.
+    pub heartbeat_warn_ms: u64,
+    pub heartbeat_error_ms: u64,
+}
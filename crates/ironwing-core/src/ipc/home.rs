@@ -0,0 +1,15 @@
+/// Home moved while the vehicle was armed, emitted on `home://moved`. RTL
+/// now returns to the new home, so the operator should know.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct HomeMoved {
+    pub old_latitude_deg: f64,
+    pub old_longitude_deg: f64,
+    pub new_latitude_deg: f64,
+    pub new_longitude_deg: f64,
+    /// Horizontal distance between the old and new home.
+    pub distance_m: f64,
+    /// Horizontal distance from the vehicle to the new home; `None` without
+    /// a position fix.
+    pub vehicle_to_new_home_m: Option<f64>,
+}
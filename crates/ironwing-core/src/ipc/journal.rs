@@ -0,0 +1,26 @@
+use serde_json::Value;
+
+use crate::ipc::{OperationId, VehicleTime};
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum JournalOutcome {
+    Ok { result: Value },
+    Err { message: String },
+}
+
+/// One command the GCS sent, recorded with the vehicle's answer.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct JournalEntry {
+    /// Wall-clock time the command was issued, in Unix milliseconds.
+    pub timestamp_ms: u64,
+    pub duration_ms: u64,
+    pub operation_id: OperationId,
+    pub request: Value,
+    pub outcome: JournalOutcome,
+    /// `timestamp_ms` on the vehicle's clocks, once TIMESYNC has an offset.
+    #[serde(default)]
+    pub vehicle_time: Option<VehicleTime>,
+}
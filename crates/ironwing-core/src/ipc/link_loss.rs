@@ -0,0 +1,25 @@
+/// What the vehicle is configured to do when it stops hearing the GCS.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct GcsFailsafeBehavior {
+    /// Parameter the behavior was read from, `None` when none was found
+    /// (parameters not downloaded yet, or an unknown firmware).
+    pub parameter: Option<String>,
+    pub value: Option<f32>,
+    /// Human-readable action, e.g. "RTL" or "SmartRTL, or Land".
+    pub action: String,
+    /// The vehicle will keep doing whatever it was doing.
+    pub does_nothing: bool,
+}
+
+/// Payload of `link://lost_in_flight`, raised when the heartbeat times out
+/// while the vehicle was last known to be armed.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LinkLostInFlight {
+    pub latitude_deg: Option<f64>,
+    pub longitude_deg: Option<f64>,
+    pub altitude_msl_m: Option<f64>,
+    pub since_last_packet_ms: u64,
+    pub failsafe: GcsFailsafeBehavior,
+}
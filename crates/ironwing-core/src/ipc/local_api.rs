@@ -0,0 +1,10 @@
+/// Whether the local HTTP API is serving, and where.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LocalApiStatus {
+    pub running: bool,
+    /// `host:port` the server is bound to; `None` while stopped.
+    pub address: Option<String>,
+    /// Whether `disconnect_link` also stops the server.
+    pub stop_on_disconnect: bool,
+}
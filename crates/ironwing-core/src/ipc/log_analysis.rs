@@ -0,0 +1,45 @@
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFindingCategory {
+    Vibration,
+    Ekf,
+    Battery,
+    Gps,
+    RcFailsafe,
+    ThrustLoss,
+    YawImbalance,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFindingSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LogFindingMetric {
+    pub name: String,
+    pub value: f64,
+    pub unit: Option<String>,
+}
+
+/// One issue `log_analyze` detected in the open log. The time range uses the
+/// log's own microsecond timestamps, so it can be passed straight to
+/// `log_query` to chart the evidence.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LogFinding {
+    pub category: LogFindingCategory,
+    pub severity: LogFindingSeverity,
+    pub start_usec: u64,
+    pub end_usec: u64,
+    pub explanation: String,
+    pub metrics: Vec<LogFindingMetric>,
+}
@@ -10,6 +10,7 @@ pub const LOG_LIBRARY_CATALOG_SCHEMA_VERSION: u16 = 1;
 pub enum LogFormat {
     Tlog,
     Bin,
+    Ulog,
 }
 
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
@@ -227,6 +228,35 @@ pub struct LogOperationProgress {
     pub message: Option<String>,
 }
 
+/// A parameter's last value in a log.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LogParam {
+    pub name: String,
+    pub value: f64,
+}
+
+/// A text message recorded in a log. `severity` follows MAV_SEVERITY, which
+/// ULog levels share.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LogStatusText {
+    pub timestamp_usec: u64,
+    pub severity: u8,
+    pub text: String,
+}
+
+/// New entries read from the end of a followed tlog, emitted on
+/// `log://appended`. The last event of a follow has `following: false`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LogAppended {
+    pub log_id: String,
+    pub new_entries: u64,
+    pub total_entries: u64,
+    pub following: bool,
+}
+
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -332,6 +362,27 @@ pub struct ChartSeriesPage {
     pub diagnostics: Vec<LogDiagnostic>,
 }
 
+/// A computed field `log_query` adds to each point, e.g.
+/// `{ name: "ground_speed", expr: "sqrt(vx^2 + vy^2)" }`. The expression may
+/// only reference fields of the queried message type.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct VirtualField {
+    pub name: String,
+    pub expr: String,
+}
+
+/// One open log's points in a `log_query_compare` result.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LogCompareSeries {
+    pub log_id: String,
+    pub file_name: String,
+    /// Time since the log's first entry when the query normalised time,
+    /// otherwise the log's own timestamps.
+    pub points: Vec<ChartPoint>,
+}
+
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -409,6 +460,35 @@ pub enum RecordingStatus {
 pub struct RecordingStartRequest {
     pub destination_path: String,
     pub mode: RecordingMode,
+    /// Split the recording into segments listed in a `.tlog-index.json`
+    /// sidecar; one file when absent.
+    #[serde(default)]
+    pub rotation: Option<TlogRotation>,
+    #[serde(default)]
+    pub fsync: TlogFsyncPolicy,
+}
+
+/// Start a new tlog segment once the current one spans either limit.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct TlogRotation {
+    #[serde(default)]
+    pub max_segment_minutes: Option<u32>,
+    #[serde(default)]
+    pub max_segment_mb: Option<u32>,
+}
+
+/// When recorded frames are forced to storage. Syncing more often survives
+/// power loss better at the cost of flash wear.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TlogFsyncPolicy {
+    /// Leave write-back to the operating system.
+    #[default]
+    OsDefault,
+    PerSecond,
+    PerWrite,
 }
 
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
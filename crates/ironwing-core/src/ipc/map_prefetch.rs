@@ -0,0 +1,54 @@
+use mavkit::MissionPlan;
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MapBounds {
+    pub south_deg: f64,
+    pub west_deg: f64,
+    pub north_deg: f64,
+    pub east_deg: f64,
+}
+
+/// What to cover with tiles: the route of a mission plus a corridor either
+/// side of it, or a fixed box.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MapPrefetchArea {
+    Mission {
+        plan: MissionPlan,
+        /// Buffer either side of the route; `MAP_PREFETCH_DEFAULT_CORRIDOR_M`
+        /// when absent.
+        #[serde(default)]
+        corridor_m: Option<f64>,
+    },
+    Bbox {
+        bounds: MapBounds,
+    },
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MapPrefetchRequest {
+    pub area: MapPrefetchArea,
+    pub min_zoom: u8,
+    pub max_zoom: u8,
+    /// Tile server URL with `{z}`, `{x}` and `{y}` placeholders.
+    pub tile_url_template: String,
+    /// Tiles are stored as `<dest_dir>/<z>/<x>/<y>.<ext>`.
+    pub dest_dir: String,
+}
+
+/// Sent on `map://prefetch_progress` as tiles complete, and returned by
+/// `map_prefetch` when the run ends.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MapPrefetchProgress {
+    pub total_tiles: u32,
+    pub downloaded: u32,
+    /// Already on disk from an earlier run.
+    pub skipped: u32,
+    pub failed: u32,
+    pub bytes: u64,
+    pub cancelled: bool,
+}
@@ -0,0 +1,24 @@
+/// Result of `send_command_long`: the target's final COMMAND_ACK.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AckResult {
+    pub command_id: u16,
+    /// MAV_CMD name.
+    pub command: String,
+    pub accepted: bool,
+    /// Why the command was refused, when it was.
+    pub message: Option<String>,
+    /// How many times the command was sent before the final ACK.
+    pub attempts: u8,
+}
+
+/// A MAV_RESULT_IN_PROGRESS ACK for a command the GCS is waiting on, sent
+/// on `command://progress`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CommandProgress {
+    /// MAV_CMD name.
+    pub command: String,
+    /// Percent complete, when the target reports one.
+    pub progress: Option<u8>,
+}
@@ -1,4 +1,4 @@
-use mavkit::{HomePosition, MissionPlan, RcOverrideChannelValue};
+use mavkit::{HomePosition, MissionPlan, RcOverrideChannelValue, TransferProgress};
 
 /// Result of downloading a mission plan from a vehicle.
 /// Home position is extracted from telemetry home, not from plan items.
@@ -9,6 +9,35 @@ pub struct MissionDownload {
     pub home: Option<HomePosition>,
 }
 
+/// How a mission, fence or rally transfer ended.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferOutcome {
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// MAVKit's transfer progress with a rate, ETA and stall flag. The last event
+/// of a transfer carries its `outcome`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MissionTransferProgress {
+    #[serde(flatten)]
+    pub progress: TransferProgress,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items_per_sec: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eta_secs: Option<f64>,
+    #[serde(default)]
+    pub stalled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<TransferOutcome>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+}
+
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
 #[derive(Debug, Clone, Copy, serde::Deserialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
@@ -0,0 +1,42 @@
+use mavkit::MissionPlan;
+
+/// Altitude reference a mission can be converted to.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AltitudeFrame {
+    /// Above the home position.
+    RelativeAlt,
+    Amsl,
+    /// Above the terrain under each item.
+    Terrain,
+}
+
+/// How `mission_scale_altitudes` changes every altitude.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AltitudeChange {
+    /// Add `delta_m` to every altitude.
+    Offset { delta_m: f64 },
+    /// Multiply every altitude by `factor`.
+    Scale { factor: f64 },
+}
+
+/// An item an edit left unchanged or that needs a second look, by its index
+/// in the returned plan.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MissionEditWarning {
+    pub seq: u16,
+    pub message: String,
+}
+
+/// The edited plan, re-sequenced with DO_JUMP targets following the items
+/// they pointed at.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MissionEditResult {
+    pub plan: MissionPlan,
+    pub warnings: Vec<MissionEditWarning>,
+}
@@ -1,48 +1,182 @@
+pub mod alerts;
 pub mod analytics;
+pub mod announcements;
+pub mod battery;
 pub mod calibration;
+pub mod checklist;
 pub mod connection;
+pub mod diagnostics;
 pub mod domain;
 pub mod envelope;
+pub mod error;
+pub mod event_rates;
+pub mod failsafe;
+pub mod fence_proximity;
 pub mod firmware;
+pub mod flight_mode_switch;
+pub mod flight_modes;
+pub mod flight_summary;
+pub mod frames;
+pub mod gcs_commands;
+pub mod goto;
+pub mod gps;
 pub mod guided;
+pub mod guided_queue;
+pub mod health;
+pub mod home;
+pub mod journal;
+pub mod link_loss;
+pub mod local_api;
+pub mod log_analysis;
 #[allow(dead_code)]
 pub mod logs;
+pub mod map_prefetch;
+pub mod mavlink_console;
 pub mod mission;
+pub mod mission_edit;
+pub mod named_values;
+pub mod orbit;
+pub mod params;
+pub mod payload;
+pub mod plane;
 pub mod playback;
+pub mod precision_landing;
+pub mod preflight;
+pub mod rangefinder;
+pub mod rtl;
+pub mod safety_gates;
+pub mod scripts;
 pub mod sensor_health;
 pub mod session;
+pub mod session_export;
+pub mod settings;
 pub mod status_text;
 pub mod support;
+pub mod takeoff;
 pub mod telemetry;
+pub mod time_sync;
+pub mod track;
+pub mod tuning;
+pub mod units;
+pub mod vehicles;
+pub mod video;
+pub mod vtol;
 
+pub use alerts::{AlertCondition, AlertRule, AlertTriggered, AlertValue};
 pub use analytics::{AnalyticsProperties, AnalyticsProperty};
-pub use calibration::{CalibrationSources, calibration_snapshot_from_sources};
-pub use connection::{ConnectRequest, ConnectTransport, DemoVehiclePreset, DisconnectRequest};
+pub use announcements::{
+    Announcement, AnnouncementCategory, AnnouncementPriority, AnnouncementSettings,
+};
+pub use battery::{BatteryStats, CellCountSource};
+pub use calibration::{
+    CalibrationKind, CalibrationProgress, CalibrationResult, CalibrationSources,
+    calibration_snapshot_from_sources,
+};
+pub use checklist::{
+    ChecklistCheck, ChecklistConfig, ChecklistItem, ChecklistItemState, ChecklistItemStatus,
+    ChecklistState,
+};
+pub use connection::{
+    ConnectRequest, ConnectTransport, ConnectionInfo, DemoVehiclePreset, DisconnectAction,
+    DisconnectActionOutcome, DisconnectActionResult, DisconnectRequest, GcsIdentity,
+    LinkConnectPhase, LinkConnecting, LinkFrameStats, LinkProfile, LinkQualitySample, LinkSource,
+    LinkSourceEntry, LinkTimeoutOverrides, LinkTimeoutProfile, LinkTimeouts, PendingConnect,
+    PendingConnectSource, SerialFlowControl, SerialParity, SessionRecoverInfo, TransportLost,
+    TransportStats, UnknownMessage, UsbSerialDevice,
+};
+pub use diagnostics::{
+    Diagnostics, DiagnosticsBundleEntry, DiagnosticsExport, TaskDiagnostic, TaskStalled, TaskState,
+};
 pub use domain::{DomainProvenance, DomainValue};
 pub use envelope::{
     OperationFailure, OperationId, Reason, ReasonKind, ScopedEvent, SessionEnvelope, SourceKind,
     operation_failure_json,
 };
+pub use error::{AppError, MavResultKind};
+pub use event_rates::EventBridgeStats;
+pub use failsafe::{FailsafeEvent, FailsafeKind};
+pub use fence_proximity::{FenceBoundaryKind, FenceProximity};
 pub use firmware::*;
+pub use flight_mode_switch::{FlightModeChannelConfig, FlightModeRange};
+pub use flight_modes::FlightModeCurrent;
+pub use flight_summary::FlightSessionSummary;
+pub use frames::{FrameApplyResult, FrameOption, FrameParam};
+pub use gcs_commands::GcsCommandReceived;
+pub use goto::{
+    GotoFrame, GotoMethod, GotoOptions, GotoRequest, GotoResult, GuidedTarget, TerrainGotoResult,
+    TerrainGotoStrategy,
+};
+pub use gps::{GpsFix, GpsFixChanged, GpsInstanceStatus, GpsStatus};
 pub use guided::{
     GuidedCommandResult, GuidedFailure, GuidedFatalityScope, GuidedLiveContext, GuidedRuntime,
     GuidedSession, GuidedSnapshot, GuidedTerminationReason, StartGuidedSessionRequest,
     UpdateGuidedSessionRequest,
 };
+pub use guided_queue::{GuidedQueuePoint, GuidedQueueState, GuidedQueueStatus};
+pub use health::{HealthComponent, HealthEntry, HealthReport, HealthStatus, HealthThresholds};
+pub use home::HomeMoved;
+pub use journal::{JournalEntry, JournalOutcome};
+pub use link_loss::{GcsFailsafeBehavior, LinkLostInFlight};
+pub use local_api::LocalApiStatus;
+pub use log_analysis::{LogFinding, LogFindingCategory, LogFindingMetric, LogFindingSeverity};
 pub use logs::{
-    LogDiagnostic, LogOperationPhase, LogOperationProgress, RecordingMode, RecordingSettings,
-    RecordingSettingsResult, RecordingStartRequest, RecordingStatus, ReplayStatus,
+    LogAppended, LogDiagnostic, LogOperationPhase, LogOperationProgress, LogParam, LogStatusText,
+    RecordingMode, RecordingSettings, RecordingSettingsResult, RecordingStartRequest,
+    RecordingStatus, ReplayStatus, TlogFsyncPolicy, TlogRotation,
+};
+pub use map_prefetch::{MapBounds, MapPrefetchArea, MapPrefetchProgress, MapPrefetchRequest};
+pub use mavlink_console::{AckResult, CommandProgress};
+pub use mission::{
+    MissionDownload, MissionTransferProgress, RcOverrideChannelValueWire, RcOverrideChannelWire,
+    TransferOutcome,
+};
+pub use mission_edit::{AltitudeChange, AltitudeFrame, MissionEditResult, MissionEditWarning};
+pub use named_values::{NamedValue, NamedValueKind};
+pub use orbit::{OrbitDirection, OrbitRequest, OrbitResult, OrbitStrategy};
+pub use params::{
+    ParamBitLabel, ParamBitState, ParamExportFormat, ParamExportOptions, ParamExternalChange,
+    ParamFileEntry, ParamFileWarning, ParamMetadata, ParamSearchOptions, ParamStoreDelta,
+    ParamTuneSession, ParamTuneSummary, ParamWriteOutcome, ParsedParamFile,
+};
+pub use payload::{GripperCommand, WinchCommand, WinchStatus};
+pub use plane::{
+    PlaneCruiseThrottleResult, PlaneLaunch, PlaneLaunchKind, PlaneLoiterResult, PlaneTakeoffResult,
 };
-pub use mission::{MissionDownload, RcOverrideChannelValueWire, RcOverrideChannelWire};
 pub use playback::PlaybackSnapshot;
+pub use precision_landing::{OpticalFlowQuality, PrecisionLandingStatus, PrecisionLandingTarget};
+pub use preflight::{PositionIssue, PositionIssueKind};
+pub use rangefinder::{
+    LandingAssistBand, LandingAssistProfile, LandingAssistWarning, RangefinderOrientation,
+    RangefinderReading,
+};
+pub use rtl::{RtlPoint, RtlPreview, RtlSegment, RtlSegmentKind};
+pub use safety_gates::{SafetyGate, SafetyGateConfig, SafetyGateFailure};
+pub use scripts::{FileTransferPhase, FileTransferProgress, FileUploadReport, ScriptFile};
 pub use sensor_health::sensor_health_snapshot_from_summary;
 pub use session::{
     AckSessionSnapshotResult, OpenSessionSnapshot, SessionConnection, SessionSnapshot,
-    SessionStatus, VehicleState, session_connection_from_link_state,
+    SessionStatus, SystemResumed, VehicleState, session_connection_from_link_state,
 };
+pub use session_export::{
+    ExportReport, SessionArtifact, SessionArtifactEntry, SessionArtifactStatus,
+    SessionExportManifest, SessionExportOptions, SessionExportProgress,
+};
+pub use settings::{Settings, SettingsPatch};
 pub use status_text::{
     StatusTextEntry, StatusTextSnapshot, push_status_text_entry, status_text_entry_from_value,
-    status_text_snapshot_from_entries,
+    status_text_mark, status_text_snapshot_from_entries, status_texts_after,
 };
 pub use support::{SupportSnapshot, support_snapshot};
-pub use telemetry::{TelemetrySnapshot, telemetry_snapshot_from_value};
+pub use takeoff::{
+    TakeoffGuidedRequest, TakeoffReport, TakeoffStep, TakeoffStepReport, TakeoffStepStatus,
+};
+pub use telemetry::{
+    TelemetryDelta, TelemetryEncoding, TelemetrySnapshot, telemetry_snapshot_from_value,
+};
+pub use time_sync::{TimeSyncStatus, VehicleTime};
+pub use track::TrackPoint;
+pub use tuning::{TuneAxis, TuneReport, TuneStep, TuneTargetSource};
+pub use units::{DisplayValue, DistanceUnit, SpeedUnit, TemperatureUnit, UnitSystem};
+pub use vehicles::{ComponentListEntry, VehicleListEntry, VehicleSelected};
+pub use video::VideoStream;
+pub use vtol::{LandedState, LogVtolEvent, VtolState, VtolStatus, VtolTransitionTarget};
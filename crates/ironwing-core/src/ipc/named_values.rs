@@ -0,0 +1,24 @@
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NamedValueKind {
+    Float,
+    Int,
+    DebugVect,
+}
+
+/// Latest value of one NAMED_VALUE_FLOAT, NAMED_VALUE_INT or DEBUG_VECT
+/// stream, emitted as `custom://named_value`. `values` holds one entry for
+/// the scalar messages and x, y, z for DEBUG_VECT.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct NamedValue {
+    pub name: String,
+    pub kind: NamedValueKind,
+    pub values: Vec<f64>,
+    pub system_id: u8,
+    pub component_id: u8,
+    /// Sender's boot time (DEBUG_VECT reports microseconds, converted here).
+    pub time_boot_ms: u64,
+    pub timestamp_usec: u64,
+}
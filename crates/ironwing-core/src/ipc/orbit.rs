@@ -0,0 +1,43 @@
+use mavkit::ParamWriteResult;
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrbitDirection {
+    Cw,
+    Ccw,
+}
+
+/// Circle `latitude_deg`/`longitude_deg` at `radius_m`, flying at
+/// `velocity_mps` and holding `altitude_msl_m`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OrbitRequest {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    pub radius_m: f32,
+    pub velocity_mps: f32,
+    pub direction: OrbitDirection,
+    pub altitude_msl_m: f32,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrbitStrategy {
+    /// The autopilot accepted MAV_CMD_DO_ORBIT.
+    DoOrbit,
+    /// ArduCopter Circle mode, entered from the edge of the circle with
+    /// CIRCLE_RADIUS and CIRCLE_RATE set for the request.
+    CircleMode,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OrbitResult {
+    pub strategy: OrbitStrategy,
+    /// Why MAV_CMD_DO_ORBIT was not used, when the fallback ran.
+    pub do_orbit_error: Option<String>,
+    /// CIRCLE_* writes made by the fallback (or restored by a stop).
+    pub param_results: Vec<ParamWriteResult>,
+}
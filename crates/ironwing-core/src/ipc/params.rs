@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamExportFormat {
+    /// `NAME,VALUE` lines as written by Mission Planner.
+    #[default]
+    MissionPlanner,
+    /// Tab-separated QGroundControl `.params` file with sysid/compid columns.
+    Qgc,
+}
+
+/// Per-parameter metadata the frontend already holds (ArduPilot `apm.pdef.xml`).
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct ParamMetadata {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub units: Option<String>,
+    #[serde(default)]
+    pub min: Option<f32>,
+    #[serde(default)]
+    pub max: Option<f32>,
+    #[serde(default)]
+    pub default_value: Option<f32>,
+    /// Labels of the bits of a bitmask parameter.
+    #[serde(default)]
+    pub bitmask: Option<Vec<ParamBitLabel>>,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ParamBitLabel {
+    pub bit: u8,
+    pub label: String,
+}
+
+/// One bit of a bitmask parameter as returned by `param_get_bits`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ParamBitState {
+    pub bit: u8,
+    pub label: String,
+    pub set: bool,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct ParamExportOptions {
+    /// Restrict the export to these names. `None` exports every parameter.
+    #[serde(default)]
+    pub names: Option<Vec<String>>,
+    /// Skip parameters whose value matches `metadata[name].default_value`.
+    #[serde(default)]
+    pub modified_only: bool,
+    /// Prefix each line with a `# description (units, range)` comment.
+    #[serde(default)]
+    pub include_comments: bool,
+    #[serde(default)]
+    pub format: ParamExportFormat,
+    #[serde(default)]
+    pub metadata: HashMap<String, ParamMetadata>,
+}
+
+/// One parameter read from a file by `param_parse_file_ex`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ParamFileEntry {
+    pub name: String,
+    pub value: f64,
+    /// The type column of a QGC file; `None` for formats without one.
+    pub param_type: Option<mavkit::ParamType>,
+    /// 1-based line in the file.
+    pub line: u32,
+}
+
+/// A row of a parameter file that was skipped.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ParamFileWarning {
+    pub line: u32,
+    pub text: String,
+    pub message: String,
+}
+
+/// Result of `param_parse_file_ex`: the rows that parsed, in file order,
+/// and why the others did not.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ParsedParamFile {
+    /// What the file looked like; QGC when it has the vehicle and type
+    /// columns.
+    pub format: ParamExportFormat,
+    pub params: Vec<ParamFileEntry>,
+    pub warnings: Vec<ParamFileWarning>,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct ParamSearchOptions {
+    /// Match only names starting with the query instead of containing it.
+    #[serde(default)]
+    pub prefix_only: bool,
+    /// Also match against cached metadata descriptions.
+    #[serde(default)]
+    pub include_metadata_text: bool,
+    /// Skip parameters whose value matches the cached metadata default.
+    #[serde(default)]
+    pub modified_only: bool,
+    /// Maximum number of results; `0` returns every match.
+    #[serde(default)]
+    pub limit: usize,
+}
+
+/// A parameter write plus the outcome of the storage commit that followed
+/// it, when one was sent.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ParamWriteOutcome {
+    #[serde(flatten)]
+    pub result: mavkit::ParamWriteResult,
+    /// Whether MAV_CMD_PREFLIGHT_STORAGE was acknowledged; `None` when no
+    /// commit was requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persisted: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persist_error: Option<String>,
+}
+
+/// A parameter changed by someone other than this GCS (another GCS, a Lua
+/// script, the autopilot itself). Payload of `param://external_change`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ParamExternalChange {
+    pub name: String,
+    pub old_value: f32,
+    pub new_value: f32,
+    pub timestamp_unix_ms: u64,
+}
+
+/// Incremental `param://delta` payload.
+///
+/// `generation` increases by one per emission within a connection. A `full`
+/// delta replaces the frontend's store; otherwise `changed` is merged into it
+/// and the `removed` names are dropped from it. `received`/`total` mirror the
+/// store's downloaded and expected counts.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ParamStoreDelta {
+    pub generation: u64,
+    pub full: bool,
+    pub received: u32,
+    pub total: u32,
+    pub changed: Vec<mavkit::Param>,
+    pub removed: Vec<String>,
+}
+
+/// A parameter opened for live tuning by `param_tune_session`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ParamTuneSession {
+    pub name: String,
+    /// The value when the session opened; `param_tune_revert` and
+    /// `param_tune_end(false)` write it back.
+    pub original_value: f64,
+}
+
+/// What a tune session did, returned by `param_tune_end`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ParamTuneSummary {
+    pub name: String,
+    pub original_value: f64,
+    /// Last value the vehicle echoed back; `None` when nothing was written.
+    pub value: Option<f64>,
+    /// `param_tune_set` calls, including reverts.
+    pub updates: u32,
+    /// PARAM_SETs actually sent; the rest were superseded before their turn.
+    pub writes: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Whether MAV_CMD_PREFLIGHT_STORAGE was acknowledged; `None` when no
+    /// commit was requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persisted: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub persist_error: Option<String>,
+}
@@ -0,0 +1,41 @@
+/// MAV_CMD_DO_GRIPPER action.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GripperCommand {
+    Grab,
+    Release,
+}
+
+/// MAV_CMD_DO_WINCH action. Rates are in m/s; a negative length or rate
+/// winds the line in.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WinchCommand {
+    /// Let the line go slack.
+    Relaxed,
+    /// Pay out (or wind in) `length_m` of line at `rate_ms`.
+    RelativeLengthControl { length_m: f32, rate_ms: f32 },
+    /// Run the winch at `rate_ms` until told otherwise.
+    RateControl { rate_ms: f32 },
+}
+
+/// A WINCH_STATUS report.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WinchStatus {
+    pub line_length_m: Option<f32>,
+    pub speed_mps: Option<f32>,
+    pub tension_kg: Option<f32>,
+    pub voltage_v: f32,
+    pub current_a: Option<f32>,
+    pub temperature_c: Option<i16>,
+    pub healthy: bool,
+    pub fully_retracted: bool,
+    pub moving: bool,
+    pub clutch_engaged: bool,
+    pub locked: bool,
+    /// The raw MAV_WINCH_STATUS_FLAG bits, for the flags not broken out.
+    pub status_flags: u32,
+}
@@ -0,0 +1,59 @@
+/// How a fixed-wing expects to be launched, read from its TKOFF_* parameters.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaneLaunchKind {
+    /// The motor starts on takeoff and the plane rolls down a runway.
+    Runway,
+    /// The motor waits for the acceleration of a throw.
+    HandLaunch,
+    /// The motor waits until a catapult or bungee has the plane moving.
+    Catapult,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlaneLaunch {
+    pub kind: PlaneLaunchKind,
+    /// TKOFF_THR_MINACC: forward acceleration that releases the throttle,
+    /// m/s².
+    pub min_acceleration_mss: f32,
+    /// TKOFF_THR_MINSPD: ground speed that releases the throttle, m/s.
+    pub min_ground_speed_mps: f32,
+    /// TKOFF_THR_DELAY: wait between launch detection and the motor starting,
+    /// seconds.
+    pub throttle_delay_s: f32,
+    /// What the operator has to do once the plane is armed in Takeoff mode.
+    pub instructions: String,
+    pub warnings: Vec<String>,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlaneTakeoffResult {
+    pub launch: PlaneLaunch,
+    /// Takeoff mode climbs to TKOFF_ALT, set from the request.
+    pub altitude_m: f32,
+    pub armed: bool,
+}
+
+/// Where `plane_loiter_here` sent the plane.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlaneLoiterResult {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    pub altitude_msl_m: f32,
+    pub radius_m: f32,
+    /// Turns after which the plane returns to `return_mode`; `None` loiters
+    /// until told otherwise.
+    pub turns: Option<u32>,
+    pub return_mode: Option<String>,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlaneCruiseThrottleResult {
+    pub throttle_pct: f32,
+    pub warnings: Vec<String>,
+}
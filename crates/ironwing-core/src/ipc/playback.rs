@@ -13,6 +13,17 @@ pub struct PlaybackSeekResult {
     pub cursor_usec: Option<u64>,
 }
 
+/// Cursor progress of a running playback, emitted as
+/// `log://playback_position` at a fixed low rate.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlaybackPosition {
+    pub cursor_usec: u64,
+    pub start_usec: u64,
+    pub end_usec: u64,
+    pub speed: f32,
+}
+
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct PlaybackState {
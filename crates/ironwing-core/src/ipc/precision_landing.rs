@@ -0,0 +1,39 @@
+/// A LANDING_TARGET report: where the precision-landing sensor sees the
+/// target, as angles off the camera axis.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PrecisionLandingTarget {
+    pub target_num: u8,
+    pub angle_x_rad: f64,
+    pub angle_y_rad: f64,
+    /// Distance to the target; `None` when the sensor does not measure it.
+    pub distance_m: Option<f64>,
+    /// Whether the report carries a usable target (finite angles).
+    pub valid: bool,
+    /// Whether the report also carries a position for the target.
+    pub position_valid: bool,
+}
+
+/// Flow-sensor quality and the flow turned into ground velocities with the
+/// body rotation taken out.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OpticalFlowQuality {
+    pub sensor_id: u8,
+    /// 0 (no flow) to 100 (best).
+    pub quality_pct: f64,
+    pub flow_comp_x_mps: Option<f64>,
+    pub flow_comp_y_mps: Option<f64>,
+    pub ground_distance_m: Option<f64>,
+}
+
+/// Whether a landing target has been seen recently; the "target acquired"
+/// indicator before committing to a precision landing.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PrecisionLandingStatus {
+    pub target_acquired: bool,
+    /// Time since the last valid target report.
+    pub last_seen_ms: Option<u64>,
+    pub last_target: Option<PrecisionLandingTarget>,
+}
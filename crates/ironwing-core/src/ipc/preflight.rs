@@ -0,0 +1,24 @@
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PositionIssueKind {
+    /// The vehicle has not reported a position, so nothing could be compared.
+    PositionUnknown,
+    /// The vehicle is further from this device than a preflight check
+    /// plausibly allows.
+    PhoneDistance,
+    /// Home is far from where the disarmed vehicle sits, e.g. a stale origin.
+    HomeOffset,
+    /// Reported altitude disagrees with the terrain elevation underneath.
+    TerrainMismatch,
+}
+
+/// One finding of `preflight_position_check`, with the numbers behind it.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PositionIssue {
+    pub kind: PositionIssueKind,
+    pub message: String,
+    pub measured_m: Option<f64>,
+    pub threshold_m: Option<f64>,
+}
@@ -0,0 +1,62 @@
+/// Where a DISTANCE_SENSOR looks, from its MAV_SENSOR_ORIENTATION. Yaw
+/// rotations are named by the direction they face; anything else is `other`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RangefinderOrientation {
+    Forward,
+    ForwardRight,
+    Right,
+    BackRight,
+    Back,
+    BackLeft,
+    Left,
+    ForwardLeft,
+    Up,
+    Down,
+    Other,
+}
+
+/// One DISTANCE_SENSOR report, in metres.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RangefinderReading {
+    pub sensor_id: u8,
+    pub orientation: RangefinderOrientation,
+    /// Raw MAV_SENSOR_ORIENTATION, which tells `other` sensors apart.
+    pub orientation_id: u8,
+    pub distance_m: f64,
+    pub min_distance_m: f64,
+    pub max_distance_m: f64,
+    /// Signal quality in percent; `None` when the sensor does not report it.
+    pub signal_quality_pct: Option<u8>,
+    /// Whether the distance lies within the sensor's range.
+    pub valid: bool,
+}
+
+/// Descent-rate limit that applies at and below `height_m`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LandingAssistBand {
+    pub height_m: f64,
+    pub max_descent_rate_mps: f64,
+}
+
+/// Descent-rate limits checked against the downward rangefinder on the
+/// way down.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LandingAssistProfile {
+    pub enabled: bool,
+    pub bands: Vec<LandingAssistBand>,
+}
+
+/// Descending faster than the profile allows for the measured height.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LandingAssistWarning {
+    pub height_m: f64,
+    pub descent_rate_mps: f64,
+    pub max_descent_rate_mps: f64,
+}
@@ -0,0 +1,42 @@
+/// A point on the predicted return path, altitude relative to home.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RtlPoint {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    pub relative_alt_m: f64,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RtlSegmentKind {
+    Climb,
+    Return,
+    Descend,
+    Loiter,
+    Land,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RtlSegment {
+    pub kind: RtlSegmentKind,
+    pub from: RtlPoint,
+    pub to: RtlPoint,
+    /// Time spent in place, for loiter segments with a known duration.
+    pub duration_s: Option<f64>,
+}
+
+/// What the vehicle is expected to do on RTL from where it is now.
+///
+/// `warnings` lists what the preview had to assume: parameters that are not
+/// downloaded yet (their firmware defaults are used) or an unknown position
+/// or home, which leaves `segments` empty.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RtlPreview {
+    pub segments: Vec<RtlSegment>,
+    pub description: String,
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,35 @@
+use super::GpsFix;
+
+/// GCS-side checks run before `arm_vehicle` and `vehicle_takeoff` send
+/// anything, on top of the autopilot's own pre-arm checks. Every gate is off
+/// by default.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SafetyGateConfig {
+    pub min_fix: Option<GpsFix>,
+    pub min_satellites: Option<u8>,
+    pub max_hdop: Option<f32>,
+    pub require_home: bool,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SafetyGate {
+    GpsFix,
+    Satellites,
+    Hdop,
+    HomeSet,
+}
+
+/// A gate that did not pass, with the value it saw (`None` when the vehicle
+/// has not reported one) and the value it asked for.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SafetyGateFailure {
+    pub gate: SafetyGate,
+    pub message: String,
+    pub current: Option<String>,
+    pub required: String,
+}
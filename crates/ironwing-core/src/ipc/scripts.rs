@@ -0,0 +1,35 @@
+/// A Lua script in the vehicle's scripts directory.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ScriptFile {
+    pub name: String,
+    pub size_bytes: u64,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileTransferPhase {
+    Upload,
+    /// Reading the file back to compare checksums.
+    Verify,
+}
+
+/// Payload of `ftp://progress`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FileTransferProgress {
+    pub remote_path: String,
+    pub phase: FileTransferPhase,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+/// A file written to the vehicle and read back with a matching CRC32.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FileUploadReport {
+    pub remote_path: String,
+    pub size_bytes: u64,
+    pub crc32: u32,
+}
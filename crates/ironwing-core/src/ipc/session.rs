@@ -8,8 +8,9 @@ use crate::ipc::calibration::CalibrationSnapshot;
 use crate::ipc::guided::GuidedSnapshot;
 use crate::ipc::sensor_health::SensorHealthSnapshot;
 use crate::ipc::{
-    SessionEnvelope, domain::DomainValue, envelope::OperationFailure, playback::PlaybackSnapshot,
-    status_text::StatusTextSnapshot, support::SupportSnapshot, telemetry::TelemetrySnapshot,
+    LinkConnectPhase, SessionEnvelope, domain::DomainValue, envelope::OperationFailure,
+    playback::PlaybackSnapshot, status_text::StatusTextSnapshot, support::SupportSnapshot,
+    telemetry::TelemetrySnapshot,
 };
 
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
@@ -24,10 +25,16 @@ pub enum SessionStatus {
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum SessionConnection {
-    Connecting,
+    Connecting {
+        /// How far `connect_link` got; `None` while the link itself is
+        /// reconnecting.
+        phase: Option<LinkConnectPhase>,
+    },
     Connected,
     Disconnected,
-    Error { error: String },
+    Error {
+        error: String,
+    },
 }
 
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
@@ -59,7 +66,7 @@ pub type SessionDomain = DomainValue<SessionSnapshot>;
 #[allow(dead_code)]
 pub fn session_connection_from_link_state(link_state: &LinkState) -> SessionConnection {
     match link_state {
-        LinkState::Connecting => SessionConnection::Connecting,
+        LinkState::Connecting => SessionConnection::Connecting { phase: None },
         LinkState::Connected => SessionConnection::Connected,
         LinkState::Disconnected => SessionConnection::Disconnected,
         LinkState::Error(error) => SessionConnection::Error {
@@ -92,3 +99,11 @@ pub enum AckSessionSnapshotResult {
     Accepted { envelope: SessionEnvelope },
     Rejected { failure: OperationFailure },
 }
+
+/// Sent on `system://resumed` once per wake-up of the host, after the live
+/// state has been re-emitted.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SystemResumed {
+    pub suspended_ms: u64,
+}
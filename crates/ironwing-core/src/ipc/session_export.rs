@@ -0,0 +1,93 @@
+/// Which artifacts `session_export` should gather. Every artifact is
+/// included unless switched off.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SessionExportOptions {
+    pub tlog: bool,
+    pub flight_summary: bool,
+    pub status_text: bool,
+    pub params: bool,
+    pub track_geojson: bool,
+    pub track_kml: bool,
+}
+
+impl Default for SessionExportOptions {
+    fn default() -> Self {
+        Self {
+            tlog: true,
+            flight_summary: true,
+            status_text: true,
+            params: true,
+            track_geojson: true,
+            track_kml: true,
+        }
+    }
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionArtifact {
+    Tlog,
+    FlightSummary,
+    StatusText,
+    Params,
+    TrackGeojson,
+    TrackKml,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionArtifactStatus {
+    Included,
+    /// Requested, but the session has nothing to put in it.
+    Missing,
+    /// Switched off in `SessionExportOptions`.
+    Skipped,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SessionArtifactEntry {
+    pub artifact: SessionArtifact,
+    pub status: SessionArtifactStatus,
+    /// Path inside the archive, for included artifacts.
+    pub file_name: Option<String>,
+    /// Uncompressed size.
+    pub bytes: u64,
+    /// Why an artifact is missing, or a caveat such as a tlog that was still
+    /// being recorded.
+    pub note: Option<String>,
+}
+
+/// `manifest.json` at the root of the archive.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SessionExportManifest {
+    pub app_version: String,
+    pub created_unix_msec: u64,
+    pub artifacts: Vec<SessionArtifactEntry>,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ExportReport {
+    pub path: String,
+    /// Size of the archive on disk.
+    pub bytes_written: u64,
+    pub artifacts: Vec<SessionArtifactEntry>,
+}
+
+/// Payload of `session_export://progress`, sent as each artifact is
+/// written.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SessionExportProgress {
+    pub artifact: SessionArtifact,
+    /// 1-based position among the artifacts being gathered.
+    pub index: u32,
+    pub total: u32,
+    pub status: SessionArtifactStatus,
+}
@@ -0,0 +1,122 @@
+use crate::ipc::{
+    AlertRule, AnnouncementSettings, ChecklistConfig, HealthThresholds, LandingAssistProfile,
+    LinkTimeoutOverrides, SafetyGateConfig, TlogFsyncPolicy, TlogRotation, UnitSystem,
+};
+
+/// Backend settings that survive a restart. Saved to the settings file in the
+/// app data directory; `schema_version` drives migrations when the shape
+/// changes.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub schema_version: u32,
+    pub telemetry_rate_hz: u32,
+    pub units: UnitSystem,
+    pub alert_rules: Vec<AlertRule>,
+    pub auto_connect_usb: bool,
+    pub auto_connect_usb_baud: u32,
+    /// Frames buffered between a bridged transport (BLE, SPP, USB OTG, serial
+    /// with options) and MAVKit.
+    pub link_buffer_frames: u32,
+    /// Highest altitude a takeoff command may ask for, in metres.
+    pub takeoff_max_alt_m: f32,
+    /// Warn before arming when the vehicle's GCS failsafe is set to do
+    /// nothing.
+    pub expected_failsafe_check: bool,
+    /// Logs that may be open at once for comparison; each holds its whole
+    /// parsed index in memory.
+    pub max_open_logs: u32,
+    /// Positions kept in the live flight track before the oldest are dropped.
+    pub track_max_points: u32,
+    /// GCS-side checks before arming and takeoff.
+    pub safety_gates: SafetyGateConfig,
+    /// Pre-takeoff checklist definition.
+    pub checklist: ChecklistConfig,
+    /// Unlocks the raw COMMAND_LONG and message send console and the RC
+    /// override stream.
+    pub expert_mode: bool,
+    /// Descent-rate warnings from the downward rangefinder near the ground.
+    pub landing_assist: LandingAssistProfile,
+    /// Fastest line speed a winch command may ask for, in m/s.
+    pub winch_max_rate_mps: f32,
+    /// Limits the vehicle health report grades battery, link and heartbeat
+    /// against.
+    pub health_thresholds: HealthThresholds,
+    /// Distance from a fence boundary inside which closing on it raises a
+    /// `fence://proximity` warning, in metres.
+    pub fence_warning_margin_m: f32,
+    /// The telemetry radio also carries the RC uplink (mLRS, ELRS in MAVLink
+    /// mode), so its RADIO_STATUS counts as RC link quality.
+    pub rc_link_on_telemetry_radio: bool,
+    /// Per-transport command, mission and parameter timeouts in place of the
+    /// built-in profiles. Takes effect on the next connect.
+    pub link_timeouts: LinkTimeoutOverrides,
+    /// Connect straight away to a link given with `--connect` or an
+    /// `ironwing://connect` link instead of asking first.
+    pub auto_connect_launch_link: bool,
+    /// Spoken progress on `announce://say`.
+    pub announcements: AnnouncementSettings,
+    /// Segment limits for recordings started on connect; with neither set
+    /// the recording is one file.
+    pub recording_rotation: TlogRotation,
+    /// When recordings started on connect are forced to storage.
+    pub recording_fsync: TlogFsyncPolicy,
+    /// A telemetry group with no new data for this long is reported as
+    /// stale, in milliseconds. Raise it on links that stream slowly.
+    pub telemetry_stale_after_ms: u32,
+}
+
+/// A partial settings update; unset fields keep their current value.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SettingsPatch {
+    #[serde(default)]
+    pub telemetry_rate_hz: Option<u32>,
+    #[serde(default)]
+    pub units: Option<UnitSystem>,
+    #[serde(default)]
+    pub alert_rules: Option<Vec<AlertRule>>,
+    #[serde(default)]
+    pub auto_connect_usb: Option<bool>,
+    #[serde(default)]
+    pub auto_connect_usb_baud: Option<u32>,
+    #[serde(default)]
+    pub link_buffer_frames: Option<u32>,
+    #[serde(default)]
+    pub takeoff_max_alt_m: Option<f32>,
+    #[serde(default)]
+    pub expected_failsafe_check: Option<bool>,
+    #[serde(default)]
+    pub max_open_logs: Option<u32>,
+    #[serde(default)]
+    pub track_max_points: Option<u32>,
+    #[serde(default)]
+    pub safety_gates: Option<SafetyGateConfig>,
+    #[serde(default)]
+    pub checklist: Option<ChecklistConfig>,
+    #[serde(default)]
+    pub expert_mode: Option<bool>,
+    #[serde(default)]
+    pub landing_assist: Option<LandingAssistProfile>,
+    #[serde(default)]
+    pub winch_max_rate_mps: Option<f32>,
+    #[serde(default)]
+    pub health_thresholds: Option<HealthThresholds>,
+    #[serde(default)]
+    pub fence_warning_margin_m: Option<f32>,
+    #[serde(default)]
+    pub rc_link_on_telemetry_radio: Option<bool>,
+    #[serde(default)]
+    pub link_timeouts: Option<LinkTimeoutOverrides>,
+    #[serde(default)]
+    pub auto_connect_launch_link: Option<bool>,
+    #[serde(default)]
+    pub announcements: Option<AnnouncementSettings>,
+    #[serde(default)]
+    pub recording_rotation: Option<TlogRotation>,
+    #[serde(default)]
+    pub recording_fsync: Option<TlogFsyncPolicy>,
+    #[serde(default)]
+    pub telemetry_stale_after_ms: Option<u32>,
+}
@@ -1,6 +1,6 @@
 use serde_json::Value;
 
-use crate::ipc::{DomainProvenance, DomainValue};
+use crate::ipc::{DomainProvenance, DomainValue, VehicleTime};
 
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -9,6 +9,19 @@ pub struct StatusTextEntry {
     pub text: String,
     pub severity: String,
     pub timestamp_usec: Option<u64>,
+    /// How many consecutive identical messages this entry stands for.
+    #[serde(default = "single_occurrence")]
+    pub repeat_count: u32,
+    /// `timestamp_usec` on the vehicle's clocks, once TIMESYNC has an offset.
+    #[serde(default)]
+    pub vehicle_time: Option<VehicleTime>,
+    /// Whether the text announces a failsafe (also sent on `vehicle://failsafe`).
+    #[serde(default)]
+    pub failsafe: bool,
+}
+
+fn single_occurrence() -> u32 {
+    1
 }
 
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
@@ -18,7 +31,7 @@ pub struct StatusTextState {
 }
 
 pub type StatusTextSnapshot = DomainValue<StatusTextState>;
-pub const STATUS_TEXT_HISTORY_LIMIT: usize = 100;
+pub const STATUS_TEXT_HISTORY_LIMIT: usize = 200;
 
 pub fn status_text_entry_from_value(value: &Value) -> Option<StatusTextEntry> {
     Some(StatusTextEntry {
@@ -30,6 +43,9 @@ pub fn status_text_entry_from_value(value: &Value) -> Option<StatusTextEntry> {
             .unwrap_or("info")
             .to_string(),
         timestamp_usec: value.get("timestamp_usec").and_then(Value::as_u64),
+        repeat_count: 1,
+        vehicle_time: None,
+        failsafe: false,
     })
 }
 
@@ -41,10 +57,131 @@ pub fn status_text_snapshot_from_entries(
     DomainValue::present(StatusTextState { entries }, provenance)
 }
 
-pub fn push_status_text_entry(history: &mut Vec<StatusTextEntry>, entry: StatusTextEntry) {
+/// Append `entry` to the bounded history, or fold it into the last entry when
+/// it repeats that entry's text and severity (firmware often re-sends the same
+/// line dozens of times). A folded entry takes `entry`'s sequence, so readers
+/// following the history by sequence see the repeat. Returns `false` when the
+/// entry was folded.
+pub fn push_status_text_entry(history: &mut Vec<StatusTextEntry>, entry: StatusTextEntry) -> bool {
+    if let Some(last) = history.last_mut()
+        && last.text == entry.text
+        && last.severity == entry.severity
+    {
+        last.sequence = entry.sequence;
+        last.repeat_count = last.repeat_count.saturating_add(1);
+        last.timestamp_usec = entry.timestamp_usec.or(last.timestamp_usec);
+        last.vehicle_time = entry.vehicle_time.or(last.vehicle_time);
+        return false;
+    }
+
     history.push(entry);
     if history.len() > STATUS_TEXT_HISTORY_LIMIT {
         let drain = history.len().saturating_sub(STATUS_TEXT_HISTORY_LIMIT);
         history.drain(0..drain);
     }
+    true
+}
+
+/// A marker for the newest entry in `history`; entries pushed afterwards have
+/// a larger sequence.
+pub fn status_text_mark(history: &[StatusTextEntry]) -> u64 {
+    history.last().map_or(0, |entry| entry.sequence)
+}
+
+/// Texts of the entries in `history` pushed after `mark` was taken.
+pub fn status_texts_after(history: &[StatusTextEntry], mark: u64) -> Vec<String> {
+    history
+        .iter()
+        .filter(|entry| entry.sequence > mark)
+        .map(|entry| entry.text.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(sequence: u64, text: &str, timestamp_usec: u64) -> StatusTextEntry {
+        StatusTextEntry {
+            sequence,
+            text: text.into(),
+            severity: "info".into(),
+            timestamp_usec: Some(timestamp_usec),
+            repeat_count: 1,
+            vehicle_time: None,
+            failsafe: false,
+        }
+    }
+
+    #[test]
+    fn consecutive_duplicates_collapse_into_one_entry() {
+        let mut history = Vec::new();
+
+        assert!(push_status_text_entry(
+            &mut history,
+            entry(1, "EKF2 IMU0 is using GPS", 10)
+        ));
+        assert!(!push_status_text_entry(
+            &mut history,
+            entry(2, "EKF2 IMU0 is using GPS", 20)
+        ));
+        assert!(!push_status_text_entry(
+            &mut history,
+            entry(3, "EKF2 IMU0 is using GPS", 30)
+        ));
+        assert!(push_status_text_entry(
+            &mut history,
+            entry(4, "Arming motors", 40)
+        ));
+        assert!(push_status_text_entry(
+            &mut history,
+            entry(5, "EKF2 IMU0 is using GPS", 50)
+        ));
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].sequence, 3);
+        assert_eq!(history[0].repeat_count, 3);
+        assert_eq!(history[0].timestamp_usec, Some(30));
+        assert_eq!(history[2].repeat_count, 1);
+    }
+
+    #[test]
+    fn repeated_line_is_visible_after_a_mark() {
+        let mut history = Vec::new();
+        push_status_text_entry(&mut history, entry(1, "PreArm: Throttle too high", 10));
+        let mark = status_text_mark(&history);
+
+        push_status_text_entry(&mut history, entry(2, "PreArm: Throttle too high", 20));
+
+        assert_eq!(history.len(), 1);
+        assert_eq!(
+            status_texts_after(&history, mark),
+            vec!["PreArm: Throttle too high".to_string()]
+        );
+        assert_eq!(status_text_mark(&history), 2);
+    }
+
+    #[test]
+    fn history_keeps_the_most_recent_entries() {
+        let mut history = Vec::new();
+        for sequence in 0..(STATUS_TEXT_HISTORY_LIMIT as u64 + 5) {
+            push_status_text_entry(&mut history, entry(sequence, &format!("msg {sequence}"), 0));
+        }
+
+        assert_eq!(history.len(), STATUS_TEXT_HISTORY_LIMIT);
+        assert_eq!(history[0].sequence, 5);
+    }
+
+    #[test]
+    fn entries_without_repeat_count_deserialize_as_single() {
+        let entry: StatusTextEntry = serde_json::from_value(serde_json::json!({
+            "sequence": 1,
+            "text": "PreArm: Compass not calibrated",
+            "severity": "critical",
+            "timestamp_usec": null,
+        }))
+        .expect("deserialize entry");
+
+        assert_eq!(entry.repeat_count, 1);
+    }
 }
@@ -0,0 +1,49 @@
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TakeoffGuidedRequest {
+    pub altitude_m: f32,
+    /// Arm the vehicle first when it is disarmed; otherwise a disarmed
+    /// vehicle fails the sequence at the arm step.
+    #[serde(default)]
+    pub allow_arm: bool,
+    /// Skip the GCS-side safety gates.
+    #[serde(default)]
+    pub override_gates: bool,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TakeoffStep {
+    SetGuided,
+    Arm,
+    Takeoff,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TakeoffStepStatus {
+    Done,
+    /// Already satisfied, so nothing was sent.
+    Skipped,
+    Failed,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TakeoffStepReport {
+    pub step: TakeoffStep,
+    pub status: TakeoffStepStatus,
+    /// Why the step failed, decoded from the vehicle's answer and the
+    /// STATUSTEXT it sent right after.
+    pub message: Option<String>,
+}
+
+/// Outcome of each step in order; the sequence stops at the first failure.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TakeoffReport {
+    pub steps: Vec<TakeoffStepReport>,
+    pub success: bool,
+}
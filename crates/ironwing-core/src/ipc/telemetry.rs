@@ -1,6 +1,8 @@
+use std::collections::BTreeMap;
+
 use serde_json::Value;
 
-use crate::ipc::{DomainProvenance, DomainValue};
+use crate::ipc::{DisplayValue, DomainProvenance, DomainValue};
 
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
 #[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
@@ -66,6 +68,90 @@ pub struct TelemetryRadio {
     pub servo_outputs: Option<Vec<f64>>,
 }
 
+/// When one telemetry group last received data, as Unix-epoch microseconds,
+/// and whether that is older than the staleness threshold. A group that has
+/// never received data on the current link is stale.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct TelemetryGroupFreshness {
+    pub last_update_usec: Option<u64>,
+    pub stale: bool,
+}
+
+/// Per-group freshness for live telemetry. Playback snapshots leave every
+/// group at its default (no timestamp, not stale).
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct TelemetryFreshness {
+    pub flight: TelemetryGroupFreshness,
+    pub navigation: TelemetryGroupFreshness,
+    pub attitude: TelemetryGroupFreshness,
+    pub power: TelemetryGroupFreshness,
+    pub gps: TelemetryGroupFreshness,
+    pub terrain: TelemetryGroupFreshness,
+    pub radio: TelemetryGroupFreshness,
+}
+
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindSource {
+    /// Reported by the autopilot in WIND_COV.
+    Reported,
+    /// Estimated from the airspeed/heading versus groundspeed/track triangle.
+    Estimated,
+}
+
+/// Values computed by the backend rather than sent by the autopilot. A value
+/// that cannot be computed (e.g. wind without an airspeed sensor) is `None`.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct TelemetryDerived {
+    pub wind_speed_mps: Option<f64>,
+    /// Direction the wind blows from, degrees clockwise from north.
+    pub wind_direction_deg: Option<f64>,
+    pub wind_source: Option<WindSource>,
+    pub home_distance_m: Option<f64>,
+    pub home_bearing_deg: Option<f64>,
+    /// Horizontal distance per unit of height lost; only while descending.
+    pub glide_ratio: Option<f64>,
+    pub energy_wh_per_km: Option<f64>,
+    /// Height above ground from a downward rangefinder, next to the
+    /// barometric altitude.
+    #[serde(default)]
+    pub rangefinder_height_m: Option<f64>,
+    /// Quality of the RC receiver link, next to the telemetry link's.
+    #[serde(default)]
+    pub rc_link: Option<RcLinkQuality>,
+    /// Distance driven since connecting, from WHEEL_DISTANCE; rovers and
+    /// boats with wheel encoders only.
+    #[serde(default)]
+    pub odometer_m: Option<f64>,
+}
+
+/// Which message an RC link quality was read from.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RcLinkSource {
+    RcChannels,
+    /// A telemetry radio that also carries the RC uplink.
+    RadioStatus,
+    RadioRcChannels,
+    RadioLinkStats,
+}
+
+/// RC receiver link health normalized to percent. Each source reports
+/// only some of the figures.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RcLinkQuality {
+    pub rssi_pct: Option<f64>,
+    /// Share of RC frames received.
+    pub lq_pct: Option<f64>,
+    pub source: RcLinkSource,
+}
+
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
 #[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
 pub struct TelemetryState {
@@ -76,10 +162,43 @@ pub struct TelemetryState {
     pub gps: TelemetryGps,
     pub terrain: TelemetryTerrain,
     pub radio: TelemetryRadio,
+    #[serde(default)]
+    pub freshness: TelemetryFreshness,
+    #[serde(default)]
+    pub derived: TelemetryDerived,
+    /// Display-unit copies of the unit-bearing fields, keyed by `group.field`.
+    /// The fields themselves always stay SI. Absent when the display units
+    /// are SI or the caller asked for raw values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<BTreeMap<String, DisplayValue>>,
 }
 
 pub type TelemetrySnapshot = DomainValue<TelemetryState>;
 
+/// How `telemetry://state` ticks reach the webview.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TelemetryEncoding {
+    /// A whole `TelemetrySnapshot` on `telemetry://state` every tick.
+    #[default]
+    Full,
+    /// Changed fields only, under short keys, on `telemetry://delta`.
+    Delta,
+}
+
+/// Payload of `telemetry://delta`. A keyframe carries every field and
+/// replaces whatever the receiver held; other frames carry the fields that
+/// moved past their epsilon since they were last sent. `seq` counts frames
+/// so a receiver can tell it missed one and ask for a keyframe.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TelemetryDelta {
+    pub seq: u64,
+    pub keyframe: bool,
+    pub fields: BTreeMap<String, Value>,
+}
+
 fn number(value: &Value, key: &str) -> Option<f64> {
     value.get(key).and_then(Value::as_f64)
 }
@@ -147,6 +266,9 @@ pub fn telemetry_state_from_value(value: &Value) -> TelemetryState {
             rc_rssi: number(value, "rc_rssi"),
             servo_outputs: number_list(value, "servo_outputs"),
         },
+        freshness: TelemetryFreshness::default(),
+        derived: TelemetryDerived::default(),
+        display: None,
     }
 }
 
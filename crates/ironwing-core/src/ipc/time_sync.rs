@@ -0,0 +1,22 @@
+/// State of the vehicle clock estimate built from TIMESYNC and SYSTEM_TIME.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TimeSyncStatus {
+    /// GCS Unix time minus vehicle time since boot, in microseconds. `None`
+    /// until a TIMESYNC exchange has completed.
+    pub offset_us: Option<i64>,
+    /// Round trip of the exchange the offset was taken from.
+    pub rtt_us: Option<i64>,
+    /// Whether SYSTEM_TIME has carried a UTC time (usually from GPS).
+    pub utc_available: bool,
+}
+
+/// A GCS timestamp translated to the vehicle's clocks.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VehicleTime {
+    /// Microseconds since the vehicle booted, as in its own logs.
+    pub time_boot_us: u64,
+    /// The vehicle's UTC time in Unix microseconds, once SYSTEM_TIME has one.
+    pub utc_usec: Option<u64>,
+}
@@ -0,0 +1,13 @@
+/// One GLOBAL_POSITION_INT sample of the live vehicle's breadcrumb trail,
+/// stamped with the ground station's clock when it arrived.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TrackPoint {
+    pub timestamp_usec: u64,
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    pub altitude_msl_m: f64,
+    pub relative_alt_m: f64,
+    /// Course over ground; `None` when the vehicle reports it as unknown.
+    pub heading_deg: Option<f64>,
+}
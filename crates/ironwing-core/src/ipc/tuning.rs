@@ -0,0 +1,55 @@
+/// Body axis a tuning capture records.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TuneAxis {
+    Roll,
+    Pitch,
+    Yaw,
+}
+
+/// What the response was measured against.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TuneTargetSource {
+    /// ATTITUDE_TARGET body rates against ATTITUDE rates, in deg/s.
+    AttitudeTarget,
+    /// NAV_CONTROLLER_OUTPUT desired roll/pitch against ATTITUDE angles, in
+    /// degrees. Used when the autopilot does not stream ATTITUDE_TARGET.
+    NavControllerOutput,
+}
+
+/// Response to one step in the target. Times are seconds from the step.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TuneStep {
+    /// Seconds from the start of the capture.
+    pub start_s: f64,
+    pub from: f64,
+    pub to: f64,
+    /// 10 % to 90 % of the step; `None` when the response never got there.
+    pub rise_time_s: Option<f64>,
+    /// Peak past the target as a percentage of the step size.
+    pub overshoot_pct: f64,
+    /// Until the response stays within 5 % of the step around the target;
+    /// `None` when it had not settled before the next step.
+    pub settling_time_s: Option<f64>,
+    /// Ringing around the target; `None` without at least a full swing.
+    pub oscillation_hz: Option<f64>,
+}
+
+/// Step-response analysis of a `tune_capture_start`/`tune_capture_stop`
+/// window.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TuneReport {
+    pub axis: TuneAxis,
+    /// `None` when no target message arrived during the capture.
+    pub source: Option<TuneTargetSource>,
+    pub started_unix_msec: u64,
+    pub duration_s: f64,
+    /// Response samples paired with a target.
+    pub samples: u32,
+    pub steps: Vec<TuneStep>,
+}
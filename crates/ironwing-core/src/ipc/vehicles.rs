@@ -0,0 +1,36 @@
+/// One MAVLink system heard on the active link.
+///
+/// `mav_type`, `autopilot` and `system_status` carry the raw HEARTBEAT enum
+/// values so systems that never become a full `Vehicle` can still be listed.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VehicleListEntry {
+    pub system_id: u8,
+    pub component_id: u8,
+    pub mav_type: u8,
+    pub autopilot: u8,
+    pub system_status: u8,
+    pub armed: bool,
+    pub custom_mode: u32,
+    pub last_seen_ms_ago: u64,
+    /// True for the system the live runtime's `Vehicle` is bound to.
+    pub selected: bool,
+}
+
+/// Sent on `vehicle://selected` once the live link is bound to the system
+/// `vehicle_select` asked for.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VehicleSelected {
+    pub system_id: u8,
+}
+
+/// One component (autopilot, camera, gimbal, companion...) of the bound system.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ComponentListEntry {
+    pub component_id: u8,
+    pub mav_type: u8,
+    pub autopilot: u8,
+    pub last_seen_ms_ago: u64,
+}
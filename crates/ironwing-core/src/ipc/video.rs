@@ -0,0 +1,21 @@
+/// One stream advertised through VIDEO_STREAM_INFORMATION, emitted as part of
+/// `video://streams`. IronWing only hands `uri` to a player; it never decodes
+/// the video itself.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct VideoStream {
+    pub component_id: u8,
+    /// 1-based stream id within the component.
+    pub stream_id: u8,
+    /// Streams the component says it has in total.
+    pub count: u8,
+    pub name: String,
+    pub uri: String,
+    /// Transport, e.g. "rtsp", "rtpudp", "tcp_mpeg" or "mpeg_ts".
+    pub stream_type: String,
+    pub resolution_h: u16,
+    pub resolution_v: u16,
+    pub bitrate_bps: u32,
+    pub rotation_deg: u16,
+    pub framerate_hz: f32,
+}
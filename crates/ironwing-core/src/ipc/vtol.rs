@@ -0,0 +1,50 @@
+/// MAV_VTOL_STATE from EXTENDED_SYS_STATE.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VtolState {
+    /// Not a VTOL, or the autopilot does not say.
+    Undefined,
+    TransitionToFixedWing,
+    TransitionToMultiCopter,
+    MultiCopter,
+    FixedWing,
+}
+
+/// MAV_LANDED_STATE from EXTENDED_SYS_STATE.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LandedState {
+    Undefined,
+    OnGround,
+    InAir,
+    Takeoff,
+    Landing,
+}
+
+/// Payload of `vtol_status` and `vehicle://vtol`, sent when either state
+/// changes.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct VtolStatus {
+    pub vtol_state: VtolState,
+    pub landed_state: LandedState,
+}
+
+/// Flight configuration `vtol_transition` asks for.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VtolTransitionTarget {
+    MultiCopter,
+    FixedWing,
+}
+
+/// A change of VTOL or landed state in a log.
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LogVtolEvent {
+    pub timestamp_usec: u64,
+    pub status: VtolStatus,
+}
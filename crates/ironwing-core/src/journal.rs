@@ -0,0 +1,94 @@
+use std::collections::VecDeque;
+
+use crate::ipc::JournalEntry;
+
+/// File the desktop shell appends journal entries to, one JSON object per line.
+pub const JOURNAL_FILE_NAME: &str = "journal.jsonl";
+/// The previous journal file, kept after rotation.
+pub const JOURNAL_ROTATED_FILE_NAME: &str = "journal.1.jsonl";
+pub const JOURNAL_ROTATE_BYTES: u64 = 10 * 1024 * 1024;
+/// Entries kept in memory while the journal file cannot be written.
+pub const JOURNAL_MEMORY_CAPACITY: usize = 1000;
+
+/// Serialize an entry as a single newline-terminated line.
+pub fn encode_entry(entry: &JournalEntry) -> Result<Vec<u8>, String> {
+    let mut line = serde_json::to_vec(entry)
+        .map_err(|error| format!("failed to serialize journal entry: {error}"))?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+/// Whether appending `line_len` bytes to a file of `file_len` bytes should
+/// rotate it first. An empty file is never rotated, so an oversized entry
+/// still gets written.
+pub fn should_rotate(file_len: u64, line_len: usize) -> bool {
+    file_len > 0 && file_len + line_len as u64 > JOURNAL_ROTATE_BYTES
+}
+
+/// Entries from `text` issued within `start_ms..=end_ms`. Lines that do not
+/// parse are skipped: a crash mid-write leaves at most a torn last line, and
+/// one bad line must not hide the rest of the journal.
+pub fn parse_journal(text: &str, start_ms: u64, end_ms: u64) -> Vec<JournalEntry> {
+    text.lines()
+        .filter_map(|line| serde_json::from_str::<JournalEntry>(line).ok())
+        .filter(|entry| (start_ms..=end_ms).contains(&entry.timestamp_ms))
+        .collect()
+}
+
+/// Append to a bounded in-memory journal, dropping the oldest entry when full.
+pub fn push_bounded(memory: &mut VecDeque<JournalEntry>, entry: JournalEntry) {
+    if memory.len() == JOURNAL_MEMORY_CAPACITY {
+        memory.pop_front();
+    }
+    memory.push_back(entry);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::{JournalOutcome, OperationId};
+
+    fn entry(timestamp_ms: u64) -> JournalEntry {
+        JournalEntry {
+            timestamp_ms,
+            duration_ms: 12,
+            operation_id: OperationId::ArmVehicle,
+            request: serde_json::json!({ "force": false }),
+            outcome: JournalOutcome::Ok {
+                result: serde_json::Value::Null,
+            },
+            vehicle_time: None,
+        }
+    }
+
+    #[test]
+    fn torn_and_out_of_range_lines_are_skipped() {
+        let mut text = String::new();
+        for timestamp_ms in [100, 200, 300] {
+            let line = encode_entry(&entry(timestamp_ms)).expect("encode");
+            text.push_str(std::str::from_utf8(&line).expect("utf8"));
+        }
+        let torn = encode_entry(&entry(250)).expect("encode");
+        text.push_str(std::str::from_utf8(&torn[..torn.len() / 2]).expect("utf8"));
+
+        let entries = parse_journal(&text, 150, 300);
+        assert_eq!(entries, vec![entry(200), entry(300)]);
+    }
+
+    #[test]
+    fn rotation_waits_for_the_size_limit() {
+        assert!(!should_rotate(0, JOURNAL_ROTATE_BYTES as usize + 1));
+        assert!(!should_rotate(JOURNAL_ROTATE_BYTES - 10, 10));
+        assert!(should_rotate(JOURNAL_ROTATE_BYTES - 10, 11));
+    }
+
+    #[test]
+    fn memory_journal_drops_the_oldest_entry() {
+        let mut memory = VecDeque::new();
+        for timestamp_ms in 0..=JOURNAL_MEMORY_CAPACITY as u64 {
+            push_bounded(&mut memory, entry(timestamp_ms));
+        }
+        assert_eq!(memory.len(), JOURNAL_MEMORY_CAPACITY);
+        assert_eq!(memory.front().map(|entry| entry.timestamp_ms), Some(1));
+    }
+}
@@ -1,12 +1,93 @@
+pub mod alerts;
+pub mod announcements;
+pub mod background_keepalive;
+pub mod battery_cells;
 pub mod bluetooth_profile;
+pub mod calibration_status;
+pub mod checklist;
+pub mod component_command;
+pub mod derived_telemetry;
+pub mod diagnostics_bundle;
+pub mod dialect;
+pub mod disconnect_action;
 pub mod event_names;
+pub mod event_throttle;
+pub mod failsafe;
+pub mod fence_proximity;
+pub mod flight_mode_switch;
+pub mod flight_modes;
+pub mod flight_summary;
+pub mod flight_track;
+pub mod frames;
+pub mod gcs_commands;
+pub mod gcs_position;
+pub mod geo;
+pub mod goto;
+pub mod gps_fix;
+pub mod guided_queue;
+pub mod health;
+pub mod heartbeats;
+pub mod high_latency;
+pub mod home_moved;
 pub mod ipc;
+pub mod journal;
+pub mod link_connect;
+pub mod link_loss;
+pub mod link_sources;
 pub mod live;
 pub mod live_runtime;
+pub mod local_api;
+pub mod log_analysis;
 pub mod log_engine;
+pub mod log_expr;
+pub mod log_follow;
 pub mod log_playback;
+pub mod map_tiles;
+pub mod mavftp;
+pub mod mavlink_console;
+pub mod mavlink_frames;
+pub mod mission_edit;
+pub mod mission_resume;
+pub mod named_values;
+pub mod open_logs;
+pub mod orbit;
+pub mod param_attribution;
+pub mod param_bits;
+pub mod param_value;
+pub mod params;
+pub mod payload;
+pub mod pending_connect;
+pub mod plane;
+pub mod precision_landing;
+pub mod preflight_position;
+pub mod rangefinder;
+pub mod rc_link_quality;
+pub mod rc_override;
+pub mod rover;
+pub mod rtl_preview;
 pub mod runtime;
+pub mod safety_gates;
+pub mod serial_hotplug;
+pub mod serial_probe;
+pub mod session_recovery;
+pub mod settings;
+pub mod system_resume;
+pub mod takeoff;
+pub mod task_watchdog;
 pub mod telemetry;
+pub mod telemetry_delta;
+pub mod time_sync;
+pub mod tlog_segments;
+pub mod track_export;
+pub mod transfer_rate;
 pub mod transport;
+pub mod transport_drain;
+pub mod tune_analysis;
+pub mod udp_bind;
+pub mod ulog;
+pub mod units;
+pub mod unknown_messages;
 pub mod vehicle_config;
 pub mod vehicle_snapshot;
+pub mod video_streams;
+pub mod vtol;
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use crate::ipc::{ConnectRequest, LinkConnectPhase, LinkConnecting, Reason, ReasonKind};
+
+/// How long `connect_link` waits for the first heartbeat unless the request
+/// names its own timeout.
+pub const DEFAULT_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The bound handed to MAVKit. `connect_link` enforces the heartbeat timeout
+/// itself, so an attempt can be kept alive past it; this one is long enough to
+/// never fire in practice, and cancelling the attempt ends it.
+pub const KEEP_WAITING_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How often `link://connecting` is emitted while a connect is in flight.
+pub const LINK_CONNECTING_INTERVAL: Duration = Duration::from_secs(1);
+
+pub const NO_HEARTBEAT_MESSAGE: &str =
+    "no heartbeat received — check the vehicle is powered and on the same network";
+
+/// The heartbeat timeout `request` asks for, or `None` to wait indefinitely.
+pub fn heartbeat_timeout(request: &ConnectRequest) -> Option<Duration> {
+    if request.keep_waiting {
+        return None;
+    }
+    Some(
+        request
+            .heartbeat_timeout_secs
+            .filter(|secs| *secs > 0)
+            .map_or(DEFAULT_HEARTBEAT_TIMEOUT, |secs| {
+                Duration::from_secs(u64::from(secs))
+            }),
+    )
+}
+
+pub fn link_connecting(
+    phase: LinkConnectPhase,
+    elapsed: Duration,
+    timeout: Option<Duration>,
+) -> LinkConnecting {
+    LinkConnecting {
+        phase,
+        elapsed_ms: elapsed.as_millis() as u64,
+        timeout_ms: timeout.map(|timeout| timeout.as_millis() as u64),
+    }
+}
+
+/// Why a connect attempt ran out of time in `phase`. Only an open transport
+/// points at the vehicle; otherwise the link may never have come up.
+pub fn connect_timeout_failure(phase: LinkConnectPhase, timeout: Duration) -> Reason {
+    let message = match phase {
+        LinkConnectPhase::WaitingForHeartbeat => NO_HEARTBEAT_MESSAGE.to_string(),
+        LinkConnectPhase::OpeningTransport => format!(
+            "no response within {} s — check the address and that the device is reachable",
+            timeout.as_secs()
+        ),
+    };
+    Reason {
+        kind: ReasonKind::Timeout,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::ConnectTransport;
+
+    fn request(heartbeat_timeout_secs: Option<u32>, keep_waiting: bool) -> ConnectRequest {
+        ConnectRequest {
+            transport: ConnectTransport::Udp {
+                bind_addr: "0.0.0.0:14550".into(),
+            },
+            auto_record_on_connect: false,
+            heartbeat_timeout_secs,
+            keep_waiting,
+        }
+    }
+
+    #[test]
+    fn heartbeat_timeout_defaults_and_can_be_lifted() {
+        assert_eq!(
+            heartbeat_timeout(&request(None, false)),
+            Some(DEFAULT_HEARTBEAT_TIMEOUT)
+        );
+        assert_eq!(
+            heartbeat_timeout(&request(Some(0), false)),
+            Some(DEFAULT_HEARTBEAT_TIMEOUT)
+        );
+        assert_eq!(
+            heartbeat_timeout(&request(Some(5), false)),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(heartbeat_timeout(&request(Some(5), true)), None);
+
+        let old_request: ConnectRequest =
+            serde_json::from_str(r#"{"transport":{"kind":"udp","bind_addr":"0.0.0.0:14550"}}"#)
+                .unwrap();
+        assert!(!old_request.keep_waiting);
+        assert_eq!(old_request.heartbeat_timeout_secs, None);
+    }
+
+    #[test]
+    fn timeout_message_depends_on_the_phase() {
+        let waiting = connect_timeout_failure(
+            LinkConnectPhase::WaitingForHeartbeat,
+            DEFAULT_HEARTBEAT_TIMEOUT,
+        );
+        assert_eq!(waiting.kind, ReasonKind::Timeout);
+        assert_eq!(waiting.message, NO_HEARTBEAT_MESSAGE);
+
+        let opening =
+            connect_timeout_failure(LinkConnectPhase::OpeningTransport, Duration::from_secs(10));
+        assert!(opening.message.contains("no response within 10 s"));
+
+        let progress = link_connecting(
+            LinkConnectPhase::WaitingForHeartbeat,
+            Duration::from_millis(2_500),
+            None,
+        );
+        assert_eq!(progress.elapsed_ms, 2_500);
+        assert_eq!(progress.timeout_ms, None);
+    }
+}
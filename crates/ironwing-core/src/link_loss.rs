@@ -0,0 +1,262 @@
+use std::time::Duration;
+
+use mavkit::{LinkState, VehicleType};
+
+use crate::ipc::GcsFailsafeBehavior;
+
+/// Longest `gcs_failsafe_test` pause. Enough for any sane FS_GCS_TIMEOUT
+/// to expire, short enough that a forgotten test cannot cost the link.
+pub const GCS_FAILSAFE_TEST_MAX: Duration = Duration::from_secs(30);
+
+/// ArduCopter, Rover and Sub.
+const FS_GCS_ENABLE: &str = "FS_GCS_ENABLE";
+/// ArduPlane spells it without the trailing E.
+const FS_GCS_ENABL: &str = "FS_GCS_ENABL";
+const FS_LONG_ACTN: &str = "FS_LONG_ACTN";
+const ROVER_FS_ACTION: &str = "FS_ACTION";
+/// PX4's data-link loss action. NAV_RCL_ACT is the RC-loss counterpart and
+/// says nothing about the GCS link.
+const NAV_DLL_ACT: &str = "NAV_DLL_ACT";
+
+const COPTER_FS_GCS_ENABLE: &[(i32, &str)] = &[
+    (0, "Disabled"),
+    (1, "RTL"),
+    (2, "RTL, or continue the mission in Auto"),
+    (3, "SmartRTL, or RTL"),
+    (4, "SmartRTL, or Land"),
+    (5, "Land"),
+    (6, "Auto DO_LAND_START, or RTL"),
+    (7, "Brake, or Land"),
+];
+
+const SUB_FS_GCS_ENABLE: &[(i32, &str)] = &[
+    (0, "Disabled"),
+    (1, "Warn only"),
+    (2, "Disarm"),
+    (3, "Depth hold"),
+    (4, "Surface"),
+];
+
+const ROVER_FS_ACTIONS: &[(i32, &str)] = &[
+    (0, "Nothing"),
+    (1, "RTL"),
+    (2, "Hold"),
+    (3, "SmartRTL, or RTL"),
+    (4, "SmartRTL, or Hold"),
+];
+
+const PLANE_FS_LONG_ACTIONS: &[(i32, &str)] = &[
+    (0, "Continue, or RTL outside Auto"),
+    (1, "RTL"),
+    (2, "Glide"),
+    (3, "Deploy parachute"),
+    (4, "Auto"),
+];
+
+const PX4_DLL_ACTIONS: &[(i32, &str)] = &[
+    (0, "Disabled"),
+    (1, "Hold"),
+    (2, "Return"),
+    (3, "Land"),
+    (5, "Terminate"),
+    (6, "Disarm"),
+];
+
+fn label(table: &[(i32, &str)], value: f32) -> String {
+    let value = value.round() as i32;
+    table
+        .iter()
+        .find(|(code, _)| *code == value)
+        .map(|(_, label)| (*label).to_string())
+        .unwrap_or_else(|| format!("Unknown action {value}"))
+}
+
+fn behavior(
+    parameter: &str,
+    value: f32,
+    action: String,
+    does_nothing: bool,
+) -> GcsFailsafeBehavior {
+    GcsFailsafeBehavior {
+        parameter: Some(parameter.to_string()),
+        value: Some(value),
+        action,
+        does_nothing,
+    }
+}
+
+/// Interpret the GCS failsafe parameters of whichever firmware is connected.
+/// `param` looks a parameter up in the downloaded store.
+pub fn gcs_failsafe_behavior(
+    vehicle_type: VehicleType,
+    param: impl Fn(&str) -> Option<f32>,
+) -> GcsFailsafeBehavior {
+    if let Some(enabled) = param(FS_GCS_ENABL) {
+        if enabled.round() as i32 == 0 {
+            return behavior(FS_GCS_ENABL, enabled, "Disabled".to_string(), true);
+        }
+        return match param(FS_LONG_ACTN) {
+            Some(action) => behavior(
+                FS_LONG_ACTN,
+                action,
+                label(PLANE_FS_LONG_ACTIONS, action),
+                false,
+            ),
+            None => behavior(FS_GCS_ENABL, enabled, "Enabled".to_string(), false),
+        };
+    }
+    if let Some(enabled) = param(FS_GCS_ENABLE) {
+        let disabled = enabled.round() as i32 == 0;
+        return match vehicle_type {
+            VehicleType::GroundRover if !disabled => match param(ROVER_FS_ACTION) {
+                Some(action) => behavior(
+                    ROVER_FS_ACTION,
+                    action,
+                    label(ROVER_FS_ACTIONS, action),
+                    action.round() as i32 == 0,
+                ),
+                None => behavior(FS_GCS_ENABLE, enabled, "Enabled".to_string(), false),
+            },
+            VehicleType::Submarine => behavior(
+                FS_GCS_ENABLE,
+                enabled,
+                label(SUB_FS_GCS_ENABLE, enabled),
+                // Warn-only leaves the vehicle doing what it was doing.
+                matches!(enabled.round() as i32, 0 | 1),
+            ),
+            VehicleType::GroundRover => {
+                behavior(FS_GCS_ENABLE, enabled, "Disabled".to_string(), true)
+            }
+            _ => behavior(
+                FS_GCS_ENABLE,
+                enabled,
+                label(COPTER_FS_GCS_ENABLE, enabled),
+                disabled,
+            ),
+        };
+    }
+    if let Some(action) = param(NAV_DLL_ACT) {
+        return behavior(
+            NAV_DLL_ACT,
+            action,
+            label(PX4_DLL_ACTIONS, action),
+            action.round() as i32 == 0,
+        );
+    }
+    GcsFailsafeBehavior {
+        parameter: None,
+        value: None,
+        action: "Unknown".to_string(),
+        does_nothing: false,
+    }
+}
+
+/// Whether a link-state change means the heartbeat timed out on a link that
+/// was up.
+pub fn is_link_loss(previous: &LinkState, current: &LinkState) -> bool {
+    matches!(previous, LinkState::Connected)
+        && matches!(current, LinkState::Disconnected | LinkState::Error(_))
+}
+
+/// The pre-arm style warning shown when losing the GCS would leave the
+/// vehicle unattended.
+pub fn failsafe_check_warning(behavior: &GcsFailsafeBehavior) -> Option<String> {
+    if !behavior.does_nothing {
+        return None;
+    }
+    let parameter = behavior.parameter.as_deref().unwrap_or("GCS failsafe");
+    Some(format!(
+        "{parameter} is set to do nothing on GCS link loss; the vehicle will continue \
+         unattended if the link drops"
+    ))
+}
+
+/// How long `gcs_failsafe_test(seconds)` silences the GCS heartbeat; zero
+/// ends a running test.
+pub fn gcs_failsafe_test_duration(seconds: u32) -> Result<Duration, String> {
+    let duration = Duration::from_secs(u64::from(seconds));
+    if duration > GCS_FAILSAFE_TEST_MAX {
+        return Err(format!(
+            "a GCS failsafe test may pause the heartbeat for at most {} s",
+            GCS_FAILSAFE_TEST_MAX.as_secs()
+        ));
+    }
+    Ok(duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn params(values: &[(&str, f32)]) -> impl Fn(&str) -> Option<f32> {
+        let values: HashMap<String, f32> = values
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect();
+        move |name| values.get(name).copied()
+    }
+
+    #[test]
+    fn copter_failsafe_actions_are_named() {
+        let behavior =
+            gcs_failsafe_behavior(VehicleType::Quadrotor, params(&[("FS_GCS_ENABLE", 4.0)]));
+        assert_eq!(behavior.action, "SmartRTL, or Land");
+        assert!(!behavior.does_nothing);
+        assert_eq!(failsafe_check_warning(&behavior), None);
+
+        let disabled =
+            gcs_failsafe_behavior(VehicleType::Quadrotor, params(&[("FS_GCS_ENABLE", 0.0)]));
+        assert!(disabled.does_nothing);
+        assert!(
+            failsafe_check_warning(&disabled)
+                .expect("warning")
+                .starts_with("FS_GCS_ENABLE")
+        );
+    }
+
+    #[test]
+    fn plane_and_rover_read_their_action_parameters() {
+        let plane = gcs_failsafe_behavior(
+            VehicleType::FixedWing,
+            params(&[("FS_GCS_ENABL", 1.0), ("FS_LONG_ACTN", 2.0)]),
+        );
+        assert_eq!(plane.parameter.as_deref(), Some("FS_LONG_ACTN"));
+        assert_eq!(plane.action, "Glide");
+
+        let rover = gcs_failsafe_behavior(
+            VehicleType::GroundRover,
+            params(&[("FS_GCS_ENABLE", 1.0), ("FS_ACTION", 0.0)]),
+        );
+        assert_eq!(rover.action, "Nothing");
+        assert!(rover.does_nothing);
+    }
+
+    #[test]
+    fn missing_parameters_are_unknown_not_unsafe() {
+        let behavior = gcs_failsafe_behavior(VehicleType::Quadrotor, params(&[]));
+        assert_eq!(behavior.parameter, None);
+        assert!(!behavior.does_nothing);
+    }
+
+    #[test]
+    fn failsafe_test_pause_is_capped() {
+        assert_eq!(gcs_failsafe_test_duration(0), Ok(Duration::ZERO));
+        assert_eq!(gcs_failsafe_test_duration(30), Ok(GCS_FAILSAFE_TEST_MAX));
+        assert!(gcs_failsafe_test_duration(31).is_err());
+    }
+
+    #[test]
+    fn only_a_drop_from_connected_is_a_loss() {
+        assert!(is_link_loss(
+            &LinkState::Connected,
+            &LinkState::Disconnected
+        ));
+        assert!(!is_link_loss(
+            &LinkState::Connecting,
+            &LinkState::Disconnected
+        ));
+        assert!(!is_link_loss(&LinkState::Connected, &LinkState::Connected));
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::ipc::{LinkSource, LinkSourceEntry};
+
+/// RADIO_STATUS is injected by SiK-style telemetry radios under their own
+/// system ID (51 by default). It describes the link rather than any vehicle,
+/// so the source filter never drops it.
+pub const RADIO_STATUS_MESSAGE_ID: u32 = 109;
+
+/// Whether a message from `system_id`/`component_id` gets past the accept
+/// list. An empty list accepts everything.
+pub fn source_allowed(
+    accept: &[LinkSource],
+    system_id: u8,
+    component_id: u8,
+    message_id: u32,
+) -> bool {
+    accept.is_empty()
+        || message_id == RADIO_STATUS_MESSAGE_ID
+        || accept
+            .iter()
+            .any(|source| source.system_id == system_id && source.component_id == component_id)
+}
+
+/// The accept list that keeps only `system_id`: every component of it among
+/// the `heard` (system_id, component_id) pairs.
+pub fn system_sources(system_id: u8, heard: impl IntoIterator<Item = (u8, u8)>) -> Vec<LinkSource> {
+    heard
+        .into_iter()
+        .filter(|&(system, _)| system == system_id)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|(system_id, component_id)| LinkSource {
+            system_id,
+            component_id,
+        })
+        .collect()
+}
+
+/// Message counts per (system_id, component_id) seen on the link.
+#[derive(Debug, Clone, Default)]
+pub struct LinkSourceRegistry {
+    counts: BTreeMap<(u8, u8), u64>,
+}
+
+impl LinkSourceRegistry {
+    pub fn observe(&mut self, system_id: u8, component_id: u8) {
+        *self.counts.entry((system_id, component_id)).or_default() += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+
+    pub fn entries(&self) -> Vec<LinkSourceEntry> {
+        self.counts
+            .iter()
+            .map(|(&(system_id, component_id), &messages)| LinkSourceEntry {
+                system_id,
+                component_id,
+                messages,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accept_list_passes_radio_status_from_any_source() {
+        let accept = [LinkSource {
+            system_id: 1,
+            component_id: 1,
+        }];
+
+        assert!(source_allowed(&accept, 1, 1, 0));
+        assert!(!source_allowed(&accept, 255, 190, 0));
+        assert!(!source_allowed(&accept, 1, 154, 0));
+        assert!(source_allowed(&accept, 51, 68, RADIO_STATUS_MESSAGE_ID));
+        assert!(source_allowed(&[], 255, 190, 0));
+    }
+
+    #[test]
+    fn system_sources_keep_every_component_of_one_system() {
+        let heard = [(2, 1), (1, 1), (2, 154), (2, 1), (51, 68)];
+
+        assert_eq!(
+            system_sources(2, heard),
+            [
+                LinkSource {
+                    system_id: 2,
+                    component_id: 1,
+                },
+                LinkSource {
+                    system_id: 2,
+                    component_id: 154,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn registry_counts_messages_per_source() {
+        let mut registry = LinkSourceRegistry::default();
+        registry.observe(1, 1);
+        registry.observe(51, 68);
+        registry.observe(1, 1);
+
+        assert_eq!(
+            registry.entries(),
+            [
+                LinkSourceEntry {
+                    system_id: 1,
+                    component_id: 1,
+                    messages: 2,
+                },
+                LinkSourceEntry {
+                    system_id: 51,
+                    component_id: 68,
+                    messages: 1,
+                },
+            ]
+        );
+        registry.clear();
+        assert!(registry.entries().is_empty());
+    }
+}
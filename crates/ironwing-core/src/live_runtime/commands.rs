@@ -1,19 +1,24 @@
 use std::fmt;
 
-use mavkit::dialect::MavCmd;
 use mavkit::{
     FencePlan, FlightMode, GeoPoint2d, GeoPoint3dMsl, GuidedSpecific, HomePosition, MissionIssue,
-    MissionPlan, ParamStore, ParamWriteResult, RallyPlan, RcOverride, format_param_file,
-    parse_param_file, validate_plan,
+    MissionPlan, ParamStore, ParamType, ParamWriteResult, RallyPlan, RcOverride, validate_plan,
 };
 
-use crate::ipc::{GuidedLiveContext, MissionDownload, RcOverrideChannelWire};
+use crate::dialect::MavCmd;
+use crate::goto;
+use crate::ipc::{
+    AppError, GotoMethod, GotoRequest, GotoResult, GuidedLiveContext, LinkProfile, MissionDownload,
+    ParamExportFormat, ParamExportOptions, ParamMetadata, ParamSearchOptions, ParsedParamFile,
+    RcOverrideChannelWire,
+};
+use crate::param_value::{ParamEncoding, ParamValue};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LiveCommandError {
     InvalidInput(String),
     Unavailable(String),
-    Vehicle(String),
+    Vehicle(AppError),
 }
 
 pub type LiveCommandResult<T> = Result<T, LiveCommandError>;
@@ -27,17 +32,16 @@ impl LiveCommandError {
         Self::Unavailable(message.into())
     }
 
-    fn vehicle(error: impl fmt::Display) -> Self {
-        Self::Vehicle(error.to_string())
+    fn vehicle(error: impl Into<AppError>) -> Self {
+        Self::Vehicle(error.into())
     }
 }
 
 impl fmt::Display for LiveCommandError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::InvalidInput(message) | Self::Unavailable(message) | Self::Vehicle(message) => {
-                f.write_str(message)
-            }
+            Self::InvalidInput(message) | Self::Unavailable(message) => f.write_str(message),
+            Self::Vehicle(error) => f.write_str(error.message()),
         }
     }
 }
@@ -149,6 +153,55 @@ pub async fn guided_goto(
         .map_err(LiveCommandError::vehicle)
 }
 
+/// [`guided_goto`] with a ground speed, arrival heading, acceptance radius and
+/// altitude frame. Planes are sent MAV_CMD_DO_REPOSITION and everything else a
+/// position target; options the chosen message cannot carry are reported back
+/// rather than failing the goto.
+pub async fn guided_goto_with_options(
+    vehicle: &mavkit::Vehicle,
+    request: &GotoRequest,
+) -> LiveCommandResult<GotoResult> {
+    goto::validate_goto(request).map_err(LiveCommandError::vehicle)?;
+    let identity = vehicle.identity();
+    let method = goto::goto_method(identity.vehicle_type);
+    let session = vehicle
+        .ardupilot()
+        .guided()
+        .await
+        .map_err(LiveCommandError::vehicle)?;
+
+    let command_result = async {
+        if let Some(ground_speed_ms) = request.options.ground_speed_ms {
+            vehicle
+                .raw()
+                .command_long(
+                    MavCmd::MAV_CMD_DO_CHANGE_SPEED as u16,
+                    goto::change_speed_params(ground_speed_ms),
+                )
+                .await?;
+        }
+        let message = match method {
+            GotoMethod::PositionTarget => {
+                goto::position_target_message(identity.system_id, identity.component_id, request)
+            }
+            GotoMethod::Reposition => {
+                goto::reposition_message(identity.system_id, identity.component_id, request)
+            }
+        };
+        vehicle.raw().send(message).await
+    }
+    .await;
+    let close_result = session.close().await;
+
+    command_result
+        .and(close_result)
+        .map_err(LiveCommandError::vehicle)?;
+    Ok(GotoResult {
+        method,
+        ignored_options: goto::ignored_options(method, &request.options),
+    })
+}
+
 pub fn mission_validate(plan: &MissionPlan) -> Vec<MissionIssue> {
     validate_plan(plan)
 }
@@ -237,46 +290,207 @@ pub async fn rally_clear(vehicle: &mavkit::Vehicle) -> LiveCommandResult<()> {
     op.wait().await.map_err(LiveCommandError::vehicle)
 }
 
+/// How the connected autopilot packs integer parameters into floats.
+pub fn param_encoding(vehicle: &mavkit::Vehicle) -> ParamEncoding {
+    ParamEncoding::for_autopilot(vehicle.identity().autopilot)
+}
+
+/// Encode `value` for PARAM_SET in the type the vehicle declared for `name`.
+/// Parameters missing from the downloaded store are sent as floats.
+pub fn encode_param_write(
+    vehicle: &mavkit::Vehicle,
+    name: &str,
+    value: f64,
+) -> LiveCommandResult<f32> {
+    let declared = vehicle
+        .params()
+        .latest()
+        .and_then(|state| state.store)
+        .and_then(|store| store.params.get(name).map(|param| param.param_type))
+        .unwrap_or(ParamType::Real32);
+    ParamValue::from_number(value, declared)
+        .and_then(|typed| typed.encode(param_encoding(vehicle)))
+        .map_err(|error| LiveCommandError::invalid_input(format!("{name}: {error}")))
+}
+
+/// Turn the wire values in `result` back into numbers, undoing
+/// [`encode_param_write`].
+pub fn decode_param_write_result(
+    vehicle: &mavkit::Vehicle,
+    mut result: ParamWriteResult,
+) -> ParamWriteResult {
+    let encoding = param_encoding(vehicle);
+    let declared = vehicle
+        .params()
+        .latest()
+        .and_then(|state| state.store)
+        .and_then(|store| store.params.get(&result.name).map(|param| param.param_type));
+    if let Some(declared) = declared {
+        result = decode_typed_write_result(result, declared, encoding);
+    }
+    result
+}
+
+fn decode_typed_write_result(
+    mut result: ParamWriteResult,
+    param_type: ParamType,
+    encoding: ParamEncoding,
+) -> ParamWriteResult {
+    let decode = |wire| ParamValue::decode(wire, param_type, encoding).as_f64() as f32;
+    result.requested_value = decode(result.requested_value);
+    result.confirmed_value = decode(result.confirmed_value);
+    result
+}
+
 pub async fn param_write(
     vehicle: &mavkit::Vehicle,
     name: &str,
-    value: f32,
+    value: f64,
 ) -> LiveCommandResult<ParamWriteResult> {
+    let wire = encode_param_write(vehicle, name, value)?;
     vehicle
         .params()
-        .write(name, value)
+        .write(name, wire)
         .await
+        .map(|result| decode_param_write_result(vehicle, result))
+        .map_err(LiveCommandError::vehicle)
+}
+
+/// Write `value` in the type it carries rather than the one looked up for
+/// `name`, so integer math done by the caller reaches the wire unrounded.
+pub async fn param_write_value(
+    vehicle: &mavkit::Vehicle,
+    name: &str,
+    value: ParamValue,
+) -> LiveCommandResult<ParamWriteResult> {
+    let encoding = param_encoding(vehicle);
+    let wire = value
+        .encode(encoding)
+        .map_err(|error| LiveCommandError::invalid_input(format!("{name}: {error}")))?;
+    vehicle
+        .params()
+        .write(name, wire)
+        .await
+        .map(|result| decode_typed_write_result(result, value.param_type(), encoding))
         .map_err(LiveCommandError::vehicle)
 }
 
 pub async fn param_write_batch(
     vehicle: &mavkit::Vehicle,
-    params: Vec<(String, f32)>,
+    params: Vec<(String, f64)>,
 ) -> LiveCommandResult<Vec<ParamWriteResult>> {
+    let params = params
+        .into_iter()
+        .map(|(name, value)| {
+            let wire = encode_param_write(vehicle, &name, value)?;
+            Ok((name, wire))
+        })
+        .collect::<LiveCommandResult<Vec<_>>>()?;
     let handle = vehicle
         .params()
         .write_batch(params)
         .map_err(LiveCommandError::vehicle)?;
-    handle.wait().await.map_err(LiveCommandError::vehicle)
+    let results = handle.wait().await.map_err(LiveCommandError::vehicle)?;
+    Ok(results
+        .into_iter()
+        .map(|result| decode_param_write_result(vehicle, result))
+        .collect())
 }
 
+/// Ask the autopilot to save its parameters to persistent storage
+/// (MAV_CMD_PREFLIGHT_STORAGE, param1 = 1) and wait for the ACK.
+pub async fn param_commit_to_storage(vehicle: &mavkit::Vehicle) -> LiveCommandResult<()> {
+    vehicle
+        .raw()
+        .command_long(
+            MavCmd::MAV_CMD_PREFLIGHT_STORAGE as u16,
+            [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        )
+        .await
+        .map(|_| ())
+        .map_err(LiveCommandError::vehicle)
+}
+
+/// Names and values only; the first row that does not parse fails the
+/// whole file. `param_parse_file_ex` keeps going and reports every row.
 pub fn param_parse_file(
     contents: &str,
-) -> LiveCommandResult<std::collections::HashMap<String, f32>> {
-    parse_param_file(contents)
-        .map(|pairs| pairs.into_iter().collect())
-        .map_err(LiveCommandError::vehicle)
+) -> LiveCommandResult<std::collections::HashMap<String, f64>> {
+    let parsed = crate::params::parse_param_file(contents);
+    if let Some(warning) = parsed.warnings.first() {
+        return Err(LiveCommandError::invalid_input(format!(
+            "line {}: {}",
+            warning.line, warning.message
+        )));
+    }
+    Ok(parsed
+        .params
+        .into_iter()
+        .map(|entry| (entry.name, entry.value))
+        .collect())
+}
+
+pub fn param_parse_file_ex(contents: &str) -> ParsedParamFile {
+    crate::params::parse_param_file(contents)
 }
 
-pub fn param_format_file(store: &ParamStore) -> String {
-    format_param_file(store)
+/// Format a store the frontend holds, whose values are already numbers
+/// (see [`crate::param_value::display_param_store`]).
+pub fn param_format_file(store: &ParamStore, format: ParamExportFormat) -> String {
+    crate::params::format_param_file(store, format, crate::param_value::DISPLAY_ENCODING)
 }
 
+pub fn param_get_all(vehicle: &mavkit::Vehicle) -> LiveCommandResult<ParamStore> {
+    vehicle
+        .params()
+        .latest()
+        .and_then(|state| state.store)
+        .ok_or_else(|| LiveCommandError::unavailable("parameters not downloaded"))
+}
+
+pub fn param_search(
+    vehicle: &mavkit::Vehicle,
+    query: &str,
+    options: &ParamSearchOptions,
+    metadata: &std::collections::HashMap<String, ParamMetadata>,
+) -> LiveCommandResult<Vec<mavkit::Param>> {
+    let store = param_get_all(vehicle)?;
+    let encoding = param_encoding(vehicle);
+    let mut found = crate::params::search_params(&store, query, options, metadata, encoding);
+    for param in &mut found {
+        param.value = crate::param_value::display_value(param, encoding);
+    }
+    Ok(found)
+}
+
+pub fn param_export(
+    vehicle: &mavkit::Vehicle,
+    options: &ParamExportOptions,
+) -> LiveCommandResult<String> {
+    let store = param_get_all(vehicle)?;
+    let identity = vehicle.identity();
+    Ok(crate::params::export_params(
+        &store,
+        options,
+        identity.system_id,
+        identity.component_id,
+        param_encoding(vehicle),
+    ))
+}
+
+/// Simple accelerometer calibration with the vehicle sitting level
+/// (MAV_CMD_PREFLIGHT_CALIBRATION, param5 = 4). The full six-position
+/// calibration needs the operator to confirm each pose and is not what a
+/// one-shot command can drive.
 pub async fn calibrate_accel(vehicle: &mavkit::Vehicle) -> LiveCommandResult<()> {
     vehicle
-        .ardupilot()
-        .preflight_calibration(false, true, false, false)
+        .raw()
+        .command_long(
+            MavCmd::MAV_CMD_PREFLIGHT_CALIBRATION as u16,
+            [0.0, 0.0, 0.0, 0.0, 4.0, 0.0, 0.0],
+        )
         .await
+        .map(|_| ())
         .map_err(LiveCommandError::vehicle)
 }
 
@@ -399,6 +613,19 @@ pub fn message_rate_interval_usec(rate_hz: f32) -> LiveCommandResult<i32> {
     Ok((1_000_000.0 / rate_hz) as i32)
 }
 
+/// Send one MAVLink message on the vehicle's link without any protocol
+/// handling; callers correlate replies through `vehicle.raw().subscribe()`.
+pub async fn send_raw_message(
+    vehicle: &mavkit::Vehicle,
+    message: crate::dialect::MavMessage,
+) -> LiveCommandResult<()> {
+    vehicle
+        .raw()
+        .send(message)
+        .await
+        .map_err(LiveCommandError::vehicle)
+}
+
 pub async fn set_message_rate(
     vehicle: &mavkit::Vehicle,
     message_id: u32,
@@ -412,6 +639,39 @@ pub async fn set_message_rate(
         .map_err(LiveCommandError::vehicle)
 }
 
+/// Switch the autopilot between normal streaming and HIGH_LATENCY2 reports.
+///
+/// Leaving high-latency mode re-requests the default telemetry streams, since
+/// the autopilot does not restore the previous message intervals by itself.
+pub async fn set_link_profile(
+    vehicle: &mavkit::Vehicle,
+    profile: LinkProfile,
+) -> LiveCommandResult<()> {
+    let enable = match profile {
+        LinkProfile::Normal => 0.0,
+        LinkProfile::HighLatency => 1.0,
+    };
+    vehicle
+        .raw()
+        .command_long(
+            MavCmd::MAV_CMD_CONTROL_HIGH_LATENCY as u16,
+            [enable, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        )
+        .await
+        .map_err(LiveCommandError::vehicle)?;
+
+    if profile == LinkProfile::Normal {
+        for request in crate::telemetry::DEFAULT_TELEMETRY_STREAM_REQUESTS {
+            vehicle
+                .raw()
+                .set_message_interval(request.message_id, request.interval_usec)
+                .await
+                .map_err(LiveCommandError::vehicle)?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -431,4 +691,16 @@ mod tests {
             Err(LiveCommandError::InvalidInput(_))
         ));
     }
+
+    #[test]
+    fn strict_param_parse_fails_on_the_first_bad_row() {
+        let params = param_parse_file("RTL_ALT,1500 # cm\r\n").expect("valid file");
+        assert_eq!(params.get("RTL_ALT"), Some(&1500.0));
+        assert_eq!(
+            param_parse_file("RTL_ALT,1500\nRTL_ALT,abc\nA B C\n")
+                .unwrap_err()
+                .to_string(),
+            "line 2: invalid value \"abc\""
+        );
+    }
 }
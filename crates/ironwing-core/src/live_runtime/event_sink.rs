@@ -5,6 +5,24 @@ pub trait EventSink: Clone + 'static {
     fn emit<T>(&self, event: &'static str, payload: &T)
     where
         T: serde::Serialize + Clone + Send + 'static;
+
+    /// Emit to the host frontend only. Telemetry deltas go this way: they are
+    /// encoded against what that frontend last received, so no other
+    /// subscriber could decode them.
+    fn emit_to_frontend<T>(&self, event: &'static str, payload: &T)
+    where
+        T: serde::Serialize + Clone + Send + 'static,
+    {
+        self.emit(event, payload);
+    }
+
+    /// Emit to every subscriber except the host frontend, such as the remote
+    /// UI bridge or the local API. Sinks without other subscribers drop it.
+    fn emit_to_subscribers<T>(&self, _event: &'static str, _payload: &T)
+    where
+        T: serde::Serialize + Clone + Send + 'static,
+    {
+    }
 }
 
 impl EventSink for NoopEventSink {
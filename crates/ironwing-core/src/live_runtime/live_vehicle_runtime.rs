@@ -1,25 +1,67 @@
 use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
 use std::future::Future;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use mavkit::ardupilot::{MagCalProgress, MagCalReport};
+use mavkit::mission::MissionState;
 use mavkit::{
-    FirmwareInfo, HomePosition, ObservationSubscription, ParamStore, SensorHealthSummary, Vehicle,
+    FirmwareInfo, HomePosition, ObservationSubscription, Param, ParamOperationKind, ParamState,
+    ParamStore, SensorHealthSummary, Vehicle,
 };
 use web_time::Instant;
 
+use crate::alerts::AlertEngine;
+use crate::announcements::Announcer;
+use crate::battery_cells::{BatteryCellTracker, cell_count_from_params};
+use crate::checklist::{ChecklistInputs, ChecklistTracker, prearm_pass};
+use crate::derived_telemetry::DerivedTelemetryEstimator;
+use crate::diagnostics_bundle::LinkQualityHistory;
 use crate::event_names;
+use crate::event_throttle::EventThrottle;
+use crate::failsafe::{FailsafeLog, decode_failsafe_status_text};
+use crate::fence_proximity::{fence_boundaries, ground_velocity, nearest_fence};
+use crate::flight_modes::is_ground_vehicle;
+use crate::flight_summary::FlightSummaryTracker;
+use crate::flight_track::{FlightTrack, TrackBatch};
+use crate::gps_fix::{GpsFixTracker, GpsRawReport};
+use crate::health::{EkfStatus, HealthInputs, health_report};
+use crate::heartbeats::{HeartbeatObservation, HeartbeatRegistry};
+use crate::high_latency::HighLatencyReport;
+use crate::ipc::alerts::{AlertRule, AlertTriggered};
+use crate::ipc::announcements::{Announcement, AnnouncementSettings};
+use crate::ipc::battery::BatteryStats;
 use crate::ipc::calibration::CalibrationSnapshot;
+use crate::ipc::checklist::{ChecklistConfig, ChecklistState};
+use crate::ipc::failsafe::FailsafeEvent;
+use crate::ipc::fence_proximity::FenceProximity;
+use crate::ipc::flight_summary::FlightSessionSummary;
+use crate::ipc::gps::{GpsFixChanged, GpsStatus};
+use crate::ipc::health::{HealthReport, HealthThresholds};
+use crate::ipc::named_values::NamedValue;
+use crate::ipc::params::ParamExternalChange;
+use crate::ipc::precision_landing::{PrecisionLandingStatus, PrecisionLandingTarget};
+use crate::ipc::rangefinder::{LandingAssistProfile, LandingAssistWarning, RangefinderReading};
+use crate::ipc::telemetry::{RcLinkQuality, TelemetryState};
+use crate::ipc::time_sync::TimeSyncStatus;
+use crate::ipc::track::TrackPoint;
+use crate::ipc::units::UnitSystem;
+use crate::ipc::video::VideoStream;
+use crate::ipc::vtol::VtolStatus;
 use crate::ipc::{
-    AckSessionSnapshotResult, CalibrationSources, DomainProvenance, DomainValue,
-    OpenSessionSnapshot, ScopedEvent, SessionConnection, SessionEnvelope, SessionSnapshot,
-    SourceKind, StatusTextEntry, TelemetrySnapshot, calibration_snapshot_from_sources,
+    AckSessionSnapshotResult, CalibrationSources, ComponentListEntry, DomainProvenance,
+    DomainValue, EventBridgeStats, LinkConnectPhase, LinkFrameStats, LinkProfile,
+    LinkQualitySample, LinkSourceEntry, OpenSessionSnapshot, ParamMetadata, ParamStoreDelta,
+    ScopedEvent, SessionConnection, SessionEnvelope, SessionSnapshot, SourceKind, StatusTextEntry,
+    TelemetryEncoding, TelemetrySnapshot, VehicleListEntry, calibration_snapshot_from_sources,
     push_status_text_entry, sensor_health_snapshot_from_summary,
     session_connection_from_link_state, status_text_entry_from_value,
     status_text_snapshot_from_entries, support_snapshot,
 };
+use crate::link_loss::gcs_failsafe_behavior;
+use crate::link_sources::LinkSourceRegistry;
 use crate::live::{
     LiveSnapshotInput, SessionContext, base_live_snapshot_from_caches,
     session_snapshot_from_context,
@@ -28,11 +70,24 @@ use crate::live_runtime::event_sink::EventSink;
 use crate::live_runtime::task_set::{
     LocalTaskSpawner, LocalTimer, SendTaskSpawner, SendTimer, TelemetryIntervalProvider,
 };
+use crate::param_attribution::{ParamChangeAttribution, store_complete};
+use crate::param_value::{ParamEncoding, display_param_store};
+use crate::precision_landing::PrecisionLandingTracker;
+use crate::rangefinder::RangefinderTracker;
+use crate::rc_link_quality::rc_link_alert_source;
 use crate::runtime::SessionRuntime;
+use crate::telemetry::DEFAULT_TELEMETRY_STALE_AFTER_MS;
+use crate::telemetry_delta::{TelemetryDeltaEncoder, TelemetryFrame};
+use crate::time_sync::{TimeSyncEstimator, VehicleClock};
+use crate::units::display_values;
 use crate::vehicle_snapshot::{
-    mav_severity_name, seeded_vehicle_state, telemetry_snapshot_from_vehicle,
+    high_latency_freshness, mav_severity_name, seeded_vehicle_state,
+    telemetry_snapshot_from_vehicle, unix_epoch_usec,
 };
 
+const PARAM_DELTA_INTERVAL: Duration = Duration::from_millis(200);
+const EVENT_THROTTLE_FLUSH_INTERVAL: Duration = Duration::from_millis(20);
+
 pub struct LiveVehicleRuntime<E>
 where
     E: EventSink,
@@ -43,6 +98,42 @@ where
     live_telemetry: TelemetrySnapshot,
     status_text_history: Vec<StatusTextEntry>,
     next_status_text_sequence: u64,
+    param_generation: u64,
+    last_emitted_params: Option<HashMap<String, Param>>,
+    pending_param_store: Option<ParamStore>,
+    param_download_active: bool,
+    param_attribution: ParamChangeAttribution,
+    time_sync: TimeSyncEstimator,
+    rangefinders: RangefinderTracker,
+    precision_landing: PrecisionLandingTracker,
+    ekf_status: Option<EkfStatus>,
+    /// GLOBAL_POSITION_INT ground velocity, (east, north) in m/s.
+    ground_velocity: Option<(f64, f64)>,
+    link_drop_pct: Option<f64>,
+    /// Outlives disconnects, which are what it is there to explain.
+    link_quality_history: LinkQualityHistory,
+    vtol_status: Option<VtolStatus>,
+    param_metadata: HashMap<String, ParamMetadata>,
+    heartbeats: HeartbeatRegistry,
+    link_sources: LinkSourceRegistry,
+    link_profile: LinkProfile,
+    high_latency_telemetry: Option<(TelemetryState, Instant)>,
+    telemetry_stale_after: Duration,
+    event_throttle: EventThrottle,
+    alerts: AlertEngine,
+    announcer: Announcer,
+    flight_tracker: Option<FlightSummaryTracker>,
+    flight_summaries: Vec<FlightSessionSummary>,
+    flight_track: FlightTrack,
+    derived_telemetry: DerivedTelemetryEstimator,
+    unit_system: UnitSystem,
+    named_values: BTreeMap<String, NamedValue>,
+    video_streams: Vec<VideoStream>,
+    gps_fix: GpsFixTracker,
+    battery_cells: BatteryCellTracker,
+    failsafes: FailsafeLog,
+    telemetry_encoder: TelemetryDeltaEncoder,
+    checklist: ChecklistTracker,
     vehicle: Option<Vehicle>,
 }
 
@@ -58,6 +149,40 @@ where
             live_telemetry: TelemetrySnapshot::missing(DomainProvenance::Bootstrap),
             status_text_history: Vec::new(),
             next_status_text_sequence: 1,
+            param_generation: 0,
+            last_emitted_params: None,
+            pending_param_store: None,
+            param_download_active: false,
+            param_attribution: ParamChangeAttribution::default(),
+            time_sync: TimeSyncEstimator::default(),
+            rangefinders: RangefinderTracker::default(),
+            precision_landing: PrecisionLandingTracker::default(),
+            ekf_status: None,
+            ground_velocity: None,
+            link_drop_pct: None,
+            link_quality_history: LinkQualityHistory::default(),
+            vtol_status: None,
+            param_metadata: HashMap::new(),
+            heartbeats: HeartbeatRegistry::default(),
+            link_sources: LinkSourceRegistry::default(),
+            link_profile: LinkProfile::Normal,
+            high_latency_telemetry: None,
+            telemetry_stale_after: Duration::from_millis(u64::from(DEFAULT_TELEMETRY_STALE_AFTER_MS)),
+            event_throttle: EventThrottle::default(),
+            alerts: AlertEngine::default(),
+            announcer: Announcer::default(),
+            flight_tracker: None,
+            flight_summaries: Vec::new(),
+            flight_track: FlightTrack::default(),
+            derived_telemetry: DerivedTelemetryEstimator::default(),
+            unit_system: UnitSystem::default(),
+            named_values: BTreeMap::new(),
+            video_streams: Vec::new(),
+            gps_fix: GpsFixTracker::default(),
+            battery_cells: BatteryCellTracker::default(),
+            failsafes: FailsafeLog::default(),
+            telemetry_encoder: TelemetryDeltaEncoder::default(),
+            checklist: ChecklistTracker::default(),
             vehicle: None,
         }
     }
@@ -86,6 +211,16 @@ where
         &self.status_text_history
     }
 
+    /// How the connected vehicle carries integer parameters; CCast before
+    /// one is connected.
+    fn param_encoding(&self) -> ParamEncoding {
+        self.vehicle
+            .as_ref()
+            .map_or(ParamEncoding::CCast, |vehicle| {
+                ParamEncoding::for_autopilot(vehicle.identity().autopilot)
+            })
+    }
+
     pub fn vehicle(&self) -> Option<Vehicle> {
         self.vehicle.clone()
     }
@@ -106,12 +241,45 @@ where
         self.live_telemetry = TelemetrySnapshot::missing(DomainProvenance::Bootstrap);
         self.status_text_history.clear();
         self.next_status_text_sequence = 1;
+        self.param_generation = 0;
+        self.last_emitted_params = None;
+        self.pending_param_store = None;
+        self.param_download_active = false;
+        self.param_attribution = ParamChangeAttribution::default();
+        self.time_sync = TimeSyncEstimator::default();
+        self.rangefinders = RangefinderTracker::default();
+        self.precision_landing = PrecisionLandingTracker::default();
+        self.ekf_status = None;
+        self.ground_velocity = None;
+        self.link_drop_pct = None;
+        self.vtol_status = None;
+        self.heartbeats.clear();
+        self.link_sources.clear();
+        self.link_profile = LinkProfile::Normal;
+        self.high_latency_telemetry = None;
+        self.event_throttle.clear_pending();
+        self.alerts.reset();
+        self.announcer.reset();
+        self.flight_tracker = None;
+        self.derived_telemetry.reset();
+        self.named_values.clear();
+        self.video_streams.clear();
+        self.gps_fix = GpsFixTracker::start(Instant::now());
+        self.battery_cells = BatteryCellTracker::default();
+        self.failsafes = FailsafeLog::default();
+        self.telemetry_encoder.request_keyframe();
         self.vehicle = None;
     }
 
     pub fn prepare_connecting(&mut self) {
         self.reset_live_state();
-        self.session_context.connection = SessionConnection::Connecting;
+        self.session_context.connection = SessionConnection::Connecting { phase: None };
+    }
+
+    /// Record how far a native connect attempt got, so the session tells
+    /// "opening the link" apart from "waiting for a heartbeat".
+    pub fn set_connect_phase(&mut self, phase: LinkConnectPhase) {
+        self.session_context.connection = SessionConnection::Connecting { phase: Some(phase) };
     }
 
     pub fn set_connection_error(&mut self, error: impl Into<String>) {
@@ -127,14 +295,358 @@ where
         self.vehicle = Some(vehicle.clone());
     }
 
+    /// Apply the current value of each vehicle watch, so the first session
+    /// emission after connect carries them instead of waiting for a change.
+    pub fn seed_current_observations(&mut self, vehicle: &Vehicle) {
+        if let Some(link_state) = vehicle.link().state().latest() {
+            self.update_link_state(&link_state);
+        }
+        if let Some(sample) = vehicle.telemetry().armed().latest() {
+            self.update_armed(sample.value);
+        }
+        if let Some(mode) = vehicle.available_modes().current().latest() {
+            self.update_current_mode(mode.custom_mode, &mode.name);
+        }
+        if let Some(firmware) = vehicle.info().firmware().latest() {
+            self.update_firmware_version(firmware.version);
+        }
+        if let Some(sample) = vehicle.telemetry().home().latest() {
+            self.update_home_position(HomePosition {
+                latitude_deg: sample.value.latitude_deg,
+                longitude_deg: sample.value.longitude_deg,
+                altitude_m: sample.value.altitude_msl_m,
+            });
+        }
+    }
+
+    /// Telemetry read straight from the vehicle's watches (or the latest
+    /// HIGH_LATENCY2 report), independent of the poll bridge's interval.
+    pub fn current_telemetry(&self, vehicle: &Vehicle) -> TelemetrySnapshot {
+        let mut telemetry = match self.high_latency_telemetry.clone() {
+            Some((mut state, received_at)) => {
+                state.freshness = high_latency_freshness(received_at.elapsed());
+                TelemetrySnapshot::present(state, DomainProvenance::Stream)
+            }
+            None => telemetry_snapshot_from_vehicle(
+                vehicle,
+                DomainProvenance::Stream,
+                self.telemetry_stale_after,
+            ),
+        };
+        if let Some(state) = telemetry.value.as_mut() {
+            let vehicle_type = vehicle.identity().vehicle_type;
+            state.derived = self.derived_telemetry.derive(
+                state,
+                self.session_context.home_position.as_ref(),
+                vehicle_type,
+                Instant::now(),
+            );
+            // A rover's barometric climb rate is drift, not motion.
+            if is_ground_vehicle(vehicle_type) {
+                state.flight.climb_rate_mps = None;
+            }
+        }
+        telemetry
+    }
+
+    /// Apply a WIND_COV report; it takes precedence over the estimated wind
+    /// while fresh.
+    pub fn record_reported_wind(&mut self, north_mps: f64, east_mps: f64) {
+        self.derived_telemetry
+            .record_reported_wind(north_mps, east_mps, Instant::now());
+    }
+
+    /// Record an RC link report decoded from RC_CHANNELS, RADIO_STATUS or the
+    /// receiver link messages.
+    pub fn record_rc_link(&mut self, quality: RcLinkQuality) {
+        self.derived_telemetry
+            .record_rc_link(quality, Instant::now());
+    }
+
+    /// Record a WHEEL_DISTANCE report for the odometer.
+    pub fn record_wheel_distance(&mut self, time_usec: u64, distances: &[f64]) {
+        self.derived_telemetry
+            .record_wheel_distance(time_usec, distances);
+    }
+
+    pub fn rc_link_quality(&self) -> Option<RcLinkQuality> {
+        self.derived_telemetry.rc_link(Instant::now())
+    }
+
+    /// Record a DISTANCE_SENSOR reading. A downward one feeds the derived
+    /// height above ground and the landing-assist check against `profile`.
+    pub fn record_rangefinder(
+        &mut self,
+        reading: RangefinderReading,
+        profile: &LandingAssistProfile,
+    ) -> Option<LandingAssistWarning> {
+        self.rangefinders.observe(reading);
+        if let Some(height_m) = self.rangefinders.downward_height_m() {
+            self.derived_telemetry
+                .record_rangefinder_height(height_m, Instant::now());
+        }
+        let climb_rate_mps = self
+            .live_telemetry
+            .value
+            .as_ref()
+            .and_then(|state| state.flight.climb_rate_mps)?;
+        self.rangefinders.check_landing(climb_rate_mps, profile)
+    }
+
+    pub fn record_landing_target(&mut self, target: PrecisionLandingTarget) {
+        self.precision_landing.observe(target, Instant::now());
+    }
+
+    /// Whether a valid landing target arrived within `window`.
+    pub fn precision_landing_status(&self, window: Duration) -> PrecisionLandingStatus {
+        self.precision_landing.status(window, Instant::now())
+    }
+
+    pub fn observe_ekf_status(&mut self, ekf: EkfStatus) {
+        self.ekf_status = Some(ekf);
+    }
+
+    /// GLOBAL_POSITION_INT vx/vy, for the fence proximity check.
+    pub fn observe_ground_velocity(&mut self, vx_cm_s: i16, vy_cm_s: i16) {
+        self.ground_velocity = Some(ground_velocity(vx_cm_s, vy_cm_s));
+    }
+
+    /// SYS_STATUS drop_rate_comm, converted to a percentage.
+    pub fn observe_comm_drop_rate(&mut self, drop_rate_comm: u16) {
+        self.link_drop_pct = Some(f64::from(drop_rate_comm) / 100.0);
+    }
+
+    /// Add this second's link sample to the history kept for bug reports.
+    pub fn sample_link_quality(
+        &mut self,
+        unix_msec: u64,
+        connected: bool,
+        frame_stats: Option<LinkFrameStats>,
+    ) {
+        let sample = LinkQualitySample {
+            unix_msec,
+            connected,
+            drop_pct: self.link_drop_pct,
+            rc_link: self.rc_link_quality(),
+            frame_stats,
+        };
+        self.link_quality_history.record(sample);
+    }
+
+    pub fn link_quality_history(&self) -> Vec<LinkQualitySample> {
+        self.link_quality_history.samples()
+    }
+
+    /// Record EXTENDED_SYS_STATE; true when the VTOL or landed state changed.
+    pub fn observe_vtol_status(&mut self, status: VtolStatus) -> bool {
+        self.vtol_status.replace(status) != Some(status)
+    }
+
+    pub fn vtol_status(&self) -> Option<VtolStatus> {
+        self.vtol_status
+    }
+
+    /// One graded entry per health component the vehicle has reported on.
+    pub fn vehicle_health(&self, thresholds: &HealthThresholds) -> HealthReport {
+        let Some(vehicle) = self.vehicle.as_ref() else {
+            return health_report(&HealthInputs::default(), thresholds);
+        };
+        let identity = vehicle.identity();
+        let heartbeat_age_ms = self
+            .heartbeats
+            .vehicles(Instant::now(), None)
+            .into_iter()
+            .find(|entry| entry.system_id == identity.system_id)
+            .map(|entry| entry.last_seen_ms_ago);
+        let failsafe = self.last_emitted_params.as_ref().map(|params| {
+            gcs_failsafe_behavior(identity.vehicle_type, |name| {
+                params.get(name).map(|param| param.value)
+            })
+        });
+        let inputs = HealthInputs {
+            sensors: vehicle
+                .telemetry()
+                .sensor_health()
+                .latest()
+                .map(|sample| sample.value),
+            ekf: self.ekf_status,
+            gps_fix: self
+                .gps_fix
+                .status()
+                .instances
+                .iter()
+                .map(|instance| instance.fix)
+                .max(),
+            battery_pct: self
+                .live_telemetry
+                .value
+                .as_ref()
+                .and_then(|state| state.power.battery_pct),
+            link_drop_pct: self.link_drop_pct,
+            failsafe,
+            heartbeat_age_ms,
+            vtol: self.vtol_status,
+        };
+        health_report(&inputs, thresholds)
+    }
+
+    /// The fence boundary nearest to being breached, from the vehicle's
+    /// cached fence plan, its FENCE_* parameters and home.
+    pub fn fence_proximity(&self) -> Option<FenceProximity> {
+        let vehicle = self.vehicle.as_ref()?;
+        let state = self.live_telemetry.value.as_ref()?;
+        let position = (
+            state.navigation.latitude_deg?,
+            state.navigation.longitude_deg?,
+        );
+        // Without a velocity nothing is predicted to breach, so only the
+        // distance is reported.
+        let velocity = self.ground_velocity.unwrap_or((0.0, 0.0));
+        let plan = vehicle.fence().latest().and_then(|fence| fence.plan);
+        let home = self
+            .session_context
+            .home_position
+            .as_ref()
+            .map(|home| (home.latitude_deg, home.longitude_deg));
+        let boundaries = fence_boundaries(
+            plan.as_ref(),
+            |name| {
+                self.last_emitted_params
+                    .as_ref()?
+                    .get(name)
+                    .map(|param| param.value)
+            },
+            home,
+        );
+        nearest_fence(&boundaries, position, velocity)
+    }
+
+    /// Latest reading per rangefinder orientation.
+    pub fn rangefinder_readings(&self) -> Vec<RangefinderReading> {
+        self.rangefinders.readings()
+    }
+
+    /// Latest value per sender and name, ordered by name.
+    pub fn named_values(&self) -> Vec<NamedValue> {
+        self.named_values.values().cloned().collect()
+    }
+
+    pub fn record_named_value(&mut self, value: NamedValue) {
+        let key = format!("{}:{}:{}", value.name, value.system_id, value.component_id);
+        self.named_values.insert(key, value);
+    }
+
+    /// Video streams advertised this session, ordered by component and
+    /// stream id.
+    pub fn video_streams(&self) -> &[VideoStream] {
+        &self.video_streams
+    }
+
+    /// Returns whether the list changed.
+    pub fn record_video_stream(&mut self, stream: VideoStream) -> bool {
+        crate::video_streams::upsert_video_stream(&mut self.video_streams, stream)
+    }
+
+    pub fn gps_status(&self) -> GpsStatus {
+        self.gps_fix.status()
+    }
+
+    /// Returns the fix transition, if the report changed the fix type.
+    pub fn observe_gps_raw(&mut self, report: GpsRawReport) -> Option<GpsFixChanged> {
+        self.gps_fix.observe(report, Instant::now())
+    }
+
+    pub fn observe_gps_rtk(&mut self, instance: u8, time_last_baseline_ms: u32) {
+        self.gps_fix.observe_rtk(instance, time_last_baseline_ms);
+    }
+
+    /// Take SYSTEM_TIME: the boot clock for RTK ages and, once the vehicle
+    /// has UTC, the UTC mapping for time sync.
+    pub fn observe_vehicle_time(&mut self, time_boot_ms: u32, time_unix_usec: u64) {
+        self.gps_fix.observe_vehicle_time(time_boot_ms);
+        self.time_sync
+            .observe_system_time(time_unix_usec, time_boot_ms);
+    }
+
+    pub fn note_timesync_request(&mut self, ts1_ns: i64) {
+        self.time_sync.note_request(ts1_ns);
+    }
+
+    pub fn observe_timesync_reply(&mut self, tc1_ns: i64, ts1_ns: i64, now_ns: i64) -> bool {
+        self.time_sync.observe_reply(tc1_ns, ts1_ns, now_ns)
+    }
+
+    pub fn vehicle_clock(&self) -> Option<VehicleClock> {
+        self.time_sync.clock()
+    }
+
+    pub fn time_sync_status(&self) -> TimeSyncStatus {
+        self.time_sync.status()
+    }
+
     pub fn update_link_state(&mut self, link_state: &mavkit::LinkState) {
         self.session_context.connection = session_connection_from_link_state(link_state);
     }
 
-    pub fn update_armed(&mut self, armed: bool) {
+    /// Record the armed state and track the flight it brackets. Returns the
+    /// finished summary on the armed to disarmed transition.
+    pub fn update_armed(&mut self, armed: bool) -> Option<FlightSessionSummary> {
         if let Some(vehicle_state) = self.session_context.vehicle_state.as_mut() {
             vehicle_state.armed = armed;
         }
+        if armed {
+            self.flight_tracker
+                .get_or_insert_with(|| FlightSummaryTracker::start(unix_epoch_usec()));
+            return None;
+        }
+        let summary = self.flight_tracker.take()?.finish(unix_epoch_usec());
+        self.flight_summaries.push(summary.clone());
+        Some(summary)
+    }
+
+    /// Summaries of completed flights, oldest first.
+    pub fn flight_summaries(&self) -> &[FlightSessionSummary] {
+        &self.flight_summaries
+    }
+
+    /// Seed the history with summaries persisted by an earlier run.
+    pub fn load_flight_summaries(&mut self, summaries: Vec<FlightSessionSummary>) {
+        self.flight_summaries = summaries;
+    }
+
+    /// Append a position to the flight track. Ignored while disarmed so the
+    /// track only covers flights.
+    pub fn record_track_point(&mut self, point: TrackPoint) {
+        if self.flight_tracker.is_some() {
+            self.flight_track.push(point);
+        }
+    }
+
+    pub fn flight_track(&self) -> &FlightTrack {
+        &self.flight_track
+    }
+
+    pub fn clear_flight_track(&mut self) {
+        self.flight_track.clear();
+    }
+
+    pub fn set_track_max_points(&mut self, max_points: usize) {
+        self.flight_track.set_max_points(max_points);
+    }
+
+    /// How long a telemetry group may go without data before snapshots mark
+    /// it stale.
+    pub fn set_telemetry_stale_after(&mut self, stale_after: Duration) {
+        self.telemetry_stale_after = stale_after;
+    }
+
+    /// Seed the track with the one an earlier run persisted.
+    pub fn restore_flight_track(&mut self, points: Vec<TrackPoint>) {
+        self.flight_track.restore(points);
+    }
+
+    pub fn take_track_batch(&mut self) -> Option<TrackBatch> {
+        self.flight_track.take_batch()
     }
 
     pub fn update_current_mode(&mut self, custom_mode: u32, mode_name: &str) {
@@ -142,6 +654,7 @@ where
             vehicle_state.custom_mode = custom_mode;
             vehicle_state.mode_name = mode_name.to_string();
         }
+        self.announcer.observe_mode(mode_name, Instant::now());
     }
 
     pub fn update_firmware_version(&mut self, firmware_version: Option<String>) {
@@ -154,7 +667,371 @@ where
         self.session_context.home_position = Some(home_position);
     }
 
+    pub fn observe_heartbeat(&mut self, heartbeat: HeartbeatObservation) {
+        self.heartbeats.observe(heartbeat, Instant::now());
+    }
+
+    pub fn heartbeats(&self) -> &HeartbeatRegistry {
+        &self.heartbeats
+    }
+
+    pub fn observe_link_source(&mut self, system_id: u8, component_id: u8) {
+        self.link_sources.observe(system_id, component_id);
+    }
+
+    /// Every (system_id, component_id) heard on the link with message counts.
+    pub fn link_sources(&self) -> Vec<LinkSourceEntry> {
+        self.link_sources.entries()
+    }
+
+    /// Systems heard on the link, with the bound vehicle marked as selected.
+    pub fn vehicle_list(&self) -> Vec<VehicleListEntry> {
+        let selected = self
+            .vehicle
+            .as_ref()
+            .map(|vehicle| vehicle.identity().system_id);
+        self.heartbeats.vehicles(Instant::now(), selected)
+    }
+
+    /// Components of the bound system heard on the link.
+    pub fn component_list(&self) -> Vec<ComponentListEntry> {
+        let Some(vehicle) = self.vehicle.as_ref() else {
+            return Vec::new();
+        };
+        self.heartbeats
+            .components(vehicle.identity().system_id, Instant::now())
+    }
+
+    pub fn link_profile(&self) -> LinkProfile {
+        self.link_profile
+    }
+
+    pub fn set_link_profile(&mut self, profile: LinkProfile) {
+        self.link_profile = profile;
+        self.high_latency_telemetry = None;
+    }
+
+    /// Apply a decoded HIGH_LATENCY2 report. Reports are ignored on a normal
+    /// link so a stray HIGH_LATENCY2 cannot override full-rate telemetry.
+    pub fn record_high_latency_report(
+        &mut self,
+        report: HighLatencyReport,
+        mode_name: Option<&str>,
+    ) -> bool {
+        if self.link_profile != LinkProfile::HighLatency {
+            return false;
+        }
+        self.high_latency_telemetry = Some((report.telemetry, Instant::now()));
+        if let Some(mode_name) = mode_name {
+            self.update_current_mode(report.custom_mode, mode_name);
+        }
+        true
+    }
+
+    /// Replace the per-event maximum rates; events not listed emit on every
+    /// change. Rates survive reconnects.
+    pub fn set_event_rates(&mut self, rates: HashMap<String, u32>) -> Result<(), &'static str> {
+        // Coalescing would drop deltas the receiver needs; the telemetry
+        // rate setting paces them instead.
+        if rates.contains_key(event_names::TELEMETRY_DELTA) {
+            return Err("telemetry://delta cannot be rate limited; use set_telemetry_rate");
+        }
+        self.event_throttle.set_rates(rates)
+    }
+
+    pub fn telemetry_encoding(&self) -> TelemetryEncoding {
+        self.telemetry_encoder.encoding()
+    }
+
+    /// Select how telemetry ticks are sent. The next tick is a keyframe.
+    pub fn set_telemetry_encoding(&mut self, encoding: TelemetryEncoding) {
+        self.telemetry_encoder.set_encoding(encoding);
+    }
+
+    pub fn event_bridge_stats(&self) -> Vec<EventBridgeStats> {
+        self.event_throttle.stats()
+    }
+
+    pub fn alert_rules(&self) -> &[AlertRule] {
+        self.alerts.rules()
+    }
+
+    /// Replace the alert rule set. Rules survive reconnects; edge and
+    /// rate-limit state starts over.
+    pub fn set_alert_rules(&mut self, rules: Vec<AlertRule>) -> Result<(), String> {
+        self.alerts.set_rules(rules)
+    }
+
+    /// Run the alert rules against the latest telemetry and vehicle state.
+    pub fn evaluate_alerts(&mut self) -> Vec<AlertTriggered> {
+        if self.vehicle.is_none() {
+            return Vec::new();
+        }
+        let sources = serde_json::json!({
+            "telemetry": self.live_telemetry.value,
+            "vehicle": self.session_context.vehicle_state,
+            "battery": self.battery_cells.stats(),
+            "failsafe": self.failsafes.alert_source(),
+            "rc_link": rc_link_alert_source(
+                self.rc_link_quality().as_ref(),
+                self.session_context
+                    .vehicle_state
+                    .as_ref()
+                    .is_some_and(|vehicle_state| vehicle_state.armed),
+            ),
+        });
+        self.alerts.evaluate(&sources, Instant::now())
+    }
+
+    pub fn announcement_settings(&self) -> &AnnouncementSettings {
+        self.announcer.settings()
+    }
+
+    /// Replace the announcement settings. Milestones already passed stay
+    /// passed.
+    pub fn set_announcement_settings(&mut self, settings: AnnouncementSettings) {
+        self.announcer.set_settings(settings);
+    }
+
+    /// Altitude and battery milestones passed since the last tick, and a
+    /// mode change that has now held long enough to announce.
+    pub fn evaluate_announcements(&mut self) -> Vec<Announcement> {
+        if self.vehicle.is_none() {
+            return Vec::new();
+        }
+        let armed = self.is_armed();
+        let telemetry = self.live_telemetry.value.as_ref();
+        let altitude_m = telemetry.and_then(|telemetry| telemetry.flight.altitude_m);
+        let battery_pct = telemetry.and_then(|telemetry| telemetry.power.battery_pct);
+        [
+            self.announcer.observe_altitude(altitude_m, armed),
+            self.announcer.observe_battery(battery_pct),
+            self.announcer.poll_mode(Instant::now()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// "Waypoint N of M" when the mission's current item has moved on.
+    pub fn announce_mission_progress(
+        &mut self,
+        mission_state: &MissionState,
+    ) -> Option<Announcement> {
+        let item_count = mission_state
+            .plan
+            .as_ref()
+            .map_or(0, |plan| plan.items.len());
+        let armed = self.is_armed();
+        self.announcer.observe_mission(
+            mission_state.current_index.map(usize::from),
+            item_count,
+            armed,
+        )
+    }
+
+    pub fn announce_failsafe(&mut self, event: &FailsafeEvent) -> Option<Announcement> {
+        self.announcer.observe_failsafe(event, Instant::now())
+    }
+
+    fn is_armed(&self) -> bool {
+        self.session_context
+            .vehicle_state
+            .as_ref()
+            .is_some_and(|vehicle_state| vehicle_state.armed)
+    }
+
+    pub fn param_metadata(&self) -> &HashMap<String, ParamMetadata> {
+        &self.param_metadata
+    }
+
+    pub fn set_param_metadata(&mut self, metadata: HashMap<String, ParamMetadata>) {
+        self.param_metadata = metadata;
+    }
+
+    /// Buffer the latest parameter state for the next delta flush.
+    ///
+    /// Returns the complete store when a download-all operation has just
+    /// finished; that store is emitted once on `param://store` and becomes the
+    /// baseline for later deltas.
+    pub fn record_param_state(&mut self, state: ParamState) -> Option<ParamStore> {
+        let downloading = matches!(state.active_op, Some(ParamOperationKind::DownloadAll));
+        let download_finished = self.param_download_active && !downloading;
+        self.param_download_active = downloading;
+
+        let store = display_param_store(state.store?, self.param_encoding());
+        if download_finished {
+            self.pending_param_store = None;
+            self.last_emitted_params = Some(store.params.clone());
+            return Some(store);
+        }
+        self.pending_param_store = Some(store);
+        None
+    }
+
+    /// Next `param://delta` payload, or `None` when nothing changed since the
+    /// previous flush.
+    pub fn flush_param_delta(&mut self) -> Option<ParamStoreDelta> {
+        let store = self.pending_param_store.take()?;
+        let delta = crate::params::param_store_delta(
+            self.last_emitted_params.as_ref(),
+            &store,
+            self.param_generation + 1,
+        )?;
+        self.param_generation = delta.generation;
+        self.last_emitted_params = Some(store.params);
+        Some(delta)
+    }
+
+    /// Tag parameter writes this GCS is about to send, so their echoes are
+    /// not reported as external changes.
+    pub fn expect_param_writes<'a>(
+        &mut self,
+        names: impl IntoIterator<Item = &'a str>,
+        now_ms: u64,
+    ) {
+        self.param_attribution.expect_writes(names, now_ms);
+    }
+
+    /// External changes in `state` since the last call. Quiet until the
+    /// first download has completed.
+    pub fn observe_param_attribution(
+        &mut self,
+        state: &ParamState,
+        now_ms: u64,
+    ) -> Vec<ParamExternalChange> {
+        let Some(store) = state.store.as_ref() else {
+            return Vec::new();
+        };
+        let complete = !matches!(state.active_op, Some(ParamOperationKind::DownloadAll))
+            && store_complete(store);
+        self.param_attribution
+            .observe(&store.params, complete, now_ms)
+    }
+
+    pub fn param_external_changes(&self) -> Vec<ParamExternalChange> {
+        self.param_attribution.changes()
+    }
+
+    pub fn unit_system(&self) -> UnitSystem {
+        self.unit_system
+    }
+
+    /// Set the display units. The runtime keeps SI values internally; only
+    /// emitted and queried telemetry is converted.
+    pub fn set_unit_system(&mut self, unit_system: UnitSystem) {
+        self.unit_system = unit_system;
+    }
+
+    /// `telemetry` with display-unit copies of its fields attached, or
+    /// unchanged for SI. The fields themselves stay SI.
+    pub fn telemetry_in_display_units(
+        &self,
+        mut telemetry: TelemetrySnapshot,
+    ) -> TelemetrySnapshot {
+        if !self.unit_system.is_si()
+            && let Some(state) = telemetry.value.as_mut()
+        {
+            state.display = Some(display_values(state, &self.unit_system));
+        }
+        telemetry
+    }
+
+    pub fn checklist_config(&self) -> &ChecklistConfig {
+        self.checklist.config()
+    }
+
+    /// Replace the checklist definition. Like alert rules it survives
+    /// reconnects.
+    pub fn set_checklist(&mut self, config: ChecklistConfig) -> Result<(), String> {
+        self.checklist.set_config(config)
+    }
+
+    pub fn tick_checklist_item(&mut self, item_id: &str, checked: bool) -> Result<(), String> {
+        self.checklist.tick(item_id, checked)
+    }
+
+    pub fn reset_checklist(&mut self) {
+        self.checklist.reset();
+    }
+
+    fn checklist_inputs(&self) -> ChecklistInputs {
+        let Some(vehicle) = self.vehicle.as_ref() else {
+            return ChecklistInputs::default();
+        };
+        let gps_fix = self
+            .gps_fix
+            .status()
+            .instances
+            .iter()
+            .map(|instance| instance.fix)
+            .max();
+        let battery_pct = self
+            .live_telemetry
+            .value
+            .as_ref()
+            .and_then(|state| state.power.battery_pct);
+        let mission_items = vehicle
+            .mission()
+            .latest()
+            .map(|mission| mission.plan.map_or(0, |plan| plan.items.len()));
+        let prearm =
+            vehicle.telemetry().sensor_health().latest().map(|sample| {
+                prearm_pass(&sample.value, &self.status_text_history, unix_epoch_usec())
+            });
+        ChecklistInputs {
+            gps_fix,
+            battery_pct,
+            home_set: Some(self.session_context.home_position.is_some()),
+            mission_items,
+            prearm_pass: prearm,
+        }
+    }
+
+    pub fn checklist_state(&self) -> ChecklistState {
+        self.checklist.state(&self.checklist_inputs())
+    }
+
+    /// Re-evaluate the checklist. Returns the new state when it changed.
+    pub fn observe_checklist(&mut self) -> Option<ChecklistState> {
+        let inputs = self.checklist_inputs();
+        self.checklist.observe(&inputs)
+    }
+
+    pub fn battery_stats(&self) -> BatteryStats {
+        self.battery_cells.stats()
+    }
+
+    /// Feed the latest power telemetry to the cell tracker. Returns the new
+    /// stats when they changed.
+    pub fn observe_battery(&mut self) -> Option<BatteryStats> {
+        let power = self.live_telemetry.value.as_ref().map(|state| &state.power);
+        let param_cells = self.last_emitted_params.as_ref().and_then(|params| {
+            cell_count_from_params(|name| params.get(name).map(|param| param.value))
+        });
+        let armed = self
+            .session_context
+            .vehicle_state
+            .as_ref()
+            .is_some_and(|vehicle_state| vehicle_state.armed);
+        let changed = self.battery_cells.observe(
+            power.and_then(|power| power.battery_voltage_v),
+            power.and_then(|power| power.battery_voltage_cells.as_deref()),
+            param_cells,
+            armed,
+        );
+        changed.then(|| self.battery_cells.stats())
+    }
+
     pub fn update_live_telemetry(&mut self, telemetry: TelemetrySnapshot) {
+        if let (Some(tracker), Some(state)) =
+            (self.flight_tracker.as_mut(), telemetry.value.as_ref())
+        {
+            tracker.observe(state, self.session_context.home_position.as_ref());
+        }
+        if let Some(state) = telemetry.value.as_ref() {
+            self.derived_telemetry.observe(state);
+        }
         self.live_telemetry = telemetry;
     }
 
@@ -175,6 +1052,7 @@ where
             connected: self.is_connected(),
             provenance,
         });
+        snapshot.telemetry = self.telemetry_in_display_units(snapshot.telemetry);
 
         let Some(vehicle) = self.vehicle.as_ref() else {
             return snapshot;
@@ -184,7 +1062,9 @@ where
 
         let param_state = vehicle.params().latest();
         let param_store = param_state.as_ref().and_then(|item| item.store.clone());
-        snapshot.param_store = param_store.clone();
+        snapshot.param_store = param_store
+            .clone()
+            .map(|store| display_param_store(store, self.param_encoding()));
         snapshot.param_progress = None;
 
         let ardupilot = vehicle.ardupilot();
@@ -202,12 +1082,25 @@ where
     pub fn open_session_snapshot(&mut self, source_kind: SourceKind) -> OpenSessionSnapshot {
         let snapshot = self.session_runtime.open_session_snapshot(source_kind);
         if source_kind == SourceKind::Live {
-            self.live_snapshot_with_envelope(snapshot.envelope, DomainProvenance::Bootstrap)
+            let envelope = self.with_vehicle_system_id(snapshot.envelope);
+            self.live_snapshot_with_envelope(envelope, DomainProvenance::Bootstrap)
         } else {
             snapshot
         }
     }
 
+    /// Stamp a live envelope with the bound vehicle's system ID so scoped
+    /// events say which vehicle they came from.
+    fn with_vehicle_system_id(&self, mut envelope: SessionEnvelope) -> SessionEnvelope {
+        if envelope.source_kind == SourceKind::Live {
+            envelope.system_id = self
+                .vehicle
+                .as_ref()
+                .map(|vehicle| vehicle.identity().system_id);
+        }
+        envelope
+    }
+
     pub fn ack_session_snapshot(
         &mut self,
         session_id: &str,
@@ -219,11 +1112,13 @@ where
     }
 
     pub fn current_stream_envelope(&mut self, now: Instant) -> Option<SessionEnvelope> {
-        self.session_runtime.current_stream_envelope(now)
+        let envelope = self.session_runtime.current_stream_envelope(now)?;
+        Some(self.with_vehicle_system_id(envelope))
     }
 
     pub fn effective_session_envelope(&mut self, now: Instant) -> Option<SessionEnvelope> {
-        self.session_runtime.effective_session_envelope(now)
+        let envelope = self.session_runtime.effective_session_envelope(now)?;
+        Some(self.with_vehicle_system_id(envelope))
     }
 
     pub fn effective_source_kind(&self) -> SourceKind {
@@ -238,19 +1133,29 @@ where
         self.session_runtime.close_playback_session()
     }
 
+    /// Append a STATUSTEXT to the history, flagging it when it announces a
+    /// failsafe. Returns the new history and the decoded failsafe, if any.
     pub fn push_status_text_from_value(
         &mut self,
         value: &serde_json::Value,
         provenance: DomainProvenance,
-    ) -> Option<crate::ipc::StatusTextSnapshot> {
+    ) -> Option<(crate::ipc::StatusTextSnapshot, Option<FailsafeEvent>)> {
         let mut entry = status_text_entry_from_value(value)?;
+        let failsafe = decode_failsafe_status_text(&entry.text, entry.timestamp_usec);
+        entry.failsafe = failsafe.is_some();
+        if let Some(event) = &failsafe {
+            self.failsafes.record(event.clone());
+        }
         entry.sequence = self.next_status_text_sequence;
-        self.next_status_text_sequence = self.next_status_text_sequence.saturating_add(1);
+        entry.vehicle_time = entry
+            .timestamp_usec
+            .zip(self.time_sync.clock())
+            .and_then(|(timestamp_usec, clock)| clock.vehicle_time(timestamp_usec));
         push_status_text_entry(&mut self.status_text_history, entry);
-        Some(status_text_snapshot_from_entries(
-            self.status_text_history.clone(),
-            provenance,
-        ))
+        self.next_status_text_sequence = self.next_status_text_sequence.saturating_add(1);
+        let snapshot =
+            status_text_snapshot_from_entries(self.status_text_history.clone(), provenance);
+        Some((snapshot, failsafe))
     }
 }
 
@@ -337,13 +1242,43 @@ where
     H: LiveRuntimeHandle,
     T: serde::Serialize + Clone + Send + 'static,
 {
+    enum Emission<T> {
+        Direct(ScopedEvent<T>),
+        Coalesced(serde_json::Value),
+    }
+
     let emission = handle.with_runtime(|runtime| {
-        let envelope = runtime.current_stream_envelope(Instant::now())?;
-        Some((runtime.event_sink(), ScopedEvent { envelope, value }))
+        let now = Instant::now();
+        let envelope = runtime.current_stream_envelope(now)?;
+        let scoped = ScopedEvent { envelope, value };
+        if !runtime.event_throttle.is_throttled(event) {
+            runtime.event_throttle.record_passthrough(event, now);
+            return Some((runtime.event_sink(), Emission::Direct(scoped)));
+        }
+        let payload = serde_json::to_value(&scoped).ok()?;
+        let payload = runtime.event_throttle.offer(event, payload, now)?;
+        Some((runtime.event_sink(), Emission::Coalesced(payload)))
     });
 
-    if let Some((sink, scoped)) = emission {
-        sink.emit(event, &scoped);
+    match emission {
+        Some((sink, Emission::Direct(scoped))) => sink.emit(event, &scoped),
+        Some((sink, Emission::Coalesced(payload))) => sink.emit(event, &payload),
+        None => {}
+    }
+}
+
+fn flush_throttled_events<H>(handle: &H)
+where
+    H: LiveRuntimeHandle,
+{
+    let (sink, due) = handle.with_runtime(|runtime| {
+        (
+            runtime.event_sink(),
+            runtime.event_throttle.due(Instant::now()),
+        )
+    });
+    for (event, payload) in due {
+        sink.emit(event, &payload);
     }
 }
 
@@ -362,14 +1297,61 @@ where
 {
     let snapshot = handle.with_runtime(|runtime| runtime.session_snapshot(provenance));
     emit_scoped(handle, event_names::SESSION_STATE, snapshot);
+    emit_triggered_alerts(handle);
+}
+
+/// Re-emit the live state in full after the host woke from sleep, so the
+/// frontend replaces whatever it held before the suspend instead of applying
+/// deltas to it.
+pub fn refresh_after_resume<H>(handle: &H)
+where
+    H: LiveRuntimeHandle,
+{
+    let vehicle = handle.with_runtime(|runtime| {
+        runtime.telemetry_encoder.request_keyframe();
+        runtime.vehicle()
+    });
+    emit_session_state(handle, DomainProvenance::Stream);
+    let Some(vehicle) = vehicle else {
+        return;
+    };
+    emit_telemetry_update(handle, &vehicle);
+    if let Some(mission_state) = vehicle.mission().latest() {
+        emit_scoped(handle, event_names::MISSION_STATE, mission_state);
+    }
+}
+
+fn emit_triggered_alerts<H>(handle: &H)
+where
+    H: LiveRuntimeHandle,
+{
+    for alert in handle.with_runtime(|runtime| runtime.evaluate_alerts()) {
+        emit_scoped(handle, event_names::ALERT_TRIGGERED, alert);
+    }
+}
+
+fn emit_announcements<H>(handle: &H, announcements: impl IntoIterator<Item = Announcement>)
+where
+    H: LiveRuntimeHandle,
+{
+    for announcement in announcements {
+        emit_scoped(handle, event_names::ANNOUNCE_SAY, announcement);
+    }
 }
 
 fn initialize_live_event_bridges<H>(handle: &H, vehicle: &Vehicle)
 where
     H: LiveRuntimeHandle,
 {
-    handle.with_runtime(|runtime| runtime.seed_connected_vehicle(vehicle));
+    handle.with_runtime(|runtime| {
+        runtime.seed_connected_vehicle(vehicle);
+        runtime.seed_current_observations(vehicle);
+    });
     emit_session_state(handle, DomainProvenance::Stream);
+    emit_telemetry_update(handle, vehicle);
+    if let Some(mission_state) = vehicle.mission().latest() {
+        emit_scoped(handle, event_names::MISSION_STATE, mission_state);
+    }
 }
 
 async fn telemetry_poll_bridge<H, I, F, Sleep>(handle: H, interval: I, vehicle: Vehicle, sleep: F)
@@ -385,6 +1367,33 @@ where
     }
 }
 
+/// Coalesces PARAM_VALUE bursts so a full download costs one diff per
+/// interval instead of one store clone and serialization per message.
+async fn param_delta_bridge<H, F, Sleep>(handle: H, sleep: F)
+where
+    H: LiveRuntimeHandle,
+    F: Fn(Duration) -> Sleep + 'static,
+    Sleep: Future<Output = ()> + 'static,
+{
+    loop {
+        sleep(PARAM_DELTA_INTERVAL).await;
+        flush_param_delta(&handle);
+    }
+}
+
+/// Releases coalesced values held back by per-event rate limits.
+async fn event_throttle_bridge<H, F, Sleep>(handle: H, sleep: F)
+where
+    H: LiveRuntimeHandle,
+    F: Fn(Duration) -> Sleep + 'static,
+    Sleep: Future<Output = ()> + 'static,
+{
+    loop {
+        sleep(EVENT_THROTTLE_FLUSH_INTERVAL).await;
+        flush_throttled_events(&handle);
+    }
+}
+
 async fn observation_bridge<H, T, F>(
     handle: H,
     mut subscription: ObservationSubscription<T>,
@@ -493,9 +1502,75 @@ fn emit_telemetry_update<H>(handle: &H, vehicle: &Vehicle)
 where
     H: LiveRuntimeHandle,
 {
-    let telemetry = telemetry_snapshot_from_vehicle(vehicle, DomainProvenance::Stream);
-    handle.with_runtime(|runtime| runtime.update_live_telemetry(telemetry.clone()));
-    emit_scoped(handle, event_names::TELEMETRY_STATE, telemetry);
+    let (telemetry, battery) = handle.with_runtime(|runtime| {
+        let telemetry = runtime.current_telemetry(vehicle);
+        runtime.update_live_telemetry(telemetry.clone());
+        let battery = runtime.observe_battery();
+        (runtime.telemetry_in_display_units(telemetry), battery)
+    });
+    emit_telemetry_frame(handle, telemetry);
+    if let Some(battery) = battery {
+        emit_scoped(handle, event_names::BATTERY_STATS, battery);
+    }
+    emit_checklist_update(handle);
+    emit_triggered_alerts(handle);
+    let announcements = handle.with_runtime(|runtime| runtime.evaluate_announcements());
+    emit_announcements(handle, announcements);
+}
+
+/// Send a display-units telemetry tick. Only the host frontend selects the
+/// encoding; every other subscriber gets the whole snapshot on each tick, so
+/// the frontend switching to deltas never changes what they receive.
+fn emit_telemetry_frame<H>(handle: &H, telemetry: TelemetrySnapshot)
+where
+    H: LiveRuntimeHandle,
+{
+    let frame = handle.with_runtime(|runtime| {
+        runtime
+            .telemetry_encoder
+            .frame(telemetry.clone(), Instant::now())
+    });
+    let delta = match frame {
+        TelemetryFrame::Full(telemetry) => {
+            emit_scoped(handle, event_names::TELEMETRY_STATE, telemetry);
+            return;
+        }
+        TelemetryFrame::Delta(delta) => Some(delta),
+        TelemetryFrame::Unchanged => None,
+    };
+    let scoped = handle.with_runtime(|runtime| {
+        let envelope = runtime.current_stream_envelope(Instant::now())?;
+        Some((runtime.event_sink(), envelope))
+    });
+    let Some((sink, envelope)) = scoped else {
+        return;
+    };
+    if let Some(delta) = delta {
+        sink.emit_to_frontend(
+            event_names::TELEMETRY_DELTA,
+            &ScopedEvent {
+                envelope: envelope.clone(),
+                value: delta,
+            },
+        );
+    }
+    sink.emit_to_subscribers(
+        event_names::TELEMETRY_STATE,
+        &ScopedEvent {
+            envelope,
+            value: telemetry,
+        },
+    );
+}
+
+/// Push `checklist://update` when the checklist changed since the last push.
+pub fn emit_checklist_update<H>(handle: &H)
+where
+    H: LiveRuntimeHandle,
+{
+    if let Some(checklist) = handle.with_runtime(|runtime| runtime.observe_checklist()) {
+        emit_scoped(handle, event_names::CHECKLIST_UPDATE, checklist);
+    }
 }
 
 fn emit_link_state_update<H>(handle: &H, link_state: &mavkit::LinkState)
@@ -510,8 +1585,11 @@ fn emit_armed_update<H>(handle: &H, armed: bool)
 where
     H: LiveRuntimeHandle,
 {
-    handle.with_runtime(|runtime| runtime.update_armed(armed));
+    let summary = handle.with_runtime(|runtime| runtime.update_armed(armed));
     emit_session_state(handle, DomainProvenance::Stream);
+    if let Some(summary) = summary {
+        emit_scoped(handle, event_names::FLIGHT_SUMMARY, summary);
+    }
 }
 
 fn emit_current_mode_update<H>(handle: &H, custom_mode: u32, mode_name: &str)
@@ -544,11 +1622,22 @@ where
     emit_session_state(handle, DomainProvenance::Stream);
 }
 
-fn emit_param_store_update<H>(handle: &H, store: &ParamStore)
+fn emit_param_state_update<H>(handle: &H, param_state: ParamState)
 where
     H: LiveRuntimeHandle,
 {
-    emit_scoped(handle, event_names::PARAM_STORE, store.clone());
+    if let Some(store) = handle.with_runtime(|runtime| runtime.record_param_state(param_state)) {
+        emit_scoped(handle, event_names::PARAM_STORE, store);
+    }
+}
+
+fn flush_param_delta<H>(handle: &H)
+where
+    H: LiveRuntimeHandle,
+{
+    if let Some(delta) = handle.with_runtime(|runtime| runtime.flush_param_delta()) {
+        emit_scoped(handle, event_names::PARAM_DELTA, delta);
+    }
 }
 
 fn emit_sensor_health_update<H>(handle: &H, value: &SensorHealthSummary)
@@ -735,13 +1824,14 @@ where
     });
 
     registrar.spawn_observation(vehicle.mission().subscribe(), |handle, mission_state| {
+        let announcement =
+            handle.with_runtime(|runtime| runtime.announce_mission_progress(&mission_state));
         emit_scoped(handle, event_names::MISSION_STATE, mission_state);
+        emit_announcements(handle, announcement);
     });
 
     registrar.spawn_observation(vehicle.params().subscribe(), |handle, param_state| {
-        if let Some(store) = param_state.store.as_ref() {
-            emit_param_store_update(handle, store);
-        }
+        emit_param_state_update(handle, param_state);
     });
 
     registrar.spawn_observation(
@@ -762,12 +1852,19 @@ where
                 "id": message.id,
                 "source_system": message.source_system,
                 "source_component": message.source_component,
+                "timestamp_usec": unix_epoch_usec(),
             });
-            let snapshot = handle.with_runtime(|runtime| {
+            let pushed = handle.with_runtime(|runtime| {
                 runtime.push_status_text_from_value(&value, DomainProvenance::Stream)
             });
-            if let Some(snapshot) = snapshot {
+            if let Some((snapshot, failsafe)) = pushed {
                 emit_scoped(handle, event_names::STATUS_TEXT_STATE, snapshot);
+                if let Some(failsafe) = failsafe {
+                    let announcement =
+                        handle.with_runtime(|runtime| runtime.announce_failsafe(&failsafe));
+                    emit_scoped(handle, event_names::VEHICLE_FAILSAFE, failsafe);
+                    emit_announcements(handle, announcement);
+                }
             }
         },
     );
@@ -800,6 +1897,22 @@ pub fn spawn_local_event_bridges<H, S, T, I>(
         ));
     }
 
+    {
+        let handle = handle.clone();
+        let timer = timer.clone();
+        spawner.spawn_local(param_delta_bridge(handle, move |duration| {
+            timer.sleep(duration)
+        }));
+    }
+
+    {
+        let handle = handle.clone();
+        let timer = timer.clone();
+        spawner.spawn_local(event_throttle_bridge(handle, move |duration| {
+            timer.sleep(duration)
+        }));
+    }
+
     spawn_observation_event_bridges(
         &mut LocalObservationBridgeRegistrar::new(handle.clone(), spawner),
         vehicle,
@@ -854,6 +1967,22 @@ pub fn spawn_send_event_bridges<H, S, T, I>(
         ));
     }
 
+    {
+        let handle = handle.clone();
+        let timer = timer.clone();
+        spawner.spawn_send(param_delta_bridge(handle, move |duration| {
+            timer.sleep(duration)
+        }));
+    }
+
+    {
+        let handle = handle.clone();
+        let timer = timer.clone();
+        spawner.spawn_send(event_throttle_bridge(handle, move |duration| {
+            timer.sleep(duration)
+        }));
+    }
+
     spawn_observation_event_bridges(
         &mut SendObservationBridgeRegistrar::new(handle.clone(), spawner),
         vehicle,
@@ -891,12 +2020,17 @@ mod tests {
     #[derive(Clone, Default)]
     struct RecordingEventSink {
         events: Rc<RefCell<Vec<(String, serde_json::Value)>>>,
+        subscriber_events: Rc<RefCell<Vec<(String, serde_json::Value)>>>,
     }
 
     impl RecordingEventSink {
         fn events(&self) -> Vec<(String, serde_json::Value)> {
             self.events.borrow().clone()
         }
+
+        fn subscriber_events(&self) -> Vec<(String, serde_json::Value)> {
+            self.subscriber_events.borrow().clone()
+        }
     }
 
     impl EventSink for RecordingEventSink {
@@ -907,6 +2041,16 @@ mod tests {
             let value = serde_json::to_value(payload).expect("serialize recorded event");
             self.events.borrow_mut().push((event.to_string(), value));
         }
+
+        fn emit_to_subscribers<T>(&self, event: &'static str, payload: &T)
+        where
+            T: serde::Serialize + Clone + Send + 'static,
+        {
+            let value = serde_json::to_value(payload).expect("serialize recorded event");
+            self.subscriber_events
+                .borrow_mut()
+                .push((event.to_string(), value));
+        }
     }
 
     #[test]
@@ -990,6 +2134,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn delta_encoding_applies_to_the_frontend_only() {
+        let sink = RecordingEventSink::default();
+        let handle = LocalLiveRuntime::new(LiveVehicleRuntime::new(sink.clone()));
+        let live = handle.with_runtime(|runtime| {
+            runtime
+                .session_runtime_mut()
+                .open_session_snapshot(SourceKind::Live)
+        });
+        handle.with_runtime(|runtime| {
+            let _ = runtime.ack_session_snapshot(
+                &live.envelope.session_id,
+                live.envelope.seek_epoch,
+                live.envelope.reset_revision,
+            );
+            runtime.set_telemetry_encoding(TelemetryEncoding::Delta);
+        });
+        let telemetry = telemetry_snapshot_from_value(
+            &serde_json::json!({ "altitude_m": 12.5 }),
+            DomainProvenance::Stream,
+        );
+
+        emit_telemetry_frame(&handle, telemetry.clone());
+        emit_telemetry_frame(&handle, telemetry);
+
+        let frontend: Vec<String> = sink.events().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(frontend, [event_names::TELEMETRY_DELTA]);
+        let subscribers = sink.subscriber_events();
+        assert_eq!(subscribers.len(), 2);
+        assert!(
+            subscribers
+                .iter()
+                .all(|(name, _)| name == event_names::TELEMETRY_STATE)
+        );
+    }
+
     #[test]
     fn firmware_update_records_version_before_session_emit() {
         let sink = RecordingEventSink::default();
@@ -1040,4 +2220,112 @@ mod tests {
         assert_eq!(sink.events().len(), 1);
         assert_eq!(sink.events()[0].0, event_names::SESSION_STATE,);
     }
+
+    #[test]
+    fn param_download_buffers_deltas_and_emits_full_store_on_completion() {
+        let mut runtime = LiveVehicleRuntime::new(NoopEventSink);
+        let store_with = |count: u16| ParamStore {
+            params: (0..count)
+                .map(|index| {
+                    let name = format!("PARAM_{index}");
+                    let param = Param {
+                        name: name.clone(),
+                        value: f32::from(index),
+                        param_type: mavkit::ParamType::Real32,
+                        index,
+                    };
+                    (name, param)
+                })
+                .collect(),
+            expected_count: 4,
+        };
+        let downloading = |store: ParamStore| ParamState {
+            store: Some(store),
+            sync: mavkit::SyncState::Unknown,
+            active_op: Some(ParamOperationKind::DownloadAll),
+        };
+
+        assert_eq!(runtime.record_param_state(downloading(store_with(1))), None);
+        assert_eq!(runtime.record_param_state(downloading(store_with(2))), None);
+        let first = runtime.flush_param_delta().expect("first flush");
+        assert!(first.full);
+        assert_eq!((first.received, first.total), (2, 4));
+        assert_eq!(runtime.flush_param_delta(), None);
+
+        assert_eq!(runtime.record_param_state(downloading(store_with(3))), None);
+        let second = runtime.flush_param_delta().expect("second flush");
+        assert!(!second.full);
+        assert_eq!(second.generation, first.generation + 1);
+        assert_eq!(second.changed.len(), 1);
+
+        let completed = runtime.record_param_state(ParamState {
+            store: Some(store_with(4)),
+            sync: mavkit::SyncState::Current,
+            active_op: None,
+        });
+        assert_eq!(completed.map(|store| store.params.len()), Some(4));
+        assert_eq!(runtime.flush_param_delta(), None);
+    }
+
+    #[test]
+    fn high_latency_reports_only_apply_in_high_latency_profile() {
+        let mut runtime = LiveVehicleRuntime::new(NoopEventSink);
+        let report = || crate::high_latency::HighLatencyReport {
+            telemetry: TelemetryState::default(),
+            custom_mode: 4,
+        };
+
+        assert!(!runtime.record_high_latency_report(report(), Some("GUIDED")));
+        assert!(runtime.high_latency_telemetry.is_none());
+
+        runtime.set_link_profile(LinkProfile::HighLatency);
+        assert!(runtime.record_high_latency_report(report(), Some("GUIDED")));
+        assert!(runtime.high_latency_telemetry.is_some());
+
+        runtime.reset_live_state();
+        assert_eq!(runtime.link_profile(), LinkProfile::Normal);
+        assert!(runtime.high_latency_telemetry.is_none());
+    }
+
+    #[test]
+    fn disarm_finishes_flight_summary_and_keeps_history() {
+        let mut runtime = LiveVehicleRuntime::new(NoopEventSink);
+        let mut telemetry = TelemetryState::default();
+        telemetry.flight.altitude_m = Some(42.0);
+
+        assert_eq!(runtime.update_armed(false), None);
+        assert_eq!(runtime.update_armed(true), None);
+        runtime.update_live_telemetry(TelemetrySnapshot::present(
+            telemetry,
+            DomainProvenance::Stream,
+        ));
+        let summary = runtime.update_armed(false).expect("summary on disarm");
+
+        assert_eq!(summary.max_altitude_m, Some(42.0));
+        assert_eq!(summary.max_distance_from_home_m, None);
+        runtime.reset_live_state();
+        assert_eq!(runtime.flight_summaries(), [summary]);
+    }
+
+    #[test]
+    fn flight_track_records_only_while_armed_and_survives_disconnect() {
+        let mut runtime = LiveVehicleRuntime::new(NoopEventSink);
+        let point = |timestamp_usec| TrackPoint {
+            timestamp_usec,
+            latitude_deg: 47.0,
+            longitude_deg: 8.0,
+            altitude_msl_m: 500.0,
+            relative_alt_m: 0.0,
+            heading_deg: None,
+        };
+
+        runtime.record_track_point(point(1));
+        runtime.update_armed(true);
+        runtime.record_track_point(point(2));
+        runtime.update_armed(false);
+        runtime.record_track_point(point(3));
+        runtime.reset_live_state();
+
+        assert_eq!(runtime.flight_track().since(None, None), [point(2)]);
+    }
 }
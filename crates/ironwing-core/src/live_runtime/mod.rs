@@ -9,8 +9,9 @@ pub use capabilities::{Capability, RuntimeCapabilities};
 pub use commands::{LiveCommandError, LiveCommandResult};
 pub use event_sink::{EventSink, NoopEventSink};
 pub use live_vehicle_runtime::{
-    LiveRuntimeHandle, LiveVehicleRuntime, LocalLiveRuntime, SharedLiveRuntime, emit_scoped,
-    emit_session_state, emit_unscoped, spawn_local_event_bridges, spawn_send_event_bridges,
+    LiveRuntimeHandle, LiveVehicleRuntime, LocalLiveRuntime, SharedLiveRuntime,
+    emit_checklist_update, emit_scoped, emit_session_state, emit_unscoped, refresh_after_resume,
+    spawn_local_event_bridges, spawn_send_event_bridges,
 };
 pub use task_set::{
     FixedTelemetryInterval, LocalTaskSpawner, LocalTimer, SendTaskSpawner, SendTimer,
@@ -0,0 +1,89 @@
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+/// Shortest token accepted; anything on the machine (or, with `bind_all`,
+/// the network) that can reach the port can try guesses.
+pub const MIN_LOCAL_API_TOKEN_LEN: usize = 8;
+
+pub fn validate_local_api_token(token: &str) -> Result<(), String> {
+    if token.len() < MIN_LOCAL_API_TOKEN_LEN {
+        return Err(format!(
+            "local API token must be at least {MIN_LOCAL_API_TOKEN_LEN} characters"
+        ));
+    }
+    if !token.chars().all(|c| c.is_ascii_graphic()) {
+        return Err("local API token must be printable ASCII without spaces".to_string());
+    }
+    Ok(())
+}
+
+/// Loopback unless `bind_all`, which exposes the API on every interface.
+pub fn local_api_address(port: u16, bind_all: bool) -> SocketAddr {
+    let host = if bind_all {
+        Ipv4Addr::UNSPECIFIED
+    } else {
+        Ipv4Addr::LOCALHOST
+    };
+    SocketAddr::new(IpAddr::V4(host), port)
+}
+
+/// Whether an `Authorization` header value carries `Bearer <token>`. The
+/// comparison takes the same time wherever the first mismatch is.
+pub fn bearer_token_matches(authorization: Option<&str>, token: &str) -> bool {
+    let Some((scheme, presented)) = authorization.and_then(|value| value.trim().split_once(' '))
+    else {
+        return false;
+    };
+    if !scheme.eq_ignore_ascii_case("bearer") {
+        return false;
+    }
+    let (presented, token) = (presented.trim().as_bytes(), token.as_bytes());
+    presented.len() == token.len()
+        && presented
+            .iter()
+            .zip(token)
+            .fold(0_u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// The path of a request target without its query string.
+pub fn request_route(target: &str) -> &str {
+    target.split_once('?').map_or(target, |(path, _)| path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_must_be_long_and_printable() {
+        assert!(validate_local_api_token("s3cret-token").is_ok());
+        assert!(validate_local_api_token("short").is_err());
+        assert!(validate_local_api_token("has a space").is_err());
+        assert!(validate_local_api_token("tab\tinside!").is_err());
+        assert!(validate_local_api_token("").is_err());
+    }
+
+    #[test]
+    fn binds_loopback_unless_asked_for_all_interfaces() {
+        assert_eq!(local_api_address(8080, false).to_string(), "127.0.0.1:8080");
+        assert_eq!(local_api_address(8080, true).to_string(), "0.0.0.0:8080");
+    }
+
+    #[test]
+    fn only_the_exact_bearer_token_is_accepted() {
+        let token = "s3cret-token";
+        assert!(bearer_token_matches(Some("Bearer s3cret-token"), token));
+        assert!(bearer_token_matches(Some("bearer  s3cret-token "), token));
+        assert!(!bearer_token_matches(Some("Bearer s3cret-toke"), token));
+        assert!(!bearer_token_matches(Some("Bearer s3cret-tokens"), token));
+        assert!(!bearer_token_matches(Some("Basic s3cret-token"), token));
+        assert!(!bearer_token_matches(Some("s3cret-token"), token));
+        assert!(!bearer_token_matches(None, token));
+    }
+
+    #[test]
+    fn routes_ignore_the_query_string() {
+        assert_eq!(request_route("/track.geojson?since=10"), "/track.geojson");
+        assert_eq!(request_route("/telemetry"), "/telemetry");
+    }
+}
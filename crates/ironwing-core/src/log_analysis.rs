@@ -0,0 +1,835 @@
+use crate::ipc::{LogFinding, LogFindingCategory, LogFindingMetric, LogFindingSeverity};
+use crate::log_engine::{LogStore, LogType, StoredEntry, haversine_m};
+use crate::named_values::mavlink_name;
+
+/// EKF innovation test ratios above this are worth a look; the EKF failsafe
+/// defaults to 0.8.
+pub const EKF_VARIANCE_WARNING: f64 = 0.5;
+pub const EKF_VARIANCE_CRITICAL: f64 = 0.8;
+/// Voltage drop below the recent peak, as a fraction of that peak.
+pub const BATTERY_SAG_WARNING_RATIO: f64 = 0.15;
+pub const BATTERY_SAG_CRITICAL_RATIO: f64 = 0.25;
+/// A position step has to be both this long and this fast to be a glitch
+/// rather than flight.
+pub const GPS_GLITCH_MIN_JUMP_M: f64 = 30.0;
+pub const GPS_GLITCH_MIN_SPEED_MPS: f64 = 50.0;
+pub const GPS_GLITCH_CRITICAL_JUMP_M: f64 = 200.0;
+/// Throttle fraction treated as saturated for thrust-loss detection.
+pub const THRUST_LOSS_THROTTLE: f64 = 0.9;
+pub const THRUST_LOSS_SINK_MPS: f64 = 1.0;
+/// Yaw controller output held at this magnitude means the motors are
+/// fighting a constant yaw torque.
+pub const YAW_IMBALANCE_OUTPUT: f64 = 0.25;
+pub const YAW_IMBALANCE_CRITICAL_OUTPUT: f64 = 0.5;
+pub const VIBRATION_CLIP_CRITICAL: f64 = 100.0;
+
+const USEC_PER_SEC: u64 = 1_000_000;
+const EKF_MIN_DURATION_USEC: u64 = USEC_PER_SEC;
+const BATTERY_BASELINE_WINDOW_USEC: u64 = 10 * USEC_PER_SEC;
+const BATTERY_SAG_MIN_DURATION_USEC: u64 = USEC_PER_SEC;
+const GPS_GLITCH_MERGE_USEC: u64 = 5 * USEC_PER_SEC;
+const THRUST_LOSS_MIN_DURATION_USEC: u64 = USEC_PER_SEC;
+const YAW_IMBALANCE_MIN_DURATION_USEC: u64 = 5 * USEC_PER_SEC;
+
+/// ArduPilot `LogErrorSubsystem::FAILSAFE_RADIO` and its error codes.
+const ERR_SUBSYS_FAILSAFE_RADIO: f64 = 5.0;
+const ERR_CODE_RESOLVED: f64 = 0.0;
+const ERR_CODE_FAILSAFE_OCCURRED: f64 = 1.0;
+/// MAV_TYPE_GROUND_ROVER and MAV_TYPE_SURFACE_BOAT.
+const GROUND_VEHICLE_MAV_TYPES: [f64; 2] = [10.0, 11.0];
+
+fn metric(name: &str, value: f64, unit: Option<&str>) -> LogFindingMetric {
+    LogFindingMetric {
+        name: name.to_string(),
+        value,
+        unit: unit.map(str::to_string),
+    }
+}
+
+fn entries<'a>(store: &'a LogStore, msg: &str) -> impl Iterator<Item = &'a StoredEntry> {
+    store
+        .type_index
+        .get(msg)
+        .into_iter()
+        .flatten()
+        .map(|&idx| &store.entries[idx])
+}
+
+fn series(store: &LogStore, msg: &str, field: &str) -> Vec<(u64, f64)> {
+    entries(store, msg)
+        .filter_map(|entry| {
+            let value = entry.fields.get(field).copied()?;
+            Some((entry.timestamp_usec, value))
+        })
+        .collect()
+}
+
+/// Index ranges (inclusive) of runs where `hit` holds for at least
+/// `min_duration_usec`.
+fn sustained_runs<T>(
+    samples: &[T],
+    time_usec: impl Fn(&T) -> u64,
+    hit: impl Fn(&T) -> bool,
+    min_duration_usec: u64,
+) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (idx, sample) in samples.iter().enumerate() {
+        match (hit(sample), start) {
+            (true, None) => start = Some(idx),
+            (false, Some(first)) => {
+                runs.push((first, idx - 1));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(first) = start {
+        runs.push((first, samples.len() - 1));
+    }
+    runs.retain(|&(first, last)| {
+        time_usec(&samples[last]).saturating_sub(time_usec(&samples[first])) >= min_duration_usec
+    });
+    runs
+}
+
+/// Clipping from one IMU's cumulative clip counter. Counter resets (a new
+/// boot in the same log) are skipped rather than read as negative clipping.
+pub fn vibration_clipping(imu: usize, counts: &[(u64, f64)]) -> Option<LogFinding> {
+    let mut total = 0.0;
+    let mut first_usec = None;
+    let mut last_usec = 0;
+    for pair in counts.windows(2) {
+        let delta = pair[1].1 - pair[0].1;
+        if delta > 0.0 {
+            total += delta;
+            first_usec.get_or_insert(pair[1].0);
+            last_usec = pair[1].0;
+        }
+    }
+    let start_usec = first_usec?;
+    let severity = if total >= VIBRATION_CLIP_CRITICAL {
+        LogFindingSeverity::Critical
+    } else {
+        LogFindingSeverity::Warning
+    };
+    Some(LogFinding {
+        category: LogFindingCategory::Vibration,
+        severity,
+        start_usec,
+        end_usec: last_usec,
+        explanation: format!(
+            "IMU {imu} accelerometer clipped {total:.0} times; vibration is saturating the sensor, check propeller balance and flight controller mounting"
+        ),
+        metrics: vec![metric("clip_count", total, None)],
+    })
+}
+
+/// Sustained runs of one EKF variance (innovation test ratio) above the
+/// warning level.
+pub fn ekf_variance(name: &str, samples: &[(u64, f64)]) -> Vec<LogFinding> {
+    sustained_runs(
+        samples,
+        |sample| sample.0,
+        |sample| sample.1 > EKF_VARIANCE_WARNING,
+        EKF_MIN_DURATION_USEC,
+    )
+    .into_iter()
+    .map(|(first, last)| {
+        let run = &samples[first..=last];
+        let peak = run.iter().map(|sample| sample.1).fold(f64::MIN, f64::max);
+        let severity = if peak > EKF_VARIANCE_CRITICAL {
+            LogFindingSeverity::Critical
+        } else {
+            LogFindingSeverity::Warning
+        };
+        LogFinding {
+            category: LogFindingCategory::Ekf,
+            severity,
+            start_usec: run[0].0,
+            end_usec: run[run.len() - 1].0,
+            explanation: format!(
+                "EKF {name} variance stayed above {EKF_VARIANCE_WARNING} (peak {peak:.2}); the estimator distrusted that sensor"
+            ),
+            metrics: vec![metric("peak_variance", peak, None)],
+        }
+    })
+    .collect()
+}
+
+/// Voltage sagging well below its peak over the preceding few seconds, which
+/// points at a weak pack or high internal resistance. `samples` are
+/// `(time, volts, amps)`.
+pub fn battery_sag(samples: &[(u64, f64, Option<f64>)]) -> Vec<LogFinding> {
+    let mut baselines = Vec::with_capacity(samples.len());
+    let mut window_start = 0;
+    for (idx, sample) in samples.iter().enumerate() {
+        while sample.0.saturating_sub(samples[window_start].0) > BATTERY_BASELINE_WINDOW_USEC {
+            window_start += 1;
+        }
+        let baseline = samples[window_start..=idx]
+            .iter()
+            .map(|sample| sample.1)
+            .fold(f64::MIN, f64::max);
+        baselines.push(baseline);
+    }
+    let sag_ratio = |idx: usize| {
+        let baseline = baselines[idx];
+        if baseline > 0.0 {
+            (baseline - samples[idx].1) / baseline
+        } else {
+            0.0
+        }
+    };
+    let indices: Vec<usize> = (0..samples.len()).collect();
+    sustained_runs(
+        &indices,
+        |&idx| samples[idx].0,
+        |&idx| sag_ratio(idx) >= BATTERY_SAG_WARNING_RATIO,
+        BATTERY_SAG_MIN_DURATION_USEC,
+    )
+    .into_iter()
+    .map(|(first, last)| {
+        let worst = (first..=last)
+            .max_by(|&a, &b| sag_ratio(a).total_cmp(&sag_ratio(b)))
+            .unwrap_or(first);
+        let ratio = sag_ratio(worst);
+        let severity = if ratio >= BATTERY_SAG_CRITICAL_RATIO {
+            LogFindingSeverity::Critical
+        } else {
+            LogFindingSeverity::Warning
+        };
+        let mut metrics = vec![
+            metric("sag_v", baselines[worst] - samples[worst].1, Some("V")),
+            metric("sag_pct", ratio * 100.0, Some("%")),
+            metric("min_voltage_v", samples[worst].1, Some("V")),
+        ];
+        if let Some(current) = samples[worst].2 {
+            metrics.push(metric("current_a", current, Some("A")));
+        }
+        LogFinding {
+            category: LogFindingCategory::Battery,
+            severity,
+            start_usec: samples[first].0,
+            end_usec: samples[last].0,
+            explanation: format!(
+                "battery voltage sagged {:.0}% below its recent level under load; the pack may be worn or undersized",
+                ratio * 100.0
+            ),
+            metrics,
+        }
+    })
+    .collect()
+}
+
+/// Position steps too long and too fast to be flight. Glitches close together
+/// are reported as one finding. `fixes` are `(time, lat_deg, lon_deg)`.
+pub fn gps_glitches(fixes: &[(u64, f64, f64)]) -> Vec<LogFinding> {
+    let mut findings: Vec<LogFinding> = Vec::new();
+    for pair in fixes.windows(2) {
+        let (t0, lat0, lon0) = pair[0];
+        let (t1, lat1, lon1) = pair[1];
+        if t1 <= t0 {
+            continue;
+        }
+        let jump_m = haversine_m(lat0, lon0, lat1, lon1);
+        let speed_mps = jump_m / ((t1 - t0) as f64 / USEC_PER_SEC as f64);
+        if jump_m < GPS_GLITCH_MIN_JUMP_M || speed_mps < GPS_GLITCH_MIN_SPEED_MPS {
+            continue;
+        }
+        if let Some(last) = findings.last_mut()
+            && t0.saturating_sub(last.end_usec) <= GPS_GLITCH_MERGE_USEC
+        {
+            last.end_usec = t1;
+            if jump_m > last.metrics[0].value {
+                last.metrics[0].value = jump_m;
+                last.metrics[1].value = speed_mps;
+            }
+            if jump_m >= GPS_GLITCH_CRITICAL_JUMP_M {
+                last.severity = LogFindingSeverity::Critical;
+            }
+            continue;
+        }
+        findings.push(LogFinding {
+            category: LogFindingCategory::Gps,
+            severity: if jump_m >= GPS_GLITCH_CRITICAL_JUMP_M {
+                LogFindingSeverity::Critical
+            } else {
+                LogFindingSeverity::Warning
+            },
+            start_usec: t0,
+            end_usec: t1,
+            explanation: "GPS position jumped faster than the vehicle can fly; likely multipath or interference".to_string(),
+            metrics: vec![
+                metric("jump_m", jump_m, Some("m")),
+                metric("implied_speed_mps", speed_mps, Some("m/s")),
+            ],
+        });
+    }
+    findings
+}
+
+/// RC failsafe periods from `(time, entered)` events; a failsafe never
+/// cleared runs to `log_end_usec`.
+pub fn rc_failsafes(events: &[(u64, bool)], log_end_usec: u64) -> Vec<LogFinding> {
+    let mut findings = Vec::new();
+    let mut open: Option<u64> = None;
+    for &(time_usec, entered) in events {
+        match (entered, open) {
+            (true, None) => open = Some(time_usec),
+            (false, Some(start_usec)) => {
+                findings.push(rc_failsafe_finding(start_usec, time_usec));
+                open = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start_usec) = open {
+        findings.push(rc_failsafe_finding(
+            start_usec,
+            log_end_usec.max(start_usec),
+        ));
+    }
+    findings
+}
+
+fn rc_failsafe_finding(start_usec: u64, end_usec: u64) -> LogFinding {
+    LogFinding {
+        category: LogFindingCategory::RcFailsafe,
+        severity: LogFindingSeverity::Critical,
+        start_usec,
+        end_usec,
+        explanation: "RC failsafe triggered; the receiver lost the transmitter signal".to_string(),
+        metrics: vec![metric(
+            "duration_s",
+            end_usec.saturating_sub(start_usec) as f64 / USEC_PER_SEC as f64,
+            Some("s"),
+        )],
+    }
+}
+
+/// Descending with throttle near maximum. `samples` are
+/// `(time, throttle 0..1, climb m/s)`.
+pub fn thrust_loss(samples: &[(u64, f64, f64)]) -> Vec<LogFinding> {
+    sustained_runs(
+        samples,
+        |sample| sample.0,
+        |sample| sample.1 >= THRUST_LOSS_THROTTLE && sample.2 <= -THRUST_LOSS_SINK_MPS,
+        THRUST_LOSS_MIN_DURATION_USEC,
+    )
+    .into_iter()
+    .map(|(first, last)| {
+        let run = &samples[first..=last];
+        let max_sink = run.iter().map(|sample| -sample.2).fold(f64::MIN, f64::max);
+        LogFinding {
+            category: LogFindingCategory::ThrustLoss,
+            severity: LogFindingSeverity::Critical,
+            start_usec: run[0].0,
+            end_usec: run[run.len() - 1].0,
+            explanation: "vehicle descended at full throttle; a motor, propeller or ESC may have failed, or the vehicle is overweight".to_string(),
+            metrics: vec![metric("max_sink_rate_mps", max_sink, Some("m/s"))],
+        }
+    })
+    .collect()
+}
+
+/// Yaw controller output held off-centre, typical of a twisted motor mount
+/// or a failing motor.
+pub fn yaw_imbalance(samples: &[(u64, f64)]) -> Vec<LogFinding> {
+    sustained_runs(
+        samples,
+        |sample| sample.0,
+        |sample| sample.1.abs() >= YAW_IMBALANCE_OUTPUT,
+        YAW_IMBALANCE_MIN_DURATION_USEC,
+    )
+    .into_iter()
+    .map(|(first, last)| {
+        let run = &samples[first..=last];
+        let mean = run.iter().map(|sample| sample.1).sum::<f64>() / run.len() as f64;
+        let severity = if mean.abs() >= YAW_IMBALANCE_CRITICAL_OUTPUT {
+            LogFindingSeverity::Critical
+        } else {
+            LogFindingSeverity::Warning
+        };
+        LogFinding {
+            category: LogFindingCategory::YawImbalance,
+            severity,
+            start_usec: run[0].0,
+            end_usec: run[run.len() - 1].0,
+            explanation:
+                "yaw output stayed far from centre; check motor alignment and for a weak motor"
+                    .to_string(),
+            metrics: vec![metric("mean_yaw_output", mean, None)],
+        }
+    })
+    .collect()
+}
+
+fn bin_vibration(store: &LogStore) -> Vec<LogFinding> {
+    let mut per_imu: Vec<Vec<(u64, f64)>> = Vec::new();
+    for entry in entries(store, "VIBE") {
+        // Newer logs write one VIBE row per IMU; older ones a column per IMU.
+        if let (Some(imu), Some(clip)) = (entry.fields.get("IMU"), entry.fields.get("Clip")) {
+            let imu = *imu as usize;
+            if per_imu.len() <= imu {
+                per_imu.resize_with(imu + 1, Vec::new);
+            }
+            per_imu[imu].push((entry.timestamp_usec, *clip));
+            continue;
+        }
+        for imu in 0..3 {
+            if let Some(clip) = entry.fields.get(&format!("Clip{imu}")) {
+                if per_imu.len() <= imu {
+                    per_imu.resize_with(imu + 1, Vec::new);
+                }
+                per_imu[imu].push((entry.timestamp_usec, *clip));
+            }
+        }
+    }
+    per_imu
+        .iter()
+        .enumerate()
+        .filter_map(|(imu, counts)| vibration_clipping(imu, counts))
+        .collect()
+}
+
+fn bin_ekf(store: &LogStore) -> Vec<LogFinding> {
+    let msg = if store.type_index.contains_key("XKF4") {
+        "XKF4"
+    } else {
+        "NKF4"
+    };
+    // Every core logs its own row; the primary core is enough here.
+    let primary: Vec<&StoredEntry> = entries(store, msg)
+        .filter(|entry| entry.fields.get("C").is_none_or(|core| *core == 0.0))
+        .collect();
+    [
+        ("SV", "velocity"),
+        ("SP", "position"),
+        ("SH", "height"),
+        ("SM", "compass"),
+    ]
+    .into_iter()
+    .flat_map(|(field, name)| {
+        let samples: Vec<(u64, f64)> = primary
+            .iter()
+            .filter_map(|entry| Some((entry.timestamp_usec, *entry.fields.get(field)?)))
+            .collect();
+        ekf_variance(name, &samples)
+    })
+    .collect()
+}
+
+fn bin_rc_failsafe_events(store: &LogStore) -> Vec<(u64, bool)> {
+    entries(store, "ERR")
+        .filter(|entry| entry.fields.get("Subsys") == Some(&ERR_SUBSYS_FAILSAFE_RADIO))
+        .filter_map(|entry| {
+            let code = *entry.fields.get("ECode")?;
+            if code == ERR_CODE_FAILSAFE_OCCURRED {
+                Some((entry.timestamp_usec, true))
+            } else if code == ERR_CODE_RESOLVED {
+                Some((entry.timestamp_usec, false))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn tlog_vibration(store: &LogStore) -> Vec<LogFinding> {
+    (0..3)
+        .filter_map(|imu| {
+            vibration_clipping(imu, &series(store, "VIBRATION", &format!("clipping_{imu}")))
+        })
+        .collect()
+}
+
+fn tlog_ekf(store: &LogStore) -> Vec<LogFinding> {
+    [
+        ("velocity_variance", "velocity"),
+        ("pos_horiz_variance", "position"),
+        ("pos_vert_variance", "height"),
+        ("compass_variance", "compass"),
+    ]
+    .into_iter()
+    .flat_map(|(field, name)| ekf_variance(name, &series(store, "EKF_STATUS_REPORT", field)))
+    .collect()
+}
+
+/// Whether a STATUSTEXT announces an RC failsafe (`Some(true)`) or its end
+/// (`Some(false)`).
+fn rc_failsafe_status_text(text: &str) -> Option<bool> {
+    let text = text.to_ascii_lowercase();
+    let about_rc = text.contains("radio") || text.contains("rc ") || text.contains("throttle");
+    if !text.contains("failsafe") || !about_rc {
+        return None;
+    }
+    Some(!(text.contains("cleared") || text.contains("resolved")))
+}
+
+fn tlog_rc_failsafe_events(store: &LogStore) -> Vec<(u64, bool)> {
+    entries(store, "STATUSTEXT")
+        .filter_map(|entry| {
+            // Byte 0 is the severity; the text follows.
+            let payload = entry.raw_payload.as_deref()?;
+            let text = mavlink_name(payload.get(1..payload.len().min(51))?);
+            Some((entry.timestamp_usec, rc_failsafe_status_text(&text)?))
+        })
+        .collect()
+}
+
+/// Whether the log is from a rover or boat: a tlog by its HEARTBEAT type.
+/// BIN logs carry no HEARTBEAT, so there the Rover-only STER steering
+/// message gives it away.
+fn ground_vehicle_log(store: &LogStore) -> bool {
+    match store.summary.log_type {
+        LogType::Tlog => entries(store, "HEARTBEAT").any(|entry| {
+            entry
+                .fields
+                .get("type")
+                .is_some_and(|mav_type| GROUND_VEHICLE_MAV_TYPES.contains(mav_type))
+        }),
+        LogType::Bin => entries(store, "STER").next().is_some(),
+        LogType::Ulog => false,
+    }
+}
+
+/// Run every heuristic over the open log and return the findings in time
+/// order. Thrust loss and yaw imbalance are multicopter checks and are
+/// skipped for rovers and boats.
+pub fn analyze_log(store: &LogStore) -> Vec<LogFinding> {
+    let log_end_usec = store.summary.end_usec;
+    let airborne = !ground_vehicle_log(store);
+    let mut findings = match store.summary.log_type {
+        LogType::Bin => {
+            let mut findings = bin_vibration(store);
+            findings.extend(bin_ekf(store));
+            let battery: Vec<(u64, f64, Option<f64>)> = entries(store, "BAT")
+                .filter(|entry| entry.fields.get("Inst").is_none_or(|inst| *inst == 0.0))
+                .filter_map(|entry| {
+                    let volts = *entry.fields.get("Volt")?;
+                    if volts <= 0.0 {
+                        return None;
+                    }
+                    Some((
+                        entry.timestamp_usec,
+                        volts,
+                        entry.fields.get("Curr").copied(),
+                    ))
+                })
+                .collect();
+            findings.extend(battery_sag(&battery));
+            let fixes: Vec<(u64, f64, f64)> = entries(store, "GPS")
+                .filter(|entry| {
+                    entry
+                        .fields
+                        .get("Status")
+                        .is_none_or(|status| *status >= 3.0)
+                })
+                .filter_map(|entry| {
+                    let lat = *entry.fields.get("Lat")? / 1e7;
+                    let lng = *entry.fields.get("Lng")? / 1e7;
+                    Some((entry.timestamp_usec, lat, lng))
+                })
+                .collect();
+            findings.extend(gps_glitches(&fixes));
+            findings.extend(rc_failsafes(&bin_rc_failsafe_events(store), log_end_usec));
+            // CTUN climb rate is logged in cm/s.
+            let thrust: Vec<(u64, f64, f64)> = entries(store, "CTUN")
+                .filter_map(|entry| {
+                    let throttle = *entry.fields.get("ThO")?;
+                    let climb = *entry.fields.get("CRt")? / 100.0;
+                    Some((entry.timestamp_usec, throttle, climb))
+                })
+                .collect();
+            if airborne {
+                findings.extend(thrust_loss(&thrust));
+                findings.extend(yaw_imbalance(&series(store, "RATE", "YOut")));
+            }
+            findings
+        }
+        LogType::Tlog => {
+            let mut findings = tlog_vibration(store);
+            findings.extend(tlog_ekf(store));
+            let battery: Vec<(u64, f64, Option<f64>)> = entries(store, "SYS_STATUS")
+                .filter_map(|entry| {
+                    // UINT16_MAX millivolts means the vehicle does not know.
+                    let volts = *entry.fields.get("voltage_battery")?;
+                    if volts <= 0.0 || volts >= f64::from(u16::MAX) / 1000.0 {
+                        return None;
+                    }
+                    let current = entry.fields.get("current_battery").copied();
+                    Some((
+                        entry.timestamp_usec,
+                        volts,
+                        current.filter(|amps| *amps >= 0.0),
+                    ))
+                })
+                .collect();
+            findings.extend(battery_sag(&battery));
+            let fixes: Vec<(u64, f64, f64)> = entries(store, "GPS_RAW_INT")
+                .filter(|entry| entry.fields.get("fix_type").is_some_and(|fix| *fix >= 3.0))
+                .filter_map(|entry| {
+                    Some((
+                        entry.timestamp_usec,
+                        *entry.fields.get("lat")?,
+                        *entry.fields.get("lon")?,
+                    ))
+                })
+                .collect();
+            findings.extend(gps_glitches(&fixes));
+            findings.extend(rc_failsafes(&tlog_rc_failsafe_events(store), log_end_usec));
+            // VFR_HUD throttle is a percentage.
+            let thrust: Vec<(u64, f64, f64)> = entries(store, "VFR_HUD")
+                .filter_map(|entry| {
+                    let throttle = *entry.fields.get("throttle")? / 100.0;
+                    let climb = *entry.fields.get("climb")?;
+                    Some((entry.timestamp_usec, throttle, climb))
+                })
+                .collect();
+            if airborne {
+                findings.extend(thrust_loss(&thrust));
+            }
+            findings
+        }
+        LogType::Ulog => {
+            let battery: Vec<(u64, f64, Option<f64>)> = entries(store, "battery_status")
+                .filter_map(|entry| {
+                    let volts = *entry.fields.get("voltage_v")?;
+                    if volts <= 0.0 {
+                        return None;
+                    }
+                    // PX4 reports -1 A when it has no current sensor.
+                    let current = entry.fields.get("current_a").copied();
+                    Some((
+                        entry.timestamp_usec,
+                        volts,
+                        current.filter(|amps| *amps >= 0.0),
+                    ))
+                })
+                .collect();
+            let mut findings = battery_sag(&battery);
+            // Older PX4 logs carry degE7 integers, newer ones degrees.
+            let fixes: Vec<(u64, f64, f64)> = entries(store, "vehicle_gps_position")
+                .filter(|entry| entry.fields.get("fix_type").is_some_and(|fix| *fix >= 3.0))
+                .filter_map(|entry| {
+                    let (lat, lon) = match (
+                        entry.fields.get("latitude_deg"),
+                        entry.fields.get("longitude_deg"),
+                    ) {
+                        (Some(lat), Some(lon)) => (*lat, *lon),
+                        _ => (
+                            *entry.fields.get("lat")? / 1e7,
+                            *entry.fields.get("lon")? / 1e7,
+                        ),
+                    };
+                    Some((entry.timestamp_usec, lat, lon))
+                })
+                .collect();
+            findings.extend(gps_glitches(&fixes));
+            findings
+        }
+    };
+    findings.sort_by_key(|finding| (finding.start_usec, finding.end_usec));
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at_hz(hz: u64, values: impl IntoIterator<Item = f64>) -> Vec<(u64, f64)> {
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(idx, value)| (idx as u64 * USEC_PER_SEC / hz, value))
+            .collect()
+    }
+
+    #[test]
+    fn clipping_counts_increases_and_ignores_counter_resets() {
+        let counts = at_hz(1, [0.0, 0.0, 40.0, 90.0, 0.0, 30.0]);
+        let finding = vibration_clipping(1, &counts).expect("clipping");
+        assert_eq!(finding.severity, LogFindingSeverity::Critical);
+        assert_eq!(finding.metrics[0].value, 120.0);
+        assert_eq!(
+            (finding.start_usec, finding.end_usec),
+            (2_000_000, 5_000_000)
+        );
+
+        assert!(vibration_clipping(0, &at_hz(1, [3.0, 3.0, 3.0])).is_none());
+    }
+
+    #[test]
+    fn ekf_variance_flags_only_sustained_excursions() {
+        // A single spike is ignored; two seconds above 0.5 with a 0.9 peak is critical.
+        let samples = at_hz(
+            10,
+            [0.1, 0.9, 0.1]
+                .into_iter()
+                .chain(std::iter::repeat_n(0.6, 10))
+                .chain([0.9])
+                .chain(std::iter::repeat_n(0.6, 10))
+                .chain([0.2]),
+        );
+        let findings = ekf_variance("velocity", &samples);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, LogFindingSeverity::Critical);
+        assert_eq!(findings[0].start_usec, 300_000);
+        assert_eq!(findings[0].end_usec, 2_300_000);
+    }
+
+    #[test]
+    fn battery_sag_is_measured_against_the_recent_peak() {
+        let mut samples: Vec<(u64, f64, Option<f64>)> = (0..20)
+            .map(|sec| (sec * USEC_PER_SEC, 16.4, Some(10.0)))
+            .collect();
+        samples.extend((20..23).map(|sec| (sec * USEC_PER_SEC, 13.2, Some(80.0))));
+        samples.push((23 * USEC_PER_SEC, 16.2, Some(10.0)));
+
+        let findings = battery_sag(&samples);
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.severity, LogFindingSeverity::Warning);
+        assert_eq!(
+            (finding.start_usec, finding.end_usec),
+            (20_000_000, 22_000_000)
+        );
+        assert!((finding.metrics[0].value - 3.2).abs() < 1e-9);
+        assert_eq!(finding.metrics[3].value, 80.0);
+
+        // A slow discharge never sags against its own recent peak.
+        let slow: Vec<_> = (0..600)
+            .map(|sec| (sec * USEC_PER_SEC, 16.8 - sec as f64 * 0.005, None))
+            .collect();
+        assert!(battery_sag(&slow).is_empty());
+    }
+
+    #[test]
+    fn gps_glitches_merge_nearby_jumps() {
+        let base = (47.0, 8.0);
+        let fixes = vec![
+            (0, base.0, base.1),
+            (200_000, base.0, base.1 + 0.00001),
+            // ~150 m jump in 0.2 s, then back.
+            (400_000, base.0 + 0.00135, base.1),
+            (600_000, base.0, base.1),
+            (20_000_000, base.0, base.1 + 0.003),
+        ];
+        let findings = gps_glitches(&fixes);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            (findings[0].start_usec, findings[0].end_usec),
+            (200_000, 600_000)
+        );
+        assert_eq!(findings[0].severity, LogFindingSeverity::Warning);
+        assert!(findings[0].metrics[0].value > 140.0);
+    }
+
+    #[test]
+    fn rc_failsafe_periods_pair_entry_and_clear() {
+        let events = [(1_000_000, true), (3_000_000, false), (8_000_000, true)];
+        let findings = rc_failsafes(&events, 10_000_000);
+        assert_eq!(findings.len(), 2);
+        assert_eq!(
+            (findings[0].start_usec, findings[0].end_usec),
+            (1_000_000, 3_000_000)
+        );
+        assert_eq!(
+            (findings[1].start_usec, findings[1].end_usec),
+            (8_000_000, 10_000_000)
+        );
+        assert_eq!(findings[1].metrics[0].value, 2.0);
+
+        assert_eq!(
+            rc_failsafe_status_text("Radio Failsafe - Disarming"),
+            Some(true)
+        );
+        assert_eq!(
+            rc_failsafe_status_text("Radio Failsafe Cleared"),
+            Some(false)
+        );
+        assert_eq!(rc_failsafe_status_text("Battery failsafe"), None);
+    }
+
+    #[test]
+    fn thrust_loss_needs_full_throttle_while_sinking() {
+        let samples: Vec<(u64, f64, f64)> = (0..30)
+            .map(|idx| {
+                let time = idx * USEC_PER_SEC / 10;
+                match idx {
+                    10..=25 => (time, 0.95, -2.5),
+                    // Full throttle while climbing is fine.
+                    0..=5 => (time, 1.0, 3.0),
+                    _ => (time, 0.5, 0.0),
+                }
+            })
+            .collect();
+        let findings = thrust_loss(&samples);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            (findings[0].start_usec, findings[0].end_usec),
+            (1_000_000, 2_500_000)
+        );
+        assert_eq!(findings[0].metrics[0].value, 2.5);
+    }
+
+    #[test]
+    fn yaw_imbalance_needs_a_held_output() {
+        let held = at_hz(10, std::iter::repeat_n(0.3, 60));
+        let findings = yaw_imbalance(&held);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, LogFindingSeverity::Warning);
+
+        // Brief hard yaw inputs are normal flying.
+        let brief = at_hz(
+            10,
+            std::iter::repeat_n(0.8, 20).chain(std::iter::repeat_n(0.0, 40)),
+        );
+        assert!(yaw_imbalance(&brief).is_empty());
+    }
+
+    /// A tlog of a vehicle of `mav_type` bogging down: full throttle with
+    /// the baro reading a descent, as ArduRover's VFR_HUD does on a slope.
+    fn stuck_at_full_throttle(mav_type: f64) -> LogStore {
+        let mut entries = Vec::new();
+        for idx in 0..30_u64 {
+            let time = idx * USEC_PER_SEC / 10;
+            let fields = |pairs: &[(&str, f64)]| {
+                pairs
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), *value))
+                    .collect()
+            };
+            if idx % 10 == 0 {
+                entries.push(StoredEntry::from_numeric_fields(
+                    entries.len() as u64,
+                    time,
+                    "HEARTBEAT",
+                    fields(&[("type", mav_type), ("custom_mode", 10.0)]),
+                ));
+            }
+            entries.push(StoredEntry::from_numeric_fields(
+                entries.len() as u64,
+                time,
+                "VFR_HUD",
+                fields(&[("throttle", 100.0), ("climb", -1.5)]),
+            ));
+        }
+        LogStore::from_entries("drive.tlog", LogType::Tlog, entries)
+    }
+
+    #[test]
+    fn rover_logs_skip_multicopter_checks() {
+        let thrust_findings = |store: &LogStore| {
+            analyze_log(store)
+                .into_iter()
+                .filter(|finding| finding.category == LogFindingCategory::ThrustLoss)
+                .count()
+        };
+        // MAV_TYPE_QUADROTOR, MAV_TYPE_GROUND_ROVER, MAV_TYPE_SURFACE_BOAT.
+        assert_eq!(thrust_findings(&stuck_at_full_throttle(2.0)), 1);
+        assert_eq!(thrust_findings(&stuck_at_full_throttle(10.0)), 0);
+        assert_eq!(thrust_findings(&stuck_at_full_throttle(11.0)), 0);
+    }
+}
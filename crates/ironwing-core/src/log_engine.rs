@@ -1,27 +1,37 @@
-use std::collections::{BTreeMap, HashMap};
-use std::io::{Cursor, Write};
+use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::{Cursor, Read, Write};
 
-use mavkit::dialect::MavMessage;
 use mavlink::{Message, ReadVersion, peek_reader::PeekReader, read_versioned_raw_message};
 use serde::Serialize;
 use serde_json::{Map as JsonMap, Value as JsonValue};
 
+use crate::dialect::MavMessage;
 use crate::ipc::logs::{
     ChartPoint, ChartSeries, ChartSeriesPage, ChartSeriesRequest, LogDiagnostic,
-    LogDiagnosticSeverity, LogDiagnosticSource, LogExportRequest, RawMessageFieldFilter,
-    RawMessagePage, RawMessageQuery, RawMessageRecord,
+    LogDiagnosticSeverity, LogDiagnosticSource, LogExportRequest, LogParam, LogStatusText,
+    RawMessageFieldFilter, RawMessagePage, RawMessageQuery, RawMessageRecord, VirtualField,
 };
 use crate::ipc::playback::PlaybackSeekResult;
-use crate::ipc::{SessionEnvelope, VehicleState};
+use crate::ipc::{LogVtolEvent, SessionEnvelope, VehicleState};
+use crate::log_expr::{ExprError, parse_expr};
 use crate::log_playback::{
     PlaybackFrame, PlaybackLogBounds, playback_frame_from_parts, resolve_playback_cursor_usec,
 };
+use crate::mavlink_frames::{
+    FrameHeader, MAVLINK_V1_HEADER_LEN, MAVLINK_V1_STX, MAVLINK_V2_HEADER_LEN, MAVLINK_V2_STX,
+    UnknownFrame, frame_header, is_known_message,
+};
+use crate::named_values::mavlink_name;
+use crate::ulog::{UlogRecord, UlogValue, parse_ulog};
+use crate::vtol::vtol_status_from_payload;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LogType {
     Tlog,
     Bin,
+    Ulog,
 }
 
 impl From<LogType> for crate::ipc::logs::LogFormat {
@@ -29,6 +39,7 @@ impl From<LogType> for crate::ipc::logs::LogFormat {
         match value {
             LogType::Tlog => Self::Tlog,
             LogType::Bin => Self::Bin,
+            LogType::Ulog => Self::Ulog,
         }
     }
 }
@@ -42,12 +53,37 @@ pub struct LogSummary {
     pub total_entries: usize,
     pub message_types: HashMap<String, usize>,
     pub log_type: LogType,
+    /// Handle assigned when the log is opened; absent for library stores
+    /// parsed on the side.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_id: Option<String>,
+    /// Runs of entries with sane timestamps. A log whose clock jumped
+    /// backwards or by more than an hour has more than one.
+    pub segments: Vec<LogSegment>,
+    /// The tlog ended in a partial frame, usually because it is still being
+    /// recorded. Entries stop at the last complete frame.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LogSegment {
+    pub start_usec: u64,
+    pub end_usec: u64,
+    pub entries: usize,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct LogDataPoint {
     pub timestamp_usec: u64,
     pub fields: HashMap<String, f64>,
+    /// Unit of each converted field; absent for raw SI values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub units: Option<BTreeMap<String, String>>,
+    /// First point of a new timestamp segment; charts should not join it to
+    /// the point before.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub gap_before: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -93,6 +129,9 @@ pub struct LogStore {
     pub entries: Vec<StoredEntry>,
     pub type_index: HashMap<String, Vec<usize>>,
     pub playback_cursor_usec: Option<u64>,
+    /// Bytes of the source tlog up to the end of its last complete frame,
+    /// where following the file resumes; `None` for other formats.
+    pub tlog_parsed_bytes: Option<u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -188,6 +227,35 @@ impl LogStore {
         &self.type_index
     }
 
+    /// Append entries parsed from the end of a tlog that is still growing
+    /// and refresh the summary. `chunk` continues at `tlog_parsed_bytes`.
+    pub fn append_tlog_chunk(&mut self, chunk: TlogChunk) {
+        let first = self.entries.len();
+        for (offset, entry) in chunk.entries.iter().enumerate() {
+            self.type_index
+                .entry(entry.msg_name.clone())
+                .or_default()
+                .push(first + offset);
+        }
+        self.entries.extend(chunk.entries);
+        let log_id = self.summary.log_id.take();
+        self.summary = summarize_entries(&self.source_path, self.summary.log_type, &self.entries);
+        self.summary.log_id = log_id;
+        self.summary.truncated = chunk.truncated;
+        self.tlog_parsed_bytes =
+            Some(self.tlog_parsed_bytes.unwrap_or(0) + chunk.consumed_bytes as u64);
+    }
+
+    /// Segment index of every entry, in entry order.
+    fn entry_segments(&self) -> Vec<usize> {
+        self.summary
+            .segments
+            .iter()
+            .enumerate()
+            .flat_map(|(index, segment)| std::iter::repeat_n(index, segment.entries))
+            .collect()
+    }
+
     pub fn playback_bounds(&self) -> Option<(u64, u64)> {
         (!self.entries.is_empty()).then_some((self.summary.start_usec, self.summary.end_usec))
     }
@@ -270,43 +338,207 @@ const MAX_RAW_MESSAGE_LIMIT: usize = 500;
 const DEFAULT_FLIGHT_PATH_POINTS: usize = 1_000;
 const MAX_FLIGHT_PATH_POINTS: usize = 5_000;
 const TELEMETRY_TRACK_INTERVAL_USEC: u64 = 100_000;
+/// Consecutive entries further apart than this start a new segment.
+const MAX_ENTRY_GAP_USEC: u64 = 3_600 * 1_000_000;
+/// Time fields below this (2001-09-09) count from boot, not the Unix epoch.
+const MIN_UNIX_TIME_USEC: f64 = 1e15;
 
 pub fn parse_log_bytes(path: &str, bytes: &[u8], log_type: LogType) -> Result<ParsedLog, String> {
     match log_type {
         LogType::Tlog => parse_tlog_bytes(path, bytes),
         LogType::Bin => parse_bin_bytes(path, bytes),
+        LogType::Ulog => parse_ulog_bytes(path, bytes),
     }
 }
 
+/// Entries of `msg_type` in the time range. `segment` restricts the query
+/// to one timestamp segment; otherwise all segments are returned with
+/// `gap_before` set where a new one starts.
 pub fn query_log_messages(
     store: &LogStore,
     msg_type: &str,
     start_usec: Option<u64>,
     end_usec: Option<u64>,
     max_points: Option<usize>,
+    segment: Option<usize>,
 ) -> Result<Vec<LogDataPoint>, String> {
     let indices = store
         .type_index
         .get(msg_type)
         .ok_or_else(|| format!("no entries for message type: {msg_type}"))?;
+    if let Some(segment) = segment
+        && segment >= store.summary.segments.len()
+    {
+        return Err(format!(
+            "segment {segment} out of range; the log has {} segments",
+            store.summary.segments.len()
+        ));
+    }
+    let entry_segments = store.entry_segments();
     let mut points = Vec::new();
     for &idx in indices {
         let entry = &store.entries[idx];
-        if in_time_range(entry.timestamp_usec, start_usec, end_usec) {
-            points.push(LogDataPoint {
+        let entry_segment = entry_segments.get(idx).copied().unwrap_or_default();
+        if segment.is_none_or(|segment| segment == entry_segment)
+            && in_time_range(entry.timestamp_usec, start_usec, end_usec)
+        {
+            points.push((entry_segment, entry));
+        }
+    }
+    let points = downsample_if_needed(
+        points,
+        bounded_max_points(
+            max_points,
+            DEFAULT_COMPAT_QUERY_POINTS,
+            MAX_CHART_QUERY_POINTS,
+        ),
+    );
+    let mut previous_segment = None;
+    Ok(points
+        .into_iter()
+        .map(|(entry_segment, entry)| {
+            let gap_before = previous_segment.is_some_and(|previous| previous != entry_segment);
+            previous_segment = Some(entry_segment);
+            LogDataPoint {
                 timestamp_usec: entry.timestamp_usec,
                 fields: entry.fields.clone(),
-            });
+                units: None,
+                gap_before,
+            }
+        })
+        .collect())
+}
+
+/// Evaluate `virtual_fields` against each point's logged fields and add the
+/// results. Expressions are checked against the fields `msg_type` has in this
+/// log first, so a typo fails the query instead of producing an empty series.
+/// Run this before unit conversion: expressions see raw SI values.
+pub fn apply_virtual_fields(
+    store: &LogStore,
+    msg_type: &str,
+    points: &mut [LogDataPoint],
+    virtual_fields: &[VirtualField],
+) -> Result<(), String> {
+    let known: BTreeSet<&str> = store
+        .type_index
+        .get(msg_type)
+        .into_iter()
+        .flatten()
+        .flat_map(|&idx| store.entries[idx].fields.keys().map(String::as_str))
+        .collect();
+    let mut exprs = Vec::with_capacity(virtual_fields.len());
+    for field in virtual_fields {
+        if field.name.trim().is_empty() {
+            return Err("virtual field names must not be empty".to_string());
+        }
+        let expr = parse_expr(&field.expr)
+            .map_err(|error| format!("{}: {}", field.name, error.describe(&field.expr)))?;
+        if let Some((name, position)) = expr
+            .fields()
+            .into_iter()
+            .find(|(name, _)| !known.contains(name))
+        {
+            let error = ExprError {
+                position,
+                message: format!("{msg_type} has no field `{name}`"),
+            };
+            return Err(format!("{}: {}", field.name, error.describe(&field.expr)));
         }
+        exprs.push((field.name.as_str(), expr));
     }
-    Ok(downsample_if_needed(
+    for point in points {
+        for (name, expr) in &exprs {
+            if let Some(value) = expr.eval(&point.fields) {
+                point.fields.insert((*name).to_string(), value);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rewrite each segment's timestamps from the first SYSTEM_TIME or GPS
+/// time inside it, then re-sort the entries. Segments with neither keep
+/// their timestamps. Errors when the log has no usable time reference.
+pub fn repair_timestamps(store: &LogStore) -> Result<LogStore, String> {
+    let mut entries = store.entries.clone();
+    let mut start = 0;
+    let mut anchored = 0;
+    for segment in &store.summary.segments {
+        let range = start..start + segment.entries;
+        start = range.end;
+        let Some(offset) = entries[range.clone()]
+            .iter()
+            .find_map(unix_time_offset_usec)
+        else {
+            continue;
+        };
+        anchored += 1;
+        for entry in &mut entries[range] {
+            entry.timestamp_usec = (i128::from(entry.timestamp_usec) + offset).max(0) as u64;
+        }
+    }
+    if anchored == 0 {
+        return Err("log has no SYSTEM_TIME or GPS time to repair timestamps from".to_string());
+    }
+    entries.sort_by_key(|entry| entry.timestamp_usec);
+    let mut repaired = build_store(&store.source_path, store.summary.log_type, entries);
+    repaired.summary.log_id = store.summary.log_id.clone();
+    repaired.summary.truncated = store.summary.truncated;
+    repaired.tlog_parsed_bytes = store.tlog_parsed_bytes;
+    repaired.entry_id = store.entry_id.clone();
+    Ok(repaired)
+}
+
+/// Unix time minus the entry's own timestamp, for entries that carry a
+/// wall-clock time.
+fn unix_time_offset_usec(entry: &StoredEntry) -> Option<i128> {
+    let unix_usec = match entry.msg_name.as_str() {
+        "SYSTEM_TIME" => entry.fields.get("time_unix_usec"),
+        "GPS_RAW_INT" => entry.fields.get("time_usec"),
+        _ => None,
+    }
+    .copied()
+    .filter(|usec| *usec >= MIN_UNIX_TIME_USEC)?;
+    Some(unix_usec as i128 - i128::from(entry.timestamp_usec))
+}
+
+/// One field of `msg_type` as chart points, for comparing the same signal
+/// across logs. With `normalize_time` the timestamps become time since the
+/// log's first entry so flights recorded at different times line up.
+pub fn compare_series_points(
+    store: &LogStore,
+    msg_type: &str,
+    field: &str,
+    normalize_time: bool,
+    max_points: Option<usize>,
+) -> Vec<ChartPoint> {
+    let offset_usec = if normalize_time {
+        store.summary.start_usec
+    } else {
+        0
+    };
+    let points = store
+        .type_index
+        .get(msg_type)
+        .into_iter()
+        .flatten()
+        .filter_map(|&idx| {
+            let entry = &store.entries[idx];
+            let value = entry.fields.get(field).copied()?;
+            Some(ChartPoint {
+                timestamp_usec: entry.timestamp_usec.saturating_sub(offset_usec),
+                value,
+            })
+        })
+        .collect();
+    downsample_if_needed(
         points,
         bounded_max_points(
             max_points,
-            DEFAULT_COMPAT_QUERY_POINTS,
+            DEFAULT_CHART_QUERY_POINTS,
             MAX_CHART_QUERY_POINTS,
         ),
-    ))
+    )
 }
 
 pub fn query_raw_message_page(
@@ -403,16 +635,15 @@ pub fn flight_path_points(
     end_usec: Option<u64>,
     max_points: Option<usize>,
 ) -> Result<Vec<FlightPathPoint>, String> {
-    let gps_type = if store.type_index.contains_key("GLOBAL_POSITION_INT") {
-        "GLOBAL_POSITION_INT"
-    } else if store.type_index.contains_key("GPS") {
-        "GPS"
-    } else {
-        return Err("no GPS data in log".into());
-    };
+    let gps_type = ["GLOBAL_POSITION_INT", "GPS", "vehicle_global_position"]
+        .into_iter()
+        .find(|gps_type| store.type_index.contains_key(*gps_type))
+        .ok_or("no GPS data in log")?;
+    // PX4's global position carries no heading; points get 0.
     let (lat_key, lon_key, alt_key, hdg_key, needs_dege7_scale) = match store.summary.log_type {
         LogType::Tlog => ("lat", "lon", "relative_alt", "hdg", false),
         LogType::Bin => ("Lat", "Lng", "Alt", "GCrs", true),
+        LogType::Ulog => ("lat", "lon", "alt", "", false),
     };
     let mut points = Vec::new();
     for &index in &store.type_index[gps_type] {
@@ -479,47 +710,62 @@ pub fn telemetry_at(store: &LogStore, cursor_usec: Option<u64>) -> TelemetrySnap
     telemetry
 }
 
+/// Where each format logs what the flight summary needs, as (message,
+/// field) pairs.
+struct SummarySources {
+    altitude: (&'static str, &'static str),
+    speed: (&'static str, &'static str),
+    battery_voltage: (&'static str, &'static str),
+    consumed_mah: (&'static str, &'static str),
+    position: (&'static str, &'static str, &'static str),
+    position_in_dege7: bool,
+    satellites: (&'static str, &'static str),
+}
+
+fn summary_sources(log_type: LogType) -> SummarySources {
+    match log_type {
+        LogType::Bin => SummarySources {
+            altitude: ("CTUN", "Alt"),
+            speed: ("GPS", "Spd"),
+            battery_voltage: ("BAT", "Volt"),
+            consumed_mah: ("BAT", "CurrTot"),
+            position: ("GPS", "Lat", "Lng"),
+            position_in_dege7: true,
+            satellites: ("GPS", "NSats"),
+        },
+        LogType::Tlog => SummarySources {
+            altitude: ("VFR_HUD", "alt"),
+            speed: ("VFR_HUD", "groundspeed"),
+            battery_voltage: ("SYS_STATUS", "voltage_battery"),
+            consumed_mah: ("BATTERY_STATUS", "current_consumed"),
+            position: ("GLOBAL_POSITION_INT", "lat", "lon"),
+            position_in_dege7: false,
+            satellites: ("GPS_RAW_INT", "satellites_visible"),
+        },
+        LogType::Ulog => SummarySources {
+            altitude: ("vehicle_global_position", "alt"),
+            speed: ("vehicle_gps_position", "vel_m_s"),
+            battery_voltage: ("battery_status", "voltage_v"),
+            consumed_mah: ("battery_status", "discharged_mah"),
+            position: ("vehicle_global_position", "lat", "lon"),
+            position_in_dege7: false,
+            satellites: ("vehicle_gps_position", "satellites_used"),
+        },
+    }
+}
+
 pub fn flight_summary(store: &LogStore) -> FlightSummary {
-    let is_bin = store.summary.log_type == LogType::Bin;
-    let (alt_msg, alt_field) = if is_bin {
-        ("CTUN", "Alt")
-    } else {
-        ("VFR_HUD", "alt")
-    };
-    let (spd_msg, spd_field) = if is_bin {
-        ("GPS", "Spd")
-    } else {
-        ("VFR_HUD", "groundspeed")
-    };
-    let (bat_msg, bat_v_field) = if is_bin {
-        ("BAT", "Volt")
-    } else {
-        ("SYS_STATUS", "voltage_battery")
-    };
-    let (gps_msg, lat_key, lon_key, sats_key, needs_dege7) = if is_bin {
-        ("GPS", "Lat", "Lng", "NSats", true)
-    } else {
-        (
-            "GLOBAL_POSITION_INT",
-            "lat",
-            "lon",
-            "satellites_visible",
-            false,
-        )
-    };
-    let sats_msg = if is_bin { "GPS" } else { "GPS_RAW_INT" };
-    let (alt_max, alt_avg) = field_max_avg(store, alt_msg, alt_field);
-    let (spd_max, spd_avg) = field_max_avg(store, spd_msg, spd_field);
+    let sources = summary_sources(store.summary.log_type);
+    let (alt_max, alt_avg) = field_max_avg(store, sources.altitude.0, sources.altitude.1);
+    let (spd_max, spd_avg) = field_max_avg(store, sources.speed.0, sources.speed.1);
     let (battery_start_v, battery_end_v, battery_min_v) =
-        first_last_min_positive(store, bat_msg, bat_v_field);
-    let mah_consumed = if is_bin {
-        last_field(store, "BAT", "CurrTot")
-    } else {
-        last_field(store, "BATTERY_STATUS", "current_consumed")
-    };
+        first_last_min_positive(store, sources.battery_voltage.0, sources.battery_voltage.1);
+    let mah_consumed = last_field(store, sources.consumed_mah.0, sources.consumed_mah.1);
+    let (gps_msg, lat_key, lon_key) = sources.position;
     let (total_distance_m, max_distance_from_home_m) =
-        distance_stats(store, gps_msg, lat_key, lon_key, needs_dege7);
-    let (gps_sats_min, gps_sats_max) = sats_stats(store, sats_msg, sats_key);
+        distance_stats(store, gps_msg, lat_key, lon_key, sources.position_in_dege7);
+    let (gps_sats_min, gps_sats_max) =
+        sats_stats(store, sources.satellites.0, sources.satellites.1);
     FlightSummary {
         duration_secs: store.summary.duration_secs,
         max_alt_m: alt_max,
@@ -618,10 +864,43 @@ fn parse_bin_bytes(path: &str, bytes: &[u8]) -> Result<ParsedLog, String> {
     })
 }
 
-fn parse_tlog_bytes(path: &str, bytes: &[u8]) -> Result<ParsedLog, String> {
-    let mut reader = PeekReader::new(Cursor::new(bytes));
+/// Entries decoded from a run of tlog bytes.
+#[derive(Debug, Clone)]
+pub struct TlogChunk {
+    pub entries: Vec<StoredEntry>,
+    /// Bytes up to the end of the last complete frame.
+    pub consumed_bytes: usize,
+    /// Bytes were left over after the last complete frame.
+    pub truncated: bool,
+}
+
+/// Counts the bytes handed to the MAVLink reader, which reads no further
+/// than the frame it is decoding.
+struct CountingReader<'a> {
+    inner: Cursor<&'a [u8]>,
+    read: &'a Cell<usize>,
+}
+
+impl Read for CountingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.read.set(self.read.get() + read);
+        Ok(read)
+    }
+}
+
+/// Decode the timestamped frames in `bytes`, numbering entries from
+/// `first_sequence`. A partial frame at the end (a tlog still being
+/// written) stops the parse cleanly instead of failing it.
+pub fn parse_tlog_chunk(bytes: &[u8], first_sequence: u64) -> Result<TlogChunk, String> {
+    let read = Cell::new(0);
+    let mut reader = PeekReader::new(CountingReader {
+        inner: Cursor::new(bytes),
+        read: &read,
+    });
     let mut entries = Vec::new();
-    let mut sequence = 0_u64;
+    let mut sequence = first_sequence;
+    let mut consumed_bytes = 0;
     loop {
         let ts_bytes = match reader.read_exact(8) {
             Ok(bytes) => bytes,
@@ -637,8 +916,30 @@ fn parse_tlog_bytes(path: &str, bytes: &[u8]) -> Result<ParsedLog, String> {
                 .try_into()
                 .map_err(|_| "invalid TLOG timestamp width")?,
         );
-        let raw = read_versioned_raw_message::<MavMessage, _>(&mut reader, ReadVersion::Any)
-            .map_err(|error| format!("failed to parse TLOG frame: {error}"))?;
+        match read_unknown_frame(&mut reader) {
+            Ok(Some(frame)) => {
+                entries.push(unknown_to_stored(sequence, timestamp_usec, frame));
+                sequence += 1;
+                consumed_bytes = read.get();
+                continue;
+            }
+            Ok(None) => {}
+            Err(mavlink::error::MessageReadError::Io(error))
+                if error.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                break;
+            }
+            Err(error) => return Err(format!("failed to parse TLOG frame: {error}")),
+        }
+        let raw = match read_versioned_raw_message::<MavMessage, _>(&mut reader, ReadVersion::Any) {
+            Ok(raw) => raw,
+            Err(mavlink::error::MessageReadError::Io(error))
+                if error.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                break;
+            }
+            Err(error) => return Err(format!("failed to parse TLOG frame: {error}")),
+        };
         let message = MavMessage::parse(raw.version(), raw.message_id(), raw.payload())
             .map_err(|error| format!("failed to decode TLOG payload: {error}"))?;
         entries.push(tlog_to_stored(
@@ -650,13 +951,372 @@ fn parse_tlog_bytes(path: &str, bytes: &[u8]) -> Result<ParsedLog, String> {
             message,
         ));
         sequence += 1;
+        consumed_bytes = read.get();
+    }
+    Ok(TlogChunk {
+        entries,
+        consumed_bytes,
+        truncated: consumed_bytes < bytes.len(),
+    })
+}
+
+fn parse_tlog_bytes(path: &str, bytes: &[u8]) -> Result<ParsedLog, String> {
+    let chunk = parse_tlog_chunk(bytes, 0)?;
+    let mut store = build_store(path, LogType::Tlog, chunk.entries);
+    store.summary.truncated = chunk.truncated;
+    store.tlog_parsed_bytes = Some(chunk.consumed_bytes as u64);
+    let mut diagnostics = segment_diagnostics(&store.summary.segments);
+    if chunk.truncated {
+        diagnostics.push(log_diagnostic(
+            LogDiagnosticSeverity::Warning,
+            LogDiagnosticSource::Parse,
+            "truncated_frame",
+            format!(
+                "the last {} bytes are a partial frame; the log may still be recording",
+                bytes.len() - chunk.consumed_bytes
+            ),
+            true,
+            store.playback_bounds().map(|(_, end)| end),
+        ));
     }
+    Ok(ParsedLog { store, diagnostics })
+}
+
+/// Parse the segments of a rotated recording, in order, as one log. A
+/// segment that is missing or corrupt is reported and skipped rather than
+/// failing the whole recording; a partial frame is only expected at the end
+/// of the last one.
+pub fn parse_tlog_segments(
+    path: &str,
+    segments: &[(String, Result<Vec<u8>, String>)],
+) -> Result<ParsedLog, String> {
+    let mut entries = Vec::new();
+    let mut diagnostics = Vec::new();
+    let mut truncated = false;
+    let mut skipped = 0;
+    for (position, (name, bytes)) in segments.iter().enumerate() {
+        let chunk = bytes
+            .as_ref()
+            .map_err(Clone::clone)
+            .and_then(|bytes| parse_tlog_chunk(bytes, entries.len() as u64));
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(error) => {
+                diagnostics.push(log_diagnostic(
+                    LogDiagnosticSeverity::Warning,
+                    LogDiagnosticSource::Parse,
+                    "segment_skipped",
+                    format!("segment {name} was skipped: {error}"),
+                    true,
+                    entries
+                        .last()
+                        .map(|entry: &StoredEntry| entry.timestamp_usec),
+                ));
+                skipped += 1;
+                continue;
+            }
+        };
+        if chunk.truncated {
+            let last = position + 1 == segments.len();
+            truncated |= last;
+            diagnostics.push(log_diagnostic(
+                LogDiagnosticSeverity::Warning,
+                LogDiagnosticSource::Parse,
+                "truncated_frame",
+                if last {
+                    format!(
+                        "segment {name} ends in a partial frame; the log may still be recording"
+                    )
+                } else {
+                    format!("segment {name} ends in a partial frame")
+                },
+                true,
+                chunk.entries.last().map(|entry| entry.timestamp_usec),
+            ));
+        }
+        entries.extend(chunk.entries);
+    }
+    if skipped > 0 && skipped == segments.len() {
+        return Err(format!("no segment of {path} could be read"));
+    }
+    let mut store = build_store(path, LogType::Tlog, entries);
+    store.summary.truncated = truncated;
+    let mut all_diagnostics = segment_diagnostics(&store.summary.segments);
+    all_diagnostics.extend(diagnostics);
     Ok(ParsedLog {
-        store: build_store(path, LogType::Tlog, entries),
-        diagnostics: Vec::new(),
+        store,
+        diagnostics: all_diagnostics,
     })
 }
 
+/// Entry name for ULog `logged_messages` records.
+pub const ULOG_LOGGED_MESSAGE: &str = "logged_message";
+/// Entry name for ULog parameter values and changes.
+pub const ULOG_PARAMETER: &str = "parameter";
+
+/// ULog topics become entries named after the topic, with `[n]` appended for
+/// instances past the first (`battery_status[1]`). Text fields are kept in
+/// `field_values` only.
+fn parse_ulog_bytes(path: &str, bytes: &[u8]) -> Result<ParsedLog, String> {
+    let log = parse_ulog(bytes)?;
+    let mut parts = Vec::new();
+    let (mut dropouts, mut dropped_ms) = (0_u32, 0_u64);
+    for record in log.records {
+        let mut fields = HashMap::new();
+        let mut field_values = BTreeMap::new();
+        let (timestamp_usec, msg_name) = match record {
+            UlogRecord::Data {
+                timestamp_usec,
+                name,
+                multi_id,
+                fields: values,
+            } => {
+                for (field, value) in values {
+                    let value = match value {
+                        UlogValue::Number(number) => {
+                            fields.insert(field.clone(), number);
+                            JsonValue::from(number)
+                        }
+                        UlogValue::Text(text) => JsonValue::from(text),
+                    };
+                    field_values.insert(field, value);
+                }
+                let name = if multi_id == 0 {
+                    name
+                } else {
+                    format!("{name}[{multi_id}]")
+                };
+                (timestamp_usec, name)
+            }
+            UlogRecord::LoggedMessage {
+                timestamp_usec,
+                level,
+                text,
+            } => {
+                fields.insert("level".to_string(), f64::from(level));
+                field_values.insert("level".to_string(), JsonValue::from(level));
+                field_values.insert("message".to_string(), JsonValue::from(text));
+                (timestamp_usec, ULOG_LOGGED_MESSAGE.to_string())
+            }
+            UlogRecord::Parameter {
+                timestamp_usec,
+                name,
+                value,
+            } => {
+                fields.insert("value".to_string(), value);
+                field_values.insert("name".to_string(), JsonValue::from(name));
+                field_values.insert("value".to_string(), JsonValue::from(value));
+                (timestamp_usec, ULOG_PARAMETER.to_string())
+            }
+            UlogRecord::Dropout { duration_ms, .. } => {
+                dropouts += 1;
+                dropped_ms += u64::from(duration_ms);
+                continue;
+            }
+        };
+        parts.push(StoredEntryParts {
+            sequence: 0,
+            timestamp_usec,
+            msg_name,
+            fields,
+            field_values,
+            raw_payload: None,
+            system_id: None,
+            component_id: None,
+        });
+    }
+    // Topics are buffered separately, so file order is only roughly time
+    // order; unsorted, every reordering would start a new segment.
+    parts.sort_by_key(|parts| parts.timestamp_usec);
+    let entries = parts
+        .into_iter()
+        .enumerate()
+        .map(|(sequence, parts)| {
+            build_stored_entry(StoredEntryParts {
+                sequence: sequence as u64,
+                ..parts
+            })
+        })
+        .collect();
+    let mut store = build_store(path, LogType::Ulog, entries);
+    store.summary.truncated = log.truncated;
+    let mut diagnostics = segment_diagnostics(&store.summary.segments);
+    if dropouts > 0 {
+        diagnostics.push(log_diagnostic(
+            LogDiagnosticSeverity::Warning,
+            LogDiagnosticSource::Parse,
+            "ulog_dropouts",
+            format!("the logger dropped data {dropouts} times, {dropped_ms} ms in total"),
+            true,
+            None,
+        ));
+    }
+    if log.truncated {
+        diagnostics.push(log_diagnostic(
+            LogDiagnosticSeverity::Warning,
+            LogDiagnosticSource::Parse,
+            "truncated_frame",
+            "the ULog ends in a partial message; logging stopped abruptly".to_string(),
+            true,
+            store.playback_bounds().map(|(_, end)| end),
+        ));
+    }
+    Ok(ParsedLog { store, diagnostics })
+}
+
+/// The last value the log recorded for each parameter, by name: PARAM_VALUE
+/// for tlogs, the parameter records of a ULog. BIN logs keep only numeric
+/// fields, so their PARM names are not available.
+pub fn log_params(store: &LogStore) -> Result<Vec<LogParam>, String> {
+    let mut params = BTreeMap::new();
+    match store.summary.log_type {
+        LogType::Tlog => {
+            for &index in store.type_index.get("PARAM_VALUE").into_iter().flatten() {
+                let Some(payload) = store.entries[index].raw_payload.as_deref() else {
+                    continue;
+                };
+                // MAVLink 2 trims trailing zeros from payloads.
+                let mut padded = [0_u8; 25];
+                let len = payload.len().min(padded.len());
+                padded[..len].copy_from_slice(&payload[..len]);
+                let value = f32::from_le_bytes([padded[0], padded[1], padded[2], padded[3]]);
+                params.insert(mavlink_name(&padded[8..24]), f64::from(value));
+            }
+        }
+        LogType::Ulog => {
+            for &index in store.type_index.get(ULOG_PARAMETER).into_iter().flatten() {
+                let entry = &store.entries[index];
+                let name = entry.field_values.get("name").and_then(JsonValue::as_str);
+                if let (Some(name), Some(value)) = (name, entry.fields.get("value")) {
+                    params.insert(name.to_string(), *value);
+                }
+            }
+        }
+        LogType::Bin => {
+            return Err("parameter names are not kept for BIN logs".to_string());
+        }
+    }
+    Ok(params
+        .into_iter()
+        .map(|(name, value)| LogParam { name, value })
+        .collect())
+}
+
+/// Text messages in the log in time order: STATUSTEXT for tlogs, the
+/// `logged_messages` of a ULog. BIN MSG text is not kept.
+pub fn log_status_texts(store: &LogStore) -> Result<Vec<LogStatusText>, String> {
+    let (msg_name, from_entry): (&str, fn(&StoredEntry) -> Option<LogStatusText>) =
+        match store.summary.log_type {
+            LogType::Tlog => ("STATUSTEXT", |entry| {
+                // Byte 0 is the severity; the text follows.
+                let payload = entry.raw_payload.as_deref()?;
+                Some(LogStatusText {
+                    timestamp_usec: entry.timestamp_usec,
+                    severity: *payload.first()?,
+                    text: mavlink_name(payload.get(1..payload.len().min(51))?),
+                })
+            }),
+            LogType::Ulog => (ULOG_LOGGED_MESSAGE, |entry| {
+                Some(LogStatusText {
+                    timestamp_usec: entry.timestamp_usec,
+                    severity: *entry.fields.get("level")? as u8,
+                    text: entry.field_values.get("message")?.as_str()?.to_string(),
+                })
+            }),
+            LogType::Bin => return Err("message text is not kept for BIN logs".to_string()),
+        };
+    Ok(store
+        .type_index
+        .get(msg_name)
+        .into_iter()
+        .flatten()
+        .filter_map(|&index| from_entry(&store.entries[index]))
+        .collect())
+}
+
+/// VTOL and landed state changes in time order, from EXTENDED_SYS_STATE.
+/// Only tlogs carry it; the first message of the log is always included.
+pub fn log_vtol_events(store: &LogStore) -> Result<Vec<LogVtolEvent>, String> {
+    if store.summary.log_type != LogType::Tlog {
+        return Err("VTOL state is only kept in tlogs".to_string());
+    }
+    let mut events: Vec<LogVtolEvent> = Vec::new();
+    for &index in store
+        .type_index
+        .get("EXTENDED_SYS_STATE")
+        .into_iter()
+        .flatten()
+    {
+        let entry = &store.entries[index];
+        let Some(payload) = entry.raw_payload.as_deref() else {
+            continue;
+        };
+        let status = vtol_status_from_payload(payload);
+        if events.last().is_none_or(|last| last.status != status) {
+            events.push(LogVtolEvent {
+                timestamp_usec: entry.timestamp_usec,
+                status,
+            });
+        }
+    }
+    Ok(events)
+}
+
+/// One warning per timestamp discontinuity between segments.
+fn segment_diagnostics(segments: &[LogSegment]) -> Vec<LogDiagnostic> {
+    segments
+        .windows(2)
+        .map(|pair| {
+            let (before, after) = (&pair[0], &pair[1]);
+            let message = if after.start_usec < before.end_usec {
+                format!(
+                    "timestamps jump back {:.1} s; the log is split into segments",
+                    (before.end_usec - after.start_usec) as f64 / 1_000_000.0
+                )
+            } else {
+                format!(
+                    "timestamps jump ahead {:.1} h; the log is split into segments",
+                    (after.start_usec - before.end_usec) as f64 / 3_600_000_000.0
+                )
+            };
+            log_diagnostic(
+                LogDiagnosticSeverity::Warning,
+                LogDiagnosticSource::Index,
+                "timestamp_discontinuity",
+                message,
+                true,
+                Some(after.start_usec),
+            )
+        })
+        .collect()
+}
+
+/// Split entries wherever the timestamp goes backwards or skips ahead by
+/// more than `MAX_ENTRY_GAP_USEC`.
+fn split_segments(entries: &[StoredEntry]) -> Vec<LogSegment> {
+    let mut segments: Vec<LogSegment> = Vec::new();
+    let mut previous: Option<u64> = None;
+    for entry in entries {
+        let timestamp = entry.timestamp_usec;
+        let continues = previous.is_some_and(|previous| {
+            timestamp >= previous && timestamp - previous <= MAX_ENTRY_GAP_USEC
+        });
+        match segments.last_mut() {
+            Some(segment) if continues => {
+                segment.end_usec = timestamp;
+                segment.entries += 1;
+            }
+            _ => segments.push(LogSegment {
+                start_usec: timestamp,
+                end_usec: timestamp,
+                entries: 1,
+            }),
+        }
+        previous = Some(timestamp);
+    }
+    segments
+}
+
 fn extract_fields(msg: &MavMessage) -> (String, HashMap<String, f64>) {
     let name = msg.message_name().to_string();
     let mut fields = HashMap::new();
@@ -701,11 +1361,17 @@ fn extract_fields(msg: &MavMessage) -> (String, HashMap<String, f64>) {
             fields.insert("satellites_visible".into(), d.satellites_visible as f64);
             fields.insert("eph".into(), d.eph as f64 / 100.0);
             fields.insert("epv".into(), d.epv as f64 / 100.0);
+            fields.insert("time_usec".into(), d.time_usec as f64);
+        }
+        MavMessage::SYSTEM_TIME(d) => {
+            fields.insert("time_unix_usec".into(), d.time_unix_usec as f64);
+            fields.insert("time_boot_ms".into(), d.time_boot_ms as f64);
         }
         MavMessage::HEARTBEAT(d) => {
             fields.insert("custom_mode".into(), d.custom_mode as f64);
             fields.insert("base_mode".into(), d.base_mode.bits() as f64);
             fields.insert("system_status".into(), d.system_status as u8 as f64);
+            fields.insert("type".into(), d.mavtype as u8 as f64);
         }
         MavMessage::RC_CHANNELS(d) => {
             for (i, value) in [
@@ -749,6 +1415,21 @@ fn extract_fields(msg: &MavMessage) -> (String, HashMap<String, f64>) {
             fields.insert("energy_consumed".into(), d.energy_consumed as f64);
             fields.insert("battery_remaining".into(), d.battery_remaining as f64);
         }
+        MavMessage::VIBRATION(d) => {
+            fields.insert("vibration_x".into(), d.vibration_x as f64);
+            fields.insert("vibration_y".into(), d.vibration_y as f64);
+            fields.insert("vibration_z".into(), d.vibration_z as f64);
+            fields.insert("clipping_0".into(), d.clipping_0 as f64);
+            fields.insert("clipping_1".into(), d.clipping_1 as f64);
+            fields.insert("clipping_2".into(), d.clipping_2 as f64);
+        }
+        MavMessage::EKF_STATUS_REPORT(d) => {
+            fields.insert("velocity_variance".into(), d.velocity_variance as f64);
+            fields.insert("pos_horiz_variance".into(), d.pos_horiz_variance as f64);
+            fields.insert("pos_vert_variance".into(), d.pos_vert_variance as f64);
+            fields.insert("compass_variance".into(), d.compass_variance as f64);
+            fields.insert("terrain_alt_variance".into(), d.terrain_alt_variance as f64);
+        }
         MavMessage::NAV_CONTROLLER_OUTPUT(d) => {
             fields.insert("nav_roll".into(), d.nav_roll as f64);
             fields.insert("nav_pitch".into(), d.nav_pitch as f64);
@@ -758,6 +1439,59 @@ fn extract_fields(msg: &MavMessage) -> (String, HashMap<String, f64>) {
             fields.insert("alt_error".into(), d.alt_error as f64);
             fields.insert("xtrack_error".into(), d.xtrack_error as f64);
         }
+        MavMessage::LANDING_TARGET(d) => {
+            fields.insert("target_num".into(), d.target_num as f64);
+            fields.insert("angle_x".into(), d.angle_x as f64);
+            fields.insert("angle_y".into(), d.angle_y as f64);
+            fields.insert("distance".into(), d.distance as f64);
+            fields.insert("size_x".into(), d.size_x as f64);
+            fields.insert("size_y".into(), d.size_y as f64);
+            fields.insert("x".into(), d.x as f64);
+            fields.insert("y".into(), d.y as f64);
+            fields.insert("z".into(), d.z as f64);
+            fields.insert("position_valid".into(), d.position_valid as f64);
+        }
+        MavMessage::OPTICAL_FLOW(d) => {
+            fields.insert("sensor_id".into(), d.sensor_id as f64);
+            fields.insert("flow_x".into(), d.flow_x as f64);
+            fields.insert("flow_y".into(), d.flow_y as f64);
+            fields.insert("flow_comp_m_x".into(), d.flow_comp_m_x as f64);
+            fields.insert("flow_comp_m_y".into(), d.flow_comp_m_y as f64);
+            fields.insert("quality".into(), d.quality as f64);
+            fields.insert("ground_distance".into(), d.ground_distance as f64);
+        }
+        MavMessage::OPTICAL_FLOW_RAD(d) => {
+            fields.insert("sensor_id".into(), d.sensor_id as f64);
+            fields.insert("integration_time_us".into(), d.integration_time_us as f64);
+            fields.insert("integrated_x".into(), d.integrated_x as f64);
+            fields.insert("integrated_y".into(), d.integrated_y as f64);
+            fields.insert("integrated_xgyro".into(), d.integrated_xgyro as f64);
+            fields.insert("integrated_ygyro".into(), d.integrated_ygyro as f64);
+            fields.insert("integrated_zgyro".into(), d.integrated_zgyro as f64);
+            fields.insert("temperature".into(), d.temperature as f64 / 100.0);
+            fields.insert("quality".into(), d.quality as f64);
+            fields.insert("distance".into(), d.distance as f64);
+        }
+        // Companion-computer values are keyed by their sender-chosen name so
+        // each stream is plottable as its own field.
+        MavMessage::NAMED_VALUE_FLOAT(d) => {
+            fields.insert(mavlink_name(&d.name[..]), d.value as f64);
+            fields.insert("time_boot_ms".into(), d.time_boot_ms as f64);
+        }
+        MavMessage::NAMED_VALUE_INT(d) => {
+            fields.insert(mavlink_name(&d.name[..]), d.value as f64);
+            fields.insert("time_boot_ms".into(), d.time_boot_ms as f64);
+        }
+        MavMessage::DEBUG_VECT(d) => {
+            let name = mavlink_name(&d.name[..]);
+            fields.insert(format!("{name}.x"), d.x as f64);
+            fields.insert(format!("{name}.y"), d.y as f64);
+            fields.insert(format!("{name}.z"), d.z as f64);
+        }
+        MavMessage::TUNNEL(d) => {
+            fields.insert("payload_type".into(), d.payload_type as u16 as f64);
+            fields.insert("payload_length".into(), d.payload_length as f64);
+        }
         _ => {}
     }
     (name, fields)
@@ -840,6 +1574,61 @@ fn tlog_to_stored(
     })
 }
 
+/// Take the next frame off `reader` if it is for a message outside the
+/// dialect, which the MAVLink reader would skip as a CRC failure. Anything
+/// else is left for the reader.
+fn read_unknown_frame<R: Read>(
+    reader: &mut PeekReader<R>,
+) -> Result<Option<UnknownFrame>, mavlink::error::MessageReadError> {
+    let peek_len = match reader.peek_exact(1)?[0] {
+        MAVLINK_V1_STX => MAVLINK_V1_HEADER_LEN,
+        MAVLINK_V2_STX => MAVLINK_V2_HEADER_LEN,
+        _ => return Ok(None),
+    };
+    let FrameHeader::Frame {
+        system_id,
+        component_id,
+        message_id,
+        header_len,
+        crc_end,
+        total_len,
+    } = frame_header(reader.peek_exact(peek_len)?)
+    else {
+        return Ok(None);
+    };
+    if is_known_message(message_id) {
+        return Ok(None);
+    }
+    let frame = reader.read_exact(total_len)?;
+    Ok(Some(UnknownFrame {
+        system_id,
+        component_id,
+        message_id,
+        payload: frame[header_len..crc_end].to_vec(),
+    }))
+}
+
+/// Messages outside the dialect are kept as opaque entries named by id, with
+/// the payload length as their only field.
+fn unknown_to_stored(sequence: u64, timestamp_usec: u64, frame: UnknownFrame) -> StoredEntry {
+    let fields = HashMap::from([("payload_len".to_string(), frame.payload.len() as f64)]);
+    let field_values = json_fields_from_numeric(&fields);
+    build_stored_entry(StoredEntryParts {
+        sequence,
+        timestamp_usec,
+        msg_name: unknown_message_name(frame.message_id),
+        fields,
+        field_values,
+        raw_payload: Some(frame.payload),
+        system_id: Some(frame.system_id),
+        component_id: Some(frame.component_id),
+    })
+}
+
+fn unknown_message_name(message_id: u32) -> String {
+    format!("UNKNOWN_{message_id}")
+}
+
 fn bin_to_stored(sequence: u64, entry: &ardupilot_binlog::Entry) -> Option<StoredEntry> {
     let timestamp_usec = entry.timestamp_usec?;
     let mut fields = HashMap::new();
@@ -878,17 +1667,19 @@ fn build_store(path: &str, log_type: LogType, entries: Vec<StoredEntry>) -> LogS
         entries,
         type_index,
         playback_cursor_usec: None,
+        tlog_parsed_bytes: None,
     }
 }
 
 fn summarize_entries(path: &str, log_type: LogType, entries: &[StoredEntry]) -> LogSummary {
-    let start_usec = entries.first().map_or(0, |entry| entry.timestamp_usec);
-    let end_usec = entries.last().map_or(0, |entry| entry.timestamp_usec);
-    let duration_secs = if end_usec > start_usec {
-        (end_usec - start_usec) as f64 / 1_000_000.0
-    } else {
-        0.0
-    };
+    let segments = split_segments(entries);
+    let start_usec = segments.iter().map(|s| s.start_usec).min().unwrap_or(0);
+    let end_usec = segments.iter().map(|s| s.end_usec).max().unwrap_or(0);
+    // Gaps between segments are clock errors, not flight time.
+    let duration_secs = segments
+        .iter()
+        .map(|segment| (segment.end_usec - segment.start_usec) as f64 / 1_000_000.0)
+        .sum();
     let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path).to_string();
     let mut message_types = HashMap::new();
     for entry in entries {
@@ -902,6 +1693,9 @@ fn summarize_entries(path: &str, log_type: LogType, entries: &[StoredEntry]) ->
         total_entries: entries.len(),
         message_types,
         log_type,
+        log_id: None,
+        segments,
+        truncated: false,
     }
 }
 
@@ -1026,7 +1820,7 @@ fn in_time_range(timestamp_usec: u64, start_usec: Option<u64>, end_usec: Option<
         && end_usec.is_none_or(|end| timestamp_usec <= end)
 }
 
-fn bounded_max_points(max_points: Option<usize>, default: usize, max: usize) -> usize {
+pub(crate) fn bounded_max_points(max_points: Option<usize>, default: usize, max: usize) -> usize {
     match max_points {
         Some(value) if value > 0 => value.min(max),
         _ => default,
@@ -1042,7 +1836,7 @@ fn bounded_page_limit(limit: u32, default: usize, max: usize) -> usize {
     }
 }
 
-fn downsample_if_needed<T: Clone>(items: Vec<T>, max_points: usize) -> Vec<T> {
+pub(crate) fn downsample_if_needed<T: Clone>(items: Vec<T>, max_points: usize) -> Vec<T> {
     if max_points == 0 || items.len() <= max_points {
         return items;
     }
@@ -1060,6 +1854,7 @@ fn apply_entry(log_type: LogType, snap: &mut TelemetrySnapshot, entry: &StoredEn
     match log_type {
         LogType::Tlog => apply_tlog_entry(snap, entry),
         LogType::Bin => apply_bin_entry(snap, entry),
+        LogType::Ulog => apply_ulog_entry(snap, entry),
     }
 }
 
@@ -1185,7 +1980,87 @@ fn apply_bin_entry(snap: &mut TelemetrySnapshot, entry: &StoredEntry) {
     }
 }
 
-fn haversine_m(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+/// PX4 arming_state value for armed.
+const PX4_ARMING_STATE_ARMED: f64 = 2.0;
+
+fn apply_ulog_entry(snap: &mut TelemetrySnapshot, entry: &StoredEntry) {
+    let f = &entry.fields;
+    match entry.msg_name.as_str() {
+        "vehicle_attitude" => {
+            let q = (0..4)
+                .map(|i| f.get(&format!("q[{i}]")).copied())
+                .collect::<Option<Vec<f64>>>();
+            if let Some([w, x, y, z]) = q.as_deref() {
+                let (w, x, y, z) = (*w, *x, *y, *z);
+                snap.roll_deg = Some(
+                    (2.0 * (w * x + y * z))
+                        .atan2(1.0 - 2.0 * (x * x + y * y))
+                        .to_degrees(),
+                );
+                snap.pitch_deg = Some((2.0 * (w * y - z * x)).clamp(-1.0, 1.0).asin().to_degrees());
+                snap.yaw_deg = Some(
+                    (2.0 * (w * z + x * y))
+                        .atan2(1.0 - 2.0 * (y * y + z * z))
+                        .to_degrees(),
+                );
+            }
+        }
+        "vehicle_global_position" => {
+            snap.latitude_deg = f.get("lat").copied();
+            snap.longitude_deg = f.get("lon").copied();
+            snap.altitude_m = f.get("alt").copied();
+        }
+        "vehicle_local_position" => {
+            // NED: down is positive.
+            snap.climb_rate_mps = f.get("vz").map(|vz| -vz);
+            snap.heading_deg = f
+                .get("heading")
+                .filter(|heading| heading.is_finite())
+                .map(|heading| heading.to_degrees().rem_euclid(360.0));
+            if let (Some(vx), Some(vy)) = (f.get("vx"), f.get("vy")) {
+                snap.speed_mps = Some(vx.hypot(*vy));
+            }
+        }
+        "vehicle_gps_position" => {
+            snap.gps_fix_type = f.get("fix_type").map(|v| gps_fix_type_name(*v).into());
+            snap.gps_satellites = f.get("satellites_used").copied();
+            snap.gps_hdop = f.get("hdop").copied();
+        }
+        "battery_status" => {
+            snap.battery_voltage_v = f.get("voltage_v").copied();
+            snap.battery_current_a = f.get("current_a").copied();
+            snap.battery_pct = f.get("remaining").map(|remaining| remaining * 100.0);
+        }
+        "vehicle_status" => {
+            snap.armed = f
+                .get("arming_state")
+                .map(|state| *state == PX4_ARMING_STATE_ARMED);
+            // PX4's nav_state stands in for the custom mode.
+            snap.custom_mode = f.get("nav_state").map(|state| *state as u32);
+        }
+        "airspeed_validated" => snap.airspeed_mps = f.get("true_airspeed_m_s").copied(),
+        "input_rc" => {
+            let chans: Vec<f64> = (0..8)
+                .filter_map(|i| f.get(&format!("values[{i}]")).copied())
+                .collect();
+            if !chans.is_empty() {
+                snap.rc_channels = Some(chans);
+            }
+            snap.rc_rssi = f.get("rssi").copied();
+        }
+        "actuator_outputs" => {
+            let servos: Vec<f64> = (0..8)
+                .filter_map(|i| f.get(&format!("output[{i}]")).copied())
+                .collect();
+            if !servos.is_empty() {
+                snap.servo_outputs = Some(servos);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn haversine_m(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
     let r = 6_371_000.0;
     let dlat = (lat2 - lat1).to_radians();
     let dlon = (lon2 - lon1).to_radians();
@@ -1366,7 +2241,7 @@ fn write_csv_export(entries: Vec<&StoredEntry>) -> Result<(Vec<u8>, u64), String
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mavkit::dialect::{
+    use crate::dialect::{
         GLOBAL_POSITION_INT_DATA, HEARTBEAT_DATA, MavAutopilot, MavModeFlag, MavState, MavType,
         VFR_HUD_DATA,
     };
@@ -1411,6 +2286,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn extract_fields_covers_precision_landing_messages() {
+        let target = MavMessage::LANDING_TARGET(crate::dialect::LANDING_TARGET_DATA {
+            angle_x: 0.25,
+            angle_y: -0.5,
+            distance: 3.0,
+            ..Default::default()
+        });
+        let (name, fields) = extract_fields(&target);
+        assert_eq!(name, "LANDING_TARGET");
+        assert_close(fields["angle_x"], 0.25);
+        assert_close(fields["distance"], 3.0);
+
+        let flow = MavMessage::OPTICAL_FLOW_RAD(crate::dialect::OPTICAL_FLOW_RAD_DATA {
+            quality: 200,
+            temperature: 2_550,
+            ..Default::default()
+        });
+        let (name, fields) = extract_fields(&flow);
+        assert_eq!(name, "OPTICAL_FLOW_RAD");
+        assert_close(fields["quality"], 200.0);
+        assert_close(fields["temperature"], 25.5);
+    }
+
     #[test]
     fn extract_fields_scales_global_position_int_units() {
         let message = MavMessage::GLOBAL_POSITION_INT(GLOBAL_POSITION_INT_DATA {
@@ -1598,6 +2497,156 @@ mod tests {
         assert_close(snapshot.speed_mps.expect("ground speed"), 11.0);
     }
 
+    #[test]
+    fn seeking_back_rederives_state_from_prior_messages() {
+        let entries = [(100, 42.0, 47.0), (300, 5.0, 48.0)]
+            .into_iter()
+            .enumerate()
+            .flat_map(|(idx, (timestamp_usec, custom_mode, lat))| {
+                let sequence = idx as u64 * 2;
+                [
+                    numeric_entry(
+                        sequence,
+                        timestamp_usec,
+                        "HEARTBEAT",
+                        HashMap::from([
+                            ("custom_mode".to_string(), custom_mode),
+                            ("base_mode".to_string(), 0.0),
+                        ]),
+                    ),
+                    numeric_entry(
+                        sequence + 1,
+                        timestamp_usec + 50,
+                        "GLOBAL_POSITION_INT",
+                        HashMap::from([("lat".to_string(), lat), ("lon".to_string(), 8.0)]),
+                    ),
+                ]
+            })
+            .collect();
+        let mut store = store_from_entries("seek.tlog", LogType::Tlog, entries);
+
+        store.set_playback_cursor_usec(Some(400));
+        assert_eq!(telemetry_at(&store, Some(400)).custom_mode, Some(5));
+        store.set_playback_cursor_usec(Some(200));
+        let snapshot = telemetry_at(&store, store.resolved_playback_cursor_usec());
+
+        assert_eq!(snapshot.custom_mode, Some(42));
+        assert_eq!(snapshot.latitude_deg, Some(47.0));
+    }
+
+    fn clock_jump_entries() -> Vec<StoredEntry> {
+        const HOUR_USEC: u64 = 3_600_000_000;
+        let alt = |sequence, timestamp_usec| {
+            numeric_entry(
+                sequence,
+                timestamp_usec,
+                "VFR_HUD",
+                HashMap::from([("alt".to_string(), sequence as f64)]),
+            )
+        };
+        vec![
+            numeric_entry(
+                0,
+                5 * HOUR_USEC,
+                "SYSTEM_TIME",
+                HashMap::from([("time_unix_usec".to_string(), 1.7e15)]),
+            ),
+            alt(1, 5 * HOUR_USEC + 1_000_000),
+            alt(2, 5 * HOUR_USEC + 2_000_000),
+            // Reconnect with the clock set back a day.
+            numeric_entry(
+                3,
+                1_000_000,
+                "SYSTEM_TIME",
+                HashMap::from([("time_unix_usec".to_string(), 1.7e15 + 10_000_000.0)]),
+            ),
+            alt(4, 2_000_000),
+        ]
+    }
+
+    #[test]
+    fn backwards_clock_splits_the_log_into_segments() {
+        let store = store_from_entries("jump.tlog", LogType::Tlog, clock_jump_entries());
+        let summary = store.summary();
+
+        assert_eq!(summary.segments.len(), 2);
+        assert_eq!(summary.segments[0].entries, 3);
+        assert_eq!(summary.segments[1].start_usec, 1_000_000);
+        assert_close(summary.duration_secs, 3.0);
+        assert_eq!(segment_diagnostics(&summary.segments).len(), 1);
+
+        let all = query_log_messages(&store, "VFR_HUD", None, None, None, None).unwrap();
+        let gaps: Vec<bool> = all.iter().map(|point| point.gap_before).collect();
+        assert_eq!(gaps, [false, false, true]);
+
+        let second = query_log_messages(&store, "VFR_HUD", None, None, None, Some(1)).unwrap();
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].fields["alt"], 4.0);
+        assert!(query_log_messages(&store, "VFR_HUD", None, None, None, Some(2)).is_err());
+    }
+
+    #[test]
+    fn virtual_fields_are_computed_per_point_and_checked_against_the_type() {
+        let entries = [(3.0, 4.0), (6.0, 8.0)]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (vx, vy))| {
+                numeric_entry(
+                    idx as u64,
+                    (idx as u64 + 1) * 1_000_000,
+                    "LOCAL_POSITION_NED",
+                    HashMap::from([("vx".to_string(), vx), ("vy".to_string(), vy)]),
+                )
+            })
+            .collect();
+        let store = store_from_entries("speed.tlog", LogType::Tlog, entries);
+        let mut points =
+            query_log_messages(&store, "LOCAL_POSITION_NED", None, None, None, None).unwrap();
+        let speed = VirtualField {
+            name: "speed".into(),
+            expr: "sqrt(vx^2 + vy^2)".into(),
+        };
+        apply_virtual_fields(&store, "LOCAL_POSITION_NED", &mut points, &[speed]).unwrap();
+        let speeds: Vec<f64> = points.iter().map(|point| point.fields["speed"]).collect();
+        assert_eq!(speeds, [5.0, 10.0]);
+        assert_eq!(points[0].fields["vx"], 3.0);
+
+        let typo = VirtualField {
+            name: "climb".into(),
+            expr: "vx + vz".into(),
+        };
+        let error =
+            apply_virtual_fields(&store, "LOCAL_POSITION_NED", &mut points, &[typo]).unwrap_err();
+        assert_eq!(
+            error,
+            "climb: LOCAL_POSITION_NED has no field `vz` at position 5\n  vx + vz\n       ^"
+        );
+        let unparsable = VirtualField {
+            name: "bad".into(),
+            expr: "vx *".into(),
+        };
+        assert!(
+            apply_virtual_fields(&store, "LOCAL_POSITION_NED", &mut points, &[unparsable])
+                .unwrap_err()
+                .contains("unexpected end of expression at position 4")
+        );
+    }
+
+    #[test]
+    fn repair_timestamps_reanchors_each_segment_on_system_time() {
+        let store = store_from_entries("jump.tlog", LogType::Tlog, clock_jump_entries());
+        let repaired = repair_timestamps(&store).expect("repair");
+
+        assert_eq!(repaired.summary().segments.len(), 1);
+        assert_eq!(repaired.summary().start_usec, 1_700_000_000_000_000);
+        let order: Vec<u64> = repaired.entries().iter().map(|e| e.sequence).collect();
+        assert_eq!(order, [0, 1, 2, 3, 4]);
+        assert_eq!(repaired.entries()[4].timestamp_usec, 1_700_000_011_000_000);
+
+        let plain = store_from_entries("plain.tlog", LogType::Tlog, vec![]);
+        assert!(repair_timestamps(&plain).is_err());
+    }
+
     #[test]
     fn flight_path_points_scale_bin_gps_dege7_values() {
         let store = store_from_entries(
@@ -1771,4 +2820,237 @@ mod tests {
         assert_eq!(vehicle_state.custom_mode, 4);
         assert_eq!(frame.playback.cursor_usec, Some(100));
     }
+
+    fn tlog_bytes(timestamps: &[u64]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for (index, timestamp) in timestamps.iter().enumerate() {
+            bytes.extend_from_slice(&timestamp.to_le_bytes());
+            let heartbeat = MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+                custom_mode: index as u32,
+                ..Default::default()
+            });
+            mavlink::write_v2_msg(&mut bytes, mavlink::MavHeader::default(), &heartbeat)
+                .expect("write v2");
+        }
+        bytes
+    }
+
+    #[test]
+    fn a_partial_final_frame_truncates_the_tlog_instead_of_failing() {
+        let whole = tlog_bytes(&[100, 200, 300]);
+        let parsed = parse_log_bytes("live.tlog", &whole, LogType::Tlog).expect("complete");
+        assert!(!parsed.store.summary().truncated);
+        assert_eq!(parsed.store.tlog_parsed_bytes, Some(whole.len() as u64));
+
+        for cut in [whole.len() - 3, whole.len() - 20] {
+            let parsed = parse_log_bytes("live.tlog", &whole[..cut], LogType::Tlog)
+                .expect("truncated tlog parses");
+            let summary = parsed.store.summary();
+            assert!(summary.truncated);
+            assert_eq!(summary.total_entries, 2);
+            assert_eq!(summary.end_usec, 200);
+            assert!(
+                parsed
+                    .diagnostics
+                    .iter()
+                    .any(|diagnostic| diagnostic.code == "truncated_frame")
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_messages_stay_in_the_tlog_as_opaque_entries() {
+        let mut bytes = tlog_bytes(&[100]);
+        bytes.extend_from_slice(&150_u64.to_le_bytes());
+        // A fork-only message, id 77001, from system 1 component 1.
+        let id = 77_001_u32.to_le_bytes();
+        bytes.extend_from_slice(&[0xFD, 3, 0, 0, 0, 1, 1, id[0], id[1], id[2], 7, 8, 9, 0, 0]);
+        bytes.extend(tlog_bytes(&[200]));
+
+        let parsed = parse_log_bytes("fork.tlog", &bytes, LogType::Tlog).expect("parses");
+        assert!(!parsed.store.summary().truncated);
+        let entries = parsed.store.entries();
+        assert_eq!(entries.len(), 3);
+        let unknown = &entries[1];
+        assert_eq!(unknown.msg_name, "UNKNOWN_77001");
+        assert_eq!(unknown.timestamp_usec, 150);
+        assert_eq!(unknown.raw_payload.as_deref(), Some(&[7, 8, 9][..]));
+        assert_eq!(unknown.fields["payload_len"], 3.0);
+        assert_eq!(
+            (unknown.system_id, unknown.component_id),
+            (Some(1), Some(1))
+        );
+        assert_eq!(entries[2].msg_name, "HEARTBEAT");
+    }
+
+    #[test]
+    fn rotated_segments_parse_as_one_log_and_skip_bad_ones() {
+        let segment = |timestamps: &[u64]| Ok(tlog_bytes(timestamps));
+        let mut last = tlog_bytes(&[700, 800]);
+        last.truncate(last.len() - 3);
+        let segments = vec![
+            ("f-001.tlog".to_string(), segment(&[100, 200, 300])),
+            ("f-002.tlog".to_string(), Err("not found".to_string())),
+            // No frame start at all: read as one partial frame.
+            ("f-003.tlog".to_string(), Ok(vec![0xff; 40])),
+            ("f-004.tlog".to_string(), segment(&[400, 500, 600])),
+            ("f-005.tlog".to_string(), Ok(last)),
+        ];
+        let parsed = parse_tlog_segments("f.tlog-index.json", &segments).expect("parses");
+        let summary = parsed.store.summary();
+        assert_eq!(summary.total_entries, 7);
+        assert_eq!((summary.start_usec, summary.end_usec), (100, 700));
+        assert!(summary.truncated);
+        let sequences: Vec<_> = parsed.store.entries().iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, (0..7).collect::<Vec<_>>());
+        let codes: Vec<_> = parsed.diagnostics.iter().map(|d| d.code.as_str()).collect();
+        assert_eq!(
+            codes,
+            vec!["segment_skipped", "truncated_frame", "truncated_frame"]
+        );
+
+        let unreadable = vec![("f-001.tlog".to_string(), Err("not found".to_string()))];
+        assert!(parse_tlog_segments("f.tlog-index.json", &unreadable).is_err());
+    }
+
+    #[test]
+    fn following_appends_the_tail_from_the_last_complete_frame() {
+        let whole = tlog_bytes(&[100, 200, 300, 400]);
+        let cut = whole.len() - 5;
+        let mut store = parse_log_bytes("live.tlog", &whole[..cut], LogType::Tlog)
+            .expect("truncated tlog")
+            .store;
+        store.summary.log_id = Some("log-1".into());
+        let offset = store.tlog_parsed_bytes.expect("tlog offset") as usize;
+
+        let chunk =
+            parse_tlog_chunk(&whole[offset..], store.entries().len() as u64).expect("tail parses");
+        assert_eq!(chunk.entries.len(), 1);
+        store.append_tlog_chunk(chunk);
+
+        let summary = store.summary();
+        assert!(!summary.truncated);
+        assert_eq!(summary.total_entries, 4);
+        assert_eq!(summary.end_usec, 400);
+        assert_eq!(summary.log_id.as_deref(), Some("log-1"));
+        assert_eq!(store.tlog_parsed_bytes, Some(whole.len() as u64));
+        assert_eq!(store.type_index()["HEARTBEAT"], vec![0, 1, 2, 3]);
+        assert_eq!(store.entries()[3].sequence, 3);
+    }
+
+    const ULOG_BASIC: &[u8] = include_bytes!("../tests/fixtures/ulog/basic.ulg");
+
+    #[test]
+    fn ulog_topics_become_entries_in_time_order() {
+        let parsed = parse_log_bytes("flight.ulg", ULOG_BASIC, LogType::Ulog).expect("ulog");
+        let store = parsed.store;
+        let summary = store.summary();
+        assert_eq!(summary.log_type, LogType::Ulog);
+        assert!(summary.truncated);
+        assert_eq!(summary.end_usec, 2_200_000);
+        assert!(
+            store
+                .entries()
+                .windows(2)
+                .all(|pair| pair[0].timestamp_usec <= pair[1].timestamp_usec)
+        );
+        assert_eq!(store.type_index()["battery_status"].len(), 1);
+        assert_eq!(store.type_index()["battery_status[1]"].len(), 1);
+
+        let esc = &store.entries()[store.type_index()["esc_status"][0]];
+        assert_eq!(esc.fields["esc[1].esc_rpm"], 5200.0);
+        assert_eq!(esc.field_values["name"], JsonValue::from("quad"));
+        assert!(!esc.fields.contains_key("name"));
+
+        let codes: Vec<&str> = parsed
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.code.as_str())
+            .collect();
+        assert!(codes.contains(&"ulog_dropouts"));
+        assert!(codes.contains(&"truncated_frame"));
+    }
+
+    #[test]
+    fn params_and_status_texts_come_from_ulog_records() {
+        let store = parse_log_bytes("flight.ulg", ULOG_BASIC, LogType::Ulog)
+            .expect("ulog")
+            .store;
+        assert_eq!(
+            log_params(&store).expect("params"),
+            vec![
+                LogParam {
+                    name: "MPC_XY_VEL_MAX".into(),
+                    value: 8.0,
+                },
+                LogParam {
+                    name: "SYS_AUTOSTART".into(),
+                    value: 4001.0,
+                },
+            ]
+        );
+        assert_eq!(
+            log_status_texts(&store).expect("texts"),
+            vec![
+                LogStatusText {
+                    timestamp_usec: 2_050_000,
+                    severity: 4,
+                    text: "Takeoff detected".into(),
+                },
+                LogStatusText {
+                    timestamp_usec: 2_150_000,
+                    severity: 3,
+                    text: "Battery low".into(),
+                },
+            ]
+        );
+
+        let bin = LogStore::from_entries("flight.bin", LogType::Bin, Vec::new());
+        assert!(log_params(&bin).is_err());
+        assert!(log_status_texts(&bin).is_err());
+    }
+
+    #[test]
+    fn vtol_events_keep_only_state_changes() {
+        use crate::ipc::{LandedState, VtolState};
+
+        let payloads: [&[u8]; 4] = [&[3, 1], &[3, 2], &[3, 2], &[4, 2]];
+        let entries = payloads
+            .iter()
+            .enumerate()
+            .map(|(i, payload)| {
+                stored_entry_with_values(
+                    i as u64,
+                    i as u64 * 1_000_000,
+                    "EXTENDED_SYS_STATE",
+                    HashMap::new(),
+                    BTreeMap::new(),
+                    Some(payload.to_vec()),
+                )
+            })
+            .collect();
+        let store = LogStore::from_entries("flight.tlog", LogType::Tlog, entries);
+        let events = log_vtol_events(&store).expect("events");
+        let timeline: Vec<_> = events
+            .iter()
+            .map(|event| {
+                (
+                    event.timestamp_usec,
+                    event.status.vtol_state,
+                    event.status.landed_state,
+                )
+            })
+            .collect();
+        assert_eq!(
+            timeline,
+            [
+                (0, VtolState::MultiCopter, LandedState::OnGround),
+                (1_000_000, VtolState::MultiCopter, LandedState::InAir),
+                (3_000_000, VtolState::FixedWing, LandedState::InAir),
+            ]
+        );
+
+        let bin = LogStore::from_entries("flight.bin", LogType::Bin, Vec::new());
+        assert!(log_vtol_events(&bin).is_err());
+    }
 }
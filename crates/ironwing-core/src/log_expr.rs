@@ -0,0 +1,486 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// A parsed arithmetic expression over the fields of one log message, such as
+/// `sqrt(vx^2 + vy^2)`. Supports `+ - * / ^`, unary minus, parentheses and
+/// `sqrt`, `abs`, `min`, `max`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    root: Node,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f64),
+    Field { name: String, position: usize },
+    Neg(Box<Node>),
+    Binary(BinaryOp, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Sqrt,
+    Abs,
+    Min,
+    Max,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sqrt" => Some(Self::Sqrt),
+            "abs" => Some(Self::Abs),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            _ => None,
+        }
+    }
+
+    fn arity(self) -> usize {
+        match self {
+            Self::Sqrt | Self::Abs => 1,
+            Self::Min | Self::Max => 2,
+        }
+    }
+}
+
+/// Why an expression did not parse, or names a field the message lacks.
+/// `position` is the character offset the problem starts at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExprError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl ExprError {
+    /// The message, the expression, and a caret under `position`.
+    pub fn describe(&self, source: &str) -> String {
+        format!(
+            "{self}\n  {source}\n  {}^",
+            " ".repeat(self.position.min(source.chars().count()))
+        )
+    }
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+    Comma,
+    Open,
+    Close,
+}
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, ExprError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let start = pos;
+        let ch = chars[pos];
+        let token = match ch {
+            _ if ch.is_whitespace() => {
+                pos += 1;
+                continue;
+            }
+            '+' | '-' | '*' | '/' | '^' => {
+                pos += 1;
+                Token::Op(ch)
+            }
+            ',' => {
+                pos += 1;
+                Token::Comma
+            }
+            '(' => {
+                pos += 1;
+                Token::Open
+            }
+            ')' => {
+                pos += 1;
+                Token::Close
+            }
+            _ if ch.is_ascii_digit() || ch == '.' => {
+                while pos < chars.len() && (chars[pos].is_ascii_digit() || chars[pos] == '.') {
+                    pos += 1;
+                }
+                if pos < chars.len() && matches!(chars[pos], 'e' | 'E') {
+                    let mut exponent = pos + 1;
+                    if exponent < chars.len() && matches!(chars[exponent], '+' | '-') {
+                        exponent += 1;
+                    }
+                    if exponent < chars.len() && chars[exponent].is_ascii_digit() {
+                        pos = exponent;
+                        while pos < chars.len() && chars[pos].is_ascii_digit() {
+                            pos += 1;
+                        }
+                    }
+                }
+                let text: String = chars[start..pos].iter().collect();
+                let value = text.parse().map_err(|_| ExprError {
+                    position: start,
+                    message: format!("invalid number `{text}`"),
+                })?;
+                Token::Number(value)
+            }
+            // Field names may carry ULog array and struct paths: `q[0]`,
+            // `esc[1].esc_rpm`.
+            _ if ch.is_alphabetic() || ch == '_' => {
+                while pos < chars.len()
+                    && (chars[pos].is_alphanumeric() || matches!(chars[pos], '_' | '.' | '[' | ']'))
+                {
+                    pos += 1;
+                }
+                Token::Ident(chars[start..pos].iter().collect())
+            }
+            _ => {
+                return Err(ExprError {
+                    position: start,
+                    message: format!("unexpected character `{ch}`"),
+                });
+            }
+        };
+        tokens.push((start, token));
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(_, token)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.next)
+            .map_or(self.end, |(position, _)| *position)
+    }
+
+    fn error(&self, message: impl Into<String>) -> ExprError {
+        ExprError {
+            position: self.position(),
+            message: message.into(),
+        }
+    }
+
+    fn expect(&mut self, expected: &Token, what: &str) -> Result<(), ExprError> {
+        if self.peek() == Some(expected) {
+            self.next += 1;
+            Ok(())
+        } else {
+            Err(self.error(format!("expected {what}")))
+        }
+    }
+
+    fn sum(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.product()?;
+        while let Some(Token::Op(op @ ('+' | '-'))) = self.peek() {
+            let op = if *op == '+' {
+                BinaryOp::Add
+            } else {
+                BinaryOp::Sub
+            };
+            self.next += 1;
+            node = Node::Binary(op, Box::new(node), Box::new(self.product()?));
+        }
+        Ok(node)
+    }
+
+    fn product(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.unary()?;
+        while let Some(Token::Op(op @ ('*' | '/'))) = self.peek() {
+            let op = if *op == '*' {
+                BinaryOp::Mul
+            } else {
+                BinaryOp::Div
+            };
+            self.next += 1;
+            node = Node::Binary(op, Box::new(node), Box::new(self.unary()?));
+        }
+        Ok(node)
+    }
+
+    /// Unary minus binds looser than `^`, so `-x^2` is `-(x^2)`.
+    fn unary(&mut self) -> Result<Node, ExprError> {
+        match self.peek() {
+            Some(Token::Op('-')) => {
+                self.next += 1;
+                Ok(Node::Neg(Box::new(self.unary()?)))
+            }
+            Some(Token::Op('+')) => {
+                self.next += 1;
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    /// `^` is right-associative: `2^3^2` is `2^9`.
+    fn power(&mut self) -> Result<Node, ExprError> {
+        let base = self.primary()?;
+        if self.peek() == Some(&Token::Op('^')) {
+            self.next += 1;
+            let exponent = self.unary()?;
+            return Ok(Node::Binary(
+                BinaryOp::Pow,
+                Box::new(base),
+                Box::new(exponent),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Node, ExprError> {
+        let position = self.position();
+        let Some(token) = self.peek().cloned() else {
+            return Err(self.error("unexpected end of expression"));
+        };
+        self.next += 1;
+        match token {
+            Token::Number(value) => Ok(Node::Number(value)),
+            Token::Open => {
+                let node = self.sum()?;
+                self.expect(&Token::Close, "`)`")?;
+                Ok(node)
+            }
+            Token::Ident(name) if self.peek() == Some(&Token::Open) => {
+                let function = Function::from_name(&name).ok_or_else(|| ExprError {
+                    position,
+                    message: format!("unknown function `{name}`"),
+                })?;
+                self.next += 1;
+                let mut args = vec![self.sum()?];
+                while self.peek() == Some(&Token::Comma) {
+                    self.next += 1;
+                    args.push(self.sum()?);
+                }
+                self.expect(&Token::Close, "`,` or `)`")?;
+                if args.len() != function.arity() {
+                    return Err(ExprError {
+                        position,
+                        message: format!(
+                            "`{name}` takes {} argument(s), got {}",
+                            function.arity(),
+                            args.len()
+                        ),
+                    });
+                }
+                Ok(Node::Call(function, args))
+            }
+            Token::Ident(name) => Ok(Node::Field { name, position }),
+            _ => {
+                self.next -= 1;
+                Err(self.error("expected a number, field, or `(`"))
+            }
+        }
+    }
+}
+
+/// Parse `source` into an expression.
+pub fn parse_expr(source: &str) -> Result<Expr, ExprError> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        tokens,
+        next: 0,
+        end: source.chars().count(),
+    };
+    let root = parser.sum()?;
+    if parser.peek().is_some() {
+        return Err(parser.error("expected an operator"));
+    }
+    Ok(Expr { root })
+}
+
+impl Expr {
+    /// Every field reference with its position, in source order.
+    pub fn fields(&self) -> Vec<(&str, usize)> {
+        fn walk<'a>(node: &'a Node, out: &mut Vec<(&'a str, usize)>) {
+            match node {
+                Node::Number(_) => {}
+                Node::Field { name, position } => out.push((name, *position)),
+                Node::Neg(inner) => walk(inner, out),
+                Node::Binary(_, left, right) => {
+                    walk(left, out);
+                    walk(right, out);
+                }
+                Node::Call(_, args) => args.iter().for_each(|arg| walk(arg, out)),
+            }
+        }
+        let mut out = Vec::new();
+        walk(&self.root, &mut out);
+        out
+    }
+
+    /// The value for one message, or `None` when it lacks a referenced
+    /// field. Division by zero and the like give IEEE infinities and NaN.
+    pub fn eval(&self, fields: &HashMap<String, f64>) -> Option<f64> {
+        fn eval(node: &Node, fields: &HashMap<String, f64>) -> Option<f64> {
+            Some(match node {
+                Node::Number(value) => *value,
+                Node::Field { name, .. } => *fields.get(name)?,
+                Node::Neg(inner) => -eval(inner, fields)?,
+                Node::Binary(op, left, right) => {
+                    let (left, right) = (eval(left, fields)?, eval(right, fields)?);
+                    match op {
+                        BinaryOp::Add => left + right,
+                        BinaryOp::Sub => left - right,
+                        BinaryOp::Mul => left * right,
+                        BinaryOp::Div => left / right,
+                        BinaryOp::Pow => left.powf(right),
+                    }
+                }
+                Node::Call(function, args) => {
+                    let first = eval(&args[0], fields)?;
+                    match function {
+                        Function::Sqrt => first.sqrt(),
+                        Function::Abs => first.abs(),
+                        Function::Min => first.min(eval(&args[1], fields)?),
+                        Function::Max => first.max(eval(&args[1], fields)?),
+                    }
+                }
+            })
+        }
+        eval(&self.root, fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(source: &str, fields: &[(&str, f64)]) -> f64 {
+        let fields = fields
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect();
+        parse_expr(source)
+            .expect("parses")
+            .eval(&fields)
+            .expect("all fields present")
+    }
+
+    /// A spread of values including zero, negatives, fractions and large
+    /// magnitudes for the property checks below.
+    const SAMPLES: [f64; 9] = [0.0, 1.0, -1.0, 0.5, -2.25, 3.0, 17.0, -1e3, 6.02e5];
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(eval("1 + 2 * 3", &[]), 7.0);
+        assert_eq!(eval("(1 + 2) * 3", &[]), 9.0);
+        assert_eq!(eval("8 / 4 / 2", &[]), 1.0);
+        assert_eq!(eval("10 - 4 - 3", &[]), 3.0);
+        assert_eq!(eval("2 ^ 3 ^ 2", &[]), 512.0);
+        assert_eq!(eval("-2 ^ 2", &[]), -4.0);
+        assert_eq!(eval("2 ^ -1", &[]), 0.5);
+        assert_eq!(eval("1.5e2 + .5", &[]), 150.5);
+    }
+
+    #[test]
+    fn fields_and_functions() {
+        let fields = [
+            ("vx", 3.0),
+            ("vy", -4.0),
+            ("q[0]", 0.5),
+            ("esc[1].esc_rpm", 5200.0),
+        ];
+        assert_eq!(eval("sqrt(vx^2 + vy^2)", &fields), 5.0);
+        assert_eq!(eval("abs(vy) * q[0]", &fields), 2.0);
+        assert_eq!(eval("max(vx, vy) - min(vx, vy)", &fields), 7.0);
+        assert_eq!(eval("esc[1].esc_rpm / 60", &fields), 5200.0 / 60.0);
+
+        let expr = parse_expr("vx * missing").expect("parses");
+        assert_eq!(expr.fields(), vec![("vx", 0), ("missing", 5)]);
+        assert_eq!(expr.eval(&HashMap::from([("vx".into(), 1.0)])), None);
+    }
+
+    #[test]
+    fn arithmetic_identities_hold_for_every_sample() {
+        for a in SAMPLES {
+            for b in SAMPLES {
+                let fields = [("a", a), ("b", b)];
+                assert_eq!(eval("a + b", &fields), eval("b + a", &fields));
+                assert_eq!(eval("a * b", &fields), eval("b * a", &fields));
+                assert_eq!(eval("a - b", &fields), -eval("b - a", &fields));
+                assert_eq!(eval("min(a, b)", &fields), eval("-max(-a, -b)", &fields));
+                assert_eq!(
+                    eval("abs(a * b)", &fields),
+                    eval("abs(a) * abs(b)", &fields)
+                );
+                assert_eq!(eval("a + 0 * b", &fields), a);
+                assert_eq!(eval("--a", &fields), a);
+                assert_eq!(eval("sqrt(a^2)", &fields), a.abs());
+            }
+        }
+    }
+
+    #[test]
+    fn whitespace_and_redundant_parentheses_do_not_change_the_value() {
+        let forms = [
+            "a*b+a/3-b^2",
+            " a * b + a / 3 - b ^ 2 ",
+            "((a*b)+(a/3))-(b^2)",
+            "(a*(b))+((a)/3)-((b)^(2))",
+        ];
+        for a in SAMPLES {
+            for b in SAMPLES {
+                let fields = [("a", a), ("b", b)];
+                let expected = a * b + a / 3.0 - b * b;
+                for form in forms {
+                    assert_eq!(eval(form, &fields), expected, "{form} with a={a} b={b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_position() {
+        let cases = [
+            ("vx +", 4, "unexpected end of expression"),
+            ("vx $ 2", 3, "unexpected character `$`"),
+            ("(vx + 1", 7, "expected `)`"),
+            ("vx vy", 3, "expected an operator"),
+            ("hypot(vx, vy)", 0, "unknown function `hypot`"),
+            ("1 + sqrt(vx, vy)", 4, "`sqrt` takes 1 argument(s), got 2"),
+            ("2 * )", 4, "expected a number, field, or `(`"),
+            ("1..2", 0, "invalid number `1..2`"),
+        ];
+        for (source, position, message) in cases {
+            let error = parse_expr(source).expect_err(source);
+            assert_eq!(
+                (error.position, error.message.as_str()),
+                (position, message),
+                "{source}"
+            );
+        }
+
+        let error = parse_expr("vx +").unwrap_err();
+        assert_eq!(
+            error.describe("vx +"),
+            "unexpected end of expression at position 4\n  vx +\n      ^"
+        );
+    }
+}
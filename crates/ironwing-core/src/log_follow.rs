@@ -0,0 +1,53 @@
+use std::time::{Duration, Instant};
+
+/// How often a followed tlog's tail is re-read.
+pub const LOG_FOLLOW_INTERVAL: Duration = Duration::from_secs(2);
+/// Following stops once the file has not grown for this long.
+pub const LOG_FOLLOW_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Watches a followed file's length and says when the recording has
+/// stopped writing to it.
+#[derive(Debug, Clone)]
+pub struct LogFollowIdle {
+    len: u64,
+    grew_at: Instant,
+}
+
+impl LogFollowIdle {
+    pub fn new(len: u64, now: Instant) -> Self {
+        Self { len, grew_at: now }
+    }
+
+    /// Record the file's current length; `false` once it has stayed the
+    /// same (or shrunk) for `LOG_FOLLOW_IDLE_TIMEOUT`.
+    pub fn observe(&mut self, len: u64, now: Instant) -> bool {
+        if len > self.len {
+            self.len = len;
+            self.grew_at = now;
+        }
+        now.duration_since(self.grew_at) < LOG_FOLLOW_IDLE_TIMEOUT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn following_stops_thirty_seconds_after_the_last_growth() {
+        let start = Instant::now();
+        let mut idle = LogFollowIdle::new(100, start);
+        assert!(idle.observe(100, start + Duration::from_secs(20)));
+        assert!(idle.observe(150, start + Duration::from_secs(25)));
+        assert!(idle.observe(150, start + Duration::from_secs(54)));
+        assert!(!idle.observe(150, start + Duration::from_secs(55)));
+    }
+
+    #[test]
+    fn a_shrinking_file_does_not_count_as_growth() {
+        let start = Instant::now();
+        let mut idle = LogFollowIdle::new(100, start);
+        assert!(idle.observe(40, start + Duration::from_secs(10)));
+        assert!(!idle.observe(90, start + LOG_FOLLOW_IDLE_TIMEOUT));
+    }
+}
@@ -0,0 +1,307 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use mavkit::GeoPoint3d;
+
+use crate::ipc::{MapBounds, MapPrefetchArea, MapPrefetchRequest};
+use crate::mission_edit::item_position;
+
+/// Most tiles one prefetch may fetch; tile servers' usage policies forbid
+/// bulk scraping, and a field tablet has no use for more.
+pub const MAP_PREFETCH_MAX_TILES: usize = 20_000;
+pub const MAP_PREFETCH_MAX_ZOOM: u8 = 19;
+/// Downloads in flight at once.
+pub const MAP_PREFETCH_CONCURRENCY: usize = 4;
+/// Spacing between request starts across all workers (at most 10 per second).
+pub const MAP_PREFETCH_REQUEST_INTERVAL: Duration = Duration::from_millis(100);
+pub const MAP_PREFETCH_MAX_ATTEMPTS: u32 = 3;
+pub const MAP_PREFETCH_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+pub const MAP_PREFETCH_DEFAULT_CORRIDOR_M: f64 = 200.0;
+/// Records the size of every stored tile so an interrupted run can be
+/// resumed without trusting half-written files.
+pub const MAP_PREFETCH_MANIFEST: &str = "prefetch-manifest.json";
+
+/// Web Mercator's latitude limit.
+const MAX_LATITUDE_DEG: f64 = 85.051_128_78;
+const METRES_PER_DEGREE_LAT: f64 = 111_320.0;
+const EQUATOR_M: f64 = 40_075_016.686;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TileId {
+    pub z: u8,
+    pub x: u32,
+    pub y: u32,
+}
+
+/// The slippy-map tile holding `(latitude_deg, longitude_deg)` at `zoom`.
+pub fn tile_for(latitude_deg: f64, longitude_deg: f64, zoom: u8) -> TileId {
+    let n = f64::from(1_u32 << zoom);
+    let latitude = latitude_deg
+        .clamp(-MAX_LATITUDE_DEG, MAX_LATITUDE_DEG)
+        .to_radians();
+    let x = ((longitude_deg + 180.0) / 360.0 * n).floor();
+    let y = ((1.0 - latitude.tan().asinh() / std::f64::consts::PI) / 2.0 * n).floor();
+    let max = n - 1.0;
+    TileId {
+        z: zoom,
+        x: x.clamp(0.0, max) as u32,
+        y: y.clamp(0.0, max) as u32,
+    }
+}
+
+fn add_bounds(tiles: &mut BTreeSet<TileId>, bounds: &MapBounds, zoom: u8) {
+    let north_west = tile_for(bounds.north_deg, bounds.west_deg, zoom);
+    let south_east = tile_for(bounds.south_deg, bounds.east_deg, zoom);
+    for x in north_west.x..=south_east.x {
+        for y in north_west.y..=south_east.y {
+            tiles.insert(TileId { z: zoom, x, y });
+            if tiles.len() > MAP_PREFETCH_MAX_TILES {
+                return;
+            }
+        }
+    }
+}
+
+fn around(latitude_deg: f64, longitude_deg: f64, corridor_m: f64) -> MapBounds {
+    let dlat = corridor_m / METRES_PER_DEGREE_LAT;
+    let dlon = corridor_m / (METRES_PER_DEGREE_LAT * latitude_deg.to_radians().cos().max(0.01));
+    MapBounds {
+        south_deg: latitude_deg - dlat,
+        west_deg: longitude_deg - dlon,
+        north_deg: latitude_deg + dlat,
+        east_deg: longitude_deg + dlon,
+    }
+}
+
+/// Tiles within `corridor_m` of the route through `points`. Each leg is
+/// sampled at most half a tile apart and the square around every sample is
+/// covered, which over-covers the corner of a turn slightly.
+fn add_route(tiles: &mut BTreeSet<TileId>, points: &[(f64, f64)], corridor_m: f64, zoom: u8) {
+    let corridor_m = corridor_m.max(0.0);
+    for (index, &(lat, lon)) in points.iter().enumerate() {
+        let (next_lat, next_lon) = points.get(index + 1).copied().unwrap_or((lat, lon));
+        let tile_m = EQUATOR_M * lat.to_radians().cos() / f64::from(1_u32 << zoom);
+        let step_m = (tile_m / 2.0).max(1.0);
+        let (east, north) = crate::geo::local_offset_m(lat, lon, next_lat, next_lon);
+        let steps = (east.hypot(north) / step_m).ceil().max(1.0) as usize;
+        for step in 0..=steps {
+            let t = step as f64 / steps as f64;
+            let bounds = around(
+                lat + (next_lat - lat) * t,
+                lon + (next_lon - lon) * t,
+                corridor_m,
+            );
+            add_bounds(tiles, &bounds, zoom);
+            if tiles.len() > MAP_PREFETCH_MAX_TILES {
+                return;
+            }
+        }
+    }
+}
+
+fn latitude_longitude(position: &GeoPoint3d) -> (f64, f64) {
+    match position {
+        GeoPoint3d::Msl(point) => (point.latitude_deg, point.longitude_deg),
+        GeoPoint3d::RelHome(point) => (point.latitude_deg, point.longitude_deg),
+        GeoPoint3d::Terrain(point) => (point.latitude_deg, point.longitude_deg),
+    }
+}
+
+fn check_template(template: &str) -> Result<(), String> {
+    if !(template.starts_with("https://") || template.starts_with("http://")) {
+        return Err("tile URL must be http or https".to_string());
+    }
+    for placeholder in ["{z}", "{x}", "{y}"] {
+        if !template.contains(placeholder) {
+            return Err(format!("tile URL is missing {placeholder}"));
+        }
+    }
+    Ok(())
+}
+
+/// Every tile `request` asks for, lowest zoom first. Fails on an invalid
+/// request or one over `MAP_PREFETCH_MAX_TILES`.
+pub fn prefetch_tiles(request: &MapPrefetchRequest) -> Result<Vec<TileId>, String> {
+    check_template(&request.tile_url_template)?;
+    if request.min_zoom > request.max_zoom || request.max_zoom > MAP_PREFETCH_MAX_ZOOM {
+        return Err(format!(
+            "zoom range must be ascending and at most {MAP_PREFETCH_MAX_ZOOM}"
+        ));
+    }
+    let mut tiles = BTreeSet::new();
+    for zoom in request.min_zoom..=request.max_zoom {
+        match &request.area {
+            MapPrefetchArea::Bbox { bounds } => {
+                if bounds.south_deg > bounds.north_deg || bounds.west_deg > bounds.east_deg {
+                    return Err("bounding box corners are swapped".to_string());
+                }
+                add_bounds(&mut tiles, bounds, zoom);
+            }
+            MapPrefetchArea::Mission { plan, corridor_m } => {
+                let points: Vec<_> = plan
+                    .items
+                    .iter()
+                    .filter_map(item_position)
+                    .map(|position| latitude_longitude(&position))
+                    .filter(|(lat, lon)| *lat != 0.0 || *lon != 0.0)
+                    .collect();
+                if points.is_empty() {
+                    return Err("the mission has no positioned items".to_string());
+                }
+                add_route(
+                    &mut tiles,
+                    &points,
+                    corridor_m.unwrap_or(MAP_PREFETCH_DEFAULT_CORRIDOR_M),
+                    zoom,
+                );
+            }
+        }
+        if tiles.len() > MAP_PREFETCH_MAX_TILES {
+            return Err(format!(
+                "more than {MAP_PREFETCH_MAX_TILES} tiles; narrow the area or the zoom range"
+            ));
+        }
+    }
+    // BTreeSet orders by zoom first, so coarse tiles are fetched first.
+    Ok(tiles.into_iter().collect())
+}
+
+pub fn tile_url(template: &str, tile: TileId) -> String {
+    template
+        .replace("{z}", &tile.z.to_string())
+        .replace("{x}", &tile.x.to_string())
+        .replace("{y}", &tile.y.to_string())
+}
+
+/// The file extension of the tiles `template` serves, `png` when the URL
+/// does not say.
+pub fn tile_extension(template: &str) -> &str {
+    let path = template.split(['?', '#']).next().unwrap_or(template);
+    let last = path.rsplit('/').next().unwrap_or(path);
+    match last.rsplit_once('.') {
+        Some((_, extension))
+            if !extension.is_empty()
+                && extension.len() <= 4
+                && extension.chars().all(|c| c.is_ascii_alphanumeric()) =>
+        {
+            extension
+        }
+        _ => "png",
+    }
+}
+
+/// `z/x/y.ext`, relative to the destination directory.
+pub fn tile_relative_path(tile: TileId, extension: &str) -> String {
+    format!("{}/{}/{}.{extension}", tile.z, tile.x, tile.y)
+}
+
+/// Sizes of the tiles a destination directory holds, keyed by
+/// [`tile_relative_path`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PrefetchManifest {
+    pub tiles: BTreeMap<String, u64>,
+}
+
+impl PrefetchManifest {
+    /// Whether the tile at `path` was stored whole by an earlier run.
+    pub fn is_complete(&self, path: &str, size_on_disk: Option<u64>) -> bool {
+        size_on_disk.is_some_and(|size| self.tiles.get(path) == Some(&size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bbox_request(bounds: MapBounds, min_zoom: u8, max_zoom: u8) -> MapPrefetchRequest {
+        MapPrefetchRequest {
+            area: MapPrefetchArea::Bbox { bounds },
+            min_zoom,
+            max_zoom,
+            tile_url_template: "https://tile.example.org/{z}/{x}/{y}.png".to_string(),
+            dest_dir: "/tmp/tiles".to_string(),
+        }
+    }
+
+    #[test]
+    fn tile_coordinates_match_the_slippy_map_scheme() {
+        assert_eq!(tile_for(0.0, 0.0, 0), TileId { z: 0, x: 0, y: 0 });
+        // Zurich at zoom 10, as served by OpenStreetMap.
+        assert_eq!(
+            tile_for(47.3769, 8.5417, 10),
+            TileId {
+                z: 10,
+                x: 536,
+                y: 358
+            }
+        );
+        assert_eq!(tile_for(89.0, 180.0, 2), TileId { z: 2, x: 3, y: 0 });
+    }
+
+    #[test]
+    fn bbox_and_caps() {
+        let bounds = MapBounds {
+            south_deg: 47.37,
+            west_deg: 8.53,
+            north_deg: 47.38,
+            east_deg: 8.55,
+        };
+        let tiles = prefetch_tiles(&bbox_request(bounds, 10, 12)).unwrap();
+        assert_eq!(tiles.first().map(|tile| tile.z), Some(10));
+        assert!(tiles.iter().all(|tile| (10..=12).contains(&tile.z)));
+
+        let world = MapBounds {
+            south_deg: -80.0,
+            west_deg: -179.0,
+            north_deg: 80.0,
+            east_deg: 179.0,
+        };
+        let error = prefetch_tiles(&bbox_request(world, 0, 12)).unwrap_err();
+        assert!(error.contains("more than"));
+
+        let mut bad = bbox_request(bounds, 10, 12);
+        bad.tile_url_template = "https://tile.example.org/{z}/{x}.png".to_string();
+        assert!(prefetch_tiles(&bad).is_err());
+    }
+
+    #[test]
+    fn route_corridor_covers_the_legs_between_waypoints() {
+        let mut tiles = BTreeSet::new();
+        // A 5 km leg east along 47.4 N with a 100 m corridor at zoom 15
+        // (tiles about 820 m wide there).
+        add_route(&mut tiles, &[(47.4, 8.5), (47.4, 8.566)], 100.0, 15);
+        let start = tile_for(47.4, 8.5, 15);
+        let end = tile_for(47.4, 8.566, 15);
+        for x in start.x..=end.x {
+            assert!(tiles.contains(&TileId {
+                z: 15,
+                x,
+                y: start.y
+            }));
+        }
+        assert!(tiles.len() < 3 * (end.x - start.x + 1) as usize);
+    }
+
+    #[test]
+    fn paths_and_resume() {
+        let tile = TileId { z: 3, x: 4, y: 2 };
+        let template = "https://tile.example.org/{z}/{x}/{y}.jpg?key=abc";
+        assert_eq!(
+            tile_url(template, tile),
+            "https://tile.example.org/3/4/2.jpg?key=abc"
+        );
+        assert_eq!(tile_extension(template), "jpg");
+        assert_eq!(
+            tile_extension("https://tiles.example.org/{z}/{x}/{y}"),
+            "png"
+        );
+        assert_eq!(tile_relative_path(tile, "jpg"), "3/4/2.jpg");
+
+        let mut manifest = PrefetchManifest::default();
+        manifest.tiles.insert("3/4/2.jpg".to_string(), 1234);
+        assert!(manifest.is_complete("3/4/2.jpg", Some(1234)));
+        // A write cut short leaves a different size behind.
+        assert!(!manifest.is_complete("3/4/2.jpg", Some(512)));
+        assert!(!manifest.is_complete("3/4/3.jpg", Some(1234)));
+    }
+}
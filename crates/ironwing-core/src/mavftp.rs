@@ -0,0 +1,365 @@
+//! MAVLink FTP (FILE_TRANSFER_PROTOCOL) packet codec and the ArduPilot paths
+//! the script and firmware helpers work with.
+
+use std::fmt;
+
+use crate::component_command::ComponentCommand;
+use crate::dialect::{MavCmd, STORAGE_INFORMATION_DATA, StorageStatus};
+
+pub const FILE_TRANSFER_PROTOCOL_MESSAGE_ID: u32 = 110;
+pub const FTP_PAYLOAD_LEN: usize = 251;
+const HEADER_LEN: usize = 12;
+/// Data bytes one packet carries.
+pub const FTP_DATA_MAX: usize = FTP_PAYLOAD_LEN - HEADER_LEN;
+
+pub const SCRIPTS_DIR: &str = "/APM/scripts";
+/// ArduPilot flashes this file from the root of the SD card on the next boot
+/// and renames it to `ardupilot-flashed.abin`.
+pub const FIRMWARE_STAGE_PATH: &str = "/ardupilot.abin";
+pub const STORAGE_INFORMATION_MESSAGE_ID: u32 = 261;
+const BYTES_PER_MIB: f64 = 1024.0 * 1024.0;
+const ENOSPC: u8 = 28;
+pub const SCRIPTING_DISABLED: &str = "scripting not enabled \u{2014} set SCR_ENABLE=1 and reboot";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum FtpOpcode {
+    TerminateSession = 1,
+    ListDirectory = 3,
+    OpenFileRo = 4,
+    ReadFile = 5,
+    CreateFile = 6,
+    WriteFile = 7,
+    RemoveFile = 8,
+    Ack = 128,
+    Nak = 129,
+}
+
+/// One FTP packet, the payload of FILE_TRANSFER_PROTOCOL.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FtpPacket {
+    pub seq_number: u16,
+    pub session: u8,
+    pub opcode: u8,
+    pub req_opcode: u8,
+    pub burst_complete: bool,
+    pub offset: u32,
+    pub data: Vec<u8>,
+}
+
+impl FtpPacket {
+    pub fn request(opcode: FtpOpcode, session: u8, offset: u32, data: &[u8]) -> Self {
+        Self {
+            session,
+            opcode: opcode as u8,
+            offset,
+            data: data[..data.len().min(FTP_DATA_MAX)].to_vec(),
+            ..Self::default()
+        }
+    }
+
+    pub fn encode(&self) -> [u8; FTP_PAYLOAD_LEN] {
+        let mut payload = [0; FTP_PAYLOAD_LEN];
+        let size = self.data.len().min(FTP_DATA_MAX);
+        payload[0..2].copy_from_slice(&self.seq_number.to_le_bytes());
+        payload[2] = self.session;
+        payload[3] = self.opcode;
+        payload[4] = size as u8;
+        payload[5] = self.req_opcode;
+        payload[6] = u8::from(self.burst_complete);
+        payload[8..12].copy_from_slice(&self.offset.to_le_bytes());
+        payload[HEADER_LEN..HEADER_LEN + size].copy_from_slice(&self.data[..size]);
+        payload
+    }
+
+    pub fn decode(payload: &[u8]) -> Option<Self> {
+        if payload.len() < HEADER_LEN {
+            return None;
+        }
+        let size = usize::from(payload[4]).min(payload.len() - HEADER_LEN);
+        Some(Self {
+            seq_number: u16::from_le_bytes([payload[0], payload[1]]),
+            session: payload[2],
+            opcode: payload[3],
+            req_opcode: payload[5],
+            burst_complete: payload[6] != 0,
+            offset: u32::from_le_bytes(payload[8..12].try_into().ok()?),
+            data: payload[HEADER_LEN..HEADER_LEN + size].to_vec(),
+        })
+    }
+
+    pub fn is_ack(&self) -> bool {
+        self.opcode == FtpOpcode::Ack as u8
+    }
+
+    pub fn is_nak(&self) -> bool {
+        self.opcode == FtpOpcode::Nak as u8
+    }
+
+    /// The error a NAK carries.
+    pub fn nak_error(&self) -> FtpError {
+        match self.data.first().copied().unwrap_or(1) {
+            2 => FtpError::FailErrno(self.data.get(1).copied().unwrap_or(0)),
+            3 => FtpError::InvalidDataSize,
+            4 => FtpError::InvalidSession,
+            5 => FtpError::NoSessionsAvailable,
+            6 => FtpError::Eof,
+            7 => FtpError::UnknownCommand,
+            8 => FtpError::FileExists,
+            9 => FtpError::FileProtected,
+            10 => FtpError::FileNotFound,
+            _ => FtpError::Fail,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FtpError {
+    Fail,
+    FailErrno(u8),
+    InvalidDataSize,
+    InvalidSession,
+    NoSessionsAvailable,
+    Eof,
+    UnknownCommand,
+    FileExists,
+    FileProtected,
+    FileNotFound,
+    /// The autopilot stopped answering.
+    Timeout,
+    /// The link refused the request.
+    Link(String),
+}
+
+impl fmt::Display for FtpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FailErrno(ENOSPC) => f.write_str("SD card full"),
+            Self::FailErrno(errno) => {
+                write!(f, "file operation failed on the vehicle (errno {errno})")
+            }
+            Self::Fail => {
+                f.write_str("file operation failed on the vehicle; is an SD card inserted?")
+            }
+            Self::InvalidDataSize | Self::InvalidSession | Self::Eof => {
+                f.write_str("file transfer protocol error; try again")
+            }
+            Self::NoSessionsAvailable => {
+                f.write_str("the vehicle is busy with another file transfer")
+            }
+            Self::UnknownCommand => f.write_str("the vehicle does not support this file operation"),
+            Self::FileExists => f.write_str("file already exists on the vehicle"),
+            Self::FileProtected => f.write_str("file is write-protected on the vehicle"),
+            Self::FileNotFound => f.write_str("file not found on the vehicle"),
+            Self::Timeout => f.write_str("the vehicle stopped answering file transfer requests"),
+            Self::Link(error) => write!(f, "file transfer failed: {error}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FtpDirEntry {
+    File { name: String, size: u64 },
+    Directory { name: String },
+}
+
+/// Parse one ListDirectory reply: NUL-separated `F<name>\t<size>`, `D<name>`
+/// and `S` (skipped) entries. Returns the entries and how many the reply
+/// held, skipped ones included, to advance the listing offset.
+pub fn parse_directory_listing(data: &[u8]) -> (Vec<FtpDirEntry>, u32) {
+    let mut entries = Vec::new();
+    let mut count = 0;
+    for raw in data.split(|byte| *byte == 0).filter(|raw| !raw.is_empty()) {
+        count += 1;
+        let text = String::from_utf8_lossy(&raw[1..]);
+        match raw[0] {
+            b'F' => {
+                let (name, size) = text.split_once('\t').unwrap_or((&text, "0"));
+                entries.push(FtpDirEntry::File {
+                    name: name.to_string(),
+                    size: size.trim().parse().unwrap_or(0),
+                });
+            }
+            b'D' if text != "." && text != ".." => entries.push(FtpDirEntry::Directory {
+                name: text.to_string(),
+            }),
+            _ => {}
+        }
+    }
+    (entries, count)
+}
+
+/// Remote path for a script called `name`, which must be a bare `.lua` file
+/// name.
+pub fn script_remote_path(name: &str) -> Result<String, String> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(format!("'{name}' is not a script file name"));
+    }
+    if !name.to_ascii_lowercase().ends_with(".lua") {
+        return Err(format!(
+            "'{name}' is not a Lua script; expected a .lua file"
+        ));
+    }
+    Ok(format!("{SCRIPTS_DIR}/{name}"))
+}
+
+/// What to tell the user when a scripts directory operation fails.
+/// `scr_enable` is the vehicle's SCR_ENABLE, when known.
+pub fn scripts_error(error: &FtpError, scr_enable: Option<f32>) -> String {
+    match (error, scr_enable) {
+        (_, Some(enabled)) if enabled == 0.0 => SCRIPTING_DISABLED.to_string(),
+        (FtpError::FileNotFound, _) => {
+            "no scripts directory on the SD card; set SCR_ENABLE=1 and reboot to create it"
+                .to_string()
+        }
+        _ => error.to_string(),
+    }
+}
+
+/// ArduPilot only flashes `.abin` images from the SD card.
+pub fn validate_firmware_image(name: &str) -> Result<(), String> {
+    if name.to_ascii_lowercase().ends_with(".abin") {
+        Ok(())
+    } else {
+        Err(format!(
+            "'{name}' is not an .abin image; ArduPilot only flashes .abin files from the SD card"
+        ))
+    }
+}
+
+/// MAV_CMD_REQUEST_MESSAGE for STORAGE_INFORMATION of every storage device.
+pub fn request_storage_information(system_id: u8, component_id: u8) -> ComponentCommand {
+    ComponentCommand {
+        system_id,
+        component_id,
+        command: MavCmd::MAV_CMD_REQUEST_MESSAGE,
+        params: [
+            STORAGE_INFORMATION_MESSAGE_ID as f32,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+    }
+}
+
+/// Refuse an upload of `len` bytes that the reported storage cannot hold, so
+/// a large image fails before the transfer rather than part way through.
+pub fn check_free_space(storage: &STORAGE_INFORMATION_DATA, len: usize) -> Result<(), String> {
+    if storage.status != StorageStatus::STORAGE_STATUS_READY {
+        return Err("the vehicle reports no usable SD card".into());
+    }
+    let free_bytes = f64::from(storage.available_capacity) * BYTES_PER_MIB;
+    if free_bytes < len as f64 {
+        return Err(format!(
+            "SD card has {:.1} MiB free but the file needs {:.1} MiB",
+            storage.available_capacity,
+            len as f64 / BYTES_PER_MIB
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packets_round_trip_through_the_payload() {
+        let packet = FtpPacket {
+            seq_number: 0x1234,
+            session: 2,
+            opcode: FtpOpcode::WriteFile as u8,
+            req_opcode: 0,
+            burst_complete: false,
+            offset: 478,
+            data: b"print('hello')".to_vec(),
+        };
+        let payload = packet.encode();
+        assert_eq!(payload[4], 14);
+        assert_eq!(&payload[8..12], &478u32.to_le_bytes());
+        assert_eq!(FtpPacket::decode(&payload), Some(packet));
+
+        let long = FtpPacket::request(FtpOpcode::WriteFile, 0, 0, &[7; 400]);
+        assert_eq!(long.data.len(), FTP_DATA_MAX);
+    }
+
+    #[test]
+    fn naks_translate_to_user_messages() {
+        let nak = |data: &[u8]| FtpPacket {
+            opcode: FtpOpcode::Nak as u8,
+            data: data.to_vec(),
+            ..FtpPacket::default()
+        };
+        assert_eq!(nak(&[2, ENOSPC]).nak_error().to_string(), "SD card full");
+        assert_eq!(nak(&[10]).nak_error(), FtpError::FileNotFound);
+        assert_eq!(nak(&[6]).nak_error(), FtpError::Eof);
+        assert_eq!(nak(&[]).nak_error(), FtpError::Fail);
+    }
+
+    #[test]
+    fn directory_listings_skip_dot_entries() {
+        let data = b"D.\0D..\0Fhello.lua\t1024\0Sjunk\0Dmodules\0";
+        let (entries, count) = parse_directory_listing(data);
+        assert_eq!(count, 5);
+        assert_eq!(
+            entries,
+            [
+                FtpDirEntry::File {
+                    name: "hello.lua".to_string(),
+                    size: 1024,
+                },
+                FtpDirEntry::Directory {
+                    name: "modules".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn scripts_errors_point_at_scr_enable() {
+        assert_eq!(
+            scripts_error(&FtpError::FileNotFound, Some(0.0)),
+            SCRIPTING_DISABLED
+        );
+        assert!(scripts_error(&FtpError::FileNotFound, Some(1.0)).contains("no scripts directory"));
+        assert_eq!(
+            scripts_error(&FtpError::FailErrno(ENOSPC), Some(1.0)),
+            "SD card full"
+        );
+    }
+
+    #[test]
+    fn script_and_firmware_names_are_checked() {
+        assert_eq!(
+            script_remote_path("follow.lua").as_deref(),
+            Ok("/APM/scripts/follow.lua")
+        );
+        assert!(script_remote_path("follow.py").is_err());
+        assert!(script_remote_path("../follow.lua").is_err());
+        assert!(script_remote_path("").is_err());
+        assert!(validate_firmware_image("arducopter.abin").is_ok());
+        assert!(validate_firmware_image("arducopter.apj").is_err());
+    }
+
+    #[test]
+    fn free_space_check_compares_mib_against_the_image() {
+        let storage = STORAGE_INFORMATION_DATA {
+            status: StorageStatus::STORAGE_STATUS_READY,
+            available_capacity: 1.5,
+            ..STORAGE_INFORMATION_DATA::default()
+        };
+        assert!(check_free_space(&storage, 1024 * 1024).is_ok());
+        assert_eq!(
+            check_free_space(&storage, 2 * 1024 * 1024).unwrap_err(),
+            "SD card has 1.5 MiB free but the file needs 2.0 MiB"
+        );
+        let missing = STORAGE_INFORMATION_DATA {
+            status: StorageStatus::STORAGE_STATUS_NOT_SUPPORTED,
+            ..storage
+        };
+        assert!(check_free_space(&missing, 1).is_err());
+    }
+}
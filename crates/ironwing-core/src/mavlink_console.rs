@@ -0,0 +1,94 @@
+use crate::dialect::{MavCmd, MavMessage};
+use num_traits::FromPrimitive;
+
+/// The MAV_CMD with numeric id `command_id`, if the dialect defines one.
+pub fn mav_cmd_from_id(command_id: u16) -> Result<MavCmd, String> {
+    MavCmd::from_u16(command_id).ok_or_else(|| format!("unknown MAV_CMD {command_id}"))
+}
+
+/// Build a message from its name and a JSON object of its fields, using the
+/// dialect's serde form: field names as in the generated `*_DATA` structs
+/// and enum fields as `{ "type": "VARIANT" }`. Every field must be given.
+pub fn mav_message_from_json(
+    message_name: &str,
+    fields: &serde_json::Value,
+) -> Result<MavMessage, String> {
+    if message_name.is_empty()
+        || !message_name.chars().all(|character| {
+            character.is_ascii_uppercase() || character.is_ascii_digit() || character == '_'
+        })
+    {
+        return Err(format!("'{message_name}' is not a MAVLink message name"));
+    }
+    let serde_json::Value::Object(fields) = fields else {
+        return Err("message fields must be a JSON object".to_string());
+    };
+    if fields.contains_key("type") {
+        return Err("'type' is reserved for the message name".to_string());
+    }
+
+    let mut tagged = fields.clone();
+    tagged.insert(
+        "type".to_string(),
+        serde_json::Value::String(message_name.to_string()),
+    );
+    serde_json::from_value(serde_json::Value::Object(tagged)).map_err(|error| {
+        let error = error.to_string();
+        if error.starts_with("unknown variant") {
+            format!("unknown MAVLink message '{message_name}'")
+        } else {
+            format!("invalid {message_name} fields: {error}")
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dialect::SYSTEM_TIME_DATA;
+
+    use super::*;
+
+    #[test]
+    fn builds_a_message_from_its_serde_form() {
+        let message = MavMessage::SYSTEM_TIME(SYSTEM_TIME_DATA {
+            time_unix_usec: 1_700_000_000_000_000,
+            time_boot_ms: 42,
+        });
+        let mut fields = serde_json::to_value(&message).expect("serialize");
+        fields.as_object_mut().expect("object").remove("type");
+        assert_eq!(
+            mav_message_from_json("SYSTEM_TIME", &fields).expect("valid message"),
+            message
+        );
+    }
+
+    #[test]
+    fn construction_errors_name_the_problem() {
+        let fields = serde_json::json!({ "time_unix_usec": 1, "time_boot_ms": "soon" });
+        let error = mav_message_from_json("SYSTEM_TIME", &fields).unwrap_err();
+        assert!(
+            error.starts_with("invalid SYSTEM_TIME fields: invalid type"),
+            "{error}"
+        );
+
+        let error = mav_message_from_json("SYSTEM_TIME", &serde_json::json!({ "time_boot_ms": 1 }))
+            .unwrap_err();
+        assert!(error.contains("time_unix_usec"), "{error}");
+
+        assert_eq!(
+            mav_message_from_json("NOT_A_MESSAGE", &serde_json::json!({})).unwrap_err(),
+            "unknown MAVLink message 'NOT_A_MESSAGE'"
+        );
+        assert!(mav_message_from_json("system_time", &serde_json::json!({})).is_err());
+        assert!(mav_message_from_json("SYSTEM_TIME", &serde_json::json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn command_ids_map_to_mav_cmd() {
+        assert_eq!(
+            mav_cmd_from_id(400),
+            Ok(MavCmd::MAV_CMD_COMPONENT_ARM_DISARM)
+        );
+        assert!(mav_cmd_from_id(u16::MAX).is_err());
+    }
+}
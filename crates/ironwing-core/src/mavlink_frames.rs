@@ -0,0 +1,455 @@
+use crate::dialect::MavMessage;
+use crate::ipc::{LinkFrameStats, LinkSource};
+use crate::link_sources::source_allowed;
+
+pub(crate) const MAVLINK_V1_STX: u8 = 0xFE;
+pub(crate) const MAVLINK_V2_STX: u8 = 0xFD;
+pub(crate) const MAVLINK_V1_HEADER_LEN: usize = 6;
+pub(crate) const MAVLINK_V2_HEADER_LEN: usize = 10;
+pub(crate) const MAVLINK_IFLAG_SIGNED: u8 = 0x01;
+const MAVLINK_CHECKSUM_LEN: usize = 2;
+const MAVLINK_SIGNATURE_LEN: usize = 13;
+
+/// MAVLink's X.25 (CRC-16/MCRF4XX) checksum.
+pub(crate) fn crc_x25(bytes: &[u8], crc_extra: u8) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for byte in bytes.iter().copied().chain(std::iter::once(crc_extra)) {
+        let mut tmp = byte ^ (crc & 0xFF) as u8;
+        tmp ^= tmp << 4;
+        crc = (crc >> 8) ^ (u16::from(tmp) << 8) ^ (u16::from(tmp) << 3) ^ (u16::from(tmp) >> 4);
+    }
+    crc
+}
+
+/// Whether `message_id` is in the dialect IronWing was built with. The
+/// generated code has no CRC_EXTRA, 0, for messages it does not define.
+pub fn is_known_message(message_id: u32) -> bool {
+    <MavMessage as mavlink::Message>::extra_crc(message_id) != 0
+}
+
+/// A frame for a message outside the dialect, which MAVKit would drop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownFrame {
+    pub system_id: u8,
+    pub component_id: u8,
+    pub message_id: u32,
+    pub payload: Vec<u8>,
+}
+
+pub(crate) enum FrameHeader {
+    /// Not enough bytes yet to read the header.
+    Incomplete,
+    /// The start byte is followed by a header no sender would produce.
+    Invalid,
+    Frame {
+        system_id: u8,
+        component_id: u8,
+        message_id: u32,
+        header_len: usize,
+        crc_end: usize,
+        total_len: usize,
+    },
+}
+
+pub(crate) fn frame_header(bytes: &[u8]) -> FrameHeader {
+    let (header_len, source, message_id, signed) = match bytes.first() {
+        Some(&MAVLINK_V1_STX) if bytes.len() >= MAVLINK_V1_HEADER_LEN => (
+            MAVLINK_V1_HEADER_LEN,
+            (bytes[3], bytes[4]),
+            u32::from(bytes[5]),
+            false,
+        ),
+        Some(&MAVLINK_V2_STX) if bytes.len() >= MAVLINK_V2_HEADER_LEN => {
+            if bytes[2] & !MAVLINK_IFLAG_SIGNED != 0 {
+                return FrameHeader::Invalid;
+            }
+            (
+                MAVLINK_V2_HEADER_LEN,
+                (bytes[5], bytes[6]),
+                u32::from_le_bytes([bytes[7], bytes[8], bytes[9], 0]),
+                bytes[2] & MAVLINK_IFLAG_SIGNED != 0,
+            )
+        }
+        Some(&MAVLINK_V1_STX | &MAVLINK_V2_STX) => return FrameHeader::Incomplete,
+        _ => return FrameHeader::Invalid,
+    };
+    let crc_end = header_len + usize::from(bytes[1]);
+    let signature_len = if signed { MAVLINK_SIGNATURE_LEN } else { 0 };
+    FrameHeader::Frame {
+        system_id: source.0,
+        component_id: source.1,
+        message_id,
+        header_len,
+        crc_end,
+        total_len: crc_end + MAVLINK_CHECKSUM_LEN + signature_len,
+    }
+}
+
+const HEARTBEAT_MESSAGE_ID: u32 = 0;
+
+/// `bytes` without the HEARTBEAT frames in it, or `None` when it holds none.
+///
+/// Used on outgoing writes, which MAVKit makes a whole frame at a time, so
+/// anything that does not parse as complete frames is left alone.
+pub fn without_heartbeats(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut kept = Vec::with_capacity(bytes.len());
+    let mut dropped = false;
+    let mut rest = bytes;
+    while !rest.is_empty() {
+        let FrameHeader::Frame {
+            message_id,
+            total_len,
+            ..
+        } = frame_header(rest)
+        else {
+            return None;
+        };
+        if rest.len() < total_len {
+            return None;
+        }
+        if message_id == HEARTBEAT_MESSAGE_ID {
+            dropped = true;
+        } else {
+            kept.extend_from_slice(&rest[..total_len]);
+        }
+        rest = &rest[total_len..];
+    }
+    dropped.then_some(kept)
+}
+
+/// `bytes` with each frame numbered from `next_sequence` and its checksum
+/// redone, or `None` when it does not parse as complete frames.
+///
+/// Used on outgoing writes so MAVKit's frames and the ones IronWing encodes
+/// itself share one sequence counter. Signed frames and messages outside the
+/// dialect keep their number, since their signature or CRC_EXTRA is not
+/// known here.
+pub fn resequenced(bytes: &[u8], mut next_sequence: impl FnMut() -> u8) -> Option<Vec<u8>> {
+    let mut out = bytes.to_vec();
+    let mut pos = 0;
+    while pos < out.len() {
+        let FrameHeader::Frame {
+            message_id,
+            header_len,
+            crc_end,
+            total_len,
+            ..
+        } = frame_header(&out[pos..])
+        else {
+            return None;
+        };
+        if out.len() - pos < total_len {
+            return None;
+        }
+        let signed = total_len > crc_end + MAVLINK_CHECKSUM_LEN;
+        if !signed && is_known_message(message_id) {
+            let frame = &mut out[pos..pos + total_len];
+            let sequence_at = if header_len == MAVLINK_V2_HEADER_LEN { 4 } else { 2 };
+            frame[sequence_at] = next_sequence();
+            let crc_extra = <MavMessage as mavlink::Message>::extra_crc(message_id);
+            let crc = crc_x25(&frame[1..crc_end], crc_extra);
+            frame[crc_end..crc_end + MAVLINK_CHECKSUM_LEN].copy_from_slice(&crc.to_le_bytes());
+        }
+        pos += total_len;
+    }
+    Some(out)
+}
+
+/// Splits a raw byte stream into checksummed MAVLink frames.
+///
+/// Lossy links (BLE above all) corrupt frames in transit. Only frames whose
+/// CRC matches are passed on, so the MAVLink parser downstream never sees a
+/// broken frame; the scanner resyncs on the next start byte and counts what
+/// it dropped.
+///
+/// Frames for messages outside the dialect have no CRC_EXTRA to check
+/// against. One whose length lands exactly on the next start byte is taken
+/// as a real frame and set aside for [`FrameScanner::take_unknown`] instead
+/// of being passed on; anything else is dropped as bad CRC.
+///
+/// An optional accept list of sources drops valid frames from anything else
+/// on the link (a second GCS, say) before MAVKit sees them.
+#[derive(Debug, Default)]
+pub struct FrameScanner {
+    pending: Vec<u8>,
+    stats: LinkFrameStats,
+    accept: Vec<LinkSource>,
+    unknown: Vec<UnknownFrame>,
+}
+
+impl FrameScanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_source_filter(accept: Vec<LinkSource>) -> Self {
+        Self {
+            accept,
+            ..Self::default()
+        }
+    }
+
+    pub fn stats(&self) -> LinkFrameStats {
+        self.stats
+    }
+
+    /// Frames for messages outside the dialect found since the last call.
+    pub fn take_unknown(&mut self) -> Vec<UnknownFrame> {
+        std::mem::take(&mut self.unknown)
+    }
+
+    /// Feed received bytes and return the complete valid frames they finish,
+    /// concatenated. A trailing partial frame is kept for the next call.
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<u8> {
+        self.pending.extend_from_slice(bytes);
+        let mut frames = Vec::new();
+        let mut pos = 0;
+        while pos < self.pending.len() {
+            let rest = &self.pending[pos..];
+            let Some(start) = rest
+                .iter()
+                .position(|byte| matches!(*byte, MAVLINK_V1_STX | MAVLINK_V2_STX))
+            else {
+                self.stats.skipped_bytes += rest.len() as u64;
+                pos = self.pending.len();
+                break;
+            };
+            self.stats.skipped_bytes += start as u64;
+            pos += start;
+
+            let rest = &self.pending[pos..];
+            match frame_header(rest) {
+                FrameHeader::Incomplete => break,
+                FrameHeader::Invalid => {
+                    self.stats.skipped_bytes += 1;
+                    pos += 1;
+                }
+                FrameHeader::Frame {
+                    system_id,
+                    component_id,
+                    message_id,
+                    header_len,
+                    crc_end,
+                    total_len,
+                } => {
+                    if rest.len() < total_len {
+                        break;
+                    }
+                    if !is_known_message(message_id) {
+                        // Wait for the byte after the frame to tell a frame
+                        // from noise.
+                        let Some(&next) = rest.get(total_len) else {
+                            break;
+                        };
+                        if matches!(next, MAVLINK_V1_STX | MAVLINK_V2_STX) {
+                            if source_allowed(&self.accept, system_id, component_id, message_id) {
+                                self.unknown.push(UnknownFrame {
+                                    system_id,
+                                    component_id,
+                                    message_id,
+                                    payload: rest[header_len..crc_end].to_vec(),
+                                });
+                            } else {
+                                self.stats.filtered_frames += 1;
+                            }
+                            pos += total_len;
+                            continue;
+                        }
+                    }
+                    let crc_extra = <MavMessage as mavlink::Message>::extra_crc(message_id);
+                    let expected = u16::from_le_bytes([rest[crc_end], rest[crc_end + 1]]);
+                    if crc_x25(&rest[1..crc_end], crc_extra) == expected {
+                        if source_allowed(&self.accept, system_id, component_id, message_id) {
+                            frames.extend_from_slice(&rest[..total_len]);
+                            self.stats.frames += 1;
+                        } else {
+                            self.stats.filtered_frames += 1;
+                        }
+                        pos += total_len;
+                    } else {
+                        // The start byte may have been payload noise; resync
+                        // from the byte after it rather than skipping the frame.
+                        self.stats.bad_crc_frames += 1;
+                        self.stats.skipped_bytes += 1;
+                        pos += 1;
+                    }
+                }
+            }
+        }
+        self.pending.drain(..pos);
+        frames
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mavlink::MavHeader;
+
+    use super::*;
+    use crate::dialect::{ATTITUDE_DATA, HEARTBEAT_DATA, RADIO_STATUS_DATA};
+
+    fn frames() -> Vec<Vec<u8>> {
+        let heartbeat = MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+            mavlink_version: 3,
+            ..HEARTBEAT_DATA::default()
+        });
+        let attitude = MavMessage::ATTITUDE(ATTITUDE_DATA {
+            roll: 0.25,
+            ..ATTITUDE_DATA::default()
+        });
+        let mut v2_heartbeat = Vec::new();
+        mavlink::write_v2_msg(&mut v2_heartbeat, MavHeader::default(), &heartbeat)
+            .expect("write v2");
+        let mut v1_attitude = Vec::new();
+        mavlink::write_v1_msg(&mut v1_attitude, MavHeader::default(), &attitude).expect("write v1");
+        let mut v2_attitude = Vec::new();
+        mavlink::write_v2_msg(&mut v2_attitude, MavHeader::default(), &attitude).expect("write v2");
+        vec![v2_heartbeat, v1_attitude, v2_attitude]
+    }
+
+    #[test]
+    fn delivers_every_valid_frame_through_interleaved_garbage() {
+        let frames = frames();
+        let mut corrupt = frames[0].clone();
+        let last = corrupt.len() - 1;
+        corrupt[last] ^= 0xFF;
+
+        let mut stream = vec![0x00, 0x13, 0x37];
+        stream.extend_from_slice(&frames[0]);
+        stream.extend_from_slice(&[0xFD, 0x80, 0x42]);
+        stream.extend_from_slice(&corrupt);
+        stream.extend_from_slice(&frames[1]);
+        stream.extend_from_slice(&[0x55; 7]);
+        stream.extend_from_slice(&frames[2]);
+
+        // Feed in small uneven chunks, the way BLE notifications arrive.
+        let mut scanner = FrameScanner::new();
+        let mut delivered = Vec::new();
+        for chunk in stream.chunks(5) {
+            delivered.extend(scanner.push(chunk));
+        }
+
+        assert_eq!(delivered, frames.concat());
+        let stats = scanner.stats();
+        assert_eq!(stats.frames, 3);
+        assert!(stats.bad_crc_frames >= 1);
+        assert!(stats.skipped_bytes >= 3 + 3 + corrupt.len() as u64 + 7);
+        assert!(stats.frame_error_rate() > 0.0);
+    }
+
+    #[test]
+    fn keeps_a_partial_frame_until_the_rest_arrives() {
+        let frame = frames().remove(0);
+        let mut scanner = FrameScanner::new();
+
+        assert!(scanner.push(&frame[..4]).is_empty());
+        assert_eq!(scanner.push(&frame[4..]), frame);
+        assert_eq!(
+            scanner.stats(),
+            LinkFrameStats {
+                frames: 1,
+                bad_crc_frames: 0,
+                skipped_bytes: 0,
+                filtered_frames: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn resequenced_frames_take_consecutive_numbers_and_still_check_out() {
+        let frames = frames();
+        let mut sequence = 41_u8;
+        let renumbered = resequenced(&frames.concat(), || {
+            sequence = sequence.wrapping_add(1);
+            sequence
+        })
+        .expect("complete frames");
+
+        let v2_heartbeat = &renumbered[..frames[0].len()];
+        let v1_attitude = &renumbered[frames[0].len()..frames[0].len() + frames[1].len()];
+        let v2_attitude = &renumbered[frames[0].len() + frames[1].len()..];
+        assert_eq!((v2_heartbeat[4], v1_attitude[2], v2_attitude[4]), (42, 43, 44));
+        let mut scanner = FrameScanner::new();
+        assert_eq!(scanner.push(&renumbered), renumbered);
+        assert_eq!(scanner.stats().bad_crc_frames, 0);
+
+        assert_eq!(resequenced(&frames[0][..5], || 0), None);
+    }
+
+    #[test]
+    fn source_filter_drops_other_systems_but_keeps_radio_status() {
+        let radio_status = MavMessage::RADIO_STATUS(RADIO_STATUS_DATA::default());
+        let heartbeat = MavMessage::HEARTBEAT(HEARTBEAT_DATA::default());
+        let from = |system_id, component_id, message: &MavMessage| {
+            let header = MavHeader {
+                system_id,
+                component_id,
+                sequence: 0,
+            };
+            let mut frame = Vec::new();
+            mavlink::write_v2_msg(&mut frame, header, message).expect("write v2");
+            frame
+        };
+        let vehicle = from(1, 1, &heartbeat);
+        let radio = from(51, 68, &radio_status);
+        let other_gcs = from(255, 190, &heartbeat);
+
+        let mut scanner = FrameScanner::with_source_filter(vec![LinkSource {
+            system_id: 1,
+            component_id: 1,
+        }]);
+        let delivered = scanner.push(&[other_gcs, vehicle.clone(), radio.clone()].concat());
+
+        assert_eq!(delivered, [vehicle, radio].concat());
+        assert_eq!(scanner.stats().frames, 2);
+        assert_eq!(scanner.stats().filtered_frames, 1);
+    }
+
+    #[test]
+    fn heartbeats_are_cut_out_of_outgoing_writes() {
+        let frames = frames();
+
+        assert_eq!(without_heartbeats(&frames[0]), Some(Vec::new()));
+        assert_eq!(
+            without_heartbeats(&frames.concat()),
+            Some([frames[1].clone(), frames[2].clone()].concat())
+        );
+        assert_eq!(without_heartbeats(&frames[1]), None);
+        assert_eq!(without_heartbeats(&frames[0][..4]), None);
+    }
+
+    #[test]
+    fn unknown_messages_are_set_aside_when_framing_holds() {
+        // A fork-only message, id 77001, from system 1 component 1.
+        let id = 77_001_u32.to_le_bytes();
+        let payload = [7_u8, 8, 9];
+        let mut unknown = vec![MAVLINK_V2_STX, 3, 0, 0, 0, 1, 1, id[0], id[1], id[2]];
+        unknown.extend_from_slice(&payload);
+        unknown.extend_from_slice(&[0x12, 0x34]);
+        let known = frames().remove(0);
+
+        let mut scanner = FrameScanner::new();
+        assert!(scanner.push(&unknown).is_empty());
+        // Nothing follows yet, so it could still be noise.
+        assert!(scanner.take_unknown().is_empty());
+        assert_eq!(scanner.push(&known), known);
+        assert_eq!(
+            scanner.take_unknown(),
+            [UnknownFrame {
+                system_id: 1,
+                component_id: 1,
+                message_id: 77_001,
+                payload: payload.to_vec(),
+            }]
+        );
+        assert_eq!(scanner.stats().bad_crc_frames, 0);
+
+        // Followed by noise instead of a frame, it is dropped.
+        let mut noisy = unknown.clone();
+        noisy.push(0x55);
+        noisy.extend_from_slice(&known);
+        assert_eq!(FrameScanner::new().push(&noisy), known);
+        assert!(is_known_message(0));
+        assert!(!is_known_message(77_001));
+    }
+}
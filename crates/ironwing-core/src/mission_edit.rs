@@ -0,0 +1,645 @@
+use mavkit::mission::commands::{DoCommand, NavCommand};
+use mavkit::{GeoPoint3d, MissionCommand, MissionFrame, MissionItem, MissionPlan};
+use serde_json::Value;
+
+use crate::ipc::{AltitudeChange, AltitudeFrame, MissionEditResult, MissionEditWarning};
+
+fn warning(seq: usize, message: impl Into<String>) -> MissionEditWarning {
+    MissionEditWarning {
+        seq: seq as u16,
+        message: message.into(),
+    }
+}
+
+/// The `position` of the command in `value`, a serialized mission item. Every
+/// positioned command lays it out the same way on the wire, e.g.
+/// `{"command": {"Nav": {"Waypoint": {"position": ...}}}}`.
+fn position_slot(value: &mut Value) -> Option<&mut Value> {
+    value
+        .get_mut("command")?
+        .as_object_mut()?
+        .values_mut()
+        .next()?
+        .as_object_mut()?
+        .values_mut()
+        .next()?
+        .get_mut("position")
+}
+
+/// Where the command in `item` acts, for any Nav or Do command that has a
+/// position.
+pub fn item_position(item: &MissionItem) -> Option<GeoPoint3d> {
+    let mut value = serde_json::to_value(item).ok()?;
+    serde_json::from_value(position_slot(&mut value)?.take()).ok()
+}
+
+fn with_position(item: &MissionItem, position: &GeoPoint3d) -> Result<MissionItem, String> {
+    let mut value = serde_json::to_value(item).map_err(|error| error.to_string())?;
+    let slot = position_slot(&mut value).ok_or("item has no position")?;
+    *slot = serde_json::to_value(position).map_err(|error| error.to_string())?;
+    serde_json::from_value(value).map_err(|error| error.to_string())
+}
+
+fn altitude_m(position: &GeoPoint3d) -> f64 {
+    match position {
+        GeoPoint3d::Msl(point) => f64::from(point.altitude_msl_m),
+        GeoPoint3d::RelHome(point) => f64::from(point.relative_alt_m),
+        GeoPoint3d::Terrain(point) => f64::from(point.altitude_terrain_m),
+    }
+}
+
+fn with_altitude(position: &GeoPoint3d, altitude_m: f64) -> GeoPoint3d {
+    let mut position = position.clone();
+    match &mut position {
+        GeoPoint3d::Msl(point) => point.altitude_msl_m = altitude_m as f32,
+        GeoPoint3d::RelHome(point) => point.relative_alt_m = altitude_m as f32,
+        GeoPoint3d::Terrain(point) => point.altitude_terrain_m = altitude_m as f32,
+    }
+    position
+}
+
+fn frame_of(position: &GeoPoint3d) -> AltitudeFrame {
+    match position {
+        GeoPoint3d::Msl(_) => AltitudeFrame::Amsl,
+        GeoPoint3d::RelHome(_) => AltitudeFrame::RelativeAlt,
+        GeoPoint3d::Terrain(_) => AltitudeFrame::Terrain,
+    }
+}
+
+fn frame_name(frame: AltitudeFrame) -> &'static str {
+    match frame {
+        AltitudeFrame::RelativeAlt => "relative",
+        AltitudeFrame::Amsl => "AMSL",
+        AltitudeFrame::Terrain => "terrain",
+    }
+}
+
+/// `position` in `target`, with `home_alt_m` (AMSL) bridging relative and
+/// AMSL altitudes. Terrain altitudes need terrain data under each item, which
+/// the plan does not have.
+fn convert_position(
+    position: &GeoPoint3d,
+    target: AltitudeFrame,
+    home_alt_m: Option<f64>,
+) -> Result<GeoPoint3d, String> {
+    let altitude_m = altitude_m(position);
+    let (latitude_deg, longitude_deg) = match position {
+        GeoPoint3d::Msl(point) => (point.latitude_deg, point.longitude_deg),
+        GeoPoint3d::RelHome(point) => (point.latitude_deg, point.longitude_deg),
+        GeoPoint3d::Terrain(point) => (point.latitude_deg, point.longitude_deg),
+    };
+    let source = frame_of(position);
+    let converted_m = match (source, target) {
+        _ if source == target => return Ok(position.clone()),
+        (AltitudeFrame::Terrain, _) | (_, AltitudeFrame::Terrain) => {
+            return Err(format!(
+                "cannot convert between {} and {} altitudes without terrain data",
+                frame_name(source),
+                frame_name(target)
+            ));
+        }
+        (AltitudeFrame::RelativeAlt, AltitudeFrame::Amsl) => {
+            altitude_m + home_alt_m.ok_or("no home altitude to convert against")?
+        }
+        _ => altitude_m - home_alt_m.ok_or("no home altitude to convert against")?,
+    };
+    let converted = match target {
+        AltitudeFrame::Amsl => GeoPoint3d::Msl(mavkit::GeoPoint3dMsl {
+            latitude_deg,
+            longitude_deg,
+            altitude_msl_m: converted_m as f32,
+        }),
+        _ => GeoPoint3d::RelHome(mavkit::GeoPoint3dRelHome {
+            latitude_deg,
+            longitude_deg,
+            relative_alt_m: converted_m as f32,
+        }),
+    };
+    Ok(converted)
+}
+
+/// Apply `edit` to the position of every item that has one. Items it refuses
+/// are kept as they were with a warning, as are raw commands in a global
+/// frame since their coordinates are not interpreted.
+fn edit_positions(
+    plan: &MissionPlan,
+    edit: impl Fn(&GeoPoint3d) -> Result<GeoPoint3d, String>,
+) -> MissionEditResult {
+    let mut warnings = Vec::new();
+    let items = plan
+        .items
+        .iter()
+        .enumerate()
+        .map(|(seq, item)| {
+            if let MissionCommand::Other(raw) = &item.command {
+                if !matches!(raw.frame, MissionFrame::Mission) {
+                    warnings.push(warning(seq, "raw command left unchanged"));
+                }
+                return item.clone();
+            }
+            let Some(position) = item_position(item) else {
+                return item.clone();
+            };
+            match edit(&position).and_then(|position| with_position(item, &position)) {
+                Ok(edited) => edited,
+                Err(message) => {
+                    warnings.push(warning(seq, message));
+                    item.clone()
+                }
+            }
+        })
+        .collect();
+    MissionEditResult {
+        plan: MissionPlan { items },
+        warnings,
+    }
+}
+
+/// Point every DO_JUMP at wherever its target ended up. `new_index` maps an
+/// index in the old plan to the new one, `None` when the item is gone.
+fn remap_jumps(
+    items: &mut [MissionItem],
+    new_index: impl Fn(u16) -> Option<u16>,
+    warnings: &mut Vec<MissionEditWarning>,
+) {
+    for (seq, item) in items.iter_mut().enumerate() {
+        let MissionCommand::Do(DoCommand::Jump(jump)) = &mut item.command else {
+            continue;
+        };
+        match new_index(jump.target_index) {
+            Some(target_index) => jump.target_index = target_index,
+            None => warnings.push(warning(
+                seq,
+                format!("DO_JUMP target {} does not exist", jump.target_index),
+            )),
+        }
+    }
+}
+
+/// `plan` with `item` inserted after `after_seq`.
+pub fn insert_waypoint(
+    plan: &MissionPlan,
+    after_seq: u16,
+    item: MissionItem,
+) -> Result<MissionEditResult, String> {
+    let at = usize::from(after_seq) + 1;
+    if at > plan.items.len() {
+        return Err(format!(
+            "mission has {} items; there is no item {after_seq} to insert after",
+            plan.items.len()
+        ));
+    }
+    let old_len = plan.items.len();
+    let mut items = plan.items.clone();
+    let mut warnings = Vec::new();
+    remap_jumps(
+        &mut items,
+        |target| {
+            (usize::from(target) < old_len).then(|| {
+                if target > after_seq {
+                    target + 1
+                } else {
+                    target
+                }
+            })
+        },
+        &mut warnings,
+    );
+    for warning in &mut warnings {
+        if usize::from(warning.seq) >= at {
+            warning.seq += 1;
+        }
+    }
+    // The new item's jump target, if any, already refers to the new plan.
+    if let MissionCommand::Do(DoCommand::Jump(jump)) = &item.command {
+        if usize::from(jump.target_index) > old_len {
+            warnings.push(warning(
+                at,
+                format!("DO_JUMP target {} does not exist", jump.target_index),
+            ));
+        }
+    }
+    items.insert(at, item);
+    Ok(MissionEditResult {
+        plan: MissionPlan { items },
+        warnings,
+    })
+}
+
+fn is_nav(item: &MissionItem) -> bool {
+    matches!(item.command, MissionCommand::Nav(_))
+}
+
+fn is_takeoff(item: &MissionItem) -> bool {
+    matches!(
+        item.command,
+        MissionCommand::Nav(NavCommand::Takeoff(_) | NavCommand::VtolTakeoff(_))
+    )
+}
+
+fn is_landing(item: &MissionItem) -> bool {
+    matches!(
+        item.command,
+        MissionCommand::Nav(
+            NavCommand::Land(_) | NavCommand::VtolLand(_) | NavCommand::ReturnToLaunch
+        )
+    )
+}
+
+/// `plan` flown the other way round. Each navigation item keeps the Do and
+/// Condition items that follow it, since they run on the leg after it. A
+/// leading takeoff and a trailing land or RTL stay where they are.
+pub fn reverse(plan: &MissionPlan) -> MissionEditResult {
+    let items = &plan.items;
+    // Groups of [nav, following non-nav items...]; anything before the first
+    // nav item goes with the head.
+    let first_nav = items.iter().position(is_nav).unwrap_or(items.len());
+    let mut groups: Vec<std::ops::Range<usize>> = Vec::new();
+    for (index, item) in items.iter().enumerate().skip(first_nav) {
+        if is_nav(item) {
+            groups.push(index..index + 1);
+        } else if let Some(group) = groups.last_mut() {
+            group.end = index + 1;
+        }
+    }
+    let mut head = 0..first_nav;
+    if groups
+        .first()
+        .is_some_and(|group| is_takeoff(&items[group.start]))
+    {
+        head.end = groups.remove(0).end;
+    }
+    let tail = match groups.last() {
+        Some(group) if is_landing(&items[group.start]) => groups.pop(),
+        _ => None,
+    };
+
+    let order: Vec<usize> = head
+        .chain(groups.into_iter().rev().flatten())
+        .chain(tail.into_iter().flatten())
+        .collect();
+    let mut new_index = vec![0u16; items.len()];
+    for (new, old) in order.iter().enumerate() {
+        new_index[*old] = new as u16;
+    }
+    let mut reordered: Vec<MissionItem> = order.iter().map(|old| items[*old].clone()).collect();
+
+    let mut warnings = Vec::new();
+    remap_jumps(
+        &mut reordered,
+        |target| new_index.get(usize::from(target)).copied(),
+        &mut warnings,
+    );
+    for (new, old) in order.iter().enumerate() {
+        let MissionCommand::Do(DoCommand::Jump(jump)) = &items[*old].command else {
+            continue;
+        };
+        let Some(&target_new) = new_index.get(usize::from(jump.target_index)) else {
+            continue;
+        };
+        let was_backward = usize::from(jump.target_index) < *old;
+        let is_backward = usize::from(target_new) < new;
+        if was_backward != is_backward {
+            warnings.push(warning(
+                new,
+                "DO_JUMP now jumps the other way; check the loop still makes sense",
+            ));
+        }
+    }
+    MissionEditResult {
+        plan: MissionPlan { items: reordered },
+        warnings,
+    }
+}
+
+/// Move every positioned item by `dlat_deg`/`dlon_deg`. Longitudes wrap at
+/// the antimeridian; an item that would go past a pole is left in place.
+pub fn translate(
+    plan: &MissionPlan,
+    dlat_deg: f64,
+    dlon_deg: f64,
+) -> Result<MissionEditResult, String> {
+    if !dlat_deg.is_finite() || !dlon_deg.is_finite() {
+        return Err("offsets must be finite numbers".to_string());
+    }
+    Ok(edit_positions(plan, |position| {
+        let mut moved = position.clone();
+        let (latitude_deg, longitude_deg) = match &mut moved {
+            GeoPoint3d::Msl(point) => (&mut point.latitude_deg, &mut point.longitude_deg),
+            GeoPoint3d::RelHome(point) => (&mut point.latitude_deg, &mut point.longitude_deg),
+            GeoPoint3d::Terrain(point) => (&mut point.latitude_deg, &mut point.longitude_deg),
+        };
+        let latitude = *latitude_deg + dlat_deg;
+        if !(-90.0..=90.0).contains(&latitude) {
+            return Err("would move past a pole".to_string());
+        }
+        *latitude_deg = latitude;
+        *longitude_deg = (*longitude_deg + dlon_deg + 180.0).rem_euclid(360.0) - 180.0;
+        Ok(moved)
+    }))
+}
+
+/// Offset or scale every altitude in its own frame. Relative and terrain
+/// altitudes are not taken below zero.
+pub fn scale_altitudes(
+    plan: &MissionPlan,
+    change: AltitudeChange,
+) -> Result<MissionEditResult, String> {
+    let apply = |altitude_m: f64| match change {
+        AltitudeChange::Offset { delta_m } => altitude_m + delta_m,
+        AltitudeChange::Scale { factor } => altitude_m * factor,
+    };
+    if !apply(1.0).is_finite() {
+        return Err("altitude change must be a finite number".to_string());
+    }
+    Ok(edit_positions(plan, |position| {
+        let altitude_m = apply(altitude_m(position));
+        if altitude_m < 0.0 && frame_of(position) != AltitudeFrame::Amsl {
+            return Err(format!(
+                "would put the item {:.1} m below its {} reference",
+                -altitude_m,
+                frame_name(frame_of(position))
+            ));
+        }
+        Ok(with_altitude(position, altitude_m))
+    }))
+}
+
+/// Convert every positioned item to `target`. `home_alt_m` is the AMSL
+/// altitude of the plan's home, needed between relative and AMSL.
+pub fn convert_frame(
+    plan: &MissionPlan,
+    target: AltitudeFrame,
+    home_alt_m: Option<f64>,
+) -> Result<MissionEditResult, String> {
+    if home_alt_m.is_some_and(|altitude| !altitude.is_finite()) {
+        return Err("home altitude must be a finite number".to_string());
+    }
+    Ok(edit_positions(plan, |position| {
+        convert_position(position, target, home_alt_m)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use mavkit::mission::commands::{DoChangeSpeed, DoJump, NavTakeoff, NavWaypoint, SpeedType};
+    use mavkit::{GeoPoint3dMsl, GeoPoint3dRelHome, GeoPoint3dTerrain};
+
+    use super::*;
+
+    fn rel(latitude_deg: f64, relative_alt_m: f32) -> GeoPoint3d {
+        GeoPoint3d::RelHome(GeoPoint3dRelHome {
+            latitude_deg,
+            longitude_deg: 8.55,
+            relative_alt_m,
+        })
+    }
+
+    fn nav_item(command: NavCommand) -> MissionItem {
+        MissionItem {
+            command: MissionCommand::Nav(command),
+            autocontinue: true,
+        }
+    }
+
+    fn waypoint_at(position: GeoPoint3d) -> MissionItem {
+        nav_item(NavCommand::Waypoint(NavWaypoint {
+            position,
+            hold_time_s: 0.0,
+            acceptance_radius_m: 1.0,
+            pass_radius_m: 0.0,
+            yaw_deg: 0.0,
+        }))
+    }
+
+    fn waypoint(latitude_deg: f64, relative_alt_m: f32) -> MissionItem {
+        waypoint_at(rel(latitude_deg, relative_alt_m))
+    }
+
+    fn takeoff(relative_alt_m: f32) -> MissionItem {
+        nav_item(NavCommand::Takeoff(NavTakeoff {
+            position: rel(47.39, relative_alt_m),
+            pitch_deg: 0.0,
+        }))
+    }
+
+    fn speed(speed_mps: f32) -> MissionItem {
+        MissionItem {
+            command: MissionCommand::Do(DoCommand::ChangeSpeed(DoChangeSpeed {
+                speed_type: SpeedType::Groundspeed,
+                speed_mps,
+                throttle_pct: 0.0,
+            })),
+            autocontinue: true,
+        }
+    }
+
+    fn jump(target_index: u16) -> MissionItem {
+        MissionItem {
+            command: MissionCommand::Do(DoCommand::Jump(DoJump {
+                target_index,
+                repeat_count: 2,
+            })),
+            autocontinue: true,
+        }
+    }
+
+    fn rtl() -> MissionItem {
+        nav_item(NavCommand::ReturnToLaunch)
+    }
+
+    fn plan(items: Vec<MissionItem>) -> MissionPlan {
+        MissionPlan { items }
+    }
+
+    #[test]
+    fn positions_are_found_and_replaced_through_the_wire_format() {
+        let item = waypoint(47.40, 30.0);
+        assert_eq!(item_position(&item), Some(rel(47.40, 30.0)));
+        assert_eq!(item_position(&speed(5.0)), None);
+        assert_eq!(item_position(&rtl()), None);
+        let moved = with_position(&item, &rel(47.41, 35.0)).expect("replaced");
+        assert_eq!(moved, waypoint(47.41, 35.0));
+    }
+
+    #[test]
+    fn insert_shifts_jump_targets_after_the_insertion_point() {
+        let original = plan(vec![
+            takeoff(20.0),
+            waypoint(47.40, 30.0),
+            waypoint(47.41, 30.0),
+            jump(1),
+            jump(2),
+        ]);
+        let edited = insert_waypoint(&original, 1, waypoint(47.405, 30.0)).expect("inserted");
+        assert_eq!(
+            edited.plan.items,
+            vec![
+                takeoff(20.0),
+                waypoint(47.40, 30.0),
+                waypoint(47.405, 30.0),
+                waypoint(47.41, 30.0),
+                jump(1),
+                jump(3),
+            ]
+        );
+        assert!(edited.warnings.is_empty());
+
+        let appended = insert_waypoint(&original, 4, waypoint(47.42, 30.0)).expect("appended");
+        assert_eq!(appended.plan.items.len(), 6);
+        assert_eq!(appended.plan.items[4], jump(2));
+        assert!(insert_waypoint(&original, 5, waypoint(47.42, 30.0)).is_err());
+        assert!(insert_waypoint(&plan(Vec::new()), 0, waypoint(47.42, 30.0)).is_err());
+    }
+
+    #[test]
+    fn insert_reports_jumps_that_already_pointed_nowhere() {
+        let edited = insert_waypoint(
+            &plan(vec![waypoint(47.40, 30.0), jump(7)]),
+            0,
+            waypoint(47.41, 30.0),
+        )
+        .expect("inserted");
+        assert_eq!(edited.warnings.len(), 1);
+        assert_eq!(edited.warnings[0].seq, 2);
+    }
+
+    #[test]
+    fn reverse_keeps_takeoff_and_landing_and_carries_do_items_with_their_leg() {
+        let original = plan(vec![
+            takeoff(20.0),
+            speed(5.0),
+            waypoint(47.40, 30.0),
+            waypoint(47.41, 30.0),
+            speed(8.0),
+            waypoint(47.42, 30.0),
+            rtl(),
+        ]);
+        let reversed = reverse(&original);
+        assert_eq!(
+            reversed.plan.items,
+            vec![
+                takeoff(20.0),
+                speed(5.0),
+                waypoint(47.42, 30.0),
+                waypoint(47.41, 30.0),
+                speed(8.0),
+                waypoint(47.40, 30.0),
+                rtl(),
+            ]
+        );
+        assert!(reversed.warnings.is_empty());
+        assert_eq!(reverse(&plan(Vec::new())).plan.items, Vec::new());
+    }
+
+    #[test]
+    fn reverse_follows_jump_targets_and_flags_loops_that_changed_direction() {
+        let original = plan(vec![
+            waypoint(47.40, 30.0),
+            waypoint(47.41, 30.0),
+            jump(0),
+            waypoint(47.42, 30.0),
+        ]);
+        let reversed = reverse(&original);
+        assert_eq!(
+            reversed.plan.items,
+            vec![
+                waypoint(47.42, 30.0),
+                waypoint(47.41, 30.0),
+                jump(3),
+                waypoint(47.40, 30.0),
+            ]
+        );
+        assert_eq!(reversed.warnings.len(), 1);
+        assert_eq!(reversed.warnings[0].seq, 2);
+    }
+
+    #[test]
+    fn translate_moves_every_position_and_wraps_longitude() {
+        let original = plan(vec![takeoff(20.0), speed(5.0), waypoint(47.40, 30.0)]);
+        let moved = translate(&original, 0.01, 180.0).expect("translated");
+        assert!(moved.warnings.is_empty());
+        let Some(GeoPoint3d::RelHome(point)) = item_position(&moved.plan.items[2]) else {
+            panic!("relative waypoint");
+        };
+        assert!((point.latitude_deg - 47.41).abs() < 1e-9);
+        assert!((point.longitude_deg - -171.45).abs() < 1e-9);
+        assert_eq!(moved.plan.items[1], speed(5.0));
+
+        let past_pole = translate(&original, 50.0, 0.0).expect("translated");
+        assert_eq!(past_pole.warnings.len(), 2);
+        assert_eq!(past_pole.plan, original);
+        assert!(translate(&original, f64::NAN, 0.0).is_err());
+    }
+
+    #[test]
+    fn altitude_changes_apply_in_each_frame_and_stay_above_the_reference() {
+        let original = plan(vec![takeoff(20.0), waypoint(47.40, 30.0)]);
+        let raised =
+            scale_altitudes(&original, AltitudeChange::Offset { delta_m: 5.0 }).expect("offset");
+        assert_eq!(
+            raised.plan.items,
+            vec![takeoff(25.0), waypoint(47.40, 35.0)]
+        );
+        let doubled =
+            scale_altitudes(&original, AltitudeChange::Scale { factor: 2.0 }).expect("scaled");
+        assert_eq!(doubled.plan.items[1], waypoint(47.40, 60.0));
+
+        let lowered =
+            scale_altitudes(&original, AltitudeChange::Offset { delta_m: -25.0 }).expect("offset");
+        assert_eq!(lowered.plan.items[0], takeoff(20.0));
+        assert_eq!(lowered.plan.items[1], waypoint(47.40, 5.0));
+        assert_eq!(lowered.warnings.len(), 1);
+        assert_eq!(lowered.warnings[0].seq, 0);
+
+        let below_sea = plan(vec![waypoint_at(GeoPoint3d::Msl(GeoPoint3dMsl {
+            latitude_deg: 31.5,
+            longitude_deg: 35.5,
+            altitude_msl_m: 10.0,
+        }))]);
+        let dead_sea = scale_altitudes(&below_sea, AltitudeChange::Offset { delta_m: -400.0 })
+            .expect("offset");
+        assert!(dead_sea.warnings.is_empty());
+        assert!(
+            scale_altitudes(
+                &original,
+                AltitudeChange::Scale {
+                    factor: f64::INFINITY
+                }
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn frame_conversion_uses_the_home_altitude() {
+        let original = plan(vec![
+            waypoint(47.40, 30.0),
+            waypoint_at(GeoPoint3d::Terrain(GeoPoint3dTerrain {
+                latitude_deg: 47.41,
+                longitude_deg: 8.55,
+                altitude_terrain_m: 40.0,
+            })),
+        ]);
+        let amsl = convert_frame(&original, AltitudeFrame::Amsl, Some(488.0)).expect("converted");
+        assert_eq!(
+            item_position(&amsl.plan.items[0]),
+            Some(GeoPoint3d::Msl(GeoPoint3dMsl {
+                latitude_deg: 47.40,
+                longitude_deg: 8.55,
+                altitude_msl_m: 518.0,
+            }))
+        );
+        assert_eq!(amsl.plan.items[1], original.items[1]);
+        assert_eq!(amsl.warnings.len(), 1);
+        assert_eq!(amsl.warnings[0].seq, 1);
+
+        let back =
+            convert_frame(&amsl.plan, AltitudeFrame::RelativeAlt, Some(488.0)).expect("back");
+        assert_eq!(back.plan, original);
+
+        let no_home = convert_frame(&original, AltitudeFrame::Amsl, None).expect("converted");
+        assert_eq!(no_home.plan, original);
+        assert_eq!(no_home.warnings.len(), 2);
+    }
+}
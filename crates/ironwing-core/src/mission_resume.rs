@@ -0,0 +1,240 @@
+use mavkit::mission::commands::{DoCommand, NavCommand, NavTakeoff};
+use mavkit::{GeoPoint3d, MissionCommand, MissionItem, MissionPlan};
+
+/// Build the plan to fly after a landing partway through `plan`, typically
+/// for a battery swap.
+///
+/// `last_completed_seq` is the index into `plan.items` of the last item the
+/// vehicle finished. Everything up to it is trimmed and replaced by a takeoff
+/// and a leg to the first remaining waypoint, both at the highest altitude the
+/// remaining waypoints use so the return leg clears whatever the mission was
+/// planned to clear. Speed changes and camera trigger distances set before the
+/// cut are re-emitted after the takeoff so the rest of the mission flies with
+/// the same settings.
+pub fn prepare_resume(plan: &MissionPlan, last_completed_seq: u16) -> Result<MissionPlan, String> {
+    let cut = usize::from(last_completed_seq) + 1;
+    if cut >= plan.items.len() {
+        return Err(format!(
+            "mission has {} items; nothing remains after item {last_completed_seq}",
+            plan.items.len()
+        ));
+    }
+    let (flown, remaining) = plan.items.split_at(cut);
+
+    let Some(first_waypoint) = remaining.iter().find_map(|item| match &item.command {
+        MissionCommand::Nav(NavCommand::Waypoint(waypoint)) => Some(waypoint),
+        _ => None,
+    }) else {
+        return Err(format!(
+            "no waypoint remains after item {last_completed_seq}"
+        ));
+    };
+
+    let safe_position = remaining
+        .iter()
+        .filter_map(|item| match &item.command {
+            MissionCommand::Nav(NavCommand::Waypoint(waypoint)) => Some(&waypoint.position),
+            _ => None,
+        })
+        .filter(|position| same_frame(position, &first_waypoint.position))
+        .max_by(|a, b| altitude_m(a).total_cmp(&altitude_m(b)))
+        .map_or_else(
+            || first_waypoint.position.clone(),
+            |highest| with_altitude_of(&first_waypoint.position, highest),
+        );
+
+    let mut items = Vec::with_capacity(remaining.len() + 4);
+    items.push(MissionItem {
+        command: MissionCommand::Nav(NavCommand::Takeoff(NavTakeoff {
+            position: safe_position.clone(),
+            pitch_deg: 0.0,
+        })),
+        autocontinue: true,
+    });
+    items.extend(carried_settings(flown));
+    let mut approach = first_waypoint.clone();
+    approach.position = safe_position;
+    items.push(MissionItem {
+        command: MissionCommand::Nav(NavCommand::Waypoint(approach)),
+        autocontinue: true,
+    });
+    // Items between the cut and the first waypoint (DO_ commands queued for
+    // that leg) still apply, so keep them along with the waypoint itself.
+    items.extend(remaining.iter().cloned());
+
+    Ok(MissionPlan { items })
+}
+
+/// The latest speed change and camera trigger distance among the flown items,
+/// in the order they were set.
+fn carried_settings(flown: &[MissionItem]) -> Vec<MissionItem> {
+    let last_index = |matches: fn(&DoCommand) -> bool| {
+        flown.iter().rposition(|item| match &item.command {
+            MissionCommand::Do(command) => matches(command),
+            _ => false,
+        })
+    };
+    let mut indices: Vec<usize> = [
+        last_index(|command| matches!(command, DoCommand::ChangeSpeed(_))),
+        last_index(|command| matches!(command, DoCommand::CamTriggerDistance(_))),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    indices.sort_unstable();
+    indices
+        .into_iter()
+        .map(|index| flown[index].clone())
+        .collect()
+}
+
+fn same_frame(a: &GeoPoint3d, b: &GeoPoint3d) -> bool {
+    std::mem::discriminant(a) == std::mem::discriminant(b)
+}
+
+fn altitude_m(position: &GeoPoint3d) -> f64 {
+    match position {
+        GeoPoint3d::Msl(point) => f64::from(point.altitude_msl_m),
+        GeoPoint3d::RelHome(point) => f64::from(point.relative_alt_m),
+        GeoPoint3d::Terrain(point) => f64::from(point.altitude_terrain_m),
+    }
+}
+
+/// `position` with the altitude of `source`, which must share its frame.
+fn with_altitude_of(position: &GeoPoint3d, source: &GeoPoint3d) -> GeoPoint3d {
+    let mut position = position.clone();
+    match (&mut position, source) {
+        (GeoPoint3d::Msl(point), GeoPoint3d::Msl(source)) => {
+            point.altitude_msl_m = source.altitude_msl_m;
+        }
+        (GeoPoint3d::RelHome(point), GeoPoint3d::RelHome(source)) => {
+            point.relative_alt_m = source.relative_alt_m;
+        }
+        (GeoPoint3d::Terrain(point), GeoPoint3d::Terrain(source)) => {
+            point.altitude_terrain_m = source.altitude_terrain_m;
+        }
+        _ => {}
+    }
+    position
+}
+
+#[cfg(test)]
+mod tests {
+    use mavkit::GeoPoint3dRelHome;
+    use mavkit::mission::commands::{DoCamTriggerDistance, DoChangeSpeed, NavWaypoint, SpeedType};
+
+    use super::*;
+
+    fn waypoint(latitude_deg: f64, relative_alt_m: f64) -> MissionItem {
+        MissionItem {
+            command: MissionCommand::Nav(NavCommand::Waypoint(NavWaypoint {
+                position: GeoPoint3d::RelHome(GeoPoint3dRelHome {
+                    latitude_deg,
+                    longitude_deg: 8.55,
+                    relative_alt_m,
+                }),
+                hold_time_s: 0.0,
+                acceptance_radius_m: 1.0,
+                pass_radius_m: 0.0,
+                yaw_deg: 0.0,
+            })),
+            autocontinue: true,
+        }
+    }
+
+    fn speed(speed_mps: f32) -> MissionItem {
+        MissionItem {
+            command: MissionCommand::Do(DoCommand::ChangeSpeed(DoChangeSpeed {
+                speed_type: SpeedType::Groundspeed,
+                speed_mps,
+                throttle_pct: 0.0,
+            })),
+            autocontinue: true,
+        }
+    }
+
+    fn cam_trigger(meters: f32) -> MissionItem {
+        MissionItem {
+            command: MissionCommand::Do(DoCommand::CamTriggerDistance(DoCamTriggerDistance {
+                meters,
+                trigger_now: false,
+            })),
+            autocontinue: true,
+        }
+    }
+
+    fn takeoff(latitude_deg: f64, relative_alt_m: f64) -> MissionItem {
+        MissionItem {
+            command: MissionCommand::Nav(NavCommand::Takeoff(NavTakeoff {
+                position: GeoPoint3d::RelHome(GeoPoint3dRelHome {
+                    latitude_deg,
+                    longitude_deg: 8.55,
+                    relative_alt_m,
+                }),
+                pitch_deg: 0.0,
+            })),
+            autocontinue: true,
+        }
+    }
+
+    #[test]
+    fn resumes_at_the_highest_remaining_altitude_with_settings_carried_over() {
+        let plan = MissionPlan {
+            items: vec![
+                takeoff(47.39, 20.0),
+                speed(5.0),
+                cam_trigger(10.0),
+                waypoint(47.40, 30.0),
+                speed(8.0),
+                waypoint(47.41, 30.0),
+                waypoint(47.42, 45.0),
+                waypoint(47.43, 35.0),
+            ],
+        };
+
+        let resumed = prepare_resume(&plan, 5).expect("resume plan");
+
+        assert_eq!(
+            resumed.items,
+            vec![
+                takeoff(47.42, 45.0),
+                cam_trigger(10.0),
+                speed(8.0),
+                waypoint(47.42, 45.0),
+                waypoint(47.42, 45.0),
+                waypoint(47.43, 35.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_do_commands_queued_after_the_cut() {
+        let plan = MissionPlan {
+            items: vec![
+                takeoff(47.39, 20.0),
+                speed(5.0),
+                waypoint(47.40, 30.0),
+                cam_trigger(12.0),
+                waypoint(47.41, 25.0),
+            ],
+        };
+
+        let resumed = prepare_resume(&plan, 2).expect("resume plan");
+
+        assert_eq!(resumed.items.len(), 5);
+        assert_eq!(resumed.items[1], speed(5.0));
+        assert_eq!(resumed.items[2], waypoint(47.41, 25.0));
+        assert_eq!(resumed.items[3], cam_trigger(12.0));
+        assert_eq!(resumed.items[4], waypoint(47.41, 25.0));
+    }
+
+    #[test]
+    fn rejects_a_cut_past_the_last_waypoint() {
+        let plan = MissionPlan {
+            items: vec![takeoff(47.39, 20.0), waypoint(47.40, 30.0), speed(5.0)],
+        };
+
+        assert!(prepare_resume(&plan, 2).is_err());
+        assert!(prepare_resume(&plan, 1).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use mavlink::MavHeader;
+use num_traits::FromPrimitive;
+
+use crate::dialect::{MavMessage, MavTunnelPayloadType, TUNNEL_DATA};
+use crate::ipc::connection::GcsIdentity;
+use crate::ipc::named_values::{NamedValue, NamedValueKind};
+use crate::mavlink_frames::{MAVLINK_V2_HEADER_LEN, crc_x25};
+
+/// MAVLink message ids of NAMED_VALUE_FLOAT, NAMED_VALUE_INT and DEBUG_VECT.
+pub const NAMED_VALUE_MESSAGE_IDS: [u32; 3] = [251, 252, 250];
+
+/// Payload capacity of one TUNNEL message.
+pub const TUNNEL_PAYLOAD_MAX: usize = 128;
+
+const TUNNEL_MESSAGE_ID: u32 = 385;
+
+/// Decode a fixed-width, NUL-padded MAVLink char array.
+pub fn mavlink_name(bytes: &[u8]) -> String {
+    let end = bytes
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).trim().to_string()
+}
+
+/// Extract a named value from NAMED_VALUE_FLOAT, NAMED_VALUE_INT or
+/// DEBUG_VECT; other messages yield `None`.
+pub fn named_value_from_message(
+    message: &MavMessage,
+    system_id: u8,
+    component_id: u8,
+    timestamp_usec: u64,
+) -> Option<NamedValue> {
+    let (name, kind, values, time_boot_ms) = match message {
+        MavMessage::NAMED_VALUE_FLOAT(data) => (
+            mavlink_name(&data.name[..]),
+            NamedValueKind::Float,
+            vec![f64::from(data.value)],
+            u64::from(data.time_boot_ms),
+        ),
+        MavMessage::NAMED_VALUE_INT(data) => (
+            mavlink_name(&data.name[..]),
+            NamedValueKind::Int,
+            vec![f64::from(data.value)],
+            u64::from(data.time_boot_ms),
+        ),
+        MavMessage::DEBUG_VECT(data) => (
+            mavlink_name(&data.name[..]),
+            NamedValueKind::DebugVect,
+            vec![f64::from(data.x), f64::from(data.y), f64::from(data.z)],
+            data.time_usec / 1000,
+        ),
+        _ => return None,
+    };
+    Some(NamedValue {
+        name,
+        kind,
+        values,
+        system_id,
+        component_id,
+        time_boot_ms,
+        timestamp_usec,
+    })
+}
+
+/// Split `data` into TUNNEL messages of at most [`TUNNEL_PAYLOAD_MAX`] bytes.
+/// `payload_type` must be one the dialect defines.
+pub fn tunnel_messages(
+    target_system: u8,
+    target_component: u8,
+    payload_type: u16,
+    data: &[u8],
+) -> Result<Vec<MavMessage>, String> {
+    if data.is_empty() {
+        return Err("tunnel payload must not be empty".to_string());
+    }
+    let payload_type = MavTunnelPayloadType::from_u16(payload_type).ok_or_else(|| {
+        format!("tunnel payload type {payload_type} is not defined in the MAVLink dialect")
+    })?;
+    Ok(data
+        .chunks(TUNNEL_PAYLOAD_MAX)
+        .map(|chunk| {
+            let mut payload = [0_u8; TUNNEL_PAYLOAD_MAX];
+            payload[..chunk.len()].copy_from_slice(chunk);
+            MavMessage::TUNNEL(TUNNEL_DATA {
+                payload_type,
+                target_system,
+                target_component,
+                payload_length: chunk.len() as u8,
+                payload,
+            })
+        })
+        .collect())
+}
+
+/// Whether the dialect defines `payload_type`, so MAVKit can encode a TUNNEL
+/// carrying it. The private range from 32768 up never is.
+pub fn is_dialect_tunnel_payload_type(payload_type: u16) -> bool {
+    MavTunnelPayloadType::from_u16(payload_type).is_some()
+}
+
+/// `data` as encoded MAVLink 2 TUNNEL frames, for payload types the dialect
+/// has no variant for. Each frame is encoded with a placeholder type, then
+/// the type field, the first two payload bytes, is overwritten and the
+/// checksum redone. Sequence numbers are left at zero for the link outbox,
+/// which numbers every frame it sends.
+pub fn tunnel_frames(
+    source: GcsIdentity,
+    target_system: u8,
+    target_component: u8,
+    payload_type: u16,
+    data: &[u8],
+) -> Result<Vec<Vec<u8>>, String> {
+    let messages = tunnel_messages(target_system, target_component, 0, data)?;
+    let crc_extra = <MavMessage as mavlink::Message>::extra_crc(TUNNEL_MESSAGE_ID);
+    let header = MavHeader {
+        system_id: source.system_id,
+        component_id: source.component_id,
+        sequence: 0,
+    };
+    messages
+        .iter()
+        .map(|message| {
+            let mut frame = Vec::new();
+            mavlink::write_v2_msg(&mut frame, header, message)
+                .map_err(|error| format!("failed to encode TUNNEL: {error}"))?;
+            let crc_end = MAVLINK_V2_HEADER_LEN + usize::from(frame[1]);
+            frame[MAVLINK_V2_HEADER_LEN..MAVLINK_V2_HEADER_LEN + 2]
+                .copy_from_slice(&payload_type.to_le_bytes());
+            let crc = crc_x25(&frame[1..crc_end], crc_extra);
+            frame[crc_end..crc_end + 2].copy_from_slice(&crc.to_le_bytes());
+            Ok(frame)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dialect::{HEARTBEAT_DATA, NAMED_VALUE_FLOAT_DATA};
+    use crate::mavlink_frames::FrameScanner;
+
+    use super::*;
+
+    #[test]
+    fn named_value_float_decodes_padded_name() {
+        let mut name = [0_u8; 10];
+        name[..4].copy_from_slice(b"rpm1");
+        let message = MavMessage::NAMED_VALUE_FLOAT(NAMED_VALUE_FLOAT_DATA {
+            time_boot_ms: 1500,
+            name: name.into(),
+            value: 4200.0,
+        });
+
+        let value = named_value_from_message(&message, 1, 191, 42).expect("named value");
+
+        assert_eq!(value.name, "rpm1");
+        assert_eq!(value.kind, NamedValueKind::Float);
+        assert_eq!(value.values, [4200.0]);
+        assert_eq!((value.time_boot_ms, value.timestamp_usec), (1500, 42));
+        assert!(
+            named_value_from_message(&MavMessage::HEARTBEAT(HEARTBEAT_DATA::default()), 1, 1, 0)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn tunnel_payload_is_chunked_to_message_limit() {
+        let data: Vec<u8> = (0..=255).chain(0..44).map(|byte| byte as u8).collect();
+
+        let messages = tunnel_messages(1, 1, 0, &data).expect("known payload type");
+
+        let lengths: Vec<u8> = messages
+            .iter()
+            .map(|message| match message {
+                MavMessage::TUNNEL(data) => data.payload_length,
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(lengths, [128, 128, 44]);
+        assert!(tunnel_messages(1, 1, 0, &[]).is_err());
+    }
+
+    #[test]
+    fn private_tunnel_payload_types_are_encoded_as_frames() {
+        let source = GcsIdentity {
+            system_id: 255,
+            component_id: 190,
+        };
+        assert!(!is_dialect_tunnel_payload_type(40_000));
+        assert!(is_dialect_tunnel_payload_type(0));
+
+        let frames = tunnel_frames(source, 1, 1, 40_000, &[0xAB; 130]).expect("frames");
+
+        assert_eq!(frames.len(), 2);
+        for frame in &frames {
+            assert_eq!(&frame[10..12], &40_000_u16.to_le_bytes());
+            // The checksum still matches, so the frame survives a CRC check.
+            assert_eq!(FrameScanner::new().push(frame), *frame);
+        }
+    }
+}
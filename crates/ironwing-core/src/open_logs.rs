@@ -0,0 +1,138 @@
+use crate::log_engine::LogStore;
+
+/// Logs open at the same time, keyed by the id `log_open` hands out. The most
+/// recently opened log is the one playback and id-less queries act on.
+#[derive(Debug, Default)]
+pub struct OpenLogs {
+    logs: Vec<(String, LogStore)>,
+    next_id: u64,
+}
+
+impl OpenLogs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.logs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.logs.is_empty()
+    }
+
+    /// Whether one more log fits under `max_open`. A log reopened from the
+    /// same path replaces its earlier handle, so it always fits.
+    pub fn ensure_capacity(&self, path: &str, max_open: usize) -> Result<(), String> {
+        let reopening = self
+            .logs
+            .iter()
+            .any(|(_, store)| store.source_path() == path);
+        if !reopening && self.logs.len() >= max_open {
+            return Err(format!(
+                "{} logs are already open (limit {max_open}); close one before opening another",
+                self.logs.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Add a parsed log as the most recent one and return its id.
+    pub fn insert(&mut self, mut store: LogStore, max_open: usize) -> Result<String, String> {
+        self.ensure_capacity(store.source_path(), max_open)?;
+        self.logs
+            .retain(|(_, open)| open.source_path() != store.source_path());
+        self.next_id += 1;
+        let log_id = format!("log-{}", self.next_id);
+        store.summary.log_id = Some(log_id.clone());
+        self.logs.push((log_id.clone(), store));
+        Ok(log_id)
+    }
+
+    fn position(&self, log_id: Option<&str>) -> Result<usize, String> {
+        match log_id {
+            None if self.logs.is_empty() => Err("no log loaded".to_string()),
+            None => Ok(self.logs.len() - 1),
+            Some(log_id) => self
+                .logs
+                .iter()
+                .position(|(id, _)| id == log_id)
+                .ok_or_else(|| format!("no open log with id {log_id}")),
+        }
+    }
+
+    /// The log with `log_id`, or the most recent one when no id is given.
+    pub fn get(&self, log_id: Option<&str>) -> Result<&LogStore, String> {
+        let idx = self.position(log_id)?;
+        Ok(&self.logs[idx].1)
+    }
+
+    pub fn get_mut(&mut self, log_id: Option<&str>) -> Result<&mut LogStore, String> {
+        let idx = self.position(log_id)?;
+        Ok(&mut self.logs[idx].1)
+    }
+
+    pub fn most_recent(&self) -> Option<&LogStore> {
+        self.logs.last().map(|(_, store)| store)
+    }
+
+    pub fn most_recent_mut(&mut self) -> Option<&mut LogStore> {
+        self.logs.last_mut().map(|(_, store)| store)
+    }
+
+    pub fn is_most_recent(&self, log_id: Option<&str>) -> bool {
+        match (log_id, self.logs.last()) {
+            (_, None) => false,
+            (None, Some(_)) => true,
+            (Some(log_id), Some((id, _))) => id == log_id,
+        }
+    }
+
+    pub fn remove(&mut self, log_id: Option<&str>) -> Option<LogStore> {
+        let idx = self.position(log_id).ok()?;
+        Some(self.logs.remove(idx).1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_engine::LogType;
+
+    fn store(path: &str) -> LogStore {
+        LogStore::from_entries(path, LogType::Tlog, Vec::new())
+    }
+
+    #[test]
+    fn ids_resolve_and_default_to_the_most_recent_log() {
+        let mut logs = OpenLogs::new();
+        let first = logs.insert(store("/a.tlog"), 4).expect("first");
+        let second = logs.insert(store("/b.tlog"), 4).expect("second");
+
+        assert_eq!(logs.get(Some(&first)).unwrap().source_path(), "/a.tlog");
+        assert_eq!(logs.get(None).unwrap().source_path(), "/b.tlog");
+        assert_eq!(
+            logs.get(None).unwrap().summary().log_id.as_deref(),
+            Some(second.as_str())
+        );
+        assert!(logs.get(Some("log-99")).is_err());
+
+        assert!(logs.remove(None).is_some());
+        assert!(logs.is_most_recent(Some(&first)));
+    }
+
+    #[test]
+    fn cap_rejects_new_paths_but_allows_reopening() {
+        let mut logs = OpenLogs::new();
+        logs.insert(store("/a.tlog"), 2).expect("a");
+        logs.insert(store("/b.tlog"), 2).expect("b");
+
+        let error = logs.insert(store("/c.tlog"), 2).expect_err("over the cap");
+        assert!(error.contains("limit 2"));
+
+        let reopened = logs.insert(store("/a.tlog"), 2).expect("reopen a");
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs.get(None).unwrap().source_path(), "/a.tlog");
+        assert_eq!(reopened, "log-3");
+    }
+}
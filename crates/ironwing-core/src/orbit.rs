@@ -0,0 +1,159 @@
+use crate::derived_telemetry::bearing_deg;
+use crate::ipc::{OrbitDirection, OrbitRequest};
+
+pub const ORBIT_MIN_RADIUS_M: f32 = 1.0;
+
+pub const CIRCLE_RADIUS_PARAM: &str = "CIRCLE_RADIUS";
+pub const CIRCLE_RATE_PARAM: &str = "CIRCLE_RATE";
+
+/// Modes tried, in order, when an orbit stops.
+pub const ORBIT_STOP_MODES: &[&str] = &["LOITER", "HOLD", "POSHOLD"];
+
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+/// MAV_CMD_DO_ORBIT yaw behaviour: nose toward the circle centre.
+const ORBIT_YAW_BEHAVIOUR_HOLD_FRONT_TO_CIRCLE_CENTER: f32 = 0.0;
+
+pub fn validate_orbit(request: &OrbitRequest) -> Result<(), String> {
+    if !request.radius_m.is_finite() || request.radius_m < ORBIT_MIN_RADIUS_M {
+        return Err(format!(
+            "orbit radius must be at least {ORBIT_MIN_RADIUS_M} m, got {} m",
+            request.radius_m
+        ));
+    }
+    if !request.velocity_mps.is_finite() || request.velocity_mps <= 0.0 {
+        return Err(format!(
+            "orbit velocity must be positive, got {} m/s",
+            request.velocity_mps
+        ));
+    }
+    if !(-90.0..=90.0).contains(&request.latitude_deg)
+        || !(-180.0..=180.0).contains(&request.longitude_deg)
+    {
+        return Err("orbit centre is not a valid position".to_string());
+    }
+    Ok(())
+}
+
+/// COMMAND_LONG params for MAV_CMD_DO_ORBIT. A negative radius orbits
+/// counter-clockwise; zero orbits means circle until told otherwise.
+pub fn do_orbit_params(request: &OrbitRequest) -> [f32; 7] {
+    let signed_radius = match request.direction {
+        OrbitDirection::Cw => request.radius_m,
+        OrbitDirection::Ccw => -request.radius_m,
+    };
+    [
+        signed_radius,
+        request.velocity_mps,
+        ORBIT_YAW_BEHAVIOUR_HOLD_FRONT_TO_CIRCLE_CENTER,
+        0.0,
+        request.latitude_deg as f32,
+        request.longitude_deg as f32,
+        request.altitude_msl_m,
+    ]
+}
+
+/// CIRCLE_RADIUS (cm) and CIRCLE_RATE (deg/s, negative for counter-clockwise)
+/// for ArduCopter's Circle mode.
+pub fn circle_mode_params(request: &OrbitRequest) -> Vec<(String, f32)> {
+    let rate_deg_s = (request.velocity_mps / request.radius_m).to_degrees();
+    let signed_rate = match request.direction {
+        OrbitDirection::Cw => rate_deg_s,
+        OrbitDirection::Ccw => -rate_deg_s,
+    };
+    vec![
+        (CIRCLE_RADIUS_PARAM.to_string(), request.radius_m * 100.0),
+        (CIRCLE_RATE_PARAM.to_string(), signed_rate),
+    ]
+}
+
+/// Where to send the vehicle before switching to Circle mode.
+///
+/// Circle mode centres the circle `CIRCLE_RADIUS` ahead of the nose. A goto
+/// yaws toward its target, so arriving at the edge point nearest the vehicle
+/// leaves it facing the requested centre.
+pub fn circle_entry_point(
+    request: &OrbitRequest,
+    vehicle_latitude_deg: f64,
+    vehicle_longitude_deg: f64,
+) -> (f64, f64) {
+    let bearing = bearing_deg(
+        request.latitude_deg,
+        request.longitude_deg,
+        vehicle_latitude_deg,
+        vehicle_longitude_deg,
+    );
+    offset_point(
+        request.latitude_deg,
+        request.longitude_deg,
+        bearing,
+        f64::from(request.radius_m),
+    )
+}
+
+/// The point `distance_m` from the start along `bearing_deg`. Flat-earth,
+/// which is plenty for orbit-sized distances.
+pub fn offset_point(
+    latitude_deg: f64,
+    longitude_deg: f64,
+    bearing_deg: f64,
+    distance_m: f64,
+) -> (f64, f64) {
+    let (sin, cos) = bearing_deg.to_radians().sin_cos();
+    let dlat = distance_m * cos / EARTH_RADIUS_M;
+    let dlon = distance_m * sin / (EARTH_RADIUS_M * latitude_deg.to_radians().cos());
+    (
+        latitude_deg + dlat.to_degrees(),
+        longitude_deg + dlon.to_degrees(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log_engine::haversine_m;
+
+    fn request(direction: OrbitDirection) -> OrbitRequest {
+        OrbitRequest {
+            latitude_deg: 47.397742,
+            longitude_deg: 8.545594,
+            radius_m: 20.0,
+            velocity_mps: 5.0,
+            direction,
+            altitude_msl_m: 520.0,
+        }
+    }
+
+    #[test]
+    fn rejects_radius_under_one_metre() {
+        let mut small = request(OrbitDirection::Cw);
+        small.radius_m = 0.5;
+        assert!(validate_orbit(&small).is_err());
+        small.radius_m = ORBIT_MIN_RADIUS_M;
+        assert!(validate_orbit(&small).is_ok());
+    }
+
+    #[test]
+    fn direction_sets_the_sign_of_radius_and_rate() {
+        assert_eq!(do_orbit_params(&request(OrbitDirection::Cw))[0], 20.0);
+        assert_eq!(do_orbit_params(&request(OrbitDirection::Ccw))[0], -20.0);
+
+        let cw = circle_mode_params(&request(OrbitDirection::Cw));
+        let ccw = circle_mode_params(&request(OrbitDirection::Ccw));
+        assert_eq!(cw[0], (CIRCLE_RADIUS_PARAM.to_string(), 2000.0));
+        assert!((cw[1].1 - 14.323945).abs() < 1e-4);
+        assert_eq!(ccw[1].1, -cw[1].1);
+    }
+
+    #[test]
+    fn entry_point_is_on_the_circle_between_centre_and_vehicle() {
+        let orbit = request(OrbitDirection::Cw);
+        let (vehicle_lat, vehicle_lon) = (47.3985, 8.545594);
+
+        let (lat, lon) = circle_entry_point(&orbit, vehicle_lat, vehicle_lon);
+
+        let from_centre = haversine_m(orbit.latitude_deg, orbit.longitude_deg, lat, lon);
+        assert!((from_centre - 20.0).abs() < 0.1, "{from_centre}");
+        assert!(lat > orbit.latitude_deg);
+        assert!((lon - orbit.longitude_deg).abs() < 1e-7);
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::{HashMap, VecDeque};
+
+use mavkit::{Param, ParamStore};
+
+use crate::ipc::ParamExternalChange;
+
+/// How long a write we sent claims the next change of its parameter. Long
+/// enough for a slow batch over a telemetry radio.
+pub const PENDING_WRITE_TTL_MS: u64 = 60_000;
+/// External changes kept for `param_external_changes`.
+pub const MAX_EXTERNAL_CHANGES: usize = 500;
+
+/// Tells parameter changes made by this GCS apart from everyone else's.
+/// Writes we send are tagged first; a PARAM_VALUE that changes a stored value
+/// with no tagged write for that name is external. Nothing is attributed until
+/// the first complete download, which is the baseline.
+#[derive(Debug, Clone, Default)]
+pub struct ParamChangeAttribution {
+    baseline: Option<HashMap<String, f32>>,
+    /// Parameter name to the time its write was tagged.
+    pending_writes: HashMap<String, u64>,
+    changes: VecDeque<ParamExternalChange>,
+}
+
+/// Every parameter the vehicle announced has arrived.
+pub fn store_complete(store: &ParamStore) -> bool {
+    let expected = store.expected_count as u32;
+    expected > 0 && store.params.len() as u32 >= expected
+}
+
+impl ParamChangeAttribution {
+    pub fn expect_writes<'a>(&mut self, names: impl IntoIterator<Item = &'a str>, now_ms: u64) {
+        for name in names {
+            self.pending_writes.insert(name.to_string(), now_ms);
+        }
+    }
+
+    /// Compare `params` with the last values seen. `complete` is false while a
+    /// download is running, when nothing is attributed or recorded.
+    pub fn observe(
+        &mut self,
+        params: &HashMap<String, Param>,
+        complete: bool,
+        now_ms: u64,
+    ) -> Vec<ParamExternalChange> {
+        if !complete {
+            return Vec::new();
+        }
+        let values: HashMap<String, f32> = params
+            .iter()
+            .map(|(name, param)| (name.clone(), param.value))
+            .collect();
+        let Some(baseline) = self.baseline.replace(values) else {
+            return Vec::new();
+        };
+        self.pending_writes
+            .retain(|_, tagged_ms| now_ms.saturating_sub(*tagged_ms) < PENDING_WRITE_TTL_MS);
+
+        let mut changes = Vec::new();
+        for (name, param) in params {
+            let Some(&old_value) = baseline.get(name) else {
+                continue;
+            };
+            if old_value.to_bits() == param.value.to_bits() {
+                continue;
+            }
+            if self.pending_writes.remove(name).is_some() {
+                continue;
+            }
+            changes.push(ParamExternalChange {
+                name: name.clone(),
+                old_value,
+                new_value: param.value,
+                timestamp_unix_ms: now_ms,
+            });
+        }
+        changes.sort_by(|a, b| a.name.cmp(&b.name));
+        for change in &changes {
+            if self.changes.len() == MAX_EXTERNAL_CHANGES {
+                self.changes.pop_front();
+            }
+            self.changes.push_back(change.clone());
+        }
+        changes
+    }
+
+    /// External changes this session, oldest first.
+    pub fn changes(&self) -> Vec<ParamExternalChange> {
+        self.changes.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mavkit::ParamType;
+
+    use super::*;
+
+    fn params(values: &[(&str, f32)]) -> HashMap<String, Param> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, (name, value))| {
+                (
+                    name.to_string(),
+                    Param {
+                        name: name.to_string(),
+                        value: *value,
+                        param_type: ParamType::Real32,
+                        index: index as u16,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn only_untagged_changes_after_the_download_are_external() {
+        let mut attribution = ParamChangeAttribution::default();
+        // Values filling in during the download are never reported.
+        assert!(
+            attribution
+                .observe(&params(&[("RTL_ALT", 0.0)]), false, 0)
+                .is_empty()
+        );
+        let downloaded = params(&[("RTL_ALT", 1500.0), ("WPNAV_SPEED", 500.0)]);
+        assert!(attribution.observe(&downloaded, true, 1_000).is_empty());
+
+        attribution.expect_writes(["RTL_ALT"], 2_000);
+        let ours = params(&[("RTL_ALT", 2000.0), ("WPNAV_SPEED", 500.0)]);
+        assert!(attribution.observe(&ours, true, 2_500).is_empty());
+
+        let theirs = params(&[("RTL_ALT", 2000.0), ("WPNAV_SPEED", 800.0)]);
+        let changes = attribution.observe(&theirs, true, 3_000);
+        assert_eq!(
+            changes,
+            [ParamExternalChange {
+                name: "WPNAV_SPEED".to_string(),
+                old_value: 500.0,
+                new_value: 800.0,
+                timestamp_unix_ms: 3_000,
+            }]
+        );
+        assert_eq!(attribution.changes(), changes);
+    }
+
+    #[test]
+    fn tags_expire_and_are_used_once() {
+        let mut attribution = ParamChangeAttribution::default();
+        attribution.observe(&params(&[("RTL_ALT", 1500.0)]), true, 0);
+        attribution.expect_writes(["RTL_ALT"], 0);
+        assert!(
+            attribution
+                .observe(&params(&[("RTL_ALT", 1600.0)]), true, 100)
+                .is_empty()
+        );
+        // The tag was spent on our own write.
+        assert_eq!(
+            attribution
+                .observe(&params(&[("RTL_ALT", 1700.0)]), true, 200)
+                .len(),
+            1
+        );
+
+        attribution.expect_writes(["RTL_ALT"], 300);
+        let late = 300 + PENDING_WRITE_TTL_MS;
+        assert_eq!(
+            attribution
+                .observe(&params(&[("RTL_ALT", 1800.0)]), true, late)
+                .len(),
+            1
+        );
+    }
+}
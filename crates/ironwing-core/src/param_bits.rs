@@ -0,0 +1,207 @@
+use mavkit::{Param, ParamType};
+
+use crate::ipc::params::{ParamBitState, ParamMetadata};
+use crate::param_value::{ParamEncoding, ParamValue, param_value};
+
+/// Number of bits an integer parameter of `param_type` holds, `None` for
+/// floats.
+fn bit_width(param_type: ParamType) -> Option<u8> {
+    match param_type {
+        ParamType::Uint8 | ParamType::Int8 => Some(8),
+        ParamType::Uint16 | ParamType::Int16 => Some(16),
+        ParamType::Uint32 | ParamType::Int32 => Some(32),
+        ParamType::Real32 => None,
+    }
+}
+
+/// The raw bits of an integer parameter. Signed types are taken as two's
+/// complement at their declared width, so an INT8 of -1 has all eight bits
+/// set.
+pub fn param_bits(param: &Param, encoding: ParamEncoding) -> Result<u32, String> {
+    Ok(match param_value(param, encoding) {
+        ParamValue::Uint8(value) => value.into(),
+        ParamValue::Int8(value) => (value as u8).into(),
+        ParamValue::Uint16(value) => value.into(),
+        ParamValue::Int16(value) => (value as u16).into(),
+        ParamValue::Uint32(value) => value,
+        ParamValue::Int32(value) => value as u32,
+        ParamValue::Real32(_) => {
+            return Err(format!(
+                "{} is a float parameter, not a bitmask",
+                param.name
+            ));
+        }
+    })
+}
+
+/// `bits` as a value of `param_type`; the inverse of [`param_bits`].
+fn typed_value(bits: u32, param_type: ParamType) -> ParamValue {
+    match param_type {
+        ParamType::Uint8 => ParamValue::Uint8(bits as u8),
+        ParamType::Int8 => ParamValue::Int8(bits as u8 as i8),
+        ParamType::Uint16 => ParamValue::Uint16(bits as u16),
+        ParamType::Int16 => ParamValue::Int16(bits as u16 as i16),
+        ParamType::Uint32 => ParamValue::Uint32(bits),
+        ParamType::Int32 => ParamValue::Int32(bits as i32),
+        ParamType::Real32 => ParamValue::Real32(bits as f32),
+    }
+}
+
+/// Apply `set` and `clear` to `param` with integer math in its declared
+/// type. Whether the result can be sent exactly is left to
+/// [`ParamValue::encode`].
+pub fn apply_param_bits(
+    param: &Param,
+    encoding: ParamEncoding,
+    set: &[u8],
+    clear: &[u8],
+) -> Result<ParamValue, String> {
+    let width = bit_width(param.param_type)
+        .ok_or_else(|| format!("{} is a float parameter, not a bitmask", param.name))?;
+    if let Some(bit) = set.iter().find(|bit| clear.contains(bit)) {
+        return Err(format!("bit {bit} is both set and cleared"));
+    }
+    if let Some(bit) = set.iter().chain(clear).find(|&&bit| bit >= width) {
+        return Err(format!("{} has no bit {bit}", param.name));
+    }
+    let mut bits = param_bits(param, encoding)?;
+    for &bit in set {
+        bits |= 1 << bit;
+    }
+    for &bit in clear {
+        bits &= !(1 << bit);
+    }
+    Ok(typed_value(bits, param.param_type))
+}
+
+/// Every labelled bit of `param`, plus any set bit the metadata has no label
+/// for, in bit order.
+pub fn describe_param_bits(
+    param: &Param,
+    encoding: ParamEncoding,
+    metadata: Option<&ParamMetadata>,
+) -> Result<Vec<ParamBitState>, String> {
+    let bits = param_bits(param, encoding)?;
+    let labels = metadata
+        .and_then(|metadata| metadata.bitmask.as_deref())
+        .unwrap_or_default();
+    let width = bit_width(param.param_type).unwrap_or(32);
+    Ok((0..width)
+        .filter_map(|bit| {
+            let set = bits & (1 << bit) != 0;
+            let label = labels.iter().find(|label| label.bit == bit);
+            match (label, set) {
+                (Some(label), _) => Some(label.label.clone()),
+                (None, true) => Some(format!("Bit {bit}")),
+                (None, false) => None,
+            }
+            .map(|label| ParamBitState { bit, label, set })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::params::ParamBitLabel;
+
+    fn param(value: f32, param_type: ParamType) -> Param {
+        Param {
+            name: "LOG_BITMASK".to_string(),
+            value,
+            param_type,
+            index: 0,
+        }
+    }
+
+    fn apply(param: &Param, set: &[u8], clear: &[u8]) -> Result<ParamValue, String> {
+        apply_param_bits(param, ParamEncoding::CCast, set, clear)
+    }
+
+    #[test]
+    fn bits_are_applied_with_integer_math() {
+        let log_bitmask = param(176_126.0, ParamType::Int32);
+        // Bits 1-11, 13, 15 and 17.
+        let value = apply(&log_bitmask, &[0], &[1, 17]).unwrap();
+        assert_eq!(value, ParamValue::Int32(45_053));
+
+        // ARMING_CHECK = -1 (INT32) means every check.
+        let arming_check = param(-1.0, ParamType::Int32);
+        assert_eq!(apply(&arming_check, &[], &[0]), Ok(ParamValue::Int32(-2)));
+        assert_eq!(
+            apply(&arming_check, &[], &[31]),
+            Ok(ParamValue::Int32(i32::MAX))
+        );
+
+        let int8 = param(-1.0, ParamType::Int8);
+        assert_eq!(param_bits(&int8, ParamEncoding::CCast), Ok(0xff));
+        assert_eq!(apply(&int8, &[], &[7]), Ok(ParamValue::Int8(127)));
+        assert!(apply(&int8, &[8], &[]).is_err());
+        assert!(apply(&int8, &[1], &[1]).is_err());
+        assert!(apply(&param(1.0, ParamType::Real32), &[0], &[]).is_err());
+    }
+
+    #[test]
+    fn wide_bitmasks_stay_exact_where_the_encoding_allows() {
+        // Bit 24 plus bit 0 needs 25 significant bits.
+        let ccast = param(16_777_216.0, ParamType::Uint32);
+        let value = apply(&ccast, &[0], &[]).unwrap();
+        assert_eq!(value, ParamValue::Uint32(16_777_217));
+        assert!(value.encode(ParamEncoding::CCast).is_err());
+
+        let byte_wise = param(f32::from_bits(16_777_216), ParamType::Uint32);
+        let value = apply_param_bits(&byte_wise, ParamEncoding::ByteWise, &[0], &[]).unwrap();
+        assert_eq!(
+            value.encode(ParamEncoding::ByteWise).map(f32::to_bits),
+            Ok(16_777_217)
+        );
+    }
+
+    #[test]
+    fn labels_come_from_metadata() {
+        let metadata = ParamMetadata {
+            bitmask: Some(vec![
+                ParamBitLabel {
+                    bit: 0,
+                    label: "Fast attitude".to_string(),
+                },
+                ParamBitLabel {
+                    bit: 2,
+                    label: "GPS".to_string(),
+                },
+            ]),
+            ..Default::default()
+        };
+        let bits = describe_param_bits(
+            &param(10.0, ParamType::Int32),
+            ParamEncoding::CCast,
+            Some(&metadata),
+        )
+        .unwrap();
+        assert_eq!(
+            bits,
+            vec![
+                ParamBitState {
+                    bit: 0,
+                    label: "Fast attitude".to_string(),
+                    set: false,
+                },
+                ParamBitState {
+                    bit: 1,
+                    label: "Bit 1".to_string(),
+                    set: true,
+                },
+                ParamBitState {
+                    bit: 2,
+                    label: "GPS".to_string(),
+                    set: false,
+                },
+                ParamBitState {
+                    bit: 3,
+                    label: "Bit 3".to_string(),
+                    set: true,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use std::fmt;
+
+use mavkit::{AutopilotType, Param, ParamStore, ParamType};
+
+/// How integer parameters travel in the `float` field of PARAM_VALUE and
+/// PARAM_SET.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamEncoding {
+    /// The integer is converted to the nearest float, so INT32/UINT32 values
+    /// past 2^24 cannot be sent exactly (ArduPilot).
+    CCast,
+    /// The integer's bytes are copied into the float unchanged
+    /// (MAV_PROTOCOL_CAPABILITY_PARAM_ENCODE_BYTEWISE, used by PX4).
+    ByteWise,
+}
+
+impl ParamEncoding {
+    pub fn for_autopilot(autopilot: AutopilotType) -> Self {
+        match autopilot {
+            AutopilotType::Px4 => Self::ByteWise,
+            _ => Self::CCast,
+        }
+    }
+}
+
+/// A parameter value in its declared MAV_PARAM_TYPE.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParamValue {
+    Uint8(u8),
+    Int8(i8),
+    Uint16(u16),
+    Int16(i16),
+    Uint32(u32),
+    Int32(i32),
+    Real32(f32),
+}
+
+impl ParamValue {
+    pub fn param_type(self) -> ParamType {
+        match self {
+            Self::Uint8(_) => ParamType::Uint8,
+            Self::Int8(_) => ParamType::Int8,
+            Self::Uint16(_) => ParamType::Uint16,
+            Self::Int16(_) => ParamType::Int16,
+            Self::Uint32(_) => ParamType::Uint32,
+            Self::Int32(_) => ParamType::Int32,
+            Self::Real32(_) => ParamType::Real32,
+        }
+    }
+
+    /// `value` as `param_type`. Integer types refuse fractions and values
+    /// outside their range instead of truncating.
+    pub fn from_number(value: f64, param_type: ParamType) -> Result<Self, String> {
+        if param_type == ParamType::Real32 {
+            return Ok(Self::Real32(value as f32));
+        }
+        if value.fract() != 0.0 || !value.is_finite() {
+            return Err(format!("{value} is not an integer"));
+        }
+        let integer = value as i64;
+        let out_of_range = || format!("{value} is out of range for {param_type:?}");
+        Ok(match param_type {
+            ParamType::Uint8 => Self::Uint8(integer.try_into().map_err(|_| out_of_range())?),
+            ParamType::Int8 => Self::Int8(integer.try_into().map_err(|_| out_of_range())?),
+            ParamType::Uint16 => Self::Uint16(integer.try_into().map_err(|_| out_of_range())?),
+            ParamType::Int16 => Self::Int16(integer.try_into().map_err(|_| out_of_range())?),
+            ParamType::Uint32 => Self::Uint32(integer.try_into().map_err(|_| out_of_range())?),
+            ParamType::Int32 => Self::Int32(integer.try_into().map_err(|_| out_of_range())?),
+            ParamType::Real32 => unreachable!(),
+        })
+    }
+
+    /// Exact for every integer type.
+    pub fn as_f64(self) -> f64 {
+        match self {
+            Self::Uint8(value) => value.into(),
+            Self::Int8(value) => value.into(),
+            Self::Uint16(value) => value.into(),
+            Self::Int16(value) => value.into(),
+            Self::Uint32(value) => value.into(),
+            Self::Int32(value) => value.into(),
+            Self::Real32(value) => value.into(),
+        }
+    }
+
+    /// Read the float field of a PARAM_VALUE declared as `param_type`.
+    pub fn decode(wire: f32, param_type: ParamType, encoding: ParamEncoding) -> Self {
+        match (encoding, param_type) {
+            (_, ParamType::Real32) => Self::Real32(wire),
+            (ParamEncoding::CCast, ParamType::Uint8) => Self::Uint8(wire as u8),
+            (ParamEncoding::CCast, ParamType::Int8) => Self::Int8(wire as i8),
+            (ParamEncoding::CCast, ParamType::Uint16) => Self::Uint16(wire as u16),
+            (ParamEncoding::CCast, ParamType::Int16) => Self::Int16(wire as i16),
+            (ParamEncoding::CCast, ParamType::Uint32) => Self::Uint32(wire as u32),
+            (ParamEncoding::CCast, ParamType::Int32) => Self::Int32(wire as i32),
+            (ParamEncoding::ByteWise, param_type) => {
+                let bits = wire.to_bits();
+                match param_type {
+                    ParamType::Uint8 => Self::Uint8(bits as u8),
+                    ParamType::Int8 => Self::Int8(bits as u8 as i8),
+                    ParamType::Uint16 => Self::Uint16(bits as u16),
+                    ParamType::Int16 => Self::Int16(bits as u16 as i16),
+                    ParamType::Uint32 => Self::Uint32(bits),
+                    _ => Self::Int32(bits as i32),
+                }
+            }
+        }
+    }
+
+    /// The float field of a PARAM_SET carrying this value. Fails under
+    /// [`ParamEncoding::CCast`] when the nearest float is a different
+    /// integer.
+    pub fn encode(self, encoding: ParamEncoding) -> Result<f32, String> {
+        if let Self::Real32(value) = self {
+            return Ok(value);
+        }
+        match encoding {
+            ParamEncoding::CCast => {
+                let wire = self.as_f64() as f32;
+                if f64::from(wire) != self.as_f64() {
+                    return Err(format!(
+                        "{self} cannot be sent exactly; this autopilot carries integer \
+                         parameters as floats"
+                    ));
+                }
+                Ok(wire)
+            }
+            ParamEncoding::ByteWise => {
+                let bits = match self {
+                    Self::Uint8(value) => u32::from(value),
+                    Self::Int8(value) => u32::from(value as u8),
+                    Self::Uint16(value) => u32::from(value),
+                    Self::Int16(value) => u32::from(value as u16),
+                    Self::Uint32(value) => value,
+                    Self::Int32(value) => value as u32,
+                    Self::Real32(_) => unreachable!(),
+                };
+                Ok(f32::from_bits(bits))
+            }
+        }
+    }
+}
+
+impl fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Uint8(value) => write!(f, "{value}"),
+            Self::Int8(value) => write!(f, "{value}"),
+            Self::Uint16(value) => write!(f, "{value}"),
+            Self::Int16(value) => write!(f, "{value}"),
+            Self::Uint32(value) => write!(f, "{value}"),
+            Self::Int32(value) => write!(f, "{value}"),
+            Self::Real32(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// The typed value of a parameter as received from the vehicle.
+pub fn param_value(param: &Param, encoding: ParamEncoding) -> ParamValue {
+    ParamValue::decode(param.value, param.param_type, encoding)
+}
+
+/// How values are held in stores handed to the frontend: every integer is
+/// the number itself, whatever the autopilot puts on the wire.
+pub const DISPLAY_ENCODING: ParamEncoding = ParamEncoding::CCast;
+
+/// `store` as received under `encoding`, with each value rewritten into
+/// [`DISPLAY_ENCODING`]. INT32/UINT32 values past 2^24 are rounded to the
+/// nearest float, as the frontend holds them as numbers anyway.
+pub fn display_param_store(mut store: ParamStore, encoding: ParamEncoding) -> ParamStore {
+    for param in store.params.values_mut() {
+        param.value = display_value(param, encoding);
+    }
+    store
+}
+
+/// One parameter's value in [`DISPLAY_ENCODING`].
+pub fn display_value(param: &Param, encoding: ParamEncoding) -> f32 {
+    if encoding == DISPLAY_ENCODING {
+        return param.value;
+    }
+    param_value(param, encoding).as_f64() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_TYPES: [ParamType; 7] = [
+        ParamType::Uint8,
+        ParamType::Int8,
+        ParamType::Uint16,
+        ParamType::Int16,
+        ParamType::Uint32,
+        ParamType::Int32,
+        ParamType::Real32,
+    ];
+
+    fn extremes(param_type: ParamType) -> Vec<f64> {
+        let (min, max) = match param_type {
+            ParamType::Uint8 => (0.0, f64::from(u8::MAX)),
+            ParamType::Int8 => (f64::from(i8::MIN), f64::from(i8::MAX)),
+            ParamType::Uint16 => (0.0, f64::from(u16::MAX)),
+            ParamType::Int16 => (f64::from(i16::MIN), f64::from(i16::MAX)),
+            ParamType::Uint32 => (0.0, f64::from(u32::MAX)),
+            ParamType::Int32 => (f64::from(i32::MIN), f64::from(i32::MAX)),
+            ParamType::Real32 => (-1.5e9, 0.125),
+        };
+        vec![min, max, 0.0, 1.0, 100.0]
+    }
+
+    #[test]
+    fn display_stores_hold_numbers_not_wire_bits() {
+        let param = |name: &str, value: f32, param_type| {
+            (
+                name.to_string(),
+                Param {
+                    name: name.to_string(),
+                    value,
+                    param_type,
+                    index: 0,
+                },
+            )
+        };
+        let store = ParamStore {
+            params: [
+                param("SYS_AUTOSTART", f32::from_bits(4001), ParamType::Int32),
+                param("MPC_XY_VEL_MAX", 12.0, ParamType::Real32),
+            ]
+            .into_iter()
+            .collect(),
+            expected_count: 2,
+        };
+        // PX4 copies the integer's bytes into the float field.
+        let display = display_param_store(store.clone(), ParamEncoding::ByteWise);
+        assert_eq!(display.params["SYS_AUTOSTART"].value, 4001.0);
+        assert_eq!(display.params["MPC_XY_VEL_MAX"].value, 12.0);
+        let unchanged = display_param_store(store, ParamEncoding::CCast);
+        assert_eq!(unchanged.params["SYS_AUTOSTART"].value.to_bits(), 4001);
+    }
+
+    #[test]
+    fn byte_wise_round_trips_every_value_of_every_type() {
+        for param_type in ALL_TYPES {
+            for number in extremes(param_type) {
+                let value = ParamValue::from_number(number, param_type).unwrap();
+                let wire = value.encode(ParamEncoding::ByteWise).unwrap();
+                let decoded = ParamValue::decode(wire, param_type, ParamEncoding::ByteWise);
+                assert_eq!(decoded, value, "{param_type:?} {number}");
+                assert_eq!(decoded.param_type(), param_type);
+                assert_eq!(decoded.as_f64(), number);
+            }
+        }
+    }
+
+    #[test]
+    fn c_cast_round_trips_what_a_float_can_hold_and_refuses_the_rest() {
+        for param_type in ALL_TYPES {
+            for number in extremes(param_type) {
+                let value = ParamValue::from_number(number, param_type).unwrap();
+                match value.encode(ParamEncoding::CCast) {
+                    Ok(wire) => {
+                        let decoded = ParamValue::decode(wire, param_type, ParamEncoding::CCast);
+                        assert_eq!(decoded, value, "{param_type:?} {number}");
+                    }
+                    // Only 32-bit extremes exceed the 24-bit mantissa.
+                    Err(_) => assert!(
+                        matches!(param_type, ParamType::Uint32 | ParamType::Int32)
+                            && number.abs() > f64::from(1 << 24),
+                        "{param_type:?} {number}"
+                    ),
+                }
+            }
+        }
+        // i32::MIN is a power of two, so it survives the cast.
+        assert!(
+            ParamValue::Int32(i32::MIN)
+                .encode(ParamEncoding::CCast)
+                .is_ok()
+        );
+        assert!(
+            ParamValue::Int32(16_777_217)
+                .encode(ParamEncoding::CCast)
+                .is_err()
+        );
+        assert_eq!(
+            ParamValue::Int32(16_777_217)
+                .encode(ParamEncoding::ByteWise)
+                .map(f32::to_bits),
+            Ok(16_777_217)
+        );
+    }
+
+    #[test]
+    fn numbers_are_checked_against_the_declared_type() {
+        assert!(ParamValue::from_number(1.5, ParamType::Int32).is_err());
+        assert!(ParamValue::from_number(256.0, ParamType::Uint8).is_err());
+        assert!(ParamValue::from_number(-1.0, ParamType::Uint16).is_err());
+        assert_eq!(
+            ParamValue::from_number(-1.0, ParamType::Int8),
+            Ok(ParamValue::Int8(-1))
+        );
+        assert_eq!(ParamValue::Uint32(4_000_000_123).to_string(), "4000000123");
+        assert_eq!(ParamValue::Real32(0.25).to_string(), "0.25");
+    }
+}
@@ -0,0 +1,750 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use mavkit::{AutopilotType, Param, ParamStore, ParamType};
+
+use crate::ipc::params::{
+    ParamExportFormat, ParamExportOptions, ParamFileEntry, ParamFileWarning, ParamMetadata,
+    ParamSearchOptions, ParamStoreDelta, ParsedParamFile,
+};
+use crate::param_value::{ParamEncoding, param_value};
+
+/// Tolerance used when comparing a value against its metadata default; values
+/// round-trip through `f32` on the wire so exact equality is too strict.
+const DEFAULT_VALUE_EPSILON: f32 = 1e-6;
+
+/// Whether parameter writes are followed by a storage commit when the caller
+/// does not say. ArduPilot saves every PARAM_SET to EEPROM itself; PX4 keeps
+/// changes in RAM until MAV_CMD_PREFLIGHT_STORAGE.
+pub fn persist_params_by_default(autopilot: AutopilotType) -> bool {
+    matches!(autopilot, AutopilotType::Px4)
+}
+
+/// Serialize `store` to a parameter file, applying the filters in `options`.
+///
+/// `system_id`/`component_id` are only used by the QGC format, which records the
+/// originating vehicle on every line.
+pub fn export_params(
+    store: &ParamStore,
+    options: &ParamExportOptions,
+    system_id: u8,
+    component_id: u8,
+    encoding: ParamEncoding,
+) -> String {
+    let mut params: Vec<&Param> = store
+        .params
+        .values()
+        .filter(|param| {
+            options
+                .names
+                .as_ref()
+                .is_none_or(|names| names.iter().any(|name| name == &param.name))
+        })
+        .filter(|param| {
+            !options.modified_only || !matches_default(param, &options.metadata, encoding)
+        })
+        .collect();
+    params.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut out = String::new();
+    if options.format == ParamExportFormat::Qgc {
+        out.push_str("# Onboard parameters\n#\n# Vehicle-Id Component-Id Name Value Type\n");
+    }
+
+    for param in params {
+        if options.include_comments
+            && let Some(comment) = options.metadata.get(&param.name).and_then(metadata_comment)
+        {
+            let _ = writeln!(out, "# {comment}");
+        }
+        let value = param_value(param, encoding);
+        let _ = match options.format {
+            ParamExportFormat::MissionPlanner => writeln!(out, "{},{value}", param.name),
+            ParamExportFormat::Qgc => writeln!(
+                out,
+                "{system_id}\t{component_id}\t{}\t{value}\t{}",
+                param.name,
+                mav_param_type_code(param.param_type)
+            ),
+        };
+    }
+
+    out
+}
+
+/// Serialize every parameter in `store`, whose values are held in
+/// `encoding`, as `format`. QGC rows name vehicle 1, component 1.
+pub fn format_param_file(
+    store: &ParamStore,
+    format: ParamExportFormat,
+    encoding: ParamEncoding,
+) -> String {
+    let options = ParamExportOptions {
+        format,
+        ..Default::default()
+    };
+    export_params(store, &options, 1, 1, encoding)
+}
+
+/// Longest name MAVLink's 16-byte param_id field holds.
+const MAX_PARAM_NAME_LEN: usize = 16;
+
+/// A data row: `NAME,VALUE` or `NAME VALUE` from Mission Planner and this
+/// app, or `SYSID COMPID NAME VALUE TYPE` from QGroundControl.
+enum ParamRow<'a> {
+    Plain {
+        name: &'a str,
+        value: &'a str,
+    },
+    Qgc {
+        name: &'a str,
+        value: &'a str,
+        type_code: &'a str,
+    },
+}
+
+fn split_row(line: &str) -> Option<ParamRow<'_>> {
+    let fields: Vec<&str> = line
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|field| !field.is_empty())
+        .collect();
+    match fields[..] {
+        [name, value] => Some(ParamRow::Plain { name, value }),
+        [system_id, component_id, name, value, type_code]
+            if system_id.parse::<u8>().is_ok() && component_id.parse::<u8>().is_ok() =>
+        {
+            Some(ParamRow::Qgc {
+                name,
+                value,
+                type_code,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn parse_name(name: &str) -> Result<String, String> {
+    let valid = name.len() <= MAX_PARAM_NAME_LEN
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(name.to_string())
+    } else {
+        Err(format!("invalid name {name:?}"))
+    }
+}
+
+fn parse_number(value: &str) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| format!("invalid value {value:?}"))
+}
+
+fn parse_entry(row: ParamRow<'_>, line: u32) -> Result<ParamFileEntry, String> {
+    let (name, value, param_type) = match row {
+        ParamRow::Plain { name, value } => (name, value, None),
+        ParamRow::Qgc {
+            name,
+            value,
+            type_code,
+        } => {
+            let param_type = type_code
+                .parse::<u8>()
+                .ok()
+                .and_then(param_type_from_code)
+                .ok_or_else(|| format!("unknown type {type_code:?}"))?;
+            (name, value, Some(param_type))
+        }
+    };
+    Ok(ParamFileEntry {
+        name: parse_name(name)?,
+        value: parse_number(value)?,
+        param_type,
+        line,
+    })
+}
+
+/// Parse a parameter file in any of the formats ground stations write:
+/// this app's and Mission Planner's `NAME,VALUE` (CRLF line ends and `#`
+/// comments after the value included) or QGroundControl's tab-separated
+/// rows with vehicle, component and type columns. Rows that do not parse
+/// become warnings instead of failing the file. Values are kept as `f64`
+/// so integers past 2^24 survive until they are encoded for their declared
+/// type.
+pub fn parse_param_file(contents: &str) -> ParsedParamFile {
+    let mut parsed = ParsedParamFile {
+        format: ParamExportFormat::MissionPlanner,
+        params: Vec::new(),
+        warnings: Vec::new(),
+    };
+    for (index, text) in contents.lines().enumerate() {
+        let line = index as u32 + 1;
+        let data = text.split('#').next().unwrap_or_default().trim();
+        if data.is_empty() {
+            continue;
+        }
+        let entry = match split_row(data) {
+            Some(row) => {
+                if matches!(row, ParamRow::Qgc { .. }) {
+                    parsed.format = ParamExportFormat::Qgc;
+                }
+                parse_entry(row, line)
+            }
+            None => Err("expected NAME,VALUE".to_string()),
+        };
+        match entry {
+            Ok(entry) => parsed.params.push(entry),
+            Err(message) => parsed.warnings.push(ParamFileWarning {
+                line,
+                text: text.trim_end().to_string(),
+                message,
+            }),
+        }
+    }
+    parsed
+}
+
+/// Filter `store` by name and, optionally, metadata description text.
+///
+/// Matching is case-insensitive. Results are sorted by name and truncated to
+/// `options.limit` when it is non-zero.
+pub fn search_params(
+    store: &ParamStore,
+    query: &str,
+    options: &ParamSearchOptions,
+    metadata: &HashMap<String, ParamMetadata>,
+    encoding: ParamEncoding,
+) -> Vec<Param> {
+    let query = query.trim().to_ascii_lowercase();
+    let mut matches: Vec<&Param> = store
+        .params
+        .values()
+        .filter(|param| {
+            if query.is_empty() {
+                return true;
+            }
+            let name = param.name.to_ascii_lowercase();
+            let name_matches = if options.prefix_only {
+                name.starts_with(&query)
+            } else {
+                name.contains(&query)
+            };
+            name_matches
+                || (options.include_metadata_text
+                    && metadata
+                        .get(&param.name)
+                        .and_then(|entry| entry.description.as_deref())
+                        .is_some_and(|text| text.to_lowercase().contains(&query)))
+        })
+        .filter(|param| !options.modified_only || !matches_default(param, metadata, encoding))
+        .collect();
+    matches.sort_by(|a, b| a.name.cmp(&b.name));
+    if options.limit > 0 {
+        matches.truncate(options.limit);
+    }
+    matches.into_iter().cloned().collect()
+}
+
+/// Diff `next` against the last emitted store.
+///
+/// Returns a full delta when nothing has been emitted yet, `None` when no
+/// parameter changed, and otherwise only the added or changed parameters plus
+/// the names of those no longer in `next`.
+pub fn param_store_delta(
+    previous: Option<&HashMap<String, Param>>,
+    next: &ParamStore,
+    generation: u64,
+) -> Option<ParamStoreDelta> {
+    let Some(previous) = previous else {
+        let mut changed: Vec<Param> = next.params.values().cloned().collect();
+        changed.sort_by_key(|param| param.index);
+        return Some(ParamStoreDelta {
+            generation,
+            full: true,
+            received: next.params.len() as u32,
+            total: next.expected_count as u32,
+            changed,
+            removed: Vec::new(),
+        });
+    };
+
+    let mut changed: Vec<Param> = next
+        .params
+        .iter()
+        .filter(|(name, param)| previous.get(*name) != Some(*param))
+        .map(|(_, param)| param.clone())
+        .collect();
+    let mut removed: Vec<String> = previous
+        .keys()
+        .filter(|name| !next.params.contains_key(*name))
+        .cloned()
+        .collect();
+    if changed.is_empty() && removed.is_empty() {
+        return None;
+    }
+    changed.sort_by_key(|param| param.index);
+    removed.sort();
+    Some(ParamStoreDelta {
+        generation,
+        full: false,
+        received: next.params.len() as u32,
+        total: next.expected_count as u32,
+        changed,
+        removed,
+    })
+}
+
+fn matches_default(
+    param: &Param,
+    metadata: &HashMap<String, ParamMetadata>,
+    encoding: ParamEncoding,
+) -> bool {
+    let value = param_value(param, encoding).as_f64();
+    metadata
+        .get(&param.name)
+        .and_then(|metadata| metadata.default_value)
+        .is_some_and(|default| {
+            (value - f64::from(default)).abs() <= f64::from(DEFAULT_VALUE_EPSILON)
+        })
+}
+
+/// Builds `description (units, range)`, omitting whichever parts are unknown.
+fn metadata_comment(metadata: &ParamMetadata) -> Option<String> {
+    let mut details = Vec::new();
+    if let Some(units) = metadata.units.as_deref().filter(|units| !units.is_empty()) {
+        details.push(units.to_string());
+    }
+    if let (Some(min), Some(max)) = (metadata.min, metadata.max) {
+        details.push(format!("{min} to {max}"));
+    }
+
+    let description = metadata
+        .description
+        .as_deref()
+        .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|text| !text.is_empty());
+
+    match (description, details.is_empty()) {
+        (None, true) => None,
+        (None, false) => Some(format!("({})", details.join(", "))),
+        (Some(description), true) => Some(description),
+        (Some(description), false) => Some(format!("{description} ({})", details.join(", "))),
+    }
+}
+
+/// `MAV_PARAM_TYPE` codes as written in the last column of QGC `.params` files.
+fn param_type_from_code(code: u8) -> Option<ParamType> {
+    match code {
+        1 => Some(ParamType::Uint8),
+        2 => Some(ParamType::Int8),
+        3 => Some(ParamType::Uint16),
+        4 => Some(ParamType::Int16),
+        5 => Some(ParamType::Uint32),
+        6 => Some(ParamType::Int32),
+        9 => Some(ParamType::Real32),
+        _ => None,
+    }
+}
+
+fn mav_param_type_code(param_type: ParamType) -> u8 {
+    match param_type {
+        ParamType::Uint8 => 1,
+        ParamType::Int8 => 2,
+        ParamType::Uint16 => 3,
+        ParamType::Int16 => 4,
+        ParamType::Uint32 => 5,
+        ParamType::Int32 => 6,
+        ParamType::Real32 => 9,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(name: &str, value: f32, param_type: ParamType, index: u16) -> (String, Param) {
+        (
+            name.to_string(),
+            Param {
+                name: name.to_string(),
+                value,
+                param_type,
+                index,
+            },
+        )
+    }
+
+    fn sample_store() -> ParamStore {
+        ParamStore {
+            params: HashMap::from([
+                param("RTL_ALT", 1500.0, ParamType::Int16, 0),
+                param("ATC_RAT_RLL_P", 0.135, ParamType::Real32, 1),
+                param("FRAME_CLASS", 1.0, ParamType::Int8, 2),
+            ]),
+            expected_count: 3,
+        }
+    }
+
+    fn sample_metadata() -> HashMap<String, ParamMetadata> {
+        HashMap::from([
+            (
+                "RTL_ALT".to_string(),
+                ParamMetadata {
+                    description: Some("RTL Altitude".into()),
+                    units: Some("cm".into()),
+                    min: Some(200.0),
+                    max: Some(300000.0),
+                    default_value: Some(1500.0),
+                    bitmask: None,
+                },
+            ),
+            (
+                "ATC_RAT_RLL_P".to_string(),
+                ParamMetadata {
+                    description: Some("Roll axis rate controller P gain".into()),
+                    default_value: Some(0.135),
+                    ..Default::default()
+                },
+            ),
+            (
+                "FRAME_CLASS".to_string(),
+                ParamMetadata {
+                    description: Some("Frame Class".into()),
+                    default_value: Some(0.0),
+                    ..Default::default()
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn mission_planner_export_filters_and_annotates() {
+        let store = sample_store();
+
+        let plain = export_params(
+            &store,
+            &ParamExportOptions::default(),
+            1,
+            1,
+            ParamEncoding::CCast,
+        );
+        assert_eq!(plain, "ATC_RAT_RLL_P,0.135\nFRAME_CLASS,1\nRTL_ALT,1500\n");
+
+        let subset = export_params(
+            &store,
+            &ParamExportOptions {
+                names: Some(vec!["RTL_ALT".into()]),
+                include_comments: true,
+                metadata: sample_metadata(),
+                ..Default::default()
+            },
+            1,
+            1,
+            ParamEncoding::CCast,
+        );
+        assert_eq!(subset, "# RTL Altitude (cm, 200 to 300000)\nRTL_ALT,1500\n");
+
+        let modified = export_params(
+            &store,
+            &ParamExportOptions {
+                modified_only: true,
+                metadata: sample_metadata(),
+                ..Default::default()
+            },
+            1,
+            1,
+            ParamEncoding::CCast,
+        );
+        assert_eq!(modified, "FRAME_CLASS,1\n");
+    }
+
+    #[test]
+    fn qgc_export_includes_vehicle_columns_and_type_codes() {
+        let exported = export_params(
+            &sample_store(),
+            &ParamExportOptions {
+                format: ParamExportFormat::Qgc,
+                include_comments: true,
+                metadata: sample_metadata(),
+                ..Default::default()
+            },
+            3,
+            1,
+            ParamEncoding::CCast,
+        );
+
+        let lines: Vec<&str> = exported.lines().collect();
+        assert_eq!(lines[2], "# Vehicle-Id Component-Id Name Value Type");
+        assert_eq!(lines[3], "# Roll axis rate controller P gain");
+        assert_eq!(lines[4], "3\t1\tATC_RAT_RLL_P\t0.135\t9");
+        assert_eq!(lines[6], "3\t1\tFRAME_CLASS\t1\t2");
+        assert_eq!(lines[8], "3\t1\tRTL_ALT\t1500\t4");
+    }
+
+    #[test]
+    fn parsed_values_keep_integer_precision() {
+        let parsed = parse_param_file("# saved\nLOG_BITMASK,16777217\nATC_RAT_RLL_P 0.135\n\n");
+        let values: Vec<(&str, f64)> = parsed
+            .params
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.value))
+            .collect();
+        assert_eq!(
+            values,
+            vec![("LOG_BITMASK", 16_777_217.0), ("ATC_RAT_RLL_P", 0.135)]
+        );
+        assert!(parsed.warnings.is_empty());
+    }
+
+    fn by_name(parsed: &ParsedParamFile) -> HashMap<&str, f32> {
+        parsed
+            .params
+            .iter()
+            .map(|entry| (entry.name.as_str(), entry.value as f32))
+            .collect()
+    }
+
+    #[test]
+    fn qgc_and_mission_planner_files_are_detected() {
+        let qgc = parse_param_file(include_str!("../tests/fixtures/params/qgc_4x.params"));
+        assert_eq!(qgc.format, ParamExportFormat::Qgc);
+        assert!(qgc.warnings.is_empty(), "{:?}", qgc.warnings);
+        assert_eq!(qgc.params.len(), 11);
+        let type_of = |name: &str| {
+            qgc.params
+                .iter()
+                .find(|entry| entry.name == name)
+                .and_then(|entry| entry.param_type)
+        };
+        assert_eq!(type_of("LOG_BITMASK"), Some(ParamType::Int32));
+        assert_eq!(type_of("ATC_RAT_RLL_P"), Some(ParamType::Real32));
+        assert_eq!(type_of("FRAME_CLASS"), Some(ParamType::Int8));
+        assert_eq!(qgc.params[0].line, 9);
+
+        let mission_planner = parse_param_file(include_str!(
+            "../tests/fixtures/params/mission_planner.param"
+        ));
+        assert_eq!(mission_planner.format, ParamExportFormat::MissionPlanner);
+        assert!(
+            mission_planner.warnings.is_empty(),
+            "{:?}",
+            mission_planner.warnings
+        );
+        assert_eq!(mission_planner.params.len(), 11);
+        assert!(
+            mission_planner
+                .params
+                .iter()
+                .all(|entry| entry.param_type.is_none())
+        );
+        assert_eq!(by_name(&qgc), by_name(&mission_planner));
+    }
+
+    #[test]
+    fn bad_rows_become_warnings_with_line_numbers() {
+        let parsed = parse_param_file(
+            "RTL_ALT,1500\nRTL_ALT,abc\r\nA B C\n1\t1\tANGLE_MAX\t3000\t7\nTHIS_NAME_IS_TOO_LONG,1\nANGLE_MAX,3000\n",
+        );
+        assert_eq!(parsed.params.len(), 2);
+        assert_eq!(parsed.params[1].line, 6);
+        let warnings: Vec<(u32, &str, &str)> = parsed
+            .warnings
+            .iter()
+            .map(|warning| {
+                (
+                    warning.line,
+                    warning.text.as_str(),
+                    warning.message.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                (2, "RTL_ALT,abc", "invalid value \"abc\""),
+                (3, "A B C", "expected NAME,VALUE"),
+                (4, "1\t1\tANGLE_MAX\t3000\t7", "unknown type \"7\""),
+                (
+                    5,
+                    "THIS_NAME_IS_TOO_LONG,1",
+                    "invalid name \"THIS_NAME_IS_TOO_LONG\""
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn qgc_export_parses_back_with_types() {
+        let store = sample_store();
+        let parsed = parse_param_file(&format_param_file(
+            &store,
+            ParamExportFormat::Qgc,
+            ParamEncoding::CCast,
+        ));
+        assert_eq!(parsed.format, ParamExportFormat::Qgc);
+        assert!(parsed.warnings.is_empty(), "{:?}", parsed.warnings);
+        assert_eq!(parsed.params.len(), store.params.len());
+        for entry in &parsed.params {
+            let param = &store.params[&entry.name];
+            assert_eq!(entry.param_type, Some(param.param_type));
+            assert_eq!(entry.value as f32, param.value);
+        }
+    }
+
+    #[test]
+    fn byte_wise_integers_are_exported_exactly() {
+        let store = ParamStore {
+            params: HashMap::from([param(
+                "SYS_AUTOSTART",
+                f32::from_bits(16_777_217),
+                ParamType::Int32,
+                0,
+            )]),
+            expected_count: 1,
+        };
+        let exported = export_params(
+            &store,
+            &ParamExportOptions::default(),
+            1,
+            1,
+            ParamEncoding::ByteWise,
+        );
+        assert_eq!(exported, "SYS_AUTOSTART,16777217\n");
+    }
+
+    #[test]
+    fn search_matches_prefix_substring_and_descriptions() {
+        let store = sample_store();
+        let metadata = sample_metadata();
+        let names = |params: Vec<Param>| -> Vec<String> {
+            params.into_iter().map(|param| param.name).collect()
+        };
+
+        let substring = search_params(
+            &store,
+            "rll",
+            &ParamSearchOptions::default(),
+            &metadata,
+            ParamEncoding::CCast,
+        );
+        assert_eq!(names(substring), ["ATC_RAT_RLL_P"]);
+
+        let prefix = ParamSearchOptions {
+            prefix_only: true,
+            ..Default::default()
+        };
+        assert!(search_params(&store, "rll", &prefix, &metadata, ParamEncoding::CCast).is_empty());
+        assert_eq!(
+            names(search_params(
+                &store,
+                "rt",
+                &prefix,
+                &metadata,
+                ParamEncoding::CCast
+            )),
+            ["RTL_ALT"]
+        );
+
+        let described = ParamSearchOptions {
+            include_metadata_text: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            names(search_params(
+                &store,
+                "altitude",
+                &described,
+                &metadata,
+                ParamEncoding::CCast
+            )),
+            ["RTL_ALT"]
+        );
+
+        let modified_limited = ParamSearchOptions {
+            modified_only: true,
+            limit: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            names(search_params(
+                &store,
+                "",
+                &modified_limited,
+                &metadata,
+                ParamEncoding::CCast
+            )),
+            ["FRAME_CLASS"]
+        );
+    }
+
+    #[test]
+    fn store_delta_emits_only_changed_params_after_first_full_emission() {
+        let mut store = sample_store();
+        let first = param_store_delta(None, &store, 1).expect("initial delta");
+        assert!(first.full);
+        assert_eq!(first.changed.len(), 3);
+
+        let previous = store.params.clone();
+        assert_eq!(param_store_delta(Some(&previous), &store, 2), None);
+
+        store.params.get_mut("RTL_ALT").expect("RTL_ALT").value = 2000.0;
+        let delta = param_store_delta(Some(&previous), &store, 2).expect("changed delta");
+        assert!(!delta.full);
+        assert_eq!(delta.generation, 2);
+        assert_eq!((delta.received, delta.total), (3, 3));
+        assert_eq!(delta.changed.len(), 1);
+        assert_eq!(delta.changed[0].name, "RTL_ALT");
+        assert!(delta.removed.is_empty());
+    }
+
+    #[test]
+    fn store_delta_reports_removed_params() {
+        let mut store = sample_store();
+        let previous = store.params.clone();
+        store.params.remove("RTL_ALT");
+
+        let delta = param_store_delta(Some(&previous), &store, 2).expect("removal delta");
+        assert!(!delta.full);
+        assert!(delta.changed.is_empty());
+        assert_eq!(delta.removed, ["RTL_ALT"]);
+        assert_eq!(delta.received, 2);
+
+        // A param replaced under another name shows up on both sides.
+        let mut renamed = store.params.remove("FRAME_CLASS").expect("FRAME_CLASS");
+        renamed.name = "FRAME_TYPE".to_string();
+        store.params.insert(renamed.name.clone(), renamed);
+        let delta = param_store_delta(Some(&previous), &store, 3).expect("rename delta");
+        assert_eq!(delta.changed.len(), 1);
+        assert_eq!(delta.changed[0].name, "FRAME_TYPE");
+        assert_eq!(delta.removed, ["FRAME_CLASS", "RTL_ALT"]);
+    }
+
+    #[test]
+    fn single_param_delta_is_far_smaller_than_full_store_payload() {
+        let mut store = ParamStore {
+            params: (0..900u16)
+                .map(|index| param(&format!("PARAM_{index:04}"), 1.0, ParamType::Real32, index))
+                .collect(),
+            expected_count: 900,
+        };
+        let previous = store.params.clone();
+        store.params.get_mut("PARAM_0042").expect("param").value = 2.0;
+
+        let full_bytes = serde_json::to_vec(&store).expect("serialize store").len();
+        let delta = param_store_delta(Some(&previous), &store, 2).expect("delta");
+        let delta_bytes = serde_json::to_vec(&delta).expect("serialize delta").len();
+
+        // ~60 KB for the full store versus ~120 B for one changed param.
+        assert!(
+            delta_bytes * 100 < full_bytes,
+            "{delta_bytes} vs {full_bytes}"
+        );
+    }
+
+    #[test]
+    fn only_px4_commits_param_writes_by_default() {
+        assert!(persist_params_by_default(AutopilotType::Px4));
+        assert!(!persist_params_by_default(AutopilotType::ArduPilotMega));
+    }
+}
@@ -0,0 +1,151 @@
+use crate::dialect::WINCH_STATUS_DATA;
+use crate::ipc::{GripperCommand, WinchCommand, WinchStatus};
+
+pub const WINCH_STATUS_MESSAGE_ID: u32 = 9005;
+
+pub const DEFAULT_WINCH_MAX_RATE_MPS: f32 = 1.0;
+
+/// WINCH_ACTIONS values for MAV_CMD_DO_WINCH's param2.
+const WINCH_RELAXED: f32 = 0.0;
+const WINCH_RELATIVE_LENGTH_CONTROL: f32 = 1.0;
+const WINCH_RATE_CONTROL: f32 = 2.0;
+
+/// MAV_WINCH_STATUS_FLAG bits broken out in [`WinchStatus`].
+const WINCH_STATUS_HEALTHY: u32 = 1;
+const WINCH_STATUS_FULLY_RETRACTED: u32 = 2;
+const WINCH_STATUS_MOVING: u32 = 4;
+const WINCH_STATUS_CLUTCH_ENGAGED: u32 = 8;
+const WINCH_STATUS_LOCKED: u32 = 16;
+
+/// WINCH_STATUS sends INT16_MAX for an unknown temperature.
+const TEMPERATURE_UNKNOWN: i16 = i16::MAX;
+
+pub fn validate_winch_max_rate(max_rate_mps: f32) -> Result<(), String> {
+    if !max_rate_mps.is_finite() || max_rate_mps <= 0.0 {
+        return Err("winch_max_rate_mps must be greater than zero".to_string());
+    }
+    Ok(())
+}
+
+/// MAV_CMD_DO_GRIPPER parameters.
+pub fn gripper_params(instance: u8, action: GripperCommand) -> [f32; 7] {
+    let action = match action {
+        GripperCommand::Release => 0.0,
+        GripperCommand::Grab => 1.0,
+    };
+    [f32::from(instance), action, 0.0, 0.0, 0.0, 0.0, 0.0]
+}
+
+/// MAV_CMD_DO_WINCH parameters, refusing rates faster than `max_rate_mps`
+/// in either direction.
+pub fn winch_params(
+    instance: u8,
+    action: WinchCommand,
+    max_rate_mps: f32,
+) -> Result<[f32; 7], String> {
+    let (action, length_m, rate_mps) = match action {
+        WinchCommand::Relaxed => (WINCH_RELAXED, 0.0, 0.0),
+        WinchCommand::RelativeLengthControl { length_m, rate_ms } => {
+            if !length_m.is_finite() {
+                return Err("winch length must be a finite number".to_string());
+            }
+            (WINCH_RELATIVE_LENGTH_CONTROL, length_m, rate_ms)
+        }
+        WinchCommand::RateControl { rate_ms } => (WINCH_RATE_CONTROL, 0.0, rate_ms),
+    };
+    if !rate_mps.is_finite() {
+        return Err("winch rate must be a finite number".to_string());
+    }
+    if rate_mps.abs() > max_rate_mps {
+        return Err(format!(
+            "winch rate {rate_mps} m/s exceeds the {max_rate_mps} m/s limit"
+        ));
+    }
+    Ok([
+        f32::from(instance),
+        action,
+        length_m,
+        rate_mps,
+        0.0,
+        0.0,
+        0.0,
+    ])
+}
+
+fn known(value: f32) -> Option<f32> {
+    value.is_finite().then_some(value)
+}
+
+pub fn winch_status(data: &WINCH_STATUS_DATA) -> WinchStatus {
+    let flags = data.status.bits();
+    WinchStatus {
+        line_length_m: known(data.line_length),
+        speed_mps: known(data.speed),
+        tension_kg: known(data.tension),
+        voltage_v: data.voltage,
+        current_a: known(data.current),
+        temperature_c: (data.temperature != TEMPERATURE_UNKNOWN).then_some(data.temperature),
+        healthy: flags & WINCH_STATUS_HEALTHY != 0,
+        fully_retracted: flags & WINCH_STATUS_FULLY_RETRACTED != 0,
+        moving: flags & WINCH_STATUS_MOVING != 0,
+        clutch_engaged: flags & WINCH_STATUS_CLUTCH_ENGAGED != 0,
+        locked: flags & WINCH_STATUS_LOCKED != 0,
+        status_flags: flags,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::MavWinchStatusFlag;
+
+    #[test]
+    fn winch_actions_map_to_do_winch_params() {
+        assert_eq!(
+            winch_params(1, WinchCommand::Relaxed, 1.0),
+            Ok([1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0])
+        );
+        assert_eq!(
+            winch_params(
+                2,
+                WinchCommand::RelativeLengthControl {
+                    length_m: -3.5,
+                    rate_ms: 0.5
+                },
+                1.0
+            ),
+            Ok([2.0, 1.0, -3.5, 0.5, 0.0, 0.0, 0.0])
+        );
+        assert_eq!(gripper_params(1, GripperCommand::Grab)[1], 1.0);
+        assert_eq!(gripper_params(1, GripperCommand::Release)[1], 0.0);
+    }
+
+    #[test]
+    fn winch_rates_above_the_limit_are_refused() {
+        let error = winch_params(1, WinchCommand::RateControl { rate_ms: -1.5 }, 1.0)
+            .expect_err("too fast");
+        assert!(error.contains("exceeds"), "{error}");
+        assert!(winch_params(1, WinchCommand::RateControl { rate_ms: f32::NAN }, 1.0).is_err());
+        assert!(winch_params(1, WinchCommand::RateControl { rate_ms: 1.0 }, 1.0).is_ok());
+    }
+
+    #[test]
+    fn winch_status_breaks_out_flags_and_unknowns() {
+        let status = winch_status(&WINCH_STATUS_DATA {
+            line_length: 4.2,
+            speed: f32::NAN,
+            tension: 1.5,
+            voltage: 24.0,
+            current: f32::NAN,
+            temperature: i16::MAX,
+            status: MavWinchStatusFlag::MAV_WINCH_STATUS_HEALTHY
+                | MavWinchStatusFlag::MAV_WINCH_STATUS_MOVING,
+            ..Default::default()
+        });
+        assert_eq!(status.line_length_m, Some(4.2));
+        assert_eq!(status.speed_mps, None);
+        assert_eq!(status.temperature_c, None);
+        assert!(status.healthy && status.moving);
+        assert!(!status.locked);
+    }
+}
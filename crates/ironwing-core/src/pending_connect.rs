@@ -0,0 +1,369 @@
+use crate::ipc::{
+    ConnectRequest, ConnectTransport, PendingConnect, PendingConnectSource, SerialFlowControl,
+    SerialParity,
+};
+use crate::transport::DEFAULT_SERIAL_BAUD;
+use crate::udp_bind::parse_udp_bind_addr;
+
+pub const CONNECT_FLAG: &str = "--connect";
+pub const DEEP_LINK_SCHEME: &str = "ironwing://";
+const DEEP_LINK_CONNECT_HOST: &str = "connect";
+const DEEP_LINK_ADDRESS_PARAM: &str = "address";
+
+const ADDRESS_FORMS: &str = "use udpin:0.0.0.0:14550, tcpout:host:5760, serial:/dev/ttyUSB0:57600, ble:AA:BB:CC:DD:EE:FF or spp:AA:BB:CC:DD:EE:FF";
+
+/// A connection given in the process arguments: `--connect <address>`,
+/// `--connect=<address>`, or a deep link that the desktop shell passes as
+/// an argument. `Err` for a `--connect` with nothing after it.
+pub fn launch_argument<I>(args: I) -> Option<Result<(PendingConnectSource, String), String>>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == CONNECT_FLAG {
+            return Some(
+                args.next()
+                    .map(|address| (PendingConnectSource::CommandLine, address))
+                    .ok_or_else(|| format!("{CONNECT_FLAG} needs an address; {ADDRESS_FORMS}")),
+            );
+        }
+        if let Some(address) = arg
+            .strip_prefix(CONNECT_FLAG)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(Ok((PendingConnectSource::CommandLine, address.to_string())));
+        }
+        if arg.starts_with(DEEP_LINK_SCHEME) {
+            return Some(Ok((PendingConnectSource::DeepLink, arg)));
+        }
+    }
+    None
+}
+
+fn parse_port(port: &str, input: &str) -> Result<u16, String> {
+    match port.parse::<u16>() {
+        Ok(port) if port > 0 => Ok(port),
+        _ => Err(format!(
+            "`{input}` has no valid port; use a number from 1 to 65535"
+        )),
+    }
+}
+
+fn parse_host_port(address: &str, input: &str) -> Result<String, String> {
+    let Some((host, port)) = address.rsplit_once(':') else {
+        return Err(format!(
+            "`{input}` needs a host and a port, e.g. tcpout:192.168.4.1:5760"
+        ));
+    };
+    if host.is_empty() {
+        return Err(format!("`{input}` has no host"));
+    }
+    parse_port(port, input)?;
+    Ok(address.to_string())
+}
+
+fn parse_serial(address: &str, input: &str) -> Result<ConnectTransport, String> {
+    let Some((port, baud)) = address
+        .rsplit_once(':')
+        .filter(|(port, _)| !port.is_empty())
+    else {
+        return Err(format!(
+            "`{input}` needs a port and a baud rate, e.g. serial:/dev/ttyUSB0:57600"
+        ));
+    };
+    let auto_baud = baud == "auto";
+    let baud = if auto_baud {
+        DEFAULT_SERIAL_BAUD
+    } else {
+        match baud.parse::<u32>() {
+            Ok(baud) if baud > 0 => baud,
+            _ => {
+                return Err(format!(
+                    "`{baud}` is not a baud rate; use a number or `auto`"
+                ));
+            }
+        }
+    };
+    Ok(ConnectTransport::Serial {
+        port: port.to_string(),
+        baud,
+        flow_control: SerialFlowControl::None,
+        parity: SerialParity::None,
+        auto_baud,
+    })
+}
+
+fn parse_bluetooth_address(address: &str, input: &str) -> Result<String, String> {
+    let octets: Vec<_> = address.split(':').collect();
+    let valid = octets.len() == 6
+        && octets
+            .iter()
+            .all(|octet| octet.len() == 2 && octet.bytes().all(|byte| byte.is_ascii_hexdigit()));
+    if valid {
+        Ok(address.to_ascii_uppercase())
+    } else {
+        Err(format!(
+            "`{input}` does not end in a Bluetooth address such as AA:BB:CC:DD:EE:FF"
+        ))
+    }
+}
+
+/// Parse a MAVKit-style connection string into what `connect_link` takes.
+/// Only the forms the app can connect with are accepted: listening UDP,
+/// outgoing TCP, serial, and Bluetooth LE or SPP.
+pub fn parse_connection_string(input: &str) -> Result<ConnectTransport, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(format!("the connection address is empty; {ADDRESS_FORMS}"));
+    }
+    let Some((kind, address)) = input.split_once(':') else {
+        return Err(format!("`{input}` has no link type; {ADDRESS_FORMS}"));
+    };
+    match kind.to_ascii_lowercase().as_str() {
+        "udpin" | "udp" => {
+            let bind_addr = parse_udp_bind_addr(address).map_err(|error| error.reason().message)?;
+            Ok(ConnectTransport::Udp {
+                bind_addr: bind_addr.to_string(),
+            })
+        }
+        "tcpout" | "tcp" => Ok(ConnectTransport::Tcp {
+            address: parse_host_port(address, input)?,
+        }),
+        "serial" => parse_serial(address, input),
+        "ble" => Ok(ConnectTransport::BluetoothBle {
+            address: parse_bluetooth_address(address, input)?,
+            profile: None,
+        }),
+        "spp" => Ok(ConnectTransport::BluetoothSpp {
+            address: parse_bluetooth_address(address, input)?,
+        }),
+        "udpout" | "udpbcast" | "tcpin" => {
+            Err(format!("`{kind}` links are not supported; {ADDRESS_FORMS}"))
+        }
+        _ => Err(format!("`{kind}` is not a link type; {ADDRESS_FORMS}")),
+    }
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    char::from(byte).to_digit(16).map(|digit| digit as u8)
+}
+
+/// Decode `%XX` escapes and `+` for space, as query strings are written.
+fn percent_decode(text: &str) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'%' => {
+                let value = bytes
+                    .get(index + 1..index + 3)
+                    .and_then(|hex| Some((hex_value(hex[0])? << 4) | hex_value(hex[1])?))
+                    .ok_or_else(|| format!("`{text}` has a broken %-escape"))?;
+                out.push(value);
+                index += 3;
+            }
+            b'+' => {
+                out.push(b' ');
+                index += 1;
+            }
+            byte => {
+                out.push(byte);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8(out).map_err(|_| format!("`{text}` is not UTF-8 once decoded"))
+}
+
+/// The connection string in an `ironwing://connect?address=<address>` link.
+pub fn deep_link_address(link: &str) -> Result<String, String> {
+    let rest = link
+        .strip_prefix(DEEP_LINK_SCHEME)
+        .ok_or_else(|| format!("`{link}` is not an {DEEP_LINK_SCHEME} link"))?;
+    let (target, query) = rest.split_once('?').unwrap_or((rest, ""));
+    if target.trim_end_matches('/') != DEEP_LINK_CONNECT_HOST {
+        return Err(format!(
+            "`{link}` is not a connect link; use {DEEP_LINK_SCHEME}{DEEP_LINK_CONNECT_HOST}?{DEEP_LINK_ADDRESS_PARAM}=udpin:0.0.0.0:14550"
+        ));
+    }
+    let query = query.split('#').next().unwrap_or_default();
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == DEEP_LINK_ADDRESS_PARAM)
+        .map(|(_, value)| percent_decode(value))
+        .unwrap_or_else(|| {
+            Err(format!(
+                "`{link}` has no {DEEP_LINK_ADDRESS_PARAM} parameter"
+            ))
+        })
+}
+
+/// What to hand the frontend for a connection given at launch or by a link.
+pub fn pending_connect(
+    source: PendingConnectSource,
+    input: &str,
+    auto_connect: bool,
+) -> PendingConnect {
+    let transport = match source {
+        PendingConnectSource::CommandLine => parse_connection_string(input),
+        PendingConnectSource::DeepLink => {
+            deep_link_address(input).and_then(|address| parse_connection_string(&address))
+        }
+    };
+    match transport {
+        Ok(transport) => PendingConnect::Ready {
+            source,
+            input: input.to_string(),
+            request: ConnectRequest {
+                transport,
+                auto_record_on_connect: false,
+                heartbeat_timeout_secs: None,
+                keep_waiting: false,
+            },
+            auto_connect,
+        },
+        Err(message) => PendingConnect::Invalid {
+            source,
+            input: input.to_string(),
+            message,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn connection_strings_map_to_transports() {
+        assert!(matches!(
+            parse_connection_string("udpin:0.0.0.0:14550"),
+            Ok(ConnectTransport::Udp { bind_addr }) if bind_addr == "0.0.0.0:14550"
+        ));
+        assert!(matches!(
+            parse_connection_string(" udpin::14551 "),
+            Ok(ConnectTransport::Udp { bind_addr }) if bind_addr == "0.0.0.0:14551"
+        ));
+        assert!(matches!(
+            parse_connection_string("UDPIN:[::]:14550"),
+            Ok(ConnectTransport::Udp { bind_addr }) if bind_addr == "[::]:14550"
+        ));
+        assert!(matches!(
+            parse_connection_string("tcpout:192.168.4.1:5760"),
+            Ok(ConnectTransport::Tcp { address }) if address == "192.168.4.1:5760"
+        ));
+        assert!(matches!(
+            parse_connection_string("tcp:sitl.local:5760"),
+            Ok(ConnectTransport::Tcp { address }) if address == "sitl.local:5760"
+        ));
+        assert!(matches!(
+            parse_connection_string("serial:/dev/ttyUSB0:57600"),
+            Ok(ConnectTransport::Serial { port, baud: 57600, auto_baud: false, .. })
+                if port == "/dev/ttyUSB0"
+        ));
+        assert!(matches!(
+            parse_connection_string("serial:COM3:auto"),
+            Ok(ConnectTransport::Serial { port, auto_baud: true, .. }) if port == "COM3"
+        ));
+        assert!(matches!(
+            parse_connection_string("ble:c8:2b:96:0a:1f:e3"),
+            Ok(ConnectTransport::BluetoothBle { address, profile: None })
+                if address == "C8:2B:96:0A:1F:E3"
+        ));
+        assert!(matches!(
+            parse_connection_string("spp:00:1A:7D:DA:71:13"),
+            Ok(ConnectTransport::BluetoothSpp { address }) if address == "00:1A:7D:DA:71:13"
+        ));
+    }
+
+    #[test]
+    fn malformed_connection_strings_are_rejected() {
+        for input in [
+            "",
+            "14550",
+            "udpin:",
+            "udpin:0.0.0.0:0",
+            "udpin:::14550",
+            "tcpout:5760",
+            "tcpout::5760",
+            "tcpout:host:port",
+            "serial:/dev/ttyUSB0",
+            "serial:/dev/ttyUSB0:fast",
+            "serial::57600",
+            "ble:C8:2B:96:0A:1F",
+            "spp:not-an-address",
+            "udpout:10.0.0.1:14550",
+            "tcpin:0.0.0.0:5760",
+            "ws://localhost:8080",
+        ] {
+            assert!(parse_connection_string(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn deep_links_carry_a_percent_encoded_address() {
+        assert_eq!(
+            deep_link_address("ironwing://connect?address=udpin%3A0.0.0.0%3A14550").as_deref(),
+            Ok("udpin:0.0.0.0:14550")
+        );
+        assert_eq!(
+            deep_link_address("ironwing://connect/?from=qgc&address=tcpout:10.0.0.2:5760#x")
+                .as_deref(),
+            Ok("tcpout:10.0.0.2:5760")
+        );
+        assert!(deep_link_address("ironwing://mission?address=udpin:14550").is_err());
+        assert!(deep_link_address("ironwing://connect?addr=udpin:14550").is_err());
+        assert!(deep_link_address("ironwing://connect?address=udpin%3").is_err());
+        assert!(deep_link_address("https://connect?address=udpin:14550").is_err());
+
+        let pending = pending_connect(
+            PendingConnectSource::DeepLink,
+            "ironwing://connect?address=serial%3A%2Fdev%2FttyACM0%3A115200",
+            true,
+        );
+        assert!(matches!(
+            pending,
+            PendingConnect::Ready {
+                request: ConnectRequest {
+                    transport: ConnectTransport::Serial { baud: 115200, .. },
+                    ..
+                },
+                auto_connect: true,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn launch_arguments_name_the_address() {
+        assert!(launch_argument(args(&["--verbose"])).is_none());
+        assert!(matches!(
+            launch_argument(args(&["--connect", "udpin:0.0.0.0:14550"])),
+            Some(Ok((PendingConnectSource::CommandLine, address))) if address == "udpin:0.0.0.0:14550"
+        ));
+        assert!(matches!(
+            launch_argument(args(&["--connect=tcpout:127.0.0.1:5760"])),
+            Some(Ok((PendingConnectSource::CommandLine, address))) if address == "tcpout:127.0.0.1:5760"
+        ));
+        assert!(matches!(
+            launch_argument(args(&["ironwing://connect?address=udpin:14550"])),
+            Some(Ok((PendingConnectSource::DeepLink, _)))
+        ));
+        assert!(matches!(
+            launch_argument(args(&["--connect"])),
+            Some(Err(_))
+        ));
+
+        let invalid = pending_connect(PendingConnectSource::CommandLine, "udpin:nope", false);
+        assert!(
+            matches!(invalid, PendingConnect::Invalid { message, .. } if message.contains("nope"))
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use mavkit::VehicleType;
+
+use crate::derived_telemetry::bearing_deg;
+use crate::flight_modes::is_fixed_wing;
+use crate::ipc::{PlaneLaunch, PlaneLaunchKind};
+
+pub const TKOFF_ALT_PARAM: &str = "TKOFF_ALT";
+pub const TKOFF_THR_MINACC_PARAM: &str = "TKOFF_THR_MINACC";
+pub const TKOFF_THR_MINSPD_PARAM: &str = "TKOFF_THR_MINSPD";
+pub const TKOFF_THR_DELAY_PARAM: &str = "TKOFF_THR_DELAY";
+pub const ARSPD_USE_PARAM: &str = "ARSPD_USE";
+
+pub const PLANE_LOITER_MIN_RADIUS_M: f32 = 10.0;
+pub const PLANE_LOITER_MAX_TURNS: u32 = 100;
+/// DO_CHANGE_SPEED speed type for airspeed, ignored when only the throttle
+/// changes.
+const SPEED_TYPE_AIRSPEED: f32 = 0.0;
+/// TKOFF_THR_DELAY below which a thrown plane's propeller may start while
+/// it is still near the operator's hand, in seconds.
+const HAND_LAUNCH_MIN_SAFE_DELAY_S: f32 = 0.2;
+
+/// Fixed-wing commands refuse every other frame with this error.
+pub fn ensure_plane(vehicle_type: VehicleType) -> Result<(), String> {
+    if is_fixed_wing(vehicle_type) {
+        Ok(())
+    } else {
+        Err(format!(
+            "not applicable to this vehicle type ({vehicle_type:?}); fixed-wing only"
+        ))
+    }
+}
+
+/// The launch the TKOFF_* parameters set up:
+///
+/// | TKOFF_THR_MINACC | TKOFF_THR_MINSPD | launch      |
+/// |------------------|------------------|-------------|
+/// | 0                | 0                | runway      |
+/// | > 0              | 0                | hand launch |
+/// | any              | > 0              | catapult    |
+///
+/// A minimum ground speed means the motor waits for something else to get
+/// the plane moving, which a throw rarely manages reliably.
+pub fn plane_launch(param: impl Fn(&str) -> Option<f32>) -> Result<PlaneLaunch, String> {
+    let required = |name: &str| {
+        param(name).ok_or_else(|| format!("{name} is not in the downloaded parameters"))
+    };
+    let min_acceleration_mss = required(TKOFF_THR_MINACC_PARAM)?;
+    let min_ground_speed_mps = required(TKOFF_THR_MINSPD_PARAM)?;
+    // Deciseconds on the wire.
+    let throttle_delay_s = param(TKOFF_THR_DELAY_PARAM).unwrap_or(0.0) / 10.0;
+
+    let kind = if min_ground_speed_mps > 0.0 {
+        PlaneLaunchKind::Catapult
+    } else if min_acceleration_mss > 0.0 {
+        PlaneLaunchKind::HandLaunch
+    } else {
+        PlaneLaunchKind::Runway
+    };
+    let instructions = match kind {
+        PlaneLaunchKind::Runway => {
+            "Line up on the runway; the motor starts as soon as the plane is armed in Takeoff."
+                .to_string()
+        }
+        PlaneLaunchKind::HandLaunch => format!(
+            "Arm, then throw firmly level; the motor starts once acceleration passes \
+             {min_acceleration_mss} m/s²."
+        ),
+        PlaneLaunchKind::Catapult => format!(
+            "Arm on the launcher, then release; the motor starts once the plane passes \
+             {min_ground_speed_mps} m/s over the ground."
+        ),
+    };
+    let mut warnings = Vec::new();
+    if kind == PlaneLaunchKind::HandLaunch && throttle_delay_s < HAND_LAUNCH_MIN_SAFE_DELAY_S {
+        warnings.push(format!(
+            "the motor starts {throttle_delay_s} s after the throw; keep hands clear of the \
+             propeller or raise {TKOFF_THR_DELAY_PARAM}"
+        ));
+    }
+    Ok(PlaneLaunch {
+        kind,
+        min_acceleration_mss,
+        min_ground_speed_mps,
+        throttle_delay_s,
+        instructions,
+        warnings,
+    })
+}
+
+pub fn validate_cruise_throttle(throttle_pct: f32) -> Result<(), String> {
+    if !(0.0..=100.0).contains(&throttle_pct) {
+        return Err(format!(
+            "cruise throttle must be between 0 and 100 %, got {throttle_pct}"
+        ));
+    }
+    Ok(())
+}
+
+/// MAV_CMD_DO_CHANGE_SPEED params that change the cruise throttle only.
+pub fn cruise_throttle_params(throttle_pct: f32) -> [f32; 7] {
+    [SPEED_TYPE_AIRSPEED, -1.0, throttle_pct, 0.0, 0.0, 0.0, 0.0]
+}
+
+/// With an airspeed sensor in use TECS picks the throttle itself, so the
+/// cruise throttle only matters if the sensor fails.
+pub fn cruise_throttle_warnings(arspd_use: Option<f32>) -> Vec<String> {
+    match arspd_use {
+        Some(value) if value > 0.0 => vec![format!(
+            "{ARSPD_USE_PARAM} is set, so the autopilot flies by airspeed and uses the cruise \
+             throttle only without the sensor"
+        )],
+        _ => Vec::new(),
+    }
+}
+
+pub fn validate_loiter(radius_m: f32, turns: Option<u32>) -> Result<(), String> {
+    if !radius_m.is_finite() || radius_m < PLANE_LOITER_MIN_RADIUS_M {
+        return Err(format!(
+            "loiter radius must be at least {PLANE_LOITER_MIN_RADIUS_M} m, got {radius_m} m"
+        ));
+    }
+    if turns.is_some_and(|turns| turns == 0 || turns > PLANE_LOITER_MAX_TURNS) {
+        return Err(format!(
+            "loiter turns must be between 1 and {PLANE_LOITER_MAX_TURNS}"
+        ));
+    }
+    Ok(())
+}
+
+/// Counts full circles flown around a loiter centre from position updates,
+/// in either direction.
+#[derive(Debug, Clone)]
+pub struct LoiterTurnCounter {
+    centre: (f64, f64),
+    last_bearing_deg: Option<f64>,
+    swept_deg: f64,
+}
+
+impl LoiterTurnCounter {
+    pub fn new(latitude_deg: f64, longitude_deg: f64) -> Self {
+        Self {
+            centre: (latitude_deg, longitude_deg),
+            last_bearing_deg: None,
+            swept_deg: 0.0,
+        }
+    }
+
+    /// Completed turns after a position update.
+    pub fn observe(&mut self, latitude_deg: f64, longitude_deg: f64) -> u32 {
+        let bearing = bearing_deg(self.centre.0, self.centre.1, latitude_deg, longitude_deg);
+        if let Some(last) = self.last_bearing_deg {
+            // The shorter way round between two fixes.
+            let step = (bearing - last + 540.0).rem_euclid(360.0) - 180.0;
+            self.swept_deg += step;
+        }
+        self.last_bearing_deg = Some(bearing);
+        (self.swept_deg.abs() / 360.0) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn launch(params: &[(&str, f32)]) -> Result<PlaneLaunch, String> {
+        let params: HashMap<&str, f32> = params.iter().copied().collect();
+        plane_launch(|name| params.get(name).copied())
+    }
+
+    #[test]
+    fn launch_kind_follows_the_takeoff_parameters() {
+        let cases = [
+            (0.0, 0.0, 0.0, PlaneLaunchKind::Runway, 0),
+            (15.0, 0.0, 2.0, PlaneLaunchKind::HandLaunch, 0),
+            (15.0, 0.0, 0.0, PlaneLaunchKind::HandLaunch, 1),
+            (15.0, 4.0, 0.0, PlaneLaunchKind::Catapult, 0),
+            (0.0, 4.0, 0.0, PlaneLaunchKind::Catapult, 0),
+        ];
+        for (min_acc, min_speed, delay_ds, kind, warnings) in cases {
+            let launch = launch(&[
+                (TKOFF_THR_MINACC_PARAM, min_acc),
+                (TKOFF_THR_MINSPD_PARAM, min_speed),
+                (TKOFF_THR_DELAY_PARAM, delay_ds),
+            ])
+            .unwrap();
+            assert_eq!(launch.kind, kind, "{min_acc} m/s², {min_speed} m/s");
+            assert_eq!(launch.warnings.len(), warnings, "{launch:?}");
+            assert_eq!(launch.throttle_delay_s, delay_ds / 10.0);
+        }
+
+        let missing = launch(&[(TKOFF_THR_MINACC_PARAM, 15.0)]).unwrap_err();
+        assert!(missing.contains(TKOFF_THR_MINSPD_PARAM), "{missing}");
+    }
+
+    #[test]
+    fn only_fixed_wing_frames_are_accepted() {
+        assert!(ensure_plane(VehicleType::FixedWing).is_ok());
+        assert!(ensure_plane(VehicleType::VtolFixedrotor).is_ok());
+        let error = ensure_plane(VehicleType::Quadrotor).unwrap_err();
+        assert!(error.starts_with("not applicable to this vehicle type"));
+    }
+
+    #[test]
+    fn cruise_throttle_is_checked_and_sent_without_a_speed() {
+        assert!(validate_cruise_throttle(45.0).is_ok());
+        assert!(validate_cruise_throttle(101.0).is_err());
+        assert_eq!(cruise_throttle_params(45.0)[1..3], [-1.0, 45.0]);
+        assert_eq!(cruise_throttle_warnings(Some(1.0)).len(), 1);
+        assert!(cruise_throttle_warnings(Some(0.0)).is_empty());
+    }
+
+    #[test]
+    fn turns_are_counted_in_either_direction() {
+        const M_PER_DEG: f64 = 111_195.0;
+        let (lat, lon) = (47.0, 8.0);
+        let circle = |steps: usize, clockwise: bool| {
+            let mut counter = LoiterTurnCounter::new(lat, lon);
+            let mut turns = 0;
+            for step in 0..=steps {
+                let mut angle = (step as f64 * 30.0).to_radians();
+                if !clockwise {
+                    angle = -angle;
+                }
+                let north = 80.0 * angle.cos() / M_PER_DEG;
+                let east = 80.0 * angle.sin() / (M_PER_DEG * lat.to_radians().cos());
+                turns = counter.observe(lat + north, lon + east);
+            }
+            turns
+        };
+        assert_eq!(circle(11, true), 0);
+        assert_eq!(circle(13, true), 1);
+        assert_eq!(circle(25, false), 2);
+        assert!(validate_loiter(80.0, Some(2)).is_ok());
+        assert!(validate_loiter(80.0, Some(0)).is_err());
+        assert!(validate_loiter(5.0, None).is_err());
+    }
+}
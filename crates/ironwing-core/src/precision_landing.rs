@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use web_time::Instant;
+
+use crate::dialect::{LANDING_TARGET_DATA, OPTICAL_FLOW_DATA, OPTICAL_FLOW_RAD_DATA};
+use crate::ipc::{OpticalFlowQuality, PrecisionLandingStatus, PrecisionLandingTarget};
+
+pub const OPTICAL_FLOW_MESSAGE_ID: u32 = 100;
+pub const OPTICAL_FLOW_RAD_MESSAGE_ID: u32 = 106;
+pub const LANDING_TARGET_MESSAGE_ID: u32 = 149;
+
+/// How recent a target must be to count as acquired when the caller does
+/// not say.
+pub const DEFAULT_TARGET_WINDOW: Duration = Duration::from_secs(2);
+
+fn quality_pct(quality: u8) -> f64 {
+    f64::from(quality) * 100.0 / 255.0
+}
+
+fn positive_distance(distance_m: f32) -> Option<f64> {
+    (distance_m.is_finite() && distance_m > 0.0).then_some(f64::from(distance_m))
+}
+
+pub fn landing_target(data: &LANDING_TARGET_DATA) -> PrecisionLandingTarget {
+    PrecisionLandingTarget {
+        target_num: data.target_num,
+        angle_x_rad: f64::from(data.angle_x),
+        angle_y_rad: f64::from(data.angle_y),
+        distance_m: positive_distance(data.distance),
+        valid: data.angle_x.is_finite() && data.angle_y.is_finite(),
+        position_valid: data.position_valid != 0,
+    }
+}
+
+/// OPTICAL_FLOW as sent by ArduPilot: the compensated flow is already a
+/// velocity.
+pub fn optical_flow_quality(data: &OPTICAL_FLOW_DATA) -> OpticalFlowQuality {
+    OpticalFlowQuality {
+        sensor_id: data.sensor_id,
+        quality_pct: quality_pct(data.quality),
+        flow_comp_x_mps: Some(f64::from(data.flow_comp_m_x)),
+        flow_comp_y_mps: Some(f64::from(data.flow_comp_m_y)),
+        ground_distance_m: positive_distance(data.ground_distance),
+    }
+}
+
+/// OPTICAL_FLOW_RAD: integrated flow minus the integrated gyro is the
+/// angular rate the ground moved at; times the distance it is a velocity.
+/// Without a distance or integration time there is no velocity to give.
+pub fn optical_flow_rad_quality(data: &OPTICAL_FLOW_RAD_DATA) -> OpticalFlowQuality {
+    let distance_m = positive_distance(data.distance);
+    let integration_s = f64::from(data.integration_time_us) / 1e6;
+    let velocity = |flow: f32, gyro: f32| {
+        let distance_m = distance_m.filter(|_| integration_s > 0.0)?;
+        Some(f64::from(flow - gyro) / integration_s * distance_m)
+    };
+    OpticalFlowQuality {
+        sensor_id: data.sensor_id,
+        quality_pct: quality_pct(data.quality),
+        flow_comp_x_mps: velocity(data.integrated_x, data.integrated_xgyro),
+        flow_comp_y_mps: velocity(data.integrated_y, data.integrated_ygyro),
+        ground_distance_m: distance_m,
+    }
+}
+
+/// The last valid landing target and when it arrived.
+#[derive(Debug, Clone, Default)]
+pub struct PrecisionLandingTracker {
+    last_target: Option<(PrecisionLandingTarget, Instant)>,
+}
+
+impl PrecisionLandingTracker {
+    pub fn observe(&mut self, target: PrecisionLandingTarget, now: Instant) {
+        if target.valid {
+            self.last_target = Some((target, now));
+        }
+    }
+
+    /// Acquired when a valid target arrived within `window` of `now`.
+    pub fn status(&self, window: Duration, now: Instant) -> PrecisionLandingStatus {
+        let age = self
+            .last_target
+            .as_ref()
+            .map(|(_, received_at)| now.saturating_duration_since(*received_at));
+        PrecisionLandingStatus {
+            target_acquired: age.is_some_and(|age| age <= window),
+            last_seen_ms: age.map(|age| age.as_millis() as u64),
+            last_target: self.last_target.as_ref().map(|(target, _)| target.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(angle_x: f32) -> PrecisionLandingTarget {
+        landing_target(&LANDING_TARGET_DATA {
+            angle_x,
+            angle_y: -0.05,
+            distance: 0.0,
+            target_num: 1,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn landing_target_reports_angles_and_validity() {
+        let seen = target(0.1);
+        assert!(seen.valid);
+        assert_eq!(seen.distance_m, None);
+        assert!(!seen.position_valid);
+        assert!(!target(f32::NAN).valid);
+    }
+
+    #[test]
+    fn target_is_acquired_only_within_the_window() {
+        let mut tracker = PrecisionLandingTracker::default();
+        let now = Instant::now();
+        assert!(!tracker.status(DEFAULT_TARGET_WINDOW, now).target_acquired);
+
+        tracker.observe(target(0.1), now);
+        tracker.observe(target(f32::NAN), now + Duration::from_millis(500));
+        let status = tracker.status(DEFAULT_TARGET_WINDOW, now + Duration::from_secs(1));
+        assert!(status.target_acquired);
+        assert_eq!(status.last_seen_ms, Some(1_000));
+
+        let later = tracker.status(DEFAULT_TARGET_WINDOW, now + Duration::from_secs(3));
+        assert!(!later.target_acquired);
+        assert!(later.last_target.is_some());
+    }
+
+    #[test]
+    fn flow_rad_is_gyro_compensated_into_velocity() {
+        let flow = optical_flow_rad_quality(&OPTICAL_FLOW_RAD_DATA {
+            integration_time_us: 100_000,
+            integrated_x: 0.03,
+            integrated_xgyro: 0.01,
+            integrated_y: 0.0,
+            integrated_ygyro: 0.0,
+            distance: 2.0,
+            quality: 255,
+            ..Default::default()
+        });
+        assert_eq!(flow.quality_pct, 100.0);
+        assert!((flow.flow_comp_x_mps.expect("x") - 0.4).abs() < 1e-6);
+        assert_eq!(flow.flow_comp_y_mps, Some(0.0));
+
+        let no_range = optical_flow_rad_quality(&OPTICAL_FLOW_RAD_DATA {
+            integration_time_us: 100_000,
+            ..Default::default()
+        });
+        assert_eq!(no_range.flow_comp_x_mps, None);
+    }
+}
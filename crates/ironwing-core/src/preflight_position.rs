@@ -0,0 +1,175 @@
+use crate::ipc::{PositionIssue, PositionIssueKind};
+use crate::log_engine::haversine_m;
+
+/// A disarmed vehicle this far from the operator's device is probably
+/// reporting a wrong position.
+pub const PHONE_DISTANCE_THRESHOLD_M: f64 = 1000.0;
+pub const HOME_OFFSET_THRESHOLD_M: f64 = 100.0;
+pub const TERRAIN_MISMATCH_THRESHOLD_M: f64 = 50.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionFix {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    pub altitude_msl_m: Option<f64>,
+}
+
+/// What the check compares. Missing inputs skip their check.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PositionCheckInputs {
+    pub vehicle: Option<PositionFix>,
+    pub home: Option<PositionFix>,
+    pub armed: bool,
+    /// The operator's device, when it can report its location.
+    pub phone: Option<PositionFix>,
+    pub terrain_elevation_m: Option<f64>,
+}
+
+fn issue(
+    kind: PositionIssueKind,
+    message: String,
+    measured_m: f64,
+    threshold_m: f64,
+) -> PositionIssue {
+    PositionIssue {
+        kind,
+        message,
+        measured_m: Some(measured_m),
+        threshold_m: Some(threshold_m),
+    }
+}
+
+fn distance_m(a: &PositionFix, b: &PositionFix) -> f64 {
+    haversine_m(
+        a.latitude_deg,
+        a.longitude_deg,
+        b.latitude_deg,
+        b.longitude_deg,
+    )
+}
+
+pub fn position_issues(inputs: &PositionCheckInputs) -> Vec<PositionIssue> {
+    let Some(vehicle) = inputs.vehicle else {
+        return vec![PositionIssue {
+            kind: PositionIssueKind::PositionUnknown,
+            message: "vehicle has not reported a position".to_string(),
+            measured_m: None,
+            threshold_m: None,
+        }];
+    };
+    let mut issues = Vec::new();
+
+    if let Some(phone) = inputs.phone {
+        let distance = distance_m(&vehicle, &phone);
+        if distance > PHONE_DISTANCE_THRESHOLD_M {
+            issues.push(issue(
+                PositionIssueKind::PhoneDistance,
+                format!("vehicle reports a position {distance:.0} m from this device"),
+                distance,
+                PHONE_DISTANCE_THRESHOLD_M,
+            ));
+        }
+    }
+
+    if !inputs.armed
+        && let Some(home) = inputs.home
+    {
+        let distance = distance_m(&vehicle, &home);
+        if distance > HOME_OFFSET_THRESHOLD_M {
+            issues.push(issue(
+                PositionIssueKind::HomeOffset,
+                format!("home is {distance:.0} m from the vehicle's current position"),
+                distance,
+                HOME_OFFSET_THRESHOLD_M,
+            ));
+        }
+    }
+
+    if let (Some(altitude), Some(terrain)) = (vehicle.altitude_msl_m, inputs.terrain_elevation_m) {
+        let difference = (altitude - terrain).abs();
+        if difference > TERRAIN_MISMATCH_THRESHOLD_M {
+            issues.push(issue(
+                PositionIssueKind::TerrainMismatch,
+                format!(
+                    "reported altitude {altitude:.0} m differs from the terrain elevation \
+                     {terrain:.0} m by {difference:.0} m"
+                ),
+                difference,
+                TERRAIN_MISMATCH_THRESHOLD_M,
+            ));
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix(latitude_deg: f64, longitude_deg: f64, altitude_msl_m: Option<f64>) -> PositionFix {
+        PositionFix {
+            latitude_deg,
+            longitude_deg,
+            altitude_msl_m,
+        }
+    }
+
+    fn kinds(issues: &[PositionIssue]) -> Vec<PositionIssueKind> {
+        issues.iter().map(|issue| issue.kind).collect()
+    }
+
+    #[test]
+    fn consistent_positions_pass() {
+        let here = fix(47.3977, 8.5456, Some(488.0));
+        let inputs = PositionCheckInputs {
+            vehicle: Some(here),
+            home: Some(here),
+            phone: Some(fix(47.3978, 8.5457, None)),
+            terrain_elevation_m: Some(480.0),
+            ..PositionCheckInputs::default()
+        };
+        assert!(position_issues(&inputs).is_empty());
+    }
+
+    #[test]
+    fn stale_origin_trips_every_check_with_numbers() {
+        // A SITL default origin reported while the operator stands elsewhere.
+        let inputs = PositionCheckInputs {
+            vehicle: Some(fix(-35.3632, 149.1652, Some(584.0))),
+            home: Some(fix(-35.3700, 149.1652, None)),
+            phone: Some(fix(47.3977, 8.5456, None)),
+            terrain_elevation_m: Some(488.0),
+            ..PositionCheckInputs::default()
+        };
+
+        let issues = position_issues(&inputs);
+
+        assert_eq!(
+            kinds(&issues),
+            [
+                PositionIssueKind::PhoneDistance,
+                PositionIssueKind::HomeOffset,
+                PositionIssueKind::TerrainMismatch,
+            ]
+        );
+        assert_eq!(issues[2].measured_m, Some(96.0));
+        assert_eq!(issues[1].threshold_m, Some(HOME_OFFSET_THRESHOLD_M));
+    }
+
+    #[test]
+    fn home_offset_is_ignored_once_armed_and_missing_position_is_reported() {
+        let inputs = PositionCheckInputs {
+            vehicle: Some(fix(47.3977, 8.5456, None)),
+            home: Some(fix(47.4077, 8.5456, None)),
+            armed: true,
+            ..PositionCheckInputs::default()
+        };
+        assert!(position_issues(&inputs).is_empty());
+
+        assert_eq!(
+            kinds(&position_issues(&PositionCheckInputs::default())),
+            [PositionIssueKind::PositionUnknown]
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::BTreeMap;
+
+use crate::dialect::DISTANCE_SENSOR_DATA;
+use crate::ipc::{
+    LandingAssistBand, LandingAssistProfile, LandingAssistWarning, RangefinderOrientation,
+    RangefinderReading,
+};
+
+pub const DISTANCE_SENSOR_MESSAGE_ID: u32 = 132;
+
+/// Landing assist only looks at the last part of the descent.
+pub const LANDING_ASSIST_MAX_HEIGHT_M: f64 = 10.0;
+
+/// MAV_SENSOR_ROTATION_PITCH_90 and _PITCH_270.
+const ORIENTATION_UP: u8 = 24;
+const ORIENTATION_DOWN: u8 = 25;
+
+impl Default for LandingAssistProfile {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            bands: vec![
+                LandingAssistBand {
+                    height_m: 10.0,
+                    max_descent_rate_mps: 3.0,
+                },
+                LandingAssistBand {
+                    height_m: 5.0,
+                    max_descent_rate_mps: 1.5,
+                },
+                LandingAssistBand {
+                    height_m: 2.0,
+                    max_descent_rate_mps: 0.7,
+                },
+            ],
+        }
+    }
+}
+
+pub fn validate_landing_assist(profile: &LandingAssistProfile) -> Result<(), String> {
+    for band in &profile.bands {
+        if !band.height_m.is_finite()
+            || band.height_m <= 0.0
+            || band.height_m > LANDING_ASSIST_MAX_HEIGHT_M
+        {
+            return Err(format!(
+                "landing assist heights must be above 0 and at most {LANDING_ASSIST_MAX_HEIGHT_M} m"
+            ));
+        }
+        if !band.max_descent_rate_mps.is_finite() || band.max_descent_rate_mps <= 0.0 {
+            return Err("landing assist descent rates must be greater than zero".to_string());
+        }
+    }
+    Ok(())
+}
+
+pub fn orientation_from_mav(orientation_id: u8) -> RangefinderOrientation {
+    match orientation_id {
+        0 => RangefinderOrientation::Forward,
+        1 => RangefinderOrientation::ForwardRight,
+        2 => RangefinderOrientation::Right,
+        3 => RangefinderOrientation::BackRight,
+        4 => RangefinderOrientation::Back,
+        5 => RangefinderOrientation::BackLeft,
+        6 => RangefinderOrientation::Left,
+        7 => RangefinderOrientation::ForwardLeft,
+        ORIENTATION_UP => RangefinderOrientation::Up,
+        ORIENTATION_DOWN => RangefinderOrientation::Down,
+        _ => RangefinderOrientation::Other,
+    }
+}
+
+pub fn rangefinder_reading(data: &DISTANCE_SENSOR_DATA) -> RangefinderReading {
+    let orientation_id = data.orientation as u8;
+    RangefinderReading {
+        sensor_id: data.id,
+        orientation: orientation_from_mav(orientation_id),
+        orientation_id,
+        distance_m: f64::from(data.current_distance) / 100.0,
+        min_distance_m: f64::from(data.min_distance) / 100.0,
+        max_distance_m: f64::from(data.max_distance) / 100.0,
+        signal_quality_pct: (data.signal_quality > 0).then_some(data.signal_quality),
+        valid: (data.min_distance..=data.max_distance).contains(&data.current_distance),
+    }
+}
+
+/// The band that governs `height_m`: the lowest one at or above it.
+fn band_for(height_m: f64, profile: &LandingAssistProfile) -> Option<LandingAssistBand> {
+    profile
+        .bands
+        .iter()
+        .filter(|band| height_m <= band.height_m)
+        .min_by(|a, b| a.height_m.total_cmp(&b.height_m))
+        .copied()
+}
+
+/// A warning when descending faster than `profile` allows at `height_m`
+/// above ground. `climb_rate_mps` is negative while descending.
+pub fn landing_assist_check(
+    height_m: f64,
+    climb_rate_mps: f64,
+    profile: &LandingAssistProfile,
+) -> Option<LandingAssistWarning> {
+    if !profile.enabled || height_m > LANDING_ASSIST_MAX_HEIGHT_M {
+        return None;
+    }
+    let band = band_for(height_m, profile)?;
+    let descent_rate_mps = -climb_rate_mps;
+    (descent_rate_mps > band.max_descent_rate_mps).then_some(LandingAssistWarning {
+        height_m,
+        descent_rate_mps,
+        max_descent_rate_mps: band.max_descent_rate_mps,
+    })
+}
+
+/// Latest reading per orientation, and which landing-assist band last
+/// warned so a warning is raised once per band rather than per reading.
+#[derive(Debug, Clone, Default)]
+pub struct RangefinderTracker {
+    readings: BTreeMap<u8, RangefinderReading>,
+    warned_band_height_m: Option<f64>,
+}
+
+impl RangefinderTracker {
+    pub fn observe(&mut self, reading: RangefinderReading) {
+        self.readings.insert(reading.orientation_id, reading);
+    }
+
+    pub fn readings(&self) -> Vec<RangefinderReading> {
+        self.readings.values().cloned().collect()
+    }
+
+    /// Height above ground from a valid downward reading, if there is one.
+    pub fn downward_height_m(&self) -> Option<f64> {
+        self.readings
+            .get(&ORIENTATION_DOWN)
+            .filter(|reading| reading.valid)
+            .map(|reading| reading.distance_m)
+    }
+
+    /// Run the landing-assist check on the downward reading. Returns a
+    /// warning when the descent first exceeds a band's limit; it is raised
+    /// again only after entering a lower band or recovering.
+    pub fn check_landing(
+        &mut self,
+        climb_rate_mps: f64,
+        profile: &LandingAssistProfile,
+    ) -> Option<LandingAssistWarning> {
+        let Some(warning) = self
+            .downward_height_m()
+            .and_then(|height_m| landing_assist_check(height_m, climb_rate_mps, profile))
+        else {
+            self.warned_band_height_m = None;
+            return None;
+        };
+        let band_height_m = band_for(warning.height_m, profile).map(|band| band.height_m);
+        if self.warned_band_height_m == band_height_m {
+            return None;
+        }
+        self.warned_band_height_m = band_height_m;
+        Some(warning)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::MavSensorOrientation;
+
+    fn sensor(orientation: MavSensorOrientation, distance_cm: u16) -> RangefinderReading {
+        rangefinder_reading(&DISTANCE_SENSOR_DATA {
+            min_distance: 20,
+            max_distance: 4000,
+            current_distance: distance_cm,
+            id: 1,
+            orientation,
+            signal_quality: 80,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn readings_convert_and_flag_out_of_range_distances() {
+        let reading = sensor(MavSensorOrientation::MAV_SENSOR_ROTATION_PITCH_270, 850);
+        assert_eq!(reading.orientation, RangefinderOrientation::Down);
+        assert_eq!(reading.distance_m, 8.5);
+        assert_eq!(reading.max_distance_m, 40.0);
+        assert_eq!(reading.signal_quality_pct, Some(80));
+        assert!(reading.valid);
+
+        let too_far = sensor(MavSensorOrientation::MAV_SENSOR_ROTATION_NONE, 4500);
+        assert_eq!(too_far.orientation, RangefinderOrientation::Forward);
+        assert!(!too_far.valid);
+    }
+
+    #[test]
+    fn sensors_are_tracked_per_orientation() {
+        let mut tracker = RangefinderTracker::default();
+        tracker.observe(sensor(
+            MavSensorOrientation::MAV_SENSOR_ROTATION_PITCH_270,
+            300,
+        ));
+        tracker.observe(sensor(MavSensorOrientation::MAV_SENSOR_ROTATION_NONE, 1200));
+        tracker.observe(sensor(
+            MavSensorOrientation::MAV_SENSOR_ROTATION_PITCH_270,
+            250,
+        ));
+
+        assert_eq!(tracker.readings().len(), 2);
+        assert_eq!(tracker.downward_height_m(), Some(2.5));
+    }
+
+    #[test]
+    fn landing_assist_uses_the_band_for_the_height() {
+        let profile = LandingAssistProfile::default();
+        assert_eq!(landing_assist_check(12.0, -5.0, &profile), None);
+        assert_eq!(landing_assist_check(8.0, -2.5, &profile), None);
+        let warning = landing_assist_check(4.0, -2.0, &profile).expect("warning");
+        assert_eq!(warning.max_descent_rate_mps, 1.5);
+        assert_eq!(landing_assist_check(1.5, 0.5, &profile), None);
+        let disabled = LandingAssistProfile {
+            enabled: false,
+            ..profile
+        };
+        assert_eq!(landing_assist_check(1.0, -3.0, &disabled), None);
+    }
+
+    #[test]
+    fn landing_warning_is_raised_once_per_band() {
+        let profile = LandingAssistProfile::default();
+        let mut tracker = RangefinderTracker::default();
+        tracker.observe(sensor(
+            MavSensorOrientation::MAV_SENSOR_ROTATION_PITCH_270,
+            450,
+        ));
+        assert!(tracker.check_landing(-2.0, &profile).is_some());
+        tracker.observe(sensor(
+            MavSensorOrientation::MAV_SENSOR_ROTATION_PITCH_270,
+            400,
+        ));
+        assert!(tracker.check_landing(-2.0, &profile).is_none());
+        tracker.observe(sensor(
+            MavSensorOrientation::MAV_SENSOR_ROTATION_PITCH_270,
+            180,
+        ));
+        assert!(tracker.check_landing(-2.0, &profile).is_some());
+        tracker.observe(sensor(
+            MavSensorOrientation::MAV_SENSOR_ROTATION_PITCH_270,
+            150,
+        ));
+        assert!(tracker.check_landing(-0.3, &profile).is_none());
+        assert!(tracker.check_landing(-1.0, &profile).is_some());
+    }
+}
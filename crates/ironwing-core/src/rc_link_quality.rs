@@ -0,0 +1,292 @@
+use std::time::Duration;
+
+use web_time::Instant;
+
+use crate::ipc::telemetry::{RcLinkQuality, RcLinkSource};
+use crate::link_sources::RADIO_STATUS_MESSAGE_ID;
+
+pub const RC_CHANNELS_MESSAGE_ID: u32 = 65;
+/// RADIO_RC_CHANNELS and RADIO_LINK_STATS from the development dialect, sent
+/// by mLRS and ELRS receivers. They are decoded from the raw payload since the
+/// vehicle dialect may not carry them.
+pub const RADIO_RC_CHANNELS_MESSAGE_ID: u32 = 420;
+pub const RADIO_LINK_STATS_MESSAGE_ID: u32 = 421;
+
+/// A source that has not reported for this long no longer counts.
+pub const RC_LINK_MAX_AGE: Duration = Duration::from_secs(3);
+
+/// Default threshold of the built-in armed RC quality alert, in percent.
+pub const RC_LINK_LOW_PCT: f64 = 30.0;
+
+const UNKNOWN: u8 = u8::MAX;
+/// RADIO_RC_CHANNELS_FLAGS_FAILSAFE.
+const RADIO_RC_CHANNELS_FAILSAFE: u16 = 1;
+/// RADIO_LINK_STATS_FLAGS_RSSI_DBM: rssi bytes are negated dBm.
+const RADIO_LINK_STATS_RSSI_DBM: u8 = 1;
+/// dBm mapped to 0 % and 100 %; receivers lose lock around the former.
+const RSSI_DBM_FLOOR: f64 = -120.0;
+const RSSI_DBM_CEILING: f64 = -50.0;
+
+/// RC_CHANNELS and RADIO_STATUS rssi: 0..=254 in device units, scaled
+/// linearly, 255 unknown.
+pub fn scaled_rssi_pct(raw: u8) -> Option<f64> {
+    (raw != UNKNOWN).then(|| f64::from(raw) / 254.0 * 100.0)
+}
+
+/// RADIO_LINK_STATS rssi in dBm mode: 1..=254 is -1..=-254 dBm, 0 no
+/// reception, 255 unknown. Mapped linearly from -120 dBm (0 %) to -50 dBm
+/// (100 %).
+pub fn dbm_rssi_pct(raw: u8) -> Option<f64> {
+    match raw {
+        UNKNOWN => None,
+        0 => Some(0.0),
+        raw => {
+            let dbm = -f64::from(raw);
+            Some(
+                ((dbm - RSSI_DBM_FLOOR) / (RSSI_DBM_CEILING - RSSI_DBM_FLOOR) * 100.0)
+                    .clamp(0.0, 100.0),
+            )
+        }
+    }
+}
+
+/// Link quality given directly as 0..=100 %, 255 unknown.
+fn lq_pct(raw: u8) -> Option<f64> {
+    (raw != UNKNOWN).then(|| f64::from(raw.min(100)))
+}
+
+/// MAVLink 2 trims trailing zero bytes, so a missing offset reads as zero.
+fn byte(payload: &[u8], offset: usize) -> u8 {
+    payload.get(offset).copied().unwrap_or(0)
+}
+
+/// Decode the RC link quality carried by one raw message, if any.
+///
+/// - RC_CHANNELS: `rssi` only.
+/// - RADIO_STATUS: only when `telemetry_radio_is_rc`, i.e. the telemetry
+///   radio also carries the RC uplink. The air side receives that uplink, so
+///   its `remrssi` is used.
+/// - RADIO_RC_CHANNELS: no signal figures; the failsafe flag reads as 0 %
+///   link quality.
+/// - RADIO_LINK_STATS: `rx_LQ_rc` and the first antenna's rssi, in dBm or
+///   device units per its flags.
+pub fn decode_rc_link(
+    message_id: u32,
+    payload: &[u8],
+    telemetry_radio_is_rc: bool,
+) -> Option<RcLinkQuality> {
+    match message_id {
+        // time_boot_ms (4), chan1..18_raw (36), chancount, rssi
+        RC_CHANNELS_MESSAGE_ID => Some(RcLinkQuality {
+            rssi_pct: Some(scaled_rssi_pct(byte(payload, 41))?),
+            lq_pct: None,
+            source: RcLinkSource::RcChannels,
+        }),
+        // rxerrors, fixed (u16 each), rssi, remrssi, ...
+        RADIO_STATUS_MESSAGE_ID if telemetry_radio_is_rc => Some(RcLinkQuality {
+            rssi_pct: Some(scaled_rssi_pct(byte(payload, 5))?),
+            lq_pct: None,
+            source: RcLinkSource::RadioStatus,
+        }),
+        // time_last_update_ms (4), flags (u16), ...
+        RADIO_RC_CHANNELS_MESSAGE_ID => {
+            let flags = u16::from_le_bytes([byte(payload, 4), byte(payload, 5)]);
+            (flags & RADIO_RC_CHANNELS_FAILSAFE != 0).then_some(RcLinkQuality {
+                rssi_pct: None,
+                lq_pct: Some(0.0),
+                source: RcLinkSource::RadioRcChannels,
+            })
+        }
+        // target_system, target_component, flags, rx_LQ_rc, rx_LQ_ser, rx_rssi1
+        RADIO_LINK_STATS_MESSAGE_ID => {
+            let rssi = byte(payload, 5);
+            let rssi_pct = if byte(payload, 2) & RADIO_LINK_STATS_RSSI_DBM != 0 {
+                dbm_rssi_pct(rssi)
+            } else {
+                scaled_rssi_pct(rssi)
+            };
+            let lq_pct = lq_pct(byte(payload, 3));
+            (rssi_pct.is_some() || lq_pct.is_some()).then_some(RcLinkQuality {
+                rssi_pct,
+                lq_pct,
+                source: RcLinkSource::RadioLinkStats,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Share of the RC link that works: link quality where the source reports
+/// it, otherwise rssi.
+pub fn rc_link_pct(quality: &RcLinkQuality) -> Option<f64> {
+    quality.lq_pct.or(quality.rssi_pct)
+}
+
+/// Latest report per source. The most specific fresh source wins: link
+/// stats, then RADIO_RC_CHANNELS, RADIO_STATUS and RC_CHANNELS.
+#[derive(Debug, Clone, Default)]
+pub struct RcLinkTracker {
+    latest: Vec<(RcLinkQuality, Instant)>,
+}
+
+fn priority(source: RcLinkSource) -> u8 {
+    match source {
+        RcLinkSource::RadioLinkStats => 0,
+        RcLinkSource::RadioRcChannels => 1,
+        RcLinkSource::RadioStatus => 2,
+        RcLinkSource::RcChannels => 3,
+    }
+}
+
+impl RcLinkTracker {
+    pub fn record(&mut self, quality: RcLinkQuality, now: Instant) {
+        self.latest
+            .retain(|(latest, _)| latest.source != quality.source);
+        self.latest.push((quality, now));
+    }
+
+    pub fn current(&self, now: Instant) -> Option<RcLinkQuality> {
+        self.latest
+            .iter()
+            .filter(|(_, received_at)| {
+                now.saturating_duration_since(*received_at) <= RC_LINK_MAX_AGE
+            })
+            .min_by_key(|(quality, _)| priority(quality.source))
+            .map(|(quality, _)| *quality)
+    }
+}
+
+/// The `rc_link` alert source: `armed_quality_pct` is only present while
+/// armed, so the low-quality rule stays quiet on the bench.
+pub fn rc_link_alert_source(quality: Option<&RcLinkQuality>, armed: bool) -> serde_json::Value {
+    match quality.and_then(rc_link_pct).filter(|_| armed) {
+        Some(pct) => serde_json::json!({ "armed_quality_pct": pct }),
+        None => serde_json::json!({}),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rc_channels(rssi: u8) -> Vec<u8> {
+        let mut payload = vec![0; 42];
+        payload[40] = 8;
+        payload[41] = rssi;
+        payload
+    }
+
+    #[test]
+    fn rc_channels_rssi_scales_to_percent_and_255_is_unknown() {
+        let quality = decode_rc_link(RC_CHANNELS_MESSAGE_ID, &rc_channels(254), false).unwrap();
+        assert_eq!(quality.rssi_pct, Some(100.0));
+        assert_eq!(quality.lq_pct, None);
+        assert_eq!(quality.source, RcLinkSource::RcChannels);
+        assert_eq!(
+            decode_rc_link(RC_CHANNELS_MESSAGE_ID, &rc_channels(127), false)
+                .unwrap()
+                .rssi_pct,
+            Some(50.0)
+        );
+        assert_eq!(
+            decode_rc_link(RC_CHANNELS_MESSAGE_ID, &rc_channels(255), false),
+            None
+        );
+        // A zero rssi is trimmed off the MAVLink 2 payload.
+        assert_eq!(
+            decode_rc_link(RC_CHANNELS_MESSAGE_ID, &[0; 41], false)
+                .unwrap()
+                .rssi_pct,
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn radio_status_counts_only_when_it_is_the_rc_radio() {
+        let payload = [0, 0, 0, 0, 200, 127, 100, 0, 0];
+        assert_eq!(
+            decode_rc_link(RADIO_STATUS_MESSAGE_ID, &payload, false),
+            None
+        );
+        let quality = decode_rc_link(RADIO_STATUS_MESSAGE_ID, &payload, true).unwrap();
+        assert_eq!(quality.rssi_pct, Some(50.0));
+        assert_eq!(quality.source, RcLinkSource::RadioStatus);
+    }
+
+    #[test]
+    fn radio_rc_channels_reports_only_failsafe() {
+        let mut payload = vec![0; 73];
+        assert_eq!(
+            decode_rc_link(RADIO_RC_CHANNELS_MESSAGE_ID, &payload, false),
+            None
+        );
+        payload[4] = 1;
+        let quality = decode_rc_link(RADIO_RC_CHANNELS_MESSAGE_ID, &payload, false).unwrap();
+        assert_eq!(quality.lq_pct, Some(0.0));
+        assert_eq!(rc_link_pct(&quality), Some(0.0));
+    }
+
+    #[test]
+    fn radio_link_stats_uses_lq_and_dbm_rssi() {
+        // flags = RSSI_DBM, rx_LQ_rc = 87, rx_rssi1 = -85 dBm
+        let quality =
+            decode_rc_link(RADIO_LINK_STATS_MESSAGE_ID, &[1, 1, 1, 87, 255, 85], false).unwrap();
+        assert_eq!(quality.lq_pct, Some(87.0));
+        assert_eq!(quality.rssi_pct, Some(50.0));
+        assert_eq!(rc_link_pct(&quality), Some(87.0));
+
+        assert_eq!(dbm_rssi_pct(0), Some(0.0));
+        assert_eq!(dbm_rssi_pct(30), Some(100.0));
+        assert_eq!(dbm_rssi_pct(130), Some(0.0));
+        assert_eq!(dbm_rssi_pct(255), None);
+
+        let scaled = decode_rc_link(
+            RADIO_LINK_STATS_MESSAGE_ID,
+            &[1, 1, 0, 255, 255, 254],
+            false,
+        )
+        .unwrap();
+        assert_eq!(scaled.lq_pct, None);
+        assert_eq!(scaled.rssi_pct, Some(100.0));
+        assert_eq!(
+            decode_rc_link(
+                RADIO_LINK_STATS_MESSAGE_ID,
+                &[1, 1, 0, 255, 255, 255],
+                false
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn tracker_prefers_the_most_specific_fresh_source() {
+        let start = Instant::now();
+        let mut tracker = RcLinkTracker::default();
+        let rc_channels = decode_rc_link(RC_CHANNELS_MESSAGE_ID, &rc_channels(200), false).unwrap();
+        let link_stats =
+            decode_rc_link(RADIO_LINK_STATS_MESSAGE_ID, &[1, 1, 0, 90, 255, 255], false).unwrap();
+
+        tracker.record(rc_channels, start);
+        tracker.record(link_stats, start);
+        assert_eq!(tracker.current(start), Some(link_stats));
+
+        let later = start + RC_LINK_MAX_AGE + Duration::from_millis(1);
+        tracker.record(rc_channels, later);
+        assert_eq!(tracker.current(later), Some(rc_channels));
+        assert_eq!(
+            tracker.current(later + RC_LINK_MAX_AGE + Duration::from_millis(1)),
+            None
+        );
+    }
+
+    #[test]
+    fn alert_source_is_empty_while_disarmed() {
+        let quality = decode_rc_link(RC_CHANNELS_MESSAGE_ID, &rc_channels(50), false).unwrap();
+        assert_eq!(
+            rc_link_alert_source(Some(&quality), false),
+            serde_json::json!({})
+        );
+        let armed = rc_link_alert_source(Some(&quality), true);
+        assert!(armed["armed_quality_pct"].as_f64().unwrap() < RC_LINK_LOW_PCT);
+    }
+}
@@ -0,0 +1,244 @@
+use std::time::Duration;
+
+use mavkit::{SystemStatus, VehicleType};
+use web_time::Instant;
+
+use crate::ipc::{RcOverrideChannelValueWire, RcOverrideChannelWire, VehicleState};
+
+/// RC_CHANNELS_OVERRIDE is resent at 10 Hz; ArduPilot drops an override
+/// it has not heard for RC_OVERRIDE_TIME (1 s by default).
+pub const RC_OVERRIDE_PERIOD: Duration = Duration::from_millis(100);
+/// The stream stops and releases every channel when the values have not
+/// been refreshed for this long.
+pub const RC_OVERRIDE_DEADMAN: Duration = Duration::from_secs(1);
+/// RC_CHANNELS_OVERRIDE carries 18 channels.
+pub const MAX_RC_OVERRIDE_CHANNELS: usize = 18;
+/// Stick centre, sent for every overridden channel but throttle before
+/// releasing.
+pub const RC_OVERRIDE_NEUTRAL_US: u16 = 1500;
+const RCMAP_THROTTLE: &str = "RCMAP_THROTTLE";
+/// ArduPilot's defaults for RCMAP_THROTTLE, RCx_MIN and RCx_TRIM.
+const DEFAULT_THROTTLE_CHANNEL: u8 = 3;
+const DEFAULT_RC_MIN_US: u16 = 1100;
+const DEFAULT_RC_TRIM_US: u16 = 1500;
+/// Neutral frames sent on stop before the release, half a second's worth.
+pub const RC_OVERRIDE_NEUTRAL_FRAMES: u32 = 5;
+
+/// ArduPilot reports ACTIVE from the moment the motors arm and CRITICAL or
+/// EMERGENCY while in a failsafe, so this errs towards calling an armed
+/// vehicle on the ground airborne.
+pub fn is_airborne(state: &VehicleState) -> bool {
+    state.armed
+        && matches!(
+            state.system_status,
+            SystemStatus::Active | SystemStatus::Critical | SystemStatus::Emergency
+        )
+}
+
+/// `values[i]` is channel `i + 1`; `None` releases it. 0 and UINT16_MAX are
+/// the release and ignore markers on the wire, so they are not PWM values.
+pub fn validate_override_values(values: &[Option<u16>]) -> Result<(), String> {
+    if values.len() > MAX_RC_OVERRIDE_CHANNELS {
+        return Err(format!(
+            "RC override takes at most {MAX_RC_OVERRIDE_CHANNELS} channels"
+        ));
+    }
+    if values
+        .iter()
+        .flatten()
+        .any(|pwm_us| *pwm_us == 0 || *pwm_us == u16::MAX)
+    {
+        return Err("RC override PWM values must be between 1 and 65534 us".to_string());
+    }
+    Ok(())
+}
+
+pub fn override_channels(values: &[Option<u16>]) -> Vec<RcOverrideChannelWire> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| RcOverrideChannelWire {
+            channel: index as u8 + 1,
+            value: match value {
+                Some(pwm_us) => RcOverrideChannelValueWire::Pwm { pwm_us: *pwm_us },
+                None => RcOverrideChannelValueWire::Release,
+            },
+        })
+        .collect()
+}
+
+/// The throttle channel (1-based, from RCMAP_THROTTLE) and the PWM that
+/// stops the vehicle: RCx_TRIM for rovers, boats and subs, whose throttle
+/// centres on stopped, and RCx_MIN for everything that flies, where stick
+/// centre would hold half throttle.
+pub fn throttle_neutral(
+    vehicle_type: VehicleType,
+    param: impl Fn(&str) -> Option<f32>,
+) -> (u8, u16) {
+    let channel = param(RCMAP_THROTTLE)
+        .map(|channel| channel.round() as u8)
+        .filter(|channel| (1..=MAX_RC_OVERRIDE_CHANNELS as u8).contains(channel))
+        .unwrap_or(DEFAULT_THROTTLE_CHANNEL);
+    let centred = matches!(
+        vehicle_type,
+        VehicleType::GroundRover | VehicleType::Submarine
+    );
+    let (suffix, default_us) = if centred {
+        ("TRIM", DEFAULT_RC_TRIM_US)
+    } else {
+        ("MIN", DEFAULT_RC_MIN_US)
+    };
+    let pwm_us = param(&format!("RC{channel}_{suffix}"))
+        .map(|pwm_us| pwm_us.round() as u16)
+        .filter(|pwm_us| *pwm_us != 0 && *pwm_us != u16::MAX)
+        .unwrap_or(default_us);
+    (channel, pwm_us)
+}
+
+/// Stick centre on every channel in `values` that is overridden, except the
+/// throttle channel, which goes to its `throttle_neutral` PWM.
+pub fn neutral_values(values: &[Option<u16>], throttle: (u8, u16)) -> Vec<Option<u16>> {
+    let (throttle_channel, throttle_us) = throttle;
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            value.map(|_| {
+                if index + 1 == usize::from(throttle_channel) {
+                    throttle_us
+                } else {
+                    RC_OVERRIDE_NEUTRAL_US
+                }
+            })
+        })
+        .collect()
+}
+
+/// Every channel handed back to the receiver.
+pub fn release_values() -> Vec<Option<u16>> {
+    vec![None; MAX_RC_OVERRIDE_CHANNELS]
+}
+
+/// The latest values from the frontend and when they were last refreshed.
+#[derive(Debug, Clone)]
+pub struct RcOverrideStream {
+    values: Vec<Option<u16>>,
+    refreshed_at: Instant,
+}
+
+impl RcOverrideStream {
+    /// Starts with every channel released; the deadman runs from `now`.
+    pub fn new(now: Instant) -> Self {
+        Self {
+            values: Vec::new(),
+            refreshed_at: now,
+        }
+    }
+
+    pub fn set(&mut self, values: Vec<Option<u16>>, now: Instant) -> Result<(), String> {
+        validate_override_values(&values)?;
+        self.values = values;
+        self.refreshed_at = now;
+        Ok(())
+    }
+
+    pub fn values(&self) -> &[Option<u16>] {
+        &self.values
+    }
+
+    /// The values to send at `now`, or `None` once the deadman has expired.
+    pub fn frame(&self, now: Instant) -> Option<Vec<Option<u16>>> {
+        (now.saturating_duration_since(self.refreshed_at) <= RC_OVERRIDE_DEADMAN)
+            .then(|| self.values.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mavkit::AutopilotType;
+
+    fn vehicle_state(armed: bool, system_status: SystemStatus) -> VehicleState {
+        VehicleState {
+            armed,
+            custom_mode: 0,
+            mode_name: String::new(),
+            system_status,
+            vehicle_type: VehicleType::Quadrotor,
+            autopilot: AutopilotType::ArduPilotMega,
+            firmware_version: None,
+            system_id: 1,
+            component_id: 1,
+            heartbeat_received: true,
+        }
+    }
+
+    #[test]
+    fn armed_and_active_counts_as_airborne() {
+        assert!(is_airborne(&vehicle_state(true, SystemStatus::Active)));
+        assert!(is_airborne(&vehicle_state(true, SystemStatus::Critical)));
+        assert!(!is_airborne(&vehicle_state(true, SystemStatus::Standby)));
+        assert!(!is_airborne(&vehicle_state(false, SystemStatus::Active)));
+    }
+
+    #[test]
+    fn values_map_to_channels_and_markers_are_refused() {
+        let channels = override_channels(&[Some(1500), None, Some(1100)]);
+        assert_eq!(channels.len(), 3);
+        assert_eq!(channels[2].channel, 3);
+        assert!(matches!(
+            channels[1].value,
+            RcOverrideChannelValueWire::Release
+        ));
+        assert!(matches!(
+            channels[0].value,
+            RcOverrideChannelValueWire::Pwm { pwm_us: 1500 }
+        ));
+
+        assert!(validate_override_values(&[Some(0)]).is_err());
+        assert!(validate_override_values(&[None, Some(u16::MAX)]).is_err());
+        assert!(validate_override_values(&[None; MAX_RC_OVERRIDE_CHANNELS + 1]).is_err());
+        assert_eq!(
+            neutral_values(&[Some(1900), None, Some(1700)], (3, 1100)),
+            vec![Some(RC_OVERRIDE_NEUTRAL_US), None, Some(1100)]
+        );
+    }
+
+    #[test]
+    fn throttle_neutral_follows_rcmap_and_vehicle_kind() {
+        let params = |name: &str| match name {
+            "RCMAP_THROTTLE" => Some(2.0),
+            "RC2_MIN" => Some(982.0),
+            "RC2_TRIM" => Some(1492.0),
+            _ => None,
+        };
+        assert_eq!(throttle_neutral(VehicleType::Quadrotor, params), (2, 982));
+        assert_eq!(
+            throttle_neutral(VehicleType::GroundRover, params),
+            (2, 1492)
+        );
+        assert_eq!(
+            throttle_neutral(VehicleType::FixedWing, |_| None),
+            (3, 1100)
+        );
+    }
+
+    #[test]
+    fn deadman_expires_without_a_refresh() {
+        let start = Instant::now();
+        let mut stream = RcOverrideStream::new(start);
+        assert_eq!(stream.frame(start), Some(Vec::new()));
+
+        let refreshed = start + Duration::from_millis(800);
+        stream
+            .set(vec![Some(1600)], refreshed)
+            .expect("valid values");
+        assert_eq!(
+            stream.frame(start + Duration::from_millis(1_500)),
+            Some(vec![Some(1600)])
+        );
+        assert_eq!(stream.frame(refreshed + Duration::from_millis(1_100)), None);
+        assert!(stream.set(vec![Some(0)], refreshed).is_err());
+        assert_eq!(stream.values(), &[Some(1600)]);
+    }
+}
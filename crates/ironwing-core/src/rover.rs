@@ -0,0 +1,105 @@
+use mavkit::VehicleType;
+
+use crate::flight_modes::is_ground_vehicle;
+
+/// MAVLink message id of WHEEL_DISTANCE.
+pub const WHEEL_DISTANCE_MESSAGE_ID: u32 = 9000;
+pub const HOLD_MODE_NAME: &str = "HOLD";
+
+/// Rover commands refuse every other frame with this error.
+pub fn ensure_rover(vehicle_type: VehicleType) -> Result<(), String> {
+    if is_ground_vehicle(vehicle_type) {
+        Ok(())
+    } else {
+        Err(format!(
+            "not applicable to this vehicle type ({vehicle_type:?}); rover and boat only"
+        ))
+    }
+}
+
+/// MAV_CMD_DO_SET_REVERSE params: param1 1 drives backwards, 0 forwards.
+pub fn reverse_params(enabled: bool) -> [f32; 7] {
+    [
+        if enabled { 1.0 } else { 0.0 },
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+    ]
+}
+
+/// Distance driven, from the cumulative per-wheel distances in
+/// WHEEL_DISTANCE. Reversing counts too, so the odometer only grows.
+#[derive(Debug, Clone, Default)]
+pub struct WheelOdometer {
+    /// `(time_usec, mean wheel distance)` of the last report.
+    last: Option<(u64, f64)>,
+    total_m: f64,
+}
+
+impl WheelOdometer {
+    /// `distances` are the `count` wheels the message reports, in metres.
+    /// A timestamp going back means the vehicle rebooted and its counters
+    /// restarted, so that step is not driving.
+    pub fn record(&mut self, time_usec: u64, distances: &[f64]) {
+        let wheels: Vec<f64> = distances
+            .iter()
+            .copied()
+            .filter(|distance| distance.is_finite())
+            .collect();
+        if wheels.is_empty() {
+            return;
+        }
+        let mean_m = wheels.iter().sum::<f64>() / wheels.len() as f64;
+        if let Some((last_usec, last_m)) = self.last
+            && time_usec >= last_usec
+        {
+            self.total_m += (mean_m - last_m).abs();
+        }
+        self.last = Some((time_usec, mean_m));
+    }
+
+    /// `None` until the first report.
+    pub fn distance_m(&self) -> Option<f64> {
+        self.last.map(|_| self.total_m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn odometer_adds_both_directions_and_skips_resets() {
+        let mut odometer = WheelOdometer::default();
+        assert_eq!(odometer.distance_m(), None);
+        // Two wheels, as an ArduRover with WENC1/WENC2 sends them.
+        let reports: [(u64, &[f64]); 6] = [
+            (1_000_000, &[10.0, 10.2]),
+            (2_000_000, &[12.0, 12.2]),
+            (3_000_000, &[15.0, 15.4]),
+            // Reversing a metre.
+            (4_000_000, &[14.0, 14.4]),
+            // Rebooted.
+            (500_000, &[0.0, 0.0]),
+            (1_500_000, &[1.0, f64::NAN]),
+        ];
+        for (time_usec, distances) in reports {
+            odometer.record(time_usec, distances);
+        }
+        let distance = odometer.distance_m().unwrap();
+        assert!((distance - 7.1).abs() < 1e-9, "{distance}");
+    }
+
+    #[test]
+    fn only_ground_frames_are_accepted() {
+        assert!(ensure_rover(VehicleType::GroundRover).is_ok());
+        assert!(ensure_rover(VehicleType::SurfaceBoat).is_ok());
+        let error = ensure_rover(VehicleType::Quadrotor).unwrap_err();
+        assert!(error.starts_with("not applicable to this vehicle type"));
+        assert_eq!(reverse_params(true)[0], 1.0);
+        assert_eq!(reverse_params(false)[0], 0.0);
+    }
+}
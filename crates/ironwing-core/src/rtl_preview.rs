@@ -0,0 +1,403 @@
+use mavkit::{HomePosition, ParamStore};
+
+use crate::ipc::{RtlPoint, RtlPreview, RtlSegment, RtlSegmentKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RtlFirmware {
+    ArduPilot,
+    Px4,
+}
+
+/// The vehicle's global position when RTL would start.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RtlStart {
+    pub latitude_deg: f64,
+    pub longitude_deg: f64,
+    pub altitude_msl_m: f64,
+}
+
+/// The RTL behaviour a firmware's parameters describe, in metres above home.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RtlBehaviour {
+    /// Minimum return altitude; `None` returns at the current altitude.
+    return_alt_m: Option<f64>,
+    /// Altitude to hold over home before landing; `None` descends straight to
+    /// the final stage.
+    descend_alt_m: Option<f64>,
+    /// Time to loiter over home; `None` when it loiters until told otherwise.
+    loiter_s: Option<f64>,
+    /// `None` lands; otherwise hovers at this altitude once home.
+    final_alt_m: Option<f64>,
+}
+
+struct ParamReader<'a> {
+    store: Option<&'a ParamStore>,
+    warnings: Vec<String>,
+}
+
+impl ParamReader<'_> {
+    fn get(&mut self, name: &str, default: f32, unit: &str) -> f64 {
+        match self
+            .store
+            .and_then(|store| store.params.get(name))
+            .map(|param| param.value)
+        {
+            Some(value) => f64::from(value),
+            None => {
+                self.warnings.push(format!(
+                    "{name} is not downloaded; assuming the default {default} {unit}"
+                ));
+                f64::from(default)
+            }
+        }
+    }
+}
+
+fn ardupilot_behaviour(params: &mut ParamReader<'_>) -> RtlBehaviour {
+    let rtl_alt_cm = params.get("RTL_ALT", 1500.0, "cm");
+    let final_alt_cm = params.get("RTL_ALT_FINAL", 0.0, "cm");
+    let loiter_ms = params.get("RTL_LOIT_TIME", 5000.0, "ms");
+    RtlBehaviour {
+        return_alt_m: (rtl_alt_cm > 0.0).then_some(rtl_alt_cm / 100.0),
+        descend_alt_m: None,
+        loiter_s: Some(loiter_ms / 1000.0),
+        final_alt_m: (final_alt_cm > 0.0).then_some(final_alt_cm / 100.0),
+    }
+}
+
+fn px4_behaviour(params: &mut ParamReader<'_>) -> RtlBehaviour {
+    let return_alt_m = params.get("RTL_RETURN_ALT", 60.0, "m");
+    let descend_alt_m = params.get("RTL_DESCEND_ALT", 30.0, "m");
+    let land_delay_s = params.get("RTL_LAND_DELAY", 0.0, "s");
+    RtlBehaviour {
+        return_alt_m: Some(return_alt_m),
+        descend_alt_m: Some(descend_alt_m),
+        // A negative delay keeps the vehicle loitering at the descend altitude.
+        loiter_s: (land_delay_s >= 0.0).then_some(land_delay_s),
+        final_alt_m: (land_delay_s < 0.0).then_some(descend_alt_m),
+    }
+}
+
+fn point(latitude_deg: f64, longitude_deg: f64, relative_alt_m: f64) -> RtlPoint {
+    RtlPoint {
+        latitude_deg,
+        longitude_deg,
+        relative_alt_m,
+    }
+}
+
+fn segment(kind: RtlSegmentKind, from: RtlPoint, to: RtlPoint) -> RtlSegment {
+    RtlSegment {
+        kind,
+        from,
+        to,
+        duration_s: None,
+    }
+}
+
+fn path(
+    behaviour: &RtlBehaviour,
+    start: RtlStart,
+    home: &HomePosition,
+) -> (Vec<RtlSegment>, Vec<String>) {
+    let home_alt_msl_m = f64::from(home.altitude_m);
+    let current_alt_m = start.altitude_msl_m - home_alt_msl_m;
+    let return_alt_m = behaviour
+        .return_alt_m
+        .map_or(current_alt_m, |minimum| minimum.max(current_alt_m));
+
+    let mut segments = Vec::new();
+    let mut steps = Vec::new();
+    let here = point(start.latitude_deg, start.longitude_deg, current_alt_m);
+    let mut at = here;
+
+    if return_alt_m > current_alt_m {
+        let climbed = point(here.latitude_deg, here.longitude_deg, return_alt_m);
+        segments.push(segment(RtlSegmentKind::Climb, at, climbed));
+        steps.push(format!("climb to {return_alt_m:.0} m"));
+        at = climbed;
+    }
+
+    let over_home = point(home.latitude_deg, home.longitude_deg, return_alt_m);
+    segments.push(segment(RtlSegmentKind::Return, at, over_home));
+    steps.push(format!("fly home at {return_alt_m:.0} m"));
+    at = over_home;
+
+    if let Some(descend_alt_m) = behaviour.descend_alt_m
+        && descend_alt_m < at.relative_alt_m
+    {
+        let lowered = point(home.latitude_deg, home.longitude_deg, descend_alt_m);
+        segments.push(segment(RtlSegmentKind::Descend, at, lowered));
+        steps.push(format!("descend to {descend_alt_m:.0} m"));
+        at = lowered;
+    }
+
+    match behaviour.loiter_s {
+        Some(seconds) if seconds > 0.0 => {
+            segments.push(RtlSegment {
+                duration_s: Some(seconds),
+                ..segment(RtlSegmentKind::Loiter, at, at)
+            });
+            steps.push(format!("loiter {seconds:.0} s"));
+        }
+        _ => {}
+    }
+
+    match behaviour.final_alt_m {
+        Some(final_alt_m) => {
+            if (final_alt_m - at.relative_alt_m).abs() > f64::EPSILON {
+                let settled = point(home.latitude_deg, home.longitude_deg, final_alt_m);
+                segments.push(segment(RtlSegmentKind::Descend, at, settled));
+                at = settled;
+            }
+            segments.push(segment(RtlSegmentKind::Loiter, at, at));
+            steps.push(format!("hold over home at {final_alt_m:.0} m"));
+        }
+        None => {
+            let landed = point(home.latitude_deg, home.longitude_deg, 0.0);
+            segments.push(segment(RtlSegmentKind::Land, at, landed));
+            steps.push("land".to_string());
+        }
+    }
+
+    (segments, steps)
+}
+
+/// Predict the RTL path from `start` to `home` using the firmware's RTL
+/// parameters. Missing parameters fall back to firmware defaults and a missing
+/// position or home leaves the path empty; both are reported as warnings.
+pub fn rtl_preview(
+    firmware: RtlFirmware,
+    store: Option<&ParamStore>,
+    start: Option<RtlStart>,
+    home: Option<&HomePosition>,
+) -> RtlPreview {
+    let mut params = ParamReader {
+        store,
+        warnings: Vec::new(),
+    };
+    let behaviour = match firmware {
+        RtlFirmware::ArduPilot => ardupilot_behaviour(&mut params),
+        RtlFirmware::Px4 => px4_behaviour(&mut params),
+    };
+    let mut warnings = params.warnings;
+
+    let (segments, steps) = match (start, home) {
+        (Some(start), Some(home)) => path(&behaviour, start, home),
+        (start, home) => {
+            if start.is_none() {
+                warnings.push("vehicle position is unknown".to_string());
+            }
+            if home.is_none() {
+                warnings.push("home position is unknown".to_string());
+            }
+            (Vec::new(), Vec::new())
+        }
+    };
+
+    let description = if steps.is_empty() {
+        "Return path cannot be predicted yet.".to_string()
+    } else {
+        let mut text = steps.join(", then ");
+        text[..1].make_ascii_uppercase();
+        text.push('.');
+        text
+    };
+
+    RtlPreview {
+        segments,
+        description,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mavkit::{Param, ParamType};
+
+    use super::*;
+
+    fn store(params: &[(&str, f32)]) -> ParamStore {
+        ParamStore {
+            params: params
+                .iter()
+                .enumerate()
+                .map(|(index, &(name, value))| {
+                    (
+                        name.to_string(),
+                        Param {
+                            name: name.to_string(),
+                            value,
+                            param_type: ParamType::Real32,
+                            index: index as u16,
+                        },
+                    )
+                })
+                .collect::<HashMap<_, _>>(),
+            expected_count: params.len() as _,
+        }
+    }
+
+    fn home() -> HomePosition {
+        HomePosition {
+            latitude_deg: 47.397742,
+            longitude_deg: 8.545594,
+            altitude_m: 488.0,
+        }
+    }
+
+    fn start(relative_alt_m: f64) -> RtlStart {
+        RtlStart {
+            latitude_deg: 47.4,
+            longitude_deg: 8.55,
+            altitude_msl_m: 488.0 + relative_alt_m,
+        }
+    }
+
+    fn kinds(preview: &RtlPreview) -> Vec<RtlSegmentKind> {
+        preview
+            .segments
+            .iter()
+            .map(|segment| segment.kind)
+            .collect()
+    }
+
+    #[test]
+    fn ardupilot_climbs_to_rtl_alt_then_loiters_and_lands() {
+        let params = store(&[
+            ("RTL_ALT", 3000.0),
+            ("RTL_ALT_FINAL", 0.0),
+            ("RTL_LOIT_TIME", 5000.0),
+        ]);
+
+        let preview = rtl_preview(
+            RtlFirmware::ArduPilot,
+            Some(&params),
+            Some(start(12.0)),
+            Some(&home()),
+        );
+
+        assert!(preview.warnings.is_empty());
+        assert_eq!(
+            kinds(&preview),
+            [
+                RtlSegmentKind::Climb,
+                RtlSegmentKind::Return,
+                RtlSegmentKind::Loiter,
+                RtlSegmentKind::Land,
+            ]
+        );
+        assert!((preview.segments[0].to.relative_alt_m - 30.0).abs() < 1e-9);
+        assert_eq!(preview.segments[2].duration_s, Some(5.0));
+        assert_eq!(
+            preview.description,
+            "Climb to 30 m, then fly home at 30 m, then loiter 5 s, then land."
+        );
+    }
+
+    #[test]
+    fn ardupilot_above_rtl_alt_returns_at_current_altitude_and_holds_at_final() {
+        let params = store(&[
+            ("RTL_ALT", 1500.0),
+            ("RTL_ALT_FINAL", 500.0),
+            ("RTL_LOIT_TIME", 0.0),
+        ]);
+
+        let preview = rtl_preview(
+            RtlFirmware::ArduPilot,
+            Some(&params),
+            Some(start(40.0)),
+            Some(&home()),
+        );
+
+        assert_eq!(
+            kinds(&preview),
+            [
+                RtlSegmentKind::Return,
+                RtlSegmentKind::Descend,
+                RtlSegmentKind::Loiter,
+            ]
+        );
+        assert!((preview.segments[0].to.relative_alt_m - 40.0).abs() < 1e-9);
+        assert!((preview.segments[1].to.relative_alt_m - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn px4_descends_to_descend_alt_before_landing() {
+        let params = store(&[
+            ("RTL_RETURN_ALT", 50.0),
+            ("RTL_DESCEND_ALT", 20.0),
+            ("RTL_LAND_DELAY", 10.0),
+        ]);
+
+        let preview = rtl_preview(
+            RtlFirmware::Px4,
+            Some(&params),
+            Some(start(10.0)),
+            Some(&home()),
+        );
+
+        assert!(preview.warnings.is_empty());
+        assert_eq!(
+            kinds(&preview),
+            [
+                RtlSegmentKind::Climb,
+                RtlSegmentKind::Return,
+                RtlSegmentKind::Descend,
+                RtlSegmentKind::Loiter,
+                RtlSegmentKind::Land,
+            ]
+        );
+        assert!((preview.segments[2].to.relative_alt_m - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn px4_negative_land_delay_loiters_without_landing() {
+        let params = store(&[
+            ("RTL_RETURN_ALT", 50.0),
+            ("RTL_DESCEND_ALT", 20.0),
+            ("RTL_LAND_DELAY", -1.0),
+        ]);
+
+        let preview = rtl_preview(
+            RtlFirmware::Px4,
+            Some(&params),
+            Some(start(60.0)),
+            Some(&home()),
+        );
+
+        assert_eq!(
+            kinds(&preview),
+            [
+                RtlSegmentKind::Return,
+                RtlSegmentKind::Descend,
+                RtlSegmentKind::Loiter,
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_params_and_home_give_a_partial_preview_with_warnings() {
+        let preview = rtl_preview(
+            RtlFirmware::ArduPilot,
+            None,
+            Some(start(10.0)),
+            Some(&home()),
+        );
+        assert_eq!(preview.warnings.len(), 3);
+        assert!(preview.warnings[0].contains("RTL_ALT"));
+        assert_eq!(preview.segments[0].kind, RtlSegmentKind::Climb);
+        assert!((preview.segments[0].to.relative_alt_m - 15.0).abs() < 1e-9);
+
+        let no_home = rtl_preview(RtlFirmware::Px4, None, Some(start(10.0)), None);
+        assert!(no_home.segments.is_empty());
+        assert!(
+            no_home
+                .warnings
+                .iter()
+                .any(|warning| warning == "home position is unknown")
+        );
+    }
+}
@@ -63,6 +63,7 @@ impl SessionRuntime {
             source_kind,
             seek_epoch: self.next_seek_epoch,
             reset_revision: self.reset_revision,
+            system_id: None,
         };
 
         self.next_seek_epoch = self.next_seek_epoch.saturating_add(1);
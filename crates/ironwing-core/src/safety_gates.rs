@@ -0,0 +1,202 @@
+use crate::ipc::{AppError, GpsFix, GpsStatus, SafetyGate, SafetyGateConfig, SafetyGateFailure};
+
+/// What the gates compare, from the GPS and home state the backend already
+/// tracks.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SafetyGateInputs {
+    pub fix: Option<GpsFix>,
+    pub satellites: Option<u8>,
+    pub hdop: Option<f32>,
+    pub home_set: bool,
+}
+
+impl SafetyGateInputs {
+    /// Gate on the receiver with the best fix, so a dead second GPS does not
+    /// hold back a vehicle whose primary is healthy.
+    pub fn from_gps(status: &GpsStatus, home_set: bool) -> Self {
+        let best = status.instances.iter().max_by_key(|instance| instance.fix);
+        Self {
+            fix: best.map(|instance| instance.fix),
+            satellites: best.and_then(|instance| instance.satellites),
+            hdop: best.and_then(|instance| instance.hdop),
+            home_set,
+        }
+    }
+}
+
+fn failure(
+    gate: SafetyGate,
+    message: String,
+    current: Option<String>,
+    required: String,
+) -> SafetyGateFailure {
+    SafetyGateFailure {
+        gate,
+        message,
+        current,
+        required,
+    }
+}
+
+pub fn validate_safety_gates(config: &SafetyGateConfig) -> Result<(), String> {
+    if config
+        .max_hdop
+        .is_some_and(|hdop| !hdop.is_finite() || hdop <= 0.0)
+    {
+        return Err("max_hdop must be greater than zero".to_string());
+    }
+    Ok(())
+}
+
+pub fn failing_gates(
+    config: &SafetyGateConfig,
+    inputs: &SafetyGateInputs,
+) -> Vec<SafetyGateFailure> {
+    let mut failures = Vec::new();
+
+    if let Some(min_fix) = config.min_fix
+        && inputs.fix.is_none_or(|fix| fix < min_fix)
+    {
+        failures.push(failure(
+            SafetyGate::GpsFix,
+            format!("GPS fix is below {min_fix:?}"),
+            inputs.fix.map(|fix| format!("{fix:?}")),
+            format!("{min_fix:?}"),
+        ));
+    }
+
+    if let Some(min_satellites) = config.min_satellites
+        && inputs
+            .satellites
+            .is_none_or(|satellites| satellites < min_satellites)
+    {
+        failures.push(failure(
+            SafetyGate::Satellites,
+            format!("fewer than {min_satellites} satellites"),
+            inputs.satellites.map(|satellites| satellites.to_string()),
+            min_satellites.to_string(),
+        ));
+    }
+
+    if let Some(max_hdop) = config.max_hdop
+        && inputs.hdop.is_none_or(|hdop| hdop > max_hdop)
+    {
+        failures.push(failure(
+            SafetyGate::Hdop,
+            format!("HDOP is above {max_hdop}"),
+            inputs.hdop.map(|hdop| format!("{hdop:.2}")),
+            max_hdop.to_string(),
+        ));
+    }
+
+    if config.require_home && !inputs.home_set {
+        failures.push(failure(
+            SafetyGate::HomeSet,
+            "home position is not set".to_string(),
+            Some("not set".to_string()),
+            "set".to_string(),
+        ));
+    }
+
+    failures
+}
+
+/// `Ok` when every enabled gate passes, otherwise the structured
+/// `safety_gate` error listing the ones that failed.
+pub fn check_safety_gates(
+    config: &SafetyGateConfig,
+    inputs: &SafetyGateInputs,
+) -> Result<(), AppError> {
+    let failures = failing_gates(config, inputs);
+    if failures.is_empty() {
+        return Ok(());
+    }
+    let message = failures
+        .iter()
+        .map(|failure| failure.message.as_str())
+        .collect::<Vec<_>>()
+        .join("; ");
+    Err(AppError::SafetyGate {
+        failures,
+        message: format!("safety gates failed: {message}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ipc::GpsInstanceStatus;
+
+    use super::*;
+
+    fn strict() -> SafetyGateConfig {
+        SafetyGateConfig {
+            min_fix: Some(GpsFix::Fix3d),
+            min_satellites: Some(8),
+            max_hdop: Some(1.5),
+            require_home: true,
+        }
+    }
+
+    #[test]
+    fn default_gates_let_everything_through() {
+        assert_eq!(
+            check_safety_gates(&SafetyGateConfig::default(), &SafetyGateInputs::default()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn each_failing_gate_is_listed_with_its_value() {
+        let inputs = SafetyGateInputs {
+            fix: Some(GpsFix::Fix2d),
+            satellites: Some(5),
+            hdop: None,
+            home_set: false,
+        };
+        let Err(AppError::SafetyGate { failures, .. }) = check_safety_gates(&strict(), &inputs)
+        else {
+            panic!("expected safety gate error");
+        };
+        let gates: Vec<SafetyGate> = failures.iter().map(|failure| failure.gate).collect();
+        assert_eq!(
+            gates,
+            [
+                SafetyGate::GpsFix,
+                SafetyGate::Satellites,
+                SafetyGate::Hdop,
+                SafetyGate::HomeSet
+            ]
+        );
+        assert_eq!(failures[0].current.as_deref(), Some("Fix2d"));
+        assert_eq!(failures[1].current.as_deref(), Some("5"));
+        assert_eq!(failures[2].current, None);
+    }
+
+    #[test]
+    fn healthy_vehicle_passes_strict_gates() {
+        let status = GpsStatus {
+            instances: vec![
+                GpsInstanceStatus {
+                    instance: 0,
+                    fix: GpsFix::RtkFixed,
+                    satellites: Some(18),
+                    hdop: Some(0.7),
+                    rtk_baseline_age_ms: None,
+                    time_to_3d_fix_ms: Some(20_000),
+                },
+                GpsInstanceStatus {
+                    instance: 1,
+                    fix: GpsFix::NoFix,
+                    satellites: Some(0),
+                    hdop: None,
+                    rtk_baseline_age_ms: None,
+                    time_to_3d_fix_ms: None,
+                },
+            ],
+            time_to_first_3d_fix_ms: Some(20_000),
+        };
+        let inputs = SafetyGateInputs::from_gps(&status, true);
+        assert_eq!(inputs.satellites, Some(18));
+        assert_eq!(check_safety_gates(&strict(), &inputs), Ok(()));
+    }
+}
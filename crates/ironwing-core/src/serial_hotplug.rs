@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use ironwing_firmware::discovery::is_known_fc_application_port;
+
+use crate::ipc::firmware::PortInfo;
+
+/// How often the desktop shell re-enumerates serial ports.
+pub const SERIAL_PORT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PortChanges {
+    pub attached: Vec<PortInfo>,
+    pub detached: Vec<PortInfo>,
+}
+
+/// Ports present only in `current` are attached, ports present only in
+/// `previous` detached. Ports are matched by name.
+pub fn diff_ports(previous: &[PortInfo], current: &[PortInfo]) -> PortChanges {
+    let missing_from = |ports: &[PortInfo], port: &PortInfo| {
+        !ports.iter().any(|other| other.port_name == port.port_name)
+    };
+    PortChanges {
+        attached: current
+            .iter()
+            .filter(|port| missing_from(previous, port))
+            .cloned()
+            .collect(),
+        detached: previous
+            .iter()
+            .filter(|port| missing_from(current, port))
+            .cloned()
+            .collect(),
+    }
+}
+
+/// The newly attached port to auto-connect to: the one the last link was lost
+/// on if it came back, otherwise the first with a known autopilot VID/PID.
+pub fn auto_connect_port<'a>(
+    attached: &'a [PortInfo],
+    lost_port: Option<&str>,
+) -> Option<&'a PortInfo> {
+    attached
+        .iter()
+        .find(|port| Some(port.port_name.as_str()) == lost_port)
+        .or_else(|| {
+            attached
+                .iter()
+                .find(|port| is_known_fc_application_port(port))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn port(name: &str, vid_pid: Option<(u16, u16)>) -> PortInfo {
+        PortInfo {
+            port_name: name.to_string(),
+            vid: vid_pid.map(|(vid, _)| vid),
+            pid: vid_pid.map(|(_, pid)| pid),
+            serial_number: None,
+            manufacturer: None,
+            product: None,
+            location: None,
+        }
+    }
+
+    #[test]
+    fn diff_reports_attached_and_detached_ports() {
+        let previous = [port("/dev/ttyS0", None), port("/dev/ttyACM0", None)];
+        let current = [port("/dev/ttyS0", None), port("/dev/ttyUSB0", None)];
+
+        let changes = diff_ports(&previous, &current);
+
+        assert_eq!(changes.attached, [port("/dev/ttyUSB0", None)]);
+        assert_eq!(changes.detached, [port("/dev/ttyACM0", None)]);
+        assert_eq!(diff_ports(&current, &current), PortChanges::default());
+    }
+
+    #[test]
+    fn auto_connect_prefers_the_lost_port_then_known_autopilots() {
+        let cube = port("/dev/ttyACM1", Some((0x2DAE, 0x1058)));
+        let attached = [port("/dev/ttyUSB0", None), cube.clone()];
+
+        assert_eq!(auto_connect_port(&attached, None), Some(&cube));
+        assert_eq!(
+            auto_connect_port(&attached, Some("/dev/ttyUSB0")),
+            Some(&attached[0])
+        );
+        assert_eq!(auto_connect_port(&attached[..1], None), None);
+    }
+}
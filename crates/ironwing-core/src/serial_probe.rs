@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use crate::mavlink_frames::{
+    MAVLINK_IFLAG_SIGNED, MAVLINK_V1_HEADER_LEN, MAVLINK_V1_STX, MAVLINK_V2_HEADER_LEN,
+    MAVLINK_V2_STX, crc_x25,
+};
+
+/// Rates tried by auto-baud, most common first.
+pub const AUTO_BAUD_RATES: [u32; 4] = [115_200, 57_600, 921_600, 1_500_000];
+
+/// How long each rate gets to produce a valid HEARTBEAT.
+pub const AUTO_BAUD_PROBE_WINDOW: Duration = Duration::from_secs(2);
+
+const HEARTBEAT_MESSAGE_ID: u32 = 0;
+const HEARTBEAT_CRC_EXTRA: u8 = 50;
+const HEARTBEAT_PAYLOAD_LEN: usize = 9;
+
+/// Whether `bytes` holds at least one complete v1 or v2 HEARTBEAT frame with
+/// a valid checksum. Bytes read at the wrong baud rate decode as noise, so a
+/// checksummed frame is the signal that the rate is right.
+pub fn contains_heartbeat(bytes: &[u8]) -> bool {
+    (0..bytes.len()).any(|start| heartbeat_at(&bytes[start..]))
+}
+
+fn heartbeat_at(frame: &[u8]) -> bool {
+    let (header_len, message_id) = match frame.first() {
+        Some(&MAVLINK_V1_STX) if frame.len() >= MAVLINK_V1_HEADER_LEN => {
+            (MAVLINK_V1_HEADER_LEN, u32::from(frame[5]))
+        }
+        Some(&MAVLINK_V2_STX) if frame.len() >= MAVLINK_V2_HEADER_LEN => {
+            if frame[2] & !MAVLINK_IFLAG_SIGNED != 0 {
+                return false;
+            }
+            (
+                MAVLINK_V2_HEADER_LEN,
+                u32::from_le_bytes([frame[7], frame[8], frame[9], 0]),
+            )
+        }
+        _ => return false,
+    };
+    let payload_len = usize::from(frame[1]);
+    // MAVLink 2 trims trailing zero bytes, so the payload may be shorter.
+    if message_id != HEARTBEAT_MESSAGE_ID || payload_len > HEARTBEAT_PAYLOAD_LEN {
+        return false;
+    }
+    let crc_end = header_len + payload_len;
+    let Some(crc) = frame.get(crc_end..crc_end + 2) else {
+        return false;
+    };
+    crc_x25(&frame[1..crc_end], HEARTBEAT_CRC_EXTRA) == u16::from_le_bytes([crc[0], crc[1]])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dialect::{HEARTBEAT_DATA, MavMessage};
+    use mavlink::MavHeader;
+
+    use super::*;
+
+    fn heartbeat() -> MavMessage {
+        MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+            mavlink_version: 3,
+            ..HEARTBEAT_DATA::default()
+        })
+    }
+
+    #[test]
+    fn finds_heartbeats_behind_noise() {
+        let mut v2 = vec![0x00, 0xFD, 0x42, 0xFE];
+        mavlink::write_v2_msg(&mut v2, MavHeader::default(), &heartbeat()).expect("write v2");
+        assert!(contains_heartbeat(&v2));
+
+        let mut v1 = vec![0x13, 0x37];
+        mavlink::write_v1_msg(&mut v1, MavHeader::default(), &heartbeat()).expect("write v1");
+        assert!(contains_heartbeat(&v1));
+    }
+
+    #[test]
+    fn rejects_corrupt_and_truncated_frames() {
+        let mut frame = Vec::new();
+        mavlink::write_v2_msg(&mut frame, MavHeader::default(), &heartbeat()).expect("write v2");
+
+        assert!(!contains_heartbeat(&frame[..frame.len() - 1]));
+        let last = frame.len() - 1;
+        frame[last] ^= 0xFF;
+        assert!(!contains_heartbeat(&frame));
+        assert!(!contains_heartbeat(&[0x55; 64]));
+    }
+}
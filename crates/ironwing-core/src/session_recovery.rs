@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use crate::ipc::SessionRecoverInfo;
+
+/// File name, under the app data directory, of the connection that is open
+/// right now. A clean disconnect deletes it, so finding it at startup means
+/// the previous run was killed while connected.
+pub const ACTIVE_SESSION_FILE_NAME: &str = "active_session.json";
+
+/// How often the active session file is refreshed while connected.
+pub const SESSION_RECOVERY_WRITE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Sessions not refreshed for longer than this are not offered for
+/// recovery; the vehicle has long since landed or left range.
+pub const SESSION_RECOVERY_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// The saved session from `contents`, unless it is unreadable or stale.
+pub fn recoverable_session(contents: &str, now_unix_msec: u64) -> Option<SessionRecoverInfo> {
+    let info: SessionRecoverInfo = serde_json::from_str(contents).ok()?;
+    let age_msec = now_unix_msec.saturating_sub(info.updated_at_unix_msec);
+    (age_msec <= SESSION_RECOVERY_MAX_AGE.as_millis() as u64).then_some(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::{ConnectRequest, ConnectTransport};
+
+    fn saved_at(updated_at_unix_msec: u64) -> String {
+        serde_json::to_string(&SessionRecoverInfo {
+            request: ConnectRequest {
+                transport: ConnectTransport::Udp {
+                    bind_addr: "0.0.0.0:14550".into(),
+                },
+                auto_record_on_connect: true,
+                heartbeat_timeout_secs: None,
+                keep_waiting: false,
+            },
+            connected_at_unix_msec: 1_000,
+            updated_at_unix_msec,
+            armed: true,
+            last_latitude_deg: Some(47.4),
+            last_longitude_deg: Some(8.5),
+            recording_paths: vec!["/logs/flight.tlog".into()],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn a_recent_session_is_offered_for_recovery() {
+        let info = recoverable_session(&saved_at(10_000), 20_000).expect("recent session");
+        assert!(info.armed);
+        assert!(info.request.auto_record_on_connect);
+        assert_eq!(info.recording_paths, ["/logs/flight.tlog"]);
+    }
+
+    #[test]
+    fn stale_or_unreadable_files_are_ignored() {
+        let hour_msec = SESSION_RECOVERY_MAX_AGE.as_millis() as u64;
+        assert!(recoverable_session(&saved_at(10_000), 10_000 + hour_msec).is_some());
+        assert!(recoverable_session(&saved_at(10_000), 10_001 + hour_msec).is_none());
+        assert!(recoverable_session("{\"request\":", 10_000).is_none());
+    }
+}
@@ -0,0 +1,310 @@
+use std::ops::RangeInclusive;
+
+use crate::alerts::{default_alert_rules, validate_alert_rules};
+use crate::announcements::validate_announcement_settings;
+use crate::checklist::validate_checklist;
+use crate::fence_proximity::{DEFAULT_FENCE_WARNING_MARGIN_M, validate_fence_warning_margin};
+use crate::flight_track::DEFAULT_TRACK_MAX_POINTS;
+use crate::health::validate_health_thresholds;
+use crate::ipc::{
+    AnnouncementSettings, ChecklistConfig, HealthThresholds, LandingAssistProfile,
+    LinkTimeoutOverrides, SafetyGateConfig, Settings, SettingsPatch, TlogFsyncPolicy, TlogRotation,
+    UnitSystem,
+};
+use crate::payload::{DEFAULT_WINCH_MAX_RATE_MPS, validate_winch_max_rate};
+use crate::rangefinder::validate_landing_assist;
+use crate::safety_gates::validate_safety_gates;
+use crate::takeoff::DEFAULT_TAKEOFF_MAX_ALT_M;
+use crate::telemetry::{
+    DEFAULT_TELEMETRY_INTERVAL_MS, DEFAULT_TELEMETRY_STALE_AFTER_MS, telemetry_interval_ms_for_rate,
+};
+use crate::tlog_segments::validate_tlog_rotation;
+use crate::vehicle_config::validate_link_timeout_overrides;
+
+/// File name the desktop shell persists settings under.
+pub const SETTINGS_FILE_NAME: &str = "settings.json";
+
+/// Bump with a matching step in `migrate` whenever the file shape changes.
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
+pub const DEFAULT_AUTO_CONNECT_USB_BAUD: u32 = 115_200;
+pub const DEFAULT_LINK_BUFFER_FRAMES: u32 = 64;
+pub const LINK_BUFFER_FRAMES_RANGE: RangeInclusive<u32> = 8..=4096;
+pub const DEFAULT_MAX_OPEN_LOGS: u32 = 4;
+pub const MAX_OPEN_LOGS_RANGE: RangeInclusive<u32> = 1..=16;
+pub const TRACK_MAX_POINTS_RANGE: RangeInclusive<u32> = 1_000..=500_000;
+pub const TELEMETRY_STALE_AFTER_MS_RANGE: RangeInclusive<u32> = 500..=120_000;
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
+            telemetry_rate_hz: (1000 / DEFAULT_TELEMETRY_INTERVAL_MS) as u32,
+            units: UnitSystem::default(),
+            alert_rules: default_alert_rules(),
+            auto_connect_usb: false,
+            auto_connect_usb_baud: DEFAULT_AUTO_CONNECT_USB_BAUD,
+            link_buffer_frames: DEFAULT_LINK_BUFFER_FRAMES,
+            takeoff_max_alt_m: DEFAULT_TAKEOFF_MAX_ALT_M,
+            expected_failsafe_check: true,
+            max_open_logs: DEFAULT_MAX_OPEN_LOGS,
+            track_max_points: DEFAULT_TRACK_MAX_POINTS,
+            safety_gates: SafetyGateConfig::default(),
+            checklist: ChecklistConfig::default(),
+            expert_mode: false,
+            landing_assist: LandingAssistProfile::default(),
+            winch_max_rate_mps: DEFAULT_WINCH_MAX_RATE_MPS,
+            health_thresholds: HealthThresholds::default(),
+            fence_warning_margin_m: DEFAULT_FENCE_WARNING_MARGIN_M,
+            rc_link_on_telemetry_radio: false,
+            link_timeouts: LinkTimeoutOverrides::default(),
+            auto_connect_launch_link: false,
+            announcements: AnnouncementSettings::default(),
+            recording_rotation: TlogRotation::default(),
+            recording_fsync: TlogFsyncPolicy::default(),
+            telemetry_stale_after_ms: DEFAULT_TELEMETRY_STALE_AFTER_MS,
+        }
+    }
+}
+
+pub fn validate_settings(settings: &Settings) -> Result<(), String> {
+    telemetry_interval_ms_for_rate(settings.telemetry_rate_hz)?;
+    validate_alert_rules(&settings.alert_rules)?;
+    if settings.auto_connect_usb && settings.auto_connect_usb_baud == 0 {
+        return Err("auto-connect baud rate must be greater than zero".to_string());
+    }
+    if !LINK_BUFFER_FRAMES_RANGE.contains(&settings.link_buffer_frames) {
+        return Err(format!(
+            "link_buffer_frames must be between {} and {}",
+            LINK_BUFFER_FRAMES_RANGE.start(),
+            LINK_BUFFER_FRAMES_RANGE.end()
+        ));
+    }
+    if !settings.takeoff_max_alt_m.is_finite() || settings.takeoff_max_alt_m <= 0.0 {
+        return Err("takeoff_max_alt_m must be greater than zero".to_string());
+    }
+    if !MAX_OPEN_LOGS_RANGE.contains(&settings.max_open_logs) {
+        return Err(format!(
+            "max_open_logs must be between {} and {}",
+            MAX_OPEN_LOGS_RANGE.start(),
+            MAX_OPEN_LOGS_RANGE.end()
+        ));
+    }
+    if !TRACK_MAX_POINTS_RANGE.contains(&settings.track_max_points) {
+        return Err(format!(
+            "track_max_points must be between {} and {}",
+            TRACK_MAX_POINTS_RANGE.start(),
+            TRACK_MAX_POINTS_RANGE.end()
+        ));
+    }
+    if !TELEMETRY_STALE_AFTER_MS_RANGE.contains(&settings.telemetry_stale_after_ms) {
+        return Err(format!(
+            "telemetry_stale_after_ms must be between {} and {}",
+            TELEMETRY_STALE_AFTER_MS_RANGE.start(),
+            TELEMETRY_STALE_AFTER_MS_RANGE.end()
+        ));
+    }
+    validate_safety_gates(&settings.safety_gates)?;
+    validate_checklist(&settings.checklist)?;
+    validate_landing_assist(&settings.landing_assist)?;
+    validate_winch_max_rate(settings.winch_max_rate_mps)?;
+    validate_health_thresholds(&settings.health_thresholds)?;
+    validate_fence_warning_margin(settings.fence_warning_margin_m)?;
+    validate_link_timeout_overrides(&settings.link_timeouts)?;
+    validate_announcement_settings(&settings.announcements)?;
+    validate_tlog_rotation(&settings.recording_rotation)?;
+    Ok(())
+}
+
+/// `current` with `patch` merged in, validated as a whole so an invalid field
+/// rejects the entire update.
+pub fn apply_patch(current: &Settings, patch: SettingsPatch) -> Result<Settings, String> {
+    let mut next = current.clone();
+    if let Some(rate_hz) = patch.telemetry_rate_hz {
+        next.telemetry_rate_hz = rate_hz;
+    }
+    if let Some(units) = patch.units {
+        next.units = units;
+    }
+    if let Some(rules) = patch.alert_rules {
+        next.alert_rules = rules;
+    }
+    if let Some(enabled) = patch.auto_connect_usb {
+        next.auto_connect_usb = enabled;
+    }
+    if let Some(baud) = patch.auto_connect_usb_baud {
+        next.auto_connect_usb_baud = baud;
+    }
+    if let Some(frames) = patch.link_buffer_frames {
+        next.link_buffer_frames = frames;
+    }
+    if let Some(max_alt_m) = patch.takeoff_max_alt_m {
+        next.takeoff_max_alt_m = max_alt_m;
+    }
+    if let Some(enabled) = patch.expected_failsafe_check {
+        next.expected_failsafe_check = enabled;
+    }
+    if let Some(max_open_logs) = patch.max_open_logs {
+        next.max_open_logs = max_open_logs;
+    }
+    if let Some(max_points) = patch.track_max_points {
+        next.track_max_points = max_points;
+    }
+    if let Some(gates) = patch.safety_gates {
+        next.safety_gates = gates;
+    }
+    if let Some(checklist) = patch.checklist {
+        next.checklist = checklist;
+    }
+    if let Some(enabled) = patch.expert_mode {
+        next.expert_mode = enabled;
+    }
+    if let Some(profile) = patch.landing_assist {
+        next.landing_assist = profile;
+    }
+    if let Some(max_rate_mps) = patch.winch_max_rate_mps {
+        next.winch_max_rate_mps = max_rate_mps;
+    }
+    if let Some(thresholds) = patch.health_thresholds {
+        next.health_thresholds = thresholds;
+    }
+    if let Some(margin_m) = patch.fence_warning_margin_m {
+        next.fence_warning_margin_m = margin_m;
+    }
+    if let Some(enabled) = patch.rc_link_on_telemetry_radio {
+        next.rc_link_on_telemetry_radio = enabled;
+    }
+    if let Some(overrides) = patch.link_timeouts {
+        next.link_timeouts = overrides;
+    }
+    if let Some(enabled) = patch.auto_connect_launch_link {
+        next.auto_connect_launch_link = enabled;
+    }
+    if let Some(announcements) = patch.announcements {
+        next.announcements = announcements;
+    }
+    if let Some(rotation) = patch.recording_rotation {
+        next.recording_rotation = rotation;
+    }
+    if let Some(fsync) = patch.recording_fsync {
+        next.recording_fsync = fsync;
+    }
+    if let Some(stale_after_ms) = patch.telemetry_stale_after_ms {
+        next.telemetry_stale_after_ms = stale_after_ms;
+    }
+    validate_settings(&next)?;
+    Ok(next)
+}
+
+/// Bring a settings document of any earlier schema version up to date.
+/// Each step upgrades one version in place.
+fn migrate(document: &mut serde_json::Value) -> Result<(), String> {
+    let object = document
+        .as_object_mut()
+        .ok_or("settings file is not a JSON object")?;
+    let version = match object.get("schema_version") {
+        None => 0,
+        Some(value) => value
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or("schema_version is not a version number")?,
+    };
+    if version > SETTINGS_SCHEMA_VERSION {
+        return Err(format!(
+            "settings schema version {version} is newer than this build understands ({SETTINGS_SCHEMA_VERSION})"
+        ));
+    }
+    // Version 0 is a file written before settings were versioned; its fields
+    // already match version 1.
+    object.insert(
+        "schema_version".to_string(),
+        serde_json::Value::from(SETTINGS_SCHEMA_VERSION),
+    );
+    Ok(())
+}
+
+/// Parse and migrate a saved settings file. Fields the file lacks take their
+/// defaults; anything unreadable or invalid is an error so the caller can fall
+/// back to defaults.
+pub fn parse_settings(bytes: &[u8]) -> Result<Settings, String> {
+    let mut document: serde_json::Value = serde_json::from_slice(bytes)
+        .map_err(|error| format!("failed to parse settings: {error}"))?;
+    migrate(&mut document)?;
+    let settings: Settings = serde_json::from_value(document)
+        .map_err(|error| format!("failed to parse settings: {error}"))?;
+    validate_settings(&settings)?;
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::DistanceUnit;
+
+    #[test]
+    fn patch_merges_only_the_fields_it_sets() {
+        let current = Settings::default();
+        let next = apply_patch(
+            &current,
+            SettingsPatch {
+                telemetry_rate_hz: Some(10),
+                ..SettingsPatch::default()
+            },
+        )
+        .expect("valid patch");
+
+        assert_eq!(next.telemetry_rate_hz, 10);
+        assert_eq!(
+            Settings {
+                telemetry_rate_hz: current.telemetry_rate_hz,
+                ..next
+            },
+            current
+        );
+    }
+
+    #[test]
+    fn invalid_field_rejects_the_whole_patch() {
+        let patch = SettingsPatch {
+            telemetry_rate_hz: Some(25),
+            link_buffer_frames: Some(128),
+            ..SettingsPatch::default()
+        };
+        assert!(apply_patch(&Settings::default(), patch).is_err());
+
+        let patch = SettingsPatch {
+            auto_connect_usb: Some(true),
+            auto_connect_usb_baud: Some(0),
+            ..SettingsPatch::default()
+        };
+        assert!(apply_patch(&Settings::default(), patch).is_err());
+
+        let patch = SettingsPatch {
+            telemetry_stale_after_ms: Some(100),
+            ..SettingsPatch::default()
+        };
+        assert!(apply_patch(&Settings::default(), patch).is_err());
+    }
+
+    #[test]
+    fn saved_settings_round_trip_and_fill_missing_fields() {
+        let mut settings = Settings::default();
+        settings.units.distance = DistanceUnit::Ft;
+        let bytes = serde_json::to_vec(&settings).expect("serialize");
+        assert_eq!(parse_settings(&bytes), Ok(settings));
+
+        let unversioned = br#"{ "telemetry_rate_hz": 2 }"#;
+        let migrated = parse_settings(unversioned).expect("migrate version 0");
+        assert_eq!(migrated.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert_eq!(migrated.telemetry_rate_hz, 2);
+        assert_eq!(migrated.link_buffer_frames, DEFAULT_LINK_BUFFER_FRAMES);
+        assert_eq!(migrated.telemetry_stale_after_ms, DEFAULT_TELEMETRY_STALE_AFTER_MS);
+    }
+
+    #[test]
+    fn corrupt_or_future_files_are_errors() {
+        assert!(parse_settings(b"{ not json").is_err());
+        assert!(parse_settings(br#"{ "telemetry_rate_hz": 0 }"#).is_err());
+        assert!(parse_settings(br#"{ "schema_version": 99 }"#).is_err());
+    }
+}
@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+/// How often the resume watcher compares the clocks.
+pub const RESUME_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A check this much later than scheduled means the host was asleep;
+/// shorter stalls are scheduling jitter.
+pub const RESUME_JUMP_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// How long the host was suspended between two checks `expected` apart,
+/// given how far the monotonic and wall clocks advanced. The monotonic clock
+/// stops during suspend on Linux and macOS but keeps counting on Windows,
+/// while the wall clock always does, so whichever moved further is the time
+/// that really passed. `wall` is `None` when the wall clock went backwards.
+pub fn suspended_for(
+    expected: Duration,
+    monotonic: Duration,
+    wall: Option<Duration>,
+) -> Option<Duration> {
+    let passed = monotonic.max(wall.unwrap_or_default());
+    let late = passed.saturating_sub(expected);
+    (late >= RESUME_JUMP_THRESHOLD).then_some(late)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn either_clock_jumping_counts_as_a_resume() {
+        let second = Duration::from_secs(1);
+        assert_eq!(suspended_for(second, second, Some(second)), None);
+        // A busy runtime delays a tick by a second or two.
+        assert_eq!(
+            suspended_for(second, Duration::from_secs(3), Some(Duration::from_secs(3))),
+            None
+        );
+        // Linux/macOS: the monotonic clock stood still while the lid was shut.
+        assert_eq!(
+            suspended_for(second, second, Some(Duration::from_secs(601))),
+            Some(Duration::from_secs(600))
+        );
+        // Windows: the monotonic clock kept counting.
+        assert_eq!(
+            suspended_for(second, Duration::from_secs(601), None),
+            Some(Duration::from_secs(600))
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::ipc::{AppError, MavResultKind, TakeoffStep};
+
+pub const DEFAULT_TAKEOFF_MAX_ALT_M: f32 = 120.0;
+
+/// How long after a refused command its STATUSTEXT explanation is collected.
+pub const DENIAL_STATUSTEXT_WINDOW_MS: u64 = 1000;
+
+pub fn validate_takeoff_altitude(altitude_m: f32, max_alt_m: f32) -> Result<(), AppError> {
+    if !altitude_m.is_finite() || altitude_m <= 0.0 {
+        return Err(AppError::validation(
+            "takeoff altitude must be greater than zero",
+        ));
+    }
+    if altitude_m > max_alt_m {
+        return Err(AppError::validation(format!(
+            "takeoff altitude {altitude_m} m exceeds the {max_alt_m} m limit"
+        )));
+    }
+    Ok(())
+}
+
+fn step_label(step: TakeoffStep) -> &'static str {
+    match step {
+        TakeoffStep::SetGuided => "Switching to Guided",
+        TakeoffStep::Arm => "Arming",
+        TakeoffStep::Takeoff => "Takeoff",
+    }
+}
+
+fn step_hint(step: TakeoffStep) -> &'static str {
+    match step {
+        TakeoffStep::SetGuided => "Guided mode may be unavailable without a position fix",
+        TakeoffStep::Arm => "check the pre-arm messages",
+        TakeoffStep::Takeoff => "the vehicle must be armed and in Guided mode",
+    }
+}
+
+/// An actionable explanation for a failed step. A refusal
+/// (MAV_RESULT_DENIED or TEMPORARILY_REJECTED) is explained with the
+/// STATUSTEXT the vehicle sent right after it when there is any, since that
+/// is where ArduPilot says why; otherwise with a hint for the step.
+pub fn describe_step_failure(
+    step: TakeoffStep,
+    error: &AppError,
+    status_texts: &[String],
+) -> String {
+    let label = step_label(step);
+    let AppError::CommandDenied { result, .. } = error else {
+        return format!("{label} failed: {}", error.message());
+    };
+    let temporary = *result == MavResultKind::TemporarilyRejected;
+    let verdict = if temporary {
+        "temporarily rejected"
+    } else {
+        "denied"
+    };
+    if !status_texts.is_empty() {
+        return format!("{label} {verdict}: {}", status_texts.join("; "));
+    }
+    if temporary {
+        format!("{label} {verdict}: the vehicle is busy, try again in a moment")
+    } else {
+        format!("{label} {verdict}: {}", step_hint(step))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn denied(result: MavResultKind) -> AppError {
+        AppError::CommandDenied {
+            result,
+            message: "command rejected".to_string(),
+        }
+    }
+
+    #[test]
+    fn altitude_is_checked_against_the_limit() {
+        assert!(validate_takeoff_altitude(10.0, DEFAULT_TAKEOFF_MAX_ALT_M).is_ok());
+        assert!(validate_takeoff_altitude(120.5, DEFAULT_TAKEOFF_MAX_ALT_M).is_err());
+        assert!(validate_takeoff_altitude(0.0, DEFAULT_TAKEOFF_MAX_ALT_M).is_err());
+        assert!(validate_takeoff_altitude(f32::NAN, DEFAULT_TAKEOFF_MAX_ALT_M).is_err());
+    }
+
+    #[test]
+    fn denial_prefers_the_vehicles_statustext() {
+        let texts = vec!["PreArm: Need Position Estimate".to_string()];
+        assert_eq!(
+            describe_step_failure(TakeoffStep::Arm, &denied(MavResultKind::Denied), &texts),
+            "Arming denied: PreArm: Need Position Estimate"
+        );
+        assert_eq!(
+            describe_step_failure(TakeoffStep::Takeoff, &denied(MavResultKind::Denied), &[]),
+            "Takeoff denied: the vehicle must be armed and in Guided mode"
+        );
+        assert_eq!(
+            describe_step_failure(TakeoffStep::Takeoff, &denied(MavResultKind::TemporarilyRejected), &[]),
+            "Takeoff temporarily rejected: the vehicle is busy, try again in a moment"
+        );
+    }
+
+    #[test]
+    fn other_failures_keep_their_message() {
+        let error = AppError::Timeout {
+            message: "no ACK".to_string(),
+        };
+        assert_eq!(
+            describe_step_failure(TakeoffStep::SetGuided, &error, &[]),
+            "Switching to Guided failed: no ACK"
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use web_time::Instant;
+
+use crate::ipc::{TaskDiagnostic, TaskStalled, TaskState};
+
+/// How long the MAVLink event loop may go without a message while the link
+/// still reads as connected. Longer than MAVKit's own heartbeat timeout, so
+/// a vehicle that merely went quiet is reported as link loss first.
+pub const EVENT_LOOP_STALL_AFTER: Duration = Duration::from_secs(10);
+/// Restarts a task gets after panicking before it is left dead.
+pub const MAX_TASK_RESTARTS: u32 = 3;
+
+#[derive(Debug, Clone)]
+struct WatchedTask {
+    state: TaskState,
+    /// `None` for tasks that only wait on events and may be idle for good.
+    stall_after: Option<Duration>,
+    last_beat: Instant,
+    restarts: u32,
+    detail: Option<String>,
+}
+
+/// Heartbeats and fates of the internal tasks behind a session: event loop,
+/// bridges and transport drains.
+#[derive(Debug, Clone, Default)]
+pub struct TaskTable {
+    tasks: BTreeMap<String, WatchedTask>,
+}
+
+impl TaskTable {
+    /// Start watching `name`, replacing whatever a previous session left.
+    pub fn register(&mut self, name: &str, stall_after: Option<Duration>, now: Instant) {
+        self.tasks.insert(
+            name.to_string(),
+            WatchedTask {
+                state: TaskState::Running,
+                stall_after,
+                last_beat: now,
+                restarts: 0,
+                detail: None,
+            },
+        );
+    }
+
+    /// `name` was spawned again after a panic; its restart count stays.
+    pub fn restarted(&mut self, name: &str, now: Instant) {
+        if let Some(task) = self.tasks.get_mut(name) {
+            task.state = TaskState::Running;
+            task.last_beat = now;
+        }
+    }
+
+    /// A task that was aborted with its session is no longer watched. One
+    /// that died stays in the table for the bug report.
+    pub fn unregister(&mut self, name: &str) {
+        if let Some(task) = self.tasks.get(name)
+            && matches!(task.state, TaskState::Running | TaskState::Stalled)
+        {
+            self.tasks.remove(name);
+        }
+    }
+
+    pub fn beat(&mut self, name: &str, now: Instant) {
+        if let Some(task) = self.tasks.get_mut(name) {
+            task.last_beat = now;
+            if task.state == TaskState::Stalled {
+                task.state = TaskState::Running;
+            }
+        }
+    }
+
+    pub fn can_restart(&self, name: &str) -> bool {
+        self.tasks
+            .get(name)
+            .is_some_and(|task| task.restarts < MAX_TASK_RESTARTS)
+    }
+
+    /// Record that `name` ended; `panic` carries the panic message. Returns
+    /// the report to send.
+    pub fn finished(&mut self, name: &str, panic: Option<String>, restarting: bool) -> TaskStalled {
+        let state = if panic.is_some() {
+            TaskState::Panicked
+        } else {
+            TaskState::Exited
+        };
+        if let Some(task) = self.tasks.get_mut(name) {
+            task.state = state;
+            task.detail.clone_from(&panic);
+            if restarting {
+                task.restarts += 1;
+            }
+        }
+        TaskStalled {
+            component: name.to_string(),
+            state,
+            detail: panic,
+            restarting,
+        }
+    }
+
+    /// Tasks whose heartbeat is overdue, each reported once per stall.
+    pub fn check_stalls(&mut self, now: Instant) -> Vec<TaskStalled> {
+        let mut stalled = Vec::new();
+        for (name, task) in &mut self.tasks {
+            let Some(stall_after) = task.stall_after else {
+                continue;
+            };
+            if task.state == TaskState::Running
+                && now.saturating_duration_since(task.last_beat) > stall_after
+            {
+                task.state = TaskState::Stalled;
+                stalled.push(TaskStalled {
+                    component: name.clone(),
+                    state: TaskState::Stalled,
+                    detail: None,
+                    restarting: false,
+                });
+            }
+        }
+        stalled
+    }
+
+    pub fn snapshot(&self, now: Instant) -> Vec<TaskDiagnostic> {
+        self.tasks
+            .iter()
+            .map(|(name, task)| TaskDiagnostic {
+                name: name.clone(),
+                state: task.state,
+                restarts: task.restarts,
+                last_beat_age_ms: now.saturating_duration_since(task.last_beat).as_millis() as u64,
+                detail: task.detail.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overdue_heartbeats_are_reported_once_until_the_task_beats_again() {
+        let mut table = TaskTable::default();
+        let start = Instant::now();
+        table.register("event_loop", Some(EVENT_LOOP_STALL_AFTER), start);
+        table.register("wind", None, start);
+
+        let later = start + Duration::from_secs(5);
+        table.beat("event_loop", later);
+        assert!(
+            table
+                .check_stalls(later + EVENT_LOOP_STALL_AFTER)
+                .is_empty()
+        );
+
+        let overdue = later + EVENT_LOOP_STALL_AFTER + Duration::from_secs(1);
+        let stalled = table.check_stalls(overdue);
+        assert_eq!(stalled.len(), 1);
+        assert_eq!(stalled[0].component, "event_loop");
+        assert!(table.check_stalls(overdue).is_empty());
+
+        table.beat("event_loop", overdue);
+        let states: Vec<_> = table
+            .snapshot(overdue)
+            .into_iter()
+            .map(|task| task.state)
+            .collect();
+        assert_eq!(states, [TaskState::Running, TaskState::Running]);
+    }
+
+    #[test]
+    fn panics_keep_their_row_and_use_up_restarts() {
+        let mut table = TaskTable::default();
+        let now = Instant::now();
+        table.register("wind", None, now);
+        for _ in 0..MAX_TASK_RESTARTS {
+            assert!(table.can_restart("wind"));
+            let report = table.finished("wind", Some("index out of bounds".into()), true);
+            assert_eq!(report.state, TaskState::Panicked);
+            table.restarted("wind", now);
+        }
+        assert!(!table.can_restart("wind"));
+        table.finished("wind", Some("index out of bounds".into()), false);
+
+        // Aborting the session's tasks leaves the dead one for the report.
+        table.unregister("wind");
+        let rows = table.snapshot(now);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].restarts, MAX_TASK_RESTARTS);
+        assert_eq!(rows[0].detail.as_deref(), Some("index out of bounds"));
+
+        table.register("drain:udp", None, now);
+        table.unregister("drain:udp");
+        assert_eq!(table.snapshot(now).len(), 1);
+    }
+}
@@ -1,4 +1,13 @@
+use std::time::Duration;
+
+use crate::ipc::telemetry::TelemetryGroupFreshness;
+
 pub const DEFAULT_TELEMETRY_INTERVAL_MS: u64 = 200;
+/// Default for the `telemetry_stale_after_ms` setting: a telemetry group with
+/// no new data for this long is reported as stale.
+pub const DEFAULT_TELEMETRY_STALE_AFTER_MS: u32 = 3_000;
+/// HIGH_LATENCY2 reports arrive tens of seconds apart on satellite links.
+pub const HIGH_LATENCY_STALE_AFTER: Duration = Duration::from_secs(60);
 pub const MIN_TELEMETRY_RATE_HZ: u32 = 1;
 pub const MAX_TELEMETRY_RATE_HZ: u32 = 20;
 pub const TELEMETRY_RATE_RANGE_ERROR: &str = "rate_hz must be between 1 and 20";
@@ -95,6 +104,24 @@ pub fn telemetry_interval_ms_for_rate(rate_hz: u32) -> Result<u64, &'static str>
     Ok(1000 / u64::from(rate_hz))
 }
 
+/// Freshness of one telemetry group given the age of its newest sample.
+pub fn group_freshness(
+    newest_age: Option<Duration>,
+    now_usec: u64,
+    stale_after: Duration,
+) -> TelemetryGroupFreshness {
+    match newest_age {
+        Some(age) => TelemetryGroupFreshness {
+            last_update_usec: Some(now_usec.saturating_sub(age.as_micros() as u64)),
+            stale: age > stale_after,
+        },
+        None => TelemetryGroupFreshness {
+            last_update_usec: None,
+            stale: true,
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,4 +149,40 @@ mod tests {
             Err(TELEMETRY_RATE_RANGE_ERROR)
         );
     }
+
+    #[test]
+    fn group_freshness_marks_old_and_missing_groups_stale() {
+        let now_usec = 10_000_000;
+        let stale_after = Duration::from_secs(3);
+
+        assert_eq!(
+            group_freshness(
+                Some(Duration::from_millis(250)),
+                now_usec,
+                stale_after
+            ),
+            TelemetryGroupFreshness {
+                last_update_usec: Some(9_750_000),
+                stale: false,
+            }
+        );
+        assert_eq!(
+            group_freshness(
+                Some(Duration::from_secs(20)),
+                now_usec,
+                stale_after
+            ),
+            TelemetryGroupFreshness {
+                last_update_usec: Some(0),
+                stale: true,
+            }
+        );
+        assert_eq!(
+            group_freshness(None, now_usec, stale_after),
+            TelemetryGroupFreshness {
+                last_update_usec: None,
+                stale: true,
+            }
+        );
+    }
 }
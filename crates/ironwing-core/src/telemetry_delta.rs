@@ -0,0 +1,464 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use serde_json::{Map, Value};
+use web_time::Instant;
+
+use crate::ipc::{TelemetryDelta, TelemetryEncoding, TelemetrySnapshot};
+
+/// A full frame goes out at least this often, so a receiver that lost a
+/// frame is never wrong for longer.
+pub const TELEMETRY_KEYFRAME_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Synthetic field telling whether the snapshot has a `value` at all; every
+/// other field lives inside it.
+pub const VALUE_PRESENT_KEY: &str = "vp";
+
+/// One leaf of a serialized `TelemetrySnapshot`: its wire key, its JSON
+/// pointer, and how far a number may drift before it is resent (`0.0` sends
+/// every change).
+struct TelemetryField {
+    key: &'static str,
+    pointer: &'static str,
+    epsilon: f64,
+}
+
+const fn field(key: &'static str, pointer: &'static str, epsilon: f64) -> TelemetryField {
+    TelemetryField {
+        key,
+        pointer,
+        epsilon,
+    }
+}
+
+/// Wire keys are part of the contract: never reuse or renumber one, only add.
+const TELEMETRY_FIELDS: &[TelemetryField] = &[
+    field("av", "/available", 0.0),
+    field("co", "/complete", 0.0),
+    field("pv", "/provenance", 0.0),
+    field("f.alt", "/value/flight/altitude_m", 0.05),
+    field("f.spd", "/value/flight/speed_mps", 0.05),
+    field("f.clb", "/value/flight/climb_rate_mps", 0.05),
+    field("f.thr", "/value/flight/throttle_pct", 0.5),
+    field("f.as", "/value/flight/airspeed_mps", 0.05),
+    field("n.lat", "/value/navigation/latitude_deg", 1e-7),
+    field("n.lon", "/value/navigation/longitude_deg", 1e-7),
+    field("n.hdg", "/value/navigation/heading_deg", 0.1),
+    field("n.wpd", "/value/navigation/wp_dist_m", 0.1),
+    field("n.nbr", "/value/navigation/nav_bearing_deg", 0.1),
+    field("n.tbr", "/value/navigation/target_bearing_deg", 0.1),
+    field("n.xte", "/value/navigation/xtrack_error_m", 0.05),
+    field("a.r", "/value/attitude/roll_deg", 0.1),
+    field("a.p", "/value/attitude/pitch_deg", 0.1),
+    field("a.y", "/value/attitude/yaw_deg", 0.1),
+    field("p.pct", "/value/power/battery_pct", 0.5),
+    field("p.v", "/value/power/battery_voltage_v", 0.01),
+    field("p.a", "/value/power/battery_current_a", 0.05),
+    field("p.cells", "/value/power/battery_voltage_cells", 0.0),
+    field("p.wh", "/value/power/energy_consumed_wh", 0.01),
+    field("p.ttr", "/value/power/battery_time_remaining_s", 1.0),
+    field("g.fix", "/value/gps/fix_type", 0.0),
+    field("g.sat", "/value/gps/satellites", 0.0),
+    field("g.hdop", "/value/gps/hdop", 0.01),
+    field("t.th", "/value/terrain/terrain_height_m", 0.05),
+    field("t.hat", "/value/terrain/height_above_terrain_m", 0.05),
+    field("r.rc", "/value/radio/rc_channels", 0.0),
+    field("r.rssi", "/value/radio/rc_rssi", 0.0),
+    field("r.srv", "/value/radio/servo_outputs", 0.0),
+    field(
+        "fr.fl.t",
+        "/value/freshness/flight/last_update_usec",
+        500_000.0,
+    ),
+    field("fr.fl.s", "/value/freshness/flight/stale", 0.0),
+    field(
+        "fr.nv.t",
+        "/value/freshness/navigation/last_update_usec",
+        500_000.0,
+    ),
+    field("fr.nv.s", "/value/freshness/navigation/stale", 0.0),
+    field(
+        "fr.at.t",
+        "/value/freshness/attitude/last_update_usec",
+        500_000.0,
+    ),
+    field("fr.at.s", "/value/freshness/attitude/stale", 0.0),
+    field(
+        "fr.pw.t",
+        "/value/freshness/power/last_update_usec",
+        500_000.0,
+    ),
+    field("fr.pw.s", "/value/freshness/power/stale", 0.0),
+    field(
+        "fr.gp.t",
+        "/value/freshness/gps/last_update_usec",
+        500_000.0,
+    ),
+    field("fr.gp.s", "/value/freshness/gps/stale", 0.0),
+    field(
+        "fr.tr.t",
+        "/value/freshness/terrain/last_update_usec",
+        500_000.0,
+    ),
+    field("fr.tr.s", "/value/freshness/terrain/stale", 0.0),
+    field(
+        "fr.rd.t",
+        "/value/freshness/radio/last_update_usec",
+        500_000.0,
+    ),
+    field("fr.rd.s", "/value/freshness/radio/stale", 0.0),
+    field("d.ws", "/value/derived/wind_speed_mps", 0.1),
+    field("d.wd", "/value/derived/wind_direction_deg", 1.0),
+    field("d.wsrc", "/value/derived/wind_source", 0.0),
+    field("d.hd", "/value/derived/home_distance_m", 0.1),
+    field("d.hb", "/value/derived/home_bearing_deg", 0.1),
+    field("d.gr", "/value/derived/glide_ratio", 0.1),
+    field("d.epk", "/value/derived/energy_wh_per_km", 0.1),
+    field("d.rf", "/value/derived/rangefinder_height_m", 0.05),
+    field("d.rc", "/value/derived/rc_link", 0.0),
+    field("d.odo", "/value/derived/odometer_m", 0.1),
+    field("dv", "/value/display", 0.0),
+];
+
+/// Each wire key with the JSON pointer it fills in a serialized
+/// `TelemetrySnapshot`, for decoders outside this crate.
+pub fn telemetry_delta_fields() -> impl Iterator<Item = (&'static str, &'static str)> {
+    TELEMETRY_FIELDS
+        .iter()
+        .map(|field| (field.key, field.pointer))
+}
+
+fn flatten(snapshot: &TelemetrySnapshot) -> HashMap<&'static str, Value> {
+    let value = serde_json::to_value(snapshot).unwrap_or(Value::Null);
+    let present = value.get("value").is_some_and(|value| !value.is_null());
+    let mut fields: HashMap<&'static str, Value> = TELEMETRY_FIELDS
+        .iter()
+        .map(|field| {
+            let leaf = value.pointer(field.pointer).cloned().unwrap_or(Value::Null);
+            (field.key, leaf)
+        })
+        .collect();
+    fields.insert(VALUE_PRESENT_KEY, Value::Bool(present));
+    fields
+}
+
+fn moved(previous: Option<&Value>, current: &Value, epsilon: f64) -> bool {
+    let Some(previous) = previous else {
+        return true;
+    };
+    match (previous.as_f64(), current.as_f64()) {
+        (Some(previous), Some(current)) => (current - previous).abs() > epsilon,
+        _ => previous != current,
+    }
+}
+
+fn epsilon(key: &str) -> f64 {
+    TELEMETRY_FIELDS
+        .iter()
+        .find(|field| field.key == key)
+        .map_or(0.0, |field| field.epsilon)
+}
+
+/// What a telemetry tick sends under the selected encoding.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TelemetryFrame {
+    Full(TelemetrySnapshot),
+    Delta(TelemetryDelta),
+    /// Delta encoding and nothing moved: send nothing.
+    Unchanged,
+}
+
+/// Turns successive telemetry snapshots into `telemetry://delta` frames.
+/// Numbers are compared with what was last sent, not last seen, so slow
+/// drift still goes out once it passes the epsilon.
+#[derive(Debug, Default)]
+pub struct TelemetryDeltaEncoder {
+    encoding: TelemetryEncoding,
+    sent: HashMap<&'static str, Value>,
+    seq: u64,
+    last_keyframe: Option<Instant>,
+}
+
+impl TelemetryDeltaEncoder {
+    pub fn encoding(&self) -> TelemetryEncoding {
+        self.encoding
+    }
+
+    /// Switching (or re-selecting) the encoding always starts with a
+    /// keyframe, so a webview attaching calls this to get a full state.
+    pub fn set_encoding(&mut self, encoding: TelemetryEncoding) {
+        self.encoding = encoding;
+        self.request_keyframe();
+    }
+
+    pub fn request_keyframe(&mut self) {
+        self.last_keyframe = None;
+    }
+
+    pub fn frame(&mut self, snapshot: TelemetrySnapshot, now: Instant) -> TelemetryFrame {
+        match self.encoding {
+            TelemetryEncoding::Full => TelemetryFrame::Full(snapshot),
+            TelemetryEncoding::Delta => self
+                .encode(&snapshot, now)
+                .map_or(TelemetryFrame::Unchanged, TelemetryFrame::Delta),
+        }
+    }
+
+    /// The delta for `snapshot`, or `None` when nothing moved.
+    pub fn encode(&mut self, snapshot: &TelemetrySnapshot, now: Instant) -> Option<TelemetryDelta> {
+        let keyframe = self
+            .last_keyframe
+            .is_none_or(|last| now.saturating_duration_since(last) >= TELEMETRY_KEYFRAME_INTERVAL);
+        let current = flatten(snapshot);
+        let fields: BTreeMap<String, Value> = current
+            .iter()
+            .filter(|(key, value)| keyframe || moved(self.sent.get(*key), value, epsilon(key)))
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect();
+        if fields.is_empty() {
+            return None;
+        }
+        if keyframe {
+            self.sent = current;
+            self.last_keyframe = Some(now);
+        } else {
+            for key in fields.keys() {
+                if let Some((key, value)) = current.get_key_value(key.as_str()) {
+                    self.sent.insert(*key, value.clone());
+                }
+            }
+        }
+        self.seq = self.seq.wrapping_add(1);
+        Some(TelemetryDelta {
+            seq: self.seq,
+            keyframe,
+            fields,
+        })
+    }
+}
+
+/// The receiving side: fold `delta` into the fields held so far.
+pub fn apply_telemetry_delta(fields: &mut BTreeMap<String, Value>, delta: &TelemetryDelta) {
+    if delta.keyframe {
+        fields.clear();
+    }
+    fields.extend(
+        delta
+            .fields
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone())),
+    );
+}
+
+fn insert_at(root: &mut Value, pointer: &str, leaf: Value) {
+    let mut node = root;
+    let mut segments = pointer.trim_start_matches('/').split('/').peekable();
+    while let Some(segment) = segments.next() {
+        let Value::Object(object) = node else {
+            return;
+        };
+        if segments.peek().is_none() {
+            object.insert(segment.to_string(), leaf);
+            return;
+        }
+        node = object
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+}
+
+/// Rebuild the snapshot from received fields; `None` until a keyframe has
+/// been applied.
+pub fn telemetry_from_fields(fields: &BTreeMap<String, Value>) -> Option<TelemetrySnapshot> {
+    let present = fields.get(VALUE_PRESENT_KEY)?.as_bool()?;
+    let mut root = Value::Object(Map::new());
+    for field in TELEMETRY_FIELDS {
+        if !present && field.pointer.starts_with("/value/") {
+            continue;
+        }
+        let leaf = fields.get(field.key).cloned().unwrap_or(Value::Null);
+        insert_at(&mut root, field.pointer, leaf);
+    }
+    if !present {
+        insert_at(&mut root, "/value", Value::Null);
+    }
+    serde_json::from_value(root).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::telemetry::{TelemetryState, WindSource};
+    use crate::ipc::{DisplayValue, DomainProvenance, DomainValue};
+
+    fn snapshot(altitude_m: f64, latitude_deg: f64) -> TelemetrySnapshot {
+        let mut state = TelemetryState::default();
+        state.flight.altitude_m = Some(altitude_m);
+        state.flight.speed_mps = Some(12.0);
+        state.navigation.latitude_deg = Some(latitude_deg);
+        state.navigation.longitude_deg = Some(8.5456);
+        state.attitude.roll_deg = Some(1.5);
+        state.power.battery_pct = Some(84.0);
+        state.power.battery_voltage_cells = Some(vec![4.1, 4.1, 4.0]);
+        state.gps.fix_type = Some("fix_3d".into());
+        state.gps.satellites = Some(14);
+        state.radio.rc_channels = Some(vec![1500.0, 1500.0, 1000.0]);
+        state.freshness.flight.last_update_usec = Some(1_000_000);
+        state.derived.wind_source = Some(WindSource::Estimated);
+        state.display = Some(BTreeMap::from([(
+            "flight.altitude_m".into(),
+            DisplayValue {
+                value: Some(32.8),
+                unit: "ft".into(),
+            },
+        )]));
+        DomainValue::present(state, DomainProvenance::Stream)
+    }
+
+    fn leaves(value: &Value, pointer: String, out: &mut Vec<String>) {
+        match value {
+            Value::Object(object) if pointer != "/value/display" => {
+                for (key, child) in object {
+                    leaves(child, format!("{pointer}/{key}"), out);
+                }
+            }
+            _ => out.push(pointer),
+        }
+    }
+
+    #[test]
+    fn every_snapshot_field_has_a_key() {
+        let value = serde_json::to_value(snapshot(10.0, 47.0)).expect("serialize");
+        let mut pointers = Vec::new();
+        leaves(&value, String::new(), &mut pointers);
+        for pointer in pointers {
+            assert!(
+                TELEMETRY_FIELDS
+                    .iter()
+                    .any(|field| field.pointer == pointer),
+                "no wire key for {pointer}"
+            );
+        }
+        let mut keys: Vec<_> = TELEMETRY_FIELDS.iter().map(|field| field.key).collect();
+        keys.push(VALUE_PRESENT_KEY);
+        let count = keys.len();
+        keys.sort_unstable();
+        keys.dedup();
+        assert_eq!(keys.len(), count, "duplicate wire keys");
+    }
+
+    #[test]
+    fn only_fields_past_their_epsilon_are_sent() {
+        let start = Instant::now();
+        let mut encoder = TelemetryDeltaEncoder::default();
+        encoder.set_encoding(TelemetryEncoding::Delta);
+        let first = encoder
+            .encode(&snapshot(10.0, 47.0), start)
+            .expect("keyframe");
+        assert!(first.keyframe);
+        assert_eq!(first.fields.len(), TELEMETRY_FIELDS.len() + 1);
+
+        let tick = start + Duration::from_millis(200);
+        assert_eq!(encoder.encode(&snapshot(10.01, 47.0), tick), None);
+
+        let delta = encoder
+            .encode(
+                &snapshot(10.2, 47.000001),
+                tick + Duration::from_millis(200),
+            )
+            .expect("delta");
+        assert!(!delta.keyframe);
+        assert_eq!(delta.seq, 2);
+        assert_eq!(
+            delta.fields.keys().collect::<Vec<_>>(),
+            vec!["f.alt", "n.lat"]
+        );
+    }
+
+    #[test]
+    fn slow_drift_is_sent_once_it_adds_up() {
+        let start = Instant::now();
+        let mut encoder = TelemetryDeltaEncoder::default();
+        encoder.encode(&snapshot(10.0, 47.0), start);
+        let mut sent = false;
+        for step in 1..=10 {
+            let now = start + Duration::from_millis(100 * step);
+            let frame = encoder.encode(&snapshot(10.0 + 0.01 * step as f64, 47.0), now);
+            sent |= frame.is_some_and(|frame| frame.fields.contains_key("f.alt"));
+        }
+        assert!(sent, "0.1 m of drift in 0.01 m steps must be sent");
+    }
+
+    #[test]
+    fn keyframes_recur_and_follow_a_reattach() {
+        let start = Instant::now();
+        let mut encoder = TelemetryDeltaEncoder::default();
+        assert!(encoder.encode(&snapshot(10.0, 47.0), start).is_some());
+        let later = start + TELEMETRY_KEYFRAME_INTERVAL;
+        let frame = encoder
+            .encode(&snapshot(10.0, 47.0), later)
+            .expect("keyframe");
+        assert!(frame.keyframe);
+
+        let soon = later + Duration::from_millis(200);
+        assert_eq!(encoder.encode(&snapshot(10.0, 47.0), soon), None);
+        encoder.set_encoding(TelemetryEncoding::Delta);
+        let frame = encoder
+            .encode(&snapshot(10.0, 47.0), soon)
+            .expect("keyframe");
+        assert!(frame.keyframe);
+    }
+
+    #[test]
+    fn a_receiver_joining_at_a_keyframe_reconstructs_the_state() {
+        let start = Instant::now();
+        let mut encoder = TelemetryDeltaEncoder::default();
+        let mut received = BTreeMap::new();
+        let mut latest = snapshot(10.0, 47.0);
+        assert_eq!(telemetry_from_fields(&received), None);
+
+        for step in 0..40_u32 {
+            let now = start + Duration::from_millis(200 * u64::from(step));
+            latest = snapshot(10.0 + f64::from(step) * 0.3, 47.0 + f64::from(step) * 1e-5);
+            if let Some(state) = latest.value.as_mut() {
+                state.gps.satellites = Some(10 + u64::from(step % 3));
+                state.flight.speed_mps = (step % 7 != 0).then_some(12.0);
+            }
+            if step == 5 {
+                // A reconnecting webview starts over from an empty store.
+                received.clear();
+                encoder.request_keyframe();
+            }
+            if let Some(frame) = encoder.encode(&latest, now) {
+                apply_telemetry_delta(&mut received, &frame);
+            }
+            let rebuilt = telemetry_from_fields(&received).expect("rebuilt");
+            let (rebuilt, expected) = (
+                rebuilt.value.expect("value"),
+                latest.value.clone().expect("value"),
+            );
+            let altitude_error = (rebuilt.flight.altitude_m.expect("altitude")
+                - expected.flight.altitude_m.expect("altitude"))
+            .abs();
+            assert!(altitude_error <= 0.05, "altitude off by {altitude_error}");
+            assert_eq!(rebuilt.gps, expected.gps);
+            assert_eq!(rebuilt.flight.speed_mps, expected.flight.speed_mps);
+            assert_eq!(rebuilt.display, expected.display);
+            assert_eq!(rebuilt.derived.wind_source, expected.derived.wind_source);
+        }
+        assert_eq!(
+            telemetry_from_fields(&received).map(|rebuilt| rebuilt.provenance),
+            Some(latest.provenance)
+        );
+    }
+
+    #[test]
+    fn a_missing_value_round_trips_as_missing() {
+        let mut encoder = TelemetryDeltaEncoder::default();
+        let missing = TelemetrySnapshot::missing(DomainProvenance::Stream);
+        let frame = encoder.encode(&missing, Instant::now()).expect("keyframe");
+        let mut received = BTreeMap::new();
+        apply_telemetry_delta(&mut received, &frame);
+        assert_eq!(telemetry_from_fields(&received), Some(missing));
+    }
+}
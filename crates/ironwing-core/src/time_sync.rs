@@ -0,0 +1,272 @@
+use std::collections::VecDeque;
+
+use crate::dialect::{MavMessage, SYSTEM_TIME_DATA, TIMESYNC_DATA};
+use crate::ipc::{TimeSyncStatus, VehicleTime};
+
+pub const TIMESYNC_MESSAGE_ID: u32 = 111;
+
+/// Exchanges the offset is chosen from. A short window keeps the estimate
+/// following clock drift while still riding out a few delayed replies.
+const SAMPLE_WINDOW: usize = 16;
+/// Requests that may still be answered; older ones are forgotten.
+const PENDING_REQUESTS: usize = 8;
+/// Replies slower than this say more about the link than the clocks.
+const MAX_RTT_NS: i64 = 10_000_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TimeSyncSample {
+    offset_ns: i64,
+    rtt_ns: i64,
+}
+
+/// Maps GCS Unix time onto the vehicle's boot clock and, once SYSTEM_TIME
+/// carries it, the vehicle's UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VehicleClock {
+    /// GCS Unix time minus vehicle time since boot.
+    pub offset_us: i64,
+    /// Vehicle UTC at boot, in Unix microseconds.
+    pub utc_at_boot_us: Option<i64>,
+}
+
+impl VehicleClock {
+    /// `gcs_unix_us` on the vehicle's clocks, or `None` when it falls before
+    /// the vehicle booted.
+    pub fn vehicle_time(&self, gcs_unix_us: u64) -> Option<VehicleTime> {
+        let time_boot_us = u64::try_from((gcs_unix_us as i64).checked_sub(self.offset_us)?).ok()?;
+        Some(VehicleTime {
+            time_boot_us,
+            utc_usec: self
+                .utc_at_boot_us
+                .and_then(|utc_at_boot| u64::try_from(utc_at_boot + time_boot_us as i64).ok()),
+        })
+    }
+}
+
+/// Offset between the GCS and vehicle clocks from TIMESYNC round trips.
+///
+/// Each exchange bounds the offset by its round trip; the one with the
+/// shortest round trip in the recent window is the tightest, so its offset
+/// is used rather than an average that queueing delays would skew.
+#[derive(Debug, Clone, Default)]
+pub struct TimeSyncEstimator {
+    pending: VecDeque<i64>,
+    samples: VecDeque<TimeSyncSample>,
+    utc_at_boot_us: Option<i64>,
+}
+
+impl TimeSyncEstimator {
+    /// Remember a request sent with `ts1_ns`, so only replies to our own
+    /// requests (not another GCS's) become samples.
+    pub fn note_request(&mut self, ts1_ns: i64) {
+        self.pending.push_back(ts1_ns);
+        if self.pending.len() > PENDING_REQUESTS {
+            self.pending.pop_front();
+        }
+    }
+
+    /// Take a reply carrying the vehicle's `tc1_ns` and our echoed `ts1_ns`,
+    /// received at GCS time `now_ns`. Returns whether it became a sample.
+    pub fn observe_reply(&mut self, tc1_ns: i64, ts1_ns: i64, now_ns: i64) -> bool {
+        let Some(index) = self.pending.iter().position(|&sent| sent == ts1_ns) else {
+            return false;
+        };
+        self.pending.remove(index);
+        let rtt_ns = now_ns - ts1_ns;
+        if !(0..=MAX_RTT_NS).contains(&rtt_ns) {
+            return false;
+        }
+        // The vehicle stamped tc1 somewhere inside the round trip; assume the
+        // middle.
+        let offset_ns = ts1_ns + rtt_ns / 2 - tc1_ns;
+        self.samples.push_back(TimeSyncSample { offset_ns, rtt_ns });
+        if self.samples.len() > SAMPLE_WINDOW {
+            self.samples.pop_front();
+        }
+        true
+    }
+
+    /// Take SYSTEM_TIME. A zero `time_unix_usec` means the vehicle has no UTC
+    /// yet and leaves any earlier mapping in place.
+    pub fn observe_system_time(&mut self, time_unix_usec: u64, time_boot_ms: u32) {
+        if time_unix_usec == 0 {
+            return;
+        }
+        self.utc_at_boot_us = Some(time_unix_usec as i64 - i64::from(time_boot_ms) * 1000);
+    }
+
+    fn best_sample(&self) -> Option<TimeSyncSample> {
+        self.samples
+            .iter()
+            .min_by_key(|sample| sample.rtt_ns)
+            .copied()
+    }
+
+    pub fn clock(&self) -> Option<VehicleClock> {
+        let sample = self.best_sample()?;
+        Some(VehicleClock {
+            offset_us: sample.offset_ns / 1000,
+            utc_at_boot_us: self.utc_at_boot_us,
+        })
+    }
+
+    pub fn status(&self) -> TimeSyncStatus {
+        let sample = self.best_sample();
+        TimeSyncStatus {
+            offset_us: sample.map(|sample| sample.offset_ns / 1000),
+            rtt_us: sample.map(|sample| sample.rtt_ns / 1000),
+            utc_available: self.utc_at_boot_us.is_some(),
+        }
+    }
+}
+
+/// A TIMESYNC request stamped with the GCS time `now_ns`.
+pub fn timesync_request(now_ns: i64, target_system: u8, target_component: u8) -> MavMessage {
+    MavMessage::TIMESYNC(TIMESYNC_DATA {
+        tc1: 0,
+        ts1: now_ns,
+        target_system,
+        target_component,
+    })
+}
+
+/// The answer to a TIMESYNC request from `source`, or `None` when `message`
+/// is itself a reply.
+pub fn timesync_reply(
+    message: &TIMESYNC_DATA,
+    now_ns: i64,
+    source: (u8, u8),
+) -> Option<MavMessage> {
+    if message.tc1 != 0 {
+        return None;
+    }
+    Some(MavMessage::TIMESYNC(TIMESYNC_DATA {
+        tc1: now_ns,
+        ts1: message.ts1,
+        target_system: source.0,
+        target_component: source.1,
+    }))
+}
+
+/// SYSTEM_TIME carrying the vehicle's clocks at one GCS instant, written
+/// into recordings under the GCS's own ids so the tlog can be lined up with
+/// the vehicle's dataflash log. `time_unix_usec` stays 0 without UTC, as the
+/// vehicle itself sends it.
+pub fn system_time_annotation(time: VehicleTime) -> MavMessage {
+    MavMessage::SYSTEM_TIME(SYSTEM_TIME_DATA {
+        time_unix_usec: time.utc_usec.unwrap_or(0),
+        time_boot_ms: (time.time_boot_us / 1000) as u32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNIX_NS: i64 = 1_700_000_000_000_000_000;
+
+    /// One exchange against a vehicle whose boot clock reads `gcs - offset`,
+    /// with the given one-way delays.
+    fn exchange(
+        estimator: &mut TimeSyncEstimator,
+        offset_ns: i64,
+        sent_ns: i64,
+        uplink_ns: i64,
+        downlink_ns: i64,
+    ) -> bool {
+        estimator.note_request(sent_ns);
+        let tc1 = sent_ns + uplink_ns - offset_ns;
+        estimator.observe_reply(tc1, sent_ns, sent_ns + uplink_ns + downlink_ns)
+    }
+
+    #[test]
+    fn symmetric_exchange_recovers_the_offset() {
+        let offset_ns = UNIX_NS - 42_000_000_000;
+        let mut estimator = TimeSyncEstimator::default();
+        assert!(exchange(
+            &mut estimator,
+            offset_ns,
+            UNIX_NS,
+            15_000_000,
+            15_000_000
+        ));
+
+        let status = estimator.status();
+        assert_eq!(status.offset_us, Some(offset_ns / 1000));
+        assert_eq!(status.rtt_us, Some(30_000));
+        assert!(!status.utc_available);
+    }
+
+    #[test]
+    fn min_rtt_sample_wins_over_queued_replies() {
+        let offset_ns = UNIX_NS - 5_000_000_000;
+        let mut estimator = TimeSyncEstimator::default();
+        // Replies held up on the downlink skew their offsets by half the
+        // extra delay; the one quick exchange must set the estimate.
+        exchange(&mut estimator, offset_ns, UNIX_NS, 10_000_000, 400_000_000);
+        exchange(
+            &mut estimator,
+            offset_ns,
+            UNIX_NS + 1_000_000_000,
+            10_000_000,
+            10_000_000,
+        );
+        exchange(
+            &mut estimator,
+            offset_ns,
+            UNIX_NS + 2_000_000_000,
+            10_000_000,
+            250_000_000,
+        );
+
+        let status = estimator.status();
+        assert_eq!(status.offset_us, Some(offset_ns / 1000));
+        assert_eq!(status.rtt_us, Some(20_000));
+    }
+
+    #[test]
+    fn replies_to_other_requests_are_ignored() {
+        let mut estimator = TimeSyncEstimator::default();
+        estimator.note_request(UNIX_NS);
+        // Another GCS's request echoed back, and a reply far too slow.
+        assert!(!estimator.observe_reply(1_000, UNIX_NS - 7, UNIX_NS + 1_000));
+        estimator.note_request(UNIX_NS + 1);
+        assert!(!estimator.observe_reply(1_000, UNIX_NS + 1, UNIX_NS + 1 + MAX_RTT_NS + 1));
+        assert_eq!(estimator.status().offset_us, None);
+        assert_eq!(estimator.clock(), None);
+    }
+
+    #[test]
+    fn clock_maps_gcs_time_to_boot_and_utc() {
+        let offset_ns = UNIX_NS - 60_000_000_000;
+        let mut estimator = TimeSyncEstimator::default();
+        exchange(&mut estimator, offset_ns, UNIX_NS, 5_000_000, 5_000_000);
+        // The vehicle's GPS clock runs 2 s behind the GCS.
+        let vehicle_utc_us = UNIX_NS / 1000 - 2_000_000;
+        estimator.observe_system_time(0, 60_000);
+        assert!(!estimator.status().utc_available);
+        estimator.observe_system_time(vehicle_utc_us as u64, 60_000);
+
+        let clock = estimator.clock().expect("clock");
+        let time = clock.vehicle_time((UNIX_NS / 1000) as u64).expect("time");
+        assert_eq!(time.time_boot_us, 60_000_000);
+        assert_eq!(time.utc_usec, Some(vehicle_utc_us as u64));
+        assert_eq!(clock.vehicle_time((offset_ns / 1000 - 1) as u64), None);
+    }
+
+    #[test]
+    fn requests_are_answered_and_replies_are_not() {
+        let request = TIMESYNC_DATA {
+            tc1: 0,
+            ts1: 123,
+            target_system: 255,
+            target_component: 190,
+        };
+        let Some(MavMessage::TIMESYNC(reply)) = timesync_reply(&request, 999, (1, 1)) else {
+            panic!("expected a reply");
+        };
+        assert_eq!((reply.tc1, reply.ts1), (999, 123));
+        assert_eq!((reply.target_system, reply.target_component), (1, 1));
+        assert!(timesync_reply(&reply, 1_000, (1, 1)).is_none());
+    }
+}
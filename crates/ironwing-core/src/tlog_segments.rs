@@ -0,0 +1,367 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use web_time::Instant;
+
+use crate::ipc::{TlogFsyncPolicy, TlogRotation};
+
+/// Suffix of the sidecar listing the segments of a rotated recording.
+pub const TLOG_INDEX_SUFFIX: &str = ".tlog-index.json";
+const TLOG_INDEX_VERSION: u32 = 1;
+const SYNC_INTERVAL: Duration = Duration::from_secs(1);
+const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// One segment file of a rotated recording. Times are the tlog frame
+/// timestamps of its first and last frames.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TlogSegment {
+    pub file_name: String,
+    pub start_usec: Option<u64>,
+    pub end_usec: Option<u64>,
+    pub bytes: u64,
+    pub frames: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TlogSegmentIndex {
+    pub version: u32,
+    pub segments: Vec<TlogSegment>,
+}
+
+/// A limit of zero would start a new segment for every frame.
+pub fn validate_tlog_rotation(rotation: &TlogRotation) -> Result<(), String> {
+    if rotation.max_segment_minutes == Some(0) || rotation.max_segment_mb == Some(0) {
+        return Err("tlog segment limits must be greater than zero".to_string());
+    }
+    Ok(())
+}
+
+/// The rotation to record with, `None` when `rotation` sets no limit.
+pub fn active_rotation(rotation: TlogRotation) -> Option<TlogRotation> {
+    (rotation != TlogRotation::default()).then_some(rotation)
+}
+
+pub fn is_tlog_index(path: &str) -> bool {
+    path.to_ascii_lowercase().ends_with(TLOG_INDEX_SUFFIX)
+}
+
+/// `flight.tlog` → `flight.tlog-index.json`, next to it.
+pub fn tlog_index_path(destination: &Path) -> PathBuf {
+    destination.with_file_name(format!("{}{TLOG_INDEX_SUFFIX}", file_stem(destination)))
+}
+
+pub fn read_tlog_index(bytes: &[u8]) -> Result<TlogSegmentIndex, String> {
+    let index: TlogSegmentIndex =
+        serde_json::from_slice(bytes).map_err(|error| format!("invalid tlog index: {error}"))?;
+    if index.version != TLOG_INDEX_VERSION {
+        return Err(format!("unsupported tlog index version {}", index.version));
+    }
+    Ok(index)
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "recording".to_string())
+}
+
+fn segment_file_name(stem: &str, number: usize) -> String {
+    format!("{stem}-{number:03}.tlog")
+}
+
+/// The timestamp that starts every tlog frame.
+fn frame_timestamp(frame: &[u8]) -> Option<u64> {
+    Some(u64::from_le_bytes(frame.get(..8)?.try_into().ok()?))
+}
+
+/// Writes whole tlog frames, starting a new segment file between frames when
+/// the current one reaches a [`TlogRotation`] limit. Without rotation the
+/// recording is the single file it was created at.
+pub struct SegmentedTlogWriter {
+    destination: PathBuf,
+    rotation: Option<TlogRotation>,
+    fsync: TlogFsyncPolicy,
+    file: BufWriter<File>,
+    index: TlogSegmentIndex,
+    last_sync: Instant,
+}
+
+impl SegmentedTlogWriter {
+    pub fn create(
+        destination: &Path,
+        rotation: Option<TlogRotation>,
+        fsync: TlogFsyncPolicy,
+    ) -> io::Result<Self> {
+        let file_name = match rotation {
+            Some(_) => segment_file_name(&file_stem(destination), 1),
+            None => destination
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        };
+        let mut writer = Self {
+            destination: destination.to_path_buf(),
+            rotation,
+            fsync,
+            file: BufWriter::new(File::create(destination.with_file_name(&file_name))?),
+            index: TlogSegmentIndex {
+                version: TLOG_INDEX_VERSION,
+                segments: Vec::new(),
+            },
+            last_sync: Instant::now(),
+        };
+        writer.index.segments.push(Self::empty_segment(file_name));
+        writer.write_index()?;
+        Ok(writer)
+    }
+
+    fn empty_segment(file_name: String) -> TlogSegment {
+        TlogSegment {
+            file_name,
+            start_usec: None,
+            end_usec: None,
+            bytes: 0,
+            frames: 0,
+        }
+    }
+
+    /// What to open to read the recording back: the index when rotating,
+    /// otherwise the tlog itself.
+    pub fn path(&self) -> PathBuf {
+        match self.rotation {
+            Some(_) => tlog_index_path(&self.destination),
+            None => self.destination.clone(),
+        }
+    }
+
+    fn current(&mut self) -> &mut TlogSegment {
+        self.index
+            .segments
+            .last_mut()
+            .expect("a segment is always open")
+    }
+
+    fn should_rotate(&self, frame_len: usize, timestamp: Option<u64>) -> bool {
+        let (Some(rotation), Some(segment)) = (self.rotation, self.index.segments.last()) else {
+            return false;
+        };
+        if segment.frames == 0 {
+            return false;
+        }
+        let too_big = rotation
+            .max_segment_mb
+            .is_some_and(|mb| segment.bytes + frame_len as u64 > u64::from(mb) * BYTES_PER_MB);
+        let too_long = rotation.max_segment_minutes.is_some_and(|minutes| {
+            match (segment.start_usec, timestamp) {
+                (Some(start), Some(now)) => {
+                    now.saturating_sub(start) >= u64::from(minutes) * 60_000_000
+                }
+                _ => false,
+            }
+        });
+        too_big || too_long
+    }
+
+    /// Close the current segment durably and open the next. Called only
+    /// between frames, so no frame is split across files.
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        self.file.get_ref().sync_all()?;
+        let file_name =
+            segment_file_name(&file_stem(&self.destination), self.index.segments.len() + 1);
+        self.file = BufWriter::new(File::create(self.destination.with_file_name(&file_name))?);
+        self.index.segments.push(Self::empty_segment(file_name));
+        self.write_index()
+    }
+
+    /// Append one frame: the 8-byte timestamp followed by the MAVLink
+    /// packet.
+    pub fn write_frame(&mut self, frame: &[u8]) -> io::Result<()> {
+        let timestamp = frame_timestamp(frame);
+        if self.should_rotate(frame.len(), timestamp) {
+            self.rotate()?;
+        }
+        self.file.write_all(frame)?;
+        let segment = self.current();
+        segment.bytes += frame.len() as u64;
+        segment.frames += 1;
+        if let Some(timestamp) = timestamp {
+            segment.start_usec.get_or_insert(timestamp);
+            segment.end_usec = Some(timestamp);
+        }
+        match self.fsync {
+            TlogFsyncPolicy::OsDefault => Ok(()),
+            TlogFsyncPolicy::PerWrite => self.sync(),
+            TlogFsyncPolicy::PerSecond if self.last_sync.elapsed() >= SYNC_INTERVAL => self.sync(),
+            TlogFsyncPolicy::PerSecond => Ok(()),
+        }
+    }
+
+    fn sync(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        self.file.get_ref().sync_data()?;
+        self.last_sync = Instant::now();
+        Ok(())
+    }
+
+    /// The index is rewritten whole through a temporary file, so a crash
+    /// leaves either the old or the new list.
+    fn write_index(&self) -> io::Result<()> {
+        if self.rotation.is_none() {
+            return Ok(());
+        }
+        let path = self.path();
+        let partial = path.with_extension("part");
+        let bytes = serde_json::to_vec_pretty(&self.index).map_err(io::Error::other)?;
+        std::fs::write(&partial, bytes)?;
+        std::fs::rename(&partial, &path)
+    }
+
+    /// Flush the last segment and record final time ranges in the index.
+    pub fn finish(mut self) -> io::Result<TlogSegmentIndex> {
+        self.file.flush()?;
+        if self.fsync != TlogFsyncPolicy::OsDefault {
+            self.file.get_ref().sync_all()?;
+        }
+        self.write_index()?;
+        Ok(self.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dialect::{HEARTBEAT_DATA, MavMessage};
+    use crate::log_engine::parse_tlog_chunk;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "ironwing-tlog-segments-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// A heartbeat frame carrying `sequence` as its custom mode. The last
+    /// payload byte is set so every frame has the same length.
+    fn frame(timestamp_usec: u64, sequence: u32) -> Vec<u8> {
+        let mut bytes = timestamp_usec.to_le_bytes().to_vec();
+        let heartbeat = MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+            custom_mode: sequence,
+            mavlink_version: 3,
+            ..Default::default()
+        });
+        mavlink::write_v2_msg(&mut bytes, mavlink::MavHeader::default(), &heartbeat).unwrap();
+        bytes
+    }
+
+    /// Custom modes of every frame in the recording, reading the segments in
+    /// index order.
+    fn read_back(dir: &Path, index: &TlogSegmentIndex) -> Vec<u32> {
+        index
+            .segments
+            .iter()
+            .flat_map(|segment| {
+                let bytes = std::fs::read(dir.join(&segment.file_name)).unwrap();
+                let chunk = parse_tlog_chunk(&bytes, 0).unwrap();
+                assert!(!chunk.truncated, "{} ends mid-frame", segment.file_name);
+                assert_eq!(chunk.entries.len() as u64, segment.frames);
+                chunk.entries
+            })
+            .map(|entry| entry.fields["custom_mode"] as u32)
+            .collect()
+    }
+
+    #[test]
+    fn rotation_by_size_hands_off_between_frames_without_losing_any() {
+        let dir = temp_dir("size");
+        let frame_len = frame(0, 0).len() as u64;
+        let rotation = TlogRotation {
+            max_segment_minutes: None,
+            max_segment_mb: Some(1),
+        };
+        let mut writer = SegmentedTlogWriter::create(
+            &dir.join("flight.tlog"),
+            Some(rotation),
+            TlogFsyncPolicy::OsDefault,
+        )
+        .unwrap();
+        assert_eq!(writer.path(), dir.join("flight.tlog-index.json"));
+
+        let count = (2 * BYTES_PER_MB / frame_len + 10) as u32;
+        for sequence in 0..count {
+            writer
+                .write_frame(&frame(u64::from(sequence) * 1_000, sequence))
+                .unwrap();
+        }
+        let index = writer.finish().unwrap();
+
+        assert_eq!(index.segments.len(), 3);
+        assert_eq!(index.segments[1].file_name, "flight-002.tlog");
+        assert!(
+            index
+                .segments
+                .iter()
+                .all(|segment| segment.bytes <= BYTES_PER_MB)
+        );
+        assert_eq!(read_back(&dir, &index), (0..count).collect::<Vec<_>>());
+        assert_eq!(index.segments[0].start_usec, Some(0));
+        assert_eq!(
+            index.segments[1].start_usec,
+            index.segments[0].end_usec.map(|end| end + 1_000)
+        );
+
+        let saved = read_tlog_index(&std::fs::read(dir.join("flight.tlog-index.json")).unwrap());
+        assert_eq!(saved, Ok(index));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rotation_by_duration_follows_frame_timestamps() {
+        let dir = temp_dir("duration");
+        let rotation = TlogRotation {
+            max_segment_minutes: Some(1),
+            max_segment_mb: None,
+        };
+        let mut writer =
+            SegmentedTlogWriter::create(&dir.join("f.tlog"), Some(rotation), Default::default())
+                .unwrap();
+        // One frame every 20 s for 3 minutes.
+        for sequence in 0..10 {
+            writer
+                .write_frame(&frame(u64::from(sequence) * 20_000_000, sequence))
+                .unwrap();
+        }
+        let index = writer.finish().unwrap();
+        let frames: Vec<_> = index
+            .segments
+            .iter()
+            .map(|segment| segment.frames)
+            .collect();
+        assert_eq!(frames, vec![3, 3, 3, 1]);
+        assert_eq!(read_back(&dir, &index), (0..10).collect::<Vec<_>>());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn without_rotation_there_is_one_file_and_no_index() {
+        let dir = temp_dir("single");
+        let mut writer =
+            SegmentedTlogWriter::create(&dir.join("f.tlog"), None, TlogFsyncPolicy::PerWrite)
+                .unwrap();
+        for sequence in 0..5 {
+            writer.write_frame(&frame(0, sequence)).unwrap();
+        }
+        assert_eq!(writer.path(), dir.join("f.tlog"));
+        let index = writer.finish().unwrap();
+        assert_eq!(index.segments[0].file_name, "f.tlog");
+        assert_eq!(read_back(&dir, &index), (0..5).collect::<Vec<_>>());
+        assert!(!dir.join("f.tlog-index.json").exists());
+        assert!(is_tlog_index("/x/F.TLOG-INDEX.JSON"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,105 @@
+use std::fmt::Write as _;
+
+use crate::ipc::TrackPoint;
+
+/// The track as a GeoJSON FeatureCollection holding one LineString, with
+/// MSL altitude as the third coordinate and the sample times alongside.
+pub fn track_geojson(points: &[TrackPoint]) -> serde_json::Value {
+    let coordinates: Vec<[f64; 3]> = points
+        .iter()
+        .map(|point| {
+            [
+                point.longitude_deg,
+                point.latitude_deg,
+                point.altitude_msl_m,
+            ]
+        })
+        .collect();
+    let times: Vec<u64> = points.iter().map(|point| point.timestamp_usec).collect();
+    serde_json::json!({
+        "type": "FeatureCollection",
+        "features": [{
+            "type": "Feature",
+            "geometry": {
+                "type": "LineString",
+                "coordinates": coordinates,
+            },
+            "properties": {
+                "name": "Flight track",
+                "timestamps_usec": times,
+            },
+        }],
+    })
+}
+
+/// The track as a KML document with one absolute-altitude LineString.
+pub fn track_kml(points: &[TrackPoint]) -> String {
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<kml xmlns=\"http://www.opengis.net/kml/2.2\">\n",
+        "<Document>\n",
+        "<name>Flight track</name>\n",
+        "<Placemark>\n",
+        "<name>Flight track</name>\n",
+        "<LineString>\n",
+        "<altitudeMode>absolute</altitudeMode>\n",
+        "<coordinates>\n",
+    ));
+    for point in points {
+        let _ = writeln!(
+            out,
+            "{},{},{}",
+            point.longitude_deg, point.latitude_deg, point.altitude_msl_m
+        );
+    }
+    out.push_str("</coordinates>\n</LineString>\n</Placemark>\n</Document>\n</kml>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points() -> Vec<TrackPoint> {
+        vec![
+            TrackPoint {
+                timestamp_usec: 1_000_000,
+                latitude_deg: 47.5,
+                longitude_deg: 8.25,
+                altitude_msl_m: 420.0,
+                relative_alt_m: 0.0,
+                heading_deg: None,
+            },
+            TrackPoint {
+                timestamp_usec: 2_000_000,
+                latitude_deg: 47.501,
+                longitude_deg: 8.251,
+                altitude_msl_m: 450.5,
+                relative_alt_m: 30.5,
+                heading_deg: Some(90.0),
+            },
+        ]
+    }
+
+    #[test]
+    fn geojson_uses_lon_lat_alt_order() {
+        let geojson = track_geojson(&points());
+        let feature = &geojson["features"][0];
+        assert_eq!(feature["geometry"]["type"], "LineString");
+        assert_eq!(
+            feature["geometry"]["coordinates"][1],
+            serde_json::json!([8.251, 47.501, 450.5])
+        );
+        assert_eq!(
+            feature["properties"]["timestamps_usec"],
+            serde_json::json!([1_000_000, 2_000_000])
+        );
+    }
+
+    #[test]
+    fn kml_lists_one_coordinate_per_point() {
+        let kml = track_kml(&points());
+        assert!(kml.contains("8.25,47.5,420\n8.251,47.501,450.5\n"));
+        assert!(kml.ends_with("</kml>\n"));
+    }
+}
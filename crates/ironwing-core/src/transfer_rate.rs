@@ -0,0 +1,194 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use mavkit::{MissionType, TransferDirection, TransferPhase, TransferProgress};
+use web_time::Instant;
+
+use crate::ipc::{OperationId, TransferOutcome};
+
+/// Span of progress samples the item rate is averaged over.
+pub const TRANSFER_RATE_WINDOW: Duration = Duration::from_secs(5);
+
+/// A transfer counts as stalled once no item has completed for this many
+/// times the interval its current rate predicts.
+pub const TRANSFER_STALL_FACTOR: f64 = 3.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransferRateEstimate {
+    pub items_per_sec: Option<f64>,
+    pub eta_secs: Option<f64>,
+    pub stalled: bool,
+}
+
+/// Sliding-window item rate, ETA and stall detection for a mission transfer.
+#[derive(Debug, Default)]
+pub struct TransferRateTracker {
+    /// (time, completed items) each time the completed count advanced.
+    samples: VecDeque<(Instant, u32)>,
+}
+
+impl TransferRateTracker {
+    pub fn observe(&mut self, completed_items: u32, now: Instant) {
+        if self
+            .samples
+            .back()
+            .is_some_and(|&(_, last)| last == completed_items)
+        {
+            return;
+        }
+        self.samples.push_back((now, completed_items));
+        // Keep one sample older than the window so the rate spans all of it.
+        while self.samples.len() > 2
+            && self
+                .samples
+                .get(1)
+                .is_some_and(|&(at, _)| now.duration_since(at) >= TRANSFER_RATE_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn estimate(
+        &self,
+        completed_items: u32,
+        total_items: u32,
+        now: Instant,
+    ) -> TransferRateEstimate {
+        let (Some(&(first_at, first)), Some(&(last_at, last))) =
+            (self.samples.front(), self.samples.back())
+        else {
+            return TransferRateEstimate {
+                items_per_sec: None,
+                eta_secs: None,
+                stalled: false,
+            };
+        };
+        let span = last_at.duration_since(first_at).as_secs_f64();
+        let items_per_sec = (last > first && span > 0.0).then(|| f64::from(last - first) / span);
+        let eta_secs =
+            items_per_sec.map(|rate| f64::from(total_items.saturating_sub(completed_items)) / rate);
+        let stalled = items_per_sec.is_some_and(|rate| {
+            completed_items < total_items
+                && now.duration_since(last_at).as_secs_f64() > TRANSFER_STALL_FACTOR / rate
+        });
+        TransferRateEstimate {
+            items_per_sec,
+            eta_secs,
+            stalled,
+        }
+    }
+}
+
+/// The progress a transfer's terminal event carries: the last report with
+/// its phase set to the outcome, or an empty one for a transfer that ended
+/// before reporting anything.
+pub fn terminal_progress(
+    operation_id: OperationId,
+    last: Option<TransferProgress>,
+    outcome: TransferOutcome,
+) -> Option<TransferProgress> {
+    let phase = match outcome {
+        TransferOutcome::Completed => TransferPhase::Completed,
+        TransferOutcome::Failed => TransferPhase::Failed,
+        TransferOutcome::Cancelled => TransferPhase::Cancelled,
+    };
+    if let Some(last) = last {
+        return Some(TransferProgress { phase, ..last });
+    }
+    let (mission_type, direction) = match operation_id {
+        OperationId::MissionUpload | OperationId::MissionClear => {
+            (MissionType::Mission, TransferDirection::Upload)
+        }
+        OperationId::MissionDownload => (MissionType::Mission, TransferDirection::Download),
+        OperationId::FenceUpload | OperationId::FenceClear => {
+            (MissionType::Fence, TransferDirection::Upload)
+        }
+        OperationId::FenceDownload => (MissionType::Fence, TransferDirection::Download),
+        OperationId::RallyUpload | OperationId::RallyClear => {
+            (MissionType::Rally, TransferDirection::Upload)
+        }
+        OperationId::RallyDownload => (MissionType::Rally, TransferDirection::Download),
+        _ => return None,
+    };
+    Some(TransferProgress {
+        direction,
+        mission_type,
+        phase,
+        completed_items: 0,
+        total_items: 0,
+        retries_used: 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_rate_and_eta_from_acked_items() {
+        let start = Instant::now();
+        let mut tracker = TransferRateTracker::default();
+        for item in 0..=4 {
+            tracker.observe(item, start + Duration::from_millis(500 * u64::from(item)));
+        }
+
+        let now = start + Duration::from_secs(2);
+        let estimate = tracker.estimate(4, 10, now);
+
+        assert_eq!(estimate.items_per_sec, Some(2.0));
+        assert_eq!(estimate.eta_secs, Some(3.0));
+        assert!(!estimate.stalled);
+    }
+
+    #[test]
+    fn flags_a_stall_after_three_expected_intervals() {
+        let start = Instant::now();
+        let mut tracker = TransferRateTracker::default();
+        tracker.observe(0, start);
+        tracker.observe(1, start + Duration::from_secs(1));
+
+        let last_ack = start + Duration::from_secs(1);
+        assert!(
+            !tracker
+                .estimate(1, 5, last_ack + Duration::from_secs(2))
+                .stalled
+        );
+        assert!(
+            tracker
+                .estimate(1, 5, last_ack + Duration::from_secs(4))
+                .stalled
+        );
+        assert!(
+            !tracker
+                .estimate(5, 5, last_ack + Duration::from_secs(4))
+                .stalled
+        );
+        assert!(!TransferRateTracker::default().estimate(0, 5, start).stalled);
+    }
+
+    #[test]
+    fn a_transfer_that_never_reported_still_gets_a_terminal_progress() {
+        let progress = terminal_progress(OperationId::FenceDownload, None, TransferOutcome::Failed)
+            .expect("plan transfer");
+        assert_eq!(progress.mission_type, MissionType::Fence);
+        assert_eq!(progress.direction, TransferDirection::Download);
+        assert_eq!(progress.phase, TransferPhase::Failed);
+
+        let cancelled = terminal_progress(
+            OperationId::MissionUpload,
+            Some(TransferProgress {
+                completed_items: 3,
+                total_items: 9,
+                ..progress
+            }),
+            TransferOutcome::Cancelled,
+        )
+        .expect("plan transfer");
+        assert_eq!(cancelled.completed_items, 3);
+        assert_eq!(cancelled.phase, TransferPhase::Cancelled);
+
+        assert!(
+            terminal_progress(OperationId::ConnectLink, None, TransferOutcome::Completed).is_none()
+        );
+    }
+}
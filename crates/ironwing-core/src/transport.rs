@@ -39,6 +39,14 @@ pub enum TransportDescriptor {
         discovery_error: Option<&'static str>,
         validation: AddressValidation,
     },
+    UsbSerial {
+        label: &'static str,
+        available: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        discovery_error: Option<&'static str>,
+        validation: UsbSerialValidation,
+        default_baud: u32,
+    },
     #[serde(rename = "websocket")]
     WebSocket {
         label: &'static str,
@@ -103,6 +111,7 @@ pub struct NativeTransportOptions {
     pub serial: Option<TransportAvailability>,
     pub bluetooth_ble: TransportAvailability,
     pub bluetooth_spp: Option<TransportAvailability>,
+    pub usb_serial: Option<TransportAvailability>,
 }
 
 impl NativeTransportOptions {
@@ -113,6 +122,7 @@ impl NativeTransportOptions {
             serial: Some(TransportAvailability::available()),
             bluetooth_ble: TransportAvailability::available(),
             bluetooth_spp: None,
+            usb_serial: None,
         }
     }
 
@@ -123,6 +133,7 @@ impl NativeTransportOptions {
             serial: None,
             bluetooth_ble: TransportAvailability::available(),
             bluetooth_spp: Some(TransportAvailability::available()),
+            usb_serial: Some(TransportAvailability::available()),
         }
     }
 }
@@ -153,6 +164,13 @@ pub struct SerialValidation {
     pub baud_required: bool,
 }
 
+#[cfg_attr(feature = "typescript", derive(specta::Type))]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct UsbSerialValidation {
+    pub device_required: bool,
+    pub baud_required: bool,
+}
+
 #[cfg_attr(feature = "typescript", derive(specta::Type))]
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct AddressValidation {
@@ -248,6 +266,19 @@ impl TransportDescriptor {
         }
     }
 
+    pub fn usb_serial(availability: TransportAvailability) -> Self {
+        Self::UsbSerial {
+            label: "USB OTG serial",
+            available: availability.available,
+            discovery_error: availability.discovery_error,
+            validation: UsbSerialValidation {
+                device_required: true,
+                baud_required: true,
+            },
+            default_baud: DEFAULT_SERIAL_BAUD,
+        }
+    }
+
     pub fn websocket(availability: TransportAvailability) -> Self {
         Self::WebSocket {
             label: "WebSocket",
@@ -309,6 +340,10 @@ pub fn native_transport_descriptors(options: NativeTransportOptions) -> Vec<Tran
         transports.push(TransportDescriptor::bluetooth_spp(bluetooth_spp));
     }
 
+    if let Some(usb_serial) = options.usb_serial {
+        transports.push(TransportDescriptor::usb_serial(usb_serial));
+    }
+
     transports
 }
 
@@ -354,6 +389,34 @@ mod tests {
         assert!(value[0].get("discovery_error").is_none());
     }
 
+    #[test]
+    fn native_android_transport_descriptors_offer_usb_serial_instead_of_serial() {
+        let value = serde_json::to_value(native_transport_descriptors(
+            NativeTransportOptions::android(),
+        ))
+        .expect("serialize native transports");
+        let kinds: Vec<_> = value
+            .as_array()
+            .expect("transport list")
+            .iter()
+            .map(|transport| transport["kind"].as_str().expect("kind"))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            [
+                "udp",
+                "tcp",
+                "demo",
+                "bluetooth_ble",
+                "bluetooth_spp",
+                "usb_serial"
+            ]
+        );
+        assert_eq!(value[5]["default_baud"], DEFAULT_SERIAL_BAUD);
+        assert_eq!(value[5]["validation"]["device_required"], true);
+    }
+
     #[test]
     fn web_transport_descriptors_carry_discovery_errors_when_unavailable() {
         let value = serde_json::to_value(web_transport_descriptors(WebTransportOptions {
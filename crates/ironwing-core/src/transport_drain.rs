@@ -0,0 +1,146 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// First pause before retrying a failed transport write; doubled per
+/// consecutive failure up to `DRAIN_RETRY_MAX_BACKOFF`.
+pub const DRAIN_RETRY_INITIAL_BACKOFF: Duration = Duration::from_millis(25);
+pub const DRAIN_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Consecutive failed writes after which the transport is declared dead,
+/// roughly five seconds of retrying with the backoff above.
+pub const DRAIN_MAX_CONSECUTIVE_FAILURES: u32 = 10;
+
+/// Backoff for the outgoing drain of a byte-stream transport. A BLE or SPP
+/// write can fail while the radio is busy; only a run of failures means the
+/// link is gone.
+#[derive(Debug, Default)]
+pub struct DrainRetry {
+    consecutive_failures: u32,
+}
+
+impl DrainRetry {
+    pub fn succeeded(&mut self) {
+        self.consecutive_failures = 0;
+    }
+
+    /// How long to wait before retrying, or `None` once the failures are
+    /// persistent.
+    pub fn failed(&mut self) -> Option<Duration> {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= DRAIN_MAX_CONSECUTIVE_FAILURES {
+            return None;
+        }
+        let doublings = (self.consecutive_failures - 1).min(16);
+        Some((DRAIN_RETRY_INITIAL_BACKOFF * 2u32.pow(doublings)).min(DRAIN_RETRY_MAX_BACKOFF))
+    }
+}
+
+/// Outgoing messages held while the transport stalls. Messages are written
+/// in chunks; a failed chunk is retried without resending the ones before
+/// it. When full, the oldest message not yet started is dropped so a long
+/// stall delivers the latest commands rather than stale ones.
+#[derive(Debug)]
+pub struct OutgoingQueue {
+    messages: VecDeque<Vec<u8>>,
+    /// Bytes of the front message already written.
+    sent: usize,
+    capacity: usize,
+    chunk_size: Option<usize>,
+}
+
+impl OutgoingQueue {
+    /// `chunk_size` of `None` writes each message whole.
+    pub fn new(capacity: usize, chunk_size: Option<usize>) -> Self {
+        Self {
+            messages: VecDeque::new(),
+            sent: 0,
+            capacity: capacity.max(1),
+            chunk_size: chunk_size.filter(|size| *size > 0),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Queue `message`; returns whether an older message was dropped to
+    /// make room.
+    pub fn push(&mut self, message: Vec<u8>) -> bool {
+        if message.is_empty() {
+            return false;
+        }
+        let mut dropped = false;
+        if self.messages.len() >= self.capacity {
+            let oldest_unstarted = usize::from(self.sent > 0);
+            dropped = self.messages.remove(oldest_unstarted).is_some();
+            if oldest_unstarted == 0 {
+                self.sent = 0;
+            }
+        }
+        self.messages.push_back(message);
+        dropped
+    }
+
+    pub fn next_chunk(&self) -> Option<&[u8]> {
+        let rest = &self.messages.front()?[self.sent..];
+        let len = self
+            .chunk_size
+            .map_or(rest.len(), |size| size.min(rest.len()));
+        Some(&rest[..len])
+    }
+
+    /// Mark the chunk from `next_chunk` as written.
+    pub fn chunk_sent(&mut self) {
+        let Some(len) = self.next_chunk().map(<[u8]>::len) else {
+            return;
+        };
+        self.sent += len;
+        if self
+            .messages
+            .front()
+            .is_some_and(|front| self.sent >= front.len())
+        {
+            self.messages.pop_front();
+            self.sent = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_backs_off_then_gives_up() {
+        let mut retry = DrainRetry::default();
+        assert_eq!(retry.failed(), Some(Duration::from_millis(25)));
+        assert_eq!(retry.failed(), Some(Duration::from_millis(50)));
+        retry.succeeded();
+        assert_eq!(retry.failed(), Some(Duration::from_millis(25)));
+
+        let delays: Vec<_> = std::iter::from_fn(|| retry.failed()).collect();
+        assert_eq!(delays.len() as u32, DRAIN_MAX_CONSECUTIVE_FAILURES - 2);
+        assert_eq!(delays.last(), Some(&DRAIN_RETRY_MAX_BACKOFF));
+    }
+
+    #[test]
+    fn queue_resumes_mid_message_and_drops_the_oldest_unstarted() {
+        let mut queue = OutgoingQueue::new(2, Some(2));
+        assert!(!queue.push(vec![1, 2, 3]));
+        assert_eq!(queue.next_chunk(), Some(&[1, 2][..]));
+        queue.chunk_sent();
+        // A failed write leaves the same chunk up next.
+        assert_eq!(queue.next_chunk(), Some(&[3][..]));
+
+        assert!(!queue.push(vec![4]));
+        assert!(queue.push(vec![5]));
+        queue.chunk_sent();
+        assert_eq!(queue.next_chunk(), Some(&[5][..]));
+        queue.chunk_sent();
+        assert!(queue.is_empty());
+
+        let mut whole = OutgoingQueue::new(4, None);
+        whole.push(vec![7, 8, 9]);
+        assert_eq!(whole.next_chunk(), Some(&[7, 8, 9][..]));
+    }
+}
@@ -0,0 +1,492 @@
+//! Step-response metrics for attitude rate tuning, from ATTITUDE paired with
+//! the autopilot's own targets.
+
+use crate::dialect::{AttitudeTargetTypemask, MavMessage};
+use crate::ipc::{TuneAxis, TuneReport, TuneStep, TuneTargetSource};
+use crate::telemetry::DEFAULT_TELEMETRY_STREAM_REQUESTS;
+
+pub const ATTITUDE_MESSAGE_ID: u32 = 30;
+pub const NAV_CONTROLLER_OUTPUT_MESSAGE_ID: u32 = 62;
+pub const ATTITUDE_TARGET_MESSAGE_ID: u32 = 83;
+
+/// Messages a capture raises to [`TUNE_CAPTURE_INTERVAL_USEC`].
+pub const TUNE_CAPTURE_MESSAGE_IDS: [u32; 3] = [
+    ATTITUDE_MESSAGE_ID,
+    ATTITUDE_TARGET_MESSAGE_ID,
+    NAV_CONTROLLER_OUTPUT_MESSAGE_ID,
+];
+
+/// 50 Hz: enough to resolve a rise time of a few tens of milliseconds.
+pub const TUNE_CAPTURE_INTERVAL_USEC: i32 = 20_000;
+
+/// Ten minutes at the capture rate; later samples are dropped.
+const MAX_CAPTURE_SAMPLES: usize = 30_000;
+
+/// Smallest target change that counts as a step.
+const MIN_RATE_STEP_DEG_S: f64 = 20.0;
+const MIN_ANGLE_STEP_DEG: f64 = 5.0;
+
+/// A target level must hold this long to start or end a step.
+const MIN_PLATEAU_S: f64 = 0.2;
+/// Wobble allowed within a held target, as a fraction of the smallest step.
+const PLATEAU_TOLERANCE: f64 = 0.1;
+
+const RISE_LOW: f64 = 0.1;
+const RISE_HIGH: f64 = 0.9;
+const SETTLING_BAND: f64 = 0.05;
+/// The error has to pass this far through the target for a swing to count,
+/// so sensor noise around a settled response is not read as ringing.
+const OSCILLATION_HYSTERESIS: f64 = 0.02;
+
+/// The interval to put a raised message back to: the one IronWing requests on
+/// connect, or `0` for the autopilot's own default.
+pub fn restore_interval_usec(message_id: u32) -> i32 {
+    DEFAULT_TELEMETRY_STREAM_REQUESTS
+        .iter()
+        .find(|request| request.message_id == message_id)
+        .map_or(0, |request| request.interval_usec)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResponseSample {
+    pub time_s: f64,
+    pub target: f64,
+    pub actual: f64,
+}
+
+struct Plateau {
+    last: usize,
+    level: f64,
+}
+
+/// Runs of at least [`MIN_PLATEAU_S`] where the target stays put. Samples
+/// between them are the target moving, shaped or not.
+fn plateaus(samples: &[ResponseSample], tolerance: f64) -> Vec<Plateau> {
+    let mut plateaus = Vec::new();
+    let mut first = 0;
+    while first < samples.len() {
+        let anchor = samples[first].target;
+        let mut last = first;
+        while last + 1 < samples.len() && (samples[last + 1].target - anchor).abs() <= tolerance {
+            last += 1;
+        }
+        if samples[last].time_s - samples[first].time_s >= MIN_PLATEAU_S {
+            let run = &samples[first..=last];
+            let level = run.iter().map(|sample| sample.target).sum::<f64>() / run.len() as f64;
+            plateaus.push(Plateau { last, level });
+        }
+        first = last + 1;
+    }
+    plateaus
+}
+
+/// Steps of at least `min_step` between held target levels, each measured
+/// until the target moves again.
+pub fn detect_steps(samples: &[ResponseSample], min_step: f64) -> Vec<TuneStep> {
+    plateaus(samples, min_step * PLATEAU_TOLERANCE)
+        .windows(2)
+        .filter(|pair| (pair[1].level - pair[0].level).abs() >= min_step)
+        .map(|pair| {
+            let (before, after) = (&pair[0], &pair[1]);
+            step_response(
+                &samples[before.last..=after.last],
+                before.level,
+                after.level,
+            )
+        })
+        .collect()
+}
+
+/// When a value going from `va` at `a` to `vb` at `b` passes `threshold`.
+fn crossing_time(a: &ResponseSample, b: &ResponseSample, va: f64, vb: f64, threshold: f64) -> f64 {
+    if vb == va {
+        return b.time_s;
+    }
+    a.time_s + (threshold - va) / (vb - va) * (b.time_s - a.time_s)
+}
+
+/// Metrics for the response in `samples`, which start when the target leaves
+/// `from` and end when it next moves away from `to`.
+pub fn step_response(samples: &[ResponseSample], from: f64, to: f64) -> TuneStep {
+    let start_s = samples.first().map_or(0.0, |sample| sample.time_s);
+    let size = to - from;
+    let progress: Vec<f64> = samples
+        .iter()
+        .map(|sample| (sample.actual - from) / size)
+        .collect();
+    let first_crossing = |threshold: f64| {
+        (1..samples.len())
+            .find(|&i| progress[i - 1] < threshold && progress[i] >= threshold)
+            .map(|i| {
+                crossing_time(
+                    &samples[i - 1],
+                    &samples[i],
+                    progress[i - 1],
+                    progress[i],
+                    threshold,
+                )
+            })
+    };
+
+    let rise_time_s = first_crossing(RISE_LOW)
+        .zip(first_crossing(RISE_HIGH))
+        .map(|(low, high)| high - low);
+
+    let peak = progress.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let overshoot_pct = ((peak - 1.0) * 100.0).max(0.0);
+
+    let outside = |i: usize| (progress[i] - 1.0).abs() > SETTLING_BAND;
+    let settling_time_s = match (0..samples.len()).rev().find(|&i| outside(i)) {
+        None => Some(0.0),
+        Some(i) if i + 1 == samples.len() => None,
+        Some(i) => {
+            let (a, b) = (progress[i] - 1.0, progress[i + 1] - 1.0);
+            let edge = SETTLING_BAND.copysign(a);
+            Some(crossing_time(&samples[i], &samples[i + 1], a, b, edge) - start_s)
+        }
+    };
+
+    TuneStep {
+        start_s,
+        from,
+        to,
+        rise_time_s,
+        overshoot_pct,
+        settling_time_s,
+        oscillation_hz: oscillation_hz(samples, &progress),
+    }
+}
+
+/// Swings of the error through zero, counted once they pass the hysteresis
+/// band. Three crossings (the rise, the overshoot, the undershoot) make a
+/// full period.
+fn oscillation_hz(samples: &[ResponseSample], progress: &[f64]) -> Option<f64> {
+    let mut crossings = Vec::new();
+    let mut side = -1.0;
+    let mut last_zero = None;
+    for i in 1..samples.len() {
+        let (a, b) = (progress[i - 1] - 1.0, progress[i] - 1.0);
+        if a.signum() != b.signum() {
+            last_zero = Some(crossing_time(&samples[i - 1], &samples[i], a, b, 0.0));
+        }
+        if b * side < -OSCILLATION_HYSTERESIS {
+            side = -side;
+            crossings.extend(last_zero.take());
+        }
+    }
+    if crossings.len() < 3 {
+        return None;
+    }
+    let span = crossings[crossings.len() - 1] - crossings[0];
+    Some((crossings.len() - 1) as f64 / (2.0 * span))
+}
+
+/// Collects target/response pairs for one axis while a capture runs. Each
+/// ATTITUDE sample is paired with the most recent target.
+#[derive(Debug, Clone)]
+pub struct TuneCapture {
+    axis: TuneAxis,
+    started_unix_msec: u64,
+    first_boot_ms: Option<u32>,
+    last_time_s: f64,
+    rate_target: Option<f64>,
+    angle_target: Option<f64>,
+    rate_samples: Vec<ResponseSample>,
+    angle_samples: Vec<ResponseSample>,
+}
+
+impl TuneCapture {
+    pub fn new(axis: TuneAxis, started_unix_msec: u64) -> Self {
+        Self {
+            axis,
+            started_unix_msec,
+            first_boot_ms: None,
+            last_time_s: 0.0,
+            rate_target: None,
+            angle_target: None,
+            rate_samples: Vec::new(),
+            angle_samples: Vec::new(),
+        }
+    }
+
+    pub fn observe(&mut self, message: &MavMessage) {
+        match message {
+            MavMessage::ATTITUDE(data) => {
+                let first = *self.first_boot_ms.get_or_insert(data.time_boot_ms);
+                let time_s = f64::from(data.time_boot_ms.wrapping_sub(first)) / 1000.0;
+                self.last_time_s = time_s;
+                let (angle, rate) = match self.axis {
+                    TuneAxis::Roll => (data.roll, data.rollspeed),
+                    TuneAxis::Pitch => (data.pitch, data.pitchspeed),
+                    TuneAxis::Yaw => (data.yaw, data.yawspeed),
+                };
+                let push = |samples: &mut Vec<ResponseSample>, target: Option<f64>, actual: f32| {
+                    if let Some(target) = target
+                        && samples.len() < MAX_CAPTURE_SAMPLES
+                    {
+                        samples.push(ResponseSample {
+                            time_s,
+                            target,
+                            actual: f64::from(actual.to_degrees()),
+                        });
+                    }
+                };
+                push(&mut self.rate_samples, self.rate_target, rate);
+                push(&mut self.angle_samples, self.angle_target, angle);
+            }
+            MavMessage::ATTITUDE_TARGET(data) => {
+                let (ignore, rate) = match self.axis {
+                    TuneAxis::Roll => (
+                        AttitudeTargetTypemask::ATTITUDE_TARGET_TYPEMASK_BODY_ROLL_RATE_IGNORE,
+                        data.body_roll_rate,
+                    ),
+                    TuneAxis::Pitch => (
+                        AttitudeTargetTypemask::ATTITUDE_TARGET_TYPEMASK_BODY_PITCH_RATE_IGNORE,
+                        data.body_pitch_rate,
+                    ),
+                    TuneAxis::Yaw => (
+                        AttitudeTargetTypemask::ATTITUDE_TARGET_TYPEMASK_BODY_YAW_RATE_IGNORE,
+                        data.body_yaw_rate,
+                    ),
+                };
+                if !data.type_mask.contains(ignore) {
+                    self.rate_target = Some(f64::from(rate.to_degrees()));
+                }
+            }
+            MavMessage::NAV_CONTROLLER_OUTPUT(data) => {
+                self.angle_target = match self.axis {
+                    TuneAxis::Roll => Some(f64::from(data.nav_roll)),
+                    TuneAxis::Pitch => Some(f64::from(data.nav_pitch)),
+                    TuneAxis::Yaw => None,
+                };
+            }
+            _ => {}
+        }
+    }
+
+    /// Analyse against ATTITUDE_TARGET rates when the autopilot sent them,
+    /// otherwise against NAV_CONTROLLER_OUTPUT angles.
+    pub fn finish(self) -> TuneReport {
+        let (source, samples, min_step) = if !self.rate_samples.is_empty() {
+            (
+                Some(TuneTargetSource::AttitudeTarget),
+                self.rate_samples,
+                MIN_RATE_STEP_DEG_S,
+            )
+        } else if !self.angle_samples.is_empty() {
+            (
+                Some(TuneTargetSource::NavControllerOutput),
+                self.angle_samples,
+                MIN_ANGLE_STEP_DEG,
+            )
+        } else {
+            (None, Vec::new(), MIN_RATE_STEP_DEG_S)
+        };
+        TuneReport {
+            axis: self.axis,
+            source,
+            started_unix_msec: self.started_unix_msec,
+            duration_s: self.last_time_s,
+            samples: samples.len() as u32,
+            steps: detect_steps(&samples, min_step),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::dialect::{ATTITUDE_DATA, ATTITUDE_TARGET_DATA, NAV_CONTROLLER_OUTPUT_DATA};
+
+    const SAMPLE_HZ: f64 = 50.0;
+
+    /// Unit step response of `wn² / (s² + 2ζwn·s + wn²)` at `t` seconds.
+    fn second_order(zeta: f64, wn: f64, t: f64) -> f64 {
+        if t <= 0.0 {
+            return 0.0;
+        }
+        if zeta < 1.0 {
+            let wd = wn * (1.0 - zeta * zeta).sqrt();
+            let phi = zeta.acos();
+            1.0 - (-zeta * wn * t).exp() / (1.0 - zeta * zeta).sqrt() * (wd * t + phi).sin()
+        } else {
+            1.0 - (1.0 + wn * t) * (-wn * t).exp()
+        }
+    }
+
+    /// Target held at `from` for a second, then at `to` for `hold_s`, with
+    /// the response following `second_order`.
+    fn step_samples(from: f64, to: f64, hold_s: f64, zeta: f64, wn: f64) -> Vec<ResponseSample> {
+        let step_at = 1.0;
+        let count = ((step_at + hold_s) * SAMPLE_HZ) as usize;
+        (0..count)
+            .map(|i| {
+                let time_s = i as f64 / SAMPLE_HZ;
+                let (target, response) = if time_s < step_at {
+                    (from, 0.0)
+                } else {
+                    (
+                        to,
+                        second_order(zeta, wn, time_s - step_at + 1.0 / SAMPLE_HZ),
+                    )
+                };
+                ResponseSample {
+                    time_s,
+                    target,
+                    actual: from + (to - from) * response,
+                }
+            })
+            .collect()
+    }
+
+    fn approx(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn underdamped_step_reports_overshoot_and_ringing() {
+        let (zeta, wn) = (0.3, 20.0);
+        let samples = step_samples(0.0, 90.0, 2.0, zeta, wn);
+
+        let steps = detect_steps(&samples, MIN_RATE_STEP_DEG_S);
+
+        assert_eq!(steps.len(), 1);
+        let step = &steps[0];
+        approx(step.start_s, 0.98, 1e-9);
+        assert_eq!((step.from, step.to), (0.0, 90.0));
+        let expected_overshoot = 100.0 * (-zeta * PI / (1.0 - zeta * zeta).sqrt()).exp();
+        approx(step.overshoot_pct, expected_overshoot, 1.5);
+        let damped_hz = wn * (1.0 - zeta * zeta).sqrt() / (2.0 * PI);
+        approx(
+            step.oscillation_hz.expect("rings"),
+            damped_hz,
+            damped_hz * 0.05,
+        );
+        // 10-90 % rise of a ζ = 0.3 system is 1.32 / ωn.
+        approx(step.rise_time_s.expect("rises"), 1.32 / wn, 0.005);
+        // The 5 % envelope decays in about 3 / (ζωn).
+        let settling = step.settling_time_s.expect("settles");
+        assert!(
+            (0.6 * 3.0 / (zeta * wn)..=3.0 / (zeta * wn) + 0.05).contains(&settling),
+            "{settling}"
+        );
+    }
+
+    #[test]
+    fn critically_damped_step_does_not_overshoot() {
+        let wn = 15.0;
+        let samples = step_samples(10.0, -50.0, 1.5, 1.0, wn);
+
+        let steps = detect_steps(&samples, MIN_RATE_STEP_DEG_S);
+
+        assert_eq!(steps.len(), 1);
+        let step = &steps[0];
+        assert_eq!((step.from, step.to), (10.0, -50.0));
+        assert!(step.overshoot_pct < 0.5, "{}", step.overshoot_pct);
+        assert_eq!(step.oscillation_hz, None);
+        // 10-90 % rise of a critically damped system is 3.36 / ωn.
+        approx(step.rise_time_s.expect("rises"), 3.36 / wn, 0.01);
+        // Within 5 % once (1 + ωn·t)e^(-ωn·t) < 0.05, at ωn·t ≈ 4.74.
+        approx(step.settling_time_s.expect("settles"), 4.74 / wn, 0.02);
+    }
+
+    #[test]
+    fn small_target_moves_and_unsettled_steps() {
+        let mut samples = step_samples(0.0, 10.0, 1.0, 0.5, 10.0);
+        assert!(detect_steps(&samples, MIN_RATE_STEP_DEG_S).is_empty());
+
+        // A response cut short by the next step has not settled.
+        samples = step_samples(0.0, 60.0, 0.25, 0.1, 10.0);
+        let steps = detect_steps(&samples, MIN_RATE_STEP_DEG_S);
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].settling_time_s, None);
+    }
+
+    #[test]
+    fn back_to_back_steps_are_measured_separately() {
+        let mut samples = step_samples(0.0, 60.0, 1.0, 0.5, 20.0);
+        let offset = samples.len() as f64 / SAMPLE_HZ;
+        samples.extend(
+            step_samples(60.0, -60.0, 1.0, 0.5, 20.0)
+                .into_iter()
+                .skip(SAMPLE_HZ as usize)
+                .map(|sample| ResponseSample {
+                    time_s: sample.time_s + offset - 1.0,
+                    ..sample
+                }),
+        );
+
+        let steps = detect_steps(&samples, MIN_RATE_STEP_DEG_S);
+
+        assert_eq!(steps.len(), 2);
+        assert_eq!((steps[1].from, steps[1].to), (60.0, -60.0));
+        approx(steps[0].overshoot_pct, steps[1].overshoot_pct, 0.5);
+    }
+
+    #[test]
+    fn capture_pairs_attitude_with_the_latest_target() {
+        let mut capture = TuneCapture::new(TuneAxis::Roll, 1_000);
+        let nav = |nav_roll| {
+            MavMessage::NAV_CONTROLLER_OUTPUT(NAV_CONTROLLER_OUTPUT_DATA {
+                nav_roll,
+                ..NAV_CONTROLLER_OUTPUT_DATA::default()
+            })
+        };
+        let attitude = |time_boot_ms, roll: f64, rollspeed: f64| {
+            MavMessage::ATTITUDE(ATTITUDE_DATA {
+                time_boot_ms,
+                roll: roll.to_radians() as f32,
+                rollspeed: rollspeed.to_radians() as f32,
+                ..ATTITUDE_DATA::default()
+            })
+        };
+
+        capture.observe(&attitude(5_000, 0.0, 0.0));
+        capture.observe(&nav(0.0));
+        for i in 1..=50u32 {
+            capture.observe(&attitude(5_000 + i * 20, 0.0, 0.0));
+        }
+        capture.observe(&nav(20.0));
+        for i in 51..=100u32 {
+            capture.observe(&attitude(5_000 + i * 20, 20.0, 0.0));
+        }
+        let report = capture.clone().finish();
+        assert_eq!(report.source, Some(TuneTargetSource::NavControllerOutput));
+        assert_eq!(report.samples, 100);
+        approx(report.duration_s, 2.0, 1e-9);
+        assert_eq!(report.steps.len(), 1);
+        approx(report.steps[0].to, 20.0, 1e-3);
+
+        // Rate targets win once the autopilot sends them; ignored axes do not
+        // count.
+        capture.observe(&MavMessage::ATTITUDE_TARGET(ATTITUDE_TARGET_DATA {
+            body_roll_rate: 1.0,
+            type_mask: AttitudeTargetTypemask::ATTITUDE_TARGET_TYPEMASK_BODY_ROLL_RATE_IGNORE,
+            ..ATTITUDE_TARGET_DATA::default()
+        }));
+        capture.observe(&attitude(7_020, 20.0, 0.0));
+        assert_eq!(
+            capture.clone().finish().source,
+            Some(TuneTargetSource::NavControllerOutput)
+        );
+        capture.observe(&MavMessage::ATTITUDE_TARGET(ATTITUDE_TARGET_DATA {
+            body_roll_rate: 0.5,
+            ..ATTITUDE_TARGET_DATA::default()
+        }));
+        capture.observe(&attitude(7_040, 20.0, 28.0));
+        let report = capture.finish();
+        assert_eq!(report.source, Some(TuneTargetSource::AttitudeTarget));
+        assert_eq!(report.samples, 1);
+    }
+
+    #[test]
+    fn raised_intervals_go_back_to_the_connect_defaults() {
+        assert_eq!(restore_interval_usec(ATTITUDE_MESSAGE_ID), 200_000);
+        assert_eq!(restore_interval_usec(ATTITUDE_TARGET_MESSAGE_ID), 0);
+    }
+}
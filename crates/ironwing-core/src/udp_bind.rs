@@ -0,0 +1,189 @@
+use std::io::ErrorKind;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use crate::ipc::{Reason, ReasonKind};
+
+/// Ports below this need elevated privileges on Linux and Android.
+pub const FIRST_UNPRIVILEGED_PORT: u16 = 1024;
+
+/// Why a typed UDP bind address was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UdpBindAddrError {
+    Empty,
+    InvalidPort(String),
+    PortZero,
+    InvalidHost(String),
+    /// An IPv6 address without brackets, so the port cannot be told apart.
+    UnbracketedIpv6(String),
+}
+
+impl UdpBindAddrError {
+    pub fn reason(&self) -> Reason {
+        let message = match self {
+            Self::Empty => "enter a UDP port to listen on, such as 14550".to_string(),
+            Self::InvalidPort(port) => {
+                format!("`{port}` is not a port number; use a number from 1 to 65535")
+            }
+            Self::PortZero => "port 0 is not allowed; use a port such as 14550".to_string(),
+            Self::InvalidHost(host) => {
+                format!("`{host}` is not an IP address; use 0.0.0.0 to listen on every interface")
+            }
+            Self::UnbracketedIpv6(input) => {
+                format!("put IPv6 addresses in brackets, e.g. `[::]:14550` instead of `{input}`")
+            }
+        };
+        Reason {
+            kind: ReasonKind::InvalidInput,
+            message,
+        }
+    }
+}
+
+/// Parse a user-entered bind address. `14550`, `:14550`, `0.0.0.0:14550`
+/// and `[::]:14550` are accepted; a missing host means every IPv4
+/// interface.
+pub fn parse_udp_bind_addr(input: &str) -> Result<SocketAddr, UdpBindAddrError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(UdpBindAddrError::Empty);
+    }
+    let (host, port) = match input.rsplit_once(':') {
+        Some((host, _)) if host.contains(':') && !host.starts_with('[') => {
+            return Err(UdpBindAddrError::UnbracketedIpv6(input.to_string()));
+        }
+        Some((host, port)) => (host, port),
+        None => ("", input),
+    };
+    let port: u16 = port
+        .parse()
+        .map_err(|_| UdpBindAddrError::InvalidPort(port.to_string()))?;
+    if port == 0 {
+        return Err(UdpBindAddrError::PortZero);
+    }
+    let ip = match host {
+        "" => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        _ => {
+            let literal = host
+                .strip_prefix('[')
+                .and_then(|host| host.strip_suffix(']'))
+                .unwrap_or(host);
+            literal
+                .parse()
+                .map_err(|_| UdpBindAddrError::InvalidHost(host.to_string()))?
+        }
+    };
+    Ok(SocketAddr::new(ip, port))
+}
+
+/// The reason to report when a trial bind of `addr` failed with `kind`.
+pub fn udp_bind_failure(addr: SocketAddr, kind: ErrorKind) -> Reason {
+    match kind {
+        ErrorKind::AddrInUse => Reason {
+            kind: ReasonKind::Conflict,
+            message: format!(
+                "UDP port {} is already in use; close the other ground station or pick another port",
+                addr.port()
+            ),
+        },
+        ErrorKind::PermissionDenied if addr.port() < FIRST_UNPRIVILEGED_PORT => Reason {
+            kind: ReasonKind::PermissionDenied,
+            message: format!(
+                "ports below {FIRST_UNPRIVILEGED_PORT} need administrator rights; use a port such as 14550"
+            ),
+        },
+        ErrorKind::PermissionDenied => Reason {
+            kind: ReasonKind::PermissionDenied,
+            message: format!("not allowed to listen on {addr}"),
+        },
+        ErrorKind::AddrNotAvailable => Reason {
+            kind: ReasonKind::InvalidInput,
+            message: format!(
+                "{} is not an address of this device; use 0.0.0.0 to listen on every interface",
+                addr.ip()
+            ),
+        },
+        other => Reason {
+            kind: ReasonKind::Failed,
+            message: format!("cannot listen on {addr}: {other}"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepted_forms_normalize_to_a_socket_address() {
+        let cases = [
+            ("14550", "0.0.0.0:14550"),
+            (":14550", "0.0.0.0:14550"),
+            ("  0.0.0.0:14550 ", "0.0.0.0:14550"),
+            ("127.0.0.1:14551", "127.0.0.1:14551"),
+            ("[::]:14550", "[::]:14550"),
+            ("[::1]:14550", "[::1]:14550"),
+            ("80", "0.0.0.0:80"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(
+                parse_udp_bind_addr(input),
+                Ok(expected.parse().unwrap()),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejected_forms_name_the_problem() {
+        let cases = [
+            ("", UdpBindAddrError::Empty),
+            ("   ", UdpBindAddrError::Empty),
+            ("0.0.0.0:", UdpBindAddrError::InvalidPort(String::new())),
+            ("abc", UdpBindAddrError::InvalidPort("abc".into())),
+            (
+                "0.0.0.0:70000",
+                UdpBindAddrError::InvalidPort("70000".into()),
+            ),
+            (":0", UdpBindAddrError::PortZero),
+            (
+                "localhost:14550",
+                UdpBindAddrError::InvalidHost("localhost".into()),
+            ),
+            (
+                "300.1.1.1:14550",
+                UdpBindAddrError::InvalidHost("300.1.1.1".into()),
+            ),
+            ("[::]x:14550", UdpBindAddrError::InvalidHost("[::]x".into())),
+            (
+                "::1:14550",
+                UdpBindAddrError::UnbracketedIpv6("::1:14550".into()),
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_udp_bind_addr(input), Err(expected), "{input}");
+        }
+        assert_eq!(
+            UdpBindAddrError::PortZero.reason().kind,
+            ReasonKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn bind_failures_carry_a_hint() {
+        let low: SocketAddr = "0.0.0.0:80".parse().unwrap();
+        let high: SocketAddr = "0.0.0.0:14550".parse().unwrap();
+
+        let in_use = udp_bind_failure(high, ErrorKind::AddrInUse);
+        assert_eq!(in_use.kind, ReasonKind::Conflict);
+        assert!(in_use.message.contains("14550 is already in use"));
+
+        let privileged = udp_bind_failure(low, ErrorKind::PermissionDenied);
+        assert_eq!(privileged.kind, ReasonKind::PermissionDenied);
+        assert!(privileged.message.contains("below 1024"));
+        assert!(
+            !udp_bind_failure(high, ErrorKind::PermissionDenied)
+                .message
+                .contains("below 1024")
+        );
+    }
+}
@@ -0,0 +1,572 @@
+use std::collections::HashMap;
+
+/// "ULog" followed by 0x01 0x12 0x35.
+const ULOG_MAGIC: [u8; 7] = [0x55, 0x4c, 0x6f, 0x67, 0x01, 0x12, 0x35];
+const ULOG_HEADER_LEN: usize = 16;
+const MESSAGE_HEADER_LEN: usize = 3;
+/// `incompat_flags[0]` bit: data was appended at the offsets in the flag bits
+/// message, after a gap the writer may have left partially filled.
+const INCOMPAT_DATA_APPENDED: u8 = 0x01;
+/// Nested format definitions deeper than this are treated as a cycle.
+const MAX_FORMAT_DEPTH: usize = 8;
+
+/// A decoded field value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UlogValue {
+    Number(f64),
+    Text(String),
+}
+
+/// One record of a ULog file, in file order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UlogRecord {
+    /// A logged uORB message. `fields` are flattened: arrays as `q[0]`,
+    /// nested formats as `esc[0].esc_rpm`.
+    Data {
+        timestamp_usec: u64,
+        name: String,
+        multi_id: u8,
+        fields: Vec<(String, UlogValue)>,
+    },
+    /// A `logged_messages` record (PX4's console output).
+    LoggedMessage {
+        timestamp_usec: u64,
+        /// Syslog level, 0 (emergency) to 7 (debug), as MAV_SEVERITY.
+        level: u8,
+        text: String,
+    },
+    /// An initial parameter value, or a change logged during the flight.
+    Parameter {
+        timestamp_usec: u64,
+        name: String,
+        value: f64,
+    },
+    /// The logger dropped data for this long.
+    Dropout {
+        timestamp_usec: u64,
+        duration_ms: u16,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ulog {
+    /// Logging start, from the file header.
+    pub start_usec: u64,
+    pub records: Vec<UlogRecord>,
+    /// The file ended in a partial message, usually because logging stopped
+    /// abruptly.
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Primitive {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+    F64,
+    Bool,
+    Char,
+}
+
+impl Primitive {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "int8_t" => Self::I8,
+            "uint8_t" => Self::U8,
+            "int16_t" => Self::I16,
+            "uint16_t" => Self::U16,
+            "int32_t" => Self::I32,
+            "uint32_t" => Self::U32,
+            "int64_t" => Self::I64,
+            "uint64_t" => Self::U64,
+            "float" => Self::F32,
+            "double" => Self::F64,
+            "bool" => Self::Bool,
+            "char" => Self::Char,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Self::I8 | Self::U8 | Self::Bool | Self::Char => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::I64 | Self::U64 | Self::F64 => 8,
+        }
+    }
+
+    fn read(self, bytes: &[u8]) -> f64 {
+        let eight = || -> [u8; 8] { bytes[..8].try_into().unwrap_or_default() };
+        match self {
+            Self::I8 => f64::from(bytes[0] as i8),
+            Self::U8 | Self::Char => f64::from(bytes[0]),
+            Self::Bool => f64::from(u8::from(bytes[0] != 0)),
+            Self::I16 => f64::from(i16::from_le_bytes([bytes[0], bytes[1]])),
+            Self::U16 => f64::from(u16::from_le_bytes([bytes[0], bytes[1]])),
+            Self::I32 => f64::from(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+            Self::U32 => f64::from(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+            Self::F32 => f64::from(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+            Self::I64 => i64::from_le_bytes(eight()) as f64,
+            Self::U64 => u64::from_le_bytes(eight()) as f64,
+            Self::F64 => f64::from_le_bytes(eight()),
+        }
+    }
+}
+
+/// A field of a format after flattening nested formats and arrays.
+#[derive(Debug, Clone)]
+struct FlatField {
+    name: String,
+    kind: Primitive,
+    offset: usize,
+    /// Characters in a `char[n]` string; 0 for a single value.
+    text_len: usize,
+}
+
+#[derive(Debug, Clone)]
+struct Subscription {
+    name: String,
+    multi_id: u8,
+    fields: Vec<FlatField>,
+    timestamp_offset: Option<usize>,
+}
+
+/// "vehicle_attitude:uint64_t timestamp;float[4] q;..." as (type, name) pairs.
+fn parse_format(body: &str) -> Result<(String, Vec<(String, String)>), String> {
+    let (name, fields) = body
+        .split_once(':')
+        .ok_or_else(|| format!("malformed ULog format definition {body:?}"))?;
+    let fields = fields
+        .split(';')
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            field
+                .split_once(' ')
+                .map(|(kind, name)| (kind.to_string(), name.to_string()))
+                .ok_or_else(|| format!("malformed ULog field {field:?} in {name}"))
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((name.to_string(), fields))
+}
+
+/// "float[4]" → ("float", Some(4)).
+fn split_array(kind: &str) -> Result<(&str, Option<usize>), String> {
+    let Some((base, count)) = kind.split_once('[') else {
+        return Ok((kind, None));
+    };
+    let count = count
+        .strip_suffix(']')
+        .and_then(|count| count.parse().ok())
+        .ok_or_else(|| format!("malformed ULog array type {kind:?}"))?;
+    Ok((base, Some(count)))
+}
+
+fn flatten(
+    formats: &HashMap<String, Vec<(String, String)>>,
+    format: &str,
+    prefix: &str,
+    offset: &mut usize,
+    out: &mut Vec<FlatField>,
+    depth: usize,
+) -> Result<(), String> {
+    if depth > MAX_FORMAT_DEPTH {
+        return Err(format!("ULog format {format} nests too deeply"));
+    }
+    let fields = formats
+        .get(format)
+        .ok_or_else(|| format!("ULog format {format} is not defined"))?;
+    for (kind, name) in fields {
+        let (base, count) = split_array(kind)?;
+        let padding = name.starts_with("_padding");
+        match (Primitive::from_name(base), count) {
+            (Some(Primitive::Char), Some(count)) => {
+                if !padding {
+                    out.push(FlatField {
+                        name: format!("{prefix}{name}"),
+                        kind: Primitive::Char,
+                        offset: *offset,
+                        text_len: count,
+                    });
+                }
+                *offset += count;
+            }
+            (Some(kind), count) => {
+                for index in 0..count.unwrap_or(1) {
+                    if !padding {
+                        let name = match count {
+                            Some(_) => format!("{prefix}{name}[{index}]"),
+                            None => format!("{prefix}{name}"),
+                        };
+                        out.push(FlatField {
+                            name,
+                            kind,
+                            offset: *offset,
+                            text_len: 0,
+                        });
+                    }
+                    *offset += kind.size();
+                }
+            }
+            (None, count) => {
+                for index in 0..count.unwrap_or(1) {
+                    let nested_prefix = match count {
+                        Some(_) => format!("{prefix}{name}[{index}]."),
+                        None => format!("{prefix}{name}."),
+                    };
+                    flatten(formats, base, &nested_prefix, offset, out, depth + 1)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn decode_fields(fields: &[FlatField], payload: &[u8]) -> Vec<(String, UlogValue)> {
+    fields
+        .iter()
+        .filter_map(|field| {
+            if field.text_len > 0 {
+                let bytes = payload.get(field.offset..field.offset + field.text_len)?;
+                let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+                let text = String::from_utf8_lossy(&bytes[..end]).into_owned();
+                return Some((field.name.clone(), UlogValue::Text(text)));
+            }
+            let bytes = payload.get(field.offset..field.offset + field.kind.size())?;
+            Some((
+                field.name.clone(),
+                UlogValue::Number(field.kind.read(bytes)),
+            ))
+        })
+        .collect()
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(bytes.get(at..at + 8)?.try_into().ok()?))
+}
+
+/// A `P` message: "float NAME" or "int32_t NAME" and its 4-byte value.
+fn parse_parameter(body: &[u8]) -> Option<(String, f64)> {
+    let key_len = usize::from(*body.first()?);
+    let key = std::str::from_utf8(body.get(1..1 + key_len)?).ok()?;
+    let (kind, name) = key.split_once(' ')?;
+    let value = body.get(1 + key_len..1 + key_len + 4)?;
+    let value = match kind {
+        "float" => Primitive::F32.read(value),
+        "int32_t" => Primitive::I32.read(value),
+        _ => return None,
+    };
+    Some((name.to_string(), value))
+}
+
+/// `L` and `C` messages: level (an ASCII digit), an optional tag, the
+/// timestamp and the text.
+fn parse_logged_message(body: &[u8], tagged: bool) -> Option<UlogRecord> {
+    let level = *body.first()?;
+    let at = if tagged { 3 } else { 1 };
+    let timestamp_usec = read_u64(body, at)?;
+    let text = String::from_utf8_lossy(body.get(at + 8..)?)
+        .trim_end_matches('\0')
+        .to_string();
+    Some(UlogRecord::LoggedMessage {
+        timestamp_usec,
+        level: if level.is_ascii_digit() {
+            level - b'0'
+        } else {
+            level
+        },
+        text,
+    })
+}
+
+#[derive(Default)]
+struct Parser {
+    formats: HashMap<String, Vec<(String, String)>>,
+    subscriptions: HashMap<u16, Subscription>,
+    records: Vec<UlogRecord>,
+    /// Latest timestamp seen, for records that carry none.
+    now_usec: u64,
+    appended_offsets: Vec<usize>,
+}
+
+impl Parser {
+    fn message(&mut self, kind: u8, body: &[u8]) -> Result<(), String> {
+        match kind {
+            b'B' => {
+                let incompat = body.get(8..16).unwrap_or_default();
+                if incompat
+                    .first()
+                    .is_some_and(|flags| flags & !INCOMPAT_DATA_APPENDED != 0)
+                    || incompat.iter().skip(1).any(|flags| *flags != 0)
+                {
+                    return Err(
+                        "ULog file uses incompatible features this reader does not know"
+                            .to_string(),
+                    );
+                }
+                if incompat
+                    .first()
+                    .is_some_and(|flags| flags & INCOMPAT_DATA_APPENDED != 0)
+                {
+                    self.appended_offsets = (0..3)
+                        .filter_map(|index| read_u64(body, 16 + index * 8))
+                        .filter(|offset| *offset > 0)
+                        .map(|offset| offset as usize)
+                        .collect();
+                }
+            }
+            b'F' => {
+                let (name, fields) = parse_format(&String::from_utf8_lossy(body))?;
+                self.formats.insert(name, fields);
+            }
+            b'A' => {
+                let (Some(&multi_id), Some(msg_id)) = (body.first(), read_u16(body, 1)) else {
+                    return Err("malformed ULog subscription".to_string());
+                };
+                let name = String::from_utf8_lossy(&body[3..]).into_owned();
+                let mut fields = Vec::new();
+                flatten(&self.formats, &name, "", &mut 0, &mut fields, 0)?;
+                let timestamp_offset = fields
+                    .iter()
+                    .find(|field| field.name == "timestamp" && field.kind == Primitive::U64)
+                    .map(|field| field.offset);
+                self.subscriptions.insert(
+                    msg_id,
+                    Subscription {
+                        name,
+                        multi_id,
+                        fields,
+                        timestamp_offset,
+                    },
+                );
+            }
+            b'R' => {
+                if let Some(msg_id) = read_u16(body, 0) {
+                    self.subscriptions.remove(&msg_id);
+                }
+            }
+            b'D' => {
+                let Some(msg_id) = read_u16(body, 0) else {
+                    return Ok(());
+                };
+                // Data for a subscription this file never announced is skipped.
+                let Some(subscription) = self.subscriptions.get(&msg_id) else {
+                    return Ok(());
+                };
+                let payload = &body[2..];
+                let timestamp_usec = subscription
+                    .timestamp_offset
+                    .and_then(|offset| read_u64(payload, offset))
+                    .unwrap_or(self.now_usec);
+                self.now_usec = self.now_usec.max(timestamp_usec);
+                self.records.push(UlogRecord::Data {
+                    timestamp_usec,
+                    name: subscription.name.clone(),
+                    multi_id: subscription.multi_id,
+                    fields: decode_fields(&subscription.fields, payload),
+                });
+            }
+            b'L' | b'C' => {
+                if let Some(record) = parse_logged_message(body, kind == b'C') {
+                    self.records.push(record);
+                }
+            }
+            b'P' => {
+                if let Some((name, value)) = parse_parameter(body) {
+                    self.records.push(UlogRecord::Parameter {
+                        timestamp_usec: self.now_usec,
+                        name,
+                        value,
+                    });
+                }
+            }
+            b'O' => {
+                if let Some(duration_ms) = read_u16(body, 0) {
+                    self.records.push(UlogRecord::Dropout {
+                        timestamp_usec: self.now_usec,
+                        duration_ms,
+                    });
+                }
+            }
+            // Info, multi-info, default parameters and sync markers carry
+            // nothing the log views use; unknown types are skipped by size
+            // as the format requires.
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Parse a PX4 ULog file. A partial message at the end of the file, or just
+/// before appended data, is skipped rather than failing the parse.
+pub fn parse_ulog(bytes: &[u8]) -> Result<Ulog, String> {
+    if bytes.len() < ULOG_HEADER_LEN || bytes[..7] != ULOG_MAGIC {
+        return Err("not a ULog file".to_string());
+    }
+    let start_usec = read_u64(bytes, 8).unwrap_or(0);
+    let mut parser = Parser {
+        now_usec: start_usec,
+        ..Parser::default()
+    };
+    let mut pos = ULOG_HEADER_LEN;
+    let mut truncated = false;
+    while pos < bytes.len() {
+        let limit = parser
+            .appended_offsets
+            .iter()
+            .copied()
+            .filter(|offset| *offset > pos)
+            .min()
+            .unwrap_or(bytes.len())
+            .min(bytes.len());
+        let size = read_u16(bytes, pos).map(usize::from);
+        let end = size.map(|size| pos + MESSAGE_HEADER_LEN + size);
+        match end {
+            Some(end) if end <= limit => {
+                let kind = bytes[pos + 2];
+                parser.message(kind, &bytes[pos + MESSAGE_HEADER_LEN..end])?;
+                pos = end;
+            }
+            // A message cut short by appended data: resume at the offset.
+            _ if limit < bytes.len() => pos = limit,
+            _ => {
+                truncated = true;
+                break;
+            }
+        }
+    }
+    Ok(Ulog {
+        start_usec,
+        records: parser.records,
+        truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASIC: &[u8] = include_bytes!("../tests/fixtures/ulog/basic.ulg");
+    const APPENDED: &[u8] = include_bytes!("../tests/fixtures/ulog/appended.ulg");
+
+    fn data<'a>(log: &'a Ulog, topic: &str) -> Vec<(u64, u8, &'a [(String, UlogValue)])> {
+        log.records
+            .iter()
+            .filter_map(|record| match record {
+                UlogRecord::Data {
+                    timestamp_usec,
+                    name,
+                    multi_id,
+                    fields,
+                } if name == topic => Some((*timestamp_usec, *multi_id, fields.as_slice())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn number(fields: &[(String, UlogValue)], name: &str) -> f64 {
+        match fields.iter().find(|(field, _)| field == name) {
+            Some((_, UlogValue::Number(value))) => *value,
+            other => panic!("{name}: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_files_without_the_ulog_magic() {
+        assert!(parse_ulog(b"not a ulog file at all").is_err());
+        assert!(parse_ulog(&BASIC[..10]).is_err());
+    }
+
+    #[test]
+    fn data_messages_decode_with_flattened_field_names() {
+        let log = parse_ulog(BASIC).expect("basic fixture");
+        assert_eq!(log.start_usec, 1_000_000);
+
+        let attitude = data(&log, "vehicle_attitude");
+        assert_eq!(attitude.len(), 3);
+        let (timestamp, multi_id, fields) = attitude[1];
+        assert_eq!((timestamp, multi_id), (2_100_000, 0));
+        assert!((number(fields, "q[0]") - 0.9239).abs() < 1e-4);
+        assert!((number(fields, "q[3]") - 0.3827).abs() < 1e-4);
+        assert!(fields.iter().all(|(name, _)| !name.starts_with("_padding")));
+
+        let batteries = data(&log, "battery_status");
+        assert_eq!(batteries.iter().filter(|(_, id, _)| *id == 1).count(), 1);
+        assert!((number(batteries[0].2, "voltage_v") - 16.4).abs() < 1e-4);
+
+        // Nested formats flatten under their field name.
+        let esc = data(&log, "esc_status");
+        assert_eq!(number(esc[0].2, "esc[1].esc_rpm"), 5200.0);
+        assert_eq!(
+            esc[0].2.iter().find(|(name, _)| name == "name"),
+            Some(&("name".to_string(), UlogValue::Text("quad".to_string())))
+        );
+    }
+
+    #[test]
+    fn parameters_logged_messages_and_dropouts_are_records() {
+        let log = parse_ulog(BASIC).expect("basic fixture");
+        let params: Vec<_> = log
+            .records
+            .iter()
+            .filter_map(|record| match record {
+                UlogRecord::Parameter {
+                    timestamp_usec,
+                    name,
+                    value,
+                } => Some((*timestamp_usec, name.as_str(), *value)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                (1_000_000, "MPC_XY_VEL_MAX", 12.0),
+                (1_000_000, "SYS_AUTOSTART", 4001.0),
+                (2_100_000, "MPC_XY_VEL_MAX", 8.0),
+            ]
+        );
+        assert!(log.records.contains(&UlogRecord::LoggedMessage {
+            timestamp_usec: 2_050_000,
+            level: 4,
+            text: "Takeoff detected".to_string(),
+        }));
+        assert!(log.records.contains(&UlogRecord::LoggedMessage {
+            timestamp_usec: 2_150_000,
+            level: 3,
+            text: "Battery low".to_string(),
+        }));
+        assert!(log.records.contains(&UlogRecord::Dropout {
+            timestamp_usec: 2_100_000,
+            duration_ms: 120,
+        }));
+    }
+
+    #[test]
+    fn a_trailing_partial_message_truncates_the_log() {
+        let log = parse_ulog(BASIC).expect("basic fixture");
+        assert!(log.truncated);
+        assert_eq!(data(&log, "vehicle_attitude").last().unwrap().0, 2_200_000);
+    }
+
+    #[test]
+    fn appended_data_is_read_after_the_gap_before_its_offset() {
+        let log = parse_ulog(APPENDED).expect("appended fixture");
+        assert!(!log.truncated);
+        let timestamps: Vec<u64> = data(&log, "vehicle_attitude")
+            .iter()
+            .map(|(timestamp, _, _)| *timestamp)
+            .collect();
+        assert_eq!(timestamps, vec![2_000_000, 3_000_000]);
+    }
+}
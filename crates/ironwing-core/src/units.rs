@@ -0,0 +1,283 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::ipc::telemetry::TelemetryState;
+use crate::ipc::units::{DisplayValue, DistanceUnit, SpeedUnit, TemperatureUnit, UnitSystem};
+
+const FEET_PER_METER: f64 = 1.0 / 0.3048;
+const KMH_PER_MPS: f64 = 3.6;
+const MPH_PER_MPS: f64 = 3600.0 / 1609.344;
+const KNOTS_PER_MPS: f64 = 3600.0 / 1852.0;
+
+impl DistanceUnit {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::M => "m",
+            Self::Ft => "ft",
+        }
+    }
+
+    pub fn convert_meters(self, meters: f64) -> f64 {
+        match self {
+            Self::M => meters,
+            Self::Ft => meters * FEET_PER_METER,
+        }
+    }
+}
+
+impl SpeedUnit {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Ms => "m/s",
+            Self::Kmh => "km/h",
+            Self::Mph => "mph",
+            Self::Kn => "kn",
+        }
+    }
+
+    pub fn convert_mps(self, mps: f64) -> f64 {
+        match self {
+            Self::Ms => mps,
+            Self::Kmh => mps * KMH_PER_MPS,
+            Self::Mph => mps * MPH_PER_MPS,
+            Self::Kn => mps * KNOTS_PER_MPS,
+        }
+    }
+}
+
+impl TemperatureUnit {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::C => "°C",
+            Self::F => "°F",
+        }
+    }
+
+    pub fn convert_celsius(self, celsius: f64) -> f64 {
+        match self {
+            Self::C => celsius,
+            Self::F => celsius * 9.0 / 5.0 + 32.0,
+        }
+    }
+}
+
+impl UnitSystem {
+    pub fn is_si(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quantity {
+    Distance,
+    Speed,
+    Temperature,
+}
+
+impl Quantity {
+    fn convert(self, units: &UnitSystem, value: f64) -> f64 {
+        match self {
+            Self::Distance => units.distance.convert_meters(value),
+            Self::Speed => units.speed.convert_mps(value),
+            Self::Temperature => units.temperature.convert_celsius(value),
+        }
+    }
+
+    fn symbol(self, units: &UnitSystem) -> &'static str {
+        match self {
+            Self::Distance => units.distance.symbol(),
+            Self::Speed => units.speed.symbol(),
+            Self::Temperature => units.temperature.symbol(),
+        }
+    }
+}
+
+/// Log fields with a known SI unit, by message type. Fields not listed are
+/// passed through unconverted.
+const LOG_FIELD_QUANTITIES: &[(&str, &str, Quantity)] = &[
+    ("ARSP", "Airspeed", Quantity::Speed),
+    ("BARO", "Alt", Quantity::Distance),
+    ("BARO", "Temp", Quantity::Temperature),
+    ("CTUN", "Alt", Quantity::Distance),
+    ("CTUN", "BAlt", Quantity::Distance),
+    ("CTUN", "DAlt", Quantity::Distance),
+    ("GPS", "Alt", Quantity::Distance),
+    ("GPS", "Spd", Quantity::Speed),
+    ("GPS", "VZ", Quantity::Speed),
+    ("POS", "Alt", Quantity::Distance),
+    ("POS", "RelHomeAlt", Quantity::Distance),
+    ("VFR_HUD", "airspeed", Quantity::Speed),
+    ("VFR_HUD", "alt", Quantity::Distance),
+    ("VFR_HUD", "climb", Quantity::Speed),
+    ("VFR_HUD", "groundspeed", Quantity::Speed),
+];
+
+/// Convert the known fields of one log message in place and return the unit
+/// of each converted field.
+pub fn convert_log_fields(
+    msg_type: &str,
+    fields: &mut HashMap<String, f64>,
+    units: &UnitSystem,
+) -> BTreeMap<String, String> {
+    let mut field_units = BTreeMap::new();
+    for (_, field, quantity) in LOG_FIELD_QUANTITIES
+        .iter()
+        .filter(|(msg, _, _)| *msg == msg_type)
+    {
+        if let Some(value) = fields.get_mut(*field) {
+            *value = quantity.convert(units, *value);
+            field_units.insert(field.to_string(), quantity.symbol(units).to_string());
+        }
+    }
+    field_units
+}
+
+/// The unit-bearing fields of `state` converted to `units`, keyed by
+/// `group.field`. `state` itself is left in SI, so a field named `_m` or
+/// `_mps` always holds meters or meters per second.
+pub fn display_values(
+    state: &TelemetryState,
+    units: &UnitSystem,
+) -> BTreeMap<String, DisplayValue> {
+    let fields = [
+        (
+            "flight.altitude_m",
+            state.flight.altitude_m,
+            Quantity::Distance,
+        ),
+        ("flight.speed_mps", state.flight.speed_mps, Quantity::Speed),
+        (
+            "flight.climb_rate_mps",
+            state.flight.climb_rate_mps,
+            Quantity::Speed,
+        ),
+        (
+            "flight.airspeed_mps",
+            state.flight.airspeed_mps,
+            Quantity::Speed,
+        ),
+        (
+            "navigation.wp_dist_m",
+            state.navigation.wp_dist_m,
+            Quantity::Distance,
+        ),
+        (
+            "navigation.xtrack_error_m",
+            state.navigation.xtrack_error_m,
+            Quantity::Distance,
+        ),
+        (
+            "terrain.terrain_height_m",
+            state.terrain.terrain_height_m,
+            Quantity::Distance,
+        ),
+        (
+            "terrain.height_above_terrain_m",
+            state.terrain.height_above_terrain_m,
+            Quantity::Distance,
+        ),
+        (
+            "derived.wind_speed_mps",
+            state.derived.wind_speed_mps,
+            Quantity::Speed,
+        ),
+        (
+            "derived.home_distance_m",
+            state.derived.home_distance_m,
+            Quantity::Distance,
+        ),
+    ];
+    fields
+        .into_iter()
+        .map(|(path, value, quantity)| {
+            (
+                path.to_string(),
+                DisplayValue {
+                    value: value.map(|value| quantity.convert(units, value)),
+                    unit: quantity.symbol(units).to_string(),
+                },
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn every_unit_converts_from_si() {
+        for (unit, expected, symbol) in [
+            (DistanceUnit::M, 100.0, "m"),
+            (DistanceUnit::Ft, 328.083_989_501_312_3, "ft"),
+        ] {
+            assert_close(unit.convert_meters(100.0), expected);
+            assert_eq!(unit.symbol(), symbol);
+        }
+        for (unit, expected, symbol) in [
+            (SpeedUnit::Ms, 10.0, "m/s"),
+            (SpeedUnit::Kmh, 36.0, "km/h"),
+            (SpeedUnit::Mph, 22.369_362_920_544_02, "mph"),
+            (SpeedUnit::Kn, 19.438_444_924_406_05, "kn"),
+        ] {
+            assert_close(unit.convert_mps(10.0), expected);
+            assert_eq!(unit.symbol(), symbol);
+        }
+        for (unit, celsius, expected, symbol) in [
+            (TemperatureUnit::C, 25.0, 25.0, "°C"),
+            (TemperatureUnit::F, 25.0, 77.0, "°F"),
+            (TemperatureUnit::F, -40.0, -40.0, "°F"),
+            (TemperatureUnit::F, 0.0, 32.0, "°F"),
+        ] {
+            assert_close(unit.convert_celsius(celsius), expected);
+            assert_eq!(unit.symbol(), symbol);
+        }
+        assert!(UnitSystem::default().is_si());
+    }
+
+    #[test]
+    fn display_values_convert_without_touching_si_fields() {
+        let units = UnitSystem {
+            distance: DistanceUnit::Ft,
+            speed: SpeedUnit::Kn,
+            temperature: TemperatureUnit::F,
+        };
+        let mut state = TelemetryState::default();
+        state.flight.altitude_m = Some(30.48);
+        state.flight.speed_mps = Some(1852.0 / 3600.0);
+
+        let display = display_values(&state, &units);
+
+        assert_close(display["flight.altitude_m"].value.unwrap(), 100.0);
+        assert_eq!(display["flight.altitude_m"].unit, "ft");
+        assert_close(display["flight.speed_mps"].value.unwrap(), 1.0);
+        assert_eq!(display["flight.airspeed_mps"].value, None);
+        assert_eq!(display["derived.wind_speed_mps"].unit, "kn");
+        assert_eq!(state.flight.altitude_m, Some(30.48));
+    }
+
+    #[test]
+    fn log_fields_convert_only_known_fields() {
+        let units = UnitSystem {
+            distance: DistanceUnit::Ft,
+            speed: SpeedUnit::Kmh,
+            temperature: TemperatureUnit::F,
+        };
+        let mut fields = HashMap::from([
+            ("Alt".to_string(), 3.048),
+            ("Temp".to_string(), 100.0),
+            ("Press".to_string(), 101_325.0),
+        ]);
+
+        let field_units = convert_log_fields("BARO", &mut fields, &units);
+
+        assert_close(fields["Alt"], 10.0);
+        assert_close(fields["Temp"], 212.0);
+        assert_eq!(fields["Press"], 101_325.0);
+        assert_eq!(field_units.len(), 2);
+        assert_eq!(field_units["Temp"], "°F");
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use web_time::Instant;
+
+use crate::ipc::UnknownMessage;
+use crate::mavlink_frames::UnknownFrame;
+
+/// Shortest gap between two `mavlink://unknown` events for one message.
+pub const UNKNOWN_MESSAGE_EMIT_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+struct Seen {
+    count: u64,
+    /// Count and time at the last emit, for the rate.
+    emitted: Option<(u64, Instant)>,
+}
+
+/// Counts messages outside the dialect per source and message id and
+/// decides when each is worth another event.
+#[derive(Debug, Clone, Default)]
+pub struct UnknownMessageTracker {
+    seen: HashMap<(u8, u8, u32), Seen>,
+}
+
+impl UnknownMessageTracker {
+    /// The event to emit for `frame`, if its message is due one. The first
+    /// of each goes out straight away.
+    pub fn observe(&mut self, frame: UnknownFrame, now: Instant) -> Option<UnknownMessage> {
+        let key = (frame.system_id, frame.component_id, frame.message_id);
+        let seen = self.seen.entry(key).or_insert(Seen {
+            count: 0,
+            emitted: None,
+        });
+        seen.count += 1;
+        let rate_hz = match seen.emitted {
+            None => None,
+            Some((_, at)) if now.saturating_duration_since(at) < UNKNOWN_MESSAGE_EMIT_INTERVAL => {
+                return None;
+            }
+            Some((count, at)) => {
+                Some((seen.count - count) as f64 / now.saturating_duration_since(at).as_secs_f64())
+            }
+        };
+        seen.emitted = Some((seen.count, now));
+        Some(UnknownMessage {
+            system_id: frame.system_id,
+            component_id: frame.component_id,
+            message_id: frame.message_id,
+            payload: frame.payload,
+            count: seen.count,
+            rate_hz,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.seen.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(message_id: u32) -> UnknownFrame {
+        UnknownFrame {
+            system_id: 1,
+            component_id: 1,
+            message_id,
+            payload: vec![1, 2, 3],
+        }
+    }
+
+    #[test]
+    fn emits_once_a_second_with_the_rate_in_between() {
+        let mut tracker = UnknownMessageTracker::default();
+        let start = Instant::now();
+        let first = tracker.observe(frame(77_001), start).expect("first");
+        assert_eq!((first.count, first.rate_hz), (1, None));
+
+        // 10 Hz for a second: nine are held back, the tenth reports.
+        let mut emitted = Vec::new();
+        for tick in 1..=10 {
+            let at = start + Duration::from_millis(100 * tick);
+            emitted.extend(tracker.observe(frame(77_001), at));
+        }
+        assert_eq!(emitted.len(), 1);
+        assert_eq!(emitted[0].count, 11);
+        assert_eq!(emitted[0].rate_hz, Some(10.0));
+
+        // Another message id is tracked on its own.
+        assert!(tracker.observe(frame(77_002), start).is_some());
+    }
+}
@@ -1,5 +1,10 @@
 use std::time::Duration;
 
+use crate::component_command::CommandRetryPolicy;
+use crate::ipc::connection::{
+    ConnectTransport, GcsIdentity, LinkTimeoutOverrides, LinkTimeoutProfile, LinkTimeouts,
+};
+
 /// Build a MAVKit vehicle config for normal live links.
 ///
 /// This helper intentionally keeps MAVKit's init policy defaults intact. In
@@ -30,3 +35,243 @@ pub fn adapter_vehicle_config(
         ..mavkit::VehicleConfig::default()
     }
 }
+
+/// Apply the user's GCS identity to a config built by one of the helpers above.
+///
+/// MAVKit's event loop sends the 1 Hz GCS HEARTBEAT with these IDs, so the
+/// identity must be fixed before the vehicle connects.
+pub fn with_gcs_identity(config: mavkit::VehicleConfig, gcs: GcsIdentity) -> mavkit::VehicleConfig {
+    mavkit::VehicleConfig {
+        gcs_system_id: gcs.system_id,
+        gcs_component_id: gcs.component_id,
+        ..config
+    }
+}
+
+/// The timeout profile for a link opened on `transport`. WebSocket runs over
+/// TCP, and the in-process demo vehicle never drops a frame either.
+pub fn link_timeout_profile(transport: &ConnectTransport) -> LinkTimeoutProfile {
+    match transport {
+        ConnectTransport::Udp { .. } => LinkTimeoutProfile::Udp,
+        ConnectTransport::Tcp { .. }
+        | ConnectTransport::WebSocket { .. }
+        | ConnectTransport::Demo { .. } => LinkTimeoutProfile::Tcp,
+        ConnectTransport::Serial { .. }
+        | ConnectTransport::UsbSerial { .. }
+        | ConnectTransport::WebSerial { .. } => LinkTimeoutProfile::Serial,
+        ConnectTransport::BluetoothBle { .. } | ConnectTransport::WebBluetooth { .. } => {
+            LinkTimeoutProfile::Ble
+        }
+        ConnectTransport::BluetoothSpp { .. } => LinkTimeoutProfile::Spp,
+    }
+}
+
+/// Built-in timeouts for `profile`. None gives a command less time in total
+/// than [`CommandRetryPolicy::default`]; UDP spends it on more, closer
+/// resends since a lost frame is the likely reason for a missing ACK. BLE
+/// moves MAVLink in 20-byte chunks, so an ACK can take well over a second to
+/// come back.
+pub fn default_link_timeouts(profile: LinkTimeoutProfile) -> LinkTimeouts {
+    let (command_ack_ms, command_attempts, command_completion_ms, transfer_ms) = match profile {
+        LinkTimeoutProfile::Udp => (500, 6, 10_000, 3_000),
+        LinkTimeoutProfile::Tcp => (1_500, 2, 10_000, 3_000),
+        LinkTimeoutProfile::Serial => (1_000, 3, 10_000, 5_000),
+        LinkTimeoutProfile::Spp => (2_000, 3, 15_000, 8_000),
+        LinkTimeoutProfile::Ble => (2_500, 3, 20_000, 15_000),
+    };
+    LinkTimeouts {
+        command_ack_ms,
+        command_attempts,
+        command_completion_ms,
+        transfer_ms,
+    }
+}
+
+/// The timeouts a link on `profile` uses: the user's override when set,
+/// otherwise the built-in ones.
+pub fn link_timeouts(
+    profile: LinkTimeoutProfile,
+    overrides: &LinkTimeoutOverrides,
+) -> LinkTimeouts {
+    let override_ = match profile {
+        LinkTimeoutProfile::Udp => overrides.udp,
+        LinkTimeoutProfile::Tcp => overrides.tcp,
+        LinkTimeoutProfile::Serial => overrides.serial,
+        LinkTimeoutProfile::Ble => overrides.ble,
+        LinkTimeoutProfile::Spp => overrides.spp,
+    };
+    override_.unwrap_or_else(|| default_link_timeouts(profile))
+}
+
+pub fn validate_link_timeout_overrides(overrides: &LinkTimeoutOverrides) -> Result<(), String> {
+    let profiles = [
+        ("udp", overrides.udp),
+        ("tcp", overrides.tcp),
+        ("serial", overrides.serial),
+        ("ble", overrides.ble),
+        ("spp", overrides.spp),
+    ];
+    for (name, timeouts) in profiles {
+        let Some(timeouts) = timeouts else {
+            continue;
+        };
+        if !(100..=30_000).contains(&timeouts.command_ack_ms) {
+            return Err(format!(
+                "{name} command ACK timeout must be between 100 and 30000 ms"
+            ));
+        }
+        if !(1..=10).contains(&timeouts.command_attempts) {
+            return Err(format!("{name} command attempts must be between 1 and 10"));
+        }
+        if timeouts.command_completion_ms < timeouts.command_ack_ms {
+            return Err(format!(
+                "{name} command completion timeout must not be shorter than the ACK timeout"
+            ));
+        }
+        if !(500..=120_000).contains(&timeouts.transfer_ms) {
+            return Err(format!(
+                "{name} transfer timeout must be between 500 and 120000 ms"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Apply link timeouts to a config built by one of the helpers above. MAVKit
+/// bounds a whole command, so its timeout covers every attempt.
+pub fn with_link_timeouts(
+    config: mavkit::VehicleConfig,
+    timeouts: &LinkTimeouts,
+) -> mavkit::VehicleConfig {
+    let command_ms = u64::from(timeouts.command_ack_ms) * u64::from(timeouts.command_attempts);
+    mavkit::VehicleConfig {
+        command_timeout: Duration::from_millis(command_ms),
+        command_completion_timeout: Duration::from_millis(timeouts.command_completion_ms.into()),
+        transfer_timeout: Duration::from_millis(timeouts.transfer_ms.into()),
+        ..config
+    }
+}
+
+/// The resend policy for COMMAND_LONG exchanges IronWing runs itself.
+pub fn command_retry_policy(timeouts: &LinkTimeouts) -> CommandRetryPolicy {
+    CommandRetryPolicy {
+        attempts: timeouts.command_attempts,
+        spacing: Duration::from_millis(timeouts.command_ack_ms.into()),
+        in_progress_timeout: Duration::from_millis(timeouts.command_completion_ms.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dialect::MavCmd;
+
+    use super::*;
+    use crate::component_command::{CommandExchange, ComponentCommand, ExchangeEvent, ObservedAck};
+
+    /// Run one arm command against a vehicle that answers every send after
+    /// `ack_delay`, stepping a simulated clock the way the exchange runner
+    /// waits. True when an ACK lands before the exchange gives up.
+    fn acked_within_policy(profile: LinkTimeoutProfile, ack_delay: Duration) -> bool {
+        let command = ComponentCommand {
+            system_id: 1,
+            component_id: 1,
+            command: MavCmd::MAV_CMD_COMPONENT_ARM_DISARM,
+            params: [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        };
+        let ack = ObservedAck {
+            source_system: 1,
+            source_component: 1,
+            command: command.command as u16,
+            result: 0,
+            progress: 0,
+        };
+        let policy = command_retry_policy(&default_link_timeouts(profile));
+        let mut exchange = CommandExchange::new(command, policy);
+        let mut now = Duration::ZERO;
+        let mut acks_due = Vec::new();
+        while exchange.next_send().is_some() {
+            acks_due.push(now + ack_delay);
+            let deadline = now + exchange.wait_timeout();
+            if acks_due.iter().any(|due| *due <= deadline) {
+                return exchange.on_ack(&ack) == ExchangeEvent::Finished(Ok(()));
+            }
+            now = deadline;
+        }
+        false
+    }
+
+    #[test]
+    fn no_profile_gives_up_sooner_than_the_default_policy() {
+        let total = |policy: CommandRetryPolicy| policy.spacing * u32::from(policy.attempts);
+        let floor = total(CommandRetryPolicy::default());
+        for profile in [
+            LinkTimeoutProfile::Udp,
+            LinkTimeoutProfile::Tcp,
+            LinkTimeoutProfile::Serial,
+            LinkTimeoutProfile::Spp,
+            LinkTimeoutProfile::Ble,
+        ] {
+            let policy = command_retry_policy(&default_link_timeouts(profile));
+            assert!(total(policy) >= floor, "{profile:?}");
+        }
+    }
+
+    #[test]
+    fn a_slow_ack_succeeds_over_ble_but_times_out_over_udp() {
+        let delay = Duration::from_millis(3_500);
+        assert!(acked_within_policy(LinkTimeoutProfile::Ble, delay));
+        assert!(acked_within_policy(LinkTimeoutProfile::Spp, delay));
+        assert!(!acked_within_policy(LinkTimeoutProfile::Udp, delay));
+        assert!(acked_within_policy(
+            LinkTimeoutProfile::Udp,
+            Duration::from_millis(100)
+        ));
+    }
+
+    #[test]
+    fn profiles_follow_the_transport_and_overrides_win() {
+        let ble = ConnectTransport::BluetoothBle {
+            address: "AA:BB".into(),
+            profile: None,
+        };
+        let udp = ConnectTransport::Udp {
+            bind_addr: "0.0.0.0:14550".into(),
+        };
+        assert_eq!(link_timeout_profile(&ble), LinkTimeoutProfile::Ble);
+        assert_eq!(link_timeout_profile(&udp), LinkTimeoutProfile::Udp);
+        let tcp = ConnectTransport::Tcp {
+            address: "127.0.0.1:5760".into(),
+        };
+        assert_eq!(link_timeout_profile(&tcp), LinkTimeoutProfile::Tcp);
+
+        let custom = LinkTimeouts {
+            command_ack_ms: 5_000,
+            command_attempts: 2,
+            command_completion_ms: 30_000,
+            transfer_ms: 20_000,
+        };
+        let overrides = LinkTimeoutOverrides {
+            ble: Some(custom),
+            ..LinkTimeoutOverrides::default()
+        };
+        assert_eq!(link_timeouts(LinkTimeoutProfile::Ble, &overrides), custom);
+        assert_eq!(
+            link_timeouts(LinkTimeoutProfile::Udp, &overrides),
+            default_link_timeouts(LinkTimeoutProfile::Udp)
+        );
+        assert!(validate_link_timeout_overrides(&overrides).is_ok());
+
+        let config = with_link_timeouts(mavkit::VehicleConfig::default(), &custom);
+        assert_eq!(config.command_timeout, Duration::from_secs(10));
+        assert_eq!(config.transfer_timeout, Duration::from_secs(20));
+
+        let too_short = LinkTimeoutOverrides {
+            udp: Some(LinkTimeouts {
+                command_ack_ms: 10,
+                ..custom
+            }),
+            ..LinkTimeoutOverrides::default()
+        };
+        assert!(validate_link_timeout_overrides(&too_short).is_err());
+    }
+}
@@ -1,10 +1,29 @@
+use std::time::Duration;
+
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use crate::ipc::telemetry::TelemetryFreshness;
 use crate::ipc::{
     DomainProvenance, TelemetrySnapshot, VehicleState, telemetry_snapshot_from_value,
 };
+use crate::telemetry::{HIGH_LATENCY_STALE_AFTER, group_freshness};
+
+pub fn unix_epoch_usec() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_micros() as u64)
+}
 
+fn newest(ages: &[Option<Duration>]) -> Option<Duration> {
+    ages.iter().flatten().min().copied()
+}
+
+/// Telemetry from the vehicle's watches. A group with no sample newer than
+/// `stale_after` is marked stale.
 pub fn telemetry_snapshot_from_vehicle(
     vehicle: &mavkit::Vehicle,
     provenance: DomainProvenance,
+    stale_after: Duration,
 ) -> TelemetrySnapshot {
     let telemetry = vehicle.telemetry();
     let position_global = telemetry.position().global();
@@ -75,7 +94,75 @@ pub fn telemetry_snapshot_from_vehicle(
         "servo_outputs": (!servo_output_values.is_empty()).then_some(servo_output_values),
     });
 
-    telemetry_snapshot_from_value(&snapshot, provenance)
+    let age_of_global = position_global.latest().map(|s| s.received_at.elapsed());
+    let age_of_heading = heading.latest().map(|s| s.received_at.elapsed());
+    let flight_ages = [
+        age_of_global,
+        groundspeed.latest().map(|s| s.received_at.elapsed()),
+        airspeed.latest().map(|s| s.received_at.elapsed()),
+        climb_rate.latest().map(|s| s.received_at.elapsed()),
+        throttle.latest().map(|s| s.received_at.elapsed()),
+    ];
+    let navigation_ages = [
+        age_of_global,
+        age_of_heading,
+        nav_wp.latest().map(|s| s.received_at.elapsed()),
+        nav_guidance.latest().map(|s| s.received_at.elapsed()),
+    ];
+    let power_ages = [
+        bat_remaining.latest().map(|s| s.received_at.elapsed()),
+        bat_voltage.latest().map(|s| s.received_at.elapsed()),
+        bat_current.latest().map(|s| s.received_at.elapsed()),
+        bat_cells.latest().map(|s| s.received_at.elapsed()),
+    ];
+    let radio_ages: Vec<Option<Duration>> = rc_channels
+        .iter()
+        .map(|channel| channel.latest().map(|s| s.received_at.elapsed()))
+        .chain(std::iter::once(
+            rc_rssi.latest().map(|s| s.received_at.elapsed()),
+        ))
+        .chain(
+            servo_outputs
+                .iter()
+                .map(|servo| servo.latest().map(|s| s.received_at.elapsed())),
+        )
+        .collect();
+
+    let now_usec = unix_epoch_usec();
+    let freshness_of =
+        |ages: &[Option<Duration>]| group_freshness(newest(ages), now_usec, stale_after);
+    let freshness = TelemetryFreshness {
+        flight: freshness_of(&flight_ages),
+        navigation: freshness_of(&navigation_ages),
+        attitude: freshness_of(&[attitude.latest().map(|s| s.received_at.elapsed())]),
+        power: freshness_of(&power_ages),
+        gps: freshness_of(&[gps_quality.latest().map(|s| s.received_at.elapsed())]),
+        terrain: freshness_of(&[terrain_clearance.latest().map(|s| s.received_at.elapsed())]),
+        radio: freshness_of(&radio_ages),
+    };
+
+    let mut telemetry = telemetry_snapshot_from_value(&snapshot, provenance);
+    if let Some(state) = telemetry.value.as_mut() {
+        state.freshness = freshness;
+    }
+    telemetry
+}
+
+/// Freshness for a HIGH_LATENCY2-sourced snapshot: the groups the report fills
+/// share its age, everything else has no data on a high-latency link.
+pub fn high_latency_freshness(report_age: Duration) -> TelemetryFreshness {
+    let now_usec = unix_epoch_usec();
+    let reported = group_freshness(Some(report_age), now_usec, HIGH_LATENCY_STALE_AFTER);
+    let missing = group_freshness(None, now_usec, HIGH_LATENCY_STALE_AFTER);
+    TelemetryFreshness {
+        flight: reported,
+        navigation: reported,
+        attitude: missing,
+        power: reported,
+        gps: missing,
+        terrain: missing,
+        radio: missing,
+    }
 }
 
 pub fn seeded_vehicle_state(vehicle: &mavkit::Vehicle) -> VehicleState {
@@ -0,0 +1,165 @@
+use crate::component_command::ComponentCommand;
+use crate::dialect::{MavCmd, VIDEO_STREAM_INFORMATION_DATA};
+use crate::ipc::VideoStream;
+use crate::named_values::mavlink_name;
+
+pub const VIDEO_STREAM_INFORMATION_MESSAGE_ID: u32 = 269;
+
+/// MAV_COMP_ID_ALL, so every camera on the system answers.
+const ALL_COMPONENTS: u8 = 0;
+
+pub fn video_stream_from_message(
+    data: &VIDEO_STREAM_INFORMATION_DATA,
+    component_id: u8,
+) -> VideoStream {
+    let stream_type = format!("{:?}", data.mavtype);
+    VideoStream {
+        component_id,
+        stream_id: data.stream_id,
+        count: data.count,
+        name: mavlink_name(&data.name[..]),
+        uri: mavlink_name(&data.uri[..]),
+        stream_type: stream_type
+            .trim_start_matches("VIDEO_STREAM_TYPE_")
+            .to_ascii_lowercase(),
+        resolution_h: data.resolution_h,
+        resolution_v: data.resolution_v,
+        bitrate_bps: data.bitrate,
+        rotation_deg: data.rotation,
+        framerate_hz: data.framerate,
+    }
+}
+
+/// Insert or replace `stream` keeping the list ordered by component and
+/// stream id. Returns whether the list changed.
+pub fn upsert_video_stream(streams: &mut Vec<VideoStream>, stream: VideoStream) -> bool {
+    let key = (stream.component_id, stream.stream_id);
+    match streams.binary_search_by_key(&key, |existing| (existing.component_id, existing.stream_id))
+    {
+        Ok(index) if streams[index] == stream => false,
+        Ok(index) => {
+            streams[index] = stream;
+            true
+        }
+        Err(index) => {
+            streams.insert(index, stream);
+            true
+        }
+    }
+}
+
+/// Streams a component has announced through `count` but not yet described,
+/// as (component id, stream id).
+pub fn missing_video_streams(streams: &[VideoStream]) -> Vec<(u8, u8)> {
+    let mut missing = Vec::new();
+    let mut components: Vec<(u8, u8)> = streams
+        .iter()
+        .map(|stream| (stream.component_id, stream.count))
+        .collect();
+    components.dedup_by_key(|(component_id, _)| *component_id);
+    for (component_id, count) in components {
+        for stream_id in 1..=count {
+            let known = streams
+                .iter()
+                .any(|stream| stream.component_id == component_id && stream.stream_id == stream_id);
+            if !known {
+                missing.push((component_id, stream_id));
+            }
+        }
+    }
+    missing
+}
+
+/// MAV_CMD_REQUEST_MESSAGE for VIDEO_STREAM_INFORMATION. Stream id 0 asks for
+/// all streams; `None` addresses every component.
+pub fn request_video_stream(
+    system_id: u8,
+    component_id: Option<u8>,
+    stream_id: u8,
+) -> ComponentCommand {
+    ComponentCommand {
+        system_id,
+        component_id: component_id.unwrap_or(ALL_COMPONENTS),
+        command: MavCmd::MAV_CMD_REQUEST_MESSAGE,
+        params: [
+            VIDEO_STREAM_INFORMATION_MESSAGE_ID as f32,
+            f32::from(stream_id),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dialect::VideoStreamType;
+
+    use super::*;
+
+    fn padded<const N: usize>(text: &str) -> [u8; N] {
+        let mut bytes = [0_u8; N];
+        bytes[..text.len()].copy_from_slice(text.as_bytes());
+        bytes
+    }
+
+    fn stream(component_id: u8, stream_id: u8, count: u8) -> VideoStream {
+        VideoStream {
+            component_id,
+            stream_id,
+            count,
+            name: String::new(),
+            uri: String::new(),
+            stream_type: "rtsp".to_string(),
+            resolution_h: 0,
+            resolution_v: 0,
+            bitrate_bps: 0,
+            rotation_deg: 0,
+            framerate_hz: 0.0,
+        }
+    }
+
+    #[test]
+    fn stream_information_decodes_uri_and_format() {
+        let data = VIDEO_STREAM_INFORMATION_DATA {
+            stream_id: 1,
+            count: 1,
+            mavtype: VideoStreamType::VIDEO_STREAM_TYPE_RTSP,
+            framerate: 30.0,
+            resolution_h: 1920,
+            resolution_v: 1080,
+            bitrate: 4_000_000,
+            rotation: 180,
+            name: padded::<32>("Main").into(),
+            uri: padded::<160>("rtsp://192.168.0.10:8554/H264Video").into(),
+            ..Default::default()
+        };
+
+        let stream = video_stream_from_message(&data, 100);
+
+        assert_eq!(stream.uri, "rtsp://192.168.0.10:8554/H264Video");
+        assert_eq!(stream.name, "Main");
+        assert_eq!(stream.stream_type, "rtsp");
+        assert_eq!((stream.resolution_h, stream.resolution_v), (1920, 1080));
+        assert_eq!(stream.rotation_deg, 180);
+    }
+
+    #[test]
+    fn upsert_orders_streams_and_ignores_repeats() {
+        let mut streams = Vec::new();
+        assert!(upsert_video_stream(&mut streams, stream(100, 2, 2)));
+        assert!(upsert_video_stream(&mut streams, stream(100, 1, 2)));
+        assert!(!upsert_video_stream(&mut streams, stream(100, 1, 2)));
+
+        let ids: Vec<u8> = streams.iter().map(|stream| stream.stream_id).collect();
+        assert_eq!(ids, [1, 2]);
+    }
+
+    #[test]
+    fn announced_but_undescribed_streams_are_missing() {
+        let streams = vec![stream(100, 1, 3), stream(101, 1, 1)];
+        assert_eq!(missing_video_streams(&streams), [(100, 2), (100, 3)]);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::ipc::{LandedState, VtolState, VtolStatus, VtolTransitionTarget};
+
+pub const EXTENDED_SYS_STATE_MESSAGE_ID: u32 = 245;
+
+/// MAV_VTOL_STATE values.
+const MAV_VTOL_STATE_TRANSITION_TO_FW: u8 = 1;
+const MAV_VTOL_STATE_TRANSITION_TO_MC: u8 = 2;
+const MAV_VTOL_STATE_MC: u8 = 3;
+const MAV_VTOL_STATE_FW: u8 = 4;
+
+pub fn vtol_status(vtol_state: u8, landed_state: u8) -> VtolStatus {
+    VtolStatus {
+        vtol_state: match vtol_state {
+            MAV_VTOL_STATE_TRANSITION_TO_FW => VtolState::TransitionToFixedWing,
+            MAV_VTOL_STATE_TRANSITION_TO_MC => VtolState::TransitionToMultiCopter,
+            MAV_VTOL_STATE_MC => VtolState::MultiCopter,
+            MAV_VTOL_STATE_FW => VtolState::FixedWing,
+            _ => VtolState::Undefined,
+        },
+        landed_state: match landed_state {
+            1 => LandedState::OnGround,
+            2 => LandedState::InAir,
+            3 => LandedState::Takeoff,
+            4 => LandedState::Landing,
+            _ => LandedState::Undefined,
+        },
+    }
+}
+
+/// EXTENDED_SYS_STATE from its raw payload: byte 0 is vtol_state, byte 1
+/// landed_state. MAVLink 2 trims trailing zeros, so either may be missing.
+pub fn vtol_status_from_payload(payload: &[u8]) -> VtolStatus {
+    vtol_status(
+        payload.first().copied().unwrap_or(0),
+        payload.get(1).copied().unwrap_or(0),
+    )
+}
+
+/// Whether the vehicle is a VTOL at all; other frames send
+/// EXTENDED_SYS_STATE with an undefined VTOL state.
+pub fn is_vtol(status: &VtolStatus) -> bool {
+    status.vtol_state != VtolState::Undefined
+}
+
+/// MAV_CMD_DO_VTOL_TRANSITION params: param1 is the MAV_VTOL_STATE asked
+/// for, param2 0 for a normal transition.
+pub fn transition_params(target: VtolTransitionTarget) -> [f32; 7] {
+    let state = match target {
+        VtolTransitionTarget::MultiCopter => MAV_VTOL_STATE_MC,
+        VtolTransitionTarget::FixedWing => MAV_VTOL_STATE_FW,
+    };
+    [f32::from(state), 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]
+}
+
+/// Transitions are refused until the vehicle has reported itself a VTOL,
+/// and while it says it is on the ground.
+pub fn ensure_transition_allowed(status: Option<VtolStatus>) -> Result<(), String> {
+    let Some(status) = status.filter(is_vtol) else {
+        return Err("vehicle has not reported a VTOL state; VTOL only".to_string());
+    };
+    if status.landed_state == LandedState::OnGround {
+        return Err("vehicle is on the ground; take off before transitioning".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_decodes_with_trailing_zeros_trimmed() {
+        assert_eq!(
+            vtol_status_from_payload(&[4, 2]),
+            VtolStatus {
+                vtol_state: VtolState::FixedWing,
+                landed_state: LandedState::InAir,
+            }
+        );
+        assert_eq!(
+            vtol_status_from_payload(&[3]).landed_state,
+            LandedState::Undefined
+        );
+        assert_eq!(
+            vtol_status_from_payload(&[]).vtol_state,
+            VtolState::Undefined
+        );
+    }
+
+    #[test]
+    fn transitions_need_a_flying_vtol() {
+        let status = |vtol_state, landed_state| Some(vtol_status(vtol_state, landed_state));
+        assert!(ensure_transition_allowed(status(3, 2)).is_ok());
+        assert!(ensure_transition_allowed(status(4, 4)).is_ok());
+        let landed = ensure_transition_allowed(status(3, 1)).unwrap_err();
+        assert!(landed.contains("on the ground"), "{landed}");
+        assert!(ensure_transition_allowed(status(0, 2)).is_err());
+        assert!(ensure_transition_allowed(None).is_err());
+        assert_eq!(transition_params(VtolTransitionTarget::FixedWing)[0], 4.0);
+        assert_eq!(transition_params(VtolTransitionTarget::MultiCopter)[0], 3.0);
+    }
+}
@@ -4,12 +4,16 @@ use std::time::Duration;
 
 use futures::channel::oneshot;
 use futures::future::{AbortHandle, Abortable};
+use ironwing_core::calibration_status::{
+    CALIBRATION_POLL_INTERVAL, CALIBRATION_RESULT_TIMEOUT, CalibrationFirmware, CalibrationWatch,
+    completes_on_ack,
+};
 use ironwing_core::event_names;
 use ironwing_core::ipc::{
-    AckSessionSnapshotResult, DomainProvenance, GuidedCommandResult, GuidedFailure,
-    GuidedFatalityScope, GuidedLiveContext, GuidedRuntime, MissionDownload, OperationFailure,
-    OperationId, RcOverrideChannelWire, Reason, ReasonKind, SourceKind, StartGuidedSessionRequest,
-    UpdateGuidedSessionRequest, operation_failure_json,
+    AckSessionSnapshotResult, CalibrationKind, CalibrationResult, DomainProvenance,
+    GuidedCommandResult, GuidedFailure, GuidedFatalityScope, GuidedLiveContext, GuidedRuntime,
+    MissionDownload, OperationFailure, OperationId, RcOverrideChannelWire, Reason, ReasonKind,
+    SourceKind, StartGuidedSessionRequest, UpdateGuidedSessionRequest, operation_failure_json,
 };
 use ironwing_core::live_runtime::commands as live_commands;
 use ironwing_core::live_runtime::{
@@ -416,7 +420,7 @@ impl IronwingWasmRuntime {
     }
 
     #[wasm_bindgen(js_name = paramWrite)]
-    pub async fn param_write(&self, name: String, value: f32) -> Result<JsValue, JsValue> {
+    pub async fn param_write(&self, name: String, value: f64) -> Result<JsValue, JsValue> {
         let vehicle = live_vehicle_for_write(&self.state, OperationId::ParamWrite)?;
         let result = live_commands::param_write(&vehicle, &name, value)
             .await
@@ -426,8 +430,16 @@ impl IronwingWasmRuntime {
 
     #[wasm_bindgen(js_name = paramWriteBatch)]
     pub async fn param_write_batch(&self, params: JsValue) -> Result<JsValue, JsValue> {
-        let params: Vec<(String, f32)> = from_js(params)?;
+        let params: Vec<(String, f64)> = from_js(params)?;
         let vehicle = live_vehicle_for_write(&self.state, OperationId::ParamWriteBatch)?;
+        let params = params
+            .into_iter()
+            .map(|(name, value)| {
+                let wire = live_commands::encode_param_write(&vehicle, &name, value)
+                    .map_err(|error| JsValue::from_str(&error.to_string()))?;
+                Ok((name, wire))
+            })
+            .collect::<Result<Vec<_>, JsValue>>()?;
         let handle = vehicle
             .params()
             .write_batch(params)
@@ -441,10 +453,13 @@ impl IronwingWasmRuntime {
             }
         });
 
-        let result = handle
+        let result: Vec<_> = handle
             .wait()
             .await
-            .map_err(|error| JsValue::from_str(&error.to_string()))?;
+            .map_err(|error| JsValue::from_str(&error.to_string()))?
+            .into_iter()
+            .map(|result| live_commands::decode_param_write_result(&vehicle, result))
+            .collect();
         to_js(&result)
     }
 
@@ -464,9 +479,13 @@ impl IronwingWasmRuntime {
     }
 
     #[wasm_bindgen(js_name = paramFormatFile)]
-    pub fn param_format_file(&self, store: JsValue) -> Result<String, JsValue> {
+    pub fn param_format_file(&self, store: JsValue, format: JsValue) -> Result<String, JsValue> {
         let store: ParamStore = from_js(store)?;
-        Ok(live_commands::param_format_file(&store))
+        let format: Option<ParamExportFormat> = from_js(format)?;
+        Ok(live_commands::param_format_file(
+            &store,
+            format.unwrap_or_default(),
+        ))
     }
 
     #[wasm_bindgen(js_name = missionValidate)]
@@ -645,19 +664,23 @@ impl IronwingWasmRuntime {
     }
 
     #[wasm_bindgen(js_name = calibrateAccel)]
-    pub async fn calibrate_accel(&self) -> Result<(), JsValue> {
-        let vehicle = live_vehicle_for_write(&self.state, OperationId::CalibrateAccel)?;
-        live_commands::calibrate_accel(&vehicle)
-            .await
-            .map_err(|error| JsValue::from_str(&error.to_string()))
+    pub async fn calibrate_accel(&self) -> Result<JsValue, JsValue> {
+        simple_calibration(
+            &self.state,
+            CalibrationKind::Accel,
+            OperationId::CalibrateAccel,
+        )
+        .await
     }
 
     #[wasm_bindgen(js_name = calibrateGyro)]
-    pub async fn calibrate_gyro(&self) -> Result<(), JsValue> {
-        let vehicle = live_vehicle_for_write(&self.state, OperationId::CalibrateGyro)?;
-        live_commands::calibrate_gyro(&vehicle)
-            .await
-            .map_err(|error| JsValue::from_str(&error.to_string()))
+    pub async fn calibrate_gyro(&self) -> Result<JsValue, JsValue> {
+        simple_calibration(
+            &self.state,
+            CalibrationKind::Gyro,
+            OperationId::CalibrateGyro,
+        )
+        .await
     }
 
     #[wasm_bindgen(js_name = calibrateCompassStart)]
@@ -775,6 +798,50 @@ fn live_vehicle(state: &Rc<RefCell<RuntimeState>>) -> Result<mavkit::Vehicle, Js
         .ok_or_else(|| WasmError::invalid_input("live vehicle is not connected").into())
 }
 
+/// Start a gyro or accel calibration and follow the STATUSTEXT history for
+/// its result, as the native `calibrate_accel`/`calibrate_gyro` do.
+async fn simple_calibration(
+    state: &Rc<RefCell<RuntimeState>>,
+    kind: CalibrationKind,
+    operation_id: OperationId,
+) -> Result<JsValue, JsValue> {
+    let vehicle = live_vehicle_for_write(state, operation_id)?;
+    let firmware = CalibrationFirmware::from(vehicle.identity().autopilot);
+    let live_runtime = state.borrow().live_runtime.clone();
+    let mark = live_runtime.with_runtime(|runtime| {
+        runtime
+            .status_text_history()
+            .last()
+            .map_or(0, |entry| entry.sequence)
+    });
+    match kind {
+        CalibrationKind::Gyro => live_commands::calibrate_gyro(&vehicle).await,
+        _ => live_commands::calibrate_accel(&vehicle).await,
+    }
+    .map_err(|error| JsValue::from_str(&error.to_string()))?;
+    if completes_on_ack(firmware, kind) {
+        return to_js(&CalibrationResult::Succeeded);
+    }
+
+    let mut watch = CalibrationWatch::new(firmware, kind, mark, CALIBRATION_RESULT_TIMEOUT);
+    loop {
+        let mut progress = Vec::new();
+        let (result, sink) = live_runtime.with_runtime(|runtime| {
+            let result = watch.observe(runtime.status_text_history(), |update| {
+                progress.push(update)
+            });
+            (result, runtime.event_sink())
+        });
+        for update in &progress {
+            let _ = sink.emit(event_names::CALIBRATION_PROGRESS, update);
+        }
+        if let Some(result) = result {
+            return to_js(&result);
+        }
+        sleep_ms(CALIBRATION_POLL_INTERVAL.as_millis() as u32).await;
+    }
+}
+
 fn live_vehicle_for_write(
     state: &Rc<RefCell<RuntimeState>>,
     operation_id: OperationId,
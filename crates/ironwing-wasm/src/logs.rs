@@ -9,8 +9,9 @@ fn log_type_from_format(format: &str) -> Result<LogType, JsValue> {
     match format.to_ascii_lowercase().as_str() {
         "tlog" => Ok(LogType::Tlog),
         "bin" => Ok(LogType::Bin),
+        "ulg" | "ulog" => Ok(LogType::Ulog),
         _ => Err(JsValue::from_str(
-            "unsupported log format; expected tlog or bin",
+            "unsupported log format; expected tlog, bin or ulg",
         )),
     }
 }
@@ -46,6 +47,7 @@ pub fn log_query_messages(
         start_usec,
         end_usec,
         max_points,
+        None,
     )?)
 }
 
@@ -37,6 +37,12 @@ class SendArgs {
     val data: List<Int> = emptyList()
 }
 
+@InvokeArg
+class KeepaliveArgs {
+    val title: String = ""
+    val text: String = ""
+}
+
 @TauriPlugin(
     permissions = [
         Permission(
@@ -226,6 +232,34 @@ class ClassicBluetoothPlugin(private val activity: android.app.Activity) : Plugi
         }
     }
 
+    // -----------------------------------------------------------------------
+    // Background keep-alive (foreground service)
+    // -----------------------------------------------------------------------
+
+    @Command
+    fun startKeepalive(invoke: Invoke) {
+        val args = invoke.parseArgs(KeepaliveArgs::class.java)
+        try {
+            LinkKeepaliveService.start(activity, args.title, args.text)
+            invoke.resolve()
+        } catch (e: Exception) {
+            invoke.reject("Failed to start keep-alive service: ${e.message}")
+        }
+    }
+
+    @Command
+    fun updateKeepalive(invoke: Invoke) {
+        val args = invoke.parseArgs(KeepaliveArgs::class.java)
+        LinkKeepaliveService.update(activity, args.title, args.text)
+        invoke.resolve()
+    }
+
+    @Command
+    fun stopKeepalive(invoke: Invoke) {
+        LinkKeepaliveService.stop(activity)
+        invoke.resolve()
+    }
+
     private fun disconnectInternal() {
         readThread?.interrupt()
         readThread = null
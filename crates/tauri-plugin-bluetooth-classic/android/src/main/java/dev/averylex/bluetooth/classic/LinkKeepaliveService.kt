@@ -0,0 +1,109 @@
+package dev.averylex.bluetooth.classic
+
+import android.app.Notification
+import android.app.NotificationChannel
+import android.app.NotificationManager
+import android.app.PendingIntent
+import android.app.Service
+import android.content.Context
+import android.content.Intent
+import android.content.pm.ServiceInfo
+import android.os.Build
+import android.os.IBinder
+import androidx.core.app.NotificationCompat
+import androidx.core.content.ContextCompat
+
+/**
+ * Foreground service that keeps the process alive while a vehicle link is
+ * open, so Android does not suspend the app (and drop the link) when the
+ * pilot switches to another app. It does no work itself; the ongoing
+ * notification shows telemetry the Rust side pushes in.
+ */
+class LinkKeepaliveService : Service() {
+
+    companion object {
+        private const val CHANNEL_ID = "ironwing_link_keepalive"
+        private const val NOTIFICATION_ID = 4201
+        private const val EXTRA_TITLE = "title"
+        private const val EXTRA_TEXT = "text"
+
+        @Volatile
+        var running = false
+            private set
+
+        fun start(context: Context, title: String, text: String) {
+            val intent = Intent(context, LinkKeepaliveService::class.java)
+                .putExtra(EXTRA_TITLE, title)
+                .putExtra(EXTRA_TEXT, text)
+            ContextCompat.startForegroundService(context, intent)
+        }
+
+        fun update(context: Context, title: String, text: String) {
+            if (!running) return
+            val manager = context.getSystemService(Context.NOTIFICATION_SERVICE) as NotificationManager
+            manager.notify(NOTIFICATION_ID, buildNotification(context, title, text))
+        }
+
+        fun stop(context: Context) {
+            context.stopService(Intent(context, LinkKeepaliveService::class.java))
+        }
+
+        private fun buildNotification(context: Context, title: String, text: String): Notification {
+            val launch = context.packageManager.getLaunchIntentForPackage(context.packageName)
+            val contentIntent = launch?.let {
+                PendingIntent.getActivity(context, 0, it, PendingIntent.FLAG_IMMUTABLE)
+            }
+            return NotificationCompat.Builder(context, CHANNEL_ID)
+                .setSmallIcon(context.applicationInfo.icon)
+                .setContentTitle(title)
+                .setContentText(text)
+                .setContentIntent(contentIntent)
+                .setOngoing(true)
+                .setOnlyAlertOnce(true)
+                .setCategory(NotificationCompat.CATEGORY_SERVICE)
+                .setForegroundServiceBehavior(NotificationCompat.FOREGROUND_SERVICE_IMMEDIATE)
+                .build()
+        }
+    }
+
+    override fun onBind(intent: Intent?): IBinder? = null
+
+    override fun onStartCommand(intent: Intent?, flags: Int, startId: Int): Int {
+        ensureChannel()
+        val notification = buildNotification(
+            this,
+            intent?.getStringExtra(EXTRA_TITLE) ?: "",
+            intent?.getStringExtra(EXTRA_TEXT) ?: ""
+        )
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.Q) {
+            startForeground(
+                NOTIFICATION_ID,
+                notification,
+                ServiceInfo.FOREGROUND_SERVICE_TYPE_CONNECTED_DEVICE
+            )
+        } else {
+            startForeground(NOTIFICATION_ID, notification)
+        }
+        running = true
+        // A restart after the process died would have no link to keep.
+        return START_NOT_STICKY
+    }
+
+    override fun onDestroy() {
+        running = false
+        super.onDestroy()
+    }
+
+    private fun ensureChannel() {
+        if (Build.VERSION.SDK_INT < Build.VERSION_CODES.O) return
+        val manager = getSystemService(Context.NOTIFICATION_SERVICE) as NotificationManager
+        if (manager.getNotificationChannel(CHANNEL_ID) != null) return
+        val channel = NotificationChannel(
+            CHANNEL_ID,
+            "Vehicle link",
+            NotificationManager.IMPORTANCE_LOW
+        )
+        channel.description = "Keeps the vehicle connection open in the background"
+        manager.createNotificationChannel(channel)
+    }
+}
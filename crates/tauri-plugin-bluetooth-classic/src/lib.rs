@@ -30,6 +30,13 @@ struct SendArgs {
     data: Vec<u8>,
 }
 
+#[cfg(mobile)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KeepaliveArgs {
+    title: String,
+    text: String,
+}
+
 #[cfg(mobile)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct BondedDevicesResponse {
@@ -4,7 +4,7 @@ use tauri::{
     plugin::{PluginApi, PluginHandle},
 };
 
-use crate::{BondedDevice, BondedDevicesResponse, ConnectArgs, SendArgs};
+use crate::{BondedDevice, BondedDevicesResponse, ConnectArgs, KeepaliveArgs, SendArgs};
 
 #[cfg(target_os = "android")]
 const PLUGIN_IDENTIFIER: &str = "dev.averylex.bluetooth.classic";
@@ -61,4 +61,41 @@ impl<R: Runtime> BluetoothClassic<R> {
         )?;
         Ok(())
     }
+
+    /// Start the foreground service that keeps the process, and with it the
+    /// link, alive while the app is in the background. Its ongoing
+    /// notification shows `title` and `text`.
+    pub fn start_keepalive(
+        &self,
+        title: &str,
+        text: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.0
+            .run_mobile_plugin::<()>("startKeepalive", keepalive_args(title, text))?;
+        Ok(())
+    }
+
+    /// Replace the keep-alive notification's text; ignored when the service
+    /// is not running.
+    pub fn update_keepalive(
+        &self,
+        title: &str,
+        text: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.0
+            .run_mobile_plugin::<()>("updateKeepalive", keepalive_args(title, text))?;
+        Ok(())
+    }
+
+    pub fn stop_keepalive(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.0.run_mobile_plugin::<()>("stopKeepalive", ())?;
+        Ok(())
+    }
+}
+
+fn keepalive_args(title: &str, text: &str) -> KeepaliveArgs {
+    KeepaliveArgs {
+        title: title.to_string(),
+        text: text.to_string(),
+    }
 }
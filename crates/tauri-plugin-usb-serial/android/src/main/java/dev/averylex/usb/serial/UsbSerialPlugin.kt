@@ -0,0 +1,292 @@
+package dev.averylex.usb.serial
+
+import android.app.PendingIntent
+import android.content.BroadcastReceiver
+import android.content.Context
+import android.content.Intent
+import android.content.IntentFilter
+import android.hardware.usb.UsbDevice
+import android.hardware.usb.UsbManager
+import android.os.Build
+import android.os.Handler
+import android.os.Looper
+import android.util.Base64
+import androidx.core.content.ContextCompat
+import app.tauri.annotation.Command
+import app.tauri.annotation.InvokeArg
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSArray
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+import com.hoho.android.usbserial.driver.UsbSerialDriver
+import com.hoho.android.usbserial.driver.UsbSerialPort
+import com.hoho.android.usbserial.driver.UsbSerialProber
+import com.hoho.android.usbserial.util.SerialInputOutputManager
+import java.io.IOException
+
+private const val ACTION_USB_PERMISSION = "dev.averylex.usb.serial.USB_PERMISSION"
+private const val WRITE_TIMEOUT_MS = 1000
+private const val REATTACH_POLL_MS = 1000L
+
+@InvokeArg
+class ConnectArgs {
+    val deviceId: String = ""
+    val baud: Int = 57600
+}
+
+@InvokeArg
+class SendArgs {
+    val data: List<Int> = emptyList()
+}
+
+/** The adapter to reopen when it comes back after being unplugged. */
+private data class ReattachTarget(val vendorId: Int, val productId: Int, val baud: Int)
+
+@TauriPlugin
+class UsbSerialPlugin(private val activity: android.app.Activity) : Plugin(activity) {
+
+    private val usbManager: UsbManager =
+        activity.getSystemService(Context.USB_SERVICE) as UsbManager
+    private val handler = Handler(Looper.getMainLooper())
+
+    private var port: UsbSerialPort? = null
+    private var ioManager: SerialInputOutputManager? = null
+    private var connectedDevice: UsbDevice? = null
+    private var reattachTarget: ReattachTarget? = null
+    private var pendingPermission: Pair<UsbSerialDriver, (Boolean) -> Unit>? = null
+
+    private val usbReceiver = object : BroadcastReceiver() {
+        override fun onReceive(context: Context, intent: Intent) {
+            when (intent.action) {
+                ACTION_USB_PERMISSION -> {
+                    val granted = intent.getBooleanExtra(UsbManager.EXTRA_PERMISSION_GRANTED, false)
+                    val pending = pendingPermission ?: return
+                    pendingPermission = null
+                    pending.second(granted)
+                }
+                UsbManager.ACTION_USB_DEVICE_DETACHED -> {
+                    val device = deviceExtra(intent) ?: return
+                    if (device.deviceName == connectedDevice?.deviceName) {
+                        onConnectedDeviceDetached(device)
+                    }
+                }
+            }
+        }
+    }
+
+    init {
+        val filter = IntentFilter().apply {
+            addAction(ACTION_USB_PERMISSION)
+            addAction(UsbManager.ACTION_USB_DEVICE_DETACHED)
+        }
+        ContextCompat.registerReceiver(
+            activity,
+            usbReceiver,
+            filter,
+            ContextCompat.RECEIVER_NOT_EXPORTED
+        )
+    }
+
+    @Suppress("DEPRECATION")
+    private fun deviceExtra(intent: Intent): UsbDevice? =
+        if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.TIRAMISU) {
+            intent.getParcelableExtra(UsbManager.EXTRA_DEVICE, UsbDevice::class.java)
+        } else {
+            intent.getParcelableExtra(UsbManager.EXTRA_DEVICE)
+        }
+
+    private fun drivers(): List<UsbSerialDriver> =
+        UsbSerialProber.getDefaultProber().findAllDrivers(usbManager)
+
+    // -----------------------------------------------------------------------
+    // Commands
+    // -----------------------------------------------------------------------
+
+    @Command
+    fun listDevices(invoke: Invoke) {
+        val devices = JSArray()
+        for (driver in drivers()) {
+            val device = driver.device
+            val obj = JSObject()
+            obj.put("deviceId", device.deviceName)
+            obj.put("vendorId", device.vendorId)
+            obj.put("productId", device.productId)
+            obj.put("manufacturer", device.manufacturerName)
+            obj.put("product", device.productName)
+            obj.put("hasPermission", usbManager.hasPermission(device))
+            devices.put(obj)
+        }
+
+        val result = JSObject()
+        result.put("devices", devices)
+        invoke.resolve(result)
+    }
+
+    @Command
+    fun connect(invoke: Invoke) {
+        val args = invoke.parseArgs(ConnectArgs::class.java)
+        if (args.deviceId.isEmpty()) {
+            invoke.reject("deviceId is required")
+            return
+        }
+
+        val driver = drivers().firstOrNull { it.device.deviceName == args.deviceId }
+        if (driver == null) {
+            invoke.reject("USB serial device ${args.deviceId} is not attached")
+            return
+        }
+
+        disconnectInternal()
+        withPermission(driver) { granted ->
+            if (!granted) {
+                invoke.reject("USB permission denied for ${args.deviceId}")
+                return@withPermission
+            }
+            try {
+                open(driver, args.baud)
+                invoke.resolve()
+            } catch (e: Exception) {
+                closePort()
+                invoke.reject("Failed to open ${args.deviceId}: ${e.message}")
+            }
+        }
+    }
+
+    @Command
+    fun disconnect(invoke: Invoke) {
+        disconnectInternal()
+        invoke.resolve()
+    }
+
+    @Command
+    fun send(invoke: Invoke) {
+        val args = invoke.parseArgs(SendArgs::class.java)
+        if (args.data.isEmpty()) {
+            invoke.reject("data is required")
+            return
+        }
+        val current = port
+        if (current == null) {
+            invoke.reject("USB serial device is not connected")
+            return
+        }
+
+        val bytes = ByteArray(args.data.size)
+        for (i in args.data.indices) {
+            bytes[i] = args.data[i].toByte()
+        }
+
+        try {
+            current.write(bytes, WRITE_TIMEOUT_MS)
+            invoke.resolve()
+        } catch (e: IOException) {
+            invoke.reject("Failed to send: ${e.message}")
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Port lifecycle
+    // -----------------------------------------------------------------------
+
+    private fun withPermission(driver: UsbSerialDriver, onResult: (Boolean) -> Unit) {
+        if (usbManager.hasPermission(driver.device)) {
+            onResult(true)
+            return
+        }
+        pendingPermission?.second?.invoke(false)
+        pendingPermission = driver to onResult
+        val intent = Intent(ACTION_USB_PERMISSION).setPackage(activity.packageName)
+        val flags = if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.S) {
+            PendingIntent.FLAG_MUTABLE
+        } else {
+            0
+        }
+        usbManager.requestPermission(
+            driver.device,
+            PendingIntent.getBroadcast(activity, 0, intent, flags)
+        )
+    }
+
+    private fun open(driver: UsbSerialDriver, baud: Int) {
+        val connection = usbManager.openDevice(driver.device)
+            ?: throw IOException("could not open USB device")
+        val serialPort = driver.ports.first()
+        serialPort.open(connection)
+        serialPort.setParameters(baud, 8, UsbSerialPort.STOPBITS_1, UsbSerialPort.PARITY_NONE)
+
+        val manager = SerialInputOutputManager(serialPort, object : SerialInputOutputManager.Listener {
+            override fun onNewData(data: ByteArray) {
+                val event = JSObject()
+                event.put("data", Base64.encodeToString(data, Base64.NO_WRAP))
+                trigger("data", event)
+            }
+
+            override fun onRunError(e: Exception) {
+                // The port went away; the detach broadcast drives reconnection.
+            }
+        })
+        manager.start()
+
+        port = serialPort
+        ioManager = manager
+        connectedDevice = driver.device
+        reattachTarget = ReattachTarget(driver.device.vendorId, driver.device.productId, baud)
+    }
+
+    private fun onConnectedDeviceDetached(device: UsbDevice) {
+        closePort()
+        val event = JSObject()
+        event.put("deviceId", device.deviceName)
+        trigger("detached", event)
+        handler.postDelayed({ pollForReattach() }, REATTACH_POLL_MS)
+    }
+
+    /**
+     * A bumped cable re-enumerates the adapter under a new device name, so
+     * match it by VID/PID and reopen it at the same baud rate.
+     */
+    private fun pollForReattach() {
+        val target = reattachTarget ?: return
+        if (port != null) return
+        val driver = drivers().firstOrNull {
+            it.device.vendorId == target.vendorId && it.device.productId == target.productId
+        }
+        if (driver == null) {
+            handler.postDelayed({ pollForReattach() }, REATTACH_POLL_MS)
+            return
+        }
+        withPermission(driver) { granted ->
+            if (!granted || reattachTarget != target) return@withPermission
+            try {
+                open(driver, target.baud)
+                val event = JSObject()
+                event.put("deviceId", driver.device.deviceName)
+                trigger("reconnected", event)
+            } catch (e: Exception) {
+                closePort()
+                handler.postDelayed({ pollForReattach() }, REATTACH_POLL_MS)
+            }
+        }
+    }
+
+    private fun closePort() {
+        ioManager?.stop()
+        ioManager = null
+        try { port?.close() } catch (_: IOException) {}
+        port = null
+        connectedDevice = null
+    }
+
+    private fun disconnectInternal() {
+        reattachTarget = null
+        handler.removeCallbacksAndMessages(null)
+        closePort()
+    }
+
+    override fun onDestroy() {
+        disconnectInternal()
+        try { activity.unregisterReceiver(usbReceiver) } catch (_: IllegalArgumentException) {}
+        super.onDestroy()
+    }
+}
@@ -0,0 +1,8 @@
+const COMMANDS: &[&str] = &["list_devices", "connect", "disconnect", "send"];
+
+fn main() {
+    tauri_plugin::Builder::new(COMMANDS)
+        .android_path("android")
+        .try_build()
+        .expect("failed to build tauri-plugin-usb-serial");
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+#[cfg(mobile)]
+use tauri::Manager;
+use tauri::{
+    Runtime,
+    plugin::{Builder, TauriPlugin},
+};
+
+#[cfg(mobile)]
+mod mobile;
+
+#[cfg(mobile)]
+pub use mobile::UsbSerial;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsbDevice {
+    pub device_id: String,
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub manufacturer: Option<String>,
+    pub product: Option<String>,
+    pub has_permission: bool,
+}
+
+#[cfg(mobile)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConnectArgs {
+    device_id: String,
+    baud: u32,
+}
+
+#[cfg(mobile)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SendArgs {
+    data: Vec<u8>,
+}
+
+#[cfg(mobile)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DevicesResponse {
+    devices: Vec<UsbDevice>,
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+async fn list_devices<R: Runtime>(app: tauri::AppHandle<R>) -> Result<Vec<UsbDevice>, String> {
+    #[cfg(mobile)]
+    {
+        let usb = app.state::<UsbSerial<R>>();
+        usb.list_devices().map_err(|e| e.to_string())
+    }
+    #[cfg(not(mobile))]
+    {
+        let _ = app;
+        Err("USB OTG serial is only available on Android".into())
+    }
+}
+
+#[tauri::command]
+async fn connect<R: Runtime>(
+    app: tauri::AppHandle<R>,
+    device_id: String,
+    baud: u32,
+) -> Result<(), String> {
+    #[cfg(mobile)]
+    {
+        let usb = app.state::<UsbSerial<R>>();
+        usb.connect(&device_id, baud).map_err(|e| e.to_string())
+    }
+    #[cfg(not(mobile))]
+    {
+        let _ = (app, device_id, baud);
+        Err("USB OTG serial is only available on Android".into())
+    }
+}
+
+#[tauri::command]
+async fn disconnect<R: Runtime>(app: tauri::AppHandle<R>) -> Result<(), String> {
+    #[cfg(mobile)]
+    {
+        let usb = app.state::<UsbSerial<R>>();
+        usb.disconnect().map_err(|e| e.to_string())
+    }
+    #[cfg(not(mobile))]
+    {
+        let _ = app;
+        Err("USB OTG serial is only available on Android".into())
+    }
+}
+
+#[tauri::command]
+async fn send<R: Runtime>(app: tauri::AppHandle<R>, data: Vec<u8>) -> Result<(), String> {
+    #[cfg(mobile)]
+    {
+        let usb = app.state::<UsbSerial<R>>();
+        usb.send(&data).map_err(|e| e.to_string())
+    }
+    #[cfg(not(mobile))]
+    {
+        let _ = (app, data);
+        Err("USB OTG serial is only available on Android".into())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Plugin init
+// ---------------------------------------------------------------------------
+
+pub fn init<R: Runtime>() -> TauriPlugin<R> {
+    Builder::new("usb-serial")
+        .invoke_handler(tauri::generate_handler![
+            list_devices,
+            connect,
+            disconnect,
+            send,
+        ])
+        .setup(|app, api| {
+            #[cfg(mobile)]
+            {
+                let handle = mobile::init(app, api)?;
+                app.manage(handle);
+            }
+            #[cfg(not(mobile))]
+            {
+                let _ = (app, api);
+            }
+            Ok(())
+        })
+        .build()
+}
@@ -0,0 +1,60 @@
+use serde::de::DeserializeOwned;
+use tauri::{
+    AppHandle, Runtime,
+    plugin::{PluginApi, PluginHandle},
+};
+
+use crate::{ConnectArgs, DevicesResponse, SendArgs, UsbDevice};
+
+#[cfg(target_os = "android")]
+const PLUGIN_IDENTIFIER: &str = "dev.averylex.usb.serial";
+
+pub fn init<R: Runtime, C: DeserializeOwned>(
+    _app: &AppHandle<R>,
+    api: PluginApi<R, C>,
+) -> Result<UsbSerial<R>, Box<dyn std::error::Error>> {
+    #[cfg(target_os = "android")]
+    let handle = api.register_android_plugin(PLUGIN_IDENTIFIER, "UsbSerialPlugin")?;
+    #[cfg(target_os = "ios")]
+    let handle = {
+        let _ = api;
+        return Err("USB OTG serial is not supported on iOS".into());
+    };
+    Ok(UsbSerial(handle))
+}
+
+pub struct UsbSerial<R: Runtime>(PluginHandle<R>);
+
+impl<R: Runtime> UsbSerial<R> {
+    pub fn list_devices(&self) -> Result<Vec<UsbDevice>, Box<dyn std::error::Error>> {
+        let resp: DevicesResponse = self.0.run_mobile_plugin("listDevices", ())?;
+        Ok(resp.devices)
+    }
+
+    /// Open the device, asking the user for USB permission first if needed.
+    pub fn connect(&self, device_id: &str, baud: u32) -> Result<(), Box<dyn std::error::Error>> {
+        self.0.run_mobile_plugin::<()>(
+            "connect",
+            ConnectArgs {
+                device_id: device_id.to_string(),
+                baud,
+            },
+        )?;
+        Ok(())
+    }
+
+    pub fn disconnect(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.0.run_mobile_plugin::<()>("disconnect", ())?;
+        Ok(())
+    }
+
+    pub fn send(&self, data: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+        self.0.run_mobile_plugin::<()>(
+            "send",
+            SendArgs {
+                data: data.to_vec(),
+            },
+        )?;
+        Ok(())
+    }
+}
@@ -13,6 +13,8 @@ allprojects {
     repositories {
         google()
         mavenCentral()
+        // usb-serial-for-android, used by tauri-plugin-usb-serial
+        maven { url = uri("https://jitpack.io") }
     }
 }
 
@@ -0,0 +1,40 @@
+use ironwing_core::alerts::default_alert_rules;
+
+use crate::AppState;
+use crate::ipc::{AlertRule, AppError, SettingsPatch};
+use crate::settings::update_settings;
+
+#[tauri::command]
+pub(crate) fn alerts_get_rules(state: tauri::State<'_, AppState>) -> Vec<AlertRule> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.alert_rules().to_vec())
+}
+
+/// Replace the alert rules and save them with the other settings. Built-in
+/// rules are switched off by sending them back with `enabled: false`.
+#[tauri::command]
+pub(crate) async fn alerts_set_rules(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    rules: Vec<AlertRule>,
+) -> Result<(), AppError> {
+    let patch = SettingsPatch {
+        alert_rules: Some(rules),
+        ..SettingsPatch::default()
+    };
+    update_settings(&app, &state, patch).await?;
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn alerts_reset_rules(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<AlertRule>, AppError> {
+    let patch = SettingsPatch {
+        alert_rules: Some(default_alert_rules()),
+        ..SettingsPatch::default()
+    };
+    Ok(update_settings(&app, &state, patch).await?.alert_rules)
+}
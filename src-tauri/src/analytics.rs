@@ -1,3 +1,4 @@
+use crate::ipc::AppError;
 use crate::ipc::analytics::{AnalyticsProperties, AnalyticsProperty};
 use serde_json::{Map, Value};
 use tauri_plugin_aptabase::EventTracker;
@@ -32,7 +33,7 @@ pub(crate) async fn analytics_track_event(
     app: tauri::AppHandle,
     name: String,
     props: Option<AnalyticsProperties>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     if !analytics_enabled() {
         return Ok(());
     }
@@ -43,8 +44,9 @@ pub(crate) async fn analytics_track_event(
     }
 
     let props = props.and_then(properties_to_value);
-    app.track_event(event_name, props)
-        .map_err(|error| format!("failed to enqueue analytics event {event_name:?}: {error}"))
+    app.track_event(event_name, props).map_err(|error| {
+        AppError::internal(format!("failed to enqueue analytics event {event_name:?}: {error}"))
+    })
 }
 
 fn properties_to_value(props: AnalyticsProperties) -> Option<Value> {
@@ -0,0 +1,130 @@
+use ironwing_core::background_keepalive::{
+    KEEPALIVE_NOTIFICATION_TITLE, KEEPALIVE_UPDATE_INTERVAL, keepalive_notification_text,
+};
+use tauri::Manager;
+
+use crate::AppState;
+use crate::helpers::vehicle_is_armed;
+use crate::ipc::AppError;
+
+/// Whether the Android foreground service is up, and whether the user asked
+/// for it even while disarmed.
+#[derive(Debug, Default)]
+pub(crate) struct KeepaliveState {
+    requested: bool,
+    running: bool,
+}
+
+#[cfg(target_os = "android")]
+fn show_notification(app: &tauri::AppHandle, start: bool, text: &str) -> Result<(), String> {
+    let bt: tauri::State<'_, tauri_plugin_bluetooth_classic::BluetoothClassic<tauri::Wry>> =
+        app.state();
+    let result = if start {
+        bt.start_keepalive(KEEPALIVE_NOTIFICATION_TITLE, text)
+    } else {
+        bt.update_keepalive(KEEPALIVE_NOTIFICATION_TITLE, text)
+    };
+    result.map_err(|error| error.to_string())
+}
+
+#[cfg(target_os = "android")]
+fn hide_notification(app: &tauri::AppHandle) -> Result<(), String> {
+    let bt: tauri::State<'_, tauri_plugin_bluetooth_classic::BluetoothClassic<tauri::Wry>> =
+        app.state();
+    bt.stop_keepalive().map_err(|error| error.to_string())
+}
+
+/// Desktop processes are not suspended in the background.
+#[cfg(not(target_os = "android"))]
+fn show_notification(_app: &tauri::AppHandle, _start: bool, _text: &str) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(not(target_os = "android"))]
+fn hide_notification(_app: &tauri::AppHandle) -> Result<(), String> {
+    Ok(())
+}
+
+/// Run the service while a vehicle is connected and either armed or the user
+/// asked for it, with the notification showing current telemetry; stop it
+/// otherwise.
+async fn refresh_keepalive(state: &AppState, app: &tauri::AppHandle) -> Result<(), String> {
+    let mut keepalive = state.background_keepalive.lock().await;
+    let vehicle = state.live_runtime.with_runtime(|runtime| runtime.vehicle());
+    let wanted = vehicle
+        .as_ref()
+        .is_some_and(|vehicle| keepalive.requested || vehicle_is_armed(vehicle));
+    match vehicle.filter(|_| wanted) {
+        Some(vehicle) => {
+            let telemetry = state
+                .live_runtime
+                .with_runtime(|runtime| runtime.current_telemetry(&vehicle))
+                .value;
+            let mode = vehicle
+                .available_modes()
+                .current()
+                .latest()
+                .map(|mode| mode.name);
+            let text = keepalive_notification_text(mode.as_deref(), telemetry.as_ref());
+            show_notification(app, !keepalive.running, &text)?;
+            keepalive.running = true;
+        }
+        None if keepalive.running => {
+            hide_notification(app)?;
+            keepalive.running = false;
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+/// Keep the service in step with arming and refresh its notification every
+/// `KEEPALIVE_UPDATE_INTERVAL`.
+pub(crate) async fn background_keepalive_bridge(handle: tauri::AppHandle) {
+    let mut interval = tokio::time::interval(KEEPALIVE_UPDATE_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+        let state: tauri::State<'_, AppState> = handle.state();
+        if let Err(error) = refresh_keepalive(&state, &handle).await {
+            tracing::warn!("background keep-alive update failed: {error}");
+        }
+    }
+}
+
+/// Stop the service when the link goes away; a later connection starts it
+/// again only once armed or asked for.
+pub(crate) async fn stop_keepalive_on_disconnect(state: &AppState, app: &tauri::AppHandle) {
+    let mut keepalive = state.background_keepalive.lock().await;
+    keepalive.requested = false;
+    if std::mem::take(&mut keepalive.running)
+        && let Err(error) = hide_notification(app)
+    {
+        tracing::warn!("failed to stop background keep-alive: {error}");
+    }
+}
+
+/// Keep the link alive in the background while connected, armed or not.
+/// A no-op on desktop.
+#[tauri::command]
+pub(crate) async fn background_keepalive_start(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), AppError> {
+    state.background_keepalive.lock().await.requested = true;
+    refresh_keepalive(&state, &app)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Go back to keeping the link alive only while armed.
+#[tauri::command]
+pub(crate) async fn background_keepalive_stop(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), AppError> {
+    state.background_keepalive.lock().await.requested = false;
+    refresh_keepalive(&state, &app)
+        .await
+        .map_err(AppError::from)
+}
@@ -3,6 +3,8 @@ use tauri_plugin_blec::models::ScanFilter;
 
 use ironwing_core::{bluetooth_profile, transport::BluetoothProfile};
 
+use crate::ipc::AppError;
+
 #[cfg(target_os = "android")]
 use tauri::Manager;
 
@@ -28,7 +30,7 @@ pub(crate) fn scan_filter_for_profile(profile: BluetoothProfile) -> ScanFilter {
 
 #[cfg(target_os = "android")]
 #[tauri::command]
-pub(crate) async fn bt_request_permissions(app: tauri::AppHandle) -> Result<(), String> {
+pub(crate) async fn bt_request_permissions(app: tauri::AppHandle) -> Result<(), AppError> {
     let bt: tauri::State<'_, tauri_plugin_bluetooth_classic::BluetoothClassic<tauri::Wry>> =
         app.state();
     bt.request_bt_permissions()
@@ -38,7 +40,7 @@ pub(crate) async fn bt_request_permissions(app: tauri::AppHandle) -> Result<(),
 
 #[cfg(not(target_os = "android"))]
 #[tauri::command]
-pub(crate) async fn bt_request_permissions() -> Result<(), String> {
+pub(crate) async fn bt_request_permissions() -> Result<(), AppError> {
     Ok(())
 }
 
@@ -46,7 +48,7 @@ pub(crate) async fn bt_request_permissions() -> Result<(), String> {
 pub(crate) async fn bt_scan_ble(
     timeout_ms: Option<u64>,
     profile: Option<BluetoothProfile>,
-) -> Result<Vec<BluetoothDevice>, String> {
+) -> Result<Vec<BluetoothDevice>, AppError> {
     let handler =
         tauri_plugin_blec::get_handler().map_err(|e| format!("BLE plugin not initialized: {e}"))?;
 
@@ -84,7 +86,7 @@ pub(crate) async fn bt_scan_ble(
 }
 
 #[tauri::command]
-pub(crate) async fn bt_stop_scan_ble() -> Result<(), String> {
+pub(crate) async fn bt_stop_scan_ble() -> Result<(), AppError> {
     let handler =
         tauri_plugin_blec::get_handler().map_err(|e| format!("BLE plugin not initialized: {e}"))?;
     handler
@@ -98,7 +100,7 @@ pub(crate) async fn bt_stop_scan_ble() -> Result<(), String> {
 #[tauri::command]
 pub(crate) async fn bt_get_bonded_devices(
     app: tauri::AppHandle,
-) -> Result<Vec<BluetoothDevice>, String> {
+) -> Result<Vec<BluetoothDevice>, AppError> {
     let bt: tauri::State<'_, tauri_plugin_bluetooth_classic::BluetoothClassic<tauri::Wry>> =
         app.state();
     let devices = bt
@@ -117,6 +119,6 @@ pub(crate) async fn bt_get_bonded_devices(
 
 #[cfg(not(target_os = "android"))]
 #[tauri::command]
-pub(crate) async fn bt_get_bonded_devices() -> Result<Vec<BluetoothDevice>, String> {
-    Err("not supported on desktop".to_string())
+pub(crate) async fn bt_get_bonded_devices() -> Result<Vec<BluetoothDevice>, AppError> {
+    Err(AppError::validation("not supported on desktop"))
 }
@@ -1,14 +1,39 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
+use ironwing_core::derived_telemetry::WIND_COV_MESSAGE_ID;
+use ironwing_core::dialect::MavMessage;
+use ironwing_core::event_names;
+use ironwing_core::gps_fix::{
+    GPS_RAW_INT_MESSAGE_ID, GPS_RTK_MESSAGE_ID, GPS2_RAW_MESSAGE_ID, GPS2_RTK_MESSAGE_ID,
+    GpsRawReport,
+};
+use ironwing_core::heartbeats::HeartbeatObservation;
+use ironwing_core::high_latency::{HIGH_LATENCY2_MESSAGE_ID, high_latency2_report};
+use ironwing_core::link_sources::RADIO_STATUS_MESSAGE_ID;
+use ironwing_core::live_runtime::commands as live_commands;
 use ironwing_core::live_runtime::{self, SendTaskSpawner, SendTimer, TelemetryIntervalProvider};
+use ironwing_core::named_values::{NAMED_VALUE_MESSAGE_IDS, named_value_from_message};
+use ironwing_core::rangefinder::{DISTANCE_SENSOR_MESSAGE_ID, rangefinder_reading};
+use ironwing_core::rc_link_quality::{
+    RADIO_LINK_STATS_MESSAGE_ID, RADIO_RC_CHANNELS_MESSAGE_ID, RC_CHANNELS_MESSAGE_ID,
+    decode_rc_link,
+};
+use ironwing_core::rover::WHEEL_DISTANCE_MESSAGE_ID;
 use ironwing_core::telemetry;
+use ironwing_core::vehicle_snapshot::unix_epoch_usec;
+use ironwing_core::video_streams::{
+    VIDEO_STREAM_INFORMATION_MESSAGE_ID, video_stream_from_message,
+};
 use mavkit::Vehicle;
+use mavlink::MavlinkVersion;
 use serde::Serialize;
 use tauri::Manager;
 
 use crate::AppState;
 use crate::guided::{emit_guided_snapshot, live_context_from_vehicle};
+use crate::ipc::DomainProvenance;
+use crate::task_watchdog::{supervise, watch};
 
 #[cfg(test)]
 pub(crate) use ironwing_core::live::SessionContext;
@@ -16,17 +41,39 @@ pub(crate) use ironwing_core::live::SessionContext;
 pub(crate) static TELEMETRY_INTERVAL_MS: AtomicU64 =
     AtomicU64::new(telemetry::DEFAULT_TELEMETRY_INTERVAL_MS);
 
-#[derive(Default)]
+/// GPS reports plus SYSTEM_TIME, the vehicle boot clock for RTK ages.
+const GPS_FIX_MESSAGE_IDS: [u32; 5] = [
+    GPS_RAW_INT_MESSAGE_ID,
+    GPS2_RAW_MESSAGE_ID,
+    GPS_RTK_MESSAGE_ID,
+    GPS2_RTK_MESSAGE_ID,
+    SYSTEM_TIME_MESSAGE_ID,
+];
+const SYSTEM_TIME_MESSAGE_ID: u32 = 2;
+
+/// Spawns the core's bridges under the task watchdog. They are handed over
+/// as bare futures, so a panic is reported but cannot be restarted.
 struct TokioTaskSet {
+    app: tauri::AppHandle,
     tasks: Vec<tokio::task::JoinHandle<()>>,
 }
 
+impl TokioTaskSet {
+    fn new(app: &tauri::AppHandle) -> Self {
+        Self {
+            app: app.clone(),
+            tasks: Vec::new(),
+        }
+    }
+}
+
 impl SendTaskSpawner for TokioTaskSet {
     fn spawn_send<F>(&mut self, future: F)
     where
         F: std::future::Future<Output = ()> + Send + 'static,
     {
-        self.tasks.push(tokio::spawn(future));
+        let name = format!("live_runtime.{}", self.tasks.len());
+        self.tasks.push(watch(&self.app, name, future));
     }
 }
 
@@ -70,12 +117,361 @@ async fn reconcile_guided_runtime(handle: &tauri::AppHandle, vehicle: &Vehicle)
     }
 }
 
+/// Feeds every HEARTBEAT on the link, from any system or component, into the
+/// runtime's heartbeat registry. The bound `Vehicle` only tracks its own system.
+async fn heartbeat_registry_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        let Ok(MavMessage::HEARTBEAT(heartbeat)) =
+            MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+        else {
+            continue;
+        };
+        let observation = HeartbeatObservation {
+            system_id: raw_msg.system_id,
+            component_id: raw_msg.component_id,
+            mav_type: heartbeat.mavtype as u8,
+            autopilot: heartbeat.autopilot as u8,
+            base_mode: heartbeat.base_mode.bits(),
+            custom_mode: heartbeat.custom_mode,
+            system_status: heartbeat.system_status as u8,
+        };
+        let state: tauri::State<'_, AppState> = handle.state();
+        state
+            .live_runtime
+            .with_runtime(|runtime| runtime.observe_heartbeat(observation));
+    }
+}
+
+/// Counts messages per (system_id, component_id) for `link_sources`.
+async fn link_source_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        let state: tauri::State<'_, AppState> = handle.state();
+        state.live_runtime.with_runtime(|runtime| {
+            runtime.observe_link_source(raw_msg.system_id, raw_msg.component_id)
+        });
+    }
+}
+
+/// Decodes HIGH_LATENCY2 from the bound vehicle into the runtime; the report is
+/// only applied while the link profile is `high_latency`.
+async fn high_latency_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        if raw_msg.message_id != HIGH_LATENCY2_MESSAGE_ID || raw_msg.system_id != system_id {
+            continue;
+        }
+        let Ok(MavMessage::HIGH_LATENCY2(data)) =
+            MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+        else {
+            continue;
+        };
+        let report = high_latency2_report(&data);
+        let mode_name = live_commands::get_available_modes(&vehicle)
+            .into_iter()
+            .find(|mode| mode.custom_mode == report.custom_mode)
+            .map(|mode| mode.name);
+        let state: tauri::State<'_, AppState> = handle.state();
+        let applied = state.live_runtime.with_runtime(|runtime| {
+            runtime.record_high_latency_report(report, mode_name.as_deref())
+        });
+        if applied && mode_name.is_some() {
+            live_runtime::emit_session_state(&state.live_runtime, DomainProvenance::Stream);
+        }
+    }
+}
+
+/// Feeds WIND_COV from the bound vehicle into the derived telemetry, where it
+/// replaces the wind-triangle estimate while fresh.
+async fn wind_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        if raw_msg.message_id != WIND_COV_MESSAGE_ID || raw_msg.system_id != system_id {
+            continue;
+        }
+        let Ok(MavMessage::WIND_COV(data)) =
+            MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+        else {
+            continue;
+        };
+        if !data.wind_x.is_finite() || !data.wind_y.is_finite() {
+            continue;
+        }
+        let state: tauri::State<'_, AppState> = handle.state();
+        state.live_runtime.with_runtime(|runtime| {
+            runtime.record_reported_wind(f64::from(data.wind_x), f64::from(data.wind_y))
+        });
+    }
+}
+
+/// Feeds WHEEL_DISTANCE from the bound vehicle into the derived odometer.
+async fn wheel_distance_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        if raw_msg.message_id != WHEEL_DISTANCE_MESSAGE_ID || raw_msg.system_id != system_id {
+            continue;
+        }
+        let Ok(MavMessage::WHEEL_DISTANCE(data)) =
+            MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+        else {
+            continue;
+        };
+        let wheels = usize::from(data.count).min(data.distance.len());
+        let state: tauri::State<'_, AppState> = handle.state();
+        state.live_runtime.with_runtime(|runtime| {
+            runtime.record_wheel_distance(data.time_usec, &data.distance[..wheels])
+        });
+    }
+}
+
+/// Feeds RC receiver link reports into the derived telemetry. RC_CHANNELS
+/// must come from the bound vehicle; radios report under their own IDs.
+async fn rc_link_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        let relevant = match raw_msg.message_id {
+            RC_CHANNELS_MESSAGE_ID => raw_msg.system_id == system_id,
+            RADIO_STATUS_MESSAGE_ID
+            | RADIO_RC_CHANNELS_MESSAGE_ID
+            | RADIO_LINK_STATS_MESSAGE_ID => true,
+            _ => false,
+        };
+        if !relevant {
+            continue;
+        }
+        let state: tauri::State<'_, AppState> = handle.state();
+        let telemetry_radio_is_rc = raw_msg.message_id == RADIO_STATUS_MESSAGE_ID
+            && state.settings.lock().await.rc_link_on_telemetry_radio;
+        let Some(quality) =
+            decode_rc_link(raw_msg.message_id, &raw_msg.payload, telemetry_radio_is_rc)
+        else {
+            continue;
+        };
+        state
+            .live_runtime
+            .with_runtime(|runtime| runtime.record_rc_link(quality));
+    }
+}
+
+/// Relays DISTANCE_SENSOR from the bound vehicle as `rangefinder://reading`,
+/// one sensor per orientation, and raises landing-assist warnings from the
+/// downward one.
+async fn rangefinder_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        if raw_msg.message_id != DISTANCE_SENSOR_MESSAGE_ID || raw_msg.system_id != system_id {
+            continue;
+        }
+        let Ok(MavMessage::DISTANCE_SENSOR(data)) =
+            MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+        else {
+            continue;
+        };
+        let reading = rangefinder_reading(&data);
+        let state: tauri::State<'_, AppState> = handle.state();
+        let profile = state.settings.lock().await.landing_assist.clone();
+        let warning = state
+            .live_runtime
+            .with_runtime(|runtime| runtime.record_rangefinder(reading.clone(), &profile));
+        live_runtime::emit_scoped(
+            &state.live_runtime,
+            event_names::RANGEFINDER_READING,
+            reading,
+        );
+        if let Some(warning) = warning {
+            live_runtime::emit_scoped(
+                &state.live_runtime,
+                event_names::LANDING_ASSIST_WARNING,
+                warning,
+            );
+        }
+    }
+}
+
+/// Forwards NAMED_VALUE_FLOAT/INT and DEBUG_VECT from any component of the
+/// bound vehicle's system (autopilot scripts, companion computers).
+async fn named_value_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        if !NAMED_VALUE_MESSAGE_IDS.contains(&raw_msg.message_id) || raw_msg.system_id != system_id
+        {
+            continue;
+        }
+        let Ok(message) =
+            MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+        else {
+            continue;
+        };
+        let Some(value) = named_value_from_message(
+            &message,
+            raw_msg.system_id,
+            raw_msg.component_id,
+            unix_epoch_usec(),
+        ) else {
+            continue;
+        };
+        let state: tauri::State<'_, AppState> = handle.state();
+        state
+            .live_runtime
+            .with_runtime(|runtime| runtime.record_named_value(value.clone()));
+        live_runtime::emit_scoped(&state.live_runtime, event_names::NAMED_VALUE, value);
+    }
+}
+
+/// Records VIDEO_STREAM_INFORMATION from any component of the bound vehicle's
+/// system, whether requested by `video_streams` or sent unsolicited.
+async fn video_stream_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        if raw_msg.message_id != VIDEO_STREAM_INFORMATION_MESSAGE_ID
+            || raw_msg.system_id != system_id
+        {
+            continue;
+        }
+        let Ok(MavMessage::VIDEO_STREAM_INFORMATION(data)) =
+            MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+        else {
+            continue;
+        };
+        let stream = video_stream_from_message(&data, raw_msg.component_id);
+        let state: tauri::State<'_, AppState> = handle.state();
+        let streams = state.live_runtime.with_runtime(|runtime| {
+            runtime
+                .record_video_stream(stream)
+                .then(|| runtime.video_streams().to_vec())
+        });
+        if let Some(streams) = streams {
+            live_runtime::emit_scoped(&state.live_runtime, event_names::VIDEO_STREAMS, streams);
+        }
+    }
+}
+
+/// Tracks GPS fix transitions from GPS_RAW_INT/GPS2_RAW, with RTK baseline
+/// ages from GPS_RTK/GPS2_RTK timed against the vehicle's boot clock.
+async fn gps_fix_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        if !GPS_FIX_MESSAGE_IDS.contains(&raw_msg.message_id) || raw_msg.system_id != system_id {
+            continue;
+        }
+        let Ok(message) =
+            MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+        else {
+            continue;
+        };
+        let state: tauri::State<'_, AppState> = handle.state();
+        let report = match message {
+            MavMessage::GPS_RAW_INT(data) => GpsRawReport {
+                instance: 0,
+                fix_type: data.fix_type as u8,
+                satellites_visible: data.satellites_visible,
+                eph: data.eph,
+            },
+            MavMessage::GPS2_RAW(data) => GpsRawReport {
+                instance: 1,
+                fix_type: data.fix_type as u8,
+                satellites_visible: data.satellites_visible,
+                eph: data.eph,
+            },
+            MavMessage::GPS_RTK(data) => {
+                state
+                    .live_runtime
+                    .with_runtime(|runtime| runtime.observe_gps_rtk(0, data.time_last_baseline_ms));
+                continue;
+            }
+            MavMessage::GPS2_RTK(data) => {
+                state
+                    .live_runtime
+                    .with_runtime(|runtime| runtime.observe_gps_rtk(1, data.time_last_baseline_ms));
+                continue;
+            }
+            MavMessage::SYSTEM_TIME(data) => {
+                state.live_runtime.with_runtime(|runtime| {
+                    runtime.observe_vehicle_time(data.time_boot_ms, data.time_unix_usec)
+                });
+                continue;
+            }
+            _ => continue,
+        };
+        let changed = state
+            .live_runtime
+            .with_runtime(|runtime| runtime.observe_gps_raw(report));
+        if let Some(changed) = changed {
+            live_runtime::emit_scoped(&state.live_runtime, event_names::GPS_FIX_CHANGED, changed);
+        }
+    }
+}
+
+/// Spawn `bridge` under the task watchdog, restarted from a fresh clone of
+/// the handle and vehicle if it panics.
+fn supervise_bridge<F, Fut>(
+    app: &tauri::AppHandle,
+    vehicle: &Vehicle,
+    name: &str,
+    bridge: F,
+) -> tokio::task::JoinHandle<()>
+where
+    F: Fn(tauri::AppHandle, Vehicle) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = ()> + Send + 'static,
+{
+    let handle = app.clone();
+    let vehicle = vehicle.clone();
+    supervise(app, name, move || bridge(handle.clone(), vehicle.clone()))
+}
+
+/// Re-checks the guided session whenever the link state changes.
+async fn guided_link_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    let mut link_sub = vehicle.link().state().subscribe();
+    while link_sub.recv().await.is_some() {
+        reconcile_guided_runtime(&handle, &vehicle).await;
+    }
+}
+
 pub(crate) async fn spawn_event_bridges(
     app: &tauri::AppHandle,
     vehicle: &Vehicle,
 ) -> Vec<tokio::task::JoinHandle<()>> {
     let state: tauri::State<'_, AppState> = app.state();
-    let mut task_set = TokioTaskSet::default();
+    let mut task_set = TokioTaskSet::new(app);
 
     live_runtime::spawn_send_event_bridges(
         state.live_runtime.clone(),
@@ -85,25 +481,121 @@ pub(crate) async fn spawn_event_bridges(
         vehicle,
     );
 
-    {
-        let handle = app.clone();
-        let guided_vehicle = vehicle.clone();
-        let mut link_sub = vehicle.link().state().subscribe();
-        task_set.tasks.push(tokio::spawn(async move {
-            while link_sub.recv().await.is_some() {
-                reconcile_guided_runtime(&handle, &guided_vehicle).await;
-            }
-        }));
-    }
+    task_set.tasks.extend([
+        supervise_bridge(app, vehicle, "guided_link", guided_link_bridge),
+        supervise_bridge(
+            app,
+            vehicle,
+            "heartbeat_registry",
+            heartbeat_registry_bridge,
+        ),
+        supervise_bridge(app, vehicle, "link_source", link_source_bridge),
+        supervise_bridge(app, vehicle, "high_latency", high_latency_bridge),
+        supervise_bridge(app, vehicle, "wind", wind_bridge),
+        supervise_bridge(app, vehicle, "wheel_distance", wheel_distance_bridge),
+        supervise_bridge(app, vehicle, "rangefinder", rangefinder_bridge),
+        supervise_bridge(app, vehicle, "rc_link", rc_link_bridge),
+        supervise_bridge(app, vehicle, "named_value", named_value_bridge),
+        supervise_bridge(app, vehicle, "gps_fix", gps_fix_bridge),
+        supervise_bridge(app, vehicle, "video_stream", video_stream_bridge),
+        supervise_bridge(
+            app,
+            vehicle,
+            "flight_track",
+            crate::flight_track::flight_track_bridge,
+        ),
+        supervise_bridge(
+            app,
+            vehicle,
+            "link_loss",
+            crate::link_loss::link_loss_watcher,
+        ),
+        supervise_bridge(
+            app,
+            vehicle,
+            "gcs_command",
+            crate::gcs_commands::gcs_command_bridge,
+        ),
+        supervise_bridge(
+            app,
+            vehicle,
+            "param_attribution",
+            crate::param_changes::param_attribution_bridge,
+        ),
+        supervise_bridge(
+            app,
+            vehicle,
+            "time_sync",
+            crate::time_sync::time_sync_bridge,
+        ),
+        supervise_bridge(
+            app,
+            vehicle,
+            "precision_landing",
+            crate::precision_landing::precision_landing_bridge,
+        ),
+        supervise_bridge(
+            app,
+            vehicle,
+            "winch_status",
+            crate::payload::winch_status_bridge,
+        ),
+        supervise_bridge(app, vehicle, "health", crate::health::health_bridge),
+        supervise_bridge(
+            app,
+            vehicle,
+            "fence_proximity",
+            crate::fence_proximity::fence_proximity_bridge,
+        ),
+        supervise_bridge(
+            app,
+            vehicle,
+            "home_moved",
+            crate::home_moved::home_moved_bridge,
+        ),
+        supervise_bridge(
+            app,
+            vehicle,
+            "session_recovery",
+            crate::session_recovery::session_recovery_bridge,
+        ),
+        supervise_bridge(app, vehicle, "background_keepalive", |handle, _| {
+            crate::background_keepalive::background_keepalive_bridge(handle)
+        }),
+        supervise_bridge(
+            app,
+            vehicle,
+            "unknown_message",
+            crate::unknown_messages::unknown_message_bridge,
+        ),
+        supervise_bridge(
+            app,
+            vehicle,
+            "link_quality",
+            crate::diagnostics_bundle::link_quality_bridge,
+        ),
+    ]);
+    task_set
+        .tasks
+        .push(tokio::spawn(crate::task_watchdog::task_monitor(
+            app.clone(),
+            vehicle.clone(),
+        )));
 
     task_set.tasks
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
+    use crate::ipc::SourceKind;
     use crate::ipc::session::VehicleState;
+    use crate::mock_link::{LinkFaults, MOCK_SYSTEM_ID, MockLink, heartbeat};
+    use ironwing_core::live_runtime::{LiveVehicleRuntime, NoopEventSink};
+    use ironwing_core::vehicle_config;
     use ironwing_core::vehicle_snapshot::mav_severity_name;
-    use mavkit::{AutopilotType, VehicleType};
+    use mavkit::{AutopilotType, Vehicle, VehicleType};
 
     /// Guard against the serde wire format drifting — multi-word enum variants
     /// must serialize as snake_case strings so the frontend can match them.
@@ -140,4 +632,36 @@ mod tests {
         assert_eq!(mav_severity_name(Info), "info");
         assert_eq!(mav_severity_name(Debug), "debug");
     }
+
+    #[tokio::test]
+    async fn live_envelope_carries_the_bound_vehicle_system_id() {
+        let MockLink {
+            gcs,
+            vehicle: mut far_end,
+        } = MockLink::new(LinkFaults::default(), LinkFaults::default());
+        far_end.send(&heartbeat(0)).await;
+        let vehicle = Vehicle::from_connection(
+            gcs,
+            vehicle_config::live_vehicle_config(Duration::from_secs(5)),
+        )
+        .await
+        .expect("connect over the mock link");
+
+        let mut runtime = LiveVehicleRuntime::new(NoopEventSink);
+        let live = runtime.open_session_snapshot(SourceKind::Live);
+        assert_eq!(live.envelope.system_id, None);
+        let _ = runtime.ack_session_snapshot(
+            &live.envelope.session_id,
+            live.envelope.seek_epoch,
+            live.envelope.reset_revision,
+        );
+
+        runtime.seed_connected_vehicle(&vehicle);
+        let envelope = runtime
+            .current_stream_envelope(web_time::Instant::now())
+            .expect("active live envelope");
+        assert_eq!(envelope.system_id, Some(MOCK_SYSTEM_ID));
+        let json = serde_json::to_value(&envelope).expect("serialize envelope");
+        assert_eq!(json["system_id"], MOCK_SYSTEM_ID);
+    }
 }
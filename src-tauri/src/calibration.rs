@@ -0,0 +1,90 @@
+use ironwing_core::calibration_status::{
+    CALIBRATION_POLL_INTERVAL, CALIBRATION_RESULT_TIMEOUT, COMPASS_CALIBRATION_WATCH,
+    CalibrationFirmware, CalibrationWatch, completes_on_ack,
+};
+use ironwing_core::event_names;
+use ironwing_core::live_runtime::commands as live_commands;
+use tauri::Manager;
+
+use crate::AppState;
+use crate::e2e_emit::emit_event;
+use crate::helpers::{ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{AppError, CalibrationKind, CalibrationResult, OperationId, status_text_mark};
+
+/// A marker for the newest STATUSTEXT seen so far.
+pub(crate) fn current_status_text_mark(state: &AppState) -> u64 {
+    state
+        .live_runtime
+        .with_runtime(|runtime| status_text_mark(runtime.status_text_history()))
+}
+
+/// Poll the STATUSTEXT history until `watch` has a result, emitting
+/// `calibration://progress` for each calibration message.
+async fn follow_calibration(
+    app: &tauri::AppHandle,
+    mut watch: CalibrationWatch,
+) -> CalibrationResult {
+    let state = app.state::<AppState>();
+    loop {
+        let mut progress = Vec::new();
+        let result = state.live_runtime.with_runtime(|runtime| {
+            watch.observe(runtime.status_text_history(), |update| {
+                progress.push(update)
+            })
+        });
+        for update in &progress {
+            emit_event(app, event_names::CALIBRATION_PROGRESS, update);
+        }
+        if let Some(result) = result {
+            return result;
+        }
+        tokio::time::sleep(CALIBRATION_POLL_INTERVAL).await;
+    }
+}
+
+/// Start a gyro or accel calibration and wait up to
+/// `CALIBRATION_RESULT_TIMEOUT` for the vehicle to report how it ended.
+pub(crate) async fn run_simple_calibration(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    kind: CalibrationKind,
+    operation_id: OperationId,
+) -> Result<CalibrationResult, AppError> {
+    ensure_live_write_allowed(state, operation_id).await?;
+    let vehicle = with_vehicle(state).await?;
+    let firmware = CalibrationFirmware::from(vehicle.identity().autopilot);
+    let mark = current_status_text_mark(state);
+    match kind {
+        CalibrationKind::Gyro => live_commands::calibrate_gyro(&vehicle).await?,
+        CalibrationKind::Accel => live_commands::calibrate_accel(&vehicle).await?,
+        CalibrationKind::Compass => {
+            return Err(AppError::validation(
+                "compass calibration runs through calibrate_compass_start",
+            ));
+        }
+    }
+    if completes_on_ack(firmware, kind) {
+        return Ok(CalibrationResult::Succeeded);
+    }
+    let watch = CalibrationWatch::new(firmware, kind, mark, CALIBRATION_RESULT_TIMEOUT);
+    Ok(follow_calibration(app, watch).await)
+}
+
+/// Relay compass calibration STATUSTEXT after `mark` as
+/// `calibration://progress`; the result itself arrives as MAG_CAL_REPORT.
+pub(crate) fn watch_compass_calibration(
+    app: &tauri::AppHandle,
+    vehicle: &mavkit::Vehicle,
+    mark: u64,
+) {
+    let app = app.clone();
+    let watch = CalibrationWatch::new(
+        CalibrationFirmware::from(vehicle.identity().autopilot),
+        CalibrationKind::Compass,
+        mark,
+        COMPASS_CALIBRATION_WATCH,
+    );
+    tokio::spawn(async move {
+        follow_calibration(&app, watch).await;
+    });
+}
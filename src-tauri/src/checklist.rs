@@ -0,0 +1,78 @@
+use ironwing_core::checklist::check_checklist;
+use ironwing_core::live_runtime;
+
+use crate::AppState;
+use crate::ipc::{AppError, ChecklistConfig, ChecklistItem, ChecklistState, SettingsPatch};
+
+/// Refuse to arm while an enforced checklist has required items outstanding.
+/// Unlike the safety gates, force-arming does not skip it: the checklist is
+/// the operator's own sign-off, and enforcement can be turned off instead.
+pub(crate) fn ensure_checklist(state: &AppState) -> Result<(), AppError> {
+    check_checklist(
+        &state
+            .live_runtime
+            .with_runtime(|runtime| runtime.checklist_state()),
+    )
+}
+
+fn current_state(state: &AppState) -> ChecklistState {
+    live_runtime::emit_checklist_update(&state.live_runtime);
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.checklist_state())
+}
+
+/// Replace the checklist and save it with the other settings. `enforce`
+/// keeps the current enforcement mode when omitted.
+#[tauri::command]
+pub(crate) async fn checklist_define(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    items: Vec<ChecklistItem>,
+    enforce: Option<bool>,
+) -> Result<ChecklistState, AppError> {
+    let enforce_on_arm = enforce.unwrap_or_else(|| {
+        state
+            .live_runtime
+            .with_runtime(|runtime| runtime.checklist_config().enforce_on_arm)
+    });
+    let patch = SettingsPatch {
+        checklist: Some(ChecklistConfig {
+            items,
+            enforce_on_arm,
+        }),
+        ..SettingsPatch::default()
+    };
+    crate::settings::update_settings(&app, &state, patch).await?;
+    Ok(current_state(&state))
+}
+
+#[tauri::command]
+pub(crate) fn checklist_state(state: tauri::State<'_, AppState>) -> ChecklistState {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.checklist_state())
+}
+
+/// Tick or untick a manual item.
+#[tauri::command]
+pub(crate) fn checklist_check(
+    state: tauri::State<'_, AppState>,
+    item_id: String,
+    checked: bool,
+) -> Result<ChecklistState, AppError> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.tick_checklist_item(&item_id, checked))
+        .map_err(AppError::validation)?;
+    Ok(current_state(&state))
+}
+
+/// Untick every manual item, e.g. before the next sortie.
+#[tauri::command]
+pub(crate) fn checklist_reset(state: tauri::State<'_, AppState>) -> ChecklistState {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.reset_checklist());
+    current_state(&state)
+}
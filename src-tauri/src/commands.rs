@@ -1,36 +1,55 @@
 use std::collections::HashMap;
 use std::future::Future;
-use std::sync::atomic::Ordering;
 
-use crate::bridges::TELEMETRY_INTERVAL_MS;
 use crate::bridges::emit_scoped;
+use crate::calibration;
+use crate::component_commands::{autopilot_command, command_long_to_component};
 use crate::e2e_emit::emit_event;
 use crate::guided::{emit_guided_snapshot, live_context_from_vehicle};
+use crate::ipc::telemetry::RcLinkQuality;
 use crate::ipc::{
-    AckSessionSnapshotResult, DomainProvenance, DomainValue, GuidedCommandResult, GuidedFailure,
-    GuidedFatalityScope, GuidedLiveContext, MissionDownload, OpenSessionSnapshot, OperationId,
-    RcOverrideChannelWire, ScopedEvent, SessionEnvelope, SourceKind, StartGuidedSessionRequest,
-    UpdateGuidedSessionRequest,
+    AckSessionSnapshotResult, AppError, BatteryStats, CalibrationKind, CalibrationResult,
+    DistanceUnit, DomainProvenance, DomainValue, EventBridgeStats, FlightModeChannelConfig,
+    FlightModeCurrent, GpsStatus, GuidedCommandResult, GuidedFailure, GuidedFatalityScope,
+    GuidedLiveContext, LinkProfile, MissionDownload, MissionTransferProgress, OpenSessionSnapshot,
+    OperationId, ParamBitState, ParamExportFormat, ParamExportOptions, ParamMetadata,
+    ParamSearchOptions, ParamWriteOutcome, ParsedParamFile, RcOverrideChannelWire, RtlPreview,
+    ScopedEvent, SessionConnection, SessionEnvelope, SettingsPatch, SourceKind, SpeedUnit,
+    StartGuidedSessionRequest, StatusTextEntry, TelemetryEncoding, TelemetrySnapshot,
+    TemperatureUnit, TransferOutcome, UnitSystem, UpdateGuidedSessionRequest, VehicleListEntry,
+    VehicleSelected, VehicleState, session_connection_from_link_state,
 };
+use crate::journal;
+use crate::param_changes::expect_param_writes;
+use crate::safety_gates::ensure_safety_gates;
+use crate::settings;
 use crate::{
     AppState,
-    helpers::{ensure_live_write_allowed, with_vehicle},
+    helpers::{ensure_live_write_allowed, vehicle_is_armed, with_vehicle},
 };
+use ironwing_core::component_command::ComponentCommand;
+use ironwing_core::dialect::MavCmd;
 use ironwing_core::event_names;
-use ironwing_core::live_runtime::RuntimeCapabilities;
+use ironwing_core::flight_mode_switch::{flight_mode_channel_config, flight_mode_slot_write};
+use ironwing_core::flight_modes::{current_flight_mode, resolve_mode_by_name};
+use ironwing_core::link_sources;
 use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::live_runtime::{self, RuntimeCapabilities};
+use ironwing_core::param_bits::{apply_param_bits, describe_param_bits};
+use ironwing_core::param_value::display_param_store;
+use ironwing_core::param_value::param_value;
+use ironwing_core::rtl_preview::{RtlFirmware, RtlStart};
+use ironwing_core::takeoff::validate_takeoff_altitude;
 use ironwing_core::telemetry::{self, MessageRateInfo};
+use ironwing_core::transfer_rate::{self, TransferRateEstimate, TransferRateTracker};
 use ironwing_core::transport::{self, TransportDescriptor};
 use mavkit::{
-    FencePlan, FlightMode, HomePosition, MissionIssue, MissionPlan, ParamStore, ParamWriteResult,
-    RallyPlan,
+    AutopilotType, FencePlan, FlightMode, HomePosition, MissionIssue, MissionPlan,
+    ObservationSubscription, ParamStore, ParamWriteResult, RallyPlan, TransferProgress,
 };
+use serde_json::json;
 use tauri::Manager;
 
-#[cfg(test)]
-use crate::ipc::SessionConnection;
-#[cfg(test)]
-use crate::ipc::StatusTextEntry;
 #[cfg(test)]
 use ironwing_core::live::{LiveSnapshotInput, base_live_snapshot_from_caches};
 
@@ -133,22 +152,115 @@ async fn build_live_snapshot(
     snapshot
 }
 
+/// How often a transfer with no new progress is re-checked for a stall.
+const TRANSFER_STALL_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+fn emit_transfer_progress(
+    state: &AppState,
+    progress: &TransferProgress,
+    estimate: TransferRateEstimate,
+    outcome: Option<(TransferOutcome, Option<String>)>,
+) {
+    let (outcome, failure_reason) = outcome.unzip();
+    live_runtime::emit_scoped(
+        &state.live_runtime,
+        event_names::MISSION_PROGRESS,
+        MissionTransferProgress {
+            progress: progress.clone(),
+            items_per_sec: estimate.items_per_sec,
+            eta_secs: estimate.eta_secs,
+            stalled: estimate.stalled,
+            outcome,
+            failure_reason: failure_reason.flatten(),
+        },
+    );
+}
+
 async fn run_cancellable_plan_op<T, Start, Wait>(
     state: &AppState,
     operation_id: OperationId,
     start: Start,
-) -> Result<T, String>
+) -> Result<T, AppError>
 where
-    Start: FnOnce(mavkit::Vehicle) -> Result<(crate::MissionCancelToken, Wait), String>,
-    Wait: Future<Output = Result<T, String>>,
+    Start: FnOnce(
+        mavkit::Vehicle,
+    ) -> Result<
+        (
+            crate::MissionCancelToken,
+            ObservationSubscription<TransferProgress>,
+            Wait,
+        ),
+        AppError,
+    >,
+    Wait: Future<Output = Result<T, AppError>>,
 {
     ensure_live_write_allowed(state, operation_id).await?;
     let vehicle = with_vehicle(state).await?;
-    let (cancel_token, wait) = start(vehicle)?;
+    let (cancel_token, mut progress_sub, wait) = start(vehicle)?;
 
-    state.mission_op_cancel.lock().await.replace(cancel_token);
-    let result = wait.await;
+    state
+        .mission_op_cancel
+        .lock()
+        .await
+        .replace(cancel_token.clone());
+
+    // Relay progress with a rate and ETA while the operation runs, re-emitting
+    // when it stalls or recovers between progress reports.
+    let mut tracker = TransferRateTracker::default();
+    let mut last_progress: Option<TransferProgress> = None;
+    let mut reported_stall = false;
+    let mut stall_check = tokio::time::interval(TRANSFER_STALL_CHECK_INTERVAL);
+    tokio::pin!(wait);
+    let result = loop {
+        tokio::select! {
+            result = &mut wait => break result,
+            Some(progress) = progress_sub.recv() => {
+                let now = web_time::Instant::now();
+                let completed = u32::from(progress.completed_items);
+                let total = u32::from(progress.total_items);
+                tracker.observe(completed, now);
+                let estimate = tracker.estimate(completed, total, now);
+                reported_stall = estimate.stalled;
+                emit_transfer_progress(state, &progress, estimate, None);
+                last_progress = Some(progress);
+            }
+            _ = stall_check.tick() => {
+                let Some(progress) = last_progress.as_ref() else {
+                    continue;
+                };
+                let estimate = tracker.estimate(
+                    u32::from(progress.completed_items),
+                    u32::from(progress.total_items),
+                    web_time::Instant::now(),
+                );
+                if estimate.stalled != reported_stall {
+                    reported_stall = estimate.stalled;
+                    emit_transfer_progress(state, progress, estimate, None);
+                }
+            }
+        }
+    };
     state.mission_op_cancel.lock().await.take();
+
+    // Close the stream with a terminal event so the UI can tell a finished
+    // transfer from a cancelled or failed one, even when it ended before the
+    // first progress report.
+    let (outcome, failure_reason) = match &result {
+        Ok(_) => (TransferOutcome::Completed, None),
+        Err(_) if cancel_token.is_cancelled() => (TransferOutcome::Cancelled, None),
+        Err(error) => (TransferOutcome::Failed, Some(error.clone())),
+    };
+    if let Some(progress) = transfer_rate::terminal_progress(operation_id, last_progress, outcome) {
+        let estimate = TransferRateEstimate {
+            stalled: false,
+            ..tracker.estimate(
+                u32::from(progress.completed_items),
+                u32::from(progress.total_items),
+                web_time::Instant::now(),
+            )
+        };
+        emit_transfer_progress(state, &progress, estimate, Some((outcome, failure_reason)));
+    }
     result
 }
 
@@ -193,54 +305,147 @@ pub(crate) async fn emit_live_snapshot_restore(
     );
 }
 
+/// Arm the vehicle. `force` is the MAVLink force-arm that bypasses the
+/// autopilot's pre-arm checks; `override_gates` only skips the GCS-side
+/// safety gates.
 #[tauri::command]
 pub(crate) async fn arm_vehicle(
     state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
     force: bool,
-) -> Result<(), String> {
-    ensure_live_write_allowed(state.inner(), OperationId::ArmVehicle).await?;
-    let vehicle = with_vehicle(&state).await?;
-    live_commands::arm(&vehicle, force)
-        .await
-        .map_err(|e| e.to_string())
+    override_gates: Option<bool>,
+) -> Result<(), AppError> {
+    let override_gates = override_gates.unwrap_or(false);
+    journal::record(
+        &state.journal,
+        OperationId::ArmVehicle,
+        json!({ "force": force, "override_gates": override_gates }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::ArmVehicle).await?;
+            let vehicle = with_vehicle(&state).await?;
+            ensure_safety_gates(&state, override_gates).await?;
+            crate::checklist::ensure_checklist(&state)?;
+            crate::link_loss::check_expected_failsafe(&app, &state, &vehicle).await;
+            let identity = vehicle.identity();
+            let arm = ComponentCommand::arm(identity.system_id, identity.component_id, force);
+            autopilot_command(&app, &state.command_link, &vehicle, arm).await
+        },
+    )
+    .await
 }
 
 #[tauri::command]
 pub(crate) async fn disarm_vehicle(
     state: tauri::State<'_, AppState>,
     force: bool,
-) -> Result<(), String> {
-    ensure_live_write_allowed(state.inner(), OperationId::DisarmVehicle).await?;
-    let vehicle = with_vehicle(&state).await?;
-    live_commands::disarm(&vehicle, force)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::DisarmVehicle,
+        json!({ "force": force }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::DisarmVehicle).await?;
+            let vehicle = with_vehicle(&state).await?;
+            live_commands::disarm(&vehicle, force)
+                .await
+                .map_err(AppError::from)
+        },
+    )
+    .await
 }
 
 #[tauri::command]
 pub(crate) async fn set_flight_mode(
     state: tauri::State<'_, AppState>,
     custom_mode: u32,
-) -> Result<(), String> {
-    ensure_live_write_allowed(state.inner(), OperationId::SetFlightMode).await?;
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::SetFlightMode,
+        json!({ "custom_mode": custom_mode }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::SetFlightMode).await?;
+            let vehicle = with_vehicle(&state).await?;
+            live_commands::set_flight_mode(&vehicle, custom_mode)
+                .await
+                .map_err(AppError::from)
+        },
+    )
+    .await
+}
+
+/// Switch to the mode called `name` in the connected vehicle's mode table,
+/// so the frontend never needs firmware-specific custom_mode numbers.
+#[tauri::command]
+pub(crate) async fn set_flight_mode_by_name(
+    state: tauri::State<'_, AppState>,
+    name: String,
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::SetFlightModeByName,
+        json!({ "name": name }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::SetFlightModeByName).await?;
+            let vehicle = with_vehicle(&state).await?;
+            let modes = live_commands::get_available_modes(&vehicle);
+            let mode = resolve_mode_by_name(&modes, &name).map_err(AppError::validation)?;
+            live_commands::set_flight_mode(&vehicle, mode.custom_mode)
+                .await
+                .map_err(AppError::from)
+        },
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn flight_mode_current(
+    state: tauri::State<'_, AppState>,
+) -> Result<FlightModeCurrent, AppError> {
     let vehicle = with_vehicle(&state).await?;
-    live_commands::set_flight_mode(&vehicle, custom_mode)
-        .await
-        .map_err(|e| e.to_string())
+    let current = vehicle
+        .available_modes()
+        .current()
+        .latest()
+        .ok_or_else(|| AppError::validation("no heartbeat has reported a mode yet"))?;
+    let identity = vehicle.identity();
+    Ok(current_flight_mode(
+        &live_commands::get_available_modes(&vehicle),
+        current.custom_mode,
+        identity.autopilot,
+        identity.vehicle_type,
+    ))
 }
 
-/// Takeoff requires a guided session in the new mavkit API. This uses a
-/// raw COMMAND_LONG as a back-compat shim until the caller migrates.
+/// Takeoff requires a guided session in the new mavkit API. This sends
+/// MAV_CMD_NAV_TAKEOFF itself as a back-compat shim until the caller
+/// migrates. `override_gates` skips the GCS-side safety gates, as on
+/// `arm_vehicle`.
 #[tauri::command]
 pub(crate) async fn vehicle_takeoff(
     state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
     altitude_m: f32,
-) -> Result<(), String> {
-    ensure_live_write_allowed(state.inner(), OperationId::VehicleTakeoff).await?;
-    let vehicle = with_vehicle(&state).await?;
-    live_commands::takeoff(&vehicle, altitude_m)
-        .await
-        .map_err(|e| e.to_string())
+    override_gates: Option<bool>,
+) -> Result<(), AppError> {
+    let override_gates = override_gates.unwrap_or(false);
+    journal::record(
+        &state.journal,
+        OperationId::VehicleTakeoff,
+        json!({ "altitude_m": altitude_m, "override_gates": override_gates }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::VehicleTakeoff).await?;
+            let max_alt_m = state.settings.lock().await.takeoff_max_alt_m;
+            validate_takeoff_altitude(altitude_m, max_alt_m)?;
+            let vehicle = with_vehicle(&state).await?;
+            ensure_safety_gates(&state, override_gates).await?;
+            let identity = vehicle.identity();
+            let takeoff =
+                ComponentCommand::takeoff(identity.system_id, identity.component_id, altitude_m);
+            autopilot_command(&app, &state.command_link, &vehicle, takeoff).await
+        },
+    )
+    .await
 }
 
 #[tauri::command]
@@ -248,146 +453,442 @@ pub(crate) async fn start_guided_session(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
     request: StartGuidedSessionRequest,
-) -> Result<GuidedCommandResult, String> {
-    let vehicle = state.live_runtime.with_runtime(|runtime| runtime.vehicle());
-    let source_kind = state
-        .live_runtime
-        .with_runtime(|runtime| runtime.guided_source_kind());
-    let context = vehicle
-        .as_ref()
-        .map(live_context_from_vehicle)
-        .unwrap_or(GuidedLiveContext::unavailable());
+) -> Result<GuidedCommandResult, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::StartGuidedSession,
+        json!({ "request": request }),
+        async {
+            let vehicle = state.live_runtime.with_runtime(|runtime| runtime.vehicle());
+            let source_kind = state
+                .live_runtime
+                .with_runtime(|runtime| runtime.guided_source_kind());
+            let context = vehicle
+                .as_ref()
+                .map(live_context_from_vehicle)
+                .unwrap_or(GuidedLiveContext::unavailable());
+
+            {
+                let mut guided_runtime = state.guided_runtime.lock().await;
+                if let Err(failure) =
+                    guided_runtime.reserve_start(source_kind, context, request.session.clone())
+                {
+                    return Ok(GuidedCommandResult::Rejected { failure });
+                }
+            }
+
+            let Some(vehicle) = vehicle else {
+                return Ok(guided_operation_failure(
+                    crate::ipc::OperationId::StartGuidedSession,
+                    crate::ipc::ReasonKind::Unavailable,
+                    "guided control requires a live vehicle session",
+                    true,
+                    GuidedFatalityScope::Session,
+                ));
+            };
+
+            let crate::ipc::GuidedSession::Goto {
+                latitude_deg,
+                longitude_deg,
+                altitude_msl_m,
+            } = request.session;
+
+            if let Err(error) =
+                live_commands::guided_goto(&vehicle, latitude_deg, longitude_deg, altitude_msl_m)
+                    .await
+            {
+                return Ok(state.guided_runtime.lock().await.abort_reserved(
+                    crate::ipc::OperationId::StartGuidedSession,
+                    crate::ipc::ReasonKind::Failed,
+                    error.to_string(),
+                ));
+            }
+            crate::goto::emit_guided_target(
+                &app,
+                crate::ipc::GuidedTarget {
+                    latitude_deg,
+                    longitude_deg,
+                    altitude_m: altitude_msl_m,
+                    options: crate::ipc::GotoOptions {
+                        frame: crate::ipc::GotoFrame::Amsl,
+                        ..crate::ipc::GotoOptions::default()
+                    },
+                },
+            );
 
-    {
-        let mut guided_runtime = state.guided_runtime.lock().await;
-        if let Err(failure) =
-            guided_runtime.reserve_start(source_kind, context, request.session.clone())
-        {
-            return Ok(GuidedCommandResult::Rejected { failure });
-        }
-    }
+            let result = state
+                .guided_runtime
+                .lock()
+                .await
+                .commit_reserved(crate::ipc::OperationId::StartGuidedSession);
+            if let GuidedCommandResult::Accepted { state: guided } = &result {
+                emit_guided_snapshot(&state, &app, guided.clone()).await;
+            }
+            Ok(result)
+        },
+    )
+    .await
+}
 
-    let Some(vehicle) = vehicle else {
-        return Ok(guided_operation_failure(
-            crate::ipc::OperationId::StartGuidedSession,
-            crate::ipc::ReasonKind::Unavailable,
-            "guided control requires a live vehicle session",
-            true,
-            GuidedFatalityScope::Session,
-        ));
-    };
+#[tauri::command]
+pub(crate) async fn update_guided_session(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+    request: UpdateGuidedSessionRequest,
+) -> Result<GuidedCommandResult, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::UpdateGuidedSession,
+        json!({ "request": request }),
+        async {
+            let vehicle = state.live_runtime.with_runtime(|runtime| runtime.vehicle());
+            let source_kind = state
+                .live_runtime
+                .with_runtime(|runtime| runtime.guided_source_kind());
+            let context = vehicle
+                .as_ref()
+                .map(live_context_from_vehicle)
+                .unwrap_or(GuidedLiveContext::unavailable());
+
+            {
+                let mut guided_runtime = state.guided_runtime.lock().await;
+                if let Err(failure) =
+                    guided_runtime.reserve_update(source_kind, context, request.session.clone())
+                {
+                    return Ok(GuidedCommandResult::Rejected { failure });
+                }
+            }
+
+            let Some(vehicle) = vehicle else {
+                return Ok(guided_operation_failure(
+                    crate::ipc::OperationId::UpdateGuidedSession,
+                    crate::ipc::ReasonKind::Unavailable,
+                    "guided control requires a live vehicle session",
+                    true,
+                    GuidedFatalityScope::Session,
+                ));
+            };
+
+            let crate::ipc::GuidedSession::Goto {
+                latitude_deg,
+                longitude_deg,
+                altitude_msl_m,
+            } = request.session;
+
+            if let Err(error) =
+                live_commands::guided_goto(&vehicle, latitude_deg, longitude_deg, altitude_msl_m)
+                    .await
+            {
+                return Ok(state.guided_runtime.lock().await.abort_reserved(
+                    crate::ipc::OperationId::UpdateGuidedSession,
+                    crate::ipc::ReasonKind::Failed,
+                    error.to_string(),
+                ));
+            }
+            crate::goto::emit_guided_target(
+                &app,
+                crate::ipc::GuidedTarget {
+                    latitude_deg,
+                    longitude_deg,
+                    altitude_m: altitude_msl_m,
+                    options: crate::ipc::GotoOptions {
+                        frame: crate::ipc::GotoFrame::Amsl,
+                        ..crate::ipc::GotoOptions::default()
+                    },
+                },
+            );
 
-    let crate::ipc::GuidedSession::Goto {
-        latitude_deg,
-        longitude_deg,
-        altitude_msl_m,
-    } = request.session;
+            let result = state
+                .guided_runtime
+                .lock()
+                .await
+                .commit_reserved(crate::ipc::OperationId::UpdateGuidedSession);
+            if let GuidedCommandResult::Accepted { state: guided } = &result {
+                emit_guided_snapshot(&state, &app, guided.clone()).await;
+            }
+            Ok(result)
+        },
+    )
+    .await
+}
 
-    if let Err(error) =
-        live_commands::guided_goto(&vehicle, latitude_deg, longitude_deg, altitude_msl_m).await
-    {
-        return Ok(state.guided_runtime.lock().await.abort_reserved(
-            crate::ipc::OperationId::StartGuidedSession,
-            crate::ipc::ReasonKind::Failed,
-            error.to_string(),
-        ));
-    }
+#[tauri::command]
+pub(crate) async fn stop_guided_session(
+    state: tauri::State<'_, AppState>,
+    _app: tauri::AppHandle,
+) -> Result<GuidedCommandResult, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::StopGuidedSession,
+        json!({}),
+        async {
+            let vehicle = state.live_runtime.with_runtime(|runtime| runtime.vehicle());
+            let source_kind = state
+                .live_runtime
+                .with_runtime(|runtime| runtime.guided_source_kind());
+            let context = vehicle
+                .as_ref()
+                .map(live_context_from_vehicle)
+                .unwrap_or(GuidedLiveContext::unavailable());
+            let result = state.guided_runtime.lock().await.stop(source_kind, context);
+            Ok(result)
+        },
+    )
+    .await
+}
 
-    let result = state
-        .guided_runtime
-        .lock()
-        .await
-        .commit_reserved(crate::ipc::OperationId::StartGuidedSession);
-    if let GuidedCommandResult::Accepted { state: guided } = &result {
-        emit_guided_snapshot(&state, &app, guided.clone()).await;
-    }
-    Ok(result)
+#[tauri::command]
+pub(crate) async fn get_available_modes(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<FlightMode>, AppError> {
+    let vehicle = with_vehicle(&state).await?;
+    Ok(live_commands::get_available_modes(&vehicle))
 }
 
+/// The mode-switch channel, each slot's PWM band and mode, and the slot the
+/// physical switch selects right now.
 #[tauri::command]
-pub(crate) async fn update_guided_session(
+pub(crate) async fn flightmode_channel_config(
     state: tauri::State<'_, AppState>,
-    app: tauri::AppHandle,
-    request: UpdateGuidedSessionRequest,
-) -> Result<GuidedCommandResult, String> {
-    let vehicle = state.live_runtime.with_runtime(|runtime| runtime.vehicle());
-    let source_kind = state
-        .live_runtime
-        .with_runtime(|runtime| runtime.guided_source_kind());
-    let context = vehicle
-        .as_ref()
-        .map(live_context_from_vehicle)
-        .unwrap_or(GuidedLiveContext::unavailable());
+) -> Result<FlightModeChannelConfig, AppError> {
+    let vehicle = with_vehicle(&state).await?;
+    let store = live_commands::param_get_all(&vehicle).map_err(AppError::from)?;
+    let modes = live_commands::get_available_modes(&vehicle);
+    let rc = vehicle.telemetry().rc();
+    let encoding = live_commands::param_encoding(&vehicle);
+    flight_mode_channel_config(
+        vehicle.identity().autopilot,
+        |name| {
+            store
+                .params
+                .get(name)
+                .map(|param| param_value(param, encoding).as_f64() as f32)
+        },
+        |custom_mode| {
+            modes
+                .iter()
+                .find(|mode| mode.custom_mode == custom_mode)
+                .map(|mode| mode.name.clone())
+        },
+        |channel| {
+            rc.channel_pwm_us((channel - 1) as _)
+                .and_then(|pwm| pwm.latest())
+                .map(|sample| f64::from(sample.value).round() as u16)
+        },
+    )
+    .map_err(AppError::validation)
+}
 
-    {
-        let mut guided_runtime = state.guided_runtime.lock().await;
-        if let Err(failure) =
-            guided_runtime.reserve_update(source_kind, context, request.session.clone())
-        {
-            return Ok(GuidedCommandResult::Rejected { failure });
+/// Put `custom_mode` on mode-switch slot `slot` (1..=6).
+#[tauri::command]
+pub(crate) async fn flightmode_assign(
+    state: tauri::State<'_, AppState>,
+    slot: u8,
+    custom_mode: u32,
+) -> Result<ParamWriteOutcome, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::FlightmodeAssign,
+        json!({ "slot": slot, "custom_mode": custom_mode }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::FlightmodeAssign).await?;
+            let vehicle = with_vehicle(&state).await?;
+            let store = live_commands::param_get_all(&vehicle).map_err(AppError::from)?;
+            let encoding = live_commands::param_encoding(&vehicle);
+            let (name, value) = flight_mode_slot_write(
+                vehicle.identity().autopilot,
+                |name| {
+                    store
+                        .params
+                        .get(name)
+                        .map(|param| param_value(param, encoding).as_f64() as f32)
+                },
+                slot,
+                custom_mode,
+            )
+            .map_err(AppError::validation)?;
+            expect_param_writes(state.inner(), [name.as_str()]);
+            let result = live_commands::param_write(&vehicle, &name, f64::from(value))
+                .await
+                .map_err(AppError::from)?;
+            let persistence = persist_param_writes(&vehicle, None, result.success).await;
+            Ok(param_write_outcome(result, &persistence))
+        },
+    )
+    .await
+}
+
+/// Current telemetry with display-unit copies attached, or SI only when `raw`
+/// is set.
+#[tauri::command]
+pub(crate) fn get_current_telemetry(
+    state: tauri::State<'_, AppState>,
+    raw: Option<bool>,
+) -> TelemetrySnapshot {
+    state.live_runtime.with_runtime(|runtime| {
+        let telemetry = match runtime.vehicle() {
+            Some(vehicle) => runtime.current_telemetry(&vehicle),
+            None => runtime.live_telemetry().clone(),
+        };
+        if raw.unwrap_or(false) {
+            telemetry
+        } else {
+            runtime.telemetry_in_display_units(telemetry)
         }
-    }
+    })
+}
 
-    let Some(vehicle) = vehicle else {
-        return Ok(guided_operation_failure(
-            crate::ipc::OperationId::UpdateGuidedSession,
-            crate::ipc::ReasonKind::Unavailable,
-            "guided control requires a live vehicle session",
-            true,
-            GuidedFatalityScope::Session,
-        ));
+/// Choose display units for telemetry events, telemetry queries and
+/// `log_query`. Takes effect from the next telemetry emission and is saved
+/// with the other settings.
+#[tauri::command]
+pub(crate) async fn set_unit_system(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    distance: DistanceUnit,
+    speed: SpeedUnit,
+    temperature: TemperatureUnit,
+) -> Result<(), AppError> {
+    let patch = SettingsPatch {
+        units: Some(UnitSystem {
+            distance,
+            speed,
+            temperature,
+        }),
+        ..SettingsPatch::default()
     };
+    settings::update_settings(&app, &state, patch).await?;
+    Ok(())
+}
 
-    let crate::ipc::GuidedSession::Goto {
-        latitude_deg,
-        longitude_deg,
-        altitude_msl_m,
-    } = request.session;
+#[tauri::command]
+pub(crate) fn get_current_vehicle_state(state: tauri::State<'_, AppState>) -> Option<VehicleState> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.session_context().vehicle_state.clone())
+}
 
-    if let Err(error) =
-        live_commands::guided_goto(&vehicle, latitude_deg, longitude_deg, altitude_msl_m).await
-    {
-        return Ok(state.guided_runtime.lock().await.abort_reserved(
-            crate::ipc::OperationId::UpdateGuidedSession,
-            crate::ipc::ReasonKind::Failed,
-            error.to_string(),
-        ));
-    }
+#[tauri::command]
+pub(crate) fn get_current_link_state(state: tauri::State<'_, AppState>) -> SessionConnection {
+    state.live_runtime.with_runtime(|runtime| {
+        runtime
+            .vehicle()
+            .and_then(|vehicle| vehicle.link().state().latest())
+            .map(|link_state| session_connection_from_link_state(&link_state))
+            .unwrap_or_else(|| runtime.session_context().connection.clone())
+    })
+}
 
-    let result = state
-        .guided_runtime
-        .lock()
-        .await
-        .commit_reserved(crate::ipc::OperationId::UpdateGuidedSession);
-    if let GuidedCommandResult::Accepted { state: guided } = &result {
-        emit_guided_snapshot(&state, &app, guided.clone()).await;
-    }
-    Ok(result)
+/// The bounded STATUSTEXT history for the live link, so a webview that
+/// attached late (or reloaded) still sees earlier messages.
+#[tauri::command]
+pub(crate) fn statustext_history(state: tauri::State<'_, AppState>) -> Vec<StatusTextEntry> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.status_text_history().to_vec())
 }
 
+/// Fix state of every GPS instance seen this session, with time to first 3D
+/// fix.
 #[tauri::command]
-pub(crate) async fn stop_guided_session(
-    state: tauri::State<'_, AppState>,
-    _app: tauri::AppHandle,
-) -> Result<GuidedCommandResult, String> {
-    let vehicle = state.live_runtime.with_runtime(|runtime| runtime.vehicle());
-    let source_kind = state
+pub(crate) fn gps_status(state: tauri::State<'_, AppState>) -> GpsStatus {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.gps_status())
+}
+
+/// Cell count and per-cell voltage of the connected vehicle's battery.
+#[tauri::command]
+pub(crate) fn battery_stats(state: tauri::State<'_, AppState>) -> BatteryStats {
+    state
         .live_runtime
-        .with_runtime(|runtime| runtime.guided_source_kind());
-    let context = vehicle
+        .with_runtime(|runtime| runtime.battery_stats())
+}
+
+/// RC receiver link quality from the freshest RC link report, if any.
+#[tauri::command]
+pub(crate) fn rc_link_quality(state: tauri::State<'_, AppState>) -> Option<RcLinkQuality> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.rc_link_quality())
+}
+
+#[tauri::command]
+pub(crate) fn vehicle_list(state: tauri::State<'_, AppState>) -> Vec<VehicleListEntry> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.vehicle_list())
+}
+
+/// Select which heard system the live session controls.
+///
+/// The live `Vehicle` binds to the first system whose heartbeat passes the
+/// link, so selecting another one reconnects the active link with the source
+/// filter narrowed to that system's components, then emits
+/// `vehicle://selected`. The narrowed filter holds until the link is
+/// disconnected or another link is opened.
+#[tauri::command]
+pub(crate) async fn vehicle_select(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+    system_id: u8,
+) -> Result<(), AppError> {
+    let vehicle = with_vehicle(&state).await?;
+    if system_id == vehicle.identity().system_id {
+        return Ok(());
+    }
+    let (heard, sources) = state.live_runtime.with_runtime(|runtime| {
+        let sources = link_sources::system_sources(
+            system_id,
+            runtime
+                .link_sources()
+                .into_iter()
+                .map(|entry| (entry.system_id, entry.component_id)),
+        );
+        (runtime.heartbeats().contains_vehicle(system_id), sources)
+    });
+    if !heard {
+        return Err(AppError::validation(format!(
+            "system {system_id} has not been heard on this link"
+        )));
+    }
+    let Some(request) = state
+        .session_recovery
+        .lock()
+        .await
         .as_ref()
-        .map(live_context_from_vehicle)
-        .unwrap_or(GuidedLiveContext::unavailable());
-    let result = state.guided_runtime.lock().await.stop(source_kind, context);
-    Ok(result)
+        .map(|info| info.request.clone())
+    else {
+        return Err(AppError::not_connected());
+    };
+    if vehicle_is_armed(&vehicle) {
+        return Err(AppError::validation(
+            "disarm before switching to another vehicle",
+        ));
+    }
+    crate::connection::connect_selected_vehicle(&state, &app, request, sources).await?;
+    emit_event(
+        &app,
+        event_names::VEHICLE_SELECTED,
+        &VehicleSelected { system_id },
+    );
+    Ok(())
 }
 
 #[tauri::command]
-pub(crate) async fn get_available_modes(
+pub(crate) async fn set_link_profile(
     state: tauri::State<'_, AppState>,
-) -> Result<Vec<FlightMode>, String> {
+    profile: LinkProfile,
+) -> Result<(), AppError> {
+    ensure_live_write_allowed(state.inner(), OperationId::SetLinkProfile).await?;
     let vehicle = with_vehicle(&state).await?;
-    Ok(live_commands::get_available_modes(&vehicle))
+    live_commands::set_link_profile(&vehicle, profile)
+        .await
+        .map_err(AppError::from)?;
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.set_link_profile(profile));
+    Ok(())
 }
 
 #[tauri::command]
@@ -395,12 +896,41 @@ pub(crate) async fn set_message_rate(
     state: tauri::State<'_, AppState>,
     message_id: u32,
     rate_hz: f32,
-) -> Result<(), String> {
+    target_component: Option<u8>,
+) -> Result<(), AppError> {
     ensure_live_write_allowed(state.inner(), OperationId::SetMessageRate).await?;
     let vehicle = with_vehicle(&state).await?;
+    if state
+        .live_runtime
+        .with_runtime(|runtime| runtime.link_profile())
+        == LinkProfile::HighLatency
+    {
+        return Err("message rates are suppressed while the link is in high-latency mode".into());
+    }
+    if target_component.is_some() {
+        let interval_usec =
+            live_commands::message_rate_interval_usec(rate_hz).map_err(AppError::from)?;
+        return command_long_to_component(
+            &state.command_link,
+            &vehicle,
+            target_component,
+            MavCmd::MAV_CMD_SET_MESSAGE_INTERVAL,
+            [
+                message_id as f32,
+                interval_usec as f32,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+                0.0,
+            ],
+        )
+        .await
+        .map_err(AppError::from);
+    }
     live_commands::set_message_rate(&vehicle, message_id, rate_hz)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Returns the set of MAVLink messages whose streaming rate is user-configurable,
@@ -412,34 +942,84 @@ pub(crate) fn get_available_message_rates() -> Vec<MessageRateInfo> {
 }
 
 #[tauri::command]
-pub(crate) fn set_telemetry_rate(rate_hz: u32) -> Result<(), String> {
-    let interval_ms = telemetry::telemetry_interval_ms_for_rate(rate_hz)?;
-    TELEMETRY_INTERVAL_MS.store(interval_ms, Ordering::Relaxed);
+pub(crate) async fn set_telemetry_rate(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    rate_hz: u32,
+) -> Result<(), AppError> {
+    let patch = SettingsPatch {
+        telemetry_rate_hz: Some(rate_hz),
+        ..SettingsPatch::default()
+    };
+    settings::update_settings(&app, &state, patch).await?;
     Ok(())
 }
 
+/// Cap how often each named event is emitted; see `EventThrottle`. The map
+/// replaces any previous configuration, and `telemetry://state` keeps using
+/// `set_telemetry_rate` for its poll interval.
+#[tauri::command]
+pub(crate) fn set_event_rates(
+    state: tauri::State<'_, AppState>,
+    rates: HashMap<String, u32>,
+) -> Result<(), AppError> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.set_event_rates(rates))
+        .map_err(AppError::validation)
+}
+
+/// Switch the webview's telemetry ticks between whole snapshots on
+/// `telemetry://state` and changed fields on `telemetry://delta`. Either way
+/// the next tick is a keyframe, so a webview calls this whenever it
+/// (re)attaches. Remote UI and local API clients always get whole snapshots.
+#[tauri::command]
+pub(crate) fn set_telemetry_encoding(
+    state: tauri::State<'_, AppState>,
+    encoding: TelemetryEncoding,
+) {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.set_telemetry_encoding(encoding));
+}
+
+#[tauri::command]
+pub(crate) fn event_bridge_stats(state: tauri::State<'_, AppState>) -> Vec<EventBridgeStats> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.event_bridge_stats())
+}
+
 #[tauri::command]
 pub(crate) async fn mission_upload(
     state: tauri::State<'_, AppState>,
     plan: MissionPlan,
-) -> Result<(), String> {
-    run_cancellable_plan_op(state.inner(), OperationId::MissionUpload, move |vehicle| {
-        let op = vehicle.mission().upload(plan).map_err(|e| e.to_string())?;
-        Ok((op.cancel_token(), async move {
-            op.wait().await.map_err(|e| e.to_string())
-        }))
-    })
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::MissionUpload,
+        json!({ "plan": plan }),
+        async {
+            run_cancellable_plan_op(state.inner(), OperationId::MissionUpload, move |vehicle| {
+                let op = vehicle.mission().upload(plan).map_err(AppError::from)?;
+                Ok((op.cancel_token(), op.subscribe(), async move {
+                    op.wait().await.map_err(AppError::from)
+                }))
+            })
+            .await
+        },
+    )
     .await
 }
 
 #[tauri::command]
 pub(crate) async fn mission_download(
     state: tauri::State<'_, AppState>,
-) -> Result<MissionDownload, String> {
+) -> Result<MissionDownload, AppError> {
     run_cancellable_plan_op(state.inner(), OperationId::MissionDownload, |vehicle| {
-        let op = vehicle.mission().download().map_err(|e| e.to_string())?;
-        Ok((op.cancel_token(), async move {
-            let plan = op.wait().await.map_err(|e| e.to_string())?;
+        let op = vehicle.mission().download().map_err(AppError::from)?;
+        Ok((op.cancel_token(), op.subscribe(), async move {
+            let plan = op.wait().await.map_err(AppError::from)?;
             let home = vehicle
                 .telemetry()
                 .home()
@@ -456,13 +1036,21 @@ pub(crate) async fn mission_download(
 }
 
 #[tauri::command]
-pub(crate) async fn mission_clear(state: tauri::State<'_, AppState>) -> Result<(), String> {
-    run_cancellable_plan_op(state.inner(), OperationId::MissionClear, |vehicle| {
-        let op = vehicle.mission().clear().map_err(|e| e.to_string())?;
-        Ok((op.cancel_token(), async move {
-            op.wait().await.map_err(|e| e.to_string())
-        }))
-    })
+pub(crate) async fn mission_clear(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::MissionClear,
+        json!({}),
+        async {
+            run_cancellable_plan_op(state.inner(), OperationId::MissionClear, |vehicle| {
+                let op = vehicle.mission().clear().map_err(AppError::from)?;
+                Ok((op.cancel_token(), op.subscribe(), async move {
+                    op.wait().await.map_err(AppError::from)
+                }))
+            })
+            .await
+        },
+    )
     .await
 }
 
@@ -470,34 +1058,47 @@ pub(crate) async fn mission_clear(state: tauri::State<'_, AppState>) -> Result<(
 pub(crate) async fn fence_upload(
     state: tauri::State<'_, AppState>,
     plan: FencePlan,
-) -> Result<(), String> {
-    run_cancellable_plan_op(state.inner(), OperationId::FenceUpload, move |vehicle| {
-        let op = vehicle.fence().upload(plan).map_err(|e| e.to_string())?;
-        Ok((op.cancel_token(), async move {
-            op.wait().await.map_err(|e| e.to_string())
-        }))
-    })
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::FenceUpload,
+        json!({ "plan": plan }),
+        async {
+            run_cancellable_plan_op(state.inner(), OperationId::FenceUpload, move |vehicle| {
+                let op = vehicle.fence().upload(plan).map_err(AppError::from)?;
+                Ok((op.cancel_token(), op.subscribe(), async move {
+                    op.wait().await.map_err(AppError::from)
+                }))
+            })
+            .await
+        },
+    )
     .await
 }
 
 #[tauri::command]
-pub(crate) async fn fence_download(state: tauri::State<'_, AppState>) -> Result<FencePlan, String> {
+pub(crate) async fn fence_download(
+    state: tauri::State<'_, AppState>,
+) -> Result<FencePlan, AppError> {
     run_cancellable_plan_op(state.inner(), OperationId::FenceDownload, |vehicle| {
-        let op = vehicle.fence().download().map_err(|e| e.to_string())?;
-        Ok((op.cancel_token(), async move {
-            op.wait().await.map_err(|e| e.to_string())
+        let op = vehicle.fence().download().map_err(AppError::from)?;
+        Ok((op.cancel_token(), op.subscribe(), async move {
+            op.wait().await.map_err(AppError::from)
         }))
     })
     .await
 }
 
 #[tauri::command]
-pub(crate) async fn fence_clear(state: tauri::State<'_, AppState>) -> Result<(), String> {
-    run_cancellable_plan_op(state.inner(), OperationId::FenceClear, |vehicle| {
-        let op = vehicle.fence().clear().map_err(|e| e.to_string())?;
-        Ok((op.cancel_token(), async move {
-            op.wait().await.map_err(|e| e.to_string())
-        }))
+pub(crate) async fn fence_clear(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    journal::record(&state.journal, OperationId::FenceClear, json!({}), async {
+        run_cancellable_plan_op(state.inner(), OperationId::FenceClear, |vehicle| {
+            let op = vehicle.fence().clear().map_err(AppError::from)?;
+            Ok((op.cancel_token(), op.subscribe(), async move {
+                op.wait().await.map_err(AppError::from)
+            }))
+        })
+        .await
     })
     .await
 }
@@ -506,88 +1107,176 @@ pub(crate) async fn fence_clear(state: tauri::State<'_, AppState>) -> Result<(),
 pub(crate) async fn rally_upload(
     state: tauri::State<'_, AppState>,
     plan: RallyPlan,
-) -> Result<(), String> {
-    run_cancellable_plan_op(state.inner(), OperationId::RallyUpload, move |vehicle| {
-        let op = vehicle.rally().upload(plan).map_err(|e| e.to_string())?;
-        Ok((op.cancel_token(), async move {
-            op.wait().await.map_err(|e| e.to_string())
-        }))
-    })
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::RallyUpload,
+        json!({ "plan": plan }),
+        async {
+            run_cancellable_plan_op(state.inner(), OperationId::RallyUpload, move |vehicle| {
+                let op = vehicle.rally().upload(plan).map_err(AppError::from)?;
+                Ok((op.cancel_token(), op.subscribe(), async move {
+                    op.wait().await.map_err(AppError::from)
+                }))
+            })
+            .await
+        },
+    )
     .await
 }
 
 #[tauri::command]
-pub(crate) async fn rally_download(state: tauri::State<'_, AppState>) -> Result<RallyPlan, String> {
+pub(crate) async fn rally_download(
+    state: tauri::State<'_, AppState>,
+) -> Result<RallyPlan, AppError> {
     run_cancellable_plan_op(state.inner(), OperationId::RallyDownload, |vehicle| {
-        let op = vehicle.rally().download().map_err(|e| e.to_string())?;
-        Ok((op.cancel_token(), async move {
-            op.wait().await.map_err(|e| e.to_string())
+        let op = vehicle.rally().download().map_err(AppError::from)?;
+        Ok((op.cancel_token(), op.subscribe(), async move {
+            op.wait().await.map_err(AppError::from)
         }))
     })
     .await
 }
 
 #[tauri::command]
-pub(crate) async fn rally_clear(state: tauri::State<'_, AppState>) -> Result<(), String> {
-    run_cancellable_plan_op(state.inner(), OperationId::RallyClear, |vehicle| {
-        let op = vehicle.rally().clear().map_err(|e| e.to_string())?;
-        Ok((op.cancel_token(), async move {
-            op.wait().await.map_err(|e| e.to_string())
-        }))
+pub(crate) async fn rally_clear(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    journal::record(&state.journal, OperationId::RallyClear, json!({}), async {
+        run_cancellable_plan_op(state.inner(), OperationId::RallyClear, |vehicle| {
+            let op = vehicle.rally().clear().map_err(AppError::from)?;
+            Ok((op.cancel_token(), op.subscribe(), async move {
+                op.wait().await.map_err(AppError::from)
+            }))
+        })
+        .await
     })
     .await
 }
 
+/// Predicted RTL path from the vehicle's current position. Works from
+/// whatever is known; gaps are listed in the preview's warnings.
+#[tauri::command]
+pub(crate) async fn rtl_preview(state: tauri::State<'_, AppState>) -> Result<RtlPreview, AppError> {
+    let vehicle = with_vehicle(&state).await?;
+    let firmware = match vehicle.identity().autopilot {
+        AutopilotType::Px4 => RtlFirmware::Px4,
+        _ => RtlFirmware::ArduPilot,
+    };
+    let store = live_commands::param_get_all(&vehicle).ok();
+    let start = vehicle
+        .telemetry()
+        .position()
+        .global()
+        .latest()
+        .map(|sample| RtlStart {
+            latitude_deg: sample.value.latitude_deg,
+            longitude_deg: sample.value.longitude_deg,
+            altitude_msl_m: f64::from(sample.value.altitude_msl_m),
+        });
+    let home = vehicle
+        .telemetry()
+        .home()
+        .latest()
+        .map(|sample| HomePosition {
+            latitude_deg: sample.value.latitude_deg,
+            longitude_deg: sample.value.longitude_deg,
+            altitude_m: sample.value.altitude_msl_m,
+        });
+    Ok(ironwing_core::rtl_preview::rtl_preview(
+        firmware,
+        store.as_ref(),
+        start,
+        home.as_ref(),
+    ))
+}
+
+/// Plan for finishing the cached mission after a landing at
+/// `last_completed_seq`. Nothing is sent; the caller validates and uploads it
+/// like any other plan.
+#[tauri::command]
+pub(crate) async fn mission_prepare_resume(
+    state: tauri::State<'_, AppState>,
+    last_completed_seq: u16,
+) -> Result<MissionPlan, AppError> {
+    let vehicle = with_vehicle(&state).await?;
+    let plan = vehicle
+        .mission()
+        .latest()
+        .and_then(|mission| mission.plan)
+        .ok_or_else(|| AppError::validation("no mission has been uploaded or downloaded yet"))?;
+    ironwing_core::mission_resume::prepare_resume(&plan, last_completed_seq)
+        .map_err(AppError::validation)
+}
+
 #[tauri::command]
 pub(crate) async fn mission_set_current(
     state: tauri::State<'_, AppState>,
     seq: u16,
-) -> Result<(), String> {
-    ensure_live_write_allowed(state.inner(), OperationId::MissionSetCurrent).await?;
-    let vehicle = with_vehicle(&state).await?;
-    live_commands::mission_set_current(&vehicle, seq)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::MissionSetCurrent,
+        json!({ "seq": seq }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::MissionSetCurrent).await?;
+            let vehicle = with_vehicle(&state).await?;
+            live_commands::mission_set_current(&vehicle, seq)
+                .await
+                .map_err(AppError::from)
+        },
+    )
+    .await
 }
 
 #[tauri::command]
-pub(crate) async fn mission_cancel(state: tauri::State<'_, AppState>) -> Result<(), String> {
+pub(crate) async fn mission_cancel(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
     if let Some(token) = state.mission_op_cancel.lock().await.take() {
         token.cancel();
     }
     Ok(())
 }
 
+/// Calibrate the accelerometers and wait up to
+/// `CALIBRATION_RESULT_TIMEOUT` for the vehicle to report the result.
 #[tauri::command]
-pub(crate) async fn calibrate_accel(state: tauri::State<'_, AppState>) -> Result<(), String> {
-    ensure_live_write_allowed(state.inner(), OperationId::CalibrateAccel).await?;
-    let vehicle = with_vehicle(&state).await?;
-    live_commands::calibrate_accel(&vehicle)
-        .await
-        .map_err(|e| e.to_string())
+pub(crate) async fn calibrate_accel(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<CalibrationResult, AppError> {
+    calibration::run_simple_calibration(
+        state.inner(),
+        &app,
+        CalibrationKind::Accel,
+        OperationId::CalibrateAccel,
+    )
+    .await
 }
 
 #[tauri::command]
-pub(crate) async fn calibrate_gyro(state: tauri::State<'_, AppState>) -> Result<(), String> {
-    ensure_live_write_allowed(state.inner(), OperationId::CalibrateGyro).await?;
-    let vehicle = with_vehicle(&state).await?;
-    live_commands::calibrate_gyro(&vehicle)
-        .await
-        .map_err(|e| e.to_string())
+pub(crate) async fn calibrate_gyro(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<CalibrationResult, AppError> {
+    calibration::run_simple_calibration(
+        state.inner(),
+        &app,
+        CalibrationKind::Gyro,
+        OperationId::CalibrateGyro,
+    )
+    .await
 }
 
 #[tauri::command]
 pub(crate) async fn param_download_all(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     ensure_live_write_allowed(state.inner(), OperationId::ParamDownloadAll).await?;
 
     // Guard: reject concurrent download
     {
         let guard = state.param_download_abort.lock().await;
         if guard.is_some() {
-            return Err("parameter download already in progress".to_string());
+            return Err(AppError::internal("parameter download already in progress"));
         }
     }
 
@@ -595,7 +1284,7 @@ pub(crate) async fn param_download_all(
         .await?
         .params()
         .download_all()
-        .map_err(|e| e.to_string())?;
+        .map_err(AppError::from)?;
 
     // Spawn progress bridge: relay ParamOperationProgress to the shared progress event.
     let mut progress_sub = handle.subscribe();
@@ -638,60 +1327,291 @@ pub(crate) async fn param_download_all(
     Ok(())
 }
 
+/// Commit written parameters to storage when `persist` asks for it, or by
+/// default on autopilots that do not save PARAM_SET themselves. Returns the
+/// `persisted` / `persist_error` pair for the write outcomes.
+pub(crate) async fn persist_param_writes(
+    vehicle: &mavkit::Vehicle,
+    persist: Option<bool>,
+    any_written: bool,
+) -> (Option<bool>, Option<String>) {
+    let persist = persist.unwrap_or_else(|| {
+        ironwing_core::params::persist_params_by_default(vehicle.identity().autopilot)
+    });
+    if !persist || !any_written {
+        return (None, None);
+    }
+    if vehicle_is_armed(vehicle) {
+        return (
+            Some(false),
+            Some("parameters are not saved to storage while armed".to_string()),
+        );
+    }
+    match live_commands::param_commit_to_storage(vehicle).await {
+        Ok(()) => (Some(true), None),
+        Err(error) => (Some(false), Some(AppError::from(error).to_string())),
+    }
+}
+
+fn param_write_outcome(
+    result: ParamWriteResult,
+    (persisted, persist_error): &(Option<bool>, Option<String>),
+) -> ParamWriteOutcome {
+    ParamWriteOutcome {
+        result,
+        persisted: *persisted,
+        persist_error: persist_error.clone(),
+    }
+}
+
 #[tauri::command]
 pub(crate) async fn param_write(
     state: tauri::State<'_, AppState>,
     name: String,
-    value: f32,
-) -> Result<ParamWriteResult, String> {
-    ensure_live_write_allowed(state.inner(), OperationId::ParamWrite).await?;
-    let vehicle = with_vehicle(&state).await?;
-    live_commands::param_write(&vehicle, &name, value)
-        .await
-        .map_err(|e| e.to_string())
+    value: f64,
+    persist: Option<bool>,
+) -> Result<ParamWriteOutcome, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::ParamWrite,
+        json!({ "name": name, "value": value, "persist": persist }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::ParamWrite).await?;
+            let vehicle = with_vehicle(&state).await?;
+            expect_param_writes(state.inner(), [name.as_str()]);
+            let result = live_commands::param_write(&vehicle, &name, value)
+                .await
+                .map_err(AppError::from)?;
+            let persistence = persist_param_writes(&vehicle, persist, result.success).await;
+            Ok(param_write_outcome(result, &persistence))
+        },
+    )
+    .await
 }
 
 #[tauri::command]
 pub(crate) async fn param_write_batch(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
-    params: Vec<(String, f32)>,
-) -> Result<Vec<ParamWriteResult>, String> {
-    ensure_live_write_allowed(state.inner(), OperationId::ParamWriteBatch).await?;
-    let handle = with_vehicle(&state)
-        .await?
-        .params()
-        .write_batch(params)
-        .map_err(|e| e.to_string())?;
+    params: Vec<(String, f64)>,
+    persist: Option<bool>,
+) -> Result<Vec<ParamWriteOutcome>, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::ParamWriteBatch,
+        json!({ "params": params, "persist": persist }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::ParamWriteBatch).await?;
+            let vehicle = with_vehicle(&state).await?;
+            expect_param_writes(state.inner(), params.iter().map(|(name, _)| name.as_str()));
+            let params = params
+                .into_iter()
+                .map(|(name, value)| {
+                    let wire = live_commands::encode_param_write(&vehicle, &name, value)?;
+                    Ok((name, wire))
+                })
+                .collect::<Result<Vec<_>, AppError>>()?;
+            let handle = vehicle
+                .params()
+                .write_batch(params)
+                .map_err(AppError::from)?;
+
+            // Spawn progress bridge: relay ParamOperationProgress to the shared progress event.
+            let mut progress_sub = handle.subscribe();
+            let app_for_bridge = app.clone();
+            let bridge_task = tokio::spawn(async move {
+                while let Some(p) = progress_sub.recv().await {
+                    emit_scoped(&app_for_bridge, event_names::PARAM_PROGRESS, p).await;
+                }
+            });
+            // The bridge task self-terminates when the progress channel closes (i.e. when
+            // `handle` is dropped at function return). Not tracked in background_tasks
+            // because it needs no external cancellation for this synchronous operation.
+            drop(bridge_task);
 
-    // Spawn progress bridge: relay ParamOperationProgress to the shared progress event.
-    let mut progress_sub = handle.subscribe();
-    let app_for_bridge = app.clone();
-    let bridge_task = tokio::spawn(async move {
-        while let Some(p) = progress_sub.recv().await {
-            emit_scoped(&app_for_bridge, event_names::PARAM_PROGRESS, p).await;
-        }
-    });
-    // The bridge task self-terminates when the progress channel closes (i.e. when
-    // `handle` is dropped at function return). Not tracked in background_tasks
-    // because it needs no external cancellation for this synchronous operation.
-    drop(bridge_task);
+            let results: Vec<_> = handle
+                .wait()
+                .await
+                .map_err(AppError::from)?
+                .into_iter()
+                .map(|result| live_commands::decode_param_write_result(&vehicle, result))
+                .collect();
+            let any_written = results.iter().any(|result| result.success);
+            let persistence = persist_param_writes(&vehicle, persist, any_written).await;
+            Ok(results
+                .into_iter()
+                .map(|result| param_write_outcome(result, &persistence))
+                .collect())
+        },
+    )
+    .await
+}
 
-    handle.wait().await.map_err(|e| e.to_string())
+fn downloaded_param(vehicle: &mavkit::Vehicle, name: &str) -> Result<mavkit::Param, AppError> {
+    live_commands::param_get_all(vehicle)
+        .map_err(AppError::from)?
+        .params
+        .remove(name)
+        .ok_or_else(|| AppError::validation(format!("unknown parameter {name}")))
 }
 
+/// The bits of bitmask parameter `name`, labelled from the metadata set by
+/// `param_set_metadata`.
 #[tauri::command]
-pub(crate) fn param_parse_file(contents: String) -> Result<HashMap<String, f32>, String> {
-    live_commands::param_parse_file(&contents).map_err(|e| e.to_string())
+pub(crate) async fn param_get_bits(
+    state: tauri::State<'_, AppState>,
+    name: String,
+) -> Result<Vec<ParamBitState>, AppError> {
+    let vehicle = with_vehicle(&state).await?;
+    let param = downloaded_param(&vehicle, &name)?;
+    let metadata = state
+        .live_runtime
+        .with_runtime(|runtime| runtime.param_metadata().get(&name).cloned());
+    describe_param_bits(
+        &param,
+        live_commands::param_encoding(&vehicle),
+        metadata.as_ref(),
+    )
+    .map_err(AppError::validation)
+}
+
+/// Set and clear bits of bitmask parameter `name` on its current value and
+/// write the result. The bits are changed as an integer of the parameter's
+/// declared type, never through float arithmetic.
+#[tauri::command]
+pub(crate) async fn param_set_bits(
+    state: tauri::State<'_, AppState>,
+    name: String,
+    bits_to_set: Vec<u8>,
+    bits_to_clear: Vec<u8>,
+    persist: Option<bool>,
+) -> Result<mavkit::Param, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::ParamSetBits,
+        json!({
+            "name": name,
+            "set": bits_to_set,
+            "clear": bits_to_clear,
+            "persist": persist,
+        }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::ParamSetBits).await?;
+            let vehicle = with_vehicle(&state).await?;
+            let param = downloaded_param(&vehicle, &name)?;
+            let encoding = live_commands::param_encoding(&vehicle);
+            let value = apply_param_bits(&param, encoding, &bits_to_set, &bits_to_clear)
+                .map_err(AppError::validation)?;
+            expect_param_writes(state.inner(), [name.as_str()]);
+            let result = live_commands::param_write_value(&vehicle, &name, value)
+                .await
+                .map_err(AppError::from)?;
+            if !result.success {
+                return Err(AppError::internal(format!(
+                    "{name} was not confirmed by the vehicle"
+                )));
+            }
+            if let (_, Some(error)) = persist_param_writes(&vehicle, persist, true).await {
+                tracing::warn!("{name} was written but not saved to storage: {error}");
+            }
+            Ok(mavkit::Param {
+                value: value.as_f64() as f32,
+                ..param
+            })
+        },
+    )
+    .await
+}
+
+/// Save the autopilot's current parameters to persistent storage. Refused
+/// while armed, as some autopilots stall the main loop while writing flash.
+#[tauri::command]
+pub(crate) async fn param_commit_to_storage(
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::ParamCommitToStorage,
+        json!({}),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::ParamCommitToStorage).await?;
+            let vehicle = with_vehicle(&state).await?;
+            if vehicle_is_armed(&vehicle) {
+                return Err(AppError::validation(
+                    "disarm before saving parameters to storage",
+                ));
+            }
+            live_commands::param_commit_to_storage(&vehicle)
+                .await
+                .map_err(AppError::from)
+        },
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) fn param_parse_file(contents: String) -> Result<HashMap<String, f64>, AppError> {
+    live_commands::param_parse_file(&contents).map_err(AppError::from)
 }
 
+/// Every row of a Mission Planner or QGC parameter file, with the declared
+/// types QGC files carry and a warning per row that did not parse.
 #[tauri::command]
-pub(crate) fn param_format_file(store: ParamStore) -> String {
-    live_commands::param_format_file(&store)
+pub(crate) fn param_parse_file_ex(contents: String) -> ParsedParamFile {
+    live_commands::param_parse_file_ex(&contents)
 }
 
 #[tauri::command]
-pub(crate) async fn param_cancel(state: tauri::State<'_, AppState>) -> Result<(), String> {
+pub(crate) fn param_format_file(store: ParamStore, format: Option<ParamExportFormat>) -> String {
+    live_commands::param_format_file(&store, format.unwrap_or_default())
+}
+
+#[tauri::command]
+pub(crate) async fn param_export(
+    state: tauri::State<'_, AppState>,
+    options: ParamExportOptions,
+) -> Result<String, AppError> {
+    let vehicle = with_vehicle(&state).await?;
+    live_commands::param_export(&vehicle, &options).map_err(AppError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn param_get_all(
+    state: tauri::State<'_, AppState>,
+) -> Result<ParamStore, AppError> {
+    let vehicle = with_vehicle(&state).await?;
+    let store = live_commands::param_get_all(&vehicle).map_err(AppError::from)?;
+    Ok(display_param_store(
+        store,
+        live_commands::param_encoding(&vehicle),
+    ))
+}
+
+#[tauri::command]
+pub(crate) async fn param_search(
+    state: tauri::State<'_, AppState>,
+    query: String,
+    options: ParamSearchOptions,
+) -> Result<Vec<mavkit::Param>, AppError> {
+    let vehicle = with_vehicle(&state).await?;
+    let metadata = state
+        .live_runtime
+        .with_runtime(|runtime| runtime.param_metadata().clone());
+    live_commands::param_search(&vehicle, &query, &options, &metadata).map_err(AppError::from)
+}
+
+#[tauri::command]
+pub(crate) fn param_set_metadata(
+    state: tauri::State<'_, AppState>,
+    metadata: HashMap<String, ParamMetadata>,
+) {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.set_param_metadata(metadata));
+}
+
+#[tauri::command]
+pub(crate) async fn param_cancel(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
     // Abort the wait task — its Drop calls ParamOperationHandle::cancel()
     if let Some(abort) = state.param_download_abort.lock().await.take() {
         abort.abort();
@@ -700,12 +1620,36 @@ pub(crate) async fn param_cancel(state: tauri::State<'_, AppState>) -> Result<()
 }
 
 #[tauri::command]
-pub(crate) async fn reboot_vehicle(state: tauri::State<'_, AppState>) -> Result<(), String> {
-    ensure_live_write_allowed(state.inner(), OperationId::RebootVehicle).await?;
-    let vehicle = with_vehicle(&state).await?;
-    live_commands::reboot_vehicle(&vehicle)
-        .await
-        .map_err(|e| e.to_string())
+pub(crate) async fn reboot_vehicle(
+    state: tauri::State<'_, AppState>,
+    target_component: Option<u8>,
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::RebootVehicle,
+        json!({ "target_component": target_component }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::RebootVehicle).await?;
+            let vehicle = with_vehicle(&state).await?;
+            if target_component
+                .is_some_and(|component_id| component_id != vehicle.identity().component_id)
+            {
+                return command_long_to_component(
+                    &state.command_link,
+                    &vehicle,
+                    target_component,
+                    MavCmd::MAV_CMD_PREFLIGHT_REBOOT_SHUTDOWN,
+                    [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+                )
+                .await
+                .map_err(AppError::from);
+            }
+            live_commands::reboot_vehicle(&vehicle)
+                .await
+                .map_err(AppError::from)
+        },
+    )
+    .await
 }
 
 #[tauri::command]
@@ -714,12 +1658,12 @@ pub(crate) async fn motor_test(
     motor_instance: u8,
     throttle_pct: f32,
     duration_s: f32,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     ensure_live_write_allowed(state.inner(), OperationId::MotorTest).await?;
     let vehicle = with_vehicle(&state).await?;
     live_commands::motor_test(&vehicle, motor_instance, throttle_pct, duration_s)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -727,69 +1671,81 @@ pub(crate) async fn set_servo(
     state: tauri::State<'_, AppState>,
     instance: u8,
     pwm_us: u16,
-) -> Result<(), String> {
-    ensure_live_write_allowed(state.inner(), OperationId::SetServo).await?;
-    let vehicle = with_vehicle(&state).await?;
-    live_commands::set_servo(&vehicle, instance, pwm_us)
-        .await
-        .map_err(|e| e.to_string())
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::SetServo,
+        json!({ "instance": instance, "pwm_us": pwm_us }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::SetServo).await?;
+            let vehicle = with_vehicle(&state).await?;
+            live_commands::set_servo(&vehicle, instance, pwm_us)
+                .await
+                .map_err(AppError::from)
+        },
+    )
+    .await
 }
 
 #[tauri::command]
 pub(crate) async fn rc_override(
     state: tauri::State<'_, AppState>,
     channels: Vec<RcOverrideChannelWire>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     ensure_live_write_allowed(state.inner(), OperationId::RcOverride).await?;
     let vehicle = with_vehicle(&state).await?;
     live_commands::rc_override(&vehicle, channels)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn calibrate_compass_start(
     state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
     compass_mask: u8,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     ensure_live_write_allowed(state.inner(), OperationId::CalibrateCompassStart).await?;
     let vehicle = with_vehicle(&state).await?;
-    live_commands::calibrate_compass_start(&vehicle, compass_mask)
-        .await
-        .map_err(|e| e.to_string())
+    let mark = calibration::current_status_text_mark(&state);
+    live_commands::calibrate_compass_start(&vehicle, compass_mask).await?;
+    calibration::watch_compass_calibration(&app, &vehicle, mark);
+    Ok(())
 }
 
 #[tauri::command]
 pub(crate) async fn calibrate_compass_accept(
     state: tauri::State<'_, AppState>,
     _compass_mask: u8,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     ensure_live_write_allowed(state.inner(), OperationId::CalibrateCompassAccept).await?;
     let vehicle = with_vehicle(&state).await?;
     live_commands::calibrate_compass_accept(&vehicle)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn calibrate_compass_cancel(
     state: tauri::State<'_, AppState>,
     _compass_mask: u8,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     ensure_live_write_allowed(state.inner(), OperationId::CalibrateCompassCancel).await?;
     let vehicle = with_vehicle(&state).await?;
     live_commands::calibrate_compass_cancel(&vehicle)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
-pub(crate) async fn request_prearm_checks(state: tauri::State<'_, AppState>) -> Result<(), String> {
+pub(crate) async fn request_prearm_checks(
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
     ensure_live_write_allowed(state.inner(), OperationId::RequestPrearmChecks).await?;
     let vehicle = with_vehicle(&state).await?;
     live_commands::request_prearm_checks(&vehicle)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -797,11 +1753,11 @@ pub(crate) async fn open_session_snapshot(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
     source_kind: SourceKind,
-) -> Result<OpenSessionSnapshot, String> {
+) -> Result<OpenSessionSnapshot, AppError> {
     if source_kind == SourceKind::Playback {
-        let guard = state.log_store.lock().await;
-        let Some(store) = guard.as_ref() else {
-            return Err("no log open".to_string());
+        let guard = state.open_logs.lock().await;
+        let Some(store) = guard.most_recent() else {
+            return Err(AppError::validation("no log open"));
         };
 
         state
@@ -881,10 +1837,17 @@ mod tests {
             ),
             active_link_target: tokio::sync::Mutex::new(None),
             demo_vehicle: tokio::sync::Mutex::new(None),
+            gcs_identity: tokio::sync::Mutex::new(crate::ipc::GcsIdentity::default()),
+            link_source_filter: tokio::sync::Mutex::new(Vec::new()),
+            vehicle_selection: tokio::sync::Mutex::new(None),
+            link_outbox: crate::link_outbox::LinkOutbox::default(),
+            command_link: crate::component_commands::CommandLink::default(),
+            auto_connect_usb: tokio::sync::Mutex::new(None),
             connect_abort: tokio::sync::Mutex::new(None),
+            parked_connect: tokio::sync::Mutex::new(None),
             background_tasks: tokio::sync::Mutex::new(Vec::new()),
-            background_listeners: tokio::sync::Mutex::new(Vec::new()),
-            log_store: tokio::sync::Mutex::new(None),
+            link_teardown: tokio::sync::Mutex::new(None),
+            open_logs: tokio::sync::Mutex::new(ironwing_core::open_logs::OpenLogs::new()),
             cached_library_store: tokio::sync::Mutex::new(None),
             log_operation: crate::logs::LogOperationState::new(),
             playback_runtime: crate::logs::PlaybackRuntimeState::new(),
@@ -897,7 +1860,22 @@ mod tests {
             param_download_abort: tokio::sync::Mutex::new(None),
             mission_op_cancel: tokio::sync::Mutex::new(None),
             guided_runtime: tokio::sync::Mutex::new(crate::ipc::GuidedRuntime::default()),
+            orbit: tokio::sync::Mutex::new(None),
+            gcs_position: tokio::sync::Mutex::new(crate::gcs_position::GcsPositionState::default()),
+            rc_override: tokio::sync::Mutex::new(crate::rc_override::RcOverrideState::default()),
+            settings: tokio::sync::Mutex::new(crate::ipc::Settings::default()),
+            journal: crate::journal::Journal::new(),
             remote_ui_events: crate::remote_ui::event_channel(),
+            local_api: tokio::sync::Mutex::new(crate::local_api::LocalApiState::default()),
+            log_follow: tokio::sync::Mutex::new(None),
+            session_recovery: tokio::sync::Mutex::new(None),
+            background_keepalive: tokio::sync::Mutex::new(
+                crate::background_keepalive::KeepaliveState::default(),
+            ),
+            task_watchdog: crate::task_watchdog::TaskWatchdog::default(),
+            pending_connect: tokio::sync::Mutex::new(None),
+            param_tune: tokio::sync::Mutex::new(None),
+            tune_capture: tokio::sync::Mutex::new(None),
         }
     }
 
@@ -959,6 +1937,9 @@ mod tests {
             text: "Live telemetry resumed".into(),
             severity: "info".into(),
             timestamp_usec: Some(42),
+            repeat_count: 1,
+            vehicle_time: None,
+            failsafe: false,
         }];
 
         let envelope = runtime.close_playback_session().expect("live envelope");
@@ -999,6 +1980,45 @@ mod tests {
         );
     }
 
+    /// Every mode a simulated vehicle advertises resolves to itself by its
+    /// own name in any case, and nothing else resolves.
+    #[tokio::test]
+    async fn every_advertised_mode_resolves_by_name() {
+        use mavkit::sim::{DemoProfile, DemoVehicle};
+
+        for (label, profile) in [
+            ("copter", DemoProfile::ArduCopter),
+            ("plane", DemoProfile::ArduPlane),
+            ("quadplane", DemoProfile::ArduQuadPlane),
+        ] {
+            let (vehicle, demo) = DemoVehicle::builder()
+                .profile(profile)
+                .connect(mavkit::VehicleConfig::default())
+                .await
+                .expect("demo vehicle");
+            let modes = live_commands::get_available_modes(&vehicle);
+            assert!(!modes.is_empty(), "{label} advertises no modes");
+            for mode in &modes {
+                for name in [
+                    mode.name.to_ascii_uppercase(),
+                    mode.name.to_ascii_lowercase(),
+                ] {
+                    let resolved = resolve_mode_by_name(&modes, &name).expect("advertised mode");
+                    assert_eq!(resolved.custom_mode, mode.custom_mode, "{name}");
+                }
+                let prefix = &mode.name[..mode.name.len() - 1];
+                if !modes
+                    .iter()
+                    .any(|other| other.name.eq_ignore_ascii_case(prefix))
+                {
+                    assert!(resolve_mode_by_name(&modes, prefix).is_err(), "{prefix}");
+                }
+            }
+            let _ = vehicle.disconnect().await;
+            let _ = demo.shutdown().await;
+        }
+    }
+
     #[tokio::test]
     async fn playback_rejects_write_commands() {
         let state = app_state_for_tests();
@@ -1103,7 +2123,7 @@ pub(crate) async fn ack_session_snapshot(
     session_id: String,
     seek_epoch: u64,
     reset_revision: u64,
-) -> Result<AckSessionSnapshotResult, String> {
+) -> Result<AckSessionSnapshotResult, AppError> {
     Ok(state.live_runtime.with_runtime(|runtime| {
         runtime.ack_session_snapshot(&session_id, seek_epoch, reset_revision)
     }))
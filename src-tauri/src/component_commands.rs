@@ -0,0 +1,451 @@
+use std::sync::{Mutex, MutexGuard};
+
+use ironwing_core::component_command::{
+    CommandExchange, CommandRetryPolicy, ComponentCommand, ExchangeEvent, LateAckLog, ObservedAck,
+};
+use ironwing_core::dialect::{MavCmd, MavMessage};
+use ironwing_core::event_names;
+use ironwing_core::live_runtime::commands as live_commands;
+use mavkit::Vehicle;
+use mavlink::MavlinkVersion;
+use tokio::time::Instant;
+use tokio_stream::StreamExt;
+
+use crate::AppState;
+use crate::e2e_emit::emit_event;
+use crate::helpers::{ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{AppError, CommandProgress, ComponentListEntry, MavResultKind, OperationId};
+
+const MAV_MOUNT_MODE_MAVLINK_TARGETING: f32 = 2.0;
+
+#[derive(Default)]
+struct CommandLinkState {
+    /// From the transport's timeout profile; `None` until the first connect.
+    policy: Option<CommandRetryPolicy>,
+    /// Exchanges that gave up before their ACK arrived.
+    late_acks: LateAckLog,
+}
+
+/// Command exchange state that belongs to the current link: how commands are
+/// resent on it and which of them timed out.
+#[derive(Default)]
+pub(crate) struct CommandLink(Mutex<CommandLinkState>);
+
+impl CommandLink {
+    fn lock(&self) -> MutexGuard<'_, CommandLinkState> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Switch command exchanges to a new link's policy and start its late
+    /// ACK count from zero.
+    pub(crate) fn reset(&self, policy: CommandRetryPolicy) {
+        *self.lock() = CommandLinkState {
+            policy: Some(policy),
+            late_acks: LateAckLog::new(),
+        };
+    }
+
+    /// ACKs on the current link that arrived after their command timed out.
+    pub(crate) fn late_acks(&self) -> u64 {
+        self.lock().late_acks.late_acks()
+    }
+}
+
+/// Send a COMMAND_LONG to `target_component`, or through mavkit's own command
+/// path when no component (or the autopilot's own component) is requested.
+pub(crate) async fn command_long_to_component(
+    link: &CommandLink,
+    vehicle: &Vehicle,
+    target_component: Option<u8>,
+    command: MavCmd,
+    params: [f32; 7],
+) -> Result<(), AppError> {
+    let identity = vehicle.identity();
+    let component_id = match target_component {
+        Some(component_id) if component_id != identity.component_id => component_id,
+        _ => {
+            return vehicle
+                .raw()
+                .command_long(command as u16, params)
+                .await
+                .map(|_| ())
+                .map_err(AppError::from);
+        }
+    };
+
+    let command = ComponentCommand {
+        system_id: identity.system_id,
+        component_id,
+        command,
+        params,
+    };
+    run_command_exchange(link, vehicle, command).await?.result
+}
+
+/// Run `command` on the vehicle's own autopilot with retries, reporting
+/// MAV_RESULT_IN_PROGRESS ACKs on `command://progress`. Arm and takeoff go
+/// through here rather than MAVKit's single-ACK command path, so a lost ACK
+/// on a poor link is resent instead of failing the command.
+pub(crate) async fn autopilot_command(
+    app: &tauri::AppHandle,
+    link: &CommandLink,
+    vehicle: &Vehicle,
+    command: ComponentCommand,
+) -> Result<(), AppError> {
+    let name = format!("{:?}", command.command);
+    run_command_exchange_with_progress(link, vehicle, command, |progress| {
+        emit_event(
+            app,
+            event_names::COMMAND_PROGRESS,
+            &CommandProgress {
+                command: name.clone(),
+                progress,
+            },
+        );
+    })
+    .await?
+    .result
+}
+
+/// How a COMMAND_LONG exchange ended once the target answered.
+pub(crate) struct ExchangeOutcome {
+    /// `Err` carries the target's refusal.
+    pub(crate) result: Result<(), AppError>,
+    /// Sends it took, retries included.
+    pub(crate) attempts: u8,
+}
+
+/// Send `command` with retries until its final COMMAND_ACK arrives. Errors
+/// when the link fails or the target never finishes answering.
+pub(crate) async fn run_command_exchange(
+    link: &CommandLink,
+    vehicle: &Vehicle,
+    command: ComponentCommand,
+) -> Result<ExchangeOutcome, AppError> {
+    run_command_exchange_with_progress(link, vehicle, command, |_| {}).await
+}
+
+/// [`run_command_exchange`], calling `on_progress` for each new
+/// MAV_RESULT_IN_PROGRESS ACK.
+pub(crate) async fn run_command_exchange_with_progress(
+    link: &CommandLink,
+    vehicle: &Vehicle,
+    command: ComponentCommand,
+    mut on_progress: impl FnMut(Option<u8>),
+) -> Result<ExchangeOutcome, AppError> {
+    let component_id = command.component_id;
+    let policy = {
+        let mut link = link.lock();
+        link.late_acks.exchange_started(&command);
+        link.policy.unwrap_or_default()
+    };
+    let mut exchange = CommandExchange::new(command, policy);
+    // Subscribe before sending so a fast ACK cannot slip past.
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+
+    while let Some(message) = exchange.next_send() {
+        live_commands::send_raw_message(vehicle, message)
+            .await
+            .map_err(AppError::from)?;
+
+        let mut deadline = Instant::now() + exchange.wait_timeout();
+        loop {
+            let raw_msg = match tokio::time::timeout_at(deadline, raw_stream.next()).await {
+                Ok(Some(raw_msg)) => raw_msg,
+                Ok(None) => {
+                    return Err(AppError::Transport {
+                        kind: "closed".to_string(),
+                        message: "link closed while waiting for COMMAND_ACK".to_string(),
+                    });
+                }
+                Err(_) => break,
+            };
+            let Ok(MavMessage::COMMAND_ACK(ack)) =
+                MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+            else {
+                continue;
+            };
+            let observed = ObservedAck {
+                source_system: raw_msg.system_id,
+                source_component: raw_msg.component_id,
+                command: ack.command as u16,
+                result: ack.result as u8,
+                progress: ack.progress,
+            };
+            if link.lock()
+                .late_acks
+                .claim(&observed, web_time::Instant::now())
+            {
+                tracing::warn!(
+                    "late COMMAND_ACK for {:?} from component {}, after it timed out",
+                    observed.command,
+                    observed.source_component
+                );
+                continue;
+            }
+            match exchange.on_ack(&observed) {
+                ExchangeEvent::Ignored => {}
+                ExchangeEvent::InProgress { progress } => {
+                    tracing::debug!(
+                        "component {component_id} working on {:?} ({progress:?}%)",
+                        command.command
+                    );
+                    on_progress(progress);
+                    deadline = Instant::now() + exchange.wait_timeout();
+                }
+                ExchangeEvent::Finished(result) => {
+                    return Ok(ExchangeOutcome {
+                        result: result.map_err(|message| AppError::CommandDenied {
+                            result: MavResultKind::from_mav_result(observed.result),
+                            message,
+                        }),
+                        attempts: exchange.sent(),
+                    });
+                }
+            }
+        }
+    }
+
+    link.lock()
+        .late_acks
+        .timed_out(command, web_time::Instant::now());
+    let message = if exchange.in_progress() {
+        format!(
+            "component {component_id} did not finish {:?}",
+            command.command
+        )
+    } else {
+        format!(
+            "component {component_id} did not acknowledge {:?} after {} attempts",
+            command.command,
+            exchange.sent()
+        )
+    };
+    Err(AppError::Timeout { message })
+}
+
+#[tauri::command]
+pub(crate) fn components_list(state: tauri::State<'_, AppState>) -> Vec<ComponentListEntry> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.component_list())
+}
+
+#[tauri::command]
+pub(crate) async fn camera_trigger(
+    state: tauri::State<'_, AppState>,
+    target_component: Option<u8>,
+) -> Result<(), AppError> {
+    ensure_live_write_allowed(state.inner(), OperationId::CameraTrigger).await?;
+    let vehicle = with_vehicle(&state).await?;
+    command_long_to_component(
+        &state.command_link,
+        &vehicle,
+        target_component,
+        MavCmd::MAV_CMD_DO_DIGICAM_CONTROL,
+        [0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn gimbal_set_angles(
+    state: tauri::State<'_, AppState>,
+    pitch_deg: f32,
+    roll_deg: f32,
+    yaw_deg: f32,
+    target_component: Option<u8>,
+) -> Result<(), AppError> {
+    ensure_live_write_allowed(state.inner(), OperationId::GimbalSetAngles).await?;
+    let vehicle = with_vehicle(&state).await?;
+    command_long_to_component(
+        &state.command_link,
+        &vehicle,
+        target_component,
+        MavCmd::MAV_CMD_DO_MOUNT_CONTROL,
+        [
+            pitch_deg,
+            roll_deg,
+            yaw_deg,
+            0.0,
+            0.0,
+            0.0,
+            MAV_MOUNT_MODE_MAVLINK_TARGETING,
+        ],
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::GcsIdentity;
+    use crate::mock_link::{LinkFaults, MOCK_AUTOPILOT_ID, MOCK_SYSTEM_ID, MockLink, heartbeat};
+    use ironwing_core::dialect::MavResult;
+    use ironwing_core::gcs_commands::gcs_command_ack;
+    use ironwing_core::vehicle_config;
+    use std::time::Duration;
+    use tokio::task::JoinHandle;
+
+    const CAMERA_ID: u8 = 100;
+
+    /// Connect over a link whose vehicle end answers every COMMAND_LONG for
+    /// the camera with `replies`, one COMMAND_ACK per (component, result).
+    async fn connect_with_camera(
+        downlink: LinkFaults,
+        replies: Vec<(u8, MavResult)>,
+    ) -> (Vehicle, JoinHandle<()>) {
+        let MockLink {
+            gcs,
+            vehicle: mut far_end,
+        } = MockLink::new(LinkFaults::default(), downlink);
+        let gcs_identity = GcsIdentity::default();
+        let script = tokio::spawn(async move {
+            far_end.send(&heartbeat(0)).await;
+            while let Some(message) = far_end.recv().await {
+                let MavMessage::COMMAND_LONG(command) = message else {
+                    continue;
+                };
+                if command.target_component != CAMERA_ID {
+                    continue;
+                }
+                for &(component_id, result) in &replies {
+                    let ack = gcs_command_ack(
+                        &command,
+                        gcs_identity.system_id,
+                        gcs_identity.component_id,
+                        result,
+                    );
+                    far_end.send_from(component_id, &ack).await;
+                }
+            }
+        });
+
+        let vehicle = Vehicle::from_connection(
+            gcs,
+            vehicle_config::live_vehicle_config(Duration::from_secs(5)),
+        )
+        .await
+        .expect("connect over the mock link");
+        (vehicle, script)
+    }
+
+    fn camera_trigger() -> ComponentCommand {
+        ComponentCommand {
+            system_id: MOCK_SYSTEM_ID,
+            component_id: CAMERA_ID,
+            command: MavCmd::MAV_CMD_DO_DIGICAM_CONTROL,
+            params: [0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+        }
+    }
+
+    /// A link that resends quickly, so a lost ACK costs the test little.
+    fn fast_link() -> CommandLink {
+        let link = CommandLink::default();
+        link.reset(CommandRetryPolicy {
+            attempts: 3,
+            spacing: Duration::from_millis(200),
+            in_progress_timeout: Duration::from_secs(2),
+        });
+        link
+    }
+
+    #[tokio::test]
+    async fn exchange_settles_on_the_ack_from_the_targeted_component() {
+        // The autopilot refuses the command it forwarded before the camera
+        // itself accepts it.
+        let (vehicle, script) = connect_with_camera(
+            LinkFaults::default(),
+            vec![
+                (MOCK_AUTOPILOT_ID, MavResult::MAV_RESULT_DENIED),
+                (CAMERA_ID, MavResult::MAV_RESULT_ACCEPTED),
+            ],
+        )
+        .await;
+
+        let outcome = run_command_exchange(&CommandLink::default(), &vehicle, camera_trigger())
+            .await
+            .expect("camera answers");
+
+        assert_eq!(outcome.result, Ok(()));
+        assert_eq!(outcome.attempts, 1);
+        script.abort();
+    }
+
+    #[tokio::test]
+    async fn a_lost_ack_is_recovered_by_the_resend() {
+        // Downlink frames are the heartbeat, then one ACK per send: dropping
+        // every second frame loses exactly the first ACK.
+        let (vehicle, script) = connect_with_camera(
+            LinkFaults::drop_every(2),
+            vec![(CAMERA_ID, MavResult::MAV_RESULT_ACCEPTED)],
+        )
+        .await;
+        let link = fast_link();
+
+        let outcome = run_command_exchange(&link, &vehicle, camera_trigger())
+            .await
+            .expect("camera answers the resend");
+
+        assert_eq!(outcome.result, Ok(()));
+        assert_eq!(outcome.attempts, 2);
+        assert_eq!(link.late_acks(), 0);
+        script.abort();
+    }
+
+    #[tokio::test]
+    async fn in_progress_then_accepted_reports_progress_and_succeeds() {
+        let (vehicle, script) = connect_with_camera(
+            LinkFaults::default(),
+            vec![
+                (CAMERA_ID, MavResult::MAV_RESULT_IN_PROGRESS),
+                (CAMERA_ID, MavResult::MAV_RESULT_ACCEPTED),
+            ],
+        )
+        .await;
+        let mut progress = Vec::new();
+
+        let outcome = run_command_exchange_with_progress(
+            &fast_link(),
+            &vehicle,
+            camera_trigger(),
+            |percent| progress.push(percent),
+        )
+        .await
+        .expect("camera finishes");
+
+        assert_eq!(outcome.result, Ok(()));
+        assert_eq!(outcome.attempts, 1);
+        assert_eq!(progress, vec![Some(0)]);
+        script.abort();
+    }
+
+    #[tokio::test]
+    async fn in_progress_then_failed_is_a_denial() {
+        let (vehicle, script) = connect_with_camera(
+            LinkFaults::default(),
+            vec![
+                (CAMERA_ID, MavResult::MAV_RESULT_IN_PROGRESS),
+                (CAMERA_ID, MavResult::MAV_RESULT_FAILED),
+            ],
+        )
+        .await;
+
+        let outcome = run_command_exchange(&fast_link(), &vehicle, camera_trigger())
+            .await
+            .expect("camera finishes");
+
+        assert!(matches!(
+            outcome.result,
+            Err(AppError::CommandDenied {
+                result: MavResultKind::Failed,
+                ..
+            })
+        ));
+        assert_eq!(outcome.attempts, 1);
+        script.abort();
+    }
+}
@@ -2,30 +2,167 @@ use mavkit::Vehicle;
 use mavkit::sim::{DemoProfile, DemoVehicle, DemoVehicleHandle};
 use mavkit::stream::{ChannelBridge, StreamConnection};
 use std::future::Future;
-use std::time::Duration;
-use tauri::Listener;
-#[cfg(target_os = "android")]
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use tauri::Manager;
+use tokio::sync::watch;
 use tokio::task::JoinHandle;
 
 use crate::AppState;
+use crate::e2e_emit::emit_event;
 use crate::guided::emit_guided_reset;
 use crate::ipc::{
-    ConnectRequest, ConnectTransport, DemoVehiclePreset, DisconnectRequest, DomainProvenance,
+    AppError, ConnectRequest, ConnectTransport, ConnectionInfo, DemoVehiclePreset,
+    DisconnectRequest, DomainProvenance, GcsIdentity, LinkConnectPhase, LinkSource,
+    LinkSourceEntry, LinkTimeouts, OperationFailure, OperationId, Reason, RecordingStartRequest,
+    operation_failure_json,
 };
+use crate::link_outbox::LinkOutbox;
+use crate::link_teardown::{BridgeFeed, LinkTeardown, TransportPlugin};
+use crate::net_link;
 use crate::recording::auto_record_start_request;
+#[cfg(not(target_os = "android"))]
+use crate::serial_link::{self, SerialOptions};
+use crate::transport_drain::{DrainStats, spawn_drain_task};
+use ironwing_core::event_names;
+use ironwing_core::link_connect::{
+    DEFAULT_HEARTBEAT_TIMEOUT, KEEP_WAITING_TIMEOUT, LINK_CONNECTING_INTERVAL,
+    connect_timeout_failure, heartbeat_timeout, link_connecting,
+};
+use ironwing_core::settings::DEFAULT_LINK_BUFFER_FRAMES;
+use ironwing_core::udp_bind::{parse_udp_bind_addr, udp_bind_failure};
 use ironwing_core::{bluetooth_profile, telemetry, transport::BluetoothProfile, vehicle_config};
 
-/// Total time budget for the entire connect flow (TCP handshake + MAVLink
-/// heartbeat wait).  The underlying `mavlink::connect_async` call has no
-/// built-in timeout for the TCP socket phase, so without a wrapper the
-/// connect can hang for the OS-level TCP timeout (~2 min on Linux).
-const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Frames buffered between a bridged transport and MAVKit. Set from the
+/// settings store; takes effect on the next connect.
+pub(crate) static LINK_BUFFER_FRAMES: AtomicUsize =
+    AtomicUsize::new(DEFAULT_LINK_BUFFER_FRAMES as usize);
+
+fn new_link_bridge(link_outbox: &LinkOutbox) -> ChannelBridge {
+    let bridge = ChannelBridge::new(LINK_BUFFER_FRAMES.load(Ordering::Relaxed));
+    ChannelBridge {
+        outgoing_rx: link_outbox.attach(bridge.outgoing_rx),
+        ..bridge
+    }
+}
+
+/// One connect attempt: which phase it is in, how long it may wait for the
+/// first heartbeat, the timeouts its link gets and the outbox its bridged
+/// transport sends through. The phase is a watch so the transport can report
+/// that it is open while the progress reporter reads it.
+#[derive(Clone)]
+struct ConnectWait {
+    phase: Arc<watch::Sender<LinkConnectPhase>>,
+    started: Instant,
+    heartbeat_timeout: Option<Duration>,
+    link_timeouts: LinkTimeouts,
+    link_outbox: LinkOutbox,
+}
+
+impl ConnectWait {
+    fn new(
+        heartbeat_timeout: Option<Duration>,
+        link_timeouts: LinkTimeouts,
+        link_outbox: LinkOutbox,
+    ) -> Self {
+        let (phase, _) = watch::channel(LinkConnectPhase::OpeningTransport);
+        Self {
+            phase: Arc::new(phase),
+            started: Instant::now(),
+            heartbeat_timeout,
+            link_timeouts,
+            link_outbox,
+        }
+    }
+
+    fn transport_open(&self) {
+        self.phase
+            .send_replace(LinkConnectPhase::WaitingForHeartbeat);
+    }
+
+    /// The bound for the whole attempt; effectively none when the user
+    /// chose to keep waiting.
+    fn timeout(&self) -> Duration {
+        self.heartbeat_timeout.unwrap_or(KEEP_WAITING_TIMEOUT)
+    }
+
+    /// MAVKit itself never gives up on the heartbeat: `finish_connect`
+    /// enforces the timeout, so an attempt with an open transport can be
+    /// kept alive past it.
+    fn vehicle_config(&self, gcs: GcsIdentity) -> mavkit::VehicleConfig {
+        let config = vehicle_config::with_link_timeouts(
+            vehicle_config::live_vehicle_config(KEEP_WAITING_TIMEOUT),
+            &self.link_timeouts,
+        );
+        vehicle_config::with_gcs_identity(config, gcs)
+    }
+
+    fn timeout_error(&self) -> String {
+        connect_link_failure(connect_timeout_failure(
+            *self.phase.borrow(),
+            self.timeout(),
+        ))
+    }
+
+    /// Map a failed `Vehicle::from_connection`, so running out of time reads
+    /// as a missing heartbeat rather than a generic MAVKit timeout.
+    fn connect_error(&self, error: mavkit::VehicleError) -> String {
+        match error {
+            mavkit::VehicleError::Timeout(_) => self.timeout_error(),
+            error => format!("Vehicle connection failed: {error}"),
+        }
+    }
+}
+
+/// Emit `link://connecting` every `LINK_CONNECTING_INTERVAL` until aborted,
+/// and put each phase into the session as it is reached.
+async fn report_connect_progress(app: tauri::AppHandle, wait: ConnectWait) {
+    let mut phase = wait.phase.subscribe();
+    publish_connect_phase(&app, *phase.borrow_and_update());
+    let mut interval = tokio::time::interval(LINK_CONNECTING_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            Ok(()) = phase.changed() => {
+                publish_connect_phase(&app, *phase.borrow_and_update());
+                continue;
+            }
+        }
+        let progress = link_connecting(
+            *wait.phase.borrow(),
+            wait.started.elapsed(),
+            wait.heartbeat_timeout,
+        );
+        emit_event(&app, event_names::LINK_CONNECTING, &progress);
+    }
+}
+
+fn publish_connect_phase(app: &tauri::AppHandle, phase: LinkConnectPhase) {
+    let state: tauri::State<'_, AppState> = app.state();
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.set_connect_phase(phase));
+    ironwing_core::live_runtime::emit_session_state(&state.live_runtime, DomainProvenance::Stream);
+}
+
+/// Stops a task however the scope holding it ends, such as the progress
+/// reporter of `connect_link`.
+pub(crate) struct AbortOnDrop(pub(crate) JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
 
 struct ConnectedVehicle {
     vehicle: Vehicle,
     tasks: Vec<JoinHandle<()>>,
-    listeners: Vec<tauri::EventId>,
+    teardown: Option<LinkTeardown>,
     demo_handle: Option<DemoVehicleHandle>,
 }
 
@@ -34,19 +171,35 @@ async fn abort_background_tasks(state: &AppState) {
     for task in tasks.drain(..) {
         task.abort();
     }
+    // The time sync bridge is among them; its estimate must not outlive it.
+    state.journal.set_vehicle_clock(None);
 }
 
-async fn clear_background_listeners(state: &AppState, app: &tauri::AppHandle) {
-    let mut listeners = state.background_listeners.lock().await;
-    for listener in listeners.drain(..) {
-        app.unlisten(listener);
+/// Install the tasks for a new link and abort whatever set was still stored.
+///
+/// `connect_link` already clears the previous set before connecting, but two
+/// overlapping connects can both reach this point; without the abort the
+/// earlier set would keep bridging events from a vehicle nobody owns.
+fn replace_background_tasks(
+    slot: &mut Vec<JoinHandle<()>>,
+    tasks: Vec<JoinHandle<()>>,
+) -> Vec<JoinHandle<()>> {
+    let stale = std::mem::replace(slot, tasks);
+    for task in &stale {
+        task.abort();
     }
+    stale
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum ActiveLinkTarget {
-    Serial { port: String },
+    Serial {
+        port: String,
+        baud: u32,
+    },
     BluetoothBle,
+    #[cfg(target_os = "android")]
+    UsbSerial,
     Other,
 }
 
@@ -58,66 +211,131 @@ fn demo_profile(vehicle_preset: DemoVehiclePreset) -> DemoProfile {
     }
 }
 
+/// Connect through a MAVKit address, which opens the transport and waits
+/// for the heartbeat in one call.
 async fn connect_via_address(
-    state: &AppState,
     address: String,
+    gcs: GcsIdentity,
+    wait: ConnectWait,
 ) -> Result<ConnectedVehicle, String> {
-    let config = vehicle_config::live_vehicle_config(CONNECT_TIMEOUT);
-    tracing::info!("connecting to {address} (timeout {CONNECT_TIMEOUT:?})");
-    let task = tokio::spawn(async move {
-        let result = tokio::time::timeout(
-            CONNECT_TIMEOUT,
-            Vehicle::connect_with_config(&address, config),
-        )
-        .await;
-        match &result {
-            Ok(Ok(_)) => tracing::info!("vehicle connected to {address}"),
-            Ok(Err(e)) => tracing::warn!("vehicle connect failed for {address}: {e}"),
-            Err(_) => tracing::warn!("vehicle connect timed out for {address}"),
+    tracing::info!("connecting to {address}");
+    let vehicle = match Vehicle::connect_with_config(&address, wait.vehicle_config(gcs)).await {
+        Ok(vehicle) => {
+            tracing::info!("vehicle connected to {address}");
+            vehicle
         }
-        result.map_err(|_| mavkit::VehicleError::Timeout("connecting to vehicle".into()))?
-    });
-    *state.connect_abort.lock().await = Some(task.abort_handle());
-
-    let vehicle = task
-        .await
-        .map_err(|e| {
-            if e.is_cancelled() {
-                "connection cancelled".to_string()
-            } else {
-                e.to_string()
-            }
-        })?
-        .map_err(|e| e.to_string())?;
-
-    *state.connect_abort.lock().await = None;
+        Err(error) => {
+            tracing::warn!("vehicle connect failed for {address}: {error}");
+            return Err(wait.connect_error(error));
+        }
+    };
     Ok(ConnectedVehicle {
         vehicle,
         tasks: Vec::new(),
-        listeners: Vec::new(),
+        teardown: None,
         demo_handle: None,
     })
 }
 
-async fn connect_with_abort<F>(state: &AppState, future: F) -> Result<ConnectedVehicle, String>
+async fn connect_with_abort<F, T>(state: &AppState, future: F) -> Result<T, String>
 where
-    F: Future<Output = Result<ConnectedVehicle, String>> + Send + 'static,
+    F: Future<Output = Result<T, String>> + Send + 'static,
+    T: Send + 'static,
 {
     let task = tokio::spawn(future);
     *state.connect_abort.lock().await = Some(task.abort_handle());
 
-    let result = task.await.map_err(|e| {
-        if e.is_cancelled() {
-            "connection cancelled".to_string()
-        } else {
-            e.to_string()
-        }
-    })?;
+    let result = task.await.map_err(join_error)?;
 
     *state.connect_abort.lock().await = None;
     result
 }
 
+const CONNECT_CANCELLED: &str = "connection cancelled";
+
+fn join_error(error: tokio::task::JoinError) -> String {
+    if error.is_cancelled() {
+        CONNECT_CANCELLED.to_string()
+    } else {
+        error.to_string()
+    }
+}
+
+type ConnectFuture = Pin<Box<dyn Future<Output = Result<ConnectedVehicle, String>> + Send>>;
+
+/// A connect attempt: the task opening the link and waiting for the first
+/// heartbeat, and what `connect_link` still does once it succeeds. Parked in
+/// `AppState::parked_connect` when the heartbeat timeout passes with the
+/// transport open, until the user keeps waiting or gives up.
+pub(crate) struct ConnectAttempt {
+    task: JoinHandle<Result<ConnectedVehicle, String>>,
+    target: ActiveLinkTarget,
+    wait: ConnectWait,
+    recovery_request: ConnectRequest,
+    auto_record_request: Option<RecordingStartRequest>,
+}
+
+/// Abort the connect attempt in flight, or the one parked waiting for a
+/// heartbeat, releasing its socket.
+async fn abort_connect_attempt(state: &AppState) {
+    if let Some(handle) = state.connect_abort.lock().await.take() {
+        handle.abort();
+    }
+    if let Some(attempt) = state.parked_connect.lock().await.take() {
+        attempt.task.abort();
+    }
+}
+
+/// Wait for `attempt` within its heartbeat timeout, then install the vehicle.
+async fn finish_connect(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    mut attempt: ConnectAttempt,
+) -> Result<(), String> {
+    *state.connect_abort.lock().await = Some(attempt.task.abort_handle());
+    let joined = match attempt.wait.heartbeat_timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, &mut attempt.task).await {
+            Ok(joined) => joined,
+            Err(_) => return Err(park_connect_attempt(state, attempt).await),
+        },
+        None => (&mut attempt.task).await,
+    };
+    *state.connect_abort.lock().await = None;
+    let vehicle = joined.map_err(join_error)??;
+
+    store_connected_vehicle(state, app, vehicle, attempt.target).await?;
+    crate::session_recovery::begin_session_recovery(state, app, attempt.recovery_request).await;
+    maybe_start_auto_recording(state, app, attempt.auto_record_request).await;
+    Ok(())
+}
+
+/// The heartbeat timeout passed. With the transport open the attempt goes on
+/// listening, and `connect_keep_waiting` picks it up again; a disconnect or
+/// the next connect drops it. Without one there is nothing to wait on.
+async fn park_connect_attempt(state: &AppState, attempt: ConnectAttempt) -> String {
+    let error = attempt.wait.timeout_error();
+    if *attempt.wait.phase.borrow() == LinkConnectPhase::WaitingForHeartbeat {
+        *state.parked_connect.lock().await = Some(attempt);
+    } else {
+        attempt.task.abort();
+        *state.connect_abort.lock().await = None;
+    }
+    error
+}
+
+/// A failed attempt leaves the session disconnected, unless it was parked
+/// (its transport is still open) or cancelled (whoever cancelled it owns the
+/// session now).
+async fn settle_failed_connect(state: &AppState, error: &str) {
+    if error == CONNECT_CANCELLED || state.parked_connect.lock().await.is_some() {
+        return;
+    }
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.reset_live_state());
+    ironwing_core::live_runtime::emit_session_state(&state.live_runtime, DomainProvenance::Stream);
+}
+
 async fn request_tcp_telemetry_streams(vehicle: Vehicle) {
     for request in telemetry::DEFAULT_TELEMETRY_STREAM_REQUESTS {
         if let Err(err) = vehicle
@@ -133,12 +351,18 @@ async fn request_tcp_telemetry_streams(vehicle: Vehicle) {
     }
 }
 
-async fn connect_demo(vehicle_preset: DemoVehiclePreset) -> Result<ConnectedVehicle, String> {
-    let config = vehicle_config::adapter_vehicle_config(
-        CONNECT_TIMEOUT,
-        Duration::from_secs(10),
-        Duration::from_secs(20),
-        Duration::from_secs(20),
+async fn connect_demo(
+    vehicle_preset: DemoVehiclePreset,
+    gcs: GcsIdentity,
+) -> Result<ConnectedVehicle, String> {
+    let config = vehicle_config::with_gcs_identity(
+        vehicle_config::adapter_vehicle_config(
+            DEFAULT_HEARTBEAT_TIMEOUT,
+            Duration::from_secs(10),
+            Duration::from_secs(20),
+            Duration::from_secs(20),
+        ),
+        gcs,
     );
 
     let (vehicle, demo_handle) = DemoVehicle::builder()
@@ -150,7 +374,7 @@ async fn connect_demo(vehicle_preset: DemoVehiclePreset) -> Result<ConnectedVehi
     Ok(ConnectedVehicle {
         vehicle,
         tasks: Vec::new(),
-        listeners: Vec::new(),
+        teardown: None,
         demo_handle: Some(demo_handle),
     })
 }
@@ -161,22 +385,11 @@ async fn shutdown_demo_vehicle(state: &AppState) {
     }
 }
 
-async fn teardown_transport_target(target: Option<&ActiveLinkTarget>) {
-    if !matches!(target, Some(ActiveLinkTarget::BluetoothBle)) {
-        return;
-    }
-
-    let handler = match tauri_plugin_blec::get_handler() {
-        Ok(handler) => handler,
-        Err(error) => {
-            tracing::debug!("BLE plugin was not initialized during BLE teardown: {error}");
-            return;
-        }
-    };
-
-    match handler.disconnect().await {
-        Ok(()) | Err(tauri_plugin_blec::Error::NoDeviceConnected) => {}
-        Err(error) => tracing::warn!("BLE disconnect failed during teardown: {error}"),
+/// Run the teardown of the stored transport link, if any.
+async fn teardown_link(state: &AppState, app: &tauri::AppHandle) {
+    let teardown = state.link_teardown.lock().await.take();
+    if let Some(teardown) = teardown {
+        teardown.run(app).await;
     }
 }
 
@@ -189,12 +402,21 @@ async fn store_connected_vehicle(
     let ConnectedVehicle {
         vehicle,
         mut tasks,
-        listeners,
+        teardown,
         demo_handle,
     } = connected_vehicle;
     tasks.extend(crate::bridges::spawn_event_bridges(app, &vehicle).await);
-    *state.background_tasks.lock().await = tasks;
-    *state.background_listeners.lock().await = listeners;
+    let stale = replace_background_tasks(&mut *state.background_tasks.lock().await, tasks);
+    if !stale.is_empty() {
+        tracing::warn!(
+            "aborted {} bridge tasks left over from an overlapping connect",
+            stale.len()
+        );
+    }
+    let stale_teardown = std::mem::replace(&mut *state.link_teardown.lock().await, teardown);
+    if let Some(stale_teardown) = stale_teardown {
+        stale_teardown.run(app).await;
+    }
     *state.demo_vehicle.lock().await = demo_handle;
     *state.active_link_target.lock().await = Some(active_target);
     Ok(())
@@ -217,18 +439,86 @@ async fn maybe_start_auto_recording(
     }
 }
 
+/// Open a new link. A system picked with `vehicle_select` on the previous
+/// link does not carry over to this one.
 #[tauri::command]
 pub(crate) async fn connect_link(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
     request: ConnectRequest,
+) -> Result<(), AppError> {
+    state.vehicle_selection.lock().await.take();
+    reconnect_link(&state, &app, request).await
+}
+
+/// Reopen `request` with the source filter narrowed to `sources`, one
+/// system's components. The selection lasts until the next `connect_link`
+/// or disconnect.
+pub(crate) async fn connect_selected_vehicle(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    request: ConnectRequest,
+    sources: Vec<LinkSource>,
+) -> Result<(), AppError> {
+    *state.vehicle_selection.lock().await = Some(sources);
+    reconnect_link(state, app, request).await
+}
+
+async fn reconnect_link(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    request: ConnectRequest,
+) -> Result<(), AppError> {
+    let result = open_link(state, app, request).await;
+    if let Err(error) = &result {
+        settle_failed_connect(state, error).await;
+    }
+    result.map_err(AppError::from)
+}
+
+/// Resume the attempt `connect_link` stopped waiting on at the heartbeat
+/// timeout; its transport stayed open. Returns once a heartbeat arrives, or
+/// fails when the attempt is cancelled.
+#[tauri::command]
+pub(crate) async fn connect_keep_waiting(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<(), AppError> {
+    let Some(mut attempt) = state.parked_connect.lock().await.take() else {
+        return Err(AppError::validation(
+            "no connect attempt is waiting for a heartbeat",
+        ));
+    };
+    attempt.wait.heartbeat_timeout = None;
+    let _progress = AbortOnDrop(tokio::spawn(report_connect_progress(
+        app.clone(),
+        attempt.wait.clone(),
+    )));
+    let result = finish_connect(&state, &app, attempt).await;
+    if let Err(error) = &result {
+        settle_failed_connect(&state, error).await;
+    }
+    result.map_err(AppError::from)
+}
+
+async fn open_link(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    request: ConnectRequest,
 ) -> Result<(), String> {
-    let auto_record_request = auto_record_start_request(request.auto_record_on_connect);
+    let auto_record_request = auto_record_start_request(
+        request.auto_record_on_connect,
+        &*state.settings.lock().await,
+    );
+    let recovery_request = request.clone();
+    // A typo in the bind address must not cost the current link. Binding
+    // itself waits until after teardown, since that link may hold the port.
+    if let ConnectTransport::Udp { bind_addr } = &request.transport {
+        parse_udp_bind(bind_addr)?;
+    }
 
     // Abort any in-flight connect attempt so its socket is released
-    if let Some(handle) = state.connect_abort.lock().await.take() {
-        handle.abort();
-    }
+    abort_connect_attempt(state).await;
     // Abort any in-flight param download before switching vehicle
     if let Some(abort) = state.param_download_abort.lock().await.take() {
         abort.abort();
@@ -236,14 +526,13 @@ pub(crate) async fn connect_link(
     if let Some(token) = state.mission_op_cancel.lock().await.take() {
         token.cancel();
     }
-    abort_background_tasks(&state).await;
-    clear_background_listeners(&state, &app).await;
+    abort_background_tasks(state).await;
 
     // Disconnect any existing vehicle
     {
         let _ = emit_guided_reset(
-            &state,
-            &app,
+            state,
+            app,
             crate::ipc::DomainProvenance::Stream,
             crate::ipc::guided::GuidedTerminationReason::SourceSwitch,
             "live source switched",
@@ -254,66 +543,121 @@ pub(crate) async fn connect_link(
             runtime.reset_live_state();
             previous
         });
-        let previous_target = state.active_link_target.lock().await.take();
+        state.active_link_target.lock().await.take();
         if let Some(v) = prev {
             let _ = v.disconnect().await;
         }
-        shutdown_demo_vehicle(&state).await;
-        teardown_transport_target(previous_target.as_ref()).await;
+        shutdown_demo_vehicle(state).await;
+        teardown_link(state, app).await;
+        crate::session_recovery::end_session_recovery(state, app).await;
     }
 
-    match request.transport {
+    let gcs = *state.gcs_identity.lock().await;
+    let source_filter = match state.vehicle_selection.lock().await.clone() {
+        Some(sources) => sources,
+        None => state.link_source_filter.lock().await.clone(),
+    };
+    let profile = vehicle_config::link_timeout_profile(&request.transport);
+    let link_timeouts =
+        vehicle_config::link_timeouts(profile, &state.settings.lock().await.link_timeouts);
+    state
+        .command_link
+        .reset(vehicle_config::command_retry_policy(&link_timeouts));
+    state.link_outbox.reset();
+    let wait = ConnectWait::new(
+        heartbeat_timeout(&request),
+        link_timeouts,
+        state.link_outbox.clone(),
+    );
+    let _progress = AbortOnDrop(tokio::spawn(report_connect_progress(
+        app.clone(),
+        wait.clone(),
+    )));
+    let (attempt, target): (ConnectFuture, ActiveLinkTarget) = match request.transport {
         ConnectTransport::Udp { bind_addr } => {
-            let vehicle = connect_via_address(&state, format!("udpin:{bind_addr}")).await?;
-            store_connected_vehicle(&state, &app, vehicle, ActiveLinkTarget::Other).await?;
+            let socket = bind_udp_socket(&bind_addr)?;
+            let attempt = connect_udp_socket(socket, gcs, source_filter, wait.clone());
+            (Box::pin(attempt), ActiveLinkTarget::Other)
         }
         ConnectTransport::Tcp { address } => {
-            let mut connected_vehicle =
-                connect_via_address(&state, format!("tcpout:{address}")).await?;
-            let vehicle = connected_vehicle.vehicle.clone();
-            connected_vehicle
-                .tasks
-                .push(tokio::spawn(request_tcp_telemetry_streams(vehicle)));
-            store_connected_vehicle(&state, &app, connected_vehicle, ActiveLinkTarget::Other)
-                .await?;
+            let wait = wait.clone();
+            let attempt = async move {
+                let mut connected_vehicle =
+                    connect_tcp_stream(&address, gcs, source_filter, wait).await?;
+                let vehicle = connected_vehicle.vehicle.clone();
+                connected_vehicle
+                    .tasks
+                    .push(tokio::spawn(request_tcp_telemetry_streams(vehicle)));
+                Ok::<_, String>(connected_vehicle)
+            };
+            (Box::pin(attempt), ActiveLinkTarget::Other)
         }
-        ConnectTransport::Serial { port, baud } => {
+        ConnectTransport::Serial {
+            port,
+            baud,
+            flow_control,
+            parity,
+            auto_baud,
+        } => {
             #[cfg(not(target_os = "android"))]
             {
-                let vehicle = connect_via_address(&state, format!("serial:{port}:{baud}")).await?;
-                store_connected_vehicle(&state, &app, vehicle, ActiveLinkTarget::Serial { port })
-                    .await?;
+                let options = SerialOptions {
+                    flow_control,
+                    parity,
+                };
+                let baud = if auto_baud {
+                    let probe_port = port.clone();
+                    connect_with_abort(state, serial_link::detect_baud(probe_port, options)).await?
+                } else {
+                    baud
+                };
+                let attempt: ConnectFuture = if options.needs_port_bridge()
+                    || !source_filter.is_empty()
+                {
+                    let serial_port = port.clone();
+                    let wait = wait.clone();
+                    Box::pin(async move {
+                        connect_serial_port(&serial_port, baud, options, gcs, source_filter, wait)
+                            .await
+                    })
+                } else {
+                    // MAVKit fails at once when the port cannot be opened.
+                    wait.transport_open();
+                    Box::pin(connect_via_address(
+                        format!("serial:{port}:{baud}"),
+                        gcs,
+                        wait.clone(),
+                    ))
+                };
+                (attempt, ActiveLinkTarget::Serial { port, baud })
             }
             #[cfg(target_os = "android")]
             {
-                let _ = (port, baud);
+                let _ = (port, baud, flow_control, parity, auto_baud);
                 return Err("Serial transport is not supported on Android.".into());
             }
         }
         ConnectTransport::BluetoothBle { address, profile } => {
             let profile = profile.unwrap_or(BluetoothProfile::NordicUart);
-            let vehicle =
-                connect_with_abort(&state, async move { connect_ble(&address, profile).await })
-                    .await?;
-            store_connected_vehicle(&state, &app, vehicle, ActiveLinkTarget::BluetoothBle).await?;
-        }
-        ConnectTransport::Demo { vehicle_preset } => {
-            let vehicle =
-                connect_with_abort(&state, async move { connect_demo(vehicle_preset).await })
-                    .await?;
-            store_connected_vehicle(&state, &app, vehicle, ActiveLinkTarget::Other).await?;
+            let ble_app = app.clone();
+            let wait = wait.clone();
+            let attempt = async move {
+                connect_ble(&ble_app, &address, profile, gcs, source_filter, wait).await
+            };
+            (Box::pin(attempt), ActiveLinkTarget::BluetoothBle)
         }
+        ConnectTransport::Demo { vehicle_preset } => (
+            Box::pin(connect_demo(vehicle_preset, gcs)),
+            ActiveLinkTarget::Other,
+        ),
         ConnectTransport::BluetoothSpp { address } => {
             #[cfg(target_os = "android")]
             {
                 let spp_app = app.clone();
-                let vehicle =
-                    connect_with_abort(
-                        &state,
-                        async move { connect_spp(&spp_app, &address).await },
-                    )
-                    .await?;
-                store_connected_vehicle(&state, &app, vehicle, ActiveLinkTarget::Other).await?;
+                let wait = wait.clone();
+                let attempt =
+                    async move { connect_spp(&spp_app, &address, gcs, source_filter, wait).await };
+                (Box::pin(attempt), ActiveLinkTarget::Other)
             }
             #[cfg(not(target_os = "android"))]
             {
@@ -321,6 +665,22 @@ pub(crate) async fn connect_link(
                 return Err("Classic Bluetooth SPP is only supported on Android.".into());
             }
         }
+        ConnectTransport::UsbSerial { device_id, baud } => {
+            #[cfg(target_os = "android")]
+            {
+                let usb_app = app.clone();
+                let wait = wait.clone();
+                let attempt = async move {
+                    connect_usb_serial(&usb_app, &device_id, baud, gcs, source_filter, wait).await
+                };
+                (Box::pin(attempt), ActiveLinkTarget::UsbSerial)
+            }
+            #[cfg(not(target_os = "android"))]
+            {
+                let _ = (device_id, baud);
+                return Err("USB OTG serial is only supported on Android.".into());
+            }
+        }
         ConnectTransport::WebSocket { .. }
         | ConnectTransport::WebSerial { .. }
         | ConnectTransport::WebBluetooth { .. } => {
@@ -328,20 +688,170 @@ pub(crate) async fn connect_link(
                 "Browser-owned transports are not supported by the native Tauri backend.".into(),
             );
         }
-    }
+    };
 
-    maybe_start_auto_recording(&state, &app, auto_record_request).await;
-    Ok(())
+    let attempt = ConnectAttempt {
+        task: tokio::spawn(attempt),
+        target,
+        wait,
+        recovery_request,
+        auto_record_request,
+    };
+    finish_connect(state, app, attempt).await
+}
+
+fn connect_link_failure(reason: Reason) -> String {
+    operation_failure_json(OperationFailure {
+        operation_id: OperationId::ConnectLink,
+        reason,
+    })
+}
+
+fn parse_udp_bind(input: &str) -> Result<SocketAddr, String> {
+    parse_udp_bind_addr(input).map_err(|error| connect_link_failure(error.reason()))
+}
+
+/// Parse the typed bind address and bind it, so the user gets "port in use"
+/// rather than an opaque error from inside the connect task.
+fn bind_udp_socket(input: &str) -> Result<std::net::UdpSocket, String> {
+    let addr = parse_udp_bind(input)?;
+    std::net::UdpSocket::bind(addr)
+        .map_err(|error| connect_link_failure(udp_bind_failure(addr, error.kind())))
+}
+
+/// Listen for a vehicle on a bound UDP socket.
+async fn connect_udp_socket(
+    socket: std::net::UdpSocket,
+    gcs: GcsIdentity,
+    source_filter: Vec<LinkSource>,
+    wait: ConnectWait,
+) -> Result<ConnectedVehicle, String> {
+    let ChannelBridge {
+        reader,
+        writer,
+        incoming_tx,
+        outgoing_rx,
+    } = new_link_bridge(&wait.link_outbox);
+    let bridge = net_link::spawn_udp_bridge(socket, incoming_tx, outgoing_rx, source_filter)?;
+    let connection = StreamConnection::new(reader, writer);
+    let connection: Box<
+        dyn mavlink::AsyncMavConnection<ironwing_core::dialect::MavMessage> + Sync + Send,
+    > = Box::new(connection);
+
+    let config = wait.vehicle_config(gcs);
+    wait.transport_open();
+    let vehicle = Vehicle::from_connection(connection, config)
+        .await
+        .map_err(|e| wait.connect_error(e))?;
+
+    Ok(ConnectedVehicle {
+        vehicle,
+        tasks: vec![bridge],
+        teardown: None,
+        demo_handle: None,
+    })
+}
+
+/// Connect to a MAVLink TCP server. The socket phase is bounded by the same
+/// timeout as the heartbeat; without it an unreachable host would hang for
+/// the OS-level TCP timeout (~2 min on Linux).
+async fn connect_tcp_stream(
+    address: &str,
+    gcs: GcsIdentity,
+    source_filter: Vec<LinkSource>,
+    wait: ConnectWait,
+) -> Result<ConnectedVehicle, String> {
+    let stream = tokio::time::timeout(wait.timeout(), net_link::connect_tcp(address))
+        .await
+        .map_err(|_| wait.timeout_error())??;
+    let ChannelBridge {
+        reader,
+        writer,
+        incoming_tx,
+        outgoing_rx,
+    } = new_link_bridge(&wait.link_outbox);
+    let bridge = net_link::spawn_tcp_bridge(stream, incoming_tx, outgoing_rx, source_filter);
+    let connection = StreamConnection::new(reader, writer);
+    let connection: Box<
+        dyn mavlink::AsyncMavConnection<ironwing_core::dialect::MavMessage> + Sync + Send,
+    > = Box::new(connection);
+
+    let config = wait.vehicle_config(gcs);
+    wait.transport_open();
+    let vehicle = Vehicle::from_connection(connection, config)
+        .await
+        .map_err(|e| wait.connect_error(e))?;
+
+    Ok(ConnectedVehicle {
+        vehicle,
+        tasks: vec![bridge],
+        teardown: None,
+        demo_handle: None,
+    })
+}
+
+/// Connect over a port opened with options MAVKit's `serial:` address cannot
+/// express (flow control, parity, a link source filter).
+#[cfg(not(target_os = "android"))]
+async fn connect_serial_port(
+    port: &str,
+    baud: u32,
+    options: SerialOptions,
+    gcs: GcsIdentity,
+    source_filter: Vec<LinkSource>,
+    wait: ConnectWait,
+) -> Result<ConnectedVehicle, String> {
+    let serial = serial_link::open_port(port, baud, options)?;
+    let ChannelBridge {
+        reader,
+        writer,
+        incoming_tx,
+        outgoing_rx,
+    } = new_link_bridge(&wait.link_outbox);
+    serial_link::spawn_port_bridge(serial, incoming_tx, outgoing_rx, source_filter)?;
+
+    let connection = StreamConnection::new(reader, writer);
+    let connection: Box<
+        dyn mavlink::AsyncMavConnection<ironwing_core::dialect::MavMessage> + Sync + Send,
+    > = Box::new(connection);
+
+    let config = wait.vehicle_config(gcs);
+    wait.transport_open();
+    let vehicle = Vehicle::from_connection(connection, config)
+        .await
+        .map_err(|e| wait.connect_error(e))?;
+
+    Ok(ConnectedVehicle {
+        vehicle,
+        tasks: Vec::new(),
+        teardown: None,
+        demo_handle: None,
+    })
 }
 
 /// Connect via BLE NUS (Nordic UART Service) using tauri-plugin-blec.
-async fn connect_ble(address: &str, profile: BluetoothProfile) -> Result<ConnectedVehicle, String> {
+async fn connect_ble(
+    app: &tauri::AppHandle,
+    address: &str,
+    profile: BluetoothProfile,
+    gcs: GcsIdentity,
+    source_filter: Vec<LinkSource>,
+    wait: ConnectWait,
+) -> Result<ConnectedVehicle, String> {
     match profile {
-        BluetoothProfile::NordicUart => connect_nordic_uart_ble(address).await,
+        BluetoothProfile::NordicUart => {
+            connect_nordic_uart_ble(app, address, gcs, source_filter, wait).await
+        }
     }
 }
 
-async fn connect_nordic_uart_ble(address: &str) -> Result<ConnectedVehicle, String> {
+async fn connect_nordic_uart_ble(
+    app: &tauri::AppHandle,
+    address: &str,
+    gcs: GcsIdentity,
+    source_filter: Vec<LinkSource>,
+    wait: ConnectWait,
+) -> Result<ConnectedVehicle, String> {
     let handler =
         tauri_plugin_blec::get_handler().map_err(|e| format!("BLE plugin not initialized: {e}"))?;
 
@@ -395,63 +905,80 @@ async fn connect_nordic_uart_ble(address: &str) -> Result<ConnectedVehicle, Stri
         reader,
         writer,
         incoming_tx,
-        mut outgoing_rx,
-    } = ChannelBridge::new(64);
+        outgoing_rx,
+    } = new_link_bridge(&wait.link_outbox);
 
     // Subscribe to NUS TX notifications → push into incoming channel
-    let tx_sender = incoming_tx.clone();
-    handler
-        .subscribe(nus_tx, move |data: Vec<u8>| {
-            let _ = tx_sender.try_send(data);
-        })
+    let feed = BridgeFeed::new(incoming_tx, source_filter);
+    let subscription_feed = feed.clone();
+    if let Err(e) = handler
+        .subscribe(nus_tx, move |data: Vec<u8>| subscription_feed.push(data))
         .await
-        .map_err(|e| format!("BLE subscribe failed: {e}"))?;
+    {
+        let _ = handler.disconnect().await;
+        return Err(format!("BLE subscribe failed: {e}"));
+    }
 
     // Spawn task to drain outgoing channel → send via BLE write
-    let writer_task = tokio::spawn(async move {
-        let handler = match tauri_plugin_blec::get_handler() {
-            Ok(h) => h,
-            Err(_) => return,
-        };
-        while let Some(data) = outgoing_rx.recv().await {
-            for chunk in data.chunks(bluetooth_profile::NORDIC_UART_DEFAULT_CHUNK_SIZE) {
-                if let Err(e) = handler
-                    .send_data(
-                        nus_rx,
-                        chunk,
-                        tauri_plugin_blec::models::WriteType::WithoutResponse,
-                    )
-                    .await
-                {
-                    tracing::warn!("BLE write error: {e}");
-                    return;
-                }
-            }
-        }
-    });
+    let drain_stats = DrainStats::default();
+    let writer_task = spawn_drain_task(
+        app,
+        "BLE",
+        outgoing_rx,
+        Some(bluetooth_profile::NORDIC_UART_DEFAULT_CHUNK_SIZE),
+        drain_stats.clone(),
+        move |chunk: Vec<u8>| async move {
+            handler
+                .send_data(
+                    nus_rx,
+                    &chunk,
+                    tauri_plugin_blec::models::WriteType::WithoutResponse,
+                )
+                .await
+        },
+    );
 
     // Create StreamConnection and build Vehicle
     let connection = StreamConnection::new(reader, writer);
     let connection: Box<
-        dyn mavlink::AsyncMavConnection<mavkit::dialect::MavMessage> + Sync + Send,
+        dyn mavlink::AsyncMavConnection<ironwing_core::dialect::MavMessage> + Sync + Send,
     > = Box::new(connection);
 
-    let config = vehicle_config::live_vehicle_config(CONNECT_TIMEOUT);
-    let vehicle = Vehicle::from_connection(connection, config)
-        .await
-        .map_err(|e| format!("Vehicle connection failed: {e}"))?;
+    let config = wait.vehicle_config(gcs);
+    wait.transport_open();
+    let teardown = LinkTeardown::new(
+        feed,
+        writer_task,
+        TransportPlugin::Ble {
+            notify_characteristic: nus_tx,
+        },
+    )
+    .with_drain_stats(drain_stats);
+    let vehicle = match Vehicle::from_connection(connection, config).await {
+        Ok(vehicle) => vehicle,
+        Err(e) => {
+            teardown.run(app).await;
+            return Err(wait.connect_error(e));
+        }
+    };
 
     Ok(ConnectedVehicle {
         vehicle,
-        tasks: vec![writer_task],
-        listeners: Vec::new(),
+        tasks: Vec::new(),
+        teardown: Some(teardown),
         demo_handle: None,
     })
 }
 
 /// Connect via Classic SPP on Android using tauri-plugin-bluetooth-classic.
 #[cfg(target_os = "android")]
-async fn connect_spp(app: &tauri::AppHandle, address: &str) -> Result<ConnectedVehicle, String> {
+async fn connect_spp(
+    app: &tauri::AppHandle,
+    address: &str,
+    gcs: GcsIdentity,
+    source_filter: Vec<LinkSource>,
+    wait: ConnectWait,
+) -> Result<ConnectedVehicle, String> {
     use base64::Engine;
     use tauri::Listener;
 
@@ -464,16 +991,17 @@ async fn connect_spp(app: &tauri::AppHandle, address: &str) -> Result<ConnectedV
         reader,
         writer,
         incoming_tx,
-        mut outgoing_rx,
-    } = ChannelBridge::new(64);
+        outgoing_rx,
+    } = new_link_bridge(&wait.link_outbox);
 
     // Listen for incoming data events from the Kotlin plugin
-    let tx_sender = incoming_tx.clone();
+    let feed = BridgeFeed::new(incoming_tx, source_filter);
+    let listener_feed = feed.clone();
     let listener_id = app.listen("plugin:bluetooth-classic://data", move |event| {
         if let Ok(payload) = serde_json::from_str::<serde_json::Value>(event.payload()) {
             if let Some(data_b64) = payload.get("data").and_then(|v| v.as_str()) {
                 if let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(data_b64) {
-                    let _ = tx_sender.try_send(bytes);
+                    listener_feed.push(bytes);
                 }
             }
         }
@@ -481,47 +1009,267 @@ async fn connect_spp(app: &tauri::AppHandle, address: &str) -> Result<ConnectedV
 
     // Spawn task to drain outgoing channel → send via Classic BT
     let bt_app = app.clone();
-    let writer_task = tokio::spawn(async move {
-        while let Some(data) = outgoing_rx.recv().await {
+    let drain_stats = DrainStats::default();
+    let writer_task = spawn_drain_task(
+        app,
+        "SPP",
+        outgoing_rx,
+        None,
+        drain_stats.clone(),
+        move |data: Vec<u8>| {
             let bt: tauri::State<'_, tauri_plugin_bluetooth_classic::BluetoothClassic<tauri::Wry>> =
                 bt_app.state();
-            if let Err(e) = bt.send(&data) {
-                tracing::warn!("SPP write error: {e}");
-                return;
-            }
+            let result = bt.send(&data).map_err(|e| e.to_string());
+            async move { result }
+        },
+    );
+
+    let connection = StreamConnection::new(reader, writer);
+    let connection: Box<
+        dyn mavlink::AsyncMavConnection<ironwing_core::dialect::MavMessage> + Sync + Send,
+    > = Box::new(connection);
+
+    let config = wait.vehicle_config(gcs);
+    wait.transport_open();
+    let teardown = LinkTeardown::new(feed, writer_task, TransportPlugin::Spp)
+        .with_listeners(vec![listener_id])
+        .with_drain_stats(drain_stats);
+    let vehicle = match Vehicle::from_connection(connection, config).await {
+        Ok(vehicle) => vehicle,
+        Err(e) => {
+            teardown.run(app).await;
+            return Err(wait.connect_error(e));
         }
+    };
+
+    Ok(ConnectedVehicle {
+        vehicle,
+        tasks: Vec::new(),
+        teardown: Some(teardown),
+        demo_handle: None,
+    })
+}
+
+/// Connect to a USB serial adapter over OTG on Android using
+/// tauri-plugin-usb-serial. The plugin reopens the adapter itself after a
+/// cable bump, so the link only sees a gap in traffic.
+#[cfg(target_os = "android")]
+async fn connect_usb_serial(
+    app: &tauri::AppHandle,
+    device_id: &str,
+    baud: u32,
+    gcs: GcsIdentity,
+    source_filter: Vec<LinkSource>,
+    wait: ConnectWait,
+) -> Result<ConnectedVehicle, String> {
+    use base64::Engine;
+    use tauri::Listener;
+
+    let usb: tauri::State<'_, tauri_plugin_usb_serial::UsbSerial<tauri::Wry>> = app.state();
+    usb.connect(device_id, baud)
+        .map_err(|e: Box<dyn std::error::Error>| e.to_string())?;
+
+    let ChannelBridge {
+        reader,
+        writer,
+        incoming_tx,
+        mut outgoing_rx,
+    } = new_link_bridge(&wait.link_outbox);
+
+    let feed = BridgeFeed::new(incoming_tx, source_filter);
+    let listener_feed = feed.clone();
+    let data_listener = app.listen("plugin:usb-serial://data", move |event| {
+        if let Ok(payload) = serde_json::from_str::<serde_json::Value>(event.payload())
+            && let Some(data_b64) = payload.get("data").and_then(|v| v.as_str())
+            && let Ok(bytes) = base64::engine::general_purpose::STANDARD.decode(data_b64)
+        {
+            listener_feed.push(bytes);
+        }
+    });
+    let detach_listener = app.listen("plugin:usb-serial://detached", |_| {
+        tracing::warn!("USB serial adapter detached; waiting for it to come back");
+    });
+    let reattach_listener = app.listen("plugin:usb-serial://reconnected", |_| {
+        tracing::info!("USB serial adapter reattached");
     });
 
+    // Writes fail while the cable is out; drop them instead of ending the
+    // task so traffic resumes once the plugin has reopened the adapter.
+    let usb_app = app.clone();
+    let writer_task =
+        crate::task_watchdog::watch(app, "drain:usb_serial".to_string(), async move {
+            while let Some(data) = outgoing_rx.recv().await {
+                let usb: tauri::State<'_, tauri_plugin_usb_serial::UsbSerial<tauri::Wry>> =
+                    usb_app.state();
+                if let Err(e) = usb.send(&data) {
+                    tracing::debug!("USB serial write dropped: {e}");
+                }
+            }
+        });
+
     let connection = StreamConnection::new(reader, writer);
     let connection: Box<
-        dyn mavlink::AsyncMavConnection<mavkit::dialect::MavMessage> + Sync + Send,
+        dyn mavlink::AsyncMavConnection<ironwing_core::dialect::MavMessage> + Sync + Send,
     > = Box::new(connection);
 
-    let config = vehicle_config::live_vehicle_config(CONNECT_TIMEOUT);
-    let vehicle = Vehicle::from_connection(connection, config)
-        .await
-        .map_err(|e| format!("Vehicle connection failed: {e}"))?;
+    let config = wait.vehicle_config(gcs);
+    wait.transport_open();
+    let teardown = LinkTeardown::new(feed, writer_task, TransportPlugin::UsbSerial)
+        .with_listeners(vec![data_listener, detach_listener, reattach_listener]);
+    let vehicle = match Vehicle::from_connection(connection, config).await {
+        Ok(vehicle) => vehicle,
+        Err(e) => {
+            teardown.run(app).await;
+            return Err(wait.connect_error(e));
+        }
+    };
 
     Ok(ConnectedVehicle {
         vehicle,
-        tasks: vec![writer_task],
-        listeners: vec![listener_id],
+        tasks: Vec::new(),
+        teardown: Some(teardown),
         demo_handle: None,
     })
 }
 
+/// Connect to an in-process simulated vehicle without building a transport
+/// request, so frontend work can run against a live backend without SITL.
+///
+/// This reuses MAVKit's demo vehicle (the same one behind the `demo`
+/// transport), which already answers arm/disarm, mode, mission and parameter
+/// exchanges. It is not a byte-level simulator: no MAVLink frames are encoded,
+/// so link faults are tested against `mock_link::MockLink` instead, and that
+/// fixture only scripts command ACKs rather than mission or parameter
+/// protocols. Release builds refuse the call.
+#[tauri::command]
+pub(crate) async fn connect_mock(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+    vehicle_preset: Option<DemoVehiclePreset>,
+) -> Result<(), AppError> {
+    if !cfg!(debug_assertions) {
+        return Err(AppError::validation("connect_mock is only available in debug builds"));
+    }
+    connect_link(
+        state,
+        app,
+        ConnectRequest {
+            transport: ConnectTransport::Demo {
+                vehicle_preset: vehicle_preset.unwrap_or(DemoVehiclePreset::Quadcopter),
+            },
+            auto_record_on_connect: false,
+            heartbeat_timeout_secs: None,
+            keep_waiting: false,
+        },
+    )
+    .await
+}
+
+/// Set the system/component IDs used for the next connection. The identity is
+/// baked into the MAVKit config at connect time, so changing it while a
+/// vehicle is connected is rejected rather than silently deferred.
+#[tauri::command]
+pub(crate) async fn set_gcs_identity(
+    state: tauri::State<'_, AppState>,
+    system_id: u8,
+    component_id: u8,
+) -> Result<(), AppError> {
+    let gcs = GcsIdentity {
+        system_id,
+        component_id,
+    };
+    gcs.validate().map_err(AppError::validation)?;
+    if is_vehicle_connected(&state).await {
+        return Err(AppError::validation(
+            "disconnect before changing the GCS identity",
+        ));
+    }
+    *state.gcs_identity.lock().await = gcs;
+    Ok(())
+}
+
+/// Keep only messages from these (system_id, component_id) pairs; an empty
+/// list turns the filter off. RADIO_STATUS always passes so link quality keeps
+/// working. Like the GCS identity it is applied when a link connects.
+#[tauri::command]
+pub(crate) async fn set_link_source_filter(
+    state: tauri::State<'_, AppState>,
+    sources: Vec<LinkSource>,
+) -> Result<(), AppError> {
+    if is_vehicle_connected(&state).await {
+        return Err(AppError::validation(
+            "disconnect before changing the link source filter",
+        ));
+    }
+    *state.link_source_filter.lock().await = sources;
+    Ok(())
+}
+
+/// Every (system_id, component_id) heard on the current link with message
+/// counts, to see who shares the link before setting a source filter.
+#[tauri::command]
+pub(crate) async fn link_sources(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<LinkSourceEntry>, AppError> {
+    Ok(state
+        .live_runtime
+        .with_runtime(|runtime| runtime.link_sources()))
+}
+
+#[tauri::command]
+pub(crate) async fn connection_info(
+    state: tauri::State<'_, AppState>,
+) -> Result<ConnectionInfo, AppError> {
+    let gcs = *state.gcs_identity.lock().await;
+    let (vehicle, link_profile) = state
+        .live_runtime
+        .with_runtime(|runtime| (runtime.vehicle(), runtime.link_profile()));
+    let identity = vehicle.map(|vehicle| vehicle.identity());
+    let serial_baud = match active_link_target(&state).await {
+        Some(ActiveLinkTarget::Serial { baud, .. }) if identity.is_some() => Some(baud),
+        _ => None,
+    };
+    let frame_stats = state
+        .link_teardown
+        .lock()
+        .await
+        .as_ref()
+        .and_then(LinkTeardown::frame_stats)
+        .filter(|_| identity.is_some());
+    let transport_stats = state
+        .link_teardown
+        .lock()
+        .await
+        .as_ref()
+        .and_then(LinkTeardown::transport_stats)
+        .filter(|_| identity.is_some());
+    Ok(ConnectionInfo {
+        connected: identity.is_some(),
+        gcs,
+        link_profile,
+        vehicle_system_id: identity.as_ref().map(|identity| identity.system_id),
+        vehicle_component_id: identity.as_ref().map(|identity| identity.component_id),
+        serial_baud,
+        frame_stats,
+        transport_stats,
+        gcs_position_fixes_sent: state.gcs_position.lock().await.fixes_sent(),
+        late_command_acks: state.command_link.late_acks(),
+    })
+}
+
 #[tauri::command]
 pub(crate) async fn disconnect_link(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
     request: Option<DisconnectRequest>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let expected_session_id = state
         .live_runtime
         .with_runtime(|runtime| runtime.effective_session_envelope(web_time::Instant::now()))
         .map(|envelope| envelope.session_id);
-    validate_disconnect_request(expected_session_id.as_deref(), request.as_ref())?;
-    force_disconnect(&state, &app).await
+    validate_disconnect_request(expected_session_id.as_deref(), request.as_ref())
+        .map_err(AppError::validation)?;
+    force_disconnect(&state, &app).await.map_err(AppError::from)
 }
 
 fn validate_disconnect_request(
@@ -562,9 +1310,9 @@ pub(crate) async fn force_disconnect(
         "live vehicle disconnected",
     )
     .await;
-    if let Some(handle) = state.connect_abort.lock().await.take() {
-        handle.abort();
-    }
+    state.vehicle_selection.lock().await.take();
+    state.link_outbox.reset();
+    abort_connect_attempt(state).await;
     // Abort any in-flight param download before aborting background tasks
     if let Some(abort) = state.param_download_abort.lock().await.take() {
         abort.abort();
@@ -573,7 +1321,9 @@ pub(crate) async fn force_disconnect(
         token.cancel();
     }
     abort_background_tasks(state).await;
-    clear_background_listeners(state, app).await;
+    crate::local_api::stop_local_api_on_disconnect(state).await;
+    crate::session_recovery::end_session_recovery(state, app).await;
+    crate::background_keepalive::stop_keepalive_on_disconnect(state, app).await;
 
     let vehicle = state.live_runtime.with_runtime(|runtime| {
         let previous = runtime.take_vehicle();
@@ -582,7 +1332,7 @@ pub(crate) async fn force_disconnect(
     });
     ironwing_core::live_runtime::emit_session_state(&state.live_runtime, DomainProvenance::Stream);
 
-    let previous_target = state.active_link_target.lock().await.take();
+    state.active_link_target.lock().await.take();
     let vehicle_disconnect_result = if let Some(v) = vehicle {
         v.disconnect().await.map_err(|e| e.to_string())
     } else {
@@ -592,7 +1342,7 @@ pub(crate) async fn force_disconnect(
         tracing::warn!("vehicle disconnect failed during teardown: {error}");
     }
     shutdown_demo_vehicle(state).await;
-    teardown_transport_target(previous_target.as_ref()).await;
+    teardown_link(state, app).await;
     vehicle_disconnect_result
 }
 
@@ -610,6 +1360,44 @@ pub(crate) async fn active_link_target(state: &AppState) -> Option<ActiveLinkTar
 mod tests {
     use super::*;
 
+    #[test]
+    fn udp_bind_reports_a_port_in_use() {
+        let holder = std::net::UdpSocket::bind("127.0.0.1:0").expect("bind holder");
+        let port = holder.local_addr().unwrap().port();
+
+        let error = bind_udp_socket(&format!("127.0.0.1:{port}")).unwrap_err();
+        let failure: OperationFailure = serde_json::from_str(&error).expect("structured error");
+        assert_eq!(failure.operation_id, OperationId::ConnectLink);
+        assert_eq!(failure.reason.kind, crate::ipc::ReasonKind::Conflict);
+
+        drop(holder);
+        let socket = bind_udp_socket(&format!("127.0.0.1:{port}")).expect("port is free again");
+        assert_eq!(socket.local_addr().unwrap().port(), port);
+        let error = bind_udp_socket("udp://14550").unwrap_err();
+        let failure: OperationFailure = serde_json::from_str(&error).expect("structured error");
+        assert_eq!(failure.reason.kind, crate::ipc::ReasonKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn replacing_background_tasks_aborts_the_previous_set() {
+        let idle = || tokio::spawn(std::future::pending::<()>());
+        let mut slot = vec![idle(), idle(), idle()];
+
+        let stale = replace_background_tasks(&mut slot, vec![idle()]);
+
+        assert_eq!(slot.len(), 1);
+        assert_eq!(stale.len(), 3);
+        for task in stale {
+            let error = task
+                .await
+                .expect_err("stale bridge task must not finish cleanly");
+            assert!(error.is_cancelled());
+        }
+        for task in slot {
+            task.abort();
+        }
+    }
+
     #[test]
     fn typed_connect_request_deserializes_transport_field() {
         let request: ConnectRequest = serde_json::from_value(serde_json::json!({
@@ -663,23 +1451,30 @@ mod tests {
                 bind_addr: "0.0.0.0:14550".into(),
             },
             auto_record_on_connect: false,
+            heartbeat_timeout_secs: None,
+            keep_waiting: false,
         };
         let enabled = ConnectRequest {
             transport: ConnectTransport::Udp {
                 bind_addr: "0.0.0.0:14550".into(),
             },
             auto_record_on_connect: true,
+            heartbeat_timeout_secs: None,
+            keep_waiting: false,
         };
 
+        let settings = crate::ipc::Settings::default();
         assert_eq!(
-            auto_record_start_request(disabled.auto_record_on_connect),
+            auto_record_start_request(disabled.auto_record_on_connect, &settings),
             None
         );
         assert_eq!(
-            auto_record_start_request(enabled.auto_record_on_connect),
+            auto_record_start_request(enabled.auto_record_on_connect, &settings),
             Some(crate::ipc::RecordingStartRequest {
                 destination_path: String::new(),
                 mode: crate::ipc::RecordingMode::AutoOnConnect,
+                rotation: None,
+                fsync: crate::ipc::TlogFsyncPolicy::OsDefault,
             })
         );
     }
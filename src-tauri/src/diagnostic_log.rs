@@ -0,0 +1,185 @@
+//! Tracing output, to stderr and to a size-capped set of files in the app
+//! log directory that `diagnostics_export` bundles.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use tauri::Manager;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt;
+use tracing_subscriber::prelude::*;
+
+const LOG_FILE_STEM: &str = "ironwing";
+/// A file is rolled over once it would grow past this.
+const MAX_LOG_FILE_BYTES: u64 = 2 * 1024 * 1024;
+/// The live file plus rolled-over ones; older ones are deleted.
+const KEPT_LOG_FILES: usize = 3;
+
+static LOG_FILES: OnceLock<Mutex<RollingFile>> = OnceLock::new();
+
+/// `ironwing.log` and, oldest last, `ironwing.1.log` and so on.
+struct RollingFile {
+    dir: PathBuf,
+    file: File,
+    size: u64,
+    max_bytes: u64,
+    kept: usize,
+}
+
+fn log_path(dir: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        dir.join(format!("{LOG_FILE_STEM}.log"))
+    } else {
+        dir.join(format!("{LOG_FILE_STEM}.{index}.log"))
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+impl RollingFile {
+    fn open(dir: &Path, max_bytes: u64, kept: usize) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let file = open_append(&log_path(dir, 0))?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            file,
+            size,
+            max_bytes,
+            kept: kept.max(1),
+        })
+    }
+
+    fn roll_over(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        let _ = fs::remove_file(log_path(&self.dir, self.kept - 1));
+        for index in (0..self.kept - 1).rev() {
+            let from = log_path(&self.dir, index);
+            if from.exists() {
+                fs::rename(&from, log_path(&self.dir, index + 1))?;
+            }
+        }
+        self.file = open_append(&log_path(&self.dir, 0))?;
+        self.size = 0;
+        Ok(())
+    }
+
+    /// The files that exist, oldest first.
+    fn paths(&self) -> Vec<PathBuf> {
+        (0..self.kept)
+            .rev()
+            .map(|index| log_path(&self.dir, index))
+            .filter(|path| path.exists())
+            .collect()
+    }
+}
+
+impl Write for RollingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > self.max_bytes {
+            self.roll_over()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn lock_log_files() -> Option<MutexGuard<'static, RollingFile>> {
+    LOG_FILES.get().map(|files| {
+        files
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    })
+}
+
+/// What the file layer writes through; each formatted event is one write.
+struct LogFiles;
+
+impl Write for LogFiles {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match lock_log_files() {
+            Some(mut files) => files.write(buf),
+            None => Ok(buf.len()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        lock_log_files().map_or(Ok(()), |mut files| files.flush())
+    }
+}
+
+/// Install the global subscriber. Without a log directory only stderr gets
+/// the output.
+pub(crate) fn install(app: &tauri::AppHandle) {
+    let files = match app.path().app_log_dir() {
+        Ok(dir) => RollingFile::open(&dir, MAX_LOG_FILE_BYTES, KEPT_LOG_FILES)
+            .map_err(|error| format!("failed to open log files in {}: {error}", dir.display())),
+        Err(error) => Err(format!("no log directory: {error}")),
+    };
+    let file_layer = match files {
+        Ok(files) => {
+            let _ = LOG_FILES.set(Mutex::new(files));
+            Some(fmt::layer().with_ansi(false).with_writer(|| LogFiles))
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            None
+        }
+    };
+    let _ = tracing_subscriber::registry()
+        .with(fmt::layer().with_writer(io::stderr))
+        .with(file_layer)
+        .with(LevelFilter::INFO)
+        .try_init();
+}
+
+/// The log files written so far, oldest first, flushed.
+pub(crate) fn log_files() -> Vec<PathBuf> {
+    let Some(mut files) = lock_log_files() else {
+        return Vec::new();
+    };
+    let _ = files.flush();
+    files.paths()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolls_over_at_the_cap_and_keeps_a_fixed_number_of_files() {
+        let dir = std::env::temp_dir().join(format!("ironwing-log-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut files = RollingFile::open(&dir, 100, 3).expect("open");
+        for line in 0..10 {
+            files
+                .write_all(format!("{line:02} {}\n", "x".repeat(36)).as_bytes())
+                .expect("write");
+        }
+        files.flush().expect("flush");
+
+        let paths = files.paths();
+        let names: Vec<_> = paths
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, ["ironwing.2.log", "ironwing.1.log", "ironwing.log"]);
+        for path in &paths {
+            assert!(fs::metadata(path).unwrap().len() <= 100);
+        }
+        // Forty-byte lines, two to a file: the oldest kept file starts at 04.
+        let oldest = fs::read_to_string(&paths[0]).unwrap();
+        assert!(oldest.starts_with("04 "), "{oldest}");
+        assert!(fs::read_to_string(&paths[2]).unwrap().starts_with("08 "));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
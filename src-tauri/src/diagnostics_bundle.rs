@@ -0,0 +1,179 @@
+use std::fs::File;
+use std::io::{BufWriter, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ironwing_core::diagnostics_bundle::{recent_status_texts, redact_mac_addresses};
+use ironwing_core::vehicle_snapshot::unix_epoch_usec;
+use mavkit::{LinkState, Vehicle};
+use tauri::Manager;
+
+use crate::AppState;
+use crate::ipc::{AppError, Diagnostics, DiagnosticsBundleEntry, DiagnosticsExport};
+use crate::link_teardown::LinkTeardown;
+use crate::zip_stream::ZipStreamWriter;
+
+const LINK_QUALITY_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Samples link health into the runtime's history once a second.
+pub(crate) async fn link_quality_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    let mut interval = tokio::time::interval(LINK_QUALITY_SAMPLE_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+        let state: tauri::State<'_, AppState> = handle.state();
+        let connected = matches!(vehicle.link().state().latest(), Some(LinkState::Connected));
+        let frame_stats = state
+            .link_teardown
+            .lock()
+            .await
+            .as_ref()
+            .and_then(LinkTeardown::frame_stats);
+        let unix_msec = unix_epoch_usec() / 1_000;
+        state
+            .live_runtime
+            .with_runtime(|runtime| runtime.sample_link_quality(unix_msec, connected, frame_stats));
+    }
+}
+
+enum BundleSource {
+    Bytes(Vec<u8>),
+    File(PathBuf),
+}
+
+struct BundleFile {
+    file_name: String,
+    source: BundleSource,
+}
+
+fn json_file(file_name: &str, value: &impl serde::Serialize) -> Result<BundleFile, String> {
+    let bytes = serde_json::to_vec_pretty(value)
+        .map_err(|error| format!("failed to serialize {file_name}: {error}"))?;
+    Ok(BundleFile {
+        file_name: file_name.to_string(),
+        source: BundleSource::Bytes(bytes),
+    })
+}
+
+async fn gather(
+    app: &tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    created_unix_msec: u64,
+    redact: bool,
+) -> Result<Vec<BundleFile>, String> {
+    let system = serde_json::json!({
+        "app_version": app.package_info().version.to_string(),
+        "tauri_version": tauri::VERSION,
+        "os": std::env::consts::OS,
+        "os_family": std::env::consts::FAMILY,
+        "arch": std::env::consts::ARCH,
+        "created_unix_msec": created_unix_msec,
+        "mac_addresses_redacted": redact,
+    });
+    let connection = crate::connection::connection_info(state.clone()).await?;
+    let (link_quality, status_texts, event_bridges) = state.live_runtime.with_runtime(|runtime| {
+        (
+            runtime.link_quality_history(),
+            recent_status_texts(runtime.status_text_history()).to_vec(),
+            runtime.event_bridge_stats(),
+        )
+    });
+    let tasks = Diagnostics {
+        tasks: state.task_watchdog.snapshot(),
+        event_bridges,
+    };
+
+    let mut files = vec![
+        json_file("system.json", &system)?,
+        json_file("connection.json", &connection)?,
+        json_file("link_quality.json", &link_quality)?,
+        json_file("statustext.json", &status_texts)?,
+        json_file("tasks.json", &tasks)?,
+    ];
+    files.extend(
+        crate::diagnostic_log::log_files()
+            .into_iter()
+            .filter_map(|path| {
+                let name = path.file_name()?.to_string_lossy().to_string();
+                Some(BundleFile {
+                    file_name: format!("logs/{name}"),
+                    source: BundleSource::File(path),
+                })
+            }),
+    );
+    Ok(files)
+}
+
+fn read_source(source: BundleSource, file_name: &str) -> Result<Vec<u8>, String> {
+    match source {
+        BundleSource::Bytes(bytes) => Ok(bytes),
+        BundleSource::File(path) => {
+            let mut bytes = Vec::new();
+            File::open(&path)
+                .and_then(|mut file| file.read_to_end(&mut bytes))
+                .map_err(|error| format!("failed to read {file_name}: {error}"))?;
+            Ok(bytes)
+        }
+    }
+}
+
+fn write_bundle(
+    path: &Path,
+    files: Vec<BundleFile>,
+    unix_secs: i64,
+    redact: bool,
+) -> Result<DiagnosticsExport, String> {
+    let file = File::create(path).map_err(|error| format!("failed to create archive: {error}"))?;
+    let mut zip = ZipStreamWriter::new(BufWriter::new(file), unix_secs);
+
+    let mut entries = Vec::with_capacity(files.len());
+    for BundleFile { file_name, source } in files {
+        let mut bytes = read_source(source, &file_name)?;
+        if redact {
+            bytes = redact_mac_addresses(&String::from_utf8_lossy(&bytes)).into_bytes();
+        }
+        let written = zip
+            .add_entry(&file_name, &mut bytes.as_slice())
+            .map_err(|error| format!("failed to write {file_name}: {error}"))?;
+        entries.push(DiagnosticsBundleEntry {
+            file_name,
+            bytes: written,
+        });
+    }
+    let (writer, bytes_written) = zip
+        .finish()
+        .map_err(|error| format!("failed to finish archive: {error}"))?;
+    writer
+        .into_inner()
+        .map_err(|error| format!("failed to write archive: {}", error.error()))?;
+
+    Ok(DiagnosticsExport {
+        path: path.display().to_string(),
+        bytes_written,
+        entries,
+    })
+}
+
+/// Zip what a bug report needs: the rolling log files, connection and
+/// transport stats, the link quality history, the last status texts of the
+/// current session, app and OS versions and the task table. With `redact`
+/// BLE MAC addresses are masked in every file.
+#[tauri::command]
+pub(crate) async fn diagnostics_export(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    path: String,
+    redact: Option<bool>,
+) -> Result<DiagnosticsExport, AppError> {
+    let redact = redact.unwrap_or(false);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let files = gather(&app, state, now.as_millis() as u64, redact).await?;
+    tokio::task::spawn_blocking(move || {
+        write_bundle(Path::new(&path), files, now.as_secs() as i64, redact)
+    })
+    .await
+    .map_err(|error| format!("export task failed: {error}"))?
+    .map_err(AppError::from)
+}
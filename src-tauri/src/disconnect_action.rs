@@ -0,0 +1,90 @@
+use ironwing_core::component_command::ComponentCommand;
+use ironwing_core::dialect::MavCmd;
+use ironwing_core::disconnect_action::{
+    DISCONNECT_ACTION_TIMEOUT, DisconnectStep, disconnect_step, sent_outcome,
+};
+use ironwing_core::event_names;
+use ironwing_core::live_runtime;
+use serde_json::json;
+
+use crate::AppState;
+use crate::component_commands::{CommandLink, run_command_exchange};
+use crate::connection::force_disconnect;
+use crate::helpers::{ensure_live_write_allowed, vehicle_is_armed, with_vehicle};
+use crate::ipc::{
+    AppError, DisconnectAction, DisconnectActionOutcome, DisconnectActionResult, OperationId,
+};
+use crate::journal;
+
+/// Disconnect, first commanding an armed vehicle to RTL or land and waiting
+/// up to 3 s for the ACK. The outcome goes out on `link://disconnect_action`
+/// before the transport closes; the link closes whatever the outcome.
+/// Disconnecting from an armed vehicle needs `confirm_armed`.
+#[tauri::command]
+pub(crate) async fn disconnect_link_ex(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+    action: DisconnectAction,
+    confirm_armed: bool,
+) -> Result<DisconnectActionResult, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::DisconnectLinkEx,
+        json!({ "action": action, "confirm_armed": confirm_armed }),
+        async {
+            let vehicle = with_vehicle(&state).await.ok();
+            let armed = vehicle.as_ref().is_some_and(vehicle_is_armed);
+            let outcome = match disconnect_step(action, armed, confirm_armed)
+                .map_err(AppError::validation)?
+            {
+                DisconnectStep::Skip(outcome) => outcome,
+                DisconnectStep::Send(command) => {
+                    ensure_live_write_allowed(state.inner(), OperationId::DisconnectLinkEx).await?;
+                    // `armed` implies a vehicle.
+                    let vehicle = vehicle.expect("armed vehicle");
+                    send_action(&state.command_link, &vehicle, command).await
+                }
+            };
+            let result = DisconnectActionResult {
+                action,
+                armed,
+                outcome,
+            };
+            live_runtime::emit_scoped(
+                &state.live_runtime,
+                event_names::LINK_DISCONNECT_ACTION,
+                result.clone(),
+            );
+            force_disconnect(&state, &app).await?;
+            Ok(result)
+        },
+    )
+    .await
+}
+
+async fn send_action(
+    link: &CommandLink,
+    vehicle: &mavkit::Vehicle,
+    command: MavCmd,
+) -> DisconnectActionOutcome {
+    let identity = vehicle.identity();
+    let exchange = run_command_exchange(
+        link,
+        vehicle,
+        ComponentCommand {
+            system_id: identity.system_id,
+            component_id: identity.component_id,
+            command,
+            params: [0.0; 7],
+        },
+    );
+    let ack = tokio::time::timeout(DISCONNECT_ACTION_TIMEOUT, exchange)
+        .await
+        .ok()
+        .map(|outcome| {
+            outcome
+                .map(|outcome| outcome.result.map_err(String::from))
+                .map_err(String::from)
+        });
+    sent_outcome(ack)
+}
@@ -2,10 +2,27 @@ pub(crate) fn emit_event<S: serde::Serialize + Clone + Send + 'static>(
     handle: &tauri::AppHandle,
     event: &str,
     payload: &S,
+) {
+    emit_to_webview(handle, event, payload);
+    emit_to_subscribers(handle, event, payload);
+}
+
+pub(crate) fn emit_to_webview<S: serde::Serialize + Clone + Send + 'static>(
+    handle: &tauri::AppHandle,
+    event: &str,
+    payload: &S,
 ) {
     use tauri::Emitter;
-    use tauri::Manager;
     let _ = handle.emit(event, payload);
+}
+
+/// Publish to the remote UI bridge and local API streams only.
+pub(crate) fn emit_to_subscribers<S: serde::Serialize>(
+    handle: &tauri::AppHandle,
+    event: &str,
+    payload: &S,
+) {
+    use tauri::Manager;
     if let Ok(remote_event) = crate::remote_ui::RemoteUiEvent::new(event, payload) {
         let state: tauri::State<'_, crate::AppState> = handle.state();
         let _ = state.remote_ui_events.send(remote_event);
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use ironwing_core::dialect::MavMessage;
+use ironwing_core::event_names;
+use ironwing_core::fence_proximity::is_fence_warning;
+use ironwing_core::flight_track::GLOBAL_POSITION_INT_MESSAGE_ID;
+use ironwing_core::live_runtime;
+use mavkit::Vehicle;
+use mavlink::MavlinkVersion;
+use tauri::Manager;
+
+use crate::AppState;
+
+const FENCE_PROXIMITY_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Checks the vehicle against its fence twice a second and emits
+/// `fence://proximity` while it is within the warning margin of a boundary
+/// and closing on it, judged by the GLOBAL_POSITION_INT ground velocity. The
+/// autopilot still enforces the fence; this is only the early warning.
+pub(crate) async fn fence_proximity_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    let mut interval = tokio::time::interval(FENCE_PROXIMITY_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let state: tauri::State<'_, AppState> = handle.state();
+                let margin_m = state.settings.lock().await.fence_warning_margin_m;
+                let proximity = state
+                    .live_runtime
+                    .with_runtime(|runtime| runtime.fence_proximity());
+                if let Some(proximity) =
+                    proximity.filter(|proximity| is_fence_warning(proximity, margin_m))
+                {
+                    live_runtime::emit_scoped(
+                        &state.live_runtime,
+                        event_names::FENCE_PROXIMITY,
+                        proximity,
+                    );
+                }
+            }
+            raw_msg = raw_stream.next() => {
+                let Some(raw_msg) = raw_msg else {
+                    break;
+                };
+                if raw_msg.system_id != system_id
+                    || raw_msg.message_id != GLOBAL_POSITION_INT_MESSAGE_ID
+                {
+                    continue;
+                }
+                if let Ok(MavMessage::GLOBAL_POSITION_INT(data)) =
+                    MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+                {
+                    let state: tauri::State<'_, AppState> = handle.state();
+                    state
+                        .live_runtime
+                        .with_runtime(|runtime| runtime.observe_ground_velocity(data.vx, data.vy));
+                }
+            }
+        }
+    }
+}
@@ -26,7 +26,7 @@ use crate::firmware::types::{
     SerialReadinessBlockedReason, SerialReadinessRequest, SerialReadinessResponse,
 };
 use crate::helpers::ensure_live_write_allowed;
-use crate::ipc::OperationId;
+use crate::ipc::{AppError, OperationId};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum SessionCancelAction {
@@ -104,7 +104,7 @@ pub(crate) async fn firmware_install_update(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
     request: SerialFlashRequest,
-) -> Result<SerialFlowResult, String> {
+) -> Result<SerialFlowResult, AppError> {
     ensure_live_write_allowed(state.inner(), OperationId::FirmwareInstallUpdate).await?;
     let SerialFlashRequest {
         port,
@@ -113,7 +113,7 @@ pub(crate) async fn firmware_install_update(
         options,
     } = request;
 
-    let options = normalize_serial_options(options)?;
+    let options = normalize_serial_options(options).map_err(AppError::validation)?;
 
     state
         .firmware_session
@@ -132,7 +132,7 @@ pub(crate) async fn firmware_install_update(
 
     if let Err(err) = validate_started_serial_flash_request(&readiness_request, &available_ports) {
         finish_serial_session_failed(&state, err.clone());
-        return Err(err);
+        return Err(AppError::validation(err));
     }
 
     if cancellation_signal(&state) {
@@ -145,7 +145,7 @@ pub(crate) async fn firmware_install_update(
     if selected_port_has_active_serial_link(active_link_target.as_ref(), &port) {
         let err = "selected serial port has an active MAVLink connection; reboot to bootloader before starting install".to_string();
         finish_serial_session_failed(&state, err.clone());
-        return Err(err);
+        return Err(AppError::validation(err));
     }
 
     let apj_bytes = match resolve_source_with_cancellation(&state, source).await {
@@ -157,7 +157,7 @@ pub(crate) async fn firmware_install_update(
         }
         Err(ResolveSerialSourceError::Failed(err)) => {
             finish_serial_session_failed(&state, err.clone());
-            return Err(err);
+            return Err(AppError::internal(err));
         }
     };
 
@@ -171,7 +171,7 @@ pub(crate) async fn firmware_install_update(
         Ok(artifact) => artifact,
         Err(err) => {
             finish_serial_session_failed(&state, err.clone());
-            return Err(err);
+            return Err(AppError::validation(err));
         }
     };
 
@@ -257,15 +257,17 @@ pub(crate) async fn firmware_install_update(
     state: tauri::State<'_, AppState>,
     _app: tauri::AppHandle,
     _request: SerialFlashRequest,
-) -> Result<SerialFlowResult, String> {
+) -> Result<SerialFlowResult, AppError> {
     ensure_live_write_allowed(state.inner(), OperationId::FirmwareInstallUpdate).await?;
-    Err(crate::firmware::types::FirmwareError::PlatformUnsupported.to_string())
+    Err(AppError::validation(
+        crate::firmware::types::FirmwareError::PlatformUnsupported.to_string(),
+    ))
 }
 
 #[tauri::command]
 pub(crate) async fn firmware_install_update_preflight(
     state: tauri::State<'_, AppState>,
-) -> Result<SerialPreflightInfo, String> {
+) -> Result<SerialPreflightInfo, AppError> {
     let vehicle_connected = connection::is_vehicle_connected(&state).await;
 
     let param_count = if vehicle_connected {
@@ -314,7 +316,7 @@ pub(crate) fn firmware_session_clear_completed(state: tauri::State<'_, AppState>
 #[tauri::command]
 pub(crate) async fn firmware_session_cancel(
     state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     match classify_session_cancel_action(
         &state.firmware_session.status(),
         state.firmware_abort.lock().await.is_some(),
@@ -346,7 +348,7 @@ pub(crate) async fn firmware_session_cancel(
 pub(crate) async fn firmware_install_update_readiness(
     state: tauri::State<'_, AppState>,
     request: SerialReadinessRequest,
-) -> Result<SerialReadinessResponse, String> {
+) -> Result<SerialReadinessResponse, AppError> {
     let available_ports = match crate::firmware::discovery::list_firmware_ports() {
         InventoryResult::Available { ports } => ports,
         InventoryResult::Unsupported => Vec::new(),
@@ -374,7 +376,7 @@ pub(crate) async fn firmware_reboot_to_bootloader(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
     port: String,
-) -> Result<FirmwareRebootToBootloaderResult, String> {
+) -> Result<FirmwareRebootToBootloaderResult, AppError> {
     if let Err(error) =
         ensure_live_write_allowed(state.inner(), OperationId::FirmwareInstallUpdate).await
     {
@@ -427,7 +429,7 @@ pub(crate) async fn firmware_reboot_to_bootloader(
     _state: tauri::State<'_, AppState>,
     _app: tauri::AppHandle,
     _port: String,
-) -> Result<FirmwareRebootToBootloaderResult, String> {
+) -> Result<FirmwareRebootToBootloaderResult, AppError> {
     Ok(FirmwareRebootToBootloaderResult::Unsupported {
         reason: "firmware serial reboot is not supported on this platform".into(),
     })
@@ -438,12 +440,13 @@ pub(crate) async fn firmware_reboot_to_bootloader(
 pub(crate) async fn firmware_detect_bootloader_board(
     state: tauri::State<'_, AppState>,
     port: String,
-) -> Result<FirmwareBootloaderBoardInfo, String> {
+) -> Result<FirmwareBootloaderBoardInfo, AppError> {
     let selected_port = port.trim().to_string();
     validate_bootloader_board_detection_target(
         &selected_port,
         connection::active_link_target(&state).await.as_ref(),
-    )?;
+    )
+    .map_err(AppError::validation)?;
 
     tokio::task::spawn_blocking(move || {
         let deps = serial_executor::RealSerialDeps;
@@ -453,14 +456,17 @@ pub(crate) async fn firmware_detect_bootloader_board(
     })
     .await
     .unwrap_or_else(|error| Err(format!("bootloader board detection task failed: {error}")))
+    .map_err(AppError::from)
 }
 
 #[cfg(target_os = "android")]
 #[tauri::command]
 pub(crate) async fn firmware_detect_bootloader_board(
     _port: String,
-) -> Result<FirmwareBootloaderBoardInfo, String> {
-    Err(crate::firmware::types::FirmwareError::PlatformUnsupported.to_string())
+) -> Result<FirmwareBootloaderBoardInfo, AppError> {
+    Err(AppError::validation(
+        crate::firmware::types::FirmwareError::PlatformUnsupported.to_string(),
+    ))
 }
 
 pub(crate) fn serial_readiness_request_token(request: &SerialReadinessRequest) -> String {
@@ -519,7 +525,7 @@ fn selected_port_has_active_serial_link(
 ) -> bool {
     matches!(
         active_link_target,
-        Some(ActiveLinkTarget::Serial { port }) if port == selected_port
+        Some(ActiveLinkTarget::Serial { port, .. }) if port == selected_port
     )
 }
 
@@ -538,7 +544,7 @@ fn bootloader_board_detection_error(port: &str, error: FirmwareError) -> String
 fn validate_bootloader_board_detection_target(
     selected_port: &str,
     active_link_target: Option<&ActiveLinkTarget>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     if selected_port.trim().is_empty() {
         return Err("serial port not selected".into());
     }
@@ -599,7 +605,7 @@ pub(crate) fn evaluate_serial_readiness(
 fn validate_started_serial_flash_request(
     request: &SerialReadinessRequest,
     available_ports: &[PortInfo],
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     if request.port.trim().is_empty() {
         return Err("serial port not selected".into());
     }
@@ -762,7 +768,7 @@ pub(crate) async fn firmware_bootloader_installation(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
     request: DfuFlashRequest,
-) -> Result<DfuRecoveryResult, String> {
+) -> Result<DfuRecoveryResult, AppError> {
     ensure_live_write_allowed(state.inner(), OperationId::FirmwareBootloaderInstallation).await?;
     state
         .firmware_session
@@ -850,7 +856,7 @@ pub(crate) async fn firmware_bootloader_installation(
     state: tauri::State<'_, AppState>,
     _app: tauri::AppHandle,
     _request: DfuFlashRequest,
-) -> Result<DfuRecoveryResult, String> {
+) -> Result<DfuRecoveryResult, AppError> {
     ensure_live_write_allowed(state.inner(), OperationId::FirmwareBootloaderInstallation).await?;
     Ok(DfuRecoveryResult::PlatformUnsupported)
 }
@@ -1417,12 +1423,14 @@ mod tests {
         assert!(selected_port_has_active_serial_link(
             Some(&ActiveLinkTarget::Serial {
                 port: "/dev/ttyACM0".into(),
+                baud: 115_200,
             }),
             "/dev/ttyACM0",
         ));
         assert!(!selected_port_has_active_serial_link(
             Some(&ActiveLinkTarget::Serial {
                 port: "/dev/ttyUSB1".into(),
+                baud: 115_200,
             }),
             "/dev/ttyACM0",
         ));
@@ -1439,6 +1447,7 @@ mod tests {
             "/dev/ttyACM0",
             Some(&ActiveLinkTarget::Serial {
                 port: "/dev/ttyACM0".into(),
+                baud: 115_200,
             }),
         );
 
@@ -1485,6 +1494,7 @@ mod tests {
                 &ports,
                 Some(&ActiveLinkTarget::Serial {
                     port: "/dev/ttyACM0".into(),
+                    baud: 115_200,
                 }),
             ),
             FirmwareInstallBootloaderStatus::NotInBootloader { can_reboot: true }
@@ -1495,6 +1505,7 @@ mod tests {
                 &ports,
                 Some(&ActiveLinkTarget::Serial {
                     port: "/dev/ttyUSB1".into(),
+                    baud: 115_200,
                 }),
             ),
             FirmwareInstallBootloaderStatus::Unknown
@@ -1537,6 +1548,7 @@ mod tests {
                 &ports,
                 Some(&ActiveLinkTarget::Serial {
                     port: "/dev/ttyACM0".into(),
+                    baud: 115_200,
                 }),
             ),
             FirmwareInstallBootloaderStatus::AlreadyInBootloader
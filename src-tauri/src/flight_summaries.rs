@@ -0,0 +1,87 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use ironwing_core::event_names;
+use ironwing_core::flight_summary::FLIGHT_SUMMARIES_FILE_NAME;
+use tauri::{Listener, Manager};
+
+use crate::AppState;
+use crate::ipc::FlightSessionSummary;
+
+fn flight_summaries_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|error| format!("failed to resolve app-data directory: {error}"))?;
+    Ok(app_data_dir.join(FLIGHT_SUMMARIES_FILE_NAME))
+}
+
+/// Read the JSON-lines history. Lines that fail to parse (e.g. a write cut
+/// short by a crash) are skipped rather than discarding the whole file.
+fn read_flight_summaries(app: &tauri::AppHandle) -> Result<Vec<FlightSessionSummary>, String> {
+    let path = flight_summaries_path(app)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|error| format!("failed to read flight summaries: {error}"))?;
+    Ok(contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn append_flight_summary(
+    app: &tauri::AppHandle,
+    summary: &FlightSessionSummary,
+) -> Result<(), String> {
+    let path = flight_summaries_path(app)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create app-data directory: {error}"))?;
+    }
+    let mut line = serde_json::to_string(summary)
+        .map_err(|error| format!("failed to serialize flight summary: {error}"))?;
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|error| format!("failed to write flight summary: {error}"))
+}
+
+/// Load the persisted history into the runtime and append every summary the
+/// runtime emits from now on.
+pub(crate) fn install_flight_summary_history(app: &tauri::AppHandle) {
+    match read_flight_summaries(app) {
+        Ok(summaries) => {
+            let state = app.state::<AppState>();
+            state
+                .live_runtime
+                .with_runtime(|runtime| runtime.load_flight_summaries(summaries));
+        }
+        Err(error) => tracing::warn!("ignoring saved flight summaries: {error}"),
+    }
+
+    let handle = app.clone();
+    app.listen(event_names::FLIGHT_SUMMARY, move |event| {
+        let summary = serde_json::from_str::<serde_json::Value>(event.payload())
+            .ok()
+            .and_then(|mut payload| serde_json::from_value(payload["value"].take()).ok());
+        let Some(summary) = summary else {
+            return;
+        };
+        if let Err(error) = append_flight_summary(&handle, &summary) {
+            tracing::warn!("{error}");
+        }
+    });
+}
+
+#[tauri::command]
+pub(crate) fn flight_summaries(state: tauri::State<'_, AppState>) -> Vec<FlightSessionSummary> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.flight_summaries().to_vec())
+}
@@ -0,0 +1,152 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use ironwing_core::dialect::MavMessage;
+use ironwing_core::event_names;
+use ironwing_core::flight_track::{
+    GLOBAL_POSITION_INT_MESSAGE_ID, LAST_FLIGHT_TRACK_FILE_NAME, TRACK_APPEND_INTERVAL, TrackBatch,
+    track_point_from_global_position,
+};
+use ironwing_core::live_runtime;
+use ironwing_core::vehicle_snapshot::unix_epoch_usec;
+use mavkit::Vehicle;
+use mavlink::MavlinkVersion;
+use tauri::Manager;
+
+use crate::AppState;
+use crate::ipc::TrackPoint;
+
+fn flight_track_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|error| format!("failed to resolve app-data directory: {error}"))?;
+    Ok(app_data_dir.join(LAST_FLIGHT_TRACK_FILE_NAME))
+}
+
+/// Read the JSON-lines track, skipping lines cut short by a crash.
+fn read_flight_track(app: &tauri::AppHandle) -> Result<Vec<TrackPoint>, String> {
+    let path = flight_track_path(app)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|error| format!("failed to read flight track: {error}"))?;
+    Ok(contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Append the batch, truncating first when the track was cleared or a new
+/// session replaced the previous one.
+fn persist_track_batch(app: &tauri::AppHandle, batch: &TrackBatch) -> Result<(), String> {
+    let path = flight_track_path(app)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create app-data directory: {error}"))?;
+    }
+    let mut lines = String::new();
+    for point in &batch.points {
+        let line = serde_json::to_string(point)
+            .map_err(|error| format!("failed to serialize track point: {error}"))?;
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+    std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(!batch.restart)
+        .truncate(batch.restart)
+        .open(&path)
+        .and_then(|mut file| file.write_all(lines.as_bytes()))
+        .map_err(|error| format!("failed to write flight track: {error}"))
+}
+
+/// Restore the track persisted by the previous run, then every
+/// `TRACK_APPEND_INTERVAL` emit the new points as `track://append` and
+/// append them to disk.
+pub(crate) fn install_flight_track(app: &tauri::AppHandle) {
+    match read_flight_track(app) {
+        Ok(points) if !points.is_empty() => {
+            let state = app.state::<AppState>();
+            state
+                .live_runtime
+                .with_runtime(|runtime| runtime.restore_flight_track(points));
+        }
+        Ok(_) => {}
+        Err(error) => tracing::warn!("ignoring saved flight track: {error}"),
+    }
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(TRACK_APPEND_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            let state = app.state::<AppState>();
+            let Some(batch) = state
+                .live_runtime
+                .with_runtime(|runtime| runtime.take_track_batch())
+            else {
+                continue;
+            };
+            if !batch.points.is_empty() {
+                live_runtime::emit_scoped(
+                    &state.live_runtime,
+                    event_names::TRACK_APPEND,
+                    batch.points.clone(),
+                );
+            }
+            if let Err(error) = persist_track_batch(&app, &batch) {
+                tracing::warn!("{error}");
+            }
+        }
+    });
+}
+
+/// Feeds every GLOBAL_POSITION_INT of the bound vehicle into the flight
+/// track, ahead of the telemetry throttle.
+pub(crate) async fn flight_track_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        if raw_msg.message_id != GLOBAL_POSITION_INT_MESSAGE_ID || raw_msg.system_id != system_id {
+            continue;
+        }
+        let Ok(MavMessage::GLOBAL_POSITION_INT(data)) =
+            MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+        else {
+            continue;
+        };
+        let Some(point) = track_point_from_global_position(&data, unix_epoch_usec()) else {
+            continue;
+        };
+        let state: tauri::State<'_, AppState> = handle.state();
+        state
+            .live_runtime
+            .with_runtime(|runtime| runtime.record_track_point(point));
+    }
+}
+
+#[tauri::command]
+pub(crate) fn track_get(
+    state: tauri::State<'_, AppState>,
+    since_usec: Option<u64>,
+    max_points: Option<usize>,
+) -> Vec<TrackPoint> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.flight_track().since(since_usec, max_points))
+}
+
+#[tauri::command]
+pub(crate) fn track_clear(state: tauri::State<'_, AppState>) {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.clear_flight_track());
+}
@@ -0,0 +1,95 @@
+use ironwing_core::frames::{frame_options as available_frames, frame_param_writes};
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::param_value::param_value;
+use ironwing_core::params::persist_params_by_default;
+use mavkit::Vehicle;
+use serde_json::json;
+
+use crate::AppState;
+use crate::helpers::{ensure_live_write_allowed, vehicle_is_armed, with_vehicle};
+use crate::ipc::{AppError, FrameApplyResult, FrameOption, OperationId};
+use crate::journal;
+use crate::param_changes::expect_param_writes;
+
+fn vehicle_frames(vehicle: &Vehicle) -> Result<Vec<FrameOption>, AppError> {
+    let store = live_commands::param_get_all(vehicle).map_err(AppError::from)?;
+    let identity = vehicle.identity();
+    let encoding = live_commands::param_encoding(vehicle);
+    available_frames(identity.autopilot, identity.vehicle_type, |name| {
+        store
+            .params
+            .get(name)
+            .map(|param| param_value(param, encoding).as_f64() as f32)
+    })
+    .map_err(AppError::validation)
+}
+
+/// Airframes the connected vehicle can be switched to, with the one its
+/// parameters currently select marked `current`.
+#[tauri::command]
+pub(crate) async fn frame_options(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<FrameOption>, AppError> {
+    let vehicle = with_vehicle(&state).await?;
+    vehicle_frames(&vehicle)
+}
+
+/// Write the parameters of `option_id` and, when `reboot` is set and every
+/// write was accepted, reboot so the new frame takes effect. PX4 only loads
+/// SYS_AUTOSTART from storage, so the writes are always committed there, even
+/// when the user reboots later.
+#[tauri::command]
+pub(crate) async fn frame_apply(
+    state: tauri::State<'_, AppState>,
+    option_id: String,
+    reboot: Option<bool>,
+) -> Result<FrameApplyResult, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::FrameApply,
+        json!({ "option_id": option_id, "reboot": reboot }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::FrameApply).await?;
+            let vehicle = with_vehicle(&state).await?;
+            if vehicle_is_armed(&vehicle) {
+                return Err(AppError::validation("disarm before changing the frame"));
+            }
+            let option = vehicle_frames(&vehicle)?
+                .into_iter()
+                .find(|option| option.id == option_id)
+                .ok_or_else(|| AppError::validation(format!("unknown frame option {option_id}")))?;
+
+            let writes: Vec<_> = frame_param_writes(&option)
+                .into_iter()
+                .map(|(name, value)| (name, f64::from(value)))
+                .collect();
+            expect_param_writes(state.inner(), writes.iter().map(|(name, _)| name.as_str()));
+            let param_results = live_commands::param_write_batch(&vehicle, writes)
+                .await
+                .map_err(AppError::from)?;
+            let all_written = param_results.iter().all(|result| result.success);
+
+            if all_written && persist_params_by_default(vehicle.identity().autopilot) {
+                live_commands::param_commit_to_storage(&vehicle)
+                    .await
+                    .map_err(AppError::from)?;
+            }
+
+            let mut rebooted = false;
+            if reboot.unwrap_or(false) && all_written {
+                live_commands::reboot_vehicle(&vehicle)
+                    .await
+                    .map_err(AppError::from)?;
+                rebooted = true;
+            }
+
+            Ok(FrameApplyResult {
+                option_id: option.id,
+                param_results,
+                reboot_required: !rebooted,
+                rebooted,
+            })
+        },
+    )
+    .await
+}
@@ -0,0 +1,95 @@
+use ironwing_core::dialect::{COMMAND_LONG_DATA, MavMessage};
+use ironwing_core::event_names;
+use ironwing_core::gcs_commands::{
+    COMMAND_LONG_MESSAGE_ID, GcsCommandAction, classify_gcs_command, gcs_command_received,
+    gcs_command_replies,
+};
+use ironwing_core::live_runtime;
+use ironwing_core::live_runtime::commands as live_commands;
+use mavkit::Vehicle;
+use mavlink::MavlinkVersion;
+use tauri::Manager;
+
+use crate::AppState;
+use crate::gcs_position::{gcs_fix, time_boot_ms};
+
+/// Only Android has a location of its own.
+const DEVICE_LOCATION: bool = cfg!(target_os = "android");
+
+async fn send(vehicle: &Vehicle, messages: Vec<MavMessage>) {
+    for message in messages {
+        if let Err(error) = live_commands::send_raw_message(vehicle, message).await {
+            tracing::warn!("failed to answer a command sent to the GCS: {error}");
+        }
+    }
+}
+
+/// Answer MAV_CMD_REQUEST_MESSAGE for GLOBAL_POSITION_INT with the manual GCS
+/// position or this device's location.
+async fn answer_position_request(
+    handle: tauri::AppHandle,
+    vehicle: Vehicle,
+    command: COMMAND_LONG_DATA,
+    source: (u8, u8),
+) {
+    let position = gcs_fix(&handle).await;
+    let replies = gcs_command_replies(
+        &command,
+        source,
+        GcsCommandAction::SendPosition,
+        position.as_ref(),
+        DEVICE_LOCATION,
+        time_boot_ms(),
+    );
+    send(&vehicle, replies).await;
+}
+
+/// ACKs every COMMAND_LONG addressed to this GCS so senders stop retrying.
+/// HEARTBEAT and GLOBAL_POSITION_INT requests are answered; anything else is
+/// refused as unsupported and surfaced as `gcs://command_received`.
+pub(crate) async fn gcs_command_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let gcs = {
+        let state: tauri::State<'_, AppState> = handle.state();
+        *state.gcs_identity.lock().await
+    };
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        if raw_msg.message_id != COMMAND_LONG_MESSAGE_ID {
+            continue;
+        }
+        let Ok(MavMessage::COMMAND_LONG(command)) =
+            MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+        else {
+            continue;
+        };
+        let source = (raw_msg.system_id, raw_msg.component_id);
+        match classify_gcs_command(&command, gcs) {
+            GcsCommandAction::Ignore => {}
+            GcsCommandAction::SendPosition => {
+                // The device fix can take seconds; keep acknowledging others.
+                tokio::spawn(answer_position_request(
+                    handle.clone(),
+                    vehicle.clone(),
+                    command,
+                    source,
+                ));
+            }
+            action => {
+                let replies =
+                    gcs_command_replies(&command, source, action, None, DEVICE_LOCATION, 0);
+                send(&vehicle, replies).await;
+                if action == GcsCommandAction::Unsupported {
+                    let state: tauri::State<'_, AppState> = handle.state();
+                    live_runtime::emit_scoped(
+                        &state.live_runtime,
+                        event_names::GCS_COMMAND_RECEIVED,
+                        gcs_command_received(&command, source.0, source.1),
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use ironwing_core::gcs_commands::gcs_global_position_int;
+use ironwing_core::gcs_position::{gcs_time_boot_ms, manual_position, stream_period};
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::preflight_position::PositionFix;
+use tauri::Manager;
+use tokio::time::MissedTickBehavior;
+
+use crate::AppState;
+use crate::helpers::{ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{AppError, OperationId};
+
+/// The GCS position stream and where it gets positions from.
+#[derive(Default)]
+pub(crate) struct GcsPositionState {
+    task: Option<tokio::task::AbortHandle>,
+    manual: Option<PositionFix>,
+    fixes_sent: Arc<AtomicU64>,
+}
+
+impl GcsPositionState {
+    pub(crate) fn fixes_sent(&self) -> u64 {
+        self.fixes_sent.load(Ordering::Relaxed)
+    }
+}
+
+/// `time_boot_ms` for everything the GCS sends, counted from the first use.
+pub(crate) fn time_boot_ms() -> u32 {
+    static STARTED: OnceLock<Instant> = OnceLock::new();
+    gcs_time_boot_ms(STARTED.get_or_init(Instant::now).elapsed())
+}
+
+/// This device's location when it is accurate enough to send.
+#[cfg(target_os = "android")]
+async fn device_fix(app: &tauri::AppHandle) -> Option<PositionFix> {
+    use ironwing_core::gcs_position::fix_accurate_enough;
+    use tauri_plugin_geolocation::{GeolocationExt, PositionOptions};
+
+    let app = app.clone();
+    let position = tokio::task::spawn_blocking(move || {
+        app.geolocation()
+            .get_current_position(Some(PositionOptions {
+                enable_high_accuracy: true,
+                timeout: 5_000,
+                maximum_age: 1_000,
+            }))
+    })
+    .await
+    .ok()?;
+    match position {
+        Ok(position) if fix_accurate_enough(position.coords.accuracy) => Some(PositionFix {
+            latitude_deg: position.coords.latitude,
+            longitude_deg: position.coords.longitude,
+            altitude_msl_m: position.coords.altitude,
+        }),
+        Ok(position) => {
+            tracing::debug!(
+                "skipping GCS fix with {:.0} m accuracy",
+                position.coords.accuracy
+            );
+            None
+        }
+        Err(error) => {
+            tracing::debug!("no GCS fix: {error}");
+            None
+        }
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+async fn device_fix(_app: &tauri::AppHandle) -> Option<PositionFix> {
+    None
+}
+
+/// The manually entered position when there is one, otherwise the device's.
+pub(crate) async fn gcs_fix(app: &tauri::AppHandle) -> Option<PositionFix> {
+    let state: tauri::State<'_, AppState> = app.state();
+    let manual = state.gcs_position.lock().await.manual;
+    match manual {
+        Some(fix) => Some(fix),
+        None => device_fix(app).await,
+    }
+}
+
+async fn stream_gcs_position(
+    app: tauri::AppHandle,
+    vehicle: mavkit::Vehicle,
+    period: std::time::Duration,
+    fixes_sent: Arc<AtomicU64>,
+) {
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let Some(fix) = gcs_fix(&app).await else {
+            continue;
+        };
+        let message = gcs_global_position_int(&fix, time_boot_ms());
+        match live_commands::send_raw_message(&vehicle, message).await {
+            Ok(()) => {
+                fixes_sent.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(error) => tracing::warn!("failed to send GCS position: {error}"),
+        }
+    }
+}
+
+/// Stream GLOBAL_POSITION_INT for the operator's location at `rate_hz`, for
+/// ArduPilot's follow and return-to-GCS behaviour. Uses the manual position
+/// when one is set, otherwise the device location (Android only). Stops on
+/// disconnect.
+#[tauri::command]
+pub(crate) async fn gcs_position_stream_start(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    rate_hz: f64,
+) -> Result<(), AppError> {
+    ensure_live_write_allowed(state.inner(), OperationId::GcsPositionStreamStart).await?;
+    let period = stream_period(rate_hz).map_err(AppError::validation)?;
+    let vehicle = with_vehicle(&state).await?;
+
+    let mut gcs_position = state.gcs_position.lock().await;
+    if gcs_position.manual.is_none() && !cfg!(target_os = "android") {
+        return Err(AppError::validation(
+            "set a manual GCS position first; this device has no location",
+        ));
+    }
+    if let Some(task) = gcs_position.task.take() {
+        task.abort();
+    }
+    gcs_position.fixes_sent = Arc::new(AtomicU64::new(0));
+    let task = tokio::spawn(stream_gcs_position(
+        app.clone(),
+        vehicle,
+        period,
+        gcs_position.fixes_sent.clone(),
+    ));
+    gcs_position.task = Some(task.abort_handle());
+    drop(gcs_position);
+    // Owned with the link's other tasks so a disconnect stops it too.
+    state.background_tasks.lock().await.push(task);
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) async fn gcs_position_stream_stop(
+    state: tauri::State<'_, AppState>,
+) -> Result<(), AppError> {
+    if let Some(task) = state.gcs_position.lock().await.task.take() {
+        task.abort();
+    }
+    Ok(())
+}
+
+/// A fixed operator position for devices without a location of their own.
+#[tauri::command]
+pub(crate) async fn gcs_position_set_manual(
+    state: tauri::State<'_, AppState>,
+    lat: f64,
+    lon: f64,
+    alt: f64,
+) -> Result<(), AppError> {
+    state.gcs_position.lock().await.manual =
+        Some(manual_position(lat, lon, alt).map_err(AppError::validation)?);
+    Ok(())
+}
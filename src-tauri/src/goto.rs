@@ -0,0 +1,118 @@
+use ironwing_core::event_names;
+use ironwing_core::goto::{terrain_frame_supported, terrain_goto_request};
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::param_value::param_value;
+use serde_json::json;
+
+use crate::AppState;
+use crate::e2e_emit::emit_event;
+use crate::helpers::{ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{AppError, GotoRequest, GotoResult, GuidedTarget, OperationId, TerrainGotoResult};
+use crate::journal;
+
+/// Tell the map where the vehicle was just sent, so its marker follows what
+/// was commanded rather than where the user clicked.
+pub(crate) fn emit_guided_target(app: &tauri::AppHandle, target: GuidedTarget) {
+    emit_event(app, event_names::GUIDED_TARGET, &target);
+}
+
+/// Fly to a point in Guided with optional ground speed, arrival heading,
+/// acceptance radius and altitude frame.
+#[tauri::command]
+pub(crate) async fn vehicle_guided_goto_ex(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+    request: GotoRequest,
+) -> Result<GotoResult, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::VehicleGuidedGotoEx,
+        json!({ "request": request }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::VehicleGuidedGotoEx).await?;
+            let vehicle = with_vehicle(&state).await?;
+            let result = live_commands::guided_goto_with_options(&vehicle, &request).await?;
+            emit_guided_target(
+                &app,
+                GuidedTarget {
+                    latitude_deg: request.latitude_deg,
+                    longitude_deg: request.longitude_deg,
+                    altitude_m: request.altitude_m,
+                    options: request.options,
+                },
+            );
+            Ok(result)
+        },
+    )
+    .await
+}
+
+/// Fly to a point holding `agl_m` above the ground there.
+/// `ground_elevation_m` is the frontend's DEM sample at the target, metres
+/// AMSL. The terrain frame is used when the autopilot can follow terrain;
+/// otherwise the equivalent AMSL altitude is sent and a warning says the
+/// height only holds over the target.
+#[tauri::command]
+pub(crate) async fn vehicle_guided_goto_terrain(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+    latitude_deg: f64,
+    longitude_deg: f64,
+    agl_m: f32,
+    ground_elevation_m: f64,
+) -> Result<TerrainGotoResult, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::VehicleGuidedGotoTerrain,
+        json!({
+            "latitude_deg": latitude_deg,
+            "longitude_deg": longitude_deg,
+            "agl_m": agl_m,
+            "ground_elevation_m": ground_elevation_m,
+        }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::VehicleGuidedGotoTerrain).await?;
+            if !ground_elevation_m.is_finite() {
+                return Err(AppError::validation("no ground elevation for the target"));
+            }
+            let vehicle = with_vehicle(&state).await?;
+            let terrain_enable = live_commands::param_get_all(&vehicle)
+                .ok()
+                .and_then(|store| {
+                    store.params.get("TERRAIN_ENABLE").map(|param| {
+                        param_value(param, live_commands::param_encoding(&vehicle)).as_f64()
+                    })
+                });
+            let supported = terrain_frame_supported(vehicle.identity().autopilot, terrain_enable);
+            let (request, strategy, warnings) = terrain_goto_request(
+                latitude_deg,
+                longitude_deg,
+                agl_m,
+                ground_elevation_m,
+                supported,
+            );
+            for warning in &warnings {
+                tracing::warn!("terrain goto: {warning}");
+            }
+
+            let goto = live_commands::guided_goto_with_options(&vehicle, &request).await?;
+            emit_guided_target(
+                &app,
+                GuidedTarget {
+                    latitude_deg,
+                    longitude_deg,
+                    altitude_m: request.altitude_m,
+                    options: request.options,
+                },
+            );
+            Ok(TerrainGotoResult {
+                strategy,
+                ground_elevation_m,
+                altitude_m: request.altitude_m,
+                goto,
+                warnings,
+            })
+        },
+    )
+    .await
+}
@@ -0,0 +1,245 @@
+use ironwing_core::event_names;
+use ironwing_core::guided_queue::{
+    GuidedQueue, QueueStep, is_queue_mode, queue_acceptance_radius_m, queue_stop_reason,
+};
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::param_value::param_value;
+use mavkit::{LinkState, Vehicle};
+use serde_json::json;
+use tauri::Manager;
+use tokio_util::sync::CancellationToken;
+
+use crate::AppState;
+use crate::e2e_emit::emit_event;
+use crate::goto::emit_guided_target;
+use crate::helpers::{ensure_live_write_allowed, vehicle_is_armed, with_vehicle};
+use crate::ipc::{
+    AppError, GotoOptions, GotoRequest, GuidedQueuePoint, GuidedQueueStatus, GuidedTarget,
+    OperationId,
+};
+use crate::journal;
+
+/// The queue and the token of the task flying it.
+#[derive(Default)]
+pub(crate) struct GuidedQueueSlot {
+    queue: GuidedQueue,
+    cancel: Option<CancellationToken>,
+}
+
+/// Guided on ArduPilot; Hold or Offboard on PX4.
+fn in_guided(vehicle: &Vehicle) -> bool {
+    let autopilot = vehicle.identity().autopilot;
+    vehicle
+        .available_modes()
+        .current()
+        .latest()
+        .is_some_and(|mode| is_queue_mode(autopilot, &mode.name))
+}
+
+fn acceptance_radius_m(vehicle: &Vehicle) -> f64 {
+    let store = live_commands::param_get_all(vehicle).ok();
+    let encoding = live_commands::param_encoding(vehicle);
+    let identity = vehicle.identity();
+    queue_acceptance_radius_m(identity.autopilot, identity.vehicle_type, |name| {
+        let param = store.as_ref()?.params.get(name)?;
+        Some(param_value(param, encoding).as_f64() as f32)
+    })
+}
+
+fn link_connected(vehicle: &Vehicle) -> bool {
+    matches!(vehicle.link().state().latest(), Some(LinkState::Connected))
+}
+
+async fn goto_point(
+    app: &tauri::AppHandle,
+    vehicle: &Vehicle,
+    point: GuidedQueuePoint,
+) -> Result<(), AppError> {
+    let request = GotoRequest {
+        latitude_deg: point.latitude_deg,
+        longitude_deg: point.longitude_deg,
+        altitude_m: point.altitude_m,
+        options: GotoOptions::default(),
+    };
+    live_commands::guided_goto_with_options(vehicle, &request).await?;
+    emit_guided_target(
+        app,
+        GuidedTarget {
+            latitude_deg: request.latitude_deg,
+            longitude_deg: request.longitude_deg,
+            altitude_m: request.altitude_m,
+            options: request.options,
+        },
+    );
+    Ok(())
+}
+
+/// Stop the queue with `reason`, unless a newer run has replaced this one.
+async fn stop_run(app: &tauri::AppHandle, cancel: &CancellationToken, reason: &str) {
+    let state = app.state::<AppState>();
+    let mut slot = state.guided_queue.lock().await;
+    if cancel.is_cancelled() {
+        return;
+    }
+    cancel.cancel();
+    slot.queue.stop(reason);
+    slot.cancel = None;
+    tracing::info!("guided queue stopped: {reason}");
+    emit_event(
+        app,
+        event_names::GUIDED_QUEUE_PROGRESS,
+        &slot.queue.status(),
+    );
+}
+
+/// Follow position updates, sending the next goto as each point is reached.
+/// Leaving Guided, disarming or losing the link stops the queue.
+async fn run_queue(app: tauri::AppHandle, vehicle: Vehicle, cancel: CancellationToken) {
+    let mut positions = vehicle.telemetry().position().global().subscribe();
+    let mut modes = vehicle.available_modes().current().subscribe();
+    let mut armed = vehicle.telemetry().armed().subscribe();
+    let mut link = vehicle.link().state().subscribe();
+    loop {
+        let position = tokio::select! {
+            _ = cancel.cancelled() => return,
+            sample = positions.recv() => match sample {
+                Some(sample) => Some(sample.value),
+                None => {
+                    stop_run(&app, &cancel, "link lost").await;
+                    return;
+                }
+            },
+            _ = modes.recv() => None,
+            _ = armed.recv() => None,
+            _ = link.recv() => None,
+        };
+        if let Some(reason) = queue_stop_reason(
+            link_connected(&vehicle),
+            vehicle_is_armed(&vehicle),
+            in_guided(&vehicle),
+        ) {
+            stop_run(&app, &cancel, reason).await;
+            return;
+        }
+        let Some(position) = position else {
+            continue;
+        };
+
+        let state = app.state::<AppState>();
+        let mut slot = state.guided_queue.lock().await;
+        if cancel.is_cancelled() {
+            return;
+        }
+        let step = slot
+            .queue
+            .observe(position.latitude_deg, position.longitude_deg);
+        let status = slot.queue.status();
+        match step {
+            QueueStep::Hold => continue,
+            QueueStep::Finished => {
+                slot.cancel = None;
+                cancel.cancel();
+                emit_event(&app, event_names::GUIDED_QUEUE_PROGRESS, &status);
+                return;
+            }
+            QueueStep::Advance(point) => {
+                drop(slot);
+                emit_event(&app, event_names::GUIDED_QUEUE_PROGRESS, &status);
+                if let Err(error) = goto_point(&app, &vehicle, point).await {
+                    let reason = format!("goto to the next point failed: {error}");
+                    stop_run(&app, &cancel, &reason).await;
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Replace the queued points. Refused while a queue is being flown.
+#[tauri::command]
+pub(crate) async fn guided_queue_set(
+    state: tauri::State<'_, AppState>,
+    points: Vec<GuidedQueuePoint>,
+) -> Result<GuidedQueueStatus, AppError> {
+    let queue = GuidedQueue::new(points).map_err(AppError::validation)?;
+    let mut slot = state.guided_queue.lock().await;
+    if slot.queue.is_running() {
+        return Err(AppError::validation(
+            "stop the running guided queue before changing its points",
+        ));
+    }
+    slot.queue = queue;
+    Ok(slot.queue.status())
+}
+
+/// Fly the queued points in order. The vehicle must be armed and in Guided
+/// (Hold or Offboard on PX4); progress is sent on `guided_queue://progress`.
+#[tauri::command]
+pub(crate) async fn guided_queue_start(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<GuidedQueueStatus, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::GuidedQueueStart,
+        json!({}),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::GuidedQueueStart).await?;
+            let vehicle = with_vehicle(&state).await?;
+            if let Some(reason) =
+                queue_stop_reason(true, vehicle_is_armed(&vehicle), in_guided(&vehicle))
+            {
+                return Err(AppError::validation(format!(
+                    "cannot start the guided queue: {reason}"
+                )));
+            }
+
+            let mut slot = state.guided_queue.lock().await;
+            let first = slot
+                .queue
+                .start(acceptance_radius_m(&vehicle))
+                .map_err(AppError::validation)?;
+            if let Err(error) = goto_point(&app, &vehicle, first).await {
+                slot.queue
+                    .stop(format!("goto to the first point failed: {error}"));
+                return Err(error);
+            }
+            let cancel = CancellationToken::new();
+            slot.cancel = Some(cancel.clone());
+            let status = slot.queue.status();
+            drop(slot);
+
+            emit_event(&app, event_names::GUIDED_QUEUE_PROGRESS, &status);
+            tokio::spawn(run_queue(app.clone(), vehicle, cancel));
+            Ok(status)
+        },
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn guided_queue_status(
+    state: tauri::State<'_, AppState>,
+) -> Result<GuidedQueueStatus, AppError> {
+    Ok(state.guided_queue.lock().await.queue.status())
+}
+
+/// Stop advancing through the queue. The vehicle keeps its current target;
+/// its mode is left alone.
+#[tauri::command]
+pub(crate) async fn guided_queue_stop(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+) -> Result<GuidedQueueStatus, AppError> {
+    let mut slot = state.guided_queue.lock().await;
+    if let Some(cancel) = slot.cancel.take() {
+        cancel.cancel();
+        slot.queue.stop("stopped by the operator");
+        emit_event(
+            &app,
+            event_names::GUIDED_QUEUE_PROGRESS,
+            &slot.queue.status(),
+        );
+    }
+    Ok(slot.queue.status())
+}
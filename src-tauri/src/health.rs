@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use ironwing_core::dialect::MavMessage;
+use ironwing_core::event_names;
+use ironwing_core::health::{EKF_STATUS_REPORT_MESSAGE_ID, SYS_STATUS_MESSAGE_ID, ekf_status};
+use ironwing_core::live_runtime;
+use ironwing_core::vtol::{EXTENDED_SYS_STATE_MESSAGE_ID, vtol_status};
+use mavkit::Vehicle;
+use mavlink::MavlinkVersion;
+use tauri::Manager;
+
+use crate::AppState;
+use crate::ipc::{AppError, HealthReport};
+
+const HEALTH_EMIT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Feeds EKF_STATUS_REPORT, the SYS_STATUS drop rate and EXTENDED_SYS_STATE
+/// into the runtime and emits `vehicle://health` once a second, and
+/// `vehicle://vtol` whenever the VTOL or landed state changes.
+pub(crate) async fn health_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    let mut interval = tokio::time::interval(HEALTH_EMIT_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let state: tauri::State<'_, AppState> = handle.state();
+                let thresholds = state.settings.lock().await.health_thresholds;
+                let report = state
+                    .live_runtime
+                    .with_runtime(|runtime| runtime.vehicle_health(&thresholds));
+                live_runtime::emit_scoped(&state.live_runtime, event_names::VEHICLE_HEALTH, report);
+            }
+            raw_msg = raw_stream.next() => {
+                let Some(raw_msg) = raw_msg else {
+                    break;
+                };
+                if raw_msg.system_id != system_id
+                    || ![
+                        SYS_STATUS_MESSAGE_ID,
+                        EKF_STATUS_REPORT_MESSAGE_ID,
+                        EXTENDED_SYS_STATE_MESSAGE_ID,
+                    ]
+                    .contains(&raw_msg.message_id)
+                {
+                    continue;
+                }
+                let Ok(message) =
+                    MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+                else {
+                    continue;
+                };
+                let state: tauri::State<'_, AppState> = handle.state();
+                let vtol_changed = state.live_runtime.with_runtime(|runtime| match message {
+                    MavMessage::SYS_STATUS(data) => {
+                        runtime.observe_comm_drop_rate(data.drop_rate_comm);
+                        None
+                    }
+                    MavMessage::EKF_STATUS_REPORT(data) => {
+                        runtime.observe_ekf_status(ekf_status(&data));
+                        None
+                    }
+                    MavMessage::EXTENDED_SYS_STATE(data) => {
+                        let status = vtol_status(data.vtol_state as u8, data.landed_state as u8);
+                        runtime.observe_vtol_status(status).then_some(status)
+                    }
+                    _ => None,
+                });
+                if let Some(status) = vtol_changed {
+                    live_runtime::emit_scoped(&state.live_runtime, event_names::VEHICLE_VTOL, status);
+                }
+            }
+        }
+    }
+}
+
+/// Sensors, EKF, GPS, battery, link, failsafe parameters and heartbeat, each
+/// graded against the configured health thresholds.
+#[tauri::command]
+pub(crate) async fn vehicle_health(
+    state: tauri::State<'_, AppState>,
+) -> Result<HealthReport, AppError> {
+    let thresholds = state.settings.lock().await.health_thresholds;
+    Ok(state
+        .live_runtime
+        .with_runtime(|runtime| runtime.vehicle_health(&thresholds)))
+}
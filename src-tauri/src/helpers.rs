@@ -1,22 +1,52 @@
 use crate::AppState;
 use crate::ipc::{
-    OperationFailure, OperationId, Reason, ReasonKind, SourceKind, operation_failure_json,
+    AppError, OperationFailure, OperationId, Reason, ReasonKind, SourceKind, operation_failure_json,
 };
 use tokio::sync::MappedMutexGuard;
 
-pub(crate) async fn with_vehicle(state: &AppState) -> Result<mavkit::Vehicle, String> {
+pub(crate) async fn with_vehicle(state: &AppState) -> Result<mavkit::Vehicle, AppError> {
     state
         .live_runtime
         .with_runtime(|runtime| runtime.vehicle())
-        .ok_or_else(|| "not connected".to_string())
+        .ok_or_else(AppError::not_connected)
+}
+
+pub(crate) fn vehicle_is_armed(vehicle: &mavkit::Vehicle) -> bool {
+    vehicle
+        .telemetry()
+        .armed()
+        .latest()
+        .is_some_and(|sample| sample.value)
+}
+
+/// Features that send whatever they are given stay off until the user opts
+/// in through settings.
+pub(crate) async fn ensure_expert_mode(state: &AppState, feature: &str) -> Result<(), AppError> {
+    if state.settings.lock().await.expert_mode {
+        Ok(())
+    } else {
+        Err(AppError::validation(format!(
+            "expert mode is off; enable it in settings to use {feature}"
+        )))
+    }
 }
 
 pub(crate) async fn with_log_store(
     state: &AppState,
 ) -> Result<MappedMutexGuard<'_, crate::logs::LogStore>, String> {
-    let guard = state.log_store.lock().await;
-    tokio::sync::MutexGuard::try_map(guard, |opt| opt.as_mut())
-        .map_err(|_| "no log loaded".to_string())
+    with_open_log(state, None).await
+}
+
+/// The open log with `log_id`, or the most recently opened one.
+pub(crate) async fn with_open_log<'a>(
+    state: &'a AppState,
+    log_id: Option<&str>,
+) -> Result<MappedMutexGuard<'a, crate::logs::LogStore>, String> {
+    let guard = state.open_logs.lock().await;
+    guard.get(log_id)?;
+    Ok(tokio::sync::MutexGuard::map(guard, |logs| {
+        logs.get_mut(log_id).expect("log checked above")
+    }))
 }
 
 pub(crate) async fn ensure_live_write_allowed(
@@ -0,0 +1,46 @@
+use ironwing_core::event_names;
+use ironwing_core::home_moved::home_moved;
+use ironwing_core::live_runtime;
+use mavkit::Vehicle;
+use serde_json::json;
+use tauri::Manager;
+
+use crate::AppState;
+use crate::helpers::vehicle_is_armed;
+use crate::ipc::OperationId;
+use crate::journal;
+
+/// Watches HOME_POSITION and, when home shifts while the vehicle is armed,
+/// emits `home://moved` and records it in the operation journal: RTL will now
+/// return somewhere else. Changes while disarmed (the first fix, setting home
+/// on the ground) only update the reference.
+pub(crate) async fn home_moved_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    let mut previous = vehicle
+        .telemetry()
+        .home()
+        .latest()
+        .map(|sample| (sample.value.latitude_deg, sample.value.longitude_deg));
+    let mut subscription = vehicle.telemetry().home().subscribe();
+    while let Some(sample) = subscription.recv().await {
+        let home = (sample.value.latitude_deg, sample.value.longitude_deg);
+        let Some(old) = previous.replace(home) else {
+            continue;
+        };
+        if !vehicle_is_armed(&vehicle) {
+            continue;
+        }
+        let position = vehicle
+            .telemetry()
+            .position()
+            .global()
+            .latest()
+            .map(|sample| (sample.value.latitude_deg, sample.value.longitude_deg));
+        let Some(moved) = home_moved(old, home, position) else {
+            continue;
+        };
+        tracing::warn!("home moved {:.0} m while armed", moved.distance_m);
+        let state: tauri::State<'_, AppState> = handle.state();
+        journal::record_observed(&state.journal, OperationId::HomeMoved, json!(moved));
+        live_runtime::emit_scoped(&state.live_runtime, event_names::HOME_MOVED, moved);
+    }
+}
@@ -0,0 +1,169 @@
+//! Minimal HTTP/1.1 plumbing shared by the remote UI bridge and the local
+//! API: one request per connection, `connection: close`, no keep-alive.
+
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+const MAX_REQUEST_BYTES: usize = 1024 * 1024;
+/// A client that opens a connection and then stalls must not hold it (and
+/// its connection slot) forever.
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+pub(crate) struct HttpRequest {
+    pub(crate) method: String,
+    pub(crate) path: String,
+    headers: Vec<(String, String)>,
+    pub(crate) body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// The value of header `name`, compared case-insensitively.
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub(crate) async fn read_http_request(stream: &mut TcpStream) -> Result<HttpRequest, String> {
+    tokio::time::timeout(REQUEST_READ_TIMEOUT, read_request(stream))
+        .await
+        .map_err(|_| "timed out reading request".to_string())?
+}
+
+async fn read_request(stream: &mut TcpStream) -> Result<HttpRequest, String> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0_u8; 4096];
+
+    loop {
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|error| format!("failed reading request: {error}"))?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if buffer.len() > MAX_REQUEST_BYTES {
+            return Err("HTTP request is too large".to_string());
+        }
+        if header_end(&buffer).is_some() {
+            break;
+        }
+    }
+
+    let header_end = header_end(&buffer).ok_or_else(|| "missing HTTP headers".to_string())?;
+    let head = String::from_utf8_lossy(&buffer[..header_end]);
+    let mut lines = head.lines();
+    let request_line = lines
+        .next()
+        .ok_or_else(|| "missing request line".to_string())?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+    let content_length = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    let body_start = header_end + 4;
+    while buffer.len() < body_start + content_length {
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|error| format!("failed reading request body: {error}"))?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if buffer.len() > MAX_REQUEST_BYTES {
+            return Err("HTTP request is too large".to_string());
+        }
+    }
+
+    let body = buffer
+        .get(body_start..body_start + content_length)
+        .unwrap_or_default()
+        .to_vec();
+
+    Ok(HttpRequest {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+fn header_end(buffer: &[u8]) -> Option<usize> {
+    buffer.windows(4).position(|window| window == b"\r\n\r\n")
+}
+
+pub(crate) async fn write_json(
+    stream: &mut TcpStream,
+    status: u16,
+    value: Value,
+) -> Result<(), String> {
+    let body =
+        serde_json::to_vec(&value).map_err(|error| format!("serialize response: {error}"))?;
+    write_response(stream, status, "application/json", &body).await
+}
+
+pub(crate) async fn write_response(
+    stream: &mut TcpStream,
+    status: u16,
+    content_type: &str,
+    body: &[u8],
+) -> Result<(), String> {
+    let reason = match status {
+        200 => "OK",
+        204 => "No Content",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "OK",
+    };
+    let headers = format!(
+        "HTTP/1.1 {status} {reason}\r\ncontent-type: {content_type}\r\ncontent-length: {}\r\naccess-control-allow-origin: *\r\naccess-control-allow-methods: GET, POST, OPTIONS\r\naccess-control-allow-headers: content-type, authorization\r\nconnection: close\r\n\r\n",
+        body.len(),
+    );
+    stream
+        .write_all(headers.as_bytes())
+        .await
+        .map_err(|error| format!("write response headers: {error}"))?;
+    stream
+        .write_all(body)
+        .await
+        .map_err(|error| format!("write response body: {error}"))
+}
+
+/// Sends the headers that open a server-sent event stream; frames follow
+/// with [`write_sse_event`].
+pub(crate) async fn write_sse_headers(stream: &mut TcpStream) -> Result<(), String> {
+    let headers = "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ncache-control: no-cache\r\naccess-control-allow-origin: *\r\nconnection: keep-alive\r\n\r\n";
+    stream
+        .write_all(headers.as_bytes())
+        .await
+        .map_err(|error| format!("write SSE headers: {error}"))
+}
+
+pub(crate) async fn write_sse_event(
+    stream: &mut TcpStream,
+    event: &str,
+    data: &str,
+) -> Result<(), String> {
+    let frame = format!("event: {event}\ndata: {data}\n\n");
+    stream
+        .write_all(frame.as_bytes())
+        .await
+        .map_err(|error| format!("write SSE event: {error}"))
+}
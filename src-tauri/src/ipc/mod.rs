@@ -21,6 +21,9 @@ pub(crate) mod guided {
 pub(crate) mod logs {
     pub(crate) use ironwing_core::ipc::logs::*;
 }
+pub(crate) mod params {
+    pub(crate) use ironwing_core::ipc::params::*;
+}
 pub(crate) mod playback {
     pub(crate) use ironwing_core::ipc::playback::*;
 }
@@ -39,5 +42,8 @@ pub(crate) mod support {
 pub(crate) mod telemetry {
     pub(crate) use ironwing_core::ipc::telemetry::*;
 }
+pub(crate) mod vehicles {
+    pub(crate) use ironwing_core::ipc::vehicles::*;
+}
 
 pub(crate) use ironwing_core::ipc::*;
@@ -0,0 +1,262 @@
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, mpsc};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use ironwing_core::event_names;
+use ironwing_core::journal::{
+    JOURNAL_FILE_NAME, JOURNAL_ROTATED_FILE_NAME, encode_entry, parse_journal, push_bounded,
+    should_rotate,
+};
+use ironwing_core::time_sync::VehicleClock;
+use serde_json::Value;
+use tauri::Manager;
+
+use crate::AppState;
+use crate::e2e_emit::emit_event;
+use crate::ipc::{AppError, JournalEntry, JournalOutcome, OperationId};
+
+/// Append-only record of the commands sent to the vehicle.
+///
+/// Entries are handed to a writer thread so a slow or failing disk never
+/// holds up a command. Entries that cannot be written are kept in a bounded
+/// in-memory buffer instead, and the first failure raises one warning event.
+pub(crate) struct Journal {
+    sender: Mutex<Option<mpsc::Sender<JournalEntry>>>,
+    dir: Mutex<Option<PathBuf>>,
+    memory: Arc<Mutex<VecDeque<JournalEntry>>>,
+    vehicle_clock: Mutex<Option<VehicleClock>>,
+}
+
+impl Journal {
+    /// A journal that keeps entries in memory until [`Journal::open`] is called.
+    pub(crate) fn new() -> Self {
+        Self {
+            sender: Mutex::new(None),
+            dir: Mutex::new(None),
+            memory: Arc::new(Mutex::new(VecDeque::new())),
+            vehicle_clock: Mutex::new(None),
+        }
+    }
+
+    /// Start writing to the journal file in the app data directory.
+    pub(crate) fn open(&self, app: &tauri::AppHandle) {
+        let dir = match app.path().app_data_dir() {
+            Ok(dir) => dir,
+            Err(error) => {
+                warn_degraded(
+                    app,
+                    &format!("failed to resolve app-data directory: {error}"),
+                );
+                return;
+            }
+        };
+        let (sender, receiver) = mpsc::channel();
+        let writer = JournalWriter {
+            dir: dir.clone(),
+            file: None,
+            len: 0,
+        };
+        let memory = self.memory.clone();
+        let app = app.clone();
+        let spawned = std::thread::Builder::new()
+            .name("journal-writer".into())
+            .spawn(move || run_writer(app, writer, receiver, memory));
+        if let Err(error) = spawned {
+            tracing::warn!("operation journal kept in memory: {error}");
+            return;
+        }
+        *lock(&self.dir) = Some(dir);
+        *lock(&self.sender) = Some(sender);
+    }
+
+    /// Stamp later entries with the vehicle's clocks; `None` once the
+    /// vehicle is gone.
+    pub(crate) fn set_vehicle_clock(&self, clock: Option<VehicleClock>) {
+        *lock(&self.vehicle_clock) = clock;
+    }
+
+    fn append(&self, mut entry: JournalEntry) {
+        entry.vehicle_time = lock(&self.vehicle_clock)
+            .and_then(|clock| clock.vehicle_time(entry.timestamp_ms * 1000));
+        let unsent = match lock(&self.sender).as_ref() {
+            Some(sender) => sender.send(entry).err().map(|error| error.0),
+            None => Some(entry),
+        };
+        if let Some(entry) = unsent {
+            push_bounded(&mut lock(&self.memory), entry);
+        }
+    }
+
+    /// Entries issued within `start_ms..=end_ms`, oldest first, from the
+    /// journal files and from memory.
+    fn query(&self, start_ms: u64, end_ms: u64) -> Vec<JournalEntry> {
+        let mut entries = Vec::new();
+        if let Some(dir) = lock(&self.dir).clone() {
+            for file_name in [JOURNAL_ROTATED_FILE_NAME, JOURNAL_FILE_NAME] {
+                if let Ok(text) = std::fs::read_to_string(dir.join(file_name)) {
+                    entries.extend(parse_journal(&text, start_ms, end_ms));
+                }
+            }
+        }
+        entries.extend(
+            lock(&self.memory)
+                .iter()
+                .filter(|entry| (start_ms..=end_ms).contains(&entry.timestamp_ms))
+                .cloned(),
+        );
+        entries.sort_by_key(|entry| entry.timestamp_ms);
+        entries
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+struct JournalWriter {
+    dir: PathBuf,
+    file: Option<File>,
+    len: u64,
+}
+
+impl JournalWriter {
+    fn append(&mut self, entry: &JournalEntry) -> Result<(), String> {
+        let line = encode_entry(entry)?;
+        let path = self.dir.join(JOURNAL_FILE_NAME);
+        if self.file.is_none() {
+            std::fs::create_dir_all(&self.dir)
+                .map_err(|error| format!("failed to create app-data directory: {error}"))?;
+            self.open(&path)?;
+        }
+        if should_rotate(self.len, line.len()) {
+            self.file = None;
+            std::fs::rename(&path, self.dir.join(JOURNAL_ROTATED_FILE_NAME))
+                .map_err(|error| format!("failed to rotate operation journal: {error}"))?;
+            self.open(&path)?;
+        }
+        let Some(file) = self.file.as_mut() else {
+            return Err("operation journal is not open".to_string());
+        };
+        let written = file.write_all(&line).and_then(|()| file.sync_data());
+        if let Err(error) = written {
+            self.file = None;
+            return Err(format!("failed to write operation journal: {error}"));
+        }
+        self.len += line.len() as u64;
+        Ok(())
+    }
+
+    fn open(&mut self, path: &Path) -> Result<(), String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|error| format!("failed to open operation journal: {error}"))?;
+        self.len = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+        self.file = Some(file);
+        Ok(())
+    }
+}
+
+fn run_writer(
+    app: tauri::AppHandle,
+    mut writer: JournalWriter,
+    receiver: mpsc::Receiver<JournalEntry>,
+    memory: Arc<Mutex<VecDeque<JournalEntry>>>,
+) {
+    let mut warned = false;
+    for entry in receiver {
+        if let Err(error) = writer.append(&entry) {
+            push_bounded(&mut lock(&memory), entry);
+            if !warned {
+                warned = true;
+                warn_degraded(&app, &error);
+            }
+        }
+    }
+}
+
+fn warn_degraded(app: &tauri::AppHandle, error: &str) {
+    let message = format!("operation journal is being kept in memory only: {error}");
+    tracing::warn!("{message}");
+    emit_event(app, event_names::JOURNAL_WARNING, &message);
+}
+
+fn unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Run a vehicle command and journal the request together with its result.
+/// The command's result is returned unchanged.
+pub(crate) async fn record<T, E, F>(
+    journal: &Journal,
+    operation_id: OperationId,
+    request: Value,
+    operation: F,
+) -> Result<T, E>
+where
+    T: serde::Serialize,
+    E: std::fmt::Display,
+    F: Future<Output = Result<T, E>>,
+{
+    let timestamp_ms = unix_ms();
+    let started = Instant::now();
+    let result = operation.await;
+    let outcome = match &result {
+        Ok(value) => JournalOutcome::Ok {
+            result: serde_json::to_value(value).unwrap_or(Value::Null),
+        },
+        Err(error) => JournalOutcome::Err {
+            message: error.to_string(),
+        },
+    };
+    journal.append(JournalEntry {
+        timestamp_ms,
+        duration_ms: started.elapsed().as_millis() as u64,
+        operation_id,
+        request,
+        outcome,
+        vehicle_time: None,
+    });
+    result
+}
+
+/// Journal something the vehicle did on its own, such as a parameter
+/// changed by another GCS, alongside the commands this GCS sent.
+pub(crate) fn record_observed(journal: &Journal, operation_id: OperationId, request: Value) {
+    journal.append(JournalEntry {
+        timestamp_ms: unix_ms(),
+        duration_ms: 0,
+        operation_id,
+        request,
+        outcome: JournalOutcome::Ok {
+            result: Value::Null,
+        },
+        vehicle_time: None,
+    });
+}
+
+/// Journal entries for commands issued between `start_ms` and `end_ms`
+/// (Unix milliseconds, inclusive), oldest first.
+#[tauri::command]
+pub(crate) async fn journal_query(
+    app: tauri::AppHandle,
+    start_ms: u64,
+    end_ms: u64,
+) -> Result<Vec<JournalEntry>, AppError> {
+    tokio::task::spawn_blocking(move || {
+        let state = app.state::<AppState>();
+        state.journal.query(start_ms, end_ms)
+    })
+    .await
+    .map_err(|error| AppError::internal(format!("journal query failed: {error}")))
+}
@@ -1,17 +1,31 @@
+use alerts::{alerts_get_rules, alerts_reset_rules, alerts_set_rules};
 use analytics::{analytics_status, analytics_track_event};
 use bluetooth::{bt_get_bonded_devices, bt_request_permissions, bt_scan_ble, bt_stop_scan_ble};
+use checklist::{checklist_check, checklist_define, checklist_reset, checklist_state};
 use commands::{
-    ack_session_snapshot, arm_vehicle, available_transports, calibrate_accel,
+    ack_session_snapshot, arm_vehicle, available_transports, battery_stats, calibrate_accel,
     calibrate_compass_accept, calibrate_compass_cancel, calibrate_compass_start, calibrate_gyro,
-    disarm_vehicle, fence_clear, fence_download, fence_upload, get_available_message_rates,
-    get_available_modes, mission_cancel, mission_clear, mission_download, mission_set_current,
-    mission_upload, mission_validate, motor_test, open_session_snapshot, param_cancel,
-    param_download_all, param_format_file, param_parse_file, param_write, param_write_batch,
-    rally_clear, rally_download, rally_upload, rc_override, reboot_vehicle, request_prearm_checks,
-    runtime_capabilities, set_flight_mode, set_message_rate, set_servo, set_telemetry_rate,
-    start_guided_session, stop_guided_session, update_guided_session, vehicle_takeoff,
+    disarm_vehicle, event_bridge_stats, fence_clear, fence_download, fence_upload,
+    flight_mode_current, flightmode_assign, flightmode_channel_config, get_available_message_rates,
+    get_available_modes, get_current_link_state, get_current_telemetry, get_current_vehicle_state,
+    gps_status, mission_cancel, mission_clear, mission_download, mission_prepare_resume,
+    mission_set_current, mission_upload, mission_validate, motor_test, open_session_snapshot,
+    param_cancel, param_commit_to_storage, param_download_all, param_export, param_format_file,
+    param_get_all, param_get_bits, param_parse_file, param_parse_file_ex, param_search,
+    param_set_bits, param_set_metadata, param_write, param_write_batch, rally_clear,
+    rally_download, rally_upload, rc_link_quality, rc_override, reboot_vehicle,
+    request_prearm_checks, rtl_preview, runtime_capabilities, set_event_rates, set_flight_mode,
+    set_flight_mode_by_name, set_link_profile, set_message_rate, set_servo, set_telemetry_encoding,
+    set_telemetry_rate, set_unit_system, start_guided_session, statustext_history,
+    stop_guided_session, update_guided_session, vehicle_list, vehicle_select, vehicle_takeoff,
 };
-use connection::{ActiveLinkTarget, connect_link, disconnect_link};
+use component_commands::{camera_trigger, components_list, gimbal_set_angles};
+use connection::{
+    ActiveLinkTarget, connect_keep_waiting, connect_link, connect_mock, connection_info,
+    disconnect_link, link_sources, set_gcs_identity, set_link_source_filter,
+};
+use diagnostics_bundle::diagnostics_export;
+use disconnect_action::disconnect_link_ex;
 use firmware::commands::{
     firmware_bootloader_installation, firmware_detect_bootloader_board, firmware_install_update,
     firmware_install_update_preflight, firmware_install_update_readiness,
@@ -20,40 +34,142 @@ use firmware::commands::{
 };
 use firmware::discovery::firmware_list_dfu_devices;
 use firmware::types::FirmwareSessionHandle;
+use flight_summaries::flight_summaries;
+use flight_track::{track_clear, track_get};
+use frames::{frame_apply, frame_options};
+use gcs_position::{gcs_position_set_manual, gcs_position_stream_start, gcs_position_stream_stop};
+use goto::{vehicle_guided_goto_ex, vehicle_guided_goto_terrain};
+use guided_queue::{guided_queue_set, guided_queue_start, guided_queue_status, guided_queue_stop};
+use health::vehicle_health;
 use ipc::GuidedRuntime;
 use ironwing_core::live_runtime::{LiveVehicleRuntime, SharedLiveRuntime};
+use ironwing_core::open_logs::OpenLogs;
+use journal::journal_query;
+use link_loss::{gcs_failsafe_test, set_expected_failsafe_check};
+use local_api::{local_api_start, local_api_stop};
 use log_library::{
     log_library_cancel, log_library_list, log_library_register, log_library_register_open_file,
     log_library_reindex, log_library_relink, log_library_remove,
 };
 use logs::{LogOperationState, LogStore, PlaybackRuntimeState};
+use map_prefetch::{map_prefetch, map_prefetch_cancel};
+use mavlink_console::{send_command_long, send_raw_message};
+use mission_edit::{
+    mission_convert_frame, mission_insert_waypoint, mission_reverse, mission_scale_altitudes,
+    mission_translate,
+};
+use named_values::{named_values, tunnel_send};
+use orbit::{vehicle_orbit, vehicle_orbit_stop};
+use param_changes::param_external_changes;
+use param_tune::{param_tune_end, param_tune_revert, param_tune_session, param_tune_set};
+use payload::{gripper_control, winch_control};
+use pending_connect::pending_connect_take;
+use plane::{plane_launch_info, plane_loiter_here, plane_set_cruise_throttle, plane_takeoff};
+use precision_landing::precision_landing_status;
+use preflight::preflight_position_check;
+use rc_override::{rc_override_set, rc_override_start, rc_override_stop};
 use recording::{
     TlogRecorderHandle, recording_settings_read, recording_settings_write, recording_start,
     recording_status, recording_stop,
 };
 use remote_ui::RemoteUiEvent;
-use serial_ports::list_serial_port_inventory;
+use rover::{rover_hold, rover_set_reverse};
+use safety_gates::set_safety_gates;
+use serial_ports::{list_serial_port_inventory, set_auto_connect_usb};
+use session_export::session_export;
+use settings::{settings_get, settings_update};
+use takeoff::takeoff_guided;
+use task_watchdog::diagnostics;
 use tauri::Manager;
 use tauri_event_sink::TauriEventSink;
+use time_sync::time_sync_status;
+use tune_capture::{tune_capture_start, tune_capture_stop, tune_report_export};
+use usb_serial::list_usb_devices;
+use vehicle_files::{firmware_stage, script_delete, script_upload, scripts_list};
+use video::video_streams;
+use vtol::{vtol_status, vtol_transition};
+mod alerts;
 mod analytics;
+mod background_keepalive;
 mod bluetooth;
 mod bridges;
+mod calibration;
+mod checklist;
 mod commands;
+mod component_commands;
 mod connection;
+mod diagnostic_log;
+mod diagnostics_bundle;
+mod disconnect_action;
 mod e2e_emit;
 #[allow(dead_code)]
 // Firmware module is conditionally used via Tauri commands; not all paths are exercised in all builds
+mod fence_proximity;
 mod firmware;
+mod flight_summaries;
+mod flight_track;
+mod frames;
+mod gcs_commands;
+mod gcs_position;
+mod goto;
 mod guided;
+mod guided_queue;
+mod health;
 mod helpers;
+mod home_moved;
+mod http;
 mod ipc;
+mod journal;
+mod link_loss;
+mod link_outbox;
+mod link_teardown;
+mod local_api;
+mod log_follow;
 mod log_library;
 mod logs;
+mod map_prefetch;
+mod mavftp;
+mod mavlink_console;
+mod mission_edit;
+#[cfg(test)]
+mod mock_link;
+mod named_values;
+mod net_link;
+mod orbit;
+mod param_changes;
+mod param_tune;
+mod payload;
+mod pending_connect;
+mod plane;
+mod precision_landing;
+mod preflight;
+mod rc_override;
 mod recording;
 mod remote_ui;
+mod rover;
+mod safety_gates;
+#[cfg(not(target_os = "android"))]
+mod serial_hotplug;
+#[cfg(not(target_os = "android"))]
+mod serial_link;
 mod serial_ports;
+mod session_export;
+mod session_recovery;
 mod session_runtime;
+mod settings;
+mod system_resume;
+mod takeoff;
+mod task_watchdog;
 mod tauri_event_sink;
+mod time_sync;
+mod transport_drain;
+mod tune_capture;
+mod unknown_messages;
+mod usb_serial;
+mod vehicle_files;
+mod video;
+mod vtol;
+mod zip_stream;
 
 pub(crate) type MissionCancelToken = tokio_util::sync::CancellationToken;
 
@@ -66,10 +182,22 @@ pub(crate) struct AppState {
     pub(crate) live_runtime: SharedLiveRuntime<TauriEventSink>,
     pub(crate) active_link_target: tokio::sync::Mutex<Option<ActiveLinkTarget>>,
     pub(crate) demo_vehicle: tokio::sync::Mutex<Option<mavkit::sim::DemoVehicleHandle>>,
+    pub(crate) gcs_identity: tokio::sync::Mutex<ipc::GcsIdentity>,
+    pub(crate) link_source_filter: tokio::sync::Mutex<Vec<ipc::LinkSource>>,
+    /// Sources of the system picked with `vehicle_select`; overrides the link
+    /// source filter until the link is disconnected.
+    pub(crate) vehicle_selection: tokio::sync::Mutex<Option<Vec<ipc::LinkSource>>>,
+    pub(crate) link_outbox: link_outbox::LinkOutbox,
+    pub(crate) command_link: component_commands::CommandLink,
+    /// Baud rate for USB auto-connect; `None` while it is off.
+    pub(crate) auto_connect_usb: tokio::sync::Mutex<Option<u32>>,
     pub(crate) connect_abort: tokio::sync::Mutex<Option<tokio::task::AbortHandle>>,
+    /// A connect attempt past its heartbeat timeout with the transport still
+    /// open, kept until the user chooses to keep waiting or gives up.
+    pub(crate) parked_connect: tokio::sync::Mutex<Option<connection::ConnectAttempt>>,
     pub(crate) background_tasks: tokio::sync::Mutex<Vec<tokio::task::JoinHandle<()>>>,
-    pub(crate) background_listeners: tokio::sync::Mutex<Vec<tauri::EventId>>,
-    pub(crate) log_store: tokio::sync::Mutex<Option<LogStore>>,
+    pub(crate) link_teardown: tokio::sync::Mutex<Option<link_teardown::LinkTeardown>>,
+    pub(crate) open_logs: tokio::sync::Mutex<OpenLogs>,
     pub(crate) cached_library_store: tokio::sync::Mutex<Option<LogStore>>,
     pub(crate) log_operation: LogOperationState,
     pub(crate) playback_runtime: PlaybackRuntimeState,
@@ -79,8 +207,27 @@ pub(crate) struct AppState {
     pub(crate) firmware_cancel_requested: std::sync::Arc<std::sync::atomic::AtomicBool>,
     pub(crate) param_download_abort: tokio::sync::Mutex<Option<tokio::task::AbortHandle>>,
     pub(crate) mission_op_cancel: tokio::sync::Mutex<Option<MissionCancelToken>>,
+    pub(crate) map_prefetch_cancel: tokio::sync::Mutex<Option<tokio_util::sync::CancellationToken>>,
     pub(crate) guided_runtime: tokio::sync::Mutex<GuidedRuntime>,
+    pub(crate) guided_queue: tokio::sync::Mutex<guided_queue::GuidedQueueSlot>,
+    pub(crate) orbit: tokio::sync::Mutex<Option<orbit::ActiveOrbit>>,
+    /// Stops the turn count of a `plane_loiter_here` with turns.
+    pub(crate) plane_loiter: tokio::sync::Mutex<Option<tokio_util::sync::CancellationToken>>,
+    pub(crate) gcs_position: tokio::sync::Mutex<gcs_position::GcsPositionState>,
+    pub(crate) rc_override: tokio::sync::Mutex<rc_override::RcOverrideState>,
+    pub(crate) settings: tokio::sync::Mutex<ipc::Settings>,
+    pub(crate) journal: journal::Journal,
     pub(crate) remote_ui_events: tokio::sync::broadcast::Sender<RemoteUiEvent>,
+    pub(crate) local_api: tokio::sync::Mutex<local_api::LocalApiState>,
+    pub(crate) log_follow: tokio::sync::Mutex<Option<log_follow::ActiveLogFollow>>,
+    pub(crate) session_recovery: tokio::sync::Mutex<Option<ipc::SessionRecoverInfo>>,
+    pub(crate) background_keepalive: tokio::sync::Mutex<background_keepalive::KeepaliveState>,
+    pub(crate) task_watchdog: task_watchdog::TaskWatchdog,
+    /// A `--connect` or deep-link connection the frontend has not taken yet.
+    pub(crate) pending_connect: tokio::sync::Mutex<Option<ipc::PendingConnect>>,
+    /// The parameter being tuned from a slider, if any.
+    pub(crate) param_tune: tokio::sync::Mutex<Option<param_tune::TuneSession>>,
+    pub(crate) tune_capture: tokio::sync::Mutex<Option<tune_capture::ActiveTuneCapture>>,
 }
 
 fn ble_plugin_enabled() -> bool {
@@ -97,10 +244,17 @@ pub fn run() {
         live_runtime: SharedLiveRuntime::new(LiveVehicleRuntime::new(tauri_event_sink.clone())),
         active_link_target: tokio::sync::Mutex::new(None),
         demo_vehicle: tokio::sync::Mutex::new(None),
+        gcs_identity: tokio::sync::Mutex::new(ipc::GcsIdentity::default()),
+        link_source_filter: tokio::sync::Mutex::new(Vec::new()),
+        vehicle_selection: tokio::sync::Mutex::new(None),
+        link_outbox: link_outbox::LinkOutbox::default(),
+        command_link: component_commands::CommandLink::default(),
+        auto_connect_usb: tokio::sync::Mutex::new(None),
         connect_abort: tokio::sync::Mutex::new(None),
+        parked_connect: tokio::sync::Mutex::new(None),
         background_tasks: tokio::sync::Mutex::new(Vec::new()),
-        background_listeners: tokio::sync::Mutex::new(Vec::new()),
-        log_store: tokio::sync::Mutex::new(None),
+        link_teardown: tokio::sync::Mutex::new(None),
+        open_logs: tokio::sync::Mutex::new(OpenLogs::new()),
         cached_library_store: tokio::sync::Mutex::new(None),
         log_operation: LogOperationState::new(),
         playback_runtime: PlaybackRuntimeState::new(),
@@ -110,8 +264,26 @@ pub fn run() {
         firmware_cancel_requested: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
         param_download_abort: tokio::sync::Mutex::new(None),
         mission_op_cancel: tokio::sync::Mutex::new(None),
+        map_prefetch_cancel: tokio::sync::Mutex::new(None),
         guided_runtime: tokio::sync::Mutex::new(GuidedRuntime::default()),
+        guided_queue: tokio::sync::Mutex::new(guided_queue::GuidedQueueSlot::default()),
+        orbit: tokio::sync::Mutex::new(None),
+        plane_loiter: tokio::sync::Mutex::new(None),
+        gcs_position: tokio::sync::Mutex::new(gcs_position::GcsPositionState::default()),
+        rc_override: tokio::sync::Mutex::new(rc_override::RcOverrideState::default()),
+        settings: tokio::sync::Mutex::new(ipc::Settings::default()),
+        journal: journal::Journal::new(),
         remote_ui_events: remote_ui::event_channel(),
+        local_api: tokio::sync::Mutex::new(local_api::LocalApiState::default()),
+        log_follow: tokio::sync::Mutex::new(None),
+        session_recovery: tokio::sync::Mutex::new(None),
+        background_keepalive: tokio::sync::Mutex::new(
+            background_keepalive::KeepaliveState::default(),
+        ),
+        task_watchdog: task_watchdog::TaskWatchdog::default(),
+        pending_connect: tokio::sync::Mutex::new(None),
+        param_tune: tokio::sync::Mutex::new(None),
+        tune_capture: tokio::sync::Mutex::new(None),
     };
     let mut builder = tauri::Builder::default()
         .manage(state)
@@ -133,14 +305,25 @@ pub fn run() {
     {
         builder = builder
             .plugin(tauri_plugin_bluetooth_classic::init())
-            .plugin(tauri_plugin_geolocation::init());
+            .plugin(tauri_plugin_usb_serial::init())
+            .plugin(tauri_plugin_geolocation::init())
+            .plugin(tauri_plugin_deep_link::init());
     }
     builder = builder.invoke_handler(tauri::generate_handler![
+        connect_keep_waiting,
         connect_link,
+        connect_mock,
+        connection_info,
         disconnect_link,
+        set_gcs_identity,
+        gcs_failsafe_test,
+        set_link_source_filter,
+        link_sources,
         analytics_status,
         analytics_track_event,
         list_serial_port_inventory,
+        set_auto_connect_usb,
+        list_usb_devices,
         available_transports,
         runtime_capabilities,
         bt_request_permissions,
@@ -151,6 +334,7 @@ pub fn run() {
         mission_upload,
         mission_download,
         mission_clear,
+        mission_prepare_resume,
         mission_set_current,
         mission_cancel,
         fence_upload,
@@ -163,18 +347,127 @@ pub fn run() {
         disarm_vehicle,
         set_flight_mode,
         vehicle_takeoff,
+        statustext_history,
+        gps_status,
+        battery_stats,
+        rc_link_quality,
+        vehicle_list,
+        vehicle_select,
+        components_list,
+        camera_trigger,
+        gimbal_set_angles,
+        vehicle_guided_goto_ex,
+        vehicle_guided_goto_terrain,
+        guided_queue_set,
+        guided_queue_start,
+        guided_queue_status,
+        guided_queue_stop,
+        vehicle_orbit,
+        vehicle_orbit_stop,
+        plane_launch_info,
+        plane_takeoff,
+        plane_loiter_here,
+        plane_set_cruise_throttle,
+        vtol_status,
+        vtol_transition,
+        rover_hold,
+        rover_set_reverse,
+        frame_options,
+        frame_apply,
+        settings_get,
+        settings_update,
+        journal_query,
+        set_expected_failsafe_check,
+        set_safety_gates,
+        checklist_define,
+        checklist_state,
+        checklist_check,
+        checklist_reset,
+        session_export,
+        pending_connect_take,
+        param_tune_session,
+        param_tune_set,
+        param_tune_revert,
+        param_tune_end,
+        tune_capture_start,
+        tune_capture_stop,
+        tune_report_export,
+        diagnostics_export,
+        map_prefetch,
+        map_prefetch_cancel,
+        crate::session_recovery::session_recover_info,
+        crate::background_keepalive::background_keepalive_start,
+        crate::background_keepalive::background_keepalive_stop,
+        send_command_long,
+        send_raw_message,
+        gcs_position_stream_start,
+        gcs_position_stream_stop,
+        gcs_position_set_manual,
+        scripts_list,
+        script_upload,
+        script_delete,
+        firmware_stage,
+        param_external_changes,
+        time_sync_status,
+        precision_landing_status,
+        gripper_control,
+        winch_control,
+        vehicle_health,
+        rc_override_start,
+        rc_override_set,
+        rc_override_stop,
+        mission_insert_waypoint,
+        mission_reverse,
+        mission_translate,
+        mission_scale_altitudes,
+        mission_convert_frame,
+        set_flight_mode_by_name,
+        flight_mode_current,
+        local_api_start,
+        local_api_stop,
+        disconnect_link_ex,
+        set_telemetry_encoding,
+        takeoff_guided,
+        preflight_position_check,
         start_guided_session,
         update_guided_session,
         stop_guided_session,
         get_available_modes,
+        flightmode_channel_config,
+        flightmode_assign,
         get_available_message_rates,
+        get_current_telemetry,
+        get_current_vehicle_state,
+        get_current_link_state,
+        set_link_profile,
         set_message_rate,
         set_telemetry_rate,
+        set_unit_system,
+        set_event_rates,
+        event_bridge_stats,
+        diagnostics,
+        alerts_get_rules,
+        alerts_set_rules,
+        alerts_reset_rules,
+        flight_summaries,
+        track_get,
+        track_clear,
+        named_values,
+        video_streams,
+        tunnel_send,
         param_download_all,
         param_write,
         param_write_batch,
+        param_get_bits,
+        param_set_bits,
+        param_commit_to_storage,
         param_parse_file,
+        param_parse_file_ex,
         param_format_file,
+        param_export,
+        param_get_all,
+        param_search,
+        param_set_metadata,
         param_cancel,
         calibrate_accel,
         calibrate_gyro,
@@ -186,6 +479,7 @@ pub fn run() {
         set_servo,
         rc_override,
         request_prearm_checks,
+        rtl_preview,
         log_library_list,
         log_library_register,
         log_library_register_open_file,
@@ -202,8 +496,16 @@ pub fn run() {
         crate::logs::log_get_flight_path,
         crate::logs::log_get_telemetry_track,
         crate::logs::log_get_flight_summary,
+        crate::logs::log_repair_timestamps,
+        crate::logs::log_analyze,
+        crate::logs::log_extract_params,
+        crate::logs::log_statustext,
+        crate::logs::log_vtol_events,
+        crate::logs::log_list_types,
+        crate::logs::log_query_compare,
         crate::logs::log_export_csv,
         crate::logs::log_close,
+        crate::log_follow::log_follow,
         crate::logs::playback_play,
         crate::logs::playback_pause,
         crate::logs::playback_seek,
@@ -230,10 +532,21 @@ pub fn run() {
 
     builder
         .setup(|_app| {
+            diagnostic_log::install(_app.handle());
             let state = _app.state::<AppState>();
             state
                 .live_runtime
                 .with_runtime(|runtime| runtime.event_sink().set_handle(_app.handle().clone()));
+            tauri::async_runtime::block_on(settings::load_saved_settings(_app.handle()));
+            tauri::async_runtime::block_on(pending_connect::capture_launch_argument(_app.handle()));
+            #[cfg(target_os = "android")]
+            pending_connect::register_deep_links(_app.handle());
+            state.journal.open(_app.handle());
+            flight_summaries::install_flight_summary_history(_app.handle());
+            flight_track::install_flight_track(_app.handle());
+            #[cfg(not(target_os = "android"))]
+            serial_hotplug::spawn_serial_port_watcher(_app.handle().clone());
+            system_resume::spawn_resume_watcher(_app.handle().clone());
 
             #[cfg(desktop)]
             {
@@ -247,6 +560,12 @@ pub fn run() {
             }
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri app");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri app")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                let state: tauri::State<'_, AppState> = app.state();
+                tauri::async_runtime::block_on(local_api::stop_local_api(&state));
+            }
+        });
 }
@@ -0,0 +1,138 @@
+use std::time::Instant;
+
+use ironwing_core::event_names;
+use ironwing_core::link_loss::{
+    failsafe_check_warning, gcs_failsafe_behavior, gcs_failsafe_test_duration, is_link_loss,
+};
+use ironwing_core::live_runtime::commands as live_commands;
+use mavkit::{LinkState, Vehicle};
+
+use crate::AppState;
+use crate::e2e_emit::emit_event;
+use crate::helpers::with_vehicle;
+use crate::ipc::{AppError, GcsFailsafeBehavior, LinkLostInFlight, SettingsPatch};
+
+/// The vehicle's GCS failsafe as configured in the downloaded parameters.
+pub(crate) fn gcs_failsafe(vehicle: &Vehicle) -> GcsFailsafeBehavior {
+    let store = live_commands::param_get_all(vehicle).ok();
+    gcs_failsafe_behavior(vehicle.identity().vehicle_type, |name| {
+        store
+            .as_ref()
+            .and_then(|store| store.params.get(name))
+            .map(|param| param.value)
+    })
+}
+
+/// Emit `link://failsafe_warning` when the check is enabled and losing the
+/// GCS would leave the vehicle unattended. Returns the warning.
+pub(crate) async fn check_expected_failsafe(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    vehicle: &Vehicle,
+) -> Option<String> {
+    if !state.settings.lock().await.expected_failsafe_check {
+        return None;
+    }
+    let warning = failsafe_check_warning(&gcs_failsafe(vehicle))?;
+    emit_event(app, event_names::LINK_FAILSAFE_WARNING, &warning);
+    Some(warning)
+}
+
+fn report_lost_in_flight(app: &tauri::AppHandle, vehicle: &Vehicle, last_packet: Instant) {
+    let position = vehicle
+        .telemetry()
+        .position()
+        .global()
+        .latest()
+        .map(|sample| sample.value);
+    let lost = LinkLostInFlight {
+        latitude_deg: position.as_ref().map(|position| position.latitude_deg),
+        longitude_deg: position.as_ref().map(|position| position.longitude_deg),
+        altitude_msl_m: position.as_ref().map(|position| position.altitude_msl_m),
+        since_last_packet_ms: last_packet.elapsed().as_millis() as u64,
+        failsafe: gcs_failsafe(vehicle),
+    };
+    tracing::warn!(
+        "link lost while armed; vehicle failsafe: {}",
+        lost.failsafe.action
+    );
+    emit_event(app, event_names::LINK_LOST_IN_FLIGHT, &lost);
+}
+
+/// Watch for the heartbeat timing out while the vehicle was last known to be
+/// armed, timing the silence from the last packet of any kind.
+pub(crate) async fn link_loss_watcher(app: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    let mut raw_open = true;
+    let mut link_sub = vehicle.link().state().subscribe();
+    let mut previous = vehicle
+        .link()
+        .state()
+        .latest()
+        .unwrap_or(LinkState::Connecting);
+    let mut last_packet = Instant::now();
+    loop {
+        tokio::select! {
+            message = raw_stream.next(), if raw_open => match message {
+                Some(_) => last_packet = Instant::now(),
+                None => raw_open = false,
+            },
+            link_state = link_sub.recv() => {
+                let Some(link_state) = link_state else {
+                    return;
+                };
+                let armed = vehicle
+                    .telemetry()
+                    .armed()
+                    .latest()
+                    .is_some_and(|sample| sample.value);
+                if armed && is_link_loss(&previous, &link_state) {
+                    report_lost_in_flight(&app, &vehicle, last_packet);
+                }
+                previous = link_state;
+            }
+        }
+    }
+}
+
+/// Turn the pre-arm warning for a do-nothing GCS failsafe on or off. Returns
+/// the warning for the connected vehicle, if any.
+#[tauri::command]
+pub(crate) async fn set_expected_failsafe_check(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<Option<String>, AppError> {
+    let patch = SettingsPatch {
+        expected_failsafe_check: Some(enabled),
+        ..SettingsPatch::default()
+    };
+    crate::settings::update_settings(&app, &state, patch).await?;
+    let Some(vehicle) = state.live_runtime.with_runtime(|runtime| runtime.vehicle()) else {
+        return Ok(None);
+    };
+    Ok(check_expected_failsafe(&app, &state, &vehicle).await)
+}
+
+/// Stop sending our HEARTBEAT for `seconds` (at most 30) so the vehicle's
+/// GCS failsafe can be checked on the bench. It resumes by itself; zero
+/// resumes it early.
+#[tauri::command]
+pub(crate) async fn gcs_failsafe_test(
+    state: tauri::State<'_, AppState>,
+    seconds: u32,
+) -> Result<(), AppError> {
+    let duration = gcs_failsafe_test_duration(seconds).map_err(AppError::validation)?;
+    with_vehicle(&state).await?;
+    state
+        .link_outbox
+        .pause_heartbeats(duration)
+        .map_err(AppError::validation)?;
+    if !duration.is_zero() {
+        tracing::warn!("GCS heartbeat paused for {seconds} s to test the failsafe");
+    }
+    Ok(())
+}
@@ -0,0 +1,161 @@
+//! The outgoing side of a bridged link, between MAVKit's writer and the
+//! transport. MAVKit owns the heartbeat timer and only sends messages its
+//! dialect can encode, so both the `gcs_failsafe_test` heartbeat pause and
+//! frames IronWing encodes itself go through here. Every frame is renumbered
+//! on the way out, so the two share one sequence counter rather than
+//! repeating each other's numbers. Links whose byte stream MAVKit owns have
+//! no outbox.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use ironwing_core::mavlink_frames::{resequenced, without_heartbeats};
+use tokio::sync::mpsc;
+
+/// Frames queued by IronWing itself ahead of the transport.
+const INJECTED_FRAMES: usize = 16;
+
+#[derive(Default)]
+struct OutboxState {
+    injected_tx: Option<mpsc::Sender<Vec<u8>>>,
+    paused_until: Option<Instant>,
+    sequence: u8,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct LinkOutbox(Arc<Mutex<OutboxState>>);
+
+impl LinkOutbox {
+    fn lock(&self) -> MutexGuard<'_, OutboxState> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Forget the previous link: nothing attached and no pause running.
+    pub(crate) fn reset(&self) {
+        *self.lock() = OutboxState::default();
+    }
+
+    fn heartbeats_paused(&self) -> bool {
+        self.lock()
+            .paused_until
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    /// Hold back our HEARTBEAT for `duration`; zero resumes it now.
+    pub(crate) fn pause_heartbeats(&self, duration: Duration) -> Result<(), String> {
+        let mut outbox = self.lock();
+        if outbox.injected_tx.is_none() {
+            return Err(
+                "this link's heartbeat is written by MAVKit directly and cannot be paused".into(),
+            );
+        }
+        outbox.paused_until = (!duration.is_zero()).then(|| Instant::now() + duration);
+        Ok(())
+    }
+
+    /// Sequence number for the next frame out on the link.
+    fn next_sequence(&self) -> u8 {
+        let mut outbox = self.lock();
+        outbox.sequence = outbox.sequence.wrapping_add(1);
+        outbox.sequence
+    }
+
+    /// Write already-encoded MAVLink frames to the link, in order. Their
+    /// sequence numbers are replaced with the link's own.
+    pub(crate) async fn send_frames(&self, frames: Vec<Vec<u8>>) -> Result<(), String> {
+        let Some(injected_tx) = self.lock().injected_tx.clone() else {
+            return Err(
+                "this link is written by MAVKit directly and cannot send raw frames".into(),
+            );
+        };
+        for frame in frames {
+            injected_tx
+                .send(frame)
+                .await
+                .map_err(|_| "the link closed while sending".to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Pass a bridge's outgoing frames through the outbox. The forwarding
+    /// task ends when MAVKit drops its writer.
+    pub(crate) fn attach(
+        &self,
+        mut outgoing_rx: mpsc::Receiver<Vec<u8>>,
+    ) -> mpsc::Receiver<Vec<u8>> {
+        let (injected_tx, mut injected_rx) = mpsc::channel(INJECTED_FRAMES);
+        self.lock().injected_tx = Some(injected_tx);
+        let (link_tx, link_rx) = mpsc::channel(1);
+        let outbox = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let mut data = tokio::select! {
+                    data = outgoing_rx.recv() => match data {
+                        Some(data) => data,
+                        None => return,
+                    },
+                    Some(frame) = injected_rx.recv() => frame,
+                };
+                if outbox.heartbeats_paused() {
+                    match without_heartbeats(&data) {
+                        Some(rest) if rest.is_empty() => continue,
+                        Some(rest) => data = rest,
+                        None => {}
+                    }
+                }
+                if let Some(renumbered) = resequenced(&data, || outbox.next_sequence()) {
+                    data = renumbered;
+                }
+                if link_tx.send(data).await.is_err() {
+                    return;
+                }
+            }
+        });
+        link_rx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironwing_core::dialect::{ATTITUDE_DATA, MavMessage};
+    use ironwing_core::mavlink_frames::FrameScanner;
+    use mavlink::MavHeader;
+
+    fn attitude(sequence: u8) -> Vec<u8> {
+        let header = MavHeader {
+            sequence,
+            ..MavHeader::default()
+        };
+        let mut frame = Vec::new();
+        mavlink::write_v2_msg(&mut frame, header, &MavMessage::ATTITUDE(ATTITUDE_DATA::default()))
+            .expect("write attitude");
+        frame
+    }
+
+    #[tokio::test]
+    async fn injected_frames_share_the_links_sequence_counter() {
+        let outbox = LinkOutbox::default();
+        let (mavkit_tx, mavkit_rx) = mpsc::channel(4);
+        let mut link_rx = outbox.attach(mavkit_rx);
+
+        // MAVKit counts from its own zero; the injected frames were encoded
+        // with a number of their own too.
+        let mut sent = Vec::new();
+        mavkit_tx.send(attitude(0)).await.expect("mavkit frame");
+        sent.push(link_rx.recv().await.expect("first frame"));
+        outbox.send_frames(vec![attitude(0), attitude(1)]).await.expect("injected frames");
+        sent.push(link_rx.recv().await.expect("second frame"));
+        sent.push(link_rx.recv().await.expect("third frame"));
+        mavkit_tx.send(attitude(1)).await.expect("mavkit frame");
+        sent.push(link_rx.recv().await.expect("fourth frame"));
+
+        let sequences: Vec<u8> = sent.iter().map(|frame| frame[4]).collect();
+        assert_eq!(sequences, [1, 2, 3, 4]);
+        for frame in &sent {
+            assert_eq!(FrameScanner::new().push(frame), *frame);
+        }
+    }
+}
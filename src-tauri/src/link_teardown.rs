@@ -0,0 +1,228 @@
+use std::sync::{Arc, Mutex};
+
+use ironwing_core::mavlink_frames::FrameScanner;
+use tauri::Listener;
+#[cfg(target_os = "android")]
+use tauri::Manager;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::ipc::{LinkFrameStats, LinkSource, TransportStats};
+use crate::transport_drain::DrainStats;
+
+struct FeedState {
+    sender: Option<mpsc::Sender<Vec<u8>>>,
+    scanner: FrameScanner,
+}
+
+/// Incoming side of a transport bridge, shared with the plugin callbacks that
+/// feed it. Bytes pass through a [`FrameScanner`] so only checksummed frames
+/// reach MAVKit. Closing it drops the channel sender even when a callback
+/// outlives the link, so no late bytes reach the reader.
+#[derive(Clone)]
+pub(crate) struct BridgeFeed(Arc<Mutex<FeedState>>);
+
+impl BridgeFeed {
+    pub(crate) fn new(sender: mpsc::Sender<Vec<u8>>, source_filter: Vec<LinkSource>) -> Self {
+        Self(Arc::new(Mutex::new(FeedState {
+            sender: Some(sender),
+            scanner: FrameScanner::with_source_filter(source_filter),
+        })))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, FeedState> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn push(&self, data: Vec<u8>) {
+        let mut state = self.lock();
+        if state.sender.is_none() {
+            return;
+        }
+        let frames = state.scanner.push(&data);
+        crate::unknown_messages::publish(state.scanner.take_unknown());
+        if let Some(sender) = state.sender.as_ref()
+            && !frames.is_empty()
+        {
+            let _ = sender.try_send(frames);
+        }
+    }
+
+    pub(crate) fn frame_stats(&self) -> LinkFrameStats {
+        self.lock().scanner.stats()
+    }
+
+    fn close(&self) {
+        self.lock().sender.take();
+    }
+}
+
+/// The plugin-side state a link holds open.
+pub(crate) enum TransportPlugin {
+    Ble {
+        notify_characteristic: uuid::Uuid,
+    },
+    #[cfg(target_os = "android")]
+    Spp,
+    #[cfg(target_os = "android")]
+    UsbSerial,
+}
+
+/// Everything a byte-stream transport leaves running for its link: the bridge
+/// feed, the outgoing drain task, event listeners and the plugin connection.
+#[derive(Default)]
+pub(crate) struct LinkTeardown {
+    feed: Option<BridgeFeed>,
+    drain_task: Option<JoinHandle<()>>,
+    drain_stats: Option<DrainStats>,
+    listeners: Vec<tauri::EventId>,
+    plugin: Option<TransportPlugin>,
+}
+
+impl LinkTeardown {
+    pub(crate) fn new(
+        feed: BridgeFeed,
+        drain_task: JoinHandle<()>,
+        plugin: TransportPlugin,
+    ) -> Self {
+        Self {
+            feed: Some(feed),
+            drain_task: Some(drain_task),
+            drain_stats: None,
+            listeners: Vec::new(),
+            plugin: Some(plugin),
+        }
+    }
+
+    pub(crate) fn frame_stats(&self) -> Option<LinkFrameStats> {
+        self.feed.as_ref().map(BridgeFeed::frame_stats)
+    }
+
+    pub(crate) fn transport_stats(&self) -> Option<TransportStats> {
+        self.drain_stats.as_ref().map(DrainStats::snapshot)
+    }
+
+    pub(crate) fn with_drain_stats(mut self, drain_stats: DrainStats) -> Self {
+        self.drain_stats = Some(drain_stats);
+        self
+    }
+
+    pub(crate) fn with_listeners(mut self, listeners: Vec<tauri::EventId>) -> Self {
+        self.listeners = listeners;
+        self
+    }
+
+    /// Stop bytes flowing in both directions. Done first so nothing from the
+    /// old device can reach a link opened while the plugin is still closing.
+    fn cut_bridge(&mut self) {
+        if let Some(feed) = self.feed.take() {
+            feed.close();
+        }
+        if let Some(task) = self.drain_task.take() {
+            task.abort();
+        }
+    }
+
+    pub(crate) async fn run(mut self, app: &tauri::AppHandle) {
+        self.cut_bridge();
+        for listener in self.listeners.drain(..) {
+            app.unlisten(listener);
+        }
+        match self.plugin.take() {
+            Some(TransportPlugin::Ble {
+                notify_characteristic,
+            }) => teardown_ble(notify_characteristic).await,
+            #[cfg(target_os = "android")]
+            Some(TransportPlugin::Spp) => {
+                let bt: tauri::State<
+                    '_,
+                    tauri_plugin_bluetooth_classic::BluetoothClassic<tauri::Wry>,
+                > = app.state();
+                if let Err(error) = bt.disconnect() {
+                    tracing::warn!("SPP disconnect failed during teardown: {error}");
+                }
+            }
+            #[cfg(target_os = "android")]
+            Some(TransportPlugin::UsbSerial) => {
+                let usb: tauri::State<'_, tauri_plugin_usb_serial::UsbSerial<tauri::Wry>> =
+                    app.state();
+                if let Err(error) = usb.disconnect() {
+                    tracing::warn!("USB serial disconnect failed during teardown: {error}");
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+async fn teardown_ble(notify_characteristic: uuid::Uuid) {
+    let handler = match tauri_plugin_blec::get_handler() {
+        Ok(handler) => handler,
+        Err(error) => {
+            tracing::debug!("BLE plugin was not initialized during BLE teardown: {error}");
+            return;
+        }
+    };
+
+    if let Err(error) = handler.unsubscribe(notify_characteristic).await {
+        tracing::debug!("BLE unsubscribe failed during teardown: {error}");
+    }
+    match handler.disconnect().await {
+        Ok(()) | Err(tauri_plugin_blec::Error::NoDeviceConnected) => {}
+        Err(error) => tracing::warn!("BLE disconnect failed during teardown: {error}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironwing_core::dialect::{HEARTBEAT_DATA, MavMessage};
+    use mavkit::stream::ChannelBridge;
+    use std::time::Duration;
+    use tokio::io::AsyncReadExt;
+
+    #[tokio::test]
+    async fn no_bytes_flow_after_the_bridge_is_cut() {
+        let ChannelBridge {
+            mut reader,
+            writer: _writer,
+            incoming_tx,
+            outgoing_rx: _outgoing_rx,
+        } = ChannelBridge::new(8);
+        let feed = BridgeFeed::new(incoming_tx, Vec::new());
+        // Stands in for the plugin callback that keeps its own clone.
+        let subscription = feed.clone();
+        let drain_task = tokio::spawn(std::future::pending::<()>());
+        let mut teardown = LinkTeardown {
+            feed: Some(feed),
+            drain_task: Some(drain_task),
+            ..LinkTeardown::default()
+        };
+
+        let mut frame = Vec::new();
+        mavlink::write_v2_msg(
+            &mut frame,
+            mavlink::MavHeader::default(),
+            &MavMessage::HEARTBEAT(HEARTBEAT_DATA::default()),
+        )
+        .expect("write heartbeat");
+
+        subscription.push(frame.clone());
+        let mut live = vec![0u8; frame.len()];
+        reader.read_exact(&mut live).await.expect("read live frame");
+        assert_eq!(live, frame);
+
+        teardown.cut_bridge();
+        subscription.push(frame.clone());
+        assert_eq!(teardown.frame_stats(), None);
+
+        let mut buf = [0u8; 16];
+        let read = tokio::time::timeout(Duration::from_millis(50), reader.read(&mut buf)).await;
+        assert!(
+            matches!(read, Err(_) | Ok(Ok(0))),
+            "stale bytes reached the reader: {read:?}"
+        );
+    }
+}
@@ -0,0 +1,237 @@
+use std::sync::Arc;
+
+use ironwing_core::event_names;
+use ironwing_core::local_api::{
+    bearer_token_matches, local_api_address, request_route, validate_local_api_token,
+};
+use ironwing_core::track_export::track_geojson;
+use serde_json::json;
+use tauri::Manager;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+
+use crate::AppState;
+use crate::commands;
+use crate::http::{
+    read_http_request, write_json, write_response, write_sse_event, write_sse_headers,
+};
+use crate::ipc::{AppError, LocalApiStatus, ScopedEvent, TelemetrySnapshot};
+
+/// The running local API server, if any.
+#[derive(Default)]
+pub(crate) struct LocalApiState {
+    task: Option<tokio::task::AbortHandle>,
+    address: Option<String>,
+    stop_on_disconnect: bool,
+}
+
+impl LocalApiState {
+    fn status(&self) -> LocalApiStatus {
+        let running = self.task.as_ref().is_some_and(|task| !task.is_finished());
+        LocalApiStatus {
+            running,
+            address: self.address.clone().filter(|_| running),
+            stop_on_disconnect: running && self.stop_on_disconnect,
+        }
+    }
+
+    fn stop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.address = None;
+        self.stop_on_disconnect = false;
+    }
+}
+
+/// Serve read-only telemetry, vehicle state and the flight track over HTTP
+/// for external tools. Every request must carry `Authorization: Bearer
+/// <token>`. The server listens on 127.0.0.1 unless `bind_all` is set.
+#[tauri::command]
+pub(crate) async fn local_api_start(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    port: u16,
+    token: String,
+    bind_all: Option<bool>,
+    stop_on_disconnect: Option<bool>,
+) -> Result<LocalApiStatus, AppError> {
+    validate_local_api_token(&token).map_err(AppError::validation)?;
+    let mut local_api = state.local_api.lock().await;
+    if let Some(address) = local_api.status().address {
+        return Err(AppError::validation(format!(
+            "local API is already running at {address}"
+        )));
+    }
+    let addr = local_api_address(port, bind_all.unwrap_or(false));
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|error| match error.kind() {
+            std::io::ErrorKind::AddrInUse => {
+                AppError::validation(format!("port {port} is already in use"))
+            }
+            _ => AppError::internal(format!("failed to bind local API at {addr}: {error}")),
+        })?;
+    let address = listener
+        .local_addr()
+        .map_or_else(|_| addr.to_string(), |bound| bound.to_string());
+    tracing::info!("local API listening at http://{address}");
+
+    let token: Arc<str> = token.into();
+    let task = tokio::spawn(serve_local_api(app, listener, token));
+    *local_api = LocalApiState {
+        task: Some(task.abort_handle()),
+        address: Some(address),
+        stop_on_disconnect: stop_on_disconnect.unwrap_or(false),
+    };
+    Ok(local_api.status())
+}
+
+#[tauri::command]
+pub(crate) async fn local_api_stop(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    stop_local_api(&state).await;
+    Ok(())
+}
+
+pub(crate) async fn stop_local_api(state: &AppState) {
+    state.local_api.lock().await.stop();
+}
+
+/// Called on link teardown; stops the server only if it was started with
+/// `stop_on_disconnect`.
+pub(crate) async fn stop_local_api_on_disconnect(state: &AppState) {
+    let mut local_api = state.local_api.lock().await;
+    if local_api.stop_on_disconnect {
+        local_api.stop();
+    }
+}
+
+/// Open event streams count against this too, so a handful of stuck clients
+/// cannot exhaust the app's file descriptors.
+const MAX_CONNECTIONS: usize = 16;
+
+/// Connections live in a `JoinSet` owned by this task, so aborting the server
+/// also closes open event streams.
+async fn serve_local_api(app: tauri::AppHandle, listener: TcpListener, token: Arc<str>) {
+    let mut connections = tokio::task::JoinSet::new();
+    loop {
+        let mut stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(error) => {
+                tracing::warn!("local API stopped: accept failed: {error}");
+                return;
+            }
+        };
+        while connections.try_join_next().is_some() {}
+        if connections.len() >= MAX_CONNECTIONS {
+            let _ = write_json(
+                &mut stream,
+                503,
+                json!({ "ok": false, "error": "too many connections" }),
+            )
+            .await;
+            continue;
+        }
+        let app = app.clone();
+        let token = token.clone();
+        connections.spawn(async move {
+            if let Err(error) = handle_connection(app, stream, &token).await {
+                tracing::debug!("local API request failed: {error}");
+            }
+        });
+    }
+}
+
+async fn handle_connection(
+    app: tauri::AppHandle,
+    mut stream: TcpStream,
+    token: &str,
+) -> Result<(), String> {
+    let request = read_http_request(&mut stream).await?;
+    if request.method == "OPTIONS" {
+        return write_response(&mut stream, 204, "text/plain", b"").await;
+    }
+    if !bearer_token_matches(request.header("authorization"), token) {
+        return write_json(
+            &mut stream,
+            401,
+            json!({ "ok": false, "error": "missing or invalid bearer token" }),
+        )
+        .await;
+    }
+
+    let state: tauri::State<'_, AppState> = app.state();
+    match (request.method.as_str(), request_route(&request.path)) {
+        ("GET", "/telemetry") => {
+            let telemetry = commands::get_current_telemetry(state, Some(true));
+            write_json(&mut stream, 200, json!(telemetry)).await
+        }
+        ("GET", "/state") => {
+            let vehicle_state = commands::get_current_vehicle_state(state);
+            write_json(&mut stream, 200, json!(vehicle_state)).await
+        }
+        ("GET", "/track.geojson") => {
+            let track = state
+                .live_runtime
+                .with_runtime(|runtime| runtime.flight_track().since(None, None));
+            let body = serde_json::to_vec(&track_geojson(&track))
+                .map_err(|error| format!("serialize track: {error}"))?;
+            write_response(&mut stream, 200, "application/geo+json", &body).await
+        }
+        ("GET", "/events") => stream_telemetry(&app, stream).await,
+        _ => {
+            write_json(
+                &mut stream,
+                404,
+                json!({ "ok": false, "error": "not found" }),
+            )
+            .await
+        }
+    }
+}
+
+/// Opens with the current snapshot as a `telemetry` event, then forwards each
+/// telemetry tick as a whole snapshot until the client goes away, whatever
+/// encoding the webview selected.
+async fn stream_telemetry(app: &tauri::AppHandle, mut stream: TcpStream) -> Result<(), String> {
+    write_sse_headers(&mut stream).await?;
+
+    let state: tauri::State<'_, AppState> = app.state();
+    // Subscribe before taking the snapshot so no tick falls between them.
+    let mut receiver = state.remote_ui_events.subscribe();
+    if let Some(snapshot) = current_telemetry_event(&state) {
+        let data = serde_json::to_string(&snapshot)
+            .map_err(|error| format!("serialize SSE event: {error}"))?;
+        write_sse_event(&mut stream, "telemetry", &data).await?;
+    }
+    loop {
+        let event = match receiver.recv().await {
+            Ok(event) => event,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return Ok(()),
+        };
+        if event.name() != event_names::TELEMETRY_STATE {
+            continue;
+        }
+        let data = serde_json::to_string(event.payload())
+            .map_err(|error| format!("serialize SSE event: {error}"))?;
+        write_sse_event(&mut stream, "telemetry", &data).await?;
+    }
+}
+
+/// The snapshot a new event-stream client starts from, in the same scoped
+/// shape and display units as the `telemetry` ticks that follow; `None`
+/// while no session is live.
+fn current_telemetry_event(state: &AppState) -> Option<ScopedEvent<TelemetrySnapshot>> {
+    state.live_runtime.with_runtime(|runtime| {
+        let envelope = runtime.current_stream_envelope(web_time::Instant::now())?;
+        let telemetry = match runtime.vehicle() {
+            Some(vehicle) => runtime.current_telemetry(&vehicle),
+            None => runtime.live_telemetry().clone(),
+        };
+        Some(ScopedEvent {
+            envelope,
+            value: runtime.telemetry_in_display_units(telemetry),
+        })
+    })
+}
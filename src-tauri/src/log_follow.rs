@@ -0,0 +1,175 @@
+use std::io::SeekFrom;
+use std::time::Instant;
+
+use ironwing_core::event_names;
+use ironwing_core::log_engine::{LogType, parse_tlog_chunk};
+use ironwing_core::log_follow::{LOG_FOLLOW_INTERVAL, LogFollowIdle};
+use tauri::Manager;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio::task::AbortHandle;
+
+use crate::AppState;
+use crate::e2e_emit::emit_event;
+use crate::ipc::{AppError, LogAppended};
+
+/// The open log being followed and the task re-reading its tail.
+pub(crate) struct ActiveLogFollow {
+    log_id: String,
+    task: AbortHandle,
+}
+
+/// Follow an open tlog that is still being written, or stop following.
+/// Every 2 s the bytes past the last complete frame are parsed and appended
+/// to the log, announced on `log://appended`. Following stops by itself once
+/// the file has not grown for 30 s or the log is closed.
+#[tauri::command]
+pub(crate) async fn log_follow(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+    enabled: bool,
+    log_id: Option<String>,
+) -> Result<(), AppError> {
+    let mut active = state.log_follow.lock().await;
+    if let Some(previous) = active.take() {
+        previous.task.abort();
+    }
+    if !enabled {
+        return Ok(());
+    }
+    let (log_id, path) = {
+        let logs = state.open_logs.lock().await;
+        let store = logs.get(log_id.as_deref())?;
+        if store.summary().log_type != LogType::Tlog || store.tlog_parsed_bytes.is_none() {
+            return Err(AppError::validation("only tlogs can be followed"));
+        }
+        let log_id = store
+            .summary()
+            .log_id
+            .clone()
+            .ok_or("log has no id to follow")?;
+        (log_id, store.source_path().to_string())
+    };
+    let task = tokio::spawn(follow_log(app, log_id.clone(), path));
+    *active = Some(ActiveLogFollow {
+        log_id,
+        task: task.abort_handle(),
+    });
+    Ok(())
+}
+
+async fn follow_log(app: tauri::AppHandle, log_id: String, path: String) {
+    let state: tauri::State<'_, AppState> = app.state();
+    let len = file_len(&path).await.unwrap_or(0);
+    let mut idle = LogFollowIdle::new(len, Instant::now());
+    let mut interval = tokio::time::interval(LOG_FOLLOW_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        let len = match file_len(&path).await {
+            Ok(len) => len,
+            Err(error) => {
+                tracing::warn!("stopped following {path}: {error}");
+                break;
+            }
+        };
+        if !idle.observe(len, Instant::now()) {
+            break;
+        }
+        let position = state
+            .open_logs
+            .lock()
+            .await
+            .get(Some(&log_id))
+            .ok()
+            .map(|store| {
+                (
+                    store.tlog_parsed_bytes.unwrap_or(0),
+                    store.entries().len() as u64,
+                )
+            });
+        // The log was closed or reopened under a new id.
+        let Some((offset, next_sequence)) = position else {
+            break;
+        };
+        if len <= offset {
+            continue;
+        }
+        let chunk = match read_tail(&path, offset)
+            .await
+            .and_then(|tail| parse_tlog_chunk(&tail, next_sequence))
+        {
+            Ok(chunk) => chunk,
+            Err(error) => {
+                tracing::warn!("stopped following {path}: {error}");
+                break;
+            }
+        };
+        if chunk.entries.is_empty() {
+            continue;
+        }
+        let new_entries = chunk.entries.len() as u64;
+        let mut logs = state.open_logs.lock().await;
+        let Ok(store) = logs.get_mut(Some(&log_id)) else {
+            break;
+        };
+        store.append_tlog_chunk(chunk);
+        let total_entries = store.summary().total_entries as u64;
+        drop(logs);
+        emit_event(
+            &app,
+            event_names::LOG_APPENDED,
+            &LogAppended {
+                log_id: log_id.clone(),
+                new_entries,
+                total_entries,
+                following: true,
+            },
+        );
+    }
+
+    let mut active = state.log_follow.lock().await;
+    if active
+        .as_ref()
+        .is_some_and(|follow| follow.log_id == log_id)
+    {
+        active.take();
+    }
+    drop(active);
+    let total_entries = state
+        .open_logs
+        .lock()
+        .await
+        .get(Some(&log_id))
+        .map_or(0, |store| store.summary().total_entries as u64);
+    emit_event(
+        &app,
+        event_names::LOG_APPENDED,
+        &LogAppended {
+            log_id,
+            new_entries: 0,
+            total_entries,
+            following: false,
+        },
+    );
+}
+
+async fn file_len(path: &str) -> std::io::Result<u64> {
+    tokio::fs::metadata(path)
+        .await
+        .map(|metadata| metadata.len())
+}
+
+async fn read_tail(path: &str, offset: u64) -> Result<Vec<u8>, String> {
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .map_err(|error| format!("failed to open TLOG: {error}"))?;
+    file.seek(SeekFrom::Start(offset))
+        .await
+        .map_err(|error| format!("failed to seek TLOG: {error}"))?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail)
+        .await
+        .map_err(|error| format!("failed to read TLOG: {error}"))?;
+    Ok(tail)
+}
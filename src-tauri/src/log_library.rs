@@ -8,7 +8,7 @@ use tauri::Manager;
 use tauri_plugin_dialog::DialogExt;
 use tracing::warn;
 
-use crate::ipc::OperationId;
+use crate::ipc::{AppError, OperationId};
 use crate::ipc::logs::{
     LOG_LIBRARY_CATALOG_SCHEMA_VERSION, LogCatalogMigrationError, LogDiagnostic,
     LogDiagnosticSeverity, LogDiagnosticSource, LogFormat, LogIndexReference, LogLibraryCatalog,
@@ -430,7 +430,7 @@ async fn pick_log_library_path(app: &tauri::AppHandle) -> Result<Option<String>,
     let (tx, rx) = tokio::sync::oneshot::channel();
     app.dialog()
         .file()
-        .add_filter("Telemetry logs", &["tlog", "bin"])
+        .add_filter("Telemetry logs", &["tlog", "bin", "ulg"])
         .pick_file(move |file_path| {
             let _ = tx.send(file_path);
         });
@@ -449,15 +449,15 @@ async fn pick_log_library_path(app: &tauri::AppHandle) -> Result<Option<String>,
 }
 
 #[tauri::command]
-pub(crate) async fn log_library_list(app: tauri::AppHandle) -> Result<LogLibraryCatalog, String> {
-    library_from_app(&app)?.list()
+pub(crate) async fn log_library_list(app: tauri::AppHandle) -> Result<LogLibraryCatalog, AppError> {
+    library_from_app(&app)?.list().map_err(AppError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn log_library_register(
     app: tauri::AppHandle,
     path: String,
-) -> Result<LogLibraryEntry, String> {
+) -> Result<LogLibraryEntry, AppError> {
     let library = library_from_app(&app)?;
     let state: tauri::State<'_, crate::AppState> = app.state();
     run_log_operation(
@@ -467,12 +467,13 @@ pub(crate) async fn log_library_register(
         move |reporter| async move { library.register(path, &reporter).await },
     )
     .await
+    .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn log_library_register_open_file(
     app: tauri::AppHandle,
-) -> Result<Option<LogLibraryEntry>, String> {
+) -> Result<Option<LogLibraryEntry>, AppError> {
     let Some(path) = pick_log_library_path(&app).await? else {
         return Ok(None);
     };
@@ -487,14 +488,17 @@ pub(crate) async fn log_library_register_open_file(
     )
     .await
     .map(Some)
+    .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn log_library_remove(
     app: tauri::AppHandle,
     entry_id: String,
-) -> Result<LogLibraryCatalog, String> {
-    library_from_app(&app)?.remove(&entry_id)
+) -> Result<LogLibraryCatalog, AppError> {
+    library_from_app(&app)?
+        .remove(&entry_id)
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -502,7 +506,7 @@ pub(crate) async fn log_library_relink(
     app: tauri::AppHandle,
     entry_id: String,
     path: String,
-) -> Result<LogLibraryEntry, String> {
+) -> Result<LogLibraryEntry, AppError> {
     let library = library_from_app(&app)?;
     let state: tauri::State<'_, crate::AppState> = app.state();
     run_log_operation(
@@ -512,13 +516,14 @@ pub(crate) async fn log_library_relink(
         move |reporter| async move { library.relink(entry_id, path, &reporter).await },
     )
     .await
+    .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn log_library_reindex(
     app: tauri::AppHandle,
     entry_id: String,
-) -> Result<LogLibraryEntry, String> {
+) -> Result<LogLibraryEntry, AppError> {
     let library = library_from_app(&app)?;
     let state: tauri::State<'_, crate::AppState> = app.state();
     run_log_operation(
@@ -528,12 +533,13 @@ pub(crate) async fn log_library_reindex(
         move |reporter| async move { library.reindex(entry_id, &reporter).await },
     )
     .await
+    .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn log_library_cancel(
     state: tauri::State<'_, crate::AppState>,
-) -> Result<bool, String> {
+) -> Result<bool, AppError> {
     Ok(state.log_operation.cancel().await)
 }
 
@@ -747,6 +753,7 @@ fn detect_format(path: &Path) -> Option<LogFormat> {
     match path.extension().and_then(|extension| extension.to_str()) {
         Some(extension) if extension.eq_ignore_ascii_case("tlog") => Some(LogFormat::Tlog),
         Some(extension) if extension.eq_ignore_ascii_case("bin") => Some(LogFormat::Bin),
+        Some(extension) if extension.eq_ignore_ascii_case("ulg") => Some(LogFormat::Ulog),
         _ => None,
     }
 }
@@ -822,7 +829,7 @@ fn format_catalog_migration_error(error: LogCatalogMigrationError) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mavkit::dialect::{HEARTBEAT_DATA, MavAutopilot, MavModeFlag, MavState, MavType};
+    use ironwing_core::dialect::{HEARTBEAT_DATA, MavAutopilot, MavModeFlag, MavState, MavType};
     use mavkit::tlog::TlogWriter;
     use mavlink::{MavHeader, MavlinkVersion};
     use std::io::{self, Write};
@@ -881,7 +888,7 @@ mod tests {
             sequence: 0,
         };
         for offset in 0..count {
-            let message = mavkit::dialect::MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+            let message = ironwing_core::dialect::MavMessage::HEARTBEAT(HEARTBEAT_DATA {
                 custom_mode: 3 + offset as u32,
                 mavtype: MavType::MAV_TYPE_QUADROTOR,
                 autopilot: MavAutopilot::MAV_AUTOPILOT_ARDUPILOTMEGA,
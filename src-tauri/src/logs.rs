@@ -11,9 +11,11 @@ pub(crate) use ironwing_core::log_engine::{
 };
 pub(crate) use ironwing_core::log_playback::PlaybackFrame;
 use ironwing_core::{
-    event_names,
+    event_names, log_analysis,
     log_engine::{self, ParsedLog},
     log_playback::{idle_playback_state, playback_state_for_log, validate_playback_speed},
+    tlog_segments,
+    units::convert_log_fields,
 };
 
 use crate::{
@@ -21,17 +23,22 @@ use crate::{
     e2e_emit::emit_event,
     helpers,
     ipc::{
-        LogOperationPhase, LogOperationProgress, OperationFailure, OperationId, Reason, ReasonKind,
-        ReplayStatus, ScopedEvent, SessionEnvelope,
+        AppError, LogFinding, LogOperationPhase, LogOperationProgress, LogVtolEvent,
+        OperationFailure, OperationId, Reason, ReasonKind, ReplayStatus, ScopedEvent,
+        SessionEnvelope,
         logs::{
-            ChartSeriesPage, ChartSeriesRequest, LogExportFormat, LogExportRequest,
-            LogExportResult, RawMessagePage, RawMessageQuery,
+            ChartSeriesPage, ChartSeriesRequest, LogCompareSeries, LogExportFormat,
+            LogExportRequest, LogExportResult, LogParam, LogStatusText, RawMessagePage,
+            RawMessageQuery, VirtualField,
         },
         operation_failure_json,
-        playback::{PlaybackSeekResult, PlaybackState},
+        playback::{PlaybackPosition, PlaybackSeekResult, PlaybackState},
     },
 };
 
+/// `log://playback_position` is for scrub bars, so 2 Hz is plenty.
+const PLAYBACK_POSITION_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
 #[derive(Clone)]
 pub(crate) struct PlaybackRuntimeState {
     inner: Arc<tokio::sync::Mutex<PlaybackRuntime>>,
@@ -242,6 +249,10 @@ impl PlaybackRuntimeState {
         runtime.state.clone()
     }
 
+    pub(crate) async fn snapshot(&self) -> PlaybackState {
+        self.inner.lock().await.state.clone()
+    }
+
     pub(crate) async fn current_generation(&self) -> u64 {
         self.inner.lock().await.generation
     }
@@ -732,7 +743,7 @@ async fn store_for_entry(
     )?;
     let source_path = entry.source.original_path.clone();
     if let Some(active_store) = reusable_library_store(
-        state.log_store.lock().await.as_ref(),
+        state.open_logs.lock().await.most_recent(),
         None,
         entry_id,
         &source_path,
@@ -759,15 +770,45 @@ async fn store_for_entry(
     Ok(parsed.store)
 }
 
+/// Read every segment listed in a rotated recording's index. Segments are
+/// resolved next to the index; one that cannot be read is passed on as an
+/// error for the parser to report.
+async fn parse_segmented_tlog(path: String) -> Result<ParsedLog, String> {
+    let index_bytes = tokio::fs::read(&path)
+        .await
+        .map_err(|error| format!("failed to read TLOG index: {error}"))?;
+    let index = tlog_segments::read_tlog_index(&index_bytes)?;
+    let dir = Path::new(&path)
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    let mut segments = Vec::with_capacity(index.segments.len());
+    for segment in index.segments {
+        let bytes = tokio::fs::read(dir.join(&segment.file_name))
+            .await
+            .map_err(|error| error.to_string());
+        segments.push((segment.file_name, bytes));
+    }
+    tokio::task::spawn_blocking(move || log_engine::parse_tlog_segments(&path, &segments))
+        .await
+        .map_err(|error| format!("TLOG parse task failed: {error}"))?
+}
+
 pub(crate) async fn parse_log_file(path: String) -> Result<ParsedLog, String> {
+    if tlog_segments::is_tlog_index(&path) {
+        return parse_segmented_tlog(path).await;
+    }
     let log_type = if path.ends_with(".bin") || path.ends_with(".BIN") {
         LogType::Bin
+    } else if path.ends_with(".ulg") || path.ends_with(".ULG") {
+        LogType::Ulog
     } else {
         LogType::Tlog
     };
     let read_label = match log_type {
         LogType::Bin => "BIN",
         LogType::Tlog => "TLOG",
+        LogType::Ulog => "ULOG",
     };
     let bytes = tokio::fs::read(&path)
         .await
@@ -783,8 +824,13 @@ pub(crate) async fn log_open(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
     path: String,
-) -> Result<LogSummary, String> {
-    *state.log_store.lock().await = None;
+) -> Result<LogSummary, AppError> {
+    let max_open_logs = state.settings.lock().await.max_open_logs as usize;
+    state
+        .open_logs
+        .lock()
+        .await
+        .ensure_capacity(&path, max_open_logs)?;
 
     run_log_operation(
         &state.log_operation,
@@ -815,21 +861,17 @@ pub(crate) async fn log_open(
                     None,
                 )?;
 
-                let summary = parsed.store.summary().clone();
                 let state: tauri::State<'_, AppState> = app.state();
-                {
-                    let mut guard = state.log_store.lock().await;
-                    *guard = Some(parsed.store);
-                    if let Some(store) = guard.as_ref() {
-                        state.playback_runtime.prepare_ready(store, false).await;
-                    }
-                }
-
-                Ok(summary)
+                let mut guard = state.open_logs.lock().await;
+                let log_id = guard.insert(parsed.store, max_open_logs)?;
+                let store = guard.get(Some(&log_id))?;
+                state.playback_runtime.prepare_ready(store, false).await;
+                Ok(store.summary().clone())
             }
         },
     )
     .await
+    .map_err(AppError::from)
 }
 
 pub(crate) fn emit_playback_state_snapshot(
@@ -895,6 +937,21 @@ fn playback_tick_duration() -> std::time::Duration {
     std::time::Duration::from_millis(millis)
 }
 
+fn emit_playback_position(
+    app: &tauri::AppHandle,
+    envelope: &SessionEnvelope,
+    position: PlaybackPosition,
+) {
+    emit_event(
+        app,
+        event_names::LOG_PLAYBACK_POSITION,
+        &ScopedEvent {
+            envelope: envelope.clone(),
+            value: position,
+        },
+    );
+}
+
 fn playback_elapsed_usec(started_at: std::time::Instant, speed: f32) -> u64 {
     (started_at.elapsed().as_micros() as f64 * f64::from(speed))
         .round()
@@ -916,6 +973,7 @@ async fn spawn_playback_task(
         let envelope = envelope.clone();
         async move {
             let started_at = std::time::Instant::now();
+            let mut last_position_at: Option<std::time::Instant> = None;
             loop {
                 tokio::time::sleep(playback_tick_duration()).await;
                 if !playback_runtime.matches_generation(generation).await {
@@ -927,13 +985,16 @@ async fn spawn_playback_task(
                     .min(end_usec);
 
                 let state: tauri::State<'_, AppState> = app.state();
-                let mut guard = state.log_store.lock().await;
-                let Some(store) = guard.as_mut() else {
+                let mut guard = state.open_logs.lock().await;
+                let Some(store) = guard.most_recent_mut() else {
                     break;
                 };
 
                 store.set_playback_cursor_usec(Some(next_cursor_usec));
                 let frame = store.playback_frame();
+                let log_start_usec = store
+                    .playback_bounds()
+                    .map_or(start_cursor_usec, |(start_usec, _)| start_usec);
                 let playback_state = if next_cursor_usec >= end_usec {
                     playback_runtime.prepare_end(store).await
                 } else {
@@ -946,7 +1007,23 @@ async fn spawn_playback_task(
                 }
 
                 emit_playback_frame(&app, &envelope, &frame, &playback_state);
-                if playback_state.status == ReplayStatus::Ended {
+                let ended = playback_state.status == ReplayStatus::Ended;
+                if ended
+                    || last_position_at.is_none_or(|at| at.elapsed() >= PLAYBACK_POSITION_INTERVAL)
+                {
+                    last_position_at = Some(std::time::Instant::now());
+                    emit_playback_position(
+                        &app,
+                        &envelope,
+                        PlaybackPosition {
+                            cursor_usec: next_cursor_usec,
+                            start_usec: log_start_usec,
+                            end_usec,
+                            speed,
+                        },
+                    );
+                }
+                if ended {
                     break;
                 }
             }
@@ -972,7 +1049,7 @@ async fn playback_stop_inner(
     };
 
     let idle_state = state.playback_runtime.prepare_idle().await;
-    *state.log_store.lock().await = None;
+    state.open_logs.lock().await.remove(None);
 
     if let Some(envelope) = playback_envelope.as_ref() {
         emit_playback_state_snapshot(app, envelope, &idle_state);
@@ -988,14 +1065,15 @@ async fn playback_stop_inner(
     Ok(idle_state)
 }
 
-#[tauri::command]
-pub(crate) async fn playback_seek(
-    state: tauri::State<'_, AppState>,
-    app: tauri::AppHandle,
+async fn playback_seek_inner(
+    state: &AppState,
+    app: &tauri::AppHandle,
     cursor_usec: Option<u64>,
 ) -> Result<PlaybackSeekResult, String> {
-    let mut guard = state.log_store.lock().await;
-    let store = guard.as_mut().ok_or_else(|| "no log open".to_string())?;
+    let mut guard = state.open_logs.lock().await;
+    let store = guard
+        .most_recent_mut()
+        .ok_or_else(|| "no log open".to_string())?;
     let envelope = {
         state.live_runtime.with_runtime(|runtime| {
             runtime
@@ -1012,15 +1090,52 @@ pub(crate) async fn playback_seek(
     let result = store.seek_playback(cursor_usec, envelope.clone());
     let playback_state = state.playback_runtime.prepare_seek(store).await;
     let frame = store.playback_frame();
-    emit_playback_frame(&app, &envelope, &frame, &playback_state);
+    emit_playback_frame(app, &envelope, &frame, &playback_state);
     Ok(result)
 }
 
+#[tauri::command]
+pub(crate) async fn playback_seek(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+    cursor_usec: Option<u64>,
+) -> Result<PlaybackSeekResult, AppError> {
+    playback_seek_inner(state.inner(), &app, cursor_usec)
+        .await
+        .map_err(AppError::from)
+}
+
+fn ensure_no_live_vehicle(state: &AppState) -> Result<(), AppError> {
+    if state
+        .live_runtime
+        .with_runtime(|runtime| runtime.vehicle())
+        .is_some()
+    {
+        return Err(AppError::validation(
+            "disconnect the vehicle before playing a log as live telemetry",
+        ));
+    }
+    Ok(())
+}
+
+/// Play the open log through the normal telemetry events from `start_usec`
+/// (or the current cursor), at `speed` times real time when given. Refused
+/// while a real vehicle is connected so the two cannot interleave.
 #[tauri::command]
 pub(crate) async fn playback_play(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<PlaybackState, String> {
+    speed: Option<f32>,
+    start_usec: Option<u64>,
+) -> Result<PlaybackState, AppError> {
+    ensure_no_live_vehicle(state.inner())?;
+    if let Some(speed) = speed {
+        let store = helpers::with_log_store(&state).await?;
+        state.playback_runtime.prepare_speed(&store, speed).await?;
+    }
+    if start_usec.is_some() {
+        playback_seek_inner(state.inner(), &app, start_usec).await?;
+    }
     let envelope = {
         state.live_runtime.with_runtime(|runtime| {
             runtime
@@ -1029,10 +1144,12 @@ pub(crate) async fn playback_play(
                 .map_err(|failure| failure.reason.message)
         })?
     };
-    let mut guard = state.log_store.lock().await;
-    let store = guard.as_mut().ok_or_else(|| "no log open".to_string())?;
+    let mut guard = state.open_logs.lock().await;
+    let store = guard
+        .most_recent_mut()
+        .ok_or_else(|| AppError::validation("no log open"))?;
     let Some((start_usec, end_usec)) = store.playback_bounds() else {
-        return Err("open log has no replayable entries".to_string());
+        return Err(AppError::validation("open log has no replayable entries"));
     };
     let start_cursor_usec = store.resolved_playback_cursor_usec().unwrap_or(start_usec);
     if start_cursor_usec >= end_usec {
@@ -1064,7 +1181,7 @@ pub(crate) async fn playback_play(
 pub(crate) async fn playback_pause(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<PlaybackState, String> {
+) -> Result<PlaybackState, AppError> {
     let envelope = {
         state.live_runtime.with_runtime(|runtime| {
             runtime
@@ -1073,8 +1190,10 @@ pub(crate) async fn playback_pause(
                 .map_err(|failure| failure.reason.message)
         })?
     };
-    let mut guard = state.log_store.lock().await;
-    let store = guard.as_mut().ok_or_else(|| "no log open".to_string())?;
+    let mut guard = state.open_logs.lock().await;
+    let store = guard
+        .most_recent_mut()
+        .ok_or_else(|| AppError::validation("no log open"))?;
     let playback_state = state.playback_runtime.prepare_pause(store).await;
     let frame = store.playback_frame();
     emit_playback_frame(&app, &envelope, &frame, &playback_state);
@@ -1086,7 +1205,7 @@ pub(crate) async fn playback_set_speed(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
     speed: f32,
-) -> Result<PlaybackState, String> {
+) -> Result<PlaybackState, AppError> {
     let envelope = {
         state.live_runtime.with_runtime(|runtime| {
             runtime
@@ -1095,10 +1214,12 @@ pub(crate) async fn playback_set_speed(
                 .map_err(|failure| failure.reason.message)
         })?
     };
-    let mut guard = state.log_store.lock().await;
-    let store = guard.as_mut().ok_or_else(|| "no log open".to_string())?;
+    let mut guard = state.open_logs.lock().await;
+    let store = guard
+        .most_recent_mut()
+        .ok_or_else(|| AppError::validation("no log open"))?;
     let Some((start_usec, end_usec)) = store.playback_bounds() else {
-        return Err("open log has no replayable entries".to_string());
+        return Err(AppError::validation("open log has no replayable entries"));
     };
     let start_cursor_usec = store.resolved_playback_cursor_usec().unwrap_or(start_usec);
     let playback_state = state.playback_runtime.prepare_speed(store, speed).await?;
@@ -1126,8 +1247,10 @@ pub(crate) async fn playback_set_speed(
 pub(crate) async fn playback_stop(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<PlaybackState, String> {
-    playback_stop_inner(state.inner(), &app).await
+) -> Result<PlaybackState, AppError> {
+    playback_stop_inner(state.inner(), &app)
+        .await
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -1135,9 +1258,11 @@ pub(crate) async fn log_raw_messages_query(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
     request: RawMessageQuery,
-) -> Result<RawMessagePage, String> {
+) -> Result<RawMessagePage, AppError> {
     let store = store_for_entry(state.inner(), &app, &request.entry_id).await?;
-    blocking_raw_message_query(store, request).await
+    blocking_raw_message_query(store, request)
+        .await
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -1145,9 +1270,11 @@ pub(crate) async fn log_chart_series_query(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
     request: ChartSeriesRequest,
-) -> Result<ChartSeriesPage, String> {
+) -> Result<ChartSeriesPage, AppError> {
     let store = store_for_entry(state.inner(), &app, &request.entry_id).await?;
-    blocking_chart_series_query(store, request).await
+    blocking_chart_series_query(store, request)
+        .await
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -1157,9 +1284,75 @@ pub(crate) async fn log_query(
     start_usec: Option<u64>,
     end_usec: Option<u64>,
     max_points: Option<usize>,
-) -> Result<Vec<LogDataPoint>, String> {
-    let store = helpers::with_log_store(&state).await?;
-    log_engine::query_log_messages(&store, &msg_type, start_usec, end_usec, max_points)
+    raw: Option<bool>,
+    log_id: Option<String>,
+    segment: Option<usize>,
+    virtual_fields: Option<Vec<VirtualField>>,
+) -> Result<Vec<LogDataPoint>, AppError> {
+    let unit_system = state
+        .live_runtime
+        .with_runtime(|runtime| runtime.unit_system());
+    let store = helpers::with_open_log(&state, log_id.as_deref()).await?;
+    let mut points = log_engine::query_log_messages(
+        &store, &msg_type, start_usec, end_usec, max_points, segment,
+    )?;
+    if let Some(virtual_fields) = &virtual_fields {
+        log_engine::apply_virtual_fields(&store, &msg_type, &mut points, virtual_fields)?;
+    }
+    // Charts comparing sessions pass `raw` so a unit switch cannot skew them.
+    if !raw.unwrap_or(false) && !unit_system.is_si() {
+        for point in &mut points {
+            point.units = Some(convert_log_fields(
+                &msg_type,
+                &mut point.fields,
+                &unit_system,
+            ));
+        }
+    }
+    Ok(points)
+}
+
+/// Message types present in an open log, most recent when no id is given.
+#[tauri::command]
+pub(crate) async fn log_list_types(
+    state: tauri::State<'_, AppState>,
+    log_id: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    let store = helpers::with_open_log(&state, log_id.as_deref()).await?;
+    let mut types: Vec<String> = store.type_index().keys().cloned().collect();
+    types.sort();
+    Ok(types)
+}
+
+/// The same field from several open logs, for comparing flights. With
+/// `normalize_time` each series is timed from its own log's start.
+#[tauri::command]
+pub(crate) async fn log_query_compare(
+    state: tauri::State<'_, AppState>,
+    log_ids: Vec<String>,
+    msg_type: String,
+    field: String,
+    normalize_time: bool,
+    max_points: Option<usize>,
+) -> Result<Vec<LogCompareSeries>, AppError> {
+    let logs = state.open_logs.lock().await;
+    log_ids
+        .iter()
+        .map(|log_id| {
+            let store = logs.get(Some(log_id)).map_err(AppError::validation)?;
+            Ok(LogCompareSeries {
+                log_id: log_id.clone(),
+                file_name: store.summary().file_name.clone(),
+                points: log_engine::compare_series_points(
+                    store,
+                    &msg_type,
+                    &field,
+                    normalize_time,
+                    max_points,
+                ),
+            })
+        })
+        .collect()
 }
 
 #[tauri::command]
@@ -1170,20 +1363,21 @@ pub(crate) async fn log_get_flight_path(
     start_usec: Option<u64>,
     end_usec: Option<u64>,
     max_points: Option<usize>,
-) -> Result<Vec<FlightPathPoint>, String> {
+) -> Result<Vec<FlightPathPoint>, AppError> {
     let store = if let Some(entry_id) = entry_id {
         store_for_entry(state.inner(), &app, &entry_id).await?
     } else {
         helpers::with_log_store(&state).await?.clone()
     };
     log_engine::flight_path_points(&store, start_usec, end_usec, max_points)
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn log_get_telemetry_track(
     state: tauri::State<'_, AppState>,
     max_points: Option<usize>,
-) -> Result<Vec<TelemetrySnapshot>, String> {
+) -> Result<Vec<TelemetrySnapshot>, AppError> {
     let store = helpers::with_log_store(&state).await?;
     Ok(log_engine::telemetry_track(&store, max_points))
 }
@@ -1191,45 +1385,104 @@ pub(crate) async fn log_get_telemetry_track(
 #[tauri::command]
 pub(crate) async fn log_get_summary(
     state: tauri::State<'_, AppState>,
-) -> Result<Option<LogSummary>, String> {
-    let guard = state.log_store.lock().await;
-    Ok(guard.as_ref().map(|store| store.summary().clone()))
+) -> Result<Option<LogSummary>, AppError> {
+    let guard = state.open_logs.lock().await;
+    Ok(guard.most_recent().map(|store| store.summary().clone()))
 }
 
+/// Close one open log, or the most recent one when no id is given. Closing
+/// the log playback runs on also stops playback.
 #[tauri::command]
 pub(crate) async fn log_close(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<(), String> {
-    playback_stop_inner(state.inner(), &app).await?;
+    log_id: Option<String>,
+) -> Result<(), AppError> {
+    let mut logs = state.open_logs.lock().await;
+    if logs.is_most_recent(log_id.as_deref()) || logs.is_empty() {
+        drop(logs);
+        playback_stop_inner(state.inner(), &app).await?;
+    } else {
+        logs.get(log_id.as_deref()).map_err(AppError::validation)?;
+        logs.remove(log_id.as_deref());
+    }
     Ok(())
 }
 
+/// Rewrite an open log's timestamps from the SYSTEM_TIME/GPS time it
+/// recorded, merging segments split by a bad ground-station clock. Refused
+/// while the log is playing since the cursor would jump.
+#[tauri::command]
+pub(crate) async fn log_repair_timestamps(
+    state: tauri::State<'_, AppState>,
+    log_id: Option<String>,
+) -> Result<LogSummary, AppError> {
+    let mut logs = state.open_logs.lock().await;
+    if logs.is_most_recent(log_id.as_deref())
+        && state.playback_runtime.snapshot().await.status == ReplayStatus::Playing
+    {
+        return Err(AppError::validation("pause playback before repairing timestamps"));
+    }
+    let store = logs
+        .get_mut(log_id.as_deref())
+        .map_err(AppError::validation)?;
+    *store = log_engine::repair_timestamps(store)?;
+    Ok(store.summary().clone())
+}
+
 #[tauri::command]
 pub(crate) async fn log_get_flight_summary(
     state: tauri::State<'_, AppState>,
-) -> Result<FlightSummary, String> {
+) -> Result<FlightSummary, AppError> {
     let store = helpers::with_log_store(&state).await?;
     Ok(log_engine::flight_summary(&store))
 }
 
+/// Heuristic issue report over the open log: vibration, EKF, battery, GPS,
+/// RC failsafe, thrust loss and yaw imbalance.
+#[tauri::command]
+pub(crate) async fn log_analyze(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<LogFinding>, AppError> {
+    let store = helpers::with_log_store(&state).await?;
+    Ok(log_analysis::analyze_log(&store))
+}
+
+#[tauri::command]
+pub(crate) async fn log_extract_params(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<LogParam>, AppError> {
+    let store = helpers::with_log_store(&state).await?;
+    log_engine::log_params(&store).map_err(AppError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn log_statustext(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<LogStatusText>, AppError> {
+    let store = helpers::with_log_store(&state).await?;
+    log_engine::log_status_texts(&store).map_err(AppError::from)
+}
+
+#[tauri::command]
+pub(crate) async fn log_vtol_events(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<LogVtolEvent>, AppError> {
+    let store = helpers::with_log_store(&state).await?;
+    log_engine::log_vtol_events(&store).map_err(AppError::from)
+}
+
 #[tauri::command]
 pub(crate) async fn log_export(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
     request: LogExportRequest,
-) -> Result<LogExportResult, String> {
+) -> Result<LogExportResult, AppError> {
     if request.format != LogExportFormat::Csv {
-        return Err(operation_failure_json(OperationFailure {
-            operation_id: OperationId::LogExport,
-            reason: Reason {
-                kind: ReasonKind::Unsupported,
-                message: format!(
-                    "log export format {:?} is not implemented yet",
-                    request.format
-                ),
-            },
-        }));
+        return Err(AppError::validation(format!(
+            "log export format {:?} is not implemented yet",
+            request.format
+        )));
     }
 
     run_log_operation(
@@ -1266,6 +1519,7 @@ pub(crate) async fn log_export(
         },
     )
     .await
+    .map_err(AppError::from)
 }
 
 #[tauri::command]
@@ -1274,9 +1528,11 @@ pub(crate) async fn log_export_csv(
     path: String,
     start_usec: Option<u64>,
     end_usec: Option<u64>,
-) -> Result<u64, String> {
+) -> Result<u64, AppError> {
     let store = helpers::with_log_store(&state).await?.clone();
-    blocking_compat_csv_export(store, path, start_usec, end_usec).await
+    blocking_compat_csv_export(store, path, start_usec, end_usec)
+        .await
+        .map_err(AppError::from)
 }
 
 #[cfg(test)]
@@ -1481,6 +1737,7 @@ mod tests {
             source_kind: SourceKind::Playback,
             seek_epoch: 1,
             reset_revision: 1,
+            system_id: None,
         };
 
         let result = store.seek_playback(Some(500), envelope.clone());
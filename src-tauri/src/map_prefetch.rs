@@ -0,0 +1,248 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ironwing_core::event_names;
+use ironwing_core::map_tiles::{
+    MAP_PREFETCH_CONCURRENCY, MAP_PREFETCH_MANIFEST, MAP_PREFETCH_MAX_ATTEMPTS,
+    MAP_PREFETCH_REQUEST_INTERVAL, MAP_PREFETCH_RETRY_BACKOFF, PrefetchManifest, TileId,
+    prefetch_tiles, tile_extension, tile_relative_path, tile_url,
+};
+use tauri_plugin_http::reqwest;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+use crate::AppState;
+use crate::e2e_emit::emit_event;
+use crate::ipc::{AppError, MapPrefetchProgress, MapPrefetchRequest};
+
+const TILE_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Downloads between manifest saves, so a crash loses little resume state.
+const MANIFEST_SAVE_EVERY: u32 = 50;
+
+struct Prefetch {
+    app: tauri::AppHandle,
+    client: reqwest::Client,
+    template: String,
+    extension: String,
+    dest: PathBuf,
+    cancel: CancellationToken,
+    queue: Mutex<std::vec::IntoIter<TileId>>,
+    /// When the next request may start; shared so the rate limit holds
+    /// across workers.
+    next_request: tokio::sync::Mutex<Instant>,
+    tally: Mutex<(MapPrefetchProgress, PrefetchManifest)>,
+}
+
+impl Prefetch {
+    fn next_tile(&self) -> Option<TileId> {
+        self.queue.lock().unwrap().next()
+    }
+
+    /// Record one finished tile, emit the new totals and save the manifest
+    /// now and then.
+    async fn record(&self, update: impl FnOnce(&mut MapPrefetchProgress, &mut PrefetchManifest)) {
+        let (progress, save) = {
+            let mut tally = self.tally.lock().unwrap();
+            let (progress, manifest) = &mut *tally;
+            update(progress, manifest);
+            let save = progress.downloaded > 0 && progress.downloaded % MANIFEST_SAVE_EVERY == 0;
+            (*progress, save.then(|| manifest.clone()))
+        };
+        emit_event(&self.app, event_names::MAP_PREFETCH_PROGRESS, &progress);
+        if let Some(manifest) = save {
+            save_manifest(&self.dest, &manifest).await;
+        }
+    }
+
+    async fn wait_for_slot(&self) {
+        let mut next = self.next_request.lock().await;
+        tokio::time::sleep_until(*next).await;
+        *next = Instant::now() + MAP_PREFETCH_REQUEST_INTERVAL;
+    }
+
+    /// Fetch `url`, retrying server errors and dropped connections with a
+    /// growing backoff. Client errors other than 429 are not retried.
+    async fn fetch(&self, url: &str) -> Result<Vec<u8>, String> {
+        let mut attempt = 1;
+        loop {
+            self.wait_for_slot().await;
+            let error = match self.client.get(url).send().await {
+                Ok(response) if response.status().is_success() => match response.bytes().await {
+                    Ok(body) => return Ok(body.to_vec()),
+                    Err(error) => error.to_string(),
+                },
+                Ok(response) => {
+                    let status = response.status();
+                    if status.is_client_error() && status != reqwest::StatusCode::TOO_MANY_REQUESTS
+                    {
+                        return Err(format!("HTTP {status}"));
+                    }
+                    format!("HTTP {status}")
+                }
+                Err(error) => error.to_string(),
+            };
+            if attempt >= MAP_PREFETCH_MAX_ATTEMPTS {
+                return Err(error);
+            }
+            tokio::time::sleep(MAP_PREFETCH_RETRY_BACKOFF * attempt).await;
+            attempt += 1;
+        }
+    }
+
+    async fn run_worker(&self) {
+        while let Some(tile) = self.next_tile() {
+            if self.cancel.is_cancelled() {
+                return;
+            }
+            let relative = tile_relative_path(tile, &self.extension);
+            let path = self.dest.join(&relative);
+            let size_on_disk = tokio::fs::metadata(&path).await.ok().map(|meta| meta.len());
+            let already_stored = self
+                .tally
+                .lock()
+                .unwrap()
+                .1
+                .is_complete(&relative, size_on_disk);
+            if already_stored {
+                self.record(|progress, _| progress.skipped += 1).await;
+                continue;
+            }
+
+            let url = tile_url(&self.template, tile);
+            let body = tokio::select! {
+                body = self.fetch(&url) => body,
+                _ = self.cancel.cancelled() => return,
+            };
+            let stored = match body {
+                Ok(body) => write_tile(&path, &body).await.map(|()| body.len() as u64),
+                Err(error) => Err(error),
+            };
+            match stored {
+                Ok(bytes) => {
+                    self.record(|progress, manifest| {
+                        progress.downloaded += 1;
+                        progress.bytes += bytes;
+                        manifest.tiles.insert(relative, bytes);
+                    })
+                    .await
+                }
+                Err(error) => {
+                    tracing::debug!("map prefetch: {url}: {error}");
+                    self.record(|progress, _| progress.failed += 1).await;
+                }
+            }
+        }
+    }
+}
+
+/// Write through a temporary file so an interrupted write never leaves a
+/// truncated tile under the final name.
+async fn write_tile(path: &Path, body: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|error| error.to_string())?;
+    }
+    let partial = path.with_extension("part");
+    tokio::fs::write(&partial, body)
+        .await
+        .map_err(|error| error.to_string())?;
+    tokio::fs::rename(&partial, path)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+async fn load_manifest(dest: &Path) -> PrefetchManifest {
+    tokio::fs::read(dest.join(MAP_PREFETCH_MANIFEST))
+        .await
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default()
+}
+
+async fn save_manifest(dest: &Path, manifest: &PrefetchManifest) {
+    let saved = match serde_json::to_vec(manifest) {
+        Ok(bytes) => write_tile(&dest.join(MAP_PREFETCH_MANIFEST), &bytes).await,
+        Err(error) => Err(error.to_string()),
+    };
+    if let Err(error) = saved {
+        tracing::warn!("failed to save the map prefetch manifest: {error}");
+    }
+}
+
+/// Download the tiles covering a mission route or a bounding box into
+/// `<dest_dir>/<z>/<x>/<y>.<ext>` for offline use. Tiles stored whole by an
+/// earlier run are skipped; progress is sent on `map://prefetch_progress`
+/// and the final totals are returned.
+#[tauri::command]
+pub(crate) async fn map_prefetch(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    request: MapPrefetchRequest,
+) -> Result<MapPrefetchProgress, AppError> {
+    let tiles = prefetch_tiles(&request).map_err(AppError::validation)?;
+    let dest = PathBuf::from(&request.dest_dir);
+    tokio::fs::create_dir_all(&dest).await.map_err(|error| {
+        AppError::internal(format!("failed to create {}: {error}", dest.display()))
+    })?;
+    let client = reqwest::Client::builder()
+        .user_agent(format!("IronWing/{}", app.package_info().version))
+        .timeout(TILE_REQUEST_TIMEOUT)
+        .build()
+        .map_err(|error| AppError::internal(error.to_string()))?;
+
+    let cancel = CancellationToken::new();
+    {
+        let mut active = state.map_prefetch_cancel.lock().await;
+        if active.is_some() {
+            return Err(AppError::validation("a map prefetch is already running"));
+        }
+        *active = Some(cancel.clone());
+    }
+
+    let progress = MapPrefetchProgress {
+        total_tiles: tiles.len() as u32,
+        ..MapPrefetchProgress::default()
+    };
+    let prefetch = Arc::new(Prefetch {
+        app: app.clone(),
+        client,
+        template: request.tile_url_template.clone(),
+        extension: tile_extension(&request.tile_url_template).to_string(),
+        dest: dest.clone(),
+        cancel: cancel.clone(),
+        queue: Mutex::new(tiles.into_iter()),
+        next_request: tokio::sync::Mutex::new(Instant::now()),
+        tally: Mutex::new((progress, load_manifest(&dest).await)),
+    });
+    emit_event(&app, event_names::MAP_PREFETCH_PROGRESS, &progress);
+
+    let workers: Vec<_> = (0..MAP_PREFETCH_CONCURRENCY)
+        .map(|_| {
+            let prefetch = prefetch.clone();
+            tokio::spawn(async move { prefetch.run_worker().await })
+        })
+        .collect();
+    for worker in workers {
+        let _ = worker.await;
+    }
+
+    state.map_prefetch_cancel.lock().await.take();
+    let (mut progress, manifest) = prefetch.tally.lock().unwrap().clone();
+    save_manifest(&dest, &manifest).await;
+    progress.cancelled = cancel.is_cancelled();
+    emit_event(&app, event_names::MAP_PREFETCH_PROGRESS, &progress);
+    Ok(progress)
+}
+
+/// Stop a running `map_prefetch`; tiles already written are kept. The token
+/// stays in place until the run has wound down, so a new prefetch cannot
+/// start alongside the old workers.
+#[tauri::command]
+pub(crate) async fn map_prefetch_cancel(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    if let Some(token) = state.map_prefetch_cancel.lock().await.as_ref() {
+        token.cancel();
+    }
+    Ok(())
+}
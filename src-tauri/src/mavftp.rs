@@ -0,0 +1,228 @@
+//! A small MAVLink FTP client: one request in flight, resent with the same
+//! sequence number until the autopilot answers.
+
+use std::time::Duration;
+
+use ironwing_core::dialect::{FILE_TRANSFER_PROTOCOL_DATA, MavMessage};
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::mavftp::{
+    FILE_TRANSFER_PROTOCOL_MESSAGE_ID, FtpDirEntry, FtpError, FtpOpcode, FtpPacket,
+    parse_directory_listing,
+};
+use mavkit::Vehicle;
+use mavlink::MavlinkVersion;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+const REPLY_TIMEOUT: Duration = Duration::from_millis(1500);
+const REQUEST_ATTEMPTS: u32 = 4;
+
+pub(crate) struct FtpClient {
+    vehicle: Vehicle,
+    target_system: u8,
+    target_component: u8,
+    seq: u16,
+    replies: mpsc::UnboundedReceiver<FtpPacket>,
+    listener: JoinHandle<()>,
+}
+
+impl Drop for FtpClient {
+    fn drop(&mut self) {
+        self.listener.abort();
+    }
+}
+
+impl FtpClient {
+    /// Talk to the autopilot component of `vehicle`.
+    pub(crate) async fn new(vehicle: &Vehicle) -> Self {
+        let identity = vehicle.identity();
+        let (replies_tx, replies) = mpsc::unbounded_channel();
+        let (ready_tx, ready) = oneshot::channel();
+        let listener_vehicle = vehicle.clone();
+        let listener = tokio::spawn(async move {
+            use tokio_stream::StreamExt;
+
+            let raw_stream = listener_vehicle.raw().subscribe();
+            tokio::pin!(raw_stream);
+            let _ = ready_tx.send(());
+            while let Some(raw_msg) = raw_stream.next().await {
+                if raw_msg.message_id != FILE_TRANSFER_PROTOCOL_MESSAGE_ID
+                    || raw_msg.system_id != identity.system_id
+                {
+                    continue;
+                }
+                let Ok(MavMessage::FILE_TRANSFER_PROTOCOL(data)) =
+                    MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+                else {
+                    continue;
+                };
+                if let Some(packet) = FtpPacket::decode(&data.payload)
+                    && replies_tx.send(packet).is_err()
+                {
+                    break;
+                }
+            }
+        });
+        // Subscribed before the first request goes out.
+        let _ = ready.await;
+        Self {
+            vehicle: vehicle.clone(),
+            target_system: identity.system_id,
+            target_component: identity.component_id,
+            seq: 0,
+            replies,
+            listener,
+        }
+    }
+
+    async fn request(&mut self, mut packet: FtpPacket) -> Result<FtpPacket, FtpError> {
+        self.seq = self.seq.wrapping_add(1);
+        packet.seq_number = self.seq;
+        let opcode = packet.opcode;
+        let expected_seq = self.seq.wrapping_add(1);
+        let message = MavMessage::FILE_TRANSFER_PROTOCOL(FILE_TRANSFER_PROTOCOL_DATA {
+            target_network: 0,
+            target_system: self.target_system,
+            target_component: self.target_component,
+            payload: packet.encode(),
+        });
+
+        for _ in 0..REQUEST_ATTEMPTS {
+            // A resend keeps its sequence number, so the autopilot repeats
+            // its last reply rather than running the operation twice.
+            live_commands::send_raw_message(&self.vehicle, message.clone())
+                .await
+                .map_err(|error| FtpError::Link(error.to_string()))?;
+            let deadline = Instant::now() + REPLY_TIMEOUT;
+            loop {
+                let reply = match tokio::time::timeout_at(deadline, self.replies.recv()).await {
+                    Ok(Some(reply)) => reply,
+                    Ok(None) => return Err(FtpError::Link("link closed".to_string())),
+                    Err(_) => break,
+                };
+                if reply.seq_number != expected_seq || reply.req_opcode != opcode {
+                    continue;
+                }
+                self.seq = reply.seq_number;
+                return if reply.is_nak() {
+                    Err(reply.nak_error())
+                } else {
+                    Ok(reply)
+                };
+            }
+        }
+        Err(FtpError::Timeout)
+    }
+
+    async fn terminate(&mut self, session: u8) {
+        let request = FtpPacket::request(FtpOpcode::TerminateSession, session, 0, &[]);
+        if let Err(error) = self.request(request).await {
+            tracing::debug!("failed to close FTP session {session}: {error}");
+        }
+    }
+
+    pub(crate) async fn list_directory(
+        &mut self,
+        path: &str,
+    ) -> Result<Vec<FtpDirEntry>, FtpError> {
+        let mut entries = Vec::new();
+        let mut offset = 0;
+        loop {
+            let request = FtpPacket::request(FtpOpcode::ListDirectory, 0, offset, path.as_bytes());
+            let reply = match self.request(request).await {
+                Ok(reply) => reply,
+                Err(FtpError::Eof) => break,
+                Err(error) => return Err(error),
+            };
+            let (page, count) = parse_directory_listing(&reply.data);
+            if count == 0 {
+                break;
+            }
+            entries.extend(page);
+            offset += count;
+        }
+        Ok(entries)
+    }
+
+    pub(crate) async fn remove_file(&mut self, path: &str) -> Result<(), FtpError> {
+        let request = FtpPacket::request(FtpOpcode::RemoveFile, 0, 0, path.as_bytes());
+        self.request(request).await.map(|_| ())
+    }
+
+    /// Read the whole file, reporting `(bytes_done, bytes_total)` per chunk.
+    pub(crate) async fn read_file(
+        &mut self,
+        path: &str,
+        progress: &mut dyn FnMut(u64, u64),
+    ) -> Result<Vec<u8>, FtpError> {
+        let open = FtpPacket::request(FtpOpcode::OpenFileRo, 0, 0, path.as_bytes());
+        let reply = self.request(open).await?;
+        let session = reply.session;
+        let size = reply.data.get(..4).map_or(0, |bytes| {
+            u32::from_le_bytes(bytes.try_into().unwrap_or_default())
+        });
+        let result = self.read_session(session, size, progress).await;
+        self.terminate(session).await;
+        result
+    }
+
+    async fn read_session(
+        &mut self,
+        session: u8,
+        size: u32,
+        progress: &mut dyn FnMut(u64, u64),
+    ) -> Result<Vec<u8>, FtpError> {
+        let mut contents = Vec::with_capacity(size as usize);
+        while (contents.len() as u64) < u64::from(size) {
+            let request =
+                FtpPacket::request(FtpOpcode::ReadFile, session, contents.len() as u32, &[]);
+            let reply = match self.request(request).await {
+                Ok(reply) if !reply.data.is_empty() => reply,
+                Ok(_) | Err(FtpError::Eof) => break,
+                Err(error) => return Err(error),
+            };
+            contents.extend_from_slice(&reply.data);
+            progress(contents.len() as u64, u64::from(size));
+        }
+        Ok(contents)
+    }
+
+    /// Create or replace the file at `path` with `contents`.
+    pub(crate) async fn write_file(
+        &mut self,
+        path: &str,
+        contents: &[u8],
+        progress: &mut dyn FnMut(u64, u64),
+    ) -> Result<(), FtpError> {
+        let create = FtpPacket::request(FtpOpcode::CreateFile, 0, 0, path.as_bytes());
+        let reply = match self.request(create.clone()).await {
+            Err(FtpError::FileExists) => {
+                self.remove_file(path).await?;
+                self.request(create).await?
+            }
+            reply => reply?,
+        };
+        let session = reply.session;
+        let result = self.write_session(session, contents, progress).await;
+        self.terminate(session).await;
+        result
+    }
+
+    async fn write_session(
+        &mut self,
+        session: u8,
+        contents: &[u8],
+        progress: &mut dyn FnMut(u64, u64),
+    ) -> Result<(), FtpError> {
+        let total = contents.len() as u64;
+        let mut offset = 0;
+        for chunk in contents.chunks(ironwing_core::mavftp::FTP_DATA_MAX) {
+            let request = FtpPacket::request(FtpOpcode::WriteFile, session, offset as u32, chunk);
+            self.request(request).await?;
+            offset += chunk.len();
+            progress(offset as u64, total);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use ironwing_core::component_command::ComponentCommand;
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::mavlink_console::{mav_cmd_from_id, mav_message_from_json};
+use serde_json::{Value, json};
+
+use crate::AppState;
+use crate::component_commands::run_command_exchange;
+use crate::helpers::{ensure_expert_mode, ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{AckResult, AppError, OperationId};
+use crate::journal;
+
+/// Send an arbitrary COMMAND_LONG and wait for its final COMMAND_ACK, with
+/// the same retries and IN_PROGRESS handling as the component commands. A
+/// refusal is reported in the result rather than as an error.
+#[tauri::command]
+pub(crate) async fn send_command_long(
+    state: tauri::State<'_, AppState>,
+    command_id: u16,
+    params: [f32; 7],
+    target_component: u8,
+) -> Result<AckResult, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::SendCommandLong,
+        json!({
+            "command_id": command_id,
+            "params": params,
+            "target_component": target_component,
+        }),
+        async {
+            ensure_expert_mode(&state, "the MAVLink console").await?;
+            ensure_live_write_allowed(state.inner(), OperationId::SendCommandLong).await?;
+            let command = mav_cmd_from_id(command_id).map_err(AppError::validation)?;
+            let vehicle = with_vehicle(&state).await?;
+            let outcome = run_command_exchange(
+                &state.command_link,
+                &vehicle,
+                ComponentCommand {
+                    system_id: vehicle.identity().system_id,
+                    component_id: target_component,
+                    command,
+                    params,
+                },
+            )
+            .await?;
+            Ok(AckResult {
+                command_id,
+                command: format!("{command:?}"),
+                accepted: outcome.result.is_ok(),
+                message: outcome.result.err().map(String::from),
+                attempts: outcome.attempts,
+            })
+        },
+    )
+    .await
+}
+
+/// Send one message built from its name and fields, e.g. `SYSTEM_TIME` with
+/// `{ "time_unix_usec": 0, "time_boot_ms": 0 }`. Nothing waits for a reply.
+#[tauri::command]
+pub(crate) async fn send_raw_message(
+    state: tauri::State<'_, AppState>,
+    message_name: String,
+    fields_json: Value,
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::SendRawMessage,
+        json!({ "message_name": message_name, "fields": fields_json }),
+        async {
+            ensure_expert_mode(&state, "the MAVLink console").await?;
+            ensure_live_write_allowed(state.inner(), OperationId::SendRawMessage).await?;
+            let message =
+                mav_message_from_json(&message_name, &fields_json).map_err(AppError::validation)?;
+            let vehicle = with_vehicle(&state).await?;
+            live_commands::send_raw_message(&vehicle, message)
+                .await
+                .map_err(AppError::from)
+        },
+    )
+    .await
+}
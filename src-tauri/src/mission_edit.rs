@@ -0,0 +1,50 @@
+use ironwing_core::mission_edit;
+use mavkit::{MissionItem, MissionPlan};
+
+use crate::ipc::{AltitudeChange, AltitudeFrame, AppError, MissionEditResult};
+
+/// `plan` with `item` inserted after `after_seq`, DO_JUMP targets shifted to
+/// follow the items they pointed at.
+#[tauri::command]
+pub(crate) fn mission_insert_waypoint(
+    plan: MissionPlan,
+    after_seq: u16,
+    item: MissionItem,
+) -> Result<MissionEditResult, AppError> {
+    mission_edit::insert_waypoint(&plan, after_seq, item).map_err(AppError::validation)
+}
+
+/// `plan` flown backwards, keeping a leading takeoff and a trailing landing
+/// or RTL in place.
+#[tauri::command]
+pub(crate) fn mission_reverse(plan: MissionPlan) -> MissionEditResult {
+    mission_edit::reverse(&plan)
+}
+
+#[tauri::command]
+pub(crate) fn mission_translate(
+    plan: MissionPlan,
+    dlat_deg: f64,
+    dlon_deg: f64,
+) -> Result<MissionEditResult, AppError> {
+    mission_edit::translate(&plan, dlat_deg, dlon_deg).map_err(AppError::validation)
+}
+
+#[tauri::command]
+pub(crate) fn mission_scale_altitudes(
+    plan: MissionPlan,
+    change: AltitudeChange,
+) -> Result<MissionEditResult, AppError> {
+    mission_edit::scale_altitudes(&plan, change).map_err(AppError::validation)
+}
+
+/// `home_alt_m` is the AMSL altitude of home; without it only items already
+/// in `target_frame` are left without a warning.
+#[tauri::command]
+pub(crate) fn mission_convert_frame(
+    plan: MissionPlan,
+    target_frame: AltitudeFrame,
+    home_alt_m: Option<f64>,
+) -> Result<MissionEditResult, AppError> {
+    mission_edit::convert_frame(&plan, target_frame, home_alt_m).map_err(AppError::validation)
+}
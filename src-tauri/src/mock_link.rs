@@ -0,0 +1,217 @@
+//! A duplex link for integration tests: MAVKit's `StreamConnection` on the
+//! GCS end and a scripted vehicle end on the other, with faults injected on
+//! the frames between them. MAVKit's demo vehicle runs in-process and never
+//! touches bytes, so link-level behaviour is exercised through this instead.
+
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use ironwing_core::dialect::{
+    HEARTBEAT_DATA, MavAutopilot, MavMessage, MavModeFlag, MavState, MavType,
+};
+use mavkit::stream::{ChannelBridge, StreamConnection};
+use mavlink::{MavHeader, ReadVersion, peek_reader::PeekReader, read_versioned_raw_message};
+use tokio::sync::mpsc;
+
+const MOCK_LINK_FRAMES: usize = 64;
+/// System ID the vehicle end sends as.
+pub(crate) const MOCK_SYSTEM_ID: u8 = 1;
+/// Component ID of the vehicle end's autopilot.
+pub(crate) const MOCK_AUTOPILOT_ID: u8 = 1;
+
+/// An ArduCopter quadrotor heartbeat in `custom_mode`.
+pub(crate) fn heartbeat(custom_mode: u32) -> MavMessage {
+    MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+        custom_mode,
+        mavtype: MavType::MAV_TYPE_QUADROTOR,
+        autopilot: MavAutopilot::MAV_AUTOPILOT_ARDUPILOTMEGA,
+        base_mode: MavModeFlag::empty(),
+        system_status: MavState::MAV_STATE_STANDBY,
+        mavlink_version: 3,
+    })
+}
+
+/// Faults applied to one direction of a [`MockLink`]. The default passes
+/// every frame through untouched.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct LinkFaults {
+    /// Drop the Nth, 2Nth, ... frame.
+    pub(crate) drop_every: Option<NonZeroUsize>,
+    /// Hold every delivered frame back this long.
+    pub(crate) delay: Duration,
+}
+
+impl LinkFaults {
+    pub(crate) fn drop_every(n: usize) -> Self {
+        Self {
+            drop_every: NonZeroUsize::new(n),
+            ..Self::default()
+        }
+    }
+}
+
+/// Forward frames from `rx` to `tx` with `faults` applied, until either side
+/// closes. Frames keep their order; a delayed frame holds back the ones
+/// behind it, as on a slow serial link.
+fn spawn_fault_pipe(
+    mut rx: mpsc::Receiver<Vec<u8>>,
+    tx: mpsc::Sender<Vec<u8>>,
+    faults: LinkFaults,
+) {
+    tokio::spawn(async move {
+        let mut seen = 0_usize;
+        while let Some(frame) = rx.recv().await {
+            seen += 1;
+            if faults.drop_every.is_some_and(|n| seen % n.get() == 0) {
+                continue;
+            }
+            if !faults.delay.is_zero() {
+                tokio::time::sleep(faults.delay).await;
+            }
+            if tx.send(frame).await.is_err() {
+                return;
+            }
+        }
+    });
+}
+
+/// The vehicle end of a [`MockLink`]. Each frame MAVKit writes arrives as
+/// one chunk, so [`VehicleEnd::recv`] decodes a chunk at a time.
+pub(crate) struct VehicleEnd {
+    from_gcs: mpsc::Receiver<Vec<u8>>,
+    to_gcs: mpsc::Sender<Vec<u8>>,
+    sequence: u8,
+}
+
+impl VehicleEnd {
+    /// Send `message` to the GCS from the autopilot.
+    pub(crate) async fn send(&mut self, message: &MavMessage) {
+        self.send_from(MOCK_AUTOPILOT_ID, message).await;
+    }
+
+    /// Send `message` to the GCS from another component of the same system,
+    /// such as a camera answering a command the autopilot also saw.
+    pub(crate) async fn send_from(&mut self, component_id: u8, message: &MavMessage) {
+        let header = MavHeader {
+            system_id: MOCK_SYSTEM_ID,
+            component_id,
+            sequence: self.sequence,
+        };
+        self.sequence = self.sequence.wrapping_add(1);
+        let mut frame = Vec::new();
+        mavlink::write_v2_msg(&mut frame, header, message).expect("encode mock vehicle frame");
+        // The GCS end may already be gone when a test finishes early.
+        let _ = self.to_gcs.send(frame).await;
+    }
+
+    /// The next message the GCS sent, or `None` once it hung up.
+    pub(crate) async fn recv(&mut self) -> Option<MavMessage> {
+        let frame = self.from_gcs.recv().await?;
+        let mut reader = PeekReader::new(frame.as_slice());
+        let raw = read_versioned_raw_message::<MavMessage, _>(&mut reader, ReadVersion::Any)
+            .expect("read GCS frame");
+        Some(
+            MavMessage::parse(raw.version(), raw.message_id(), raw.payload())
+                .expect("decode GCS frame"),
+        )
+    }
+}
+
+pub(crate) type GcsConnection = Box<dyn mavlink::AsyncMavConnection<MavMessage> + Sync + Send>;
+
+/// Both ends of a fault-injecting link. The pipes between them stop once
+/// either end is dropped.
+pub(crate) struct MockLink {
+    pub(crate) gcs: GcsConnection,
+    pub(crate) vehicle: VehicleEnd,
+}
+
+impl MockLink {
+    /// `uplink` applies to frames the GCS sends, `downlink` to frames the
+    /// vehicle end sends.
+    pub(crate) fn new(uplink: LinkFaults, downlink: LinkFaults) -> Self {
+        let ChannelBridge {
+            reader,
+            writer,
+            incoming_tx,
+            outgoing_rx,
+        } = ChannelBridge::new(MOCK_LINK_FRAMES);
+        let (uplink_tx, from_gcs) = mpsc::channel(MOCK_LINK_FRAMES);
+        let (to_gcs, downlink_rx) = mpsc::channel(MOCK_LINK_FRAMES);
+        spawn_fault_pipe(outgoing_rx, uplink_tx, uplink);
+        spawn_fault_pipe(downlink_rx, incoming_tx, downlink);
+        Self {
+            gcs: Box::new(StreamConnection::new(reader, writer)),
+            vehicle: VehicleEnd {
+                from_gcs,
+                to_gcs,
+                sequence: 0,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mavlink::AsyncMavConnection;
+    use tokio::time::Instant;
+
+    fn custom_mode(message: &MavMessage) -> u32 {
+        match message {
+            MavMessage::HEARTBEAT(data) => data.custom_mode,
+            other => panic!("expected a heartbeat, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn downlink_drops_every_nth_frame() {
+        let mut link = MockLink::new(LinkFaults::default(), LinkFaults::drop_every(2));
+        for mode in 1..=4 {
+            link.vehicle.send(&heartbeat(mode)).await;
+        }
+
+        let (_, first) = link.gcs.recv().await.expect("first frame");
+        let (_, second) = link.gcs.recv().await.expect("second frame");
+        assert_eq!(custom_mode(&first), 1);
+        assert_eq!(custom_mode(&second), 3);
+    }
+
+    #[tokio::test]
+    async fn uplink_drops_every_nth_frame() {
+        let mut link = MockLink::new(LinkFaults::drop_every(3), LinkFaults::default());
+        for mode in 1..=4 {
+            link.gcs
+                .send(&MavHeader::default(), &heartbeat(mode))
+                .await
+                .expect("send from GCS");
+        }
+
+        let mut received = Vec::new();
+        for _ in 0..3 {
+            received.push(custom_mode(&link.vehicle.recv().await.expect("uplink frame")));
+        }
+        assert_eq!(received, vec![1, 2, 4]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn delayed_frames_arrive_late_and_in_order() {
+        let delay = Duration::from_millis(250);
+        let mut link = MockLink::new(
+            LinkFaults::default(),
+            LinkFaults {
+                delay,
+                ..LinkFaults::default()
+            },
+        );
+        let sent = Instant::now();
+        link.vehicle.send(&heartbeat(1)).await;
+        link.vehicle.send(&heartbeat(2)).await;
+
+        let (_, first) = link.gcs.recv().await.expect("first frame");
+        assert!(sent.elapsed() >= delay);
+        let (_, second) = link.gcs.recv().await.expect("second frame");
+        assert!(sent.elapsed() >= delay * 2);
+        assert_eq!((custom_mode(&first), custom_mode(&second)), (1, 2));
+    }
+}
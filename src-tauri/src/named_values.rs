@@ -0,0 +1,59 @@
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::named_values::{is_dialect_tunnel_payload_type, tunnel_frames, tunnel_messages};
+
+use crate::AppState;
+use crate::helpers::{ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{AppError, NamedValue, OperationId};
+
+#[tauri::command]
+pub(crate) fn named_values(state: tauri::State<'_, AppState>) -> Vec<NamedValue> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.named_values())
+}
+
+/// Send `data` to the autopilot as TUNNEL messages, chunked to the 128-byte
+/// payload limit. Chunks go out in order; the receiver reassembles them.
+///
+/// Payload types the dialect does not define, such as the private range from
+/// 32768, are encoded here and written straight to the link, which needs a
+/// link IronWing bridges.
+#[tauri::command]
+pub(crate) async fn tunnel_send(
+    state: tauri::State<'_, AppState>,
+    payload_type: u16,
+    data: Vec<u8>,
+) -> Result<(), AppError> {
+    ensure_live_write_allowed(state.inner(), OperationId::TunnelSend).await?;
+    let vehicle = with_vehicle(&state).await?;
+    let identity = vehicle.identity();
+    if !is_dialect_tunnel_payload_type(payload_type) {
+        let gcs = *state.gcs_identity.lock().await;
+        let frames = tunnel_frames(
+            gcs,
+            identity.system_id,
+            identity.component_id,
+            payload_type,
+            &data,
+        )
+        .map_err(AppError::validation)?;
+        return state
+            .link_outbox
+            .send_frames(frames)
+            .await
+            .map_err(AppError::from);
+    }
+    for message in tunnel_messages(
+        identity.system_id,
+        identity.component_id,
+        payload_type,
+        &data,
+    )
+    .map_err(AppError::validation)?
+    {
+        live_commands::send_raw_message(&vehicle, message)
+            .await
+            .map_err(AppError::from)?;
+    }
+    Ok(())
+}
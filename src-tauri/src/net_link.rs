@@ -0,0 +1,178 @@
+//! UDP and TCP sockets owned by IronWing rather than MAVKit's `udpin:` /
+//! `tcpout:` addresses, so network links get the same frame scanning, link
+//! source filter and outbox as the byte-stream transports.
+
+use std::net::SocketAddr;
+
+use ironwing_core::mavlink_frames::FrameScanner;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::ipc::LinkSource;
+
+/// Large enough for any datagram a MAVLink router sends.
+const UDP_DATAGRAM_MAX: usize = 65_535;
+const TCP_READ_CHUNK: usize = 4096;
+
+/// Cut received bytes into frames and hand them to MAVKit. False once the
+/// bridge is gone.
+async fn forward_incoming(
+    scanner: &mut FrameScanner,
+    incoming_tx: &mpsc::Sender<Vec<u8>>,
+    bytes: &[u8],
+) -> bool {
+    let data = scanner.push(bytes);
+    crate::unknown_messages::publish(scanner.take_unknown());
+    data.is_empty() || incoming_tx.send(data).await.is_ok()
+}
+
+/// Pump datagrams between a bound socket and a `ChannelBridge`. Like
+/// `udpin:`, replies go to whichever peer sent last; nothing is sent before
+/// a peer has been heard.
+pub(crate) fn spawn_udp_bridge(
+    socket: std::net::UdpSocket,
+    incoming_tx: mpsc::Sender<Vec<u8>>,
+    mut outgoing_rx: mpsc::Receiver<Vec<u8>>,
+    source_filter: Vec<LinkSource>,
+) -> Result<JoinHandle<()>, String> {
+    socket
+        .set_nonblocking(true)
+        .map_err(|error| format!("failed to configure UDP socket: {error}"))?;
+    let socket = UdpSocket::from_std(socket)
+        .map_err(|error| format!("failed to register UDP socket: {error}"))?;
+    let mut scanner = FrameScanner::with_source_filter(source_filter);
+    Ok(tokio::spawn(async move {
+        let mut datagram = vec![0_u8; UDP_DATAGRAM_MAX];
+        let mut peer: Option<SocketAddr> = None;
+        loop {
+            tokio::select! {
+                received = socket.recv_from(&mut datagram) => match received {
+                    Ok((read, from)) => {
+                        peer = Some(from);
+                        if !forward_incoming(&mut scanner, &incoming_tx, &datagram[..read]).await {
+                            return;
+                        }
+                    }
+                    Err(error) => tracing::debug!("UDP receive error: {error}"),
+                },
+                outgoing = outgoing_rx.recv() => {
+                    let Some(data) = outgoing else { return };
+                    if let Some(peer) = peer
+                        && let Err(error) = socket.send_to(&data, peer).await
+                    {
+                        tracing::debug!("UDP send to {peer} failed: {error}");
+                    }
+                }
+            }
+        }
+    }))
+}
+
+/// Open a TCP connection to a MAVLink server. `tcpout:` has no timeout of its
+/// own, so the caller bounds this.
+pub(crate) async fn connect_tcp(address: &str) -> Result<TcpStream, String> {
+    let stream = TcpStream::connect(address)
+        .await
+        .map_err(|error| format!("failed to connect to {address}: {error}"))?;
+    let _ = stream.set_nodelay(true);
+    Ok(stream)
+}
+
+/// Pump bytes between a connected stream and a `ChannelBridge` until either
+/// side closes.
+pub(crate) fn spawn_tcp_bridge(
+    stream: TcpStream,
+    incoming_tx: mpsc::Sender<Vec<u8>>,
+    mut outgoing_rx: mpsc::Receiver<Vec<u8>>,
+    source_filter: Vec<LinkSource>,
+) -> JoinHandle<()> {
+    let (mut reader, mut writer) = stream.into_split();
+    let mut scanner = FrameScanner::with_source_filter(source_filter);
+    tokio::spawn(async move {
+        let mut chunk = [0_u8; TCP_READ_CHUNK];
+        loop {
+            tokio::select! {
+                read = reader.read(&mut chunk) => match read {
+                    Ok(0) => return,
+                    Ok(read) => {
+                        if !forward_incoming(&mut scanner, &incoming_tx, &chunk[..read]).await {
+                            return;
+                        }
+                    }
+                    Err(error) => {
+                        tracing::warn!("TCP read error: {error}");
+                        return;
+                    }
+                },
+                outgoing = outgoing_rx.recv() => {
+                    let Some(data) = outgoing else { return };
+                    if let Err(error) = writer.write_all(&data).await {
+                        tracing::warn!("TCP write error: {error}");
+                        return;
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironwing_core::dialect::{HEARTBEAT_DATA, MavMessage};
+    use mavlink::{MavHeader, MavlinkVersion};
+
+    fn heartbeat_from(system_id: u8) -> Vec<u8> {
+        let mut frame = Vec::new();
+        mavlink::write_versioned_msg(
+            &mut frame,
+            MavlinkVersion::V2,
+            MavHeader {
+                system_id,
+                component_id: 1,
+                sequence: 0,
+            },
+            &MavMessage::HEARTBEAT(HEARTBEAT_DATA::default()),
+        )
+        .expect("encode heartbeat");
+        frame
+    }
+
+    #[tokio::test]
+    async fn udp_bridge_filters_sources_and_replies_to_the_last_peer() {
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0").expect("bind link");
+        let link_addr = socket.local_addr().unwrap();
+        let (incoming_tx, mut incoming_rx) = mpsc::channel(8);
+        let (outgoing_tx, outgoing_rx) = mpsc::channel(8);
+        let task = spawn_udp_bridge(
+            socket,
+            incoming_tx,
+            outgoing_rx,
+            vec![LinkSource {
+                system_id: 1,
+                component_id: 1,
+            }],
+        )
+        .expect("bridge");
+
+        let vehicle = UdpSocket::bind("127.0.0.1:0").await.expect("bind vehicle");
+        vehicle
+            .send_to(&heartbeat_from(2), link_addr)
+            .await
+            .unwrap();
+        vehicle
+            .send_to(&heartbeat_from(1), link_addr)
+            .await
+            .unwrap();
+        let received = incoming_rx.recv().await.expect("accepted frame");
+        assert_eq!(received, heartbeat_from(1));
+
+        outgoing_tx.send(vec![0xfd, 1, 2]).await.unwrap();
+        let mut reply = [0_u8; 8];
+        let (read, _) = vehicle.recv_from(&mut reply).await.expect("reply");
+        assert_eq!(&reply[..read], &[0xfd, 1, 2]);
+        task.abort();
+    }
+}
@@ -0,0 +1,248 @@
+use std::time::Duration;
+
+use ironwing_core::dialect::MavCmd;
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::log_engine::haversine_m;
+use ironwing_core::orbit::{
+    CIRCLE_RADIUS_PARAM, CIRCLE_RATE_PARAM, ORBIT_STOP_MODES, circle_entry_point,
+    circle_mode_params, do_orbit_params, validate_orbit,
+};
+use ironwing_core::param_value::param_value;
+use mavkit::{ParamWriteResult, Vehicle, VehicleType};
+use serde_json::json;
+
+use crate::AppState;
+use crate::helpers::{ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{AppError, OperationId, OrbitRequest, OrbitResult, OrbitStrategy};
+use crate::journal;
+use crate::param_changes::expect_param_writes;
+
+const CIRCLE_MODE: &str = "CIRCLE";
+/// How close to the circle's edge the vehicle must get before Circle mode is
+/// entered.
+const CIRCLE_ENTRY_ACCEPTANCE_M: f64 = 2.0;
+const CIRCLE_ENTRY_POLL: Duration = Duration::from_millis(250);
+const CIRCLE_ENTRY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// An orbit in progress. The Circle mode fallback keeps the CIRCLE_* values
+/// it overwrote so stopping can put them back.
+pub(crate) struct ActiveOrbit {
+    system_id: u8,
+    restore_params: Vec<(String, f64)>,
+}
+
+fn find_mode(vehicle: &Vehicle, name: &str) -> Option<u32> {
+    vehicle
+        .available_modes()
+        .iter()
+        .find(|mode| mode.name.eq_ignore_ascii_case(name))
+        .map(|mode| mode.custom_mode)
+}
+
+fn supports_circle_mode(vehicle: &Vehicle) -> bool {
+    let multirotor = matches!(
+        vehicle.identity().vehicle_type,
+        VehicleType::Quadrotor
+            | VehicleType::Hexarotor
+            | VehicleType::Octorotor
+            | VehicleType::Tricopter
+            | VehicleType::Helicopter
+            | VehicleType::Coaxial
+    );
+    multirotor && find_mode(vehicle, CIRCLE_MODE).is_some()
+}
+
+async fn fly_to_circle_entry(vehicle: &Vehicle, request: &OrbitRequest) -> Result<(), AppError> {
+    let position = vehicle
+        .telemetry()
+        .position()
+        .global()
+        .latest()
+        .ok_or("vehicle position is unknown")?
+        .value;
+    let (entry_lat, entry_lon) =
+        circle_entry_point(request, position.latitude_deg, position.longitude_deg);
+    live_commands::guided_goto(vehicle, entry_lat, entry_lon, request.altitude_msl_m)
+        .await
+        .map_err(AppError::from)?;
+
+    let arrived = async {
+        loop {
+            if let Some(sample) = vehicle.telemetry().position().global().latest() {
+                let distance = haversine_m(
+                    sample.value.latitude_deg,
+                    sample.value.longitude_deg,
+                    entry_lat,
+                    entry_lon,
+                );
+                if distance <= CIRCLE_ENTRY_ACCEPTANCE_M {
+                    return;
+                }
+            }
+            tokio::time::sleep(CIRCLE_ENTRY_POLL).await;
+        }
+    };
+    tokio::time::timeout(CIRCLE_ENTRY_TIMEOUT, arrived)
+        .await
+        .map_err(|_| AppError::Timeout {
+            message: "vehicle did not reach the edge of the orbit".to_string(),
+        })
+}
+
+async fn orbit_with_circle_mode(
+    state: &AppState,
+    vehicle: &Vehicle,
+    request: &OrbitRequest,
+) -> Result<Vec<ParamWriteResult>, AppError> {
+    let system_id = vehicle.identity().system_id;
+    let mut orbit = state.orbit.lock().await;
+    // A second orbit before stopping the first must not capture the first
+    // one's CIRCLE_* values as the ones to put back.
+    if orbit
+        .as_ref()
+        .is_none_or(|active| active.system_id != system_id)
+    {
+        let store = live_commands::param_get_all(vehicle).map_err(AppError::from)?;
+        let encoding = live_commands::param_encoding(vehicle);
+        let restore_params = [CIRCLE_RADIUS_PARAM, CIRCLE_RATE_PARAM]
+            .into_iter()
+            .map(|name| {
+                store
+                    .params
+                    .get(name)
+                    .map(|param| (name.to_string(), param_value(param, encoding).as_f64()))
+                    .ok_or_else(|| {
+                        AppError::internal(format!("{name} is not in the downloaded parameters"))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        *orbit = Some(ActiveOrbit {
+            system_id,
+            restore_params,
+        });
+    }
+    drop(orbit);
+
+    let circle_params: Vec<_> = circle_mode_params(request)
+        .into_iter()
+        .map(|(name, value)| (name, f64::from(value)))
+        .collect();
+    expect_param_writes(state, circle_params.iter().map(|(name, _)| name.as_str()));
+    let param_results = live_commands::param_write_batch(vehicle, circle_params)
+        .await
+        .map_err(AppError::from)?;
+    if let Some(failed) = param_results.iter().find(|result| !result.success) {
+        return Err(AppError::internal(format!(
+            "vehicle rejected {}",
+            failed.name
+        )));
+    }
+
+    fly_to_circle_entry(vehicle, request).await?;
+    let circle = find_mode(vehicle, CIRCLE_MODE).ok_or("vehicle has no Circle mode")?;
+    live_commands::set_flight_mode(vehicle, circle)
+        .await
+        .map_err(AppError::from)?;
+    Ok(param_results)
+}
+
+async fn restore_circle_params(
+    state: &AppState,
+    vehicle: &Vehicle,
+) -> Result<Vec<ParamWriteResult>, AppError> {
+    let Some(active) = state.orbit.lock().await.take() else {
+        return Ok(Vec::new());
+    };
+    if active.system_id != vehicle.identity().system_id {
+        return Ok(Vec::new());
+    }
+    expect_param_writes(
+        state,
+        active.restore_params.iter().map(|(name, _)| name.as_str()),
+    );
+    live_commands::param_write_batch(vehicle, active.restore_params)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Circle a point with MAV_CMD_DO_ORBIT, falling back to ArduCopter's Circle
+/// mode when the autopilot does not take the command.
+#[tauri::command]
+pub(crate) async fn vehicle_orbit(
+    state: tauri::State<'_, AppState>,
+    request: OrbitRequest,
+) -> Result<OrbitResult, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::VehicleOrbit,
+        json!({ "request": request }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::VehicleOrbit).await?;
+            validate_orbit(&request).map_err(AppError::validation)?;
+            let vehicle = with_vehicle(&state).await?;
+
+            let do_orbit_error = match vehicle
+                .raw()
+                .command_long(MavCmd::MAV_CMD_DO_ORBIT as u16, do_orbit_params(&request))
+                .await
+            {
+                Ok(_) => {
+                    return Ok(OrbitResult {
+                        strategy: OrbitStrategy::DoOrbit,
+                        do_orbit_error: None,
+                        param_results: Vec::new(),
+                    });
+                }
+                Err(error) => error.to_string(),
+            };
+            if !supports_circle_mode(&vehicle) {
+                return Err(AppError::validation(format!(
+                    "vehicle does not support orbit: {do_orbit_error}"
+                )));
+            }
+
+            match orbit_with_circle_mode(&state, &vehicle, &request).await {
+                Ok(param_results) => Ok(OrbitResult {
+                    strategy: OrbitStrategy::CircleMode,
+                    do_orbit_error: Some(do_orbit_error),
+                    param_results,
+                }),
+                Err(error) => {
+                    if let Err(restore_error) = restore_circle_params(&state, &vehicle).await {
+                        tracing::warn!(
+                            "failed to restore CIRCLE_* after orbit failed: {restore_error}"
+                        );
+                    }
+                    Err(error)
+                }
+            }
+        },
+    )
+    .await
+}
+
+/// Leave the orbit for Loiter (or Hold) and put back any CIRCLE_* values the
+/// fallback changed.
+#[tauri::command]
+pub(crate) async fn vehicle_orbit_stop(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ParamWriteResult>, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::VehicleOrbitStop,
+        json!({}),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::VehicleOrbitStop).await?;
+            let vehicle = with_vehicle(&state).await?;
+            let hold = ORBIT_STOP_MODES
+                .iter()
+                .find_map(|name| find_mode(&vehicle, name))
+                .ok_or("vehicle has no Loiter or Hold mode")?;
+            live_commands::set_flight_mode(&vehicle, hold)
+                .await
+                .map_err(AppError::from)?;
+            restore_circle_params(&state, &vehicle).await
+        },
+    )
+    .await
+}
@@ -0,0 +1,69 @@
+use ironwing_core::event_names;
+use ironwing_core::live_runtime;
+use ironwing_core::vehicle_snapshot::unix_epoch_usec;
+use mavkit::Vehicle;
+use serde_json::json;
+use tauri::Manager;
+
+use crate::AppState;
+use crate::ipc::{AppError, OperationId, ParamExternalChange};
+use crate::journal;
+
+fn unix_ms() -> u64 {
+    unix_epoch_usec() / 1000
+}
+
+/// Tag parameter writes this GCS is about to send so the echoes from the
+/// vehicle are not reported as external changes.
+pub(crate) fn expect_param_writes<'a>(state: &AppState, names: impl IntoIterator<Item = &'a str>) {
+    let now_ms = unix_ms();
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.expect_param_writes(names, now_ms));
+}
+
+/// Compares every parameter update with the downloaded store and reports
+/// values this GCS did not write as `param://external_change`, recording each
+/// one in the operation journal.
+pub(crate) async fn param_attribution_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    let mut subscription = vehicle.params().subscribe();
+    while let Some(param_state) = subscription.recv().await {
+        let state: tauri::State<'_, AppState> = handle.state();
+        let changes = state
+            .live_runtime
+            .with_runtime(|runtime| runtime.observe_param_attribution(&param_state, unix_ms()));
+        for change in changes {
+            tracing::info!(
+                "{} changed externally from {} to {}",
+                change.name,
+                change.old_value,
+                change.new_value
+            );
+            journal::record_observed(
+                &state.journal,
+                OperationId::ParamExternalChange,
+                json!({
+                    "name": change.name,
+                    "old_value": change.old_value,
+                    "new_value": change.new_value,
+                }),
+            );
+            live_runtime::emit_scoped(
+                &state.live_runtime,
+                event_names::PARAM_EXTERNAL_CHANGE,
+                change,
+            );
+        }
+    }
+}
+
+/// Parameter changes made by other sources since the connection's first
+/// complete download, oldest first.
+#[tauri::command]
+pub(crate) async fn param_external_changes(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ParamExternalChange>, AppError> {
+    Ok(state
+        .live_runtime
+        .with_runtime(|runtime| runtime.param_external_changes()))
+}
@@ -0,0 +1,377 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::param_value::param_value;
+use serde_json::json;
+use tauri::Manager;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::AppState;
+use crate::commands::persist_param_writes;
+use crate::helpers::{ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{AppError, OperationId, ParamTuneSession, ParamTuneSummary};
+use crate::journal;
+use crate::param_changes::expect_param_writes;
+
+/// How long a tune write waits for its echo before the next value goes out.
+const ECHO_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Where tune writes go, so the coalescing can run against a scripted
+/// vehicle in tests.
+pub(crate) trait ParamTuneTarget: Send + Sync + 'static {
+    /// Send one PARAM_SET and wait for its echo; resolves to the value the
+    /// vehicle reports.
+    fn write(&self, name: &str, value: f64) -> impl Future<Output = Result<f64, String>> + Send;
+}
+
+struct LiveTuneTarget {
+    app: tauri::AppHandle,
+    vehicle: mavkit::Vehicle,
+}
+
+impl ParamTuneTarget for LiveTuneTarget {
+    async fn write(&self, name: &str, value: f64) -> Result<f64, String> {
+        expect_param_writes(&self.app.state::<AppState>(), [name]);
+        let result = live_commands::param_write(&self.vehicle, name, value)
+            .await
+            .map_err(|error| AppError::from(error).to_string())?;
+        if result.success {
+            Ok(f64::from(result.confirmed_value))
+        } else {
+            Err(format!("{name} stayed at {}", result.confirmed_value))
+        }
+    }
+}
+
+#[derive(Default)]
+struct TuneStats {
+    updates: u32,
+    writes: u32,
+    confirmed: Option<f64>,
+    last_error: Option<String>,
+}
+
+/// One parameter being tuned. Values from `set` land in a watch channel, so
+/// a value that arrives while a write is in flight replaces any older one
+/// still waiting: only the newest is sent once the echo comes back.
+pub(crate) struct TuneSession {
+    name: String,
+    original: f64,
+    values: watch::Sender<f64>,
+    stats: Arc<Mutex<TuneStats>>,
+    writer: JoinHandle<()>,
+}
+
+impl TuneSession {
+    pub(crate) fn start(target: impl ParamTuneTarget, name: String, original: f64) -> Self {
+        let (values, receiver) = watch::channel(original);
+        let stats = Arc::new(Mutex::new(TuneStats::default()));
+        let writer = tokio::spawn(write_latest(target, name.clone(), receiver, stats.clone()));
+        Self {
+            name,
+            original,
+            values,
+            stats,
+            writer,
+        }
+    }
+
+    pub(crate) fn session(&self) -> ParamTuneSession {
+        ParamTuneSession {
+            name: self.name.clone(),
+            original_value: self.original,
+        }
+    }
+
+    pub(crate) fn set(&self, value: f64) {
+        self.stats.lock().expect("tune stats lock").updates += 1;
+        self.values.send_replace(value);
+    }
+
+    pub(crate) fn revert(&self) {
+        self.set(self.original);
+    }
+
+    /// Flush the newest value, or the original without `commit`, and stop
+    /// the writer.
+    pub(crate) async fn finish(self, commit: bool) -> ParamTuneSummary {
+        if !commit {
+            self.revert();
+        }
+        drop(self.values);
+        if let Err(error) = self.writer.await {
+            tracing::warn!("tune writer for {} failed: {error}", self.name);
+        }
+        let stats = self.stats.lock().expect("tune stats lock");
+        ParamTuneSummary {
+            name: self.name,
+            original_value: self.original,
+            value: stats.confirmed,
+            updates: stats.updates,
+            writes: stats.writes,
+            last_error: stats.last_error.clone(),
+            persisted: None,
+            persist_error: None,
+        }
+    }
+}
+
+async fn write_latest(
+    target: impl ParamTuneTarget,
+    name: String,
+    mut values: watch::Receiver<f64>,
+    stats: Arc<Mutex<TuneStats>>,
+) {
+    let mut written = *values.borrow();
+    // `changed` still reports the last value after `finish` drops the sender.
+    while values.changed().await.is_ok() {
+        let value = *values.borrow_and_update();
+        if value == written {
+            continue;
+        }
+        let outcome = match tokio::time::timeout(ECHO_TIMEOUT, target.write(&name, value)).await {
+            Ok(outcome) => outcome,
+            Err(_) => Err(format!("no echo for {name} within {ECHO_TIMEOUT:?}")),
+        };
+        let mut tally = stats.lock().expect("tune stats lock");
+        tally.writes += 1;
+        match outcome {
+            Ok(confirmed) => tally.confirmed = Some(confirmed),
+            Err(error) => tally.last_error = Some(error),
+        }
+        written = value;
+    }
+}
+
+async fn open_session(
+    state: &AppState,
+) -> Result<tokio::sync::MutexGuard<'_, Option<TuneSession>>, AppError> {
+    let session = state.param_tune.lock().await;
+    if session.is_none() {
+        return Err(AppError::validation("no parameter tune session is open"));
+    }
+    Ok(session)
+}
+
+/// Open `name` for live tuning from a slider. Only one parameter is tuned at
+/// a time.
+#[tauri::command]
+pub(crate) async fn param_tune_session(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    name: String,
+) -> Result<ParamTuneSession, AppError> {
+    ensure_live_write_allowed(state.inner(), OperationId::ParamWrite).await?;
+    let vehicle = with_vehicle(&state).await?;
+    let mut slot = state.param_tune.lock().await;
+    if let Some(open) = slot.as_ref() {
+        return Err(AppError::validation(format!(
+            "{} is already being tuned",
+            open.name
+        )));
+    }
+    let store = live_commands::param_get_all(&vehicle).map_err(AppError::from)?;
+    let param = store
+        .params
+        .get(&name)
+        .ok_or_else(|| AppError::validation(format!("unknown parameter {name}")))?;
+    let original = param_value(param, live_commands::param_encoding(&vehicle)).as_f64();
+    let session = TuneSession::start(LiveTuneTarget { app, vehicle }, name, original);
+    let opened = session.session();
+    *slot = Some(session);
+    Ok(opened)
+}
+
+/// Queue `value` for the parameter being tuned and return at once. Safe to
+/// call on every slider move: values superseded before their turn are
+/// never sent.
+#[tauri::command]
+pub(crate) async fn param_tune_set(
+    state: tauri::State<'_, AppState>,
+    name: String,
+    value: f64,
+) -> Result<(), AppError> {
+    if !value.is_finite() {
+        return Err(AppError::validation(format!(
+            "{name}: value must be finite"
+        )));
+    }
+    let slot = open_session(state.inner()).await?;
+    let session = slot.as_ref().expect("checked by open_session");
+    if session.name != name {
+        return Err(AppError::validation(format!(
+            "{} is being tuned, not {name}",
+            session.name
+        )));
+    }
+    session.set(value);
+    Ok(())
+}
+
+/// Put the original value back without closing the session.
+#[tauri::command]
+pub(crate) async fn param_tune_revert(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    let slot = open_session(state.inner()).await?;
+    slot.as_ref().expect("checked by open_session").revert();
+    Ok(())
+}
+
+/// Close the session once its last value is written. With `commit` the
+/// tuned value stays and is saved to storage where the autopilot needs it;
+/// without, the original value is written back.
+#[tauri::command]
+pub(crate) async fn param_tune_end(
+    state: tauri::State<'_, AppState>,
+    commit: bool,
+) -> Result<ParamTuneSummary, AppError> {
+    let session = open_session(state.inner())
+        .await?
+        .take()
+        .expect("checked by open_session");
+    journal::record(
+        &state.journal,
+        OperationId::ParamWrite,
+        json!({ "name": session.name, "tune": true, "commit": commit }),
+        async {
+            let mut summary = session.finish(commit).await;
+            if commit && summary.value.is_some() {
+                let vehicle = with_vehicle(&state).await?;
+                (summary.persisted, summary.persist_error) =
+                    persist_param_writes(&vehicle, None, true).await;
+            }
+            Ok(summary)
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    const ECHO_LATENCY: Duration = Duration::from_millis(300);
+
+    /// Echoes every write after 300 ms, like a slow telemetry radio.
+    #[derive(Clone, Default)]
+    struct SlowVehicle {
+        writes: Arc<Mutex<Vec<f64>>>,
+        in_flight: Arc<AtomicU32>,
+    }
+
+    impl ParamTuneTarget for SlowVehicle {
+        async fn write(&self, _name: &str, value: f64) -> Result<f64, String> {
+            assert_eq!(self.in_flight.fetch_add(1, Ordering::SeqCst), 0);
+            self.writes.lock().unwrap().push(value);
+            tokio::time::sleep(ECHO_LATENCY).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            Ok(value)
+        }
+    }
+
+    impl SlowVehicle {
+        fn writes(&self) -> Vec<f64> {
+            self.writes.lock().unwrap().clone()
+        }
+    }
+
+    /// Never answers.
+    struct SilentVehicle;
+
+    impl ParamTuneTarget for SilentVehicle {
+        async fn write(&self, _name: &str, _value: f64) -> Result<f64, String> {
+            std::future::pending().await
+        }
+    }
+
+    fn start(vehicle: &SlowVehicle) -> TuneSession {
+        TuneSession::start(vehicle.clone(), "RTL_ALT".to_string(), 1500.0)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slider_drag_sends_only_the_newest_value_per_echo() {
+        let vehicle = SlowVehicle::default();
+        let session = start(&vehicle);
+
+        // 50 Hz slider drag for one second.
+        for step in 1..=50 {
+            session.set(1500.0 + f64::from(step) * 10.0);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let summary = session.finish(true).await;
+
+        let writes = vehicle.writes();
+        assert_eq!(writes.first(), Some(&1510.0));
+        assert_eq!(writes.last(), Some(&2000.0));
+        assert!(writes.len() <= 5, "{writes:?}");
+        assert_eq!(summary.updates, 50);
+        assert_eq!(summary.writes, writes.len() as u32);
+        assert_eq!(summary.value, Some(2000.0));
+        assert_eq!(summary.last_error, None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn values_queued_behind_a_write_collapse_to_the_last() {
+        let vehicle = SlowVehicle::default();
+        let session = start(&vehicle);
+
+        session.set(1600.0);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        session.set(1700.0);
+        session.set(1800.0);
+        session.set(1900.0);
+        tokio::time::sleep(Duration::from_millis(700)).await;
+
+        assert_eq!(vehicle.writes(), vec![1600.0, 1900.0]);
+        session.finish(true).await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn revert_and_uncommitted_end_restore_the_original() {
+        let vehicle = SlowVehicle::default();
+        let session = start(&vehicle);
+
+        session.set(2000.0);
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        session.revert();
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert_eq!(vehicle.writes(), vec![2000.0, 1500.0]);
+
+        session.set(2500.0);
+        let summary = session.finish(false).await;
+        assert_eq!(vehicle.writes(), vec![2000.0, 1500.0]);
+        assert_eq!(summary.value, Some(1500.0));
+        assert_eq!(summary.original_value, 1500.0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn untouched_session_ends_without_writing() {
+        let vehicle = SlowVehicle::default();
+        let summary = start(&vehicle).finish(false).await;
+
+        assert!(vehicle.writes().is_empty());
+        assert_eq!(summary.writes, 0);
+        assert_eq!(summary.value, None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_lost_echo_times_out_and_the_next_value_goes_out() {
+        let session = TuneSession::start(SilentVehicle, "RTL_ALT".to_string(), 1500.0);
+
+        session.set(1600.0);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        session.set(1700.0);
+        let summary = session.finish(true).await;
+
+        assert_eq!(summary.writes, 2);
+        assert_eq!(summary.value, None);
+        assert_eq!(
+            summary.last_error.as_deref(),
+            Some("no echo for RTL_ALT within 1s")
+        );
+    }
+}
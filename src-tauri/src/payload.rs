@@ -0,0 +1,93 @@
+use ironwing_core::dialect::{MavCmd, MavMessage};
+use ironwing_core::event_names;
+use ironwing_core::live_runtime;
+use ironwing_core::payload::{WINCH_STATUS_MESSAGE_ID, gripper_params, winch_params, winch_status};
+use mavkit::Vehicle;
+use mavlink::MavlinkVersion;
+use serde_json::json;
+use tauri::Manager;
+
+use crate::AppState;
+use crate::helpers::{ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{AppError, GripperCommand, OperationId, WinchCommand};
+use crate::journal;
+
+/// Grab or release with a gripper via MAV_CMD_DO_GRIPPER.
+#[tauri::command]
+pub(crate) async fn gripper_control(
+    state: tauri::State<'_, AppState>,
+    instance: u8,
+    action: GripperCommand,
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::GripperControl,
+        json!({ "instance": instance, "action": action }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::GripperControl).await?;
+            let vehicle = with_vehicle(&state).await?;
+            vehicle
+                .raw()
+                .command_long(
+                    MavCmd::MAV_CMD_DO_GRIPPER as u16,
+                    gripper_params(instance, action),
+                )
+                .await?;
+            Ok(())
+        },
+    )
+    .await
+}
+
+/// Drive a winch via MAV_CMD_DO_WINCH. Rates above the configured
+/// `winch_max_rate_mps` are refused before anything is sent.
+#[tauri::command]
+pub(crate) async fn winch_control(
+    state: tauri::State<'_, AppState>,
+    instance: u8,
+    action: WinchCommand,
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::WinchControl,
+        json!({ "instance": instance, "action": action }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::WinchControl).await?;
+            let max_rate_mps = state.settings.lock().await.winch_max_rate_mps;
+            let params =
+                winch_params(instance, action, max_rate_mps).map_err(AppError::validation)?;
+            let vehicle = with_vehicle(&state).await?;
+            vehicle
+                .raw()
+                .command_long(MavCmd::MAV_CMD_DO_WINCH as u16, params)
+                .await?;
+            Ok(())
+        },
+    )
+    .await
+}
+
+/// Relays WINCH_STATUS from the bound vehicle as `winch://status`.
+pub(crate) async fn winch_status_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        if raw_msg.message_id != WINCH_STATUS_MESSAGE_ID || raw_msg.system_id != system_id {
+            continue;
+        }
+        let Ok(MavMessage::WINCH_STATUS(data)) =
+            MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+        else {
+            continue;
+        };
+        let state: tauri::State<'_, AppState> = handle.state();
+        live_runtime::emit_scoped(
+            &state.live_runtime,
+            event_names::WINCH_STATUS,
+            winch_status(&data),
+        );
+    }
+}
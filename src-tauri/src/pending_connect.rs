@@ -0,0 +1,87 @@
+use ironwing_core::event_names;
+use ironwing_core::pending_connect::{launch_argument, pending_connect};
+use tauri::Manager;
+
+use crate::AppState;
+use crate::e2e_emit::emit_event;
+use crate::ipc::{AppError, PendingConnect, PendingConnectSource};
+
+/// Park a connection asked for from outside the app until the frontend
+/// takes it, and tell a frontend that is already running. A malformed one
+/// is kept too, so the user sees why nothing connected.
+async fn offer(app: &tauri::AppHandle, pending: PendingConnect) {
+    if let PendingConnect::Invalid { input, message, .. } = &pending {
+        tracing::warn!("ignoring connection `{input}`: {message}");
+    }
+    let state = app.state::<AppState>();
+    *state.pending_connect.lock().await = Some(pending.clone());
+    emit_event(app, event_names::PENDING_CONNECT, &pending);
+}
+
+async fn offer_input(app: &tauri::AppHandle, source: PendingConnectSource, input: &str) {
+    let auto_connect = app
+        .state::<AppState>()
+        .settings
+        .lock()
+        .await
+        .auto_connect_launch_link;
+    offer(app, pending_connect(source, input, auto_connect)).await;
+}
+
+/// Pick up `--connect` or a deep link from the process arguments. Runs
+/// after settings are loaded so `auto_connect_launch_link` applies.
+pub(crate) async fn capture_launch_argument(app: &tauri::AppHandle) {
+    match launch_argument(std::env::args().skip(1)) {
+        Some(Ok((source, input))) => offer_input(app, source, &input).await,
+        Some(Err(message)) => {
+            let pending = PendingConnect::Invalid {
+                source: PendingConnectSource::CommandLine,
+                input: String::new(),
+                message,
+            };
+            offer(app, pending).await;
+        }
+        None => {}
+    }
+}
+
+/// `ironwing://connect` links, both the one that launched the app and ones
+/// opened while it runs.
+#[cfg(target_os = "android")]
+pub(crate) fn register_deep_links(app: &tauri::AppHandle) {
+    use tauri_plugin_deep_link::DeepLinkExt;
+
+    let deep_link = app.deep_link();
+    match deep_link.get_current() {
+        Ok(Some(urls)) => {
+            if let Some(url) = urls.last() {
+                tauri::async_runtime::block_on(offer_input(
+                    app,
+                    PendingConnectSource::DeepLink,
+                    url.as_str(),
+                ));
+            }
+        }
+        Ok(None) => {}
+        Err(error) => tracing::warn!("failed to read the launch link: {error}"),
+    }
+    let handle = app.clone();
+    deep_link.on_open_url(move |event| {
+        let Some(url) = event.urls().last().map(|url| url.to_string()) else {
+            return;
+        };
+        let handle = handle.clone();
+        tauri::async_runtime::spawn(async move {
+            offer_input(&handle, PendingConnectSource::DeepLink, &url).await;
+        });
+    });
+}
+
+/// The connection waiting from the command line or a deep link, if any.
+/// Taking it clears it, so the connect flow runs once.
+#[tauri::command]
+pub(crate) async fn pending_connect_take(
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<PendingConnect>, AppError> {
+    Ok(state.pending_connect.lock().await.take())
+}
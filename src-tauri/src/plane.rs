@@ -0,0 +1,264 @@
+use ironwing_core::dialect::MavCmd;
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::param_value::param_value;
+use ironwing_core::plane::{
+    ARSPD_USE_PARAM, LoiterTurnCounter, TKOFF_ALT_PARAM, cruise_throttle_params,
+    cruise_throttle_warnings, ensure_plane, plane_launch, validate_cruise_throttle,
+    validate_loiter,
+};
+use ironwing_core::takeoff::validate_takeoff_altitude;
+use mavkit::Vehicle;
+use serde_json::json;
+use tauri::Manager;
+use tokio_util::sync::CancellationToken;
+
+use crate::AppState;
+use crate::helpers::{ensure_live_write_allowed, vehicle_is_armed, with_vehicle};
+use crate::ipc::{
+    AppError, GotoFrame, GotoOptions, GotoRequest, OperationId, PlaneCruiseThrottleResult,
+    PlaneLaunch, PlaneLoiterResult, PlaneTakeoffResult,
+};
+use crate::journal;
+use crate::param_changes::expect_param_writes;
+use crate::takeoff::GUIDED_MODE_NAME;
+
+const TAKEOFF_MODE_NAME: &str = "TAKEOFF";
+
+/// The connected vehicle, refused unless its heartbeat says fixed-wing.
+async fn with_plane(state: &AppState) -> Result<Vehicle, AppError> {
+    let vehicle = with_vehicle(state).await?;
+    ensure_plane(vehicle.identity().vehicle_type).map_err(AppError::validation)?;
+    Ok(vehicle)
+}
+
+fn param_f32(vehicle: &Vehicle) -> Result<impl Fn(&str) -> Option<f32>, AppError> {
+    let store = live_commands::param_get_all(vehicle).map_err(AppError::from)?;
+    let encoding = live_commands::param_encoding(vehicle);
+    Ok(move |name: &str| {
+        store
+            .params
+            .get(name)
+            .map(|param| param_value(param, encoding).as_f64() as f32)
+    })
+}
+
+fn current_mode(vehicle: &Vehicle) -> Option<(String, u32)> {
+    vehicle
+        .available_modes()
+        .current()
+        .latest()
+        .map(|mode| (mode.name.clone(), mode.custom_mode))
+}
+
+fn find_mode(vehicle: &Vehicle, name: &str) -> Option<u32> {
+    vehicle
+        .available_modes()
+        .iter()
+        .find(|mode| mode.name.eq_ignore_ascii_case(name))
+        .map(|mode| mode.custom_mode)
+}
+
+/// The launch the plane's TKOFF_* parameters expect.
+#[tauri::command]
+pub(crate) async fn plane_launch_info(
+    state: tauri::State<'_, AppState>,
+) -> Result<PlaneLaunch, AppError> {
+    let vehicle = with_plane(&state).await?;
+    plane_launch(param_f32(&vehicle)?).map_err(AppError::validation)
+}
+
+/// Set TKOFF_ALT and switch to Takeoff mode. The plane is not armed here:
+/// with a hand or catapult launch the motor starts on the throw, so arming
+/// stays with the operator holding it.
+#[tauri::command]
+pub(crate) async fn plane_takeoff(
+    state: tauri::State<'_, AppState>,
+    altitude_m: f32,
+) -> Result<PlaneTakeoffResult, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::PlaneTakeoff,
+        json!({ "altitude_m": altitude_m }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::PlaneTakeoff).await?;
+            let max_alt_m = state.settings.lock().await.takeoff_max_alt_m;
+            validate_takeoff_altitude(altitude_m, max_alt_m)?;
+            let vehicle = with_plane(&state).await?;
+            let launch = plane_launch(param_f32(&vehicle)?).map_err(AppError::validation)?;
+            let takeoff_mode = find_mode(&vehicle, TAKEOFF_MODE_NAME).ok_or_else(|| {
+                AppError::validation(
+                    "vehicle has no Takeoff mode; ArduPlane 4.1 or newer is needed",
+                )
+            })?;
+
+            expect_param_writes(&state, [TKOFF_ALT_PARAM]);
+            let written =
+                live_commands::param_write(&vehicle, TKOFF_ALT_PARAM, f64::from(altitude_m))
+                    .await
+                    .map_err(AppError::from)?;
+            if !written.success {
+                return Err(AppError::internal(format!(
+                    "vehicle rejected {TKOFF_ALT_PARAM}"
+                )));
+            }
+            live_commands::set_flight_mode(&vehicle, takeoff_mode)
+                .await
+                .map_err(AppError::from)?;
+            Ok(PlaneTakeoffResult {
+                launch,
+                altitude_m,
+                armed: vehicle_is_armed(&vehicle),
+            })
+        },
+    )
+    .await
+}
+
+/// Count turns around the loiter and put the plane back in `return_mode`
+/// once `turns` are flown. Leaving Guided or losing position ends the watch.
+async fn watch_loiter_turns(
+    app: tauri::AppHandle,
+    vehicle: Vehicle,
+    centre: (f64, f64),
+    turns: u32,
+    return_mode: u32,
+    cancel: CancellationToken,
+) {
+    let mut positions = vehicle.telemetry().position().global().subscribe();
+    let mut counter = LoiterTurnCounter::new(centre.0, centre.1);
+    loop {
+        let position = tokio::select! {
+            _ = cancel.cancelled() => return,
+            sample = positions.recv() => match sample {
+                Some(sample) => sample.value,
+                None => return,
+            },
+        };
+        let in_guided = current_mode(&vehicle)
+            .is_some_and(|(name, _)| name.eq_ignore_ascii_case(GUIDED_MODE_NAME));
+        if !in_guided {
+            return;
+        }
+        if counter.observe(position.latitude_deg, position.longitude_deg) >= turns {
+            break;
+        }
+    }
+    let state = app.state::<AppState>();
+    let mut slot = state.plane_loiter.lock().await;
+    if cancel.is_cancelled() {
+        return;
+    }
+    slot.take();
+    drop(slot);
+    tracing::info!("loiter finished {turns} turns");
+    if let Err(error) = live_commands::set_flight_mode(&vehicle, return_mode).await {
+        tracing::warn!("failed to leave the loiter after {turns} turns: {error}");
+    }
+}
+
+/// Circle the plane's current position at `radius_m` through DO_REPOSITION.
+/// With `turns`, it goes back to the mode it was flying in (an Auto mission
+/// resumes) once that many circles are done.
+#[tauri::command]
+pub(crate) async fn plane_loiter_here(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+    radius_m: f32,
+    turns: Option<u32>,
+) -> Result<PlaneLoiterResult, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::PlaneLoiterHere,
+        json!({ "radius_m": radius_m, "turns": turns }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::PlaneLoiterHere).await?;
+            validate_loiter(radius_m, turns).map_err(AppError::validation)?;
+            let vehicle = with_plane(&state).await?;
+            if !vehicle_is_armed(&vehicle) {
+                return Err(AppError::validation("vehicle is disarmed"));
+            }
+            let position = vehicle
+                .telemetry()
+                .position()
+                .global()
+                .latest()
+                .ok_or("vehicle position is unknown")?
+                .value;
+            let previous_mode = current_mode(&vehicle)
+                .filter(|(name, _)| !name.eq_ignore_ascii_case(GUIDED_MODE_NAME));
+
+            let request = GotoRequest {
+                latitude_deg: position.latitude_deg,
+                longitude_deg: position.longitude_deg,
+                altitude_m: position.altitude_msl_m as f32,
+                options: GotoOptions {
+                    acceptance_radius_m: Some(radius_m),
+                    frame: GotoFrame::Amsl,
+                    ..GotoOptions::default()
+                },
+            };
+            if let Some(cancel) = state.plane_loiter.lock().await.take() {
+                cancel.cancel();
+            }
+            live_commands::guided_goto_with_options(&vehicle, &request)
+                .await
+                .map_err(AppError::from)?;
+
+            let return_mode = turns.and(previous_mode);
+            if let (Some(turns), Some((_, custom_mode))) = (turns, &return_mode) {
+                let cancel = CancellationToken::new();
+                *state.plane_loiter.lock().await = Some(cancel.clone());
+                tokio::spawn(watch_loiter_turns(
+                    app.clone(),
+                    vehicle.clone(),
+                    (request.latitude_deg, request.longitude_deg),
+                    turns,
+                    *custom_mode,
+                    cancel,
+                ));
+            }
+            Ok(PlaneLoiterResult {
+                latitude_deg: request.latitude_deg,
+                longitude_deg: request.longitude_deg,
+                altitude_msl_m: request.altitude_m,
+                radius_m,
+                turns: return_mode.as_ref().and(turns),
+                return_mode: return_mode.map(|(name, _)| name),
+            })
+        },
+    )
+    .await
+}
+
+/// Change the cruise throttle with DO_CHANGE_SPEED, leaving the speed target
+/// alone.
+#[tauri::command]
+pub(crate) async fn plane_set_cruise_throttle(
+    state: tauri::State<'_, AppState>,
+    throttle_pct: f32,
+) -> Result<PlaneCruiseThrottleResult, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::PlaneSetCruiseThrottle,
+        json!({ "throttle_pct": throttle_pct }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::PlaneSetCruiseThrottle).await?;
+            validate_cruise_throttle(throttle_pct).map_err(AppError::validation)?;
+            let vehicle = with_plane(&state).await?;
+            let warnings = cruise_throttle_warnings(param_f32(&vehicle)?(ARSPD_USE_PARAM));
+            vehicle
+                .raw()
+                .command_long(
+                    MavCmd::MAV_CMD_DO_CHANGE_SPEED as u16,
+                    cruise_throttle_params(throttle_pct),
+                )
+                .await
+                .map_err(AppError::from)?;
+            Ok(PlaneCruiseThrottleResult {
+                throttle_pct,
+                warnings,
+            })
+        },
+    )
+    .await
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use ironwing_core::dialect::MavMessage;
+use ironwing_core::event_names;
+use ironwing_core::live_runtime;
+use ironwing_core::precision_landing::{
+    DEFAULT_TARGET_WINDOW, LANDING_TARGET_MESSAGE_ID, OPTICAL_FLOW_MESSAGE_ID,
+    OPTICAL_FLOW_RAD_MESSAGE_ID, landing_target, optical_flow_quality, optical_flow_rad_quality,
+};
+use mavkit::Vehicle;
+use mavlink::MavlinkVersion;
+use tauri::Manager;
+
+use crate::AppState;
+use crate::ipc::{AppError, PrecisionLandingStatus};
+
+/// Relays LANDING_TARGET as `precision_landing://target` and OPTICAL_FLOW /
+/// OPTICAL_FLOW_RAD as `optical_flow://quality` from the bound vehicle.
+pub(crate) async fn precision_landing_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        if raw_msg.system_id != system_id
+            || ![
+                LANDING_TARGET_MESSAGE_ID,
+                OPTICAL_FLOW_MESSAGE_ID,
+                OPTICAL_FLOW_RAD_MESSAGE_ID,
+            ]
+            .contains(&raw_msg.message_id)
+        {
+            continue;
+        }
+        let Ok(message) =
+            MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+        else {
+            continue;
+        };
+        let state: tauri::State<'_, AppState> = handle.state();
+        let flow = match message {
+            MavMessage::LANDING_TARGET(data) => {
+                let target = landing_target(&data);
+                state
+                    .live_runtime
+                    .with_runtime(|runtime| runtime.record_landing_target(target.clone()));
+                live_runtime::emit_scoped(
+                    &state.live_runtime,
+                    event_names::PRECISION_LANDING_TARGET,
+                    target,
+                );
+                continue;
+            }
+            MavMessage::OPTICAL_FLOW(data) => optical_flow_quality(&data),
+            MavMessage::OPTICAL_FLOW_RAD(data) => optical_flow_rad_quality(&data),
+            _ => continue,
+        };
+        live_runtime::emit_scoped(&state.live_runtime, event_names::OPTICAL_FLOW_QUALITY, flow);
+    }
+}
+
+/// Whether a landing target was seen in the last `window_s` seconds
+/// (default 2).
+#[tauri::command]
+pub(crate) fn precision_landing_status(
+    state: tauri::State<'_, AppState>,
+    window_s: Option<f64>,
+) -> Result<PrecisionLandingStatus, AppError> {
+    let window = match window_s {
+        None => DEFAULT_TARGET_WINDOW,
+        Some(seconds) => Duration::try_from_secs_f64(seconds)
+            .ok()
+            .filter(|window| !window.is_zero())
+            .ok_or_else(|| AppError::validation("window_s must be greater than zero"))?,
+    };
+    Ok(state
+        .live_runtime
+        .with_runtime(|runtime| runtime.precision_landing_status(window)))
+}
@@ -0,0 +1,98 @@
+use ironwing_core::preflight_position::{PositionCheckInputs, PositionFix, position_issues};
+use mavkit::Vehicle;
+
+use crate::AppState;
+use crate::helpers::with_vehicle;
+use crate::ipc::{AppError, PositionIssue};
+
+fn vehicle_inputs(state: &AppState, vehicle: &Vehicle) -> PositionCheckInputs {
+    let telemetry = vehicle.telemetry();
+    let position = telemetry
+        .position()
+        .global()
+        .latest()
+        .map(|sample| PositionFix {
+            latitude_deg: sample.value.latitude_deg,
+            longitude_deg: sample.value.longitude_deg,
+            altitude_msl_m: Some(f64::from(sample.value.altitude_msl_m)),
+        });
+    let home = state.live_runtime.with_runtime(|runtime| {
+        runtime
+            .session_context()
+            .home_position
+            .as_ref()
+            .map(|home| PositionFix {
+                latitude_deg: home.latitude_deg,
+                longitude_deg: home.longitude_deg,
+                altitude_msl_m: Some(f64::from(home.altitude_m)),
+            })
+    });
+    PositionCheckInputs {
+        vehicle: position,
+        home,
+        armed: telemetry
+            .armed()
+            .latest()
+            .is_some_and(|sample| sample.value),
+        phone: None,
+        terrain_elevation_m: telemetry
+            .terrain()
+            .clearance()
+            .latest()
+            .map(|sample| f64::from(sample.value.terrain_height_m)),
+    }
+}
+
+/// This device's own location. Only Android exposes one; desktop skips the
+/// comparison.
+#[cfg(target_os = "android")]
+async fn phone_position(app: &tauri::AppHandle) -> Option<PositionFix> {
+    use tauri_plugin_geolocation::{GeolocationExt, PositionOptions};
+
+    let app = app.clone();
+    let position = tokio::task::spawn_blocking(move || {
+        app.geolocation()
+            .get_current_position(Some(PositionOptions {
+                enable_high_accuracy: false,
+                timeout: 5_000,
+                maximum_age: 60_000,
+            }))
+    })
+    .await
+    .ok()?;
+    match position {
+        Ok(position) => Some(PositionFix {
+            latitude_deg: position.coords.latitude,
+            longitude_deg: position.coords.longitude,
+            altitude_msl_m: None,
+        }),
+        Err(error) => {
+            tracing::info!("skipping device position check: {error}");
+            None
+        }
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+async fn phone_position(_app: &tauri::AppHandle) -> Option<PositionFix> {
+    None
+}
+
+/// Sanity-check the vehicle's position before flight: against this device's
+/// location, against home while disarmed, and its altitude against the
+/// terrain. `terrain_elevation_m` is the frontend's DEM sample at the vehicle;
+/// without one the vehicle's own TERRAIN_REPORT is used.
+#[tauri::command]
+pub(crate) async fn preflight_position_check(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    terrain_elevation_m: Option<f64>,
+) -> Result<Vec<PositionIssue>, AppError> {
+    let vehicle = with_vehicle(&state).await?;
+    let mut inputs = vehicle_inputs(&state, &vehicle);
+    inputs.phone = phone_position(&app).await;
+    if terrain_elevation_m.is_some() {
+        inputs.terrain_elevation_m = terrain_elevation_m;
+    }
+    Ok(position_issues(&inputs))
+}
@@ -0,0 +1,175 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::param_value::param_value;
+use ironwing_core::rc_override::{
+    RC_OVERRIDE_NEUTRAL_FRAMES, RC_OVERRIDE_PERIOD, RcOverrideStream, is_airborne, neutral_values,
+    override_channels, release_values, throttle_neutral,
+};
+use serde_json::json;
+use tokio::time::MissedTickBehavior;
+use web_time::Instant;
+
+use crate::AppState;
+use crate::helpers::{ensure_expert_mode, ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{AppError, OperationId};
+use crate::journal;
+
+/// The RC override stream and the values it sends.
+#[derive(Default)]
+pub(crate) struct RcOverrideState {
+    task: Option<tokio::task::AbortHandle>,
+    stream: Option<Arc<Mutex<RcOverrideStream>>>,
+}
+
+impl RcOverrideState {
+    fn running(&self) -> Option<&Arc<Mutex<RcOverrideStream>>> {
+        self.task
+            .as_ref()
+            .filter(|task| !task.is_finished())
+            .and(self.stream.as_ref())
+    }
+}
+
+/// A panic while the stream was locked must not take `rc_override_stop`,
+/// which hands the channels back, down with it.
+fn lock_stream(stream: &Mutex<RcOverrideStream>) -> MutexGuard<'_, RcOverrideStream> {
+    stream
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The throttle channel and its stopped PWM from the downloaded parameters,
+/// or ArduPilot's defaults before they are downloaded.
+fn vehicle_throttle_neutral(vehicle: &mavkit::Vehicle) -> (u8, u16) {
+    let store = live_commands::param_get_all(vehicle).ok();
+    let encoding = live_commands::param_encoding(vehicle);
+    throttle_neutral(vehicle.identity().vehicle_type, |name| {
+        store
+            .as_ref()?
+            .params
+            .get(name)
+            .map(|param| param_value(param, encoding).as_f64() as f32)
+    })
+}
+
+async fn send(vehicle: &mavkit::Vehicle, values: &[Option<u16>]) {
+    if let Err(error) = live_commands::rc_override(vehicle, override_channels(values)).await {
+        tracing::warn!("failed to send RC override: {error}");
+    }
+}
+
+/// Resends the latest values every period until the deadman expires, then
+/// releases every channel.
+async fn stream_rc_override(vehicle: mavkit::Vehicle, stream: Arc<Mutex<RcOverrideStream>>) {
+    let mut interval = tokio::time::interval(RC_OVERRIDE_PERIOD);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let frame = lock_stream(&stream).frame(Instant::now());
+        let Some(values) = frame else {
+            break;
+        };
+        send(&vehicle, &values).await;
+    }
+    tracing::warn!("RC override values went stale; releasing all channels");
+    send(&vehicle, &release_values()).await;
+}
+
+/// Act as the RC source by streaming RC_CHANNELS_OVERRIDE at 10 Hz, starting
+/// with every channel released. Needs expert mode and refuses while the
+/// vehicle is airborne. Values must be refreshed with `rc_override_set` at
+/// least once a second or the stream releases everything and stops.
+#[tauri::command]
+pub(crate) async fn rc_override_start(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::RcOverrideStart,
+        json!({}),
+        async {
+            ensure_expert_mode(&state, "RC override").await?;
+            ensure_live_write_allowed(state.inner(), OperationId::RcOverrideStart).await?;
+            let vehicle = with_vehicle(&state).await?;
+            let airborne = state.live_runtime.with_runtime(|runtime| {
+                runtime
+                    .session_context()
+                    .vehicle_state
+                    .as_ref()
+                    .is_some_and(is_airborne)
+            });
+            if airborne {
+                return Err(AppError::validation(
+                    "RC override cannot start while the vehicle is airborne",
+                ));
+            }
+
+            let mut rc_override = state.rc_override.lock().await;
+            if let Some(task) = rc_override.task.take() {
+                task.abort();
+            }
+            let stream = Arc::new(Mutex::new(RcOverrideStream::new(Instant::now())));
+            let task = tokio::spawn(stream_rc_override(vehicle, stream.clone()));
+            rc_override.task = Some(task.abort_handle());
+            rc_override.stream = Some(stream);
+            drop(rc_override);
+            // Owned with the link's other tasks so a disconnect stops it too.
+            state.background_tasks.lock().await.push(task);
+            Ok(())
+        },
+    )
+    .await
+}
+
+/// Replace the override values and refresh the deadman. `channels[i]` is
+/// channel `i + 1` in microseconds; `None` releases that channel.
+#[tauri::command]
+pub(crate) async fn rc_override_set(
+    state: tauri::State<'_, AppState>,
+    channels: Vec<Option<u16>>,
+) -> Result<(), AppError> {
+    ensure_live_write_allowed(state.inner(), OperationId::RcOverrideSet).await?;
+    let rc_override = state.rc_override.lock().await;
+    let stream = rc_override
+        .running()
+        .ok_or_else(|| AppError::validation("RC override is not running"))?;
+    lock_stream(stream)
+        .set(channels, Instant::now())
+        .map_err(AppError::validation)
+}
+
+/// Stop the stream, hold the overridden channels at stick centre (throttle at
+/// its stopped position) for half a second, then hand every channel back to
+/// the receiver.
+#[tauri::command]
+pub(crate) async fn rc_override_stop(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::RcOverrideStop,
+        json!({}),
+        async {
+            let (task, stream) = {
+                let mut rc_override = state.rc_override.lock().await;
+                (rc_override.task.take(), rc_override.stream.take())
+            };
+            // A finished stream has already released everything.
+            let Some(task) = task.filter(|task| !task.is_finished()) else {
+                return Ok(());
+            };
+            task.abort();
+            let vehicle = with_vehicle(&state).await?;
+            let throttle = vehicle_throttle_neutral(&vehicle);
+            let neutral = stream
+                .map(|stream| neutral_values(lock_stream(&stream).values(), throttle))
+                .unwrap_or_default();
+            let mut interval = tokio::time::interval(RC_OVERRIDE_PERIOD);
+            for _ in 0..RC_OVERRIDE_NEUTRAL_FRAMES {
+                interval.tick().await;
+                send(&vehicle, &neutral).await;
+            }
+            interval.tick().await;
+            live_commands::rc_override(&vehicle, override_channels(&release_values())).await?;
+            Ok(())
+        },
+    )
+    .await
+}
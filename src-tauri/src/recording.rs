@@ -1,9 +1,12 @@
-use std::io::BufWriter;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use ironwing_core::dialect::MavMessage;
+use ironwing_core::time_sync::system_time_annotation;
+use ironwing_core::tlog_segments::{SegmentedTlogWriter, active_rotation};
+use ironwing_core::vehicle_snapshot::unix_epoch_usec;
 use mavkit::{Vehicle, tlog::TlogWriter};
 use mavlink::MavlinkVersion;
 use tauri::Manager;
@@ -11,8 +14,9 @@ use tauri::Manager;
 use crate::{
     AppState,
     ipc::{
-        OperationFailure, OperationId, Reason, ReasonKind, RecordingMode, RecordingSettings,
-        RecordingSettingsResult, RecordingStartRequest, RecordingStatus,
+        AppError, OperationFailure, OperationId, Reason, ReasonKind, RecordingMode,
+        RecordingSettings, RecordingSettingsResult, RecordingStartRequest, RecordingStatus,
+        Settings, TlogFsyncPolicy,
     },
     log_library::LogLibrary,
 };
@@ -21,6 +25,7 @@ const RECORDINGS_DIR_SEGMENTS: [&str; 2] = ["logs", "recordings"];
 const AUTO_RECORD_FILENAME_TEMPLATE: &str =
     "YYYY-MM-DD_HH-MM-SS_{vehicle-or-sysid-or-unknown}.tlog";
 const ADD_COMPLETED_RECORDINGS_TO_LIBRARY: bool = true;
+const TLOG_TIME_ANNOTATION_INTERVAL: Duration = Duration::from_secs(10);
 
 enum CompletedRecordingRegistration {
     Skip,
@@ -54,6 +59,8 @@ enum RecorderState {
 struct RecorderRuntime {
     generation: u64,
     state: RecorderState,
+    /// Destination of the most recent recording, kept after it stops.
+    last_destination_path: Option<String>,
 }
 
 pub(crate) struct StoppedRecording {
@@ -74,6 +81,7 @@ impl TlogRecorderHandle {
             state: Arc::new(std::sync::Mutex::new(RecorderRuntime {
                 generation: 0,
                 state: RecorderState::Idle,
+                last_destination_path: None,
             })),
         }
     }
@@ -107,10 +115,14 @@ impl TlogRecorderHandle {
                 .map_err(|error| format!("failed to create recording directory: {error}"))?;
         }
 
-        let file = std::fs::File::create(&destination_path)
-            .map_err(|e| format!("failed to create file: {e}"))?;
-        let writer = BufWriter::new(file);
-        let mut tlog_writer = TlogWriter::new(writer, MavlinkVersion::V2);
+        let mut tlog_writer = SegmentedTlogWriter::create(
+            Path::new(&destination_path),
+            request.rotation,
+            request.fsync,
+        )
+        .map_err(|e| format!("failed to create file: {e}"))?;
+        // With rotation the recording is opened through its segment index.
+        let destination_path = tlog_writer.path().to_string_lossy().to_string();
 
         let file_name = Path::new(&destination_path)
             .file_name()
@@ -128,56 +140,73 @@ impl TlogRecorderHandle {
         let raw_stream = vehicle.raw().subscribe();
         let (cancel_tx, mut cancel_rx) = tokio::sync::oneshot::channel();
 
+        let app = app.clone();
         let handle = tokio::spawn(async move {
             use mavlink::Message;
             use tokio_stream::StreamExt;
             tokio::pin!(raw_stream);
+            let mut annotate = tokio::time::interval(TLOG_TIME_ANNOTATION_INTERVAL);
             loop {
-                tokio::select! {
+                let (header, msg) = tokio::select! {
                     _ = &mut cancel_rx => break,
+                    _ = annotate.tick() => match vehicle_time_annotation(&app).await {
+                        Some(annotation) => annotation,
+                        None => continue,
+                    },
                     maybe_msg = raw_stream.next() => {
-                        match maybe_msg {
-                            Some(raw_msg) => {
-                                // Reconstruct a MavHeader from the RawMessage fields
-                                let header = mavlink::MavHeader {
-                                    system_id: raw_msg.system_id,
-                                    component_id: raw_msg.component_id,
-                                    sequence: 0,
-                                };
-                                // Re-parse the raw payload back into a typed message
-                                if let Ok(msg) = mavkit::dialect::MavMessage::parse(
-                                    MavlinkVersion::V2,
-                                    raw_msg.message_id,
-                                    &raw_msg.payload,
-                                ) {
-                                    match tlog_writer.write_now(&header, &msg) {
-                                        Ok(n) => {
-                                            bytes_counter.fetch_add(n as u64, Ordering::Relaxed);
-                                        }
-                                        Err(e) => {
-                                            *runtime_failure_writer
-                                                .lock()
-                                                .unwrap_or_else(|poisoned| poisoned.into_inner()) =
-                                                Some(operation_failure(
-                                                    OperationId::RecordingStart,
-                                                    &format!("tlog write error: {e}"),
-                                                ));
-                                            tracing::warn!("tlog write error: {e}");
-                                            break;
-                                        }
-                                    }
-                                }
-                            }
-                            None => break,
-                        }
+                        let Some(raw_msg) = maybe_msg else {
+                            break;
+                        };
+                        // Reconstruct a MavHeader from the RawMessage fields
+                        let header = mavlink::MavHeader {
+                            system_id: raw_msg.system_id,
+                            component_id: raw_msg.component_id,
+                            sequence: 0,
+                        };
+                        // Re-parse the raw payload back into a typed message
+                        let Ok(msg) = MavMessage::parse(
+                            MavlinkVersion::V2,
+                            raw_msg.message_id,
+                            &raw_msg.payload,
+                        ) else {
+                            continue;
+                        };
+                        (header, msg)
+                    }
+                };
+                // Each frame is encoded whole before it reaches the segment
+                // writer, so rotation can only fall between frames.
+                let mut frame = Vec::new();
+                let encoded = TlogWriter::new(&mut frame, MavlinkVersion::V2)
+                    .write_now(&header, &msg)
+                    .map_err(|e| e.to_string());
+                let written = encoded
+                    .and_then(|_| tlog_writer.write_frame(&frame).map_err(|e| e.to_string()));
+                match written {
+                    Ok(()) => {
+                        bytes_counter.fetch_add(frame.len() as u64, Ordering::Relaxed);
+                    }
+                    Err(e) => {
+                        *runtime_failure_writer
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+                            Some(operation_failure(
+                                OperationId::RecordingStart,
+                                &format!("tlog write error: {e}"),
+                            ));
+                        tracing::warn!("tlog write error: {e}");
+                        break;
                     }
                 }
             }
-            let _ = tlog_writer.flush();
+            if let Err(e) = tlog_writer.finish() {
+                tracing::warn!("failed to finish tlog: {e}");
+            }
         });
 
         let name = file_name.clone();
         guard.generation = guard.generation.saturating_add(1);
+        guard.last_destination_path = Some(destination_path.clone());
         guard.state = RecorderState::Recording {
             cancel: cancel_tx,
             handle,
@@ -239,6 +268,18 @@ impl TlogRecorderHandle {
         };
     }
 
+    /// Path of the current or most recent recording, and whether it is
+    /// still being written.
+    pub(crate) fn latest_recording(&self) -> Option<(String, bool)> {
+        let guard = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        let path = guard.last_destination_path.clone()?;
+        let active = matches!(
+            guard.state,
+            RecorderState::Recording { .. } | RecorderState::Stopping { .. }
+        );
+        Some((path, active))
+    }
+
     pub(crate) fn status(&self) -> RecordingStatus {
         let guard = self.state.lock().unwrap_or_else(|e| e.into_inner());
         match &guard.state {
@@ -279,21 +320,43 @@ impl TlogRecorderHandle {
     }
 }
 
+/// The vehicle's boot and UTC clocks at this instant as a SYSTEM_TIME from the
+/// GCS, once time sync has an offset.
+async fn vehicle_time_annotation(
+    app: &tauri::AppHandle,
+) -> Option<(mavlink::MavHeader, MavMessage)> {
+    let state = app.try_state::<AppState>()?;
+    let time = state
+        .live_runtime
+        .with_runtime(|runtime| runtime.vehicle_clock())?
+        .vehicle_time(unix_epoch_usec())?;
+    let gcs = *state.gcs_identity.lock().await;
+    let header = mavlink::MavHeader {
+        system_id: gcs.system_id,
+        component_id: gcs.component_id,
+        sequence: 0,
+    };
+    Some((header, system_time_annotation(time)))
+}
+
 #[tauri::command]
 pub(crate) async fn recording_start(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
     request: RecordingStartRequest,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     let vehicle = crate::helpers::with_vehicle(&state).await?;
-    state.recorder.start(&vehicle, &app, request)
+    state
+        .recorder
+        .start(&vehicle, &app, request)
+        .map_err(AppError::from)
 }
 
 #[tauri::command]
 pub(crate) async fn recording_stop(
     state: tauri::State<'_, AppState>,
     app: tauri::AppHandle,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     if let Some(stopped_recording) = state.recorder.stop() {
         queue_stopped_recording_finalization(&state.recorder, &app, stopped_recording);
     }
@@ -308,7 +371,7 @@ pub(crate) fn recording_status(state: tauri::State<'_, AppState>) -> RecordingSt
 #[tauri::command]
 pub(crate) fn recording_settings_read(
     app: tauri::AppHandle,
-) -> Result<RecordingSettingsResult, String> {
+) -> Result<RecordingSettingsResult, AppError> {
     Ok(RecordingSettingsResult {
         operation_id: OperationId::RecordingSettingsRead,
         settings: default_recording_settings(&app)?,
@@ -319,7 +382,7 @@ pub(crate) fn recording_settings_read(
 pub(crate) fn recording_settings_write(
     app: tauri::AppHandle,
     settings: RecordingSettings,
-) -> Result<RecordingSettingsResult, String> {
+) -> Result<RecordingSettingsResult, AppError> {
     let defaults = default_recording_settings(&app)?;
     Ok(RecordingSettingsResult {
         operation_id: OperationId::RecordingSettingsWrite,
@@ -332,10 +395,16 @@ pub(crate) fn recording_settings_write(
     })
 }
 
-pub(crate) fn auto_record_start_request(enabled: bool) -> Option<RecordingStartRequest> {
-    enabled.then_some(RecordingStartRequest {
+/// Recordings started on connect rotate and sync as the settings say.
+pub(crate) fn auto_record_start_request(
+    enabled: bool,
+    settings: &Settings,
+) -> Option<RecordingStartRequest> {
+    enabled.then(|| RecordingStartRequest {
         destination_path: String::new(),
         mode: RecordingMode::AutoOnConnect,
+        rotation: active_rotation(settings.recording_rotation),
+        fsync: settings.recording_fsync,
     })
 }
 
@@ -500,7 +569,7 @@ fn format_unix_secs_utc(unix_secs: i64) -> String {
     format!("{year:04}-{month:02}-{day:02}_{hour:02}-{minute:02}-{second:02}")
 }
 
-fn civil_from_days(days_since_epoch: i64) -> (i64, i64, i64) {
+pub(crate) fn civil_from_days(days_since_epoch: i64) -> (i64, i64, i64) {
     let shifted_days = days_since_epoch + 719_468;
     let era = if shifted_days >= 0 {
         shifted_days
@@ -558,7 +627,8 @@ async fn register_completed_recording(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mavkit::dialect::{HEARTBEAT_DATA, MavAutopilot, MavModeFlag, MavState, MavType};
+    use crate::ipc::TlogRotation;
+    use ironwing_core::dialect::{HEARTBEAT_DATA, MavAutopilot, MavModeFlag, MavState, MavType};
     use mavkit::tlog::TlogWriter;
     use mavlink::{MavHeader, MavlinkVersion};
 
@@ -591,7 +661,7 @@ mod tests {
             component_id: 1,
             sequence: 0,
         };
-        let message = mavkit::dialect::MavMessage::HEARTBEAT(HEARTBEAT_DATA {
+        let message = ironwing_core::dialect::MavMessage::HEARTBEAT(HEARTBEAT_DATA {
             custom_mode: 3,
             mavtype: MavType::MAV_TYPE_QUADROTOR,
             autopilot: MavAutopilot::MAV_AUTOPILOT_ARDUPILOTMEGA,
@@ -619,6 +689,7 @@ mod tests {
                 .lock()
                 .unwrap_or_else(|poisoned| poisoned.into_inner()) = RecorderRuntime {
                 generation: 1,
+                last_destination_path: None,
                 state: RecorderState::Recording {
                     cancel,
                     handle,
@@ -677,6 +748,7 @@ mod tests {
                 .lock()
                 .unwrap_or_else(|poisoned| poisoned.into_inner()) = RecorderRuntime {
                 generation: 1,
+                last_destination_path: None,
                 state: RecorderState::Recording {
                     cancel,
                     handle,
@@ -761,6 +833,7 @@ mod tests {
                 .lock()
                 .unwrap_or_else(|poisoned| poisoned.into_inner()) = RecorderRuntime {
                 generation: 1,
+                last_destination_path: None,
                 state: RecorderState::Recording {
                     cancel,
                     handle: tokio::spawn(async {
@@ -842,13 +915,25 @@ mod tests {
 
     #[test]
     fn auto_record_start_request_follows_connect_setting() {
-        assert_eq!(auto_record_start_request(false), None);
+        let mut settings = Settings::default();
+        assert_eq!(auto_record_start_request(false, &settings), None);
         assert_eq!(
-            auto_record_start_request(true),
+            auto_record_start_request(true, &settings),
             Some(RecordingStartRequest {
                 destination_path: String::new(),
                 mode: RecordingMode::AutoOnConnect,
+                rotation: None,
+                fsync: TlogFsyncPolicy::OsDefault,
             })
         );
+
+        settings.recording_rotation = TlogRotation {
+            max_segment_minutes: Some(10),
+            max_segment_mb: None,
+        };
+        settings.recording_fsync = TlogFsyncPolicy::PerSecond;
+        let request = auto_record_start_request(true, &settings).unwrap();
+        assert_eq!(request.rotation, Some(settings.recording_rotation));
+        assert_eq!(request.fsync, TlogFsyncPolicy::PerSecond);
     }
 }
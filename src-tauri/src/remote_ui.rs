@@ -3,11 +3,17 @@ use std::net::SocketAddr;
 use serde::{Serialize, de::DeserializeOwned};
 use serde_json::{Value, json};
 use tauri::Manager;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::broadcast;
 
-use crate::{AppState, bluetooth, commands, connection, firmware, logs, recording};
+use crate::http::{
+    read_http_request, write_json, write_response, write_sse_event, write_sse_headers,
+};
+use crate::ipc::{AppError, TelemetryEncoding};
+use crate::{
+    AppState, bluetooth, commands, connection, disconnect_action, firmware, local_api, log_follow,
+    logs, param_tune, recording, tune_capture,
+};
 
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct RemoteUiEvent {
@@ -22,6 +28,14 @@ impl RemoteUiEvent {
             payload: serde_json::to_value(payload)?,
         })
     }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.event
+    }
+
+    pub(crate) fn payload(&self) -> &Value {
+        &self.payload
+    }
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -31,16 +45,8 @@ struct RemoteInvokeRequest {
     args: Value,
 }
 
-#[derive(Debug)]
-struct HttpRequest {
-    method: String,
-    path: String,
-    body: Vec<u8>,
-}
-
 const DEFAULT_REMOTE_UI_HOST: &str = "127.0.0.1";
 const DEFAULT_REMOTE_UI_PORT: u16 = 14242;
-const MAX_REQUEST_BYTES: usize = 1024 * 1024;
 
 pub(crate) fn remote_ui_enabled() -> bool {
     std::env::var("IRONWING_REMOTE_UI").is_ok_and(|value| value == "1" || value == "true")
@@ -114,109 +120,8 @@ async fn handle_connection(app: tauri::AppHandle, mut stream: TcpStream) -> Resu
     }
 }
 
-async fn read_http_request(stream: &mut TcpStream) -> Result<HttpRequest, String> {
-    let mut buffer = Vec::new();
-    let mut chunk = [0_u8; 4096];
-
-    loop {
-        let read = stream
-            .read(&mut chunk)
-            .await
-            .map_err(|error| format!("failed reading request: {error}"))?;
-        if read == 0 {
-            break;
-        }
-        buffer.extend_from_slice(&chunk[..read]);
-        if buffer.len() > MAX_REQUEST_BYTES {
-            return Err("remote UI request is too large".to_string());
-        }
-        if header_end(&buffer).is_some() {
-            break;
-        }
-    }
-
-    let header_end = header_end(&buffer).ok_or_else(|| "missing HTTP headers".to_string())?;
-    let headers = String::from_utf8_lossy(&buffer[..header_end]);
-    let mut lines = headers.lines();
-    let request_line = lines
-        .next()
-        .ok_or_else(|| "missing request line".to_string())?;
-    let mut parts = request_line.split_whitespace();
-    let method = parts.next().unwrap_or_default().to_string();
-    let path = parts.next().unwrap_or_default().to_string();
-    let content_length = lines
-        .filter_map(|line| line.split_once(':'))
-        .find_map(|(name, value)| {
-            name.eq_ignore_ascii_case("content-length")
-                .then(|| value.trim().parse::<usize>().ok())
-                .flatten()
-        })
-        .unwrap_or(0);
-    let body_start = header_end + 4;
-    while buffer.len() < body_start + content_length {
-        let read = stream
-            .read(&mut chunk)
-            .await
-            .map_err(|error| format!("failed reading request body: {error}"))?;
-        if read == 0 {
-            break;
-        }
-        buffer.extend_from_slice(&chunk[..read]);
-        if buffer.len() > MAX_REQUEST_BYTES {
-            return Err("remote UI request is too large".to_string());
-        }
-    }
-
-    let body = buffer
-        .get(body_start..body_start + content_length)
-        .unwrap_or_default()
-        .to_vec();
-
-    Ok(HttpRequest { method, path, body })
-}
-
-fn header_end(buffer: &[u8]) -> Option<usize> {
-    buffer.windows(4).position(|window| window == b"\r\n\r\n")
-}
-
-async fn write_json(stream: &mut TcpStream, status: u16, value: Value) -> Result<(), String> {
-    let body =
-        serde_json::to_vec(&value).map_err(|error| format!("serialize response: {error}"))?;
-    write_response(stream, status, "application/json", &body).await
-}
-
-async fn write_response(
-    stream: &mut TcpStream,
-    status: u16,
-    content_type: &str,
-    body: &[u8],
-) -> Result<(), String> {
-    let reason = match status {
-        200 => "OK",
-        204 => "No Content",
-        404 => "Not Found",
-        _ => "OK",
-    };
-    let headers = format!(
-        "HTTP/1.1 {status} {reason}\r\ncontent-type: {content_type}\r\ncontent-length: {}\r\naccess-control-allow-origin: *\r\naccess-control-allow-methods: GET, POST, OPTIONS\r\naccess-control-allow-headers: content-type\r\nconnection: close\r\n\r\n",
-        body.len(),
-    );
-    stream
-        .write_all(headers.as_bytes())
-        .await
-        .map_err(|error| format!("write response headers: {error}"))?;
-    stream
-        .write_all(body)
-        .await
-        .map_err(|error| format!("write response body: {error}"))
-}
-
 async fn stream_events(app: tauri::AppHandle, mut stream: TcpStream) -> Result<(), String> {
-    let headers = "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ncache-control: no-cache\r\naccess-control-allow-origin: *\r\nconnection: keep-alive\r\n\r\n";
-    stream
-        .write_all(headers.as_bytes())
-        .await
-        .map_err(|error| format!("write SSE headers: {error}"))?;
+    write_sse_headers(&mut stream).await?;
 
     let state: tauri::State<'_, AppState> = app.state();
     let mut receiver = state.remote_ui_events.subscribe();
@@ -228,36 +133,33 @@ async fn stream_events(app: tauri::AppHandle, mut stream: TcpStream) -> Result<(
         };
         let data = serde_json::to_string(&event)
             .map_err(|error| format!("serialize SSE event: {error}"))?;
-        let frame = format!("event: ironwing\ndata: {data}\n\n");
-        stream
-            .write_all(frame.as_bytes())
-            .await
-            .map_err(|error| format!("write SSE event: {error}"))?;
+        write_sse_event(&mut stream, "ironwing", &data).await?;
     }
 }
 
-fn arg<T: DeserializeOwned>(args: &Value, key: &str) -> Result<T, String> {
+fn arg<T: DeserializeOwned>(args: &Value, key: &str) -> Result<T, AppError> {
     serde_json::from_value(args.get(key).cloned().unwrap_or(Value::Null))
-        .map_err(|error| format!("invalid {key}: {error}"))
+        .map_err(|error| AppError::validation(format!("invalid {key}: {error}")))
 }
 
-fn optional_arg<T: DeserializeOwned>(args: &Value, key: &str) -> Result<Option<T>, String> {
+fn optional_arg<T: DeserializeOwned>(args: &Value, key: &str) -> Result<Option<T>, AppError> {
     match args.get(key) {
         Some(Value::Null) | None => Ok(None),
         Some(value) => serde_json::from_value(value.clone())
             .map(Some)
-            .map_err(|error| format!("invalid {key}: {error}")),
+            .map_err(|error| AppError::validation(format!("invalid {key}: {error}"))),
     }
 }
 
-fn ok<T: Serialize>(value: T) -> Result<Value, String> {
-    serde_json::to_value(value).map_err(|error| format!("serialize command result: {error}"))
+fn ok<T: Serialize>(value: T) -> Result<Value, AppError> {
+    serde_json::to_value(value)
+        .map_err(|error| AppError::internal(format!("serialize command result: {error}")))
 }
 
 async fn dispatch_invoke(
     app: &tauri::AppHandle,
     request: RemoteInvokeRequest,
-) -> Result<Value, String> {
+) -> Result<Value, AppError> {
     let state: tauri::State<'_, AppState> = app.state();
     let args = request.args;
 
@@ -268,6 +170,10 @@ async fn dispatch_invoke(
             connection::connect_link(state, app.clone(), arg(&args, "request")?).await?;
             ok(())
         }
+        "connect_keep_waiting" => {
+            connection::connect_keep_waiting(state, app.clone()).await?;
+            ok(())
+        }
         "disconnect_link" => {
             connection::disconnect_link(state, app.clone(), optional_arg(&args, "request")?)
                 .await?;
@@ -287,7 +193,13 @@ async fn dispatch_invoke(
         )
         .await?),
         "arm_vehicle" => {
-            commands::arm_vehicle(state, arg(&args, "force")?).await?;
+            commands::arm_vehicle(
+                state,
+                app.clone(),
+                arg(&args, "force")?,
+                optional_arg(&args, "overrideGates")?,
+            )
+            .await?;
             ok(())
         }
         "disarm_vehicle" => {
@@ -299,13 +211,50 @@ async fn dispatch_invoke(
             ok(())
         }
         "vehicle_takeoff" => {
-            commands::vehicle_takeoff(state, arg(&args, "altitudeM")?).await?;
+            commands::vehicle_takeoff(
+                state,
+                app.clone(),
+                arg(&args, "altitudeM")?,
+                optional_arg(&args, "overrideGates")?,
+            )
+            .await?;
             ok(())
         }
         "get_available_modes" => ok(commands::get_available_modes(state).await?),
+        "set_flight_mode_by_name" => {
+            commands::set_flight_mode_by_name(state, arg(&args, "name")?).await?;
+            ok(())
+        }
+        "flight_mode_current" => ok(commands::flight_mode_current(state).await?),
+        "local_api_start" => ok(local_api::local_api_start(
+            app.clone(),
+            state,
+            arg(&args, "port")?,
+            arg(&args, "token")?,
+            optional_arg(&args, "bindAll")?,
+            optional_arg(&args, "stopOnDisconnect")?,
+        )
+        .await?),
+        "disconnect_link_ex" => ok(disconnect_action::disconnect_link_ex(
+            state,
+            app.clone(),
+            arg(&args, "action")?,
+            arg(&args, "confirmArmed")?,
+        )
+        .await?),
+        "local_api_stop" => {
+            local_api::local_api_stop(state).await?;
+            ok(())
+        }
         "get_available_message_rates" => ok(commands::get_available_message_rates()),
+        // The encoding belongs to the webview; remote clients always get
+        // whole snapshots, so selecting one here changes nothing.
+        "set_telemetry_encoding" => {
+            arg::<TelemetryEncoding>(&args, "encoding")?;
+            ok(())
+        }
         "set_telemetry_rate" => {
-            commands::set_telemetry_rate(arg(&args, "rateHz")?)?;
+            commands::set_telemetry_rate(app.clone(), state, arg(&args, "rateHz")?).await?;
             ok(())
         }
         "set_message_rate" => {
@@ -321,6 +270,26 @@ async fn dispatch_invoke(
         }
         "stop_guided_session" => ok(commands::stop_guided_session(state, app.clone()).await?),
         "mission_validate" => ok(commands::mission_validate(arg(&args, "plan")?)),
+        "mission_insert_waypoint" => ok(crate::mission_edit::mission_insert_waypoint(
+            arg(&args, "plan")?,
+            arg(&args, "afterSeq")?,
+            arg(&args, "item")?,
+        )?),
+        "mission_reverse" => ok(crate::mission_edit::mission_reverse(arg(&args, "plan")?)),
+        "mission_translate" => ok(crate::mission_edit::mission_translate(
+            arg(&args, "plan")?,
+            arg(&args, "dlatDeg")?,
+            arg(&args, "dlonDeg")?,
+        )?),
+        "mission_scale_altitudes" => ok(crate::mission_edit::mission_scale_altitudes(
+            arg(&args, "plan")?,
+            arg(&args, "change")?,
+        )?),
+        "mission_convert_frame" => ok(crate::mission_edit::mission_convert_frame(
+            arg(&args, "plan")?,
+            arg(&args, "targetFrame")?,
+            optional_arg(&args, "homeAltM")?,
+        )?),
         "mission_upload" => {
             commands::mission_upload(state, arg(&args, "plan")?).await?;
             ok(())
@@ -330,6 +299,9 @@ async fn dispatch_invoke(
             commands::mission_clear(state).await?;
             ok(())
         }
+        "mission_prepare_resume" => {
+            ok(commands::mission_prepare_resume(state, arg(&args, "lastCompletedSeq")?).await?)
+        }
         "mission_set_current" => {
             commands::mission_set_current(state, arg(&args, "seq")?).await?;
             ok(())
@@ -364,24 +336,52 @@ async fn dispatch_invoke(
             commands::param_cancel(state).await?;
             ok(())
         }
-        "param_write" => {
-            ok(commands::param_write(state, arg(&args, "name")?, arg(&args, "value")?).await?)
+        "param_write" => ok(commands::param_write(
+            state,
+            arg(&args, "name")?,
+            arg(&args, "value")?,
+            optional_arg(&args, "persist")?,
+        )
+        .await?),
+        "param_write_batch" => ok(commands::param_write_batch(
+            state,
+            app.clone(),
+            arg(&args, "params")?,
+            optional_arg(&args, "persist")?,
+        )
+        .await?),
+        "param_tune_session" => {
+            ok(param_tune::param_tune_session(app.clone(), state, arg(&args, "name")?).await?)
         }
-        "param_write_batch" => {
-            ok(commands::param_write_batch(state, app.clone(), arg(&args, "params")?).await?)
+        "param_tune_set" => {
+            param_tune::param_tune_set(state, arg(&args, "name")?, arg(&args, "value")?).await?;
+            ok(())
         }
-        "param_parse_file" => ok(commands::param_parse_file(arg(&args, "contents")?)?),
-        "param_format_file" => ok(commands::param_format_file(arg(&args, "store")?)),
-        "calibrate_accel" => {
-            commands::calibrate_accel(state).await?;
+        "param_tune_revert" => {
+            param_tune::param_tune_revert(state).await?;
             ok(())
         }
-        "calibrate_gyro" => {
-            commands::calibrate_gyro(state).await?;
+        "param_tune_end" => ok(param_tune::param_tune_end(state, arg(&args, "commit")?).await?),
+        "tune_capture_start" => {
+            tune_capture::tune_capture_start(state, arg(&args, "axis")?).await?;
             ok(())
         }
+        "tune_capture_stop" => ok(tune_capture::tune_capture_stop(state).await?),
+        "tune_report_export" => {
+            tune_capture::tune_report_export(arg(&args, "path")?, arg(&args, "report")?).await?;
+            ok(())
+        }
+        "param_parse_file" => ok(commands::param_parse_file(arg(&args, "contents")?)?),
+        "param_parse_file_ex" => ok(commands::param_parse_file_ex(arg(&args, "contents")?)),
+        "param_format_file" => ok(commands::param_format_file(
+            arg(&args, "store")?,
+            optional_arg(&args, "format")?,
+        )),
+        "calibrate_accel" => ok(commands::calibrate_accel(state, app.clone()).await?),
+        "calibrate_gyro" => ok(commands::calibrate_gyro(state, app.clone()).await?),
         "calibrate_compass_start" => {
-            commands::calibrate_compass_start(state, arg(&args, "compassMask")?).await?;
+            commands::calibrate_compass_start(state, app.clone(), arg(&args, "compassMask")?)
+                .await?;
             ok(())
         }
         "calibrate_compass_accept" => {
@@ -481,11 +481,25 @@ async fn dispatch_invoke(
             optional_arg(&args, "startUsec")?,
             optional_arg(&args, "endUsec")?,
             optional_arg(&args, "maxPoints")?,
+            optional_arg(&args, "raw")?,
+            optional_arg(&args, "logId")?,
+            optional_arg(&args, "segment")?,
+            optional_arg(&args, "virtualFields")?,
         )
         .await?),
         "log_get_summary" => ok(logs::log_get_summary(state).await?),
         "log_close" => {
-            logs::log_close(state, app.clone()).await?;
+            logs::log_close(state, app.clone(), optional_arg(&args, "logId")?).await?;
+            ok(())
+        }
+        "log_follow" => {
+            log_follow::log_follow(
+                state,
+                app.clone(),
+                arg(&args, "enabled")?,
+                optional_arg(&args, "logId")?,
+            )
+            .await?;
             ok(())
         }
         "log_get_flight_summary" => ok(logs::log_get_flight_summary(state).await?),
@@ -504,7 +518,13 @@ async fn dispatch_invoke(
         "playback_seek" => {
             ok(logs::playback_seek(state, app.clone(), arg(&args, "cursorUsec")?).await?)
         }
-        "playback_play" => ok(logs::playback_play(state, app.clone()).await?),
+        "playback_play" => ok(logs::playback_play(
+            state,
+            app.clone(),
+            optional_arg(&args, "speed")?,
+            optional_arg(&args, "startUsec")?,
+        )
+        .await?),
         "playback_pause" => ok(logs::playback_pause(state, app.clone()).await?),
         "playback_set_speed" => {
             ok(logs::playback_set_speed(state, app.clone(), arg(&args, "speed")?).await?)
@@ -548,7 +568,9 @@ async fn dispatch_invoke(
             ok(())
         }
         "firmware_list_dfu_devices" => ok(firmware::discovery::firmware_list_dfu_devices()),
-        other => Err(format!("unsupported remote UI command: {other}")),
+        other => Err(AppError::validation(format!(
+            "unsupported remote UI command: {other}"
+        ))),
     }
 }
 
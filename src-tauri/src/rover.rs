@@ -0,0 +1,61 @@
+use ironwing_core::dialect::MavCmd;
+use ironwing_core::flight_modes::resolve_mode_by_name;
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::rover::{HOLD_MODE_NAME, ensure_rover, reverse_params};
+use mavkit::Vehicle;
+use serde_json::json;
+
+use crate::AppState;
+use crate::helpers::{ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{AppError, OperationId};
+use crate::journal;
+
+/// The connected vehicle, refused unless its heartbeat says rover or boat.
+async fn with_rover(state: &AppState) -> Result<Vehicle, AppError> {
+    let vehicle = with_vehicle(state).await?;
+    ensure_rover(vehicle.identity().vehicle_type).map_err(AppError::validation)?;
+    Ok(vehicle)
+}
+
+/// Stop in Hold mode. Boats drift in Hold; Loiter keeps them in place.
+#[tauri::command]
+pub(crate) async fn rover_hold(state: tauri::State<'_, AppState>) -> Result<(), AppError> {
+    journal::record(&state.journal, OperationId::RoverHold, json!({}), async {
+        ensure_live_write_allowed(state.inner(), OperationId::RoverHold).await?;
+        let vehicle = with_rover(&state).await?;
+        let modes = live_commands::get_available_modes(&vehicle);
+        let hold = resolve_mode_by_name(&modes, HOLD_MODE_NAME).map_err(AppError::validation)?;
+        live_commands::set_flight_mode(&vehicle, hold.custom_mode)
+            .await
+            .map_err(AppError::from)?;
+        Ok(())
+    })
+    .await
+}
+
+/// Drive backwards (or forwards again) with MAV_CMD_DO_SET_REVERSE.
+#[tauri::command]
+pub(crate) async fn rover_set_reverse(
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::RoverSetReverse,
+        json!({ "enabled": enabled }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::RoverSetReverse).await?;
+            let vehicle = with_rover(&state).await?;
+            vehicle
+                .raw()
+                .command_long(
+                    MavCmd::MAV_CMD_DO_SET_REVERSE as u16,
+                    reverse_params(enabled),
+                )
+                .await
+                .map(|_| ())
+                .map_err(AppError::from)
+        },
+    )
+    .await
+}
@@ -0,0 +1,49 @@
+use ironwing_core::safety_gates::{SafetyGateInputs, check_safety_gates, failing_gates};
+
+use crate::AppState;
+use crate::ipc::{AppError, SafetyGateConfig, SafetyGateFailure, SettingsPatch};
+
+fn gate_inputs(state: &AppState) -> SafetyGateInputs {
+    state.live_runtime.with_runtime(|runtime| {
+        SafetyGateInputs::from_gps(
+            &runtime.gps_status(),
+            runtime.session_context().home_position.is_some(),
+        )
+    })
+}
+
+/// Run the configured gates before a command is sent. `override_gates`
+/// skips them; the autopilot's own pre-arm checks are unaffected.
+pub(crate) async fn ensure_safety_gates(
+    state: &AppState,
+    override_gates: bool,
+) -> Result<(), AppError> {
+    if override_gates {
+        return Ok(());
+    }
+    let config = state.settings.lock().await.safety_gates.clone();
+    check_safety_gates(&config, &gate_inputs(state))
+}
+
+/// Replace the GCS-side arming/takeoff gates. Returns the gates the connected
+/// vehicle would fail right now.
+#[tauri::command]
+pub(crate) async fn set_safety_gates(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    config: SafetyGateConfig,
+) -> Result<Vec<SafetyGateFailure>, AppError> {
+    let patch = SettingsPatch {
+        safety_gates: Some(config.clone()),
+        ..SettingsPatch::default()
+    };
+    crate::settings::update_settings(&app, &state, patch).await?;
+    if state
+        .live_runtime
+        .with_runtime(|runtime| runtime.vehicle())
+        .is_none()
+    {
+        return Ok(Vec::new());
+    }
+    Ok(failing_gates(&config, &gate_inputs(&state)))
+}
@@ -0,0 +1,109 @@
+use ironwing_core::event_names;
+use ironwing_core::serial_hotplug::{SERIAL_PORT_POLL_INTERVAL, auto_connect_port, diff_ports};
+use tauri::Manager;
+
+use crate::AppState;
+use crate::connection::{
+    ActiveLinkTarget, active_link_target, connect_link, force_disconnect, is_vehicle_connected,
+};
+use crate::e2e_emit::emit_event;
+use crate::firmware::discovery::list_firmware_ports;
+use crate::firmware::types::{InventoryResult, PortInfo};
+use crate::ipc::{ConnectRequest, ConnectTransport, SerialFlowControl, SerialParity};
+
+async fn list_ports() -> Vec<PortInfo> {
+    match tokio::task::spawn_blocking(list_firmware_ports).await {
+        Ok(InventoryResult::Available { ports }) => ports,
+        Ok(InventoryResult::Unsupported) => Vec::new(),
+        Err(error) => {
+            tracing::warn!("serial port enumeration task failed: {error}");
+            Vec::new()
+        }
+    }
+}
+
+/// Tear the link down when its port disappears, the same way a lost link is
+/// handled. Returns whether `port_name` was the connected port.
+async fn disconnect_detached_port(app: &tauri::AppHandle, port_name: &str) -> bool {
+    let state = app.state::<AppState>();
+    let active = matches!(
+        active_link_target(&state).await,
+        Some(ActiveLinkTarget::Serial { port, .. }) if port == port_name
+    );
+    if !active {
+        return false;
+    }
+    tracing::info!("serial port {port_name} detached; disconnecting");
+    if let Err(error) = force_disconnect(&state, app).await {
+        tracing::warn!("disconnect after {port_name} was detached failed: {error}");
+    }
+    true
+}
+
+async fn auto_connect(
+    app: &tauri::AppHandle,
+    attached: &[PortInfo],
+    lost_port: &mut Option<String>,
+) {
+    let state = app.state::<AppState>();
+    let Some(baud) = *state.auto_connect_usb.lock().await else {
+        return;
+    };
+    if is_vehicle_connected(&state).await {
+        return;
+    }
+    let Some(port) = auto_connect_port(attached, lost_port.as_deref()) else {
+        return;
+    };
+    let port_name = port.port_name.clone();
+    tracing::info!("auto-connecting to {port_name} at {baud} baud");
+    let request = ConnectRequest {
+        transport: ConnectTransport::Serial {
+            port: port_name.clone(),
+            baud,
+            flow_control: SerialFlowControl::None,
+            parity: SerialParity::None,
+            auto_baud: false,
+        },
+        auto_record_on_connect: false,
+        heartbeat_timeout_secs: None,
+        keep_waiting: false,
+    };
+    match connect_link(state, app.clone(), request).await {
+        Ok(()) => *lost_port = None,
+        Err(error) => tracing::warn!("auto-connect to {port_name} failed: {error}"),
+    }
+}
+
+/// Poll the serial ports and emit `serial://attached` / `serial://detached`
+/// for every change, disconnecting when the connected port goes away and
+/// auto-connecting when enabled.
+pub(crate) fn spawn_serial_port_watcher(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut known = list_ports().await;
+        // The port the link was lost on; it is reconnected first when it
+        // comes back, whatever its VID/PID.
+        let mut lost_port = None;
+        let mut interval = tokio::time::interval(SERIAL_PORT_POLL_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            let current = list_ports().await;
+            let changes = diff_ports(&known, &current);
+            known = current;
+
+            for port in &changes.detached {
+                emit_event(&app, event_names::SERIAL_DETACHED, port);
+                if disconnect_detached_port(&app, &port.port_name).await {
+                    lost_port = Some(port.port_name.clone());
+                }
+            }
+            for port in &changes.attached {
+                emit_event(&app, event_names::SERIAL_ATTACHED, port);
+            }
+            if !changes.attached.is_empty() {
+                auto_connect(&app, &changes.attached, &mut lost_port).await;
+            }
+        }
+    });
+}
@@ -0,0 +1,164 @@
+use std::io::{Read, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use ironwing_core::mavlink_frames::FrameScanner;
+use ironwing_core::serial_probe::{AUTO_BAUD_PROBE_WINDOW, AUTO_BAUD_RATES, contains_heartbeat};
+use tokio::sync::mpsc;
+
+use crate::ipc::{LinkSource, SerialFlowControl, SerialParity};
+
+const SERIAL_READ_TIMEOUT: Duration = Duration::from_millis(100);
+const SERIAL_READ_CHUNK: usize = 1024;
+/// Longer than any HEARTBEAT frame, so one split across reads is still found.
+const PROBE_TAIL_LEN: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct SerialOptions {
+    pub(crate) flow_control: SerialFlowControl,
+    pub(crate) parity: SerialParity,
+}
+
+impl SerialOptions {
+    /// MAVKit's `serial:` address only carries port and baud; anything else
+    /// needs the port opened here and bridged into a stream connection.
+    pub(crate) fn needs_port_bridge(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+pub(crate) fn open_port(
+    port: &str,
+    baud: u32,
+    options: SerialOptions,
+) -> Result<Box<dyn serialport::SerialPort>, String> {
+    let flow_control = match options.flow_control {
+        SerialFlowControl::None => serialport::FlowControl::None,
+        SerialFlowControl::Hardware => serialport::FlowControl::Hardware,
+    };
+    let parity = match options.parity {
+        SerialParity::None => serialport::Parity::None,
+        SerialParity::Odd => serialport::Parity::Odd,
+        SerialParity::Even => serialport::Parity::Even,
+    };
+    serialport::new(port, baud)
+        .flow_control(flow_control)
+        .parity(parity)
+        .timeout(SERIAL_READ_TIMEOUT)
+        .open()
+        .map_err(|error| format!("failed to open {port} at {baud} baud: {error}"))
+}
+
+/// Raises the flag when dropped, so the blocking probe stops once the connect
+/// task that owns it is aborted through `connect_abort`.
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Listen on `port` at `baud` for one probe window. The port is closed on
+/// return, before the next rate is tried.
+fn probe_rate(
+    port: &str,
+    baud: u32,
+    options: SerialOptions,
+    cancelled: &AtomicBool,
+) -> Result<bool, String> {
+    let mut serial = open_port(port, baud, options)?;
+    if let Err(error) = serial.clear(serialport::ClearBuffer::Input) {
+        tracing::debug!("failed to flush {port} before probing: {error}");
+    }
+    let deadline = Instant::now() + AUTO_BAUD_PROBE_WINDOW;
+    let mut received = Vec::new();
+    let mut chunk = [0_u8; SERIAL_READ_CHUNK];
+    while Instant::now() < deadline && !cancelled.load(Ordering::Relaxed) {
+        match serial.read(&mut chunk) {
+            Ok(read) => {
+                received.extend_from_slice(&chunk[..read]);
+                if contains_heartbeat(&received) {
+                    return Ok(true);
+                }
+                let stale = received.len().saturating_sub(PROBE_TAIL_LEN);
+                received.drain(..stale);
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::TimedOut => {}
+            Err(error) => return Err(format!("failed to read {port}: {error}")),
+        }
+    }
+    Ok(false)
+}
+
+/// Try each of [`AUTO_BAUD_RATES`] until one yields a valid HEARTBEAT.
+pub(crate) async fn detect_baud(port: String, options: SerialOptions) -> Result<u32, String> {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let _cancel_on_drop = CancelOnDrop(cancelled.clone());
+    tokio::task::spawn_blocking(move || {
+        for baud in AUTO_BAUD_RATES {
+            if cancelled.load(Ordering::Relaxed) {
+                return Err("connection cancelled".to_string());
+            }
+            tracing::debug!("probing {port} at {baud} baud");
+            if probe_rate(&port, baud, options, &cancelled)? {
+                tracing::info!("detected {baud} baud on {port}");
+                return Ok(baud);
+            }
+        }
+        Err(format!(
+            "no MAVLink heartbeat on {port} at any of {AUTO_BAUD_RATES:?} baud"
+        ))
+    })
+    .await
+    .map_err(|error| error.to_string())?
+}
+
+/// Pump bytes between an open port and a `ChannelBridge`. Both threads exit
+/// once the bridge side of their channel is dropped, closing the port.
+/// Incoming bytes are cut into frames: with a source filter only accepted
+/// sources are passed on, and messages outside the dialect are published for
+/// `mavlink://unknown`.
+pub(crate) fn spawn_port_bridge(
+    serial: Box<dyn serialport::SerialPort>,
+    incoming_tx: mpsc::Sender<Vec<u8>>,
+    mut outgoing_rx: mpsc::Receiver<Vec<u8>>,
+    source_filter: Vec<LinkSource>,
+) -> Result<(), String> {
+    let mut writer = serial
+        .try_clone()
+        .map_err(|error| format!("failed to clone serial port handle: {error}"))?;
+    let mut reader = serial;
+
+    let mut scanner = FrameScanner::with_source_filter(source_filter);
+    std::thread::spawn(move || {
+        let mut chunk = [0_u8; SERIAL_READ_CHUNK];
+        while !incoming_tx.is_closed() {
+            match reader.read(&mut chunk) {
+                Ok(0) => {}
+                Ok(read) => {
+                    let data = scanner.push(&chunk[..read]);
+                    crate::unknown_messages::publish(scanner.take_unknown());
+                    if !data.is_empty() && incoming_tx.blocking_send(data).is_err() {
+                        return;
+                    }
+                }
+                Err(error) if error.kind() == std::io::ErrorKind::TimedOut => {}
+                Err(error) => {
+                    tracing::warn!("serial read error: {error}");
+                    return;
+                }
+            }
+        }
+    });
+    std::thread::spawn(move || {
+        while let Some(data) = outgoing_rx.blocking_recv() {
+            if let Err(error) = writer.write_all(&data) {
+                tracing::warn!("serial write error: {error}");
+                return;
+            }
+        }
+    });
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use serde::Serialize;
 
+use crate::AppState;
 use crate::firmware::discovery::list_firmware_ports;
 use crate::firmware::types::{InventoryResult, PortInfo};
+use crate::ipc::AppError;
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
@@ -27,3 +29,34 @@ pub(crate) fn list_serial_port_inventory() -> SerialPortInventoryResult {
         },
     }
 }
+
+/// Enable or disable connecting automatically, at `baud`, when a known
+/// autopilot is plugged in while no vehicle is connected. Saved with the
+/// other settings.
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+pub(crate) async fn set_auto_connect_usb(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    enabled: bool,
+    baud: u32,
+) -> Result<(), AppError> {
+    let patch = crate::ipc::SettingsPatch {
+        auto_connect_usb: Some(enabled),
+        auto_connect_usb_baud: Some(baud),
+        ..Default::default()
+    };
+    crate::settings::update_settings(&app, &state, patch).await?;
+    Ok(())
+}
+
+#[cfg(target_os = "android")]
+#[tauri::command]
+pub(crate) async fn set_auto_connect_usb(
+    _app: tauri::AppHandle,
+    _state: tauri::State<'_, AppState>,
+    _enabled: bool,
+    _baud: u32,
+) -> Result<(), AppError> {
+    Err(AppError::validation("USB auto-connect is only supported on desktop"))
+}
@@ -0,0 +1,306 @@
+use std::fs::File;
+use std::io::{BufWriter, Read};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ironwing_core::event_names;
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::tlog_segments::{is_tlog_index, read_tlog_index};
+use ironwing_core::track_export::{track_geojson, track_kml};
+
+use crate::AppState;
+use crate::e2e_emit::emit_event;
+use crate::ipc::{
+    AppError, ExportReport, ParamExportOptions, SessionArtifact, SessionArtifactEntry,
+    SessionArtifactStatus, SessionExportManifest, SessionExportOptions, SessionExportProgress,
+};
+use crate::zip_stream::ZipStreamWriter;
+
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+enum ArtifactSource {
+    /// Streamed from disk; the note is carried into the manifest.
+    File(PathBuf, Option<String>),
+    /// A rotated recording: the bytes of its index followed by every
+    /// segment, stored side by side so the index still finds them.
+    Segments(Vec<u8>, Vec<PathBuf>, Option<String>),
+    Bytes(Vec<u8>),
+    Missing(String),
+    Skipped,
+}
+
+struct PendingArtifact {
+    artifact: SessionArtifact,
+    file_name: String,
+    source: ArtifactSource,
+}
+
+fn pending(artifact: SessionArtifact, file_name: &str, source: ArtifactSource) -> PendingArtifact {
+    PendingArtifact {
+        artifact,
+        file_name: file_name.to_string(),
+        source,
+    }
+}
+
+fn json_bytes(value: &impl serde::Serialize) -> ArtifactSource {
+    match serde_json::to_vec_pretty(value) {
+        Ok(bytes) => ArtifactSource::Bytes(bytes),
+        Err(error) => ArtifactSource::Missing(format!("failed to serialize: {error}")),
+    }
+}
+
+fn tlog_artifact(state: &AppState) -> PendingArtifact {
+    let Some((path, active)) = state.recorder.latest_recording() else {
+        return pending(
+            SessionArtifact::Tlog,
+            "session.tlog",
+            ArtifactSource::Missing("nothing was recorded this session".to_string()),
+        );
+    };
+    let path = PathBuf::from(path);
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "session.tlog".to_string());
+    let note = active.then(|| {
+        "recording was still running; the archive holds what was written so far".to_string()
+    });
+    if !is_tlog_index(&file_name) {
+        return pending(
+            SessionArtifact::Tlog,
+            &file_name,
+            ArtifactSource::File(path, note),
+        );
+    }
+    let index = std::fs::read(&path)
+        .map_err(|error| format!("failed to read {}: {error}", path.display()))
+        .and_then(|bytes| read_tlog_index(&bytes).map(|index| (bytes, index)));
+    let source = match index {
+        Ok((bytes, index)) => {
+            let segments = index
+                .segments
+                .iter()
+                .map(|segment| path.with_file_name(&segment.file_name))
+                .collect();
+            ArtifactSource::Segments(bytes, segments, note)
+        }
+        Err(error) => ArtifactSource::Missing(error),
+    };
+    pending(SessionArtifact::Tlog, &file_name, source)
+}
+
+fn optional(
+    enabled: bool,
+    missing: Option<&str>,
+    render: impl FnOnce() -> ArtifactSource,
+) -> ArtifactSource {
+    if !enabled {
+        ArtifactSource::Skipped
+    } else if let Some(reason) = missing {
+        ArtifactSource::Missing(reason.to_string())
+    } else {
+        render()
+    }
+}
+
+/// Snapshot everything the export needs while the runtime is reachable, so
+/// writing the archive does not hold it.
+fn gather(state: &AppState, include: &SessionExportOptions) -> Vec<PendingArtifact> {
+    let (summary, status_texts, track, vehicle) = state.live_runtime.with_runtime(|runtime| {
+        (
+            runtime.flight_summaries().last().cloned(),
+            runtime.status_text_history().to_vec(),
+            runtime.flight_track().since(None, None),
+            runtime.vehicle(),
+        )
+    });
+
+    let tlog = if include.tlog {
+        tlog_artifact(state)
+    } else {
+        pending(
+            SessionArtifact::Tlog,
+            "session.tlog",
+            ArtifactSource::Skipped,
+        )
+    };
+    let summary = optional(
+        include.flight_summary,
+        summary.is_none().then_some("no flight has finished yet"),
+        || json_bytes(&summary),
+    );
+    let status_text = optional(
+        include.status_text,
+        status_texts
+            .is_empty()
+            .then_some("no status text was received"),
+        || json_bytes(&status_texts),
+    );
+    let params = optional(
+        include.params,
+        vehicle.is_none().then_some("no vehicle is connected"),
+        || {
+            let vehicle = vehicle.expect("checked above");
+            match live_commands::param_export(&vehicle, &ParamExportOptions::default()) {
+                Ok(text) => ArtifactSource::Bytes(text.into_bytes()),
+                Err(error) => ArtifactSource::Missing(error.to_string()),
+            }
+        },
+    );
+    let no_track = track.is_empty().then_some("no positions were tracked");
+    let geojson = optional(include.track_geojson, no_track, || {
+        json_bytes(&track_geojson(&track))
+    });
+    let kml = optional(include.track_kml, no_track, || {
+        ArtifactSource::Bytes(track_kml(&track).into_bytes())
+    });
+
+    vec![
+        tlog,
+        pending(
+            SessionArtifact::FlightSummary,
+            "flight_summary.json",
+            summary,
+        ),
+        pending(SessionArtifact::StatusText, "statustext.json", status_text),
+        pending(SessionArtifact::Params, "params.param", params),
+        pending(SessionArtifact::TrackGeojson, "track.geojson", geojson),
+        pending(SessionArtifact::TrackKml, "track.kml", kml),
+    ]
+}
+
+fn write_artifact(
+    zip: &mut ZipStreamWriter<BufWriter<File>>,
+    artifact: PendingArtifact,
+) -> Result<SessionArtifactEntry, String> {
+    let entry = |status, file_name, bytes, note| SessionArtifactEntry {
+        artifact: artifact.artifact,
+        status,
+        file_name,
+        bytes,
+        note,
+    };
+    let write = |zip: &mut ZipStreamWriter<_>, mut reader: &mut dyn Read| {
+        zip.add_entry(&artifact.file_name, &mut reader)
+            .map_err(|error| format!("failed to write {}: {error}", artifact.file_name))
+    };
+    Ok(match artifact.source {
+        ArtifactSource::Skipped => entry(SessionArtifactStatus::Skipped, None, 0, None),
+        ArtifactSource::Missing(reason) => {
+            entry(SessionArtifactStatus::Missing, None, 0, Some(reason))
+        }
+        ArtifactSource::Bytes(bytes) => {
+            let size = write(zip, &mut bytes.as_slice())?;
+            let file_name = Some(artifact.file_name.clone());
+            entry(SessionArtifactStatus::Included, file_name, size, None)
+        }
+        ArtifactSource::File(path, note) => match File::open(&path) {
+            Ok(mut file) => {
+                let size = write(zip, &mut file)?;
+                let file_name = Some(artifact.file_name.clone());
+                entry(SessionArtifactStatus::Included, file_name, size, note)
+            }
+            Err(error) => entry(
+                SessionArtifactStatus::Missing,
+                None,
+                0,
+                Some(format!("failed to open {}: {error}", path.display())),
+            ),
+        },
+        ArtifactSource::Segments(index, segments, note) => {
+            let mut size = write(zip, &mut index.as_slice())?;
+            let mut notes: Vec<String> = note.into_iter().collect();
+            for segment in segments {
+                let name = segment
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                match File::open(&segment) {
+                    Ok(mut file) => {
+                        size += zip
+                            .add_entry(&name, &mut file)
+                            .map_err(|error| format!("failed to write {name}: {error}"))?;
+                    }
+                    Err(error) => notes.push(format!("segment {name} is missing: {error}")),
+                }
+            }
+            let file_name = Some(artifact.file_name.clone());
+            let note = (!notes.is_empty()).then(|| notes.join("; "));
+            entry(SessionArtifactStatus::Included, file_name, size, note)
+        }
+    })
+}
+
+fn write_archive(
+    app: &tauri::AppHandle,
+    path: &Path,
+    artifacts: Vec<PendingArtifact>,
+    app_version: String,
+) -> Result<ExportReport, String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let file = File::create(path).map_err(|error| format!("failed to create archive: {error}"))?;
+    let mut zip = ZipStreamWriter::new(BufWriter::new(file), now.as_secs() as i64);
+
+    let total = artifacts.len() as u32;
+    let mut entries = Vec::with_capacity(artifacts.len());
+    for (index, artifact) in artifacts.into_iter().enumerate() {
+        let entry = write_artifact(&mut zip, artifact)?;
+        emit_event(
+            app,
+            event_names::SESSION_EXPORT_PROGRESS,
+            &SessionExportProgress {
+                artifact: entry.artifact,
+                index: index as u32 + 1,
+                total,
+                status: entry.status,
+            },
+        );
+        entries.push(entry);
+    }
+
+    let manifest = SessionExportManifest {
+        app_version,
+        created_unix_msec: now.as_millis() as u64,
+        artifacts: entries.clone(),
+    };
+    let manifest = serde_json::to_vec_pretty(&manifest)
+        .map_err(|error| format!("failed to serialize manifest: {error}"))?;
+    zip.add_entry(MANIFEST_FILE_NAME, &mut manifest.as_slice())
+        .map_err(|error| format!("failed to write manifest: {error}"))?;
+    let (writer, bytes_written) = zip
+        .finish()
+        .map_err(|error| format!("failed to finish archive: {error}"))?;
+    writer
+        .into_inner()
+        .map_err(|error| format!("failed to write archive: {}", error.error()))?;
+
+    Ok(ExportReport {
+        path: path.display().to_string(),
+        bytes_written,
+        artifacts: entries,
+    })
+}
+
+/// Zip the current or most recent session's tlog, flight summary, status
+/// text, parameters and track into one archive with a `manifest.json`.
+/// Artifacts the session does not have are listed as missing rather than
+/// failing the export.
+#[tauri::command]
+pub(crate) async fn session_export(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    path: String,
+    include: Option<SessionExportOptions>,
+) -> Result<ExportReport, AppError> {
+    let artifacts = gather(&state, &include.unwrap_or_default());
+    let app_version = app.package_info().version.to_string();
+    tokio::task::spawn_blocking(move || {
+        write_archive(&app, Path::new(&path), artifacts, app_version)
+    })
+    .await
+    .map_err(|error| format!("export task failed: {error}"))?
+    .map_err(AppError::from)
+}
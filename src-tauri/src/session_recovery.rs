@@ -0,0 +1,124 @@
+use std::path::PathBuf;
+
+use ironwing_core::session_recovery::{
+    ACTIVE_SESSION_FILE_NAME, SESSION_RECOVERY_WRITE_INTERVAL, recoverable_session,
+};
+use ironwing_core::vehicle_snapshot::unix_epoch_usec;
+use mavkit::Vehicle;
+use tauri::Manager;
+
+use crate::AppState;
+use crate::helpers::vehicle_is_armed;
+use crate::ipc::{AppError, ConnectRequest, SessionRecoverInfo};
+
+fn active_session_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|error| format!("failed to resolve app-data directory: {error}"))?;
+    Ok(app_data_dir.join(ACTIVE_SESSION_FILE_NAME))
+}
+
+fn unix_epoch_msec() -> u64 {
+    unix_epoch_usec() / 1_000
+}
+
+/// Write through a temporary file so a kill mid-write leaves the previous
+/// snapshot rather than half a JSON document.
+fn write_active_session(app: &tauri::AppHandle, info: &SessionRecoverInfo) -> Result<(), String> {
+    let path = active_session_path(app)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create app-data directory: {error}"))?;
+    }
+    let json = serde_json::to_vec(info)
+        .map_err(|error| format!("failed to serialize active session: {error}"))?;
+    let pending = path.with_extension("json.tmp");
+    std::fs::write(&pending, json)
+        .and_then(|()| std::fs::rename(&pending, &path))
+        .map_err(|error| format!("failed to write active session: {error}"))
+}
+
+/// Start tracking a freshly connected session for crash recovery.
+pub(crate) async fn begin_session_recovery(
+    state: &AppState,
+    app: &tauri::AppHandle,
+    request: ConnectRequest,
+) {
+    let now = unix_epoch_msec();
+    let info = SessionRecoverInfo {
+        request,
+        connected_at_unix_msec: now,
+        updated_at_unix_msec: now,
+        armed: false,
+        last_latitude_deg: None,
+        last_longitude_deg: None,
+        recording_paths: Vec::new(),
+    };
+    if let Err(error) = write_active_session(app, &info) {
+        tracing::warn!("{error}");
+    }
+    *state.session_recovery.lock().await = Some(info);
+}
+
+/// A clean disconnect: nothing to recover next launch.
+pub(crate) async fn end_session_recovery(state: &AppState, app: &tauri::AppHandle) {
+    if state.session_recovery.lock().await.take().is_none() {
+        return;
+    }
+    let Ok(path) = active_session_path(app) else {
+        return;
+    };
+    if let Err(error) = std::fs::remove_file(&path)
+        && error.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!("failed to delete active session file: {error}");
+    }
+}
+
+/// Every `SESSION_RECOVERY_WRITE_INTERVAL` refresh the active session file
+/// with the vehicle's armed state, last position and the tlog being recorded.
+pub(crate) async fn session_recovery_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    let mut interval = tokio::time::interval(SESSION_RECOVERY_WRITE_INTERVAL);
+    loop {
+        interval.tick().await;
+        let state: tauri::State<'_, AppState> = handle.state();
+        let mut guard = state.session_recovery.lock().await;
+        let Some(info) = guard.as_mut() else {
+            continue;
+        };
+        info.updated_at_unix_msec = unix_epoch_msec();
+        info.armed = vehicle_is_armed(&vehicle);
+        if let Some(sample) = vehicle.telemetry().position().global().latest() {
+            info.last_latitude_deg = Some(sample.value.latitude_deg);
+            info.last_longitude_deg = Some(sample.value.longitude_deg);
+        }
+        if let Some((path, true)) = state.recorder.latest_recording()
+            && !info.recording_paths.contains(&path)
+        {
+            info.recording_paths.push(path);
+        }
+        if let Err(error) = write_active_session(&handle, info) {
+            tracing::warn!("{error}");
+        }
+    }
+}
+
+/// The session the previous run left open, if it was killed while connected
+/// within the last hour.
+#[tauri::command]
+pub(crate) fn session_recover_info(
+    app: tauri::AppHandle,
+) -> Result<Option<SessionRecoverInfo>, AppError> {
+    let path = active_session_path(&app)?;
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(AppError::internal(format!(
+                "failed to read active session: {error}"
+            )));
+        }
+    };
+    Ok(recoverable_session(&contents, unix_epoch_msec()))
+}
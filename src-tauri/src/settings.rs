@@ -0,0 +1,155 @@
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use ironwing_core::alerts::ALERT_RULES_FILE_NAME;
+use ironwing_core::settings::{SETTINGS_FILE_NAME, apply_patch, parse_settings};
+use ironwing_core::telemetry;
+use tauri::Manager;
+
+use crate::AppState;
+use crate::bridges::TELEMETRY_INTERVAL_MS;
+use crate::connection::LINK_BUFFER_FRAMES;
+use crate::ipc::{AlertRule, AppError, Settings, SettingsPatch};
+
+fn app_data_path(app: &tauri::AppHandle, file_name: &str) -> Result<PathBuf, String> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|error| format!("failed to resolve app-data directory: {error}"))?;
+    Ok(app_data_dir.join(file_name))
+}
+
+/// Alert rules saved by builds that kept them in their own file, so the
+/// first settings file carries them over.
+fn read_legacy_alert_rules(app: &tauri::AppHandle) -> Option<Vec<AlertRule>> {
+    let path = app_data_path(app, ALERT_RULES_FILE_NAME).ok()?;
+    let bytes = std::fs::read(path).ok()?;
+    match serde_json::from_slice(&bytes) {
+        Ok(rules) => Some(rules),
+        Err(error) => {
+            tracing::warn!("ignoring saved alert rules: {error}");
+            None
+        }
+    }
+}
+
+fn read_settings(app: &tauri::AppHandle) -> Result<Option<Settings>, String> {
+    let path = app_data_path(app, SETTINGS_FILE_NAME)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let bytes =
+        std::fs::read(&path).map_err(|error| format!("failed to read settings: {error}"))?;
+    parse_settings(&bytes).map(Some)
+}
+
+/// Write through a temporary file so a crash mid-write never leaves a
+/// truncated settings file behind.
+fn write_settings(app: &tauri::AppHandle, settings: &Settings) -> Result<(), String> {
+    let path = app_data_path(app, SETTINGS_FILE_NAME)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|error| format!("failed to create app-data directory: {error}"))?;
+    }
+    let bytes = serde_json::to_vec_pretty(settings)
+        .map_err(|error| format!("failed to serialize settings: {error}"))?;
+    let temp_path = path.with_extension("json.tmp");
+    std::fs::write(&temp_path, bytes)
+        .map_err(|error| format!("failed to write settings: {error}"))?;
+    std::fs::rename(&temp_path, &path).map_err(|error| format!("failed to write settings: {error}"))
+}
+
+/// Push validated settings out to the runtime, statics and connection state.
+async fn apply_settings(state: &AppState, settings: &Settings) -> Result<(), String> {
+    let interval_ms = telemetry::telemetry_interval_ms_for_rate(settings.telemetry_rate_hz)?;
+    state.live_runtime.with_runtime(|runtime| {
+        runtime.set_unit_system(settings.units);
+        runtime.set_track_max_points(settings.track_max_points as usize);
+        runtime.set_telemetry_stale_after(Duration::from_millis(u64::from(
+            settings.telemetry_stale_after_ms,
+        )));
+        runtime.set_alert_rules(settings.alert_rules.clone())?;
+        runtime.set_announcement_settings(settings.announcements.clone());
+        runtime.set_checklist(settings.checklist.clone())
+    })?;
+    TELEMETRY_INTERVAL_MS.store(interval_ms, Ordering::Relaxed);
+    LINK_BUFFER_FRAMES.store(settings.link_buffer_frames as usize, Ordering::Relaxed);
+    *state.auto_connect_usb.lock().await = settings
+        .auto_connect_usb
+        .then_some(settings.auto_connect_usb_baud);
+    Ok(())
+}
+
+/// Load the saved settings at startup. A missing file starts from defaults
+/// (plus any alert rules saved before settings existed); a corrupt one is
+/// logged and also falls back to defaults rather than blocking startup.
+pub(crate) async fn load_saved_settings(app: &tauri::AppHandle) {
+    let settings = match read_settings(app) {
+        Ok(Some(settings)) => settings,
+        Ok(None) => {
+            let mut settings = Settings::default();
+            if let Some(rules) = read_legacy_alert_rules(app) {
+                settings = apply_patch(
+                    &settings,
+                    SettingsPatch {
+                        alert_rules: Some(rules),
+                        ..SettingsPatch::default()
+                    },
+                )
+                .unwrap_or_else(|error| {
+                    tracing::warn!("ignoring saved alert rules: {error}");
+                    Settings::default()
+                });
+            }
+            settings
+        }
+        Err(error) => {
+            tracing::warn!("ignoring saved settings, using defaults: {error}");
+            Settings::default()
+        }
+    };
+    let state = app.state::<AppState>();
+    let mut current = state.settings.lock().await;
+    if let Err(error) = apply_settings(&state, &settings).await {
+        tracing::warn!("ignoring saved settings, using defaults: {error}");
+        let defaults = Settings::default();
+        if let Err(error) = apply_settings(&state, &defaults).await {
+            tracing::warn!("failed to apply default settings: {error}");
+        }
+        *current = defaults;
+        return;
+    }
+    *current = settings;
+}
+
+/// Merge `patch`, save the result and apply it. Nothing changes unless the
+/// merged settings validate and are written to disk.
+pub(crate) async fn update_settings(
+    app: &tauri::AppHandle,
+    state: &AppState,
+    patch: SettingsPatch,
+) -> Result<Settings, AppError> {
+    let mut current = state.settings.lock().await;
+    let next = apply_patch(&current, patch).map_err(AppError::validation)?;
+    write_settings(app, &next)?;
+    apply_settings(state, &next)
+        .await
+        .map_err(AppError::validation)?;
+    *current = next.clone();
+    Ok(next)
+}
+
+#[tauri::command]
+pub(crate) async fn settings_get(state: tauri::State<'_, AppState>) -> Result<Settings, AppError> {
+    Ok(state.settings.lock().await.clone())
+}
+
+#[tauri::command]
+pub(crate) async fn settings_update(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, AppState>,
+    patch: SettingsPatch,
+) -> Result<Settings, AppError> {
+    update_settings(&app, &state, patch).await
+}
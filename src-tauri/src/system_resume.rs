@@ -0,0 +1,99 @@
+use std::time::{Duration, SystemTime};
+
+use ironwing_core::event_names;
+use ironwing_core::live_runtime;
+use ironwing_core::system_resume::{RESUME_CHECK_INTERVAL, suspended_for};
+use tauri::Manager;
+use tokio::time::{Instant, MissedTickBehavior};
+
+use crate::AppState;
+use crate::e2e_emit::emit_event;
+use crate::ipc::SystemResumed;
+
+/// Compare the monotonic and wall clocks every `RESUME_CHECK_INTERVAL` and
+/// call `on_resumed` once per wake-up. Missed ticks are skipped, so a long
+/// suspend yields one late tick rather than a burst of catch-up ticks.
+pub(crate) async fn watch_for_resume(
+    wall_clock: impl Fn() -> SystemTime,
+    mut on_resumed: impl FnMut(Duration),
+) {
+    let mut interval = tokio::time::interval(RESUME_CHECK_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    interval.tick().await;
+    let mut last = (Instant::now(), wall_clock());
+    loop {
+        interval.tick().await;
+        let now = (Instant::now(), wall_clock());
+        let monotonic = now.0.saturating_duration_since(last.0);
+        let wall = now.1.duration_since(last.1).ok();
+        if let Some(suspended) = suspended_for(RESUME_CHECK_INTERVAL, monotonic, wall) {
+            on_resumed(suspended);
+        }
+        last = now;
+    }
+}
+
+/// Re-emit the live state and send `system://resumed` after the host slept.
+fn on_resumed(app: &tauri::AppHandle, suspended: Duration) {
+    tracing::info!("host resumed after {suspended:?}; refreshing live state");
+    let state = app.state::<AppState>();
+    live_runtime::refresh_after_resume(&state.live_runtime);
+    emit_event(
+        app,
+        event_names::SYSTEM_RESUMED,
+        &SystemResumed {
+            suspended_ms: suspended.as_millis() as u64,
+        },
+    );
+}
+
+pub(crate) fn spawn_resume_watcher(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        watch_for_resume(SystemTime::now, |suspended| on_resumed(&app, suspended)).await;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn a_long_suspend_is_reported_once() {
+        let start = Instant::now();
+        let wall_start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let wall_jump_secs = Arc::new(AtomicU64::new(0));
+        let resumes = Arc::new(Mutex::new(Vec::new()));
+        let watcher = tokio::spawn({
+            let wall_jump_secs = wall_jump_secs.clone();
+            let resumes = resumes.clone();
+            watch_for_resume(
+                move || {
+                    wall_start
+                        + start.elapsed()
+                        + Duration::from_secs(wall_jump_secs.load(Ordering::Relaxed))
+                },
+                move |suspended| resumes.lock().unwrap().push(suspended),
+            )
+        });
+
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        assert!(resumes.lock().unwrap().is_empty());
+
+        // A monotonic clock that counts suspend: ten minutes pass at once.
+        tokio::time::advance(Duration::from_secs(600)).await;
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        assert_eq!(resumes.lock().unwrap().len(), 1);
+        assert!(resumes.lock().unwrap()[0] >= Duration::from_secs(590));
+
+        // A monotonic clock that stops during suspend: only the wall clock
+        // jumps.
+        wall_jump_secs.store(600, Ordering::Relaxed);
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        assert_eq!(resumes.lock().unwrap().len(), 2);
+
+        watcher.abort();
+    }
+}
@@ -0,0 +1,500 @@
+use std::time::Duration;
+
+use ironwing_core::component_command::ComponentCommand;
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::takeoff::{
+    DENIAL_STATUSTEXT_WINDOW_MS, describe_step_failure, validate_takeoff_altitude,
+};
+use mavkit::AutopilotType;
+use serde_json::json;
+
+use crate::AppState;
+use crate::component_commands::autopilot_command;
+use crate::helpers::{ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{
+    AppError, OperationId, TakeoffGuidedRequest, TakeoffReport, TakeoffStep, TakeoffStepReport,
+    TakeoffStepStatus, status_text_mark, status_texts_after,
+};
+use crate::journal;
+use crate::safety_gates::ensure_safety_gates;
+
+pub(crate) const GUIDED_MODE_NAME: &str = "GUIDED";
+const MODE_CONFIRM_TIMEOUT: Duration = Duration::from_secs(3);
+const MODE_CONFIRM_POLL: Duration = Duration::from_millis(100);
+
+/// What the takeoff sequence needs from a vehicle, so it can run against a
+/// scripted vehicle in tests.
+pub(crate) trait TakeoffTarget {
+    fn autopilot(&self) -> AutopilotType;
+    fn in_guided(&self) -> bool;
+    fn is_armed(&self) -> bool;
+    /// The enforced pre-arm checklist `arm_vehicle` requires.
+    fn ensure_checklist(&self) -> Result<(), AppError>;
+    /// A marker for the newest STATUSTEXT seen so far.
+    fn status_text_mark(&self) -> u64;
+    /// STATUSTEXT received after `mark`, once the vehicle has had a moment to
+    /// explain a refusal.
+    async fn status_texts_after(&self, mark: u64) -> Vec<String>;
+    async fn set_guided(&self) -> Result<(), AppError>;
+    async fn arm(&self) -> Result<(), AppError>;
+    async fn takeoff(&self, altitude_m: f32) -> Result<(), AppError>;
+}
+
+struct LiveTakeoff<'a> {
+    state: &'a AppState,
+    app: &'a tauri::AppHandle,
+    vehicle: mavkit::Vehicle,
+}
+
+impl TakeoffTarget for LiveTakeoff<'_> {
+    fn autopilot(&self) -> AutopilotType {
+        self.vehicle.identity().autopilot
+    }
+
+    fn in_guided(&self) -> bool {
+        self.vehicle
+            .available_modes()
+            .current()
+            .latest()
+            .is_some_and(|mode| mode.name.eq_ignore_ascii_case(GUIDED_MODE_NAME))
+    }
+
+    fn is_armed(&self) -> bool {
+        self.vehicle
+            .telemetry()
+            .armed()
+            .latest()
+            .is_some_and(|sample| sample.value)
+    }
+
+    fn ensure_checklist(&self) -> Result<(), AppError> {
+        crate::checklist::ensure_checklist(self.state)
+    }
+
+    fn status_text_mark(&self) -> u64 {
+        self.state
+            .live_runtime
+            .with_runtime(|runtime| status_text_mark(runtime.status_text_history()))
+    }
+
+    async fn status_texts_after(&self, mark: u64) -> Vec<String> {
+        tokio::time::sleep(Duration::from_millis(DENIAL_STATUSTEXT_WINDOW_MS)).await;
+        self.state
+            .live_runtime
+            .with_runtime(|runtime| status_texts_after(runtime.status_text_history(), mark))
+    }
+
+    async fn set_guided(&self) -> Result<(), AppError> {
+        let guided = self
+            .vehicle
+            .available_modes()
+            .iter()
+            .find(|mode| mode.name.eq_ignore_ascii_case(GUIDED_MODE_NAME))
+            .ok_or_else(|| AppError::internal("vehicle has no Guided mode"))?;
+        live_commands::set_flight_mode(&self.vehicle, guided.custom_mode).await?;
+        // The mode command does not wait for the switch; takeoff sent before
+        // the heartbeat confirms Guided would be refused.
+        let deadline = tokio::time::Instant::now() + MODE_CONFIRM_TIMEOUT;
+        while !self.in_guided() {
+            if tokio::time::Instant::now() >= deadline {
+                return Err(AppError::Timeout {
+                    message: "vehicle did not report Guided mode".to_string(),
+                });
+            }
+            tokio::time::sleep(MODE_CONFIRM_POLL).await;
+        }
+        Ok(())
+    }
+
+    async fn arm(&self) -> Result<(), AppError> {
+        let identity = self.vehicle.identity();
+        let arm = ComponentCommand::arm(identity.system_id, identity.component_id, false);
+        autopilot_command(self.app, &self.state.command_link, &self.vehicle, arm)
+            .await
+    }
+
+    async fn takeoff(&self, altitude_m: f32) -> Result<(), AppError> {
+        let identity = self.vehicle.identity();
+        let takeoff =
+            ComponentCommand::takeoff(identity.system_id, identity.component_id, altitude_m);
+        autopilot_command(self.app, &self.state.command_link, &self.vehicle, takeoff)
+            .await
+    }
+}
+
+async fn step_outcome(
+    target: &impl TakeoffTarget,
+    step: TakeoffStep,
+    mark: u64,
+    result: Result<(), AppError>,
+) -> TakeoffStepReport {
+    match result {
+        Ok(()) => TakeoffStepReport {
+            step,
+            status: TakeoffStepStatus::Done,
+            message: None,
+        },
+        Err(error) => {
+            let status_texts = if matches!(error, AppError::CommandDenied { .. }) {
+                target.status_texts_after(mark).await
+            } else {
+                Vec::new()
+            };
+            TakeoffStepReport {
+                step,
+                status: TakeoffStepStatus::Failed,
+                message: Some(describe_step_failure(step, &error, &status_texts)),
+            }
+        }
+    }
+}
+
+fn skipped(step: TakeoffStep) -> TakeoffStepReport {
+    TakeoffStepReport {
+        step,
+        status: TakeoffStepStatus::Skipped,
+        message: None,
+    }
+}
+
+/// Switch to Guided, arm when allowed, then send NAV_TAKEOFF, stopping at the
+/// first step that fails. A sequence that may arm first has to pass the
+/// enforced checklist, the same as `arm_vehicle`.
+///
+/// Guided and NAV_TAKEOFF-in-Guided are ArduPilot's; PX4 takes off through
+/// its own Takeoff mode, so any other autopilot is refused before a command
+/// goes out.
+pub(crate) async fn run_takeoff_sequence(
+    target: &impl TakeoffTarget,
+    altitude_m: f32,
+    allow_arm: bool,
+) -> Result<TakeoffReport, AppError> {
+    let autopilot = target.autopilot();
+    if autopilot != AutopilotType::ArduPilotMega {
+        return Err(AppError::UnsupportedAutopilot {
+            autopilot,
+            message: "guided takeoff is only supported on ArduPilot".to_string(),
+        });
+    }
+    if allow_arm && !target.is_armed() {
+        target.ensure_checklist()?;
+    }
+    let mut steps = Vec::new();
+
+    let set_guided = if target.in_guided() {
+        skipped(TakeoffStep::SetGuided)
+    } else {
+        let mark = target.status_text_mark();
+        let result = target.set_guided().await;
+        step_outcome(target, TakeoffStep::SetGuided, mark, result).await
+    };
+    let failed = set_guided.status == TakeoffStepStatus::Failed;
+    steps.push(set_guided);
+    if failed {
+        return Ok(TakeoffReport {
+            steps,
+            success: false,
+        });
+    }
+
+    let arm = if target.is_armed() {
+        skipped(TakeoffStep::Arm)
+    } else if !allow_arm {
+        TakeoffStepReport {
+            step: TakeoffStep::Arm,
+            status: TakeoffStepStatus::Failed,
+            message: Some("vehicle is disarmed; arm it or allow arming".to_string()),
+        }
+    } else {
+        let mark = target.status_text_mark();
+        let result = target.arm().await;
+        step_outcome(target, TakeoffStep::Arm, mark, result).await
+    };
+    let failed = arm.status == TakeoffStepStatus::Failed;
+    steps.push(arm);
+    if failed {
+        return Ok(TakeoffReport {
+            steps,
+            success: false,
+        });
+    }
+
+    let mark = target.status_text_mark();
+    let result = target.takeoff(altitude_m).await;
+    let takeoff = step_outcome(target, TakeoffStep::Takeoff, mark, result).await;
+    let success = takeoff.status == TakeoffStepStatus::Done;
+    steps.push(takeoff);
+    Ok(TakeoffReport { steps, success })
+}
+
+/// Take off in Guided, switching mode and (when `allow_arm` is set) arming
+/// first. Step failures are reported in the result rather than as an error.
+#[tauri::command]
+pub(crate) async fn takeoff_guided(
+    state: tauri::State<'_, AppState>,
+    app: tauri::AppHandle,
+    request: TakeoffGuidedRequest,
+) -> Result<TakeoffReport, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::TakeoffGuided,
+        json!({ "request": request }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::TakeoffGuided).await?;
+            let max_alt_m = state.settings.lock().await.takeoff_max_alt_m;
+            validate_takeoff_altitude(request.altitude_m, max_alt_m)?;
+            let vehicle = with_vehicle(&state).await?;
+            ensure_safety_gates(&state, request.override_gates).await?;
+            let target = LiveTakeoff {
+                state: state.inner(),
+                app: &app,
+                vehicle,
+            };
+            run_takeoff_sequence(&target, request.altitude_m, request.allow_arm).await
+        },
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+
+    use super::*;
+    use crate::ipc::{MavResultKind, StatusTextEntry, push_status_text_entry};
+
+    struct ScriptedVehicle {
+        autopilot: AutopilotType,
+        guided: Cell<bool>,
+        armed: Cell<bool>,
+        takeoff_error: Option<AppError>,
+        checklist_error: Option<AppError>,
+        /// STATUSTEXT the vehicle sends when it refuses the takeoff.
+        takeoff_status_text: Option<&'static str>,
+        status_texts: RefCell<Vec<StatusTextEntry>>,
+        calls: RefCell<Vec<&'static str>>,
+    }
+
+    impl Default for ScriptedVehicle {
+        fn default() -> Self {
+            Self {
+                autopilot: AutopilotType::ArduPilotMega,
+                guided: Cell::default(),
+                armed: Cell::default(),
+                takeoff_error: None,
+                checklist_error: None,
+                takeoff_status_text: None,
+                status_texts: RefCell::default(),
+                calls: RefCell::default(),
+            }
+        }
+    }
+
+    impl ScriptedVehicle {
+        fn send_status_text(&self, text: &str) {
+            let mut history = self.status_texts.borrow_mut();
+            let sequence = status_text_mark(&history) + 1;
+            push_status_text_entry(
+                &mut history,
+                StatusTextEntry {
+                    sequence,
+                    text: text.to_string(),
+                    severity: "error".to_string(),
+                    timestamp_usec: None,
+                    repeat_count: 1,
+                    vehicle_time: None,
+                    failsafe: false,
+                },
+            );
+        }
+    }
+
+    impl TakeoffTarget for ScriptedVehicle {
+        fn autopilot(&self) -> AutopilotType {
+            self.autopilot
+        }
+
+        fn in_guided(&self) -> bool {
+            self.guided.get()
+        }
+
+        fn is_armed(&self) -> bool {
+            self.armed.get()
+        }
+
+        fn ensure_checklist(&self) -> Result<(), AppError> {
+            match &self.checklist_error {
+                Some(error) => Err(error.clone()),
+                None => Ok(()),
+            }
+        }
+
+        fn status_text_mark(&self) -> u64 {
+            status_text_mark(&self.status_texts.borrow())
+        }
+
+        async fn status_texts_after(&self, mark: u64) -> Vec<String> {
+            status_texts_after(&self.status_texts.borrow(), mark)
+        }
+
+        async fn set_guided(&self) -> Result<(), AppError> {
+            self.calls.borrow_mut().push("set_guided");
+            self.guided.set(true);
+            Ok(())
+        }
+
+        async fn arm(&self) -> Result<(), AppError> {
+            self.calls.borrow_mut().push("arm");
+            self.armed.set(true);
+            Ok(())
+        }
+
+        async fn takeoff(&self, _altitude_m: f32) -> Result<(), AppError> {
+            self.calls.borrow_mut().push("takeoff");
+            match &self.takeoff_error {
+                Some(error) => {
+                    if let Some(text) = self.takeoff_status_text {
+                        self.send_status_text(text);
+                    }
+                    Err(error.clone())
+                }
+                None => Ok(()),
+            }
+        }
+    }
+
+    fn statuses(report: &TakeoffReport) -> Vec<TakeoffStepStatus> {
+        report.steps.iter().map(|step| step.status).collect()
+    }
+
+    #[tokio::test]
+    async fn full_sequence_switches_mode_arms_and_takes_off() {
+        let vehicle = ScriptedVehicle::default();
+
+        let report = run_takeoff_sequence(&vehicle, 10.0, true)
+            .await
+            .expect("sequence runs");
+
+        assert!(report.success);
+        assert_eq!(statuses(&report), vec![TakeoffStepStatus::Done; 3]);
+        assert_eq!(
+            *vehicle.calls.borrow(),
+            vec!["set_guided", "arm", "takeoff"]
+        );
+    }
+
+    #[tokio::test]
+    async fn satisfied_steps_are_skipped_and_arming_needs_permission() {
+        let vehicle = ScriptedVehicle {
+            guided: Cell::new(true),
+            ..ScriptedVehicle::default()
+        };
+
+        let report = run_takeoff_sequence(&vehicle, 10.0, false)
+            .await
+            .expect("sequence runs");
+
+        assert!(!report.success);
+        assert_eq!(
+            statuses(&report),
+            vec![TakeoffStepStatus::Skipped, TakeoffStepStatus::Failed]
+        );
+        assert!(vehicle.calls.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn px4_is_refused_before_any_command_is_sent() {
+        let vehicle = ScriptedVehicle {
+            autopilot: AutopilotType::Px4,
+            ..ScriptedVehicle::default()
+        };
+
+        let result = run_takeoff_sequence(&vehicle, 10.0, true).await;
+
+        assert!(matches!(
+            result,
+            Err(AppError::UnsupportedAutopilot {
+                autopilot: AutopilotType::Px4,
+                ..
+            })
+        ));
+        assert!(vehicle.calls.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn incomplete_checklist_blocks_a_sequence_that_would_arm() {
+        let checklist_error = AppError::Validation {
+            message: "checklist incomplete: props".to_string(),
+            issues: vec!["props".to_string()],
+        };
+        let vehicle = ScriptedVehicle {
+            checklist_error: Some(checklist_error.clone()),
+            ..ScriptedVehicle::default()
+        };
+
+        let result = run_takeoff_sequence(&vehicle, 10.0, true).await;
+
+        assert_eq!(result.err(), Some(checklist_error));
+        assert!(vehicle.calls.borrow().is_empty());
+
+        // An armed vehicle is not armed again, so the checklist does not apply.
+        vehicle.armed.set(true);
+        let report = run_takeoff_sequence(&vehicle, 10.0, true)
+            .await
+            .expect("sequence runs");
+        assert!(report.success);
+    }
+
+    #[tokio::test]
+    async fn denied_takeoff_is_explained_by_the_following_statustext() {
+        let vehicle = ScriptedVehicle {
+            guided: Cell::new(true),
+            armed: Cell::new(true),
+            takeoff_error: Some(AppError::CommandDenied {
+                result: MavResultKind::Denied,
+                message: "command rejected".to_string(),
+            }),
+            takeoff_status_text: Some("Takeoff: throttle not at zero"),
+            ..ScriptedVehicle::default()
+        };
+
+        let report = run_takeoff_sequence(&vehicle, 10.0, true)
+            .await
+            .expect("sequence runs");
+
+        assert!(!report.success);
+        let takeoff = report.steps.last().expect("takeoff step");
+        assert_eq!(takeoff.step, TakeoffStep::Takeoff);
+        assert_eq!(takeoff.status, TakeoffStepStatus::Failed);
+        assert_eq!(
+            takeoff.message.as_deref(),
+            Some("Takeoff denied: Takeoff: throttle not at zero")
+        );
+    }
+
+    #[tokio::test]
+    async fn retried_denial_repeating_the_last_statustext_is_still_explained() {
+        let vehicle = ScriptedVehicle {
+            guided: Cell::new(true),
+            armed: Cell::new(true),
+            takeoff_error: Some(AppError::CommandDenied {
+                result: MavResultKind::Denied,
+                message: "command rejected".to_string(),
+            }),
+            takeoff_status_text: Some("Takeoff: throttle not at zero"),
+            ..ScriptedVehicle::default()
+        };
+        vehicle.send_status_text("Takeoff: throttle not at zero");
+
+        let report = run_takeoff_sequence(&vehicle, 10.0, true)
+            .await
+            .expect("sequence runs");
+
+        assert_eq!(vehicle.status_texts.borrow().len(), 1);
+        let takeoff = report.steps.last().expect("takeoff step");
+        assert_eq!(
+            takeoff.message.as_deref(),
+            Some("Takeoff denied: Takeoff: throttle not at zero")
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::any::Any;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ironwing_core::event_names;
+use ironwing_core::task_watchdog::{EVENT_LOOP_STALL_AFTER, TaskTable};
+use mavkit::{LinkState, Vehicle};
+use tauri::Manager;
+use tokio::task::JoinHandle;
+use web_time::Instant;
+
+use crate::AppState;
+use crate::connection::AbortOnDrop;
+use crate::e2e_emit::emit_event;
+use crate::ipc::{Diagnostics, TaskDiagnostic, TaskStalled};
+
+const EVENT_LOOP_TASK: &str = "event_loop";
+const STALL_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The task table shared by every supervised task and the monitor.
+#[derive(Clone, Default)]
+pub(crate) struct TaskWatchdog(Arc<Mutex<TaskTable>>);
+
+impl TaskWatchdog {
+    fn with<R>(&self, apply: impl FnOnce(&mut TaskTable) -> R) -> R {
+        apply(
+            &mut self
+                .0
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        )
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<TaskDiagnostic> {
+        self.with(|table| table.snapshot(Instant::now()))
+    }
+}
+
+/// Stops watching a task when its supervisor is dropped, which is how
+/// aborting the session's tasks reaches the table.
+struct Registration {
+    watchdog: TaskWatchdog,
+    name: String,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.watchdog.with(|table| table.unregister(&self.name));
+    }
+}
+
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => panic.downcast_ref::<&str>().map_or_else(
+            || "unknown panic".to_string(),
+            |message| message.to_string(),
+        ),
+    }
+}
+
+fn report(app: &tauri::AppHandle, stalled: &TaskStalled) {
+    tracing::error!(
+        "internal task {} {:?}{}: {}",
+        stalled.component,
+        stalled.state,
+        if stalled.restarting {
+            ", restarting"
+        } else {
+            ""
+        },
+        stalled.detail.as_deref().unwrap_or("no detail")
+    );
+    emit_event(app, event_names::TASK_STALLED, stalled);
+}
+
+/// Spawn what `make` returns as a task watched under `name`. When the task
+/// panics or returns it is reported on `internal://task_stalled`; after a
+/// panic a `restartable` task is spawned again from `make`, up to
+/// `MAX_TASK_RESTARTS` times. Aborting the returned handle aborts the task.
+fn spawn_supervised<F, Fut>(
+    app: &tauri::AppHandle,
+    name: String,
+    restartable: bool,
+    mut make: F,
+) -> JoinHandle<()>
+where
+    F: FnMut() -> Option<Fut> + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let app = app.clone();
+    tokio::spawn(async move {
+        let watchdog = app.state::<AppState>().task_watchdog.clone();
+        watchdog.with(|table| table.register(&name, None, Instant::now()));
+        let _registration = Registration {
+            watchdog: watchdog.clone(),
+            name: name.clone(),
+        };
+        while let Some(future) = make() {
+            let mut task = AbortOnDrop(tokio::spawn(future));
+            let panic = match (&mut task.0).await {
+                Ok(()) => None,
+                Err(error) if error.is_panic() => Some(panic_message(error.into_panic())),
+                Err(_) => return,
+            };
+            let restarting =
+                restartable && panic.is_some() && watchdog.with(|table| table.can_restart(&name));
+            let stalled = watchdog.with(|table| table.finished(&name, panic, restarting));
+            report(&app, &stalled);
+            if !restarting {
+                return;
+            }
+            watchdog.with(|table| table.restarted(&name, Instant::now()));
+        }
+    })
+}
+
+/// A bridge that can be rebuilt from its inputs, say a fresh subscription
+/// to the vehicle's streams, and so is restarted after a panic.
+pub(crate) fn supervise<F, Fut>(app: &tauri::AppHandle, name: &str, make: F) -> JoinHandle<()>
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    spawn_supervised(app, name.to_string(), true, move || Some(make()))
+}
+
+/// A task that owns state it cannot get back, such as a channel receiver;
+/// reported when it dies but not restarted.
+pub(crate) fn watch<Fut>(app: &tauri::AppHandle, name: String, future: Fut) -> JoinHandle<()>
+where
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let mut future = Some(future);
+    spawn_supervised(app, name, false, move || future.take())
+}
+
+/// Beats for MAVKit's event loop on every message it delivers and checks
+/// the table for overdue heartbeats. A silent event loop only counts as
+/// stalled while MAVKit still reads the link as connected; a vehicle that
+/// went away shows up as link loss instead.
+pub(crate) async fn task_monitor(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let watchdog = handle.state::<AppState>().task_watchdog.clone();
+    watchdog.with(|table| {
+        table.register(
+            EVENT_LOOP_TASK,
+            Some(EVENT_LOOP_STALL_AFTER),
+            Instant::now(),
+        )
+    });
+    let _registration = Registration {
+        watchdog: watchdog.clone(),
+        name: EVENT_LOOP_TASK.to_string(),
+    };
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    let mut interval = tokio::time::interval(STALL_CHECK_INTERVAL);
+    loop {
+        tokio::select! {
+            raw_msg = raw_stream.next() => {
+                if raw_msg.is_none() {
+                    let stalled =
+                        watchdog.with(|table| table.finished(EVENT_LOOP_TASK, None, false));
+                    report(&handle, &stalled);
+                    return;
+                }
+                watchdog.with(|table| table.beat(EVENT_LOOP_TASK, Instant::now()));
+            }
+            _ = interval.tick() => {
+                if !matches!(vehicle.link().state().latest(), Some(LinkState::Connected)) {
+                    // Nothing is expected while disconnected.
+                    watchdog.with(|table| table.beat(EVENT_LOOP_TASK, Instant::now()));
+                    continue;
+                }
+                for stalled in watchdog.with(|table| table.check_stalls(Instant::now())) {
+                    report(&handle, &stalled);
+                }
+            }
+        }
+    }
+}
+
+/// The task table and event throttle counters, for bug reports.
+#[tauri::command]
+pub(crate) fn diagnostics(state: tauri::State<'_, AppState>) -> Diagnostics {
+    Diagnostics {
+        tasks: state.task_watchdog.snapshot(),
+        event_bridges: state
+            .live_runtime
+            .with_runtime(|runtime| runtime.event_bridge_stats()),
+    }
+}
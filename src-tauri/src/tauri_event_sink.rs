@@ -13,6 +13,13 @@ impl TauriEventSink {
             .unwrap_or_else(|poisoned| poisoned.into_inner());
         *guard = Some(handle);
     }
+
+    fn handle(&self) -> Option<tauri::AppHandle> {
+        self.handle
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
 }
 
 impl ironwing_core::live_runtime::EventSink for TauriEventSink {
@@ -20,13 +27,26 @@ impl ironwing_core::live_runtime::EventSink for TauriEventSink {
     where
         T: serde::Serialize + Clone + Send + 'static,
     {
-        let handle = self
-            .handle
-            .lock()
-            .unwrap_or_else(|poisoned| poisoned.into_inner())
-            .clone();
-        if let Some(handle) = handle {
+        if let Some(handle) = self.handle() {
             crate::e2e_emit::emit_event(&handle, event, payload);
         }
     }
+
+    fn emit_to_frontend<T>(&self, event: &'static str, payload: &T)
+    where
+        T: serde::Serialize + Clone + Send + 'static,
+    {
+        if let Some(handle) = self.handle() {
+            crate::e2e_emit::emit_to_webview(&handle, event, payload);
+        }
+    }
+
+    fn emit_to_subscribers<T>(&self, event: &'static str, payload: &T)
+    where
+        T: serde::Serialize + Clone + Send + 'static,
+    {
+        if let Some(handle) = self.handle() {
+            crate::e2e_emit::emit_to_subscribers(&handle, event, payload);
+        }
+    }
 }
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use ironwing_core::dialect::MavMessage;
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::time_sync::{TIMESYNC_MESSAGE_ID, timesync_reply, timesync_request};
+use ironwing_core::vehicle_snapshot::unix_epoch_usec;
+use mavkit::Vehicle;
+use mavlink::MavlinkVersion;
+use tauri::Manager;
+
+use crate::AppState;
+use crate::ipc::TimeSyncStatus;
+
+const TIMESYNC_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+fn unix_ns() -> i64 {
+    unix_epoch_usec() as i64 * 1000
+}
+
+async fn send(vehicle: &Vehicle, message: MavMessage) {
+    if let Err(error) = live_commands::send_raw_message(vehicle, message).await {
+        tracing::debug!("failed to send TIMESYNC: {error}");
+    }
+}
+
+/// Answers the vehicle's TIMESYNC requests and sends our own once a second,
+/// feeding the replies into the runtime's clock estimate. The operation
+/// journal is kept stamped with the latest estimate.
+pub(crate) async fn time_sync_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let identity = vehicle.identity();
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    let mut interval = tokio::time::interval(TIMESYNC_REQUEST_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let state: tauri::State<'_, AppState> = handle.state();
+                let now_ns = unix_ns();
+                let clock = state.live_runtime.with_runtime(|runtime| {
+                    runtime.note_timesync_request(now_ns);
+                    runtime.vehicle_clock()
+                });
+                state.journal.set_vehicle_clock(clock);
+                send(
+                    &vehicle,
+                    timesync_request(now_ns, identity.system_id, identity.component_id),
+                )
+                .await;
+            }
+            raw_msg = raw_stream.next() => {
+                let Some(raw_msg) = raw_msg else {
+                    break;
+                };
+                if raw_msg.message_id != TIMESYNC_MESSAGE_ID
+                    || raw_msg.system_id != identity.system_id
+                {
+                    continue;
+                }
+                let Ok(MavMessage::TIMESYNC(timesync)) =
+                    MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+                else {
+                    continue;
+                };
+                let now_ns = unix_ns();
+                if let Some(reply) =
+                    timesync_reply(&timesync, now_ns, (raw_msg.system_id, raw_msg.component_id))
+                {
+                    send(&vehicle, reply).await;
+                    continue;
+                }
+                let state: tauri::State<'_, AppState> = handle.state();
+                state.live_runtime.with_runtime(|runtime| {
+                    runtime.observe_timesync_reply(timesync.tc1, timesync.ts1, now_ns)
+                });
+            }
+        }
+    }
+    let state: tauri::State<'_, AppState> = handle.state();
+    state.journal.set_vehicle_clock(None);
+}
+
+/// The current offset between the GCS and vehicle clocks.
+#[tauri::command]
+pub(crate) fn time_sync_status(state: tauri::State<'_, AppState>) -> TimeSyncStatus {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.time_sync_status())
+}
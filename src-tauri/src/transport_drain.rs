@@ -0,0 +1,197 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use ironwing_core::event_names;
+use ironwing_core::transport_drain::{DrainRetry, OutgoingQueue};
+use tauri::Manager;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::AppState;
+use crate::e2e_emit::emit_event;
+use crate::ipc::{TransportLost, TransportStats};
+use crate::task_watchdog::watch;
+
+/// Write counters of one drain task, shared with `connection_info`.
+#[derive(Clone, Default)]
+pub(crate) struct DrainStats(Arc<Mutex<TransportStats>>);
+
+impl DrainStats {
+    fn update(&self, apply: impl FnOnce(&mut TransportStats)) {
+        apply(
+            &mut self
+                .0
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        );
+    }
+
+    pub(crate) fn snapshot(&self) -> TransportStats {
+        *self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Write everything MAVKit sends to the transport through `send`. Transient
+/// write errors are retried with backoff while further messages wait in an
+/// [`OutgoingQueue`] of `capacity` messages. Returns `Ok` when MAVKit closes
+/// its side, or the last error once the failures are persistent.
+pub(crate) async fn drain_outgoing<S, Fut, E>(
+    mut outgoing_rx: mpsc::Receiver<Vec<u8>>,
+    capacity: usize,
+    chunk_size: Option<usize>,
+    stats: DrainStats,
+    mut send: S,
+) -> Result<(), String>
+where
+    S: FnMut(Vec<u8>) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: std::fmt::Display,
+{
+    let mut queue = OutgoingQueue::new(capacity, chunk_size);
+    let mut retry = DrainRetry::default();
+    loop {
+        if queue.is_empty() {
+            match outgoing_rx.recv().await {
+                Some(message) => {
+                    queue.push(message);
+                }
+                None => return Ok(()),
+            }
+        }
+        while let Ok(message) = outgoing_rx.try_recv() {
+            if queue.push(message) {
+                stats.update(|stats| stats.dropped_messages += 1);
+            }
+        }
+        let Some(chunk) = queue.next_chunk().map(<[u8]>::to_vec) else {
+            continue;
+        };
+        match send(chunk).await {
+            Ok(()) => {
+                queue.chunk_sent();
+                retry.succeeded();
+                stats.update(|stats| stats.chunks_sent += 1);
+            }
+            Err(error) => {
+                let error = error.to_string();
+                stats.update(|stats| stats.write_failures += 1);
+                let Some(delay) = retry.failed() else {
+                    return Err(error);
+                };
+                tracing::debug!("transport write failed, retrying in {delay:?}: {error}");
+                stats.update(|stats| stats.write_retries += 1);
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+}
+
+/// Run [`drain_outgoing`] for `transport` under the task watchdog; when it
+/// gives up, report the transport lost and disconnect the way a lost link is
+/// handled.
+pub(crate) fn spawn_drain_task<S, Fut, E>(
+    app: &tauri::AppHandle,
+    transport: &'static str,
+    outgoing_rx: mpsc::Receiver<Vec<u8>>,
+    chunk_size: Option<usize>,
+    stats: DrainStats,
+    send: S,
+) -> JoinHandle<()>
+where
+    S: FnMut(Vec<u8>) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), E>> + Send + 'static,
+    E: std::fmt::Display + 'static,
+{
+    let capacity = crate::connection::LINK_BUFFER_FRAMES.load(std::sync::atomic::Ordering::Relaxed);
+    let name = format!("drain:{transport}");
+    let supervisor = app;
+    let app = app.clone();
+    watch(supervisor, name, async move {
+        let Err(error) =
+            drain_outgoing(outgoing_rx, capacity, chunk_size, stats.clone(), send).await
+        else {
+            return;
+        };
+        tracing::warn!("{transport} writes kept failing; disconnecting: {error}");
+        let lost = TransportLost {
+            transport: transport.to_string(),
+            error,
+            stats: stats.snapshot(),
+        };
+        emit_event(&app, event_names::LINK_TRANSPORT_LOST, &lost);
+        // The teardown aborts this task, so disconnect from a fresh one.
+        tokio::spawn(async move {
+            let state = app.state::<AppState>();
+            if let Err(error) = crate::connection::force_disconnect(&state, &app).await {
+                tracing::warn!("disconnect after {transport} was lost failed: {error}");
+            }
+        });
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ironwing_core::transport_drain::DRAIN_MAX_CONSECUTIVE_FAILURES;
+
+    #[tokio::test(start_paused = true)]
+    async fn a_queued_arm_command_survives_two_failed_writes() {
+        let (outgoing_tx, outgoing_rx) = mpsc::channel(8);
+        let (sent_tx, mut sent_rx) = mpsc::unbounded_channel();
+        let stats = DrainStats::default();
+        let mut attempts = 0;
+        let drain = tokio::spawn(drain_outgoing(
+            outgoing_rx,
+            8,
+            Some(4),
+            stats.clone(),
+            move |chunk: Vec<u8>| {
+                attempts += 1;
+                let result = if attempts <= 2 {
+                    Err("GATT busy")
+                } else {
+                    sent_tx.send(chunk).map_err(|_| "closed")
+                };
+                async move { result }
+            },
+        ));
+
+        let arm_command = b"COMMAND_LONG arm".to_vec();
+        outgoing_tx.send(arm_command.clone()).await.unwrap();
+        drop(outgoing_tx);
+        assert_eq!(drain.await.unwrap(), Ok(()));
+
+        let mut written = Vec::new();
+        while let Ok(chunk) = sent_rx.try_recv() {
+            written.extend(chunk);
+        }
+        assert_eq!(written, arm_command);
+        let stats = stats.snapshot();
+        assert_eq!(stats.write_failures, 2);
+        assert_eq!(stats.write_retries, 2);
+        assert_eq!(stats.chunks_sent, 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn persistent_failures_end_the_drain() {
+        let (outgoing_tx, outgoing_rx) = mpsc::channel(8);
+        let stats = DrainStats::default();
+        outgoing_tx.send(vec![0xfd, 0x09]).await.unwrap();
+
+        let result = drain_outgoing(outgoing_rx, 8, None, stats.clone(), |_chunk| async {
+            Err::<(), _>("not connected")
+        })
+        .await;
+
+        assert_eq!(result, Err("not connected".to_string()));
+        let stats = stats.snapshot();
+        assert_eq!(
+            stats.write_failures,
+            u64::from(DRAIN_MAX_CONSECUTIVE_FAILURES)
+        );
+        assert_eq!(stats.chunks_sent, 0);
+    }
+}
@@ -0,0 +1,113 @@
+use std::sync::{Arc, Mutex};
+
+use ironwing_core::dialect::MavMessage;
+use ironwing_core::tune_analysis::{
+    TUNE_CAPTURE_INTERVAL_USEC, TUNE_CAPTURE_MESSAGE_IDS, TuneCapture, restore_interval_usec,
+};
+use ironwing_core::vehicle_snapshot::unix_epoch_usec;
+use mavkit::Vehicle;
+use mavlink::MavlinkVersion;
+
+use crate::AppState;
+use crate::helpers::{ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{AppError, LinkProfile, OperationId, TuneAxis, TuneReport};
+
+pub(crate) struct ActiveTuneCapture {
+    vehicle: Vehicle,
+    capture: Arc<Mutex<TuneCapture>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+async fn record(vehicle: Vehicle, capture: Arc<Mutex<TuneCapture>>) {
+    use tokio_stream::StreamExt;
+
+    let system_id = vehicle.identity().system_id;
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    while let Some(raw_msg) = raw_stream.next().await {
+        if !TUNE_CAPTURE_MESSAGE_IDS.contains(&raw_msg.message_id) || raw_msg.system_id != system_id
+        {
+            continue;
+        }
+        let Ok(message) =
+            MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+        else {
+            continue;
+        };
+        capture.lock().expect("tune capture lock").observe(&message);
+    }
+}
+
+async fn set_intervals(vehicle: &Vehicle, interval_usec: impl Fn(u32) -> i32) {
+    for message_id in TUNE_CAPTURE_MESSAGE_IDS {
+        if let Err(error) = vehicle
+            .raw()
+            .set_message_interval(message_id, interval_usec(message_id))
+            .await
+        {
+            tracing::warn!("failed to set the interval of message {message_id}: {error}");
+        }
+    }
+}
+
+/// Start recording `axis` for step-response analysis. ATTITUDE and the
+/// target messages are raised to 50 Hz until `tune_capture_stop`.
+#[tauri::command]
+pub(crate) async fn tune_capture_start(
+    state: tauri::State<'_, AppState>,
+    axis: TuneAxis,
+) -> Result<(), AppError> {
+    ensure_live_write_allowed(state.inner(), OperationId::SetMessageRate).await?;
+    let vehicle = with_vehicle(&state).await?;
+    if state
+        .live_runtime
+        .with_runtime(|runtime| runtime.link_profile())
+        == LinkProfile::HighLatency
+    {
+        return Err("message rates are suppressed while the link is in high-latency mode".into());
+    }
+    let mut active = state.tune_capture.lock().await;
+    if active.is_some() {
+        return Err(AppError::validation("a tuning capture is already running"));
+    }
+    let capture = Arc::new(Mutex::new(TuneCapture::new(
+        axis,
+        unix_epoch_usec() / 1_000,
+    )));
+    let task = tokio::spawn(record(vehicle.clone(), capture.clone()));
+    set_intervals(&vehicle, |_| TUNE_CAPTURE_INTERVAL_USEC).await;
+    *active = Some(ActiveTuneCapture {
+        vehicle,
+        capture,
+        task,
+    });
+    Ok(())
+}
+
+/// Stop recording, put the message intervals back and analyse every step
+/// in the target seen during the capture.
+#[tauri::command]
+pub(crate) async fn tune_capture_stop(
+    state: tauri::State<'_, AppState>,
+) -> Result<TuneReport, AppError> {
+    let active = state
+        .tune_capture
+        .lock()
+        .await
+        .take()
+        .ok_or_else(|| AppError::validation("no tuning capture is running"))?;
+    active.task.abort();
+    set_intervals(&active.vehicle, restore_interval_usec).await;
+    let capture = active.capture.lock().expect("tune capture lock").clone();
+    Ok(capture.finish())
+}
+
+/// Save `report` as pretty-printed JSON.
+#[tauri::command]
+pub(crate) async fn tune_report_export(path: String, report: TuneReport) -> Result<(), AppError> {
+    let json = serde_json::to_vec_pretty(&report)
+        .map_err(|error| format!("failed to serialize the report: {error}"))?;
+    tokio::fs::write(&path, json)
+        .await
+        .map_err(|error| AppError::internal(format!("failed to write {path}: {error}")))
+}
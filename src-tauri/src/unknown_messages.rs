@@ -0,0 +1,65 @@
+use std::sync::LazyLock;
+use std::time::Instant;
+
+use ironwing_core::event_names;
+use ironwing_core::live_runtime;
+use ironwing_core::mavlink_frames::{UnknownFrame, is_known_message};
+use ironwing_core::unknown_messages::UnknownMessageTracker;
+use mavkit::Vehicle;
+use tauri::Manager;
+use tokio::sync::broadcast;
+
+use crate::AppState;
+
+/// Frames the transport scanners set aside as outside the dialect. The
+/// scanners run on plugin callbacks and port threads, ahead of any session,
+/// so they publish here and the bridge of the current session listens.
+static UNKNOWN_FRAMES: LazyLock<broadcast::Sender<UnknownFrame>> =
+    LazyLock::new(|| broadcast::channel(256).0);
+
+pub(crate) fn publish(frames: Vec<UnknownFrame>) {
+    for frame in frames {
+        // No receiver just means no session is listening yet.
+        let _ = UNKNOWN_FRAMES.send(frame);
+    }
+}
+
+/// Relays messages outside the dialect as `mavlink://unknown`, at most once a
+/// second per source and message id. They come from the transport scanners
+/// and from anything MAVKit hands through undecoded.
+pub(crate) async fn unknown_message_bridge(handle: tauri::AppHandle, vehicle: Vehicle) {
+    use tokio_stream::StreamExt;
+
+    let mut scanned = UNKNOWN_FRAMES.subscribe();
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    let mut tracker = UnknownMessageTracker::default();
+    loop {
+        let frame = tokio::select! {
+            frame = scanned.recv() => match frame {
+                Ok(frame) => frame,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            raw_msg = raw_stream.next() => {
+                let Some(raw_msg) = raw_msg else {
+                    break;
+                };
+                if is_known_message(raw_msg.message_id) {
+                    continue;
+                }
+                UnknownFrame {
+                    system_id: raw_msg.system_id,
+                    component_id: raw_msg.component_id,
+                    message_id: raw_msg.message_id,
+                    payload: raw_msg.payload.to_vec(),
+                }
+            }
+        };
+        let Some(message) = tracker.observe(frame, Instant::now()) else {
+            continue;
+        };
+        let state: tauri::State<'_, AppState> = handle.state();
+        live_runtime::emit_scoped(&state.live_runtime, event_names::MAVLINK_UNKNOWN, message);
+    }
+}
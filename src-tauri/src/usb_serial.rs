@@ -0,0 +1,31 @@
+use crate::ipc::{AppError, UsbSerialDevice};
+#[cfg(target_os = "android")]
+use tauri::Manager;
+
+#[cfg(target_os = "android")]
+#[tauri::command]
+pub(crate) async fn list_usb_devices(
+    app: tauri::AppHandle,
+) -> Result<Vec<UsbSerialDevice>, AppError> {
+    let usb: tauri::State<'_, tauri_plugin_usb_serial::UsbSerial<tauri::Wry>> = app.state();
+    let devices = usb
+        .list_devices()
+        .map_err(|e: Box<dyn std::error::Error>| e.to_string())?;
+    Ok(devices
+        .into_iter()
+        .map(|d| UsbSerialDevice {
+            device_id: d.device_id,
+            vendor_id: d.vendor_id,
+            product_id: d.product_id,
+            manufacturer: d.manufacturer,
+            product: d.product,
+            has_permission: d.has_permission,
+        })
+        .collect())
+}
+
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+pub(crate) async fn list_usb_devices() -> Result<Vec<UsbSerialDevice>, AppError> {
+    Err(AppError::validation("not supported on desktop"))
+}
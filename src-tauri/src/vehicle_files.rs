@@ -0,0 +1,268 @@
+use std::path::Path;
+use std::time::Duration;
+
+use ironwing_core::dialect::MavMessage;
+use ironwing_core::event_names;
+use ironwing_core::live_runtime;
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::mavftp::{
+    FIRMWARE_STAGE_PATH, FtpDirEntry, FtpError, SCRIPTING_DISABLED, SCRIPTS_DIR,
+    STORAGE_INFORMATION_MESSAGE_ID, check_free_space, request_storage_information,
+    script_remote_path, scripts_error, validate_firmware_image,
+};
+use mavkit::Vehicle;
+use mavlink::MavlinkVersion;
+use serde_json::json;
+use tokio_stream::StreamExt;
+
+use crate::AppState;
+use crate::helpers::{ensure_live_write_allowed, vehicle_is_armed, with_vehicle};
+use crate::ipc::{
+    AppError, FileTransferPhase, FileTransferProgress, FileUploadReport, OperationId, ScriptFile,
+};
+use crate::journal;
+use crate::mavftp::FtpClient;
+
+/// How long to wait for STORAGE_INFORMATION. Autopilots that do not send it
+/// get the upload anyway and report a full card as it happens.
+const STORAGE_INFO_WAIT: Duration = Duration::from_secs(2);
+
+/// File operations stall the autopilot's SD card access; none run in flight.
+async fn disarmed_vehicle(state: &AppState) -> Result<Vehicle, AppError> {
+    let vehicle = with_vehicle(state).await?;
+    if vehicle_is_armed(&vehicle) {
+        return Err(AppError::validation(
+            "disarm before changing files on the vehicle",
+        ));
+    }
+    Ok(vehicle)
+}
+
+fn scr_enable(vehicle: &Vehicle) -> Option<f32> {
+    live_commands::param_get_all(vehicle)
+        .ok()?
+        .params
+        .get("SCR_ENABLE")
+        .map(|param| param.value)
+}
+
+fn local_file_name(local_path: &str) -> Result<String, String> {
+    Path::new(local_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("'{local_path}' is not a file path"))
+}
+
+/// Refuse an upload of `len` bytes when the autopilot reports too little free
+/// space on its SD card.
+async fn ensure_free_space(vehicle: &Vehicle, len: usize) -> Result<(), AppError> {
+    let identity = vehicle.identity();
+    let request = request_storage_information(identity.system_id, identity.component_id);
+    // Subscribe before asking so a fast answer cannot slip past.
+    let raw_stream = vehicle.raw().subscribe();
+    tokio::pin!(raw_stream);
+    live_commands::send_raw_message(vehicle, request.message())
+        .await
+        .map_err(AppError::from)?;
+    let storage = tokio::time::timeout(STORAGE_INFO_WAIT, async {
+        while let Some(raw_msg) = raw_stream.next().await {
+            if raw_msg.message_id != STORAGE_INFORMATION_MESSAGE_ID
+                || raw_msg.system_id != identity.system_id
+            {
+                continue;
+            }
+            if let Ok(MavMessage::STORAGE_INFORMATION(storage)) =
+                MavMessage::parse(MavlinkVersion::V2, raw_msg.message_id, &raw_msg.payload)
+            {
+                return Some(storage);
+            }
+        }
+        None
+    })
+    .await;
+    match storage {
+        Ok(Some(storage)) => check_free_space(&storage, len).map_err(AppError::validation),
+        _ => Ok(()),
+    }
+}
+
+/// Emits `ftp://progress` whenever the whole percentage moves.
+fn progress_reporter<'a>(
+    state: &'a AppState,
+    remote_path: &'a str,
+    phase: FileTransferPhase,
+) -> impl FnMut(u64, u64) + 'a {
+    let mut last_percent = None;
+    move |bytes_done, bytes_total| {
+        let percent = (bytes_done * 100).checked_div(bytes_total).unwrap_or(100);
+        if last_percent == Some(percent) {
+            return;
+        }
+        last_percent = Some(percent);
+        live_runtime::emit_scoped(
+            &state.live_runtime,
+            event_names::FTP_PROGRESS,
+            FileTransferProgress {
+                remote_path: remote_path.to_string(),
+                phase,
+                bytes_done,
+                bytes_total,
+            },
+        );
+    }
+}
+
+/// Write `contents` to `remote_path`, then read it back and compare CRC32s.
+/// `explain` turns FTP errors into messages for the user.
+async fn upload_verified(
+    state: &AppState,
+    vehicle: &Vehicle,
+    remote_path: &str,
+    contents: &[u8],
+    explain: impl Fn(&FtpError) -> String,
+) -> Result<FileUploadReport, String> {
+    let mut client = FtpClient::new(vehicle).await;
+    client
+        .write_file(
+            remote_path,
+            contents,
+            &mut progress_reporter(state, remote_path, FileTransferPhase::Upload),
+        )
+        .await
+        .map_err(|error| explain(&error))?;
+    let written = client
+        .read_file(
+            remote_path,
+            &mut progress_reporter(state, remote_path, FileTransferPhase::Verify),
+        )
+        .await
+        .map_err(|error| format!("uploaded, but reading it back failed: {}", explain(&error)))?;
+
+    let crc32 = crc32fast::hash(contents);
+    if written.len() != contents.len() || crc32fast::hash(&written) != crc32 {
+        return Err(format!(
+            "verification failed: {remote_path} on the vehicle does not match the local file"
+        ));
+    }
+    Ok(FileUploadReport {
+        remote_path: remote_path.to_string(),
+        size_bytes: contents.len() as u64,
+        crc32,
+    })
+}
+
+/// Lua scripts in the vehicle's `/APM/scripts`.
+#[tauri::command]
+pub(crate) async fn scripts_list(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ScriptFile>, AppError> {
+    let vehicle = disarmed_vehicle(&state).await?;
+    let entries = FtpClient::new(&vehicle)
+        .await
+        .list_directory(SCRIPTS_DIR)
+        .await
+        .map_err(|error| scripts_error(&error, scr_enable(&vehicle)))?;
+    Ok(entries
+        .into_iter()
+        .filter_map(|entry| match entry {
+            FtpDirEntry::File { name, size } => Some(ScriptFile {
+                name,
+                size_bytes: size,
+            }),
+            FtpDirEntry::Directory { .. } => None,
+        })
+        .collect())
+}
+
+/// Upload a `.lua` file into `/APM/scripts` under its own name. Scripts run
+/// after the next reboot or `scripting restart`.
+#[tauri::command]
+pub(crate) async fn script_upload(
+    state: tauri::State<'_, AppState>,
+    local_path: String,
+) -> Result<FileUploadReport, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::ScriptUpload,
+        json!({ "local_path": local_path }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::ScriptUpload).await?;
+            let remote_path = local_file_name(&local_path)
+                .and_then(|name| script_remote_path(&name))
+                .map_err(AppError::validation)?;
+            let vehicle = disarmed_vehicle(&state).await?;
+            let contents = tokio::fs::read(&local_path)
+                .await
+                .map_err(|error| format!("failed to read {local_path}: {error}"))?;
+            let enabled = scr_enable(&vehicle);
+            if enabled == Some(0.0) {
+                return Err(AppError::validation(SCRIPTING_DISABLED));
+            }
+            upload_verified(&state, &vehicle, &remote_path, &contents, |error| {
+                scripts_error(error, enabled)
+            })
+            .await
+            .map_err(AppError::from)
+        },
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn script_delete(
+    state: tauri::State<'_, AppState>,
+    name: String,
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::ScriptDelete,
+        json!({ "name": name }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::ScriptDelete).await?;
+            let remote_path = script_remote_path(&name).map_err(AppError::validation)?;
+            let vehicle = disarmed_vehicle(&state).await?;
+            FtpClient::new(&vehicle)
+                .await
+                .remove_file(&remote_path)
+                .await
+                .map_err(|error| AppError::internal(error.to_string()))
+        },
+    )
+    .await
+}
+
+/// Upload an `.abin` image to where ArduPilot looks for one at boot; the
+/// vehicle flashes it on the next reboot. An image the SD card cannot hold is
+/// refused before the transfer starts.
+#[tauri::command]
+pub(crate) async fn firmware_stage(
+    state: tauri::State<'_, AppState>,
+    local_path: String,
+) -> Result<FileUploadReport, AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::FirmwareStage,
+        json!({ "local_path": local_path }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::FirmwareStage).await?;
+            local_file_name(&local_path)
+                .and_then(|name| validate_firmware_image(&name))
+                .map_err(AppError::validation)?;
+            let vehicle = disarmed_vehicle(&state).await?;
+            let contents = tokio::fs::read(&local_path)
+                .await
+                .map_err(|error| format!("failed to read {local_path}: {error}"))?;
+            ensure_free_space(&vehicle, contents.len()).await?;
+            upload_verified(
+                &state,
+                &vehicle,
+                FIRMWARE_STAGE_PATH,
+                &contents,
+                FtpError::to_string,
+            )
+            .await
+            .map_err(AppError::from)
+        },
+    )
+    .await
+}
@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use ironwing_core::live_runtime::commands as live_commands;
+use ironwing_core::video_streams::{missing_video_streams, request_video_stream};
+use mavkit::Vehicle;
+
+use crate::AppState;
+use crate::helpers::with_vehicle;
+use crate::ipc::{AppError, VideoStream};
+
+/// How long to wait for cameras to describe their streams. A vehicle without
+/// a camera never answers, so this bounds the wait rather than failing.
+const VIDEO_STREAM_WAIT: Duration = Duration::from_secs(3);
+const VIDEO_STREAM_POLL: Duration = Duration::from_millis(100);
+
+fn cached_streams(state: &AppState) -> Vec<VideoStream> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.video_streams().to_vec())
+}
+
+async fn request_streams(
+    vehicle: &Vehicle,
+    targets: &[(Option<u8>, u8)],
+) -> Result<(), AppError> {
+    let system_id = vehicle.identity().system_id;
+    for (component_id, stream_id) in targets {
+        let command = request_video_stream(system_id, *component_id, *stream_id);
+        live_commands::send_raw_message(vehicle, command.message())
+            .await
+            .map_err(AppError::from)?;
+    }
+    Ok(())
+}
+
+/// Video streams advertised by the vehicle's cameras. The list is cached for
+/// the session (and kept current by `video://streams`), so only the first
+/// call waits for answers; with no camera it returns an empty list.
+#[tauri::command]
+pub(crate) async fn video_streams(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<VideoStream>, AppError> {
+    let vehicle = with_vehicle(&state).await?;
+    let cached = cached_streams(&state);
+    if !cached.is_empty() && missing_video_streams(&cached).is_empty() {
+        return Ok(cached);
+    }
+
+    request_streams(&vehicle, &[(None, 0)]).await?;
+    let deadline = tokio::time::Instant::now() + VIDEO_STREAM_WAIT;
+    let mut requested_missing = false;
+    while tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(VIDEO_STREAM_POLL).await;
+        let streams = cached_streams(&state);
+        if streams.is_empty() {
+            continue;
+        }
+        let missing = missing_video_streams(&streams);
+        if missing.is_empty() {
+            return Ok(streams);
+        }
+        // Cameras that only answer for one stream id at a time.
+        if !requested_missing {
+            requested_missing = true;
+            let targets: Vec<_> = missing
+                .into_iter()
+                .map(|(component_id, stream_id)| (Some(component_id), stream_id))
+                .collect();
+            request_streams(&vehicle, &targets).await?;
+        }
+    }
+    Ok(cached_streams(&state))
+}
@@ -0,0 +1,59 @@
+use ironwing_core::component_command::ComponentCommand;
+use ironwing_core::dialect::MavCmd;
+use ironwing_core::vtol::{ensure_transition_allowed, transition_params};
+use serde_json::json;
+
+use crate::AppState;
+use crate::component_commands::run_command_exchange;
+use crate::helpers::{ensure_live_write_allowed, with_vehicle};
+use crate::ipc::{AppError, OperationId, VtolStatus, VtolTransitionTarget};
+use crate::journal;
+
+/// The last EXTENDED_SYS_STATE of the connected vehicle; `None` until one
+/// arrives.
+#[tauri::command]
+pub(crate) fn vtol_status(state: tauri::State<'_, AppState>) -> Option<VtolStatus> {
+    state
+        .live_runtime
+        .with_runtime(|runtime| runtime.vtol_status())
+}
+
+/// Ask a VTOL to transition with MAV_CMD_DO_VTOL_TRANSITION and wait for
+/// the autopilot to accept it. Refused while EXTENDED_SYS_STATE says the
+/// vehicle is on the ground; the transition itself shows up on
+/// `vehicle://vtol`.
+#[tauri::command]
+pub(crate) async fn vtol_transition(
+    state: tauri::State<'_, AppState>,
+    to: VtolTransitionTarget,
+) -> Result<(), AppError> {
+    journal::record(
+        &state.journal,
+        OperationId::VtolTransition,
+        json!({ "to": to }),
+        async {
+            ensure_live_write_allowed(state.inner(), OperationId::VtolTransition).await?;
+            let vehicle = with_vehicle(&state).await?;
+            ensure_transition_allowed(
+                state
+                    .live_runtime
+                    .with_runtime(|runtime| runtime.vtol_status()),
+            )
+            .map_err(AppError::validation)?;
+            let identity = vehicle.identity();
+            run_command_exchange(
+                &state.command_link,
+                &vehicle,
+                ComponentCommand {
+                    system_id: identity.system_id,
+                    component_id: identity.component_id,
+                    command: MavCmd::MAV_CMD_DO_VTOL_TRANSITION,
+                    params: transition_params(to),
+                },
+            )
+            .await?
+            .result
+        },
+    )
+    .await
+}
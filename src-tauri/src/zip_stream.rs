@@ -0,0 +1,242 @@
+//! Minimal streaming zip writer: entries are deflated straight into the
+//! output with their sizes in a trailing data descriptor, so nothing is
+//! buffered and the output does not need to be seekable. No zip64, so the
+//! archive and every entry must stay under 4 GiB.
+
+use std::io::{self, Read, Write};
+
+use flate2::Compression;
+use flate2::write::DeflateEncoder;
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const DATA_DESCRIPTOR_SIGNATURE: u32 = 0x0807_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+const VERSION_NEEDED: u16 = 20;
+/// Bit 3: sizes follow the data; bit 11: names are UTF-8.
+const FLAGS: u16 = 0x0808;
+const METHOD_DEFLATE: u16 = 8;
+const COPY_CHUNK: usize = 64 * 1024;
+
+struct CountingWriter<W> {
+    inner: W,
+    count: u64,
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.count += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+struct CentralEntry {
+    name: String,
+    crc: u32,
+    compressed: u32,
+    uncompressed: u32,
+    offset: u32,
+}
+
+pub(crate) struct ZipStreamWriter<W: Write> {
+    out: CountingWriter<W>,
+    entries: Vec<CentralEntry>,
+    dos_time: u16,
+    dos_date: u16,
+}
+
+fn too_large() -> io::Error {
+    io::Error::other("archive exceeds 4 GiB; zip64 is not supported")
+}
+
+fn to_u32(value: u64) -> io::Result<u32> {
+    u32::try_from(value).map_err(|_| too_large())
+}
+
+/// MS-DOS time and date fields for a UTC timestamp.
+fn dos_timestamp(unix_secs: i64) -> (u16, u16) {
+    let (year, month, day) = crate::recording::civil_from_days(unix_secs.div_euclid(86_400));
+    let seconds_of_day = unix_secs.rem_euclid(86_400);
+    let time = ((seconds_of_day / 3_600) << 11)
+        | (((seconds_of_day % 3_600) / 60) << 5)
+        | ((seconds_of_day % 60) / 2);
+    // DOS dates start in 1980.
+    let date = ((year.clamp(1980, 2107) - 1980) << 9) | (month << 5) | day;
+    (time as u16, date as u16)
+}
+
+impl<W: Write> ZipStreamWriter<W> {
+    /// Every entry is stamped with `unix_secs`.
+    pub(crate) fn new(inner: W, unix_secs: i64) -> Self {
+        let (dos_time, dos_date) = dos_timestamp(unix_secs);
+        Self {
+            out: CountingWriter { inner, count: 0 },
+            entries: Vec::new(),
+            dos_time,
+            dos_date,
+        }
+    }
+
+    /// Deflate everything `reader` yields into a new entry. Returns the
+    /// uncompressed size.
+    pub(crate) fn add_entry(&mut self, name: &str, reader: &mut impl Read) -> io::Result<u64> {
+        let offset = to_u32(self.out.count)?;
+        let name_len = u16::try_from(name.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "entry name too long"))?;
+
+        let out = &mut self.out;
+        out.write_all(&LOCAL_HEADER_SIGNATURE.to_le_bytes())?;
+        out.write_all(&VERSION_NEEDED.to_le_bytes())?;
+        out.write_all(&FLAGS.to_le_bytes())?;
+        out.write_all(&METHOD_DEFLATE.to_le_bytes())?;
+        out.write_all(&self.dos_time.to_le_bytes())?;
+        out.write_all(&self.dos_date.to_le_bytes())?;
+        // CRC and sizes are in the data descriptor.
+        out.write_all(&[0; 12])?;
+        out.write_all(&name_len.to_le_bytes())?;
+        out.write_all(&0u16.to_le_bytes())?;
+        out.write_all(name.as_bytes())?;
+
+        let data_start = out.count;
+        let mut hasher = crc32fast::Hasher::new();
+        let mut uncompressed = 0u64;
+        let mut encoder = DeflateEncoder::new(&mut *out, Compression::default());
+        let mut buffer = vec![0; COPY_CHUNK];
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            hasher.update(&buffer[..read]);
+            uncompressed += read as u64;
+            encoder.write_all(&buffer[..read])?;
+        }
+        encoder.finish()?;
+
+        let crc = hasher.finalize();
+        let compressed = to_u32(out.count - data_start)?;
+        let uncompressed_u32 = to_u32(uncompressed)?;
+        out.write_all(&DATA_DESCRIPTOR_SIGNATURE.to_le_bytes())?;
+        out.write_all(&crc.to_le_bytes())?;
+        out.write_all(&compressed.to_le_bytes())?;
+        out.write_all(&uncompressed_u32.to_le_bytes())?;
+
+        self.entries.push(CentralEntry {
+            name: name.to_string(),
+            crc,
+            compressed,
+            uncompressed: uncompressed_u32,
+            offset,
+        });
+        Ok(uncompressed)
+    }
+
+    /// Write the central directory. Returns the inner writer and the archive
+    /// size.
+    pub(crate) fn finish(mut self) -> io::Result<(W, u64)> {
+        let directory_start = to_u32(self.out.count)?;
+        let entry_count = u16::try_from(self.entries.len())
+            .map_err(|_| io::Error::other("too many entries for a zip archive"))?;
+        for entry in &self.entries {
+            let out = &mut self.out;
+            out.write_all(&CENTRAL_HEADER_SIGNATURE.to_le_bytes())?;
+            // Made by: MS-DOS, same version as needed.
+            out.write_all(&VERSION_NEEDED.to_le_bytes())?;
+            out.write_all(&VERSION_NEEDED.to_le_bytes())?;
+            out.write_all(&FLAGS.to_le_bytes())?;
+            out.write_all(&METHOD_DEFLATE.to_le_bytes())?;
+            out.write_all(&self.dos_time.to_le_bytes())?;
+            out.write_all(&self.dos_date.to_le_bytes())?;
+            out.write_all(&entry.crc.to_le_bytes())?;
+            out.write_all(&entry.compressed.to_le_bytes())?;
+            out.write_all(&entry.uncompressed.to_le_bytes())?;
+            out.write_all(&(entry.name.len() as u16).to_le_bytes())?;
+            // Extra field, comment, disk number, internal and external
+            // attributes.
+            out.write_all(&[0; 12])?;
+            out.write_all(&entry.offset.to_le_bytes())?;
+            out.write_all(entry.name.as_bytes())?;
+        }
+        let directory_size = to_u32(self.out.count)? - directory_start;
+
+        let out = &mut self.out;
+        out.write_all(&END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes())?;
+        out.write_all(&[0; 4])?;
+        out.write_all(&entry_count.to_le_bytes())?;
+        out.write_all(&entry_count.to_le_bytes())?;
+        out.write_all(&directory_size.to_le_bytes())?;
+        out.write_all(&directory_start.to_le_bytes())?;
+        out.write_all(&0u16.to_le_bytes())?;
+        out.flush()?;
+        let size = out.count;
+        Ok((self.out.inner, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use flate2::read::DeflateDecoder;
+
+    use super::*;
+
+    fn u16_at(bytes: &[u8], at: usize) -> u16 {
+        u16::from_le_bytes([bytes[at], bytes[at + 1]])
+    }
+
+    fn u32_at(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn entries_round_trip_through_the_central_directory() {
+        let mut zip = ZipStreamWriter::new(Vec::new(), 1_700_000_000);
+        let body = b"PreArm: Need 3D Fix\n".repeat(500);
+        zip.add_entry("statustext.json", &mut body.as_slice())
+            .unwrap();
+        zip.add_entry("manifest.json", &mut &b"{}"[..]).unwrap();
+        let (bytes, size) = zip.finish().unwrap();
+        assert_eq!(size, bytes.len() as u64);
+
+        let end = bytes.len() - 22;
+        assert_eq!(u32_at(&bytes, end), END_OF_CENTRAL_DIRECTORY_SIGNATURE);
+        assert_eq!(u16_at(&bytes, end + 10), 2);
+        let directory = u32_at(&bytes, end + 16) as usize;
+        assert_eq!(u32_at(&bytes, directory), CENTRAL_HEADER_SIGNATURE);
+
+        let crc = u32_at(&bytes, directory + 16);
+        let compressed = u32_at(&bytes, directory + 20) as usize;
+        let uncompressed = u32_at(&bytes, directory + 24) as usize;
+        let name_len = u16_at(&bytes, directory + 28) as usize;
+        let offset = u32_at(&bytes, directory + 42) as usize;
+        assert_eq!(
+            &bytes[directory + 46..directory + 46 + name_len],
+            b"statustext.json"
+        );
+        assert_eq!(uncompressed, body.len());
+        assert!(compressed < uncompressed);
+
+        assert_eq!(u32_at(&bytes, offset), LOCAL_HEADER_SIGNATURE);
+        let data = offset + 30 + name_len;
+        let mut inflated = Vec::new();
+        DeflateDecoder::new(&bytes[data..data + compressed])
+            .read_to_end(&mut inflated)
+            .unwrap();
+        assert_eq!(inflated, body);
+        assert_eq!(crc, crc32fast::hash(&body));
+    }
+
+    #[test]
+    fn dos_timestamp_packs_utc_fields() {
+        // 2023-11-14 22:13:20 UTC
+        let (time, date) = dos_timestamp(1_700_000_000);
+        assert_eq!(time, (22 << 11) | (13 << 5) | 10);
+        assert_eq!(date, ((2023 - 1980) << 9) | (11 << 5) | 14);
+    }
+}
@@ -1,5 +1,13 @@
 import { EVENT_NAMES } from "./lib/generated/events";
-import type { CalibrationLifecycle, CalibrationState, CalibrationStep } from "./lib/generated/ironwing";
+import type {
+  CalibrationLifecycle,
+  CalibrationProgress,
+  CalibrationResult,
+  CalibrationState,
+  CalibrationStep,
+  FrameApplyResult,
+  FrameOption,
+} from "./lib/generated/ironwing";
 import { typedInvoke, typedListen, type UnlistenFn } from "./lib/ipc/client";
 import { createLatestScopedValueHandler } from "./lib/scoped-session-events";
 import type { DomainValue } from "./lib/domain-status";
@@ -33,11 +41,18 @@ export async function subscribeCalibrationStateEvent(
   return typedListen(EVENT_NAMES.CALIBRATION_STATE, (event) => cb(event.payload));
 }
 
-export async function calibrateAccel(): Promise<void> {
+export async function subscribeCalibrationProgress(
+  cb: (progress: CalibrationProgress) => void,
+): Promise<UnlistenFn> {
+  return typedListen(EVENT_NAMES.CALIBRATION_PROGRESS, (event) => cb(event.payload));
+}
+
+/** Resolves once the vehicle reports the result, or with `timeout` after 30 s. */
+export async function calibrateAccel(): Promise<CalibrationResult> {
   return typedInvoke("calibrate_accel");
 }
 
-export async function calibrateGyro(): Promise<void> {
+export async function calibrateGyro(): Promise<CalibrationResult> {
   return typedInvoke("calibrate_gyro");
 }
 
@@ -72,6 +87,16 @@ export function rebootVehicle(): Promise<void> {
   return typedInvoke("reboot_vehicle");
 }
 
+/** Airframes the connected vehicle can be switched to; `current` marks the active one. */
+export function frameOptions(): Promise<FrameOption[]> {
+  return typedInvoke("frame_options");
+}
+
+/** Write the frame's parameters; refused while armed. Pass `reboot` to restart into it. */
+export function applyFrame(optionId: string, reboot?: boolean): Promise<FrameApplyResult> {
+  return typedInvoke("frame_apply", { optionId, reboot });
+}
+
 export function requestPrearmChecks(): Promise<void> {
   return typedInvoke("request_prearm_checks");
 }
@@ -114,8 +114,9 @@ export async function stopGuidedSession(): Promise<GuidedCommandResult> {
   return typedInvoke("stop_guided_session");
 }
 
-export async function guidedTakeoff(altitudeM: number): Promise<void> {
-  await typedInvoke("vehicle_takeoff", { altitudeM });
+/** `overrideGates` skips the GCS-side safety gates. */
+export async function guidedTakeoff(altitudeM: number, overrideGates?: boolean): Promise<void> {
+  await typedInvoke("vehicle_takeoff", { altitudeM, overrideGates });
 }
 
 export async function subscribeGuidedState(
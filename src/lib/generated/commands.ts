@@ -1,6 +1,6 @@
 // @generated by `cargo run -p ironwing-contract-export --`
 // Do not edit this file by hand.
-import type { SourceKind } from "./ironwing";
+import type { AckResult, AlertRule, AltitudeChange, AltitudeFrame, BatteryStats, CalibrationResult, ChecklistItem, ChecklistState, ComponentListEntry, ConnectionInfo, Diagnostics, DiagnosticsExport, DisconnectAction, DisconnectActionResult, DistanceUnit, EventBridgeStats, ExportReport, FileUploadReport, FlightModeChannelConfig, FlightModeCurrent, FlightSessionSummary, FrameApplyResult, FrameOption, GotoRequest, GotoResult, GpsStatus, GripperCommand, GuidedQueuePoint, GuidedQueueStatus, HealthReport, JournalEntry, LinkProfile, LinkSource, LinkSourceEntry, LocalApiStatus, LogCompareSeries, LogFinding, LogParam, LogStatusText, LogVtolEvent, MapPrefetchProgress, MapPrefetchRequest, MissionEditResult, NamedValue, OrbitRequest, OrbitResult, ParamBitState, ParamExportFormat, ParamExportOptions, ParamExternalChange, ParamMetadata, ParamSearchOptions, ParamTuneSession, ParamTuneSummary, ParamWriteOutcome, ParsedParamFile, PendingConnect, PlaneCruiseThrottleResult, PlaneLaunch, PlaneLoiterResult, PlaneTakeoffResult, PositionIssue, PrecisionLandingStatus, RcLinkQuality, RtlPreview, SafetyGateConfig, SafetyGateFailure, ScriptFile, SessionExportOptions, SessionRecoverInfo, Settings, SettingsPatch, SourceKind, SpeedUnit, TakeoffGuidedRequest, TakeoffReport, TelemetryEncoding, TemperatureUnit, TerrainGotoResult, TimeSyncStatus, TrackPoint, TuneAxis, TuneReport, UsbSerialDevice, VehicleListEntry, VideoStream, VirtualField, VtolStatus, VtolTransitionTarget, WinchCommand } from "./ironwing";
 import type { RcOverrideChannel } from "../../calibration";
 import type {
   BootloaderInstallationResult,
@@ -33,19 +33,26 @@ import type {
   RawMessageQuery,
 } from "../../logs";
 import type { MissionDownload, MissionIssue } from "../../mission";
-import type { FencePlan, RallyPlan, WireMissionPlan } from "../mavkit-types";
+import type { FencePlan, RallyPlan, WireMissionItem, WireMissionPlan } from "../mavkit-types";
 import type { Param, ParamStore, ParamWriteResult } from "../../params";
 import type { FlightPathPoint, PlaybackSeekResult, PlaybackStateSnapshot, TelemetrySnapshot } from "../../playback";
 import type { RecordingSettings, RecordingSettingsResult, RecordingStartRequest, RecordingStatus } from "../../recording";
 import type { SerialPortInfo, SerialPortInventoryResult } from "../../serial-ports";
-import type { AckSessionSnapshotResult, OpenSessionSnapshot } from "../../session";
-import type { BluetoothDevice, FlightModeEntry, MessageRateInfo } from "../../telemetry";
-import type { BluetoothProfile, ConnectRequest, DisconnectRequest, TransportDescriptor } from "../../transport";
+import type { AckSessionSnapshotResult, OpenSessionSnapshot, SessionConnection } from "../../session";
+import type { StatusMessage } from "../../statustext";
+import type { BluetoothDevice, FlightModeEntry, MessageRateInfo, TelemetryDomain, VehicleState } from "../../telemetry";
+import type { BluetoothProfile, ConnectRequest, DemoVehiclePreset, DisconnectRequest, TransportDescriptor } from "../../transport";
 
 export const INVOKE_COMMAND_NAMES = [
   "ack_session_snapshot",
+  "alerts_get_rules",
+  "alerts_reset_rules",
+  "alerts_set_rules",
   "arm_vehicle",
   "available_transports",
+  "background_keepalive_start",
+  "background_keepalive_stop",
+  "battery_stats",
   "bt_get_bonded_devices",
   "bt_request_permissions",
   "bt_scan_ble",
@@ -55,9 +62,22 @@ export const INVOKE_COMMAND_NAMES = [
   "calibrate_compass_cancel",
   "calibrate_compass_start",
   "calibrate_gyro",
+  "camera_trigger",
+  "checklist_check",
+  "checklist_define",
+  "checklist_reset",
+  "checklist_state",
+  "components_list",
+  "connect_keep_waiting",
   "connect_link",
+  "connect_mock",
+  "connection_info",
+  "diagnostics",
+  "diagnostics_export",
   "disconnect_link",
   "disarm_vehicle",
+  "disconnect_link_ex",
+  "event_bridge_stats",
   "fence_clear",
   "fence_download",
   "fence_upload",
@@ -71,13 +91,42 @@ export const INVOKE_COMMAND_NAMES = [
   "firmware_session_cancel",
   "firmware_session_clear_completed",
   "firmware_session_status",
+  "firmware_stage",
+  "flight_mode_current",
+  "flight_summaries",
+  "flightmode_assign",
+  "flightmode_channel_config",
+  "frame_apply",
+  "frame_options",
+  "gcs_failsafe_test",
+  "gcs_position_set_manual",
+  "gcs_position_stream_start",
+  "gcs_position_stream_stop",
   "get_available_message_rates",
   "get_available_modes",
+  "get_current_link_state",
+  "get_current_telemetry",
+  "get_current_vehicle_state",
+  "gimbal_set_angles",
+  "gps_status",
+  "gripper_control",
+  "guided_queue_set",
+  "guided_queue_start",
+  "guided_queue_status",
+  "guided_queue_stop",
+  "journal_query",
+  "link_sources",
   "list_serial_port_inventory",
+  "list_usb_devices",
+  "local_api_start",
+  "local_api_stop",
+  "log_analyze",
   "log_chart_series_query",
   "log_close",
   "log_export",
   "log_export_csv",
+  "log_extract_params",
+  "log_follow",
   "log_format_adapters",
   "log_get_flight_path",
   "log_get_flight_summary",
@@ -90,32 +139,70 @@ export const INVOKE_COMMAND_NAMES = [
   "log_library_reindex",
   "log_library_relink",
   "log_library_remove",
+  "log_list_types",
   "log_open",
   "log_query",
+  "log_query_compare",
   "log_raw_messages_query",
+  "log_repair_timestamps",
+  "log_statustext",
+  "log_vtol_events",
+  "map_prefetch",
+  "map_prefetch_cancel",
   "mission_cancel",
   "mission_clear",
+  "mission_convert_frame",
   "mission_download",
+  "mission_insert_waypoint",
+  "mission_prepare_resume",
+  "mission_reverse",
+  "mission_scale_altitudes",
   "mission_set_current",
+  "mission_translate",
   "mission_upload",
   "mission_validate",
   "motor_test",
+  "named_values",
   "open_session_snapshot",
   "param_cancel",
+  "param_commit_to_storage",
   "param_download_all",
+  "param_export",
+  "param_external_changes",
   "param_format_file",
+  "param_get_all",
+  "param_get_bits",
   "param_parse_file",
+  "param_parse_file_ex",
+  "param_search",
+  "param_set_bits",
+  "param_set_metadata",
+  "param_tune_end",
+  "param_tune_revert",
+  "param_tune_session",
+  "param_tune_set",
   "param_write",
   "param_write_batch",
+  "pending_connect_take",
+  "plane_launch_info",
+  "plane_loiter_here",
+  "plane_set_cruise_throttle",
+  "plane_takeoff",
   "playback_pause",
   "playback_play",
   "playback_seek",
   "playback_set_speed",
   "playback_stop",
+  "precision_landing_status",
+  "preflight_position_check",
   "rally_clear",
   "rally_download",
   "rally_upload",
+  "rc_link_quality",
   "rc_override",
+  "rc_override_set",
+  "rc_override_start",
+  "rc_override_stop",
   "reboot_vehicle",
   "recording_settings_read",
   "recording_settings_write",
@@ -124,14 +211,56 @@ export const INVOKE_COMMAND_NAMES = [
   "recording_stop",
   "request_prearm_checks",
   "request_web_serial_port",
+  "rover_hold",
+  "rover_set_reverse",
+  "rtl_preview",
+  "script_delete",
+  "script_upload",
+  "scripts_list",
+  "send_command_long",
+  "send_raw_message",
+  "session_export",
+  "session_recover_info",
+  "set_auto_connect_usb",
+  "set_event_rates",
+  "set_expected_failsafe_check",
   "set_flight_mode",
+  "set_flight_mode_by_name",
+  "set_gcs_identity",
+  "set_link_profile",
+  "set_link_source_filter",
   "set_message_rate",
+  "set_safety_gates",
   "set_servo",
+  "set_telemetry_encoding",
   "set_telemetry_rate",
+  "set_unit_system",
+  "settings_get",
+  "settings_update",
   "start_guided_session",
+  "statustext_history",
   "stop_guided_session",
+  "takeoff_guided",
+  "time_sync_status",
+  "track_clear",
+  "track_get",
+  "tune_capture_start",
+  "tune_capture_stop",
+  "tune_report_export",
+  "tunnel_send",
   "update_guided_session",
+  "vehicle_guided_goto_ex",
+  "vehicle_guided_goto_terrain",
+  "vehicle_health",
+  "vehicle_list",
+  "vehicle_orbit",
+  "vehicle_orbit_stop",
+  "vehicle_select",
   "vehicle_takeoff",
+  "video_streams",
+  "vtol_status",
+  "vtol_transition",
+  "winch_control",
 ] as const;
 
 export type InvokeCommandName = (typeof INVOKE_COMMAND_NAMES)[number];
@@ -158,20 +287,39 @@ type LogFlightPathArgs =
 
 export type InvokeCommandMap = {
   ack_session_snapshot: CommandSpec<{ sessionId: string; seekEpoch: number; resetRevision: number }, AckSessionSnapshotResult>;
-  arm_vehicle: CommandSpec<{ force: boolean }, void>;
+  alerts_get_rules: CommandSpec<NoArgs, AlertRule[]>;
+  alerts_reset_rules: CommandSpec<NoArgs, AlertRule[]>;
+  alerts_set_rules: CommandSpec<{ rules: AlertRule[] }, void>;
+  arm_vehicle: CommandSpec<{ force: boolean; overrideGates?: boolean }, void>;
   available_transports: CommandSpec<NoArgs, TransportDescriptor[]>;
+  background_keepalive_start: CommandSpec<NoArgs, void>;
+  background_keepalive_stop: CommandSpec<NoArgs, void>;
+  battery_stats: CommandSpec<NoArgs, BatteryStats>;
   bt_get_bonded_devices: CommandSpec<NoArgs, BluetoothDevice[]>;
   bt_request_permissions: CommandSpec<NoArgs, void>;
   bt_scan_ble: CommandSpec<{ timeoutMs?: number; profile?: BluetoothProfile }, BluetoothDevice[]>;
   bt_stop_scan_ble: CommandSpec<NoArgs, void>;
-  calibrate_accel: CommandSpec<NoArgs, void>;
+  calibrate_accel: CommandSpec<NoArgs, CalibrationResult>;
   calibrate_compass_accept: CommandSpec<{ compassMask: number }, void>;
   calibrate_compass_cancel: CommandSpec<{ compassMask: number }, void>;
   calibrate_compass_start: CommandSpec<{ compassMask: number }, void>;
-  calibrate_gyro: CommandSpec<NoArgs, void>;
+  calibrate_gyro: CommandSpec<NoArgs, CalibrationResult>;
+  camera_trigger: CommandSpec<{ targetComponent?: number }, void>;
+  checklist_check: CommandSpec<{ itemId: string; checked: boolean }, ChecklistState>;
+  checklist_define: CommandSpec<{ items: ChecklistItem[]; enforce?: boolean }, ChecklistState>;
+  checklist_reset: CommandSpec<NoArgs, ChecklistState>;
+  checklist_state: CommandSpec<NoArgs, ChecklistState>;
+  components_list: CommandSpec<NoArgs, ComponentListEntry[]>;
+  connect_keep_waiting: CommandSpec<NoArgs, void>;
   connect_link: CommandSpec<{ request: ConnectLinkRequest }, void>;
+  connect_mock: CommandSpec<{ vehiclePreset?: DemoVehiclePreset }, void>;
+  connection_info: CommandSpec<NoArgs, ConnectionInfo>;
+  diagnostics: CommandSpec<NoArgs, Diagnostics>;
+  diagnostics_export: CommandSpec<{ path: string; redact?: boolean }, DiagnosticsExport>;
   disconnect_link: CommandSpec<{ request?: DisconnectRequest }, void>;
   disarm_vehicle: CommandSpec<{ force: boolean }, void>;
+  disconnect_link_ex: CommandSpec<{ action: DisconnectAction; confirmArmed: boolean }, DisconnectActionResult>;
+  event_bridge_stats: CommandSpec<NoArgs, EventBridgeStats[]>;
   fence_clear: CommandSpec<NoArgs, void>;
   fence_download: CommandSpec<NoArgs, FencePlan>;
   fence_upload: CommandSpec<{ plan: FencePlan }, void>;
@@ -185,13 +333,42 @@ export type InvokeCommandMap = {
   firmware_session_cancel: CommandSpec<NoArgs, void>;
   firmware_session_clear_completed: CommandSpec<NoArgs, void>;
   firmware_session_status: CommandSpec<NoArgs, FirmwareSessionStatus>;
+  firmware_stage: CommandSpec<{ localPath: string }, FileUploadReport>;
+  flight_mode_current: CommandSpec<NoArgs, FlightModeCurrent>;
+  flight_summaries: CommandSpec<NoArgs, FlightSessionSummary[]>;
+  flightmode_assign: CommandSpec<{ slot: number; customMode: number }, ParamWriteOutcome>;
+  flightmode_channel_config: CommandSpec<NoArgs, FlightModeChannelConfig>;
+  frame_apply: CommandSpec<{ optionId: string; reboot?: boolean }, FrameApplyResult>;
+  frame_options: CommandSpec<NoArgs, FrameOption[]>;
+  gcs_failsafe_test: CommandSpec<{ seconds: number }, void>;
+  gcs_position_set_manual: CommandSpec<{ lat: number; lon: number; alt: number }, void>;
+  gcs_position_stream_start: CommandSpec<{ rateHz: number }, void>;
+  gcs_position_stream_stop: CommandSpec<NoArgs, void>;
   get_available_message_rates: CommandSpec<NoArgs, MessageRateInfo[]>;
   get_available_modes: CommandSpec<NoArgs, FlightModeEntry[]>;
+  get_current_link_state: CommandSpec<NoArgs, SessionConnection>;
+  get_current_telemetry: CommandSpec<{ raw?: boolean }, TelemetryDomain>;
+  get_current_vehicle_state: CommandSpec<NoArgs, VehicleState | null>;
+  gimbal_set_angles: CommandSpec<{ pitchDeg: number; rollDeg: number; yawDeg: number; targetComponent?: number }, void>;
+  gps_status: CommandSpec<NoArgs, GpsStatus>;
+  gripper_control: CommandSpec<{ instance: number; action: GripperCommand }, void>;
+  guided_queue_set: CommandSpec<{ points: GuidedQueuePoint[] }, GuidedQueueStatus>;
+  guided_queue_start: CommandSpec<NoArgs, GuidedQueueStatus>;
+  guided_queue_status: CommandSpec<NoArgs, GuidedQueueStatus>;
+  guided_queue_stop: CommandSpec<NoArgs, GuidedQueueStatus>;
+  journal_query: CommandSpec<{ startMs: number; endMs: number }, JournalEntry[]>;
+  link_sources: CommandSpec<NoArgs, LinkSourceEntry[]>;
   list_serial_port_inventory: CommandSpec<NoArgs, SerialPortInventoryResult>;
+  list_usb_devices: CommandSpec<NoArgs, UsbSerialDevice[]>;
+  local_api_start: CommandSpec<{ port: number; token: string; bindAll: boolean | null; stopOnDisconnect: boolean | null }, LocalApiStatus>;
+  local_api_stop: CommandSpec<NoArgs, void>;
+  log_analyze: CommandSpec<NoArgs, LogFinding[]>;
   log_chart_series_query: CommandSpec<{ request: ChartSeriesRequest }, ChartSeriesPage>;
-  log_close: CommandSpec<NoArgs, void>;
+  log_close: CommandSpec<{ logId?: string }, void>;
   log_export: CommandSpec<{ request: LogExportRequest }, LogExportResult>;
   log_export_csv: CommandSpec<{ path: string; startUsec: number | null; endUsec: number | null }, number>;
+  log_extract_params: CommandSpec<NoArgs, LogParam[]>;
+  log_follow: CommandSpec<{ enabled: boolean; logId?: string }, void>;
   log_format_adapters: CommandSpec<NoArgs, LogFormatAdapter[]>;
   log_get_flight_path: CommandSpec<LogFlightPathArgs, FlightPathPoint[]>;
   log_get_flight_summary: CommandSpec<NoArgs, FlightSummary>;
@@ -204,33 +381,71 @@ export type InvokeCommandMap = {
   log_library_reindex: CommandSpec<{ entryId: string }, LogLibraryEntry>;
   log_library_relink: CommandSpec<{ entryId: string; path: string }, LogLibraryEntry>;
   log_library_remove: CommandSpec<{ entryId: string }, LogLibraryCatalog>;
+  log_list_types: CommandSpec<{ logId?: string }, string[]>;
   log_open: CommandSpec<{ path: string }, LogSummary>;
-  log_query: CommandSpec<{ msgType: string; startUsec: number | null; endUsec: number | null; maxPoints: number | null }, LogDataPoint[]>;
+  log_query: CommandSpec<{ msgType: string; startUsec: number | null; endUsec: number | null; maxPoints: number | null; raw?: boolean; logId?: string; segment?: number; virtualFields?: VirtualField[] }, LogDataPoint[]>;
+  log_query_compare: CommandSpec<{ logIds: string[]; msgType: string; field: string; normalizeTime: boolean; maxPoints?: number }, LogCompareSeries[]>;
   log_raw_messages_query: CommandSpec<{ request: RawMessageQuery }, RawMessagePage>;
+  log_repair_timestamps: CommandSpec<{ logId?: string }, LogSummary>;
+  log_statustext: CommandSpec<NoArgs, LogStatusText[]>;
+  log_vtol_events: CommandSpec<NoArgs, LogVtolEvent[]>;
+  map_prefetch: CommandSpec<{ request: MapPrefetchRequest }, MapPrefetchProgress>;
+  map_prefetch_cancel: CommandSpec<NoArgs, void>;
   mission_cancel: CommandSpec<NoArgs, void>;
   mission_clear: CommandSpec<NoArgs, void>;
+  mission_convert_frame: CommandSpec<{ plan: WireMissionPlan; targetFrame: AltitudeFrame; homeAltM: number | null }, MissionEditResult>;
   mission_download: CommandSpec<NoArgs, MissionDownload>;
+  mission_insert_waypoint: CommandSpec<{ plan: WireMissionPlan; afterSeq: number; item: WireMissionItem }, MissionEditResult>;
+  mission_prepare_resume: CommandSpec<{ lastCompletedSeq: number }, WireMissionPlan>;
+  mission_reverse: CommandSpec<{ plan: WireMissionPlan }, MissionEditResult>;
+  mission_scale_altitudes: CommandSpec<{ plan: WireMissionPlan; change: AltitudeChange }, MissionEditResult>;
   mission_set_current: CommandSpec<{ seq: number }, void>;
+  mission_translate: CommandSpec<{ plan: WireMissionPlan; dlatDeg: number; dlonDeg: number }, MissionEditResult>;
   mission_upload: CommandSpec<{ plan: WireMissionPlan }, void>;
   mission_validate: CommandSpec<{ plan: WireMissionPlan }, MissionIssue[]>;
   motor_test: CommandSpec<{ motorInstance: number; throttlePct: number; durationS: number }, void>;
+  named_values: CommandSpec<NoArgs, NamedValue[]>;
   open_session_snapshot: CommandSpec<{ sourceKind: SourceKind }, OpenSessionSnapshot>;
   param_cancel: CommandSpec<NoArgs, void>;
+  param_commit_to_storage: CommandSpec<NoArgs, void>;
   param_download_all: CommandSpec<NoArgs, void>;
-  param_format_file: CommandSpec<{ store: ParamStore }, string>;
+  param_export: CommandSpec<{ options: ParamExportOptions }, string>;
+  param_external_changes: CommandSpec<NoArgs, ParamExternalChange[]>;
+  param_format_file: CommandSpec<{ store: ParamStore; format?: ParamExportFormat }, string>;
+  param_get_all: CommandSpec<NoArgs, ParamStore>;
+  param_get_bits: CommandSpec<{ name: string }, ParamBitState[]>;
   param_parse_file: CommandSpec<{ contents: string }, Record<string, number>>;
-  param_write: CommandSpec<{ name: string; value: number }, ParamWriteResult>;
-  param_write_batch: CommandSpec<{ params: [string, number][] }, ParamWriteResult[]>;
+  param_parse_file_ex: CommandSpec<{ contents: string }, ParsedParamFile>;
+  param_search: CommandSpec<{ query: string; options: ParamSearchOptions }, Param[]>;
+  param_set_bits: CommandSpec<{ name: string; bitsToSet: number[]; bitsToClear: number[]; persist?: boolean }, Param>;
+  param_set_metadata: CommandSpec<{ metadata: Record<string, ParamMetadata> }, void>;
+  param_tune_end: CommandSpec<{ commit: boolean }, ParamTuneSummary>;
+  param_tune_revert: CommandSpec<NoArgs, void>;
+  param_tune_session: CommandSpec<{ name: string }, ParamTuneSession>;
+  param_tune_set: CommandSpec<{ name: string; value: number }, void>;
+  param_write: CommandSpec<{ name: string; value: number; persist?: boolean }, ParamWriteOutcome>;
+  param_write_batch: CommandSpec<{ params: [string, number][]; persist?: boolean }, ParamWriteOutcome[]>;
+  pending_connect_take: CommandSpec<NoArgs, PendingConnect | null>;
+  plane_launch_info: CommandSpec<NoArgs, PlaneLaunch>;
+  plane_loiter_here: CommandSpec<{ radiusM: number; turns?: number | null }, PlaneLoiterResult>;
+  plane_set_cruise_throttle: CommandSpec<{ throttlePct: number }, PlaneCruiseThrottleResult>;
+  plane_takeoff: CommandSpec<{ altitudeM: number }, PlaneTakeoffResult>;
   playback_pause: CommandSpec<NoArgs, PlaybackStateSnapshot>;
-  playback_play: CommandSpec<NoArgs, PlaybackStateSnapshot>;
+  playback_play: CommandSpec<{ speed?: number; startUsec?: number }, PlaybackStateSnapshot>;
   playback_seek: CommandSpec<{ cursorUsec: number | null }, PlaybackSeekResult>;
   playback_set_speed: CommandSpec<{ speed: number }, PlaybackStateSnapshot>;
   playback_stop: CommandSpec<NoArgs, PlaybackStateSnapshot>;
+  precision_landing_status: CommandSpec<{ windowS?: number }, PrecisionLandingStatus>;
+  preflight_position_check: CommandSpec<{ terrainElevationM?: number }, PositionIssue[]>;
   rally_clear: CommandSpec<NoArgs, void>;
   rally_download: CommandSpec<NoArgs, RallyPlan>;
   rally_upload: CommandSpec<{ plan: RallyPlan }, void>;
+  rc_link_quality: CommandSpec<NoArgs, RcLinkQuality | null>;
   rc_override: CommandSpec<{ channels: RcOverrideChannel[] }, void>;
-  reboot_vehicle: CommandSpec<NoArgs, void>;
+  rc_override_set: CommandSpec<{ channels: (number | null)[] }, void>;
+  rc_override_start: CommandSpec<NoArgs, void>;
+  rc_override_stop: CommandSpec<NoArgs, void>;
+  reboot_vehicle: CommandSpec<{ targetComponent?: number }, void>;
   recording_settings_read: CommandSpec<NoArgs, RecordingSettingsResult>;
   recording_settings_write: CommandSpec<{ settings: RecordingSettings }, RecordingSettingsResult>;
   recording_start: CommandSpec<{ request: RecordingStartRequest }, string>;
@@ -238,14 +453,56 @@ export type InvokeCommandMap = {
   recording_stop: CommandSpec<NoArgs, void>;
   request_prearm_checks: CommandSpec<NoArgs, void>;
   request_web_serial_port: CommandSpec<NoArgs, SerialPortInfo | null>;
+  rover_hold: CommandSpec<NoArgs, void>;
+  rover_set_reverse: CommandSpec<{ enabled: boolean }, void>;
+  rtl_preview: CommandSpec<NoArgs, RtlPreview>;
+  script_delete: CommandSpec<{ name: string }, void>;
+  script_upload: CommandSpec<{ localPath: string }, FileUploadReport>;
+  scripts_list: CommandSpec<NoArgs, ScriptFile[]>;
+  send_command_long: CommandSpec<{ commandId: number; params: number[]; targetComponent: number }, AckResult>;
+  send_raw_message: CommandSpec<{ messageName: string; fieldsJson: unknown }, void>;
+  session_export: CommandSpec<{ path: string; include?: SessionExportOptions }, ExportReport>;
+  session_recover_info: CommandSpec<NoArgs, SessionRecoverInfo | null>;
+  set_auto_connect_usb: CommandSpec<{ enabled: boolean; baud: number }, void>;
+  set_event_rates: CommandSpec<{ rates: Record<string, number> }, void>;
+  set_expected_failsafe_check: CommandSpec<{ enabled: boolean }, string | null>;
   set_flight_mode: CommandSpec<{ customMode: number }, void>;
-  set_message_rate: CommandSpec<{ messageId: number; rateHz: number }, void>;
+  set_flight_mode_by_name: CommandSpec<{ name: string }, void>;
+  set_gcs_identity: CommandSpec<{ systemId: number; componentId: number }, void>;
+  set_link_profile: CommandSpec<{ profile: LinkProfile }, void>;
+  set_link_source_filter: CommandSpec<{ sources: LinkSource[] }, void>;
+  set_message_rate: CommandSpec<{ messageId: number; rateHz: number; targetComponent?: number }, void>;
+  set_safety_gates: CommandSpec<{ config: SafetyGateConfig }, SafetyGateFailure[]>;
   set_servo: CommandSpec<{ instance: number; pwmUs: number }, void>;
+  set_telemetry_encoding: CommandSpec<{ encoding: TelemetryEncoding }, void>;
   set_telemetry_rate: CommandSpec<{ rateHz: number }, void>;
+  set_unit_system: CommandSpec<{ distance: DistanceUnit; speed: SpeedUnit; temperature: TemperatureUnit }, void>;
+  settings_get: CommandSpec<NoArgs, Settings>;
+  settings_update: CommandSpec<{ patch: SettingsPatch }, Settings>;
   start_guided_session: CommandSpec<{ request: StartGuidedSessionRequest }, GuidedCommandResult>;
+  statustext_history: CommandSpec<NoArgs, StatusMessage[]>;
   stop_guided_session: CommandSpec<NoArgs, GuidedCommandResult>;
+  takeoff_guided: CommandSpec<{ request: TakeoffGuidedRequest }, TakeoffReport>;
+  time_sync_status: CommandSpec<NoArgs, TimeSyncStatus>;
+  track_clear: CommandSpec<NoArgs, void>;
+  track_get: CommandSpec<{ sinceUsec?: number; maxPoints?: number }, TrackPoint[]>;
+  tune_capture_start: CommandSpec<{ axis: TuneAxis }, void>;
+  tune_capture_stop: CommandSpec<NoArgs, TuneReport>;
+  tune_report_export: CommandSpec<{ path: string; report: TuneReport }, void>;
+  tunnel_send: CommandSpec<{ payloadType: number; data: number[] }, void>;
   update_guided_session: CommandSpec<{ request: UpdateGuidedSessionRequest }, GuidedCommandResult>;
-  vehicle_takeoff: CommandSpec<{ altitudeM: number }, void>;
+  vehicle_guided_goto_ex: CommandSpec<{ request: GotoRequest }, GotoResult>;
+  vehicle_guided_goto_terrain: CommandSpec<{ latitudeDeg: number; longitudeDeg: number; aglM: number; groundElevationM: number }, TerrainGotoResult>;
+  vehicle_health: CommandSpec<NoArgs, HealthReport>;
+  vehicle_list: CommandSpec<NoArgs, VehicleListEntry[]>;
+  vehicle_orbit: CommandSpec<{ request: OrbitRequest }, OrbitResult>;
+  vehicle_orbit_stop: CommandSpec<NoArgs, ParamWriteResult[]>;
+  vehicle_select: CommandSpec<{ systemId: number }, void>;
+  vehicle_takeoff: CommandSpec<{ altitudeM: number; overrideGates?: boolean }, void>;
+  video_streams: CommandSpec<NoArgs, VideoStream[]>;
+  vtol_status: CommandSpec<NoArgs, VtolStatus | null>;
+  vtol_transition: CommandSpec<{ to: VtolTransitionTarget }, void>;
+  winch_control: CommandSpec<{ instance: number; action: WinchCommand }, void>;
 };
 
 export type KnownInvokeCommandName = keyof InvokeCommandMap & InvokeCommandName;
@@ -262,8 +519,14 @@ export type PlatformSupport = "native" | "web" | "remote" | "mock";
 
 export const COMMAND_PLATFORM_SUPPORT = {
   ack_session_snapshot: ["native","web","remote","mock"] as const,
+  alerts_get_rules: ["native","remote","mock"] as const,
+  alerts_reset_rules: ["native","remote","mock"] as const,
+  alerts_set_rules: ["native","remote","mock"] as const,
   arm_vehicle: ["native","web","remote","mock"] as const,
   available_transports: ["native","web","remote","mock"] as const,
+  background_keepalive_start: ["native","remote","mock"] as const,
+  background_keepalive_stop: ["native","remote","mock"] as const,
+  battery_stats: ["native","remote","mock"] as const,
   bt_get_bonded_devices: ["native","web","remote","mock"] as const,
   bt_request_permissions: ["native","web","remote","mock"] as const,
   bt_scan_ble: ["native","web","remote","mock"] as const,
@@ -273,9 +536,22 @@ export const COMMAND_PLATFORM_SUPPORT = {
   calibrate_compass_cancel: ["native","web","remote","mock"] as const,
   calibrate_compass_start: ["native","web","remote","mock"] as const,
   calibrate_gyro: ["native","web","remote","mock"] as const,
+  camera_trigger: ["native","remote","mock"] as const,
+  checklist_check: ["native","remote","mock"] as const,
+  checklist_define: ["native","remote","mock"] as const,
+  checklist_reset: ["native","remote","mock"] as const,
+  checklist_state: ["native","remote","mock"] as const,
+  components_list: ["native","remote","mock"] as const,
+  connect_keep_waiting: ["native","remote","mock"] as const,
   connect_link: ["native","web","remote","mock"] as const,
+  connect_mock: ["native","remote","mock"] as const,
+  connection_info: ["native","remote","mock"] as const,
+  diagnostics: ["native","remote","mock"] as const,
+  diagnostics_export: ["native","remote","mock"] as const,
   disconnect_link: ["native","web","remote","mock"] as const,
   disarm_vehicle: ["native","web","remote","mock"] as const,
+  disconnect_link_ex: ["native","remote","mock"] as const,
+  event_bridge_stats: ["native","remote","mock"] as const,
   fence_clear: ["native","web","remote","mock"] as const,
   fence_download: ["native","web","remote","mock"] as const,
   fence_upload: ["native","web","remote","mock"] as const,
@@ -289,13 +565,42 @@ export const COMMAND_PLATFORM_SUPPORT = {
   firmware_session_cancel: ["native","web","remote","mock"] as const,
   firmware_session_clear_completed: ["native","web","remote","mock"] as const,
   firmware_session_status: ["native","web","remote","mock"] as const,
+  firmware_stage: ["native","remote","mock"] as const,
+  flight_mode_current: ["native","remote","mock"] as const,
+  flight_summaries: ["native","remote","mock"] as const,
+  flightmode_assign: ["native","remote","mock"] as const,
+  flightmode_channel_config: ["native","remote","mock"] as const,
+  frame_apply: ["native","remote","mock"] as const,
+  frame_options: ["native","remote","mock"] as const,
+  gcs_failsafe_test: ["native","remote","mock"] as const,
+  gcs_position_set_manual: ["native","remote","mock"] as const,
+  gcs_position_stream_start: ["native","remote","mock"] as const,
+  gcs_position_stream_stop: ["native","remote","mock"] as const,
   get_available_message_rates: ["native","web","remote","mock"] as const,
   get_available_modes: ["native","web","remote","mock"] as const,
+  get_current_link_state: ["native","remote","mock"] as const,
+  get_current_telemetry: ["native","remote","mock"] as const,
+  get_current_vehicle_state: ["native","remote","mock"] as const,
+  gimbal_set_angles: ["native","remote","mock"] as const,
+  gps_status: ["native","remote","mock"] as const,
+  gripper_control: ["native","remote","mock"] as const,
+  guided_queue_set: ["native","remote","mock"] as const,
+  guided_queue_start: ["native","remote","mock"] as const,
+  guided_queue_status: ["native","remote","mock"] as const,
+  guided_queue_stop: ["native","remote","mock"] as const,
+  journal_query: ["native","remote","mock"] as const,
+  link_sources: ["native","remote","mock"] as const,
   list_serial_port_inventory: ["native","web","remote","mock"] as const,
+  list_usb_devices: ["native","web","remote","mock"] as const,
+  local_api_start: ["native","remote","mock"] as const,
+  local_api_stop: ["native","remote","mock"] as const,
+  log_analyze: ["native","remote","mock"] as const,
   log_chart_series_query: ["native","web","remote","mock"] as const,
   log_close: ["native","web","remote","mock"] as const,
   log_export: ["native","web","remote","mock"] as const,
   log_export_csv: ["native","web","remote","mock"] as const,
+  log_extract_params: ["native","remote","mock"] as const,
+  log_follow: ["native","remote","mock"] as const,
   log_format_adapters: ["native","web","remote","mock"] as const,
   log_get_flight_path: ["native","web","remote","mock"] as const,
   log_get_flight_summary: ["native","web","remote","mock"] as const,
@@ -308,32 +613,70 @@ export const COMMAND_PLATFORM_SUPPORT = {
   log_library_reindex: ["native","web","remote","mock"] as const,
   log_library_relink: ["native","web","remote","mock"] as const,
   log_library_remove: ["native","web","remote","mock"] as const,
+  log_list_types: ["native","remote","mock"] as const,
   log_open: ["native","web","remote","mock"] as const,
   log_query: ["native","web","remote","mock"] as const,
+  log_query_compare: ["native","remote","mock"] as const,
   log_raw_messages_query: ["native","web","remote","mock"] as const,
+  log_repair_timestamps: ["native","remote","mock"] as const,
+  log_statustext: ["native","remote","mock"] as const,
+  log_vtol_events: ["native","remote","mock"] as const,
+  map_prefetch: ["native","remote","mock"] as const,
+  map_prefetch_cancel: ["native","remote","mock"] as const,
   mission_cancel: ["native","web","remote","mock"] as const,
   mission_clear: ["native","web","remote","mock"] as const,
+  mission_convert_frame: ["native","remote","mock"] as const,
   mission_download: ["native","web","remote","mock"] as const,
+  mission_insert_waypoint: ["native","remote","mock"] as const,
+  mission_prepare_resume: ["native","remote","mock"] as const,
+  mission_reverse: ["native","remote","mock"] as const,
+  mission_scale_altitudes: ["native","remote","mock"] as const,
   mission_set_current: ["native","web","remote","mock"] as const,
+  mission_translate: ["native","remote","mock"] as const,
   mission_upload: ["native","web","remote","mock"] as const,
   mission_validate: ["native","web","remote","mock"] as const,
   motor_test: ["native","web","remote","mock"] as const,
+  named_values: ["native","remote","mock"] as const,
   open_session_snapshot: ["native","web","remote","mock"] as const,
   param_cancel: ["native","web","remote","mock"] as const,
+  param_commit_to_storage: ["native","remote","mock"] as const,
   param_download_all: ["native","web","remote","mock"] as const,
+  param_export: ["native","remote","mock"] as const,
+  param_external_changes: ["native","remote","mock"] as const,
   param_format_file: ["native","web","remote","mock"] as const,
+  param_get_all: ["native","remote","mock"] as const,
+  param_get_bits: ["native","remote","mock"] as const,
   param_parse_file: ["native","web","remote","mock"] as const,
+  param_parse_file_ex: ["native","remote","mock"] as const,
+  param_search: ["native","remote","mock"] as const,
+  param_set_bits: ["native","remote","mock"] as const,
+  param_set_metadata: ["native","remote","mock"] as const,
+  param_tune_end: ["native","remote","mock"] as const,
+  param_tune_revert: ["native","remote","mock"] as const,
+  param_tune_session: ["native","remote","mock"] as const,
+  param_tune_set: ["native","remote","mock"] as const,
   param_write: ["native","web","remote","mock"] as const,
   param_write_batch: ["native","web","remote","mock"] as const,
+  pending_connect_take: ["native","remote","mock"] as const,
+  plane_launch_info: ["native","remote","mock"] as const,
+  plane_loiter_here: ["native","remote","mock"] as const,
+  plane_set_cruise_throttle: ["native","remote","mock"] as const,
+  plane_takeoff: ["native","remote","mock"] as const,
   playback_pause: ["native","web","remote","mock"] as const,
   playback_play: ["native","web","remote","mock"] as const,
   playback_seek: ["native","web","remote","mock"] as const,
   playback_set_speed: ["native","web","remote","mock"] as const,
   playback_stop: ["native","web","remote","mock"] as const,
+  precision_landing_status: ["native","remote","mock"] as const,
+  preflight_position_check: ["native","remote","mock"] as const,
   rally_clear: ["native","web","remote","mock"] as const,
   rally_download: ["native","web","remote","mock"] as const,
   rally_upload: ["native","web","remote","mock"] as const,
+  rc_link_quality: ["native","remote","mock"] as const,
   rc_override: ["native","web","remote","mock"] as const,
+  rc_override_set: ["native","remote","mock"] as const,
+  rc_override_start: ["native","remote","mock"] as const,
+  rc_override_stop: ["native","remote","mock"] as const,
   reboot_vehicle: ["native","web","remote","mock"] as const,
   recording_settings_read: ["native","web","remote","mock"] as const,
   recording_settings_write: ["native","web","remote","mock"] as const,
@@ -342,14 +685,56 @@ export const COMMAND_PLATFORM_SUPPORT = {
   recording_stop: ["native","web","remote","mock"] as const,
   request_prearm_checks: ["native","web","remote","mock"] as const,
   request_web_serial_port: ["web","mock"] as const,
+  rover_hold: ["native","remote","mock"] as const,
+  rover_set_reverse: ["native","remote","mock"] as const,
+  rtl_preview: ["native","remote","mock"] as const,
+  script_delete: ["native","remote","mock"] as const,
+  script_upload: ["native","remote","mock"] as const,
+  scripts_list: ["native","remote","mock"] as const,
+  send_command_long: ["native","remote","mock"] as const,
+  send_raw_message: ["native","remote","mock"] as const,
+  session_export: ["native","remote","mock"] as const,
+  session_recover_info: ["native","remote","mock"] as const,
+  set_auto_connect_usb: ["native","remote","mock"] as const,
+  set_event_rates: ["native","remote","mock"] as const,
+  set_expected_failsafe_check: ["native","remote","mock"] as const,
   set_flight_mode: ["native","web","remote","mock"] as const,
+  set_flight_mode_by_name: ["native","remote","mock"] as const,
+  set_gcs_identity: ["native","remote","mock"] as const,
+  set_link_profile: ["native","remote","mock"] as const,
+  set_link_source_filter: ["native","remote","mock"] as const,
   set_message_rate: ["native","web","remote","mock"] as const,
+  set_safety_gates: ["native","remote","mock"] as const,
   set_servo: ["native","web","remote","mock"] as const,
+  set_telemetry_encoding: ["native","remote","mock"] as const,
   set_telemetry_rate: ["native","web","remote","mock"] as const,
+  set_unit_system: ["native","remote","mock"] as const,
+  settings_get: ["native","remote","mock"] as const,
+  settings_update: ["native","remote","mock"] as const,
   start_guided_session: ["native","web","remote","mock"] as const,
+  statustext_history: ["native","remote","mock"] as const,
   stop_guided_session: ["native","web","remote","mock"] as const,
+  takeoff_guided: ["native","remote","mock"] as const,
+  time_sync_status: ["native","remote","mock"] as const,
+  track_clear: ["native","remote","mock"] as const,
+  track_get: ["native","remote","mock"] as const,
+  tune_capture_start: ["native","remote","mock"] as const,
+  tune_capture_stop: ["native","remote","mock"] as const,
+  tune_report_export: ["native","remote","mock"] as const,
+  tunnel_send: ["native","remote","mock"] as const,
   update_guided_session: ["native","web","remote","mock"] as const,
+  vehicle_guided_goto_ex: ["native","remote","mock"] as const,
+  vehicle_guided_goto_terrain: ["native","remote","mock"] as const,
+  vehicle_health: ["native","remote","mock"] as const,
+  vehicle_list: ["native","remote","mock"] as const,
+  vehicle_orbit: ["native","remote","mock"] as const,
+  vehicle_orbit_stop: ["native","remote","mock"] as const,
+  vehicle_select: ["native","remote","mock"] as const,
   vehicle_takeoff: ["native","web","remote","mock"] as const,
+  video_streams: ["native","remote","mock"] as const,
+  vtol_status: ["native","remote","mock"] as const,
+  vtol_transition: ["native","remote","mock"] as const,
+  winch_control: ["native","remote","mock"] as const,
 } as const satisfies Record<InvokeCommandName, readonly PlatformSupport[]>;
 
 const commandMapUsesGeneratedCatalog: UnlistedMappedCommand extends never ? true : never = true;
@@ -1,10 +1,11 @@
 // @generated by `cargo run -p ironwing-contract-export --`
 // Do not edit this file by hand.
+import type { AlertTriggered, Announcement, BatteryStats, CalibrationProgress, ChecklistState, DisconnectActionResult, FailsafeEvent, FenceProximity, FileTransferProgress, FlightSessionSummary, GcsCommandReceived, GpsFixChanged, GuidedQueueStatus, GuidedTarget, HealthReport, HomeMoved, LandingAssistWarning, LinkConnecting, LinkLostInFlight, LogAppended, MapPrefetchProgress, MissionTransferProgress, NamedValue, OpticalFlowQuality, ParamExternalChange, ParamStoreDelta, PendingConnect, PlaybackPosition, PortInfo, PrecisionLandingTarget, RangefinderReading, SessionExportProgress, SystemResumed, TaskStalled, TelemetryDelta, TrackPoint, TransportLost, UnknownMessage, VehicleSelected, VideoStream, VtolStatus, WinchStatus } from "./ironwing";
 import type { CalibrationDomain } from "../../calibration";
 import type { FirmwareProgress } from "../../firmware";
 import type { GuidedDomain } from "../../guided";
 import type { LogProgress } from "../../logs";
-import type { MissionState, TransferProgress } from "../../mission";
+import type { MissionState } from "../../mission";
 import type { ParamProgress, ParamStore } from "../../params";
 import type { PlaybackStateSnapshot } from "../../playback";
 import type { MagCalProgress, MagCalReport, SensorHealthDomain } from "../../sensor-health";
@@ -16,40 +17,197 @@ import type { TelemetryDomain } from "../../telemetry";
 export const EVENT_NAMES = {
   SESSION_STATE: "session://state",
   TELEMETRY_STATE: "telemetry://state",
+  TELEMETRY_DELTA: "telemetry://delta",
   MISSION_STATE: "mission://state",
   MISSION_PROGRESS: "mission://progress",
   PARAM_STORE: "param://store",
   PARAM_PROGRESS: "param://progress",
+  PARAM_DELTA: "param://delta",
   SENSOR_HEALTH_STATE: "sensor_health://state",
   CALIBRATION_STATE: "calibration://state",
+  CALIBRATION_PROGRESS: "calibration://progress",
   COMPASS_CAL_PROGRESS: "compass://cal_progress",
   COMPASS_CAL_REPORT: "compass://cal_report",
   STATUS_TEXT_STATE: "status_text://state",
+  ALERT_TRIGGERED: "alert://triggered",
+  ANNOUNCE_SAY: "announce://say",
+  JOURNAL_WARNING: "journal://warning",
+  LINK_LOST_IN_FLIGHT: "link://lost_in_flight",
+  LINK_CONNECTING: "link://connecting",
+  LINK_TRANSPORT_LOST: "link://transport_lost",
+  TASK_STALLED: "internal://task_stalled",
+  LINK_FAILSAFE_WARNING: "link://failsafe_warning",
+  FLIGHT_SUMMARY: "flight://summary",
+  TRACK_APPEND: "track://append",
+  NAMED_VALUE: "custom://named_value",
+  VIDEO_STREAMS: "video://streams",
+  GPS_FIX_CHANGED: "gps://fix_changed",
+  BATTERY_STATS: "battery://stats",
+  CHECKLIST_UPDATE: "checklist://update",
+  GCS_COMMAND_RECEIVED: "gcs://command_received",
+  FTP_PROGRESS: "ftp://progress",
+  COMMAND_PROGRESS: "command://progress",
+  PARAM_EXTERNAL_CHANGE: "param://external_change",
+  RANGEFINDER_READING: "rangefinder://reading",
+  LANDING_ASSIST_WARNING: "landing://assist_warning",
+  PRECISION_LANDING_TARGET: "precision_landing://target",
+  OPTICAL_FLOW_QUALITY: "optical_flow://quality",
+  WINCH_STATUS: "winch://status",
+  VEHICLE_HEALTH: "vehicle://health",
+  VEHICLE_VTOL: "vehicle://vtol",
+  MAVLINK_UNKNOWN: "mavlink://unknown",
+  FENCE_PROXIMITY: "fence://proximity",
+  LINK_DISCONNECT_ACTION: "link://disconnect_action",
+  VEHICLE_FAILSAFE: "vehicle://failsafe",
+  VEHICLE_SELECTED: "vehicle://selected",
+  HOME_MOVED: "home://moved",
+  SERIAL_ATTACHED: "serial://attached",
+  SERIAL_DETACHED: "serial://detached",
   SUPPORT_STATE: "support://state",
+  SYSTEM_RESUMED: "system://resumed",
+  PENDING_CONNECT: "app://pending_connect",
   GUIDED_STATE: "guided://state",
+  GUIDED_TARGET: "guided://target",
+  GUIDED_QUEUE_PROGRESS: "guided_queue://progress",
   PLAYBACK_STATE: "playback://state",
+  LOG_PLAYBACK_POSITION: "log://playback_position",
   LOG_PROGRESS: "log://progress",
+  LOG_APPENDED: "log://appended",
+  SESSION_EXPORT_PROGRESS: "session_export://progress",
+  MAP_PREFETCH_PROGRESS: "map://prefetch_progress",
   FIRMWARE_PROGRESS: "firmware://progress",
 } as const;
 
 export type EventName = (typeof EVENT_NAMES)[keyof typeof EVENT_NAMES];
 
+/**
+ * `telemetry://delta` wire keys and the JSON pointer each one fills in a
+ * `TelemetryDomain`. The value-present key says whether the snapshot has a
+ * `value` at all.
+ */
+export const TELEMETRY_DELTA_VALUE_PRESENT_KEY = "vp";
+
+export const TELEMETRY_DELTA_FIELDS = {
+  "av": "/available",
+  "co": "/complete",
+  "pv": "/provenance",
+  "f.alt": "/value/flight/altitude_m",
+  "f.spd": "/value/flight/speed_mps",
+  "f.clb": "/value/flight/climb_rate_mps",
+  "f.thr": "/value/flight/throttle_pct",
+  "f.as": "/value/flight/airspeed_mps",
+  "n.lat": "/value/navigation/latitude_deg",
+  "n.lon": "/value/navigation/longitude_deg",
+  "n.hdg": "/value/navigation/heading_deg",
+  "n.wpd": "/value/navigation/wp_dist_m",
+  "n.nbr": "/value/navigation/nav_bearing_deg",
+  "n.tbr": "/value/navigation/target_bearing_deg",
+  "n.xte": "/value/navigation/xtrack_error_m",
+  "a.r": "/value/attitude/roll_deg",
+  "a.p": "/value/attitude/pitch_deg",
+  "a.y": "/value/attitude/yaw_deg",
+  "p.pct": "/value/power/battery_pct",
+  "p.v": "/value/power/battery_voltage_v",
+  "p.a": "/value/power/battery_current_a",
+  "p.cells": "/value/power/battery_voltage_cells",
+  "p.wh": "/value/power/energy_consumed_wh",
+  "p.ttr": "/value/power/battery_time_remaining_s",
+  "g.fix": "/value/gps/fix_type",
+  "g.sat": "/value/gps/satellites",
+  "g.hdop": "/value/gps/hdop",
+  "t.th": "/value/terrain/terrain_height_m",
+  "t.hat": "/value/terrain/height_above_terrain_m",
+  "r.rc": "/value/radio/rc_channels",
+  "r.rssi": "/value/radio/rc_rssi",
+  "r.srv": "/value/radio/servo_outputs",
+  "fr.fl.t": "/value/freshness/flight/last_update_usec",
+  "fr.fl.s": "/value/freshness/flight/stale",
+  "fr.nv.t": "/value/freshness/navigation/last_update_usec",
+  "fr.nv.s": "/value/freshness/navigation/stale",
+  "fr.at.t": "/value/freshness/attitude/last_update_usec",
+  "fr.at.s": "/value/freshness/attitude/stale",
+  "fr.pw.t": "/value/freshness/power/last_update_usec",
+  "fr.pw.s": "/value/freshness/power/stale",
+  "fr.gp.t": "/value/freshness/gps/last_update_usec",
+  "fr.gp.s": "/value/freshness/gps/stale",
+  "fr.tr.t": "/value/freshness/terrain/last_update_usec",
+  "fr.tr.s": "/value/freshness/terrain/stale",
+  "fr.rd.t": "/value/freshness/radio/last_update_usec",
+  "fr.rd.s": "/value/freshness/radio/stale",
+  "d.ws": "/value/derived/wind_speed_mps",
+  "d.wd": "/value/derived/wind_direction_deg",
+  "d.wsrc": "/value/derived/wind_source",
+  "d.hd": "/value/derived/home_distance_m",
+  "d.hb": "/value/derived/home_bearing_deg",
+  "d.gr": "/value/derived/glide_ratio",
+  "d.epk": "/value/derived/energy_wh_per_km",
+  "d.rf": "/value/derived/rangefinder_height_m",
+  "d.rc": "/value/derived/rc_link",
+  "d.odo": "/value/derived/odometer_m",
+  "dv": "/value/display",
+} as const;
+
 export type EventPayloadMap = {
   [EVENT_NAMES.SESSION_STATE]: SessionEvent<SessionDomain>;
   [EVENT_NAMES.TELEMETRY_STATE]: SessionEvent<TelemetryDomain>;
+  [EVENT_NAMES.TELEMETRY_DELTA]: SessionEvent<TelemetryDelta>;
   [EVENT_NAMES.MISSION_STATE]: SessionEvent<MissionState>;
-  [EVENT_NAMES.MISSION_PROGRESS]: SessionEvent<TransferProgress>;
+  [EVENT_NAMES.MISSION_PROGRESS]: SessionEvent<MissionTransferProgress>;
   [EVENT_NAMES.PARAM_STORE]: SessionEvent<ParamStore>;
   [EVENT_NAMES.PARAM_PROGRESS]: SessionEvent<ParamProgress>;
+  [EVENT_NAMES.PARAM_DELTA]: SessionEvent<ParamStoreDelta>;
   [EVENT_NAMES.SENSOR_HEALTH_STATE]: SessionEvent<SensorHealthDomain>;
   [EVENT_NAMES.CALIBRATION_STATE]: SessionEvent<CalibrationDomain>;
+  [EVENT_NAMES.CALIBRATION_PROGRESS]: CalibrationProgress;
   [EVENT_NAMES.COMPASS_CAL_PROGRESS]: MagCalProgress;
   [EVENT_NAMES.COMPASS_CAL_REPORT]: MagCalReport;
   [EVENT_NAMES.STATUS_TEXT_STATE]: SessionEvent<StatusTextDomain>;
+  [EVENT_NAMES.ALERT_TRIGGERED]: SessionEvent<AlertTriggered>;
+  [EVENT_NAMES.ANNOUNCE_SAY]: SessionEvent<Announcement>;
+  [EVENT_NAMES.JOURNAL_WARNING]: string;
+  [EVENT_NAMES.LINK_LOST_IN_FLIGHT]: LinkLostInFlight;
+  [EVENT_NAMES.LINK_CONNECTING]: LinkConnecting;
+  [EVENT_NAMES.LINK_TRANSPORT_LOST]: TransportLost;
+  [EVENT_NAMES.TASK_STALLED]: TaskStalled;
+  [EVENT_NAMES.LINK_FAILSAFE_WARNING]: string;
+  [EVENT_NAMES.FLIGHT_SUMMARY]: SessionEvent<FlightSessionSummary>;
+  [EVENT_NAMES.TRACK_APPEND]: SessionEvent<TrackPoint[]>;
+  [EVENT_NAMES.NAMED_VALUE]: SessionEvent<NamedValue>;
+  [EVENT_NAMES.VIDEO_STREAMS]: SessionEvent<VideoStream[]>;
+  [EVENT_NAMES.GPS_FIX_CHANGED]: SessionEvent<GpsFixChanged>;
+  [EVENT_NAMES.BATTERY_STATS]: SessionEvent<BatteryStats>;
+  [EVENT_NAMES.CHECKLIST_UPDATE]: SessionEvent<ChecklistState>;
+  [EVENT_NAMES.GCS_COMMAND_RECEIVED]: SessionEvent<GcsCommandReceived>;
+  [EVENT_NAMES.FTP_PROGRESS]: SessionEvent<FileTransferProgress>;
+  [EVENT_NAMES.COMMAND_PROGRESS]: CommandProgress;
+  [EVENT_NAMES.PARAM_EXTERNAL_CHANGE]: SessionEvent<ParamExternalChange>;
+  [EVENT_NAMES.RANGEFINDER_READING]: SessionEvent<RangefinderReading>;
+  [EVENT_NAMES.LANDING_ASSIST_WARNING]: SessionEvent<LandingAssistWarning>;
+  [EVENT_NAMES.PRECISION_LANDING_TARGET]: SessionEvent<PrecisionLandingTarget>;
+  [EVENT_NAMES.OPTICAL_FLOW_QUALITY]: SessionEvent<OpticalFlowQuality>;
+  [EVENT_NAMES.WINCH_STATUS]: SessionEvent<WinchStatus>;
+  [EVENT_NAMES.VEHICLE_HEALTH]: SessionEvent<HealthReport>;
+  [EVENT_NAMES.VEHICLE_VTOL]: SessionEvent<VtolStatus>;
+  [EVENT_NAMES.MAVLINK_UNKNOWN]: SessionEvent<UnknownMessage>;
+  [EVENT_NAMES.FENCE_PROXIMITY]: SessionEvent<FenceProximity>;
+  [EVENT_NAMES.LINK_DISCONNECT_ACTION]: SessionEvent<DisconnectActionResult>;
+  [EVENT_NAMES.VEHICLE_FAILSAFE]: SessionEvent<FailsafeEvent>;
+  [EVENT_NAMES.VEHICLE_SELECTED]: VehicleSelected;
+  [EVENT_NAMES.HOME_MOVED]: SessionEvent<HomeMoved>;
+  [EVENT_NAMES.SERIAL_ATTACHED]: PortInfo;
+  [EVENT_NAMES.SERIAL_DETACHED]: PortInfo;
   [EVENT_NAMES.SUPPORT_STATE]: SessionEvent<SupportDomain>;
+  [EVENT_NAMES.SYSTEM_RESUMED]: SystemResumed;
+  [EVENT_NAMES.PENDING_CONNECT]: PendingConnect;
   [EVENT_NAMES.GUIDED_STATE]: SessionEvent<GuidedDomain>;
+  [EVENT_NAMES.GUIDED_TARGET]: GuidedTarget;
+  [EVENT_NAMES.GUIDED_QUEUE_PROGRESS]: GuidedQueueStatus;
   [EVENT_NAMES.PLAYBACK_STATE]: SessionEvent<PlaybackStateSnapshot>;
+  [EVENT_NAMES.LOG_PLAYBACK_POSITION]: SessionEvent<PlaybackPosition>;
   [EVENT_NAMES.LOG_PROGRESS]: LogProgress;
+  [EVENT_NAMES.LOG_APPENDED]: LogAppended;
+  [EVENT_NAMES.SESSION_EXPORT_PROGRESS]: SessionExportProgress;
+  [EVENT_NAMES.MAP_PREFETCH_PROGRESS]: MapPrefetchProgress;
   [EVENT_NAMES.FIRMWARE_PROGRESS]: FirmwareProgress;
 };
 
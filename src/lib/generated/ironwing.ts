@@ -1,6 +1,15 @@
 // @generated by `cargo run -p ironwing-contract-export --`
 // Do not edit this file by hand.
 // This file has been generated by Specta. Do not edit this file manually.
+/**  Result of `send_command_long`: the target's final COMMAND_ACK. */
+export type AckResult = {
+	command_id: number,
+	command: string,
+	accepted: boolean,
+	message: string | null,
+	attempts: number,
+};
+
 export type AckSessionSnapshotResult = { result: "accepted"; envelope: SessionEnvelope } | { result: "rejected"; failure: OperationFailure };
 
 export type ActionRequired =
@@ -17,18 +26,182 @@ export type AddressValidation = {
 	address_required: boolean,
 };
 
+export type AlertCondition = "lt" | "gt" | "eq" | "changed";
+
+/**
+ *  One alert rule evaluated by the backend against live telemetry.
+ *
+ *  `source` is a dotted path into `telemetry` (a `TelemetryState`),
+ *  `vehicle` (a `VehicleState`), `battery` (a `BatteryStats`) or `failsafe`
+ *  (decoded failsafe counts), e.g. `telemetry.power.battery_pct`,
+ *  `vehicle.mode_name`, `battery.cell_voltage_v` or `failsafe.radio` with
+ *  `changed`. `threshold` is required for `lt`, `gt` and `eq`.
+ */
+export type AlertRule = AlertRule_Serialize | AlertRule_Deserialize;
+
+/**
+ *  One alert rule evaluated by the backend against live telemetry.
+ *
+ *  `source` is a dotted path into `telemetry` (a `TelemetryState`),
+ *  `vehicle` (a `VehicleState`), `battery` (a `BatteryStats`) or `failsafe`
+ *  (decoded failsafe counts), e.g. `telemetry.power.battery_pct`,
+ *  `vehicle.mode_name`, `battery.cell_voltage_v` or `failsafe.radio` with
+ *  `changed`. `threshold` is required for `lt`, `gt` and `eq`.
+ */
+export type AlertRule_Deserialize = {
+	id: string,
+	source: string,
+	condition: AlertCondition,
+	threshold?: number | null,
+	hysteresis?: number | null,
+	min_interval_s?: number | null,
+	enabled?: boolean,
+};
+
+/**
+ *  One alert rule evaluated by the backend against live telemetry.
+ *
+ *  `source` is a dotted path into `telemetry` (a `TelemetryState`),
+ *  `vehicle` (a `VehicleState`), `battery` (a `BatteryStats`) or `failsafe`
+ *  (decoded failsafe counts), e.g. `telemetry.power.battery_pct`,
+ *  `vehicle.mode_name`, `battery.cell_voltage_v` or `failsafe.radio` with
+ *  `changed`. `threshold` is required for `lt`, `gt` and `eq`.
+ */
+export type AlertRule_Serialize = {
+	id: string,
+	source: string,
+	condition: AlertCondition,
+	threshold: number | null,
+	hysteresis: number | null,
+	min_interval_s: number | null,
+	enabled: boolean,
+};
+
+/**  Payload of `alert://triggered`. */
+export type AlertTriggered = {
+	rule_id: string,
+	source: string,
+	value: AlertValue,
+};
+
+export type AlertValue = number | null | string | boolean;
+
 /**  Typed mission command API item used by plan serialization and validation. */
 export type AltChangeAction = "neutral" | "climb" | "descend";
 
+/**  How `mission_scale_altitudes` changes every altitude. */
+export type AltitudeChange =
+/**  Add `delta_m` to every altitude. */
+{ kind: "offset"; delta_m: number | null } |
+/**  Multiply every altitude by `factor`. */
+{ kind: "scale"; factor: number | null };
+
+/**  Altitude reference a mission can be converted to. */
+export type AltitudeFrame =
+/**  Above the home position. */
+"relative_alt" | "amsl" |
+/**  Above the terrain under each item. */
+"terrain";
+
 export type AnalyticsProperty = string | number;
 
+/**  Payload of `announce://say`: one sentence worded to be spoken. */
+export type Announcement = {
+	category: AnnouncementCategory,
+	priority: AnnouncementPriority,
+	text: string,
+};
+
+export type AnnouncementCategory = "waypoint" | "altitude" | "battery" | "mode" | "failsafe";
+
+/**  How urgently a speech consumer should read an announcement out. */
+export type AnnouncementPriority =
+/**  Progress that can wait behind anything else queued. */
+"low" | "normal" |
+/**  Safety-relevant; may interrupt an announcement being spoken. */
+"high";
+
+/**  Which progress announcements are made and how often. */
+export type AnnouncementSettings = AnnouncementSettings_Serialize | AnnouncementSettings_Deserialize;
+
+/**  Which progress announcements are made and how often. */
+export type AnnouncementSettings_Deserialize = {
+	waypoint?: boolean,
+	altitude?: boolean,
+	altitude_step_m?: number | null,
+	battery?: boolean,
+	battery_step_pct?: number | null,
+	mode?: boolean,
+	mode_settle_s?: number | null,
+	failsafe?: boolean,
+};
+
+/**  Which progress announcements are made and how often. */
+export type AnnouncementSettings_Serialize = {
+	waypoint: boolean,
+	altitude: boolean,
+	altitude_step_m: number | null,
+	battery: boolean,
+	battery_step_pct: number | null,
+	mode: boolean,
+	mode_settle_s: number | null,
+	failsafe: boolean,
+};
+
+/**
+ *  Error returned by backend commands.
+ *
+ *  Serialized with a stable `code` the frontend can branch on, next to the
+ *  human-readable `message`, which may be localized or reworded by firmware
+ *  and must not be matched on.
+ */
+export type AppError = { code: "not_connected"; message: string } | { code: "timeout"; message: string } |
+/**  The vehicle answered and refused; `result` is its MAV_RESULT. */
+{ code: "command_denied"; result: MavResultKind; message: string } | { code: "validation"; issues: string[]; message: string } | { code: "transport"; kind: string; message: string } | { code: "internal"; message: string } |
+/**  GCS-side safety gates refused the command before it was sent. */
+{ code: "safety_gate"; failures: SafetyGateFailure[]; message: string } |
+/**
+ *  The command has no implementation for the connected autopilot, so
+ *  nothing was sent.
+ */
+{ code: "unsupported_autopilot"; autopilot: AutopilotType; message: string };
+
 /**  MAVLink autopilot firmware type. */
 export type AutopilotType = "unknown" | "generic" | "ardu_pilot_mega" | "px4";
 
+/**  Payload of `battery_stats` and `battery://stats`. */
+export type BatteryStats = {
+	cell_count: number | null,
+	cell_count_source: CellCountSource | null,
+	voltage_v: number | null,
+	cell_voltage_v: number | null,
+	cell_voltages_v: (number | null)[] | null,
+};
+
 export type BluetoothProfile = "nordic_uart";
 
+/**  A calibration whose outcome the vehicle reports over STATUSTEXT. */
+export type CalibrationKind = "gyro" | "accel" | "compass";
+
 export type CalibrationLifecycle = "not_started" | "running" | "complete" | "failed";
 
+/**
+ *  A calibration STATUSTEXT seen while waiting for the result, sent on
+ *  `calibration://progress`.
+ */
+export type CalibrationProgress = {
+	kind: CalibrationKind,
+	text: string,
+};
+
+/**  How a simple calibration ended, as reported after its ACK. */
+export type CalibrationResult = { outcome: "succeeded" } | { outcome: "failed"; reason: string } |
+/**
+ *  No completion message arrived in time; the calibration may still be
+ *  running on the vehicle.
+ */
+{ outcome: "timeout" };
+
 export type CalibrationState = {
 	accel: CalibrationStep | null,
 	compass: CalibrationStep | null,
@@ -72,6 +245,15 @@ export type CatalogTargetSummary = {
 	latest_version: string | null,
 };
 
+/**  Where `BatteryStats::cell_count` came from, in order of preference. */
+export type CellCountSource =
+/**  BATTERY_STATUS carries one voltage per cell. */
+"reported" |
+/**  BATT_CELL_COUNT-style parameter set on the vehicle. */
+"param" |
+/**  Guessed from the first stable pack voltage. */
+"inferred";
+
 export type ChartPoint = {
 	timestamp_usec: bigint,
 	value: number | null,
@@ -105,6 +287,98 @@ export type ChartSeriesSelector = {
 	unit: string | null,
 };
 
+/**  Backend state an automatic checklist item verifies. */
+export type ChecklistCheck = "gps3d_fix" |
+/**  Needs `threshold` as a percentage. */
+"battery_above_pct" | "home_set" |
+/**  A non-empty mission is on the vehicle. */
+"mission_uploaded" |
+/**  No sensor reports unhealthy and no PreArm message arrived recently. */
+"prearm_pass";
+
+export type ChecklistConfig = ChecklistConfig_Serialize | ChecklistConfig_Deserialize;
+
+export type ChecklistConfig_Deserialize = {
+	items?: ChecklistItem_Deserialize[],
+	enforce_on_arm?: boolean,
+};
+
+export type ChecklistConfig_Serialize = {
+	items: ChecklistItem_Serialize[],
+	enforce_on_arm: boolean,
+};
+
+/**
+ *  One checklist entry. Items without a `check` are manual and pass once the
+ *  user ticks them.
+ */
+export type ChecklistItem = ChecklistItem_Serialize | ChecklistItem_Deserialize;
+
+export type ChecklistItemState = {
+	id: string,
+	label: string,
+	check: ChecklistCheck | null,
+	required: boolean,
+	status: ChecklistItemStatus,
+	detail: string | null,
+};
+
+export type ChecklistItemStatus =
+/**  Manual item not ticked yet. */
+"unchecked" |
+/**  Manual item ticked. */
+"checked" | "pass" | "fail" |
+/**  The backend has nothing to verify the item against yet. */
+"unknown";
+
+/**
+ *  One checklist entry. Items without a `check` are manual and pass once the
+ *  user ticks them.
+ */
+export type ChecklistItem_Deserialize = {
+	id: string,
+	label: string,
+	check?: ChecklistCheck | null,
+	threshold?: number | null,
+	required?: boolean,
+};
+
+/**
+ *  One checklist entry. Items without a `check` are manual and pass once the
+ *  user ticks them.
+ */
+export type ChecklistItem_Serialize = {
+	id: string,
+	label: string,
+	check: ChecklistCheck | null,
+	threshold: number | null,
+	required: boolean,
+};
+
+/**  Payload of `checklist_state` and `checklist://update`. */
+export type ChecklistState = {
+	items: ChecklistItemState[],
+	enforce_on_arm: boolean,
+	complete: boolean,
+};
+
+/**
+ *  A MAV_RESULT_IN_PROGRESS ACK for a command the GCS is waiting on, sent
+ *  on `command://progress`.
+ */
+export type CommandProgress = {
+	command: string,
+	progress: number | null,
+};
+
+/**  One component (autopilot, camera, gimbal, companion...) of the bound system. */
+export type ComponentListEntry = {
+	component_id: number,
+	mav_type: number,
+	autopilot: number,
+	last_seen_ms_ago: bigint,
+};
+
 /**  Typed mission command API item used by plan serialization and validation. */
 export type CondDelay = {
 	delay_s: number | null,
@@ -131,18 +405,50 @@ export type ConnectRequest = ConnectRequest_Serialize | ConnectRequest_Deseriali
 export type ConnectRequest_Deserialize = {
 	transport: ConnectTransport_Deserialize,
 	auto_record_on_connect?: boolean,
+	heartbeat_timeout_secs?: number | null,
+	keep_waiting?: boolean,
 };
 
 export type ConnectRequest_Serialize = {
 	transport: ConnectTransport_Serialize,
 	auto_record_on_connect: boolean,
+	heartbeat_timeout_secs?: number | null,
+	keep_waiting: boolean,
 };
 
 export type ConnectTransport = ConnectTransport_Serialize | ConnectTransport_Deserialize;
 
-export type ConnectTransport_Deserialize = ({ kind: "udp"; bind_addr: string }) & { address?: never; baud?: never; device_id?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "tcp"; address: string }) & { baud?: never; bind_addr?: never; device_id?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "serial"; port: string; baud: number }) & { address?: never; bind_addr?: never; device_id?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "bluetooth_ble"; address: string; profile?: BluetoothProfile | null }) & { baud?: never; bind_addr?: never; device_id?: never; port?: never; port_id?: never; url?: never; vehicle_preset?: never } | ({ kind: "bluetooth_spp"; address: string }) & { baud?: never; bind_addr?: never; device_id?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "websocket"; url: string }) & { address?: never; baud?: never; bind_addr?: never; device_id?: never; port?: never; port_id?: never; profile?: never; vehicle_preset?: never } | ({ kind: "web_serial"; baud: number; port_id: string }) & { address?: never; bind_addr?: never; device_id?: never; port?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "web_bluetooth"; device_id?: string | null; profile: BluetoothProfile }) & { address?: never; baud?: never; bind_addr?: never; port?: never; port_id?: never; url?: never; vehicle_preset?: never } | ({ kind: "demo"; vehicle_preset: DemoVehiclePreset }) & { address?: never; baud?: never; bind_addr?: never; device_id?: never; port?: never; port_id?: never; profile?: never; url?: never };
+export type ConnectTransport_Deserialize = ({ kind: "udp"; bind_addr: string }) & { address?: never; auto_baud?: never; baud?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "tcp"; address: string }) & { auto_baud?: never; baud?: never; bind_addr?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "serial"; port: string; baud: number; flow_control?: SerialFlowControl; parity?: SerialParity; auto_baud?: boolean }) & { address?: never; bind_addr?: never; device_id?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "bluetooth_ble"; address: string; profile?: BluetoothProfile | null }) & { auto_baud?: never; baud?: never; bind_addr?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; url?: never; vehicle_preset?: never } | ({ kind: "bluetooth_spp"; address: string }) & { auto_baud?: never; baud?: never; bind_addr?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "usb_serial"; device_id: string; baud: number }) & { address?: never; auto_baud?: never; bind_addr?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "websocket"; url: string }) & { address?: never; auto_baud?: never; baud?: never; bind_addr?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; profile?: never; vehicle_preset?: never } | ({ kind: "web_serial"; baud: number; port_id: string }) & { address?: never; auto_baud?: never; bind_addr?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "web_bluetooth"; device_id?: string | null; profile: BluetoothProfile }) & { address?: never; auto_baud?: never; baud?: never; bind_addr?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; url?: never; vehicle_preset?: never } | ({ kind: "demo"; vehicle_preset: DemoVehiclePreset }) & { address?: never; auto_baud?: never; baud?: never; bind_addr?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; profile?: never; url?: never };
+
+export type ConnectTransport_Serialize = ({ kind: "udp"; bind_addr: string }) & { address?: never; auto_baud?: never; baud?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "tcp"; address: string }) & { auto_baud?: never; baud?: never; bind_addr?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "serial"; port: string; baud: number; flow_control: SerialFlowControl; parity: SerialParity; auto_baud: boolean }) & { address?: never; bind_addr?: never; device_id?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "bluetooth_ble"; address: string; profile?: BluetoothProfile | null }) & { auto_baud?: never; baud?: never; bind_addr?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; url?: never; vehicle_preset?: never } | ({ kind: "bluetooth_spp"; address: string }) & { auto_baud?: never; baud?: never; bind_addr?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "usb_serial"; device_id: string; baud: number }) & { address?: never; auto_baud?: never; bind_addr?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "websocket"; url: string }) & { address?: never; auto_baud?: never; baud?: never; bind_addr?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; profile?: never; vehicle_preset?: never } | ({ kind: "web_serial"; baud: number; port_id: string }) & { address?: never; auto_baud?: never; bind_addr?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "web_bluetooth"; device_id?: string | null; profile: BluetoothProfile }) & { address?: never; auto_baud?: never; baud?: never; bind_addr?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; url?: never; vehicle_preset?: never } | ({ kind: "demo"; vehicle_preset: DemoVehiclePreset }) & { address?: never; auto_baud?: never; baud?: never; bind_addr?: never; device_id?: never; flow_control?: never; parity?: never; port?: never; port_id?: never; profile?: never; url?: never };
+
+export type ConnectionInfo = ConnectionInfo_Serialize | ConnectionInfo_Deserialize;
+
+export type ConnectionInfo_Deserialize = {
+	connected: boolean,
+	gcs: GcsIdentity,
+	link_profile: LinkProfile,
+	vehicle_system_id: number | null,
+	vehicle_component_id: number | null,
+	serial_baud?: number | null,
+	frame_stats?: LinkFrameStats | null,
+	transport_stats?: TransportStats | null,
+	gcs_position_fixes_sent?: bigint,
+	late_command_acks?: bigint,
+};
 
-export type ConnectTransport_Serialize = ({ kind: "udp"; bind_addr: string }) & { address?: never; baud?: never; device_id?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "tcp"; address: string }) & { baud?: never; bind_addr?: never; device_id?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "serial"; port: string; baud: number }) & { address?: never; bind_addr?: never; device_id?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "bluetooth_ble"; address: string; profile?: BluetoothProfile | null }) & { baud?: never; bind_addr?: never; device_id?: never; port?: never; port_id?: never; url?: never; vehicle_preset?: never } | ({ kind: "bluetooth_spp"; address: string }) & { baud?: never; bind_addr?: never; device_id?: never; port?: never; port_id?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "websocket"; url: string }) & { address?: never; baud?: never; bind_addr?: never; device_id?: never; port?: never; port_id?: never; profile?: never; vehicle_preset?: never } | ({ kind: "web_serial"; baud: number; port_id: string }) & { address?: never; bind_addr?: never; device_id?: never; port?: never; profile?: never; url?: never; vehicle_preset?: never } | ({ kind: "web_bluetooth"; device_id?: string | null; profile: BluetoothProfile }) & { address?: never; baud?: never; bind_addr?: never; port?: never; port_id?: never; url?: never; vehicle_preset?: never } | ({ kind: "demo"; vehicle_preset: DemoVehiclePreset }) & { address?: never; baud?: never; bind_addr?: never; device_id?: never; port?: never; port_id?: never; profile?: never; url?: never };
+export type ConnectionInfo_Serialize = {
+	connected: boolean,
+	gcs: GcsIdentity,
+	link_profile: LinkProfile,
+	vehicle_system_id: number | null,
+	vehicle_component_id: number | null,
+	serial_baud?: number | null,
+	frame_stats?: LinkFrameStats | null,
+	transport_stats?: TransportStats | null,
+	gcs_position_fixes_sent: bigint,
+	late_command_acks: bigint,
+};
 
 export type DemoValidation = Record<string, never>;
 
@@ -180,6 +486,43 @@ export type DfuRecoverySource =
 
 export type DfuScanResult = { kind: "available"; devices: DfuDeviceInfo[] } | { kind: "unsupported" };
 
+/**  Internal state for bug reports, as returned by `diagnostics`. */
+export type Diagnostics = {
+	tasks: TaskDiagnostic[],
+	event_bridges: EventBridgeStats[],
+};
+
+/**  One file of a `diagnostics_export` bundle. */
+export type DiagnosticsBundleEntry = {
+	file_name: string,
+	bytes: bigint,
+};
+
+/**  Result of `diagnostics_export`. */
+export type DiagnosticsExport = {
+	path: string,
+	bytes_written: bigint,
+	entries: DiagnosticsBundleEntry[],
+};
+
+/**  What to command an armed vehicle to do before the link is closed. */
+export type DisconnectAction = "none" | "rtl" | "land";
+
+export type DisconnectActionOutcome =
+/**  Nothing was sent: no action was selected or the vehicle was disarmed. */
+{ status: "skipped"; reason: string } | { status: "accepted" } |
+/**  The vehicle answered with anything but MAV_RESULT_ACCEPTED. */
+{ status: "rejected"; message: string } |
+/**  No final COMMAND_ACK within the bound; the link was closed anyway. */
+{ status: "timed_out" } | { status: "failed"; message: string };
+
+/**  Emitted on `link://disconnect_action` before the transport closes. */
+export type DisconnectActionResult = {
+	action: DisconnectAction,
+	armed: boolean,
+	outcome: DisconnectActionOutcome,
+};
+
 export type DisconnectRequest = DisconnectRequest_Serialize | DisconnectRequest_Deserialize;
 
 export type DisconnectRequest_Deserialize = {
@@ -190,6 +533,17 @@ export type DisconnectRequest_Serialize = {
 	session_id?: string | null,
 };
 
+/**
+ *  A telemetry value converted to the display units, next to the SI field it
+ *  came from.
+ */
+export type DisplayValue = {
+	value: number | null,
+	unit: string,
+};
+
+export type DistanceUnit = "m" | "ft";
+
 /**  Typed mission command API item used by plan serialization and validation. */
 export type DoAutotuneEnable = {
 	enabled: boolean,
@@ -460,9 +814,83 @@ export type DomainValue<T> = {
 	value: T | null,
 };
 
+/**
+ *  Raw update count versus emitted count for one event stream, as reported
+ *  by `event_bridge_stats`.
+ */
+export type EventBridgeStats = {
+	event: string,
+	max_rate_hz: number | null,
+	changes: bigint,
+	emitted: bigint,
+};
+
+export type ExportReport = {
+	path: string,
+	bytes_written: bigint,
+	artifacts: SessionArtifactEntry[],
+};
+
+/**
+ *  A failsafe decoded from the vehicle's STATUSTEXT, emitted on
+ *  `vehicle://failsafe`.
+ */
+export type FailsafeEvent = {
+	kind: FailsafeKind,
+	action_taken: string | null,
+	timestamp_usec: bigint | null,
+	text: string,
+};
+
+/**  What tripped an onboard failsafe. */
+export type FailsafeKind = "battery" |
+/**  RC signal or throttle failsafe. */
+"radio" |
+/**  Loss of the GCS / telemetry link. */
+"gcs" | "ekf" | "geofence" | "terrain" | "crash" |
+/**  Reported as a failsafe, but of no kind listed above. */
+"other";
+
 /**  Typed mission command API item used by plan serialization and validation. */
 export type FenceAction = "disable" | "enable" | "disable_floor";
 
+/**  Which kind of fence boundary a proximity report is about. */
+export type FenceBoundaryKind = "inclusion_polygon" | "exclusion_polygon" | "inclusion_circle" | "exclusion_circle" |
+/**  The FENCE_RADIUS circle around home. */
+"home_circle";
+
+/**
+ *  The fence boundary nearest to being breached, emitted on
+ *  `fence://proximity` while the vehicle is inside the warning margin and
+ *  closing on it.
+ */
+export type FenceProximity = {
+	kind: FenceBoundaryKind,
+	region_index: number | null,
+	distance_m: number | null,
+	closing_speed_mps: number | null,
+	time_to_breach_s: number | null,
+};
+
+export type FileTransferPhase = "upload" |
+/**  Reading the file back to compare checksums. */
+"verify";
+
+/**  Payload of `ftp://progress`. */
+export type FileTransferProgress = {
+	remote_path: string,
+	phase: FileTransferPhase,
+	bytes_done: bigint,
+	bytes_total: bigint,
+};
+
+/**  A file written to the vehicle and read back with a matching CRC32. */
+export type FileUploadReport = {
+	remote_path: string,
+	size_bytes: bigint,
+	crc32: number,
+};
+
 export type FirmwareBootloaderBoardInfo = {
 	port: string,
 	board_id: number,
@@ -497,6 +925,108 @@ export type FirmwareSessionStatus = { kind: "idle" } |
 
 export type FirmwareSource = { kind: "official_catalog"; board_id: number; url: string; version: string } | { kind: "local_apj"; path: string } | { kind: "local_bin"; path: string };
 
+export type FlightModeChannelConfig = {
+	channel_param: string,
+	channel: number | null,
+	ranges: FlightModeRange[],
+	channel_pwm: number | null,
+	active_slot: number | null,
+};
+
+/**  The vehicle's current mode, decoded from its heartbeat custom_mode. */
+export type FlightModeCurrent = {
+	custom_mode: number,
+	name: string | null,
+	dangerous: boolean,
+};
+
+/**
+ *  One position of the flight-mode switch: the PWM band that selects it and
+ *  the mode its slot parameter holds.
+ */
+export type FlightModeRange = {
+	slot: number,
+	param: string,
+	pwm_low: number,
+	pwm_high: number,
+	custom_mode: number | null,
+	mode: string | null,
+};
+
+/**
+ *  Aggregates for one armed period of the live vehicle, emitted as
+ *  `flight://summary` when it disarms.
+ */
+export type FlightSessionSummary = {
+	started_at_usec: bigint,
+	ended_at_usec: bigint,
+	duration_s: number | null,
+	max_altitude_m: number | null,
+	max_distance_from_home_m: number | null,
+	max_speed_mps: number | null,
+	battery_used_pct: number | null,
+	energy_used_wh: number | null,
+	distance_flown_m: number | null,
+};
+
+export type FrameApplyResult = {
+	option_id: string,
+	param_results: ParamWriteResult[],
+	reboot_required: boolean,
+	rebooted: boolean,
+};
+
+/**
+ *  One airframe the setup wizard can offer: an ArduPilot FRAME_CLASS /
+ *  FRAME_TYPE pair (Q_FRAME_* on QuadPlanes) or a PX4 SYS_AUTOSTART id.
+ */
+export type FrameOption = {
+	id: string,
+	name: string,
+	group: string,
+	motor_layout: string | null,
+	motor_count: number,
+	params: FrameParam[],
+	current: boolean,
+};
+
+export type FrameParam = {
+	name: string,
+	value: number | null,
+};
+
+/**
+ *  A COMMAND_LONG addressed to this GCS that it has no handler for. It was
+ *  ACKed MAV_RESULT_UNSUPPORTED; the event lets the UI (or a future plugin)
+ *  see who is asking for what.
+ */
+export type GcsCommandReceived = {
+	source_system: number,
+	source_component: number,
+	target_component: number,
+	command_id: number,
+	command: string,
+	params: (number | null)[],
+	confirmation: number,
+};
+
+/**  What the vehicle is configured to do when it stops hearing the GCS. */
+export type GcsFailsafeBehavior = {
+	parameter: string | null,
+	value: number | null,
+	action: string,
+	does_nothing: boolean,
+};
+
+/**
+ *  MAVLink system/component IDs IronWing uses for its own GCS HEARTBEAT and
+ *  outgoing commands. Defaults match MAVKit's (255/190).
+ */
+export type GcsIdentity = {
+	system_id: number,
+	component_id: number,
+};
+
 /**  Tagged 3D point that preserves the altitude reference frame. */
 export type GeoPoint3d = ({ Msl: GeoPoint3dMsl }) & { RelHome?: never; Terrain?: never } | ({ RelHome: GeoPoint3dRelHome }) & { Msl?: never; Terrain?: never } | ({ Terrain: GeoPoint3dTerrain }) & { Msl?: never; RelHome?: never };
 
@@ -521,9 +1051,101 @@ export type GeoPoint3dTerrain = {
 	altitude_terrain_m: number | null,
 };
 
+/**  Altitude reference of a goto target. */
+export type GotoFrame =
+/**  Above the home position. */
+"relative_alt" | "amsl" |
+/**  Above the terrain under the target. */
+"terrain";
+
+export type GotoMethod =
+/**  SET_POSITION_TARGET_GLOBAL_INT, used for copters and rovers. */
+"position_target" |
+/**  MAV_CMD_DO_REPOSITION, used for planes. */
+"reposition";
+
+/**
+ *  Optional parts of a goto. Unset fields leave the autopilot's own defaults
+ *  in place.
+ */
+export type GotoOptions = GotoOptions_Serialize | GotoOptions_Deserialize;
+
+/**
+ *  Optional parts of a goto. Unset fields leave the autopilot's own defaults
+ *  in place.
+ */
+export type GotoOptions_Deserialize = {
+	ground_speed_ms?: number | null,
+	yaw_deg?: number | null,
+	acceptance_radius_m?: number | null,
+	frame?: GotoFrame,
+};
+
+/**
+ *  Optional parts of a goto. Unset fields leave the autopilot's own defaults
+ *  in place.
+ */
+export type GotoOptions_Serialize = {
+	ground_speed_ms: number | null,
+	yaw_deg: number | null,
+	acceptance_radius_m: number | null,
+	frame: GotoFrame,
+};
+
+export type GotoRequest = GotoRequest_Serialize | GotoRequest_Deserialize;
+
+export type GotoRequest_Deserialize = {
+	latitude_deg: number | null,
+	longitude_deg: number | null,
+	altitude_m: number | null,
+	options?: GotoOptions_Deserialize,
+};
+
+export type GotoRequest_Serialize = {
+	latitude_deg: number | null,
+	longitude_deg: number | null,
+	altitude_m: number | null,
+	options: GotoOptions_Serialize,
+};
+
+export type GotoResult = {
+	method: GotoMethod,
+	ignored_options: string[],
+};
+
+/**  GPS_FIX_TYPE, in order of quality. */
+export type GpsFix = "no_gps" | "no_fix" | "fix2d" | "fix3d" | "dgps" | "rtk_float" | "rtk_fixed" | "static" | "ppp";
+
+/**  Payload of `gps://fix_changed`. */
+export type GpsFixChanged = {
+	instance: number,
+	previous: GpsFix | null,
+	fix: GpsFix,
+	satellites: number | null,
+	hdop: number | null,
+	rtk_baseline_age_ms: number | null,
+};
+
+export type GpsInstanceStatus = {
+	instance: number,
+	fix: GpsFix,
+	satellites: number | null,
+	hdop: number | null,
+	rtk_baseline_age_ms: number | null,
+	time_to_3d_fix_ms: bigint | null,
+};
+
+export type GpsStatus = {
+	instances: GpsInstanceStatus[],
+	time_to_first_3d_fix_ms: bigint | null,
+};
+
 /**  Typed mission command API item used by plan serialization and validation. */
 export type GripperAction = "release" | "grab";
 
+/**  MAV_CMD_DO_GRIPPER action. */
+export type GripperCommand = "grab" | "release";
+
 export type GuidedAction = {
 	allowed: boolean,
 	blocking_reason: GuidedBlockingReason | null,
@@ -557,6 +1179,32 @@ export type GuidedLastCommand = {
 	at_unix_msec: bigint,
 };
 
+/**  One stop of a guided queue, altitude relative to home. */
+export type GuidedQueuePoint = {
+	latitude_deg: number | null,
+	longitude_deg: number | null,
+	altitude_m: number | null,
+};
+
+export type GuidedQueueState =
+/**  No points set. */
+"empty" |
+/**  Points set and not yet flown. */
+"ready" | "running" |
+/**  The last point was reached. */
+"completed" |
+/**  Stopped before the last point; see `stop_reason`. */
+"stopped";
+
+/**  Sent on `guided_queue://progress` whenever the queue advances or stops. */
+export type GuidedQueueStatus = {
+	state: GuidedQueueState,
+	points: GuidedQueuePoint[],
+	current_index: number | null,
+	distance_m: number | null,
+	stop_reason: string | null,
+};
+
 export type GuidedSession = { kind: "goto"; latitude_deg: number | null; longitude_deg: number | null; altitude_msl_m: number | null };
 
 export type GuidedSessionKind = "goto";
@@ -573,6 +1221,34 @@ export type GuidedState = {
 
 export type GuidedStatus = "idle" | "active" | "blocked" | "unavailable";
 
+/**
+ *  Payload of `guided://target`: the target most recently commanded to the
+ *  vehicle.
+ */
+export type GuidedTarget = GuidedTarget_Serialize | GuidedTarget_Deserialize;
+
+/**
+ *  Payload of `guided://target`: the target most recently commanded to the
+ *  vehicle.
+ */
+export type GuidedTarget_Deserialize = {
+	latitude_deg: number | null,
+	longitude_deg: number | null,
+	altitude_m: number | null,
+	options: GotoOptions_Deserialize,
+};
+
+/**
+ *  Payload of `guided://target`: the target most recently commanded to the
+ *  vehicle.
+ */
+export type GuidedTarget_Serialize = {
+	latitude_deg: number | null,
+	longitude_deg: number | null,
+	altitude_m: number | null,
+	options: GotoOptions_Serialize,
+};
+
 export type GuidedTermination = {
 	reason: GuidedTerminationReason,
 	at_unix_msec: bigint,
@@ -581,6 +1257,59 @@ export type GuidedTermination = {
 
 export type GuidedTerminationReason = "disconnect" | "mode_change" | "source_switch" | "vehicle_missing";
 
+export type HealthComponent =
+/**  SYS_STATUS sensor present/enabled/health bits. */
+"sensors" |
+/**  EKF_STATUS_REPORT flags and variances. */
+"ekf" | "gps" | "battery" |
+/**  SYS_STATUS communication drop rate as seen by the vehicle. */
+"link" |
+/**  GCS failsafe parameters. */
+"failsafe" | "heartbeat" |
+/**  EXTENDED_SYS_STATE VTOL state, for VTOL frames only. */
+"vtol";
+
+export type HealthEntry = {
+	component: HealthComponent,
+	status: HealthStatus,
+	detail: string,
+};
+
+/**
+ *  Payload of `vehicle_health` and `vehicle://health`. `overall` is the worst
+ *  entry. Components the vehicle has not reported on are left out.
+ */
+export type HealthReport = {
+	overall: HealthStatus,
+	entries: HealthEntry[],
+};
+
+/**  Severity of one health entry, in increasing order of concern. */
+export type HealthStatus = "ok" | "warn" | "error";
+
+/**  Limits the battery, link and heartbeat entries are graded against. */
+export type HealthThresholds = {
+	battery_warn_pct: number | null,
+	battery_error_pct: number | null,
+	link_drop_warn_pct: number | null,
+	link_drop_error_pct: number | null,
+	heartbeat_warn_ms: bigint,
+	heartbeat_error_ms: bigint,
+};
+
+/**
+ *  Home moved while the vehicle was armed, emitted on `home://moved`. RTL
+ *  now returns to the new home, so the operator should know.
+ */
+export type HomeMoved = {
+	old_latitude_deg: number | null,
+	old_longitude_deg: number | null,
+	new_latitude_deg: number | null,
+	new_longitude_deg: number | null,
+	distance_m: number | null,
+	vehicle_to_new_home_m: number | null,
+};
+
 /**  Home position in WGS84 coordinates. */
 export type HomePosition = {
 	latitude_deg: number | null,
@@ -595,12 +1324,209 @@ export type InventoryResult =
 /**  Android/unsupported: typed refusal, not fake data. */
 { kind: "unsupported" };
 
-export type LogCatalogMigrationError = { kind: "missing_schema_version" } | { kind: "unsupported_schema_version"; schema_version: bigint; supported_schema_version: number } | { kind: "invalid_catalog"; message: string };
+/**  One command the GCS sent, recorded with the vehicle's answer. */
+export type JournalEntry = JournalEntry_Serialize | JournalEntry_Deserialize;
 
-export type LogDiagnostic = {
-	severity: LogDiagnosticSeverity,
-	source: LogDiagnosticSource,
-	code: string,
+/**  One command the GCS sent, recorded with the vehicle's answer. */
+export type JournalEntry_Deserialize = {
+	timestamp_ms: bigint,
+	duration_ms: bigint,
+	operation_id: OperationId,
+	request: unknown,
+	outcome: JournalOutcome,
+	vehicle_time?: VehicleTime | null,
+};
+
+/**  One command the GCS sent, recorded with the vehicle's answer. */
+export type JournalEntry_Serialize = {
+	timestamp_ms: bigint,
+	duration_ms: bigint,
+	operation_id: OperationId,
+	request: unknown,
+	outcome: JournalOutcome,
+	vehicle_time: VehicleTime | null,
+};
+
+export type JournalOutcome = { kind: "ok"; result: unknown } | { kind: "err"; message: string };
+
+/**  MAV_LANDED_STATE from EXTENDED_SYS_STATE. */
+export type LandedState = "undefined" | "on_ground" | "in_air" | "takeoff" | "landing";
+
+/**  Descent-rate limit that applies at and below `height_m`. */
+export type LandingAssistBand = {
+	height_m: number | null,
+	max_descent_rate_mps: number | null,
+};
+
+/**
+ *  Descent-rate limits checked against the downward rangefinder on the
+ *  way down.
+ */
+export type LandingAssistProfile = LandingAssistProfile_Serialize | LandingAssistProfile_Deserialize;
+
+/**
+ *  Descent-rate limits checked against the downward rangefinder on the
+ *  way down.
+ */
+export type LandingAssistProfile_Deserialize = {
+	enabled?: boolean,
+	bands?: LandingAssistBand[],
+};
+
+/**
+ *  Descent-rate limits checked against the downward rangefinder on the
+ *  way down.
+ */
+export type LandingAssistProfile_Serialize = {
+	enabled: boolean,
+	bands: LandingAssistBand[],
+};
+
+/**  Descending faster than the profile allows for the measured height. */
+export type LandingAssistWarning = {
+	height_m: number | null,
+	descent_rate_mps: number | null,
+	max_descent_rate_mps: number | null,
+};
+
+export type LinkConnectPhase = "opening_transport" |
+/**  The transport is open but no vehicle heartbeat has arrived yet. */
+"waiting_for_heartbeat";
+
+/**
+ *  Progress of a `connect_link` call, emitted as `link://connecting` until
+ *  the first heartbeat arrives or the attempt fails.
+ */
+export type LinkConnecting = {
+	phase: LinkConnectPhase,
+	elapsed_ms: bigint,
+	timeout_ms: bigint | null,
+};
+
+/**  What the frame scanner made of one link's byte stream. */
+export type LinkFrameStats = {
+	frames: bigint,
+	bad_crc_frames: bigint,
+	skipped_bytes: bigint,
+	filtered_frames: bigint,
+};
+
+/**
+ *  Payload of `link://lost_in_flight`, raised when the heartbeat times out
+ *  while the vehicle was last known to be armed.
+ */
+export type LinkLostInFlight = {
+	latitude_deg: number | null,
+	longitude_deg: number | null,
+	altitude_msl_m: number | null,
+	since_last_packet_ms: bigint,
+	failsafe: GcsFailsafeBehavior,
+};
+
+/**
+ *  How telemetry is sourced from the vehicle. `high_latency` works from
+ *  HIGH_LATENCY2 reports instead of full-rate streams (Iridium, LTE relays).
+ */
+export type LinkProfile = "normal" | "high_latency";
+
+/**  Link health once a second, kept across reconnects for bug reports. */
+export type LinkQualitySample = {
+	unix_msec: bigint,
+	connected: boolean,
+	drop_pct: number | null,
+	rc_link: RcLinkQuality | null,
+	frame_stats: LinkFrameStats | null,
+};
+
+/**  A (system_id, component_id) pair on the link. */
+export type LinkSource = {
+	system_id: number,
+	component_id: number,
+};
+
+/**  A source observed on the link and how many messages it has sent. */
+export type LinkSourceEntry = {
+	system_id: number,
+	component_id: number,
+	messages: bigint,
+};
+
+/**
+ *  Replacements for the built-in timeouts of each profile; a profile left
+ *  unset keeps its defaults.
+ */
+export type LinkTimeoutOverrides = LinkTimeoutOverrides_Serialize | LinkTimeoutOverrides_Deserialize;
+
+/**
+ *  Replacements for the built-in timeouts of each profile; a profile left
+ *  unset keeps its defaults.
+ */
+export type LinkTimeoutOverrides_Deserialize = {
+	udp?: LinkTimeouts | null,
+	tcp?: LinkTimeouts | null,
+	serial?: LinkTimeouts | null,
+	ble?: LinkTimeouts | null,
+	spp?: LinkTimeouts | null,
+};
+
+/**
+ *  Replacements for the built-in timeouts of each profile; a profile left
+ *  unset keeps its defaults.
+ */
+export type LinkTimeoutOverrides_Serialize = {
+	udp: LinkTimeouts | null,
+	tcp: LinkTimeouts | null,
+	serial: LinkTimeouts | null,
+	ble: LinkTimeouts | null,
+	spp: LinkTimeouts | null,
+};
+
+/**
+ *  Timeout profile a link gets from its transport: `ble` and `spp` links are
+ *  slow enough that UDP-tuned timeouts give up on ACKs that are still coming,
+ *  and `tcp` links do not lose frames, so resending sooner gains nothing.
+ */
+export type LinkTimeoutProfile = "udp" | "tcp" | "serial" | "ble" | "spp";
+
+/**  Command, mission and parameter timeouts for one link. */
+export type LinkTimeouts = {
+	command_ack_ms: number,
+	command_attempts: number,
+	command_completion_ms: number,
+	transfer_ms: number,
+};
+
+/**  Whether the local HTTP API is serving, and where. */
+export type LocalApiStatus = {
+	running: boolean,
+	address: string | null,
+	stop_on_disconnect: boolean,
+};
+
+/**
+ *  New entries read from the end of a followed tlog, emitted on
+ *  `log://appended`. The last event of a follow has `following: false`.
+ */
+export type LogAppended = {
+	log_id: string,
+	new_entries: bigint,
+	total_entries: bigint,
+	following: boolean,
+};
+
+export type LogCatalogMigrationError = { kind: "missing_schema_version" } | { kind: "unsupported_schema_version"; schema_version: bigint; supported_schema_version: number } | { kind: "invalid_catalog"; message: string };
+
+/**  One open log's points in a `log_query_compare` result. */
+export type LogCompareSeries = {
+	log_id: string,
+	file_name: string,
+	points: ChartPoint[],
+};
+
+export type LogDiagnostic = {
+	severity: LogDiagnosticSeverity,
+	source: LogDiagnosticSource,
+	code: string,
 	message: string,
 	recoverable: boolean,
 	timestamp_usec: bigint | null,
@@ -632,7 +1558,31 @@ export type LogExportResult = {
 	diagnostics: LogDiagnostic[],
 };
 
-export type LogFormat = "tlog" | "bin";
+/**
+ *  One issue `log_analyze` detected in the open log. The time range uses the
+ *  log's own microsecond timestamps, so it can be passed straight to
+ *  `log_query` to chart the evidence.
+ */
+export type LogFinding = {
+	category: LogFindingCategory,
+	severity: LogFindingSeverity,
+	start_usec: bigint,
+	end_usec: bigint,
+	explanation: string,
+	metrics: LogFindingMetric[],
+};
+
+export type LogFindingCategory = "vibration" | "ekf" | "battery" | "gps" | "rc_failsafe" | "thrust_loss" | "yaw_imbalance";
+
+export type LogFindingMetric = {
+	name: string,
+	value: number | null,
+	unit: string | null,
+};
+
+export type LogFindingSeverity = "info" | "warning" | "critical";
+
+export type LogFormat = "tlog" | "bin" | "ulog";
 
 export type LogFormatAdapter = {
 	format: LogFormat,
@@ -700,6 +1650,28 @@ export type LogOperationProgress = {
 	message: string | null,
 };
 
+/**  A parameter's last value in a log. */
+export type LogParam = {
+	name: string,
+	value: number | null,
+};
+
+/**
+ *  A text message recorded in a log. `severity` follows MAV_SEVERITY, which
+ *  ULog levels share.
+ */
+export type LogStatusText = {
+	timestamp_usec: bigint,
+	severity: number,
+	text: string,
+};
+
+/**  A change of VTOL or landed state in a log. */
+export type LogVtolEvent = {
+	timestamp_usec: bigint,
+	status: VtolStatus,
+};
+
 /**  Typed mission command API item used by plan serialization and validation. */
 export type LoiterDirection = "clockwise" | "counter_clockwise";
 
@@ -725,6 +1697,69 @@ export type MagCalReport = {
 /**  Status of a magnetometer calibration. */
 export type MagCalStatus = "not_started" | "waiting_to_start" | "running_step_one" | "running_step_two" | "success" | "failed" | "bad_orientation" | "bad_radius";
 
+export type MapBounds = {
+	south_deg: number | null,
+	west_deg: number | null,
+	north_deg: number | null,
+	east_deg: number | null,
+};
+
+/**
+ *  What to cover with tiles: the route of a mission plus a corridor either
+ *  side of it, or a fixed box.
+ */
+export type MapPrefetchArea = MapPrefetchArea_Serialize | MapPrefetchArea_Deserialize;
+
+/**
+ *  What to cover with tiles: the route of a mission plus a corridor either
+ *  side of it, or a fixed box.
+ */
+export type MapPrefetchArea_Deserialize = ({ kind: "mission"; plan: MissionPlan; corridor_m?: number | null }) & { bounds?: never } | ({ kind: "bbox"; bounds: MapBounds }) & { corridor_m?: never; plan?: never };
+
+/**
+ *  What to cover with tiles: the route of a mission plus a corridor either
+ *  side of it, or a fixed box.
+ */
+export type MapPrefetchArea_Serialize = ({ kind: "mission"; plan: MissionPlan; corridor_m: number | null }) & { bounds?: never } | ({ kind: "bbox"; bounds: MapBounds }) & { corridor_m?: never; plan?: never };
+
+/**
+ *  Sent on `map://prefetch_progress` as tiles complete, and returned by
+ *  `map_prefetch` when the run ends.
+ */
+export type MapPrefetchProgress = {
+	total_tiles: number,
+	downloaded: number,
+	skipped: number,
+	failed: number,
+	bytes: bigint,
+	cancelled: boolean,
+};
+
+export type MapPrefetchRequest = MapPrefetchRequest_Serialize | MapPrefetchRequest_Deserialize;
+
+export type MapPrefetchRequest_Deserialize = {
+	area: MapPrefetchArea_Deserialize,
+	min_zoom: number,
+	max_zoom: number,
+	tile_url_template: string,
+	dest_dir: string,
+};
+
+export type MapPrefetchRequest_Serialize = {
+	area: MapPrefetchArea_Serialize,
+	min_zoom: number,
+	max_zoom: number,
+	tile_url_template: string,
+	dest_dir: string,
+};
+
+/**
+ *  The MAV_RESULT a vehicle refused a command with. MAVKit's rejections and
+ *  COMMAND_ACKs IronWing reads itself both map onto this, so the frontend
+ *  sees one spelling whichever path sent the command.
+ */
+export type MavResultKind = "temporarily_rejected" | "denied" | "unsupported" | "failed" | "cancelled" | "unknown";
+
 export type MessageIntervalRequest = {
 	message_id: number,
 	interval_usec: number,
@@ -748,6 +1783,24 @@ export type MissionDownload = {
 	home: HomePosition | null,
 };
 
+/**
+ *  The edited plan, re-sequenced with DO_JUMP targets following the items
+ *  they pointed at.
+ */
+export type MissionEditResult = {
+	plan: MissionPlan,
+	warnings: MissionEditWarning[],
+};
+
+/**
+ *  An item an edit left unchanged or that needs a second look, by its index
+ *  in the returned plan.
+ */
+export type MissionEditWarning = {
+	seq: number,
+	message: string,
+};
+
 /**  Typed mission command API item used by plan serialization and validation. */
 export type MissionFrame = "global" | "global_relative_alt" | "global_terrain_alt" | "mission" | { other: number };
 
@@ -778,6 +1831,56 @@ export type MissionState = {
 	active_op: MissionOperationKind | null,
 };
 
+/**
+ *  MAVKit's transfer progress with a rate, ETA and stall flag. The last event
+ *  of a transfer carries its `outcome`.
+ */
+export type MissionTransferProgress = MissionTransferProgress_Serialize | MissionTransferProgress_Deserialize;
+
+/**
+ *  MAVKit's transfer progress with a rate, ETA and stall flag. The last event
+ *  of a transfer carries its `outcome`.
+ */
+export type MissionTransferProgress_Deserialize = (TransferProgress) & {
+	items_per_sec?: number | null,
+	eta_secs?: number | null,
+	stalled?: boolean,
+	outcome?: TransferOutcome | null,
+	failure_reason?: string | null,
+};
+
+/**
+ *  MAVKit's transfer progress with a rate, ETA and stall flag. The last event
+ *  of a transfer carries its `outcome`.
+ */
+export type MissionTransferProgress_Serialize = (TransferProgress) & {
+	items_per_sec?: number | null,
+	eta_secs?: number | null,
+	stalled: boolean,
+	outcome?: TransferOutcome | null,
+	failure_reason?: string | null,
+};
+
+/**  MAVLink mission storage type. */
+export type MissionType = "mission" | "fence" | "rally";
+
+/**
+ *  Latest value of one NAMED_VALUE_FLOAT, NAMED_VALUE_INT or DEBUG_VECT
+ *  stream, emitted as `custom://named_value`. `values` holds one entry for
+ *  the scalar messages and x, y, z for DEBUG_VECT.
+ */
+export type NamedValue = {
+	name: string,
+	kind: NamedValueKind,
+	values: (number | null)[],
+	system_id: number,
+	component_id: number,
+	time_boot_ms: bigint,
+	timestamp_usec: bigint,
+};
+
+export type NamedValueKind = "float" | "int" | "debug_vect";
+
 /**  Typed mission command API item used by plan serialization and validation. */
 export type NavAltitudeWait = {
 	altitude_m: number | null,
@@ -916,10 +2019,27 @@ export type NavWaypoint = {
 	yaw_deg: number | null,
 };
 
-export type OpenSessionSnapshot = {
+export type OpenSessionSnapshot = OpenSessionSnapshot_Serialize | OpenSessionSnapshot_Deserialize;
+
+export type OpenSessionSnapshot_Deserialize = {
+	envelope: SessionEnvelope,
+	session: DomainValue<SessionSnapshot>,
+	telemetry: DomainValue<TelemetryState_Deserialize>,
+	mission_state: MissionState | null,
+	param_store: ParamStore | null,
+	param_progress: ParamOperationProgress | null,
+	support: DomainValue<SupportState>,
+	sensor_health: DomainValue<SensorHealthSummary>,
+	calibration: DomainValue<CalibrationState>,
+	guided: DomainValue<GuidedState>,
+	status_text: DomainValue<StatusTextState_Deserialize>,
+	playback: PlaybackSnapshot,
+};
+
+export type OpenSessionSnapshot_Serialize = {
 	envelope: SessionEnvelope,
 	session: DomainValue<SessionSnapshot>,
-	telemetry: DomainValue<TelemetryState>,
+	telemetry: DomainValue<TelemetryState_Serialize>,
 	mission_state: MissionState | null,
 	param_store: ParamStore | null,
 	param_progress: ParamOperationProgress | null,
@@ -927,7 +2047,7 @@ export type OpenSessionSnapshot = {
 	sensor_health: DomainValue<SensorHealthSummary>,
 	calibration: DomainValue<CalibrationState>,
 	guided: DomainValue<GuidedState>,
-	status_text: DomainValue<StatusTextState>,
+	status_text: DomainValue<StatusTextState_Serialize>,
 	playback: PlaybackSnapshot,
 };
 
@@ -936,7 +2056,49 @@ export type OperationFailure = {
 	reason: Reason,
 };
 
-export type OperationId = "open_session_snapshot" | "ack_session_snapshot" | "arm_vehicle" | "disarm_vehicle" | "set_flight_mode" | "vehicle_takeoff" | "start_guided_session" | "update_guided_session" | "stop_guided_session" | "set_message_rate" | "mission_upload" | "mission_download" | "mission_clear" | "mission_cancel" | "fence_upload" | "fence_download" | "fence_clear" | "rally_upload" | "rally_download" | "rally_clear" | "mission_set_current" | "calibrate_accel" | "calibrate_gyro" | "param_download_all" | "param_write" | "param_write_batch" | "param_cancel" | "reboot_vehicle" | "motor_test" | "set_servo" | "rc_override" | "calibrate_compass_start" | "calibrate_compass_accept" | "calibrate_compass_cancel" | "request_prearm_checks" | "log_open" | "log_library_list" | "log_library_register" | "log_library_relink" | "log_library_remove" | "log_library_reindex" | "log_library_cancel" | "log_raw_messages_query" | "log_chart_series_query" | "log_export" | "replay_open" | "replay_play" | "replay_pause" | "replay_seek" | "replay_set_speed" | "replay_stop" | "recording_start" | "recording_stop" | "recording_status" | "recording_settings_read" | "recording_settings_write" | "firmware_install_update" | "firmware_bootloader_installation";
+export type OperationId = "open_session_snapshot" | "ack_session_snapshot" | "arm_vehicle" | "disarm_vehicle" | "set_flight_mode" | "vehicle_takeoff" | "start_guided_session" | "update_guided_session" | "stop_guided_session" | "set_message_rate" | "mission_upload" | "mission_download" | "mission_clear" | "mission_cancel" | "fence_upload" | "fence_download" | "fence_clear" | "rally_upload" | "rally_download" | "rally_clear" | "mission_set_current" | "calibrate_accel" | "calibrate_gyro" | "param_download_all" | "param_write" | "param_write_batch" | "param_cancel" | "reboot_vehicle" | "motor_test" | "set_servo" | "rc_override" | "calibrate_compass_start" | "calibrate_compass_accept" | "calibrate_compass_cancel" | "request_prearm_checks" | "log_open" | "log_library_list" | "log_library_register" | "log_library_relink" | "log_library_remove" | "log_library_reindex" | "log_library_cancel" | "log_raw_messages_query" | "log_chart_series_query" | "log_export" | "replay_open" | "replay_play" | "replay_pause" | "replay_seek" | "replay_set_speed" | "replay_stop" | "recording_start" | "recording_stop" | "recording_status" | "recording_settings_read" | "recording_settings_write" | "firmware_install_update" | "firmware_bootloader_installation" | "camera_trigger" | "gimbal_set_angles" | "set_link_profile" | "tunnel_send" | "vehicle_orbit" | "vehicle_orbit_stop" | "takeoff_guided" | "vehicle_guided_goto_ex" | "param_commit_to_storage" | "frame_apply" | "flightmode_assign" | "send_command_long" | "send_raw_message" | "gcs_position_stream_start" | "script_upload" | "script_delete" | "firmware_stage" | "param_external_change" | "gripper_control" | "winch_control" | "rc_override_start" | "rc_override_set" | "rc_override_stop" | "set_flight_mode_by_name" | "disconnect_link_ex" | "home_moved" | "connect_link" | "param_set_bits" | "vehicle_guided_goto_terrain" | "guided_queue_start" | "plane_takeoff" | "plane_loiter_here" | "plane_set_cruise_throttle" | "vtol_transition" | "rover_hold" | "rover_set_reverse";
+
+/**
+ *  Flow-sensor quality and the flow turned into ground velocities with the
+ *  body rotation taken out.
+ */
+export type OpticalFlowQuality = {
+	sensor_id: number,
+	quality_pct: number | null,
+	flow_comp_x_mps: number | null,
+	flow_comp_y_mps: number | null,
+	ground_distance_m: number | null,
+};
+
+export type OrbitDirection = "cw" | "ccw";
+
+/**
+ *  Circle `latitude_deg`/`longitude_deg` at `radius_m`, flying at
+ *  `velocity_mps` and holding `altitude_msl_m`.
+ */
+export type OrbitRequest = {
+	latitude_deg: number | null,
+	longitude_deg: number | null,
+	radius_m: number | null,
+	velocity_mps: number | null,
+	direction: OrbitDirection,
+	altitude_msl_m: number | null,
+};
+
+export type OrbitResult = {
+	strategy: OrbitStrategy,
+	do_orbit_error: string | null,
+	param_results: ParamWriteResult[],
+};
+
+export type OrbitStrategy =
+/**  The autopilot accepted MAV_CMD_DO_ORBIT. */
+"do_orbit" |
+/**
+ *  ArduCopter Circle mode, entered from the edge of the circle with
+ *  CIRCLE_RADIUS and CIRCLE_RATE set for the request.
+ */
+"circle_mode";
 
 /**  Typed mission command API item used by plan serialization and validation. */
 export type ParachuteAction = "disable" | "enable" | "release";
@@ -949,6 +2111,91 @@ export type Param = {
 	index: number,
 };
 
+export type ParamBitLabel = {
+	bit: number,
+	label: string,
+};
+
+/**  One bit of a bitmask parameter as returned by `param_get_bits`. */
+export type ParamBitState = {
+	bit: number,
+	label: string,
+	set: boolean,
+};
+
+export type ParamExportFormat =
+/**  `NAME,VALUE` lines as written by Mission Planner. */
+"mission_planner" |
+/**  Tab-separated QGroundControl `.params` file with sysid/compid columns. */
+"qgc";
+
+export type ParamExportOptions = ParamExportOptions_Serialize | ParamExportOptions_Deserialize;
+
+export type ParamExportOptions_Deserialize = {
+	names?: string[] | null,
+	modified_only?: boolean,
+	include_comments?: boolean,
+	format?: ParamExportFormat,
+	metadata?: { [key in string]: ParamMetadata_Deserialize },
+};
+
+export type ParamExportOptions_Serialize = {
+	names: string[] | null,
+	modified_only: boolean,
+	include_comments: boolean,
+	format: ParamExportFormat,
+	metadata: { [key in string]: ParamMetadata_Serialize },
+};
+
+/**
+ *  A parameter changed by someone other than this GCS (another GCS, a Lua
+ *  script, the autopilot itself). Payload of `param://external_change`.
+ */
+export type ParamExternalChange = {
+	name: string,
+	old_value: number | null,
+	new_value: number | null,
+	timestamp_unix_ms: bigint,
+};
+
+/**  One parameter read from a file by `param_parse_file_ex`. */
+export type ParamFileEntry = {
+	name: string,
+	value: number | null,
+	param_type: ParamType | null,
+	line: number,
+};
+
+/**  A row of a parameter file that was skipped. */
+export type ParamFileWarning = {
+	line: number,
+	text: string,
+	message: string,
+};
+
+/**  Per-parameter metadata the frontend already holds (ArduPilot `apm.pdef.xml`). */
+export type ParamMetadata = ParamMetadata_Serialize | ParamMetadata_Deserialize;
+
+/**  Per-parameter metadata the frontend already holds (ArduPilot `apm.pdef.xml`). */
+export type ParamMetadata_Deserialize = {
+	description?: string | null,
+	units?: string | null,
+	min?: number | null,
+	max?: number | null,
+	default_value?: number | null,
+	bitmask?: ParamBitLabel[] | null,
+};
+
+/**  Per-parameter metadata the frontend already holds (ArduPilot `apm.pdef.xml`). */
+export type ParamMetadata_Serialize = {
+	description: string | null,
+	units: string | null,
+	min: number | null,
+	max: number | null,
+	default_value: number | null,
+	bitmask: ParamBitLabel[] | null,
+};
+
 /**  Lifecycle phases for one parameter operation handle. */
 export type ParamOperationProgress = ({ downloading: {
 	received: number,
@@ -959,22 +2206,209 @@ export type ParamOperationProgress = ({ downloading: {
 	name: string,
 } }) & { downloading?: never } | "completed" | "failed" | "cancelled";
 
+export type ParamSearchOptions = ParamSearchOptions_Serialize | ParamSearchOptions_Deserialize;
+
+export type ParamSearchOptions_Deserialize = {
+	prefix_only?: boolean,
+	include_metadata_text?: boolean,
+	modified_only?: boolean,
+	limit?: bigint,
+};
+
+export type ParamSearchOptions_Serialize = {
+	prefix_only: boolean,
+	include_metadata_text: boolean,
+	modified_only: boolean,
+	limit: bigint,
+};
+
 /**  In-memory store of all downloaded vehicle parameters. */
 export type ParamStore = {
 	params: { [key in string]: Param },
 	expected_count: number,
 };
 
+/**
+ *  Incremental `param://delta` payload.
+ *
+ *  `generation` increases by one per emission within a connection. A `full`
+ *  delta replaces the frontend's store; otherwise `changed` is merged into it
+ *  and the `removed` names are dropped from it. `received`/`total` mirror the
+ *  store's downloaded and expected counts.
+ */
+export type ParamStoreDelta = {
+	generation: bigint,
+	full: boolean,
+	received: number,
+	total: number,
+	changed: Param[],
+	removed: string[],
+};
+
+/**  A parameter opened for live tuning by `param_tune_session`. */
+export type ParamTuneSession = {
+	name: string,
+	original_value: number | null,
+};
+
+/**  What a tune session did, returned by `param_tune_end`. */
+export type ParamTuneSummary = ParamTuneSummary_Serialize | ParamTuneSummary_Deserialize;
+
+/**  What a tune session did, returned by `param_tune_end`. */
+export type ParamTuneSummary_Deserialize = {
+	name: string,
+	original_value: number | null,
+	value: number | null,
+	updates: number,
+	writes: number,
+	last_error?: string | null,
+	persisted?: boolean | null,
+	persist_error?: string | null,
+};
+
+/**  What a tune session did, returned by `param_tune_end`. */
+export type ParamTuneSummary_Serialize = {
+	name: string,
+	original_value: number | null,
+	value: number | null,
+	updates: number,
+	writes: number,
+	last_error?: string | null,
+	persisted?: boolean | null,
+	persist_error?: string | null,
+};
+
 /**  MAVLink parameter value type. */
 export type ParamType = "uint8" | "int8" | "uint16" | "int16" | "uint32" | "int32" | "real32";
 
-export type PlaybackSeekResult = {
-	envelope: SessionEnvelope,
-	cursor_usec: bigint | null,
-};
+/**
+ *  A parameter write plus the outcome of the storage commit that followed
+ *  it, when one was sent.
+ */
+export type ParamWriteOutcome = ParamWriteOutcome_Serialize | ParamWriteOutcome_Deserialize;
 
-export type PlaybackSnapshot = {
-	cursor_usec: bigint | null,
+/**
+ *  A parameter write plus the outcome of the storage commit that followed
+ *  it, when one was sent.
+ */
+export type ParamWriteOutcome_Deserialize = (ParamWriteResult) & {
+	persisted?: boolean | null,
+	persist_error?: string | null,
+};
+
+/**
+ *  A parameter write plus the outcome of the storage commit that followed
+ *  it, when one was sent.
+ */
+export type ParamWriteOutcome_Serialize = (ParamWriteResult) & {
+	persisted?: boolean | null,
+	persist_error?: string | null,
+};
+
+/**  Result of a single parameter write, with requested and confirmed values. */
+export type ParamWriteResult = {
+	name: string,
+	requested_value: number | null,
+	confirmed_value: number | null,
+	success: boolean,
+};
+
+/**
+ *  Result of `param_parse_file_ex`: the rows that parsed, in file order,
+ *  and why the others did not.
+ */
+export type ParsedParamFile = {
+	format: ParamExportFormat,
+	params: ParamFileEntry[],
+	warnings: ParamFileWarning[],
+};
+
+/**
+ *  A connection asked for from outside the app, waiting for the frontend
+ *  to run the connect flow. Returned by `pending_connect_take`.
+ */
+export type PendingConnect = PendingConnect_Serialize | PendingConnect_Deserialize;
+
+/**  Where a connection handed to the app at launch came from. */
+export type PendingConnectSource =
+/**  `--connect <address>`. */
+"command_line" |
+/**  An `ironwing://connect?address=...` link. */
+"deep_link";
+
+/**
+ *  A connection asked for from outside the app, waiting for the frontend
+ *  to run the connect flow. Returned by `pending_connect_take`.
+ */
+export type PendingConnect_Deserialize = ({ status: "ready"; source: PendingConnectSource; input: string; request: ConnectRequest_Deserialize; auto_connect: boolean }) & { message?: never } |
+/**  The address could not be used; shown to the user instead. */
+({ status: "invalid"; source: PendingConnectSource; input: string; message: string }) & { auto_connect?: never; request?: never };
+
+/**
+ *  A connection asked for from outside the app, waiting for the frontend
+ *  to run the connect flow. Returned by `pending_connect_take`.
+ */
+export type PendingConnect_Serialize = ({ status: "ready"; source: PendingConnectSource; input: string; request: ConnectRequest_Serialize; auto_connect: boolean }) & { message?: never } |
+/**  The address could not be used; shown to the user instead. */
+({ status: "invalid"; source: PendingConnectSource; input: string; message: string }) & { auto_connect?: never; request?: never };
+
+export type PlaneCruiseThrottleResult = {
+	throttle_pct: number | null,
+	warnings: string[],
+};
+
+export type PlaneLaunch = {
+	kind: PlaneLaunchKind,
+	min_acceleration_mss: number | null,
+	min_ground_speed_mps: number | null,
+	throttle_delay_s: number | null,
+	instructions: string,
+	warnings: string[],
+};
+
+/**  How a fixed-wing expects to be launched, read from its TKOFF_* parameters. */
+export type PlaneLaunchKind =
+/**  The motor starts on takeoff and the plane rolls down a runway. */
+"runway" |
+/**  The motor waits for the acceleration of a throw. */
+"hand_launch" |
+/**  The motor waits until a catapult or bungee has the plane moving. */
+"catapult";
+
+/**  Where `plane_loiter_here` sent the plane. */
+export type PlaneLoiterResult = {
+	latitude_deg: number | null,
+	longitude_deg: number | null,
+	altitude_msl_m: number | null,
+	radius_m: number | null,
+	turns: number | null,
+	return_mode: string | null,
+};
+
+export type PlaneTakeoffResult = {
+	launch: PlaneLaunch,
+	altitude_m: number | null,
+	armed: boolean,
+};
+
+/**
+ *  Cursor progress of a running playback, emitted as
+ *  `log://playback_position` at a fixed low rate.
+ */
+export type PlaybackPosition = {
+	cursor_usec: bigint,
+	start_usec: bigint,
+	end_usec: bigint,
+	speed: number | null,
+};
+
+export type PlaybackSeekResult = {
+	envelope: SessionEnvelope,
+	cursor_usec: bigint | null,
+};
+
+export type PlaybackSnapshot = {
+	cursor_usec: bigint | null,
 };
 
 export type PlaybackState = {
@@ -1003,6 +2437,68 @@ export type PortInfo = {
 	location: string | null,
 };
 
+/**  One finding of `preflight_position_check`, with the numbers behind it. */
+export type PositionIssue = {
+	kind: PositionIssueKind,
+	message: string,
+	measured_m: number | null,
+	threshold_m: number | null,
+};
+
+export type PositionIssueKind =
+/**  The vehicle has not reported a position, so nothing could be compared. */
+"position_unknown" |
+/**
+ *  The vehicle is further from this device than a preflight check
+ *  plausibly allows.
+ */
+"phone_distance" |
+/**  Home is far from where the disarmed vehicle sits, e.g. a stale origin. */
+"home_offset" |
+/**  Reported altitude disagrees with the terrain elevation underneath. */
+"terrain_mismatch";
+
+/**
+ *  Whether a landing target has been seen recently; the "target acquired"
+ *  indicator before committing to a precision landing.
+ */
+export type PrecisionLandingStatus = {
+	target_acquired: boolean,
+	last_seen_ms: bigint | null,
+	last_target: PrecisionLandingTarget | null,
+};
+
+/**
+ *  A LANDING_TARGET report: where the precision-landing sensor sees the
+ *  target, as angles off the camera axis.
+ */
+export type PrecisionLandingTarget = {
+	target_num: number,
+	angle_x_rad: number | null,
+	angle_y_rad: number | null,
+	distance_m: number | null,
+	valid: boolean,
+	position_valid: boolean,
+};
+
+/**
+ *  Where a DISTANCE_SENSOR looks, from its MAV_SENSOR_ORIENTATION. Yaw
+ *  rotations are named by the direction they face; anything else is `other`.
+ */
+export type RangefinderOrientation = "forward" | "forward_right" | "right" | "back_right" | "back" | "back_left" | "left" | "forward_left" | "up" | "down" | "other";
+
+/**  One DISTANCE_SENSOR report, in metres. */
+export type RangefinderReading = {
+	sensor_id: number,
+	orientation: RangefinderOrientation,
+	orientation_id: number,
+	distance_m: number | null,
+	min_distance_m: number | null,
+	max_distance_m: number | null,
+	signal_quality_pct: number | null,
+	valid: boolean,
+};
+
 export type RawMessageFieldFilter = {
 	field: string,
 	value_text: string | null,
@@ -1054,6 +2550,21 @@ export type RawMissionCommand = {
 	z: number | null,
 };
 
+/**
+ *  RC receiver link health normalized to percent. Each source reports
+ *  only some of the figures.
+ */
+export type RcLinkQuality = {
+	rssi_pct: number | null,
+	lq_pct: number | null,
+	source: RcLinkSource,
+};
+
+/**  Which message an RC link quality was read from. */
+export type RcLinkSource = "rc_channels" |
+/**  A telemetry radio that also carries the RC uplink. */
+"radio_status" | "radio_rc_channels" | "radio_link_stats";
+
 export type RcOverrideChannelValueWire = { kind: "ignore" } | { kind: "release" } | { kind: "pwm"; pwm_us: number };
 
 export type RcOverrideChannelWire = {
@@ -1088,9 +2599,20 @@ export type RecordingSettingsResult = {
 	settings: RecordingSettings,
 };
 
-export type RecordingStartRequest = {
+export type RecordingStartRequest = RecordingStartRequest_Serialize | RecordingStartRequest_Deserialize;
+
+export type RecordingStartRequest_Deserialize = {
+	destination_path: string,
+	mode: RecordingMode,
+	rotation?: TlogRotation_Deserialize | null,
+	fsync?: TlogFsyncPolicy,
+};
+
+export type RecordingStartRequest_Serialize = {
 	destination_path: string,
 	mode: RecordingMode,
+	rotation: TlogRotation_Serialize | null,
+	fsync: TlogFsyncPolicy,
 };
 
 export type RecordingStatus = { kind: "idle" } | { kind: "recording"; operation_id: OperationId; mode: RecordingMode; file_name: string; destination_path: string; bytes_written: bigint; started_at_unix_msec: bigint } | { kind: "stopping"; operation_id: OperationId; file_name: string; destination_path: string; bytes_written: bigint } | { kind: "failed"; failure: OperationFailure };
@@ -1110,6 +2632,35 @@ export type ReferencedLogFile = {
 
 export type ReplayStatus = "idle" | "loading" | "ready" | "playing" | "paused" | "seeking" | "ended" | "error";
 
+/**  A point on the predicted return path, altitude relative to home. */
+export type RtlPoint = {
+	latitude_deg: number | null,
+	longitude_deg: number | null,
+	relative_alt_m: number | null,
+};
+
+/**
+ *  What the vehicle is expected to do on RTL from where it is now.
+ *
+ *  `warnings` lists what the preview had to assume: parameters that are not
+ *  downloaded yet (their firmware defaults are used) or an unknown position
+ *  or home, which leaves `segments` empty.
+ */
+export type RtlPreview = {
+	segments: RtlSegment[],
+	description: string,
+	warnings: string[],
+};
+
+export type RtlSegment = {
+	kind: RtlSegmentKind,
+	from: RtlPoint,
+	to: RtlPoint,
+	duration_s: number | null,
+};
+
+export type RtlSegmentKind = "climb" | "return" | "descend" | "loiter" | "land";
+
 export type RuntimeCapabilities = RuntimeCapabilities_Serialize | RuntimeCapabilities_Deserialize;
 
 export type RuntimeCapabilities_Deserialize = {
@@ -1130,6 +2681,56 @@ export type RuntimeCapabilities_Serialize = {
 	parameter_transfer: Capability,
 };
 
+export type SafetyGate = "gps_fix" | "satellites" | "hdop" | "home_set";
+
+/**
+ *  GCS-side checks run before `arm_vehicle` and `vehicle_takeoff` send
+ *  anything, on top of the autopilot's own pre-arm checks. Every gate is off
+ *  by default.
+ */
+export type SafetyGateConfig = SafetyGateConfig_Serialize | SafetyGateConfig_Deserialize;
+
+/**
+ *  GCS-side checks run before `arm_vehicle` and `vehicle_takeoff` send
+ *  anything, on top of the autopilot's own pre-arm checks. Every gate is off
+ *  by default.
+ */
+export type SafetyGateConfig_Deserialize = {
+	min_fix?: GpsFix | null,
+	min_satellites?: number | null,
+	max_hdop?: number | null,
+	require_home?: boolean,
+};
+
+/**
+ *  GCS-side checks run before `arm_vehicle` and `vehicle_takeoff` send
+ *  anything, on top of the autopilot's own pre-arm checks. Every gate is off
+ *  by default.
+ */
+export type SafetyGateConfig_Serialize = {
+	min_fix: GpsFix | null,
+	min_satellites: number | null,
+	max_hdop: number | null,
+	require_home: boolean,
+};
+
+/**
+ *  A gate that did not pass, with the value it saw (`None` when the vehicle
+ *  has not reported one) and the value it asked for.
+ */
+export type SafetyGateFailure = {
+	gate: SafetyGate,
+	message: string,
+	current: string | null,
+	required: string,
+};
+
+/**  A Lua script in the vehicle's scripts directory. */
+export type ScriptFile = {
+	name: string,
+	size_bytes: bigint,
+};
+
 /**  Health state of one sensor family derived from MAVLink bitmasks. */
 export type SensorHealthState = "not_present" | "disabled" | "unhealthy" | "healthy";
 
@@ -1165,6 +2766,13 @@ export type SerialFlashPhase = "idle" | "probing" | "erasing" | "programming" |
 
 export type SerialFlashSource = { kind: "catalog_url"; url: string } | { kind: "local_apj_bytes"; data: number[] };
 
+export type SerialFlowControl = "none" |
+/**
+ *  RTS/CTS; some FTDI-based telemetry radios drop bytes at high rates
+ *  without it.
+ */
+"hardware";
+
 /**
  *  Terminal result of a serial flash flow execution.
  *  Distinguishes verified from unverified outcomes explicitly.
@@ -1187,6 +2795,8 @@ export type SerialFlowResult =
 /**  Board lacks sufficient external-flash capacity for the firmware artifact. */
 { result: "extf_capacity_insufficient"; reason: string };
 
+export type SerialParity = "none" | "odd" | "even";
+
 export type SerialPreflightInfo = {
 	vehicle_connected: boolean,
 	param_count: number,
@@ -1218,13 +2828,114 @@ export type SerialValidation = {
 	baud_required: boolean,
 };
 
-export type SessionConnection = { kind: "connecting" } | { kind: "connected" } | { kind: "disconnected" } | { kind: "error"; error: string };
+export type SessionArtifact = "tlog" | "flight_summary" | "status_text" | "params" | "track_geojson" | "track_kml";
+
+export type SessionArtifactEntry = {
+	artifact: SessionArtifact,
+	status: SessionArtifactStatus,
+	file_name: string | null,
+	bytes: bigint,
+	note: string | null,
+};
+
+export type SessionArtifactStatus = "included" |
+/**  Requested, but the session has nothing to put in it. */
+"missing" |
+/**  Switched off in `SessionExportOptions`. */
+"skipped";
+
+export type SessionConnection = { kind: "connecting"; phase: LinkConnectPhase | null } | { kind: "connected" } | { kind: "disconnected" } | { kind: "error"; error: string };
 
 export type SessionEnvelope = {
 	session_id: string,
 	source_kind: SourceKind,
 	seek_epoch: bigint,
 	reset_revision: bigint,
+	system_id?: number | null,
+};
+
+/**  `manifest.json` at the root of the archive. */
+export type SessionExportManifest = {
+	app_version: string,
+	created_unix_msec: bigint,
+	artifacts: SessionArtifactEntry[],
+};
+
+/**
+ *  Which artifacts `session_export` should gather. Every artifact is
+ *  included unless switched off.
+ */
+export type SessionExportOptions = SessionExportOptions_Serialize | SessionExportOptions_Deserialize;
+
+/**
+ *  Which artifacts `session_export` should gather. Every artifact is
+ *  included unless switched off.
+ */
+export type SessionExportOptions_Deserialize = {
+	tlog?: boolean,
+	flight_summary?: boolean,
+	status_text?: boolean,
+	params?: boolean,
+	track_geojson?: boolean,
+	track_kml?: boolean,
+};
+
+/**
+ *  Which artifacts `session_export` should gather. Every artifact is
+ *  included unless switched off.
+ */
+export type SessionExportOptions_Serialize = {
+	tlog: boolean,
+	flight_summary: boolean,
+	status_text: boolean,
+	params: boolean,
+	track_geojson: boolean,
+	track_kml: boolean,
+};
+
+/**
+ *  Payload of `session_export://progress`, sent as each artifact is
+ *  written.
+ */
+export type SessionExportProgress = {
+	artifact: SessionArtifact,
+	index: number,
+	total: number,
+	status: SessionArtifactStatus,
+};
+
+/**
+ *  The connection a previous run had open when it was killed, for offering a
+ *  one-tap reconnect. `request` can be passed back to `connect_link` as is.
+ */
+export type SessionRecoverInfo = SessionRecoverInfo_Serialize | SessionRecoverInfo_Deserialize;
+
+/**
+ *  The connection a previous run had open when it was killed, for offering a
+ *  one-tap reconnect. `request` can be passed back to `connect_link` as is.
+ */
+export type SessionRecoverInfo_Deserialize = {
+	request: ConnectRequest_Deserialize,
+	connected_at_unix_msec: bigint,
+	updated_at_unix_msec: bigint,
+	armed: boolean,
+	last_latitude_deg?: number | null,
+	last_longitude_deg?: number | null,
+	recording_paths?: string[],
+};
+
+/**
+ *  The connection a previous run had open when it was killed, for offering a
+ *  one-tap reconnect. `request` can be passed back to `connect_link` as is.
+ */
+export type SessionRecoverInfo_Serialize = {
+	request: ConnectRequest_Serialize,
+	connected_at_unix_msec: bigint,
+	updated_at_unix_msec: bigint,
+	armed: boolean,
+	last_latitude_deg?: number | null,
+	last_longitude_deg?: number | null,
+	recording_paths: string[],
 };
 
 export type SessionSnapshot = {
@@ -1236,24 +2947,179 @@ export type SessionSnapshot = {
 
 export type SessionStatus = "pending" | "active";
 
+/**
+ *  Backend settings that survive a restart. Saved to the settings file in the
+ *  app data directory; `schema_version` drives migrations when the shape
+ *  changes.
+ */
+export type Settings = Settings_Serialize | Settings_Deserialize;
+
+/**  A partial settings update; unset fields keep their current value. */
+export type SettingsPatch = SettingsPatch_Serialize | SettingsPatch_Deserialize;
+
+/**  A partial settings update; unset fields keep their current value. */
+export type SettingsPatch_Deserialize = {
+	telemetry_rate_hz?: number | null,
+	units?: UnitSystem | null,
+	alert_rules?: AlertRule_Deserialize[] | null,
+	auto_connect_usb?: boolean | null,
+	auto_connect_usb_baud?: number | null,
+	link_buffer_frames?: number | null,
+	takeoff_max_alt_m?: number | null,
+	expected_failsafe_check?: boolean | null,
+	max_open_logs?: number | null,
+	track_max_points?: number | null,
+	safety_gates?: SafetyGateConfig_Deserialize | null,
+	checklist?: ChecklistConfig_Deserialize | null,
+	expert_mode?: boolean | null,
+	landing_assist?: LandingAssistProfile_Deserialize | null,
+	winch_max_rate_mps?: number | null,
+	health_thresholds?: HealthThresholds | null,
+	fence_warning_margin_m?: number | null,
+	rc_link_on_telemetry_radio?: boolean | null,
+	link_timeouts?: LinkTimeoutOverrides_Deserialize | null,
+	auto_connect_launch_link?: boolean | null,
+	announcements?: AnnouncementSettings_Deserialize | null,
+	recording_rotation?: TlogRotation_Deserialize | null,
+	recording_fsync?: TlogFsyncPolicy | null,
+	telemetry_stale_after_ms?: number | null,
+};
+
+/**  A partial settings update; unset fields keep their current value. */
+export type SettingsPatch_Serialize = {
+	telemetry_rate_hz: number | null,
+	units: UnitSystem | null,
+	alert_rules: AlertRule_Serialize[] | null,
+	auto_connect_usb: boolean | null,
+	auto_connect_usb_baud: number | null,
+	link_buffer_frames: number | null,
+	takeoff_max_alt_m: number | null,
+	expected_failsafe_check: boolean | null,
+	max_open_logs: number | null,
+	track_max_points: number | null,
+	safety_gates: SafetyGateConfig_Serialize | null,
+	checklist: ChecklistConfig_Serialize | null,
+	expert_mode: boolean | null,
+	landing_assist: LandingAssistProfile_Serialize | null,
+	winch_max_rate_mps: number | null,
+	health_thresholds: HealthThresholds | null,
+	fence_warning_margin_m: number | null,
+	rc_link_on_telemetry_radio: boolean | null,
+	link_timeouts: LinkTimeoutOverrides_Serialize | null,
+	auto_connect_launch_link: boolean | null,
+	announcements: AnnouncementSettings_Serialize | null,
+	recording_rotation: TlogRotation_Serialize | null,
+	recording_fsync: TlogFsyncPolicy | null,
+	telemetry_stale_after_ms: number | null,
+};
+
+/**
+ *  Backend settings that survive a restart. Saved to the settings file in the
+ *  app data directory; `schema_version` drives migrations when the shape
+ *  changes.
+ */
+export type Settings_Deserialize = {
+	schema_version?: number,
+	telemetry_rate_hz?: number,
+	units?: UnitSystem,
+	alert_rules?: AlertRule_Deserialize[],
+	auto_connect_usb?: boolean,
+	auto_connect_usb_baud?: number,
+	link_buffer_frames?: number,
+	takeoff_max_alt_m?: number | null,
+	expected_failsafe_check?: boolean,
+	max_open_logs?: number,
+	track_max_points?: number,
+	safety_gates?: SafetyGateConfig_Deserialize,
+	checklist?: ChecklistConfig_Deserialize,
+	expert_mode?: boolean,
+	landing_assist?: LandingAssistProfile_Deserialize,
+	winch_max_rate_mps?: number | null,
+	health_thresholds?: HealthThresholds,
+	fence_warning_margin_m?: number | null,
+	rc_link_on_telemetry_radio?: boolean,
+	link_timeouts?: LinkTimeoutOverrides_Deserialize,
+	auto_connect_launch_link?: boolean,
+	announcements?: AnnouncementSettings_Deserialize,
+	recording_rotation?: TlogRotation_Deserialize,
+	recording_fsync?: TlogFsyncPolicy,
+	telemetry_stale_after_ms?: number,
+};
+
+/**
+ *  Backend settings that survive a restart. Saved to the settings file in the
+ *  app data directory; `schema_version` drives migrations when the shape
+ *  changes.
+ */
+export type Settings_Serialize = {
+	schema_version: number,
+	telemetry_rate_hz: number,
+	units: UnitSystem,
+	alert_rules: AlertRule_Serialize[],
+	auto_connect_usb: boolean,
+	auto_connect_usb_baud: number,
+	link_buffer_frames: number,
+	takeoff_max_alt_m: number | null,
+	expected_failsafe_check: boolean,
+	max_open_logs: number,
+	track_max_points: number,
+	safety_gates: SafetyGateConfig_Serialize,
+	checklist: ChecklistConfig_Serialize,
+	expert_mode: boolean,
+	landing_assist: LandingAssistProfile_Serialize,
+	winch_max_rate_mps: number | null,
+	health_thresholds: HealthThresholds,
+	fence_warning_margin_m: number | null,
+	rc_link_on_telemetry_radio: boolean,
+	link_timeouts: LinkTimeoutOverrides_Serialize,
+	auto_connect_launch_link: boolean,
+	announcements: AnnouncementSettings_Serialize,
+	recording_rotation: TlogRotation_Serialize,
+	recording_fsync: TlogFsyncPolicy,
+	telemetry_stale_after_ms: number,
+};
+
 export type SourceKind = "live" | "playback";
 
 /**  Typed mission command API item used by plan serialization and validation. */
 export type SpeedType = "airspeed" | "groundspeed";
 
+export type SpeedUnit = "ms" | "kmh" | "mph" | "kn";
+
 export type StartGuidedSessionRequest = {
 	session: GuidedSession,
 };
 
-export type StatusTextEntry = {
+export type StatusTextEntry = StatusTextEntry_Serialize | StatusTextEntry_Deserialize;
+
+export type StatusTextEntry_Deserialize = {
 	sequence: bigint,
 	text: string,
 	severity: string,
 	timestamp_usec: bigint | null,
+	repeat_count?: number,
+	vehicle_time?: VehicleTime | null,
+	failsafe?: boolean,
 };
 
-export type StatusTextState = {
-	entries: StatusTextEntry[],
+export type StatusTextEntry_Serialize = {
+	sequence: bigint,
+	text: string,
+	severity: string,
+	timestamp_usec: bigint | null,
+	repeat_count: number,
+	vehicle_time: VehicleTime | null,
+	failsafe: boolean,
+};
+
+export type StatusTextState = StatusTextState_Serialize | StatusTextState_Deserialize;
+
+export type StatusTextState_Deserialize = {
+	entries: StatusTextEntry_Deserialize[],
+};
+
+export type StatusTextState_Serialize = {
+	entries: StatusTextEntry_Serialize[],
 };
 
 export type SupportState = {
@@ -1266,6 +3132,14 @@ export type SupportState = {
 /**  Freshness marker for cached domain state. */
 export type SyncState = "unknown" | "current" | "possibly_stale";
 
+/**
+ *  Sent on `system://resumed` once per wake-up of the host, after the live
+ *  state has been re-emitted.
+ */
+export type SystemResumed = {
+	suspended_ms: bigint,
+};
+
 /**
  *  Vehicle lifecycle stage reported in HEARTBEAT `system_status`.
  *
@@ -1275,6 +3149,62 @@ export type SyncState = "unknown" | "current" | "possibly_stale";
  */
 export type SystemStatus = "unknown" | "boot" | "calibrating" | "standby" | "active" | "critical" | "emergency" | "poweroff";
 
+export type TakeoffGuidedRequest = TakeoffGuidedRequest_Serialize | TakeoffGuidedRequest_Deserialize;
+
+export type TakeoffGuidedRequest_Deserialize = {
+	altitude_m: number | null,
+	allow_arm?: boolean,
+	override_gates?: boolean,
+};
+
+export type TakeoffGuidedRequest_Serialize = {
+	altitude_m: number | null,
+	allow_arm: boolean,
+	override_gates: boolean,
+};
+
+/**  Outcome of each step in order; the sequence stops at the first failure. */
+export type TakeoffReport = {
+	steps: TakeoffStepReport[],
+	success: boolean,
+};
+
+export type TakeoffStep = "set_guided" | "arm" | "takeoff";
+
+export type TakeoffStepReport = {
+	step: TakeoffStep,
+	status: TakeoffStepStatus,
+	message: string | null,
+};
+
+export type TakeoffStepStatus = "done" |
+/**  Already satisfied, so nothing was sent. */
+"skipped" | "failed";
+
+/**  One row of the task table in `diagnostics`. */
+export type TaskDiagnostic = {
+	name: string,
+	state: TaskState,
+	restarts: number,
+	last_beat_age_ms: bigint,
+	detail: string | null,
+};
+
+/**  Payload of `internal://task_stalled`. */
+export type TaskStalled = {
+	component: string,
+	state: TaskState,
+	detail: string | null,
+	restarting: boolean,
+};
+
+/**  Where a watched internal task stands. */
+export type TaskState = "running" |
+/**  Alive, but its heartbeat is overdue. */
+"stalled" | "panicked" |
+/**  Returned while the session it serves was still up. */
+"exited";
+
 export type TcpValidation = {
 	address_required: boolean,
 };
@@ -1285,6 +3215,65 @@ export type TelemetryAttitude = {
 	yaw_deg: number | null,
 };
 
+/**
+ *  Payload of `telemetry://delta`. A keyframe carries every field and
+ *  replaces whatever the receiver held; other frames carry the fields that
+ *  moved past their epsilon since they were last sent. `seq` counts frames
+ *  so a receiver can tell it missed one and ask for a keyframe.
+ */
+export type TelemetryDelta = {
+	seq: bigint,
+	keyframe: boolean,
+	fields: { [key in string]: unknown },
+};
+
+/**
+ *  Values computed by the backend rather than sent by the autopilot. A value
+ *  that cannot be computed (e.g. wind without an airspeed sensor) is `None`.
+ */
+export type TelemetryDerived = TelemetryDerived_Serialize | TelemetryDerived_Deserialize;
+
+/**
+ *  Values computed by the backend rather than sent by the autopilot. A value
+ *  that cannot be computed (e.g. wind without an airspeed sensor) is `None`.
+ */
+export type TelemetryDerived_Deserialize = {
+	wind_speed_mps: number | null,
+	wind_direction_deg: number | null,
+	wind_source: WindSource | null,
+	home_distance_m: number | null,
+	home_bearing_deg: number | null,
+	glide_ratio: number | null,
+	energy_wh_per_km: number | null,
+	rangefinder_height_m?: number | null,
+	rc_link?: RcLinkQuality | null,
+	odometer_m?: number | null,
+};
+
+/**
+ *  Values computed by the backend rather than sent by the autopilot. A value
+ *  that cannot be computed (e.g. wind without an airspeed sensor) is `None`.
+ */
+export type TelemetryDerived_Serialize = {
+	wind_speed_mps: number | null,
+	wind_direction_deg: number | null,
+	wind_source: WindSource | null,
+	home_distance_m: number | null,
+	home_bearing_deg: number | null,
+	glide_ratio: number | null,
+	energy_wh_per_km: number | null,
+	rangefinder_height_m: number | null,
+	rc_link: RcLinkQuality | null,
+	odometer_m: number | null,
+};
+
+/**  How `telemetry://state` ticks reach the webview. */
+export type TelemetryEncoding =
+/**  A whole `TelemetrySnapshot` on `telemetry://state` every tick. */
+"full" |
+/**  Changed fields only, under short keys, on `telemetry://delta`. */
+"delta";
+
 export type TelemetryFlight = {
 	altitude_m: number | null,
 	speed_mps: number | null,
@@ -1293,12 +3282,36 @@ export type TelemetryFlight = {
 	airspeed_mps: number | null,
 };
 
+/**
+ *  Per-group freshness for live telemetry. Playback snapshots leave every
+ *  group at its default (no timestamp, not stale).
+ */
+export type TelemetryFreshness = {
+	flight: TelemetryGroupFreshness,
+	navigation: TelemetryGroupFreshness,
+	attitude: TelemetryGroupFreshness,
+	power: TelemetryGroupFreshness,
+	gps: TelemetryGroupFreshness,
+	terrain: TelemetryGroupFreshness,
+	radio: TelemetryGroupFreshness,
+};
+
 export type TelemetryGps = {
 	fix_type: string | null,
 	satellites: bigint | null,
 	hdop: number | null,
 };
 
+/**
+ *  When one telemetry group last received data, as Unix-epoch microseconds,
+ *  and whether that is older than the staleness threshold. A group that has
+ *  never received data on the current link is stale.
+ */
+export type TelemetryGroupFreshness = {
+	last_update_usec: bigint | null,
+	stale: boolean,
+};
+
 export type TelemetryNavigation = {
 	latitude_deg: number | null,
 	longitude_deg: number | null,
@@ -1324,7 +3337,22 @@ export type TelemetryRadio = {
 	servo_outputs: (number | null)[] | null,
 };
 
-export type TelemetryState = {
+export type TelemetryState = TelemetryState_Serialize | TelemetryState_Deserialize;
+
+export type TelemetryState_Deserialize = {
+	flight: TelemetryFlight,
+	navigation: TelemetryNavigation,
+	attitude: TelemetryAttitude,
+	power: TelemetryPower,
+	gps: TelemetryGps,
+	terrain: TelemetryTerrain,
+	radio: TelemetryRadio,
+	freshness?: TelemetryFreshness,
+	derived?: TelemetryDerived_Deserialize,
+	display?: { [key in string]: DisplayValue } | null,
+};
+
+export type TelemetryState_Serialize = {
 	flight: TelemetryFlight,
 	navigation: TelemetryNavigation,
 	attitude: TelemetryAttitude,
@@ -1332,6 +3360,9 @@ export type TelemetryState = {
 	gps: TelemetryGps,
 	terrain: TelemetryTerrain,
 	radio: TelemetryRadio,
+	freshness: TelemetryFreshness,
+	derived: TelemetryDerived_Serialize,
+	display?: { [key in string]: DisplayValue } | null,
 };
 
 export type TelemetryTerrain = {
@@ -1339,16 +3370,183 @@ export type TelemetryTerrain = {
 	height_above_terrain_m: number | null,
 };
 
+export type TemperatureUnit = "c" | "f";
+
+export type TerrainGotoResult = TerrainGotoResult_Serialize | TerrainGotoResult_Deserialize;
+
+export type TerrainGotoResult_Deserialize = {
+	strategy: TerrainGotoStrategy,
+	ground_elevation_m: number | null,
+	altitude_m: number | null,
+	goto: GotoResult,
+	warnings?: string[],
+};
+
+export type TerrainGotoResult_Serialize = {
+	strategy: TerrainGotoStrategy,
+	ground_elevation_m: number | null,
+	altitude_m: number | null,
+	goto: GotoResult,
+	warnings: string[],
+};
+
+/**  How `vehicle_guided_goto_terrain` held the requested height above ground. */
+export type TerrainGotoStrategy =
+/**  Sent in the terrain frame; the autopilot follows the ground en route. */
+"terrain_frame" |
+/**
+ *  Sent as the AMSL altitude that puts the vehicle at the requested
+ *  height over the target only.
+ */
+"amsl_equivalent";
+
+/**  State of the vehicle clock estimate built from TIMESYNC and SYSTEM_TIME. */
+export type TimeSyncStatus = {
+	offset_us: bigint | null,
+	rtt_us: bigint | null,
+	utc_available: boolean,
+};
+
+/**
+ *  When recorded frames are forced to storage. Syncing more often survives
+ *  power loss better at the cost of flash wear.
+ */
+export type TlogFsyncPolicy =
+/**  Leave write-back to the operating system. */
+"os_default" | "per_second" | "per_write";
+
+/**  Start a new tlog segment once the current one spans either limit. */
+export type TlogRotation = TlogRotation_Serialize | TlogRotation_Deserialize;
+
+/**  Start a new tlog segment once the current one spans either limit. */
+export type TlogRotation_Deserialize = {
+	max_segment_minutes?: number | null,
+	max_segment_mb?: number | null,
+};
+
+/**  Start a new tlog segment once the current one spans either limit. */
+export type TlogRotation_Serialize = {
+	max_segment_minutes: number | null,
+	max_segment_mb: number | null,
+};
+
+/**
+ *  One GLOBAL_POSITION_INT sample of the live vehicle's breadcrumb trail,
+ *  stamped with the ground station's clock when it arrived.
+ */
+export type TrackPoint = {
+	timestamp_usec: bigint,
+	latitude_deg: number | null,
+	longitude_deg: number | null,
+	altitude_msl_m: number | null,
+	relative_alt_m: number | null,
+	heading_deg: number | null,
+};
+
+/**  Direction of a mission transfer operation. */
+export type TransferDirection = "upload" | "download";
+
+/**  How a mission, fence or rally transfer ended. */
+export type TransferOutcome = "completed" | "failed" | "cancelled";
+
+/**  Current phase of a mission transfer state machine. */
+export type TransferPhase = "idle" | "request_count" | "transfer_items" | "await_ack" | "completed" | "failed" | "cancelled";
+
+/**  Snapshot of mission transfer progress. */
+export type TransferProgress = {
+	direction: TransferDirection,
+	mission_type: MissionType,
+	phase: TransferPhase,
+	completed_items: number,
+	total_items: number,
+	retries_used: number,
+};
+
 export type TransportDescriptor = TransportDescriptor_Serialize | TransportDescriptor_Deserialize;
 
-export type TransportDescriptor_Deserialize = ({ kind: "udp"; label: string; available: boolean; discovery_error: string | null; validation: UdpValidation }) & { default_baud?: never; profile?: never } | ({ kind: "tcp"; label: string; available: boolean; discovery_error: string | null; validation: TcpValidation }) & { default_baud?: never; profile?: never } | ({ kind: "serial"; label: string; available: boolean; discovery_error: string | null; validation: SerialValidation; default_baud: number }) & { profile?: never } | ({ kind: "bluetooth_ble"; label: string; available: boolean; discovery_error: string | null; validation: AddressValidation; profile: BluetoothProfile }) & { default_baud?: never } | ({ kind: "bluetooth_spp"; label: string; available: boolean; discovery_error: string | null; validation: AddressValidation }) & { default_baud?: never; profile?: never } | ({ kind: "websocket"; label: string; available: boolean; discovery_error: string | null; validation: UrlValidation }) & { default_baud?: never; profile?: never } | ({ kind: "web_serial"; label: string; available: boolean; discovery_error: string | null; validation: WebSerialValidation; default_baud: number }) & { profile?: never } | ({ kind: "web_bluetooth"; label: string; available: boolean; discovery_error: string | null; validation: WebBluetoothValidation; profile: BluetoothProfile }) & { default_baud?: never } | ({ kind: "demo"; label: string; available: boolean; discovery_error: string | null; validation: DemoValidation }) & { default_baud?: never; profile?: never };
+export type TransportDescriptor_Deserialize = ({ kind: "udp"; label: string; available: boolean; discovery_error: string | null; validation: UdpValidation }) & { default_baud?: never; profile?: never } | ({ kind: "tcp"; label: string; available: boolean; discovery_error: string | null; validation: TcpValidation }) & { default_baud?: never; profile?: never } | ({ kind: "serial"; label: string; available: boolean; discovery_error: string | null; validation: SerialValidation; default_baud: number }) & { profile?: never } | ({ kind: "bluetooth_ble"; label: string; available: boolean; discovery_error: string | null; validation: AddressValidation; profile: BluetoothProfile }) & { default_baud?: never } | ({ kind: "bluetooth_spp"; label: string; available: boolean; discovery_error: string | null; validation: AddressValidation }) & { default_baud?: never; profile?: never } | ({ kind: "usb_serial"; label: string; available: boolean; discovery_error: string | null; validation: UsbSerialValidation; default_baud: number }) & { profile?: never } | ({ kind: "websocket"; label: string; available: boolean; discovery_error: string | null; validation: UrlValidation }) & { default_baud?: never; profile?: never } | ({ kind: "web_serial"; label: string; available: boolean; discovery_error: string | null; validation: WebSerialValidation; default_baud: number }) & { profile?: never } | ({ kind: "web_bluetooth"; label: string; available: boolean; discovery_error: string | null; validation: WebBluetoothValidation; profile: BluetoothProfile }) & { default_baud?: never } | ({ kind: "demo"; label: string; available: boolean; discovery_error: string | null; validation: DemoValidation }) & { default_baud?: never; profile?: never };
+
+export type TransportDescriptor_Serialize = ({ kind: "udp"; label: string; available: boolean; discovery_error?: string | null; validation: UdpValidation }) & { default_baud?: never; profile?: never } | ({ kind: "tcp"; label: string; available: boolean; discovery_error?: string | null; validation: TcpValidation }) & { default_baud?: never; profile?: never } | ({ kind: "serial"; label: string; available: boolean; discovery_error?: string | null; validation: SerialValidation; default_baud: number }) & { profile?: never } | ({ kind: "bluetooth_ble"; label: string; available: boolean; discovery_error?: string | null; validation: AddressValidation; profile: BluetoothProfile }) & { default_baud?: never } | ({ kind: "bluetooth_spp"; label: string; available: boolean; discovery_error?: string | null; validation: AddressValidation }) & { default_baud?: never; profile?: never } | ({ kind: "usb_serial"; label: string; available: boolean; discovery_error?: string | null; validation: UsbSerialValidation; default_baud: number }) & { profile?: never } | ({ kind: "websocket"; label: string; available: boolean; discovery_error?: string | null; validation: UrlValidation }) & { default_baud?: never; profile?: never } | ({ kind: "web_serial"; label: string; available: boolean; discovery_error?: string | null; validation: WebSerialValidation; default_baud: number }) & { profile?: never } | ({ kind: "web_bluetooth"; label: string; available: boolean; discovery_error?: string | null; validation: WebBluetoothValidation; profile: BluetoothProfile }) & { default_baud?: never } | ({ kind: "demo"; label: string; available: boolean; discovery_error?: string | null; validation: DemoValidation }) & { default_baud?: never; profile?: never };
 
-export type TransportDescriptor_Serialize = ({ kind: "udp"; label: string; available: boolean; discovery_error?: string | null; validation: UdpValidation }) & { default_baud?: never; profile?: never } | ({ kind: "tcp"; label: string; available: boolean; discovery_error?: string | null; validation: TcpValidation }) & { default_baud?: never; profile?: never } | ({ kind: "serial"; label: string; available: boolean; discovery_error?: string | null; validation: SerialValidation; default_baud: number }) & { profile?: never } | ({ kind: "bluetooth_ble"; label: string; available: boolean; discovery_error?: string | null; validation: AddressValidation; profile: BluetoothProfile }) & { default_baud?: never } | ({ kind: "bluetooth_spp"; label: string; available: boolean; discovery_error?: string | null; validation: AddressValidation }) & { default_baud?: never; profile?: never } | ({ kind: "websocket"; label: string; available: boolean; discovery_error?: string | null; validation: UrlValidation }) & { default_baud?: never; profile?: never } | ({ kind: "web_serial"; label: string; available: boolean; discovery_error?: string | null; validation: WebSerialValidation; default_baud: number }) & { profile?: never } | ({ kind: "web_bluetooth"; label: string; available: boolean; discovery_error?: string | null; validation: WebBluetoothValidation; profile: BluetoothProfile }) & { default_baud?: never } | ({ kind: "demo"; label: string; available: boolean; discovery_error?: string | null; validation: DemoValidation }) & { default_baud?: never; profile?: never };
+/**  A bridged transport that kept failing writes and was torn down. */
+export type TransportLost = {
+	transport: string,
+	error: string,
+	stats: TransportStats,
+};
+
+/**  How the outgoing drain of a bridged link has fared. */
+export type TransportStats = {
+	chunks_sent: bigint,
+	write_failures: bigint,
+	write_retries: bigint,
+	dropped_messages: bigint,
+};
+
+/**  Body axis a tuning capture records. */
+export type TuneAxis = "roll" | "pitch" | "yaw";
+
+/**
+ *  Step-response analysis of a `tune_capture_start`/`tune_capture_stop`
+ *  window.
+ */
+export type TuneReport = {
+	axis: TuneAxis,
+	source: TuneTargetSource | null,
+	started_unix_msec: bigint,
+	duration_s: number | null,
+	samples: number,
+	steps: TuneStep[],
+};
+
+/**  Response to one step in the target. Times are seconds from the step. */
+export type TuneStep = {
+	start_s: number | null,
+	from: number | null,
+	to: number | null,
+	rise_time_s: number | null,
+	overshoot_pct: number | null,
+	settling_time_s: number | null,
+	oscillation_hz: number | null,
+};
+
+/**  What the response was measured against. */
+export type TuneTargetSource =
+/**  ATTITUDE_TARGET body rates against ATTITUDE rates, in deg/s. */
+"attitude_target" |
+/**
+ *  NAV_CONTROLLER_OUTPUT desired roll/pitch against ATTITUDE angles, in
+ *  degrees. Used when the autopilot does not stream ATTITUDE_TARGET.
+ */
+"nav_controller_output";
 
 export type UdpValidation = {
 	bind_addr_required: boolean,
 };
 
+/**
+ *  Display units for telemetry and log queries. The default is SI, in which
+ *  case payloads are emitted unchanged.
+ */
+export type UnitSystem = {
+	distance: DistanceUnit,
+	speed: SpeedUnit,
+	temperature: TemperatureUnit,
+};
+
+/**
+ *  A message outside the dialect, emitted on `mavlink://unknown` at most
+ *  once a second for each source and message id.
+ */
+export type UnknownMessage = {
+	system_id: number,
+	component_id: number,
+	message_id: number,
+	payload: number[],
+	count: bigint,
+	rate_hz: number | null,
+};
+
 export type UpdateGuidedSessionRequest = {
 	session: GuidedSession,
 };
@@ -1357,6 +3555,60 @@ export type UrlValidation = {
 	url_required: boolean,
 };
 
+/**  A USB serial adapter attached through OTG, as reported by Android. */
+export type UsbSerialDevice = UsbSerialDevice_Serialize | UsbSerialDevice_Deserialize;
+
+/**  A USB serial adapter attached through OTG, as reported by Android. */
+export type UsbSerialDevice_Deserialize = {
+	device_id: string,
+	vendor_id: number,
+	product_id: number,
+	manufacturer?: string | null,
+	product?: string | null,
+	has_permission: boolean,
+};
+
+/**  A USB serial adapter attached through OTG, as reported by Android. */
+export type UsbSerialDevice_Serialize = {
+	device_id: string,
+	vendor_id: number,
+	product_id: number,
+	manufacturer?: string | null,
+	product?: string | null,
+	has_permission: boolean,
+};
+
+export type UsbSerialValidation = {
+	device_required: boolean,
+	baud_required: boolean,
+};
+
+/**
+ *  One MAVLink system heard on the active link.
+ *
+ *  `mav_type`, `autopilot` and `system_status` carry the raw HEARTBEAT enum
+ *  values so systems that never become a full `Vehicle` can still be listed.
+ */
+export type VehicleListEntry = {
+	system_id: number,
+	component_id: number,
+	mav_type: number,
+	autopilot: number,
+	system_status: number,
+	armed: boolean,
+	custom_mode: number,
+	last_seen_ms_ago: bigint,
+	selected: boolean,
+};
+
+/**
+ *  Sent on `vehicle://selected` once the live link is bound to the system
+ *  `vehicle_select` asked for.
+ */
+export type VehicleSelected = {
+	system_id: number,
+};
+
 export type VehicleState = {
 	armed: boolean,
 	custom_mode: number,
@@ -1370,9 +3622,61 @@ export type VehicleState = {
 	heartbeat_received: boolean,
 };
 
+/**  A GCS timestamp translated to the vehicle's clocks. */
+export type VehicleTime = {
+	time_boot_us: bigint,
+	utc_usec: bigint | null,
+};
+
 /**  MAVLink vehicle airframe type. */
 export type VehicleType = "unknown" | "fixed_wing" | "vtol" | "quadrotor" | "hexarotor" | "octorotor" | "tricopter" | "helicopter" | "coaxial" | "ground_rover" | "submarine" | "generic";
 
+/**
+ *  One stream advertised through VIDEO_STREAM_INFORMATION, emitted as part of
+ *  `video://streams`. IronWing only hands `uri` to a player; it never decodes
+ *  the video itself.
+ */
+export type VideoStream = {
+	component_id: number,
+	stream_id: number,
+	count: number,
+	name: string,
+	uri: string,
+	stream_type: string,
+	resolution_h: number,
+	resolution_v: number,
+	bitrate_bps: number,
+	rotation_deg: number,
+	framerate_hz: number | null,
+};
+
+/**
+ *  A computed field `log_query` adds to each point, e.g.
+ *  `{ name: "ground_speed", expr: "sqrt(vx^2 + vy^2)" }`. The expression may
+ *  only reference fields of the queried message type.
+ */
+export type VirtualField = {
+	name: string,
+	expr: string,
+};
+
+/**  MAV_VTOL_STATE from EXTENDED_SYS_STATE. */
+export type VtolState =
+/**  Not a VTOL, or the autopilot does not say. */
+"undefined" | "transition_to_fixed_wing" | "transition_to_multi_copter" | "multi_copter" | "fixed_wing";
+
+/**
+ *  Payload of `vtol_status` and `vehicle://vtol`, sent when either state
+ *  changes.
+ */
+export type VtolStatus = {
+	vtol_state: VtolState,
+	landed_state: LandedState,
+};
+
+/**  Flight configuration `vtol_transition` asks for. */
+export type VtolTransitionTarget = "multi_copter" | "fixed_wing";
+
 export type WebBluetoothValidation = {
 	chooser_required: boolean,
 };
@@ -1385,6 +3689,40 @@ export type WebSerialValidation = {
 /**  Typed mission command API item used by plan serialization and validation. */
 export type WinchAction = "relax" | "length_control" | "rate_control";
 
+/**
+ *  MAV_CMD_DO_WINCH action. Rates are in m/s; a negative length or rate
+ *  winds the line in.
+ */
+export type WinchCommand =
+/**  Let the line go slack. */
+{ kind: "relaxed" } |
+/**  Pay out (or wind in) `length_m` of line at `rate_ms`. */
+{ kind: "relative_length_control"; length_m: number | null; rate_ms: number | null } |
+/**  Run the winch at `rate_ms` until told otherwise. */
+{ kind: "rate_control"; rate_ms: number | null };
+
+/**  A WINCH_STATUS report. */
+export type WinchStatus = {
+	line_length_m: number | null,
+	speed_mps: number | null,
+	tension_kg: number | null,
+	voltage_v: number | null,
+	current_a: number | null,
+	temperature_c: number | null,
+	healthy: boolean,
+	fully_retracted: boolean,
+	moving: boolean,
+	clutch_engaged: boolean,
+	locked: boolean,
+	status_flags: number,
+};
+
+export type WindSource =
+/**  Reported by the autopilot in WIND_COV. */
+"reported" |
+/**  Estimated from the airspeed/heading versus groundspeed/track triangle. */
+"estimated";
+
 /**  Typed mission command API item used by plan serialization and validation. */
 export type YawDirection = "clockwise" | "counter_clockwise";
 
@@ -1447,6 +3785,42 @@ export const OPERATION_IDS = [
   "recording_settings_write",
   "firmware_install_update",
   "firmware_bootloader_installation",
+  "camera_trigger",
+  "gimbal_set_angles",
+  "set_link_profile",
+  "tunnel_send",
+  "vehicle_orbit",
+  "vehicle_orbit_stop",
+  "takeoff_guided",
+  "vehicle_guided_goto_ex",
+  "param_commit_to_storage",
+  "frame_apply",
+  "flightmode_assign",
+  "send_command_long",
+  "send_raw_message",
+  "gcs_position_stream_start",
+  "script_upload",
+  "script_delete",
+  "firmware_stage",
+  "param_external_change",
+  "gripper_control",
+  "winch_control",
+  "rc_override_start",
+  "rc_override_set",
+  "rc_override_stop",
+  "set_flight_mode_by_name",
+  "disconnect_link_ex",
+  "home_moved",
+  "connect_link",
+  "param_set_bits",
+  "vehicle_guided_goto_terrain",
+  "guided_queue_start",
+  "plane_takeoff",
+  "plane_loiter_here",
+  "plane_set_cruise_throttle",
+  "vtol_transition",
+  "rover_hold",
+  "rover_set_reverse",
 ] as const;
 
 export const MESSAGE_RATE_CATALOG = [
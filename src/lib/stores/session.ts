@@ -422,7 +422,7 @@ export function createSessionStore(service: SessionService = createSessionServic
       ...state,
       lastPhase: "connect-requested",
       lastError: null,
-      optimisticConnection: { kind: "connecting" },
+      optimisticConnection: { kind: "connecting", phase: null },
       connectionRequestPhase: "connecting",
     }));
 
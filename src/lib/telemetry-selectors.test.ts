@@ -192,7 +192,8 @@ describe("telemetry selectors", () => {
         } as const;
         const cb = vi.fn();
 
-        listen.mockImplementation(async (_event, handler) => {
+        listen.mockImplementation(async (event, handler) => {
+            if (event !== "telemetry://state") return () => { };
             handler({
                 payload: {
                     envelope: { session_id: "session-1", source_kind: "live", seek_epoch: 0, reset_revision: 0 },
@@ -211,8 +212,8 @@ describe("telemetry selectors", () => {
         const cb = vi.fn();
         let handlerRef: ((event: { payload: unknown }) => void) | null = null;
 
-        listen.mockImplementation(async (_event, handler) => {
-            handlerRef = handler;
+        listen.mockImplementation(async (event, handler) => {
+            if (event === "telemetry://state") handlerRef = handler;
             return () => { };
         });
 
@@ -115,7 +115,7 @@ describe("logs bridge", () => {
       maxPoints: 3,
     });
     expect(invokeMock).toHaveBeenNthCalledWith(3, "log_get_summary");
-    expect(invokeMock).toHaveBeenNthCalledWith(4, "log_close");
+    expect(invokeMock).toHaveBeenNthCalledWith(4, "log_close", {});
     expect(invokeMock).toHaveBeenNthCalledWith(5, "log_format_adapters");
     expect(invokeMock).toHaveBeenNthCalledWith(6, "log_library_list");
     expect(invokeMock).toHaveBeenNthCalledWith(7, "log_library_list");
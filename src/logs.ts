@@ -18,11 +18,26 @@ export type LogSummary = {
   total_entries: number;
   message_types: Record<string, number>;
   log_type: LogType;
+  /** Handle for this log while it stays open; pass it to id-aware queries. */
+  log_id?: string;
+  /** Runs of sane timestamps; more than one when the recording clock jumped. */
+  segments?: LogSegment[];
+  /** The tlog ended in a partial frame, usually because it is still recording. */
+  truncated?: boolean;
+};
+
+export type LogSegment = {
+  start_usec: number;
+  end_usec: number;
+  entries: number;
 };
 
 export type LogDataPoint = {
   timestamp_usec: number;
   fields: Record<string, number>;
+  units?: Record<string, string>;
+  /** Set on the first point after a timestamp discontinuity. */
+  gap_before?: boolean;
 };
 
 export type ReferencedFileFingerprint = GeneratedJson.ReferencedFileFingerprint;
@@ -89,12 +104,14 @@ export async function queryLogMessages(
   startUsec?: number,
   endUsec?: number,
   maxPoints?: number,
+  raw?: boolean,
 ): Promise<LogDataPoint[]> {
   return typedInvoke("log_query", {
     msgType,
     startUsec: startUsec ?? null,
     endUsec: endUsec ?? null,
     maxPoints: maxPoints ?? null,
+    raw,
   });
 }
 
@@ -102,8 +119,8 @@ export async function getLogSummary(): Promise<LogSummary | null> {
   return typedInvoke("log_get_summary");
 }
 
-export async function closeLog(): Promise<void> {
-  return typedInvoke("log_close");
+export async function closeLog(logId?: string): Promise<void> {
+  return typedInvoke("log_close", { logId });
 }
 
 export async function listLogFormatAdapters(): Promise<LogFormatAdapter[]> {
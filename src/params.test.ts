@@ -0,0 +1,108 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+
+const { listenMock } = vi.hoisted(() => ({
+  listenMock: vi.fn(),
+}));
+
+vi.mock("@platform/core", () => ({
+  invoke: vi.fn(),
+}));
+
+vi.mock("@platform/event", () => ({
+  listen: listenMock,
+}));
+
+import { applyParamStoreDelta, subscribeParamStore, type Param, type ParamStore } from "./params";
+
+const envelope = { session_id: "s1", source_kind: "live", seek_epoch: 0, reset_revision: 0 };
+
+function param(name: string, value: number, index: number): Param {
+  return { name, value, param_type: "real32", index } as Param;
+}
+
+describe("param store deltas", () => {
+  beforeEach(() => {
+    listenMock.mockReset();
+  });
+
+  it("replaces the store on a full delta and merges partial ones", () => {
+    const full = applyParamStoreDelta(null, {
+      generation: 1n,
+      full: true,
+      received: 2,
+      total: 2,
+      changed: [param("A", 1, 0), param("B", 2, 1)],
+      removed: [],
+    });
+    expect(full?.params.A.value).toBe(1);
+
+    const next = applyParamStoreDelta(full, {
+      generation: 2n,
+      full: false,
+      received: 2,
+      total: 2,
+      changed: [param("B", 5, 1)],
+      removed: [],
+    });
+    expect(next?.params.A.value).toBe(1);
+    expect(next?.params.B.value).toBe(5);
+    expect(full?.params.B.value).toBe(2);
+  });
+
+  it("ignores a partial delta without a base store", () => {
+    expect(
+      applyParamStoreDelta(null, {
+        generation: 3n,
+        full: false,
+        received: 1,
+        total: 1,
+        changed: [param("A", 1, 0)],
+        removed: [],
+      }),
+    ).toBeNull();
+  });
+
+  it("drops removed params from the store", () => {
+    const full = applyParamStoreDelta(null, {
+      generation: 1n,
+      full: true,
+      received: 2,
+      total: 2,
+      changed: [param("A", 1, 0), param("B", 2, 1)],
+      removed: [],
+    });
+    const next = applyParamStoreDelta(full, {
+      generation: 2n,
+      full: false,
+      received: 1,
+      total: 1,
+      changed: [],
+      removed: ["B"],
+    });
+    expect(Object.keys(next!.params)).toEqual(["A"]);
+    expect(next?.expected_count).toBe(1);
+    expect(full?.params.B.value).toBe(2);
+  });
+
+  it("delivers the merged store for param://delta events", async () => {
+    const handlers = new Map<string, (event: { payload: unknown }) => void>();
+    listenMock.mockImplementation(async (event, handler) => {
+      handlers.set(event, handler);
+      return vi.fn();
+    });
+    const cb = vi.fn();
+
+    await subscribeParamStore(cb);
+    const store: ParamStore = { params: { A: param("A", 1, 0) }, expected_count: 1 };
+    handlers.get("param://store")!({ payload: { envelope, value: store } });
+    handlers.get("param://delta")!({
+      payload: {
+        envelope,
+        value: { generation: 1n, full: false, received: 1, total: 1, changed: [param("A", 4, 0)], removed: [] },
+      },
+    });
+
+    expect(cb).toHaveBeenCalledTimes(2);
+    expect(cb.mock.calls[1][0].value.params.A.value).toBe(4);
+  });
+});
@@ -1,7 +1,16 @@
 import { EVENT_NAMES } from "./lib/generated/events";
+import type {
+  ParamExportFormat,
+  ParamFileEntry,
+  ParamFileWarning,
+  ParamStoreDelta,
+  ParamTuneSession,
+  ParamTuneSummary,
+  ParsedParamFile,
+} from "./lib/generated/ironwing";
 import type * as GeneratedJson from "./lib/generated/mavkit-json";
 import { typedInvoke, typedListen, type UnlistenFn } from "./lib/ipc/client";
-import { createLatestScopedEventHandler } from "./lib/scoped-session-events";
+import { createLatestScopedEventHandler, scopedEnvelopeKey } from "./lib/scoped-session-events";
 import type { SessionEvent } from "./session";
 
 export type ParamType = GeneratedJson.Param["param_type"];
@@ -65,6 +74,16 @@ export function isParamTransferActive(p: ParamProgress): boolean {
 
 export type ParamWriteResult = GeneratedJson.ParamWriteResult;
 
+export type {
+  ParamExportFormat,
+  ParamFileEntry,
+  ParamFileWarning,
+  ParamStoreDelta,
+  ParamTuneSession,
+  ParamTuneSummary,
+  ParsedParamFile,
+};
+
 export async function downloadAllParams(): Promise<void> {
   return typedInvoke("param_download_all");
 }
@@ -73,26 +92,94 @@ export async function cancelParamDownload(): Promise<void> {
   return typedInvoke("param_cancel");
 }
 
-export async function writeParam(name: string, value: number): Promise<ParamWriteResult> {
-  return typedInvoke("param_write", { name, value });
+export async function writeParam(name: string, value: number, persist?: boolean): Promise<ParamWriteResult> {
+  return typedInvoke("param_write", { name, value, persist });
+}
+
+export async function writeBatchParams(params: [string, number][], persist?: boolean): Promise<ParamWriteResult[]> {
+  return typedInvoke("param_write_batch", { params, persist });
 }
 
-export async function writeBatchParams(params: [string, number][]): Promise<ParamWriteResult[]> {
-  return typedInvoke("param_write_batch", { params });
+/** Save the vehicle's parameters to EEPROM/flash; refused while armed. */
+export async function commitParamsToStorage(): Promise<void> {
+  return typedInvoke("param_commit_to_storage");
 }
 
 export async function parseParamFile(contents: string): Promise<Record<string, number>> {
   return typedInvoke("param_parse_file", { contents });
 }
 
-export async function formatParamFile(store: ParamStore): Promise<string> {
-  return typedInvoke("param_format_file", { store });
+/** Parse a Mission Planner or QGC file, keeping the type column and reporting bad lines. */
+export async function parseParamFileDetailed(contents: string): Promise<ParsedParamFile> {
+  return typedInvoke("param_parse_file_ex", { contents });
+}
+
+export async function formatParamFile(store: ParamStore, format?: ParamExportFormat): Promise<string> {
+  return typedInvoke("param_format_file", { store, format });
 }
 
+/** Open `name` for slider tuning; writes made through it are coalesced. */
+export async function startParamTuneSession(name: string): Promise<ParamTuneSession> {
+  return typedInvoke("param_tune_session", { name });
+}
+
+/** Safe to call on every slider move; only the newest value is written. */
+export async function setParamTuneValue(name: string, value: number): Promise<void> {
+  return typedInvoke("param_tune_set", { name, value });
+}
+
+export async function revertParamTune(): Promise<void> {
+  return typedInvoke("param_tune_revert");
+}
+
+export async function endParamTuneSession(commit: boolean): Promise<ParamTuneSummary> {
+  return typedInvoke("param_tune_end", { commit });
+}
+
+/**
+ * Merge a `param://delta` payload into `store`. A full delta replaces the
+ * store; a partial one needs a base store and returns null without one.
+ * Names in `removed` are dropped after the changes are applied.
+ */
+export function applyParamStoreDelta(store: ParamStore | null, delta: ParamStoreDelta): ParamStore | null {
+  if (!delta.full && !store) return null;
+  const params = delta.full ? {} : { ...store!.params };
+  for (const param of delta.changed) {
+    params[param.name] = param as Param;
+  }
+  for (const name of delta.removed) {
+    delete params[name];
+  }
+  return { params, expected_count: delta.total };
+}
+
+/**
+ * Delivers the full store on `param://store` and the store rebuilt from each
+ * `param://delta`, so subscribers always see a complete `ParamStore`.
+ */
 export async function subscribeParamStore(cb: (event: SessionEvent<ParamStore>) => void): Promise<UnlistenFn> {
   const handleEvent = createLatestScopedEventHandler(cb);
-
-  return typedListen(EVENT_NAMES.PARAM_STORE, (event) => handleEvent(event.payload));
+  let store: ParamStore | null = null;
+  let storeScope: string | null = null;
+
+  const unlistenStore = await typedListen(EVENT_NAMES.PARAM_STORE, (event) => {
+    store = event.payload.value;
+    storeScope = scopedEnvelopeKey(event.payload.envelope);
+    handleEvent(event.payload);
+  });
+  const unlistenDelta = await typedListen(EVENT_NAMES.PARAM_DELTA, (event) => {
+    const scope = scopedEnvelopeKey(event.payload.envelope);
+    const next = applyParamStoreDelta(scope === storeScope ? store : null, event.payload.value);
+    if (!next) return;
+    store = next;
+    storeScope = scope;
+    handleEvent({ envelope: event.payload.envelope, value: next });
+  });
+
+  return () => {
+    unlistenStore();
+    unlistenDelta();
+  };
 }
 
 export async function subscribeParamProgress(cb: (event: SessionEvent<ParamProgress>) => void): Promise<UnlistenFn> {
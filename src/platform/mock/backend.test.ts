@@ -1163,6 +1163,19 @@ describe("mock setup/calibration/arming backend parity", () => {
         expect(live.guided.value.status).toBe("idle");
     });
 
+    it("accepts a gate override separately from force and rejects a malformed one", async () => {
+        await invokeMockCommand("connect_link", {
+            request: { transport: { kind: "udp", bind_addr: "0.0.0.0:14550" } },
+        });
+
+        await expect(
+            invokeMockCommand("arm_vehicle", { force: false, overrideGates: "yes" }),
+        ).rejects.toThrow("arm_vehicle.overrideGates");
+        await expect(
+            invokeMockCommand("arm_vehicle", { force: false, overrideGates: true }),
+        ).resolves.toBeUndefined();
+    });
+
     it("disarms when connected", async () => {
         await invokeMockCommand("connect_link", {
             request: {
@@ -1185,7 +1198,7 @@ describe("mock setup/calibration/arming backend parity", () => {
             request: { transport: { kind: "udp", bind_addr: "0.0.0.0:14550" } },
         });
 
-        await expect(invokeMockCommand("calibrate_accel")).resolves.toBeUndefined();
+        await expect(invokeMockCommand("calibrate_accel")).resolves.toEqual({ outcome: "succeeded" });
     });
 
     it("starts gyro calibration when connected", async () => {
@@ -1193,7 +1206,7 @@ describe("mock setup/calibration/arming backend parity", () => {
             request: { transport: { kind: "udp", bind_addr: "0.0.0.0:14550" } },
         });
 
-        await expect(invokeMockCommand("calibrate_gyro")).resolves.toBeUndefined();
+        await expect(invokeMockCommand("calibrate_gyro")).resolves.toEqual({ outcome: "succeeded" });
     });
 
     it("starts compass calibration when connected", async () => {
@@ -2129,6 +2142,31 @@ describe("mock firmware backend parity", () => {
         await expect(invokeMockCommand("playback_play")).rejects.toThrow("no log open");
     });
 
+    it("starts playback at a speed and cursor and refuses while a vehicle is connected", async () => {
+        await invokeMockCommand("log_open", { path: "/mock/logs/flight-001.tlog" });
+        const playback = await invokeMockCommand<any>("open_session_snapshot", { sourceKind: "playback" });
+        await invokeMockCommand("ack_session_snapshot", {
+            sessionId: playback.envelope.session_id,
+            seekEpoch: playback.envelope.seek_epoch,
+            resetRevision: playback.envelope.reset_revision,
+        });
+
+        const playing = await invokeMockCommand<PlaybackStateSnapshot>("playback_play", {
+            speed: 4,
+            startUsec: 31000000,
+        });
+        expect(playing).toMatchObject({
+            status: "playing",
+            cursor_usec: 31000000,
+            speed: 4,
+        });
+
+        await invokeMockCommand("connect_link", {
+            request: { transport: { kind: "udp", bind_addr: "0.0.0.0:14550" } },
+        });
+        await expect(invokeMockCommand("playback_play")).rejects.toThrow("disconnect the vehicle");
+    });
+
     it("clamps playback seek cursor to seeded log bounds like native", async () => {
         await invokeMockCommand("log_open", { path: "/mock/logs/flight-001.tlog" });
         const playback = await invokeMockCommand<any>("open_session_snapshot", { sourceKind: "playback" });
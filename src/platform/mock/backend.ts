@@ -528,8 +528,8 @@ const mockPlaybackCommandHandlers = definePlatformCommandHandlers({
     emitMany(events);
     return result;
   },
-  playback_play: () => {
-    const { state, events } = playbackPlayResult();
+  playback_play: (args) => {
+    const { state, events } = playbackPlayResult(args as CommandArgs);
     emitMany(events);
     return state;
   },
@@ -734,10 +734,12 @@ const mockSetupActionCommandHandlers = definePlatformCommandHandlers({
   calibrate_accel: () => {
     ensureMockLiveWriteAllowed("calibrate_accel");
     requireConnectedVehicle();
+    return { outcome: "succeeded" };
   },
   calibrate_gyro: () => {
     ensureMockLiveWriteAllowed("calibrate_gyro");
     requireConnectedVehicle();
+    return { outcome: "succeeded" };
   },
   calibrate_compass_accept: () => {
     ensureMockLiveWriteAllowed("calibrate_compass_accept");
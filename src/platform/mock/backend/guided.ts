@@ -403,4 +403,7 @@ export function vehicleTakeoff(args: CommandArgs) {
   if (typeof altitudeM !== "number" || !Number.isFinite(altitudeM) || altitudeM <= 0) {
     throw new Error("takeoff altitude must be greater than 0 m");
   }
+  if (args?.overrideGates !== undefined && typeof args.overrideGates !== "boolean") {
+    throw new Error("missing or invalid vehicle_takeoff.overrideGates");
+  }
 }
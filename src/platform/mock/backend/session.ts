@@ -274,8 +274,21 @@ export function playbackSeekResult(args: CommandArgs): {
     };
 }
 
-export function playbackPlayResult() {
-    const envelope = requireActivePlaybackEnvelope();
+export function playbackPlayResult(args: CommandArgs) {
+    if (mockState.liveVehicleAvailable) {
+        throw new Error("disconnect the vehicle before playing a log as live telemetry");
+    }
+    let envelope = requireActivePlaybackEnvelope();
+    const speed = args?.speed;
+    if (speed !== undefined) {
+        if (typeof speed !== "number" || !Number.isFinite(speed)) {
+            throw new Error("missing or invalid playback_play.speed");
+        }
+        updateReplayStateForSpeed(speed);
+    }
+    if (args?.startUsec !== undefined) {
+        envelope = playbackSeekResult({ cursorUsec: args.startUsec }).envelope;
+    }
     updateReplayStateForPlay();
     return {
         state: playbackStateEvent(envelope).payload.value,
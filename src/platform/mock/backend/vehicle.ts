@@ -151,6 +151,9 @@ export function validateRcOverrideArgs(args: CommandArgs) {
 
 export function validateArmDisarmArgs(args: CommandArgs, cmd: "arm_vehicle" | "disarm_vehicle") {
   requireBoolean(args?.force, `${cmd}.force`);
+  if (cmd === "arm_vehicle" && args?.overrideGates !== undefined) {
+    requireBoolean(args.overrideGates, "arm_vehicle.overrideGates");
+  }
 }
 
 export function normalizedLiveVehicleState(mockVehicleState?: Partial<MockLiveVehicleState> & { modeName?: string } | null): MockLiveVehicleState {
@@ -2,13 +2,15 @@ const DEFAULT_REMOTE_BRIDGE_URL = "http://127.0.0.1:14242";
 
 export { openBrowserUrl as openUrl } from "../shared/open-url";
 
+import { isAppErrorPayload, toAppError } from "../shared/app-error";
+
 function remoteBridgeUrl() {
   return (import.meta.env.VITE_IRONWING_REMOTE_UI_URL ?? DEFAULT_REMOTE_BRIDGE_URL).replace(/\/$/, "");
 }
 
 type InvokeResponse<T> =
   | { ok: true; value: T }
-  | { ok: false; error: string };
+  | { ok: false; error: unknown };
 
 export async function invoke<T>(cmd: string, args?: Record<string, unknown>): Promise<T> {
   const response = await fetch(`${remoteBridgeUrl()}/invoke`, {
@@ -19,7 +21,10 @@ export async function invoke<T>(cmd: string, args?: Record<string, unknown>): Pr
 
   const result = await response.json() as InvokeResponse<T>;
   if (!response.ok || !result.ok) {
-    throw new Error(result.ok ? `remote invoke ${cmd} failed` : result.error);
+    if (!result.ok && isAppErrorPayload(result.error)) {
+      throw toAppError(result.error);
+    }
+    throw new Error(result.ok ? `remote invoke ${cmd} failed` : String(result.error));
   }
 
   return result.value;
@@ -0,0 +1,14 @@
+type AppErrorPayload = { code: string; message: string };
+
+export function isAppErrorPayload(error: unknown): error is AppErrorPayload {
+  return typeof error === "object"
+    && error !== null
+    && typeof (error as AppErrorPayload).code === "string"
+    && typeof (error as AppErrorPayload).message === "string";
+}
+
+// Structured backend errors arrive as plain objects; surface them as Errors so
+// callers keep reading `.message`, with the stable `code` alongside.
+export function toAppError(error: AppErrorPayload): Error & AppErrorPayload {
+  return Object.assign(new Error(error.message), error);
+}
@@ -1,3 +1,17 @@
 // Native Tauri IPC core — normal builds resolve @platform/core here
-export { invoke } from "@tauri-apps/api/core";
+import { invoke as tauriInvoke } from "@tauri-apps/api/core";
+
 export { openUrl } from "@tauri-apps/plugin-opener";
+
+import { isAppErrorPayload, toAppError } from "../shared/app-error";
+
+export async function invoke<T>(cmd: string, args?: Record<string, unknown>): Promise<T> {
+  try {
+    return await tauriInvoke<T>(cmd, args);
+  } catch (error) {
+    if (isAppErrorPayload(error)) {
+      throw toAppError(error);
+    }
+    throw error;
+  }
+}
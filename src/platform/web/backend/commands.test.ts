@@ -448,7 +448,7 @@ describe("web backend commands", () => {
     expect(wasmContractMock.paramWrite).toHaveBeenCalledWith("ARMING_CHECK", 0);
     expect(wasmContractMock.paramWriteBatch).toHaveBeenCalledWith([["BATT_CAPACITY", 5000]]);
     expect(wasmContractMock.paramParseFile).toHaveBeenCalledWith("ARMING_CHECK,0\n");
-    expect(wasmContractMock.paramFormatFile).toHaveBeenCalledWith(paramStore);
+    expect(wasmContractMock.paramFormatFile).toHaveBeenCalledWith(paramStore, undefined);
   });
 
   it("delegates mission, fence, and rally transfer commands to the wasm runtime", async () => {
@@ -124,7 +124,15 @@ export const logCommandHandlers = definePlatformCommandHandlers({
   log_get_flight_summary: async () => getFlightSummary(),
   log_export: async ({ request }) => exportLog(request),
   log_export_csv: async (args) => exportCompatCsv(args),
-  playback_play: async () => playPlayback(),
+  playback_play: async ({ speed, startUsec }) => {
+    if (speed !== undefined) {
+      await setPlaybackSpeed(speed);
+    }
+    if (startUsec !== undefined) {
+      await seekPlayback(startUsec);
+    }
+    return playPlayback();
+  },
   playback_pause: () => pausePlayback(),
   playback_seek: async ({ cursorUsec }) => seekPlayback(cursorUsec),
   playback_set_speed: async ({ speed }) => setPlaybackSpeed(speed),
@@ -14,5 +14,5 @@ export const paramCommandHandlers = definePlatformCommandHandlers({
   param_write: async ({ name, value }) => wasmParamWrite(name, value),
   param_write_batch: async ({ params }) => wasmParamWriteBatch(params),
   param_parse_file: async ({ contents }) => wasmParamParseFile(contents),
-  param_format_file: async ({ store }) => wasmParamFormatFile(store),
+  param_format_file: async ({ store, format }) => wasmParamFormatFile(store, format),
 });
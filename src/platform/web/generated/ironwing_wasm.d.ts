@@ -30,7 +30,7 @@ export class IronwingWasmRuntime {
     openSessionSnapshot(source_kind: string): any;
     paramCancel(): void;
     paramDownloadAll(): void;
-    paramFormatFile(store: any): string;
+    paramFormatFile(store: any, format: any): string;
     paramParseFile(contents: string): any;
     paramWrite(name: string, value: number): Promise<any>;
     paramWriteBatch(params: any): Promise<any>;
@@ -125,7 +125,7 @@ export interface InitOutput {
     readonly ironwingwasmruntime_openSessionSnapshot: (a: number, b: number, c: number) => [number, number, number];
     readonly ironwingwasmruntime_paramCancel: (a: number) => [number, number];
     readonly ironwingwasmruntime_paramDownloadAll: (a: number) => [number, number];
-    readonly ironwingwasmruntime_paramFormatFile: (a: number, b: any) => [number, number, number, number];
+    readonly ironwingwasmruntime_paramFormatFile: (a: number, b: any, c: any) => [number, number, number, number];
     readonly ironwingwasmruntime_paramParseFile: (a: number, b: number, c: number) => [number, number, number];
     readonly ironwingwasmruntime_paramWrite: (a: number, b: number, c: number, d: number) => any;
     readonly ironwingwasmruntime_paramWriteBatch: (a: number, b: any) => any;
@@ -75,7 +75,7 @@ export async function wasmParamDownloadAll(runtime: IronwingWasmRuntime): Promis
 }
 
 export async function wasmParamFormatFile(runtime: IronwingWasmRuntime, args: CommandArgs<"param_format_file">): Promise<CommandResult<"param_format_file">> {
-  return await runtime.paramFormatFile(args.store) as CommandResult<"param_format_file">;
+  return await runtime.paramFormatFile(args.store, args.format) as CommandResult<"param_format_file">;
 }
 
 export async function wasmParamParseFile(runtime: IronwingWasmRuntime, args: CommandArgs<"param_parse_file">): Promise<CommandResult<"param_parse_file">> {
@@ -6,7 +6,7 @@ import type { StartGuidedSessionRequest, UpdateGuidedSessionRequest, GuidedComma
 import type { ChartSeriesPage, ChartSeriesRequest, FlightSummary, LogDataPoint, LogExportRequest, LogExportResult, LogFormat, LogSummary, RawMessagePage, RawMessageQuery } from "../../logs";
 import type { FencePlan, MissionDownload, MissionIssue, RallyPlan } from "../../mission";
 import type { WireMissionPlan } from "../../lib/mavkit-types";
-import type { ParamStore, ParamWriteResult } from "../../params";
+import type { ParamExportFormat, ParamStore, ParamWriteResult } from "../../params";
 import type { FlightPathPoint, TelemetrySnapshot } from "../../playback";
 import type { MessageRateInfo } from "../../telemetry";
 import type { TransportDescriptor } from "../../transport";
@@ -222,9 +222,9 @@ export async function wasmParamParseFile(contents: string): Promise<Record<strin
   return wasmFacade.wasmParamParseFile(runtime, { contents });
 }
 
-export async function wasmParamFormatFile(store: ParamStore): Promise<string> {
+export async function wasmParamFormatFile(store: ParamStore, format?: ParamExportFormat): Promise<string> {
   const runtime = await ensureWasmRuntime();
-  return wasmFacade.wasmParamFormatFile(runtime, { store });
+  return wasmFacade.wasmParamFormatFile(runtime, { store, format });
 }
 
 export async function wasmMissionValidate(plan: WireMissionPlan): Promise<MissionIssue[]> {
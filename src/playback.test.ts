@@ -49,7 +49,7 @@ describe("playback bridge", () => {
     expect(invokeMock).toHaveBeenNthCalledWith(2, "log_get_telemetry_track", {
       maxPoints: 10,
     });
-    expect(invokeMock).toHaveBeenNthCalledWith(3, "playback_play");
+    expect(invokeMock).toHaveBeenNthCalledWith(3, "playback_play", {});
     expect(invokeMock).toHaveBeenNthCalledWith(4, "playback_pause");
     expect(invokeMock).toHaveBeenNthCalledWith(5, "playback_seek", { cursorUsec: 123456 });
     expect(invokeMock).toHaveBeenNthCalledWith(6, "playback_set_speed", { speed: 4 });
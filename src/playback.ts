@@ -73,8 +73,10 @@ export async function getLogTelemetryTrack(
   });
 }
 
-export async function playPlayback(): Promise<PlaybackStateSnapshot> {
-  return typedInvoke("playback_play");
+export async function playPlayback(
+  options: { speed?: number; startUsec?: number } = {},
+): Promise<PlaybackStateSnapshot> {
+  return typedInvoke("playback_play", options);
 }
 
 export async function pausePlayback(): Promise<PlaybackStateSnapshot> {
@@ -13,7 +13,7 @@ import type { SensorHealthDomain } from "./sensor-health";
 import type { StatusTextDomain } from "./statustext";
 import type { SupportDomain } from "./support";
 import type { ConnectRequest, DisconnectRequest } from "./transport";
-import type { VehicleState, TelemetryState } from "./telemetry";
+import { subscribeTelemetryEvents, type VehicleState, type TelemetryState } from "./telemetry";
 
 export { OPERATION_IDS };
 export type { OperationFailure, OperationId, ReasonKind, SourceKind };
@@ -84,10 +84,7 @@ export async function subscribeSessionState(
 export async function subscribeTelemetryState(
   cb: (event: SessionEvent<DomainValue<TelemetryState>>) => void,
 ): Promise<UnlistenFn> {
-  return typedListen(
-    EVENT_NAMES.TELEMETRY_STATE,
-    (event) => cb(event.payload),
-  );
+  return subscribeTelemetryEvents(cb);
 }
 
 export async function subscribeSupportState(
@@ -12,6 +12,7 @@ export type StatusMessage = {
     text: string;
     severity: string;
     timestamp_usec?: number;
+    repeat_count?: number;
 };
 
 export type StatusTextState = {
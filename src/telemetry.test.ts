@@ -0,0 +1,83 @@
+import { beforeEach, describe, expect, it, vi } from "vitest";
+
+const { listenMock } = vi.hoisted(() => ({
+  listenMock: vi.fn(),
+}));
+
+vi.mock("@platform/core", () => ({
+  invoke: vi.fn(),
+}));
+
+vi.mock("@platform/event", () => ({
+  listen: listenMock,
+}));
+
+import {
+  applyTelemetryDelta,
+  subscribeTelemetryState,
+  telemetryDeltaFields,
+  telemetryFromDeltaFields,
+  type TelemetryDomain,
+} from "./telemetry";
+
+const envelope = { session_id: "s1", source_kind: "live", seek_epoch: 0, reset_revision: 0 };
+
+function snapshot(altitudeM: number): TelemetryDomain {
+  return telemetryFromDeltaFields({
+    ...telemetryDeltaFields({ available: true, complete: true, provenance: "stream", value: {} }),
+    "f.alt": altitudeM,
+    "n.lat": 47.397742,
+    "g.sat": 14,
+  });
+}
+
+describe("telemetry deltas", () => {
+  beforeEach(() => {
+    listenMock.mockReset();
+  });
+
+  it("replaces the fields on a keyframe and merges other frames", () => {
+    const keyframe = applyTelemetryDelta(null, {
+      seq: 1n,
+      keyframe: true,
+      fields: telemetryDeltaFields(snapshot(10)),
+    });
+    const next = applyTelemetryDelta(keyframe, { seq: 2n, keyframe: false, fields: { "f.alt": 12.5 } });
+
+    const rebuilt = telemetryFromDeltaFields(next!);
+    expect(rebuilt.value?.flight?.altitude_m).toBe(12.5);
+    expect(rebuilt.value?.navigation?.latitude_deg).toBe(47.397742);
+    expect(rebuilt.value?.gps?.satellites).toBe(14);
+    expect(telemetryFromDeltaFields(keyframe!).value?.flight?.altitude_m).toBe(10);
+  });
+
+  it("ignores a partial frame without a keyframe", () => {
+    expect(applyTelemetryDelta(null, { seq: 3n, keyframe: false, fields: { "f.alt": 1 } })).toBeNull();
+  });
+
+  it("round-trips a snapshot without a value", () => {
+    const missing: TelemetryDomain = { available: false, complete: false, provenance: "bootstrap", value: null };
+    expect(telemetryFromDeltaFields(telemetryDeltaFields(missing))).toEqual(missing);
+  });
+
+  it("delivers each telemetry://delta merged onto the last telemetry://state", async () => {
+    const handlers = new Map<string, (event: { payload: unknown }) => void>();
+    listenMock.mockImplementation(async (event, handler) => {
+      handlers.set(event, handler);
+      return vi.fn();
+    });
+    const cb = vi.fn();
+
+    await subscribeTelemetryState(cb);
+    handlers.get("telemetry://state")!({ payload: { envelope, value: snapshot(10) } });
+    for (const [seq, altitude] of [[1n, 11], [2n, 12]] as const) {
+      handlers.get("telemetry://delta")!({
+        payload: { envelope, value: { seq, keyframe: false, fields: { "f.alt": altitude } } },
+      });
+    }
+
+    expect(cb).toHaveBeenCalledTimes(3);
+    expect(cb.mock.calls.map(([domain]) => domain.value.flight.altitude_m)).toEqual([10, 11, 12]);
+    expect(cb.mock.calls[2][0].value.gps.satellites).toBe(14);
+  });
+});
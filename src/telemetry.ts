@@ -1,9 +1,19 @@
-import { EVENT_NAMES } from "./lib/generated/events";
+import {
+  EVENT_NAMES,
+  TELEMETRY_DELTA_FIELDS,
+  TELEMETRY_DELTA_VALUE_PRESENT_KEY,
+} from "./lib/generated/events";
 import type { DomainValue } from "./lib/domain-status";
+import type {
+  FlightModeChannelConfig,
+  ParamWriteOutcome,
+  TelemetryDelta,
+  TelemetryEncoding,
+} from "./lib/generated/ironwing";
 import { typedInvoke, typedListen, type UnlistenFn } from "./lib/ipc/client";
 import type { SessionEvent } from "./session";
 import type { BluetoothProfile } from "./transport";
-import { createLatestScopedValueHandler } from "./lib/scoped-session-events";
+import { createLatestScopedValueHandler, scopedEnvelopeKey } from "./lib/scoped-session-events";
 
 export type LinkState = "connecting" | "connected" | "disconnected" | { error: string };
 
@@ -101,6 +111,17 @@ export type TelemetryState = {
     rc_rssi?: number;
     servo_outputs?: number[];
   };
+  derived?: {
+    wind_speed_mps?: number | null;
+    wind_direction_deg?: number | null;
+    wind_source?: "reported" | "estimated" | null;
+    home_distance_m?: number | null;
+    home_bearing_deg?: number | null;
+    glide_ratio?: number | null;
+    energy_wh_per_km?: number | null;
+  };
+  /** Display-unit copies of unit-bearing fields, keyed by `group.field`; the fields stay SI. */
+  display?: Record<string, { value: number | null; unit: string }>;
 };
 
 export type TelemetryDomain = DomainValue<TelemetryState>;
@@ -152,19 +173,103 @@ export async function btGetBondedDevices(): Promise<BluetoothDevice[]> {
   return typedInvoke("bt_get_bonded_devices");
 }
 
+/** `telemetry://delta` fields by wire key, as last received. */
+export type TelemetryDeltaFields = Record<string, unknown>;
+
+function pointerSegments(pointer: string): string[] {
+  return pointer.slice(1).split("/");
+}
+
+/** The wire fields of a whole snapshot, as a keyframe would carry them. */
+export function telemetryDeltaFields(domain: TelemetryDomain): TelemetryDeltaFields {
+  const fields: TelemetryDeltaFields = {
+    [TELEMETRY_DELTA_VALUE_PRESENT_KEY]: domain.value !== null,
+  };
+  for (const [key, pointer] of Object.entries(TELEMETRY_DELTA_FIELDS)) {
+    let node: unknown = domain;
+    for (const segment of pointerSegments(pointer)) {
+      node = node !== null && typeof node === "object" ? (node as Record<string, unknown>)[segment] : undefined;
+    }
+    fields[key] = node ?? null;
+  }
+  return fields;
+}
+
+/**
+ * Merge a `telemetry://delta` frame into `fields`. A keyframe replaces them;
+ * any other frame needs fields to merge onto and returns null without them.
+ */
+export function applyTelemetryDelta(
+  fields: TelemetryDeltaFields | null,
+  delta: TelemetryDelta,
+): TelemetryDeltaFields | null {
+  if (!delta.keyframe && !fields) return null;
+  const base = delta.keyframe ? {} : fields!;
+  return { ...base, ...delta.fields };
+}
+
+/** Rebuild the snapshot the backend flattened into `fields`. */
+export function telemetryFromDeltaFields(fields: TelemetryDeltaFields): TelemetryDomain {
+  const present = fields[TELEMETRY_DELTA_VALUE_PRESENT_KEY] === true;
+  const root: Record<string, unknown> = {};
+  for (const [key, pointer] of Object.entries(TELEMETRY_DELTA_FIELDS)) {
+    if (!present && pointer.startsWith("/value/")) continue;
+    const segments = pointerSegments(pointer);
+    let node = root;
+    for (const segment of segments.slice(0, -1)) {
+      node = (node[segment] ??= {}) as Record<string, unknown>;
+    }
+    node[segments[segments.length - 1]] = fields[key] ?? null;
+  }
+  if (!present) root.value = null;
+  return root as TelemetryDomain;
+}
+
+/**
+ * Delivers each `telemetry://state` snapshot and the snapshot rebuilt from
+ * each `telemetry://delta`, so subscribers see whole snapshots under either
+ * encoding.
+ */
+export async function subscribeTelemetryEvents(
+  cb: (event: SessionEvent<TelemetryDomain>) => void,
+): Promise<UnlistenFn> {
+  let fields: TelemetryDeltaFields | null = null;
+  let fieldsScope: string | null = null;
+
+  const unlistenState = await typedListen(EVENT_NAMES.TELEMETRY_STATE, (event) => {
+    fields = telemetryDeltaFields(event.payload.value);
+    fieldsScope = scopedEnvelopeKey(event.payload.envelope);
+    cb(event.payload);
+  });
+  const unlistenDelta = await typedListen(EVENT_NAMES.TELEMETRY_DELTA, (event) => {
+    const scope = scopedEnvelopeKey(event.payload.envelope);
+    const next = applyTelemetryDelta(scope === fieldsScope ? fields : null, event.payload.value);
+    if (!next) return;
+    fields = next;
+    fieldsScope = scope;
+    cb({ envelope: event.payload.envelope, value: telemetryFromDeltaFields(next) });
+  });
+
+  return () => {
+    unlistenState();
+    unlistenDelta();
+  };
+}
+
 export async function subscribeTelemetryState(
   cb: (domain: TelemetryDomain) => void,
 ): Promise<UnlistenFn> {
-  const handleEvent = createLatestScopedValueHandler(cb);
+  return subscribeTelemetryEvents(createLatestScopedValueHandler(cb));
+}
 
-  return typedListen(
-    EVENT_NAMES.TELEMETRY_STATE,
-    (event) => handleEvent(event.payload),
-  );
+/** Pick how telemetry ticks arrive; either way the next one is a keyframe. */
+export async function setTelemetryEncoding(encoding: TelemetryEncoding): Promise<void> {
+  await typedInvoke("set_telemetry_encoding", { encoding });
 }
 
-export async function armVehicle(force: boolean): Promise<void> {
-  await typedInvoke("arm_vehicle", { force });
+/** `force` is the MAVLink force-arm; `overrideGates` only skips the GCS safety gates. */
+export async function armVehicle(force: boolean, overrideGates = false): Promise<void> {
+  await typedInvoke("arm_vehicle", { force, overrideGates });
 }
 
 export async function disarmVehicle(force: boolean): Promise<void> {
@@ -179,6 +284,16 @@ export async function getAvailableModes(): Promise<FlightModeEntry[]> {
   return typedInvoke("get_available_modes");
 }
 
+/** Mode-switch channel, slot PWM bands and the slot the physical switch selects. */
+export async function getFlightModeChannelConfig(): Promise<FlightModeChannelConfig> {
+  return typedInvoke("flightmode_channel_config");
+}
+
+/** Put `customMode` on mode-switch slot `slot` (1–6). */
+export async function assignFlightModeSlot(slot: number, customMode: number): Promise<ParamWriteOutcome> {
+  return typedInvoke("flightmode_assign", { slot, customMode });
+}
+
 export async function setTelemetryRate(rateHz: number): Promise<void> {
   await typedInvoke("set_telemetry_rate", { rateHz });
 }
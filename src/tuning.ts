@@ -0,0 +1,18 @@
+import type { TuneAxis, TuneReport, TuneStep, TuneTargetSource } from "./lib/generated/ironwing";
+import { typedInvoke } from "./lib/ipc/client";
+
+export type { TuneAxis, TuneReport, TuneStep, TuneTargetSource };
+
+/** Record `axis` at a raised telemetry rate until {@link stopTuneCapture}. */
+export async function startTuneCapture(axis: TuneAxis): Promise<void> {
+  return typedInvoke("tune_capture_start", { axis });
+}
+
+/** Restore telemetry rates and analyse every step seen during the capture. */
+export async function stopTuneCapture(): Promise<TuneReport> {
+  return typedInvoke("tune_capture_stop");
+}
+
+export async function exportTuneReport(path: string, report: TuneReport): Promise<void> {
+  return typedInvoke("tune_report_export", { path, report });
+}